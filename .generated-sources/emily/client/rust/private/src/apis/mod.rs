@@ -113,12 +113,14 @@ impl From<&str> for ContentType {
     }
 }
 
+pub mod api_key_api;
 pub mod chainstate_api;
 pub mod cors_api;
 pub mod deposit_api;
 pub mod health_api;
 pub mod limits_api;
 pub mod new_block_api;
+pub mod retention_api;
 pub mod throttle_api;
 pub mod withdrawal_api;
 
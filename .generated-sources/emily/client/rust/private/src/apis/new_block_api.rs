@@ -47,6 +47,9 @@ pub async fn new_block(
         };
         req_builder = req_builder.header("x-api-key", value);
     };
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
     req_builder = req_builder.json(&p_body_body);
 
     let req = req_builder.build()?;
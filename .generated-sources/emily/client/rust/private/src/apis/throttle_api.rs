@@ -89,6 +89,9 @@ pub async fn activate_throttle_key(
         };
         req_builder = req_builder.header("x-api-key", value);
     };
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
     req_builder = req_builder.json(&p_body_body);
 
     let req = req_builder.build()?;
@@ -132,6 +135,9 @@ pub async fn add_throttle_key(
         };
         req_builder = req_builder.header("x-api-key", value);
     };
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
     req_builder = req_builder.json(&p_body_throttle_key);
 
     let req = req_builder.build()?;
@@ -175,6 +181,9 @@ pub async fn deactivate_throttle_key(
         };
         req_builder = req_builder.header("x-api-key", value);
     };
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
     req_builder = req_builder.json(&p_body_body);
 
     let req = req_builder.build()?;
@@ -216,6 +225,9 @@ pub async fn get_throttle_key(
         };
         req_builder = req_builder.header("x-api-key", value);
     };
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
     req_builder = req_builder.json(&p_body_body);
 
     let req = req_builder.build()?;
@@ -262,6 +274,9 @@ pub async fn start_throttle(
     if let Some(ref user_agent) = configuration.user_agent {
         req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
     }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
     req_builder = req_builder.json(&p_body_throttle_request);
 
     let req = req_builder.build()?;
@@ -35,6 +35,17 @@ pub enum GetWithdrawalError {
     UnknownValue(serde_json::Value),
 }
 
+/// struct for typed errors of method [`get_withdrawal_history`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum GetWithdrawalHistoryError {
+    Status400(models::ErrorResponse),
+    Status404(models::ErrorResponse),
+    Status405(models::ErrorResponse),
+    Status500(models::ErrorResponse),
+    UnknownValue(serde_json::Value),
+}
+
 /// struct for typed errors of method [`get_withdrawals`]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -92,6 +103,9 @@ pub async fn create_withdrawal(
         };
         req_builder = req_builder.header("x-api-key", value);
     };
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
     req_builder = req_builder.json(&p_body_create_withdrawal_request_body);
 
     let req = req_builder.build()?;
@@ -140,6 +154,9 @@ pub async fn get_withdrawal(
     if let Some(ref user_agent) = configuration.user_agent {
         req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
     }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
 
     let req = req_builder.build()?;
     let resp = configuration.client.execute(req).await?;
@@ -170,16 +187,68 @@ pub async fn get_withdrawal(
     }
 }
 
+pub async fn get_withdrawal_history(
+    configuration: &configuration::Configuration,
+    id: u64,
+) -> Result<models::GetWithdrawalHistoryResponse, Error<GetWithdrawalHistoryError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_path_id = id;
+
+    let uri_str = format!(
+        "{}/withdrawal/{id}/history",
+        configuration.base_path,
+        id = p_path_id
+    );
+    let mut req_builder = configuration.client.request(reqwest::Method::GET, &uri_str);
+
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::GetWithdrawalHistoryResponse`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::GetWithdrawalHistoryResponse`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<GetWithdrawalHistoryError> = serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
+
 pub async fn get_withdrawals(
     configuration: &configuration::Configuration,
     status: models::WithdrawalStatus,
     next_token: Option<&str>,
     page_size: Option<u32>,
+    include_archived: Option<bool>,
 ) -> Result<models::GetWithdrawalsResponse, Error<GetWithdrawalsError>> {
     // add a prefix to parameters to efficiently prevent name collisions
     let p_query_status = status;
     let p_query_next_token = next_token;
     let p_query_page_size = page_size;
+    let p_query_include_archived = include_archived;
 
     let uri_str = format!("{}/withdrawal", configuration.base_path);
     let mut req_builder = configuration.client.request(reqwest::Method::GET, &uri_str);
@@ -191,9 +260,15 @@ pub async fn get_withdrawals(
     if let Some(ref param_value) = p_query_page_size {
         req_builder = req_builder.query(&[("pageSize", &param_value.to_string())]);
     }
+    if let Some(ref param_value) = p_query_include_archived {
+        req_builder = req_builder.query(&[("includeArchived", &param_value.to_string())]);
+    }
     if let Some(ref user_agent) = configuration.user_agent {
         req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
     }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
 
     let req = req_builder.build()?;
     let resp = configuration.client.execute(req).await?;
@@ -251,6 +326,9 @@ pub async fn get_withdrawals_for_recipient(
     if let Some(ref user_agent) = configuration.user_agent {
         req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
     }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
 
     let req = req_builder.build()?;
     let resp = configuration.client.execute(req).await?;
@@ -302,6 +380,9 @@ pub async fn update_withdrawals_sidecar(
         };
         req_builder = req_builder.header("x-api-key", value);
     };
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
     req_builder = req_builder.json(&p_body_update_withdrawals_request_body);
 
     let req = req_builder.build()?;
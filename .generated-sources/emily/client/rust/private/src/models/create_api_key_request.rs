@@ -0,0 +1,50 @@
+/*
+ * emily-openapi-spec
+ *
+ * No description provided (generated by Openapi Generator https://github.com/openapitools/openapi-generator)
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// CreateApiKeyRequest : Request to create a new api key.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CreateApiKeyRequest {
+    /// The name of the key. It must be unique and must not contain a `:`.
+    #[serde(rename = "name")]
+    pub name: String,
+    /// The maximum number of requests per minute that may be made with this key. If none then the key is not rate limited.
+    #[serde(
+        rename = "rateLimitPerMinute",
+        default,
+        with = "::serde_with::rust::double_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub rate_limit_per_minute: Option<Option<u32>>,
+    /// The operations that this key may perform.
+    #[serde(rename = "scopes")]
+    pub scopes: Vec<models::ApiKeyScope>,
+    /// The secret associated with this key.
+    #[serde(rename = "secret")]
+    pub secret: String,
+}
+
+impl CreateApiKeyRequest {
+    /// Request to create a new api key.
+    pub fn new(
+        name: String,
+        scopes: Vec<models::ApiKeyScope>,
+        secret: String,
+    ) -> CreateApiKeyRequest {
+        CreateApiKeyRequest {
+            name,
+            rate_limit_per_minute: None,
+            scopes,
+            secret,
+        }
+    }
+}
@@ -0,0 +1,30 @@
+/*
+ * emily-openapi-spec
+ *
+ * No description provided (generated by Openapi Generator https://github.com/openapitools/openapi-generator)
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// DepositConfirmationTier : A tier of the deposit confirmation policy used by the signers.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DepositConfirmationTier {
+    /// The number of bitcoin confirmations required for deposits with an amount of at least `min_amount`.
+    #[serde(rename = "confirmations")]
+    pub confirmations: u32,
+    /// The minimum deposit amount, in sats, this tier applies to.
+    #[serde(rename = "minAmount")]
+    pub min_amount: u64,
+}
+
+impl DepositConfirmationTier {
+    /// A tier of the deposit confirmation policy used by the signers.
+    pub fn new(confirmations: u32, min_amount: u64) -> DepositConfirmationTier {
+        DepositConfirmationTier { confirmations, min_amount }
+    }
+}
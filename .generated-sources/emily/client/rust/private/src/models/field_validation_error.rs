@@ -0,0 +1,29 @@
+/*
+ * emily-openapi-spec
+ *
+ * No description provided (generated by Openapi Generator https://github.com/openapitools/openapi-generator)
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// FieldValidationError : A reason why a field of a request body failed validation.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FieldValidationError {
+    #[serde(rename = "field")]
+    pub field: models::RequestField,
+    /// Why the field failed validation.
+    #[serde(rename = "message")]
+    pub message: String,
+}
+
+impl FieldValidationError {
+    /// A reason why a field of a request body failed validation.
+    pub fn new(field: models::RequestField, message: String) -> FieldValidationError {
+        FieldValidationError { field, message }
+    }
+}
@@ -0,0 +1,43 @@
+/*
+ * emily-openapi-spec
+ *
+ * No description provided (generated by Openapi Generator https://github.com/openapitools/openapi-generator)
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// RequestField : A field of a request body that can fail validation.
+/// A field of a request body that can fail validation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum RequestField {
+    #[serde(rename = "outpoint")]
+    Outpoint,
+    #[serde(rename = "depositScript")]
+    DepositScript,
+    #[serde(rename = "reclaimScript")]
+    ReclaimScript,
+    #[serde(rename = "transactionHex")]
+    TransactionHex,
+}
+
+impl std::fmt::Display for RequestField {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Outpoint => write!(f, "outpoint"),
+            Self::DepositScript => write!(f, "depositScript"),
+            Self::ReclaimScript => write!(f, "reclaimScript"),
+            Self::TransactionHex => write!(f, "transactionHex"),
+        }
+    }
+}
+
+impl Default for RequestField {
+    fn default() -> RequestField {
+        Self::Outpoint
+    }
+}
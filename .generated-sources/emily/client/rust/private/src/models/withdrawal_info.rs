@@ -17,6 +17,13 @@ pub struct WithdrawalInfo {
     /// Amount of BTC being withdrawn in satoshis.
     #[serde(rename = "amount")]
     pub amount: u64,
+    #[serde(
+        rename = "fulfillment",
+        default,
+        with = "::serde_with::rust::double_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub fulfillment: Option<Option<Box<models::Fulfillment>>>,
     /// The most recent Stacks block hash the API was aware of when the withdrawal was last updated. If the most recent update is tied to an artifact on the Stacks blockchain then this hash is the Stacks block hash that contains that artifact.
     #[serde(rename = "lastUpdateBlockHash")]
     pub last_update_block_hash: String,
@@ -61,6 +68,7 @@ impl WithdrawalInfo {
     ) -> WithdrawalInfo {
         WithdrawalInfo {
             amount,
+            fulfillment: None,
             last_update_block_hash,
             last_update_height,
             recipient,
@@ -27,6 +27,63 @@ pub struct ApiKey {
     pub key: String,
 }
 
+/// The version of the Emily API that this client understands.
+pub const EMILY_API_VERSION: u32 = 1;
+
+/// The header that names the versions of the Emily API that a request
+/// accepts.
+pub const ACCEPT_VERSION_HEADER: &str = "accept-version";
+
+/// Headers that ask Emily to serve requests with [`EMILY_API_VERSION`].
+/// Use these as the default headers of the client in the
+/// [`Configuration`], so that Emily rejects requests outright if it no
+/// longer serves the version of the API that this client understands.
+pub fn api_version_headers() -> reqwest::header::HeaderMap {
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert(
+        ACCEPT_VERSION_HEADER,
+        reqwest::header::HeaderValue::from(EMILY_API_VERSION),
+    );
+    headers
+}
+
+/// Emily does not serve the version of the API that this client
+/// understands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IncompatibleApiVersion {
+    /// The oldest version of the API that Emily serves.
+    pub min_api_version: u32,
+    /// The newest version of the API that Emily serves.
+    pub api_version: u32,
+}
+
+impl std::fmt::Display for IncompatibleApiVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Emily serves API versions {} through {}, but this client requires version {}",
+            self.min_api_version, self.api_version, EMILY_API_VERSION
+        )
+    }
+}
+
+impl std::error::Error for IncompatibleApiVersion {}
+
+/// Check that Emily, going by the health data that it returned, serves the
+/// version of the API that this client understands.
+pub fn check_api_version(
+    health: &crate::models::HealthData,
+) -> Result<u32, IncompatibleApiVersion> {
+    if (health.min_api_version..=health.api_version).contains(&EMILY_API_VERSION) {
+        Ok(EMILY_API_VERSION)
+    } else {
+        Err(IncompatibleApiVersion {
+            min_api_version: health.min_api_version,
+            api_version: health.api_version,
+        })
+    }
+}
+
 impl Configuration {
     pub fn new() -> Configuration {
         Configuration::default()
@@ -33,6 +33,17 @@ pub enum GetLimitsForAccountError {
     UnknownValue(serde_json::Value),
 }
 
+/// struct for typed errors of method [`set_available_to_mint`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SetAvailableToMintError {
+    Status400(models::ErrorResponse),
+    Status403(models::ErrorResponse),
+    Status405(models::ErrorResponse),
+    Status500(models::ErrorResponse),
+    UnknownValue(serde_json::Value),
+}
+
 pub async fn get_limits(
     configuration: &configuration::Configuration,
 ) -> Result<models::Limits, Error<GetLimitsError>> {
@@ -42,6 +53,9 @@ pub async fn get_limits(
     if let Some(ref user_agent) = configuration.user_agent {
         req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
     }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
 
     let req = req_builder.build()?;
     let resp = configuration.client.execute(req).await?;
@@ -89,6 +103,9 @@ pub async fn get_limits_for_account(
     if let Some(ref user_agent) = configuration.user_agent {
         req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
     }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
 
     let req = req_builder.build()?;
     let resp = configuration.client.execute(req).await?;
@@ -118,3 +135,59 @@ pub async fn get_limits_for_account(
         }))
     }
 }
+
+/// Used by the signers to report how much sBTC can still be minted under the peg cap.
+pub async fn set_available_to_mint(
+    configuration: &configuration::Configuration,
+    set_available_to_mint_request_body: models::SetAvailableToMintRequestBody,
+) -> Result<models::Limits, Error<SetAvailableToMintError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_body_set_available_to_mint_request_body = set_available_to_mint_request_body;
+
+    let uri_str = format!("{}/limits", configuration.base_path);
+    let mut req_builder = configuration.client.request(reqwest::Method::PUT, &uri_str);
+
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(ref apikey) = configuration.api_key {
+        let key = apikey.key.clone();
+        let value = match apikey.prefix {
+            Some(ref prefix) => format!("{} {}", prefix, key),
+            None => key,
+        };
+        req_builder = req_builder.header("x-api-key", value);
+    };
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+    req_builder = req_builder.json(&p_body_set_available_to_mint_request_body);
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::Limits`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::Limits`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<SetAvailableToMintError> = serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
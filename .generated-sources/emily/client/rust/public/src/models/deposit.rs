@@ -23,9 +23,25 @@ pub struct Deposit {
     /// Bitcoin transaction id.
     #[serde(rename = "bitcoinTxid")]
     pub bitcoin_txid: String,
+    /// The hash of the bitcoin block that confirmed the deposit transaction.
+    #[serde(
+        rename = "depositBlockHash",
+        default,
+        with = "::serde_with::rust::double_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub deposit_block_hash: Option<Option<String>>,
     /// Raw deposit script binary in hex.
     #[serde(rename = "depositScript")]
     pub deposit_script: String,
+    /// Hex encoded SPV proof that the deposit transaction is included in the block with `deposit_block_hash`. This is a serialized `MerkleBlock`, the same format returned by bitcoin core's `gettxoutproof` RPC.
+    #[serde(
+        rename = "depositTxProof",
+        default,
+        with = "::serde_with::rust::double_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub deposit_tx_proof: Option<Option<String>>,
     #[serde(
         rename = "fulfillment",
         default,
@@ -81,7 +97,9 @@ impl Deposit {
             amount,
             bitcoin_tx_output_index,
             bitcoin_txid,
+            deposit_block_hash: None,
             deposit_script,
+            deposit_tx_proof: None,
             fulfillment: None,
             last_update_block_hash,
             last_update_height,
@@ -0,0 +1,79 @@
+/*
+ * emily-openapi-spec
+ *
+ * No description provided (generated by Openapi Generator https://github.com/openapitools/openapi-generator)
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// DepositStatusUpdate : A single status update in the history of a deposit.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DepositStatusUpdate {
+    #[serde(
+        rename = "actor",
+        default,
+        with = "::serde_with::rust::double_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub actor: Option<Option<models::StatusUpdateActor>>,
+    #[serde(
+        rename = "fulfillment",
+        default,
+        with = "::serde_with::rust::double_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub fulfillment: Option<Option<Box<models::Fulfillment>>>,
+    /// Transaction ID of the transaction that replaced this one via RBF, if this update reported the replacement.
+    #[serde(
+        rename = "replacedByTx",
+        default,
+        with = "::serde_with::rust::double_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub replaced_by_tx: Option<Option<String>>,
+    /// Stacks block hash associated with the height of this update.
+    #[serde(rename = "stacksBlockHash")]
+    pub stacks_block_hash: String,
+    /// Stacks block height the API was aware of at the time of this update.
+    #[serde(rename = "stacksBlockHeight")]
+    pub stacks_block_height: u64,
+    #[serde(rename = "status")]
+    pub status: models::DepositStatus,
+    /// The status message of this update.
+    #[serde(rename = "statusMessage")]
+    pub status_message: String,
+    /// Unix timestamp, in seconds, of when Emily recorded this update. This is missing for updates that were recorded before it was tracked.
+    #[serde(
+        rename = "updatedAt",
+        default,
+        with = "::serde_with::rust::double_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub updated_at: Option<Option<u64>>,
+}
+
+impl DepositStatusUpdate {
+    /// A single status update in the history of a deposit.
+    pub fn new(
+        stacks_block_hash: String,
+        stacks_block_height: u64,
+        status: models::DepositStatus,
+        status_message: String,
+    ) -> DepositStatusUpdate {
+        DepositStatusUpdate {
+            actor: None,
+            fulfillment: None,
+            replaced_by_tx: None,
+            stacks_block_hash,
+            stacks_block_height,
+            status,
+            status_message,
+            updated_at: None,
+        }
+    }
+}
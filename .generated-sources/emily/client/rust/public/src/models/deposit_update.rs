@@ -20,6 +20,22 @@ pub struct DepositUpdate {
     /// Bitcoin transaction id.
     #[serde(rename = "bitcoinTxid")]
     pub bitcoin_txid: String,
+    /// The hash of the bitcoin block that confirmed the deposit transaction. This must be provided along with `deposit_tx_proof`.
+    #[serde(
+        rename = "depositBlockHash",
+        default,
+        with = "::serde_with::rust::double_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub deposit_block_hash: Option<Option<String>>,
+    /// Hex encoded SPV proof that the deposit transaction is included in the block with `deposit_block_hash`. This is a serialized `MerkleBlock`, the same format returned by bitcoin core's `gettxoutproof` RPC.
+    #[serde(
+        rename = "depositTxProof",
+        default,
+        with = "::serde_with::rust::double_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub deposit_tx_proof: Option<Option<String>>,
     #[serde(
         rename = "fulfillment",
         default,
@@ -53,6 +69,8 @@ impl DepositUpdate {
         DepositUpdate {
             bitcoin_tx_output_index,
             bitcoin_txid,
+            deposit_block_hash: None,
+            deposit_tx_proof: None,
             fulfillment: None,
             replaced_by_tx: None,
             status,
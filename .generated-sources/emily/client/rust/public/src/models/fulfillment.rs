@@ -14,6 +14,14 @@ use serde::{Deserialize, Serialize};
 /// Fulfillment : Data about the fulfillment of an sBTC Operation.
 #[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Fulfillment {
+    /// The fee in satoshis that the signers assessed against this operation when constructing the bitcoin transaction that fulfilled it.
+    #[serde(
+        rename = "AssessedFee",
+        default,
+        with = "::serde_with::rust::double_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub assessed_fee: Option<Option<u64>>,
     /// Bitcoin block hash of the block that contains the bitcoin transaction that fulfilled this transaction.
     #[serde(rename = "BitcoinBlockHash")]
     pub bitcoin_block_hash: String,
@@ -32,6 +40,14 @@ pub struct Fulfillment {
     /// Stacks transaction Id that fulfilled this operation.
     #[serde(rename = "StacksTxid")]
     pub stacks_txid: String,
+    /// Hex encoded SPV proof that the bitcoin transaction that fulfilled the operation is included in the block with `bitcoin_block_hash`. This is a serialized `MerkleBlock`, the same format returned by bitcoin core's `gettxoutproof` RPC.
+    #[serde(
+        rename = "SweepTxProof",
+        default,
+        with = "::serde_with::rust::double_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub sweep_tx_proof: Option<Option<String>>,
 }
 
 impl Fulfillment {
@@ -45,12 +61,14 @@ impl Fulfillment {
         stacks_txid: String,
     ) -> Fulfillment {
         Fulfillment {
+            assessed_fee: None,
             bitcoin_block_hash,
             bitcoin_block_height,
             bitcoin_tx_index,
             bitcoin_txid,
             btc_fee,
             stacks_txid,
+            sweep_tx_proof: None,
         }
     }
 }
@@ -0,0 +1,33 @@
+/*
+ * emily-openapi-spec
+ *
+ * No description provided (generated by Openapi Generator https://github.com/openapitools/openapi-generator)
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// GetWithdrawalHistoryResponse : Response to get withdrawal history request.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GetWithdrawalHistoryResponse {
+    /// The status updates of the withdrawal in chronological order. The last update is the current status of the withdrawal.
+    #[serde(rename = "history")]
+    pub history: Vec<models::WithdrawalStatusUpdate>,
+    /// The id of the Stacks withdrawal request that initiated the sBTC operation.
+    #[serde(rename = "requestId")]
+    pub request_id: u64,
+}
+
+impl GetWithdrawalHistoryResponse {
+    /// Response to get withdrawal history request.
+    pub fn new(
+        history: Vec<models::WithdrawalStatusUpdate>,
+        request_id: u64,
+    ) -> GetWithdrawalHistoryResponse {
+        GetWithdrawalHistoryResponse { history, request_id }
+    }
+}
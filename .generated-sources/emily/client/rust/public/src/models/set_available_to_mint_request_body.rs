@@ -0,0 +1,27 @@
+/*
+ * emily-openapi-spec
+ *
+ * No description provided (generated by Openapi Generator https://github.com/openapitools/openapi-generator)
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// SetAvailableToMintRequestBody : Request structure for the signers to report how much sBTC can still be minted.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SetAvailableToMintRequestBody {
+    /// The amount of sBTC, in sats, that can still be minted under the peg cap.
+    #[serde(rename = "availableToMint")]
+    pub available_to_mint: u64,
+}
+
+impl SetAvailableToMintRequestBody {
+    /// Request structure for the signers to report how much sBTC can still be minted.
+    pub fn new(available_to_mint: u64) -> SetAvailableToMintRequestBody {
+        SetAvailableToMintRequestBody { available_to_mint }
+    }
+}
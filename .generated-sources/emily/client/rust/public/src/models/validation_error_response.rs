@@ -0,0 +1,33 @@
+/*
+ * emily-openapi-spec
+ *
+ * No description provided (generated by Openapi Generator https://github.com/openapitools/openapi-generator)
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// ValidationErrorResponse : Structure representing the response to a request whose body failed validation, with the reason that each invalid field failed validation.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ValidationErrorResponse {
+    /// The validation error of each invalid field.
+    #[serde(rename = "errors")]
+    pub errors: Vec<models::FieldValidationError>,
+    /// A summary of all of the validation errors.
+    #[serde(rename = "message")]
+    pub message: String,
+}
+
+impl ValidationErrorResponse {
+    /// Structure representing the response to a request whose body failed validation, with the reason that each invalid field failed validation.
+    pub fn new(
+        errors: Vec<models::FieldValidationError>,
+        message: String,
+    ) -> ValidationErrorResponse {
+        ValidationErrorResponse { errors, message }
+    }
+}
@@ -0,0 +1,55 @@
+/*
+ * emily-openapi-spec
+ *
+ * No description provided (generated by Openapi Generator https://github.com/openapitools/openapi-generator)
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// ApiKeyInfo : Information about an api key. This never includes the secret.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ApiKeyInfo {
+    /// Whether the key may be used.
+    #[serde(rename = "isActive")]
+    pub is_active: bool,
+    /// The name of the key.
+    #[serde(rename = "name")]
+    pub name: String,
+    /// The maximum number of requests per minute that may be made with this key. If none then the key is not rate limited.
+    #[serde(
+        rename = "rateLimitPerMinute",
+        default,
+        with = "::serde_with::rust::double_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub rate_limit_per_minute: Option<Option<u32>>,
+    /// The operations that this key may perform.
+    #[serde(rename = "scopes")]
+    pub scopes: Vec<models::ApiKeyScope>,
+    /// The time of the last change to the key, in seconds from UNIX epoch.
+    #[serde(rename = "updatedAt")]
+    pub updated_at: u64,
+}
+
+impl ApiKeyInfo {
+    /// Information about an api key. This never includes the secret.
+    pub fn new(
+        is_active: bool,
+        name: String,
+        scopes: Vec<models::ApiKeyScope>,
+        updated_at: u64,
+    ) -> ApiKeyInfo {
+        ApiKeyInfo {
+            is_active,
+            name,
+            rate_limit_per_minute: None,
+            scopes,
+            updated_at,
+        }
+    }
+}
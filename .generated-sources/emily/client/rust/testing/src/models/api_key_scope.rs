@@ -0,0 +1,40 @@
+/*
+ * emily-openapi-spec
+ *
+ * No description provided (generated by Openapi Generator https://github.com/openapitools/openapi-generator)
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// ApiKeyScope : The operations that an api key may be allowed to perform.
+/// The operations that an api key may be allowed to perform.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum ApiKeyScope {
+    #[serde(rename = "deposit-create")]
+    DepositCreate,
+    #[serde(rename = "status-read")]
+    StatusRead,
+    #[serde(rename = "admin-update")]
+    AdminUpdate,
+}
+
+impl std::fmt::Display for ApiKeyScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::DepositCreate => write!(f, "deposit-create"),
+            Self::StatusRead => write!(f, "status-read"),
+            Self::AdminUpdate => write!(f, "admin-update"),
+        }
+    }
+}
+
+impl Default for ApiKeyScope {
+    fn default() -> ApiKeyScope {
+        Self::DepositCreate
+    }
+}
@@ -0,0 +1,27 @@
+/*
+ * emily-openapi-spec
+ *
+ * No description provided (generated by Openapi Generator https://github.com/openapitools/openapi-generator)
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// CreateDepositsResponse : Response to create deposits request.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CreateDepositsResponse {
+    /// The outcome of creating each deposit, in the order of the request.
+    #[serde(rename = "deposits")]
+    pub deposits: Vec<models::DepositWithStatus>,
+}
+
+impl CreateDepositsResponse {
+    /// Response to create deposits request.
+    pub fn new(deposits: Vec<models::DepositWithStatus>) -> CreateDepositsResponse {
+        CreateDepositsResponse { deposits }
+    }
+}
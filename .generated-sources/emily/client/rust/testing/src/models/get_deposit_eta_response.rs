@@ -0,0 +1,91 @@
+/*
+ * emily-openapi-spec
+ *
+ * No description provided (generated by Openapi Generator https://github.com/openapitools/openapi-generator)
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// GetDepositEtaResponse : Response to get deposit ETA request.  The estimate is derived from the deposits currently waiting to be swept and from the fulfillments that the signers reported for recently swept deposits. It is a best-effort hint for wallets and not a guarantee.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GetDepositEtaResponse {
+    /// Output index on the bitcoin transaction associated with this specific deposit.
+    #[serde(rename = "bitcoinTxOutputIndex")]
+    pub bitcoin_tx_output_index: u32,
+    /// Bitcoin transaction id.
+    #[serde(rename = "bitcoinTxid")]
+    pub bitcoin_txid: String,
+    /// The number of pending and accepted deposits that are expected to be swept before this one.
+    #[serde(rename = "depositsAhead")]
+    pub deposits_ahead: u64,
+    /// The estimated number of bitcoin blocks until the deposit is swept. This is `None` if there is not enough data for an estimate, or if the deposit cannot currently be swept.
+    #[serde(
+        rename = "estimatedBitcoinBlocks",
+        default,
+        with = "::serde_with::rust::double_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub estimated_bitcoin_blocks: Option<Option<u64>>,
+    /// The estimated number of seconds until the deposit is swept, assuming ten minute bitcoin blocks.
+    #[serde(
+        rename = "estimatedSeconds",
+        default,
+        with = "::serde_with::rust::double_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub estimated_seconds: Option<Option<u64>>,
+    /// Whether the max fee of the deposit covers the median fee paid by recently swept deposits.
+    #[serde(rename = "feeSufficient")]
+    pub fee_sufficient: bool,
+    /// The median fee, in satoshis, paid by recently swept deposits.
+    #[serde(
+        rename = "recentMedianFee",
+        default,
+        with = "::serde_with::rust::double_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub recent_median_fee: Option<Option<u64>>,
+    #[serde(rename = "status")]
+    pub status: models::DepositStatus,
+    /// The average number of bitcoin blocks between recent sweeps, if there have been enough recent sweeps to tell.
+    #[serde(
+        rename = "sweepIntervalBlocks",
+        default,
+        with = "::serde_with::rust::double_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub sweep_interval_blocks: Option<Option<u64>>,
+    /// The number of sweeps that are expected to happen until this deposit is swept, including the sweep that sweeps it.
+    #[serde(rename = "sweepsRemaining")]
+    pub sweeps_remaining: u64,
+}
+
+impl GetDepositEtaResponse {
+    /// Response to get deposit ETA request.  The estimate is derived from the deposits currently waiting to be swept and from the fulfillments that the signers reported for recently swept deposits. It is a best-effort hint for wallets and not a guarantee.
+    pub fn new(
+        bitcoin_tx_output_index: u32,
+        bitcoin_txid: String,
+        deposits_ahead: u64,
+        fee_sufficient: bool,
+        status: models::DepositStatus,
+        sweeps_remaining: u64,
+    ) -> GetDepositEtaResponse {
+        GetDepositEtaResponse {
+            bitcoin_tx_output_index,
+            bitcoin_txid,
+            deposits_ahead,
+            estimated_bitcoin_blocks: None,
+            estimated_seconds: None,
+            fee_sufficient,
+            recent_median_fee: None,
+            status,
+            sweep_interval_blocks: None,
+            sweeps_remaining,
+        }
+    }
+}
@@ -0,0 +1,41 @@
+/*
+ * emily-openapi-spec
+ *
+ * No description provided (generated by Openapi Generator https://github.com/openapitools/openapi-generator)
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// GetDepositHistoryResponse : Response to get deposit history request.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GetDepositHistoryResponse {
+    /// Output index on the bitcoin transaction associated with this specific deposit.
+    #[serde(rename = "bitcoinTxOutputIndex")]
+    pub bitcoin_tx_output_index: u32,
+    /// Bitcoin transaction id.
+    #[serde(rename = "bitcoinTxid")]
+    pub bitcoin_txid: String,
+    /// The status updates of the deposit in chronological order. The last update is the current status of the deposit.
+    #[serde(rename = "history")]
+    pub history: Vec<models::DepositStatusUpdate>,
+}
+
+impl GetDepositHistoryResponse {
+    /// Response to get deposit history request.
+    pub fn new(
+        bitcoin_tx_output_index: u32,
+        bitcoin_txid: String,
+        history: Vec<models::DepositStatusUpdate>,
+    ) -> GetDepositHistoryResponse {
+        GetDepositHistoryResponse {
+            bitcoin_tx_output_index,
+            bitcoin_txid,
+            history,
+        }
+    }
+}
@@ -14,6 +14,12 @@ use serde::{Deserialize, Serialize};
 /// HealthData : Struct that represents the current status of the API.
 #[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct HealthData {
+    /// The current version of the API, the newest one that can be named in the `Accept-Version` header of requests.
+    #[serde(rename = "apiVersion")]
+    pub api_version: u32,
+    /// The oldest version of the API that can be named in the `Accept-Version` header of requests.
+    #[serde(rename = "minApiVersion")]
+    pub min_api_version: u32,
     /// The version of the API.
     #[serde(rename = "version")]
     pub version: String,
@@ -21,7 +27,11 @@ pub struct HealthData {
 
 impl HealthData {
     /// Struct that represents the current status of the API.
-    pub fn new(version: String) -> HealthData {
-        HealthData { version }
+    pub fn new(api_version: u32, min_api_version: u32, version: String) -> HealthData {
+        HealthData {
+            api_version,
+            min_api_version,
+            version,
+        }
     }
 }
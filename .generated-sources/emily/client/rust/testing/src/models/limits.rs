@@ -17,6 +17,14 @@ pub struct Limits {
     /// Represents the individual limits for requests coming from different accounts.
     #[serde(rename = "accountCaps")]
     pub account_caps: std::collections::HashMap<String, models::AccountLimits>,
+    /// The amount of sBTC that can still be minted under the peg cap, as last reported by the signers. This accounts for deposits that have been swept but not yet minted. This is not settable and is ignored when setting limits.
+    #[serde(
+        rename = "availableToMint",
+        default,
+        with = "::serde_with::rust::double_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub available_to_mint: Option<Option<u64>>,
     /// Total amount sBTC still available for withdrawals in current window. All withdrawals except rejected counted here
     #[serde(
         rename = "availableToWithdraw",
@@ -25,6 +33,20 @@ pub struct Limits {
         skip_serializing_if = "Option::is_none"
     )]
     pub available_to_withdraw: Option<Option<u64>>,
+    /// Number of bitcoin blocks until some sBTC is available for withdrawals in the rolling withdrawal window again. Zero if some is available now, and none if there is no rolling withdrawal cap or it is zero. This is not settable and is ignored when setting limits.
+    #[serde(
+        rename = "blocksUntilWithdrawalHeadroom",
+        default,
+        with = "::serde_with::rust::double_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub blocks_until_withdrawal_headroom: Option<Option<u64>>,
+    /// The number of bitcoin confirmations the signers require before sweeping a deposit, based on its amount. This is not settable and is ignored when setting limits.
+    #[serde(
+        rename = "depositConfirmationPolicy",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub deposit_confirmation_policy: Option<Vec<models::DepositConfirmationTier>>,
     /// Represents the total cap for all pegged-in BTC/sBTC.
     #[serde(
         rename = "pegCap",
@@ -88,7 +110,10 @@ impl Limits {
     pub fn new(account_caps: std::collections::HashMap<String, models::AccountLimits>) -> Limits {
         Limits {
             account_caps,
+            available_to_mint: None,
             available_to_withdraw: None,
+            blocks_until_withdrawal_headroom: None,
+            deposit_confirmation_policy: None,
             peg_cap: None,
             per_deposit_cap: None,
             per_deposit_minimum: None,
@@ -1,19 +1,33 @@
 pub mod account_limits;
 pub use self::account_limits::AccountLimits;
+pub mod api_key_info;
+pub use self::api_key_info::ApiKeyInfo;
+pub mod api_key_scope;
+pub use self::api_key_scope::ApiKeyScope;
 pub mod chainstate;
 pub use self::chainstate::Chainstate;
+pub mod create_api_key_request;
+pub use self::create_api_key_request::CreateApiKeyRequest;
 pub mod create_deposit_request_body;
 pub use self::create_deposit_request_body::CreateDepositRequestBody;
+pub mod create_deposits_request_body;
+pub use self::create_deposits_request_body::CreateDepositsRequestBody;
+pub mod create_deposits_response;
+pub use self::create_deposits_response::CreateDepositsResponse;
 pub mod create_withdrawal_request_body;
 pub use self::create_withdrawal_request_body::CreateWithdrawalRequestBody;
 pub mod deposit;
 pub use self::deposit::Deposit;
+pub mod deposit_confirmation_tier;
+pub use self::deposit_confirmation_tier::DepositConfirmationTier;
 pub mod deposit_info;
 pub use self::deposit_info::DepositInfo;
 pub mod deposit_parameters;
 pub use self::deposit_parameters::DepositParameters;
 pub mod deposit_status;
 pub use self::deposit_status::DepositStatus;
+pub mod deposit_status_update;
+pub use self::deposit_status_update::DepositStatusUpdate;
 pub mod deposit_update;
 pub use self::deposit_update::DepositUpdate;
 pub mod deposit_with_status;
@@ -22,20 +36,38 @@ pub mod error_response;
 pub use self::error_response::ErrorResponse;
 pub mod expected_fulfillment_info;
 pub use self::expected_fulfillment_info::ExpectedFulfillmentInfo;
+pub mod field_validation_error;
+pub use self::field_validation_error::FieldValidationError;
 pub mod fulfillment;
 pub use self::fulfillment::Fulfillment;
+pub mod get_deposit_eta_response;
+pub use self::get_deposit_eta_response::GetDepositEtaResponse;
+pub mod get_deposit_history_response;
+pub use self::get_deposit_history_response::GetDepositHistoryResponse;
 pub mod get_deposits_for_transaction_response;
 pub use self::get_deposits_for_transaction_response::GetDepositsForTransactionResponse;
 pub mod get_deposits_response;
 pub use self::get_deposits_response::GetDepositsResponse;
 pub mod get_throttle_key_response;
 pub use self::get_throttle_key_response::GetThrottleKeyResponse;
+pub mod get_withdrawal_history_response;
+pub use self::get_withdrawal_history_response::GetWithdrawalHistoryResponse;
 pub mod get_withdrawals_response;
 pub use self::get_withdrawals_response::GetWithdrawalsResponse;
 pub mod health_data;
 pub use self::health_data::HealthData;
 pub mod limits;
 pub use self::limits::Limits;
+pub mod request_field;
+pub use self::request_field::RequestField;
+pub mod rotate_api_key_request;
+pub use self::rotate_api_key_request::RotateApiKeyRequest;
+pub mod run_retention_response;
+pub use self::run_retention_response::RunRetentionResponse;
+pub mod set_available_to_mint_request_body;
+pub use self::set_available_to_mint_request_body::SetAvailableToMintRequestBody;
+pub mod status_update_actor;
+pub use self::status_update_actor::StatusUpdateActor;
 pub mod throttle_key;
 pub use self::throttle_key::ThrottleKey;
 pub mod throttle_request;
@@ -48,6 +80,8 @@ pub mod update_withdrawals_request_body;
 pub use self::update_withdrawals_request_body::UpdateWithdrawalsRequestBody;
 pub mod update_withdrawals_response;
 pub use self::update_withdrawals_response::UpdateWithdrawalsResponse;
+pub mod validation_error_response;
+pub use self::validation_error_response::ValidationErrorResponse;
 pub mod withdrawal;
 pub use self::withdrawal::Withdrawal;
 pub mod withdrawal_info;
@@ -56,6 +90,8 @@ pub mod withdrawal_parameters;
 pub use self::withdrawal_parameters::WithdrawalParameters;
 pub mod withdrawal_status;
 pub use self::withdrawal_status::WithdrawalStatus;
+pub mod withdrawal_status_update;
+pub use self::withdrawal_status_update::WithdrawalStatusUpdate;
 pub mod withdrawal_update;
 pub use self::withdrawal_update::WithdrawalUpdate;
 pub mod withdrawal_with_status;
@@ -0,0 +1,27 @@
+/*
+ * emily-openapi-spec
+ *
+ * No description provided (generated by Openapi Generator https://github.com/openapitools/openapi-generator)
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// RotateApiKeyRequest : Request to rotate the secret of an existing api key.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RotateApiKeyRequest {
+    /// The new secret associated with this key. The old secret stops working as soon as the key is rotated.
+    #[serde(rename = "secret")]
+    pub secret: String,
+}
+
+impl RotateApiKeyRequest {
+    /// Request to rotate the secret of an existing api key.
+    pub fn new(secret: String) -> RotateApiKeyRequest {
+        RotateApiKeyRequest { secret }
+    }
+}
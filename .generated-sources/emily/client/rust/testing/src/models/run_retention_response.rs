@@ -0,0 +1,51 @@
+/*
+ * emily-openapi-spec
+ *
+ * No description provided (generated by Openapi Generator https://github.com/openapitools/openapi-generator)
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// RunRetentionResponse : Response to a run of the retention policy.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RunRetentionResponse {
+    /// The number of completed deposits moved into the archive table.
+    #[serde(rename = "archivedDeposits")]
+    pub archived_deposits: u64,
+    /// The number of completed withdrawals moved into the archive table.
+    #[serde(rename = "archivedWithdrawals")]
+    pub archived_withdrawals: u64,
+    /// Whether every request that is due to be archived or purged has been. Each run handles a bounded number of requests, so the retention policy should be run again until this is true.
+    #[serde(rename = "complete")]
+    pub complete: bool,
+    /// The number of archived deposits deleted.
+    #[serde(rename = "purgedDeposits")]
+    pub purged_deposits: u64,
+    /// The number of archived withdrawals deleted.
+    #[serde(rename = "purgedWithdrawals")]
+    pub purged_withdrawals: u64,
+}
+
+impl RunRetentionResponse {
+    /// Response to a run of the retention policy.
+    pub fn new(
+        archived_deposits: u64,
+        archived_withdrawals: u64,
+        complete: bool,
+        purged_deposits: u64,
+        purged_withdrawals: u64,
+    ) -> RunRetentionResponse {
+        RunRetentionResponse {
+            archived_deposits,
+            archived_withdrawals,
+            complete,
+            purged_deposits,
+            purged_withdrawals,
+        }
+    }
+}
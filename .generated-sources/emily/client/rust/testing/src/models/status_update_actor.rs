@@ -0,0 +1,43 @@
+/*
+ * emily-openapi-spec
+ *
+ * No description provided (generated by Openapi Generator https://github.com/openapitools/openapi-generator)
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// StatusUpdateActor : The party that caused a status update of an sBTC operation.
+/// The party that caused a status update of an sBTC operation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum StatusUpdateActor {
+    #[serde(rename = "creator")]
+    Creator,
+    #[serde(rename = "signer")]
+    Signer,
+    #[serde(rename = "sidecar")]
+    Sidecar,
+    #[serde(rename = "reorg")]
+    Reorg,
+}
+
+impl std::fmt::Display for StatusUpdateActor {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Creator => write!(f, "creator"),
+            Self::Signer => write!(f, "signer"),
+            Self::Sidecar => write!(f, "sidecar"),
+            Self::Reorg => write!(f, "reorg"),
+        }
+    }
+}
+
+impl Default for StatusUpdateActor {
+    fn default() -> StatusUpdateActor {
+        Self::Creator
+    }
+}
@@ -68,6 +68,15 @@ export const contracts = {
         ],
         Response<boolean, bigint>
       >,
+      updateIsPausedWrapper: {
+        name: "update-is-paused-wrapper",
+        access: "public",
+        args: [{ name: "paused", type: "bool" }],
+        outputs: { type: { response: { ok: "bool", error: "uint128" } } },
+      } as TypedAbiFunction<
+        [paused: TypedAbiArg<boolean, "paused">],
+        Response<boolean, bigint>
+      >,
       bytesLen: {
         name: "bytes-len",
         access: "read_only",
@@ -1148,6 +1157,15 @@ export const contracts = {
         ],
         Response<boolean, bigint>
       >,
+      updateIsPaused: {
+        name: "update-is-paused",
+        access: "public",
+        args: [{ name: "paused", type: "bool" }],
+        outputs: { type: { response: { ok: "bool", error: "uint128" } } },
+      } as TypedAbiFunction<
+        [paused: TypedAbiArg<boolean, "paused">],
+        Response<boolean, bigint>
+      >,
       getActiveProtocol: {
         name: "get-active-protocol",
         access: "read_only",
@@ -1279,6 +1297,12 @@ export const contracts = {
         ],
         boolean | null
       >,
+      getIsPaused: {
+        name: "get-is-paused",
+        access: "read_only",
+        args: [],
+        outputs: { type: "bool" },
+      } as TypedAbiFunction<[], boolean>,
       getWithdrawalRequest: {
         name: "get-withdrawal-request",
         access: "read_only",
@@ -1545,6 +1569,11 @@ export const contracts = {
         },
         access: "variable",
       } as TypedAbiVariable<Uint8Array[]>,
+      isPaused: {
+        name: "is-paused",
+        type: "bool",
+        access: "variable",
+      } as TypedAbiVariable<boolean>,
       lastWithdrawalRequestId: {
         name: "last-withdrawal-request-id",
         type: "uint128",
@@ -1570,6 +1599,7 @@ export const contracts = {
       currentSignerSet: [],
       depositRole: Uint8Array.from([1]),
       governanceRole: Uint8Array.from([0]),
+      isPaused: false,
       lastWithdrawalRequestId: 0n,
       withdrawalRole: Uint8Array.from([2]),
     },
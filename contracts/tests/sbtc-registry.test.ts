@@ -203,5 +203,28 @@ describe("sBTC registry contract", () => {
       );
       expect(receipt.value).toEqual(errors.signers.ERR_KEY_SIZE);
     });
+    test("Update is paused wrapper correctly", () => {
+      expect(rov(registry.getIsPaused())).toBe(false);
+      txOk(signers.updateIsPausedWrapper({ paused: true }), deployer);
+      expect(rov(registry.getIsPaused())).toBe(true);
+      txOk(signers.updateIsPausedWrapper({ paused: false }), deployer);
+      expect(rov(registry.getIsPaused())).toBe(false);
+    });
+    test("Update is paused wrapper from a non-signer principal", () => {
+      const receipt = txErr(
+        signers.updateIsPausedWrapper({ paused: true }),
+        alice
+      );
+      expect(receipt.value).toEqual(errors.signers.ERR_INVALID_CALLER);
+      expect(rov(registry.getIsPaused())).toBe(false);
+    });
+    test("Update is paused directly in the registry", () => {
+      const receipt = txErr(
+        registry.updateIsPaused({ paused: true }),
+        deployer
+      );
+      expect(receipt.value).toEqual(errors.registry.ERR_UNAUTHORIZED);
+      expect(rov(registry.getIsPaused())).toBe(false);
+    });
   });
 });
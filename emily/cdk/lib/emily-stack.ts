@@ -60,6 +60,26 @@ export class EmilyStack extends cdk.Stack {
             pointInTimeRecovery,
         );
 
+        // The archive tables hold completed requests once they are past the retention
+        // window, and mirror the schema of the tables they archive.
+        const depositArchiveTableId: string = 'DepositArchiveTable';
+        const depositArchiveTableName: string = EmilyStackUtils.getResourceName(depositArchiveTableId, props);
+        const depositArchiveTable: dynamodb.Table = this.createOrUpdateDepositTable(
+            depositArchiveTableId,
+            depositArchiveTableName,
+            persistentResourceRemovalPolicy,
+            pointInTimeRecovery,
+        );
+
+        const withdrawalArchiveTableId: string = 'WithdrawalArchiveTable';
+        const withdrawalArchiveTableName: string = EmilyStackUtils.getResourceName(withdrawalArchiveTableId, props);
+        const withdrawalArchiveTable: dynamodb.Table = this.createOrUpdateWithdrawalTable(
+            withdrawalArchiveTableId,
+            withdrawalArchiveTableName,
+            persistentResourceRemovalPolicy,
+            pointInTimeRecovery,
+        );
+
         const chainstateTableId: string = 'ChainstateTable';
         const chainstateTableName: string = EmilyStackUtils.getResourceName(chainstateTableId, props);
         const chainstateTable: dynamodb.Table = this.createOrUpdateChainstateTable(
@@ -87,13 +107,25 @@ export class EmilyStack extends cdk.Stack {
             pointInTimeRecovery,
         );
 
+        const apiKeyTableId: string = 'ApiKeyTable';
+        const apiKeyTableName: string = EmilyStackUtils.getResourceName(apiKeyTableId, props);
+        const apiKeyTable: dynamodb.Table = this.createOrUpdateApiKeyTable(
+            apiKeyTableId,
+            apiKeyTableName,
+            persistentResourceRemovalPolicy,
+            pointInTimeRecovery,
+        );
+
         if (!EmilyStackUtils.isTablesOnly()) {
             const operationLambda: lambda.Function = this.createOrUpdateOperationLambda(
                 depositTableName,
                 withdrawalTableName,
+                depositArchiveTableName,
+                withdrawalArchiveTableName,
                 chainstateTableName,
                 limitTableName,
                 throttleTableName,
+                apiKeyTableName,
                 persistentResourceRemovalPolicy,
                 props
             );
@@ -107,9 +139,12 @@ export class EmilyStack extends cdk.Stack {
             // Give the operation lambda full access to the DynamoDB tables.
             depositTable.grantReadWriteData(operationLambda);
             withdrawalTable.grantReadWriteData(operationLambda);
+            depositArchiveTable.grantReadWriteData(operationLambda);
+            withdrawalArchiveTable.grantReadWriteData(operationLambda);
             chainstateTable.grantReadWriteData(operationLambda);
             limitTable.grantReadWriteData(operationLambda);
             throttleTable.grantReadWriteData(operationLambda);
+            apiKeyTable.grantReadWriteData(operationLambda);
 
             const sanctionsBucket: s3.Bucket = this.createSanctionsBucket(
                 persistentResourceRemovalPolicy,
@@ -299,6 +334,7 @@ export class EmilyStack extends cdk.Stack {
                 "Amount",
                 "LastUpdateBlockHash",
                 "Txid",
+                "Fulfillment",
             ]
         });
 
@@ -323,6 +359,7 @@ export class EmilyStack extends cdk.Stack {
                 "Amount",
                 "LastUpdateBlockHash",
                 "Txid",
+                "Fulfillment",
             ]
         });
 
@@ -347,6 +384,7 @@ export class EmilyStack extends cdk.Stack {
                 "Amount",
                 "LastUpdateBlockHash",
                 "Txid",
+                "Fulfillment",
             ]
         });
         return table;
@@ -430,6 +468,36 @@ export class EmilyStack extends cdk.Stack {
         return table;
     }
 
+    /**
+     * Creates or updates a DynamoDB table for api keys.
+     * @param {string} tableId The id of the table AWS resource.
+     * @param {string} tableName The name of the DynamoDB table.
+     * @returns {dynamodb.Table} The created or updated DynamoDB table.
+     * @post A DynamoDB table is returned without additional configuration.
+     */
+    createOrUpdateApiKeyTable(
+        tableId: string,
+        tableName: string,
+        removalPolicy: cdk.RemovalPolicy,
+        pointInTimeRecovery: undefined | boolean,
+    ): dynamodb.Table {
+        // Create DynamoDB table to store the api keys. Encrypted by default.
+        return new dynamodb.Table(this, tableId, {
+            tableName: tableName,
+            partitionKey: {
+                name: 'Name',
+                type: dynamodb.AttributeType.STRING,
+            },
+            sortKey: {
+                name: 'UpdatedAt',
+                type: dynamodb.AttributeType.NUMBER,
+            },
+            removalPolicy: removalPolicy,
+            billingMode: dynamodb.BillingMode.PAY_PER_REQUEST, // On-demand provisioning
+            pointInTimeRecovery: pointInTimeRecovery,
+        });
+    }
+
     /**
      * Creates or updates a DynamoDB table for limits.
      * @param {string} tableId The id of the table AWS resource.
@@ -472,9 +540,12 @@ export class EmilyStack extends cdk.Stack {
     createOrUpdateOperationLambda(
         depositTableName: string,
         withdrawalTableName: string,
+        depositArchiveTableName: string,
+        withdrawalArchiveTableName: string,
         chainstateTableName: string,
         limitTableName: string,
         throttleTableName: string,
+        apiKeyTableName: string,
         removalPolicy: cdk.RemovalPolicy,
         props: EmilyStackProps,
     ): lambda.Function {
@@ -496,9 +567,12 @@ export class EmilyStack extends cdk.Stack {
                 // Give lambda access to the table name.
                 DEPOSIT_TABLE_NAME: depositTableName,
                 WITHDRAWAL_TABLE_NAME: withdrawalTableName,
+                DEPOSIT_ARCHIVE_TABLE_NAME: depositArchiveTableName,
+                WITHDRAWAL_ARCHIVE_TABLE_NAME: withdrawalArchiveTableName,
                 CHAINSTATE_TABLE_NAME: chainstateTableName,
                 LIMIT_TABLE_NAME: limitTableName,
                 THROTTLEDOWN_TABLE_NAME: throttleTableName,
+                API_KEY_TABLE_NAME: apiKeyTableName,
                 // Declare an environment variable that will be overwritten in local SAM
                 // deployments the AWS stack. SAM can only set environment variables that are
                 // already expected to be present in the lambda.
@@ -33,6 +33,8 @@ describe('EmilyStack Test', () => {
         // that duplicate the specification.
         expect(tableNames).toContain(`DepositTable-account-region-${Constants.UNIT_TEST_STAGE_NAME}`);
         expect(tableNames).toContain(`WithdrawalTable-account-region-${Constants.UNIT_TEST_STAGE_NAME}`);
+        expect(tableNames).toContain(`DepositArchiveTable-account-region-${Constants.UNIT_TEST_STAGE_NAME}`);
+        expect(tableNames).toContain(`WithdrawalArchiveTable-account-region-${Constants.UNIT_TEST_STAGE_NAME}`);
         expect(tableNames).toContain(`ChainstateTable-account-region-${Constants.UNIT_TEST_STAGE_NAME}`);
     });
 
@@ -60,9 +62,12 @@ describe('EmilyStack Test', () => {
                 const environment = lambdaResources[lambdaLogicalId].Properties.Environment.Variables;
                 expect(environment.DEPOSIT_TABLE_NAME).toMatch(`DepositTable-account-region-${Constants.UNIT_TEST_STAGE_NAME}`);
                 expect(environment.WITHDRAWAL_TABLE_NAME).toMatch(`WithdrawalTable-account-region-${Constants.UNIT_TEST_STAGE_NAME}`);
+                expect(environment.DEPOSIT_ARCHIVE_TABLE_NAME).toMatch(`DepositArchiveTable-account-region-${Constants.UNIT_TEST_STAGE_NAME}`);
+                expect(environment.WITHDRAWAL_ARCHIVE_TABLE_NAME).toMatch(`WithdrawalArchiveTable-account-region-${Constants.UNIT_TEST_STAGE_NAME}`);
                 expect(environment.CHAINSTATE_TABLE_NAME).toMatch(`ChainstateTable-account-region-${Constants.UNIT_TEST_STAGE_NAME}`);
                 expect(environment.LIMIT_TABLE_NAME).toMatch(`LimitTable-account-region-${Constants.UNIT_TEST_STAGE_NAME}`);
                 expect(environment.THROTTLEDOWN_TABLE_NAME).toMatch(`ThrottleTable-account-region-${Constants.UNIT_TEST_STAGE_NAME}`);
+                expect(environment.API_KEY_TABLE_NAME).toMatch(`ApiKeyTable-account-region-${Constants.UNIT_TEST_STAGE_NAME}`);
                 expect(environment.IS_LOCAL).toEqual("false");
                 expect(environment.IS_MAINNET).toEqual("false");
                 expect(environment.DEPLOYER_ADDRESS).toEqual("SN3R84XZYA63QS28932XQF3G1J8R9PC3W76P9CSQS");
@@ -0,0 +1,258 @@
+//! Api key authentication and authorization for the Emily API.
+//!
+//! Callers identify themselves with an `Authorization: Bearer <token>`
+//! header, whose token is the name of the key and its secret separated by
+//! a `:`. Each key is granted a set of scopes, and every request other
+//! than health checks requires one of them. Keys may also be rate limited,
+//! in which case requests over the limit are rejected until the current
+//! one minute window has passed.
+//!
+//! Scopes are only enforced when the `ENFORCE_API_KEY_SCOPES` setting is
+//! enabled, so that existing deployments keep working until their callers
+//! have been issued keys.
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use sha2::{Digest as _, Sha256};
+use warp::Filter;
+use warp::http::Method;
+use warp::http::header::AUTHORIZATION;
+use warp::path::Peek;
+
+use crate::api::models::api_key::ApiKeyScope;
+use crate::common::error::Error;
+use crate::context::EmilyContext;
+use crate::database::accessors::{self, ApiKeyVerificationResult};
+
+/// The scheme prefix of the `Authorization` header value.
+pub const BEARER_PREFIX: &str = "Bearer ";
+
+/// The maximum number of api keys whose verified secrets are remembered.
+const MAX_VERIFIED_API_KEYS: usize = 1024;
+
+/// The length of the window over which requests are counted for rate
+/// limiting.
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+
+/// Tracks how many requests each api key has made in the current rate
+/// limiting window.
+///
+/// The counters are kept in memory, so when Emily runs as more than one
+/// lambda instance the limit applies to each instance separately.
+#[derive(Debug, Default)]
+pub struct ApiKeyRateLimiter {
+    /// The start of the current window and the number of requests made
+    /// within it, keyed by the name of the api key.
+    windows: Mutex<HashMap<String, (Instant, u32)>>,
+}
+
+impl ApiKeyRateLimiter {
+    /// Record a request made with the given key and return whether it is
+    /// within the given per-minute limit.
+    pub fn check(&self, name: &str, limit_per_minute: u32) -> bool {
+        self.check_at(name, limit_per_minute, Instant::now())
+    }
+
+    fn check_at(&self, name: &str, limit_per_minute: u32, now: Instant) -> bool {
+        let mut windows = self
+            .windows
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let (window_start, count) = windows.entry(name.to_string()).or_insert((now, 0));
+
+        if now.duration_since(*window_start) >= RATE_LIMIT_WINDOW {
+            *window_start = now;
+            *count = 0;
+        }
+        if *count >= limit_per_minute {
+            return false;
+        }
+        *count += 1;
+        true
+    }
+}
+
+/// Remembers the api key secrets that have already been checked against
+/// the stored argon2 hash of their key, so that argon2 only runs the first
+/// time that a secret is presented rather than on every request.
+///
+/// Only a SHA-256 digest of each secret is kept, along with the stored
+/// hash that it was checked against. Rotating a key changes its stored
+/// hash, so secrets verified against the old hash are no longer accepted.
+#[derive(Debug, Default)]
+pub struct VerifiedApiKeyCache {
+    /// The stored hash and the digest of the verified secret, keyed by the
+    /// name of the api key.
+    verified: Mutex<HashMap<String, (String, [u8; 32])>>,
+}
+
+impl VerifiedApiKeyCache {
+    /// Whether the given secret was verified against the given stored hash
+    /// of the named key.
+    pub fn contains(&self, name: &str, stored_hash: &str, secret: &str) -> bool {
+        let verified = self
+            .verified
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        verified.get(name).is_some_and(|(hash, digest)| {
+            hash == stored_hash && constant_time_eq(digest, &Sha256::digest(secret).into())
+        })
+    }
+
+    /// Remember that the given secret matches the given stored hash of the
+    /// named key.
+    pub fn insert(&self, name: &str, stored_hash: &str, secret: &str) {
+        let mut verified = self
+            .verified
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if verified.len() >= MAX_VERIFIED_API_KEYS && !verified.contains_key(name) {
+            verified.clear();
+        }
+        let entry = (stored_hash.to_string(), Sha256::digest(secret).into());
+        verified.insert(name.to_string(), entry);
+    }
+}
+
+/// Compare two byte strings in time that only depends on their lengths,
+/// so that the comparison does not leak how much of a secret was correct.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Returns the scope needed to make a request with the given method to the
+/// given path, or `None` if the request does not need an api key.
+pub fn required_scope(method: &Method, path: &str) -> Option<ApiKeyScope> {
+    let path = path.trim_matches('/');
+    match path.split('/').next() {
+        Some("health") => None,
+        // Api keys are only ever managed by administrators.
+        Some("api_key") => Some(ApiKeyScope::AdminUpdate),
+        _ if method == Method::POST && matches!(path, "deposit" | "deposit/bulk") => {
+            Some(ApiKeyScope::DepositCreate)
+        }
+        _ if method == Method::GET => Some(ApiKeyScope::StatusRead),
+        _ => Some(ApiKeyScope::AdminUpdate),
+    }
+}
+
+/// Check that the given `Authorization` header allows a request with the given
+/// method to the given path.
+pub async fn authorize(
+    context: &EmilyContext,
+    method: &Method,
+    path: &str,
+    api_key: Option<&str>,
+) -> Result<(), Error> {
+    if !context.settings.enforce_api_key_scopes {
+        return Ok(());
+    }
+    let Some(scope) = required_scope(method, path) else {
+        return Ok(());
+    };
+    let Some((name, secret)) = api_key
+        .and_then(|value| value.strip_prefix(BEARER_PREFIX))
+        .and_then(|token| token.split_once(':'))
+    else {
+        return Err(Error::Unauthorized);
+    };
+
+    let key = match accessors::verify_api_key(context, name, secret).await? {
+        ApiKeyVerificationResult::Valid(key) => key,
+        ApiKeyVerificationResult::Revoked => {
+            tracing::warn!(key_name = %name, "request made with a revoked api key");
+            return Err(Error::Forbidden);
+        }
+        ApiKeyVerificationResult::Invalid => return Err(Error::Unauthorized),
+    };
+
+    if !key.scopes.contains(&scope) {
+        tracing::warn!(key_name = %name, ?scope, "api key is missing the required scope");
+        return Err(Error::Forbidden);
+    }
+    if let Some(limit) = key.rate_limit_per_minute {
+        if !context.rate_limiter.check(name, limit) {
+            return Err(Error::TooManyRequests);
+        }
+    }
+    Ok(())
+}
+
+/// A filter that rejects requests whose api key does not allow them.
+pub fn with_authorization<F>(
+    context: F,
+) -> impl Filter<Extract = (), Error = warp::Rejection> + Clone
+where
+    F: Filter<Extract = (EmilyContext,), Error = Infallible> + Clone + Send + Sync + 'static,
+{
+    warp::method()
+        .and(warp::path::peek())
+        .and(warp::header::optional::<String>(AUTHORIZATION.as_str()))
+        .and(context)
+        .and_then(
+            |method: Method, path: Peek, api_key: Option<String>, context: EmilyContext| async move {
+                authorize(&context, &method, path.as_str(), api_key.as_deref())
+                    .await
+                    .map_err(warp::reject::custom)
+            },
+        )
+        .untuple_one()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_case::test_case;
+
+    #[test_case(Method::GET, "/health", None; "health")]
+    #[test_case(Method::POST, "/deposit", Some(ApiKeyScope::DepositCreate); "create-deposit")]
+    #[test_case(Method::POST, "/deposit/bulk", Some(ApiKeyScope::DepositCreate); "create-deposits-bulk")]
+    #[test_case(Method::GET, "/deposit/abcd/0", Some(ApiKeyScope::StatusRead); "get-deposit")]
+    #[test_case(Method::GET, "/withdrawal", Some(ApiKeyScope::StatusRead); "get-withdrawals")]
+    #[test_case(Method::PUT, "/deposit", Some(ApiKeyScope::AdminUpdate); "update-deposits")]
+    #[test_case(Method::POST, "/limits", Some(ApiKeyScope::AdminUpdate); "set-limits")]
+    #[test_case(Method::POST, "/api_key", Some(ApiKeyScope::AdminUpdate); "create-api-key")]
+    #[test_case(Method::GET, "/api_key/operator", Some(ApiKeyScope::AdminUpdate); "get-api-key")]
+    #[test_case(Method::POST, "/retention", Some(ApiKeyScope::AdminUpdate); "run-retention")]
+    fn required_scopes(method: Method, path: &str, expected: Option<ApiKeyScope>) {
+        assert_eq!(required_scope(&method, path), expected);
+    }
+
+    #[test]
+    fn rate_limiter_resets_after_window() {
+        let limiter = ApiKeyRateLimiter::default();
+        let start = Instant::now();
+
+        assert!(limiter.check_at("key", 2, start));
+        assert!(limiter.check_at("key", 2, start));
+        assert!(!limiter.check_at("key", 2, start));
+        // Other keys have their own counters.
+        assert!(limiter.check_at("other-key", 2, start));
+
+        assert!(limiter.check_at("key", 2, start + RATE_LIMIT_WINDOW));
+    }
+
+    #[test]
+    fn verified_api_key_cache_matches_hash_and_secret() {
+        let cache = VerifiedApiKeyCache::default();
+        assert!(!cache.contains("key", "hash", "secret"));
+
+        cache.insert("key", "hash", "secret");
+        assert!(cache.contains("key", "hash", "secret"));
+        assert!(!cache.contains("key", "hash", "other-secret"));
+        // A rotated key has a new stored hash.
+        assert!(!cache.contains("key", "rotated-hash", "secret"));
+        assert!(!cache.contains("other-key", "hash", "secret"));
+    }
+
+    #[test_case(b"secret", b"secret", true; "equal")]
+    #[test_case(b"secret", b"secreT", false; "different")]
+    #[test_case(b"secret", b"secrets", false; "different-lengths")]
+    fn constant_time_eq_works(a: &[u8], b: &[u8], expected: bool) {
+        assert_eq!(constant_time_eq(a, b), expected);
+    }
+}
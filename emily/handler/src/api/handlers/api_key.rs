@@ -0,0 +1,234 @@
+//! Handlers for api key endpoints.
+use std::time::SystemTime;
+
+use crate::{
+    api::models::api_key::{ApiKeyInfo, CreateApiKeyRequest, RotateApiKeyRequest},
+    common::error::Error,
+    context::EmilyContext,
+    database::{
+        accessors,
+        entries::api_key::{ApiKeyEntry, ApiKeyEntryKey},
+    },
+};
+use tracing::instrument;
+use warp::http::StatusCode;
+use warp::reply::{Reply, json, with_status};
+
+/// The current time in seconds from UNIX epoch.
+fn now_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        // It's impossible for this to fail.
+        .expect("Error making timestamp during api key entry creation.")
+        .as_secs()
+}
+
+/// Create api key handler.
+#[utoipa::path(
+    post,
+    operation_id = "createApiKey",
+    path = "/api_key",
+    tag = "api_key",
+    request_body = CreateApiKeyRequest,
+    responses(
+        (status = 201, description = "Api key created successfully", body = ApiKeyInfo),
+        (status = 400, description = "Invalid request body", body = ErrorResponse),
+        (status = 405, description = "Method not allowed", body = ErrorResponse),
+        (status = 409, description = "Key already exists", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(("ApiGatewayKey" = [], "EmilyApiKey" = []))
+)]
+#[instrument(skip(request, context), fields(key.name = %request.name))]
+pub async fn create_api_key(
+    request: CreateApiKeyRequest,
+    context: EmilyContext,
+) -> impl warp::reply::Reply {
+    // Internal handler so `?` can be used correctly while still returning a reply.
+    async fn handler(
+        context: EmilyContext,
+        request: CreateApiKeyRequest,
+    ) -> Result<impl warp::reply::Reply, Error> {
+        // The name and secret are sent together, separated by a `:`.
+        if request.name.is_empty() || request.name.contains(':') {
+            return Err(Error::HttpRequest(
+                StatusCode::BAD_REQUEST,
+                "api key names must be non-empty and must not contain ':'".to_string(),
+            ));
+        }
+        let entry = ApiKeyEntry {
+            key: ApiKeyEntryKey {
+                name: request.name.clone(),
+                updated_at: now_timestamp(),
+            },
+            hash: accessors::hash_api_key_secret(&request.name, &request.secret)?,
+            scopes: request.scopes,
+            rate_limit_per_minute: request.rate_limit_per_minute,
+            is_active: true,
+        };
+        accessors::add_api_key(&context, &entry).await?;
+        Ok(with_status(
+            json(&ApiKeyInfo::from(entry)),
+            StatusCode::CREATED,
+        ))
+    }
+    // Handle and respond.
+    handler(context, request)
+        .await
+        .map_or_else(Reply::into_response, Reply::into_response)
+}
+
+/// Get api key handler.
+#[utoipa::path(
+    get,
+    operation_id = "getApiKey",
+    path = "/api_key/{name}",
+    params(
+        ("name" = String, Path, description = "The name of the api key."),
+    ),
+    tag = "api_key",
+    responses(
+        (status = 200, description = "Api key retrieved successfully", body = ApiKeyInfo),
+        (status = 404, description = "Api key not found", body = ErrorResponse),
+        (status = 405, description = "Method not allowed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(("ApiGatewayKey" = [], "EmilyApiKey" = []))
+)]
+#[instrument(skip(context))]
+pub async fn get_api_key(name: String, context: EmilyContext) -> impl warp::reply::Reply {
+    // Internal handler so `?` can be used correctly while still returning a reply.
+    async fn handler(
+        name: String,
+        context: EmilyContext,
+    ) -> Result<impl warp::reply::Reply, Error> {
+        let key = accessors::get_api_key(&context, &name).await?;
+        Ok(with_status(json(&ApiKeyInfo::from(key)), StatusCode::OK))
+    }
+    // Handle and respond.
+    handler(name, context)
+        .await
+        .map_or_else(Reply::into_response, Reply::into_response)
+}
+
+/// Rotate api key handler. The old secret stops working immediately.
+#[utoipa::path(
+    post,
+    operation_id = "rotateApiKey",
+    path = "/api_key/{name}/rotate",
+    params(
+        ("name" = String, Path, description = "The name of the api key."),
+    ),
+    tag = "api_key",
+    request_body = RotateApiKeyRequest,
+    responses(
+        (status = 200, description = "Api key rotated successfully", body = ApiKeyInfo),
+        (status = 400, description = "Invalid request body", body = ErrorResponse),
+        (status = 404, description = "Api key not found", body = ErrorResponse),
+        (status = 405, description = "Method not allowed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(("ApiGatewayKey" = [], "EmilyApiKey" = []))
+)]
+#[instrument(skip(request, context))]
+pub async fn rotate_api_key(
+    name: String,
+    request: RotateApiKeyRequest,
+    context: EmilyContext,
+) -> impl warp::reply::Reply {
+    // Internal handler so `?` can be used correctly while still returning a reply.
+    async fn handler(
+        name: String,
+        request: RotateApiKeyRequest,
+        context: EmilyContext,
+    ) -> Result<impl warp::reply::Reply, Error> {
+        let mut entry = accessors::get_api_key(&context, &name).await?;
+        entry.hash = accessors::hash_api_key_secret(&name, &request.secret)?;
+        entry.key.updated_at = now_timestamp();
+        accessors::update_api_key(&context, &entry).await?;
+        tracing::info!(key_name = %name, "rotated api key");
+        Ok(with_status(json(&ApiKeyInfo::from(entry)), StatusCode::OK))
+    }
+    // Handle and respond.
+    handler(name, request, context)
+        .await
+        .map_or_else(Reply::into_response, Reply::into_response)
+}
+
+/// Set whether an existing api key may be used.
+async fn set_api_key_activity(
+    context: &EmilyContext,
+    name: String,
+    is_active: bool,
+) -> Result<(), Error> {
+    let mut entry = accessors::get_api_key(context, &name).await?;
+    entry.is_active = is_active;
+    entry.key.updated_at = now_timestamp();
+    accessors::update_api_key(context, &entry).await
+}
+
+/// Deactivate api key handler.
+#[utoipa::path(
+    patch,
+    operation_id = "deactivateApiKey",
+    path = "/api_key/{name}/deactivate",
+    params(
+        ("name" = String, Path, description = "The name of the api key."),
+    ),
+    tag = "api_key",
+    responses(
+        (status = 204, description = "Api key deactivated successfully"),
+        (status = 404, description = "Api key not found", body = ErrorResponse),
+        (status = 405, description = "Method not allowed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(("ApiGatewayKey" = [], "EmilyApiKey" = []))
+)]
+#[instrument(skip(context))]
+pub async fn deactivate_api_key(name: String, context: EmilyContext) -> impl warp::reply::Reply {
+    // Internal handler so `?` can be used correctly while still returning a reply.
+    async fn handler(
+        name: String,
+        context: EmilyContext,
+    ) -> Result<impl warp::reply::Reply, Error> {
+        set_api_key_activity(&context, name, false).await?;
+        Ok(with_status(warp::reply(), StatusCode::NO_CONTENT))
+    }
+    // Handle and respond.
+    handler(name, context)
+        .await
+        .map_or_else(Reply::into_response, Reply::into_response)
+}
+
+/// Activate api key handler.
+#[utoipa::path(
+    patch,
+    operation_id = "activateApiKey",
+    path = "/api_key/{name}/activate",
+    params(
+        ("name" = String, Path, description = "The name of the api key."),
+    ),
+    tag = "api_key",
+    responses(
+        (status = 204, description = "Api key activated successfully"),
+        (status = 404, description = "Api key not found", body = ErrorResponse),
+        (status = 405, description = "Method not allowed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(("ApiGatewayKey" = [], "EmilyApiKey" = []))
+)]
+#[instrument(skip(context))]
+pub async fn activate_api_key(name: String, context: EmilyContext) -> impl warp::reply::Reply {
+    // Internal handler so `?` can be used correctly while still returning a reply.
+    async fn handler(
+        name: String,
+        context: EmilyContext,
+    ) -> Result<impl warp::reply::Reply, Error> {
+        set_api_key_activity(&context, name, true).await?;
+        Ok(with_status(warp::reply(), StatusCode::NO_CONTENT))
+    }
+    // Handle and respond.
+    handler(name, context)
+        .await
+        .map_or_else(Reply::into_response, Reply::into_response)
+}
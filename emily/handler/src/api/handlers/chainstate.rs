@@ -28,7 +28,8 @@ use warp::reply::{Reply, json, with_status};
         (status = 404, description = "Address not found", body = ErrorResponse),
         (status = 405, description = "Method not allowed", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
-    )
+    ),
+    security(("EmilyApiKey" = []))
 )]
 #[instrument(skip(context))]
 pub async fn get_chain_tip(context: EmilyContext) -> impl warp::reply::Reply {
@@ -61,7 +62,8 @@ pub async fn get_chain_tip(context: EmilyContext) -> impl warp::reply::Reply {
         (status = 404, description = "Address not found", body = ErrorResponse),
         (status = 405, description = "Method not allowed", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
-    )
+    ),
+    security(("EmilyApiKey" = []))
 )]
 #[instrument(skip(context))]
 pub async fn get_chainstate_at_height(
@@ -98,7 +100,7 @@ pub async fn get_chainstate_at_height(
         (status = 405, description = "Method not allowed", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
-    security(("ApiGatewayKey" = []))
+    security(("ApiGatewayKey" = [], "EmilyApiKey" = []))
 )]
 #[instrument(skip(context))]
 pub async fn set_chainstate(body: Chainstate, context: EmilyContext) -> impl warp::reply::Reply {
@@ -138,7 +140,7 @@ pub async fn set_chainstate(body: Chainstate, context: EmilyContext) -> impl war
         (status = 405, description = "Method not allowed", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
-    security(("ApiGatewayKey" = []))
+    security(("ApiGatewayKey" = [], "EmilyApiKey" = []))
 )]
 #[instrument(skip(context))]
 pub async fn update_chainstate(
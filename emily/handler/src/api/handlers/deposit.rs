@@ -8,28 +8,29 @@ use tracing::instrument;
 use warp::http::StatusCode;
 use warp::reply::{Reply, json, with_status};
 
-use crate::api::models::common::DepositStatus;
 use crate::api::models::common::requests::BasicPaginationQuery;
+use crate::api::models::common::{DepositStatus, Fulfillment, StatusUpdateActor};
 use crate::api::models::deposit::responses::{
-    DepositWithStatus, GetDepositsForTransactionResponse, UpdateDepositsResponse,
+    CreateDepositsResponse, DepositWithStatus, GetDepositEtaResponse, GetDepositHistoryResponse,
+    GetDepositsForTransactionResponse, UpdateDepositsResponse,
 };
 use crate::api::models::deposit::{Deposit, DepositInfo};
 use crate::api::models::{
     deposit::requests::{
-        CreateDepositRequestBody, GetDepositsForTransactionQuery, GetDepositsQuery,
-        UpdateDepositsRequestBody,
+        CreateDepositRequestBody, CreateDepositsRequestBody, GetDepositsForTransactionQuery,
+        GetDepositsQuery, UpdateDepositsRequestBody,
     },
     deposit::responses::GetDepositsResponse,
 };
 use crate::common::error::Error;
 use crate::context::EmilyContext;
 use crate::database::accessors;
-use crate::database::entries::DepositStatusEntry;
 use crate::database::entries::chainstate::ApiStateEntry;
 use crate::database::entries::deposit::{
     DepositEntry, DepositEntryKey, DepositEvent, DepositParametersEntry,
     ValidatedUpdateDepositsRequest,
 };
+use crate::database::entries::{DepositStatusEntry, event_timestamp};
 
 /// Get deposit handler.
 #[utoipa::path(
@@ -47,7 +48,8 @@ use crate::database::entries::deposit::{
         (status = 404, description = "Address not found", body = ErrorResponse),
         (status = 405, description = "Method not allowed", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
-    )
+    ),
+    security(("EmilyApiKey" = []))
 )]
 #[instrument(skip(context))]
 pub async fn get_deposit(
@@ -68,7 +70,7 @@ pub async fn get_deposit(
             bitcoin_tx_output_index,
         };
         // Get deposit.
-        let deposit: Deposit = accessors::get_deposit_entry(&context, &key)
+        let deposit: Deposit = accessors::get_deposit_entry_or_archived(&context, &key)
             .await?
             .try_into()?;
 
@@ -82,6 +84,166 @@ pub async fn get_deposit(
         .map_or_else(Reply::into_response, Reply::into_response)
 }
 
+/// Get deposit history handler.
+#[utoipa::path(
+    get,
+    operation_id = "getDepositHistory",
+    path = "/deposit/{txid}/{index}/history",
+    params(
+        ("txid" = String, Path, description = "txid associated with the Deposit."),
+        ("index" = String, Path, description = "output index associated with the Deposit."),
+    ),
+    tag = "deposit",
+    responses(
+        (status = 200, description = "Deposit history retrieved successfully", body = GetDepositHistoryResponse),
+        (status = 400, description = "Invalid request body", body = ErrorResponse),
+        (status = 404, description = "Address not found", body = ErrorResponse),
+        (status = 405, description = "Method not allowed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(("EmilyApiKey" = []))
+)]
+#[instrument(skip(context))]
+pub async fn get_deposit_history(
+    bitcoin_txid: String,
+    bitcoin_tx_output_index: u32,
+    context: EmilyContext,
+) -> impl warp::reply::Reply {
+    tracing::debug!("in get deposit history");
+    // Internal handler so `?` can be used correctly while still returning a reply.
+    async fn handler(
+        context: EmilyContext,
+        bitcoin_txid: String,
+        bitcoin_tx_output_index: u32,
+    ) -> Result<impl warp::reply::Reply, Error> {
+        // Make key.
+        let key = DepositEntryKey {
+            bitcoin_txid,
+            bitcoin_tx_output_index,
+        };
+        // Get the deposit along with its whole history.
+        let response: GetDepositHistoryResponse =
+            accessors::get_deposit_entry_or_archived(&context, &key)
+                .await?
+                .try_into()?;
+
+        // Respond.
+        Ok(with_status(json(&response), StatusCode::OK))
+    }
+
+    // Handle and respond.
+    handler(context, bitcoin_txid, bitcoin_tx_output_index)
+        .await
+        .map_or_else(Reply::into_response, Reply::into_response)
+}
+
+/// Get deposit ETA handler.
+#[utoipa::path(
+    get,
+    operation_id = "getDepositEta",
+    path = "/deposit/{txid}/{index}/eta",
+    params(
+        ("txid" = String, Path, description = "txid associated with the Deposit."),
+        ("index" = String, Path, description = "output index associated with the Deposit."),
+    ),
+    tag = "deposit",
+    responses(
+        (status = 200, description = "Deposit ETA estimated successfully", body = GetDepositEtaResponse),
+        (status = 400, description = "Invalid request body", body = ErrorResponse),
+        (status = 404, description = "Deposit not found", body = ErrorResponse),
+        (status = 405, description = "Method not allowed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(("EmilyApiKey" = []))
+)]
+#[instrument(skip(context))]
+pub async fn get_deposit_eta(
+    bitcoin_txid: String,
+    bitcoin_tx_output_index: u32,
+    context: EmilyContext,
+) -> impl warp::reply::Reply {
+    tracing::debug!("in get deposit eta");
+    // Internal handler so `?` can be used correctly while still returning a reply.
+    async fn handler(
+        context: EmilyContext,
+        bitcoin_txid: String,
+        bitcoin_tx_output_index: u32,
+    ) -> Result<impl warp::reply::Reply, Error> {
+        let key = DepositEntryKey {
+            bitcoin_txid,
+            bitcoin_tx_output_index,
+        };
+        let deposit = accessors::get_deposit_entry(&context, &key).await?;
+
+        // Accepted deposits go before pending ones, then older before
+        // newer, so count the deposits ahead of ours on the status index
+        // instead of reading the whole backlog.
+        let height = deposit.last_update_height;
+        let deposits_ahead = match deposit.status {
+            DepositStatus::Accepted => {
+                accessors::count_deposit_entries_with_status_below_height(
+                    &context,
+                    &DepositStatus::Accepted,
+                    height,
+                )
+                .await?
+            }
+            DepositStatus::Pending => {
+                let accepted = accessors::count_deposit_entries_with_status_below_height(
+                    &context,
+                    &DepositStatus::Accepted,
+                    u64::MAX,
+                )
+                .await?;
+                let pending = accessors::count_deposit_entries_with_status_below_height(
+                    &context,
+                    &DepositStatus::Pending,
+                    height,
+                )
+                .await?;
+                accepted + pending
+            }
+            DepositStatus::Confirmed | DepositStatus::Failed | DepositStatus::Rbf => 0,
+        };
+
+        // The signers report how and when each deposit was swept, so the
+        // fulfillments of the most recently confirmed deposits tell us how
+        // often sweeps happen and how much they cost.
+        let (recently_confirmed, _) = accessors::get_deposit_entries(
+            &context,
+            &DepositStatus::Confirmed,
+            None,
+            Some(DEPOSIT_ETA_SAMPLE_SIZE),
+            false,
+        )
+        .await?;
+        let mut recent_fulfillments = Vec::with_capacity(recently_confirmed.len());
+        for entry in recently_confirmed {
+            let confirmed =
+                accessors::get_deposit_entry(&context, &entry.primary_index_key).await?;
+            recent_fulfillments.extend(confirmed.fulfillment);
+        }
+
+        let bitcoin_tip_height = accessors::get_api_state(&context)
+            .await?
+            .chaintip()
+            .bitcoin_height;
+
+        let response = estimate_deposit_eta(
+            &deposit,
+            deposits_ahead,
+            &recent_fulfillments,
+            bitcoin_tip_height,
+        );
+        Ok(with_status(json(&response), StatusCode::OK))
+    }
+
+    // Handle and respond.
+    handler(context, bitcoin_txid, bitcoin_tx_output_index)
+        .await
+        .map_or_else(Reply::into_response, Reply::into_response)
+}
+
 /// Get deposits for transaction handler.
 #[utoipa::path(
     get,
@@ -99,7 +261,8 @@ pub async fn get_deposit(
         (status = 404, description = "Address not found", body = ErrorResponse),
         (status = 405, description = "Method not allowed", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
-    )
+    ),
+    security(("EmilyApiKey" = []))
 )]
 #[instrument(skip(context))]
 pub async fn get_deposits_for_transaction(
@@ -147,7 +310,8 @@ pub async fn get_deposits_for_transaction(
     params(
         ("status" = DepositStatus, Query, description = "the status to search by when getting all deposits."),
         ("nextToken" = Option<String>, Query, description = "the next token value from the previous return of this api call."),
-        ("pageSize" = Option<u16>, Query, description = "the maximum number of items in the response list.")
+        ("pageSize" = Option<u16>, Query, description = "the maximum number of items in the response list."),
+        ("includeArchived" = Option<bool>, Query, description = "whether to include archived deposits once the other deposits are exhausted.")
     ),
     tag = "deposit",
     responses(
@@ -156,7 +320,8 @@ pub async fn get_deposits_for_transaction(
         (status = 404, description = "Address not found", body = ErrorResponse),
         (status = 405, description = "Method not allowed", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
-    )
+    ),
+    security(("EmilyApiKey" = []))
 )]
 #[instrument(skip(context))]
 pub async fn get_deposits(
@@ -175,6 +340,7 @@ pub async fn get_deposits(
             &query.status,
             query.next_token,
             query.page_size,
+            query.include_archived.unwrap_or_default(),
         )
         .await?;
         // Convert data into resource types.
@@ -207,7 +373,8 @@ pub async fn get_deposits(
         (status = 404, description = "Address not found", body = ErrorResponse),
         (status = 405, description = "Method not allowed", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
-    )
+    ),
+    security(("EmilyApiKey" = []))
 )]
 #[instrument(skip(context))]
 pub async fn get_deposits_for_recipient(
@@ -259,7 +426,8 @@ pub async fn get_deposits_for_recipient(
         (status = 404, description = "Address not found", body = ErrorResponse),
         (status = 405, description = "Method not allowed", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
-    )
+    ),
+    security(("EmilyApiKey" = []))
 )]
 #[instrument(skip(context))]
 pub async fn get_deposits_for_reclaim_pubkeys(
@@ -306,11 +474,12 @@ pub async fn get_deposits_for_reclaim_pubkeys(
     responses(
         (status = 200, description = "Deposit already exists", body = Deposit),
         (status = 201, description = "Deposit created successfully", body = Deposit),
-        (status = 400, description = "Invalid request body", body = ErrorResponse),
+        (status = 400, description = "Invalid request body", body = ValidationErrorResponse),
         (status = 404, description = "Address not found", body = ErrorResponse),
         (status = 405, description = "Method not allowed", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
-    )
+    ),
+    security(("EmilyApiKey" = []))
 )]
 #[instrument(skip(context))]
 pub async fn create_deposit(
@@ -327,76 +496,169 @@ pub async fn create_deposit(
         context: EmilyContext,
         body: CreateDepositRequestBody,
     ) -> Result<impl warp::reply::Reply, Error> {
-        // Set variables.
         let api_state = accessors::get_api_state(&context).await?;
         api_state.error_if_reorganizing()?;
 
-        let chaintip = api_state.chaintip();
-        let stacks_block_hash = chaintip.key.hash;
-        let stacks_block_height = chaintip.key.height;
+        let (response, status) = add_deposit(&context, &api_state, body).await?;
+        Ok(with_status(json(&response), status))
+    }
+    // Handle and respond.
+    handler(context, body)
+        .await
+        .map_or_else(Reply::into_response, Reply::into_response)
+}
 
-        let deposit_info = body.validate(context.settings.is_mainnet)?;
+/// Create the given deposit, returning it along with the status code to
+/// respond with. If the deposit already exists then it is returned as is.
+async fn add_deposit(
+    context: &EmilyContext,
+    api_state: &ApiStateEntry,
+    body: CreateDepositRequestBody,
+) -> Result<(Deposit, StatusCode), Error> {
+    let chaintip = api_state.chaintip();
+    let stacks_block_hash = chaintip.key.hash;
+    let stacks_block_height = chaintip.key.height;
 
-        // Check if deposit with such txid and outindex already exists.
-        let entry = accessors::get_deposit_entry(
-            &context,
-            &DepositEntryKey {
-                bitcoin_txid: body.bitcoin_txid.clone(),
-                bitcoin_tx_output_index: body.bitcoin_tx_output_index,
-            },
-        )
-        .await;
+    let deposit_info = body.validate(context.settings.is_mainnet)?;
 
-        match entry {
-            Ok(deposit_entry) => {
-                // The deposit already exists, return it.
-                let response: Deposit = deposit_entry.try_into()?;
-                return Ok(with_status(json(&response), StatusCode::OK));
-            }
-            Err(Error::NotFound) => {}
-            Err(e) => return Err(e),
+    // Check if deposit with such txid and outindex already exists,
+    // including deposits that have been archived.
+    let entry = accessors::get_deposit_entry_or_archived(
+        context,
+        &DepositEntryKey {
+            bitcoin_txid: body.bitcoin_txid.clone(),
+            bitcoin_tx_output_index: body.bitcoin_tx_output_index,
+        },
+    )
+    .await;
+
+    match entry {
+        Ok(deposit_entry) => {
+            // The deposit already exists, return it.
+            let response: Deposit = deposit_entry.try_into()?;
+            return Ok((response, StatusCode::OK));
         }
-        let reclaim_pubkeys_hash = extract_reclaim_pubkeys_hash(&deposit_info.reclaim_script);
-        if reclaim_pubkeys_hash.is_none() {
-            tracing::warn!(
-                bitcoin_txid = %body.bitcoin_txid,
-                bitcoin_tx_output_index = %body.bitcoin_tx_output_index,
-                "unknown reclaim script"
-            );
+        Err(Error::NotFound) => {}
+        Err(e) => return Err(e),
+    }
+    let reclaim_pubkeys_hash = extract_reclaim_pubkeys_hash(&deposit_info.reclaim_script);
+    if reclaim_pubkeys_hash.is_none() {
+        tracing::warn!(
+            bitcoin_txid = %body.bitcoin_txid,
+            bitcoin_tx_output_index = %body.bitcoin_tx_output_index,
+            "unknown reclaim script"
+        );
+    }
+    // Make table entry.
+    let deposit_entry: DepositEntry = DepositEntry {
+        key: DepositEntryKey {
+            bitcoin_txid: body.bitcoin_txid,
+            bitcoin_tx_output_index: body.bitcoin_tx_output_index,
+        },
+        recipient: hex::encode(deposit_info.recipient.serialize_to_vec()),
+        parameters: DepositParametersEntry {
+            max_fee: deposit_info.max_fee,
+            lock_time: deposit_info.lock_time.to_consensus_u32(),
+        },
+        history: vec![DepositEvent {
+            status: DepositStatusEntry::Pending,
+            message: "Just received deposit".to_string(),
+            stacks_block_hash: stacks_block_hash.clone(),
+            stacks_block_height,
+            updated_at: Some(event_timestamp()),
+            actor: Some(StatusUpdateActor::Creator),
+        }],
+        status: DepositStatus::Pending,
+        last_update_block_hash: stacks_block_hash,
+        last_update_height: stacks_block_height,
+        amount: deposit_info.amount,
+        reclaim_script: body.reclaim_script,
+        deposit_script: body.deposit_script,
+        reclaim_pubkeys_hash,
+        ..Default::default()
+    };
+    // Validate deposit entry.
+    deposit_entry.validate()?;
+    // Add entry to the table.
+    accessors::add_deposit_entry(context, &deposit_entry).await?;
+    // Respond.
+    let response: Deposit = deposit_entry.try_into()?;
+    Ok((response, StatusCode::CREATED))
+}
+
+/// The maximum number of deposits that can be created with a single call
+/// to the create deposits endpoint.
+pub const MAX_CREATE_DEPOSITS_PER_REQUEST: usize = 100;
+
+/// Create deposits handler.
+#[utoipa::path(
+    post,
+    operation_id = "createDeposits",
+    path = "/deposit/bulk",
+    tag = "deposit",
+    request_body = CreateDepositsRequestBody,
+    responses(
+        (status = 200, description = "Deposits processed, see the status of each one", body = CreateDepositsResponse),
+        (status = 400, description = "Invalid request body", body = ErrorResponse),
+        (status = 404, description = "Address not found", body = ErrorResponse),
+        (status = 405, description = "Method not allowed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(("EmilyApiKey" = []))
+)]
+#[instrument(skip_all, fields(num_deposits = body.deposits.len()))]
+pub async fn create_deposits(
+    body: CreateDepositsRequestBody,
+    context: EmilyContext,
+) -> impl warp::reply::Reply {
+    tracing::debug!("creating deposits");
+    // Internal handler so `?` can be used correctly while still returning a reply.
+    async fn handler(
+        context: EmilyContext,
+        body: CreateDepositsRequestBody,
+    ) -> Result<impl warp::reply::Reply, Error> {
+        if body.deposits.len() > MAX_CREATE_DEPOSITS_PER_REQUEST {
+            return Err(Error::HttpRequest(
+                StatusCode::BAD_REQUEST,
+                format!(
+                    "at most {MAX_CREATE_DEPOSITS_PER_REQUEST} deposits can be created at once"
+                ),
+            ));
         }
-        // Make table entry.
-        let deposit_entry: DepositEntry = DepositEntry {
-            key: DepositEntryKey {
-                bitcoin_txid: body.bitcoin_txid,
-                bitcoin_tx_output_index: body.bitcoin_tx_output_index,
-            },
-            recipient: hex::encode(deposit_info.recipient.serialize_to_vec()),
-            parameters: DepositParametersEntry {
-                max_fee: deposit_info.max_fee,
-                lock_time: deposit_info.lock_time.to_consensus_u32(),
-            },
-            history: vec![DepositEvent {
-                status: DepositStatusEntry::Pending,
-                message: "Just received deposit".to_string(),
-                stacks_block_hash: stacks_block_hash.clone(),
-                stacks_block_height,
-            }],
-            status: DepositStatus::Pending,
-            last_update_block_hash: stacks_block_hash,
-            last_update_height: stacks_block_height,
-            amount: deposit_info.amount,
-            reclaim_script: body.reclaim_script,
-            deposit_script: body.deposit_script,
-            reclaim_pubkeys_hash,
-            ..Default::default()
-        };
-        // Validate deposit entry.
-        deposit_entry.validate()?;
-        // Add entry to the table.
-        accessors::add_deposit_entry(&context, &deposit_entry).await?;
-        // Respond.
-        let response: Deposit = deposit_entry.try_into()?;
-        Ok(with_status(json(&response), StatusCode::CREATED))
+
+        let api_state = accessors::get_api_state(&context).await?;
+        api_state.error_if_reorganizing()?;
+
+        let mut deposits = Vec::with_capacity(body.deposits.len());
+        for deposit in body.deposits {
+            let bitcoin_txid = deposit.bitcoin_txid.clone();
+            let bitcoin_tx_output_index = deposit.bitcoin_tx_output_index;
+
+            let result = match add_deposit(&context, &api_state, deposit).await {
+                Ok((deposit, status)) => DepositWithStatus {
+                    deposit: Some(deposit),
+                    error: None,
+                    status: status.as_u16(),
+                },
+                Err(error) => {
+                    tracing::warn!(
+                        %bitcoin_txid,
+                        bitcoin_tx_output_index,
+                        %error,
+                        "failed to create deposit"
+                    );
+                    DepositWithStatus {
+                        deposit: None,
+                        status: error.status_code().as_u16(),
+                        error: Some(error.into_production_error().to_string()),
+                    }
+                }
+            };
+            deposits.push(result);
+        }
+
+        let response = CreateDepositsResponse { deposits };
+        Ok(with_status(json(&response), StatusCode::OK))
     }
     // Handle and respond.
     handler(context, body)
@@ -419,7 +681,7 @@ pub async fn create_deposit(
         (status = 405, description = "Method not allowed", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
-    security(("ApiGatewayKey" = []))
+    security(("ApiGatewayKey" = [], "EmilyApiKey" = []))
 )]
 #[instrument(skip(context))]
 pub async fn update_deposits_signer(
@@ -463,7 +725,7 @@ pub async fn update_deposits_signer(
         (status = 405, description = "Method not allowed", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
-    security(("ApiGatewayKey" = []))
+    security(("ApiGatewayKey" = [], "EmilyApiKey" = []))
 )]
 #[instrument(skip(context))]
 pub async fn update_deposits_sidecar(
@@ -501,6 +763,11 @@ async fn update_deposits(
     // Validate request.
     let validated_request: ValidatedUpdateDepositsRequest =
         body.into_validated_update_request(api_state.chaintip().into());
+    let actor = if is_from_trusted_source {
+        StatusUpdateActor::Sidecar
+    } else {
+        StatusUpdateActor::Signer
+    };
 
     // Create aggregator.
     let mut updated_deposits: Vec<(usize, DepositWithStatus)> =
@@ -521,7 +788,8 @@ async fn update_deposits(
             ));
             continue;
         }
-        let update = update.unwrap();
+        let mut update = update.unwrap();
+        update.event.actor = Some(actor);
 
         let bitcoin_txid = update.key.bitcoin_txid.clone();
         let bitcoin_tx_output_index = update.key.bitcoin_tx_output_index;
@@ -621,6 +889,99 @@ async fn update_deposits(
     Ok(with_status(json(&response), StatusCode::OK))
 }
 
+/// The number of recently confirmed deposits that are used to estimate how
+/// often sweeps happen and how much they cost.
+const DEPOSIT_ETA_SAMPLE_SIZE: u16 = 50;
+
+/// The expected number of seconds between bitcoin blocks.
+const BITCOIN_BLOCK_TIME_SECONDS: u64 = 600;
+
+/// Estimate how long it will take for the given deposit to be swept.
+///
+/// The `deposits_ahead` are the pending or accepted deposits that are
+/// expected to be swept before this one. The number of deposits per
+/// sweep, the number of blocks between sweeps and the typical fee are
+/// taken from the fulfillments of recently confirmed deposits.
+fn estimate_deposit_eta(
+    deposit: &DepositEntry,
+    deposits_ahead: u64,
+    recent_fulfillments: &[Fulfillment],
+    bitcoin_tip_height: Option<u64>,
+) -> GetDepositEtaResponse {
+    let mut fees: Vec<u64> = recent_fulfillments.iter().map(|f| f.btc_fee).collect();
+    fees.sort_unstable();
+    let recent_median_fee = fees.get(fees.len() / 2).copied();
+    let fee_sufficient = recent_median_fee.is_none_or(|fee| deposit.parameters.max_fee >= fee);
+
+    let mut sweep_heights: Vec<u64> = recent_fulfillments
+        .iter()
+        .map(|f| f.bitcoin_block_height)
+        .collect();
+    sweep_heights.sort_unstable();
+    sweep_heights.dedup();
+
+    let sweep_interval_blocks = match (sweep_heights.first(), sweep_heights.last()) {
+        (Some(first), Some(last)) if sweep_heights.len() > 1 => {
+            let gaps = sweep_heights.len() as u64 - 1;
+            Some((last - first).div_ceil(gaps).max(1))
+        }
+        _ => None,
+    };
+
+    let mut response = GetDepositEtaResponse {
+        bitcoin_txid: deposit.key.bitcoin_txid.clone(),
+        bitcoin_tx_output_index: deposit.key.bitcoin_tx_output_index,
+        status: deposit.status.clone(),
+        deposits_ahead: 0,
+        sweeps_remaining: 0,
+        sweep_interval_blocks,
+        recent_median_fee,
+        fee_sufficient,
+        estimated_bitcoin_blocks: None,
+        estimated_seconds: None,
+    };
+
+    match deposit.status {
+        DepositStatus::Pending | DepositStatus::Accepted => {}
+        DepositStatus::Confirmed => {
+            response.estimated_bitcoin_blocks = Some(0);
+            response.estimated_seconds = Some(0);
+            return response;
+        }
+        DepositStatus::Failed | DepositStatus::Rbf => return response,
+    }
+
+    response.deposits_ahead = deposits_ahead;
+
+    if sweep_heights.is_empty() {
+        return response;
+    }
+    let deposits_per_sweep = (recent_fulfillments.len() as u64)
+        .div_ceil(sweep_heights.len() as u64)
+        .max(1);
+    response.sweeps_remaining = response.deposits_ahead / deposits_per_sweep + 1;
+
+    let Some(interval) = sweep_interval_blocks else {
+        return response;
+    };
+    if !fee_sufficient {
+        return response;
+    }
+
+    // The next sweep is expected one interval after the last one, but no
+    // sooner than the next bitcoin block.
+    let last_sweep_height = sweep_heights.last().copied().unwrap_or_default();
+    let blocks_until_next_sweep = bitcoin_tip_height
+        .map(|tip| (last_sweep_height + interval).saturating_sub(tip))
+        .unwrap_or(interval)
+        .max(1);
+
+    let blocks = blocks_until_next_sweep + (response.sweeps_remaining - 1) * interval;
+    response.estimated_bitcoin_blocks = Some(blocks);
+    response.estimated_seconds = Some(blocks * BITCOIN_BLOCK_TIME_SECONDS);
+    response
+}
+
 const OP_DROP: u8 = opcodes::OP_DROP.to_u8();
 const OP_CHECKSIG: u8 = opcodes::OP_CHECKSIG.to_u8();
 const OP_CHECKSIGADD: u8 = opcodes::OP_CHECKSIGADD.to_u8();
@@ -775,6 +1136,88 @@ mod tests {
         assert_eq!(expected_hash, pubkey_from_script);
     }
 
+    fn eta_deposit(status: DepositStatus, last_update_height: u64, max_fee: u64) -> DepositEntry {
+        DepositEntry {
+            key: DepositEntryKey {
+                bitcoin_txid: "target".to_string(),
+                bitcoin_tx_output_index: 0,
+            },
+            status,
+            last_update_height,
+            parameters: DepositParametersEntry { max_fee, lock_time: 10 },
+            ..Default::default()
+        }
+    }
+
+    fn swept_at(bitcoin_block_height: u64, btc_fee: u64) -> Fulfillment {
+        Fulfillment {
+            bitcoin_block_height,
+            btc_fee,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn estimate_deposit_eta_counts_sweeps_ahead() {
+        let deposit = eta_deposit(DepositStatus::Pending, 100, 10_000);
+        // Three sweeps, two blocks apart, with two deposits in each.
+        let recent = [
+            swept_at(10, 500),
+            swept_at(10, 600),
+            swept_at(12, 700),
+            swept_at(12, 800),
+            swept_at(14, 900),
+            swept_at(14, 1_000),
+        ];
+
+        let eta = estimate_deposit_eta(&deposit, 3, &recent, Some(15));
+        assert_eq!(eta.deposits_ahead, 3);
+        assert_eq!(eta.sweeps_remaining, 2);
+        assert_eq!(eta.sweep_interval_blocks, Some(2));
+        assert_eq!(eta.recent_median_fee, Some(800));
+        assert!(eta.fee_sufficient);
+        // The next sweep is expected at block 16, one block from now, and
+        // ours is expected in the sweep after that.
+        assert_eq!(eta.estimated_bitcoin_blocks, Some(3));
+        assert_eq!(eta.estimated_seconds, Some(3 * BITCOIN_BLOCK_TIME_SECONDS));
+    }
+
+    #[test]
+    fn estimate_deposit_eta_without_enough_data() {
+        let deposit = eta_deposit(DepositStatus::Accepted, 100, 10_000);
+        let eta = estimate_deposit_eta(&deposit, 0, &[], None);
+        assert_eq!(eta.deposits_ahead, 0);
+        assert_eq!(eta.sweep_interval_blocks, None);
+        assert_eq!(eta.estimated_bitcoin_blocks, None);
+
+        // A single sweep does not tell us how often sweeps happen.
+        let eta = estimate_deposit_eta(&deposit, 0, &[swept_at(10, 500)], None);
+        assert_eq!(eta.sweeps_remaining, 1);
+        assert_eq!(eta.estimated_bitcoin_blocks, None);
+    }
+
+    #[test]
+    fn estimate_deposit_eta_low_fee_has_no_estimate() {
+        let deposit = eta_deposit(DepositStatus::Pending, 100, 100);
+        let recent = [swept_at(10, 500), swept_at(12, 500)];
+        let eta = estimate_deposit_eta(&deposit, 0, &recent, Some(12));
+        assert!(!eta.fee_sufficient);
+        assert_eq!(eta.sweep_interval_blocks, Some(2));
+        assert_eq!(eta.estimated_bitcoin_blocks, None);
+        assert_eq!(eta.estimated_seconds, None);
+    }
+
+    #[test_case(DepositStatus::Confirmed, Some(0); "confirmed")]
+    #[test_case(DepositStatus::Failed, None; "failed")]
+    #[test_case(DepositStatus::Rbf, None; "rbf")]
+    fn estimate_deposit_eta_for_finished_deposits(status: DepositStatus, expected: Option<u64>) {
+        let deposit = eta_deposit(status, 100, 10_000);
+        let recent = [swept_at(10, 500), swept_at(12, 500)];
+        let eta = estimate_deposit_eta(&deposit, 0, &recent, Some(12));
+        assert_eq!(eta.sweeps_remaining, 0);
+        assert_eq!(eta.estimated_bitcoin_blocks, expected);
+    }
+
     #[test_case(""; "empty")]
     #[test_case("-"; "empty-dash")]
     #[test_case("invalid"; "invalid-pubkey")]
@@ -2,6 +2,7 @@
 
 use warp::reply::Reply as _;
 
+use crate::api::version::{API_VERSION, MIN_SUPPORTED_API_VERSION};
 use crate::{api::models::health::responses::HealthData, context::EmilyContext};
 
 /// Get health handler.
@@ -22,6 +23,8 @@ pub async fn get_health(context: EmilyContext) -> impl warp::reply::Reply {
     // Handle and respond.
     warp::reply::json(&HealthData {
         version: context.settings.version.clone(),
+        api_version: API_VERSION,
+        min_api_version: MIN_SUPPORTED_API_VERSION,
     })
     .into_response()
 }
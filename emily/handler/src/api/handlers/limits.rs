@@ -2,7 +2,7 @@
 use std::time::SystemTime;
 
 use crate::{
-    api::models::limits::{AccountLimits, Limits},
+    api::models::limits::{AccountLimits, Limits, SetAvailableToMintRequestBody},
     common::error::Error,
     context::EmilyContext,
     database::{
@@ -25,6 +25,7 @@ use warp::reply::{Reply, json, with_status};
         (status = 405, description = "Method not allowed", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
+    security(("EmilyApiKey" = []))
 )]
 #[instrument(skip(context))]
 pub async fn get_limits(context: EmilyContext) -> impl warp::reply::Reply {
@@ -42,6 +43,10 @@ pub async fn get_limits(context: EmilyContext) -> impl warp::reply::Reply {
 /// Set limits handler.
 /// Note, that `available_to_withdraw` is not settable, but is calculated based on the other fields.
 /// Value of `available_to_withdraw` passed to this endpoint will be ignored.
+/// The same holds for `blocks_until_withdrawal_headroom`.
+/// The same holds for `deposit_confirmation_policy`, which comes from the
+/// `DEPOSIT_CONFIRMATION_POLICY` environment variable, and for
+/// `available_to_mint`, which is reported by the signers.
 #[utoipa::path(
     post,
     operation_id = "setLimits",
@@ -55,7 +60,7 @@ pub async fn get_limits(context: EmilyContext) -> impl warp::reply::Reply {
         (status = 405, description = "Method not allowed", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
-    security(("ApiGatewayKey" = []))
+    security(("ApiGatewayKey" = [], "EmilyApiKey" = []))
 )]
 #[instrument(skip(context))]
 pub async fn set_limits(limits: Limits, context: EmilyContext) -> impl warp::reply::Reply {
@@ -107,6 +112,46 @@ pub async fn set_limits(limits: Limits, context: EmilyContext) -> impl warp::rep
         .map_or_else(Reply::into_response, Reply::into_response)
 }
 
+/// Set available to mint handler.
+/// Used by the signers to report how much sBTC can still be minted under
+/// the peg cap.
+#[utoipa::path(
+    put,
+    operation_id = "setAvailableToMint",
+    path = "/limits",
+    tag = "limits",
+    request_body = SetAvailableToMintRequestBody,
+    responses(
+        (status = 200, description = "Available to mint updated successfully", body = Limits),
+        (status = 400, description = "Invalid request body", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 405, description = "Method not allowed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(("ApiGatewayKey" = [], "EmilyApiKey" = []))
+)]
+#[instrument(skip(context))]
+pub async fn set_available_to_mint(
+    body: SetAvailableToMintRequestBody,
+    context: EmilyContext,
+) -> impl warp::reply::Reply {
+    // Internal handler so `?` can be used correctly while still returning a reply.
+    async fn handler(
+        context: EmilyContext,
+        body: SetAvailableToMintRequestBody,
+    ) -> Result<impl warp::reply::Reply, Error> {
+        let entry = LimitEntry::from_available_to_mint(body.available_to_mint, SystemTime::now());
+        accessors::set_limit_for_account(&context, &entry).await?;
+        // Get the limits from the database confirming that the update was done.
+        let global_limits = accessors::get_limits(&context).await?;
+        Ok(with_status(json(&global_limits), StatusCode::OK))
+    }
+    // Handle and respond.
+    handler(context, body)
+        .await
+        .map_or_else(Reply::into_response, Reply::into_response)
+}
+
 /// Get limits for account handler.
 #[utoipa::path(
     get,
@@ -123,6 +168,7 @@ pub async fn set_limits(limits: Limits, context: EmilyContext) -> impl warp::rep
         (status = 405, description = "Method not allowed", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
+    security(("EmilyApiKey" = []))
 )]
 #[instrument(skip(context))]
 pub async fn get_limits_for_account(
@@ -164,7 +210,7 @@ pub async fn get_limits_for_account(
         (status = 405, description = "Method not allowed", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
-    security(("ApiGatewayKey" = []))
+    security(("ApiGatewayKey" = [], "EmilyApiKey" = []))
 )]
 #[instrument(skip(context))]
 pub async fn set_limits_for_account(
@@ -1,11 +1,13 @@
 //! Handlers for the emily API
 
-use crate::common::error::ErrorResponse;
+use crate::common::error::{Error, ErrorResponse};
 
 use std::convert::Infallible;
 use tracing::error;
 use warp::{Rejection, Reply, http::StatusCode};
 
+/// Api key handlers.
+pub mod api_key;
 /// Chainstate handlers.
 pub mod chainstate;
 /// Deposit handlers.
@@ -18,6 +20,8 @@ pub mod internal;
 pub mod limits;
 /// New block handlers.
 pub mod new_block;
+/// Retention handlers.
+pub mod retention;
 /// Testing handlers.
 #[cfg(feature = "testing")]
 pub mod testing;
@@ -42,6 +46,20 @@ pub async fn handle_rejection(err: Rejection) -> Result<impl Reply, Infallible>
         return Ok(warp::reply::with_status(json, StatusCode::BAD_REQUEST));
     }
 
+    // Errors raised by filters, such as the api key authorization filter.
+    // Server errors are not described to the caller, since they may
+    // contain sensitive information.
+    if let Some(e) = err.find::<Error>() {
+        let status = e.status_code();
+        let message = if status.is_server_error() {
+            Error::InternalServer.to_string()
+        } else {
+            e.to_string()
+        };
+        let json = warp::reply::json(&ErrorResponse { message });
+        return Ok(warp::reply::with_status(json, status));
+    }
+
     if let Some(e) = err.find::<warp::reject::MethodNotAllowed>() {
         let json = warp::reply::json(&ErrorResponse {
             message: format!("Method Not Allowed: {e:?}"),
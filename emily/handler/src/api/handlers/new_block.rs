@@ -10,6 +10,7 @@ use sbtc::events::{
     CompletedDepositEvent, RegistryEvent, TxInfo, WithdrawalAcceptEvent, WithdrawalCreateEvent,
     WithdrawalRejectEvent,
 };
+use sbtc::ids::DepositId;
 
 use crate::api::handlers::chainstate::set_chainstate;
 use crate::api::handlers::deposit::update_deposits_sidecar;
@@ -58,7 +59,7 @@ struct StacksBlock {
         (status = 405, description = "Method not allowed", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
-    security(("ApiGatewayKey" = []))
+    security(("ApiGatewayKey" = [], "EmilyApiKey" = []))
 )]
 #[instrument(skip_all, name = "new-block")]
 pub async fn new_block(
@@ -249,14 +250,8 @@ async fn handle_completed_deposit(
 
     // It should be impossible for a deposit to be completed without a corresponding
     // deposit request, but we handle this case just in case.
-    let deposit = accessors::get_deposit_entry(
-        context,
-        &DepositEntryKey {
-            bitcoin_txid: event.outpoint.txid.to_string(),
-            bitcoin_tx_output_index: event.outpoint.vout,
-        },
-    )
-    .await?;
+    let key = DepositEntryKey::from(DepositId::from(event.outpoint));
+    let deposit = accessors::get_deposit_entry(context, &key).await?;
 
     // The fee paid by the user is the difference between the deposit request amount
     // and the amount minted in the completed deposit event.
@@ -274,9 +269,13 @@ async fn handle_completed_deposit(
             bitcoin_txid: event.sweep_txid.to_string(),
             btc_fee,
             stacks_txid: hex::encode(event.txid.0),
+            sweep_tx_proof: None,
+            assessed_fee: None,
         }),
         status_message: format!("Included in block {}", event.block_id.to_hex()),
         replaced_by_tx: None,
+        deposit_block_hash: None,
+        deposit_tx_proof: None,
     })
 }
 
@@ -304,6 +303,8 @@ fn handle_withdrawal_accept(event: WithdrawalAcceptEvent) -> WithdrawalUpdate {
             bitcoin_txid: event.outpoint.txid.to_string(),
             btc_fee: event.fee,
             stacks_txid: hex::encode(event.txid.0),
+            sweep_tx_proof: None,
+            assessed_fee: None,
         }),
         expected_fulfillment_info: None,
         status_message: format!("Included in block {}", event.block_id.to_hex()),
@@ -455,6 +456,8 @@ mod test {
                 bitcoin_txid: event.sweep_txid.to_string(),
                 btc_fee: event.fee,
                 stacks_txid: event.txid.to_string(),
+                sweep_tx_proof: None,
+                assessed_fee: None,
             }),
             status_message: format!("Included in block {}", event.block_id.to_hex()),
             expected_fulfillment_info: None,
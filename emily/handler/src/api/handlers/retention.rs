@@ -0,0 +1,128 @@
+//! Handlers for the retention endpoint.
+//!
+//! Completed deposits and withdrawals are moved out of the tables that
+//! are queried for status polling once they are older than the configured
+//! `archive_after_days`, and deleted from the archive tables once they are
+//! older than `purge_after_days`. The age of a request is measured in
+//! bitcoin blocks from the bitcoin block that anchors the stacks block of
+//! its last update.
+
+use tracing::instrument;
+use warp::http::StatusCode;
+use warp::reply::{Reply, json, with_status};
+
+use crate::api::models::retention::RunRetentionResponse;
+use crate::common::error::Error;
+use crate::context::EmilyContext;
+use crate::database::accessors;
+
+/// The approximate number of bitcoin blocks mined per day.
+const BITCOIN_BLOCKS_PER_DAY: u64 = 144;
+
+/// The maximum number of requests that a single run archives or purges
+/// from each table, so that a run finishes well within the lambda timeout.
+pub const MAX_RETENTION_ENTRIES_PER_RUN: u16 = 50;
+
+/// Run retention handler.
+#[utoipa::path(
+    post,
+    operation_id = "runRetention",
+    path = "/retention",
+    tag = "retention",
+    responses(
+        (status = 200, description = "Retention policy applied successfully", body = RunRetentionResponse),
+        (status = 400, description = "Invalid request body", body = ErrorResponse),
+        (status = 405, description = "Method not allowed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(("ApiGatewayKey" = [], "EmilyApiKey" = []))
+)]
+#[instrument(skip(context))]
+pub async fn run_retention(context: EmilyContext) -> impl warp::reply::Reply {
+    // Internal handler so `?` can be used correctly while still returning a reply.
+    async fn handler(context: EmilyContext) -> Result<impl warp::reply::Reply, Error> {
+        let api_state = accessors::get_api_state(&context).await?;
+        // Requests that are being reorganized must stay where the reorg
+        // expects to find them.
+        api_state.error_if_reorganizing()?;
+        let bitcoin_tip = api_state.chaintip().bitcoin_height;
+
+        let mut response = RunRetentionResponse {
+            complete: true,
+            ..Default::default()
+        };
+
+        let archive_before =
+            stacks_height_cutoff(&context, bitcoin_tip, context.settings.archive_after_days)
+                .await?;
+        if let Some(before) = archive_before {
+            let (deposits, more_deposits) =
+                accessors::archive_deposit_entries(&context, before, MAX_RETENTION_ENTRIES_PER_RUN)
+                    .await?;
+            let (withdrawals, more_withdrawals) = accessors::archive_withdrawal_entries(
+                &context,
+                before,
+                MAX_RETENTION_ENTRIES_PER_RUN,
+            )
+            .await?;
+            response.archived_deposits = deposits as u64;
+            response.archived_withdrawals = withdrawals as u64;
+            response.complete &= !more_deposits && !more_withdrawals;
+        }
+
+        let purge_before =
+            stacks_height_cutoff(&context, bitcoin_tip, context.settings.purge_after_days).await?;
+        if let Some(before) = purge_before {
+            let (deposits, more_deposits) = accessors::purge_archived_deposit_entries(
+                &context,
+                before,
+                MAX_RETENTION_ENTRIES_PER_RUN,
+            )
+            .await?;
+            let (withdrawals, more_withdrawals) = accessors::purge_archived_withdrawal_entries(
+                &context,
+                before,
+                MAX_RETENTION_ENTRIES_PER_RUN,
+            )
+            .await?;
+            response.purged_deposits = deposits as u64;
+            response.purged_withdrawals = withdrawals as u64;
+            response.complete &= !more_deposits && !more_withdrawals;
+        }
+
+        tracing::info!(?response, "applied the retention policy");
+        Ok(with_status(json(&response), StatusCode::OK))
+    }
+    // Handle and respond.
+    handler(context)
+        .await
+        .map_or_else(Reply::into_response, Reply::into_response)
+}
+
+/// Returns the stacks block height below which every request was last
+/// updated at least the given number of days ago, or `None` if no request
+/// can be that old yet or the retention step is disabled.
+async fn stacks_height_cutoff(
+    context: &EmilyContext,
+    bitcoin_tip: Option<u64>,
+    days: Option<u64>,
+) -> Result<Option<u64>, Error> {
+    let (Some(bitcoin_tip), Some(days)) = (bitcoin_tip, days) else {
+        return Ok(None);
+    };
+    let Some(bitcoin_height) = bitcoin_tip.checked_sub(days.saturating_mul(BITCOIN_BLOCKS_PER_DAY))
+    else {
+        return Ok(None);
+    };
+    match accessors::get_oldest_stacks_block_at_or_below(
+        context,
+        bitcoin_height,
+        BITCOIN_BLOCKS_PER_DAY,
+    )
+    .await
+    {
+        Ok(stacks_height) => Ok(Some(stacks_height)),
+        Err(Error::NotFound) => Ok(None),
+        Err(error) => Err(error),
+    }
+}
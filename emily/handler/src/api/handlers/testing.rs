@@ -21,7 +21,7 @@ use crate::database::accessors;
         (status = 405, description = "Method not allowed"),
         (status = 500, description = "Internal server error")
     ),
-    security(("ApiGatewayKey" = []))
+    security(("ApiGatewayKey" = [], "EmilyApiKey" = []))
 )]
 #[instrument(skip(context))]
 pub async fn wipe_databases(context: EmilyContext) -> impl warp::reply::Reply {
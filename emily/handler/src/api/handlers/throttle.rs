@@ -28,7 +28,7 @@ use warp::reply::{Reply, json, with_status};
         (status = 405, description = "Method not allowed", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
-    security(("ApiGatewayKey" = []))
+    security(("ApiGatewayKey" = [], "EmilyApiKey" = []))
 )]
 #[instrument(skip(context))]
 pub async fn get_throttle_key(hash: String, context: EmilyContext) -> impl warp::reply::Reply {
@@ -103,6 +103,7 @@ pub async fn calculate_throttle_mode_limits(
         (status = 404, description = "Throttle key not found", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
+    security(("EmilyApiKey" = []))
 )]
 #[instrument(
     skip(request, context),
@@ -169,7 +170,7 @@ pub async fn start_throttle(
         (status = 409, description = "Key already exists", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
-    security(("ApiGatewayKey" = []))
+    security(("ApiGatewayKey" = [], "EmilyApiKey" = []))
 )]
 #[instrument(
     skip(key, context),
@@ -229,7 +230,7 @@ pub async fn add_throttle_key(key: ThrottleKey, context: EmilyContext) -> impl w
         (status = 405, description = "Method not allowed", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
-    security(("ApiGatewayKey" = []))
+    security(("ApiGatewayKey" = [], "EmilyApiKey" = []))
 )]
 #[instrument(skip(context))]
 pub async fn deactivate_throttle_key(
@@ -264,7 +265,7 @@ pub async fn deactivate_throttle_key(
         (status = 405, description = "Method not allowed", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
-    security(("ApiGatewayKey" = []))
+    security(("ApiGatewayKey" = [], "EmilyApiKey" = []))
 )]
 #[instrument(skip(context))]
 pub async fn activate_throttle_key(hash: String, context: EmilyContext) -> impl warp::reply::Reply {
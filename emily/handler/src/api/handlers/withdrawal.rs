@@ -2,23 +2,23 @@
 use tracing::{debug, instrument};
 use warp::reply::{Reply, json, with_status};
 
-use crate::api::models::common::WithdrawalStatus;
 use crate::api::models::common::requests::BasicPaginationQuery;
+use crate::api::models::common::{StatusUpdateActor, WithdrawalStatus};
 use crate::api::models::withdrawal::responses::WithdrawalWithStatus;
 use crate::api::models::withdrawal::{ExpectedFulfillmentInfo, Withdrawal, WithdrawalInfo};
 use crate::api::models::withdrawal::{
     requests::{CreateWithdrawalRequestBody, GetWithdrawalsQuery, UpdateWithdrawalsRequestBody},
-    responses::{GetWithdrawalsResponse, UpdateWithdrawalsResponse},
+    responses::{GetWithdrawalHistoryResponse, GetWithdrawalsResponse, UpdateWithdrawalsResponse},
 };
 use crate::common::error::Error;
 use crate::context::EmilyContext;
 use crate::database::accessors;
-use crate::database::entries::WithdrawalStatusEntry;
 use crate::database::entries::chainstate::ApiStateEntry;
 use crate::database::entries::withdrawal::{
     ValidatedUpdateWithdrawalRequest, WithdrawalEntry, WithdrawalEntryKey, WithdrawalEvent,
     WithdrawalParametersEntry,
 };
+use crate::database::entries::{WithdrawalStatusEntry, event_timestamp};
 use warp::http::StatusCode;
 
 /// Get withdrawal handler.
@@ -36,7 +36,8 @@ use warp::http::StatusCode;
         (status = 404, description = "Address not found", body = ErrorResponse),
         (status = 405, description = "Method not allowed", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
-    )
+    ),
+    security(("EmilyApiKey" = []))
 )]
 #[instrument(skip(context))]
 pub async fn get_withdrawal(request_id: u64, context: EmilyContext) -> impl warp::reply::Reply {
@@ -46,9 +47,10 @@ pub async fn get_withdrawal(request_id: u64, context: EmilyContext) -> impl warp
         request_id: u64,
     ) -> Result<impl warp::reply::Reply, Error> {
         // Get withdrawal.
-        let withdrawal: Withdrawal = accessors::get_withdrawal_entry(&context, &request_id)
-            .await?
-            .try_into()?;
+        let withdrawal: Withdrawal =
+            accessors::get_withdrawal_entry_or_archived(&context, &request_id)
+                .await?
+                .try_into()?;
 
         // Respond.
         Ok(with_status(json(&withdrawal), StatusCode::OK))
@@ -59,6 +61,49 @@ pub async fn get_withdrawal(request_id: u64, context: EmilyContext) -> impl warp
         .map_or_else(Reply::into_response, Reply::into_response)
 }
 
+/// Get withdrawal history handler.
+#[utoipa::path(
+    get,
+    operation_id = "getWithdrawalHistory",
+    path = "/withdrawal/{id}/history",
+    params(
+        ("id" = u64, Path, description = "id associated with the Withdrawal"),
+    ),
+    tag = "withdrawal",
+    responses(
+        (status = 200, description = "Withdrawal history retrieved successfully", body = GetWithdrawalHistoryResponse),
+        (status = 400, description = "Invalid request body", body = ErrorResponse),
+        (status = 404, description = "Address not found", body = ErrorResponse),
+        (status = 405, description = "Method not allowed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(("EmilyApiKey" = []))
+)]
+#[instrument(skip(context))]
+pub async fn get_withdrawal_history(
+    request_id: u64,
+    context: EmilyContext,
+) -> impl warp::reply::Reply {
+    // Internal handler so `?` can be used correctly while still returning a reply.
+    async fn handler(
+        context: EmilyContext,
+        request_id: u64,
+    ) -> Result<impl warp::reply::Reply, Error> {
+        // Get the withdrawal along with its whole history.
+        let response: GetWithdrawalHistoryResponse =
+            accessors::get_withdrawal_entry_or_archived(&context, &request_id)
+                .await?
+                .try_into()?;
+
+        // Respond.
+        Ok(with_status(json(&response), StatusCode::OK))
+    }
+    // Handle and respond.
+    handler(context, request_id)
+        .await
+        .map_or_else(Reply::into_response, Reply::into_response)
+}
+
 /// Get withdrawals handler.
 #[utoipa::path(
     get,
@@ -67,7 +112,8 @@ pub async fn get_withdrawal(request_id: u64, context: EmilyContext) -> impl warp
     params(
         ("status" = WithdrawalStatus, Query, description = "the status to search by when getting all withdrawals."),
         ("nextToken" = Option<String>, Query, description = "the next token value from the previous return of this api call."),
-        ("pageSize" = Option<u16>, Query, description = "the maximum number of items in the response list.")
+        ("pageSize" = Option<u16>, Query, description = "the maximum number of items in the response list."),
+        ("includeArchived" = Option<bool>, Query, description = "whether to include archived withdrawals once the other withdrawals are exhausted.")
     ),
     tag = "withdrawal",
     responses(
@@ -76,7 +122,8 @@ pub async fn get_withdrawal(request_id: u64, context: EmilyContext) -> impl warp
         (status = 404, description = "Address not found", body = ErrorResponse),
         (status = 405, description = "Method not allowed", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
-    )
+    ),
+    security(("EmilyApiKey" = []))
 )]
 #[instrument(skip(context))]
 pub async fn get_withdrawals(
@@ -94,6 +141,7 @@ pub async fn get_withdrawals(
             &query.status,
             query.next_token,
             query.page_size,
+            query.include_archived.unwrap_or_default(),
         )
         .await?;
         // Convert data into resource types.
@@ -127,7 +175,8 @@ pub async fn get_withdrawals(
         (status = 404, description = "Address not found", body = ErrorResponse),
         (status = 405, description = "Method not allowed", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
-    )
+    ),
+    security(("EmilyApiKey" = []))
 )]
 #[instrument(skip(context))]
 pub async fn get_withdrawals_for_recipient(
@@ -180,7 +229,8 @@ pub async fn get_withdrawals_for_recipient(
         (status = 404, description = "Address not found", body = ErrorResponse),
         (status = 405, description = "Method not allowed", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
-    )
+    ),
+    security(("EmilyApiKey" = []))
 )]
 #[instrument(skip(context))]
 pub async fn get_withdrawals_for_sender(
@@ -230,7 +280,7 @@ pub async fn get_withdrawals_for_sender(
         (status = 405, description = "Method not allowed", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
-    security(("ApiGatewayKey" = []))
+    security(("ApiGatewayKey" = [], "EmilyApiKey" = []))
 )]
 #[instrument(skip(context))]
 pub async fn create_withdrawal(
@@ -288,6 +338,8 @@ pub async fn create_withdrawal(
                 message: "Just received withdrawal".to_string(),
                 stacks_block_hash: stacks_block_hash.clone(),
                 stacks_block_height,
+                updated_at: Some(event_timestamp()),
+                actor: Some(StatusUpdateActor::Creator),
                 expected_fulfillment_info: ExpectedFulfillmentInfo {
                     bitcoin_block_height,
                     bitcoin_txid: None,
@@ -328,7 +380,7 @@ pub async fn create_withdrawal(
         (status = 405, description = "Method not allowed", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
-    security(("ApiGatewayKey" = []))
+    security(("ApiGatewayKey" = [], "EmilyApiKey" = []))
 )]
 #[instrument(skip(context))]
 pub async fn update_withdrawals_signer(
@@ -372,7 +424,7 @@ pub async fn update_withdrawals_signer(
         (status = 405, description = "Method not allowed", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
-    security(("ApiGatewayKey" = []))
+    security(("ApiGatewayKey" = [], "EmilyApiKey" = []))
 )]
 #[instrument(skip(context))]
 pub async fn update_withdrawals_sidecar(
@@ -410,6 +462,11 @@ async fn update_withdrawals(
     // Validate request.
     let validated_request: ValidatedUpdateWithdrawalRequest =
         body.into_validated_update_request(api_state.chaintip().into());
+    let actor = if is_from_trusted_source {
+        StatusUpdateActor::Sidecar
+    } else {
+        StatusUpdateActor::Signer
+    };
 
     // Create aggregator.
     let mut updated_withdrawals: Vec<(usize, WithdrawalWithStatus)> =
@@ -430,7 +487,8 @@ async fn update_withdrawals(
             ));
             continue;
         }
-        let update = update.unwrap();
+        let mut update = update.unwrap();
+        update.event.actor = Some(actor);
         let request_id = update.request_id;
         debug!(request_id, "updating withdrawal");
 
@@ -1,8 +1,12 @@
 //! This module contains the route configurations and request handlers for the Emily API.
 
+/// Api key authentication and authorization.
+pub mod auth;
 /// Request handlers.
 pub mod handlers;
 /// Request and response data structures.
 pub mod models;
 /// Route definitions.
 pub mod routes;
+/// Api version negotiation.
+pub mod version;
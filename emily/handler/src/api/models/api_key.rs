@@ -0,0 +1,73 @@
+//! Request and response structures for api key calls.
+
+use serde::{Deserialize, Serialize};
+use utoipa::{ToResponse, ToSchema};
+
+/// The operations that an api key may be allowed to perform.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    ToSchema,
+    ToResponse,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum ApiKeyScope {
+    /// Allows creating new deposit requests.
+    DepositCreate,
+    /// Allows reading the status of deposits, withdrawals and the rest of
+    /// the API state.
+    StatusRead,
+    /// Allows updating deposits, withdrawals, chainstates and limits, and
+    /// managing api keys.
+    AdminUpdate,
+}
+
+/// Request to create a new api key.
+#[derive(Clone, Default, Debug, Eq, PartialEq, Serialize, Deserialize, ToSchema, ToResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateApiKeyRequest {
+    /// The name of the key. It must be unique and must not contain a `:`.
+    pub name: String,
+    /// The secret associated with this key.
+    pub secret: String,
+    /// The operations that this key may perform.
+    pub scopes: Vec<ApiKeyScope>,
+    /// The maximum number of requests per minute that may be made with
+    /// this key. If none then the key is not rate limited.
+    #[serde(default)]
+    pub rate_limit_per_minute: Option<u32>,
+}
+
+/// Request to rotate the secret of an existing api key.
+#[derive(Clone, Default, Debug, Eq, PartialEq, Serialize, Deserialize, ToSchema, ToResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct RotateApiKeyRequest {
+    /// The new secret associated with this key. The old secret stops
+    /// working as soon as the key is rotated.
+    pub secret: String,
+}
+
+/// Information about an api key. This never includes the secret.
+#[derive(Clone, Default, Debug, Eq, PartialEq, Serialize, Deserialize, ToSchema, ToResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeyInfo {
+    /// The name of the key.
+    pub name: String,
+    /// The operations that this key may perform.
+    pub scopes: Vec<ApiKeyScope>,
+    /// The maximum number of requests per minute that may be made with
+    /// this key. If none then the key is not rate limited.
+    pub rate_limit_per_minute: Option<u32>,
+    /// Whether the key may be used.
+    pub is_active: bool,
+    /// The time of the last change to the key, in seconds from UNIX epoch.
+    pub updated_at: u64,
+}
@@ -91,6 +91,34 @@ pub enum WithdrawalStatus {
     Failed,
 }
 
+/// The party that caused a status update of an sBTC operation.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    ToSchema,
+    ToResponse,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum StatusUpdateActor {
+    /// The operation was registered with Emily by whoever submitted it.
+    Creator,
+    /// The update was reported by one of the sBTC Signers.
+    Signer,
+    /// The update was reported by the trusted sidecar, or was derived from
+    /// the events it observed on the Stacks blockchain.
+    Sidecar,
+    /// Emily reset the status of the operation after a chain reorg.
+    Reorg,
+}
+
 /// Data about the fulfillment of an sBTC Operation.
 #[derive(
     Clone,
@@ -123,4 +151,13 @@ pub struct Fulfillment {
     pub bitcoin_block_height: u64,
     /// Satoshis consumed to fulfill the sBTC operation.
     pub btc_fee: u64,
+    /// Hex encoded SPV proof that the bitcoin transaction that fulfilled the operation
+    /// is included in the block with `bitcoin_block_hash`. This is a serialized
+    /// `MerkleBlock`, the same format returned by bitcoin core's `gettxoutproof` RPC.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sweep_tx_proof: Option<String>,
+    /// The fee in satoshis that the signers assessed against this operation when
+    /// constructing the bitcoin transaction that fulfilled it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assessed_fee: Option<u64>,
 }
@@ -59,6 +59,14 @@ pub struct Deposit {
     /// Transaction ID of the transaction that replaced this one via RBF.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub replaced_by_tx: Option<String>,
+    /// The hash of the bitcoin block that confirmed the deposit transaction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deposit_block_hash: Option<String>,
+    /// Hex encoded SPV proof that the deposit transaction is included in the block
+    /// with `deposit_block_hash`. This is a serialized `MerkleBlock`, the same format
+    /// returned by bitcoin core's `gettxoutproof` RPC.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deposit_tx_proof: Option<String>,
 }
 
 /// Deposit parameters.
@@ -1,23 +1,22 @@
 //! Request structures for deposit api calls.
 
-use std::str::FromStr as _;
-
+use bitcoin::ScriptBuf;
 use bitcoin::blockdata::transaction::Transaction;
 use bitcoin::consensus::encode;
-use bitcoin::{OutPoint, ScriptBuf, Txid};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
-use warp::http::StatusCode;
 
-use sbtc::deposits::{CreateDepositRequest, DepositInfo};
+use sbtc::deposits::{CreateDepositRequest, DepositInfo, DepositScriptInputs, ReclaimScriptInputs};
+use sbtc::ids::DepositId;
 
 use crate::api::models::chainstate::Chainstate;
 use crate::api::models::common::{DepositStatus, Fulfillment};
-use crate::common::error::{self, Error, ValidationError};
-use crate::database::entries::DepositStatusEntry;
+use crate::common::error::{self, Error, FieldValidationError, RequestField, ValidationError};
 use crate::database::entries::deposit::{
-    DepositEntryKey, DepositEvent, ValidatedDepositUpdate, ValidatedUpdateDepositsRequest,
+    DepositEntryKey, DepositEvent, DepositTxProof, ValidatedDepositUpdate,
+    ValidatedUpdateDepositsRequest,
 };
+use crate::database::entries::{DepositStatusEntry, event_timestamp, proves_inclusion};
 
 /// Query structure for the GetDepositsQuery struct.
 #[derive(Clone, Default, Debug, PartialEq, Hash, Serialize, Deserialize, ToSchema)]
@@ -43,6 +42,10 @@ pub struct GetDepositsQuery {
     /// Maximum number of results to show.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub page_size: Option<u16>,
+    /// Whether to continue the search into deposits that have been archived
+    /// once the deposits that have not been archived are exhausted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_archived: Option<bool>,
 }
 
 /// Request structure for create deposit request.
@@ -61,55 +64,127 @@ pub struct CreateDepositRequestBody {
     pub transaction_hex: String,
 }
 
-fn parse_with_custom_error<T, F, E>(input: &str, parser: F, error_msg: &str) -> Result<T, Error>
-where
-    F: Fn(&str) -> Result<T, E>,
-{
-    parser(input).map_err(|_| Error::HttpRequest(StatusCode::BAD_REQUEST, error_msg.to_string()))
+/// Request structure for the create deposits request, which creates many
+/// deposits at once.
+#[derive(Clone, Default, Debug, PartialEq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateDepositsRequestBody {
+    /// The deposits to create. Each one is validated and created
+    /// independently of the others.
+    pub deposits: Vec<CreateDepositRequestBody>,
+}
+
+/// Return the parsed value, or record why it could not be parsed as a
+/// validation error for the given field.
+fn check_field<T>(
+    errors: &mut Vec<FieldValidationError>,
+    field: RequestField,
+    result: Result<T, String>,
+) -> Option<T> {
+    result
+        .map_err(|message| errors.push(FieldValidationError::new(field, message)))
+        .ok()
+}
+
+/// Parse the hex encoded script, and check that it is a well-formed
+/// deposit or reclaim script with the given parser.
+fn parse_script<T>(
+    hex: &str,
+    parser: fn(&ScriptBuf) -> Result<T, sbtc::error::Error>,
+    error_msg: &str,
+) -> Result<ScriptBuf, String> {
+    let script = ScriptBuf::from_hex(hex).map_err(|_| error_msg.to_string())?;
+    parser(&script).map_err(|error| error.to_string())?;
+    Ok(script)
+}
+
+/// Return the field of the create deposit request body that is at fault
+/// for the given error from validating the deposit transaction.
+fn field_for_deposit_error(error: &sbtc::error::Error) -> RequestField {
+    use sbtc::error::Error as SbtcError;
+
+    match error {
+        SbtcError::TxidMismatch { .. }
+        | SbtcError::OutpointIndex(..)
+        | SbtcError::UtxoScriptPubKeyMismatch(_)
+        | SbtcError::FutureWitnessSiblingOutput(..)
+        | SbtcError::NonStandardSiblingOutput(_) => RequestField::Outpoint,
+        SbtcError::DisabledLockTime(_)
+        | SbtcError::InvalidReclaimScriptLength(_)
+        | SbtcError::InvalidReclaimScriptLockTime(_)
+        | SbtcError::InvalidReclaimScript
+        | SbtcError::ScriptNum(_)
+        | SbtcError::ReclaimScriptWithSuccessOp(_) => RequestField::ReclaimScript,
+        _ => RequestField::DepositScript,
+    }
 }
 
 impl CreateDepositRequestBody {
     /// Validates that the deposit request is valid.
     /// This includes validating the request fields and if their content matches the transaction
+    ///
+    /// Each field is validated independently, so that the returned
+    /// [`Error::InvalidRequestFields`] error lists every invalid field.
     pub fn validate(&self, is_mainnet: bool) -> Result<DepositInfo, Error> {
-        let deposit_req = CreateDepositRequest {
-            outpoint: OutPoint {
-                txid: parse_with_custom_error(
-                    &self.bitcoin_txid,
-                    Txid::from_str,
-                    "invalid bitcoin txid",
-                )?,
-                vout: self.bitcoin_tx_output_index,
-            },
-            reclaim_script: parse_with_custom_error(
-                &self.reclaim_script,
-                ScriptBuf::from_hex,
-                "invalid reclaim script",
-            )?,
-            deposit_script: parse_with_custom_error(
+        let mut errors = Vec::new();
+
+        let id = check_field(
+            &mut errors,
+            RequestField::Outpoint,
+            DepositId::from_hex(&self.bitcoin_txid, self.bitcoin_tx_output_index)
+                .map_err(|_| "invalid bitcoin txid".to_string()),
+        );
+        let deposit_script = check_field(
+            &mut errors,
+            RequestField::DepositScript,
+            parse_script(
                 &self.deposit_script,
-                ScriptBuf::from_hex,
+                DepositScriptInputs::parse,
                 "invalid deposit script",
-            )?,
+            ),
+        );
+        let reclaim_script = check_field(
+            &mut errors,
+            RequestField::ReclaimScript,
+            parse_script(
+                &self.reclaim_script,
+                ReclaimScriptInputs::parse,
+                "invalid reclaim script",
+            ),
+        );
+        let tx: Option<Transaction> = check_field(
+            &mut errors,
+            RequestField::TransactionHex,
+            encode::deserialize_hex(&self.transaction_hex)
+                .map_err(|_| "invalid transaction hex".to_string()),
+        );
+
+        if let Some(tx) = &tx
+            && tx.tx_out(self.bitcoin_tx_output_index as usize).is_err()
+        {
+            let error =
+                FieldValidationError::new(RequestField::Outpoint, "invalid bitcoin output index");
+            errors.push(error);
+        }
+
+        let (deposit_req, tx) = match (id, deposit_script, reclaim_script, tx) {
+            (Some(id), Some(deposit_script), Some(reclaim_script), Some(tx))
+                if errors.is_empty() =>
+            {
+                let deposit_req = CreateDepositRequest {
+                    outpoint: id.outpoint(),
+                    reclaim_script,
+                    deposit_script,
+                };
+                (deposit_req, tx)
+            }
+            _ => return Err(Error::InvalidRequestFields(errors)),
         };
 
-        let tx: Transaction = parse_with_custom_error(
-            &self.transaction_hex,
-            encode::deserialize_hex,
-            "invalid transaction hex",
-        )?;
-
-        tx.tx_out(self.bitcoin_tx_output_index as usize)
-            .map_err(|_| {
-                Error::HttpRequest(
-                    StatusCode::BAD_REQUEST,
-                    "invalid bitcoin output index".to_string(),
-                )
-            })?;
-
-        deposit_req
-            .validate_tx(&tx, is_mainnet)
-            .map_err(|e| Error::HttpRequest(StatusCode::BAD_REQUEST, e.to_string()))
+        deposit_req.validate_tx(&tx, is_mainnet).map_err(|error| {
+            let field = field_for_deposit_error(&error);
+            Error::InvalidRequestFields(vec![FieldValidationError::new(field, error.to_string())])
+        })
     }
 }
 
@@ -133,6 +208,15 @@ pub struct DepositUpdate {
     /// Transaction ID of the transaction that replaced this one via RBF.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub replaced_by_tx: Option<String>,
+    /// The hash of the bitcoin block that confirmed the deposit transaction.
+    /// This must be provided along with `deposit_tx_proof`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deposit_block_hash: Option<String>,
+    /// Hex encoded SPV proof that the deposit transaction is included in the block
+    /// with `deposit_block_hash`. This is a serialized `MerkleBlock`, the same format
+    /// returned by bitcoin core's `gettxoutproof` RPC.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deposit_tx_proof: Option<String>,
 }
 
 impl DepositUpdate {
@@ -141,6 +225,8 @@ impl DepositUpdate {
     /// # Errors
     ///
     /// - `ValidationError::DepositMissingFulfillment`: If the deposit update is missing a fulfillment.
+    /// - `ValidationError::InvalidDepositTxProof`: If the deposit update has a proof that does not
+    ///   show that the deposit transaction is included in the given bitcoin block.
     pub fn try_into_validated_deposit_update(
         self,
         chainstate: Chainstate,
@@ -166,6 +252,22 @@ impl DepositUpdate {
                 self.bitcoin_tx_output_index,
             ));
         }
+        // The proof must show that the deposit transaction is included in
+        // the given bitcoin block.
+        let deposit_tx_proof = match (self.deposit_block_hash, self.deposit_tx_proof) {
+            (None, None) => None,
+            (Some(block_hash), Some(proof))
+                if proves_inclusion(&proof, &key.bitcoin_txid, &block_hash) =>
+            {
+                Some(DepositTxProof { block_hash, proof })
+            }
+            _ => {
+                return Err(ValidationError::InvalidDepositTxProof(
+                    key.bitcoin_txid,
+                    key.bitcoin_tx_output_index,
+                ));
+            }
+        };
         // Make status entry.
         let status_entry: DepositStatusEntry = match self.status {
             DepositStatus::Confirmed => {
@@ -193,9 +295,11 @@ impl DepositUpdate {
             message: self.status_message,
             stacks_block_height: chainstate.stacks_block_height,
             stacks_block_hash: chainstate.stacks_block_hash,
+            updated_at: Some(event_timestamp()),
+            actor: None,
         };
         // Return the validated update.
-        Ok(ValidatedDepositUpdate { key, event })
+        Ok(ValidatedDepositUpdate { key, event, deposit_tx_proof })
     }
 }
 
@@ -254,6 +358,19 @@ impl UpdateDepositsRequestBody {
                     );
                     deposits.push((index, Err(error.clone())));
                 }
+                Err(
+                    ref error @ ValidationError::InvalidDepositTxProof(
+                        ref bitcoin_txid,
+                        bitcoin_tx_output_index,
+                    ),
+                ) => {
+                    tracing::warn!(
+                        %bitcoin_txid,
+                        bitcoin_tx_output_index,
+                        "failed to update deposit: the deposit transaction proof is invalid.",
+                    );
+                    deposits.push((index, Err(error.clone())));
+                }
                 Err(error) => {
                     tracing::error!(
                         bitcoin_txid = update.bitcoin_txid,
@@ -279,6 +396,7 @@ impl UpdateDepositsRequestBody {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use bitcoin::hashes::Hash as _;
     use test_case::test_case;
 
     const CREATE_DEPOSIT_VALID: &str =
@@ -318,28 +436,124 @@ mod tests {
         assert!(deposit_request.validate(true).is_ok());
     }
 
-    #[test_case(CREATE_DEPOSIT_INVALID_TXID, "invalid bitcoin txid"; "invalid_txid")]
-    #[test_case(CREATE_DEPOSIT_INVALID_RECLAIM_SCRIPT, "invalid reclaim script"; "invalid_reclaim_script")]
-    #[test_case(CREATE_DEPOSIT_INVALID_DEPOSIT_SCRIPT, "invalid deposit script"; "invalid_deposit_script")]
-    #[test_case(CREATE_DEPOSIT_INVALID_TRANSACTION_HEX, "invalid transaction hex"; "invalid_transaction_hex")]
-    #[test_case(CREATE_DEPOSIT_INVALID_OUTPUT_INDEX, "invalid bitcoin output index"; "invalid_output_index")]
-    #[test_case(CREATE_DEPOSIT_MISMATCH_TXID, "The txid of the transaction did not match the given txid"; "mismatch_txid")]
+    #[test_case(CREATE_DEPOSIT_INVALID_TXID, RequestField::Outpoint, "invalid bitcoin txid"; "invalid_txid")]
+    #[test_case(CREATE_DEPOSIT_INVALID_RECLAIM_SCRIPT, RequestField::ReclaimScript, "invalid reclaim script"; "invalid_reclaim_script")]
+    #[test_case(CREATE_DEPOSIT_INVALID_DEPOSIT_SCRIPT, RequestField::DepositScript, "invalid deposit script"; "invalid_deposit_script")]
+    #[test_case(CREATE_DEPOSIT_INVALID_TRANSACTION_HEX, RequestField::TransactionHex, "invalid transaction hex"; "invalid_transaction_hex")]
+    #[test_case(CREATE_DEPOSIT_INVALID_OUTPUT_INDEX, RequestField::Outpoint, "invalid bitcoin output index"; "invalid_output_index")]
+    #[test_case(CREATE_DEPOSIT_MISMATCH_TXID, RequestField::Outpoint, "The txid of the transaction did not match the given txid"; "mismatch_txid")]
     #[test_case(
         CREATE_DEPOSIT_MISMATCH_RECLAIM_SCRIPT,
+        RequestField::Outpoint,
         "mismatch in expected and actual ScriptPubKeys. outpoint: f75cb869600c6a75ab90c872435da38d54d53c27afe5e03ac7dedae7822958de:0";
         "mismatch_reclaim_script")]
     #[test_case(
         CREATE_DEPOSIT_MISMATCH_DEPOSIT_SCRIPT,
+        RequestField::Outpoint,
         "mismatch in expected and actual ScriptPubKeys. outpoint: f75cb869600c6a75ab90c872435da38d54d53c27afe5e03ac7dedae7822958de:0";
         "mismatch_deposit_script")]
     #[tokio::test]
-    async fn test_deposit_validate_errors(input: &str, expected_error: &str) {
+    async fn test_deposit_validate_errors(
+        input: &str,
+        expected_field: RequestField,
+        expected_error: &str,
+    ) {
         let deposit_request = parse_request(input);
 
-        let result = deposit_request.validate(true);
+        let Err(Error::InvalidRequestFields(errors)) = deposit_request.validate(true) else {
+            panic!("expected field validation errors");
+        };
+        assert_eq!(
+            errors,
+            vec![FieldValidationError::new(expected_field, expected_error)]
+        );
         assert_eq!(
-            result.unwrap_err().to_string(),
-            format!("HTTP request failed with status code 400 Bad Request: {expected_error}")
+            Error::InvalidRequestFields(errors).to_string(),
+            format!("invalid request fields: {expected_field}: {expected_error}")
         );
     }
+
+    #[test]
+    fn deposit_validate_reports_every_invalid_field() {
+        let deposit_request = CreateDepositRequestBody {
+            bitcoin_txid: "abcdefg".to_string(),
+            transaction_hex: "xyz".to_string(),
+            // This is hex, but it is not a deposit script.
+            deposit_script: "51".to_string(),
+            ..parse_request(CREATE_DEPOSIT_VALID)
+        };
+
+        let Err(Error::InvalidRequestFields(errors)) = deposit_request.validate(true) else {
+            panic!("expected field validation errors");
+        };
+        let fields: Vec<RequestField> = errors.iter().map(|error| error.field).collect();
+        let expected = [
+            RequestField::Outpoint,
+            RequestField::DepositScript,
+            RequestField::TransactionHex,
+        ];
+        assert_eq!(fields, expected);
+    }
+
+    #[test]
+    fn deposit_update_with_tx_proof_is_validated() {
+        let block = bitcoin::constants::genesis_block(bitcoin::Network::Regtest);
+        let txid = block.txdata[0].compute_txid();
+        let merkle_block =
+            bitcoin::MerkleBlock::from_block_with_predicate(&block, |id| id == &txid);
+        let proof = bitcoin::consensus::encode::serialize_hex(&merkle_block);
+
+        let update = DepositUpdate {
+            bitcoin_txid: txid.to_string(),
+            bitcoin_tx_output_index: 0,
+            status: DepositStatus::Pending,
+            deposit_block_hash: Some(block.block_hash().to_string()),
+            deposit_tx_proof: Some(proof.clone()),
+            ..Default::default()
+        };
+
+        let validated = update
+            .clone()
+            .try_into_validated_deposit_update(Chainstate::default())
+            .unwrap();
+        let expected = DepositTxProof {
+            block_hash: block.block_hash().to_string(),
+            proof: proof.clone(),
+        };
+        assert_eq!(validated.deposit_tx_proof, Some(expected));
+        assert!(validated.is_tx_proof_only());
+
+        // The proof must be for the deposit transaction.
+        let other_txid = DepositUpdate {
+            bitcoin_txid: bitcoin::Txid::all_zeros().to_string(),
+            ..update.clone()
+        };
+        let result = other_txid.try_into_validated_deposit_update(Chainstate::default());
+        assert!(matches!(
+            result,
+            Err(ValidationError::InvalidDepositTxProof(_, 0))
+        ));
+
+        // The proof must be for the given block.
+        let other_block = DepositUpdate {
+            deposit_block_hash: Some(bitcoin::BlockHash::all_zeros().to_string()),
+            ..update.clone()
+        };
+        let result = other_block.try_into_validated_deposit_update(Chainstate::default());
+        assert!(matches!(
+            result,
+            Err(ValidationError::InvalidDepositTxProof(_, 0))
+        ));
+
+        // The block hash and the proof must be provided together.
+        let missing_block = DepositUpdate {
+            deposit_block_hash: None,
+            ..update
+        };
+        let result = missing_block.try_into_validated_deposit_update(Chainstate::default());
+        assert!(matches!(
+            result,
+            Err(ValidationError::InvalidDepositTxProof(_, 0))
+        ));
+    }
 }
@@ -3,6 +3,7 @@
 use serde::{Deserialize, Serialize};
 use utoipa::{ToResponse, ToSchema};
 
+use crate::api::models::common::{DepositStatus, Fulfillment, StatusUpdateActor};
 use crate::api::models::deposit::{Deposit, DepositInfo};
 
 /// Response to get deposits for transaction request.
@@ -25,6 +26,93 @@ pub struct GetDepositsResponse {
     pub deposits: Vec<DepositInfo>,
 }
 
+/// Response to get deposit ETA request.
+///
+/// The estimate is derived from the deposits currently waiting to be swept
+/// and from the fulfillments that the signers reported for recently swept
+/// deposits. It is a best-effort hint for wallets and not a guarantee.
+#[derive(Clone, Default, Debug, PartialEq, Hash, Serialize, Deserialize, ToSchema, ToResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct GetDepositEtaResponse {
+    /// Bitcoin transaction id.
+    pub bitcoin_txid: String,
+    /// Output index on the bitcoin transaction associated with this specific deposit.
+    pub bitcoin_tx_output_index: u32,
+    /// The status of the deposit.
+    pub status: DepositStatus,
+    /// The number of pending and accepted deposits that are expected to be
+    /// swept before this one.
+    pub deposits_ahead: u64,
+    /// The number of sweeps that are expected to happen until this deposit
+    /// is swept, including the sweep that sweeps it.
+    pub sweeps_remaining: u64,
+    /// The average number of bitcoin blocks between recent sweeps, if there
+    /// have been enough recent sweeps to tell.
+    pub sweep_interval_blocks: Option<u64>,
+    /// The median fee, in satoshis, paid by recently swept deposits.
+    pub recent_median_fee: Option<u64>,
+    /// Whether the max fee of the deposit covers the median fee paid by
+    /// recently swept deposits.
+    pub fee_sufficient: bool,
+    /// The estimated number of bitcoin blocks until the deposit is swept.
+    /// This is `None` if there is not enough data for an estimate, or if the
+    /// deposit cannot currently be swept.
+    pub estimated_bitcoin_blocks: Option<u64>,
+    /// The estimated number of seconds until the deposit is swept, assuming
+    /// ten minute bitcoin blocks.
+    pub estimated_seconds: Option<u64>,
+}
+
+/// Response to create deposits request.
+#[derive(Clone, Default, Debug, PartialEq, Hash, Serialize, Deserialize, ToSchema, ToResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateDepositsResponse {
+    /// The outcome of creating each deposit, in the order of the request.
+    pub deposits: Vec<DepositWithStatus>,
+}
+
+/// Response to get deposit history request.
+#[derive(Clone, Default, Debug, PartialEq, Hash, Serialize, Deserialize, ToSchema, ToResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct GetDepositHistoryResponse {
+    /// Bitcoin transaction id.
+    pub bitcoin_txid: String,
+    /// Output index on the bitcoin transaction associated with this specific deposit.
+    pub bitcoin_tx_output_index: u32,
+    /// The status updates of the deposit in chronological order. The last
+    /// update is the current status of the deposit.
+    pub history: Vec<DepositStatusUpdate>,
+}
+
+/// A single status update in the history of a deposit.
+#[derive(Clone, Default, Debug, PartialEq, Hash, Serialize, Deserialize, ToSchema, ToResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositStatusUpdate {
+    /// The status of the deposit after this update.
+    pub status: DepositStatus,
+    /// The status message of this update.
+    pub status_message: String,
+    /// Stacks block height the API was aware of at the time of this update.
+    pub stacks_block_height: u64,
+    /// Stacks block hash associated with the height of this update.
+    pub stacks_block_hash: String,
+    /// Details about the on chain artifacts that fulfilled the deposit, if
+    /// this update confirmed it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fulfillment: Option<Fulfillment>,
+    /// Transaction ID of the transaction that replaced this one via RBF, if
+    /// this update reported the replacement.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replaced_by_tx: Option<String>,
+    /// Unix timestamp, in seconds, of when Emily recorded this update. This
+    /// is missing for updates that were recorded before it was tracked.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<u64>,
+    /// The party that caused this update, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor: Option<StatusUpdateActor>,
+}
+
 /// Response to update deposits request.
 #[derive(Clone, Default, Debug, PartialEq, Hash, Serialize, Deserialize, ToSchema, ToResponse)]
 #[serde(rename_all = "camelCase")]
@@ -3,7 +3,14 @@ use utoipa::ToSchema;
 
 /// Struct that represents the current status of the API.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct HealthData {
     /// The version of the API.
     pub version: String,
+    /// The current version of the API, the newest one that can be named
+    /// in the `Accept-Version` header of requests.
+    pub api_version: u32,
+    /// The oldest version of the API that can be named in the
+    /// `Accept-Version` header of requests.
+    pub min_api_version: u32,
 }
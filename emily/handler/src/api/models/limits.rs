@@ -13,6 +13,11 @@ use crate::common::error::ValidationError;
 pub struct Limits {
     /// Represents the total cap for all pegged-in BTC/sBTC.
     pub peg_cap: Option<u64>,
+    /// The amount of sBTC that can still be minted under the peg cap, as
+    /// last reported by the signers. This accounts for deposits that have
+    /// been swept but not yet minted. This is not settable and is ignored
+    /// when setting limits.
+    pub available_to_mint: Option<u64>,
     /// Per deposit minimum. If none then there is no minimum.
     pub per_deposit_minimum: Option<u64>,
     /// Per deposit cap. If none then there is no cap.
@@ -26,10 +31,30 @@ pub struct Limits {
     /// Total amount sBTC still available for withdrawals in current window. All withdrawals except rejected
     /// counted here
     pub available_to_withdraw: Option<u64>,
+    /// Number of bitcoin blocks until some sBTC is available for withdrawals
+    /// in the rolling withdrawal window again. Zero if some is available now,
+    /// and none if there is no rolling withdrawal cap or it is zero. This is
+    /// not settable and is ignored when setting limits.
+    pub blocks_until_withdrawal_headroom: Option<u64>,
     /// Represents the individual limits for requests coming from different accounts.
     pub account_caps: HashMap<String, AccountLimits>,
     /// Name of key which triggered throttle mode (if throttle mode is active)
     pub throttle_mode_initiator: Option<String>,
+    /// The number of bitcoin confirmations the signers require before
+    /// sweeping a deposit, based on its amount. This is not settable and
+    /// is ignored when setting limits.
+    #[serde(default)]
+    pub deposit_confirmation_policy: Vec<DepositConfirmationTier>,
+}
+
+/// Request structure for the signers to report how much sBTC can still be
+/// minted.
+#[derive(Clone, Default, Debug, Eq, PartialEq, Serialize, Deserialize, ToSchema, ToResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct SetAvailableToMintRequestBody {
+    /// The amount of sBTC, in sats, that can still be minted under the peg
+    /// cap.
+    pub available_to_mint: u64,
 }
 
 impl Limits {
@@ -88,3 +113,36 @@ pub struct AccountLimits {
     /// Throttle key initiated throttle
     pub throttle_mode_initiator: Option<String>,
 }
+
+/// A tier of the deposit confirmation policy used by the signers.
+#[derive(
+    Clone,
+    Default,
+    Debug,
+    Eq,
+    PartialEq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    ToSchema,
+    ToResponse,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositConfirmationTier {
+    /// The minimum deposit amount, in sats, this tier applies to.
+    pub min_amount: u64,
+    /// The number of bitcoin confirmations required for deposits with an
+    /// amount of at least `min_amount`.
+    pub confirmations: u16,
+}
+
+impl From<&sbtc::deposits::DepositConfirmationTier> for DepositConfirmationTier {
+    fn from(tier: &sbtc::deposits::DepositConfirmationTier) -> Self {
+        DepositConfirmationTier {
+            min_amount: tier.min_amount,
+            confirmations: tier.confirmations.get(),
+        }
+    }
+}
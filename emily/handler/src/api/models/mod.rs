@@ -1,5 +1,7 @@
 //! This module contains the models used within Emily API endpoint interactions.
 
+/// Api structures for api keys.
+pub mod api_key;
 /// Api structures for chainstate.
 pub mod chainstate;
 /// Common API structures.
@@ -12,6 +14,8 @@ pub mod health;
 pub mod limits;
 /// Api structures for new block events.
 pub mod new_block;
+/// Api structures for the retention of old requests.
+pub mod retention;
 /// Api structures for throttle keys.
 pub mod throttle;
 /// Api structures for withdrawals.
@@ -0,0 +1,22 @@
+//! Response structures for retention api calls.
+
+use serde::{Deserialize, Serialize};
+use utoipa::{ToResponse, ToSchema};
+
+/// Response to a run of the retention policy.
+#[derive(Clone, Default, Debug, Eq, PartialEq, Serialize, Deserialize, ToSchema, ToResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct RunRetentionResponse {
+    /// The number of completed deposits moved into the archive table.
+    pub archived_deposits: u64,
+    /// The number of completed withdrawals moved into the archive table.
+    pub archived_withdrawals: u64,
+    /// The number of archived deposits deleted.
+    pub purged_deposits: u64,
+    /// The number of archived withdrawals deleted.
+    pub purged_withdrawals: u64,
+    /// Whether every request that is due to be archived or purged has
+    /// been. Each run handles a bounded number of requests, so the
+    /// retention policy should be run again until this is true.
+    pub complete: bool,
+}
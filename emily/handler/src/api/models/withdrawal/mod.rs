@@ -158,6 +158,9 @@ pub struct WithdrawalInfo {
     pub status: WithdrawalStatus,
     /// The hex encoded txid of the stacks transaction that generated this event.
     pub txid: String,
+    /// Details about the on chain artifacts that fulfilled the withdrawal.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fulfillment: Option<Fulfillment>,
 }
 
 /// Create a WithdrawalInfo, which has a subset of the data within a Withdrawal, from a Withdrawal.
@@ -174,6 +177,7 @@ impl From<Withdrawal> for WithdrawalInfo {
             last_update_block_hash: withdrawal.last_update_block_hash,
             status: withdrawal.status,
             txid: withdrawal.txid,
+            fulfillment: withdrawal.fulfillment,
         }
     }
 }
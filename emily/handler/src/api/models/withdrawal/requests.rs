@@ -7,10 +7,10 @@ use crate::api::models::chainstate::Chainstate;
 use crate::api::models::common::{Fulfillment, WithdrawalStatus};
 use crate::api::models::withdrawal::{ExpectedFulfillmentInfo, WithdrawalParameters};
 use crate::common::error::{self, ValidationError};
-use crate::database::entries::WithdrawalStatusEntry;
 use crate::database::entries::withdrawal::{
     ValidatedUpdateWithdrawalRequest, ValidatedWithdrawalUpdate, WithdrawalEvent,
 };
+use crate::database::entries::{WithdrawalStatusEntry, event_timestamp};
 
 /// Query structure for the get withdrawals request.
 #[derive(Clone, Default, Debug, PartialEq, Hash, Serialize, Deserialize, ToSchema)]
@@ -24,6 +24,10 @@ pub struct GetWithdrawalsQuery {
     /// Maximum number of results to show.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub page_size: Option<u16>,
+    /// Whether to continue the search into withdrawals that have been archived
+    /// once the withdrawals that have not been archived are exhausted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_archived: Option<bool>,
 }
 
 /// Request structure for the create withdrawal request.
@@ -105,6 +109,8 @@ impl WithdrawalUpdate {
             message: self.status_message,
             stacks_block_height: chainstate.stacks_block_height,
             stacks_block_hash: chainstate.stacks_block_hash,
+            updated_at: Some(event_timestamp()),
+            actor: None,
             expected_fulfillment_info: self.expected_fulfillment_info.unwrap_or_default(),
         };
         // Return the validated update.
@@ -3,7 +3,8 @@
 use serde::{Deserialize, Serialize};
 use utoipa::{ToResponse, ToSchema};
 
-use crate::api::models::withdrawal::{Withdrawal, WithdrawalInfo};
+use crate::api::models::common::{Fulfillment, StatusUpdateActor, WithdrawalStatus};
+use crate::api::models::withdrawal::{ExpectedFulfillmentInfo, Withdrawal, WithdrawalInfo};
 
 /// Response to get withdrawals request.
 #[derive(Clone, Default, Debug, PartialEq, Hash, Serialize, Deserialize, ToSchema, ToResponse)]
@@ -15,6 +16,45 @@ pub struct GetWithdrawalsResponse {
     pub withdrawals: Vec<WithdrawalInfo>,
 }
 
+/// Response to get withdrawal history request.
+#[derive(Clone, Default, Debug, PartialEq, Hash, Serialize, Deserialize, ToSchema, ToResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct GetWithdrawalHistoryResponse {
+    /// The id of the Stacks withdrawal request that initiated the sBTC operation.
+    pub request_id: u64,
+    /// The status updates of the withdrawal in chronological order. The
+    /// last update is the current status of the withdrawal.
+    pub history: Vec<WithdrawalStatusUpdate>,
+}
+
+/// A single status update in the history of a withdrawal.
+#[derive(Clone, Default, Debug, PartialEq, Hash, Serialize, Deserialize, ToSchema, ToResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawalStatusUpdate {
+    /// The status of the withdrawal after this update.
+    pub status: WithdrawalStatus,
+    /// The status message of this update.
+    pub status_message: String,
+    /// Stacks block height the API was aware of at the time of this update.
+    pub stacks_block_height: u64,
+    /// Stacks block hash associated with the height of this update.
+    pub stacks_block_hash: String,
+    /// Details about the on chain artifacts that fulfilled the withdrawal,
+    /// if this update confirmed it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fulfillment: Option<Fulfillment>,
+    /// Information about the expected fulfillment of the withdrawal at the
+    /// time of this update.
+    pub expected_fulfillment_info: ExpectedFulfillmentInfo,
+    /// Unix timestamp, in seconds, of when Emily recorded this update. This
+    /// is missing for updates that were recorded before it was tracked.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<u64>,
+    /// The party that caused this update, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor: Option<StatusUpdateActor>,
+}
+
 /// Response to update withdrawals request.
 #[derive(Clone, Default, Debug, PartialEq, Hash, Serialize, Deserialize, ToSchema, ToResponse)]
 #[serde(rename_all = "camelCase")]
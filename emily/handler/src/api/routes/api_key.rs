@@ -0,0 +1,96 @@
+//! Route definitions for the api key endpoints.
+
+use warp::Filter;
+
+use crate::context::EmilyContext;
+
+use super::handlers;
+
+/// Api key routes.
+pub fn routes<F>(
+    context: F,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone
+where
+    F: Filter<Extract = (EmilyContext,), Error = std::convert::Infallible>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+{
+    create_api_key(context.clone())
+        .or(get_api_key(context.clone()))
+        .boxed()
+        .or(rotate_api_key(context.clone()))
+        .boxed()
+        .or(activate_api_key(context.clone()))
+        .boxed()
+        .or(deactivate_api_key(context))
+        .boxed()
+}
+
+/// Create api key endpoint.
+fn create_api_key<F>(
+    context: F,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone
+where
+    F: Filter<Extract = (EmilyContext,), Error = std::convert::Infallible> + Clone + Send,
+{
+    warp::path!("api_key")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(context)
+        .then(handlers::api_key::create_api_key)
+}
+
+/// Get api key endpoint.
+fn get_api_key<F>(
+    context: F,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone
+where
+    F: Filter<Extract = (EmilyContext,), Error = std::convert::Infallible> + Clone + Send,
+{
+    warp::path!("api_key" / String)
+        .and(warp::get())
+        .and(context)
+        .then(handlers::api_key::get_api_key)
+}
+
+/// Rotate api key endpoint.
+fn rotate_api_key<F>(
+    context: F,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone
+where
+    F: Filter<Extract = (EmilyContext,), Error = std::convert::Infallible> + Clone + Send,
+{
+    warp::path!("api_key" / String / "rotate")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(context)
+        .then(handlers::api_key::rotate_api_key)
+}
+
+/// Activate api key endpoint.
+fn activate_api_key<F>(
+    context: F,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone
+where
+    F: Filter<Extract = (EmilyContext,), Error = std::convert::Infallible> + Clone + Send,
+{
+    warp::path!("api_key" / String / "activate")
+        .and(warp::patch())
+        .and(context)
+        .then(handlers::api_key::activate_api_key)
+}
+
+/// Deactivate api key endpoint.
+fn deactivate_api_key<F>(
+    context: F,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone
+where
+    F: Filter<Extract = (EmilyContext,), Error = std::convert::Infallible> + Clone + Send,
+{
+    warp::path!("api_key" / String / "deactivate")
+        .and(warp::patch())
+        .and(context)
+        .then(handlers::api_key::deactivate_api_key)
+}
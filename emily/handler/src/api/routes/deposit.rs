@@ -17,6 +17,10 @@ where
         + 'static,
 {
     get_deposit(context.clone())
+        .or(get_deposit_history(context.clone()))
+        .boxed()
+        .or(get_deposit_eta(context.clone()))
+        .boxed()
         .or(get_deposits_for_transaction(context.clone()))
         .boxed()
         .or(get_deposits(context.clone()))
@@ -27,6 +31,8 @@ where
         .boxed()
         .or(create_deposit(context.clone()))
         .boxed()
+        .or(create_deposits(context.clone()))
+        .boxed()
         .or(update_deposits_sidecar(context.clone()))
         .boxed()
         .or(update_deposits_signer(context))
@@ -46,6 +52,32 @@ where
         .then(handlers::deposit::get_deposit)
 }
 
+/// Get deposit history endpoint.
+fn get_deposit_history<F>(
+    context: F,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone
+where
+    F: Filter<Extract = (EmilyContext,), Error = std::convert::Infallible> + Clone + Send,
+{
+    warp::path!("deposit" / String / u32 / "history")
+        .and(warp::get())
+        .and(context)
+        .then(handlers::deposit::get_deposit_history)
+}
+
+/// Get deposit ETA endpoint.
+fn get_deposit_eta<F>(
+    context: F,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone
+where
+    F: Filter<Extract = (EmilyContext,), Error = std::convert::Infallible> + Clone + Send,
+{
+    warp::path!("deposit" / String / u32 / "eta")
+        .and(warp::get())
+        .and(context)
+        .then(handlers::deposit::get_deposit_eta)
+}
+
 /// Get deposits for transaction endpoint.
 fn get_deposits_for_transaction<F>(
     context: F,
@@ -116,6 +148,20 @@ where
         .then(handlers::deposit::create_deposit)
 }
 
+/// Create deposits endpoint.
+fn create_deposits<F>(
+    context: F,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone
+where
+    F: Filter<Extract = (EmilyContext,), Error = std::convert::Infallible> + Clone + Send,
+{
+    warp::path!("deposit" / "bulk")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(context)
+        .then(handlers::deposit::create_deposits)
+}
+
 /// Update deposits from signer endpoint.
 fn update_deposits_signer<F>(
     context: F,
@@ -20,6 +20,8 @@ where
     get_limits(context.clone())
         .or(set_limits(context.clone()))
         .boxed()
+        .or(set_available_to_mint(context.clone()))
+        .boxed()
         .or(set_limits_for_account(context.clone()))
         .boxed()
         .or(get_limits_for_account(context))
@@ -53,6 +55,20 @@ where
         .then(handlers::limits::set_limits)
 }
 
+/// Set available to mint endpoint.
+fn set_available_to_mint<F>(
+    context: F,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone
+where
+    F: Filter<Extract = (EmilyContext,), Error = std::convert::Infallible> + Clone + Send,
+{
+    warp::path!("limits")
+        .and(warp::put())
+        .and(warp::body::json())
+        .and(context)
+        .then(handlers::limits::set_available_to_mint)
+}
+
 /// Endpoint to set the limits for a specific account.
 fn set_limits_for_account<F>(
     context: F,
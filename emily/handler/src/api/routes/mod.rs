@@ -5,12 +5,16 @@ use std::convert::Infallible;
 
 use crate::context::EmilyContext;
 
+use super::auth;
 use super::handlers;
+use super::version;
 use tracing::debug;
 use warp::Filter;
 #[cfg(feature = "testing")]
 use warp::http::HeaderMap;
 
+/// Api key routes.
+mod api_key;
 /// Chainstate routes.
 mod chainstate;
 /// Deposit routes.
@@ -21,6 +25,8 @@ mod health;
 mod limits;
 /// NewBlock routes.
 mod new_block;
+/// Retention routes.
+mod retention;
 /// Testing routes.
 #[cfg(feature = "testing")]
 mod testing;
@@ -45,6 +51,19 @@ where
     (as_response,)
 }
 
+/// Names the api version that the request was served with in the
+/// response.
+fn with_api_version_header<T>(version: u32, reply: T) -> (impl warp::Reply,)
+where
+    T: warp::Reply,
+{
+    (warp::reply::with_header(
+        reply,
+        version::API_VERSION_HEADER,
+        version.to_string(),
+    ),)
+}
+
 /// This function sets up the Warp filters for handling all requests.
 #[cfg(feature = "testing")]
 pub fn routes(
@@ -54,7 +73,7 @@ pub fn routes(
 
     // `.boxed()` erases the deeply nested filter type from multiple `.or()` calls,
     // making the return type manageable and preventing compilation errors and runtime stack overflows.
-    health::routes(context.clone())
+    let routes = health::routes(context.clone())
         .or(new_block::routes(context.clone()))
         .boxed()
         .or(chainstate::routes(context.clone()))
@@ -67,12 +86,19 @@ pub fn routes(
         .boxed()
         .or(throttle::routes(context.clone()))
         .boxed()
-        .or(testing::routes(context))
+        .or(api_key::routes(context.clone()))
         .boxed()
-        .or(verbose_not_found_route())
+        .or(retention::routes(context.clone()))
+        .boxed()
+        .or(testing::routes(context.clone()))
         .boxed()
-        // Convert reply to tuple to that more routes can be added to the returned filter.
-        .map(|reply| (reply,))
+        .or(verbose_not_found_route())
+        .boxed();
+
+    auth::with_authorization(context)
+        .and(version::with_api_version())
+        .and(routes)
+        .map(with_api_version_header)
         .map(log_response)
 }
 
@@ -83,7 +109,7 @@ pub fn routes(
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
     let context = warp::any().map(move || context.clone());
 
-    health::routes(context.clone())
+    let routes = health::routes(context.clone())
         .or(new_block::routes(context.clone()))
         .boxed()
         .or(chainstate::routes(context.clone()))
@@ -94,10 +120,17 @@ pub fn routes(
         .boxed()
         .or(limits::routes(context.clone()))
         .boxed()
-        .or(throttle::routes(context))
+        .or(throttle::routes(context.clone()))
+        .boxed()
+        .or(api_key::routes(context.clone()))
         .boxed()
-        // Convert reply to tuple to that more routes can be added to the returned filter.
-        .map(|reply| (reply,))
+        .or(retention::routes(context.clone()))
+        .boxed();
+
+    auth::with_authorization(context)
+        .and(version::with_api_version())
+        .and(routes)
+        .map(with_api_version_header)
         .map(log_response)
 }
 
@@ -169,6 +202,24 @@ fn with_context(
         if let Some(h) = get_header("x-context-throttle") {
             context.settings.throttle_table_name = h;
         }
+        if let Some(h) = get_header("x-context-api-key") {
+            context.settings.api_key_table_name = h;
+        }
+        if let Some(h) = get_header("x-context-deposit-archive") {
+            context.settings.deposit_archive_table_name = h;
+        }
+        if let Some(h) = get_header("x-context-withdrawal-archive") {
+            context.settings.withdrawal_archive_table_name = h;
+        }
+        if let Some(h) = get_header("x-context-archive-after-days") {
+            context.settings.archive_after_days = h.parse().ok();
+        }
+        if let Some(h) = get_header("x-context-purge-after-days") {
+            context.settings.purge_after_days = h.parse().ok();
+        }
+        if let Some(h) = get_header("x-context-enforce-api-key-scopes") {
+            context.settings.enforce_api_key_scopes = h == "true";
+        }
 
         context
     })
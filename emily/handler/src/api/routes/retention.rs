@@ -0,0 +1,34 @@
+//! Route definitions for the retention endpoint.
+
+use warp::Filter;
+
+use crate::context::EmilyContext;
+
+use super::handlers;
+
+/// Retention routes.
+pub fn routes<F>(
+    context: F,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone
+where
+    F: Filter<Extract = (EmilyContext,), Error = std::convert::Infallible>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+{
+    run_retention(context)
+}
+
+/// Run retention endpoint.
+fn run_retention<F>(
+    context: F,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone
+where
+    F: Filter<Extract = (EmilyContext,), Error = std::convert::Infallible> + Clone + Send,
+{
+    warp::path!("retention")
+        .and(warp::post())
+        .and(context)
+        .then(handlers::retention::run_retention)
+}
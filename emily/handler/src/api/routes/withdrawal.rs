@@ -17,6 +17,8 @@ where
         + 'static,
 {
     get_withdrawal(context.clone())
+        .or(get_withdrawal_history(context.clone()))
+        .boxed()
         .or(get_withdrawals(context.clone()))
         .boxed()
         .or(get_withdrawals_for_recipient(context.clone()))
@@ -43,6 +45,19 @@ where
         .then(handlers::withdrawal::get_withdrawal)
 }
 
+/// Get withdrawal history endpoint.
+fn get_withdrawal_history<F>(
+    context: F,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone
+where
+    F: Filter<Extract = (EmilyContext,), Error = std::convert::Infallible> + Clone + Send,
+{
+    warp::path!("withdrawal" / u64 / "history")
+        .and(warp::get())
+        .and(context)
+        .then(handlers::withdrawal::get_withdrawal_history)
+}
+
 /// Get withdrawals endpoint.
 fn get_withdrawals<F>(
     context: F,
@@ -0,0 +1,79 @@
+//! Api versioning for the Emily API.
+//!
+//! Clients name the versions of the API that they understand in the
+//! `Accept-Version` header, as a comma separated list of integers. Emily
+//! serves the highest of them that it supports and names the version that
+//! it served in the `Api-Version` header of the response. Requests without
+//! an `Accept-Version` header are served the current version, and requests
+//! that only name versions that Emily does not support are rejected.
+//!
+//! Request and response models may change in a backwards compatible way
+//! without a new version. Breaking changes bump [`API_VERSION`], and older
+//! versions remain supported until [`MIN_SUPPORTED_API_VERSION`] is raised
+//! past them.
+
+use warp::Filter;
+
+use crate::common::error::Error;
+
+/// The header that clients use to name the api versions that they
+/// understand.
+pub const ACCEPT_VERSION_HEADER: &str = "accept-version";
+
+/// The header that names the api version used to serve the request.
+pub const API_VERSION_HEADER: &str = "api-version";
+
+/// The current version of the Emily API.
+pub const API_VERSION: u32 = 1;
+
+/// The oldest version of the Emily API that is still served.
+pub const MIN_SUPPORTED_API_VERSION: u32 = 1;
+
+/// Returns the version of the api to serve a request with the given
+/// `Accept-Version` header.
+pub fn negotiate_api_version(accept_version: Option<&str>) -> Result<u32, Error> {
+    let Some(accept_version) = accept_version else {
+        return Ok(API_VERSION);
+    };
+
+    accept_version
+        .split(',')
+        .filter_map(|version| version.trim().parse::<u32>().ok())
+        .filter(|version| (MIN_SUPPORTED_API_VERSION..=API_VERSION).contains(version))
+        .max()
+        .ok_or_else(|| Error::UnsupportedApiVersion(accept_version.to_string()))
+}
+
+/// A filter that negotiates the api version of the request, rejecting it
+/// if none of the versions that it accepts are supported.
+pub fn with_api_version() -> impl Filter<Extract = (u32,), Error = warp::Rejection> + Clone {
+    warp::header::optional::<String>(ACCEPT_VERSION_HEADER).and_then(
+        |accept_version: Option<String>| async move {
+            negotiate_api_version(accept_version.as_deref()).map_err(warp::reject::custom)
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_case::test_case;
+
+    #[test_case(None, Some(API_VERSION); "no-header")]
+    #[test_case(Some("1"), Some(1); "current-version")]
+    #[test_case(Some(" 1 "), Some(1); "whitespace")]
+    #[test_case(Some("1, 2"), Some(1); "newer-versions-ignored")]
+    #[test_case(Some("0, 1"), Some(1); "older-versions-ignored")]
+    #[test_case(Some("2"), None; "only-newer-version")]
+    #[test_case(Some("0"), None; "only-older-version")]
+    #[test_case(Some("latest"), None; "not-a-number")]
+    #[test_case(Some(""), None; "empty")]
+    fn api_versions_are_negotiated(accept_version: Option<&str>, expected: Option<u32>) {
+        let version = negotiate_api_version(accept_version);
+        match expected {
+            Some(expected) => assert_eq!(version.unwrap(), expected),
+            None => assert!(matches!(version, Err(Error::UnsupportedApiVersion(_)))),
+        }
+    }
+}
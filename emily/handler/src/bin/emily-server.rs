@@ -84,7 +84,13 @@ async fn main() {
     let cors = warp::cors()
         .allow_any_origin()
         .allow_methods(vec!["GET", "POST", "OPTIONS"])
-        .allow_headers(vec!["content-type", "x-api-key"])
+        .allow_headers(vec![
+            "content-type",
+            "x-api-key",
+            "authorization",
+            "accept-version",
+        ])
+        .expose_headers(vec!["api-version"])
         .build();
 
     let routes = api::routes::routes(context)
@@ -84,6 +84,12 @@ pub enum ValidationError {
         "deposit with fulfillment data must be confirmed, but got status {0:?} for txid: {1}, vout: {2}"
     )]
     DepositFulfillmentNotConfirmed(DepositStatus, String, u32),
+
+    /// A deposit update had a deposit transaction proof that does not show that
+    /// the deposit transaction is included in the given bitcoin block, or had
+    /// only one of the block hash and the proof.
+    #[error("invalid deposit transaction proof for txid: {0}, vout: {1}")]
+    InvalidDepositTxProof(String, u32),
 }
 
 /// Errors from the internal API logic.
@@ -95,6 +101,11 @@ pub enum Error {
     #[error("HTTP request failed with status code {0}: {1}")]
     HttpRequest(StatusCode, String),
 
+    /// One or more fields of the request body failed validation. Each
+    /// entry says which field is invalid and why.
+    #[error("invalid request fields: {}", format_field_errors(.0))]
+    InvalidRequestFields(Vec<FieldValidationError>),
+
     /// Network error
     #[error("Network error: {0}")]
     Network(#[from] reqwest::Error),
@@ -107,10 +118,23 @@ pub enum Error {
     #[error("Unauthorized")]
     Unauthorized,
 
+    /// The caller has made too many requests with its api key.
+    #[error("Too many requests")]
+    TooManyRequests,
+
     /// Conflict.
     #[error("Conflict")]
     Conflict,
 
+    /// The request only accepts api versions that are not served. The
+    /// value is the `Accept-Version` header of the request.
+    #[error(
+        "Unsupported api version; accepted versions: {0}; supported versions: {min}-{max}",
+        min = crate::api::version::MIN_SUPPORTED_API_VERSION,
+        max = crate::api::version::API_VERSION,
+    )]
+    UnsupportedApiVersion(String),
+
     /// This may be because you either requested a nonexistent endpoint
     /// or referenced a user that does not exist
     #[error("Resource not found")]
@@ -205,6 +229,12 @@ pub enum Error {
     #[error("Could not read the environment variable; {0}")]
     EnvVariable(#[from] env::VarError),
 
+    /// This happens when an entry of the deposit confirmation policy
+    /// environment variable is not of the form
+    /// `<min_amount>:<confirmations>` with at least one confirmation.
+    #[error("Invalid deposit confirmation policy: {0}")]
+    InvalidDepositConfirmationPolicy(#[source] sbtc::error::Error),
+
     /// This occurs when serializing or deserializing an object into or
     /// from JSON.
     #[error("{0}")]
@@ -269,11 +299,14 @@ impl Error {
     pub fn status_code(&self) -> StatusCode {
         match self {
             Error::HttpRequest(code, _) => *code,
+            Error::InvalidRequestFields(_) => StatusCode::BAD_REQUEST,
             Error::Network(_) => StatusCode::BAD_GATEWAY,
             Error::Forbidden => StatusCode::FORBIDDEN,
             Error::Unauthorized => StatusCode::UNAUTHORIZED,
+            Error::TooManyRequests => StatusCode::TOO_MANY_REQUESTS,
             Error::NotFound => StatusCode::NOT_FOUND,
             Error::Conflict => StatusCode::CONFLICT,
+            Error::UnsupportedApiVersion(_) => StatusCode::NOT_ACCEPTABLE,
             Error::InternalServer => StatusCode::INTERNAL_SERVER_ERROR,
             Error::TooManyInternalRetries => StatusCode::INTERNAL_SERVER_ERROR,
             Error::InconsistentState(_) => StatusCode::INTERNAL_SERVER_ERROR,
@@ -290,6 +323,7 @@ impl Error {
             Error::TooManyWithdrawalEntries(_) => StatusCode::INTERNAL_SERVER_ERROR,
             Error::Base64Decode(_) => StatusCode::INTERNAL_SERVER_ERROR,
             Error::EnvVariable(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Error::InvalidDepositConfirmationPolicy(_) => StatusCode::INTERNAL_SERVER_ERROR,
             Error::SerdeJson(_) => StatusCode::INTERNAL_SERVER_ERROR,
             Error::SerdeDynamo(_) => StatusCode::INTERNAL_SERVER_ERROR,
             Error::EnvParseInt(_) => StatusCode::INTERNAL_SERVER_ERROR,
@@ -308,11 +342,17 @@ impl Error {
     }
     /// Converts the error into a warp response.
     pub fn into_response(self) -> warp::reply::Response {
-        warp::reply::with_status(
-            warp::reply::json(&ErrorResponse { message: format!("{self}") }),
-            self.status_code(),
-        )
-        .into_response()
+        let status = self.status_code();
+        let message = format!("{self}");
+        match self {
+            Error::InvalidRequestFields(errors) => warp::reply::with_status(
+                warp::reply::json(&ValidationErrorResponse { message, errors }),
+                status,
+            )
+            .into_response(),
+            _ => warp::reply::with_status(warp::reply::json(&ErrorResponse { message }), status)
+                .into_response(),
+        }
     }
     /// Convert error into a presentable version of the error that can be
     /// provided to a client in production.
@@ -322,14 +362,17 @@ impl Error {
             | Error::Forbidden
             | Error::NotFound
             | Error::Unauthorized
+            | Error::TooManyRequests
             | Error::Conflict
+            | Error::UnsupportedApiVersion(_)
             | Error::TooManyInternalRetries
             | Error::InconsistentState(_)
             | Error::WithdrawalRequestIdMismatch(_, _)
             | Error::MissingAttributesDeposit(_)
             | Error::MissingAttributesWithdrawal(_)
             | Error::TooManyWithdrawalEntries(_)
-            | Error::HttpRequest(_, _) => self,
+            | Error::HttpRequest(_, _)
+            | Error::InvalidRequestFields(_) => self,
 
             _ => Error::InternalServer,
         }
@@ -404,6 +447,69 @@ pub struct ErrorResponse {
     pub(crate) message: String,
 }
 
+/// A field of a request body that can fail validation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum RequestField {
+    /// The outpoint of the deposit, which is the bitcoin txid and the
+    /// output index.
+    Outpoint,
+    /// The deposit script.
+    DepositScript,
+    /// The reclaim script.
+    ReclaimScript,
+    /// The raw transaction hex.
+    TransactionHex,
+}
+
+impl std::fmt::Display for RequestField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let field = match self {
+            RequestField::Outpoint => "outpoint",
+            RequestField::DepositScript => "depositScript",
+            RequestField::ReclaimScript => "reclaimScript",
+            RequestField::TransactionHex => "transactionHex",
+        };
+        f.write_str(field)
+    }
+}
+
+/// A reason why a field of a request body failed validation.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldValidationError {
+    /// The field that failed validation.
+    pub field: RequestField,
+    /// Why the field failed validation.
+    pub message: String,
+}
+
+impl FieldValidationError {
+    /// Create a new validation error for the given field.
+    pub fn new(field: RequestField, message: impl Into<String>) -> Self {
+        Self { field, message: message.into() }
+    }
+}
+
+/// Join the field validation errors into a single message.
+fn format_field_errors(errors: &[FieldValidationError]) -> String {
+    errors
+        .iter()
+        .map(|error| format!("{}: {}", error.field, error.message))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Structure representing the response to a request whose body failed
+/// validation, with the reason that each invalid field failed validation.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct ValidationErrorResponse {
+    /// A summary of all of the validation errors.
+    pub(crate) message: String,
+    /// The validation error of each invalid field.
+    pub(crate) errors: Vec<FieldValidationError>,
+}
+
 /// Implement reject for error.
 impl Reject for Error {}
 
@@ -7,15 +7,18 @@
 
 use std::env;
 use std::fmt;
+use std::sync::Arc;
 
 use aws_config::BehaviorVersion;
 use aws_sdk_dynamodb::Client;
 use clarity::vm::types::PrincipalData;
 use clarity::vm::types::StandardPrincipalData;
+use sbtc::deposits::DepositConfirmationPolicy;
 use serde::Deserialize;
 use serde::Serialize;
 
-use crate::api::models::limits::AccountLimits;
+use crate::api::auth::{ApiKeyRateLimiter, VerifiedApiKeyCache};
+use crate::api::models::limits::{AccountLimits, DepositConfirmationTier};
 use crate::common::error::Error;
 
 /// Emily lambda settings.
@@ -33,6 +36,23 @@ pub struct Settings {
     pub throttle_table_name: String,
     /// Limit table name.
     pub limit_table_name: String,
+    /// Api key table name.
+    pub api_key_table_name: String,
+    /// Table that completed deposits are moved to once they are past the
+    /// retention window of the deposit table.
+    pub deposit_archive_table_name: String,
+    /// Table that completed withdrawals are moved to once they are past
+    /// the retention window of the withdrawal table.
+    pub withdrawal_archive_table_name: String,
+    /// The number of days after which a completed deposit or withdrawal is
+    /// moved into its archive table. Archiving is disabled when unset.
+    pub archive_after_days: Option<u64>,
+    /// The number of days after which an archived deposit or withdrawal is
+    /// deleted. Purging is disabled when unset.
+    pub purge_after_days: Option<u64>,
+    /// Whether every request, other than health checks, must carry an api
+    /// key with the scope needed for the request.
+    pub enforce_api_key_scopes: bool,
     /// The default global limits for the system.
     pub default_limits: AccountLimits,
     /// Whether the lambda is expecting transactions on mainnet.
@@ -41,6 +61,10 @@ pub struct Settings {
     pub version: String,
     /// The address of the deployer of the sBTC smart contracts.
     pub deployer_address: StandardPrincipalData,
+    /// The deposit confirmation policy used by the signers, reported
+    /// alongside the limits so that users can estimate when their deposit
+    /// will be swept.
+    pub deposit_confirmation_policy: Vec<DepositConfirmationTier>,
 }
 
 /// Emily Context
@@ -51,6 +75,12 @@ pub struct EmilyContext {
     /// DynamoDB Client.
     #[serde(skip_serializing)]
     pub dynamodb_client: Client,
+    /// Per api key request counters used for rate limiting.
+    #[serde(skip_serializing)]
+    pub rate_limiter: Arc<ApiKeyRateLimiter>,
+    /// The api key secrets that have already been verified.
+    #[serde(skip_serializing)]
+    pub verified_api_keys: Arc<VerifiedApiKeyCache>,
 }
 
 /// Implement debug print for the context struct.
@@ -69,6 +99,21 @@ impl fmt::Debug for EmilyContext {
             )
             .field("limit_table_name", &self.settings.limit_table_name)
             .field("throttle_table_name", &self.settings.throttle_table_name)
+            .field("api_key_table_name", &self.settings.api_key_table_name)
+            .field(
+                "deposit_archive_table_name",
+                &self.settings.deposit_archive_table_name,
+            )
+            .field(
+                "withdrawal_archive_table_name",
+                &self.settings.withdrawal_archive_table_name,
+            )
+            .field("archive_after_days", &self.settings.archive_after_days)
+            .field("purge_after_days", &self.settings.purge_after_days)
+            .field(
+                "enforce_api_key_scopes",
+                &self.settings.enforce_api_key_scopes,
+            )
             .field("default_limits", &self.settings.default_limits)
             .field("is_mainnet", &self.settings.is_mainnet)
             .field("version", &self.settings.version)
@@ -76,6 +121,10 @@ impl fmt::Debug for EmilyContext {
                 "deployer_address",
                 &self.settings.deployer_address.to_string(),
             )
+            .field(
+                "deposit_confirmation_policy",
+                &self.settings.deposit_confirmation_policy,
+            )
             .finish()
     }
 }
@@ -89,6 +138,18 @@ impl Settings {
         let deployer_address = env::var("DEPLOYER_ADDRESS")?;
         let deployer_address = PrincipalData::parse_standard_principal(&deployer_address)
             .map_err(|source| Error::InvalidStacksAddress(Box::new(source)))?;
+        // The policy is parsed the same way the signers parse theirs, from
+        // a comma separated list of `<min_amount>:<confirmations>`.
+        let deposit_confirmation_policy = env::var("DEPOSIT_CONFIRMATION_POLICY")
+            .ok()
+            .map(|policy| policy.parse::<DepositConfirmationPolicy>())
+            .transpose()
+            .map_err(Error::InvalidDepositConfirmationPolicy)?
+            .unwrap_or_default()
+            .tiers()
+            .iter()
+            .map(DepositConfirmationTier::from)
+            .collect();
 
         Ok(Settings {
             is_local: env::var("IS_LOCAL")?.to_lowercase() == "true",
@@ -97,6 +158,19 @@ impl Settings {
             chainstate_table_name: env::var("CHAINSTATE_TABLE_NAME")?,
             limit_table_name: env::var("LIMIT_TABLE_NAME")?,
             throttle_table_name: env::var("THROTTLEDOWN_TABLE_NAME")?,
+            api_key_table_name: env::var("API_KEY_TABLE_NAME")?,
+            deposit_archive_table_name: env::var("DEPOSIT_ARCHIVE_TABLE_NAME")?,
+            withdrawal_archive_table_name: env::var("WITHDRAWAL_ARCHIVE_TABLE_NAME")?,
+            archive_after_days: env::var("ARCHIVE_AFTER_DAYS")
+                .ok()
+                .map(|v| v.parse())
+                .transpose()?,
+            purge_after_days: env::var("PURGE_AFTER_DAYS")
+                .ok()
+                .map(|v| v.parse())
+                .transpose()?,
+            enforce_api_key_scopes: env::var("ENFORCE_API_KEY_SCOPES")
+                .is_ok_and(|v| v.to_lowercase() == "true"),
             default_limits: AccountLimits {
                 peg_cap: env::var("DEFAULT_PEG_CAP")
                     .ok()
@@ -127,6 +201,7 @@ impl Settings {
             is_mainnet: env::var("IS_MAINNET")?.to_lowercase() == "true",
             version: env::var("VERSION")?,
             deployer_address,
+            deposit_confirmation_policy,
         })
     }
 }
@@ -152,6 +227,8 @@ impl EmilyContext {
         Ok(EmilyContext {
             settings,
             dynamodb_client: Client::new(&config),
+            rate_limiter: Arc::default(),
+            verified_api_keys: Arc::default(),
         })
     }
     /// Create a local testing instance.
@@ -167,8 +244,16 @@ impl EmilyContext {
             .build();
         let dynamodb_client = Client::new(&sdk_config);
 
-        let tables_to_find: Vec<&str> =
-            vec!["Deposit", "Chainstate", "Withdrawal", "Limit", "Throttle"];
+        let tables_to_find: Vec<&str> = vec![
+            "Deposit",
+            "Chainstate",
+            "Withdrawal",
+            "Limit",
+            "Throttle",
+            "ApiKey",
+            "DepositArchive",
+            "WithdrawalArchive",
+        ];
         let mut table_name_map: HashMap<&str, String> = HashMap::new();
 
         if skip_tables {
@@ -188,12 +273,15 @@ impl EmilyContext {
                 .unwrap_or_default();
 
             // Attempt to get all the tables by searching the output of the
-            // list tables operation.
+            // list tables operation. The archive table names contain the
+            // names of the tables they archive, so the longest match wins.
             for name in table_names {
-                for table_to_find in &tables_to_find {
-                    if name.contains(table_to_find) {
-                        table_name_map.insert(table_to_find, name.clone());
-                    }
+                let table_to_find = tables_to_find
+                    .iter()
+                    .filter(|table_to_find| name.contains(*table_to_find))
+                    .max_by_key(|table_to_find| table_to_find.len());
+                if let Some(table_to_find) = table_to_find {
+                    table_name_map.insert(table_to_find, name.clone());
                 }
             }
         }
@@ -222,6 +310,21 @@ impl EmilyContext {
                     .get("Throttle")
                     .expect("Couldn't find valid throttle table table in existing table list.")
                     .to_string(),
+                api_key_table_name: table_name_map
+                    .get("ApiKey")
+                    .expect("Couldn't find valid api key table in existing table list.")
+                    .to_string(),
+                deposit_archive_table_name: table_name_map
+                    .get("DepositArchive")
+                    .expect("Couldn't find valid deposit archive table in existing table list.")
+                    .to_string(),
+                withdrawal_archive_table_name: table_name_map
+                    .get("WithdrawalArchive")
+                    .expect("Couldn't find valid withdrawal archive table in existing table list.")
+                    .to_string(),
+                archive_after_days: None,
+                purge_after_days: None,
+                enforce_api_key_scopes: false,
                 default_limits: AccountLimits::default(),
                 is_mainnet: false,
                 version: "local-instance".to_string(),
@@ -229,8 +332,11 @@ impl EmilyContext {
                     "SN3R84XZYA63QS28932XQF3G1J8R9PC3W76P9CSQS",
                 )
                 .unwrap(),
+                deposit_confirmation_policy: Vec::new(),
             },
             dynamodb_client,
+            rate_limiter: Arc::default(),
+            verified_api_keys: Arc::default(),
         })
     }
 }
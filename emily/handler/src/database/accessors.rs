@@ -2,7 +2,7 @@
 
 use argon2::{
     Argon2,
-    password_hash::{PasswordHasher as _, SaltString},
+    password_hash::{self, PasswordHash, PasswordHasher as _, PasswordVerifier as _, SaltString},
 };
 
 use aws_sdk_dynamodb::types::AttributeValue;
@@ -19,10 +19,11 @@ use crate::common::error::{Error, Inconsistency};
 use super::entries::deposit::{
     DepositInfoByRecipientEntry, DepositInfoByReclaimPubkeysEntry,
     DepositTableByRecipientSecondaryIndex, DepositTableByReclaimPubkeysSecondaryIndex,
-    ValidatedDepositUpdate,
+    DepositTxProof, ValidatedDepositUpdate,
 };
 use super::entries::limits::{
-    GLOBAL_CAP_ACCOUNT, LimitEntry, LimitEntryKey, LimitTablePrimaryIndex,
+    AVAILABLE_TO_MINT_ACCOUNT, GLOBAL_CAP_ACCOUNT, LimitEntry, LimitEntryKey,
+    LimitTablePrimaryIndex,
 };
 use super::entries::withdrawal::{
     ValidatedWithdrawalUpdate, WithdrawalInfoByRecipientEntry, WithdrawalInfoBySenderEntry,
@@ -36,14 +37,17 @@ use super::entries::{
         ChainstateTablePrimaryIndex, SpecialApiStateIndex,
     },
     deposit::{
-        DepositEntry, DepositEntryKey, DepositInfoEntry, DepositTablePrimaryIndex,
-        DepositTableSecondaryIndex, DepositUpdatePackage,
+        DepositArchivePrimaryIndex, DepositArchiveSecondaryIndex, DepositEntry, DepositEntryKey,
+        DepositInfoEntry, DepositTablePrimaryIndex, DepositTableSecondaryIndex,
+        DepositUpdatePackage,
     },
     withdrawal::{
-        WithdrawalEntry, WithdrawalInfoEntry, WithdrawalTablePrimaryIndex,
-        WithdrawalTableSecondaryIndex, WithdrawalUpdatePackage,
+        WithdrawalArchivePrimaryIndex, WithdrawalArchiveSecondaryIndex, WithdrawalEntry,
+        WithdrawalInfoEntry, WithdrawalTablePrimaryIndex, WithdrawalTableSecondaryIndex,
+        WithdrawalUpdatePackage,
     },
 };
+use crate::database::entries::api_key::{ApiKeyEntry, ApiKeyTablePrimaryIndex};
 use crate::database::entries::throttle::{
     ThrottleKeyEntry, ThrottleKeyEntryKey, ThrottleTablePrimaryIndex,
 };
@@ -92,18 +96,32 @@ pub async fn get_deposit_entry(
     Ok(entry)
 }
 
+/// Get deposit entry, falling back to the deposit archive table if the
+/// deposit is not in the deposit table.
+pub async fn get_deposit_entry_or_archived(
+    context: &EmilyContext,
+    key: &DepositEntryKey,
+) -> Result<DepositEntry, Error> {
+    match get_deposit_entry(context, key).await {
+        Err(Error::NotFound) => get_entry::<DepositArchivePrimaryIndex>(context, key).await,
+        result => result,
+    }
+}
+
 /// Get deposit entries.
 pub async fn get_deposit_entries(
     context: &EmilyContext,
     status: &DepositStatus,
     maybe_next_token: Option<String>,
     maybe_page_size: Option<u16>,
+    include_archived: bool,
 ) -> Result<(Vec<DepositInfoEntry>, Option<String>), Error> {
-    query_with_partition_key::<DepositTableSecondaryIndex>(
+    query_with_partition_key_and_archive::<DepositTableSecondaryIndex, DepositArchiveSecondaryIndex>(
         context,
         status,
         maybe_next_token,
         maybe_page_size,
+        include_archived,
     )
     .await
 }
@@ -179,6 +197,23 @@ pub async fn get_all_deposit_entries_modified_from_height_with_status(
     .await
 }
 
+/// Counts the deposit entries with the given status that were last
+/// updated below the given height, without reading the entries.
+pub async fn count_deposit_entries_with_status_below_height(
+    context: &EmilyContext,
+    status: &DepositStatus,
+    height: u64,
+) -> Result<u64, Error> {
+    <DepositTableSecondaryIndex as TableIndexTrait>::count_with_partition_and_sort_key(
+        &context.dynamodb_client,
+        &context.settings,
+        status,
+        &height,
+        "<",
+    )
+    .await
+}
+
 /// Get deposit entries for a given transaction.
 pub async fn get_deposit_entries_for_transaction(
     context: &EmilyContext,
@@ -210,7 +245,24 @@ pub async fn pull_and_update_deposit_with_retry(
     let mut err = ConditionalCheckFailedException::builder().build();
     for _ in 0..retries {
         // Get original deposit entry.
-        let deposit_entry = get_deposit_entry(context, &update.key).await?;
+        let mut deposit_entry = get_deposit_entry(context, &update.key).await?;
+        // Attach the proof that the deposit transaction was confirmed on
+        // bitcoin if it is new. Proofs are validated along with the rest of
+        // the update, so signers may attach them regardless of the status.
+        if let Some(proof) = update.new_tx_proof_for(&deposit_entry) {
+            match set_deposit_tx_proof(context, &deposit_entry, proof).await {
+                Ok(entry) => deposit_entry = entry,
+                Err(Error::VersionConflict(error)) => {
+                    warn!(%error, "received an error when attaching a deposit transaction proof");
+                    err = *error;
+                    continue;
+                }
+                Err(error) => return Err(error),
+            }
+        }
+        if update.is_tx_proof_only() {
+            return Ok(deposit_entry);
+        }
         // Return the existing entry if no update is necessary.
         if update.is_unnecessary(&deposit_entry) {
             return Ok(deposit_entry);
@@ -303,6 +355,51 @@ pub async fn update_deposit(
         })
 }
 
+/// Attaches the proof that the deposit transaction was confirmed on bitcoin
+/// to a deposit, without changing its status or history.
+pub async fn set_deposit_tx_proof(
+    context: &EmilyContext,
+    entry: &DepositEntry,
+    proof: &DepositTxProof,
+) -> Result<DepositEntry, Error> {
+    // Setup the update procedure.
+    let update_expression: &str = " SET
+        Version = Version + :one,
+        DepositBlockHash = :block_hash,
+        DepositTxProof = :proof
+    ";
+    // Ensure the version field is what we expect it to be.
+    let condition_expression = "attribute_exists(Version) AND Version = :expected_version";
+    // Make the key item.
+    let key_item: Item = serde_dynamo::to_item(&entry.key)?;
+    // Build the update.
+    context
+        .dynamodb_client
+        .update_item()
+        .table_name(&context.settings.deposit_table_name)
+        .set_key(Some(key_item.into()))
+        .expression_attribute_values(
+            ":block_hash",
+            serde_dynamo::to_attribute_value(&proof.block_hash)?,
+        )
+        .expression_attribute_values(":proof", serde_dynamo::to_attribute_value(&proof.proof)?)
+        .expression_attribute_values(
+            ":expected_version",
+            serde_dynamo::to_attribute_value(entry.version)?,
+        )
+        .expression_attribute_values(":one", AttributeValue::N(1.to_string()))
+        .condition_expression(condition_expression)
+        .return_values(aws_sdk_dynamodb::types::ReturnValue::AllNew)
+        .update_expression(update_expression)
+        .send()
+        .await?
+        .attributes
+        .ok_or(Error::MissingAttributesDeposit(entry.key.clone()))
+        .and_then(|attributes| {
+            serde_dynamo::from_item::<Item, DepositEntry>(attributes.into()).map_err(Error::from)
+        })
+}
+
 // Withdrawal ------------------------------------------------------------------
 
 /// Add withdrawal entry.
@@ -341,15 +438,37 @@ pub async fn get_withdrawal_entry(
     context: &EmilyContext,
     key: &u64,
 ) -> Result<WithdrawalEntry, Error> {
+    get_withdrawal_entry_from::<WithdrawalTablePrimaryIndex>(context, key).await
+}
+
+/// Get withdrawal entry, falling back to the withdrawal archive table if
+/// the withdrawal is not in the withdrawal table.
+pub async fn get_withdrawal_entry_or_archived(
+    context: &EmilyContext,
+    key: &u64,
+) -> Result<WithdrawalEntry, Error> {
+    match get_withdrawal_entry(context, key).await {
+        Err(Error::NotFound) => {
+            get_withdrawal_entry_from::<WithdrawalArchivePrimaryIndex>(context, key).await
+        }
+        result => result,
+    }
+}
+
+/// Get the canonical withdrawal entry with the given request id from the
+/// table of the given index.
+async fn get_withdrawal_entry_from<T>(
+    context: &EmilyContext,
+    key: &u64,
+) -> Result<WithdrawalEntry, Error>
+where
+    T: TableIndexTrait<Entry = WithdrawalEntry>,
+{
     // Get the entries.
     let num_to_retrieve_if_multiple = 3;
-    let (entries, _) = query_with_partition_key::<WithdrawalTablePrimaryIndex>(
-        context,
-        key,
-        None,
-        Some(num_to_retrieve_if_multiple),
-    )
-    .await?;
+    let (entries, _) =
+        query_with_partition_key::<T>(context, key, None, Some(num_to_retrieve_if_multiple))
+            .await?;
     // Return.
     match entries.as_slice() {
         [] => Err(Error::NotFound),
@@ -390,12 +509,17 @@ pub async fn get_withdrawal_entries(
     status: &WithdrawalStatus,
     maybe_next_token: Option<String>,
     maybe_page_size: Option<u16>,
+    include_archived: bool,
 ) -> Result<(Vec<WithdrawalInfoEntry>, Option<String>), Error> {
-    query_with_partition_key::<WithdrawalTableSecondaryIndex>(
+    query_with_partition_key_and_archive::<
+        WithdrawalTableSecondaryIndex,
+        WithdrawalArchiveSecondaryIndex,
+    >(
         context,
         status,
         maybe_next_token,
         maybe_page_size,
+        include_archived,
     )
     .await
 }
@@ -487,6 +611,14 @@ pub async fn pull_and_update_withdrawal_with_retry(
     for _ in 0..retries {
         // Get original withdrawal entry.
         let entry = get_withdrawal_entry(context, &update.request_id).await?;
+        // Signers cannot confirm withdrawals themselves, but they may attach
+        // the proof of the sweep transaction to a confirmed withdrawal.
+        let proof_update = if is_trusted_key {
+            None
+        } else {
+            update.as_fulfillment_proof_for(&entry)?
+        };
+        let update = proof_update.as_ref().unwrap_or(&update);
         // Return the existing entry if no update is necessary.
         if update.is_unnecessary(&entry) {
             return Ok(entry);
@@ -499,9 +631,10 @@ pub async fn pull_and_update_withdrawal_with_retry(
         {
             return Ok(entry);
         }
-        let is_valid_untrusted_status_update = update.event.status
+        let is_valid_untrusted_status_update = (update.event.status
             == WithdrawalStatusEntry::Accepted
-            && entry.status == WithdrawalStatus::Pending;
+            && entry.status == WithdrawalStatus::Pending)
+            || proof_update.is_some();
         if !is_trusted_key && !is_valid_untrusted_status_update {
             return Err(Error::Forbidden);
         }
@@ -536,7 +669,8 @@ pub async fn update_withdrawal(
         Version = Version + :one,
         OpStatus = :new_op_status,
         LastUpdateHeight = :new_height,
-        LastUpdateBlockHash = :new_hash
+        LastUpdateBlockHash = :new_hash,
+        Fulfillment = :new_fulfillment
     ";
     // Ensure the version field is what we expect it to be.
     let condition_expression = "attribute_exists(Version) AND Version = :expected_version";
@@ -544,6 +678,7 @@ pub async fn update_withdrawal(
     let key_item: Item = serde_dynamo::to_item(&update.key)?;
     // Get simplified status enum.
     let status: WithdrawalStatus = (&update.event.status).into();
+    let fulfillment = update.event.status.fulfillment();
     // Execute the update.
     context
         .dynamodb_client
@@ -563,6 +698,10 @@ pub async fn update_withdrawal(
             ":new_event",
             serde_dynamo::to_attribute_value(vec![update.event.clone()])?,
         )
+        .expression_attribute_values(
+            ":new_fulfillment",
+            serde_dynamo::to_attribute_value(fulfillment)?,
+        )
         .expression_attribute_values(
             ":expected_version",
             serde_dynamo::to_attribute_value(update.version)?,
@@ -808,12 +947,18 @@ async fn get_oldest_stacks_block_in_range(
     Err(Error::NotFound)
 }
 
+/// Calculates the amount of sBTC still available for withdrawals in the
+/// rolling withdrawal window, along with the number of bitcoin blocks until
+/// some of it is available again.
+///
+/// The number of blocks is zero when some sBTC is available now, and `None`
+/// when it never will be because the rolling withdrawal cap is zero.
 #[tracing::instrument(skip(context))]
 async fn calculate_sbtc_left_for_withdrawals(
     context: &EmilyContext,
     rolling_withdrawal_blocks: Option<u64>,
     rolling_withdrawal_cap: Option<u64>,
-) -> Result<Option<u64>, Error> {
+) -> Result<Option<(u64, Option<u64>)>, Error> {
     let (Some(rolling_withdrawal_blocks), Some(rolling_withdrawal_cap)) =
         (rolling_withdrawal_blocks, rolling_withdrawal_cap)
     else {
@@ -834,6 +979,8 @@ async fn calculate_sbtc_left_for_withdrawals(
         .collect();
 
     let mut total_withdrawn = 0u64;
+    // The stacks height of the last update of each withdrawal, and its amount.
+    let mut withdrawn: Vec<(u64, u64)> = Vec::new();
     for status in all_statuses_except_failed {
         let withdrawals = get_all_withdrawal_entries_modified_from_height_with_status(
             context,
@@ -845,9 +992,35 @@ async fn calculate_sbtc_left_for_withdrawals(
         tracing::info!("Withdrawal entries retrieved successfully");
         for withdrawal in withdrawals {
             total_withdrawn = total_withdrawn.saturating_add(withdrawal.amount);
+            withdrawn.push((withdrawal.key.last_update_height, withdrawal.amount));
         }
     }
-    Ok(Some(rolling_withdrawal_cap.saturating_sub(total_withdrawn)))
+    let available = rolling_withdrawal_cap.saturating_sub(total_withdrawn);
+    if available > 0 {
+        return Ok(Some((available, Some(0))));
+    }
+
+    // Withdrawals leave the window, oldest first, once the bitcoin tip is a
+    // full window above the bitcoin block anchoring their last update.
+    withdrawn.sort_unstable();
+    let mut remaining = total_withdrawn;
+    let frees_headroom = withdrawn.iter().find(|(_, amount)| {
+        remaining = remaining.saturating_sub(*amount);
+        remaining < rolling_withdrawal_cap
+    });
+    let Some((stacks_height, _)) = frees_headroom else {
+        return Ok(Some((available, None)));
+    };
+    let anchor_height = get_chainstate_entry_at_height(context, stacks_height)
+        .await?
+        .bitcoin_height
+        .ok_or(Error::NotFound)?;
+    let blocks_until_headroom = anchor_height
+        .saturating_add(rolling_withdrawal_blocks.max(1))
+        .saturating_sub(bitcoin_tip)
+        .max(1);
+
+    Ok(Some((available, Some(blocks_until_headroom))))
 }
 
 /// Note, this function provides the direct output structure for the api call
@@ -877,7 +1050,10 @@ pub async fn get_limits(context: &EmilyContext) -> Result<Limits, Error> {
         rolling_withdrawal_blocks: default_global_cap.rolling_withdrawal_blocks,
         rolling_withdrawal_cap: default_global_cap.rolling_withdrawal_cap,
         throttle_mode_initiator: default_global_cap.throttle_mode_initiator,
+        available_to_mint: None,
     };
+    // The latest amount of sBTC that the signers reported can be minted.
+    let mut available_to_mint: Option<&LimitEntry> = None;
 
     // Aggregate all the latest entries by account.
     let mut limit_by_account: HashMap<String, LimitEntry> = HashMap::new();
@@ -890,6 +1066,10 @@ pub async fn get_limits(context: &EmilyContext) -> Result<Limits, Error> {
             if global_cap.key.timestamp < entry.key.timestamp {
                 global_cap = entry.clone();
             }
+        } else if account == AVAILABLE_TO_MINT_ACCOUNT {
+            if available_to_mint.is_none_or(|latest| latest.key.timestamp < entry.key.timestamp) {
+                available_to_mint = Some(entry);
+            }
         } else if limit_by_account.contains_key(account) {
             // If the account is already in the map then update the entry if the current
             // entry is newer.
@@ -912,21 +1092,27 @@ pub async fn get_limits(context: &EmilyContext) -> Result<Limits, Error> {
 
     // Calculate total withdrawn amount.
 
-    let available_to_withdraw = calculate_sbtc_left_for_withdrawals(
-        context,
-        global_cap.rolling_withdrawal_blocks,
-        global_cap.rolling_withdrawal_cap,
-    )
-    .await
-    .inspect_err(|error| {
-        warn!(%error, "calculate_sbtc_left_for_withdrawals did not return successfully");
-    })
-    .ok()
-    .flatten();
+    let (available_to_withdraw, blocks_until_withdrawal_headroom) =
+        calculate_sbtc_left_for_withdrawals(
+            context,
+            global_cap.rolling_withdrawal_blocks,
+            global_cap.rolling_withdrawal_cap,
+        )
+        .await
+        .inspect_err(|error| {
+            warn!(%error, "calculate_sbtc_left_for_withdrawals did not return successfully");
+        })
+        .ok()
+        .flatten()
+        .map_or((None, None), |(available, blocks)| {
+            (Some(available), blocks)
+        });
 
     // Get the global limit for the whole thing.
     Ok(Limits {
+        available_to_mint: available_to_mint.and_then(|entry| entry.available_to_mint),
         available_to_withdraw,
+        blocks_until_withdrawal_headroom,
         peg_cap: global_cap.peg_cap,
         per_deposit_minimum: global_cap.per_deposit_minimum,
         per_deposit_cap: global_cap.per_deposit_cap,
@@ -935,6 +1121,7 @@ pub async fn get_limits(context: &EmilyContext) -> Result<Limits, Error> {
         rolling_withdrawal_cap: global_cap.rolling_withdrawal_cap,
         account_caps,
         throttle_mode_initiator: global_cap.throttle_mode_initiator,
+        deposit_confirmation_policy: context.settings.deposit_confirmation_policy.clone(),
     })
 }
 
@@ -1096,6 +1283,250 @@ pub async fn verify_throttle_key(
     Ok(KeyVerificationResult::Eligible(key.name))
 }
 
+// Api keys --------------------------------------------------------------------
+
+/// Hash the secret of an api key. The name of the key is used as the salt,
+/// so that the same secret used for two keys results in different hashes.
+pub fn hash_api_key_secret(name: &str, secret: &str) -> Result<String, Error> {
+    let salt = name_to_salt(name)?;
+    let hash = Argon2::default()
+        .hash_password(secret.as_bytes(), &salt)
+        .inspect_err(|error| warn!(%error, name, "Failed to hash the secret of an api key"))
+        .map_err(|_| Error::InternalServer)?
+        .to_string();
+    Ok(hash)
+}
+
+/// Check the secret of an api key against the stored hash of the key. The
+/// hashes are compared in constant time.
+fn verify_api_key_secret(name: &str, stored_hash: &str, secret: &str) -> Result<bool, Error> {
+    let hash = PasswordHash::new(stored_hash)
+        .inspect_err(|error| warn!(%error, name, "Failed to parse the stored hash of an api key"))
+        .map_err(|_| Error::InternalServer)?;
+    match Argon2::default().verify_password(secret.as_bytes(), &hash) {
+        Ok(()) => Ok(true),
+        Err(password_hash::Error::Password) => Ok(false),
+        Err(error) => {
+            warn!(%error, name, "Failed to verify the secret of an api key");
+            Err(Error::InternalServer)
+        }
+    }
+}
+
+/// Get the current state of the api key with the given name.
+pub async fn get_api_key(context: &EmilyContext, name: &String) -> Result<ApiKeyEntry, Error> {
+    let (mut entries, _) = query_with_partition_key::<ApiKeyTablePrimaryIndex>(
+        context,
+        name,
+        None,
+        // Only get the most recent entry. The internals of this query uses
+        // scan_index_forward = false.
+        Some(1),
+    )
+    .await?;
+    entries.sort_by_key(|entry| entry.key.updated_at);
+    entries.pop().ok_or(Error::NotFound)
+}
+
+/// Add a new api key. Fails if a key with the same name already exists.
+pub async fn add_api_key(context: &EmilyContext, entry: &ApiKeyEntry) -> Result<(), Error> {
+    match get_api_key(context, &entry.key.name).await {
+        Ok(_) => {
+            warn!(name = %entry.key.name, "Attempt to insert duplicate api key");
+            Err(Error::Conflict)
+        }
+        Err(Error::NotFound) => put_entry::<ApiKeyTablePrimaryIndex>(context, entry).await,
+        Err(error) => Err(error),
+    }
+}
+
+/// Write a new state for an existing api key.
+pub async fn update_api_key(context: &EmilyContext, entry: &ApiKeyEntry) -> Result<(), Error> {
+    put_entry::<ApiKeyTablePrimaryIndex>(context, entry).await
+}
+
+/// The outcome of checking the api key presented with a request.
+pub enum ApiKeyVerificationResult {
+    /// The key is known and active, and the secret matches.
+    Valid(ApiKeyEntry),
+    /// The key is known and the secret matches, but it has been revoked.
+    Revoked,
+    /// The key is unknown or the secret does not match.
+    Invalid,
+}
+
+/// Verify the given api key name and secret.
+pub async fn verify_api_key(
+    context: &EmilyContext,
+    name: &str,
+    secret: &str,
+) -> Result<ApiKeyVerificationResult, Error> {
+    let key = match get_api_key(context, &name.to_string()).await {
+        Ok(key) => key,
+        Err(Error::NotFound) => return Ok(ApiKeyVerificationResult::Invalid),
+        Err(error) => return Err(error),
+    };
+    // Checking a secret with argon2 is deliberately slow, so secrets that
+    // have already been verified against the stored hash are remembered.
+    let cache = &context.verified_api_keys;
+    if !cache.contains(name, &key.hash, secret) {
+        if !verify_api_key_secret(name, &key.hash, secret)? {
+            return Ok(ApiKeyVerificationResult::Invalid);
+        }
+        cache.insert(name, &key.hash, secret);
+    }
+    if !key.is_active {
+        return Ok(ApiKeyVerificationResult::Revoked);
+    }
+    Ok(ApiKeyVerificationResult::Valid(key))
+}
+
+// Retention -------------------------------------------------------------------
+
+/// Deposit statuses that are final, so deposits with them can be archived.
+const COMPLETED_DEPOSIT_STATUSES: [DepositStatus; 3] = [
+    DepositStatus::Confirmed,
+    DepositStatus::Failed,
+    DepositStatus::Rbf,
+];
+
+/// Withdrawal statuses that are final, so withdrawals with them can be
+/// archived.
+const COMPLETED_WITHDRAWAL_STATUSES: [WithdrawalStatus; 2] =
+    [WithdrawalStatus::Confirmed, WithdrawalStatus::Failed];
+
+/// Returns the height of the oldest stacks block anchored to the highest
+/// bitcoin block at or below the given bitcoin height, looking at no more
+/// than `search_depth` bitcoin blocks. Every stacks block below the
+/// returned height is anchored to a bitcoin block below the given height.
+pub async fn get_oldest_stacks_block_at_or_below(
+    context: &EmilyContext,
+    bitcoin_height: u64,
+    search_depth: u64,
+) -> Result<u64, Error> {
+    let lowest_height = bitcoin_height.saturating_sub(search_depth);
+    for height in (lowest_height..=bitcoin_height).rev() {
+        match get_oldest_stacks_block_for_bitcoin_block(context, height).await {
+            Err(Error::NotFound) => continue,
+            result => return result,
+        }
+    }
+    Err(Error::NotFound)
+}
+
+/// Moves up to `limit` completed deposits that were last updated below the
+/// given stacks height from the deposit table into the archive table.
+///
+/// Returns the number of deposits that were moved and whether there are
+/// more deposits left to move.
+pub async fn archive_deposit_entries(
+    context: &EmilyContext,
+    before_stacks_height: u64,
+    limit: u16,
+) -> Result<(usize, bool), Error> {
+    let (entries, more_remaining) = query_last_updated_before::<DepositTableSecondaryIndex>(
+        context,
+        &COMPLETED_DEPOSIT_STATUSES,
+        before_stacks_height,
+        limit,
+    )
+    .await?;
+    let mut archived = 0;
+    for entry in entries {
+        // The secondary index is eventually consistent, so the deposit may
+        // already have been moved by an earlier run.
+        let deposit = match get_deposit_entry(context, &entry.primary_index_key).await {
+            Err(Error::NotFound) => continue,
+            result => result?,
+        };
+        put_entry::<DepositArchivePrimaryIndex>(context, &deposit).await?;
+        delete_entry::<DepositTablePrimaryIndex>(context, &deposit.key).await?;
+        archived += 1;
+    }
+    Ok((archived, more_remaining))
+}
+
+/// Deletes up to `limit` archived deposits that were last updated below the
+/// given stacks height.
+///
+/// Returns the number of deposits that were deleted and whether there are
+/// more deposits left to delete.
+pub async fn purge_archived_deposit_entries(
+    context: &EmilyContext,
+    before_stacks_height: u64,
+    limit: u16,
+) -> Result<(usize, bool), Error> {
+    let (entries, more_remaining) = query_last_updated_before::<DepositArchiveSecondaryIndex>(
+        context,
+        &COMPLETED_DEPOSIT_STATUSES,
+        before_stacks_height,
+        limit,
+    )
+    .await?;
+    for entry in &entries {
+        delete_entry::<DepositArchivePrimaryIndex>(context, &entry.primary_index_key).await?;
+    }
+    Ok((entries.len(), more_remaining))
+}
+
+/// Moves up to `limit` completed withdrawals that were last updated below
+/// the given stacks height from the withdrawal table into the archive
+/// table.
+///
+/// Returns the number of withdrawals that were moved and whether there are
+/// more withdrawals left to move.
+pub async fn archive_withdrawal_entries(
+    context: &EmilyContext,
+    before_stacks_height: u64,
+    limit: u16,
+) -> Result<(usize, bool), Error> {
+    let (entries, more_remaining) = query_last_updated_before::<WithdrawalTableSecondaryIndex>(
+        context,
+        &COMPLETED_WITHDRAWAL_STATUSES,
+        before_stacks_height,
+        limit,
+    )
+    .await?;
+    let mut archived = 0;
+    for entry in entries {
+        // The secondary index is eventually consistent, so the withdrawal
+        // may already have been moved by an earlier run.
+        let withdrawal =
+            match get_entry::<WithdrawalTablePrimaryIndex>(context, &entry.primary_index_key).await
+            {
+                Err(Error::NotFound) => continue,
+                result => result?,
+            };
+        put_entry::<WithdrawalArchivePrimaryIndex>(context, &withdrawal).await?;
+        delete_entry::<WithdrawalTablePrimaryIndex>(context, &withdrawal.key).await?;
+        archived += 1;
+    }
+    Ok((archived, more_remaining))
+}
+
+/// Deletes up to `limit` archived withdrawals that were last updated below
+/// the given stacks height.
+///
+/// Returns the number of withdrawals that were deleted and whether there
+/// are more withdrawals left to delete.
+pub async fn purge_archived_withdrawal_entries(
+    context: &EmilyContext,
+    before_stacks_height: u64,
+    limit: u16,
+) -> Result<(usize, bool), Error> {
+    let (entries, more_remaining) = query_last_updated_before::<WithdrawalArchiveSecondaryIndex>(
+        context,
+        &COMPLETED_WITHDRAWAL_STATUSES,
+        before_stacks_height,
+        limit,
+    )
+    .await?;
+    for entry in &entries {
+        delete_entry::<WithdrawalArchivePrimaryIndex>(context, &entry.primary_index_key).await?;
+    }
+    Ok((entries.len(), more_remaining))
+}
+
 // Testing ---------------------------------------------------------------------
 
 /// Wipes all the tables.
@@ -1107,9 +1538,24 @@ pub async fn wipe_all_tables(context: &EmilyContext) -> Result<(), Error> {
     wipe_chainstate_table(context).await?;
     wipe_limit_table(context).await?;
     wipe_throttle_table(context).await?;
+    wipe_api_key_table(context).await?;
+    wipe_archive_tables(context).await?;
     Ok(())
 }
 
+/// Wipes the deposit and withdrawal archive tables.
+#[cfg(feature = "testing")]
+async fn wipe_archive_tables(context: &EmilyContext) -> Result<(), Error> {
+    wipe::<DepositArchivePrimaryIndex>(context).await?;
+    wipe::<WithdrawalArchivePrimaryIndex>(context).await
+}
+
+/// Wipes the api key table.
+#[cfg(feature = "testing")]
+async fn wipe_api_key_table(context: &EmilyContext) -> Result<(), Error> {
+    wipe::<ApiKeyTablePrimaryIndex>(context).await
+}
+
 /// Wipes the throttle table.
 #[cfg(feature = "testing")]
 async fn wipe_throttle_table(context: &EmilyContext) -> Result<(), Error> {
@@ -1195,6 +1641,112 @@ async fn query_with_partition_key<T: TableIndexTrait>(
     .await
 }
 
+/// The prefix of a next token that continues a query in an archive table.
+/// The `.` is not part of the url safe base64 alphabet of regular next
+/// tokens, so the two cannot be confused.
+const ARCHIVE_NEXT_TOKEN_PREFIX: &str = "archive.";
+
+/// Query the table of the index `T` and, once it is exhausted and
+/// `include_archived` is set, continue with the same index `A` of the
+/// archive table.
+async fn query_with_partition_key_and_archive<T, A>(
+    context: &EmilyContext,
+    partition_key: &<<<T as TableIndexTrait>::Entry as EntryTrait>::Key as KeyTrait>::PartitionKey,
+    maybe_next_token: Option<String>,
+    maybe_page_size: Option<u16>,
+    include_archived: bool,
+) -> Result<(Vec<<T as TableIndexTrait>::Entry>, Option<String>), Error>
+where
+    T: TableIndexTrait,
+    A: TableIndexTrait<Entry = <T as TableIndexTrait>::Entry>,
+{
+    let (mut entries, next_token) = match maybe_next_token {
+        Some(token) if token.starts_with(ARCHIVE_NEXT_TOKEN_PREFIX) => {
+            let archive_token = Some(token[ARCHIVE_NEXT_TOKEN_PREFIX.len()..].to_string())
+                .filter(|token| !token.is_empty());
+            return query_archive_with_partition_key::<A>(
+                context,
+                partition_key,
+                archive_token,
+                maybe_page_size,
+            )
+            .await;
+        }
+        token => {
+            query_with_partition_key::<T>(context, partition_key, token, maybe_page_size).await?
+        }
+    };
+    if !include_archived || next_token.is_some() {
+        return Ok((entries, next_token));
+    }
+
+    // Fill up the rest of the page from the archive table.
+    let returned = u16::try_from(entries.len()).unwrap_or(u16::MAX);
+    let remaining = maybe_page_size.map(|page_size| page_size.saturating_sub(returned));
+    if remaining == Some(0) {
+        return Ok((entries, Some(ARCHIVE_NEXT_TOKEN_PREFIX.to_string())));
+    }
+    let (mut archived, next_token) =
+        query_archive_with_partition_key::<A>(context, partition_key, None, remaining).await?;
+    entries.append(&mut archived);
+    Ok((entries, next_token))
+}
+
+/// Query an index of an archive table, marking the returned next token as
+/// one that points into the archive table.
+async fn query_archive_with_partition_key<A: TableIndexTrait>(
+    context: &EmilyContext,
+    partition_key: &<<<A as TableIndexTrait>::Entry as EntryTrait>::Key as KeyTrait>::PartitionKey,
+    maybe_next_token: Option<String>,
+    maybe_page_size: Option<u16>,
+) -> Result<(Vec<<A as TableIndexTrait>::Entry>, Option<String>), Error> {
+    let (entries, next_token) =
+        query_with_partition_key::<A>(context, partition_key, maybe_next_token, maybe_page_size)
+            .await?;
+    let next_token = next_token.map(|token| format!("{ARCHIVE_NEXT_TOKEN_PREFIX}{token}"));
+    Ok((entries, next_token))
+}
+
+/// Query up to `limit` entries with any of the given partition keys whose
+/// sort key is below the given value.
+///
+/// Returns the entries and whether there are more entries left to query.
+async fn query_last_updated_before<T>(
+    context: &EmilyContext,
+    partition_keys: &[<<<T as TableIndexTrait>::Entry as EntryTrait>::Key as KeyTrait>::PartitionKey],
+    before: u64,
+    limit: u16,
+) -> Result<(Vec<<T as TableIndexTrait>::Entry>, bool), Error>
+where
+    T: TableIndexTrait,
+    <<T as TableIndexTrait>::Entry as EntryTrait>::Key: KeyTrait<SortKey = u64>,
+{
+    let mut entries = Vec::new();
+    for partition_key in partition_keys {
+        let returned = u16::try_from(entries.len()).unwrap_or(u16::MAX);
+        let remaining = limit.saturating_sub(returned);
+        if remaining == 0 {
+            return Ok((entries, true));
+        }
+        let (mut new_entries, next_token) =
+            <T as TableIndexTrait>::query_with_partition_and_sort_key(
+                &context.dynamodb_client,
+                &context.settings,
+                partition_key,
+                &before,
+                "<",
+                None,
+                Some(remaining),
+            )
+            .await?;
+        entries.append(&mut new_entries);
+        if next_token.is_some() {
+            return Ok((entries, true));
+        }
+    }
+    Ok((entries, false))
+}
+
 async fn query_all_with_partition_and_sort_key<T: TableIndexTrait>(
     context: &EmilyContext,
     partition_key: &<<<T as TableIndexTrait>::Entry as EntryTrait>::Key as KeyTrait>::PartitionKey,
@@ -0,0 +1,85 @@
+//! Entries into the api keys table.
+
+use std::hash::Hash;
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::models::api_key::{ApiKeyInfo, ApiKeyScope};
+
+use super::{EntryTrait, KeyTrait, PrimaryIndex, PrimaryIndexTrait};
+
+/// Api key table entry key. This is the primary index key.
+#[derive(Clone, Default, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ApiKeyEntryKey {
+    /// Name of the key.
+    pub name: String,
+    /// The timestamp of the change to the key, in seconds from UNIX epoch.
+    /// Every change to a key is written as a new entry, and the most
+    /// recent entry is the current state of the key.
+    pub updated_at: u64,
+}
+
+/// Api key table entry.
+#[derive(Clone, Default, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ApiKeyEntry {
+    /// Api key entry key.
+    #[serde(flatten)]
+    pub key: ApiKeyEntryKey,
+    /// Hash of the secret.
+    pub hash: String,
+    /// The operations that this key may perform.
+    pub scopes: Vec<ApiKeyScope>,
+    /// The maximum number of requests per minute for this key.
+    pub rate_limit_per_minute: Option<u32>,
+    /// Whether the key may be used.
+    pub is_active: bool,
+}
+
+/// Convert from entry to the information returned by the API.
+impl From<ApiKeyEntry> for ApiKeyInfo {
+    fn from(entry: ApiKeyEntry) -> Self {
+        ApiKeyInfo {
+            name: entry.key.name,
+            scopes: entry.scopes,
+            rate_limit_per_minute: entry.rate_limit_per_minute,
+            is_active: entry.is_active,
+            updated_at: entry.key.updated_at,
+        }
+    }
+}
+
+/// Implements the key trait for the api key entry key.
+impl KeyTrait for ApiKeyEntryKey {
+    /// The type of the partition key.
+    type PartitionKey = String;
+    /// the type of the sort key.
+    type SortKey = u64;
+    /// The table field name of the partition key.
+    const PARTITION_KEY_NAME: &'static str = "Name";
+    /// The table field name of the sort key.
+    const SORT_KEY_NAME: &'static str = "UpdatedAt";
+}
+
+/// Implements the entry trait for the api key entry.
+impl EntryTrait for ApiKeyEntry {
+    /// The type of the key for this entry type.
+    type Key = ApiKeyEntryKey;
+    /// Extract the key from the api key entry.
+    fn key(&self) -> Self::Key {
+        self.key.clone()
+    }
+}
+
+/// Primary index struct.
+pub struct ApiKeyTablePrimaryIndexInner;
+/// Api key table primary index type.
+pub type ApiKeyTablePrimaryIndex = PrimaryIndex<ApiKeyTablePrimaryIndexInner>;
+/// Definition of Primary index trait.
+impl PrimaryIndexTrait for ApiKeyTablePrimaryIndexInner {
+    type Entry = ApiKeyEntry;
+    fn table_name(settings: &crate::context::Settings) -> &str {
+        &settings.api_key_table_name
+    }
+}
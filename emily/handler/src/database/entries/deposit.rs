@@ -1,16 +1,20 @@
 //! Entries into the deposit table.
 
+use sbtc::ids::DepositId;
 use serde::{Deserialize, Serialize};
 
 use super::{
     DepositStatusEntry, EntryTrait, KeyTrait, PrimaryIndex, PrimaryIndexTrait, SecondaryIndex,
-    SecondaryIndexTrait, VersionedEntryTrait,
+    SecondaryIndexTrait, VersionedEntryTrait, event_timestamp,
 };
 use crate::{
     api::models::{
         chainstate::Chainstate,
-        common::{DepositStatus, Fulfillment},
-        deposit::{Deposit, DepositInfo, DepositParameters},
+        common::{DepositStatus, Fulfillment, StatusUpdateActor},
+        deposit::{
+            Deposit, DepositInfo, DepositParameters,
+            responses::{DepositStatusUpdate, GetDepositHistoryResponse},
+        },
     },
     common::error::{Error, Inconsistency, ValidationError},
 };
@@ -33,6 +37,15 @@ impl std::fmt::Display for DepositEntryKey {
     }
 }
 
+impl From<DepositId> for DepositEntryKey {
+    fn from(id: DepositId) -> Self {
+        DepositEntryKey {
+            bitcoin_txid: id.txid.to_string(),
+            bitcoin_tx_output_index: id.vout,
+        }
+    }
+}
+
 /// Deposit table entry.
 #[derive(Clone, Default, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -77,6 +90,14 @@ pub struct DepositEntry {
     /// Transaction ID of transaction which replaced this transaction during an RBF.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub replaced_by_tx: Option<String>,
+    /// The hash of the bitcoin block that confirmed the deposit transaction, as
+    /// reported by the signers along with `deposit_tx_proof`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deposit_block_hash: Option<String>,
+    /// Hex encoded SPV proof that the deposit transaction is included in the
+    /// block with `deposit_block_hash`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deposit_tx_proof: Option<String>,
 }
 
 /// Implements versioned entry trait for the deposit entry.
@@ -130,6 +151,18 @@ impl PrimaryIndexTrait for DepositTablePrimaryIndexInner {
     }
 }
 
+/// Archive primary index struct.
+pub struct DepositArchivePrimaryIndexInner;
+/// Deposit archive table primary index type.
+pub type DepositArchivePrimaryIndex = PrimaryIndex<DepositArchivePrimaryIndexInner>;
+/// Definition of Primary index trait.
+impl PrimaryIndexTrait for DepositArchivePrimaryIndexInner {
+    type Entry = DepositEntry;
+    fn table_name(settings: &crate::context::Settings) -> &str {
+        &settings.deposit_archive_table_name
+    }
+}
+
 /// Implementation of deposit entry.
 impl DepositEntry {
     /// Implement validate.
@@ -186,6 +219,8 @@ impl DepositEntry {
                 message: "Reprocessing deposit status after reorg.".to_string(),
                 stacks_block_height: chainstate.stacks_block_height,
                 stacks_block_hash: chainstate.stacks_block_hash.clone(),
+                updated_at: Some(event_timestamp()),
+                actor: Some(StatusUpdateActor::Reorg),
             }]
         }
         // Synchronize self with the new history.
@@ -277,6 +312,22 @@ impl TryFrom<DepositEntry> for Deposit {
             deposit_script: deposit_entry.deposit_script,
             fulfillment,
             replaced_by_tx,
+            deposit_block_hash: deposit_entry.deposit_block_hash,
+            deposit_tx_proof: deposit_entry.deposit_tx_proof,
+        })
+    }
+}
+
+impl TryFrom<DepositEntry> for GetDepositHistoryResponse {
+    type Error = Error;
+    fn try_from(deposit_entry: DepositEntry) -> Result<Self, Self::Error> {
+        // Ensure entry is valid.
+        deposit_entry.validate()?;
+
+        Ok(GetDepositHistoryResponse {
+            bitcoin_txid: deposit_entry.key.bitcoin_txid,
+            bitcoin_tx_output_index: deposit_entry.key.bitcoin_tx_output_index,
+            history: deposit_entry.history.into_iter().map(Into::into).collect(),
         })
     }
 }
@@ -305,10 +356,47 @@ pub struct DepositEvent {
     pub stacks_block_height: u64,
     /// Stacks block hash associated with the height of this update.
     pub stacks_block_hash: String,
+    /// Unix timestamp, in seconds, of when Emily recorded this update. This
+    /// is `None` for events that were recorded before it was tracked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<u64>,
+    /// The party that caused this update, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor: Option<StatusUpdateActor>,
+}
+
+impl From<DepositEvent> for DepositStatusUpdate {
+    fn from(event: DepositEvent) -> Self {
+        let status: DepositStatus = (&event.status).into();
+        let (fulfillment, replaced_by_tx) = match event.status {
+            DepositStatusEntry::Confirmed(fulfillment) => (Some(fulfillment), None),
+            DepositStatusEntry::Rbf(replaced_by_tx) => (None, Some(replaced_by_tx)),
+            _ => (None, None),
+        };
+        DepositStatusUpdate {
+            status,
+            status_message: event.message,
+            stacks_block_height: event.stacks_block_height,
+            stacks_block_hash: event.stacks_block_hash,
+            fulfillment,
+            replaced_by_tx,
+            updated_at: event.updated_at,
+            actor: event.actor,
+        }
+    }
 }
 
 /// Implementation of deposit event.
 impl DepositEvent {
+    /// Returns true if both events record the same status update, ignoring
+    /// when and by whom each of them was recorded.
+    pub fn is_same_update_as(&self, other: &DepositEvent) -> bool {
+        self.status == other.status
+            && self.message == other.message
+            && self.stacks_block_height == other.stacks_block_height
+            && self.stacks_block_hash == other.stacks_block_hash
+    }
+
     /// Errors if the next event provided could not follow the current one.
     pub fn ensure_following_event_is_valid(&self, next_event: &DepositEvent) -> Result<(), Error> {
         // Determine if event is valid.
@@ -424,6 +512,17 @@ impl SecondaryIndexTrait for DepositTableSecondaryIndexInner {
     const INDEX_NAME: &'static str = "DepositStatus";
 }
 
+/// Archive secondary index struct.
+pub struct DepositArchiveSecondaryIndexInner;
+/// Deposit archive table secondary index type.
+pub type DepositArchiveSecondaryIndex = SecondaryIndex<DepositArchiveSecondaryIndexInner>;
+/// Definition of secondary index trait.
+impl SecondaryIndexTrait for DepositArchiveSecondaryIndexInner {
+    type PrimaryIndex = DepositArchivePrimaryIndex;
+    type Entry = DepositInfoEntry;
+    const INDEX_NAME: &'static str = "DepositStatus";
+}
+
 impl From<DepositInfoEntry> for DepositInfo {
     fn from(deposit_info_entry: DepositInfoEntry) -> Self {
         // Create deposit info resource from deposit info table entry.
@@ -672,9 +771,37 @@ pub struct ValidatedDepositUpdate {
     pub key: DepositEntryKey,
     /// Deposit event.
     pub event: DepositEvent,
+    /// Proof that the deposit transaction was confirmed on bitcoin.
+    pub deposit_tx_proof: Option<DepositTxProof>,
+}
+
+/// A validated SPV proof that a deposit transaction is included in a
+/// bitcoin block.
+#[derive(Clone, Default, Debug, Eq, PartialEq, Hash)]
+pub struct DepositTxProof {
+    /// The hash of the bitcoin block that confirmed the deposit transaction.
+    pub block_hash: String,
+    /// Hex encoded merkle block that proves the inclusion of the deposit
+    /// transaction in the block.
+    pub proof: String,
 }
 
 impl ValidatedDepositUpdate {
+    /// Returns the deposit transaction proof of this update if the given
+    /// entry does not already have a proof for the same bitcoin block.
+    pub fn new_tx_proof_for(&self, entry: &DepositEntry) -> Option<&DepositTxProof> {
+        self.deposit_tx_proof
+            .as_ref()
+            .filter(|proof| entry.deposit_block_hash.as_ref() != Some(&proof.block_hash))
+    }
+
+    /// Returns true if this update only carries a deposit transaction
+    /// proof. The signers report proofs of pending deposits this way, and
+    /// such updates leave the status of the deposit as it is.
+    pub fn is_tx_proof_only(&self) -> bool {
+        self.deposit_tx_proof.is_some() && self.event.status == DepositStatusEntry::Pending
+    }
+
     /// Returns true if the update is not necessary.
     pub fn is_unnecessary(&self, entry: &DepositEntry) -> bool {
         entry
@@ -682,7 +809,7 @@ impl ValidatedDepositUpdate {
             .iter()
             .rev()
             .take_while(|event| event.stacks_block_height >= self.event.stacks_block_height)
-            .any(|event| event == &self.event)
+            .any(|event| event.is_same_update_as(&self.event))
     }
 }
 
@@ -729,6 +856,8 @@ mod tests {
             message: "".to_string(),
             stacks_block_height: 0,
             stacks_block_hash: "".to_string(),
+            updated_at: None,
+            actor: None,
         };
 
         let accepted = DepositEvent {
@@ -736,6 +865,8 @@ mod tests {
             message: "".to_string(),
             stacks_block_height: 1,
             stacks_block_hash: "".to_string(),
+            updated_at: None,
+            actor: None,
         };
 
         let deposit = DepositEntry {
@@ -753,16 +884,101 @@ mod tests {
             history: vec![pending, accepted.clone()],
             reclaim_pubkeys_hash: None,
             replaced_by_tx: None,
+            deposit_block_hash: None,
+            deposit_tx_proof: None,
         };
 
         let update = ValidatedDepositUpdate {
             key: Default::default(),
             event: accepted,
+            deposit_tx_proof: None,
+        };
+
+        assert!(update.is_unnecessary(&deposit));
+    }
+
+    #[test]
+    fn deposit_update_should_be_unnecessary_when_event_was_recorded_earlier() {
+        let accepted = DepositEvent {
+            status: DepositStatusEntry::Accepted,
+            message: "accepted".to_string(),
+            stacks_block_height: 1,
+            stacks_block_hash: "hash1".to_string(),
+            updated_at: Some(100),
+            actor: Some(StatusUpdateActor::Signer),
+        };
+
+        let deposit = DepositEntry {
+            history: vec![accepted.clone()],
+            ..Default::default()
+        };
+
+        // The same update sent again by another signer at a later time.
+        let update = ValidatedDepositUpdate {
+            key: Default::default(),
+            event: DepositEvent {
+                updated_at: Some(200),
+                actor: Some(StatusUpdateActor::Sidecar),
+                ..accepted
+            },
+            deposit_tx_proof: None,
         };
 
         assert!(update.is_unnecessary(&deposit));
     }
 
+    #[test]
+    fn deposit_history_lists_every_event_in_order() {
+        let fulfillment = Fulfillment {
+            bitcoin_txid: "txid".to_string(),
+            ..Default::default()
+        };
+        let events = vec![
+            DepositEvent {
+                status: DepositStatusEntry::Pending,
+                message: "pending".to_string(),
+                stacks_block_height: 1,
+                stacks_block_hash: "hash1".to_string(),
+                updated_at: Some(100),
+                actor: Some(StatusUpdateActor::Creator),
+            },
+            DepositEvent {
+                status: DepositStatusEntry::Confirmed(fulfillment.clone()),
+                message: "confirmed".to_string(),
+                stacks_block_height: 2,
+                stacks_block_hash: "hash2".to_string(),
+                updated_at: None,
+                actor: None,
+            },
+        ];
+        let deposit = DepositEntry {
+            key: DepositEntryKey {
+                bitcoin_txid: "deposit".to_string(),
+                bitcoin_tx_output_index: 1,
+            },
+            status: DepositStatus::Confirmed,
+            last_update_height: 2,
+            last_update_block_hash: "hash2".to_string(),
+            fulfillment: Some(fulfillment.clone()),
+            history: events,
+            ..Default::default()
+        };
+
+        let response = GetDepositHistoryResponse::try_from(deposit).unwrap();
+
+        assert_eq!(response.bitcoin_txid, "deposit");
+        assert_eq!(response.bitcoin_tx_output_index, 1);
+        assert_eq!(response.history.len(), 2);
+        assert_eq!(response.history[0].status, DepositStatus::Pending);
+        assert_eq!(response.history[0].fulfillment, None);
+        assert_eq!(response.history[0].updated_at, Some(100));
+        assert_eq!(response.history[0].actor, Some(StatusUpdateActor::Creator));
+        assert_eq!(response.history[1].status, DepositStatus::Confirmed);
+        assert_eq!(response.history[1].status_message, "confirmed");
+        assert_eq!(response.history[1].fulfillment, Some(fulfillment));
+        assert_eq!(response.history[1].actor, None);
+    }
+
     #[test]
     fn deposit_update_should_be_necessary_when_event_is_not_present() {
         let pending = DepositEvent {
@@ -770,6 +986,8 @@ mod tests {
             message: "".to_string(),
             stacks_block_height: 0,
             stacks_block_hash: "".to_string(),
+            updated_at: None,
+            actor: None,
         };
 
         let accepted = DepositEvent {
@@ -777,6 +995,8 @@ mod tests {
             message: "".to_string(),
             stacks_block_height: 1,
             stacks_block_hash: "".to_string(),
+            updated_at: None,
+            actor: None,
         };
 
         let deposit = DepositEntry {
@@ -794,11 +1014,14 @@ mod tests {
             history: vec![pending.clone()],
             reclaim_pubkeys_hash: None,
             replaced_by_tx: None,
+            deposit_block_hash: None,
+            deposit_tx_proof: None,
         };
 
         let update = ValidatedDepositUpdate {
             key: Default::default(),
             event: accepted,
+            deposit_tx_proof: None,
         };
 
         assert!(!update.is_unnecessary(&deposit));
@@ -821,6 +1044,8 @@ mod tests {
             message: "initial test pending".to_string(),
             stacks_block_height: 2,
             stacks_block_hash: "hash2".to_string(),
+            updated_at: None,
+            actor: None,
         };
 
         let accepted = DepositEvent {
@@ -828,6 +1053,8 @@ mod tests {
             message: "accepted".to_string(),
             stacks_block_height: 4,
             stacks_block_hash: "hash4".to_string(),
+            updated_at: None,
+            actor: None,
         };
 
         let fulfillment: Fulfillment = Default::default();
@@ -836,6 +1063,8 @@ mod tests {
             message: "confirmed".to_string(),
             stacks_block_height: 6,
             stacks_block_hash: "hash6".to_string(),
+            updated_at: None,
+            actor: None,
         };
 
         let mut deposit = DepositEntry {
@@ -853,6 +1082,8 @@ mod tests {
             history: vec![pending.clone(), accepted.clone(), confirmed.clone()],
             reclaim_pubkeys_hash: Some(hex::encode([1u8; 32])),
             replaced_by_tx: None,
+            deposit_block_hash: None,
+            deposit_tx_proof: None,
         };
 
         // Ensure the deposit is valid.
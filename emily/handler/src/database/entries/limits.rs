@@ -13,6 +13,10 @@ use super::{EntryTrait, KeyTrait, PrimaryIndex, PrimaryIndexTrait};
 /// The special account name for the global cap.
 pub(crate) const GLOBAL_CAP_ACCOUNT: &str = "GLOBAL";
 
+/// The account under which the amount of sBTC that can still be minted,
+/// as reported by the signers, is stored.
+pub(crate) const AVAILABLE_TO_MINT_ACCOUNT: &str = "AVAILABLE_TO_MINT";
+
 /// Limit table entry key. This is the primary index key.
 #[derive(Clone, Default, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -23,6 +27,21 @@ pub struct LimitEntryKey {
     pub timestamp: u64,
 }
 
+impl LimitEntryKey {
+    /// Create a new key for an update to the given account at the chosen
+    /// time.
+    fn new(account: String, now: SystemTime) -> Self {
+        LimitEntryKey {
+            account,
+            timestamp: now
+                .duration_since(std::time::UNIX_EPOCH)
+                // It's impossible for this to fail.
+                .expect("Error making timestamp during limit entry creation.")
+                .as_secs(),
+        }
+    }
+}
+
 /// Limit table entry key. This is the primary index key.
 #[derive(Clone, Default, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -44,6 +63,11 @@ pub struct LimitEntry {
     pub rolling_withdrawal_cap: Option<u64>,
     /// Throttle key initiated throttle mode
     pub throttle_mode_initiator: Option<String>,
+    /// The amount of sBTC that can still be minted under the peg cap, as
+    /// reported by the signers. This is only set on the entries of the
+    /// [`AVAILABLE_TO_MINT_ACCOUNT`].
+    #[serde(default)]
+    pub available_to_mint: Option<u64>,
 }
 
 /// Convert from entry to its corresponding limit.
@@ -69,14 +93,7 @@ impl LimitEntry {
         account_limit: &AccountLimits,
     ) -> Self {
         LimitEntry {
-            key: LimitEntryKey {
-                account,
-                timestamp: now
-                    .duration_since(std::time::UNIX_EPOCH)
-                    // It's impossible for this to fail.
-                    .expect("Error making timestamp during limit entry creation.")
-                    .as_secs(),
-            },
+            key: LimitEntryKey::new(account, now),
             peg_cap: account_limit.peg_cap,
             per_deposit_minimum: account_limit.per_deposit_minimum,
             per_deposit_cap: account_limit.per_deposit_cap,
@@ -84,6 +101,16 @@ impl LimitEntry {
             rolling_withdrawal_blocks: account_limit.rolling_withdrawal_blocks,
             rolling_withdrawal_cap: account_limit.rolling_withdrawal_cap,
             throttle_mode_initiator: account_limit.throttle_mode_initiator.clone(),
+            available_to_mint: None,
+        }
+    }
+    /// Create a new entry recording the amount of sBTC that the signers
+    /// reported can still be minted at the chosen time.
+    pub fn from_available_to_mint(available_to_mint: u64, now: SystemTime) -> Self {
+        LimitEntry {
+            key: LimitEntryKey::new(AVAILABLE_TO_MINT_ACCOUNT.to_string(), now),
+            available_to_mint: Some(available_to_mint),
+            ..Default::default()
         }
     }
     /// Returns true if the limit entry has no limits set.
@@ -46,7 +46,7 @@
 
 use std::{collections::HashMap, fmt::Debug};
 
-use aws_sdk_dynamodb::types::AttributeValue;
+use aws_sdk_dynamodb::types::{AttributeValue, Select};
 #[cfg(feature = "testing")]
 use aws_sdk_dynamodb::types::{DeleteRequest, WriteRequest};
 use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
@@ -71,6 +71,9 @@ pub mod withdrawal;
 /// Throttle keys table entries.
 pub mod throttle;
 
+/// Api keys table entries.
+pub mod api_key;
+
 // Event structure
 // -----------------------------------------------------------------------------
 
@@ -141,6 +144,49 @@ impl From<&DepositStatusEntry> for DepositStatus {
     }
 }
 
+impl WithdrawalStatusEntry {
+    /// Returns the fulfillment of the withdrawal if it has been confirmed.
+    pub fn fulfillment(&self) -> Option<&Fulfillment> {
+        match self {
+            WithdrawalStatusEntry::Confirmed(fulfillment) => Some(fulfillment),
+            _ => None,
+        }
+    }
+}
+
+/// Returns true if the hex encoded merkle block proves that the bitcoin
+/// transaction with the given txid is included in the bitcoin block with
+/// the given block hash.
+pub fn proves_inclusion(proof: &str, txid: &str, block_hash: &str) -> bool {
+    let Ok(bytes) = hex::decode(proof) else {
+        return false;
+    };
+    let Ok(merkle_block) = bitcoin::consensus::deserialize::<bitcoin::MerkleBlock>(&bytes) else {
+        return false;
+    };
+    let mut matches = Vec::new();
+    let mut indexes = Vec::new();
+    if merkle_block
+        .extract_matches(&mut matches, &mut indexes)
+        .is_err()
+    {
+        return false;
+    }
+
+    merkle_block.header.block_hash().to_string() == block_hash
+        && matches.iter().any(|matched| matched.to_string() == txid)
+}
+
+/// The current time in seconds from UNIX epoch, used to timestamp the
+/// events in the history of deposits and withdrawals.
+pub fn event_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        // It's impossible for this to fail.
+        .expect("Error making timestamp during event creation.")
+        .as_secs()
+}
+
 impl From<&WithdrawalStatusEntry> for WithdrawalStatus {
     fn from(value: &WithdrawalStatusEntry) -> Self {
         match value {
@@ -360,6 +406,53 @@ pub(crate) trait TableIndexTrait {
         Ok((entries, next_token))
     }
 
+    /// Generic table count of the entries with a given primary key whose
+    /// sort key satisfies the given operator. Only the count is returned by
+    /// DynamoDB, not the entries themselves.
+    async fn count_with_partition_and_sort_key(
+        dynamodb_client: &aws_sdk_dynamodb::Client,
+        settings: &Settings,
+        partition_key: &<<Self::Entry as EntryTrait>::Key as KeyTrait>::PartitionKey,
+        sort_key: &<<Self::Entry as EntryTrait>::Key as KeyTrait>::SortKey,
+        sort_key_operator: &str,
+    ) -> Result<u64, Error> {
+        let mut count: u64 = 0;
+        let mut exclusive_start_key = None;
+        loop {
+            let query_output = dynamodb_client
+                .query()
+                .table_name(Self::table_name(settings))
+                .set_index_name(Self::INDEX_NAME_IF_GSI.map(|s| s.to_string()))
+                .set_exclusive_start_key(exclusive_start_key)
+                .select(Select::Count)
+                .key_condition_expression(format!("#pk = :pk AND #sk {sort_key_operator} :sk"))
+                .expression_attribute_names(
+                    "#pk",
+                    <<Self::Entry as EntryTrait>::Key as KeyTrait>::PARTITION_KEY_NAME,
+                )
+                .expression_attribute_names(
+                    "#sk",
+                    <<Self::Entry as EntryTrait>::Key as KeyTrait>::SORT_KEY_NAME,
+                )
+                .expression_attribute_values(
+                    ":pk",
+                    serde_dynamo::to_attribute_value(partition_key)?,
+                )
+                .expression_attribute_values(":sk", serde_dynamo::to_attribute_value(sort_key)?)
+                .send()
+                .await
+                .map_err(Box::new)?;
+            count += query_output.count.max(0) as u64;
+            // A single query only counts up to 1MB of data, so keep going
+            // until the whole key range has been counted.
+            exclusive_start_key = query_output.last_evaluated_key;
+            if exclusive_start_key.is_none() {
+                break;
+            }
+        }
+        Ok(count)
+    }
+
     /// Generic put table entry.
     async fn put_entry(
         dynamodb_client: &aws_sdk_dynamodb::Client,
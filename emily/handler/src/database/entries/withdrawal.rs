@@ -5,15 +5,18 @@ use serde::{Deserialize, Serialize};
 use crate::{
     api::models::{
         chainstate::Chainstate,
-        common::WithdrawalStatus,
-        withdrawal::{ExpectedFulfillmentInfo, Withdrawal, WithdrawalInfo, WithdrawalParameters},
+        common::{Fulfillment, StatusUpdateActor, WithdrawalStatus},
+        withdrawal::{
+            ExpectedFulfillmentInfo, Withdrawal, WithdrawalInfo, WithdrawalParameters,
+            responses::{GetWithdrawalHistoryResponse, WithdrawalStatusUpdate},
+        },
     },
     common::error::{Error, Inconsistency, ValidationError},
 };
 
 use super::{
     EntryTrait, KeyTrait, PrimaryIndex, PrimaryIndexTrait, SecondaryIndex, SecondaryIndexTrait,
-    VersionedEntryTrait, WithdrawalStatusEntry,
+    VersionedEntryTrait, WithdrawalStatusEntry, event_timestamp, proves_inclusion,
 };
 
 // Withdrawal entry ---------------------------------------------------------------
@@ -67,6 +70,10 @@ pub struct WithdrawalEntry {
     pub last_update_block_hash: String,
     /// The hex encoded txid of the stacks transaction that generated this event.
     pub txid: String,
+    /// Data about the fulfillment of the withdrawal. This mirrors the fulfillment
+    /// of the latest event so that it can be projected into the secondary indexes.
+    #[serde(default)]
+    pub fulfillment: Option<Fulfillment>,
     /// History of this withdrawal transaction.
     pub history: Vec<WithdrawalEvent>,
 }
@@ -141,6 +148,8 @@ impl WithdrawalEntry {
                 message: "Reprocessing withdrawal status after reorg.".to_string(),
                 stacks_block_height: chainstate.stacks_block_height,
                 stacks_block_hash: chainstate.stacks_block_hash.clone(),
+                updated_at: Some(event_timestamp()),
+                actor: Some(StatusUpdateActor::Reorg),
                 expected_fulfillment_info: ExpectedFulfillmentInfo {
                     bitcoin_block_height: None,
                     bitcoin_txid: None,
@@ -176,6 +185,7 @@ impl WithdrawalEntry {
         self.status = new_status;
         self.last_update_height = new_last_update_height;
         self.last_update_block_hash = latest_event.stacks_block_hash.clone();
+        self.fulfillment = latest_event.status.fulfillment().cloned();
         // Return.
         Ok(())
     }
@@ -219,6 +229,23 @@ impl TryFrom<WithdrawalEntry> for Withdrawal {
     }
 }
 
+impl TryFrom<WithdrawalEntry> for GetWithdrawalHistoryResponse {
+    type Error = Error;
+    fn try_from(withdrawal_entry: WithdrawalEntry) -> Result<Self, Self::Error> {
+        // Ensure entry is valid.
+        withdrawal_entry.validate()?;
+
+        Ok(GetWithdrawalHistoryResponse {
+            request_id: withdrawal_entry.key.request_id,
+            history: withdrawal_entry
+                .history
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        })
+    }
+}
+
 /// Withdrawal parameters entry.
 #[derive(Clone, Default, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -241,12 +268,44 @@ pub struct WithdrawalEvent {
     pub stacks_block_height: u64,
     /// Stacks block hash associated with the height of this update.
     pub stacks_block_hash: String,
+    /// Unix timestamp, in seconds, of when Emily recorded this update. This
+    /// is `None` for events that were recorded before it was tracked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<u64>,
+    /// The party that caused this update, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor: Option<StatusUpdateActor>,
     /// Information about fulfillment process of the withdrawal request
     pub expected_fulfillment_info: ExpectedFulfillmentInfo,
 }
 
+impl From<WithdrawalEvent> for WithdrawalStatusUpdate {
+    fn from(event: WithdrawalEvent) -> Self {
+        WithdrawalStatusUpdate {
+            status: (&event.status).into(),
+            fulfillment: event.status.fulfillment().cloned(),
+            status_message: event.message,
+            stacks_block_height: event.stacks_block_height,
+            stacks_block_hash: event.stacks_block_hash,
+            expected_fulfillment_info: event.expected_fulfillment_info,
+            updated_at: event.updated_at,
+            actor: event.actor,
+        }
+    }
+}
+
 /// Implementation of withdrawal event.
 impl WithdrawalEvent {
+    /// Returns true if both events record the same status update, ignoring
+    /// when and by whom each of them was recorded.
+    pub fn is_same_update_as(&self, other: &WithdrawalEvent) -> bool {
+        self.status == other.status
+            && self.message == other.message
+            && self.stacks_block_height == other.stacks_block_height
+            && self.stacks_block_hash == other.stacks_block_hash
+            && self.expected_fulfillment_info == other.expected_fulfillment_info
+    }
+
     /// Errors if the next event provided could not follow the current one.
     pub fn ensure_following_event_is_valid(
         &self,
@@ -315,6 +374,18 @@ impl PrimaryIndexTrait for WithdrawalTablePrimaryIndexInner {
     }
 }
 
+/// Archive primary index struct.
+pub struct WithdrawalArchivePrimaryIndexInner;
+/// Withdrawal archive table primary index type.
+pub type WithdrawalArchivePrimaryIndex = PrimaryIndex<WithdrawalArchivePrimaryIndexInner>;
+/// Definition of Primary index trait.
+impl PrimaryIndexTrait for WithdrawalArchivePrimaryIndexInner {
+    type Entry = WithdrawalEntry;
+    fn table_name(settings: &crate::context::Settings) -> &str {
+        &settings.withdrawal_archive_table_name
+    }
+}
+
 // Withdrawal info entry ----------------------------------------------------------
 
 /// Search token for GSI.
@@ -366,6 +437,9 @@ pub struct WithdrawalInfoEntry {
     pub last_update_block_hash: String,
     /// The hex encoded txid of the stacks transaction that generated this event.
     pub txid: String,
+    /// Data about the fulfillment of the withdrawal.
+    #[serde(default)]
+    pub fulfillment: Option<Fulfillment>,
 }
 
 /// Implements the key trait for the withdrawal info entry key.
@@ -404,6 +478,17 @@ impl SecondaryIndexTrait for WithdrawalTableSecondaryIndexInner {
     const INDEX_NAME: &'static str = "WithdrawalStatus";
 }
 
+/// Archive secondary index struct.
+pub struct WithdrawalArchiveSecondaryIndexInner;
+/// Withdrawal archive table secondary index type.
+pub type WithdrawalArchiveSecondaryIndex = SecondaryIndex<WithdrawalArchiveSecondaryIndexInner>;
+/// Definition of secondary index trait.
+impl SecondaryIndexTrait for WithdrawalArchiveSecondaryIndexInner {
+    type PrimaryIndex = WithdrawalArchivePrimaryIndex;
+    type Entry = WithdrawalInfoEntry;
+    const INDEX_NAME: &'static str = "WithdrawalStatus";
+}
+
 impl From<WithdrawalInfoEntry> for WithdrawalInfo {
     fn from(withdrawal_info_entry: WithdrawalInfoEntry) -> Self {
         // Create withdrawal info resource from withdrawal info table entry.
@@ -418,6 +503,7 @@ impl From<WithdrawalInfoEntry> for WithdrawalInfo {
             last_update_block_hash: withdrawal_info_entry.last_update_block_hash,
             status: withdrawal_info_entry.key.status,
             txid: withdrawal_info_entry.txid,
+            fulfillment: withdrawal_info_entry.fulfillment,
         }
     }
 }
@@ -471,6 +557,9 @@ pub struct WithdrawalInfoByRecipientEntry {
     pub last_update_block_hash: String,
     /// The hex encoded txid of the stacks transaction that generated this event.
     pub txid: String,
+    /// Data about the fulfillment of the withdrawal.
+    #[serde(default)]
+    pub fulfillment: Option<Fulfillment>,
 }
 
 /// Implements the key trait for the withdrawal info entry key.
@@ -524,6 +613,7 @@ impl From<WithdrawalInfoByRecipientEntry> for WithdrawalInfo {
             last_update_block_hash: withdrawal_info_entry.last_update_block_hash,
             status: withdrawal_info_entry.status,
             txid: withdrawal_info_entry.txid,
+            fulfillment: withdrawal_info_entry.fulfillment,
         }
     }
 }
@@ -577,6 +667,9 @@ pub struct WithdrawalInfoBySenderEntry {
     pub last_update_block_hash: String,
     /// The hex encoded txid of the stacks transaction that generated this event.
     pub txid: String,
+    /// Data about the fulfillment of the withdrawal.
+    #[serde(default)]
+    pub fulfillment: Option<Fulfillment>,
 }
 
 /// Implements the key trait for the withdrawal info entry key.
@@ -630,6 +723,7 @@ impl From<WithdrawalInfoBySenderEntry> for WithdrawalInfo {
             last_update_block_hash: withdrawal_info_entry.last_update_block_hash,
             status: withdrawal_info_entry.status,
             txid: withdrawal_info_entry.txid,
+            fulfillment: withdrawal_info_entry.fulfillment,
         }
     }
 }
@@ -664,7 +758,69 @@ impl ValidatedWithdrawalUpdate {
             .iter()
             .rev()
             .take_while(|event| event.stacks_block_height >= self.event.stacks_block_height)
-            .any(|event| event == &self.event)
+            .any(|event| event.is_same_update_as(&self.event))
+    }
+
+    /// Returns an update that attaches the sweep transaction proof in this
+    /// update to the confirmed fulfillment of the given entry.
+    ///
+    /// Withdrawals are only confirmed by the events observed on the Stacks
+    /// blockchain, but the signers may attach the SPV proof of the sweep
+    /// transaction to a withdrawal that has already been confirmed by that
+    /// same transaction. Returns `None` if this update does not carry a
+    /// valid proof for the current fulfillment of the entry.
+    ///
+    /// Only the first proof is recorded. Once the fulfillment has a proof,
+    /// later reports for the same sweep transaction return the latest
+    /// event unchanged, so the recorded proof and assessed fee are kept.
+    pub fn as_fulfillment_proof_for(
+        &self,
+        entry: &WithdrawalEntry,
+    ) -> Result<Option<ValidatedWithdrawalUpdate>, Error> {
+        let latest_event = entry.latest_event()?;
+        let (Some(update), Some(current)) = (
+            self.event.status.fulfillment(),
+            latest_event.status.fulfillment(),
+        ) else {
+            return Ok(None);
+        };
+        let Some(proof) = update.sweep_tx_proof.as_deref() else {
+            return Ok(None);
+        };
+
+        let is_same_sweep = update.bitcoin_txid == current.bitcoin_txid
+            && update.bitcoin_tx_index == current.bitcoin_tx_index
+            && update.bitcoin_block_hash == current.bitcoin_block_hash;
+        if !is_same_sweep {
+            return Ok(None);
+        }
+        if current.sweep_tx_proof.is_some() {
+            return Ok(Some(ValidatedWithdrawalUpdate {
+                request_id: self.request_id,
+                event: latest_event.clone(),
+            }));
+        }
+        if !proves_inclusion(proof, &current.bitcoin_txid, &current.bitcoin_block_hash) {
+            return Ok(None);
+        }
+
+        let fulfillment = Fulfillment {
+            sweep_tx_proof: Some(proof.to_string()),
+            assessed_fee: update.assessed_fee,
+            ..current.clone()
+        };
+        // The proof does not change the state of the withdrawal, so the
+        // event is recorded at the same point as the latest event.
+        let event = WithdrawalEvent {
+            status: WithdrawalStatusEntry::Confirmed(fulfillment),
+            updated_at: self.event.updated_at,
+            actor: self.event.actor,
+            ..latest_event.clone()
+        };
+        Ok(Some(ValidatedWithdrawalUpdate {
+            request_id: self.request_id,
+            event,
+        }))
     }
 }
 
@@ -743,6 +899,8 @@ mod tests {
             message: "message".to_string(),
             stacks_block_height: 1,
             stacks_block_hash: "hash".to_string(),
+            updated_at: None,
+            actor: None,
             expected_fulfillment_info: ExpectedFulfillmentInfo {
                 bitcoin_block_height: None,
                 bitcoin_txid: None,
@@ -754,6 +912,8 @@ mod tests {
             message: "message".to_string(),
             stacks_block_height: 2,
             stacks_block_hash: "hash".to_string(),
+            updated_at: None,
+            actor: None,
             expected_fulfillment_info: ExpectedFulfillmentInfo {
                 bitcoin_block_height: None,
                 bitcoin_txid: None,
@@ -776,6 +936,7 @@ mod tests {
             last_update_block_hash: "hash".to_string(),
             history: vec![pending, failed.clone()],
             txid: "txid".to_string(),
+            fulfillment: None,
         };
 
         let withdrawal_update = ValidatedWithdrawalUpdate { request_id: 1, event: failed };
@@ -795,6 +956,8 @@ mod tests {
             message: "message".to_string(),
             stacks_block_height: 1,
             stacks_block_hash: "hash".to_string(),
+            updated_at: None,
+            actor: None,
             expected_fulfillment_info: ExpectedFulfillmentInfo {
                 bitcoin_block_height: None,
                 bitcoin_txid: None,
@@ -806,6 +969,8 @@ mod tests {
             message: "message".to_string(),
             stacks_block_height: 2,
             stacks_block_hash: "hash".to_string(),
+            updated_at: None,
+            actor: None,
             expected_fulfillment_info: ExpectedFulfillmentInfo {
                 bitcoin_block_height: None,
                 bitcoin_txid: None,
@@ -828,6 +993,7 @@ mod tests {
             last_update_block_hash: "hash".to_string(),
             history: vec![pending.clone()],
             txid: "txid".to_string(),
+            fulfillment: None,
         };
 
         let withdrawal_update = ValidatedWithdrawalUpdate { request_id: 1, event: failed };
@@ -856,6 +1022,8 @@ mod tests {
             message: "initial test pending".to_string(),
             stacks_block_height: 2,
             stacks_block_hash: "hash2".to_string(),
+            updated_at: None,
+            actor: None,
             expected_fulfillment_info: ExpectedFulfillmentInfo {
                 bitcoin_block_height: None,
                 bitcoin_txid: None,
@@ -867,6 +1035,8 @@ mod tests {
             message: "accepted".to_string(),
             stacks_block_height: 4,
             stacks_block_hash: "hash4".to_string(),
+            updated_at: None,
+            actor: None,
             expected_fulfillment_info: ExpectedFulfillmentInfo {
                 bitcoin_block_height: None,
                 bitcoin_txid: None,
@@ -879,6 +1049,8 @@ mod tests {
             message: "confirmed".to_string(),
             stacks_block_height: 6,
             stacks_block_hash: "hash6".to_string(),
+            updated_at: None,
+            actor: None,
             expected_fulfillment_info: ExpectedFulfillmentInfo {
                 bitcoin_block_height: None,
                 bitcoin_txid: None,
@@ -901,6 +1073,7 @@ mod tests {
             last_update_block_hash: "hash6".to_string(),
             history: vec![pending.clone(), accepted.clone(), confirmed.clone()],
             txid: "txid".to_string(),
+            fulfillment: Some(fulfillment.clone()),
         };
 
         // Ensure the withdrawal is valid.
@@ -927,6 +1100,7 @@ mod tests {
         assert_eq!(withdrawal_entry.last_update_height, expected_height);
         assert_eq!(withdrawal_entry.last_update_block_hash, expected_hash);
         assert_eq!(withdrawal_entry.status, (&expected_status).into());
+        assert_eq!(withdrawal_entry.fulfillment, None);
 
         let latest_event = withdrawal_entry
             .latest_event()
@@ -936,4 +1110,147 @@ mod tests {
         assert_eq!(latest_event.stacks_block_hash, expected_hash);
         assert_eq!(latest_event.status, expected_status);
     }
+
+    fn confirmed_entry(fulfillment: Fulfillment) -> WithdrawalEntry {
+        let confirmed = WithdrawalEvent {
+            status: WithdrawalStatusEntry::Confirmed(fulfillment.clone()),
+            message: "confirmed".to_string(),
+            stacks_block_height: 3,
+            stacks_block_hash: "hash3".to_string(),
+            updated_at: None,
+            actor: None,
+            expected_fulfillment_info: ExpectedFulfillmentInfo::default(),
+        };
+
+        WithdrawalEntry {
+            key: WithdrawalEntryKey {
+                request_id: 1,
+                stacks_block_hash: "hash".to_string(),
+            },
+            stacks_block_height: 1,
+            status: WithdrawalStatus::Confirmed,
+            last_update_height: 3,
+            last_update_block_hash: "hash3".to_string(),
+            history: vec![confirmed],
+            fulfillment: Some(fulfillment),
+            ..Default::default()
+        }
+    }
+
+    fn proof_update(fulfillment: Fulfillment) -> ValidatedWithdrawalUpdate {
+        ValidatedWithdrawalUpdate {
+            request_id: 1,
+            event: WithdrawalEvent {
+                status: WithdrawalStatusEntry::Confirmed(fulfillment),
+                stacks_block_height: 10,
+                stacks_block_hash: "hash10".to_string(),
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn signers_can_attach_a_sweep_proof_to_a_confirmed_withdrawal() {
+        let block = bitcoin::constants::genesis_block(bitcoin::Network::Regtest);
+        let txid = block.txdata[0].compute_txid();
+        let merkle_block =
+            bitcoin::MerkleBlock::from_block_with_predicate(&block, |id| id == &txid);
+        let proof = bitcoin::consensus::encode::serialize_hex(&merkle_block);
+
+        let confirmed = Fulfillment {
+            bitcoin_txid: txid.to_string(),
+            bitcoin_block_hash: block.block_hash().to_string(),
+            stacks_txid: "stacks-txid".to_string(),
+            btc_fee: 1_000,
+            ..Default::default()
+        };
+        let entry = confirmed_entry(confirmed.clone());
+
+        let reported = Fulfillment {
+            stacks_txid: String::new(),
+            btc_fee: 0,
+            sweep_tx_proof: Some(proof.clone()),
+            assessed_fee: Some(1_000),
+            ..confirmed.clone()
+        };
+        let update = proof_update(reported.clone())
+            .as_fulfillment_proof_for(&entry)
+            .unwrap()
+            .expect("proof for the confirmed sweep must be accepted");
+
+        // The proof is attached to the fulfillment that Emily observed, at
+        // the same point in the history as the confirmation.
+        let expected = Fulfillment {
+            sweep_tx_proof: Some(proof.clone()),
+            assessed_fee: Some(1_000),
+            ..confirmed.clone()
+        };
+        assert_eq!(
+            update.event.status,
+            WithdrawalStatusEntry::Confirmed(expected)
+        );
+        assert_eq!(update.event.stacks_block_height, 3);
+        assert_eq!(update.event.stacks_block_hash, "hash3");
+
+        // A proof for a different transaction is rejected.
+        let other_sweep = Fulfillment {
+            bitcoin_txid: "other-txid".to_string(),
+            ..reported.clone()
+        };
+        let update = proof_update(other_sweep).as_fulfillment_proof_for(&entry);
+        assert!(update.unwrap().is_none());
+
+        // A proof that does not decode is rejected.
+        let bad_proof = Fulfillment {
+            sweep_tx_proof: Some("deadbeef".to_string()),
+            ..reported.clone()
+        };
+        let update = proof_update(bad_proof).as_fulfillment_proof_for(&entry);
+        assert!(update.unwrap().is_none());
+
+        // Withdrawals that have not been confirmed cannot get a proof.
+        let mut accepted = entry.clone();
+        accepted.history[0].status = WithdrawalStatusEntry::Accepted;
+        let update = proof_update(reported.clone()).as_fulfillment_proof_for(&accepted);
+        assert!(update.unwrap().is_none());
+    }
+
+    #[test]
+    fn a_recorded_sweep_proof_is_not_overwritten() {
+        let block = bitcoin::constants::genesis_block(bitcoin::Network::Regtest);
+        let txid = block.txdata[0].compute_txid();
+        let merkle_block =
+            bitcoin::MerkleBlock::from_block_with_predicate(&block, |id| id == &txid);
+        let proof = bitcoin::consensus::encode::serialize_hex(&merkle_block);
+
+        let recorded = Fulfillment {
+            bitcoin_txid: txid.to_string(),
+            bitcoin_block_hash: block.block_hash().to_string(),
+            stacks_txid: "stacks-txid".to_string(),
+            btc_fee: 1_000,
+            sweep_tx_proof: Some(proof.clone()),
+            assessed_fee: Some(1_000),
+            ..Default::default()
+        };
+        let entry = confirmed_entry(recorded.clone());
+
+        // A later report of the same sweep with another fee changes
+        // nothing.
+        let reported = Fulfillment {
+            stacks_txid: String::new(),
+            btc_fee: 0,
+            assessed_fee: Some(2_000),
+            ..recorded.clone()
+        };
+        let update = proof_update(reported)
+            .as_fulfillment_proof_for(&entry)
+            .unwrap()
+            .expect("a report of the recorded sweep is not rejected");
+
+        assert_eq!(
+            update.event.status,
+            WithdrawalStatusEntry::Confirmed(recorded)
+        );
+        assert!(update.is_unnecessary(&entry));
+    }
 }
@@ -0,0 +1,165 @@
+use reqwest::StatusCode;
+
+use sbtc::testing::emily::EmilyTables;
+use testing_emily_client::apis;
+use testing_emily_client::apis::Error;
+use testing_emily_client::apis::configuration::Configuration;
+use testing_emily_client::models::{ApiKeyScope, CreateApiKeyRequest, RotateApiKeyRequest};
+
+use crate::common::{clean_test_setup, context_headers, new_test_setup};
+
+/// Returns a copy of the configuration that asks the server to enforce api
+/// key scopes and that authenticates with the api key of the given name
+/// and secret, if any.
+fn enforcing_configuration(
+    configuration: &Configuration,
+    tables: &EmilyTables,
+    api_key: Option<(&str, &str)>,
+) -> Configuration {
+    let mut headers = context_headers(tables);
+    headers.insert(
+        "x-context-enforce-api-key-scopes",
+        reqwest::header::HeaderValue::from_static("true"),
+    );
+    Configuration {
+        client: reqwest::ClientBuilder::new()
+            .default_headers(headers)
+            .build()
+            .unwrap(),
+        bearer_access_token: api_key.map(|(name, secret)| format!("{name}:{secret}")),
+        ..configuration.clone()
+    }
+}
+
+fn response_status<T>(error: Error<T>) -> StatusCode {
+    match error {
+        Error::ResponseError(response) => response.status,
+        error => panic!("unexpected error: {error:?}"),
+    }
+}
+
+#[tokio::test]
+async fn create_rotate_and_deactivate() {
+    let (configuration, tables) = new_test_setup().await;
+
+    let name = format!("{}-operator", tables.api_key);
+    let request = CreateApiKeyRequest {
+        name: name.clone(),
+        secret: "first secret".to_string(),
+        scopes: vec![ApiKeyScope::StatusRead],
+        rate_limit_per_minute: Some(Some(100)),
+    };
+    let created = apis::api_key_api::create_api_key(&configuration, request.clone())
+        .await
+        .unwrap();
+    assert_eq!(created.name, name);
+    assert_eq!(created.scopes, vec![ApiKeyScope::StatusRead]);
+    assert_eq!(created.rate_limit_per_minute, Some(Some(100)));
+    assert!(created.is_active);
+
+    // Keys cannot be created twice.
+    let error = apis::api_key_api::create_api_key(&configuration, request)
+        .await
+        .unwrap_err();
+    assert_eq!(response_status(error), StatusCode::CONFLICT);
+
+    // Rotating a key keeps everything but the secret.
+    let rotate = RotateApiKeyRequest {
+        secret: "second secret".to_string(),
+    };
+    let rotated = apis::api_key_api::rotate_api_key(&configuration, &name, rotate)
+        .await
+        .unwrap();
+    assert_eq!(rotated.scopes, created.scopes);
+    assert!(rotated.updated_at >= created.updated_at);
+
+    apis::api_key_api::deactivate_api_key(&configuration, &name)
+        .await
+        .unwrap();
+    let fetched = apis::api_key_api::get_api_key(&configuration, &name)
+        .await
+        .unwrap();
+    assert!(!fetched.is_active);
+
+    apis::api_key_api::activate_api_key(&configuration, &name)
+        .await
+        .unwrap();
+    let fetched = apis::api_key_api::get_api_key(&configuration, &name)
+        .await
+        .unwrap();
+    assert!(fetched.is_active);
+
+    let error = apis::api_key_api::get_api_key(&configuration, "unknown")
+        .await
+        .unwrap_err();
+    assert_eq!(response_status(error), StatusCode::NOT_FOUND);
+
+    clean_test_setup(tables).await;
+}
+
+#[tokio::test]
+async fn scopes_and_rate_limits_are_enforced() {
+    let (configuration, tables) = new_test_setup().await;
+
+    let name = format!("{}-reader", tables.api_key);
+    let request = CreateApiKeyRequest {
+        name: name.clone(),
+        secret: "reader secret".to_string(),
+        scopes: vec![ApiKeyScope::StatusRead],
+        rate_limit_per_minute: Some(Some(2)),
+    };
+    apis::api_key_api::create_api_key(&configuration, request)
+        .await
+        .unwrap();
+
+    // Health checks never need a key.
+    let anonymous = enforcing_configuration(&configuration, &tables, None);
+    apis::health_api::check_health(&anonymous).await.unwrap();
+
+    // Everything else does.
+    let error = apis::limits_api::get_limits(&anonymous).await.unwrap_err();
+    assert_eq!(response_status(error), StatusCode::UNAUTHORIZED);
+
+    let wrong_secret =
+        enforcing_configuration(&configuration, &tables, Some((&name, "wrong secret")));
+    let error = apis::limits_api::get_limits(&wrong_secret)
+        .await
+        .unwrap_err();
+    assert_eq!(response_status(error), StatusCode::UNAUTHORIZED);
+
+    let reader = enforcing_configuration(&configuration, &tables, Some((&name, "reader secret")));
+    let limits = apis::limits_api::get_limits(&reader).await.unwrap();
+
+    // The key may read, but it may not update anything.
+    let error = apis::limits_api::set_limits(&reader, limits)
+        .await
+        .unwrap_err();
+    assert_eq!(response_status(error), StatusCode::FORBIDDEN);
+
+    // Only requests that were allowed count towards the limit of two per
+    // minute.
+    apis::limits_api::get_limits(&reader).await.unwrap();
+    let error = apis::limits_api::get_limits(&reader).await.unwrap_err();
+    assert_eq!(response_status(error), StatusCode::TOO_MANY_REQUESTS);
+
+    // The old secret of a rotated key is rejected, even though it was
+    // verified before.
+    let rotate = RotateApiKeyRequest {
+        secret: "rotated secret".to_string(),
+    };
+    apis::api_key_api::rotate_api_key(&configuration, &name, rotate)
+        .await
+        .unwrap();
+    let error = apis::limits_api::get_limits(&reader).await.unwrap_err();
+    assert_eq!(response_status(error), StatusCode::UNAUTHORIZED);
+
+    // Deactivated keys are rejected outright.
+    let rotated = enforcing_configuration(&configuration, &tables, Some((&name, "rotated secret")));
+    apis::api_key_api::deactivate_api_key(&configuration, &name)
+        .await
+        .unwrap();
+    let error = apis::limits_api::get_limits(&rotated).await.unwrap_err();
+    assert_eq!(response_status(error), StatusCode::FORBIDDEN);
+
+    clean_test_setup(tables).await;
+}
@@ -34,26 +34,33 @@ pub async fn new_test_setup() -> (Configuration, EmilyTables) {
         ..Default::default()
     };
 
+    configuration.client = reqwest::ClientBuilder::new()
+        .default_headers(context_headers(&tables))
+        .build()
+        .unwrap();
+
+    (configuration, tables)
+}
+
+/// The headers that point the Emily server at the given test tables.
+pub fn context_headers(tables: &EmilyTables) -> reqwest::header::HeaderMap {
     let mut headers = reqwest::header::HeaderMap::new();
     for (shortname, table_name) in [
         ("deposit", &tables.deposit),
         ("withdrawal", &tables.withdrawal),
+        ("deposit-archive", &tables.deposit_archive),
+        ("withdrawal-archive", &tables.withdrawal_archive),
         ("chainstate", &tables.chainstate),
         ("limit", &tables.limit),
         ("throttle", &tables.throttle),
+        ("api-key", &tables.api_key),
     ] {
         headers.insert(
             reqwest::header::HeaderName::from_str(&format!("x-context-{shortname}")).unwrap(),
             reqwest::header::HeaderValue::from_str(table_name).unwrap(),
         );
     }
-
-    configuration.client = reqwest::ClientBuilder::new()
-        .default_headers(headers)
-        .build()
-        .unwrap();
-
-    (configuration, tables)
+    headers
 }
 
 /// Cleanup dynamodb tables
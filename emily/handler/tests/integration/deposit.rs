@@ -3,6 +3,7 @@ use std::collections::HashMap;
 
 use bitcoin::ScriptBuf;
 use bitcoin::consensus::encode::serialize_hex;
+use bitcoin::hashes::Hash as _;
 use bitcoin::opcodes::all as opcodes;
 use sbtc::deposits::DepositScriptInputs;
 use stacks_common::codec::StacksMessageCodec as _;
@@ -12,8 +13,10 @@ use test_case::test_case;
 use sbtc::testing;
 use sbtc::testing::deposits::TxSetup;
 use testing_emily_client::apis::chainstate_api::set_chainstate;
+use testing_emily_client::apis::deposit_api::CreateDepositError;
 use testing_emily_client::models::{
-    Chainstate, DepositStatus, Fulfillment, UpdateDepositsRequestBody,
+    Chainstate, CreateDepositsRequestBody, DepositStatus, FieldValidationError, Fulfillment,
+    RequestField, StatusUpdateActor, UpdateDepositsRequestBody,
 };
 use testing_emily_client::{
     apis::{self, configuration::Configuration},
@@ -182,6 +185,8 @@ async fn create_and_get_deposit_happy_path() {
         status: testing_emily_client::models::DepositStatus::Pending,
         status_message: INITIAL_DEPOSIT_STATUS_MESSAGE.into(),
         replaced_by_tx: None,
+        deposit_block_hash: None,
+        deposit_tx_proof: None,
     };
 
     // Act.
@@ -207,6 +212,280 @@ async fn create_and_get_deposit_happy_path() {
     clean_test_setup(tables).await;
 }
 
+#[tokio::test]
+async fn create_deposits_reports_the_outcome_of_each_deposit() {
+    let (configuration, tables) = new_test_setup().await;
+
+    // Arrange.
+    // --------
+    let DepositTxnData {
+        reclaim_scripts,
+        deposit_scripts,
+        bitcoin_txid,
+        transaction_hex,
+        ..
+    } = DepositTxnData::new(
+        DEPOSIT_LOCK_TIME,
+        DEPOSIT_MAX_FEE,
+        &[DEPOSIT_AMOUNT_SATS, DEPOSIT_AMOUNT_SATS],
+    );
+
+    let requests: Vec<CreateDepositRequestBody> = (0..2)
+        .map(|index| CreateDepositRequestBody {
+            bitcoin_tx_output_index: index as u32,
+            bitcoin_txid: bitcoin_txid.clone(),
+            reclaim_script: reclaim_scripts[index].clone(),
+            deposit_script: deposit_scripts[index].clone(),
+            transaction_hex: transaction_hex.clone(),
+        })
+        .collect();
+
+    let invalid_request = CreateDepositRequestBody {
+        bitcoin_txid: "not a txid".to_string(),
+        ..requests[0].clone()
+    };
+
+    // The first deposit is created ahead of time, so it already exists
+    // when the bulk request is made.
+    apis::deposit_api::create_deposit(&configuration, requests[0].clone())
+        .await
+        .expect("Received an error after making a valid create deposit request api call.");
+
+    let body = CreateDepositsRequestBody {
+        deposits: vec![requests[0].clone(), invalid_request, requests[1].clone()],
+    };
+
+    // Act.
+    // ----
+    let response = apis::deposit_api::create_deposits(&configuration, body)
+        .await
+        .expect("Received an error after making a valid create deposits request api call.");
+
+    // Assert.
+    // -------
+    let statuses: Vec<u32> = response.deposits.iter().map(|d| d.status).collect();
+    assert_eq!(statuses, [200, 400, 201]);
+
+    assert!(response.deposits[1].deposit.clone().flatten().is_none());
+    assert!(response.deposits[1].error.clone().flatten().is_some());
+
+    let created = response.deposits[2].deposit.clone().flatten().unwrap();
+    assert_eq!(created.bitcoin_txid, bitcoin_txid);
+    assert_eq!(created.bitcoin_tx_output_index, 1);
+
+    let gotten_deposit = apis::deposit_api::get_deposit(&configuration, &bitcoin_txid, "1")
+        .await
+        .expect("Received an error after making a valid get deposit request api call.");
+    assert_eq!(gotten_deposit, *created);
+
+    clean_test_setup(tables).await;
+}
+
+#[tokio::test]
+async fn create_deposits_rejects_too_many_deposits() {
+    let (configuration, tables) = new_test_setup().await;
+
+    let body = CreateDepositsRequestBody {
+        deposits: vec![CreateDepositRequestBody::default(); 101],
+    };
+
+    let response = apis::deposit_api::create_deposits(&configuration, body).await;
+    match response {
+        Err(testing_emily_client::apis::Error::ResponseError(error)) => {
+            assert_eq!(error.status, reqwest::StatusCode::BAD_REQUEST);
+        }
+        _ => panic!("expected a 400 response, got {response:?}"),
+    }
+
+    clean_test_setup(tables).await;
+}
+
+#[tokio::test]
+async fn create_deposit_reports_invalid_fields() {
+    let (configuration, tables) = new_test_setup().await;
+
+    let DepositTxnData {
+        reclaim_scripts,
+        bitcoin_txid,
+        transaction_hex,
+        ..
+    } = DepositTxnData::new(DEPOSIT_LOCK_TIME, DEPOSIT_MAX_FEE, &[DEPOSIT_AMOUNT_SATS]);
+
+    // The deposit script is not hex and the output index is out of range.
+    let request = CreateDepositRequestBody {
+        bitcoin_tx_output_index: 10,
+        bitcoin_txid,
+        reclaim_script: reclaim_scripts.first().unwrap().clone(),
+        deposit_script: "abcdefg".to_string(),
+        transaction_hex,
+    };
+    let response = apis::deposit_api::create_deposit(&configuration, request).await;
+
+    let Err(testing_emily_client::apis::Error::ResponseError(error)) = response else {
+        panic!("expected a 400 response");
+    };
+    assert_eq!(error.status, reqwest::StatusCode::BAD_REQUEST);
+    let Some(CreateDepositError::Status400(body)) = error.entity else {
+        panic!("expected a validation error response");
+    };
+    let expected = vec![
+        FieldValidationError::new(RequestField::DepositScript, "invalid deposit script".into()),
+        FieldValidationError::new(
+            RequestField::Outpoint,
+            "invalid bitcoin output index".into(),
+        ),
+    ];
+    assert_eq!(body.errors, expected);
+
+    clean_test_setup(tables).await;
+}
+
+#[tokio::test]
+async fn get_deposit_eta_without_sweep_history() {
+    let (configuration, tables) = new_test_setup().await;
+
+    // Arrange.
+    // --------
+    let bitcoin_tx_output_index = 0;
+    let DepositTxnData {
+        reclaim_scripts,
+        deposit_scripts,
+        bitcoin_txid,
+        transaction_hex,
+        ..
+    } = DepositTxnData::new(DEPOSIT_LOCK_TIME, DEPOSIT_MAX_FEE, &[DEPOSIT_AMOUNT_SATS]);
+
+    let request = CreateDepositRequestBody {
+        bitcoin_tx_output_index,
+        bitcoin_txid: bitcoin_txid.clone(),
+        reclaim_script: reclaim_scripts.first().unwrap().clone(),
+        deposit_script: deposit_scripts.first().unwrap().clone(),
+        transaction_hex,
+    };
+    apis::deposit_api::create_deposit(&configuration, request)
+        .await
+        .expect("Received an error after making a valid create deposit request api call.");
+
+    // Act.
+    // ----
+    let bitcoin_tx_output_index_string = bitcoin_tx_output_index.to_string();
+    let eta = apis::deposit_api::get_deposit_eta(
+        &configuration,
+        &bitcoin_txid,
+        &bitcoin_tx_output_index_string,
+    )
+    .await
+    .expect("Received an error after making a valid get deposit eta request api call.");
+
+    let missing: StandardError =
+        apis::deposit_api::get_deposit_eta(&configuration, &bitcoin_txid, "1")
+            .await
+            .expect_err(
+                "Received a successful response attempting to estimate a nonpresent deposit.",
+            )
+            .into();
+
+    // Assert.
+    // -------
+    // Nothing has been swept yet, so there is nothing to base an estimate on.
+    assert_eq!(eta.bitcoin_txid, bitcoin_txid);
+    assert_eq!(eta.status, DepositStatus::Pending);
+    assert_eq!(eta.deposits_ahead, 0);
+    assert_eq!(eta.sweeps_remaining, 0);
+    assert!(eta.fee_sufficient);
+    assert_eq!(eta.estimated_bitcoin_blocks, Some(None));
+    assert_eq!(missing.status_code, 404);
+
+    clean_test_setup(tables).await;
+}
+
+#[tokio::test]
+async fn get_deposit_history_records_each_status_update() {
+    let (configuration, tables) = new_test_setup().await;
+
+    // Arrange.
+    // --------
+    let bitcoin_tx_output_index = 0;
+    let DepositTxnData {
+        reclaim_scripts,
+        deposit_scripts,
+        bitcoin_txid,
+        transaction_hex,
+        ..
+    } = DepositTxnData::new(DEPOSIT_LOCK_TIME, DEPOSIT_MAX_FEE, &[DEPOSIT_AMOUNT_SATS]);
+
+    let request = CreateDepositRequestBody {
+        bitcoin_tx_output_index,
+        bitcoin_txid: bitcoin_txid.clone(),
+        reclaim_script: reclaim_scripts.first().unwrap().clone(),
+        deposit_script: deposit_scripts.first().unwrap().clone(),
+        transaction_hex,
+    };
+    apis::deposit_api::create_deposit(&configuration, request)
+        .await
+        .expect("Received an error after making a valid create deposit request api call.");
+
+    let update_request = UpdateDepositsRequestBody {
+        deposits: vec![DepositUpdate {
+            bitcoin_tx_output_index,
+            bitcoin_txid: bitcoin_txid.clone(),
+            fulfillment: None,
+            status: DepositStatus::Accepted,
+            status_message: "accepted by the signers".into(),
+            replaced_by_tx: None,
+            deposit_block_hash: None,
+            deposit_tx_proof: None,
+        }],
+    };
+    // Sending the same update twice should only be recorded once.
+    for _ in 0..2 {
+        apis::deposit_api::update_deposits_signer(&configuration, update_request.clone())
+            .await
+            .expect("Received an error after making a valid update deposit request api call.");
+    }
+
+    // Act.
+    // ----
+    let bitcoin_tx_output_index_string = bitcoin_tx_output_index.to_string();
+    let history = apis::deposit_api::get_deposit_history(
+        &configuration,
+        &bitcoin_txid,
+        &bitcoin_tx_output_index_string,
+    )
+    .await
+    .expect("Received an error after making a valid get deposit history request api call.");
+
+    let missing: StandardError =
+        apis::deposit_api::get_deposit_history(&configuration, &bitcoin_txid, "1")
+            .await
+            .expect_err(
+                "Received a successful response getting the history of a nonpresent deposit.",
+            )
+            .into();
+
+    // Assert.
+    // -------
+    assert_eq!(history.bitcoin_txid, bitcoin_txid);
+    assert_eq!(history.bitcoin_tx_output_index, bitcoin_tx_output_index);
+    assert_eq!(history.history.len(), 2);
+
+    let created = &history.history[0];
+    assert_eq!(created.status, DepositStatus::Pending);
+    assert_eq!(created.status_message, INITIAL_DEPOSIT_STATUS_MESSAGE);
+    assert_eq!(created.actor, Some(Some(StatusUpdateActor::Creator)));
+
+    let accepted = &history.history[1];
+    assert_eq!(accepted.status, DepositStatus::Accepted);
+    assert_eq!(accepted.status_message, "accepted by the signers");
+    assert_eq!(accepted.actor, Some(Some(StatusUpdateActor::Signer)));
+    assert!(accepted.updated_at.flatten() >= created.updated_at.flatten());
+    assert!(created.updated_at.flatten().is_some());
+
+    assert_eq!(missing.status_code, 404);
+
+    clean_test_setup(tables).await;
+}
+
 #[tokio::test]
 async fn wipe_databases_test() {
     let (configuration, tables) = new_test_setup().await;
@@ -314,6 +593,8 @@ async fn get_deposits_for_transaction() {
             status: testing_emily_client::models::DepositStatus::Pending,
             status_message: INITIAL_DEPOSIT_STATUS_MESSAGE.into(),
             replaced_by_tx: None,
+            deposit_block_hash: None,
+            deposit_tx_proof: None,
         };
         expected_deposits.push(expected_deposit);
     }
@@ -416,6 +697,7 @@ async fn get_deposits() {
             status,
             next_token.as_deref(),
             Some(chunksize as u32),
+            None,
         )
         .await
         .expect("Received an error after making a valid get deposits api call.");
@@ -479,7 +761,7 @@ async fn get_deposits_large_max_fee() {
     batch_create_deposits(&configuration, create_requests).await;
 
     let status = testing_emily_client::models::DepositStatus::Pending;
-    let response = apis::deposit_api::get_deposits(&configuration, status, None, None)
+    let response = apis::deposit_api::get_deposits(&configuration, status, None, None, None)
         .await
         .expect("Received an error after making a valid get deposits api call.");
 
@@ -787,6 +1069,8 @@ async fn update_deposits() {
         bitcoin_txid: "test_fulfillment_bitcoin_txid".to_string(),
         btc_fee: 2314,
         stacks_txid: "test_fulfillment_stacks_txid".to_string(),
+        sweep_tx_proof: None,
+        assessed_fee: None,
     };
 
     let num_deposits = amounts.len() * deposits_txs.len();
@@ -824,6 +1108,8 @@ async fn update_deposits() {
                 status: update_status,
                 status_message: update_status_message.into(),
                 replaced_by_tx: None,
+                deposit_block_hash: None,
+                deposit_tx_proof: None,
             };
             deposit_updates.push(deposit_update);
 
@@ -844,6 +1130,8 @@ async fn update_deposits() {
                 status: update_status,
                 status_message: update_status_message.into(),
                 replaced_by_tx: None,
+                deposit_block_hash: None,
+                deposit_tx_proof: None,
             };
             expected_deposits.push(expected_deposit);
         }
@@ -935,6 +1223,8 @@ async fn create_deposit_handles_duplicates(status: DepositStatus) {
             bitcoin_txid: "test_fulfillment_bitcoin_txid".to_string(),
             btc_fee: 2314,
             stacks_txid: "test_fulfillment_stacks_txid".to_string(),
+            sweep_tx_proof: None,
+            assessed_fee: None,
         })));
     }
     let replaced_by_tx = if status == DepositStatus::Rbf {
@@ -953,6 +1243,8 @@ async fn create_deposit_handles_duplicates(status: DepositStatus) {
                 status,
                 status_message: "foo".into(),
                 replaced_by_tx,
+                deposit_block_hash: None,
+                deposit_tx_proof: None,
             }],
         },
     )
@@ -1059,6 +1351,8 @@ async fn update_deposits_is_forbidden_for_signer(
                 bitcoin_txid: "test_fulfillment_bitcoin_txid".to_string(),
                 btc_fee: 2314,
                 stacks_txid: "test_fulfillment_stacks_txid".to_string(),
+                sweep_tx_proof: None,
+                assessed_fee: None,
             })));
         }
 
@@ -1078,6 +1372,8 @@ async fn update_deposits_is_forbidden_for_signer(
                     status: previous_status,
                     status_message: "foo".into(),
                     replaced_by_tx,
+                    deposit_block_hash: None,
+                    deposit_tx_proof: None,
                 }],
             },
         )
@@ -1095,6 +1391,8 @@ async fn update_deposits_is_forbidden_for_signer(
             bitcoin_txid: "test_fulfillment_bitcoin_txid".to_string(),
             btc_fee: 2314,
             stacks_txid: "test_fulfillment_stacks_txid".to_string(),
+            sweep_tx_proof: None,
+            assessed_fee: None,
         })));
     }
     let replaced_by_tx = if new_status == DepositStatus::Rbf {
@@ -1113,6 +1411,8 @@ async fn update_deposits_is_forbidden_for_signer(
                 status: new_status,
                 status_message: "foo".into(),
                 replaced_by_tx,
+                deposit_block_hash: None,
+                deposit_tx_proof: None,
             }],
         },
     )
@@ -1220,6 +1520,8 @@ async fn update_deposits_is_not_forbidden_for_sidecar(
                 bitcoin_txid: "test_fulfillment_bitcoin_txid".to_string(),
                 btc_fee: 2314,
                 stacks_txid: "test_fulfillment_stacks_txid".to_string(),
+                sweep_tx_proof: None,
+                assessed_fee: None,
             })));
         }
         let replaced_by_tx = if previous_status == DepositStatus::Rbf {
@@ -1238,6 +1540,8 @@ async fn update_deposits_is_not_forbidden_for_sidecar(
                     status: previous_status,
                     status_message: "foo".into(),
                     replaced_by_tx,
+                    deposit_block_hash: None,
+                    deposit_tx_proof: None,
                 }],
             },
         )
@@ -1255,6 +1559,8 @@ async fn update_deposits_is_not_forbidden_for_sidecar(
             bitcoin_txid: "test_fulfillment_bitcoin_txid".to_string(),
             btc_fee: 2314,
             stacks_txid: "test_fulfillment_stacks_txid".to_string(),
+            sweep_tx_proof: None,
+            assessed_fee: None,
         })));
     }
     let replaced_by_tx = if new_status == DepositStatus::Rbf {
@@ -1273,6 +1579,8 @@ async fn update_deposits_is_not_forbidden_for_sidecar(
                 status: new_status,
                 status_message: "foo".into(),
                 replaced_by_tx,
+                deposit_block_hash: None,
+                deposit_tx_proof: None,
             }],
         },
     )
@@ -1341,6 +1649,8 @@ async fn rbf_status_saved_successfully() {
             status: DepositStatus::Rbf,
             status_message: "RBF initiated".into(),
             replaced_by_tx: Some(Some("replaced_by_txid".to_string())),
+            deposit_block_hash: None,
+            deposit_tx_proof: None,
         }],
     };
 
@@ -1425,6 +1735,8 @@ async fn replaced_by_tx_for_not_rbf_transactions_is_bad_request(status: DepositS
             status,
             status_message: "dummy".into(),
             replaced_by_tx: Some(Some("replaced_by_txid".to_string())),
+            deposit_block_hash: None,
+            deposit_tx_proof: None,
         }],
     };
 
@@ -1527,10 +1839,15 @@ async fn emily_process_deposit_updates_when_some_of_them_already_accepted() {
         .expect("Received an error after making a valid create deposit request api call.");
 
     // Now we should have 2 pending deposits.
-    let deposits =
-        apis::deposit_api::get_deposits(&testing_configuration, DepositStatus::Pending, None, None)
-            .await
-            .expect("Received an error after making a valid get deposits api call.");
+    let deposits = apis::deposit_api::get_deposits(
+        &testing_configuration,
+        DepositStatus::Pending,
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Received an error after making a valid get deposits api call.");
     assert_eq!(deposits.deposits.len(), 2);
 
     // Update first deposit to Accepted.
@@ -1542,6 +1859,8 @@ async fn emily_process_deposit_updates_when_some_of_them_already_accepted() {
             status: DepositStatus::Accepted,
             status_message: "First update".into(),
             replaced_by_tx: None,
+            deposit_block_hash: None,
+            deposit_tx_proof: None,
         }],
     };
     let response = apis::deposit_api::update_deposits_signer(
@@ -1560,16 +1879,22 @@ async fn emily_process_deposit_updates_when_some_of_them_already_accepted() {
     assert_eq!(response.deposits.len(), 1);
 
     // Now we should have 1 pending and 1 accepted deposit.
-    let deposits =
-        apis::deposit_api::get_deposits(&testing_configuration, DepositStatus::Pending, None, None)
-            .await
-            .expect("Received an error after making a valid get deposits api call.");
+    let deposits = apis::deposit_api::get_deposits(
+        &testing_configuration,
+        DepositStatus::Pending,
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Received an error after making a valid get deposits api call.");
     assert_eq!(deposits.deposits.len(), 1);
     let deposits = apis::deposit_api::get_deposits(
         &testing_configuration,
         DepositStatus::Accepted,
         None,
         None,
+        None,
     )
     .await
     .expect("Received an error after making a valid get deposits api call.");
@@ -1585,6 +1910,8 @@ async fn emily_process_deposit_updates_when_some_of_them_already_accepted() {
                 status: DepositStatus::Accepted,
                 status_message: "Second update".into(),
                 replaced_by_tx: None,
+                deposit_block_hash: None,
+                deposit_tx_proof: None,
             },
             DepositUpdate {
                 bitcoin_tx_output_index,
@@ -1593,6 +1920,8 @@ async fn emily_process_deposit_updates_when_some_of_them_already_accepted() {
                 status: DepositStatus::Accepted,
                 status_message: "Second update".into(),
                 replaced_by_tx: None,
+                deposit_block_hash: None,
+                deposit_tx_proof: None,
             },
         ],
     };
@@ -1617,6 +1946,7 @@ async fn emily_process_deposit_updates_when_some_of_them_already_accepted() {
         DepositStatus::Accepted,
         None,
         None,
+        None,
     )
     .await
     .expect("Received an error after making a valid get deposits api call.");
@@ -1684,10 +2014,15 @@ async fn emily_process_deposit_updates_when_some_of_them_are_unknown() {
         .expect("Received an error after making a valid create deposit request api call.");
 
     // Now we should have 1 pending deposit.
-    let deposits =
-        apis::deposit_api::get_deposits(&testing_configuration, DepositStatus::Pending, None, None)
-            .await
-            .expect("Received an error after making a valid get deposits api call.");
+    let deposits = apis::deposit_api::get_deposits(
+        &testing_configuration,
+        DepositStatus::Pending,
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Received an error after making a valid get deposits api call.");
     assert_eq!(deposits.deposits.len(), 1);
 
     // Now we update both deposits to Accepted in a batch. This still should be a valid api call
@@ -1701,6 +2036,8 @@ async fn emily_process_deposit_updates_when_some_of_them_are_unknown() {
                 status: DepositStatus::Accepted,
                 status_message: "Second update".into(),
                 replaced_by_tx: None,
+                deposit_block_hash: None,
+                deposit_tx_proof: None,
             },
             DepositUpdate {
                 bitcoin_tx_output_index,
@@ -1709,6 +2046,8 @@ async fn emily_process_deposit_updates_when_some_of_them_are_unknown() {
                 status: DepositStatus::Accepted,
                 status_message: "Second update".into(),
                 replaced_by_tx: None,
+                deposit_block_hash: None,
+                deposit_tx_proof: None,
             },
         ],
     };
@@ -1749,6 +2088,7 @@ async fn emily_process_deposit_updates_when_some_of_them_are_unknown() {
         DepositStatus::Accepted,
         None,
         None,
+        None,
     )
     .await
     .expect("Received an error after making a valid get deposits api call.");
@@ -1806,6 +2146,8 @@ async fn only_completed_deposit_can_have_fulfillment(status: DepositStatus, is_s
         bitcoin_txid: "test_fulfillment_bitcoin_txid".to_string(),
         btc_fee: 2314,
         stacks_txid: "test_fulfillment_stacks_txid".to_string(),
+        sweep_tx_proof: None,
+        assessed_fee: None,
     })));
 
     // Creating replacing by tx only if necessary
@@ -1823,6 +2165,8 @@ async fn only_completed_deposit_can_have_fulfillment(status: DepositStatus, is_s
             status,
             status_message: "foo".into(),
             replaced_by_tx,
+            deposit_block_hash: None,
+            deposit_tx_proof: None,
         }],
     };
 
@@ -1876,3 +2220,84 @@ async fn only_completed_deposit_can_have_fulfillment(status: DepositStatus, is_s
 
     clean_test_setup(tables).await;
 }
+
+#[tokio::test]
+async fn signers_can_attach_deposit_tx_proofs() {
+    let (configuration, tables) = new_test_setup().await;
+
+    let bitcoin_tx_output_index = 0;
+    let DepositTxnData {
+        reclaim_scripts,
+        deposit_scripts,
+        bitcoin_txid,
+        transaction_hex,
+        ..
+    } = DepositTxnData::new(DEPOSIT_LOCK_TIME, DEPOSIT_MAX_FEE, &[DEPOSIT_AMOUNT_SATS]);
+    let create_deposit_body = CreateDepositRequestBody {
+        bitcoin_tx_output_index,
+        bitcoin_txid: bitcoin_txid.clone(),
+        deposit_script: deposit_scripts.first().unwrap().clone(),
+        reclaim_script: reclaim_scripts.first().unwrap().clone(),
+        transaction_hex: transaction_hex.clone(),
+    };
+    apis::deposit_api::create_deposit(&configuration, create_deposit_body)
+        .await
+        .expect("Received an error after making a valid create deposit request api call.");
+
+    // Put the deposit transaction in a block and prove its inclusion.
+    let tx: bitcoin::Transaction =
+        bitcoin::consensus::encode::deserialize_hex(&transaction_hex).unwrap();
+    let mut block = bitcoin::constants::genesis_block(bitcoin::Network::Regtest);
+    block.txdata = vec![tx];
+    block.header.merkle_root = block.compute_merkle_root().unwrap();
+    let txid = block.txdata[0].compute_txid();
+    let merkle_block = bitcoin::MerkleBlock::from_block_with_predicate(&block, |id| id == &txid);
+    let proof = serialize_hex(&merkle_block);
+    let block_hash = block.block_hash().to_string();
+
+    let proof_update = |block_hash: &str, proof: &str| UpdateDepositsRequestBody {
+        deposits: vec![DepositUpdate {
+            bitcoin_tx_output_index,
+            bitcoin_txid: bitcoin_txid.clone(),
+            fulfillment: None,
+            status: DepositStatus::Pending,
+            status_message: "".into(),
+            replaced_by_tx: None,
+            deposit_block_hash: Some(Some(block_hash.to_string())),
+            deposit_tx_proof: Some(Some(proof.to_string())),
+        }],
+    };
+
+    // A proof for another block is rejected.
+    let other_block_hash = bitcoin::BlockHash::from_byte_array([1; 32]).to_string();
+    let response = apis::deposit_api::update_deposits_signer(
+        &configuration,
+        proof_update(&other_block_hash, &proof),
+    )
+    .await
+    .expect("Received an error after making a valid update deposit request api call.");
+    assert_eq!(response.deposits[0].status, 400);
+
+    // A valid proof is attached without changing the status of the deposit.
+    let response = apis::deposit_api::update_deposits_signer(
+        &configuration,
+        proof_update(&block_hash, &proof),
+    )
+    .await
+    .expect("Received an error after making a valid update deposit request api call.");
+    assert_eq!(response.deposits[0].status, 200);
+
+    let deposit = apis::deposit_api::get_deposit(
+        &configuration,
+        &bitcoin_txid,
+        &bitcoin_tx_output_index.to_string(),
+    )
+    .await
+    .expect("Received an error after making a valid get deposit api call.");
+    assert_eq!(deposit.status, DepositStatus::Pending);
+    assert_eq!(deposit.status_message, INITIAL_DEPOSIT_STATUS_MESSAGE);
+    assert_eq!(deposit.deposit_block_hash, Some(Some(block_hash)));
+    assert_eq!(deposit.deposit_tx_proof, Some(Some(proof)));
+
+    clean_test_setup(tables).await;
+}
@@ -1,3 +1,7 @@
+use reqwest::StatusCode;
+use testing_emily_client::apis::configuration::{
+    ACCEPT_VERSION_HEADER, EMILY_API_VERSION, check_api_version,
+};
 use testing_emily_client::apis::health_api;
 
 use crate::common::{clean_test_setup, new_test_setup};
@@ -32,3 +36,38 @@ async fn test_dynamic_context() {
 
     clean_test_setup(tables).await;
 }
+
+#[tokio::test]
+async fn api_versions_are_negotiated() {
+    let (configuration, tables) = new_test_setup().await;
+
+    let health = health_api::check_health(&configuration).await.unwrap();
+    assert_eq!(check_api_version(&health), Ok(EMILY_API_VERSION));
+
+    let url = format!("{}/health", configuration.base_path);
+    let client = reqwest::Client::new();
+
+    // Emily names the version that it served the request with.
+    let response = client
+        .get(&url)
+        .header(ACCEPT_VERSION_HEADER, EMILY_API_VERSION)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()["api-version"],
+        EMILY_API_VERSION.to_string().as_str()
+    );
+
+    // Requests for versions that Emily does not serve are rejected.
+    let response = client
+        .get(&url)
+        .header(ACCEPT_VERSION_HEADER, health.api_version + 1)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+
+    clean_test_setup(tables).await;
+}
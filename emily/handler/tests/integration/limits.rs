@@ -18,7 +18,9 @@ async fn empty_default_is_as_expected() {
     let (configuration, tables) = new_test_setup().await;
 
     let expected_empty_default = models::Limits {
+        available_to_mint: Some(None),
         available_to_withdraw: Some(None),
+        blocks_until_withdrawal_headroom: Some(None),
         peg_cap: Some(None),
         per_deposit_minimum: Some(None),
         per_deposit_cap: Some(None),
@@ -26,6 +28,7 @@ async fn empty_default_is_as_expected() {
         rolling_withdrawal_blocks: Some(None),
         rolling_withdrawal_cap: Some(None),
         throttle_mode_initiator: Some(None),
+        deposit_confirmation_policy: Some(vec![]),
         account_caps: HashMap::new(),
     };
 
@@ -141,7 +144,9 @@ async fn adding_and_then_updating_single_accout_limit_works() {
 
     // The global limits should show the latest account caps.
     let expected_limits = Limits {
+        available_to_mint: Some(None),
         available_to_withdraw: Some(None),
+        blocks_until_withdrawal_headroom: Some(None),
         peg_cap: Some(None),
         per_deposit_minimum: Some(None),
         per_deposit_cap: Some(None),
@@ -150,6 +155,7 @@ async fn adding_and_then_updating_single_accout_limit_works() {
         rolling_withdrawal_cap: Some(None),
         account_caps: expected_account_caps.clone(),
         throttle_mode_initiator: Some(None),
+        deposit_confirmation_policy: Some(vec![]),
     };
 
     // Act.
@@ -280,7 +286,9 @@ async fn test_updating_account_limits_via_global_limit_works() {
     .map(|(account_name, limits)| (account_name.to_string(), limits.clone()))
     .collect();
     let global_limits_to_set = Limits {
+        available_to_mint: Some(None),
         available_to_withdraw: Some(None),
+        blocks_until_withdrawal_headroom: Some(None),
         peg_cap: Some(Some(123)),
         per_deposit_minimum: Some(Some(654)),
         per_deposit_cap: Some(Some(456)),
@@ -288,6 +296,7 @@ async fn test_updating_account_limits_via_global_limit_works() {
         rolling_withdrawal_blocks: Some(Some(101)),
         rolling_withdrawal_cap: Some(Some(112)),
         throttle_mode_initiator: Some(None),
+        deposit_confirmation_policy: Some(vec![]),
         account_caps: account_limits_to_set_globally.clone(),
     };
 
@@ -335,7 +344,9 @@ async fn test_updating_account_limits_via_global_limit_works() {
     .map(|(account_name, limits)| (account_name.to_string(), limits.clone()))
     .collect();
     let expected_global_limits = Limits {
+        available_to_mint: Some(None),
         available_to_withdraw: Some(Some(112)),
+        blocks_until_withdrawal_headroom: Some(Some(0)),
         peg_cap: Some(Some(123)),
         per_deposit_minimum: Some(Some(654)),
         per_deposit_cap: Some(Some(456)),
@@ -343,6 +354,7 @@ async fn test_updating_account_limits_via_global_limit_works() {
         rolling_withdrawal_blocks: Some(Some(101)),
         rolling_withdrawal_cap: Some(Some(112)),
         throttle_mode_initiator: Some(None),
+        deposit_confirmation_policy: Some(vec![]),
         account_caps: expected_global_account_limits.clone(),
     };
 
@@ -407,7 +419,9 @@ async fn test_incomplete_rolling_withdrawal_limit_config_returns_error(
 
     // Arrange.
     let limits = Limits {
+        available_to_mint: Some(None),
         available_to_withdraw: Some(None),
+        blocks_until_withdrawal_headroom: Some(None),
         peg_cap: Some(None),
         per_deposit_minimum: Some(None),
         per_deposit_cap: Some(None),
@@ -415,6 +429,7 @@ async fn test_incomplete_rolling_withdrawal_limit_config_returns_error(
         rolling_withdrawal_blocks: Some(rolling_withdrawal_blocks),
         rolling_withdrawal_cap: Some(rolling_withdrawal_cap),
         throttle_mode_initiator: Some(None),
+        deposit_confirmation_policy: Some(vec![]),
         account_caps: HashMap::new(),
     };
 
@@ -440,7 +455,9 @@ async fn test_complete_rolling_withdrawal_limit_config_works(
     let (configuration, tables) = new_test_setup().await;
 
     let limits = Limits {
+        available_to_mint: Some(None),
         available_to_withdraw: Some(rolling_withdrawal_cap),
+        blocks_until_withdrawal_headroom: Some(rolling_withdrawal_cap.map(|_| 0)),
         peg_cap: Some(None),
         per_deposit_minimum: Some(None),
         per_deposit_cap: Some(None),
@@ -448,6 +465,7 @@ async fn test_complete_rolling_withdrawal_limit_config_works(
         rolling_withdrawal_blocks: Some(rolling_withdrawal_blocks),
         rolling_withdrawal_cap: Some(rolling_withdrawal_cap),
         throttle_mode_initiator: Some(None),
+        deposit_confirmation_policy: Some(vec![]),
         account_caps: HashMap::new(),
     };
     if let Some(window_size) = rolling_withdrawal_blocks {
@@ -476,7 +494,9 @@ async fn test_available_to_withdraw_no_chainstate_in_db_at_target_height() {
 
     // Set limits
     let limits = Limits {
+        available_to_mint: Some(None),
         available_to_withdraw: Some(None),
+        blocks_until_withdrawal_headroom: Some(None),
         peg_cap: Some(None),
         per_deposit_minimum: Some(None),
         per_deposit_cap: Some(None),
@@ -484,6 +504,7 @@ async fn test_available_to_withdraw_no_chainstate_in_db_at_target_height() {
         rolling_withdrawal_blocks: Some(Some(100)),
         rolling_withdrawal_cap: Some(Some(10_000)),
         throttle_mode_initiator: Some(None),
+        deposit_confirmation_policy: Some(vec![]),
         account_caps: HashMap::new(),
     };
     // Set some chainstates to make set_limits work
@@ -535,7 +556,9 @@ async fn test_available_to_withdraw_success() {
 
     // Set limits
     let limits = Limits {
+        available_to_mint: Some(None),
         available_to_withdraw: Some(None),
+        blocks_until_withdrawal_headroom: Some(None),
         peg_cap: Some(None),
         per_deposit_minimum: Some(None),
         per_deposit_cap: Some(None),
@@ -543,6 +566,7 @@ async fn test_available_to_withdraw_success() {
         rolling_withdrawal_blocks: Some(Some(10)),
         rolling_withdrawal_cap: Some(Some(10_000)),
         throttle_mode_initiator: Some(None),
+        deposit_confirmation_policy: Some(vec![]),
         account_caps: HashMap::new(),
     };
     // Set some chainstates to make set_limits work
@@ -607,3 +631,120 @@ async fn test_available_to_withdraw_success() {
 
     clean_test_setup(tables).await;
 }
+
+#[tokio::test]
+async fn test_blocks_until_withdrawal_headroom_when_cap_is_exhausted() {
+    let (configuration, tables) = new_test_setup().await;
+
+    // Set limits
+    let limits = Limits {
+        available_to_mint: Some(None),
+        available_to_withdraw: Some(None),
+        blocks_until_withdrawal_headroom: Some(None),
+        peg_cap: Some(None),
+        per_deposit_minimum: Some(None),
+        per_deposit_cap: Some(None),
+        per_withdrawal_cap: Some(None),
+        rolling_withdrawal_blocks: Some(Some(10)),
+        rolling_withdrawal_cap: Some(Some(2000)),
+        throttle_mode_initiator: Some(None),
+        deposit_confirmation_policy: Some(vec![]),
+        account_caps: HashMap::new(),
+    };
+    // Set some chainstates to make set_limits work
+    let chainstates: Vec<Chainstate> = (0..12)
+        .map(|height| new_test_chainstate(height, height, 0))
+        .collect();
+    let _ = batch_set_chainstates(&configuration, chainstates).await;
+    let result = apis::limits_api::set_limits(&configuration, limits.clone()).await;
+    assert!(result.is_ok());
+
+    // Create chainstates
+    let min_bitcoin_height = 1_000_000;
+    let max_bitcoin_height = 1_000_020;
+    let stacks_block_per_bitcoin_block = 5;
+    let mut stacks_height = 2_000_000;
+    let mut chainstates: Vec<_> = Default::default();
+
+    for bitcoin_height in min_bitcoin_height..max_bitcoin_height {
+        for _ in 0..stacks_block_per_bitcoin_block {
+            let chainstate = new_test_chainstate(bitcoin_height, stacks_height, 0);
+            chainstates.push(chainstate);
+            stacks_height += 1;
+        }
+    }
+
+    let _ = batch_set_chainstates(&configuration, chainstates).await;
+
+    // Create withdrawals
+
+    // bitcoin heights in window: [1_000_010;1_000_019] (both sides including)
+    // stacks heights in window: [2_000_050;2_000_099] (both sides including)
+
+    // The withdrawals add up to more than the cap. The oldest one leaves the
+    // window first but is too small to make room, so the cap only has
+    // headroom once the withdrawal anchored at bitcoin block 1_000_014 leaves
+    // the window, which happens at bitcoin block 1_000_024.
+    for (stacks_height, amount) in [(2_000_050, 200), (2_000_070, 1000), (2_000_099, 1000)] {
+        let request = CreateWithdrawalRequestBody {
+            amount,
+            parameters: Box::new(WithdrawalParameters { max_fee: 100 }),
+            recipient: "test_recepient".into(),
+            sender: "test_sender".into(),
+            request_id: stacks_height,
+            stacks_block_hash: "test_hash".into(),
+            stacks_block_height: stacks_height,
+            txid: "test_txid".into(),
+        };
+
+        apis::withdrawal_api::create_withdrawal(&configuration, request.clone())
+            .await
+            .expect("Received an error after making a valid create withdrawal request api call.");
+    }
+
+    // Get limits and perform assertions
+    let limits = apis::limits_api::get_limits(&configuration)
+        .await
+        .expect("failed to get limits during a valid api call");
+    assert_eq!(limits.available_to_withdraw, Some(Some(0)));
+    assert_eq!(limits.blocks_until_withdrawal_headroom, Some(Some(5)));
+
+    clean_test_setup(tables).await;
+}
+
+#[tokio::test]
+async fn reported_available_to_mint_is_returned_with_the_limits() {
+    let (configuration, tables) = new_test_setup().await;
+
+    let limits = apis::limits_api::get_limits(&configuration)
+        .await
+        .expect("failed to get limits during a valid api call");
+    assert_eq!(limits.available_to_mint, Some(None));
+
+    let request = models::SetAvailableToMintRequestBody::new(123_456);
+    let limits = apis::limits_api::set_available_to_mint(&configuration, request)
+        .await
+        .expect("failed to report the available to mint amount during a valid api call");
+    assert_eq!(limits.available_to_mint, Some(Some(123_456)));
+    // The reported amount is not an account limit.
+    assert!(limits.account_caps.is_empty());
+
+    // Setting the limits does not change the reported amount, even if the
+    // request includes one.
+    let limits_to_set = Limits {
+        available_to_mint: Some(Some(1)),
+        peg_cap: Some(Some(1_000_000)),
+        ..limits
+    };
+    apis::limits_api::set_limits(&configuration, limits_to_set)
+        .await
+        .expect("failed to set limits during a valid api call");
+
+    let limits = apis::limits_api::get_limits(&configuration)
+        .await
+        .expect("failed to get limits during a valid api call");
+    assert_eq!(limits.peg_cap, Some(Some(1_000_000)));
+    assert_eq!(limits.available_to_mint, Some(Some(123_456)));
+
+    clean_test_setup(tables).await;
+}
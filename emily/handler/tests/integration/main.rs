@@ -1,5 +1,7 @@
 //! Integration tests for the Emily handler.
 
+/// Api key test module.
+pub mod api_key;
 /// Chainstate test module.
 pub mod chainstate;
 /// Common test utilities.
@@ -14,6 +16,8 @@ pub mod health;
 pub mod limits;
 /// New block test module.
 pub mod new_block;
+/// Retention test module.
+pub mod retention;
 /// Throttle test modeule.
 pub mod throttle;
 /// Withdrawal test module.
@@ -0,0 +1,128 @@
+use reqwest::header::HeaderValue;
+
+use testing_emily_client::apis;
+use testing_emily_client::models::{
+    CreateWithdrawalRequestBody, UpdateWithdrawalsRequestBody, WithdrawalParameters,
+    WithdrawalStatus, WithdrawalUpdate,
+};
+
+use crate::common::{
+    StandardError, batch_set_chainstates, clean_test_setup, context_headers, new_test_chainstate,
+    new_test_setup,
+};
+
+#[tokio::test]
+async fn completed_withdrawals_are_archived_then_purged() {
+    // Arrange.
+    // --------
+    let (mut configuration, tables) = new_test_setup().await;
+
+    // Archive completed requests after a day and delete them after two.
+    let mut headers = context_headers(&tables);
+    headers.insert(
+        "x-context-archive-after-days",
+        HeaderValue::from_static("1"),
+    );
+    headers.insert("x-context-purge-after-days", HeaderValue::from_static("2"));
+    configuration.client = reqwest::ClientBuilder::new()
+        .default_headers(headers)
+        .build()
+        .unwrap();
+
+    let chainstates =
+        batch_set_chainstates(&configuration, vec![new_test_chainstate(1, 1, 0)]).await;
+    let chainstate = chainstates.first().unwrap();
+
+    let request_id = 1;
+    let request = CreateWithdrawalRequestBody {
+        amount: 10000,
+        parameters: Box::new(WithdrawalParameters { max_fee: 100 }),
+        recipient: "TEST_RECIPIENT".into(),
+        sender: "TEST_SENDER".into(),
+        request_id,
+        stacks_block_hash: chainstate.stacks_block_hash.clone(),
+        stacks_block_height: chainstate.stacks_block_height,
+        txid: "test_txid".to_string(),
+    };
+    apis::withdrawal_api::create_withdrawal(&configuration, request)
+        .await
+        .expect("Received an error after making a valid create withdrawal request api call.");
+
+    let update = UpdateWithdrawalsRequestBody {
+        withdrawals: vec![WithdrawalUpdate {
+            request_id,
+            fulfillment: None,
+            expected_fulfillment_info: None,
+            status: WithdrawalStatus::Failed,
+            status_message: "failed".into(),
+        }],
+    };
+    apis::withdrawal_api::update_withdrawals_signer(&configuration, update)
+        .await
+        .expect("Received an error after making a valid update withdrawal request api call.");
+
+    // The withdrawal was last updated at stacks block 1, and stacks block 2
+    // is anchored more than a day of bitcoin blocks below the new tip.
+    batch_set_chainstates(
+        &configuration,
+        vec![new_test_chainstate(2, 2, 0), new_test_chainstate(150, 3, 0)],
+    )
+    .await;
+
+    // Act.
+    // ----
+    let archived = apis::retention_api::run_retention(&configuration)
+        .await
+        .expect("Received an error after making a valid run retention api call.");
+
+    let hot = apis::withdrawal_api::get_withdrawals(
+        &configuration,
+        WithdrawalStatus::Failed,
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Received an error after making a valid get withdrawals api call.");
+    let with_archived = apis::withdrawal_api::get_withdrawals(
+        &configuration,
+        WithdrawalStatus::Failed,
+        None,
+        None,
+        Some(true),
+    )
+    .await
+    .expect("Received an error after making a valid get withdrawals api call.");
+    let withdrawal = apis::withdrawal_api::get_withdrawal(&configuration, request_id)
+        .await
+        .expect("Received an error after making a valid get withdrawal api call.");
+
+    // Two days of bitcoin blocks later the archived withdrawal is deleted.
+    batch_set_chainstates(&configuration, vec![new_test_chainstate(300, 4, 0)]).await;
+    let purged = apis::retention_api::run_retention(&configuration)
+        .await
+        .expect("Received an error after making a valid run retention api call.");
+
+    let missing: StandardError = apis::withdrawal_api::get_withdrawal(&configuration, request_id)
+        .await
+        .expect_err("Received a successful response attempting to get a purged withdrawal.")
+        .into();
+
+    // Assert.
+    // -------
+    assert_eq!(archived.archived_withdrawals, 1);
+    assert_eq!(archived.purged_withdrawals, 0);
+    assert!(archived.complete);
+
+    assert!(hot.withdrawals.is_empty());
+    assert_eq!(with_archived.withdrawals.len(), 1);
+    assert_eq!(with_archived.withdrawals[0].request_id, request_id);
+    assert_eq!(withdrawal.status, WithdrawalStatus::Failed);
+
+    assert_eq!(purged.archived_withdrawals, 0);
+    assert_eq!(purged.purged_withdrawals, 1);
+    assert!(purged.complete);
+    assert_eq!(missing.status_code, 404);
+
+    clean_test_setup(tables).await;
+}
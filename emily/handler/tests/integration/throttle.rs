@@ -22,7 +22,9 @@ async fn base_flow() {
 
     // Set some limits first
     let limits = Limits {
+        available_to_mint: Some(None),
         available_to_withdraw: Some(Some(10_000_000_000)),
+        blocks_until_withdrawal_headroom: Some(Some(0)),
         peg_cap: Some(None),
         per_deposit_minimum: Some(None),
         per_deposit_cap: Some(None),
@@ -30,6 +32,7 @@ async fn base_flow() {
         rolling_withdrawal_blocks: Some(Some(1)),
         rolling_withdrawal_cap: Some(Some(10_000_000_000)),
         throttle_mode_initiator: Some(None),
+        deposit_confirmation_policy: Some(vec![]),
         account_caps: HashMap::new(),
     };
     // Set some chainstates to make set_limits work
@@ -203,7 +206,9 @@ async fn throttle_does_not_overwrite_stronger_limits(
     };
 
     let limits = Limits {
+        available_to_mint: Some(None),
         available_to_withdraw: Some(Some(10000)),
+        blocks_until_withdrawal_headroom: Some(Some(0)),
         peg_cap: Some(None),
         per_deposit_minimum: Some(None),
         per_deposit_cap: Some(None),
@@ -212,6 +217,7 @@ async fn throttle_does_not_overwrite_stronger_limits(
         rolling_withdrawal_cap,
         account_caps: HashMap::new(),
         throttle_mode_initiator: Some(None),
+        deposit_confirmation_policy: Some(vec![]),
     };
     // Set some chainstates to make set_limits work
     let chainstates: Vec<Chainstate> = (0..110)
@@ -320,7 +326,9 @@ async fn start_throttle_returns_proper_error() {
 
     // Set some limits first (needed for calculate_throttle_mode_limits to work)
     let limits = Limits {
+        available_to_mint: Some(None),
         available_to_withdraw: Some(Some(10000)),
+        blocks_until_withdrawal_headroom: Some(Some(0)),
         peg_cap: Some(None),
         per_deposit_minimum: Some(None),
         per_deposit_cap: Some(None),
@@ -328,6 +336,7 @@ async fn start_throttle_returns_proper_error() {
         rolling_withdrawal_blocks: Some(Some(100)),
         rolling_withdrawal_cap: Some(Some(10_000)),
         throttle_mode_initiator: Some(None),
+        deposit_confirmation_policy: Some(vec![]),
         account_caps: HashMap::new(),
     };
     let chainstates: Vec<Chainstate> = (0..110)
@@ -398,7 +407,9 @@ async fn throttle_mode_overwrites_unlimited_limits() {
 
     // Set limits to unlimited (None)
     let limits = Limits {
+        available_to_mint: Some(None),
         available_to_withdraw: Some(None),
+        blocks_until_withdrawal_headroom: Some(None),
         peg_cap: Some(None),
         per_deposit_minimum: Some(None),
         per_deposit_cap: Some(None),
@@ -406,6 +417,7 @@ async fn throttle_mode_overwrites_unlimited_limits() {
         rolling_withdrawal_blocks: Some(None),
         rolling_withdrawal_cap: Some(None),
         throttle_mode_initiator: Some(None),
+        deposit_confirmation_policy: Some(vec![]),
         account_caps: HashMap::new(),
     };
     // Set some chainstates to make set_limits work
@@ -454,7 +466,9 @@ async fn throttle_mode_initiator_correctly_shown_at_limits() {
 
     // Set some initial limits
     let initial_limits = Limits {
+        available_to_mint: Some(None),
         available_to_withdraw: Some(Some(10000)),
+        blocks_until_withdrawal_headroom: Some(Some(0)),
         peg_cap: Some(None),
         per_deposit_minimum: Some(None),
         per_deposit_cap: Some(None),
@@ -462,6 +476,7 @@ async fn throttle_mode_initiator_correctly_shown_at_limits() {
         rolling_withdrawal_blocks: Some(Some(100)),
         rolling_withdrawal_cap: Some(Some(10_000)),
         throttle_mode_initiator: Some(None),
+        deposit_confirmation_policy: Some(vec![]),
         account_caps: HashMap::new(),
     };
     // Set some chainstates to make set_limits work
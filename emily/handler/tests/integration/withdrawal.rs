@@ -152,6 +152,7 @@ async fn get_withdrawals() {
 
         let expected_withdrawal_info = WithdrawalInfo {
             amount,
+            fulfillment: None,
             last_update_block_hash: BLOCK_HASH.into(),
             last_update_height: BLOCK_HEIGHT,
             recipient: RECIPIENT.into(),
@@ -184,6 +185,7 @@ async fn get_withdrawals() {
             status,
             next_token.as_deref(),
             Some(chunksize as u32),
+            None,
         )
         .await
         .expect("Received an error after making a valid get withdrawal api call.");
@@ -249,6 +251,7 @@ async fn get_withdrawals_by_recipient() {
 
             let expected_withdrawal_info = WithdrawalInfo {
                 amount,
+                fulfillment: None,
                 last_update_block_hash: BLOCK_HASH.into(),
                 last_update_height: BLOCK_HEIGHT,
                 recipient: recipient.into(),
@@ -353,6 +356,7 @@ async fn get_withdrawals_by_sender() {
 
             let expected_withdrawal_info = WithdrawalInfo {
                 amount,
+                fulfillment: None,
                 last_update_block_hash: BLOCK_HASH.into(),
                 last_update_height: BLOCK_HEIGHT,
                 recipient: RECIPIENT.into(),
@@ -448,6 +452,8 @@ async fn update_withdrawals() {
         bitcoin_txid: "test_fulfillment_bitcoin_txid".to_string(),
         btc_fee: 2314,
         stacks_txid: "test_fulfillment_stacks_txid".to_string(),
+        sweep_tx_proof: None,
+        assessed_fee: None,
     };
 
     let mut create_requests: Vec<CreateWithdrawalRequestBody> =
@@ -597,6 +603,8 @@ async fn update_withdrawals_is_forbidden_for_signer(
                 bitcoin_txid: "test_fulfillment_bitcoin_txid".to_string(),
                 btc_fee: 2314,
                 stacks_txid: "test_fulfillment_stacks_txid".to_string(),
+                sweep_tx_proof: None,
+                assessed_fee: None,
             })));
         }
 
@@ -626,6 +634,8 @@ async fn update_withdrawals_is_forbidden_for_signer(
             bitcoin_txid: "test_fulfillment_bitcoin_txid".to_string(),
             btc_fee: 2314,
             stacks_txid: "test_fulfillment_stacks_txid".to_string(),
+            sweep_tx_proof: None,
+            assessed_fee: None,
         })));
     }
 
@@ -735,6 +745,8 @@ async fn update_withdrawals_is_not_forbidden_for_sidecar(
                 bitcoin_txid: "test_fulfillment_bitcoin_txid".to_string(),
                 btc_fee: 2314,
                 stacks_txid: "test_fulfillment_stacks_txid".to_string(),
+                sweep_tx_proof: None,
+                assessed_fee: None,
             })));
         }
 
@@ -764,6 +776,8 @@ async fn update_withdrawals_is_not_forbidden_for_sidecar(
             bitcoin_txid: "test_fulfillment_bitcoin_txid".to_string(),
             btc_fee: 2314,
             stacks_txid: "test_fulfillment_stacks_txid".to_string(),
+            sweep_tx_proof: None,
+            assessed_fee: None,
         })));
     }
 
@@ -864,6 +878,7 @@ async fn emily_process_withdrawal_updates_when_some_of_them_already_accepted() {
         WithdrawalStatus::Pending,
         None,
         None,
+        None,
     )
     .await
     .expect("Received an error after making a valid get withdrawals api call.");
@@ -900,6 +915,7 @@ async fn emily_process_withdrawal_updates_when_some_of_them_already_accepted() {
         WithdrawalStatus::Pending,
         None,
         None,
+        None,
     )
     .await
     .expect("Received an error after making a valid get withdrawals api call.");
@@ -909,6 +925,7 @@ async fn emily_process_withdrawal_updates_when_some_of_them_already_accepted() {
         WithdrawalStatus::Accepted,
         None,
         None,
+        None,
     )
     .await
     .expect("Received an error after making a valid get withdrawals api call.");
@@ -954,6 +971,7 @@ async fn emily_process_withdrawal_updates_when_some_of_them_already_accepted() {
         WithdrawalStatus::Accepted,
         None,
         None,
+        None,
     )
     .await
     .expect("Received an error after making a valid get withdrawals api call.");
@@ -1023,6 +1041,7 @@ async fn emily_process_withdrawal_updates_when_some_of_them_are_unknown() {
         WithdrawalStatus::Pending,
         None,
         None,
+        None,
     )
     .await
     .expect("Received an error after making a valid get withdrawals api call.");
@@ -1085,6 +1104,7 @@ async fn emily_process_withdrawal_updates_when_some_of_them_are_unknown() {
         WithdrawalStatus::Accepted,
         None,
         None,
+        None,
     )
     .await
     .expect("Received an error after making a valid get withdrawals api call.");
@@ -1275,6 +1295,8 @@ async fn only_confirmed_withdrawals_can_have_fulfillment(
         bitcoin_txid: "test_fulfillment_bitcoin_txid".to_string(),
         btc_fee: 2314,
         stacks_txid: "test_fulfillment_stacks_txid".to_string(),
+        sweep_tx_proof: None,
+        assessed_fee: None,
     })));
 
     let request = UpdateWithdrawalsRequestBody {
@@ -9,6 +9,8 @@ use utoipa::openapi::path::Parameter;
 use utoipa::openapi::path::ParameterIn;
 use utoipa::openapi::security::ApiKey;
 use utoipa::openapi::security::ApiKeyValue;
+use utoipa::openapi::security::HttpAuthScheme;
+use utoipa::openapi::security::HttpBuilder;
 use utoipa::openapi::security::SecurityScheme;
 
 /// The private api definition.
@@ -75,6 +77,28 @@ impl Modify for AwsApiKey {
     }
 }
 
+/// Openapi spec modifier that adds Emily's own api keys to the OpenAPI specification.
+/// Callers send them as bearer tokens, so that they are configured separately from
+/// the API Gateway key in the generated clients.
+struct EmilyApiKey;
+impl Modify for EmilyApiKey {
+    /// Modify the OpenAPI specification to include the Emily api key.
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        if let Some(schema) = openapi.components.as_mut() {
+            schema.add_security_scheme(
+                "EmilyApiKey",
+                SecurityScheme::Http(
+                    HttpBuilder::new()
+                        .scheme(HttpAuthScheme::Bearer)
+                        .bearer_format("<name>:<secret>")
+                        .description(Some("Emily api key"))
+                        .build(),
+                ),
+            );
+        }
+    }
+}
+
 /// Attaches the AWS Lambda integration to the OpenAPI specification. This is necessary
 /// for the AWS CDK to attach the lambda to the API Gateway.
 ///
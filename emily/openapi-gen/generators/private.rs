@@ -4,25 +4,30 @@ use emily_handler::common;
 use super::AwsApiKey;
 use super::AwsLambdaIntegration;
 use super::CorsSupport;
+use super::EmilyApiKey;
 
 #[derive(utoipa::OpenApi)]
 #[openapi(
-    // Add API key security scheme.
-    modifiers(&CorsSupport, &AwsApiKey, &AwsLambdaIntegration),
+    // Add API key security schemes.
+    modifiers(&CorsSupport, &AwsApiKey, &EmilyApiKey, &AwsLambdaIntegration),
     // Paths to be included in the OpenAPI specification.
     paths(
         // Health check endpoints.
         api::handlers::health::get_health,
         // Deposit endpoints.
         api::handlers::deposit::get_deposit,
+        api::handlers::deposit::get_deposit_history,
+        api::handlers::deposit::get_deposit_eta,
         api::handlers::deposit::get_deposits_for_transaction,
         api::handlers::deposit::get_deposits_for_recipient,
         api::handlers::deposit::get_deposits_for_reclaim_pubkeys,
         api::handlers::deposit::get_deposits,
         api::handlers::deposit::create_deposit,
+        api::handlers::deposit::create_deposits,
         api::handlers::deposit::update_deposits_sidecar,
         // Withdrawal endpoints.
         api::handlers::withdrawal::get_withdrawal,
+        api::handlers::withdrawal::get_withdrawal_history,
         api::handlers::withdrawal::get_withdrawals,
         api::handlers::withdrawal::get_withdrawals_for_recipient,
         api::handlers::withdrawal::create_withdrawal,
@@ -45,6 +50,14 @@ use super::CorsSupport;
         api::handlers::throttle::start_throttle,
         api::handlers::throttle::activate_throttle_key,
         api::handlers::throttle::deactivate_throttle_key,
+        // Api key endpoints.
+        api::handlers::api_key::create_api_key,
+        api::handlers::api_key::get_api_key,
+        api::handlers::api_key::rotate_api_key,
+        api::handlers::api_key::activate_api_key,
+        api::handlers::api_key::deactivate_api_key,
+        // Retention endpoints.
+        api::handlers::retention::run_retention,
         // New block endpoints.
         api::handlers::new_block::new_block,
     ),
@@ -58,9 +71,14 @@ use super::CorsSupport;
         api::models::deposit::DepositParameters,
         api::models::deposit::DepositInfo,
         api::models::deposit::requests::CreateDepositRequestBody,
+        api::models::deposit::requests::CreateDepositsRequestBody,
         api::models::deposit::requests::DepositUpdate,
         api::models::deposit::requests::UpdateDepositsRequestBody,
+        api::models::deposit::responses::CreateDepositsResponse,
         api::models::deposit::responses::GetDepositsForTransactionResponse,
+        api::models::deposit::responses::GetDepositEtaResponse,
+        api::models::deposit::responses::GetDepositHistoryResponse,
+        api::models::deposit::responses::DepositStatusUpdate,
         api::models::deposit::responses::GetDepositsResponse,
         api::models::deposit::responses::UpdateDepositsResponse,
         // Withdrawal Models.
@@ -73,6 +91,8 @@ use super::CorsSupport;
         api::models::withdrawal::requests::WithdrawalUpdate,
         api::models::withdrawal::requests::UpdateWithdrawalsRequestBody,
         api::models::withdrawal::responses::GetWithdrawalsResponse,
+        api::models::withdrawal::responses::GetWithdrawalHistoryResponse,
+        api::models::withdrawal::responses::WithdrawalStatusUpdate,
         api::models::withdrawal::responses::UpdateWithdrawalsResponse,
         // Health check datatypes.
         api::models::health::responses::HealthData,
@@ -80,17 +100,29 @@ use super::CorsSupport;
         api::models::common::DepositStatus,
         api::models::common::WithdrawalStatus,
         api::models::common::Fulfillment,
+        api::models::common::StatusUpdateActor,
         // Limits models
         api::models::limits::Limits,
         api::models::limits::AccountLimits,
+        api::models::limits::DepositConfirmationTier,
         // New Block models.
         api::models::new_block::NewBlockEventRaw,
         // Throttle models
         api::models::throttle::ThrottleKey,
         api::models::throttle::ThrottleRequest,
         api::models::throttle::GetThrottleKeyResponse,
+        // Api key models
+        api::models::api_key::ApiKeyScope,
+        api::models::api_key::CreateApiKeyRequest,
+        api::models::api_key::RotateApiKeyRequest,
+        api::models::api_key::ApiKeyInfo,
+        // Retention models
+        api::models::retention::RunRetentionResponse,
         // Errors.
         common::error::ErrorResponse,
+        common::error::ValidationErrorResponse,
+        common::error::FieldValidationError,
+        common::error::RequestField,
     ))
 )]
 pub struct ApiDoc;
@@ -4,11 +4,12 @@ use emily_handler::common;
 use super::AwsApiKey;
 use super::AwsLambdaIntegration;
 use super::CorsSupport;
+use super::EmilyApiKey;
 
 #[derive(utoipa::OpenApi)]
 #[openapi(
-    // Add API key security scheme.
-    modifiers(&CorsSupport, &AwsApiKey, &AwsLambdaIntegration),
+    // Add API key security schemes.
+    modifiers(&CorsSupport, &AwsApiKey, &EmilyApiKey, &AwsLambdaIntegration),
     // Add the servers attribute to the OpenAPI specification.
     servers(
         (url = "http://localhost:3031", description = "Local Emily server"),
@@ -21,14 +22,18 @@ use super::CorsSupport;
         api::handlers::health::get_health,
         // Deposit endpoints.
         api::handlers::deposit::get_deposit,
+        api::handlers::deposit::get_deposit_history,
+        api::handlers::deposit::get_deposit_eta,
         api::handlers::deposit::get_deposits_for_transaction,
         api::handlers::deposit::get_deposits_for_recipient,
         api::handlers::deposit::get_deposits_for_reclaim_pubkeys,
         api::handlers::deposit::get_deposits,
         api::handlers::deposit::create_deposit,
+        api::handlers::deposit::create_deposits,
         api::handlers::deposit::update_deposits_signer,
         // Withdrawal endpoints.
         api::handlers::withdrawal::get_withdrawal,
+        api::handlers::withdrawal::get_withdrawal_history,
         api::handlers::withdrawal::get_withdrawals,
         api::handlers::withdrawal::get_withdrawals_for_recipient,
         api::handlers::withdrawal::get_withdrawals_for_sender,
@@ -39,6 +44,7 @@ use super::CorsSupport;
         // Limits endpoints.
         api::handlers::limits::get_limits,
         api::handlers::limits::get_limits_for_account,
+        api::handlers::limits::set_available_to_mint,
         // Throttle endpoints.
         api::handlers::throttle::start_throttle,
     ),
@@ -52,9 +58,14 @@ use super::CorsSupport;
         api::models::deposit::DepositParameters,
         api::models::deposit::DepositInfo,
         api::models::deposit::requests::CreateDepositRequestBody,
+        api::models::deposit::requests::CreateDepositsRequestBody,
         api::models::deposit::requests::DepositUpdate, // signers may update the state of deposits to Accepted.
         api::models::deposit::requests::UpdateDepositsRequestBody, // signers may update the state of deposits to Accepted.
+        api::models::deposit::responses::CreateDepositsResponse,
         api::models::deposit::responses::GetDepositsForTransactionResponse,
+        api::models::deposit::responses::GetDepositEtaResponse,
+        api::models::deposit::responses::GetDepositHistoryResponse,
+        api::models::deposit::responses::DepositStatusUpdate,
         api::models::deposit::responses::GetDepositsResponse,
         api::models::deposit::responses::UpdateDepositsResponse, // signers may update the state of deposits to Accepted.
         // Withdrawal Models.
@@ -66,6 +77,8 @@ use super::CorsSupport;
         api::models::withdrawal::requests::WithdrawalUpdate, // signers may update the state of withdrawals to Accepted.
         api::models::withdrawal::requests::UpdateWithdrawalsRequestBody, // signers may update the state of withdrawals to Accepted.
         api::models::withdrawal::responses::GetWithdrawalsResponse,
+        api::models::withdrawal::responses::GetWithdrawalHistoryResponse,
+        api::models::withdrawal::responses::WithdrawalStatusUpdate,
         api::models::withdrawal::responses::UpdateWithdrawalsResponse, // signers may update the state of withdrawals to Accepted.
         // Health check datatypes.
         api::models::health::responses::HealthData,
@@ -73,13 +86,19 @@ use super::CorsSupport;
         api::models::common::DepositStatus,
         api::models::common::WithdrawalStatus,
         api::models::common::Fulfillment,
+        api::models::common::StatusUpdateActor,
         // Limits models
         api::models::limits::Limits,
         api::models::limits::AccountLimits,
+        api::models::limits::DepositConfirmationTier,
+        api::models::limits::SetAvailableToMintRequestBody,
         // Throttle models
         api::models::throttle::ThrottleRequest,
         // Errors.
         common::error::ErrorResponse,
+        common::error::ValidationErrorResponse,
+        common::error::FieldValidationError,
+        common::error::RequestField,
     ))
 )]
 pub struct ApiDoc;
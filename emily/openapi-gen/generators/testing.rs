@@ -4,26 +4,31 @@ use emily_handler::common;
 use super::AwsApiKey;
 use super::AwsLambdaIntegration;
 use super::CorsSupport;
+use super::EmilyApiKey;
 
 #[derive(utoipa::OpenApi)]
 #[openapi(
-    // Add API key security scheme.
-    modifiers(&CorsSupport, &AwsApiKey, &AwsLambdaIntegration),
+    // Add API key security schemes.
+    modifiers(&CorsSupport, &AwsApiKey, &EmilyApiKey, &AwsLambdaIntegration),
     // Paths to be included in the OpenAPI specification.
     paths(
         // Health check endpoints.
         api::handlers::health::get_health,
         // Deposit endpoints.
         api::handlers::deposit::get_deposit,
+        api::handlers::deposit::get_deposit_history,
+        api::handlers::deposit::get_deposit_eta,
         api::handlers::deposit::get_deposits_for_transaction,
         api::handlers::deposit::get_deposits_for_recipient,
         api::handlers::deposit::get_deposits_for_reclaim_pubkeys,
         api::handlers::deposit::get_deposits,
         api::handlers::deposit::create_deposit,
+        api::handlers::deposit::create_deposits,
         api::handlers::deposit::update_deposits_sidecar,
         api::handlers::deposit::update_deposits_signer,
         // Withdrawal endpoints.
         api::handlers::withdrawal::get_withdrawal,
+        api::handlers::withdrawal::get_withdrawal_history,
         api::handlers::withdrawal::get_withdrawals,
         api::handlers::withdrawal::get_withdrawals_for_recipient,
         api::handlers::withdrawal::get_withdrawals_for_sender,
@@ -42,6 +47,7 @@ use super::CorsSupport;
         api::handlers::limits::set_limits,
         api::handlers::limits::get_limits_for_account,
         api::handlers::limits::set_limits_for_account,
+        api::handlers::limits::set_available_to_mint,
         /// New block endpoints.
         api::handlers::new_block::new_block,
         // Throttle endpoints
@@ -50,6 +56,14 @@ use super::CorsSupport;
         api::handlers::throttle::activate_throttle_key,
         api::handlers::throttle::deactivate_throttle_key,
         api::handlers::throttle::start_throttle,
+        // Api key endpoints.
+        api::handlers::api_key::create_api_key,
+        api::handlers::api_key::get_api_key,
+        api::handlers::api_key::rotate_api_key,
+        api::handlers::api_key::activate_api_key,
+        api::handlers::api_key::deactivate_api_key,
+        // Retention endpoints.
+        api::handlers::retention::run_retention,
     ),
     // Components to be included in the OpenAPI specification.
     components(schemas(
@@ -61,9 +75,14 @@ use super::CorsSupport;
         api::models::deposit::DepositParameters,
         api::models::deposit::DepositInfo,
         api::models::deposit::requests::CreateDepositRequestBody,
+        api::models::deposit::requests::CreateDepositsRequestBody,
         api::models::deposit::requests::DepositUpdate,
         api::models::deposit::requests::UpdateDepositsRequestBody,
+        api::models::deposit::responses::CreateDepositsResponse,
         api::models::deposit::responses::GetDepositsForTransactionResponse,
+        api::models::deposit::responses::GetDepositEtaResponse,
+        api::models::deposit::responses::GetDepositHistoryResponse,
+        api::models::deposit::responses::DepositStatusUpdate,
         api::models::deposit::responses::GetDepositsResponse,
         api::models::deposit::responses::UpdateDepositsResponse,
         // Withdrawal Models.
@@ -76,6 +95,8 @@ use super::CorsSupport;
         api::models::withdrawal::requests::WithdrawalUpdate,
         api::models::withdrawal::requests::UpdateWithdrawalsRequestBody,
         api::models::withdrawal::responses::GetWithdrawalsResponse,
+        api::models::withdrawal::responses::GetWithdrawalHistoryResponse,
+        api::models::withdrawal::responses::WithdrawalStatusUpdate,
         api::models::withdrawal::responses::UpdateWithdrawalsResponse,
         // Health check datatypes.
         api::models::health::responses::HealthData,
@@ -83,17 +104,30 @@ use super::CorsSupport;
         api::models::common::DepositStatus,
         api::models::common::WithdrawalStatus,
         api::models::common::Fulfillment,
+        api::models::common::StatusUpdateActor,
         // Limits models
         api::models::limits::Limits,
         api::models::limits::AccountLimits,
+        api::models::limits::DepositConfirmationTier,
+        api::models::limits::SetAvailableToMintRequestBody,
         // New block models.
         api::models::new_block::NewBlockEventRaw,
         // Errors.
         common::error::ErrorResponse,
+        common::error::ValidationErrorResponse,
+        common::error::FieldValidationError,
+        common::error::RequestField,
         // Throttle models
         api::models::throttle::ThrottleKey,
         api::models::throttle::ThrottleRequest,
         api::models::throttle::GetThrottleKeyResponse,
+        // Api key models
+        api::models::api_key::ApiKeyScope,
+        api::models::api_key::CreateApiKeyRequest,
+        api::models::api_key::RotateApiKeyRequest,
+        api::models::api_key::ApiKeyInfo,
+        // Retention models
+        api::models::retention::RunRetentionResponse,
     ))
 )]
 pub struct ApiDoc;
@@ -5,7 +5,7 @@ from typing import Any, Optional, Self
 from bitcoinlib.scripts import Script
 
 from app import settings
-from app.utils import decode_cscript_int
+from app.utils import decode_cscript_int, lock_time_in_blocks
 
 
 class RequestStatus(Enum):
@@ -46,7 +46,10 @@ class DepositInfo:
 
     @property
     def lock_time(self) -> int:
-        """Extracts lock time from reclaim script."""
+        """Extracts the raw BIP-68 lock time from reclaim script.
+
+        Use lock_time_in_blocks to interpret it, since it may be time-based.
+        """
         script = Script.parse(self.reclaim_script)
         op_code_maybe = script.view(as_list=True)[0]
         if op_code_maybe.startswith("OP_"):
@@ -93,7 +96,8 @@ class EnrichedDepositInfo(DepositInfo):
         """Check if the deposit's time-based expiry condition has been met.
 
         Note: This only checks the time component (locktime + confirmations).
-        It does NOT check if the UTXO has been spent.
+        It does NOT check if the UTXO has been spent. Time-based lock times
+        are converted into blocks the same way the signers convert them.
 
         Args:
             bitcoin_chaintip_height: The height of the tip of the Bitcoin chain
@@ -107,7 +111,9 @@ class EnrichedDepositInfo(DepositInfo):
 
         # Calculate the block height at which the deposit becomes eligible for expiry
         expiry_eligible_height = (
-            self.confirmed_height + self.lock_time + settings.MIN_BLOCK_CONFIRMATIONS
+            self.confirmed_height
+            + lock_time_in_blocks(self.lock_time)
+            + settings.MIN_BLOCK_CONFIRMATIONS
         )
 
         # Check if the current chain tip has passed the expiry eligible height
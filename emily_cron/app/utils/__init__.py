@@ -2,4 +2,5 @@ from .common import (
     asdict_camel,
     to_camel_case,
     decode_cscript_int,
+    lock_time_in_blocks,
 )
@@ -36,3 +36,37 @@ def decode_cscript_int(vch: bytes) -> int:
         result &= num_mask
         result *= -1
     return result
+
+
+# BIP-68 relative lock-time fields, see
+# https://github.com/bitcoin/bips/blob/master/bip-0068.mediawiki
+SEQUENCE_LOCKTIME_DISABLE_FLAG = 1 << 31
+SEQUENCE_LOCKTIME_TYPE_FLAG = 1 << 22
+SEQUENCE_LOCKTIME_MASK = 0x0000FFFF
+SEQUENCE_LOCKTIME_GRANULARITY_SECONDS = 512
+
+# The number of seconds per bitcoin block that the signers assume when
+# converting a time-based lock time into blocks. This must match
+# `TIME_BASED_LOCKTIME_SECONDS_PER_BLOCK` in the signer.
+TIME_BASED_LOCKTIME_SECONDS_PER_BLOCK = 1200
+
+
+def lock_time_in_blocks(lock_time: int) -> int:
+    """Converts a reclaim script lock time into a number of blocks.
+
+    The lock time is decoded as a BIP-68 relative lock time, the same way
+    the signers decode it. Block-based lock times are used as is, while
+    time-based lock times (type flag set) are converted using
+    TIME_BASED_LOCKTIME_SECONDS_PER_BLOCK, rounding down.
+
+    Raises:
+        ValueError: If the lock time is negative or has the disable flag set.
+    """
+    if lock_time < 0 or lock_time & SEQUENCE_LOCKTIME_DISABLE_FLAG:
+        raise ValueError(f"disabled or invalid lock time: {lock_time}")
+
+    value = lock_time & SEQUENCE_LOCKTIME_MASK
+    if lock_time & SEQUENCE_LOCKTIME_TYPE_FLAG:
+        seconds = value * SEQUENCE_LOCKTIME_GRANULARITY_SECONDS
+        return seconds // TIME_BASED_LOCKTIME_SECONDS_PER_BLOCK
+    return value
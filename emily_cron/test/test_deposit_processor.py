@@ -1210,3 +1210,43 @@ class TestElectrsAPI(unittest.TestCase):
             f"/tx/{INFLIGHT_UTXO_STATUS['txid']}/outspend/{INFLIGHT_UTXO_STATUS['vin']}",
             ignore_errors=True,
         )
+
+
+class TestTimeBasedLockTime(unittest.TestCase):
+    """Tests for deposits whose reclaim script uses a time-based lock time."""
+
+    def _deposit(self, reclaim_script, confirmed_height):
+        deposit = DepositInfo(
+            bitcoin_txid="time_based_tx",
+            bitcoin_tx_output_index=0,
+            recipient="r_pending",
+            amount=10,
+            last_update_height=900,
+            last_update_block_hash="hp",
+            status="pending",
+            reclaim_script=reclaim_script,
+            deposit_script="51",
+        )
+        return EnrichedDepositInfo.from_deposit_info(
+            deposit, {"in_mempool": False, "confirmed_height": confirmed_height}
+        )
+
+    def test_time_based_lock_time_is_parsed(self):
+        # Lock time of 10 * 512 seconds with the BIP-68 type flag set.
+        deposit = self._deposit("030a004051", confirmed_height=900)
+        self.assertEqual(deposit.lock_time, (1 << 22) | 10)
+
+    def test_time_based_lock_time_expires(self):
+        # 10 * 512 seconds converts into 4 blocks.
+        deposit = self._deposit("030a004051", confirmed_height=900)
+        expiry_height = 900 + 4 + settings.MIN_BLOCK_CONFIRMATIONS
+
+        self.assertFalse(deposit.is_expired(expiry_height - 1))
+        self.assertTrue(deposit.is_expired(expiry_height))
+
+    def test_block_based_lock_time_expires(self):
+        deposit = self._deposit("03400000", confirmed_height=900)
+        expiry_height = 900 + 64 + settings.MIN_BLOCK_CONFIRMATIONS
+
+        self.assertFalse(deposit.is_expired(expiry_height - 1))
+        self.assertTrue(deposit.is_expired(expiry_height))
@@ -1,5 +1,5 @@
 import unittest
-from app.utils import decode_cscript_int
+from app.utils import decode_cscript_int, lock_time_in_blocks
 
 
 class TestDecodeCscriptInt(unittest.TestCase):
@@ -11,3 +11,26 @@ class TestDecodeCscriptInt(unittest.TestCase):
         self.assertEqual(decode_cscript_int(b"\x32\x79\x86"), -424242)
         self.assertEqual(decode_cscript_int(b"\x80\x80"), -0x80)
         self.assertEqual(decode_cscript_int(b"\x81"), -1)
+
+
+class TestLockTimeInBlocks(unittest.TestCase):
+    def test_block_based_lock_time(self):
+        self.assertEqual(lock_time_in_blocks(0), 0)
+        self.assertEqual(lock_time_in_blocks(64), 64)
+        self.assertEqual(lock_time_in_blocks(0xFFFF), 0xFFFF)
+
+    def test_time_based_lock_time(self):
+        # 10 * 512 seconds is 5120 seconds, or 4 blocks of 1200 seconds.
+        self.assertEqual(lock_time_in_blocks((1 << 22) | 10), 4)
+        self.assertEqual(lock_time_in_blocks(1 << 22), 0)
+        self.assertEqual(lock_time_in_blocks((1 << 22) | 0xFFFF), 0xFFFF * 512 // 1200)
+
+    def test_bits_outside_the_value_mask_are_ignored(self):
+        self.assertEqual(lock_time_in_blocks((1 << 16) | 64), 64)
+        self.assertEqual(lock_time_in_blocks((1 << 23) | 64), 64)
+
+    def test_disabled_lock_time(self):
+        with self.assertRaises(ValueError):
+            lock_time_in_blocks(1 << 31)
+        with self.assertRaises(ValueError):
+            lock_time_in_blocks(-1)
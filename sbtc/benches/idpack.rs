@@ -0,0 +1,82 @@
+//! Benchmarks for segmenting and encoding large sets of withdrawal IDs
+//! with idpack.
+//!
+//! These use the standard library only, so they can be run without any
+//! additional dependencies:
+//!
+//! ```text
+//! cargo bench -p sbtc --bench idpack
+//! ```
+//!
+//! Each case is run for a number of iterations and the mean time per
+//! iteration is printed, alongside the encoded size, for both the
+//! slice-based [`Segmenter::package`] API and the streaming
+//! [`BitmapSegmenter::stream`] API.
+//!
+//! [`Segmenter::package`]: sbtc::idpack::Segmenter::package
+
+use std::hint::black_box;
+use std::time::Duration;
+use std::time::Instant;
+
+use sbtc::idpack::BitmapSegmenter;
+use sbtc::idpack::Encodable as _;
+use sbtc::idpack::SegmentEncoder;
+use sbtc::idpack::Segmenter as _;
+
+/// The number of iterations to run for each case.
+const ITERATIONS: u32 = 10;
+
+/// Run the given closure [`ITERATIONS`] times and return the mean
+/// duration of a single run along with the output of the last run.
+fn bench<F>(mut f: F) -> (Duration, usize)
+where
+    F: FnMut() -> usize,
+{
+    let mut output = 0;
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        output = black_box(f());
+    }
+    (start.elapsed() / ITERATIONS, output)
+}
+
+/// Segment and encode the values in `ids` with both APIs and print the
+/// results. The streaming case regenerates the IDs on the fly, so it
+/// never holds the full set in memory.
+fn run_case<F, I>(name: &str, count: usize, ids: F)
+where
+    F: Fn() -> I,
+    I: Iterator<Item = u64>,
+{
+    let values: Vec<u64> = ids().collect();
+    let (package_time, package_size) = bench(|| {
+        let segments = BitmapSegmenter.package(black_box(&values)).unwrap();
+        segments.encode().len()
+    });
+    drop(values);
+
+    let (stream_time, stream_size) = bench(|| {
+        let mut encoder = SegmentEncoder::default();
+        for segment in BitmapSegmenter.stream(black_box(ids())) {
+            encoder.push(&segment.unwrap());
+        }
+        encoder.len()
+    });
+
+    assert_eq!(package_size, stream_size);
+    println!(
+        "{name:<32} {count:>10} ids  package: {package_time:>12?}  stream: {stream_time:>12?}  encoded: {stream_size} bytes"
+    );
+}
+
+fn main() {
+    for count in [10_000, 1_000_000, 5_000_000] {
+        run_case("dense", count, || 0..count as u64);
+        run_case("every third", count, || (0..count as u64).map(|i| i * 3));
+        run_case("clustered", count, || {
+            (0..count as u64).map(|i| (i / 100) * 10_000 + i % 100)
+        });
+        run_case("sparse", count, || (0..count as u64).map(|i| i * 1_000_000));
+    }
+}
@@ -0,0 +1,18 @@
+//! Fuzz the parser for the deposit scripts that users submit to Emily.
+
+#![no_main]
+
+use bitcoin::ScriptBuf;
+use libfuzzer_sys::fuzz_target;
+use sbtc::deposits::DepositScriptInputs;
+
+fuzz_target!(|data: &[u8]| {
+    let script = ScriptBuf::from_bytes(data.to_vec());
+    let Ok(inputs) = DepositScriptInputs::parse(&script) else {
+        return;
+    };
+    // Whatever we accept must parse to the same inputs after being
+    // written back out as a deposit script.
+    let deposit_script = inputs.deposit_script();
+    assert_eq!(DepositScriptInputs::parse(&deposit_script).unwrap(), inputs);
+});
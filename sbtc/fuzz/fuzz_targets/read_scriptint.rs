@@ -0,0 +1,19 @@
+//! Fuzz the decoder for the script numbers used as lock times in reclaim
+//! scripts.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // The first byte is the maximum size of the number and the rest is
+    // the number itself.
+    let Some((max_size, number)) = data.split_first() else {
+        return;
+    };
+    if let Ok(value) = sbtc::deposits::fuzz_read_scriptint(number, *max_size as usize) {
+        assert!(number.len() <= *max_size as usize);
+        // Numbers are at most 5 bytes, so they fit within 40 bits.
+        assert!(value.unsigned_abs() < 1 << 40);
+    }
+});
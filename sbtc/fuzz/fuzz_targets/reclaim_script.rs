@@ -0,0 +1,20 @@
+//! Fuzz the parser for the reclaim scripts that users submit to Emily.
+
+#![no_main]
+
+use bitcoin::ScriptBuf;
+use libfuzzer_sys::fuzz_target;
+use sbtc::deposits::ReclaimScriptInputs;
+
+fuzz_target!(|data: &[u8]| {
+    let script = ScriptBuf::from_bytes(data.to_vec());
+    let Ok(inputs) = ReclaimScriptInputs::parse(&script) else {
+        return;
+    };
+    // The lock time may be pushed in more than one way, so the script
+    // that we write back out need not match the input, but it must
+    // parse to the same inputs.
+    let reclaim_script = inputs.reclaim_script();
+    assert_eq!(ReclaimScriptInputs::parse(&reclaim_script).unwrap(), inputs);
+    assert!(script.as_bytes().ends_with(inputs.user_script().as_bytes()));
+});
@@ -1,12 +1,16 @@
 //! This is the transaction analysis module
 //!
 
+use std::num::NonZeroU16;
+use std::str::FromStr;
+
 use bitcoin::Address;
 use bitcoin::Network;
 use bitcoin::OutPoint;
 use bitcoin::Script;
 use bitcoin::ScriptBuf;
 use bitcoin::Transaction;
+use bitcoin::WitnessVersion;
 use bitcoin::XOnlyPublicKey;
 use bitcoin::locktime::relative::LockTime;
 use bitcoin::opcodes::Class;
@@ -82,6 +86,64 @@ const OP_PUSHNUM_16: u8 = opcodes::OP_PUSHNUM_16.to_u8();
 /// Represents the number -1.
 const OP_PUSHNUM_NEG1: u8 = opcodes::OP_PUSHNUM_NEG1.to_u8();
 
+/// The scriptPubKey of a pay-to-anchor (P2A) output, a witness version 1
+/// program that bitcoin-core treats as standard since v28.
+const PAY_TO_ANCHOR_SCRIPT: [u8; 4] = [OP_PUSHNUM_1, 0x02, 0x4e, 0x73];
+
+/// How the outputs of a deposit funding transaction, other than the
+/// deposit output itself, are treated during validation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SiblingOutputPolicy {
+    /// Only the deposit output is validated, all other outputs of the
+    /// funding transaction are ignored.
+    #[default]
+    Permissive,
+    /// Funding transactions are rejected if any other output uses a
+    /// witness version without consensus meaning yet, or has a
+    /// non-standard scriptPubKey.
+    Strict,
+}
+
+/// The kind of scriptPubKey locking an output of a deposit funding
+/// transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SiblingOutputKind {
+    /// A scriptPubKey that bitcoin-core relays by default: P2PK, P2PKH,
+    /// P2SH, P2WPKH, P2WSH, P2TR, P2A or an OP_RETURN output.
+    Standard,
+    /// A witness program whose witness version does not have any
+    /// consensus meaning yet. These are witness version 1 programs that
+    /// are not taproot or pay-to-anchor outputs, and witness version 2
+    /// through 16 programs.
+    FutureWitness(WitnessVersion),
+    /// Any other scriptPubKey, including bare multisig and witness
+    /// version 0 programs of an invalid length.
+    NonStandard,
+}
+
+impl SiblingOutputKind {
+    /// Classify the given scriptPubKey.
+    pub fn classify(script_pubkey: &Script) -> Self {
+        let is_standard = script_pubkey.is_p2pk()
+            || script_pubkey.is_p2pkh()
+            || script_pubkey.is_p2sh()
+            || script_pubkey.is_p2wpkh()
+            || script_pubkey.is_p2wsh()
+            || script_pubkey.is_p2tr()
+            || script_pubkey.is_op_return()
+            || script_pubkey.as_bytes() == PAY_TO_ANCHOR_SCRIPT;
+
+        if is_standard {
+            return SiblingOutputKind::Standard;
+        }
+
+        match script_pubkey.witness_version() {
+            None | Some(WitnessVersion::V0) => SiblingOutputKind::NonStandard,
+            Some(version) => SiblingOutputKind::FutureWitness(version),
+        }
+    }
+}
+
 /// All the info required to verify the validity of a deposit
 /// transaction. This info is sent by the user to the Emily API
 #[derive(Debug, Clone)]
@@ -114,7 +176,9 @@ pub struct DepositInfo {
     /// The stacks address to deposit the sBTC to. This can be either a
     /// standard address or a contract address.
     pub recipient: PrincipalData,
-    /// The relative lock time in the reclaim script.
+    /// The relative lock time in the reclaim script. This is either
+    /// [`LockTime::Blocks`] or, when the time-based flag is set in the
+    /// reclaim script, [`LockTime::Time`].
     pub lock_time: LockTime,
 }
 
@@ -131,6 +195,9 @@ impl CreateDepositRequest {
     ///   ScriptPubKey.
     /// * That the Stacks network for the recipient address matches the one
     ///   given as input to this function.
+    ///
+    /// The other outputs of the transaction are not checked, which is
+    /// the same as validating with [`SiblingOutputPolicy::Permissive`].
     pub fn validate_tx(&self, tx: &Transaction, is_mainnet: bool) -> Result<DepositInfo, Error> {
         if tx.compute_txid() != self.outpoint.txid {
             // The expectation is that the transaction was fetched from the
@@ -186,6 +253,49 @@ impl CreateDepositRequest {
             outpoint: self.outpoint,
         })
     }
+
+    /// Validate this deposit request, checking the outputs of the funding
+    /// transaction other than the deposit output against the given
+    /// policy.
+    ///
+    /// This performs all the checks of [`CreateDepositRequest::validate_tx`].
+    /// Under [`SiblingOutputPolicy::Strict`] it also rejects funding
+    /// transactions where any other output is a
+    /// [`SiblingOutputKind::FutureWitness`] or
+    /// [`SiblingOutputKind::NonStandard`] output.
+    pub fn validate_tx_with_policy(
+        &self,
+        tx: &Transaction,
+        is_mainnet: bool,
+        policy: SiblingOutputPolicy,
+    ) -> Result<DepositInfo, Error> {
+        let info = self.validate_tx(tx, is_mainnet)?;
+
+        if policy == SiblingOutputPolicy::Permissive {
+            return Ok(info);
+        }
+
+        let siblings = (0..)
+            .zip(tx.output.iter())
+            .filter(|(vout, _)| *vout != self.outpoint.vout);
+        for (vout, tx_out) in siblings {
+            let outpoint = OutPoint::new(self.outpoint.txid, vout);
+            match SiblingOutputKind::classify(&tx_out.script_pubkey) {
+                SiblingOutputKind::Standard => {}
+                SiblingOutputKind::FutureWitness(version) => {
+                    return Err(Error::FutureWitnessSiblingOutput(
+                        outpoint,
+                        version.to_num(),
+                    ));
+                }
+                SiblingOutputKind::NonStandard => {
+                    return Err(Error::NonStandardSiblingOutput(outpoint));
+                }
+            }
+        }
+
+        Ok(info)
+    }
 }
 
 /// Return whether the given principal address is a mainnet address.
@@ -379,15 +489,15 @@ impl DepositScriptInputs {
 /// standard locktime in bitcoin-core. We do not verify whether the
 /// user-supplied script is correct and standard.
 ///
-/// Currently, we only accept locktimes denominated in Bitcoin blocks. This
-/// implies that bit (1 << 22) in the locktime, must be zero.
+/// Locktimes may be denominated either in Bitcoin blocks or, when bit
+/// (1 << 22) in the locktime is set, in units of 512 seconds, as described
+/// in BIP-68. Use [`ReclaimScriptInputs::is_time_based`] to tell the two
+/// apart.
 ///
 /// Note that locktimes used as `OP_CSV` inputs in the reclaim script only
 /// use the 16 least significant bits for the value of the locktime. All
-/// other bits in the 32-bit locktime must be zero or the deposit
-/// transaction will fail validation. When we support time-based locktimes,
-/// a user may set bit (1 << 22) to indicate that the locktime value is
-/// time based, as described in BIP-68.
+/// other bits in the 32-bit locktime, other than the time-based flag, must
+/// be zero or the deposit transaction will fail validation.
 ///
 /// <https://github.com/bitcoin/bips/blob/17c04f9fa1ecae173d6864b65717e13dfc1880af/bip-0068.mediawiki#specification>
 /// <https://github.com/bitcoin/bips/blob/812907c2b00b92ee31e2b638622a4fe14a428aee/bip-0112.mediawiki#summary>
@@ -406,7 +516,7 @@ pub struct ReclaimScriptInputs {
 impl ReclaimScriptInputs {
     /// Create a new one, validating that:
     ///
-    /// * the lock time is a non-disabled block-based time,
+    /// * the lock time is not disabled,
     /// * the user-supplied script is within the maximum length allowed
     ///   for a reclaim script,
     /// * the user-supplied script does not contain any OP_SUCCESSx
@@ -438,23 +548,14 @@ impl ReclaimScriptInputs {
     ///
     /// OP_CSV checks can be disabled if the lock time has the disabled
     /// lock-time bit set, so we reject any such lock time to ensure that
-    /// the OP_CSV check is always enforced. We also reject time-based
-    /// (as opposed to block-height-based) lock times since we do not yet
-    /// support them.
+    /// the OP_CSV check is always enforced. Both block-based and
+    /// time-based lock times are accepted here; whether a time-based lock
+    /// time leaves the signers enough time to sweep the deposit is a
+    /// policy decision for the signers.
     ///
     /// <https://github.com/bitcoin/bitcoin/blob/v27.1/src/script/interpreter.cpp#L560-L592>
     fn validate_lock_time(lock_time: u32) -> Result<LockTime, Error> {
-        let lock_time = LockTime::from_consensus(lock_time).map_err(Error::DisabledLockTime)?;
-
-        // For now, we only accept locktimes denominated in bitcoin block
-        // units.
-        if matches!(lock_time, LockTime::Time(_)) {
-            return Err(Error::UnsupportedLockTimeUnits(
-                lock_time.to_consensus_u32(),
-            ));
-        }
-
-        Ok(lock_time)
+        LockTime::from_consensus(lock_time).map_err(Error::DisabledLockTime)
     }
 
     /// Validate the user-supplied portion of a reclaim script.
@@ -501,6 +602,18 @@ impl ReclaimScriptInputs {
         self.lock_time.to_consensus_u32()
     }
 
+    /// Get the lock time in the reclaim script as a BIP-68 relative lock
+    /// time.
+    pub fn relative_lock_time(&self) -> LockTime {
+        self.lock_time
+    }
+
+    /// Whether the lock time in the reclaim script is denominated in
+    /// units of 512 seconds rather than in blocks.
+    pub fn is_time_based(&self) -> bool {
+        self.lock_time.is_block_time()
+    }
+
     /// Return the user supplied part of the script.
     ///
     /// The full reclaim script has the form:
@@ -582,6 +695,11 @@ impl ReclaimScriptInputs {
     }
 }
 
+/// The largest number of bytes in a script number that bitcoin-core will
+/// accept, which is the size allowed for the inputs to `OP_CSV` and
+/// `OP_CLTV`.
+const MAX_SCRIPT_NUM_SIZE: usize = 5;
+
 /// Decodes an integer in script(minimal CScriptNum) format.
 ///
 /// # Notes
@@ -598,9 +716,11 @@ fn read_scriptint(v: &[u8], max_size: usize) -> Result<i64, Error> {
         None => return Ok(0),
     };
     // In rust-bitcoin, max_size is hardcoded to 4, while in bitcoin-core
-    // it is a variable, and sometimes they set it to 5. This is the only
-    // modification to this function body from rust-bitcoin's code.
-    if v.len() > max_size {
+    // it is a variable, and sometimes they set it to 5. This, and the
+    // upper bound of MAX_SCRIPT_NUM_SIZE, are the only modifications to
+    // this function body from rust-bitcoin's code. Without the upper
+    // bound, `scriptint_parse` overflows on numbers of 8 or more bytes.
+    if v.len() > max_size.min(MAX_SCRIPT_NUM_SIZE) {
         return Err(Error::ScriptNum(bitcoin::script::Error::NumericOverflow));
     }
     // Comment and code copied from Bitcoin Core:
@@ -622,6 +742,95 @@ fn read_scriptint(v: &[u8], max_size: usize) -> Result<i64, Error> {
     Ok(scriptint_parse(v))
 }
 
+/// A tier in the [`DepositConfirmationPolicy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DepositConfirmationTier {
+    /// The minimum deposit amount, in sats, that this tier applies to.
+    pub min_amount: u64,
+    /// The number of confirmations that a deposit transaction must have
+    /// before the signers will sweep it. A deposit included in the
+    /// current chain tip has one confirmation.
+    pub confirmations: NonZeroU16,
+}
+
+impl FromStr for DepositConfirmationTier {
+    type Err = Error;
+
+    /// Parse a tier from a string of the form `<min_amount>:<confirmations>`,
+    /// where `confirmations` must be at least one.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidDepositConfirmationTier(s.to_string());
+        let (min_amount, confirmations) = s.trim().split_once(':').ok_or_else(invalid)?;
+
+        Ok(Self {
+            min_amount: min_amount.trim().parse().map_err(|_| invalid())?,
+            confirmations: confirmations.trim().parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+/// A table that maps deposit amounts to the number of confirmations
+/// required before the signers will sweep a deposit.
+///
+/// Larger deposits are more profitable to double-spend through a bitcoin
+/// re-org, so they can be made to wait for more blocks. A deposit
+/// requires the largest number of confirmations across all tiers whose
+/// `min_amount` it meets, and a single confirmation if there are none.
+///
+/// The signers enforce this policy and Emily reports it to users, and
+/// both parse it from the same `<min_amount>:<confirmations>` entries.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DepositConfirmationPolicy(Vec<DepositConfirmationTier>);
+
+impl DepositConfirmationPolicy {
+    /// Create a new policy from the given tiers.
+    pub fn new(tiers: Vec<DepositConfirmationTier>) -> Self {
+        Self(tiers)
+    }
+
+    /// The tiers of this policy.
+    pub fn tiers(&self) -> &[DepositConfirmationTier] {
+        &self.0
+    }
+
+    /// The number of confirmations required for a deposit of the given
+    /// amount.
+    pub fn required_confirmations(&self, amount: u64) -> u16 {
+        self.0
+            .iter()
+            .filter(|tier| tier.min_amount <= amount)
+            .map(|tier| tier.confirmations.get())
+            .max()
+            .unwrap_or(1)
+    }
+
+    /// The largest number of confirmations required by any tier.
+    pub fn max_confirmations(&self) -> u16 {
+        self.required_confirmations(u64::MAX)
+    }
+}
+
+impl FromStr for DepositConfirmationPolicy {
+    type Err = Error;
+
+    /// Parse a policy from a comma separated list of tiers, each of the
+    /// form `<min_amount>:<confirmations>`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .filter(|tier| !tier.trim().is_empty())
+            .map(str::parse)
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self)
+    }
+}
+
+/// Decodes an integer in script(minimal CScriptNum) format, exposed for
+/// the fuzz targets of this crate.
+#[cfg(feature = "fuzzing")]
+pub fn fuzz_read_scriptint(v: &[u8], max_size: usize) -> Result<i64, Error> {
+    read_scriptint(v, max_size)
+}
+
 /// Caller to guarantee that `v` is not empty.
 ///
 /// # Notes
@@ -660,6 +869,35 @@ mod tests {
 
     const CONTRACT_ADDRESS: &str = "ST1RQHF4VE5CZ6EK3MZPZVQBA0JVSMM9H5PMHMS1Y.contract-name";
 
+    #[test]
+    fn deposit_confirmation_policy_parses_tiers() {
+        let policy: DepositConfirmationPolicy = "0:1,100000000:3, 50000000 : 2".parse().unwrap();
+
+        assert_eq!(policy.tiers().len(), 3);
+        assert_eq!(policy.required_confirmations(0), 1);
+        assert_eq!(policy.required_confirmations(49_999_999), 1);
+        assert_eq!(policy.required_confirmations(50_000_000), 2);
+        assert_eq!(policy.required_confirmations(100_000_000), 3);
+        assert_eq!(policy.max_confirmations(), 3);
+
+        let empty: DepositConfirmationPolicy = "".parse().unwrap();
+        assert_eq!(empty, DepositConfirmationPolicy::default());
+        assert_eq!(empty.required_confirmations(u64::MAX), 1);
+    }
+
+    #[test_case("1000"; "missing confirmations")]
+    #[test_case("1000:0"; "zero confirmations")]
+    #[test_case("1000:-1"; "negative confirmations")]
+    #[test_case("abc:1"; "invalid amount")]
+    #[test_case("0:1,1000:0"; "one invalid tier")]
+    fn invalid_deposit_confirmation_policy_fails(value: &str) {
+        let result = value.parse::<DepositConfirmationPolicy>();
+        assert!(matches!(
+            result,
+            Err(Error::InvalidDepositConfirmationTier(_))
+        ));
+    }
+
     /// A full reclaim script with a p2pk script at the end.
     fn reclaim_p2pk(lock_time: i64) -> ScriptBuf {
         ScriptBuf::builder()
@@ -837,6 +1075,21 @@ mod tests {
         };
     }
 
+    #[test_case(&[0xff; 8]; "eight bytes with the sign bit set")]
+    #[test_case(&[0x7f; 8]; "eight bytes")]
+    #[test_case(&[0x01; 9]; "nine bytes")]
+    #[test_case(&[0x01; 6]; "six bytes")]
+    fn read_scriptint_rejects_wide_numbers(bytes: &[u8]) {
+        // These are larger than bitcoin-core would ever accept, and some
+        // of them would overflow when decoded, so we reject them
+        // regardless of the maximum size that we are given.
+        let error = read_scriptint(bytes, usize::MAX).unwrap_err();
+        assert!(matches!(
+            error,
+            Error::ScriptNum(bitcoin::script::Error::NumericOverflow)
+        ));
+    }
+
     #[test]
     fn no_real_reclaim_script_is_fine() {
         let lock_time = 150;
@@ -897,13 +1150,22 @@ mod tests {
 
     #[test]
     fn lock_time_as_time() {
-        // We do not support time based lock times. Check that
-        let lock_time = LockTime::from_seconds_ceil(20000)
-            .unwrap()
-            .to_consensus_u32();
-        let reclaim = ReclaimScriptInputs::try_new(lock_time, ScriptBuf::new()).unwrap_err();
-
-        assert!(matches!(reclaim, Error::UnsupportedLockTimeUnits(_)));
+        // Time based lock times are accepted, and they keep the time-based
+        // flag when the reclaim script is constructed and parsed again.
+        let lock_time = LockTime::from_seconds_ceil(20000).unwrap();
+        let reclaim =
+            ReclaimScriptInputs::try_new(lock_time.to_consensus_u32(), ScriptBuf::new()).unwrap();
+
+        assert!(reclaim.is_time_based());
+        assert_eq!(reclaim.relative_lock_time(), lock_time);
+        assert_eq!(reclaim.lock_time(), lock_time.to_consensus_u32());
+
+        let parsed = ReclaimScriptInputs::parse(&reclaim.reclaim_script()).unwrap();
+        assert!(parsed.is_time_based());
+        assert_eq!(parsed, reclaim);
+
+        let block_based = ReclaimScriptInputs::try_new(40, ScriptBuf::new()).unwrap();
+        assert!(!block_based.is_time_based());
     }
 
     /// A user script of exactly `MAX_RECLAIM_SCRIPT_LENGTH` bytes is
@@ -1040,6 +1302,106 @@ mod tests {
         assert_eq!(parsed.recipient, setup.deposits.first().unwrap().recipient);
     }
 
+    /// A witness program with the given version opcode and program
+    /// length.
+    fn witness_program(version: opcodes::Opcode, program_length: usize) -> ScriptBuf {
+        let program = PushBytesBuf::try_from(vec![0x01; program_length]).unwrap();
+        ScriptBuf::builder()
+            .push_opcode(version)
+            .push_slice(program)
+            .into_script()
+    }
+
+    #[test_case(ScriptBuf::new_p2pkh(&bitcoin::PubkeyHash::all_zeros()), SiblingOutputKind::Standard; "p2pkh")]
+    #[test_case(ScriptBuf::new_p2sh(&bitcoin::ScriptHash::all_zeros()), SiblingOutputKind::Standard; "p2sh")]
+    #[test_case(ScriptBuf::new_p2wpkh(&bitcoin::WPubkeyHash::all_zeros()), SiblingOutputKind::Standard; "p2wpkh")]
+    #[test_case(ScriptBuf::new_p2wsh(&bitcoin::WScriptHash::all_zeros()), SiblingOutputKind::Standard; "p2wsh")]
+    #[test_case(witness_program(opcodes::OP_PUSHNUM_1, 32), SiblingOutputKind::Standard; "p2tr")]
+    #[test_case(ScriptBuf::from_bytes(PAY_TO_ANCHOR_SCRIPT.to_vec()), SiblingOutputKind::Standard; "p2a")]
+    #[test_case(ScriptBuf::new_op_return(PushBytesBuf::try_from(vec![0xab; 40]).unwrap()), SiblingOutputKind::Standard; "op_return")]
+    #[test_case(witness_program(opcodes::OP_PUSHNUM_1, 20), SiblingOutputKind::FutureWitness(WitnessVersion::V1); "v1 not taproot")]
+    #[test_case(witness_program(opcodes::OP_PUSHNUM_2, 32), SiblingOutputKind::FutureWitness(WitnessVersion::V2); "v2")]
+    #[test_case(witness_program(opcodes::OP_PUSHNUM_16, 40), SiblingOutputKind::FutureWitness(WitnessVersion::V16); "v16")]
+    #[test_case(witness_program(opcodes::OP_PUSHBYTES_0, 25), SiblingOutputKind::NonStandard; "v0 invalid length")]
+    #[test_case(ScriptBuf::from_bytes(vec![OP_CHECKSIG; 3]), SiblingOutputKind::NonStandard; "garbage")]
+    #[test_case(ScriptBuf::new(), SiblingOutputKind::NonStandard; "empty")]
+    fn sibling_output_classification(script_pubkey: ScriptBuf, expected: SiblingOutputKind) {
+        assert_eq!(SiblingOutputKind::classify(&script_pubkey), expected);
+    }
+
+    /// Check that exotic outputs next to the deposit output only cause the
+    /// deposit to be rejected under the strict policy, and that the
+    /// deposit output itself is never checked as a sibling.
+    #[test_case(witness_program(opcodes::OP_PUSHNUM_2, 32), Some(2); "future witness version")]
+    #[test_case(ScriptBuf::from_bytes(vec![OP_CHECKSIG; 3]), None; "non-standard")]
+    fn exotic_sibling_outputs_policy(exotic_script: ScriptBuf, witness_version: Option<u8>) {
+        let mut setup: TxSetup = testing::deposits::tx_setup(150, 15000, &[500_000]);
+        setup.tx.output.insert(
+            0,
+            bitcoin::TxOut {
+                value: bitcoin::Amount::from_sat(1_000),
+                script_pubkey: exotic_script,
+            },
+        );
+
+        let request = CreateDepositRequest {
+            outpoint: OutPoint::new(setup.tx.compute_txid(), 1),
+            reclaim_script: setup.reclaims.first().unwrap().reclaim_script(),
+            deposit_script: setup.deposits.first().unwrap().deposit_script(),
+        };
+
+        let permissive = SiblingOutputPolicy::Permissive;
+        let info = request
+            .validate_tx_with_policy(&setup.tx, false, permissive)
+            .unwrap();
+        assert_eq!(info.outpoint, request.outpoint);
+        assert_eq!(info.amount, 500_000);
+        assert!(request.validate_tx(&setup.tx, false).is_ok());
+
+        let strict = SiblingOutputPolicy::Strict;
+        let error = request
+            .validate_tx_with_policy(&setup.tx, false, strict)
+            .unwrap_err();
+        let sibling = OutPoint::new(request.outpoint.txid, 0);
+        match witness_version {
+            Some(version) => assert_matches::assert_matches!(
+                error,
+                Error::FutureWitnessSiblingOutput(outpoint, v) if outpoint == sibling && v == version
+            ),
+            None => assert_matches::assert_matches!(
+                error,
+                Error::NonStandardSiblingOutput(outpoint) if outpoint == sibling
+            ),
+        }
+    }
+
+    #[test]
+    fn strict_policy_accepts_standard_sibling_outputs() {
+        let mut setup: TxSetup = testing::deposits::tx_setup(150, 15000, &[500_000, 250_000]);
+        setup.tx.output.push(bitcoin::TxOut {
+            value: bitcoin::Amount::from_sat(10_000),
+            script_pubkey: ScriptBuf::new_p2wpkh(&bitcoin::WPubkeyHash::all_zeros()),
+        });
+        setup.tx.output.push(bitcoin::TxOut {
+            value: bitcoin::Amount::ZERO,
+            script_pubkey: ScriptBuf::new_op_return(
+                PushBytesBuf::try_from(vec![0xab; 20]).unwrap(),
+            ),
+        });
+
+        let request = CreateDepositRequest {
+            outpoint: OutPoint::new(setup.tx.compute_txid(), 1),
+            reclaim_script: setup.reclaims[1].reclaim_script(),
+            deposit_script: setup.deposits[1].deposit_script(),
+        };
+
+        let strict = SiblingOutputPolicy::Strict;
+        let info = request
+            .validate_tx_with_policy(&setup.tx, false, strict)
+            .unwrap();
+        assert_eq!(info.amount, 250_000);
+    }
+
     #[test_case(true ; "is mainnet address")]
     #[test_case(false ; "is testnet address")]
     fn tx_validation_network(is_mainnet: bool) {
@@ -51,6 +51,7 @@ pub enum Error {
     RecipientNetworkMismatch(clarity::vm::types::PrincipalData),
     /// This happens when the lock-time is given in time units instead of
     /// block units.
+    #[deprecated(note = "time-based lock-times are now supported and this error is never returned")]
     #[error("lock-time given in time units, but only block units are supported: {0}")]
     UnsupportedLockTimeUnits(u32),
     /// Failed to extract the outpoint from the bitcoin::Transaction.
@@ -71,9 +72,36 @@ pub enum Error {
         /// This is the transaction ID of from the request
         from_request: Txid,
     },
+    /// The deposit funding transaction has an output, other than the
+    /// deposit output, that uses a witness version without consensus
+    /// meaning yet, and sibling outputs are validated strictly.
+    #[error("funding transaction output {0} uses future witness version {1}")]
+    FutureWitnessSiblingOutput(OutPoint, u8),
+    /// The deposit funding transaction has an output, other than the
+    /// deposit output, with a non-standard scriptPubKey, and sibling
+    /// outputs are validated strictly.
+    #[error("funding transaction output {0} has a non-standard scriptPubKey")]
+    NonStandardSiblingOutput(OutPoint),
     /// The reclaim script contained an OP_SUCCESSx opcode.
     #[error("the reclaim script contained an OP_SUCCESSx opcode: {0}")]
     ReclaimScriptWithSuccessOp(bitcoin::ScriptBuf),
+    /// Could not parse a hex encoded bitcoin transaction ID.
+    #[error("could not parse the bitcoin transaction ID: {0}")]
+    InvalidTxid(#[source] bitcoin::hex::HexToArrayError),
+    /// Could not parse a deposit ID, which should be formatted as
+    /// `<txid>:<vout>`.
+    #[error("could not parse the deposit ID: {0}")]
+    InvalidDepositId(String),
+    /// Could not parse a withdrawal ID, which should be a request ID.
+    #[error("could not parse the withdrawal ID: {0}")]
+    InvalidWithdrawalId(String),
+    /// Could not parse a tier of a deposit confirmation policy, which
+    /// should be formatted as `<min_amount>:<confirmations>` with at
+    /// least one confirmation.
+    #[error(
+        "invalid deposit confirmation policy entry: '{0}'. Expected '<min_amount>:<confirmations>' with nonzero confirmations."
+    )]
+    InvalidDepositConfirmationTier(String),
 
     /// This is thrown when failing to parse a hex string into bytes.
     #[cfg(any(test, feature = "webhooks"))]
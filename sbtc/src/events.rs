@@ -553,54 +553,8 @@ impl RawTupleData {
     /// "tweaked" public key.
     fn try_into_script_pub_key(mut self) -> Result<ScriptBuf, EventError> {
         let version = self.remove_buff("version")?;
-        let hash_bytes_buf = self.remove_buff("hashbytes")?;
-        let hash_bytes = hash_bytes_buf.as_slice();
-
-        match version.as_slice() {
-            // version == 0x00 and (len hashbytes) == 20 => P2PKH
-            [0x00] => {
-                let bytes =
-                    <[u8; 20]>::try_from(hash_bytes).map_err(EventError::ClaritySliceConversion)?;
-                let pubkey_hash = PubkeyHash::from_byte_array(bytes);
-                Ok(ScriptBuf::new_p2pkh(&pubkey_hash))
-            }
-            // ```
-            // version == 0x01 and (len hashbytes) == 20 => P2SH
-            // version == 0x02 and (len hashbytes) == 20 => P2SH-P2WPKH
-            // version == 0x03 and (len hashbytes) == 20 => P2SH-P2WSH
-            // ```
-            //
-            // In these cases we assume the `hashbytes` is the Hash160 of
-            // the redeem script.
-            [0x01] | [0x02] | [0x03] => {
-                let bytes =
-                    <[u8; 20]>::try_from(hash_bytes).map_err(EventError::ClaritySliceConversion)?;
-                let script_hash = ScriptHash::from_byte_array(bytes);
-                Ok(ScriptBuf::new_p2sh(&script_hash))
-            }
-            // version == 0x04 and (len hashbytes) == 20 => P2WPKH
-            [0x04] if hash_bytes.len() == 20 => {
-                let program = WitnessProgram::new(WitnessVersion::V0, hash_bytes)
-                    .map_err(EventError::InvalidWitnessProgram)?;
-                Ok(ScriptBuf::new_witness_program(&program))
-            }
-            // version == 0x05 and (len hashbytes) == 32 => P2WSH
-            [0x05] if hash_bytes.len() == 32 => {
-                let program = WitnessProgram::new(WitnessVersion::V0, hash_bytes)
-                    .map_err(EventError::InvalidWitnessProgram)?;
-                Ok(ScriptBuf::new_witness_program(&program))
-            }
-            // version == 0x06 and (len hashbytes) == 32 => P2TR
-            [0x06] if hash_bytes.len() == 32 => {
-                let program = WitnessProgram::new(WitnessVersion::V1, hash_bytes)
-                    .map_err(EventError::InvalidWitnessProgram)?;
-                Ok(ScriptBuf::new_witness_program(&program))
-            }
-            // We make sure that the version and hash byte lengths conform
-            // to the above expectations in the smart contract, so this
-            // should never happen.
-            _ => Err(EventError::UnhandledRecipient(version, hash_bytes_buf)),
-        }
+        let hash_bytes = self.remove_buff("hashbytes")?;
+        recipient_script_pub_key(&version, &hash_bytes)
     }
 
     /// This function is for transforming the print events of the
@@ -749,6 +703,68 @@ impl RawTupleData {
     }
 }
 
+/// Convert a withdrawal recipient, given as the `version` and `hashbytes`
+/// fields of the clarity value
+/// ```clarity
+/// { version: (buff 1), hashbytes: (buff 32) }
+/// ```
+/// into the scriptPubKey that it locks to. This is how recipients are
+/// represented in both the `withdrawal-create` print event and the
+/// `withdrawal-requests` map of the sbtc-registry smart contract.
+pub fn recipient_script_pub_key(
+    version: &[u8],
+    hash_bytes: &[u8],
+) -> Result<ScriptBuf, EventError> {
+    match version {
+        // version == 0x00 and (len hashbytes) == 20 => P2PKH
+        [0x00] => {
+            let bytes =
+                <[u8; 20]>::try_from(hash_bytes).map_err(EventError::ClaritySliceConversion)?;
+            let pubkey_hash = PubkeyHash::from_byte_array(bytes);
+            Ok(ScriptBuf::new_p2pkh(&pubkey_hash))
+        }
+        // ```
+        // version == 0x01 and (len hashbytes) == 20 => P2SH
+        // version == 0x02 and (len hashbytes) == 20 => P2SH-P2WPKH
+        // version == 0x03 and (len hashbytes) == 20 => P2SH-P2WSH
+        // ```
+        //
+        // In these cases we assume the `hashbytes` is the Hash160 of
+        // the redeem script.
+        [0x01] | [0x02] | [0x03] => {
+            let bytes =
+                <[u8; 20]>::try_from(hash_bytes).map_err(EventError::ClaritySliceConversion)?;
+            let script_hash = ScriptHash::from_byte_array(bytes);
+            Ok(ScriptBuf::new_p2sh(&script_hash))
+        }
+        // version == 0x04 and (len hashbytes) == 20 => P2WPKH
+        [0x04] if hash_bytes.len() == 20 => {
+            let program = WitnessProgram::new(WitnessVersion::V0, hash_bytes)
+                .map_err(EventError::InvalidWitnessProgram)?;
+            Ok(ScriptBuf::new_witness_program(&program))
+        }
+        // version == 0x05 and (len hashbytes) == 32 => P2WSH
+        [0x05] if hash_bytes.len() == 32 => {
+            let program = WitnessProgram::new(WitnessVersion::V0, hash_bytes)
+                .map_err(EventError::InvalidWitnessProgram)?;
+            Ok(ScriptBuf::new_witness_program(&program))
+        }
+        // version == 0x06 and (len hashbytes) == 32 => P2TR
+        [0x06] if hash_bytes.len() == 32 => {
+            let program = WitnessProgram::new(WitnessVersion::V1, hash_bytes)
+                .map_err(EventError::InvalidWitnessProgram)?;
+            Ok(ScriptBuf::new_witness_program(&program))
+        }
+        // We make sure that the version and hash byte lengths conform
+        // to the above expectations in the smart contract, so this
+        // should never happen.
+        _ => Err(EventError::UnhandledRecipient(
+            version.to_vec(),
+            hash_bytes.to_vec(),
+        )),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::LazyLock;
@@ -24,40 +24,81 @@ impl Encodable for Segments {
     /// ## Returns
     /// * `Vec<u8>` - Encoded byte vector, or empty vector if segments collection is empty
     fn encode(&self) -> Vec<u8> {
-        let mut result = Vec::new();
-
-        // Return empty bytes for empty segments
-        if self.is_empty() {
-            return result;
+        let mut encoder = SegmentEncoder::default();
+        for segment in self.iter() {
+            encoder.push(segment);
         }
+        encoder.finish()
+    }
+}
 
-        // Track the previous segment's max value for delta encoding of offsets.
-        let mut last_segment_max_value = 0;
+/// Incremental encoder for a sequence of segments.
+///
+/// Produces exactly the same bytes as encoding the equivalent [`Segments`]
+/// collection, but accepts segments one at a time so that callers can
+/// encode the output of a streaming segmenter without first collecting
+/// every segment in memory.
+#[derive(Debug, Default, Clone)]
+pub struct SegmentEncoder {
+    /// The encoded bytes so far.
+    buffer: Vec<u8>,
+    /// The previous segment's max value, used for delta encoding of
+    /// offsets. This is zero before the first segment is pushed.
+    last_segment_max_value: u64,
+}
 
-        for segment in self.iter() {
-            // Calculate offset to encode: absolute for first segment, delta from
-            // the previous segment's max value for subsequent segments. Helps to
-            // reduce the encoded offset size when there are multiple segments.
-            let actual_offset = segment.offset().saturating_sub(last_segment_max_value); // Delta encoding for savings
+impl SegmentEncoder {
+    /// Returns the number of bytes that pushing the given segment would
+    /// add to the encoded output, without encoding it.
+    pub fn encoded_size(&self, segment: &Segment) -> usize {
+        let offset = segment.offset().saturating_sub(self.last_segment_max_value);
+        let payload_length = segment.range().div_ceil(8);
 
-            // Encode segment using the bitmap encoder
-            let mut payload_bytes = encode_bitmap(segment);
+        Leb128::calculate_size(offset)
+            + Leb128::calculate_size(payload_length)
+            + payload_length as usize
+    }
 
-            // Write the segment offset, determined above
-            Leb128::encode_into(actual_offset, &mut result);
+    /// Appends the given segment to the encoded output.
+    ///
+    /// The caller must push segments in ascending order, as produced by a
+    /// segmenter, otherwise the output will not decode.
+    pub fn push(&mut self, segment: &Segment) {
+        // Calculate offset to encode: absolute for first segment, delta from
+        // the previous segment's max value for subsequent segments. Helps to
+        // reduce the encoded offset size when there are multiple segments.
+        let actual_offset = segment.offset().saturating_sub(self.last_segment_max_value); // Delta encoding for savings
 
-            // Write the payload length header
-            let payload_length = payload_bytes.len() as u64;
-            Leb128::encode_into(payload_length, &mut result);
+        // Encode segment using the bitmap encoder
+        let mut payload_bytes = encode_bitmap(segment);
 
-            // Append the encoded segment payload
-            result.append(&mut payload_bytes);
+        // Write the segment offset, determined above
+        Leb128::encode_into(actual_offset, &mut self.buffer);
 
-            // Update the previous segment's max value for delta encoding
-            last_segment_max_value = segment.max();
-        }
+        // Write the payload length header
+        let payload_length = payload_bytes.len() as u64;
+        Leb128::encode_into(payload_length, &mut self.buffer);
+
+        // Append the encoded segment payload
+        self.buffer.append(&mut payload_bytes);
+
+        // Update the previous segment's max value for delta encoding
+        self.last_segment_max_value = segment.max();
+    }
+
+    /// Returns the number of bytes encoded so far.
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns `true` if no segments have been pushed.
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
 
-        result
+    /// Consumes the encoder, returning the encoded bytes.
+    pub fn finish(self) -> Vec<u8> {
+        self.buffer
     }
 }
 
@@ -230,6 +271,34 @@ mod tests {
         Ok(())
     }
 
+    /// Test that the incremental encoder matches encoding the whole
+    /// collection, and that its size estimates are exact.
+    #[test]
+    fn test_segment_encoder_matches_segments_encoding() -> Result<(), Box<dyn std::error::Error>> {
+        let specs: &[(u64, &[u64])] = &[(10, &[11, 12]), (100, &[101]), (200, &[201, 202, 203])];
+
+        let mut segments = Segments::default();
+        let mut encoder = SegmentEncoder::default();
+        assert!(encoder.is_empty());
+
+        for (offset, values) in specs {
+            let mut segment = Segment::new_with_offset(*offset);
+            for &value in *values {
+                segment.try_insert(value)?;
+            }
+
+            let len_before = encoder.len();
+            let expected_size = encoder.encoded_size(&segment);
+            encoder.push(&segment);
+            assert_eq!(encoder.len() - len_before, expected_size);
+
+            segments.try_push(segment)?;
+        }
+
+        assert_eq!(encoder.finish(), segments.encode());
+        Ok(())
+    }
+
     /// Test encoding of empty segments collection
     #[test]
     fn test_empty_segments() {
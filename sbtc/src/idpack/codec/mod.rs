@@ -31,6 +31,8 @@ use super::segment;
 mod decoder;
 mod encoder;
 
+pub use encoder::SegmentEncoder;
+
 /// Trait for types that can be encoded to bytes.
 pub trait Encodable {
     /// Encodes an instance into a byte vector.
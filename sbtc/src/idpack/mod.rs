@@ -20,6 +20,25 @@
 //! println!("Compressed {} integers into {} bytes", values.len(), encoded.len());
 //! ```
 //!
+//! ## Streaming
+//!
+//! Very large sets of integers can be segmented and encoded without
+//! holding every value in memory. [`BitmapSegmenter::stream`] accepts any
+//! iterator of sorted values and yields each segment as soon as it is
+//! complete, and a [`SegmentEncoder`] appends segments to the encoded
+//! output one at a time:
+//!
+//! ```
+//! use sbtc::idpack::{BitmapSegmenter, SegmentEncoder};
+//!
+//! let mut encoder = SegmentEncoder::default();
+//! for segment in BitmapSegmenter.stream((0..1_000_000).step_by(3)) {
+//!     encoder.push(&segment.unwrap());
+//! }
+//! let encoded = encoder.finish();
+//! assert!(!encoded.is_empty());
+//! ```
+//!
 //! ## Safety Considerations
 //!
 //! This library implements safeguards against memory exhaustion attacks that
@@ -31,7 +50,8 @@
 //!
 //! ## Architecture
 //!
-//! * **Segmenters**: Divide integer sequences into optimally-sized segments
+//! * **Segmenters**: Divide integer sequences into optimally-sized segments,
+//!   either from a slice or from a stream of values
 //! * **Segments**: Manage collections of un-encoded segments
 //! * **Segment**: Represents a single packaged integer range
 //! * **Codec**: Low-level encoding/decoding
@@ -50,6 +70,7 @@ pub use segment::SegmentError;
 pub use segments::Segments;
 pub use segments::SegmentsError;
 
+pub use segmenters::BitmapSegmentStream;
 pub use segmenters::BitmapSegmenter;
 pub use segmenters::Segmenter;
 pub use segmenters::SegmenterError;
@@ -57,6 +78,7 @@ pub use segmenters::SegmenterError;
 pub use codec::Decodable;
 pub use codec::DecodeError;
 pub use codec::Encodable;
+pub use codec::SegmentEncoder;
 
 /// Maximum allocation limit in bytes (1MB) for a single bitmap payload for
 /// preventing memory allocation attacks while allowing sufficient space for
@@ -163,6 +163,32 @@ impl Segmenter for BitmapSegmenter {
 }
 
 impl BitmapSegmenter {
+    /// Creates a streaming segmenter over the given sorted values.
+    ///
+    /// The returned iterator makes the same segmentation decisions as
+    /// [`Segmenter::package`], but consumes the input lazily and yields
+    /// each segment as soon as the next value starts a new one. Only the
+    /// segment under construction is held in memory, so arbitrarily long
+    /// sequences can be segmented as long as the individual segments fit.
+    ///
+    /// ## Parameters
+    /// * `values` - The sorted sequence of values to segment
+    ///
+    /// ## Returns
+    /// An iterator of segments. If the input is not sorted or contains
+    /// duplicates then the iterator yields a single
+    /// `Err(SegmenterError::InvalidSequence)` and then stops.
+    pub fn stream<I>(&self, values: I) -> BitmapSegmentStream<I::IntoIter>
+    where
+        I: IntoIterator<Item = u64>,
+    {
+        BitmapSegmentStream {
+            values: values.into_iter(),
+            current: None,
+            failed: false,
+        }
+    }
+
     /// Finds optimal segment boundaries by directly comparing byte costs
     ///
     /// This core algorithm analyzes each potential split point to optimize
@@ -291,9 +317,65 @@ impl BitmapSegmenter {
     }
 }
 
+/// A streaming bitmap segmenter, created by [`BitmapSegmenter::stream`].
+pub struct BitmapSegmentStream<I> {
+    /// The remaining input values.
+    values: I,
+    /// The segment currently under construction.
+    current: Option<Segment>,
+    /// Whether an error has been returned, after which the stream is
+    /// exhausted.
+    failed: bool,
+}
+
+impl<I> Iterator for BitmapSegmentStream<I>
+where
+    I: Iterator<Item = u64>,
+{
+    type Item = Result<Segment, SegmenterError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        for next in self.values.by_ref() {
+            // The first value always starts a new segment.
+            let Some(segment) = self.current.as_mut() else {
+                self.current = Some(Segment::new_with_offset(next));
+                continue;
+            };
+
+            // Ensure input is sorted and unique for bitmap segmentation
+            let prev = segment.max();
+            if next <= prev {
+                self.failed = true;
+                self.current = None;
+                return Some(Err(SegmenterError::InvalidSequence));
+            }
+
+            // Determine if splitting here maximizes compression, in which
+            // case the current segment is complete and `next` is the new
+            // segment's offset.
+            if BitmapCosts::calculate(segment.offset(), prev, next).should_split() {
+                return self.current.replace(Segment::new_with_offset(next)).map(Ok);
+            }
+
+            if let Err(error) = segment.try_insert(next) {
+                self.failed = true;
+                self.current = None;
+                return Some(Err(error.into()));
+            }
+        }
+
+        self.current.take().map(Ok)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::idpack::Encodable as _;
+    use crate::idpack::SegmentEncoder;
 
     use super::*;
     use assert_matches::assert_matches;
@@ -417,6 +499,56 @@ mod tests {
         Ok(())
     }
 
+    /// Tests that streaming produces the same segments as packaging
+    #[test_case(&[]; "empty input")]
+    #[test_case(&[10]; "single value")]
+    #[test_case(&[10, 11, 12, 1000, 1001, 1002]; "clear gap with byte savings")]
+    #[test_case(&[1, 1_000, 10_000, 10_001, 100_000]; "multiple varied gaps")]
+    #[test_case(&[0, u64::MAX]; "full range")]
+    fn test_stream_matches_package(values: &[u64]) {
+        let packaged: Vec<Vec<u64>> = BitmapSegmenter
+            .package(values)
+            .unwrap()
+            .iter()
+            .map(|segment| segment.as_slice().to_vec())
+            .collect();
+
+        let streamed: Vec<Vec<u64>> = BitmapSegmenter
+            .stream(values.iter().copied())
+            .map(|segment| segment.unwrap().as_slice().to_vec())
+            .collect();
+
+        assert_eq!(streamed, packaged);
+    }
+
+    /// Tests that streaming stops at the first invalid value
+    #[test_case(&[5, 3, 1]; "unsorted input")]
+    #[test_case(&[5, 5, 10]; "duplicate values")]
+    #[test_case(&[1, 1_000_000, 2]; "unsorted after a split")]
+    fn test_stream_validation_errors(values: &[u64]) {
+        let mut stream = BitmapSegmenter.stream(values.iter().copied());
+        let errors: Vec<_> = stream.by_ref().filter_map(Result::err).collect();
+
+        assert_eq!(errors, vec![SegmenterError::InvalidSequence]);
+        assert!(stream.next().is_none());
+    }
+
+    /// Tests that segments are emitted before the input is exhausted
+    #[test]
+    fn test_stream_emits_segments_early() {
+        // An endless input where every value is far enough from the
+        // previous one to start a new segment.
+        let values = (0..).map(|i: u64| i * 1_000_000);
+        let segments: Vec<_> = BitmapSegmenter
+            .stream(values)
+            .take(3)
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        let offsets: Vec<u64> = segments.iter().map(Segment::offset).collect();
+        assert_eq!(offsets, vec![0, 1_000_000, 2_000_000]);
+    }
+
     // Add property-based testing for broader input coverage
     proptest! {
         /// Property-based test for size estimation accuracy across randomized inputs
@@ -451,5 +583,25 @@ mod tests {
                 actual_size
             );
         }
+
+        /// Property-based test that streaming and encoding incrementally
+        /// produces the same bytes as packaging and encoding
+        #[test]
+        fn prop_stream_encoding_matches_package(
+            values in prop::collection::vec(0..50_000_u64, 0..100)
+                .prop_map(|mut v| {
+                    v.sort_unstable();
+                    v.dedup();
+                    v
+                })
+        ) {
+            let mut encoder = SegmentEncoder::default();
+            for segment in BitmapSegmenter.stream(values.iter().copied()) {
+                encoder.push(&segment?);
+            }
+
+            let expected = BitmapSegmenter.package(&values)?.encode();
+            prop_assert_eq!(encoder.finish(), expected);
+        }
     }
 }
@@ -3,6 +3,7 @@ mod bitmap;
 use super::segments::Segments;
 use super::{segment, segments};
 
+pub use bitmap::BitmapSegmentStream;
 pub use bitmap::BitmapSegmenter;
 
 /// Errors which can occur during the adaptive segmentation process.
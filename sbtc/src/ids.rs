@@ -0,0 +1,239 @@
+//! Typed identifiers of deposit and withdrawal requests.
+//!
+//! The signers and Emily both identify deposit requests by the outpoint of
+//! the deposit UTXO and withdrawal requests by the request ID that the
+//! sbtc-withdrawal contract assigned to them. Passing these around as
+//! [`DepositId`] and [`WithdrawalId`], instead of as tuples and bare
+//! integers, keeps the conversions between their encodings in one place,
+//! and turns mix-ups, like passing an output index where a request ID is
+//! expected, into type errors.
+
+use std::str::FromStr;
+
+use bitcoin::OutPoint;
+use bitcoin::Txid;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::error::Error;
+
+/// The ID of a deposit request, which is the outpoint of the deposit
+/// UTXO.
+///
+/// It serializes to the `bitcoinTxid` and `bitcoinTxOutputIndex` fields
+/// that Emily uses to identify deposits, and displays, and parses, as
+/// `<txid>:<vout>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct DepositId {
+    /// The ID of the deposit transaction.
+    #[serde(rename = "bitcoinTxid")]
+    pub txid: Txid,
+    /// The index of the deposit output in the deposit transaction.
+    #[serde(rename = "bitcoinTxOutputIndex")]
+    pub vout: u32,
+}
+
+impl DepositId {
+    /// Create a new deposit ID from the deposit transaction ID and the
+    /// index of the deposit output.
+    pub const fn new(txid: Txid, vout: u32) -> Self {
+        Self { txid, vout }
+    }
+
+    /// Create a new deposit ID from the hex encoded transaction ID and
+    /// the output index, as they are given in the Emily API.
+    pub fn from_hex(txid: &str, vout: u32) -> Result<Self, Error> {
+        let txid = Txid::from_str(txid).map_err(Error::InvalidTxid)?;
+        Ok(Self { txid, vout })
+    }
+
+    /// Return the deposit ID as the outpoint of the deposit UTXO.
+    pub const fn outpoint(&self) -> OutPoint {
+        OutPoint {
+            txid: self.txid,
+            vout: self.vout,
+        }
+    }
+}
+
+impl std::fmt::Display for DepositId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.txid, self.vout)
+    }
+}
+
+impl FromStr for DepositId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (txid, vout) = s
+            .split_once(':')
+            .ok_or_else(|| Error::InvalidDepositId(s.to_string()))?;
+        let vout = vout
+            .parse()
+            .map_err(|_| Error::InvalidDepositId(s.to_string()))?;
+
+        Self::from_hex(txid, vout)
+    }
+}
+
+impl From<OutPoint> for DepositId {
+    fn from(outpoint: OutPoint) -> Self {
+        Self {
+            txid: outpoint.txid,
+            vout: outpoint.vout,
+        }
+    }
+}
+
+impl From<DepositId> for OutPoint {
+    fn from(id: DepositId) -> Self {
+        id.outpoint()
+    }
+}
+
+/// The ID of a withdrawal request, as assigned by the sbtc-withdrawal
+/// contract when the request was created.
+///
+/// It serializes as the bare request ID.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct WithdrawalId(u64);
+
+impl WithdrawalId {
+    /// Create a new withdrawal ID from the request ID.
+    pub const fn new(request_id: u64) -> Self {
+        Self(request_id)
+    }
+
+    /// Return the request ID.
+    pub const fn get(&self) -> u64 {
+        self.0
+    }
+}
+
+impl std::fmt::Display for WithdrawalId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl FromStr for WithdrawalId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse()
+            .map(Self)
+            .map_err(|_| Error::InvalidWithdrawalId(s.to_string()))
+    }
+}
+
+impl From<u64> for WithdrawalId {
+    fn from(request_id: u64) -> Self {
+        Self(request_id)
+    }
+}
+
+impl From<WithdrawalId> for u64 {
+    fn from(id: WithdrawalId) -> Self {
+        id.0
+    }
+}
+
+// The signers store deposit IDs in a BYTEA `txid` column and an INTEGER
+// `output_index` column, and withdrawal IDs in a BIGINT column.
+
+#[cfg(feature = "sqlx")]
+impl<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> for DepositId {
+    fn from_row(row: &'r sqlx::postgres::PgRow) -> sqlx::Result<Self> {
+        use bitcoin::hashes::Hash as _;
+        use sqlx::Row as _;
+
+        let txid: [u8; 32] = row.try_get("txid")?;
+        let output_index: i32 = row.try_get("output_index")?;
+        let vout = u32::try_from(output_index).map_err(|error| sqlx::Error::ColumnDecode {
+            index: "output_index".to_string(),
+            source: Box::new(error),
+        })?;
+
+        Ok(Self {
+            txid: Txid::from_byte_array(txid),
+            vout,
+        })
+    }
+}
+
+#[cfg(feature = "sqlx")]
+impl<'r> sqlx::Decode<'r, sqlx::Postgres> for WithdrawalId {
+    fn decode(value: sqlx::postgres::PgValueRef<'r>) -> Result<Self, sqlx::error::BoxDynError> {
+        let request_id = <i64 as sqlx::Decode<sqlx::Postgres>>::decode(value)?;
+        Ok(Self(u64::try_from(request_id)?))
+    }
+}
+
+#[cfg(feature = "sqlx")]
+impl sqlx::Type<sqlx::Postgres> for WithdrawalId {
+    fn type_info() -> sqlx::postgres::PgTypeInfo {
+        <i64 as sqlx::Type<sqlx::Postgres>>::type_info()
+    }
+}
+
+#[cfg(feature = "sqlx")]
+impl<'r> sqlx::Encode<'r, sqlx::Postgres> for WithdrawalId {
+    fn encode_by_ref(
+        &self,
+        buf: &mut sqlx::postgres::PgArgumentBuffer,
+    ) -> Result<sqlx::encode::IsNull, sqlx::error::BoxDynError> {
+        let request_id = i64::try_from(self.0)?;
+        <i64 as sqlx::Encode<'r, sqlx::Postgres>>::encode_by_ref(&request_id, buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::hashes::Hash as _;
+
+    use super::*;
+
+    #[test]
+    fn deposit_id_round_trips_through_its_encodings() {
+        let id = DepositId::new(Txid::from_byte_array([7; 32]), 3);
+        let outpoint = OutPoint::from(id);
+        assert_eq!(DepositId::from(outpoint), id);
+
+        let displayed = id.to_string();
+        assert_eq!(displayed, format!("{}:3", id.txid));
+        assert_eq!(displayed.parse::<DepositId>().unwrap(), id);
+
+        let json = serde_json::to_value(id).unwrap();
+        assert_eq!(json["bitcoinTxid"], id.txid.to_string());
+        assert_eq!(json["bitcoinTxOutputIndex"], 3);
+        assert_eq!(serde_json::from_value::<DepositId>(json).unwrap(), id);
+    }
+
+    #[test]
+    fn malformed_deposit_ids_are_rejected() {
+        let txid = Txid::from_byte_array([7; 32]);
+        for malformed in [
+            txid.to_string(),
+            format!("{txid}:"),
+            format!("{txid}:-1"),
+            "not-a-txid:1".to_string(),
+        ] {
+            assert!(malformed.parse::<DepositId>().is_err(), "{malformed}");
+        }
+        assert!(DepositId::from_hex("abcd", 0).is_err());
+    }
+
+    #[test]
+    fn withdrawal_id_serializes_as_the_request_id() {
+        let id = WithdrawalId::new(42);
+        assert_eq!(serde_json::to_string(&id).unwrap(), "42");
+        assert_eq!(serde_json::from_str::<WithdrawalId>("42").unwrap(), id);
+        assert_eq!("42".parse::<WithdrawalId>().unwrap(), id);
+        assert_eq!(u64::from(id), 42);
+        assert!("-1".parse::<WithdrawalId>().is_err());
+    }
+}
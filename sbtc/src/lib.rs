@@ -9,6 +9,7 @@ pub mod deposits;
 pub mod error;
 pub mod events;
 pub mod idpack;
+pub mod ids;
 pub mod leb128;
 
 #[cfg(any(test, feature = "webhooks"))]
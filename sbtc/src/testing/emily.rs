@@ -27,12 +27,18 @@ pub struct EmilyTables {
     pub deposit: String,
     /// Withdrawals table name
     pub withdrawal: String,
+    /// Archived deposits table name
+    pub deposit_archive: String,
+    /// Archived withdrawals table name
+    pub withdrawal_archive: String,
     /// Chainstates table name
     pub chainstate: String,
     /// Limits table name
     pub limit: String,
     /// Throttle table name
     pub throttle: String,
+    /// Api key table name
+    pub api_key: String,
 }
 
 impl EmilyTables {
@@ -44,13 +50,16 @@ impl EmilyTables {
     }
 
     /// Get the list of tables
-    pub fn tables(&self) -> [&str; 5] {
+    pub fn tables(&self) -> [&str; 8] {
         [
             &self.chainstate,
             &self.deposit,
             &self.limit,
             &self.withdrawal,
+            &self.deposit_archive,
+            &self.withdrawal_archive,
             &self.throttle,
+            &self.api_key,
         ]
     }
 
@@ -97,14 +106,27 @@ async fn create_tables(client: &Client, table_prefix: &str) -> EmilyTables {
 
     join_all(futs).await;
 
-    let tables_to_find = vec!["Deposit", "Chainstate", "Withdrawal", "Limit", "Throttle"];
+    let tables_to_find = vec![
+        "Deposit",
+        "DepositArchive",
+        "Chainstate",
+        "Withdrawal",
+        "WithdrawalArchive",
+        "Limit",
+        "Throttle",
+        "ApiKey",
+    ];
     let mut table_name_map: HashMap<&str, String> = HashMap::new();
 
     for (resource, name) in tables {
-        for table_to_find in &tables_to_find {
-            if resource.contains(table_to_find) {
-                table_name_map.insert(table_to_find, name.clone());
-            }
+        // The archive tables contain the names of the tables they archive,
+        // so the most specific match wins.
+        let table_to_find = tables_to_find
+            .iter()
+            .filter(|table_to_find| resource.contains(*table_to_find))
+            .max_by_key(|table_to_find| table_to_find.len());
+        if let Some(table_to_find) = table_to_find {
+            table_name_map.insert(table_to_find, name);
         }
     }
 
@@ -112,9 +134,15 @@ async fn create_tables(client: &Client, table_prefix: &str) -> EmilyTables {
         client: client.clone(),
         deposit: table_name_map.remove("Deposit").unwrap().to_string(),
         withdrawal: table_name_map.remove("Withdrawal").unwrap().to_string(),
+        deposit_archive: table_name_map.remove("DepositArchive").unwrap().to_string(),
+        withdrawal_archive: table_name_map
+            .remove("WithdrawalArchive")
+            .unwrap()
+            .to_string(),
         chainstate: table_name_map.remove("Chainstate").unwrap().to_string(),
         limit: table_name_map.remove("Limit").unwrap().to_string(),
         throttle: table_name_map.remove("Throttle").unwrap().to_string(),
+        api_key: table_name_map.remove("ApiKey").unwrap().to_string(),
     };
     if !table_name_map.is_empty() {
         panic!("some Emily tables are unknown");
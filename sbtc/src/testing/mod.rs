@@ -3,3 +3,4 @@ pub mod containers;
 pub mod deposits;
 pub mod emily;
 pub mod regtest;
+pub mod vectors;
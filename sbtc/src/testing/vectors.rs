@@ -0,0 +1,376 @@
+//! Deterministic test vectors for deposit and reclaim scripts.
+//!
+//! Other implementations of the sBTC deposit format, such as wallet SDKs
+//! written in other languages, can check themselves against the suite
+//! returned by [`deposit_script_vectors`]. The suite is generated from
+//! fixed inputs, so it is the same every time it is generated, and this
+//! crate is checked against every vector in its own tests.
+//!
+//! Each valid vector lists the inputs to the deposit and reclaim scripts,
+//! the scripts and scriptPubKey that they produce, and the deposit address
+//! on each bitcoin network. Parsing the scripts is expected to return the
+//! inputs. Each invalid vector lists a script and the error returned when
+//! parsing it.
+//!
+//! The suite can be written out as JSON with
+//! ```text
+//! cargo test -p sbtc --features testing -- --ignored print_deposit_script_vectors
+//! ```
+
+use bitcoin::Network;
+use bitcoin::ScriptBuf;
+use bitcoin::XOnlyPublicKey;
+use bitcoin::opcodes::all as opcodes;
+use clarity::vm::types::PrincipalData;
+use secp256k1::SECP256K1;
+use secp256k1::SecretKey;
+use serde::Deserialize;
+use serde::Serialize;
+use stacks_common::types::chainstate::StacksAddress;
+
+use crate::deposits::DepositScriptInputs;
+use crate::deposits::ReclaimScriptInputs;
+
+/// The max fees used in the valid vectors.
+const MAX_FEES: [u64; 4] = [0, 1, 80_000, u64::MAX];
+
+/// The networks that the valid vectors include deposit addresses for.
+const NETWORKS: [Network; 4] = [
+    Network::Bitcoin,
+    Network::Testnet,
+    Network::Signet,
+    Network::Regtest,
+];
+
+/// A suite of deposit and reclaim script test vectors.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DepositScriptVectors {
+    /// Inputs that produce valid deposit and reclaim scripts.
+    pub valid: Vec<DepositScriptVector>,
+    /// Deposit scripts that fail to parse.
+    pub invalid_deposit_scripts: Vec<InvalidScriptVector>,
+    /// Reclaim scripts that fail to parse.
+    pub invalid_reclaim_scripts: Vec<InvalidScriptVector>,
+}
+
+/// A test vector for a valid deposit. Strings holding scripts are hex
+/// encoded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DepositScriptVector {
+    /// What the vector covers.
+    pub description: String,
+    /// The hex encoded x-only public key of the signers.
+    pub signers_public_key: String,
+    /// The stacks principal that receives the sBTC.
+    pub recipient: String,
+    /// The max fee that the depositor is willing to pay, in sats.
+    pub max_fee: u64,
+    /// The lock time in the reclaim script.
+    pub lock_time: u32,
+    /// The user supplied part of the reclaim script.
+    pub reclaim_user_script: String,
+    /// The expected deposit script.
+    pub deposit_script: String,
+    /// The expected reclaim script.
+    pub reclaim_script: String,
+    /// The expected scriptPubKey of the deposit output.
+    pub script_pubkey: String,
+    /// The expected deposit address on each network.
+    pub addresses: NetworkAddresses,
+}
+
+/// The deposit address of a vector on each bitcoin network.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkAddresses {
+    /// The address on mainnet.
+    pub mainnet: String,
+    /// The address on testnet.
+    pub testnet: String,
+    /// The address on signet.
+    pub signet: String,
+    /// The address on regtest.
+    pub regtest: String,
+}
+
+/// A test vector for a script that fails to parse.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InvalidScriptVector {
+    /// What the vector covers.
+    pub description: String,
+    /// The hex encoded script.
+    pub script: String,
+    /// The error returned by this crate when parsing the script.
+    pub error: String,
+}
+
+impl DepositScriptVector {
+    fn new(
+        description: String,
+        deposit: &DepositScriptInputs,
+        reclaim: &ReclaimScriptInputs,
+    ) -> Self {
+        let deposit_script = deposit.deposit_script();
+        let reclaim_script = reclaim.reclaim_script();
+        let script_pubkey =
+            crate::deposits::to_script_pubkey(deposit_script.clone(), reclaim_script.clone());
+        let [mainnet, testnet, signet, regtest] = NETWORKS.map(|network| {
+            deposit
+                .to_address(reclaim_script.clone(), network)
+                .to_string()
+        });
+
+        Self {
+            description,
+            signers_public_key: deposit.signers_public_key.to_string(),
+            recipient: deposit.recipient.to_string(),
+            max_fee: deposit.max_fee,
+            lock_time: reclaim.lock_time(),
+            reclaim_user_script: reclaim.user_script().to_hex_string(),
+            deposit_script: deposit_script.to_hex_string(),
+            reclaim_script: reclaim_script.to_hex_string(),
+            script_pubkey: script_pubkey.to_hex_string(),
+            addresses: NetworkAddresses {
+                mainnet,
+                testnet,
+                signet,
+                regtest,
+            },
+        }
+    }
+
+    /// Check that this crate produces the scripts and addresses in the
+    /// vector from its inputs, and that parsing the scripts returns the
+    /// inputs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the checks fail.
+    pub fn verify(&self) {
+        let deposit = DepositScriptInputs {
+            signers_public_key: self.signers_public_key.parse().unwrap(),
+            recipient: PrincipalData::parse(&self.recipient).unwrap(),
+            max_fee: self.max_fee,
+        };
+        let user_script = ScriptBuf::from_hex(&self.reclaim_user_script).unwrap();
+        let reclaim = ReclaimScriptInputs::try_new(self.lock_time, user_script).unwrap();
+
+        let expected = Self::new(self.description.clone(), &deposit, &reclaim);
+        assert_eq!(&expected, self, "{}", self.description);
+
+        let deposit_script = ScriptBuf::from_hex(&self.deposit_script).unwrap();
+        let parsed_deposit = DepositScriptInputs::parse(&deposit_script).unwrap();
+        assert_eq!(parsed_deposit, deposit, "{}", self.description);
+
+        let reclaim_script = ScriptBuf::from_hex(&self.reclaim_script).unwrap();
+        let parsed_reclaim = ReclaimScriptInputs::parse(&reclaim_script).unwrap();
+        assert_eq!(parsed_reclaim, reclaim, "{}", self.description);
+    }
+}
+
+impl InvalidScriptVector {
+    fn new<T: std::fmt::Debug>(
+        description: &str,
+        script: ScriptBuf,
+        parse: fn(&ScriptBuf) -> Result<T, crate::error::Error>,
+    ) -> Self {
+        let error = parse(&script).expect_err(description);
+        Self {
+            description: description.to_string(),
+            script: script.to_hex_string(),
+            error: error.to_string(),
+        }
+    }
+
+    fn verify<T: std::fmt::Debug>(&self, parse: fn(&ScriptBuf) -> Result<T, crate::error::Error>) {
+        let script = ScriptBuf::from_hex(&self.script).unwrap();
+        let error = parse(&script).expect_err(&self.description);
+        assert_eq!(error.to_string(), self.error, "{}", self.description);
+    }
+
+    /// Check that parsing the deposit script in the vector fails with the
+    /// expected error.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the script parses or fails with a different error.
+    pub fn verify_deposit_script(&self) {
+        self.verify(DepositScriptInputs::parse);
+    }
+
+    /// Check that parsing the reclaim script in the vector fails with the
+    /// expected error.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the script parses or fails with a different error.
+    pub fn verify_reclaim_script(&self) {
+        self.verify(ReclaimScriptInputs::parse);
+    }
+}
+
+impl DepositScriptVectors {
+    /// Check every vector in the suite against this crate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this crate disagrees with any of the vectors.
+    pub fn verify(&self) {
+        self.valid.iter().for_each(DepositScriptVector::verify);
+        self.invalid_deposit_scripts
+            .iter()
+            .for_each(InvalidScriptVector::verify_deposit_script);
+        self.invalid_reclaim_scripts
+            .iter()
+            .for_each(InvalidScriptVector::verify_reclaim_script);
+    }
+}
+
+/// A public key derived from a fixed secret key.
+fn public_key(seed: u8) -> XOnlyPublicKey {
+    let secret_key = SecretKey::from_slice(&[seed; 32]).unwrap();
+    secret_key.x_only_public_key(SECP256K1).0
+}
+
+/// A contract principal issued by the burn address of the network.
+fn contract_principal(mainnet: bool, name: &str) -> PrincipalData {
+    let issuer = StacksAddress::burn_address(mainnet);
+    PrincipalData::parse(&format!("{issuer}.{name}")).unwrap()
+}
+
+/// Generate the suite of deposit and reclaim script test vectors.
+pub fn deposit_script_vectors() -> DepositScriptVectors {
+    let checksig_script = ScriptBuf::builder()
+        .push_slice(public_key(0xaa).serialize())
+        .push_opcode(opcodes::OP_CHECKSIG)
+        .into_script();
+
+    let recipients = [
+        (
+            "mainnet standard principal",
+            PrincipalData::from(StacksAddress::burn_address(true)),
+        ),
+        (
+            "testnet standard principal",
+            PrincipalData::from(StacksAddress::burn_address(false)),
+        ),
+        (
+            "mainnet contract principal",
+            contract_principal(true, "sbtc-deposit"),
+        ),
+        (
+            "testnet contract principal with the longest name",
+            contract_principal(false, &"a".repeat(128)),
+        ),
+    ];
+    let reclaims = [
+        ("zero lock time and empty user script", 0, ScriptBuf::new()),
+        ("lock time pushed with OP_1", 1, checksig_script.clone()),
+        ("lock time pushed with OP_16", 16, checksig_script.clone()),
+        ("one byte lock time", 144, checksig_script.clone()),
+        ("largest block lock time", 65_535, checksig_script.clone()),
+        (
+            "time based lock time",
+            (1 << 22) | 10,
+            checksig_script.clone(),
+        ),
+    ];
+
+    let mut valid = Vec::new();
+    for (i, (recipient_description, recipient)) in recipients.iter().enumerate() {
+        for (j, (reclaim_description, lock_time, user_script)) in reclaims.iter().enumerate() {
+            let index = i * reclaims.len() + j;
+            let deposit = DepositScriptInputs {
+                signers_public_key: public_key(index as u8 + 1),
+                recipient: recipient.clone(),
+                max_fee: MAX_FEES[index % MAX_FEES.len()],
+            };
+            let reclaim = ReclaimScriptInputs::try_new(*lock_time, user_script.clone()).unwrap();
+            let description = format!("{recipient_description}, {reclaim_description}");
+            valid.push(DepositScriptVector::new(description, &deposit, &reclaim));
+        }
+    }
+
+    let deposit_script = DepositScriptInputs {
+        signers_public_key: public_key(0xbb),
+        recipient: PrincipalData::from(StacksAddress::burn_address(true)),
+        max_fee: 80_000,
+    }
+    .deposit_script()
+    .into_bytes();
+
+    let mut truncated = deposit_script.clone();
+    truncated.pop();
+    let mut checksigverify = deposit_script.clone();
+    if let Some(last) = checksigverify.last_mut() {
+        *last = opcodes::OP_CHECKSIGVERIFY.to_u8();
+    }
+
+    let invalid_deposit_scripts = vec![
+        InvalidScriptVector::new("empty script", ScriptBuf::new(), DepositScriptInputs::parse),
+        InvalidScriptVector::new(
+            "missing the final OP_CHECKSIG",
+            ScriptBuf::from_bytes(truncated),
+            DepositScriptInputs::parse,
+        ),
+        InvalidScriptVector::new(
+            "OP_CHECKSIGVERIFY instead of OP_CHECKSIG",
+            ScriptBuf::from_bytes(checksigverify),
+            DepositScriptInputs::parse,
+        ),
+    ];
+
+    let reclaim_script = |lock_time: i64| {
+        ScriptBuf::builder()
+            .push_int(lock_time)
+            .push_opcode(opcodes::OP_CSV)
+            .into_script()
+    };
+    let invalid_reclaim_scripts = vec![
+        InvalidScriptVector::new("empty script", ScriptBuf::new(), ReclaimScriptInputs::parse),
+        InvalidScriptVector::new(
+            "OP_CSV without a lock time",
+            ScriptBuf::builder()
+                .push_opcode(opcodes::OP_CSV)
+                .into_script(),
+            ReclaimScriptInputs::parse,
+        ),
+        InvalidScriptVector::new(
+            "lock time with the disable flag set",
+            reclaim_script(1 << 31),
+            ReclaimScriptInputs::parse,
+        ),
+    ];
+
+    DepositScriptVectors {
+        valid,
+        invalid_deposit_scripts,
+        invalid_reclaim_scripts,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vectors_are_deterministic() {
+        assert_eq!(deposit_script_vectors(), deposit_script_vectors());
+    }
+
+    #[test]
+    fn crate_round_trips_all_vectors() {
+        let vectors = deposit_script_vectors();
+        let json = serde_json::to_string(&vectors).unwrap();
+        let parsed: DepositScriptVectors = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed, vectors);
+        parsed.verify();
+    }
+
+    #[ignore = "this is for writing out the deposit script test vectors"]
+    #[test]
+    fn print_deposit_script_vectors() {
+        let vectors = deposit_script_vectors();
+        println!("{}", serde_json::to_string_pretty(&vectors).unwrap());
+    }
+}
@@ -0,0 +1,417 @@
+//! Criterion benchmarks for the critical storage queries and the sweep
+//! construction path, run against a Postgres database seeded with a large
+//! amount of data.
+//!
+//! These need a running Postgres instance, such as the one started with
+//! `make integration-env-up`:
+//!
+//! ```text
+//! cargo bench -p signer --bench storage
+//! ```
+//!
+//! The usual criterion arguments can be given after `--`, for example
+//! `-- --save-baseline main` to save the results as the `main` baseline.
+//! The benchmark is also configured through the following environment
+//! variables:
+//!
+//! - `SIGNER_BENCH_BLOCKS`: the number of bitcoin blocks to generate,
+//!   defaults to 1,000. Each block comes with
+//!   `SIGNER_BENCH_DEPOSITS_PER_BLOCK` deposit requests and
+//!   `SIGNER_BENCH_WITHDRAWALS_PER_BLOCK` withdrawal requests, both of
+//!   which default to 10, and a vote from each of the signers on each
+//!   request. Setting the number of blocks to 100,000 gives millions of
+//!   rows.
+//! - `SIGNER_BENCH_DATABASE_URL`: the database to use. If it already has
+//!   a bitcoin chain tip then it is assumed to have been seeded by a
+//!   previous run and is used as is, which avoids having to seed large
+//!   databases each time. Otherwise a fresh test database is created,
+//!   seeded and dropped once the benchmark completes.
+//! - `SIGNER_BENCH_BASELINE`: the name of a criterion baseline saved with
+//!   `--save-baseline`. The mean duration of each case in this run is
+//!   compared against it and the benchmark exits with a non-zero status
+//!   if any case is slower than the baseline by more than
+//!   `SIGNER_BENCH_MAX_REGRESSION` percent, which defaults to 20. This
+//!   makes the benchmark usable as a regression gate.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::time::Instant;
+
+use bitcoin::Amount;
+use bitcoin::OutPoint;
+use criterion::Criterion;
+use rand::SeedableRng as _;
+use rand_chacha::ChaCha20Rng;
+use tokio::runtime::Runtime;
+
+use signer::bitcoin::utxo;
+use signer::bitcoin::utxo::RequestPrioritization;
+use signer::bitcoin::utxo::SbtcRequests;
+use signer::bitcoin::utxo::SignerBtcState;
+use signer::bitcoin::utxo::SignerUtxo;
+use signer::config::DepositConfirmationPolicy;
+use signer::context::SbtcLimits;
+use signer::error::Error;
+use signer::keys::PrivateKey;
+use signer::keys::PublicKey;
+use signer::storage::DbRead as _;
+use signer::storage::postgres::PgStore;
+use signer::testing::storage::load::LoadFixture;
+use signer::testing::storage::load::LoadParams;
+use signer::testing::storage::model::Params;
+
+/// The name of the criterion benchmark group that all cases are in.
+const GROUP: &str = "storage";
+/// The number of samples that criterion takes of each case. Some of the
+/// queries are slow against a large database, so this is the minimum
+/// that criterion allows.
+const SAMPLE_SIZE: usize = 10;
+/// The seed for generating the signer keys and the seeded data. Using a
+/// fixed seed means that a database seeded by a previous run can be
+/// reused, since the signer keys are the same.
+const SEED: u64 = 0x5b7c;
+/// The number of signers voting on each request.
+const NUM_SIGNERS: usize = 15;
+/// The number of accepting votes required for a request to be swept.
+const SIGNATURE_THRESHOLD: u16 = 8;
+/// The number of bitcoin blocks that the queries look back through.
+const CONTEXT_WINDOW: u16 = 1000;
+/// The number of bitcoin blocks written in a single database transaction
+/// while seeding.
+const CHUNK_SIZE: usize = 100;
+
+/// Read the given environment variable, falling back to the default if
+/// it is not set or cannot be parsed.
+fn env_or<T: FromStr>(name: &str, default: T) -> T {
+    std::env::var(name)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Fetch the accepted requests along with their votes and construct the
+/// sweep transaction package for them, the same way that the transaction
+/// coordinator does. Returns the number of transactions in the package.
+async fn construct_sweep(store: &PgStore, fixture: &LoadFixture) -> Result<usize, Error> {
+    let chain_tip = &fixture.bitcoin_chain_tip;
+    let aggregate_key = &fixture.aggregate_key;
+    let min_bitcoin_height = chain_tip
+        .block_height
+        .saturating_sub(u64::from(CONTEXT_WINDOW));
+
+    let pending_deposits = store
+        .get_pending_accepted_deposit_requests(
+            chain_tip,
+            CONTEXT_WINDOW,
+            SIGNATURE_THRESHOLD,
+            &DepositConfirmationPolicy::default(),
+        )
+        .await?;
+    let mut deposits = Vec::with_capacity(pending_deposits.len());
+    for request in pending_deposits {
+        let votes = store
+            .get_deposit_request_signer_votes(&request.txid, request.output_index, aggregate_key)
+            .await?;
+        deposits.push(utxo::DepositRequest::from_model(request, votes));
+    }
+
+    let pending_withdrawals = store
+        .get_pending_accepted_withdrawal_requests(
+            &chain_tip.block_hash,
+            &fixture.stacks_chain_tip,
+            min_bitcoin_height,
+            SIGNATURE_THRESHOLD,
+        )
+        .await?;
+    let mut withdrawals = Vec::with_capacity(pending_withdrawals.len());
+    for request in pending_withdrawals {
+        let votes = store
+            .get_withdrawal_request_signer_votes(&request.qualified_id(), aggregate_key)
+            .await?;
+        withdrawals.push(utxo::WithdrawalRequest::from_model(request, votes));
+    }
+
+    let requests = SbtcRequests {
+        deposits,
+        withdrawals,
+        signer_state: SignerBtcState {
+            utxo: SignerUtxo {
+                outpoint: OutPoint::null(),
+                amount: Amount::MAX_MONEY.to_sat(),
+                public_key: aggregate_key.into(),
+            },
+            fee_rate: 10.0,
+            public_key: aggregate_key.into(),
+            last_fees: None,
+            magic_bytes: signer::LEGACY_MAGIC_BYTES,
+            change_outputs: 1,
+        },
+        accept_threshold: SIGNATURE_THRESHOLD,
+        num_signers: NUM_SIGNERS as u16,
+        sbtc_limits: SbtcLimits::unlimited(),
+        max_deposits_per_bitcoin_tx: signer::DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX,
+        max_withdrawals_per_bitcoin_tx: u16::MAX,
+        max_withdrawal_value: u64::MAX,
+        prioritization: RequestPrioritization::Fifo,
+    };
+
+    Ok(requests.construct_transactions()?.len())
+}
+
+/// Return the fixture for a database that was seeded by a previous run,
+/// or `None` if the database is empty.
+async fn existing_fixture(
+    store: &PgStore,
+    signer_keys: &[PublicKey],
+) -> Result<Option<LoadFixture>, Error> {
+    let Some(bitcoin_chain_tip) = store.get_bitcoin_canonical_chain_tip_ref().await? else {
+        return Ok(None);
+    };
+    let Some(stacks_chain_tip) = store
+        .get_stacks_chain_tip(&bitcoin_chain_tip.block_hash)
+        .await?
+    else {
+        return Ok(None);
+    };
+
+    Ok(Some(LoadFixture {
+        bitcoin_chain_tip,
+        stacks_chain_tip: stacks_chain_tip.block_hash,
+        aggregate_key: PublicKey::combine_keys(signer_keys)?,
+        num_stacks_blocks: 0,
+        num_deposit_requests: 0,
+        num_withdrawal_requests: 0,
+    }))
+}
+
+/// Seed the store, unless it was seeded by a previous run, and return the
+/// fixture describing the seeded data.
+async fn seed(store: &PgStore, signer_keys: &[PublicKey], rng: &mut ChaCha20Rng) -> LoadFixture {
+    let existing = existing_fixture(store, signer_keys)
+        .await
+        .expect("failed to read the chain tips");
+    if let Some(fixture) = existing {
+        println!(
+            "using existing data with chain tip {}",
+            fixture.bitcoin_chain_tip.block_hash
+        );
+        return fixture;
+    }
+
+    let params = LoadParams {
+        data: Params {
+            num_bitcoin_blocks: env_or("SIGNER_BENCH_BLOCKS", 1_000),
+            num_stacks_blocks_per_bitcoin_block: 2,
+            num_deposit_requests_per_block: env_or("SIGNER_BENCH_DEPOSITS_PER_BLOCK", 10),
+            num_withdraw_requests_per_block: env_or("SIGNER_BENCH_WITHDRAWALS_PER_BLOCK", 10),
+            num_signers_per_request: NUM_SIGNERS,
+            consecutive_blocks: true,
+        },
+        chunk_size: CHUNK_SIZE,
+    };
+    let start = Instant::now();
+    let fixture = signer::testing::storage::load::seed_database(store, rng, signer_keys, &params)
+        .await
+        .expect("no data was generated");
+    println!(
+        "seeded {} bitcoin blocks, {} stacks blocks, {} deposits and {} withdrawals in {:?}",
+        params.data.num_bitcoin_blocks,
+        fixture.num_stacks_blocks,
+        fixture.num_deposit_requests,
+        fixture.num_withdrawal_requests,
+        start.elapsed(),
+    );
+    fixture
+}
+
+/// Register and run all of the cases against the given store.
+fn bench_storage(
+    criterion: &mut Criterion,
+    runtime: &Runtime,
+    store: &PgStore,
+    signer_keys: &[PublicKey],
+    fixture: &LoadFixture,
+) {
+    let chain_tip = &fixture.bitcoin_chain_tip;
+    let stacks_chain_tip = &fixture.stacks_chain_tip;
+    let signer_public_key = &signer_keys[0];
+    let policy = &DepositConfirmationPolicy::default();
+    let min_bitcoin_height = chain_tip
+        .block_height
+        .saturating_sub(u64::from(CONTEXT_WINDOW));
+
+    let mut group = criterion.benchmark_group(GROUP);
+    group.sample_size(SAMPLE_SIZE);
+
+    group.bench_function("get_bitcoin_canonical_chain_tip_ref", |b| {
+        b.to_async(runtime).iter(|| async move {
+            store
+                .get_bitcoin_canonical_chain_tip_ref()
+                .await
+                .expect("query failed")
+        })
+    });
+    group.bench_function("get_stacks_chain_tip", |b| {
+        b.to_async(runtime).iter(|| async move {
+            store
+                .get_stacks_chain_tip(&chain_tip.block_hash)
+                .await
+                .expect("query failed")
+        })
+    });
+    group.bench_function("get_pending_deposit_requests", |b| {
+        b.to_async(runtime).iter(|| async move {
+            store
+                .get_pending_deposit_requests(
+                    &chain_tip.block_hash,
+                    CONTEXT_WINDOW,
+                    signer_public_key,
+                )
+                .await
+                .expect("query failed")
+        })
+    });
+    group.bench_function("get_pending_accepted_deposit_requests", |b| {
+        b.to_async(runtime).iter(|| async move {
+            store
+                .get_pending_accepted_deposit_requests(
+                    chain_tip,
+                    CONTEXT_WINDOW,
+                    SIGNATURE_THRESHOLD,
+                    policy,
+                )
+                .await
+                .expect("query failed")
+        })
+    });
+    group.bench_function("get_pending_withdrawal_requests", |b| {
+        b.to_async(runtime).iter(|| async move {
+            store
+                .get_pending_withdrawal_requests(
+                    &chain_tip.block_hash,
+                    stacks_chain_tip,
+                    CONTEXT_WINDOW,
+                    signer_public_key,
+                )
+                .await
+                .expect("query failed")
+        })
+    });
+    group.bench_function("get_pending_accepted_withdrawal_requests", |b| {
+        b.to_async(runtime).iter(|| async move {
+            store
+                .get_pending_accepted_withdrawal_requests(
+                    &chain_tip.block_hash,
+                    stacks_chain_tip,
+                    min_bitcoin_height,
+                    SIGNATURE_THRESHOLD,
+                )
+                .await
+                .expect("query failed")
+        })
+    });
+    group.bench_function("construct_sweep_transactions", |b| {
+        b.to_async(runtime).iter(|| async move {
+            construct_sweep(store, fixture)
+                .await
+                .expect("failed to construct the sweep transactions")
+        })
+    });
+
+    group.finish();
+}
+
+/// The directory where criterion writes its measurements. Like criterion,
+/// this is `CRITERION_HOME` if it is set, and the `criterion` directory
+/// in the cargo target directory otherwise.
+fn criterion_directory() -> PathBuf {
+    if let Some(home) = std::env::var_os("CRITERION_HOME") {
+        return PathBuf::from(home);
+    }
+
+    std::env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("../target"))
+        .join("criterion")
+}
+
+/// Read the mean duration, in nanoseconds, that criterion recorded for a
+/// case under the given baseline name.
+fn mean_estimate(case_directory: &Path, baseline: &str) -> Option<f64> {
+    let path = case_directory.join(baseline).join("estimates.json");
+    let contents = std::fs::read_to_string(path).ok()?;
+    let estimates: serde_json::Value = serde_json::from_str(&contents).ok()?;
+    estimates["mean"]["point_estimate"].as_f64()
+}
+
+/// Compare the cases of this run against the given criterion baseline and
+/// return the names of the cases that regressed by more than
+/// `max_regression` percent.
+fn regressions(baseline: &str, max_regression: f64) -> Vec<String> {
+    let group_directory = criterion_directory().join(GROUP);
+    let entries =
+        std::fs::read_dir(&group_directory).expect("failed to read the criterion results");
+
+    entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let mean = mean_estimate(&entry.path(), "new")?;
+            let base = mean_estimate(&entry.path(), baseline)?;
+            let change = (mean - base) / base * 100.0;
+            (change > max_regression).then(|| format!("{name}: {change:+.1}%"))
+        })
+        .collect()
+}
+
+fn main() -> ExitCode {
+    let runtime = Runtime::new().expect("failed to start the tokio runtime");
+    let mut rng = ChaCha20Rng::seed_from_u64(SEED);
+    let signer_keys: Vec<PublicKey> = (0..NUM_SIGNERS)
+        .map(|_| PublicKey::from_private_key(&PrivateKey::new(&mut rng)))
+        .collect();
+
+    let database_url: Option<String> = std::env::var("SIGNER_BENCH_DATABASE_URL").ok();
+    let store = runtime.block_on(async {
+        match database_url.as_deref() {
+            Some(url) => {
+                let store = PgStore::connect(url)
+                    .await
+                    .expect("failed to connect to database");
+                store
+                    .apply_migrations()
+                    .await
+                    .expect("failed to apply db migrations");
+                store
+            }
+            None => signer::testing::storage::new_test_database().await,
+        }
+    });
+    let fixture = runtime.block_on(seed(&store, &signer_keys, &mut rng));
+
+    let mut criterion = Criterion::default().configure_from_args();
+    bench_storage(&mut criterion, &runtime, &store, &signer_keys, &fixture);
+    criterion.final_summary();
+
+    if database_url.is_none() {
+        runtime.block_on(signer::testing::storage::drop_db(store));
+    }
+
+    let Ok(baseline) = std::env::var("SIGNER_BENCH_BASELINE") else {
+        return ExitCode::SUCCESS;
+    };
+    let max_regression = env_or("SIGNER_BENCH_MAX_REGRESSION", 20.0);
+
+    let regressions = regressions(&baseline, max_regression);
+    if regressions.is_empty() {
+        return ExitCode::SUCCESS;
+    }
+
+    eprintln!("cases slower than the {baseline} baseline by more than {max_regression}%:");
+    for regression in regressions {
+        eprintln!("  {regression}");
+    }
+    ExitCode::FAILURE
+}
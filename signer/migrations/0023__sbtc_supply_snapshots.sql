@@ -0,0 +1,26 @@
+-- Stores the results of periodically reconciling the sBTC supply that the
+-- signer expects, given the completed deposits and accepted withdrawals in
+-- its database, against the total supply reported by the sBTC token
+-- contract.
+CREATE TABLE sbtc_signer.sbtc_supply_snapshots (
+    id BIGSERIAL PRIMARY KEY,
+    -- The bitcoin chain tip at the time of the reconciliation.
+    bitcoin_block_hash BYTEA NOT NULL,
+    -- The height of the bitcoin chain tip at the time of the
+    -- reconciliation.
+    bitcoin_block_height BIGINT NOT NULL,
+    -- The total amount of sBTC, in sats, minted by completed deposits.
+    minted BIGINT NOT NULL,
+    -- The total amount of sBTC, in sats, burned by accepted withdrawals.
+    burned BIGINT NOT NULL,
+    -- The total supply of sBTC, in sats, reported by the sBTC token
+    -- contract.
+    reported_supply BIGINT NOT NULL,
+    -- Whether the difference between the expected supply and the reported
+    -- supply was within the configured tolerance.
+    within_tolerance BOOLEAN NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX ix_sbtc_supply_snapshots_created_at
+    ON sbtc_signer.sbtc_supply_snapshots(created_at);
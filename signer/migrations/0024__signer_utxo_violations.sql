@@ -0,0 +1,26 @@
+-- Stores sweep transactions where the value of the new signers' UTXO did
+-- not equal the value of the signers' input, plus the deposits swept,
+-- minus the withdrawals paid and the fee.
+CREATE TABLE sbtc_signer.signer_utxo_violations (
+    -- The ID of the sweep transaction.
+    txid BYTEA NOT NULL,
+    -- The block hash of the bitcoin block that included the transaction.
+    block_hash BYTEA NOT NULL,
+    -- The amount locked in the signers' UTXO that was spent by the
+    -- transaction.
+    signer_input BIGINT NOT NULL,
+    -- The total amount of all deposits swept in by the transaction.
+    deposits BIGINT NOT NULL,
+    -- The total amount paid out to withdrawal recipients.
+    withdrawals BIGINT NOT NULL,
+    -- The fee paid to bitcoin miners.
+    fee BIGINT NOT NULL,
+    -- The amount locked in the new signers' UTXO.
+    signer_output BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (txid, block_hash),
+    FOREIGN KEY (block_hash) REFERENCES sbtc_signer.bitcoin_blocks(block_hash)
+);
+
+CREATE INDEX ix_signer_utxo_violations_created_at
+    ON sbtc_signer.signer_utxo_violations(created_at);
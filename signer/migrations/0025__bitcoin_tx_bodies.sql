@@ -0,0 +1,22 @@
+-- Caches the full bodies of sBTC related bitcoin transactions, along with
+-- the outputs that they spend, so that they do not need to be fetched from
+-- bitcoin-core. Entries are pruned once they are older than the configured
+-- retention.
+CREATE TABLE sbtc_signer.bitcoin_tx_bodies (
+    -- The ID of the transaction.
+    txid BYTEA NOT NULL,
+    -- The hash of the bitcoin block that included the transaction.
+    block_hash BYTEA NOT NULL,
+    -- The height of the bitcoin block that included the transaction.
+    block_height BIGINT NOT NULL,
+    -- The consensus encoded transaction.
+    tx BYTEA NOT NULL,
+    -- The consensus encoded outputs spent by the inputs of the
+    -- transaction, in the order of the inputs.
+    prevouts BYTEA NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (txid, block_hash)
+);
+
+CREATE INDEX ix_bitcoin_tx_bodies_block_height
+    ON sbtc_signer.bitcoin_tx_bodies(block_height);
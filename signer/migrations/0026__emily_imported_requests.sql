@@ -0,0 +1,23 @@
+-- Records which deposit and withdrawal requests were imported from Emily
+-- by the backfill importer, rather than being observed directly by the
+-- signer. New signers joining an existing deployment use the importer to
+-- learn about requests that predate them.
+CREATE TABLE sbtc_signer.emily_imported_deposits (
+    txid BYTEA NOT NULL,
+    output_index INTEGER NOT NULL,
+    imported_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (txid, output_index),
+    FOREIGN KEY (txid, output_index)
+        REFERENCES sbtc_signer.deposit_requests(txid, output_index)
+        ON DELETE CASCADE
+);
+
+CREATE TABLE sbtc_signer.emily_imported_withdrawals (
+    request_id BIGINT NOT NULL,
+    block_hash BYTEA NOT NULL,
+    imported_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (request_id, block_hash),
+    FOREIGN KEY (request_id, block_hash)
+        REFERENCES sbtc_signer.withdrawal_requests(request_id, block_hash)
+        ON DELETE CASCADE
+);
@@ -0,0 +1,15 @@
+-- Stores the software and protocol versions that libp2p peers advertised
+-- when we last connected to them, so that operators can see which peers
+-- still need to be upgraded.
+CREATE TABLE sbtc_signer.p2p_peer_versions (
+    -- The libp2p PeerId of the peer (base58 encoded multihash).
+    peer_id TEXT PRIMARY KEY,
+    -- The agent version string advertised by the peer, which includes
+    -- the crate version of the signer binary.
+    agent_version TEXT NOT NULL,
+    -- The signer protocol version advertised by the peer. Peers that
+    -- predate version gossip are recorded with version 0.
+    protocol_version INTEGER NOT NULL,
+    -- Timestamp of the last time the peer advertised its versions.
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,17 @@
+-- Stores the libp2p peers that have been banned for misbehaving on the
+-- signer network, so that bans survive restarts and operators can see
+-- which peers are currently banned.
+CREATE TABLE sbtc_signer.p2p_peer_bans (
+    -- The libp2p PeerId of the peer (base58 encoded multihash).
+    peer_id TEXT PRIMARY KEY,
+    -- The kind of misbehavior that pushed the peer over the ban threshold.
+    reason TEXT NOT NULL,
+    -- The misbehavior score of the peer when it was banned.
+    score INTEGER NOT NULL,
+    -- Timestamp of when the peer was banned.
+    banned_at TIMESTAMPTZ NOT NULL,
+    -- Timestamp of when the ban is lifted.
+    expires_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX ix_p2p_peer_bans_expires_at ON sbtc_signer.p2p_peer_bans(expires_at);
@@ -0,0 +1,27 @@
+-- Tracks the DKG verification signing rounds that this signer has taken
+-- part in, so that a signer that restarts in the middle of a round can
+-- either rejoin it or cleanly abort it.
+CREATE TYPE sbtc_signer.dkg_verification_round_status AS ENUM (
+    'active',
+    'completed',
+    'failed',
+    'aborted'
+);
+
+CREATE TABLE sbtc_signer.dkg_verification_rounds (
+    -- The x-only aggregate key that is being verified.
+    aggregate_key BYTEA NOT NULL,
+    -- The bitcoin chain tip that identifies the coordinator tenure in
+    -- which the round is taking place.
+    bitcoin_block_hash BYTEA NOT NULL,
+    bitcoin_block_height BIGINT NOT NULL,
+    -- The current status of the round.
+    status sbtc_signer.dkg_verification_round_status NOT NULL,
+    -- The type of the last WSTS message that was processed for the round.
+    last_message_type TEXT,
+    -- Timestamp of when the round was last updated.
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (aggregate_key, bitcoin_block_hash)
+);
+
+CREATE INDEX ix_dkg_verification_rounds_status ON sbtc_signer.dkg_verification_rounds(status);
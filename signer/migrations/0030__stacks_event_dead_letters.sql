@@ -0,0 +1,27 @@
+-- Stores the sbtc-registry events sent by the stacks node that we could
+-- not parse, so that they can be inspected and replayed after a fix to
+-- the parser instead of being lost.
+CREATE TABLE sbtc_signer.stacks_event_dead_letters (
+    -- The index block hash of the stacks block that contains the event.
+    block_hash BYTEA NOT NULL,
+    -- The ID of the stacks transaction that emitted the event.
+    txid BYTEA NOT NULL,
+    -- The position of the event within the events of the block webhook.
+    event_index INTEGER NOT NULL,
+    -- The hex encoded consensus serialization of the clarity value of the
+    -- event.
+    raw_value TEXT NOT NULL,
+    -- The error returned the last time that we tried to parse the event.
+    error TEXT NOT NULL,
+    -- The number of times that we tried to parse the event.
+    attempts INTEGER NOT NULL DEFAULT 1,
+    -- Timestamp of when the event was first dead-lettered.
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- Timestamp of when the event was successfully replayed, if it was.
+    resolved_at TIMESTAMPTZ,
+    PRIMARY KEY (block_hash, txid, event_index)
+);
+
+CREATE INDEX ix_stacks_event_dead_letters_unresolved
+    ON sbtc_signer.stacks_event_dead_letters(created_at)
+    WHERE resolved_at IS NULL;
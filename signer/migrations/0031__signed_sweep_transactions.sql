@@ -0,0 +1,15 @@
+-- Stores the sweep transactions that were fully signed by the signers.
+-- They are persisted before they are broadcast, so that a coordinator
+-- that stops before broadcasting them can broadcast them again after it
+-- restarts. Entries are deleted once they no longer spend an unspent
+-- signers' UTXO, either because they were confirmed or because they
+-- were replaced.
+CREATE TABLE sbtc_signer.signed_sweep_transactions (
+    -- The ID of the transaction.
+    txid BYTEA PRIMARY KEY,
+    -- The consensus encoded signed transaction.
+    tx BYTEA NOT NULL,
+    -- The bitcoin chain tip when the transaction was signed.
+    bitcoin_chain_tip BYTEA NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,19 @@
+-- Stores broadcast sweep transactions where the fee or virtual size
+-- reported by bitcoin-core differed from the values that the signers
+-- assessed for the transaction when they validated it.
+CREATE TABLE sbtc_signer.sweep_fee_discrepancies (
+    -- The ID of the sweep transaction.
+    txid BYTEA PRIMARY KEY,
+    -- The fee that the signers assessed for the transaction.
+    assessed_fee BIGINT NOT NULL,
+    -- The fee of the transaction as reported by bitcoin-core.
+    actual_fee BIGINT NOT NULL,
+    -- The virtual size that the signers assessed for the transaction.
+    assessed_vsize BIGINT NOT NULL,
+    -- The virtual size of the transaction as reported by bitcoin-core.
+    actual_vsize BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX ix_sweep_fee_discrepancies_created_at
+    ON sbtc_signer.sweep_fee_discrepancies(created_at);
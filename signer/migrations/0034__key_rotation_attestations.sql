@@ -0,0 +1,18 @@
+-- Stores the signed attestations that signers make to the outcome of a
+-- DKG round before the new aggregate key is rotated into the
+-- sbtc-registry contract.
+CREATE TABLE sbtc_signer.key_rotation_attestations (
+    -- The aggregate key that the signer attests to.
+    aggregate_key BYTEA NOT NULL,
+    -- The public key of the signer that made the attestation.
+    signer_public_key BYTEA NOT NULL,
+    -- The SHA-256 hash of the sorted, compressed public keys of the new
+    -- signer set.
+    signer_set_hash BYTEA NOT NULL,
+    -- The number of signatures required for the new multi-sig wallet.
+    signatures_required INTEGER NOT NULL,
+    -- The compact ECDSA signature of the signer over the attestation.
+    signature BYTEA NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (aggregate_key, signer_public_key)
+);
@@ -0,0 +1,20 @@
+-- Stores the progress of the sweep transaction package that the
+-- coordinator constructed in a tenure. A coordinator that stops after
+-- broadcasting some, but not all, of the transactions in the package
+-- uses it to continue with the remaining transactions after it restarts,
+-- instead of leaving their requests for the next tenure.
+CREATE TABLE sbtc_signer.sweep_packages (
+    -- The bitcoin chain tip when the package was constructed.
+    bitcoin_chain_tip BYTEA PRIMARY KEY,
+    -- The protobuf encoded pre-sign request that was sent to the signers
+    -- for the package.
+    presign_request BYTEA NOT NULL,
+    -- The total fees and the virtual size of the transactions in the
+    -- mempool that the first transaction in the package replaces, if any.
+    last_fees_total BIGINT,
+    last_fees_vsize BIGINT,
+    -- The IDs of the transactions in the package that have been
+    -- broadcast, in package order.
+    broadcast_txids BYTEA[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,7 @@
+-- Record why a signer rejected a withdrawal request, such as its
+-- blocklist client not accepting the recipient address. The reason is
+-- only known for the signer's own decisions, since it is not shared with
+-- the other signers, so the column is NULL for decisions received from
+-- them and for accepted requests.
+ALTER TABLE sbtc_signer.withdrawal_signers
+    ADD COLUMN rejection_reason TEXT;
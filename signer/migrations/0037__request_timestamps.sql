@@ -0,0 +1,42 @@
+-- Records when each deposit and withdrawal request reached each stage of
+-- its lifecycle, as seen by this signer. These are used for reporting on
+-- the latency of request processing, and for estimating how long it will
+-- take for pending requests to be fulfilled.
+--
+-- Each timestamp is set the first time that the signer observes the
+-- stage, and is never overwritten afterwards.
+CREATE TABLE sbtc_signer.deposit_request_timestamps (
+    txid BYTEA NOT NULL,
+    output_index INTEGER NOT NULL,
+    -- The bitcoin block that confirmed the deposit transaction, which is
+    -- the first block in which the signer could have seen the request.
+    first_seen_block BYTEA,
+    -- When the signer first stored the request.
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- When the signer made its own decision on the request.
+    decided_at TIMESTAMPTZ,
+    -- When the signer observed a sweep transaction that swept the deposit.
+    swept_at TIMESTAMPTZ,
+    -- When the signer observed the completed-deposit event on stacks.
+    completed_at TIMESTAMPTZ,
+    PRIMARY KEY (txid, output_index)
+);
+
+-- Withdrawal requests are keyed by their request ID alone, since that is
+-- all that the sweep transactions and the withdrawal-accept and
+-- withdrawal-reject events tell us about the request.
+CREATE TABLE sbtc_signer.withdrawal_request_timestamps (
+    request_id BIGINT PRIMARY KEY,
+    -- The signer's bitcoin chain tip when it first saw the request.
+    first_seen_block BYTEA,
+    -- When the signer first stored the request.
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- When the signer made its own decision on the request.
+    decided_at TIMESTAMPTZ,
+    -- When the signer observed a sweep transaction that fulfilled the
+    -- withdrawal.
+    swept_at TIMESTAMPTZ,
+    -- When the signer observed the withdrawal-accept or withdrawal-reject
+    -- event on stacks.
+    completed_at TIMESTAMPTZ
+);
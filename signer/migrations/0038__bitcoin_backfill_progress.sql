@@ -0,0 +1,17 @@
+-- Stores the progress of the block observer in writing the bitcoin blocks
+-- between the blocks in the database and a new chain tip. There is at
+-- most one row. A block observer that stops partway through uses it to
+-- report the progress of the whole backfill, rather than only the blocks
+-- that remain, after it restarts.
+CREATE TABLE sbtc_signer.bitcoin_backfill_progress (
+    -- The chain tip that the block observer is catching up to.
+    target_block_hash BYTEA PRIMARY KEY,
+    target_block_height BIGINT NOT NULL,
+    -- The height of the first block that was written in the backfill.
+    start_height BIGINT NOT NULL,
+    -- The height of the last block that was written to the database.
+    processed_height BIGINT NOT NULL,
+    -- When the backfill started, which may be before a restart.
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,14 @@
+-- Stores the tenures that a deputy coordinator took over from a
+-- coordinator that went silent. A row is a lock on the tenure of its
+-- bitcoin chain tip: a deputy only takes over a tenure if it can insert
+-- the row, so it announces the takeover and resumes the sweeps at most
+-- once, even if it restarts.
+CREATE TABLE sbtc_signer.coordinator_takeovers (
+    -- The bitcoin chain tip of the tenure that was taken over.
+    bitcoin_chain_tip BYTEA PRIMARY KEY,
+    -- The public key of the coordinator that went silent.
+    coordinator_public_key BYTEA NOT NULL,
+    -- The public key of the deputy coordinator that took over.
+    deputy_public_key BYTEA NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,15 @@
+-- Stores the most recent signed address record that each signer gossiped
+-- about itself. The records outlive restarts, so a signer can reconnect
+-- to the other signers at their announced addresses even if the addresses
+-- changed since the seed peers were configured.
+CREATE TABLE sbtc_signer.p2p_peer_address_records (
+    -- The public key of the signer that announced the addresses.
+    public_key BYTEA PRIMARY KEY,
+    -- The peer ID derived from the public key, in base58.
+    peer_id TEXT NOT NULL,
+    -- The multiaddrs that the signer announced that it can be reached at.
+    addresses TEXT[] NOT NULL,
+    -- The time at which the announcement expires.
+    expires_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
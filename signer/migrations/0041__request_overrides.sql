@@ -0,0 +1,40 @@
+-- Stores the overrides that operators place on specific deposit and
+-- withdrawal requests, forcing this signer to accept or reject them
+-- regardless of its decision policies. Overrides are never deleted, a
+-- revoked override has its revoked_at column set instead, so the table
+-- doubles as an audit log of every override that was ever placed.
+CREATE TABLE sbtc_signer.request_overrides (
+    id BIGSERIAL PRIMARY KEY,
+    -- The transaction ID and output index of the deposit request that
+    -- the override applies to, if it applies to a deposit request.
+    deposit_txid BYTEA,
+    deposit_output_index INTEGER,
+    -- The ID of the withdrawal request that the override applies to, if
+    -- it applies to a withdrawal request.
+    withdrawal_request_id BIGINT,
+    -- Either 'force_accept' or 'force_reject'.
+    directive TEXT NOT NULL,
+    -- Why the operator placed the override.
+    reason TEXT NOT NULL,
+    -- When the override stops applying, if ever.
+    expires_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- When an operator revoked the override, if they did.
+    revoked_at TIMESTAMPTZ,
+    CHECK (
+        (deposit_txid IS NOT NULL
+            AND deposit_output_index IS NOT NULL
+            AND withdrawal_request_id IS NULL)
+        OR (deposit_txid IS NULL
+            AND deposit_output_index IS NULL
+            AND withdrawal_request_id IS NOT NULL)
+    )
+);
+
+CREATE INDEX ix_request_overrides_deposit
+    ON sbtc_signer.request_overrides (deposit_txid, deposit_output_index)
+    WHERE deposit_txid IS NOT NULL;
+
+CREATE INDEX ix_request_overrides_withdrawal
+    ON sbtc_signer.request_overrides (withdrawal_request_id)
+    WHERE withdrawal_request_id IS NOT NULL;
@@ -0,0 +1,11 @@
+-- The outcome of each check done when validating a deposit or withdrawal
+-- request, as a comma separated list of `check=outcome` pairs, such as
+-- `confirmation=pass,fee=transient_fail`. The outcome of a failed check
+-- says whether it may pass later on, so that we can tell apart requests
+-- worth retrying from those that should be rejected outright. Rows
+-- written before this column was added have no checks recorded.
+ALTER TABLE sbtc_signer.bitcoin_tx_sighashes
+    ADD COLUMN validation_checks TEXT NOT NULL DEFAULT '';
+
+ALTER TABLE sbtc_signer.bitcoin_withdrawals_outputs
+    ADD COLUMN validation_checks TEXT NOT NULL DEFAULT '';
@@ -0,0 +1,8 @@
+-- The fee paid by the transaction that funded a deposit request, in sats,
+-- and the virtual size of that transaction. Together they give the fee
+-- rate the depositor paid, which tells us how urgently they treated the
+-- deposit. These are NULL for deposit requests recorded before we started
+-- capturing them.
+ALTER TABLE sbtc_signer.deposit_requests
+    ADD COLUMN funding_fees_total BIGINT,
+    ADD COLUMN funding_fees_vsize BIGINT;
@@ -0,0 +1,50 @@
+-- Stores the contract calls that the coordinator submitted to the stacks
+-- node for a deposit or withdrawal request and that have been neither
+-- confirmed nor rejected yet. The coordinator consults this table before
+-- constructing a new contract call for a request so that overlapping
+-- tenures do not broadcast duplicate contract calls for it. There is at
+-- most one row for each request.
+CREATE TABLE sbtc_signer.inflight_contract_calls (
+    -- The transaction ID and output index of the deposit request that
+    -- the contract call completes, if it is a complete-deposit call.
+    deposit_txid BYTEA,
+    deposit_output_index INTEGER,
+    -- The qualified ID of the withdrawal request that the contract call
+    -- accepts or rejects, if it is a withdrawal contract call. The
+    -- request ID alone does not identify a request, since it can be
+    -- reused on a stacks fork.
+    withdrawal_request_id BIGINT,
+    withdrawal_txid BYTEA,
+    withdrawal_block_hash BYTEA,
+    -- The ID of the stacks transaction with the contract call.
+    txid BYTEA NOT NULL,
+    -- The height and hash of the bitcoin chain tip when the contract call
+    -- was submitted.
+    bitcoin_block_height BIGINT NOT NULL,
+    bitcoin_block_hash BYTEA NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (
+        (deposit_txid IS NOT NULL
+            AND deposit_output_index IS NOT NULL
+            AND withdrawal_request_id IS NULL
+            AND withdrawal_txid IS NULL
+            AND withdrawal_block_hash IS NULL)
+        OR (deposit_txid IS NULL
+            AND deposit_output_index IS NULL
+            AND withdrawal_request_id IS NOT NULL
+            AND withdrawal_txid IS NOT NULL
+            AND withdrawal_block_hash IS NOT NULL)
+    )
+);
+
+CREATE UNIQUE INDEX uix_inflight_contract_calls_deposit
+    ON sbtc_signer.inflight_contract_calls (deposit_txid, deposit_output_index)
+    WHERE deposit_txid IS NOT NULL;
+
+CREATE UNIQUE INDEX uix_inflight_contract_calls_withdrawal
+    ON sbtc_signer.inflight_contract_calls (
+        withdrawal_request_id,
+        withdrawal_txid,
+        withdrawal_block_hash
+    )
+    WHERE withdrawal_request_id IS NOT NULL;
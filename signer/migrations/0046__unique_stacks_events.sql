@@ -0,0 +1,34 @@
+-- The stacks node may deliver the same sbtc-registry event more than
+-- once, such as when it replays events after a restart. The event tables
+-- used to record each delivery as a new row, so we remove the duplicates
+-- and add unique indexes so that writing an event is idempotent.
+DELETE FROM sbtc_signer.completed_deposit_events AS a
+USING sbtc_signer.completed_deposit_events AS b
+WHERE a.id > b.id
+  AND a.txid = b.txid
+  AND a.block_hash = b.block_hash
+  AND a.bitcoin_txid = b.bitcoin_txid
+  AND a.output_index = b.output_index;
+
+DELETE FROM sbtc_signer.withdrawal_accept_events AS a
+USING sbtc_signer.withdrawal_accept_events AS b
+WHERE a.id > b.id
+  AND a.txid = b.txid
+  AND a.block_hash = b.block_hash
+  AND a.request_id = b.request_id;
+
+DELETE FROM sbtc_signer.withdrawal_reject_events AS a
+USING sbtc_signer.withdrawal_reject_events AS b
+WHERE a.id > b.id
+  AND a.txid = b.txid
+  AND a.block_hash = b.block_hash
+  AND a.request_id = b.request_id;
+
+CREATE UNIQUE INDEX uix_completed_deposit_events
+    ON sbtc_signer.completed_deposit_events (txid, block_hash, bitcoin_txid, output_index);
+
+CREATE UNIQUE INDEX uix_withdrawal_accept_events
+    ON sbtc_signer.withdrawal_accept_events (txid, block_hash, request_id);
+
+CREATE UNIQUE INDEX uix_withdrawal_reject_events
+    ON sbtc_signer.withdrawal_reject_events (txid, block_hash, request_id);
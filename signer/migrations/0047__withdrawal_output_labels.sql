@@ -0,0 +1,16 @@
+-- Human-readable labels for known withdrawal scriptPubKeys, such as the
+-- hot wallets of exchanges or custodians. The signer replaces the
+-- contents of this table with the labels in its configuration on startup.
+CREATE TABLE sbtc_signer.withdrawal_output_labels (
+    -- The scriptPubKey that the label applies to.
+    script_pubkey BYTEA PRIMARY KEY,
+    -- The label given to withdrawal outputs locked by the scriptPubKey.
+    label TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- The label of the scriptPubKey of each withdrawal output at the time
+-- that it was written, or the time that the labels were last updated.
+-- This is only used for reporting.
+ALTER TABLE sbtc_signer.bitcoin_withdrawal_tx_outputs
+    ADD COLUMN label TEXT;
@@ -0,0 +1,16 @@
+-- WSTS signer state machines that the transaction signer evicted from its
+-- in-memory cache while their round was still in progress. A state
+-- machine is removed from this table when it is restored into the cache,
+-- and entries that were never restored are pruned once the bitcoin chain
+-- has moved on.
+CREATE TABLE sbtc_signer.spilled_state_machines (
+    -- The identifier of the state machine.
+    state_machine_id TEXT PRIMARY KEY,
+    -- The state of the state machine, encrypted with the signer's private
+    -- key.
+    encrypted_state BYTEA NOT NULL,
+    -- The height of the bitcoin chain tip when the state machine was
+    -- evicted.
+    bitcoin_block_height BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
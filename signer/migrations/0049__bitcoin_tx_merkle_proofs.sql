@@ -0,0 +1,28 @@
+-- The consensus encoded headers of the bitcoin blocks that we have
+-- processed, so that header chains can be served from storage.
+CREATE TABLE sbtc_signer.bitcoin_block_headers (
+    -- The hash of the block.
+    block_hash BYTEA PRIMARY KEY,
+    -- The consensus encoded header of the block.
+    header BYTEA NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    FOREIGN KEY (block_hash) REFERENCES sbtc_signer.bitcoin_blocks(block_hash) ON DELETE CASCADE
+);
+
+-- Proofs that sBTC related bitcoin transactions were included in the
+-- bitcoin blocks that confirmed them. Each proof is a consensus encoded
+-- merkle block whose partial merkle tree matches only the one
+-- transaction, which keeps it small regardless of the size of the block.
+CREATE TABLE sbtc_signer.bitcoin_tx_merkle_proofs (
+    -- The ID of the transaction.
+    txid BYTEA NOT NULL,
+    -- The hash of the block that included the transaction.
+    block_hash BYTEA NOT NULL,
+    -- The height of the block that included the transaction.
+    block_height BIGINT NOT NULL,
+    -- The consensus encoded merkle block.
+    merkle_block BYTEA NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (txid, block_hash),
+    FOREIGN KEY (block_hash) REFERENCES sbtc_signer.bitcoin_blocks(block_hash) ON DELETE CASCADE
+);
@@ -0,0 +1,16 @@
+-- Groups the stacks blocks that we know about by the Nakamoto tenure
+-- that produced them. Every tenure starts with a sortition in a bitcoin
+-- block, and all stacks blocks produced during the tenure, including
+-- those produced after a tenure extension, are anchored to that bitcoin
+-- block. So we use the hash of the anchor block as the tenure ID.
+CREATE VIEW sbtc_signer.stacks_tenures AS
+SELECT
+    stacks_blocks.bitcoin_anchor AS tenure_id
+  , bitcoin_blocks.block_height AS anchor_height
+  , COUNT(*) AS block_count
+  , MIN(stacks_blocks.block_height) AS first_block_height
+  , MAX(stacks_blocks.block_height) AS last_block_height
+FROM sbtc_signer.stacks_blocks AS stacks_blocks
+JOIN sbtc_signer.bitcoin_blocks AS bitcoin_blocks
+  ON bitcoin_blocks.block_hash = stacks_blocks.bitcoin_anchor
+GROUP BY stacks_blocks.bitcoin_anchor, bitcoin_blocks.block_height;
@@ -0,0 +1,30 @@
+-- The stacks blocks whose transactions we have scanned for contract calls
+-- to the sBTC contracts that were signed by a multi-sig wallet.
+CREATE TABLE sbtc_signer.scanned_stacks_blocks (
+    -- The hash of the stacks block.
+    block_hash BYTEA PRIMARY KEY,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    FOREIGN KEY (block_hash) REFERENCES sbtc_signer.stacks_blocks(block_hash) ON DELETE CASCADE
+);
+
+-- Contract calls to the sBTC contracts in the scanned stacks blocks that
+-- were signed by an order independent multi-sig wallet. The signers'
+-- coordinator stops collecting signatures once it has enough of them, so
+-- the public keys of the wallet that did not sign a transaction are the
+-- keys of the signers that were slowest to respond to the signing
+-- request. The coordinator election uses them to deprioritize signers
+-- that are chronically slow.
+CREATE TABLE sbtc_signer.multisig_stacks_transactions (
+    -- The ID of the stacks transaction.
+    txid BYTEA NOT NULL,
+    -- The hash of the stacks block that includes the transaction.
+    block_hash BYTEA NOT NULL,
+    -- The hash160 of the multi-sig spending condition, which is the hash
+    -- in the stacks address of the wallet.
+    signer_hash BYTEA NOT NULL,
+    -- The public keys of the wallet that did not sign the transaction.
+    non_signers BYTEA[] NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (txid, block_hash),
+    FOREIGN KEY (block_hash) REFERENCES sbtc_signer.stacks_blocks(block_hash) ON DELETE CASCADE
+);
@@ -0,0 +1,6 @@
+-- The amount locked in the signers' UTXO spent by the sweep transaction,
+-- as recorded when it was created by the previous sweep transaction. The
+-- sweeps in a block are chained, so this must equal the signers' input.
+-- This is NULL when we do not know of the signers' UTXO being spent.
+ALTER TABLE sbtc_signer.signer_utxo_violations
+    ADD COLUMN previous_signer_output BIGINT;
@@ -0,0 +1,316 @@
+//! The current state of a deposit request, as recorded in the signer's
+//! database, for answering "where is my deposit?".
+//!
+//! The [`DepositStatusQuery`] reads everything that the signer knows
+//! about a single deposit within one read-only transaction, so the parts
+//! of the report always agree with each other: how deeply the deposit
+//! transaction is confirmed on the canonical bitcoin blockchain, how the
+//! signers voted, whether a sweep transaction spent the deposit, and
+//! whether the `complete-deposit` contract call has been observed on
+//! stacks.
+
+use bitcoin::OutPoint;
+use serde::Deserialize;
+use serde::Serialize;
+use sqlx::PgConnection;
+
+use crate::error::Error;
+use crate::storage::model::BitcoinBlockHash;
+use crate::storage::model::BitcoinTxId;
+use crate::storage::postgres::PgStore;
+
+/// How far along a deposit request is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DepositStage {
+    /// The signer has a record of the deposit request, but the deposit
+    /// transaction is not confirmed on the canonical bitcoin blockchain.
+    Unconfirmed,
+    /// The deposit transaction is confirmed, but the deposit has not been
+    /// swept.
+    Confirmed,
+    /// A sweep transaction spending the deposit is confirmed, but the
+    /// signer has not observed the `complete-deposit` contract call.
+    Swept,
+    /// The signer has observed the `complete-deposit` contract call that
+    /// minted the sBTC.
+    Completed,
+}
+
+/// The bitcoin block that confirmed a transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct BlockConfirmation {
+    /// The block hash of the block.
+    pub block_hash: BitcoinBlockHash,
+    /// The height of the block.
+    #[sqlx(try_from = "i64")]
+    pub block_height: u64,
+    /// The number of confirmations of the transaction, counting the
+    /// block itself, as of the canonical bitcoin chain tip.
+    #[sqlx(try_from = "i64")]
+    pub confirmations: u64,
+}
+
+/// The decision of one signer on the deposit request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct DepositVote {
+    /// The hex encoded public key of the signer.
+    pub signer_public_key: String,
+    /// Whether the signer accepted the deposit request.
+    pub can_accept: bool,
+    /// Whether the signer can sign for the deposited funds.
+    pub can_sign: bool,
+}
+
+/// The sweep transaction that spent the deposit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DepositSweep {
+    /// The transaction ID of the sweep transaction.
+    pub txid: String,
+    /// The block that confirmed the sweep transaction.
+    pub confirmation: BlockConfirmation,
+}
+
+/// The `complete-deposit` contract call that minted the sBTC.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct DepositCompletion {
+    /// The hex encoded ID of the stacks transaction.
+    pub stacks_txid: String,
+    /// The hex encoded ID of the stacks block that included the
+    /// transaction.
+    pub stacks_block_hash: String,
+    /// The height of the stacks block, if the signer has a record of it.
+    pub stacks_block_height: Option<i64>,
+    /// The amount of sBTC minted, in sats.
+    #[sqlx(try_from = "i64")]
+    pub amount_minted: u64,
+}
+
+/// Everything that the signer knows about a deposit request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DepositStatus {
+    /// The transaction ID of the deposit transaction.
+    pub txid: String,
+    /// The index of the deposit output in the deposit transaction.
+    pub output_index: u32,
+    /// How far along the deposit request is.
+    pub stage: DepositStage,
+    /// The amount locked in the deposit output, in sats.
+    pub amount: u64,
+    /// The maximum fee that may be charged to the deposit, in sats.
+    pub max_fee: u64,
+    /// The canonical bitcoin block that confirmed the deposit
+    /// transaction.
+    pub confirmation: Option<BlockConfirmation>,
+    /// The decisions of the signers that we have received, ordered by
+    /// public key.
+    pub votes: Vec<DepositVote>,
+    /// The confirmed sweep transaction that spent the deposit.
+    pub sweep: Option<DepositSweep>,
+    /// The `complete-deposit` contract call that minted the sBTC.
+    pub completion: Option<DepositCompletion>,
+    /// The fee that was charged to the deposit, in sats. This is the
+    /// difference between the deposited and minted amounts, so it is only
+    /// known once the deposit has been completed.
+    pub assessed_fee: Option<u64>,
+}
+
+impl DepositStatus {
+    /// The number of signers that accepted the deposit request.
+    pub fn accepted_votes(&self) -> usize {
+        self.votes.iter().filter(|vote| vote.can_accept).count()
+    }
+}
+
+/// The amounts of a deposit request, read from the database.
+#[derive(sqlx::FromRow)]
+struct DepositAmounts {
+    #[sqlx(try_from = "i64")]
+    amount: u64,
+    max_fee: [u8; 8],
+}
+
+/// A sweep transaction, read from the database.
+#[derive(sqlx::FromRow)]
+struct SweepRow {
+    txid: BitcoinTxId,
+    #[sqlx(flatten)]
+    confirmation: BlockConfirmation,
+}
+
+/// A query for the current state of a single deposit request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DepositStatusQuery {
+    /// The outpoint of the deposit.
+    pub outpoint: OutPoint,
+}
+
+impl DepositStatusQuery {
+    /// Create a query for the deposit with the given outpoint.
+    pub fn new(outpoint: OutPoint) -> Self {
+        Self { outpoint }
+    }
+
+    /// Read the state of the deposit within a single read-only
+    /// transaction. Returns `None` if the signer has no record of the
+    /// deposit request.
+    pub async fn execute(&self, db: &PgStore) -> Result<Option<DepositStatus>, Error> {
+        let mut tx = db
+            .pool()
+            .begin()
+            .await
+            .map_err(Error::SqlxBeginTransaction)?;
+
+        sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ, READ ONLY")
+            .execute(&mut *tx)
+            .await
+            .map_err(Error::SqlxQuery)?;
+
+        let status = self.read(&mut tx).await?;
+
+        tx.rollback()
+            .await
+            .map_err(Error::SqlxRollbackTransaction)?;
+
+        Ok(status)
+    }
+
+    /// Read the state of the deposit using the given connection.
+    pub async fn read(&self, conn: &mut PgConnection) -> Result<Option<DepositStatus>, Error> {
+        let txid = BitcoinTxId::from(self.outpoint.txid);
+        let output_index =
+            i32::try_from(self.outpoint.vout).map_err(Error::ConversionDatabaseInt)?;
+
+        let amounts = sqlx::query_as::<_, DepositAmounts>(
+            "SELECT
+                amount
+              , max_fee
+             FROM sbtc_signer.deposit_requests
+             WHERE txid = $1
+               AND output_index = $2",
+        )
+        .bind(txid)
+        .bind(output_index)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        let Some(DepositAmounts { amount, max_fee }) = amounts else {
+            return Ok(None);
+        };
+
+        let confirmation = sqlx::query_as::<_, BlockConfirmation>(
+            "WITH chain_tip AS (
+                SELECT MAX(block_height) AS block_height
+                FROM sbtc_signer.bitcoin_blocks
+                WHERE is_canonical = TRUE
+             )
+             SELECT
+                bb.block_hash
+              , bb.block_height
+              , chain_tip.block_height - bb.block_height + 1 AS confirmations
+             FROM sbtc_signer.bitcoin_transactions AS bt
+             JOIN sbtc_signer.bitcoin_blocks AS bb USING (block_hash)
+             CROSS JOIN chain_tip
+             WHERE bt.txid = $1
+               AND bb.is_canonical = TRUE
+             ORDER BY bb.block_height
+             LIMIT 1",
+        )
+        .bind(txid)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        let votes = sqlx::query_as::<_, DepositVote>(
+            "SELECT
+                encode(signer_pub_key, 'hex') AS signer_public_key
+              , can_accept
+              , can_sign
+             FROM sbtc_signer.deposit_signers
+             WHERE txid = $1
+               AND output_index = $2
+             ORDER BY signer_pub_key",
+        )
+        .bind(txid)
+        .bind(output_index)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        let sweep = sqlx::query_as::<_, SweepRow>(
+            "WITH chain_tip AS (
+                SELECT MAX(block_height) AS block_height
+                FROM sbtc_signer.bitcoin_blocks
+                WHERE is_canonical = TRUE
+             )
+             SELECT
+                bti.txid
+              , bb.block_hash
+              , bb.block_height
+              , chain_tip.block_height - bb.block_height + 1 AS confirmations
+             FROM sbtc_signer.bitcoin_tx_inputs AS bti
+             JOIN sbtc_signer.bitcoin_transactions AS bt ON bt.txid = bti.txid
+             JOIN sbtc_signer.bitcoin_blocks AS bb ON bb.block_hash = bt.block_hash
+             CROSS JOIN chain_tip
+             WHERE bti.prevout_txid = $1
+               AND bti.prevout_output_index = $2
+               AND bti.prevout_type = 'deposit'
+               AND bb.is_canonical = TRUE
+             ORDER BY bb.block_height
+             LIMIT 1",
+        )
+        .bind(txid)
+        .bind(output_index)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(Error::SqlxQuery)?
+        .map(|row| DepositSweep {
+            txid: row.txid.to_string(),
+            confirmation: row.confirmation,
+        });
+
+        let completion = sqlx::query_as::<_, DepositCompletion>(
+            "SELECT
+                encode(cde.txid, 'hex') AS stacks_txid
+              , encode(cde.block_hash, 'hex') AS stacks_block_hash
+              , sb.block_height AS stacks_block_height
+              , cde.amount AS amount_minted
+             FROM sbtc_signer.completed_deposit_events AS cde
+             LEFT JOIN sbtc_signer.stacks_blocks AS sb ON sb.block_hash = cde.block_hash
+             WHERE cde.bitcoin_txid = $1
+               AND cde.output_index = $2
+             ORDER BY cde.id DESC
+             LIMIT 1",
+        )
+        .bind(txid)
+        .bind(i64::from(output_index))
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        let stage = match (&confirmation, &sweep, &completion) {
+            (_, _, Some(_)) => DepositStage::Completed,
+            (_, Some(_), None) => DepositStage::Swept,
+            (Some(_), None, None) => DepositStage::Confirmed,
+            (None, None, None) => DepositStage::Unconfirmed,
+        };
+        let assessed_fee = completion
+            .as_ref()
+            .map(|completion| amount.saturating_sub(completion.amount_minted));
+
+        Ok(Some(DepositStatus {
+            txid: self.outpoint.txid.to_string(),
+            output_index: self.outpoint.vout,
+            stage,
+            amount,
+            // The max fee is stored as an 8-byte big-endian integer.
+            max_fee: u64::from_be_bytes(max_fee),
+            confirmation,
+            votes,
+            sweep,
+            completion,
+            assessed_fee,
+        }))
+    }
+}
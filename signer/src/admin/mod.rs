@@ -0,0 +1,5 @@
+//! Library APIs for administrative tasks that operators perform on a
+//! signer outside of its normal operation.
+
+pub mod deposit_status;
+pub mod snapshot;
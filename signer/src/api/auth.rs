@@ -0,0 +1,220 @@
+//! Authentication for the `/admin` and `/sweeps/{txid}/proof` endpoints.
+//!
+//! Requests to the admin endpoints must include an `Authorization: Bearer
+//! <token>` header with the token in `signer.event_observer.admin_token`.
+//! When no token, or an empty one, is configured every admin request is
+//! rejected, so the admin endpoints are never open by accident.
+//!
+//! Requests for sweep transaction proofs must include one of the tokens in
+//! `signer.event_observer.proof_api_tokens` the same way. The token tells
+//! us which client made the request, so that each client can be rate
+//! limited on its own.
+
+use axum::extract::FromRequestParts;
+use axum::http::StatusCode;
+use axum::http::header::AUTHORIZATION;
+use axum::http::request::Parts;
+
+use crate::context::Context;
+
+use super::ApiState;
+
+/// The scheme prefix of the `Authorization` header value.
+pub const BEARER_PREFIX: &str = "Bearer ";
+
+/// An extractor that rejects requests with a `401 Unauthorized` unless
+/// they carry the configured admin token, or if there is no configured
+/// admin token.
+#[derive(Debug, Clone, Copy)]
+pub struct AdminAuth;
+
+impl<C: Context> FromRequestParts<ApiState<C>> for AdminAuth {
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &ApiState<C>,
+    ) -> Result<Self, Self::Rejection> {
+        let Some(expected) = state
+            .ctx
+            .config()
+            .signer
+            .event_observer
+            .admin_token
+            .as_deref()
+            .filter(|token| !token.is_empty())
+        else {
+            tracing::warn!(uri = %parts.uri, "rejecting admin request; no admin token is configured");
+            return Err(StatusCode::UNAUTHORIZED);
+        };
+
+        let provided = parts
+            .headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix(BEARER_PREFIX));
+
+        match provided {
+            Some(token) if constant_time_eq(token.as_bytes(), expected.as_bytes()) => Ok(AdminAuth),
+            _ => Err(StatusCode::UNAUTHORIZED),
+        }
+    }
+}
+
+/// An extractor that rejects requests with a `401 Unauthorized` unless
+/// they carry one of the configured proof API tokens. It holds the index
+/// of the token in the configuration, which identifies the client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProofApiClient(pub usize);
+
+impl<C: Context> FromRequestParts<ApiState<C>> for ProofApiClient {
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &ApiState<C>,
+    ) -> Result<Self, Self::Rejection> {
+        let provided = parts
+            .headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix(BEARER_PREFIX))
+            .ok_or(StatusCode::UNAUTHORIZED)?;
+
+        // Every token is compared, so that the time it takes does not
+        // depend on which token matched.
+        let config = state.ctx.config();
+        config
+            .signer
+            .event_observer
+            .proof_api_tokens
+            .iter()
+            .enumerate()
+            .filter(|(_, token)| !token.is_empty())
+            .fold(None, |matched, (index, token)| {
+                let is_match = constant_time_eq(provided.as_bytes(), token.as_bytes());
+                matched.or(is_match.then_some(index))
+            })
+            .map(ProofApiClient)
+            .ok_or(StatusCode::UNAUTHORIZED)
+    }
+}
+
+/// Compare two byte strings in time that only depends on their lengths,
+/// so that the comparison does not leak how much of a token was correct.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::Request;
+
+    use super::*;
+    use crate::testing::context::*;
+
+    async fn extract(
+        admin_token: Option<&str>,
+        header: Option<&str>,
+    ) -> Result<AdminAuth, StatusCode> {
+        let ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .modify_settings(|settings| {
+                settings.signer.event_observer.admin_token = admin_token.map(ToString::to_string)
+            })
+            .build();
+
+        let mut request = Request::builder().uri("/admin/dead-letters");
+        if let Some(header) = header {
+            request = request.header(AUTHORIZATION, header);
+        }
+        let (mut parts, _) = request.body(()).unwrap().into_parts();
+
+        AdminAuth::from_request_parts(&mut parts, &ApiState { ctx }).await
+    }
+
+    #[tokio::test]
+    async fn admin_endpoints_are_closed_without_a_configured_token() {
+        let unauthorized = Err(StatusCode::UNAUTHORIZED);
+        assert_eq!(extract(None, None).await.map(|_| ()), unauthorized);
+        assert_eq!(
+            extract(None, Some("Bearer anything")).await.map(|_| ()),
+            unauthorized
+        );
+
+        // An empty token counts as no token at all.
+        let result = extract(Some(""), Some("Bearer ")).await;
+        assert_eq!(result.map(|_| ()), unauthorized);
+    }
+
+    async fn extract_proof_api_client(
+        tokens: &[&str],
+        header: Option<&str>,
+    ) -> Result<ProofApiClient, StatusCode> {
+        let ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .modify_settings(|settings| {
+                settings.signer.event_observer.proof_api_tokens =
+                    tokens.iter().map(ToString::to_string).collect()
+            })
+            .build();
+
+        let mut request = Request::builder().uri("/sweeps/00/proof");
+        if let Some(header) = header {
+            request = request.header(AUTHORIZATION, header);
+        }
+        let (mut parts, _) = request.body(()).unwrap().into_parts();
+
+        ProofApiClient::from_request_parts(&mut parts, &ApiState { ctx }).await
+    }
+
+    #[tokio::test]
+    async fn proof_api_clients_are_identified_by_their_token() {
+        let tokens = ["gateway-1", "", "gateway-2"];
+        let client = extract_proof_api_client(&tokens, Some("Bearer gateway-1")).await;
+        assert_eq!(client, Ok(ProofApiClient(0)));
+        let client = extract_proof_api_client(&tokens, Some("Bearer gateway-2")).await;
+        assert_eq!(client, Ok(ProofApiClient(2)));
+
+        let unauthorized = Err(StatusCode::UNAUTHORIZED);
+        assert_eq!(extract_proof_api_client(&tokens, None).await, unauthorized);
+        assert_eq!(
+            extract_proof_api_client(&tokens, Some("Bearer ")).await,
+            unauthorized
+        );
+        assert_eq!(
+            extract_proof_api_client(&tokens, Some("gateway-1")).await,
+            unauthorized
+        );
+        assert_eq!(
+            extract_proof_api_client(&[], Some("Bearer gateway-1")).await,
+            unauthorized
+        );
+    }
+
+    #[tokio::test]
+    async fn admin_endpoints_require_the_configured_token() {
+        let token = Some("s3cr3t");
+        assert!(extract(token, Some("Bearer s3cr3t")).await.is_ok());
+
+        let unauthorized = Err(StatusCode::UNAUTHORIZED);
+        assert_eq!(extract(token, None).await.map(|_| ()), unauthorized);
+        assert_eq!(
+            extract(token, Some("s3cr3t")).await.map(|_| ()),
+            unauthorized
+        );
+        assert_eq!(
+            extract(token, Some("Bearer s3cr3u")).await.map(|_| ()),
+            unauthorized
+        );
+        assert_eq!(
+            extract(token, Some("Bearer s3cr3")).await.map(|_| ()),
+            unauthorized
+        );
+    }
+}
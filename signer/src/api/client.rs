@@ -0,0 +1,302 @@
+//! A typed client for the signer's status and admin API.
+//!
+//! This is for dashboards and operational tooling that talk to a running
+//! signer, so that they share the request and response types with the
+//! server instead of reimplementing them.
+
+use std::borrow::Cow;
+use std::time::Duration;
+
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use url::Url;
+
+use crate::error::Error;
+use crate::transaction_coordinator::RotateKeysDryRunReport;
+
+use super::auth::BEARER_PREFIX;
+use super::dead_letters::{DeadLettersResponse, ReplayResponse};
+use super::descriptors::DescriptorsResponse;
+use super::health::ReadinessResponse;
+use super::info::InfoResponse;
+use super::status::StatusResponse;
+use super::sweep_tx_proofs::SweepTxProof;
+
+/// The default timeout for requests to the signer API.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The timeout for rotate-keys dry run requests, which wait for the
+/// verdicts of the other signers.
+const DRY_RUN_REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// A client for the status and admin endpoints of a signer.
+#[derive(Debug, Clone)]
+pub struct SignerApiClient {
+    /// The HTTP client used to make requests.
+    client: reqwest::Client,
+    /// The base URL of the signer API.
+    endpoint: Url,
+    /// The base URL of the signer's admin API, which is served on its own
+    /// listener.
+    admin_endpoint: Url,
+    /// The bearer token included in requests to the `/admin` endpoints.
+    admin_token: Option<String>,
+    /// The bearer token included in requests for sweep transaction
+    /// proofs.
+    proof_api_token: Option<String>,
+}
+
+impl SignerApiClient {
+    /// Create a new client for the signer API at the given URL.
+    pub fn new(endpoint: Url) -> Result<Self, Error> {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()?;
+
+        Ok(Self {
+            client,
+            admin_endpoint: endpoint.clone(),
+            endpoint,
+            admin_token: None,
+            proof_api_token: None,
+        })
+    }
+
+    /// Send requests for the `/admin` endpoints to the given URL. The
+    /// signer serves them on `signer.event_observer.admin_bind`, so this
+    /// is normally a loopback URL on the signer's host. By default they
+    /// are sent to the same URL as every other request.
+    pub fn with_admin_endpoint(mut self, admin_endpoint: Url) -> Self {
+        self.admin_endpoint = admin_endpoint;
+        self
+    }
+
+    /// Include the given token in requests to the `/admin` endpoints. It
+    /// must match the `signer.event_observer.admin_token` setting of the
+    /// signer.
+    pub fn with_admin_token(mut self, admin_token: impl Into<String>) -> Self {
+        self.admin_token = Some(admin_token.into());
+        self
+    }
+
+    /// Include the given token in requests for sweep transaction proofs.
+    /// It must be one of the `signer.event_observer.proof_api_tokens` of
+    /// the signer.
+    pub fn with_proof_api_token(mut self, proof_api_token: impl Into<String>) -> Self {
+        self.proof_api_token = Some(proof_api_token.into());
+        self
+    }
+
+    /// Return whether the signer API is up and responding to requests.
+    ///
+    /// Uses the `GET /` endpoint.
+    pub async fn is_healthy(&self) -> Result<bool, Error> {
+        let url = self.url("/")?;
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .map_err(Error::SignerApiRequest)?;
+
+        Ok(response.status() == StatusCode::OK)
+    }
+
+    /// Get the most recent errors of the signer's subsystems, and whether
+    /// any of them is degraded.
+    ///
+    /// Uses the `GET /` endpoint.
+    pub async fn status(&self) -> Result<StatusResponse, Error> {
+        let url = self.url("/")?;
+        self.send(self.client.get(url)).await
+    }
+
+    /// Get the outcome of each of the signer's readiness checks. The
+    /// signer responds with 503 Service Unavailable when it is not ready,
+    /// so this returns the checks for either status.
+    ///
+    /// Uses the `GET /readyz` endpoint.
+    pub async fn readiness(&self) -> Result<ReadinessResponse, Error> {
+        let url = self.url("/readyz")?;
+        self.client
+            .get(url)
+            .send()
+            .await
+            .map_err(Error::SignerApiRequest)?
+            .json()
+            .await
+            .map_err(Error::UnexpectedSignerApiResponse)
+    }
+
+    /// Get information about the state of the signer.
+    ///
+    /// Uses the `GET /info` endpoint.
+    pub async fn info(&self) -> Result<InfoResponse, Error> {
+        let url = self.url("/info")?;
+        self.send(self.client.get(url)).await
+    }
+
+    /// Get the output descriptors of the taproot outputs that lock, or
+    /// have locked, the signers' UTXO.
+    ///
+    /// Uses the `GET /descriptors` endpoint.
+    pub async fn descriptors(&self) -> Result<DescriptorsResponse, Error> {
+        let url = self.url("/descriptors")?;
+        self.send(self.client.get(url)).await
+    }
+
+    /// Get the sbtc-registry events that the signer could not parse and
+    /// has not replayed yet.
+    ///
+    /// Uses the `GET /admin/dead-letters` endpoint.
+    pub async fn dead_letters(&self) -> Result<DeadLettersResponse, Error> {
+        let url = self.admin_url("/admin/dead-letters")?;
+        self.send(self.admin(self.client.get(url))).await
+    }
+
+    /// Ask the signer to parse and process the unresolved dead-lettered
+    /// events again.
+    ///
+    /// Uses the `POST /admin/dead-letters/replay` endpoint.
+    pub async fn replay_dead_letters(&self) -> Result<ReplayResponse, Error> {
+        let url = self.admin_url("/admin/dead-letters/replay")?;
+        self.send(self.admin(self.client.post(url))).await
+    }
+
+    /// Ask the signers to validate the rotate-keys contract call for the
+    /// latest DKG shares, without signing or broadcasting it, and get
+    /// each signer's verdict.
+    ///
+    /// Uses the `POST /admin/rotate-keys/dry-run` endpoint.
+    pub async fn rotate_keys_dry_run(&self) -> Result<RotateKeysDryRunReport, Error> {
+        let url = self.admin_url("/admin/rotate-keys/dry-run")?;
+        let request = self.client.post(url).timeout(DRY_RUN_REQUEST_TIMEOUT);
+        self.send(self.admin(request)).await
+    }
+
+    /// Get a proof that the sweep transaction with the given ID was
+    /// confirmed on the signer's canonical bitcoin blockchain, or `None`
+    /// if the signer does not have one. The proof is checked before it is
+    /// returned, but the proof of work of its headers is left to the
+    /// caller.
+    ///
+    /// Uses the `GET /sweeps/{txid}/proof` endpoint.
+    pub async fn sweep_tx_proof(&self, txid: bitcoin::Txid) -> Result<Option<SweepTxProof>, Error> {
+        let path = format!("/sweeps/{txid}/proof");
+        let url = self
+            .endpoint
+            .join(&path)
+            .map_err(|err| Error::PathJoin(err, self.endpoint.clone(), Cow::Owned(path)))?;
+
+        let mut request = self.client.get(url);
+        if let Some(token) = &self.proof_api_token {
+            request = request.header(
+                reqwest::header::AUTHORIZATION,
+                format!("{BEARER_PREFIX}{token}"),
+            );
+        }
+
+        let response = request.send().await.map_err(Error::SignerApiRequest)?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        let proof: SweepTxProof = response
+            .error_for_status()
+            .map_err(Error::SignerApiResponse)?
+            .json()
+            .await
+            .map_err(Error::UnexpectedSignerApiResponse)?;
+
+        if proof.txid != txid || !proof.is_valid() {
+            return Err(Error::InvalidSweepTxProof(txid));
+        }
+        Ok(Some(proof))
+    }
+
+    /// Join the given path onto the endpoint of the signer API.
+    fn url(&self, path: &'static str) -> Result<Url, Error> {
+        self.endpoint
+            .join(path)
+            .map_err(|err| Error::PathJoin(err, self.endpoint.clone(), Cow::Borrowed(path)))
+    }
+
+    /// Join the given path onto the endpoint of the signer's admin API.
+    fn admin_url(&self, path: &'static str) -> Result<Url, Error> {
+        self.admin_endpoint
+            .join(path)
+            .map_err(|err| Error::PathJoin(err, self.admin_endpoint.clone(), Cow::Borrowed(path)))
+    }
+
+    /// Add the admin token, if any, to the request.
+    fn admin(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match &self.admin_token {
+            Some(token) => request.header(
+                reqwest::header::AUTHORIZATION,
+                format!("{BEARER_PREFIX}{token}"),
+            ),
+            None => request,
+        }
+    }
+
+    /// Send the request and deserialize the JSON body of the response.
+    async fn send<T: DeserializeOwned>(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<T, Error> {
+        request
+            .send()
+            .await
+            .map_err(Error::SignerApiRequest)?
+            .error_for_status()
+            .map_err(Error::SignerApiResponse)?
+            .json()
+            .await
+            .map_err(Error::UnexpectedSignerApiResponse)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn admin_requests_include_the_admin_token() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/admin/dead-letters/replay")
+            .match_header("authorization", "Bearer s3cr3t")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"replayed":2,"failed":1}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = SignerApiClient::new("http://127.0.0.1:1".parse().unwrap())
+            .unwrap()
+            .with_admin_endpoint(server.url().parse().unwrap())
+            .with_admin_token("s3cr3t");
+
+        let response = client.replay_dead_letters().await.unwrap();
+        assert_eq!(response, ReplayResponse { replayed: 2, failed: 1 });
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn unauthorized_admin_requests_are_errors() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/admin/dead-letters")
+            .with_status(401)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = SignerApiClient::new(server.url().parse().unwrap()).unwrap();
+
+        let error = client.dead_letters().await.unwrap_err();
+        assert!(matches!(error, Error::SignerApiResponse(_)));
+        mock.assert_async().await;
+    }
+}
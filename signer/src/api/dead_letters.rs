@@ -0,0 +1,286 @@
+//! Handlers for the `/admin/dead-letters` endpoints, which are for
+//! inspecting and replaying sbtc-registry events that the `/new_block`
+//! endpoint could not parse.
+
+use axum::{Json, extract::State, http::StatusCode};
+use clarity::vm::Value as ClarityValue;
+use sbtc::events::RegistryEvent;
+use sbtc::events::TxInfo;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    context::Context,
+    error::Error,
+    metrics::Metrics,
+    storage::{DbRead as _, DbWrite as _, model::StacksEventDeadLetter},
+};
+
+use super::ApiState;
+use super::auth::AdminAuth;
+use super::new_block::handle_registry_event;
+
+/// A dead-lettered sbtc-registry event.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct DeadLetterInfo {
+    /// The index block hash of the stacks block that contains the event.
+    pub block_hash: String,
+    /// The ID of the stacks transaction that emitted the event.
+    pub txid: String,
+    /// The position of the event within the events of the new block
+    /// webhook.
+    pub event_index: u32,
+    /// The hex encoded consensus serialization of the clarity value of the
+    /// event.
+    pub raw_value: String,
+    /// The error returned the last time that we tried to parse the event.
+    pub error: String,
+    /// The number of times that we tried to parse the event.
+    pub attempts: u32,
+    /// When the event was first dead-lettered.
+    pub created_at: String,
+}
+
+impl From<StacksEventDeadLetter> for DeadLetterInfo {
+    fn from(letter: StacksEventDeadLetter) -> Self {
+        Self {
+            block_hash: letter.block_hash.to_string(),
+            txid: letter.txid.to_string(),
+            event_index: letter.event_index,
+            raw_value: letter.raw_value,
+            error: letter.error,
+            attempts: letter.attempts,
+            created_at: letter.created_at.to_string(),
+        }
+    }
+}
+
+/// The response listing the dead-lettered events that have not been
+/// replayed yet.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeadLettersResponse {
+    /// The unresolved dead-lettered events, oldest first.
+    pub dead_letters: Vec<DeadLetterInfo>,
+}
+
+/// The outcome of replaying the unresolved dead-lettered events.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReplayResponse {
+    /// The number of events that were parsed and processed.
+    pub replayed: usize,
+    /// The number of events that still could not be processed.
+    pub failed: usize,
+}
+
+/// Handler for `GET /admin/dead-letters`.
+pub async fn dead_letters_handler<C: Context>(
+    _: AdminAuth,
+    state: State<ApiState<C>>,
+) -> Result<Json<DeadLettersResponse>, StatusCode> {
+    let dead_letters = state
+        .ctx
+        .get_storage()
+        .get_unresolved_stacks_event_dead_letters()
+        .await
+        .inspect_err(|error| tracing::warn!(%error, "could not fetch dead-lettered events"))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .into_iter()
+        .map(DeadLetterInfo::from)
+        .collect();
+
+    Ok(Json(DeadLettersResponse { dead_letters }))
+}
+
+/// Handler for `POST /admin/dead-letters/replay`. It tries to parse and
+/// process each unresolved dead-lettered event again, which is useful
+/// after the event parser has been fixed.
+pub async fn replay_dead_letters_handler<C: Context>(
+    _: AdminAuth,
+    state: State<ApiState<C>>,
+) -> Result<Json<ReplayResponse>, StatusCode> {
+    replay_dead_letters(&state.ctx)
+        .await
+        .inspect_err(|error| tracing::warn!(%error, "could not replay dead-lettered events"))
+        .map(Json)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+/// Replay all unresolved dead-lettered events. Events that are processed
+/// are marked as resolved, while events that still cannot be parsed have
+/// their error and number of attempts updated.
+async fn replay_dead_letters(ctx: &impl Context) -> Result<ReplayResponse, Error> {
+    let db = ctx.get_storage_mut();
+    let mut response = ReplayResponse::default();
+
+    for letter in db.get_unresolved_stacks_event_dead_letters().await? {
+        let result = replay_dead_letter(ctx, &letter).await;
+        let status = match result {
+            Ok(()) => {
+                db.resolve_stacks_event_dead_letter(
+                    &letter.block_hash,
+                    &letter.txid,
+                    letter.event_index,
+                )
+                .await?;
+                response.replayed += 1;
+                "success"
+            }
+            Err(error) => {
+                tracing::warn!(
+                    %error,
+                    block_hash = %letter.block_hash,
+                    txid = %letter.txid,
+                    event_index = %letter.event_index,
+                    "could not replay a dead-lettered event"
+                );
+                let letter = StacksEventDeadLetter {
+                    error: error.to_string(),
+                    ..letter
+                };
+                db.write_stacks_event_dead_letter(&letter).await?;
+                response.failed += 1;
+                "failure"
+            }
+        };
+
+        metrics::counter!(Metrics::StacksEventsReplayedTotal, "status" => status).increment(1);
+    }
+
+    Ok(response)
+}
+
+/// Parse and process a single dead-lettered event.
+async fn replay_dead_letter(
+    ctx: &impl Context,
+    letter: &StacksEventDeadLetter,
+) -> Result<(), Error> {
+    let value = ClarityValue::try_deserialize_hex_untyped(&letter.raw_value)
+        .map_err(Error::ClarityValueDeserialization)?;
+    let tx_info = TxInfo {
+        txid: letter.txid.into(),
+        block_id: letter.block_hash.into(),
+    };
+    let event = RegistryEvent::try_new(value, tx_info).map_err(Error::RegistryEvent)?;
+
+    handle_registry_event(ctx, event).await
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::Method;
+    use axum::http::Request;
+    use axum::http::header::AUTHORIZATION;
+    use fake::Fake as _;
+    use sbtc::webhooks::NewBlockEvent;
+    use tower::ServiceExt as _;
+
+    use super::*;
+    use crate::testing::context::*;
+
+    const ROTATE_KEYS_WEBHOOK: &str = include_str!("../../tests/fixtures/rotate-keys-event.json");
+
+    const ROTATE_KEYS_AND_INVALID_EVENT_WEBHOOK: &str =
+        include_str!("../../tests/fixtures/rotate-keys-and-invalid-event.json");
+
+    fn raw_values(webhook: &str) -> Vec<String> {
+        let new_block_event: NewBlockEvent = serde_json::from_str(webhook).unwrap();
+        new_block_event
+            .events
+            .into_iter()
+            .filter_map(|event| event.contract_event)
+            .map(|event| event.raw_value.serialize_to_hex().unwrap())
+            .collect()
+    }
+
+    fn dead_letter(raw_value: String) -> StacksEventDeadLetter {
+        StacksEventDeadLetter {
+            block_hash: fake::Faker.fake(),
+            txid: fake::Faker.fake(),
+            event_index: 0,
+            raw_value,
+            error: "could not parse".to_string(),
+            attempts: 1,
+            created_at: time::OffsetDateTime::now_utc().into(),
+            resolved_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn replay_resolves_parsable_events() {
+        let ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .build();
+        let db = ctx.get_storage_mut();
+
+        // The first event of this webhook cannot be parsed while the
+        // second one is a valid rotate-keys event.
+        let invalid = raw_values(ROTATE_KEYS_AND_INVALID_EVENT_WEBHOOK).remove(0);
+        let valid = raw_values(ROTATE_KEYS_WEBHOOK).remove(0);
+
+        let invalid_letter = dead_letter(invalid);
+        db.write_stacks_event_dead_letter(&invalid_letter)
+            .await
+            .unwrap();
+        db.write_stacks_event_dead_letter(&dead_letter(valid))
+            .await
+            .unwrap();
+
+        let state = State(ApiState { ctx: ctx.clone() });
+        let Json(listed) = dead_letters_handler(AdminAuth, state).await.unwrap();
+        assert_eq!(listed.dead_letters.len(), 2);
+
+        let state = State(ApiState { ctx: ctx.clone() });
+        let Json(response) = replay_dead_letters_handler(AdminAuth, state).await.unwrap();
+        assert_eq!(response, ReplayResponse { replayed: 1, failed: 1 });
+
+        // The valid event was processed and only the invalid one is left,
+        // with one more attempt recorded against it.
+        assert!(
+            !ctx.inner_storage()
+                .lock()
+                .await
+                .rotate_keys_transactions
+                .is_empty()
+        );
+
+        let letters = db.get_unresolved_stacks_event_dead_letters().await.unwrap();
+        assert_eq!(letters.len(), 1);
+        assert_eq!(letters[0].txid, invalid_letter.txid);
+        assert_eq!(letters[0].attempts, 2);
+    }
+
+    #[tokio::test]
+    async fn dead_letter_routes_require_the_admin_token() {
+        let ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .modify_settings(|settings| {
+                settings.signer.event_observer.admin_token = Some("s3cr3t".to_string())
+            })
+            .build();
+        let app = crate::api::get_admin_router().with_state(ApiState { ctx });
+
+        for (method, uri) in [
+            (Method::GET, "/admin/dead-letters"),
+            (Method::POST, "/admin/dead-letters/replay"),
+        ] {
+            let request = |token: Option<&str>| {
+                let mut request = Request::builder().uri(uri).method(method.clone());
+                if let Some(token) = token {
+                    request = request.header(AUTHORIZATION, format!("Bearer {token}"));
+                }
+                request.body(Body::empty()).unwrap()
+            };
+
+            let response = app.clone().oneshot(request(None)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+            let response = app.clone().oneshot(request(Some("wrong"))).await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+            let response = app.clone().oneshot(request(Some("s3cr3t"))).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+    }
+}
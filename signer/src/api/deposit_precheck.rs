@@ -0,0 +1,191 @@
+//! Handler for the `POST /admin/deposits/precheck` endpoint.
+//!
+//! The endpoint reveals blocklist verdicts, so it is only served on the
+//! admin listener and requires the admin token. Each deposit in a request
+//! may be screened by the blocklist client, so requests are also kept
+//! small and are rate limited across all clients.
+
+use axum::{Json, extract::State, http::StatusCode};
+use bitcoin::OutPoint;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    blocklist_client::BlocklistClient,
+    context::Context,
+    deposit_precheck::{DepositPrecheck, DepositVerdict, ProspectiveDeposit},
+};
+
+use super::ApiState;
+use super::auth::AdminAuth;
+
+/// The maximum number of deposits that can be checked in a single
+/// request.
+pub const MAX_PRECHECK_DEPOSITS: usize = 25;
+
+/// The body of a deposit pre-check request.
+#[derive(Debug, Deserialize)]
+pub struct DepositPrecheckRequest {
+    /// The deposits to check.
+    pub deposits: Vec<ProspectiveDeposit>,
+}
+
+/// The verdict for one of the deposits in a pre-check request.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct DepositPrecheckVerdict {
+    /// The outpoint of the deposit.
+    pub outpoint: OutPoint,
+    /// Whether the signers would accept the deposit, and if not, why.
+    pub verdict: DepositVerdict,
+}
+
+/// The response to a deposit pre-check request. The verdicts are in the
+/// same order as the deposits in the request.
+#[derive(Debug, Serialize)]
+pub struct DepositPrecheckResponse {
+    /// The verdict for each deposit.
+    pub verdicts: Vec<DepositPrecheckVerdict>,
+}
+
+/// Handler for the `/admin/deposits/precheck` endpoint. It returns whether the
+/// signers would accept each of the given deposits given the current sBTC
+/// limits, the blocklist and the deposit script policy. It responds with a
+/// `413 Payload Too Large` if the request has more than
+/// [`MAX_PRECHECK_DEPOSITS`] deposits, and with a `429 Too Many Requests`
+/// if the endpoint has exceeded its rate limit.
+pub async fn deposit_precheck_handler<C: Context>(
+    _: AdminAuth,
+    state: State<ApiState<C>>,
+    Json(body): Json<DepositPrecheckRequest>,
+) -> Result<Json<DepositPrecheckResponse>, StatusCode> {
+    if body.deposits.len() > MAX_PRECHECK_DEPOSITS {
+        return Err(StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    let config = state.ctx.config();
+    let max_per_minute = config.signer.event_observer.precheck_requests_per_minute;
+    if !state
+        .ctx
+        .state()
+        .check_precheck_request_rate(max_per_minute)
+    {
+        tracing::debug!("rate limiting deposit pre-check request");
+        return Err(StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    let blocklist_client = config.blocklist_client.as_ref().map(BlocklistClient::new);
+    let limits = state.ctx.state().get_current_limits();
+    let signers_public_key = state
+        .ctx
+        .state()
+        .registry_signer_set_info()
+        .map(|info| (&info.aggregate_key).into());
+
+    let precheck = DepositPrecheck {
+        limits: &limits,
+        blocklist_checker: blocklist_client.as_ref(),
+        network: config.signer.network.into(),
+        signers_public_key,
+    };
+
+    let verdicts = precheck
+        .can_accept_deposits(&body.deposits)
+        .await
+        .inspect_err(|error| tracing::warn!(%error, "could not pre-check deposits"))
+        .map_err(|_| StatusCode::BAD_GATEWAY)?;
+
+    let verdicts = body
+        .deposits
+        .iter()
+        .zip(verdicts)
+        .map(|(deposit, verdict)| DepositPrecheckVerdict {
+            outpoint: deposit.outpoint,
+            verdict,
+        })
+        .collect();
+
+    Ok(Json(DepositPrecheckResponse { verdicts }))
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::ScriptBuf;
+    use fake::Fake as _;
+    use rand::rngs::OsRng;
+
+    use crate::testing::{context::TestContext, dummy};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn invalid_scripts_are_reported_per_deposit() {
+        let ctx = TestContext::default_mocked();
+        let state = State(ApiState { ctx });
+
+        let deposit = ProspectiveDeposit {
+            outpoint: OutPoint::new(dummy::txid(&fake::Faker, &mut OsRng), 0),
+            amount: (1_000..100_000).fake_with_rng(&mut OsRng),
+            deposit_script: ScriptBuf::new(),
+            reclaim_script: ScriptBuf::new(),
+            sender_script_pub_keys: Vec::new(),
+        };
+        let body = DepositPrecheckRequest {
+            deposits: vec![deposit.clone()],
+        };
+
+        let Json(response) = deposit_precheck_handler(AdminAuth, state, Json(body))
+            .await
+            .unwrap();
+
+        let expected = DepositPrecheckVerdict {
+            outpoint: deposit.outpoint,
+            verdict: DepositVerdict::InvalidDepositScript,
+        };
+        assert_eq!(response.verdicts, vec![expected]);
+    }
+
+    #[tokio::test]
+    async fn too_many_deposits_are_rejected() {
+        let ctx = TestContext::default_mocked();
+        let state = State(ApiState { ctx });
+
+        let deposit = ProspectiveDeposit {
+            outpoint: OutPoint::null(),
+            amount: 0,
+            deposit_script: ScriptBuf::new(),
+            reclaim_script: ScriptBuf::new(),
+            sender_script_pub_keys: Vec::new(),
+        };
+        let deposits = vec![deposit; MAX_PRECHECK_DEPOSITS + 1];
+        let body = DepositPrecheckRequest { deposits };
+
+        let status = deposit_precheck_handler(AdminAuth, state, Json(body))
+            .await
+            .unwrap_err();
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn requests_over_the_rate_limit_are_rejected() {
+        let ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .modify_settings(|settings| {
+                settings.signer.event_observer.precheck_requests_per_minute = 2
+            })
+            .build();
+        let state = State(ApiState { ctx });
+
+        for _ in 0..2 {
+            let body = DepositPrecheckRequest { deposits: Vec::new() };
+            deposit_precheck_handler(AdminAuth, state.clone(), Json(body))
+                .await
+                .unwrap();
+        }
+
+        let body = DepositPrecheckRequest { deposits: Vec::new() };
+        let status = deposit_precheck_handler(AdminAuth, state, Json(body))
+            .await
+            .unwrap_err();
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+    }
+}
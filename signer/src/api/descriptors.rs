@@ -0,0 +1,191 @@
+//! Handler for the `/descriptors` endpoint, which exports the taproot
+//! outputs that lock, or have locked, the signers' UTXO as output
+//! descriptors.
+//!
+//! The descriptors can be imported into watch-only wallets, such as
+//! bitcoin-core's `importdescriptors` RPC or BDK, so that external
+//! accounting systems can track the peg wallet directly.
+
+use axum::{Json, extract::State, http::StatusCode};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bitcoin::descriptor::signers_descriptor,
+    context::Context,
+    keys::{PublicKey, SignerScriptPubKey as _},
+    storage::{
+        DbRead as _,
+        model::{AggregateKeyPeriod, BitcoinBlockHeight},
+    },
+};
+
+use super::ApiState;
+
+/// Whether an aggregate key locks the signers' UTXO.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DescriptorStatus {
+    /// The aggregate key locks the signers' current UTXO.
+    Active,
+    /// The aggregate key locked the signers' UTXO in the past.
+    Retired,
+    /// The aggregate key is from the latest DKG shares but has not locked
+    /// the signers' UTXO yet.
+    Pending,
+}
+
+/// The output descriptor of a taproot output used by the signers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignerDescriptorInfo {
+    /// The output descriptor, including its checksum.
+    pub descriptor: String,
+    /// The aggregate key whose x-only form is the internal key of the
+    /// taproot output.
+    pub aggregate_key: String,
+    /// The hex encoded scriptPubKey of the taproot output.
+    pub script_pubkey: String,
+    /// The address of the taproot output on the signer's network.
+    pub address: String,
+    /// Whether the aggregate key locks the signers' UTXO.
+    pub status: DescriptorStatus,
+    /// The height of the first bitcoin block with a signers' UTXO locked
+    /// by the aggregate key, if any.
+    pub start_height: Option<BitcoinBlockHeight>,
+    /// The height of the first bitcoin block with a signers' UTXO locked
+    /// by the next aggregate key, if any.
+    pub end_height: Option<BitcoinBlockHeight>,
+}
+
+/// The response from the `/descriptors` endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DescriptorsResponse {
+    /// The descriptors of the signers' taproot outputs, oldest first.
+    pub descriptors: Vec<SignerDescriptorInfo>,
+}
+
+impl SignerDescriptorInfo {
+    fn new(aggregate_key: PublicKey, network: bitcoin::Network, status: DescriptorStatus) -> Self {
+        let script_pubkey = aggregate_key.signers_script_pubkey();
+        let address = bitcoin::Address::from_script(&script_pubkey, network)
+            .expect("taproot outputs always have an address");
+
+        Self {
+            descriptor: signers_descriptor(aggregate_key),
+            aggregate_key: aggregate_key.to_string(),
+            script_pubkey: script_pubkey.to_hex_string(),
+            address: address.to_string(),
+            status,
+            start_height: None,
+            end_height: None,
+        }
+    }
+
+    fn from_period(period: &AggregateKeyPeriod, network: bitcoin::Network) -> Self {
+        let status = if period.end_height.is_some() {
+            DescriptorStatus::Retired
+        } else {
+            DescriptorStatus::Active
+        };
+
+        Self {
+            start_height: Some(period.start_height),
+            end_height: period.end_height,
+            ..Self::new(period.aggregate_key, network, status)
+        }
+    }
+}
+
+/// Handler for `GET /descriptors`. It returns the descriptors of the
+/// aggregate keys that have locked the signers' UTXO on the canonical
+/// bitcoin blockchain, followed by the descriptor of the aggregate key
+/// from the latest DKG shares if it has not locked the signers' UTXO yet.
+pub async fn descriptors_handler<C: Context>(
+    state: State<ApiState<C>>,
+) -> Result<Json<DescriptorsResponse>, StatusCode> {
+    let ctx = &state.ctx;
+    let db = ctx.get_storage();
+    let network = bitcoin::Network::from(ctx.config().signer.network);
+
+    let history = match ctx.state().bitcoin_chain_tip() {
+        Some(chain_tip) => db
+            .get_aggregate_key_history(&chain_tip.block_hash)
+            .await
+            .inspect_err(|error| {
+                tracing::error!(%error, "error reading the aggregate key history");
+            })
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+        None => Vec::new(),
+    };
+
+    let latest_shares = db
+        .get_latest_non_failed_dkg_shares()
+        .await
+        .inspect_err(|error| tracing::error!(%error, "error reading the latest DKG shares"))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let mut descriptors: Vec<SignerDescriptorInfo> = history
+        .iter()
+        .map(|period| SignerDescriptorInfo::from_period(period, network))
+        .collect();
+
+    if let Some(shares) = latest_shares
+        && !history
+            .iter()
+            .any(|period| period.aggregate_key == shares.aggregate_key)
+    {
+        let pending =
+            SignerDescriptorInfo::new(shares.aggregate_key, network, DescriptorStatus::Pending);
+        descriptors.push(pending);
+    }
+
+    Ok(Json(DescriptorsResponse { descriptors }))
+}
+
+#[cfg(test)]
+mod tests {
+    use fake::{Fake as _, Faker};
+
+    use crate::storage::DbWrite as _;
+    use crate::storage::model::{DkgSharesStatus, EncryptedDkgShares};
+    use crate::testing::context::*;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn latest_shares_are_pending_until_they_lock_a_utxo() {
+        let ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .build();
+
+        let state = State(ApiState { ctx: ctx.clone() });
+        let Json(response) = descriptors_handler(state.clone()).await.unwrap();
+        assert!(response.descriptors.is_empty());
+
+        let shares = EncryptedDkgShares {
+            dkg_shares_status: DkgSharesStatus::Verified,
+            ..Faker.fake()
+        };
+        ctx.get_storage_mut()
+            .write_encrypted_dkg_shares(&shares)
+            .await
+            .unwrap();
+
+        let Json(response) = descriptors_handler(state).await.unwrap();
+        let [descriptor] = response.descriptors.as_slice() else {
+            panic!("expected a single descriptor");
+        };
+
+        assert_eq!(descriptor.status, DescriptorStatus::Pending);
+        assert_eq!(descriptor.aggregate_key, shares.aggregate_key.to_string());
+        assert_eq!(
+            descriptor.descriptor,
+            signers_descriptor(shares.aggregate_key)
+        );
+        assert_eq!(
+            descriptor.script_pubkey,
+            shares.aggregate_key.signers_script_pubkey().to_hex_string()
+        );
+        assert!(descriptor.start_height.is_none());
+    }
+}
@@ -0,0 +1,329 @@
+//! Handlers for the `/healthz` and `/readyz` endpoints.
+//!
+//! The `/healthz` endpoint reports whether the signer process is alive,
+//! and always responds with 200 OK if it responds at all. The `/readyz`
+//! endpoint reports whether the signer is able to do useful work: its
+//! database is reachable, its bitcoin and stacks nodes respond, it is
+//! connected to enough peers to reach the signing threshold, and its
+//! view of the chain tips is fresh. It responds with 503 Service
+//! Unavailable when any of these checks fail.
+
+use axum::{Json, extract::State, http::StatusCode};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bitcoin::BitcoinInteract as _, context::Context, stacks::api::StacksInteract as _,
+    storage::DbRead as _,
+};
+
+use super::ApiState;
+
+/// The response from the `/healthz` endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LivenessResponse {
+    /// Whether the signer process is alive. This is always true.
+    pub alive: bool,
+    /// The git revision that the binary was built from.
+    pub git_revision: String,
+}
+
+/// The outcome of a single readiness check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReadinessCheck {
+    /// Whether the check passed.
+    pub ok: bool,
+    /// A human readable description of the outcome of the check.
+    pub detail: String,
+}
+
+impl ReadinessCheck {
+    fn pass(detail: impl Into<String>) -> Self {
+        Self {
+            ok: true,
+            detail: detail.into(),
+        }
+    }
+
+    fn fail(detail: impl Into<String>) -> Self {
+        Self {
+            ok: false,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// The outcome of each of the readiness checks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReadinessChecks {
+    /// Whether the database is reachable.
+    pub storage: ReadinessCheck,
+    /// Whether the bitcoin node responds.
+    pub bitcoin: ReadinessCheck,
+    /// Whether the stacks node responds.
+    pub stacks: ReadinessCheck,
+    /// Whether the signer is connected to enough peers to reach the
+    /// signing threshold.
+    pub p2p: ReadinessCheck,
+    /// Whether the signer's bitcoin chain tip is close enough to the
+    /// chain tips of the bitcoin and stacks nodes.
+    pub chain_tips: ReadinessCheck,
+}
+
+impl ReadinessChecks {
+    fn all_ok(&self) -> bool {
+        [
+            &self.storage,
+            &self.bitcoin,
+            &self.stacks,
+            &self.p2p,
+            &self.chain_tips,
+        ]
+        .iter()
+        .all(|check| check.ok)
+    }
+}
+
+/// The response from the `/readyz` endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReadinessResponse {
+    /// Whether all of the readiness checks passed.
+    pub ready: bool,
+    /// The outcome of each of the readiness checks.
+    pub checks: ReadinessChecks,
+}
+
+/// A handler that responds with 200 OK whenever the signer process is
+/// able to respond at all.
+pub async fn healthz_handler() -> Json<LivenessResponse> {
+    Json(LivenessResponse {
+        alive: true,
+        git_revision: crate::GIT_COMMIT.to_string(),
+    })
+}
+
+/// A handler that responds with 200 OK when the signer is ready to do
+/// work and 503 Service Unavailable otherwise, along with the outcome of
+/// each readiness check.
+pub async fn readyz_handler<C: Context>(
+    state: State<ApiState<C>>,
+) -> (StatusCode, Json<ReadinessResponse>) {
+    let response = check_readiness(&state.ctx).await;
+    let status = if response.ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (status, Json(response))
+}
+
+/// Run each of the readiness checks against the given context.
+async fn check_readiness<C: Context>(ctx: &C) -> ReadinessResponse {
+    let config = &ctx.config().signer.event_observer;
+    let state = ctx.state();
+
+    let storage = match ctx.get_storage().get_bitcoin_canonical_chain_tip().await {
+        Ok(_) => ReadinessCheck::pass("database is reachable"),
+        Err(error) => ReadinessCheck::fail(format!("could not query the database: {error}")),
+    };
+
+    let blockchain_info = ctx.get_bitcoin_client().get_blockchain_info().await;
+    let bitcoin = match &blockchain_info {
+        Ok(info) => ReadinessCheck::pass(format!("bitcoin node is at height {}", info.blocks)),
+        Err(error) => ReadinessCheck::fail(format!("bitcoin node is unreachable: {error}")),
+    };
+
+    let stacks_node_info = ctx.get_stacks_client().get_node_info().await;
+    let stacks = match &stacks_node_info {
+        Ok(info) => ReadinessCheck::pass(format!(
+            "stacks node is at height {} and bitcoin height {}",
+            info.stacks_tip_height, info.burn_block_height
+        )),
+        Err(error) => ReadinessCheck::fail(format!("stacks node is unreachable: {error}")),
+    };
+
+    let signatures_required = state
+        .registry_signer_set_info()
+        .map(|info| info.signatures_required)
+        .unwrap_or(ctx.config().signer.bootstrap_signatures_required);
+    // We can always count on ourselves.
+    let live_peers = state.num_live_peers(config.readiness_peer_timeout);
+    let reachable_signers = live_peers.saturating_add(1);
+    let detail =
+        format!("{reachable_signers} signers are reachable and {signatures_required} are required");
+    let p2p = if reachable_signers >= usize::from(signatures_required) {
+        ReadinessCheck::pass(detail)
+    } else {
+        ReadinessCheck::fail(detail)
+    };
+
+    let chain_tips = match (state.bitcoin_chain_tip(), blockchain_info, stacks_node_info) {
+        (None, _, _) => ReadinessCheck::fail("the signer has not processed a bitcoin block"),
+        (_, Err(_), _) | (_, _, Err(_)) => {
+            ReadinessCheck::fail("could not get the chain tips of the bitcoin and stacks nodes")
+        }
+        (Some(chain_tip), Ok(bitcoin_info), Ok(stacks_info)) => {
+            let signer_height = *chain_tip.block_height;
+            let bitcoin_lag = bitcoin_info.blocks.saturating_sub(signer_height);
+            let stacks_lag = signer_height.saturating_sub(*stacks_info.burn_block_height);
+            let detail = format!(
+                "signer bitcoin tip is at height {signer_height}, {bitcoin_lag} blocks behind \
+                the bitcoin node and {stacks_lag} blocks ahead of the stacks node"
+            );
+
+            if bitcoin_lag <= config.readiness_max_bitcoin_tip_lag
+                && stacks_lag <= config.readiness_max_stacks_tip_lag
+            {
+                ReadinessCheck::pass(detail)
+            } else {
+                ReadinessCheck::fail(detail)
+            }
+        }
+    };
+
+    let checks = ReadinessChecks {
+        storage,
+        bitcoin,
+        stacks,
+        p2p,
+        chain_tips,
+    };
+
+    ReadinessResponse { ready: checks.all_ok(), checks }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::LazyLock;
+
+    use fake::{Fake as _, Faker};
+
+    use crate::error::Error;
+    use crate::stacks::api::GetNodeInfoResponse;
+    use crate::storage::model::BitcoinBlockRef;
+    use crate::testing::context::*;
+
+    use super::*;
+
+    static NODE_INFO_RESPONSE: LazyLock<GetNodeInfoResponse> = LazyLock::new(|| {
+        let json = include_str!("../../tests/fixtures/stacksapi-get-node-info-test-data.json");
+        serde_json::from_str(json).unwrap()
+    });
+
+    #[tokio::test]
+    async fn healthz_always_reports_alive() {
+        let Json(response) = healthz_handler().await;
+        assert!(response.alive);
+        assert_eq!(response.git_revision, crate::GIT_COMMIT);
+    }
+
+    #[tokio::test]
+    async fn readyz_fails_when_the_nodes_are_unreachable() {
+        let context = TestContext::default_mocked();
+
+        context
+            .with_bitcoin_client(|client| {
+                client
+                    .expect_get_blockchain_info()
+                    .once()
+                    .returning(|| Box::pin(async { Err(Error::Dummy) }));
+            })
+            .await;
+
+        context
+            .with_stacks_client(|client| {
+                client
+                    .expect_get_node_info()
+                    .once()
+                    .returning(|| Box::pin(async { Err(Error::Dummy) }));
+            })
+            .await;
+
+        let state = State(ApiState { ctx: context });
+        let (status, Json(response)) = readyz_handler(state).await;
+
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert!(!response.ready);
+        assert!(response.checks.storage.ok);
+        assert!(!response.checks.bitcoin.ok);
+        assert!(!response.checks.stacks.ok);
+        assert!(!response.checks.chain_tips.ok);
+        // The default configuration requires two signatures, and we have
+        // not heard from any peers.
+        assert!(!response.checks.p2p.ok);
+    }
+
+    #[tokio::test]
+    async fn readyz_checks_chain_tip_freshness() {
+        let context = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .modify_settings(|settings| {
+                settings.signer.bootstrap_signatures_required = 1;
+                settings.signer.event_observer.readiness_max_bitcoin_tip_lag = 1;
+            })
+            .build();
+
+        let blockchain_info_json =
+            include_str!("../../tests/fixtures/bitcoind-getblockchaininfo-data.json");
+        let mut blockchain_info: bitcoincore_rpc_json::GetBlockchainInfoResult =
+            serde_json::from_str(blockchain_info_json).unwrap();
+        // The bitcoin node is two blocks ahead of the stacks node.
+        blockchain_info.blocks = *NODE_INFO_RESPONSE.burn_block_height + 2;
+
+        context
+            .with_bitcoin_client(|client| {
+                client
+                    .expect_get_blockchain_info()
+                    .times(2)
+                    .returning(move || {
+                        let blockchain_info = blockchain_info.clone();
+                        Box::pin(async move { Ok(blockchain_info) })
+                    });
+            })
+            .await;
+
+        context
+            .with_stacks_client(|client| {
+                client
+                    .expect_get_node_info()
+                    .times(2)
+                    .returning(|| Box::pin(async { Ok(NODE_INFO_RESPONSE.clone()) }));
+            })
+            .await;
+
+        // The signer is two blocks behind the bitcoin node, which is
+        // more than we tolerate.
+        let chain_tip = BitcoinBlockRef {
+            block_height: NODE_INFO_RESPONSE.burn_block_height,
+            block_hash: Faker.fake(),
+        };
+        context.state().set_bitcoin_chain_tip(chain_tip);
+
+        let state = State(ApiState { ctx: context.clone() });
+        let (status, Json(response)) = readyz_handler(state.clone()).await;
+
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert!(response.checks.storage.ok);
+        assert!(response.checks.bitcoin.ok);
+        assert!(response.checks.stacks.ok);
+        assert!(response.checks.p2p.ok);
+        assert!(!response.checks.chain_tips.ok);
+
+        // Once the signer catches up to within one block, it is ready.
+        let chain_tip = BitcoinBlockRef {
+            block_height: NODE_INFO_RESPONSE.burn_block_height + 1,
+            block_hash: Faker.fake(),
+        };
+        context.state().set_bitcoin_chain_tip(chain_tip);
+
+        let (status, Json(response)) = readyz_handler(state).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert!(response.ready);
+        assert!(response.checks.chain_tips.ok);
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["checks"]["chain_tips"]["ok"], true);
+    }
+}
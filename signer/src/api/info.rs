@@ -1,84 +1,289 @@
 //! Handler for the `/info` endpoint.
 
+use std::borrow::Cow;
+
 use axum::{Json, extract::State, response::IntoResponse};
 use clarity::types::chainstate::StacksBlockId;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    bitcoin::BitcoinInteract,
+    bitcoin::{BitcoinInteract, utxo::SweepCapacityPlanner},
     config::Settings,
     context::Context,
+    key_rotation_recovery::KeyRotationState,
+    sbtc_limits::MaxMintable,
     stacks::api::StacksInteract,
     storage::{
         DbRead,
-        model::{BitcoinBlockHash, BitcoinBlockHeight, StacksBlockHash, StacksBlockHeight},
+        model::{
+            AggregateKeyPeriod, BitcoinBlockHash, BitcoinBlockHeight, CapsUtilization, P2PPeerBan,
+            RequestCounts, SignerUtxoViolation, StacksBlockHash, StacksBlockHeight,
+        },
     },
 };
 
 use super::ApiState;
+use super::overrides::RequestOverrideInfo;
+
+/// The maximum number of signer UTXO invariant violations to include in
+/// the response.
+const MAX_SIGNER_UTXO_VIOLATIONS: u16 = 10;
 
-#[derive(Debug, Serialize)]
+/// The response from the `/info` endpoint.
+#[derive(Debug, Serialize, Deserialize)]
 pub struct InfoResponse {
+    /// Information about the bitcoin blockchain.
     pub bitcoin: BitcoinInfo,
+    /// Information about the stacks blockchain.
     pub stacks: StacksInfo,
+    /// Information about the distributed key generation rounds.
     pub dkg: DkgInfo,
+    /// Violations of invariants that the signer checks.
+    pub invariants: InvariantsInfo,
+    /// The number of deposit and withdrawal requests in each stage of
+    /// processing, if they could be read from the database.
+    pub requests: Option<RequestCounts>,
+    /// How much of the rolling withdrawal cap has been used, if it could
+    /// be read from the database.
+    pub caps: Option<CapsUtilization>,
+    /// The most recent calculation of the maximum amount of sBTC that can
+    /// be minted, if the signer has computed one.
+    pub mintable: Option<MaxMintable>,
+    /// The operator overrides on requests that currently apply.
+    pub overrides: Vec<RequestOverrideInfo>,
+    /// Information about the signer's P2P peers.
+    pub peers: PeersInfo,
+    /// The capacity of a single sweep transaction.
+    pub capacity: CapacityInfo,
+    /// A subset of the signer's configuration.
     pub config: Option<ConfigInfo>,
+    /// Information about how the signer binary was built.
     pub build_info: BuildInfo,
+    /// When the response was generated.
     pub timestamp: String,
 }
 
-#[derive(Debug, Serialize)]
+/// Information about how the signer binary was built.
+#[derive(Debug, Serialize, Deserialize)]
 pub struct BuildInfo {
-    pub rust_version: &'static str,
-    pub git_revision: &'static str,
-    pub target_arch: &'static str,
-    pub target_env_abi: Option<&'static str>,
+    /// The version of rustc used to build the binary.
+    pub rust_version: Cow<'static, str>,
+    /// The git revision that the binary was built from.
+    pub git_revision: Cow<'static, str>,
+    /// The target architecture of the binary.
+    pub target_arch: Cow<'static, str>,
+    /// The target environment ABI of the binary, if any.
+    pub target_env_abi: Option<Cow<'static, str>>,
 }
 
-#[derive(Debug, Default, Serialize)]
+/// Information about the bitcoin blockchain, as seen by the signer and
+/// by its bitcoin node.
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct BitcoinInfo {
+    /// The bitcoin chain tip in the signer's state.
     pub signer_tip: Option<ChainTipInfo<BitcoinBlockHash, BitcoinBlockHeight>>,
+    /// The bitcoin chain tip of the bitcoin node.
     pub node_tip: Option<ChainTipInfo<BitcoinBlockHash, BitcoinBlockHeight>>,
+    /// The chain that the bitcoin node is running on.
     pub node_chain: Option<String>,
+    /// The version of the bitcoin node.
     pub node_version: Option<usize>,
+    /// The subversion of the bitcoin node.
     pub node_subversion: Option<String>,
 }
 
-#[derive(Debug, Default, Serialize)]
+/// Information about the stacks blockchain, as seen by the signer and by
+/// its stacks node.
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct StacksInfo {
+    /// The stacks chain tip in the signer's state.
     pub signer_tip: Option<ChainTipInfo<StacksBlockHash, StacksBlockHeight>>,
+    /// The stacks chain tip of the stacks node.
     pub node_tip: Option<ChainTipInfo<StacksBlockId, StacksBlockHeight>>,
+    /// The height of the bitcoin block that the stacks node has processed.
     pub node_bitcoin_block_height: Option<BitcoinBlockHeight>,
+    /// The version of the stacks node.
     pub node_version: Option<String>,
+    /// Whether governance has paused the sBTC smart contracts.
+    pub sbtc_contract_paused: bool,
 }
 
-#[derive(Debug, Serialize)]
+/// A block at the tip of a blockchain.
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ChainTipInfo<THash, THeight> {
+    /// The hash of the block.
     pub block_hash: THash,
+    /// The height of the block.
     pub block_height: THeight,
 }
 
-#[derive(Debug, Serialize)]
+/// A subset of the signer's configuration.
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ConfigInfo {
+    /// The bitcoin network that the signer is running on.
     pub network: String,
+    /// The address that deployed the sBTC smart contracts.
     pub deployer: String,
+    /// The number of signatures required by the bootstrap signer set.
     pub bootstrap_signatures_required: u16,
+    /// The delay, in seconds, before the signer processes a new bitcoin
+    /// block.
     pub bitcoin_processing_delay: u64,
+    /// The number of bitcoin blocks that the signer considers requests in.
     pub context_window: u16,
+    /// The maximum duration, in seconds, of a signing round.
     pub signer_round_max_duration: u64,
+    /// The maximum duration, in seconds, of a bitcoin pre-sign request.
     pub bitcoin_presign_request_max_duration: u64,
+    /// The maximum duration, in seconds, of a DKG round.
     pub dkg_max_duration: u64,
+    /// The bitcoin block height from which the signer processes sBTC
+    /// requests.
     pub sbtc_bitcoin_start_height: Option<BitcoinBlockHeight>,
+    /// The number of bitcoin blocks to wait before starting DKG.
     pub dkg_begin_pause: u64,
+    /// The maximum number of deposits in a sweep transaction.
     pub max_deposits_per_bitcoin_block: u16,
+    /// The minimum bitcoin block height for running DKG.
     pub dkg_min_bitcoin_block_height: Option<BitcoinBlockHeight>,
 }
 
-#[derive(Debug, Serialize)]
+/// Violations of invariants that the signer checks.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct InvariantsInfo {
+    /// The most recent sweep transactions whose signer UTXO value did not
+    /// match the sum of their inputs and outputs, or the value of the
+    /// signers' UTXO created by the previous sweep transaction.
+    pub signer_utxo_violations: Vec<SignerUtxoViolationInfo>,
+}
+
+/// A sweep transaction that failed the signer UTXO value invariant.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SignerUtxoViolationInfo {
+    /// The ID of the sweep transaction.
+    pub txid: String,
+    /// The bitcoin block that confirmed the sweep transaction.
+    pub block_hash: BitcoinBlockHash,
+    /// The amount of the signers' input, in sats.
+    pub signer_input: u64,
+    /// The total amount of the swept deposits, in sats.
+    pub deposits: u64,
+    /// The total amount of the withdrawal outputs, in sats.
+    pub withdrawals: u64,
+    /// The fee paid by the sweep transaction, in sats.
+    pub fee: u64,
+    /// The amount of the signers' output, in sats.
+    pub signer_output: u64,
+    /// The amount of the signers' UTXO created by the previous sweep
+    /// transaction that this transaction spends, in sats, if known.
+    pub previous_signer_output: Option<u64>,
+}
+
+impl From<SignerUtxoViolation> for SignerUtxoViolationInfo {
+    fn from(violation: SignerUtxoViolation) -> Self {
+        Self {
+            txid: violation.txid.to_string(),
+            block_hash: violation.block_hash,
+            signer_input: violation.signer_input,
+            deposits: violation.deposits,
+            withdrawals: violation.withdrawals,
+            fee: violation.fee,
+            signer_output: violation.signer_output,
+            previous_signer_output: violation.previous_signer_output,
+        }
+    }
+}
+
+/// Information about the signer's P2P peers.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PeersInfo {
+    /// The peers that are currently banned.
+    pub banned: Vec<PeerBanInfo>,
+}
+
+/// A ban of a misbehaving P2P peer.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PeerBanInfo {
+    /// The ID of the banned peer.
+    pub peer_id: String,
+    /// Why the peer was banned.
+    pub reason: String,
+    /// The misbehavior score of the peer when it was banned.
+    pub score: u32,
+    /// When the peer was banned.
+    pub banned_at: String,
+    /// When the ban expires.
+    pub expires_at: String,
+}
+
+impl From<P2PPeerBan> for PeerBanInfo {
+    fn from(ban: P2PPeerBan) -> Self {
+        Self {
+            peer_id: ban.peer_id.to_string(),
+            reason: ban.reason,
+            score: ban.score,
+            banned_at: ban.banned_at.to_string(),
+            expires_at: ban.expires_at.to_string(),
+        }
+    }
+}
+
+/// The capacity of a single sweep transaction.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CapacityInfo {
+    /// The current market fee rate, in sats per vbyte, if bitcoin-core
+    /// could estimate it.
+    pub fee_rate: Option<f64>,
+    /// The maximum number of deposits in a sweep transaction.
+    pub max_deposits_per_tx: u64,
+    /// The maximum number of withdrawals in a sweep transaction.
+    pub max_withdrawals_per_tx: u64,
+    /// The fee, in sats, of a sweep transaction with the maximum number
+    /// of deposits at the current market fee rate.
+    pub max_deposits_tx_fee: Option<u64>,
+}
+
+/// Information about the distributed key generation rounds.
+#[derive(Debug, Serialize, Deserialize)]
 pub struct DkgInfo {
+    /// The number of DKG rounds that the signer has shares for.
     pub rounds: u32,
+    /// The aggregate key from the latest DKG shares.
     pub current_aggregate_key: Option<String>,
+    /// The aggregate key registered in the sbtc-registry contract.
     pub contract_aggregate_key: Option<String>,
+    /// The aggregate keys that have locked the signers' UTXO.
+    pub key_history: Vec<AggregateKeyPeriodInfo>,
+    /// How far along the signers are with their most recent key rotation.
+    pub key_rotation: KeyRotationState,
+}
+
+/// A range of bitcoin blocks during which an aggregate key locked the
+/// signers' UTXO.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AggregateKeyPeriodInfo {
+    /// The aggregate key.
+    pub aggregate_key: String,
+    /// The height of the first bitcoin block with a signers' UTXO locked
+    /// by the aggregate key.
+    pub start_height: BitcoinBlockHeight,
+    /// The height of the first bitcoin block with a signers' UTXO locked
+    /// by the next aggregate key, if any.
+    pub end_height: Option<BitcoinBlockHeight>,
+    /// The ID of the rotate-keys transaction that registered the
+    /// aggregate key, if any.
+    pub rotate_keys_txid: Option<String>,
+}
+
+impl From<AggregateKeyPeriod> for AggregateKeyPeriodInfo {
+    fn from(period: AggregateKeyPeriod) -> Self {
+        Self {
+            aggregate_key: period.aggregate_key.to_string(),
+            start_height: period.start_height,
+            end_height: period.end_height,
+            rotate_keys_txid: period.rotate_keys_txid.map(|txid| txid.to_string()),
+        }
+    }
 }
 
 impl Default for InfoResponse {
@@ -89,7 +294,7 @@ impl Default for InfoResponse {
         let target_env_abi = if crate::TARGET_ENV_ABI.is_empty() {
             None
         } else {
-            Some(crate::TARGET_ENV_ABI)
+            Some(Cow::Borrowed(crate::TARGET_ENV_ABI))
         };
 
         Self {
@@ -99,12 +304,21 @@ impl Default for InfoResponse {
                 rounds: 0,
                 current_aggregate_key: None,
                 contract_aggregate_key: None,
+                key_history: Vec::new(),
+                key_rotation: KeyRotationState::Complete,
             },
+            invariants: Default::default(),
+            requests: None,
+            caps: None,
+            mintable: None,
+            overrides: Vec::new(),
+            peers: Default::default(),
+            capacity: Default::default(),
             config: None,
             build_info: BuildInfo {
-                rust_version: crate::RUSTC_VERSION,
-                git_revision: crate::GIT_COMMIT,
-                target_arch: crate::TARGET_ARCH,
+                rust_version: Cow::Borrowed(crate::RUSTC_VERSION),
+                git_revision: Cow::Borrowed(crate::GIT_COMMIT),
+                target_arch: Cow::Borrowed(crate::TARGET_ARCH),
                 target_env_abi,
             },
             timestamp: time::OffsetDateTime::now_utc().to_string(),
@@ -140,6 +354,15 @@ pub async fn build_info<C: Context>(ctx: &C) -> InfoResponse {
     response
         .populate_dkg_info(&storage, config, &stacks_client)
         .await;
+    response.populate_key_history_info(ctx).await;
+    response.dkg.key_rotation = ctx.state().key_rotation_state();
+    response.populate_invariants_info(&storage).await;
+    response.populate_request_counts(ctx).await;
+    response.populate_caps_utilization(ctx).await;
+    response.mintable = ctx.state().max_mintable();
+    response.populate_overrides_info(&storage).await;
+    response.populate_peers_info(&storage).await;
+    response.populate_capacity_info(&bitcoin_client).await;
 
     response
 }
@@ -189,6 +412,7 @@ impl InfoResponse {
                 tracing::debug!("no local stacks tip found in the signer's state");
             }
         }
+        self.stacks.sbtc_contract_paused = ctx.state().is_contract_paused();
     }
 
     /// Populates the Bitcoin node tip information from the provided Bitcoin
@@ -291,6 +515,153 @@ impl InfoResponse {
             }
         }
     }
+
+    /// Populates the history of the aggregate keys that have locked the
+    /// signers' UTXO on the bitcoin blockchain identified by the signer's
+    /// bitcoin chain tip.
+    async fn populate_key_history_info<C: Context>(&mut self, ctx: &C) {
+        let Some(chain_tip) = ctx.state().bitcoin_chain_tip() else {
+            tracing::debug!("no local bitcoin tip found in the signer's state");
+            return;
+        };
+
+        let history = ctx
+            .get_storage()
+            .get_aggregate_key_history(&chain_tip.block_hash)
+            .await;
+
+        match history {
+            Ok(history) => {
+                self.dkg.key_history = history.into_iter().map(Into::into).collect();
+            }
+            Err(error) => {
+                tracing::error!(%error, "error reading the aggregate key history from the database");
+            }
+        }
+    }
+
+    /// Populates the most recent invariant violations from the provided
+    /// storage.
+    async fn populate_invariants_info(&mut self, storage: &impl DbRead) {
+        let violations = storage
+            .get_signer_utxo_violations(MAX_SIGNER_UTXO_VIOLATIONS)
+            .await;
+
+        match violations {
+            Ok(violations) => {
+                self.invariants.signer_utxo_violations =
+                    violations.into_iter().map(Into::into).collect();
+            }
+            Err(error) => {
+                tracing::error!(%error, "error reading signer UTXO violations from the database");
+            }
+        }
+    }
+
+    /// Populates the number of deposit and withdrawal requests in each
+    /// stage of processing on the signer's canonical blockchains.
+    async fn populate_request_counts<C: Context>(&mut self, ctx: &C) {
+        let Some(chain_tip) = ctx.state().bitcoin_chain_tip() else {
+            tracing::debug!("no local bitcoin tip found in the signer's state");
+            return;
+        };
+
+        let counts = ctx
+            .get_storage()
+            .get_request_counts(&chain_tip.block_hash)
+            .await;
+
+        match counts {
+            Ok(counts) => {
+                self.requests = Some(counts);
+            }
+            Err(error) => {
+                tracing::error!(%error, "error reading the request counts from the database");
+            }
+        }
+    }
+
+    /// Populates the utilization of the rolling withdrawal cap on the
+    /// bitcoin blockchain identified by the signer's bitcoin chain tip,
+    /// using the limits last fetched from Emily.
+    async fn populate_caps_utilization<C: Context>(&mut self, ctx: &C) {
+        let Some(chain_tip) = ctx.state().bitcoin_chain_tip() else {
+            tracing::debug!("no local bitcoin tip found in the signer's state");
+            return;
+        };
+
+        let rolling_limits = ctx.state().get_current_limits().rolling_withdrawal_limits();
+        let utilization = ctx
+            .get_storage()
+            .get_caps_utilization(
+                &chain_tip.block_hash,
+                rolling_limits.blocks,
+                rolling_limits.cap,
+            )
+            .await;
+
+        match utilization {
+            Ok(utilization) => {
+                self.caps = Some(utilization);
+            }
+            Err(error) => {
+                tracing::error!(%error, "error reading the caps utilization from the database");
+            }
+        }
+    }
+
+    async fn populate_overrides_info(&mut self, storage: &impl DbRead) {
+        match storage.get_request_overrides().await {
+            Ok(overrides) => {
+                let now = time::OffsetDateTime::now_utc();
+                self.overrides = overrides
+                    .into_iter()
+                    .filter(|request_override| request_override.is_active_at(now))
+                    .map(Into::into)
+                    .collect();
+            }
+            Err(error) => {
+                tracing::error!(%error, "error reading request overrides from the database");
+            }
+        }
+    }
+
+    async fn populate_peers_info(&mut self, storage: &impl DbRead) {
+        match storage.get_active_peer_bans().await {
+            Ok(bans) => {
+                self.peers.banned = bans.into_iter().map(Into::into).collect();
+            }
+            Err(error) => {
+                tracing::error!(%error, "error reading peer bans from the database");
+            }
+        }
+    }
+
+    /// Populates the capacity of a single sweep transaction, using the
+    /// current market fee rate when bitcoin-core can estimate it.
+    async fn populate_capacity_info(&mut self, bitcoin_client: &impl BitcoinInteract) {
+        let fee_rate = match bitcoin_client.estimate_fee_rate(1).await {
+            Ok(fee_rate) => Some(fee_rate),
+            Err(error) => {
+                tracing::warn!(%error, "could not estimate the bitcoin fee rate");
+                None
+            }
+        };
+
+        let planner = SweepCapacityPlanner::new(fee_rate.unwrap_or_default(), &[], &[]);
+        let capacity = planner.capacity();
+        let full_deposit_tx = SweepCapacityPlanner {
+            pending_deposits: capacity.max_deposits,
+            ..planner
+        };
+
+        self.capacity = CapacityInfo {
+            fee_rate,
+            max_deposits_per_tx: capacity.max_deposits,
+            max_withdrawals_per_tx: capacity.max_withdrawals,
+            max_deposits_tx_fee: fee_rate.map(|_| full_deposit_tx.capacity().planned_fee),
+        };
+    }
 }
 
 #[cfg(test)]
@@ -304,7 +675,10 @@ mod tests {
     use crate::{
         api::ApiState,
         error::Error,
-        storage::model::{BitcoinBlock, BitcoinBlockRef, StacksBlock},
+        storage::{
+            DbWrite as _,
+            model::{BitcoinBlock, BitcoinBlockRef, StacksBlock},
+        },
         testing::context::*,
     };
 
@@ -316,6 +690,11 @@ mod tests {
 
         context
             .with_bitcoin_client(|client| {
+                client
+                    .expect_estimate_fee_rate()
+                    .once()
+                    .returning(|_| Box::pin(async { Err(Error::Dummy) }));
+
                 client
                     .expect_get_blockchain_info()
                     .once()
@@ -362,6 +741,27 @@ mod tests {
         assert!(result.dkg.contract_aggregate_key.is_none());
         assert!(result.dkg.current_aggregate_key.is_none());
         assert_eq!(result.dkg.rounds, 0);
+        assert_eq!(result.dkg.key_rotation, KeyRotationState::Complete);
+
+        // Assert invariants info
+        assert!(result.invariants.signer_utxo_violations.is_empty());
+
+        // Assert peers info
+        assert!(result.peers.banned.is_empty());
+
+        // Assert caps info
+        assert!(result.caps.is_none());
+
+        // Assert mintable info
+        assert!(result.mintable.is_none());
+
+        // Assert overrides info
+        assert!(result.overrides.is_empty());
+
+        // Assert capacity info
+        assert!(result.capacity.fee_rate.is_none());
+        assert!(result.capacity.max_deposits_tx_fee.is_none());
+        assert!(result.capacity.max_deposits_per_tx > 0);
 
         // Assert build info
         #[allow(clippy::const_is_empty)]
@@ -373,7 +773,7 @@ mod tests {
         assert_eq!(result.build_info.rust_version, crate::RUSTC_VERSION);
         assert_eq!(result.build_info.git_revision, crate::GIT_COMMIT);
         assert_eq!(result.build_info.target_arch, crate::TARGET_ARCH);
-        assert_eq!(result.build_info.target_env_abi, target_env_abi);
+        assert_eq!(result.build_info.target_env_abi.as_deref(), target_env_abi);
     }
 
     #[tokio::test]
@@ -382,6 +782,11 @@ mod tests {
 
         context
             .with_bitcoin_client(|client| {
+                client
+                    .expect_estimate_fee_rate()
+                    .once()
+                    .returning(|_| Box::pin(async { Err(Error::Dummy) }));
+
                 client
                     .expect_get_blockchain_info()
                     .once()
@@ -439,6 +844,187 @@ mod tests {
         assert_eq!(stacks_local_tip.block_height, stacks_block.block_height);
     }
 
+    #[tokio::test]
+    async fn test_invariants_info() {
+        let context = TestContext::default_mocked();
+
+        context
+            .with_bitcoin_client(|client| {
+                client
+                    .expect_estimate_fee_rate()
+                    .once()
+                    .returning(|_| Box::pin(async { Err(Error::Dummy) }));
+
+                client
+                    .expect_get_blockchain_info()
+                    .once()
+                    .returning(|| Box::pin(async { Err(Error::Dummy) }));
+
+                client
+                    .expect_get_network_info()
+                    .once()
+                    .returning(|| Box::pin(async { Err(Error::Dummy) }));
+            })
+            .await;
+
+        context
+            .with_stacks_client(|client| {
+                client
+                    .expect_get_node_info()
+                    .once()
+                    .returning(|| Box::pin(async { Err(Error::Dummy) }));
+
+                client
+                    .expect_get_current_signers_aggregate_key()
+                    .once()
+                    .returning(|_| Box::pin(async { Err(Error::Dummy) }));
+            })
+            .await;
+
+        let violation = SignerUtxoViolation {
+            txid: Faker.fake(),
+            block_hash: Faker.fake(),
+            signer_input: 50_000,
+            deposits: 40_000,
+            withdrawals: 15_000,
+            fee: 5_000,
+            signer_output: 69_000,
+            previous_signer_output: Some(60_000),
+        };
+        context
+            .get_storage_mut()
+            .write_signer_utxo_violation(&violation)
+            .await
+            .unwrap();
+
+        let state = State(ApiState { ctx: context.clone() });
+        let result = info_handler(state).await;
+
+        let violations = result.invariants.signer_utxo_violations;
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].txid, violation.txid.to_string());
+        assert_eq!(violations[0].block_hash, violation.block_hash);
+        assert_eq!(violations[0].signer_output, violation.signer_output);
+        assert_eq!(violations[0].previous_signer_output, Some(60_000));
+    }
+
+    #[tokio::test]
+    async fn test_peers_info() {
+        let context = TestContext::default_mocked();
+
+        context
+            .with_bitcoin_client(|client| {
+                client
+                    .expect_estimate_fee_rate()
+                    .once()
+                    .returning(|_| Box::pin(async { Err(Error::Dummy) }));
+
+                client
+                    .expect_get_blockchain_info()
+                    .once()
+                    .returning(|| Box::pin(async { Err(Error::Dummy) }));
+
+                client
+                    .expect_get_network_info()
+                    .once()
+                    .returning(|| Box::pin(async { Err(Error::Dummy) }));
+            })
+            .await;
+
+        context
+            .with_stacks_client(|client| {
+                client
+                    .expect_get_node_info()
+                    .once()
+                    .returning(|| Box::pin(async { Err(Error::Dummy) }));
+
+                client
+                    .expect_get_current_signers_aggregate_key()
+                    .once()
+                    .returning(|_| Box::pin(async { Err(Error::Dummy) }));
+            })
+            .await;
+
+        let now = time::OffsetDateTime::now_utc();
+        let active_ban = P2PPeerBan {
+            peer_id: libp2p::PeerId::random().into(),
+            reason: "invalid-signature".to_string(),
+            score: 100,
+            banned_at: now.into(),
+            expires_at: (now + Duration::from_secs(3600)).into(),
+        };
+        let expired_ban = P2PPeerBan {
+            peer_id: libp2p::PeerId::random().into(),
+            expires_at: (now - Duration::from_secs(1)).into(),
+            ..active_ban.clone()
+        };
+
+        let storage = context.get_storage_mut();
+        storage.write_peer_ban(&active_ban).await.unwrap();
+        storage.write_peer_ban(&expired_ban).await.unwrap();
+
+        let state = State(ApiState { ctx: context.clone() });
+        let result = info_handler(state).await;
+
+        // Only bans that have not expired are listed.
+        let banned = result.peers.banned;
+        assert_eq!(banned.len(), 1);
+        assert_eq!(banned[0].peer_id, active_ban.peer_id.to_string());
+        assert_eq!(banned[0].reason, active_ban.reason);
+        assert_eq!(banned[0].score, active_ban.score);
+    }
+
+    #[tokio::test]
+    async fn test_capacity_info() {
+        let context = TestContext::default_mocked();
+
+        context
+            .with_bitcoin_client(|client| {
+                client
+                    .expect_estimate_fee_rate()
+                    .once()
+                    .returning(|_| Box::pin(async { Ok(10.0) }));
+
+                client
+                    .expect_get_blockchain_info()
+                    .once()
+                    .returning(|| Box::pin(async { Err(Error::Dummy) }));
+
+                client
+                    .expect_get_network_info()
+                    .once()
+                    .returning(|| Box::pin(async { Err(Error::Dummy) }));
+            })
+            .await;
+
+        context
+            .with_stacks_client(|client| {
+                client
+                    .expect_get_node_info()
+                    .once()
+                    .returning(|| Box::pin(async { Err(Error::Dummy) }));
+
+                client
+                    .expect_get_current_signers_aggregate_key()
+                    .once()
+                    .returning(|_| Box::pin(async { Err(Error::Dummy) }));
+            })
+            .await;
+
+        let state = State(ApiState { ctx: context });
+        let result = info_handler(state).await;
+
+        let capacity = SweepCapacityPlanner::new(10.0, &[], &[]).capacity();
+        assert_eq!(result.capacity.fee_rate, Some(10.0));
+        assert_eq!(result.capacity.max_deposits_per_tx, capacity.max_deposits);
+        assert_eq!(
+            result.capacity.max_withdrawals_per_tx,
+            capacity.max_withdrawals
+        );
+        let max_deposits_tx_fee = result.capacity.max_deposits_tx_fee.unwrap();
+        more_asserts::assert_gt!(max_deposits_tx_fee, capacity.planned_fee);
+    }
+
     #[tokio::test]
     async fn test_bitcoin_node_info() {
         let context = TestContext::default_mocked();
@@ -455,6 +1041,11 @@ mod tests {
 
         context
             .with_bitcoin_client(|client| {
+                client
+                    .expect_estimate_fee_rate()
+                    .once()
+                    .returning(|_| Box::pin(async { Err(Error::Dummy) }));
+
                 let get_network_info_response = get_network_info_response.clone();
                 let get_blockchain_info_response = get_blockchain_info_response.clone();
 
@@ -521,6 +1112,11 @@ mod tests {
 
         context
             .with_bitcoin_client(|client| {
+                client
+                    .expect_estimate_fee_rate()
+                    .once()
+                    .returning(|_| Box::pin(async { Err(Error::Dummy) }));
+
                 client
                     .expect_get_blockchain_info()
                     .once()
@@ -600,6 +1196,11 @@ mod tests {
 
         context
             .with_bitcoin_client(|client| {
+                client
+                    .expect_estimate_fee_rate()
+                    .once()
+                    .returning(|_| Box::pin(async { Err(Error::Dummy) }));
+
                 client
                     .expect_get_blockchain_info()
                     .once()
@@ -1,14 +1,37 @@
 //! This module contains functions and structs for the Signer API.
 //!
 
+mod auth;
+pub mod client;
+mod dead_letters;
+mod deposit_precheck;
+mod descriptors;
+mod health;
 mod info;
 mod new_block;
+mod overrides;
+mod rotate_keys;
 mod router;
 mod status;
+mod sweep_tx_proofs;
+mod votes;
 
+pub use dead_letters::{DeadLetterInfo, DeadLettersResponse, ReplayResponse};
+pub use descriptors::{DescriptorStatus, DescriptorsResponse, SignerDescriptorInfo};
+pub use health::{LivenessResponse, ReadinessCheck, ReadinessChecks, ReadinessResponse};
 pub use info::build_info;
+pub use info::{
+    AggregateKeyPeriodInfo, BitcoinInfo, BuildInfo, CapacityInfo, ChainTipInfo, ConfigInfo,
+    DkgInfo, InfoResponse, InvariantsInfo, PeerBanInfo, PeersInfo, SignerUtxoViolationInfo,
+    StacksInfo,
+};
 pub use new_block::new_block_handler;
-pub use router::get_router;
+pub use overrides::{CreateOverrideRequest, OverridesResponse, RequestOverrideInfo};
+pub use router::{get_admin_router, get_router};
+pub use status::StatusResponse;
+pub use sweep_tx_proofs::SweepTxProof;
+pub(crate) use sweep_tx_proofs::get_sweep_tx_proof;
+pub use votes::{DepositVotesResponse, VoteTally, WithdrawalVotes, WithdrawalVotesResponse};
 
 /// A struct with state data necessary for runtime operation.
 #[derive(Debug, Clone)]
@@ -4,6 +4,7 @@
 
 use axum::extract::State;
 use axum::http::StatusCode;
+use clarity::vm::Value as ClarityValue;
 use clarity::vm::representations::ContractName;
 use clarity::vm::types::QualifiedContractIdentifier;
 use clarity::vm::types::StandardPrincipalData;
@@ -15,10 +16,16 @@ use crate::context::Context;
 use crate::error::Error;
 use crate::metrics::Metrics;
 use crate::metrics::STACKS_BLOCKCHAIN;
+use crate::storage::DbRead as _;
 use crate::storage::DbWrite as _;
 use crate::storage::model::CompletedDepositEvent;
+use crate::storage::model::ContractCallTarget;
 use crate::storage::model::KeyRotationEvent;
+use crate::storage::model::RequestLifecycleStage;
 use crate::storage::model::StacksBlock;
+use crate::storage::model::StacksBlockHash;
+use crate::storage::model::StacksEventDeadLetter;
+use crate::storage::model::StacksTxId;
 use crate::storage::model::WithdrawalAcceptEvent;
 use crate::storage::model::WithdrawalRejectEvent;
 use crate::storage::model::WithdrawalRequest;
@@ -108,12 +115,17 @@ pub async fn new_block_handler(state: State<ApiState<impl Context>>, body: Strin
     // Although transactions can fail, only successful transactions emit
     // sBTC print events, since those events are emitted at the very end of
     // the contract call.
+    //
+    // We keep the position of each event within the webhook so that
+    // events that we fail to parse can be identified in the dead-letter
+    // table.
     let events = new_block_event
         .events
         .into_iter()
-        .filter(|x| x.committed)
-        .filter_map(|x| x.contract_event.map(|ev| (ev, x.txid)))
-        .filter(|(ev, _)| &ev.contract_identifier == registry_address && ev.topic == "print")
+        .enumerate()
+        .filter(|(_, x)| x.committed)
+        .filter_map(|(index, x)| x.contract_event.map(|ev| (index, ev, x.txid)))
+        .filter(|(_, ev, _)| &ev.contract_identifier == registry_address && ev.topic == "print")
         .collect::<Vec<_>>();
 
     if events.is_empty() {
@@ -124,30 +136,24 @@ pub async fn new_block_handler(state: State<ApiState<impl Context>>, body: Strin
 
     tracing::debug!(count = %events.len(), "processing events for new stacks block");
 
-    for (ev, txid) in events {
+    for (index, ev, txid) in events {
         let tx_info = TxInfo {
             txid: sbtc::events::StacksTxid(txid.0),
             block_id: stacks_chaintip.block_hash.into(),
         };
-        let res = match RegistryEvent::try_new(ev.raw_value, tx_info) {
-            Ok(RegistryEvent::CompletedDeposit(event)) => {
-                handle_completed_deposit(&api.ctx, event.into()).await
-            }
-            Ok(RegistryEvent::WithdrawalAccept(event)) => {
-                handle_withdrawal_accept(&api.ctx, event.into()).await
-            }
-            Ok(RegistryEvent::WithdrawalReject(event)) => {
-                handle_withdrawal_reject(&api.ctx, event.into()).await
-            }
-            Ok(RegistryEvent::WithdrawalCreate(event)) => {
-                handle_withdrawal_create(&api.ctx, event.into()).await
-            }
-            Ok(RegistryEvent::KeyRotation(event)) => {
-                handle_key_rotation(&api.ctx, event.into()).await
-            }
+        let res = match RegistryEvent::try_new(ev.raw_value.clone(), tx_info) {
+            Ok(event) => handle_registry_event(&api.ctx, event).await,
+            // We could not parse the event, so we store it in the
+            // dead-letter table, where it can be inspected and replayed
+            // once the parser has been fixed.
             Err(error) => {
                 tracing::error!(%error, %txid, "got an error when transforming the event ClarityValue");
-                continue;
+                let dead_letter = DeadLetterRef {
+                    block_hash: stacks_chaintip.block_hash,
+                    txid: txid.into(),
+                    index,
+                };
+                dead_letter_event(&api.ctx, dead_letter, &ev.raw_value, &error).await
             }
         };
         // If we got an error writing to the database, this might be an
@@ -169,6 +175,60 @@ pub async fn new_block_handler(state: State<ApiState<impl Context>>, body: Strin
     StatusCode::OK
 }
 
+/// Processes a parsed sbtc-registry event by adding it to the database.
+pub(super) async fn handle_registry_event(
+    ctx: &impl Context,
+    event: RegistryEvent,
+) -> Result<(), Error> {
+    match event {
+        RegistryEvent::CompletedDeposit(event) => handle_completed_deposit(ctx, event.into()).await,
+        RegistryEvent::WithdrawalAccept(event) => handle_withdrawal_accept(ctx, event.into()).await,
+        RegistryEvent::WithdrawalReject(event) => handle_withdrawal_reject(ctx, event.into()).await,
+        RegistryEvent::WithdrawalCreate(event) => handle_withdrawal_create(ctx, event.into()).await,
+        RegistryEvent::KeyRotation(event) => handle_key_rotation(ctx, event.into()).await,
+    }
+}
+
+/// Identifies an sbtc-registry event within the new block webhooks sent
+/// by the stacks node.
+#[derive(Debug, Clone, Copy)]
+struct DeadLetterRef {
+    block_hash: StacksBlockHash,
+    txid: StacksTxId,
+    index: usize,
+}
+
+/// Stores an sbtc-registry event that could not be parsed in the
+/// dead-letter table.
+async fn dead_letter_event(
+    ctx: &impl Context,
+    dead_letter: DeadLetterRef,
+    raw_value: &ClarityValue,
+    error: &sbtc::events::EventError,
+) -> Result<(), Error> {
+    let raw_value = raw_value
+        .serialize_to_hex()
+        .map_err(Box::new)
+        .map_err(Error::ClarityValueSerialization)?;
+
+    let letter = StacksEventDeadLetter {
+        block_hash: dead_letter.block_hash,
+        txid: dead_letter.txid,
+        event_index: u32::try_from(dead_letter.index).map_err(|_| Error::TypeConversion)?,
+        raw_value,
+        error: error.to_string(),
+        attempts: 1,
+        created_at: time::OffsetDateTime::now_utc().into(),
+        resolved_at: None,
+    };
+    ctx.get_storage_mut()
+        .write_stacks_event_dead_letter(&letter)
+        .await?;
+
+    metrics::counter!(Metrics::StacksEventsDeadLetteredTotal).increment(1);
+    Ok(())
+}
+
 /// Processes a completed deposit event by adding the event to the database.
 ///
 /// # Parameters
@@ -185,9 +245,23 @@ async fn handle_completed_deposit(
     ctx: &impl Context,
     event: CompletedDepositEvent,
 ) -> Result<(), Error> {
-    ctx.get_storage_mut()
-        .write_completed_deposit_event(&event)
-        .await?;
+    let db = ctx.get_storage_mut();
+    let outcome = db.write_completed_deposit_event(&event).await?;
+    if !outcome.is_inserted() {
+        tracing::debug!(topic = "completed-deposit", "event was already recorded");
+    }
+    db.write_deposit_request_timestamp(
+        &event.outpoint.txid.into(),
+        event.outpoint.vout,
+        RequestLifecycleStage::Completed,
+    )
+    .await?;
+
+    let target = ContractCallTarget::Deposit {
+        txid: event.outpoint.txid.into(),
+        output_index: event.outpoint.vout,
+    };
+    db.delete_inflight_contract_call(&target).await?;
 
     tracing::debug!(topic = "completed-deposit", "handled stacks event");
     Ok(())
@@ -209,15 +283,40 @@ async fn handle_withdrawal_accept(
     ctx: &impl Context,
     event: WithdrawalAcceptEvent,
 ) -> Result<(), Error> {
-    ctx.get_storage_mut()
-        .write_withdrawal_accept_event(&event)
+    let db = ctx.get_storage_mut();
+    let outcome = db.write_withdrawal_accept_event(&event).await?;
+    if !outcome.is_inserted() {
+        tracing::debug!(topic = "withdrawal-accept", "event was already recorded");
+    }
+    db.write_withdrawal_request_timestamp(event.request_id, RequestLifecycleStage::Completed)
         .await?;
 
+    delete_inflight_withdrawal_contract_calls(ctx, event.request_id).await?;
+
     tracing::debug!(topic = "withdrawal-accept", "handled stacks event");
 
     Ok(())
 }
 
+/// Clear the in-flight contract calls for the withdrawal requests with
+/// the given request ID.
+///
+/// Withdrawal accept and reject events only carry the request ID of the
+/// request that they complete, and we key in-flight contract calls on the
+/// qualified request ID, so we clear the contract calls for each request
+/// with that request ID.
+async fn delete_inflight_withdrawal_contract_calls(
+    ctx: &impl Context,
+    request_id: u64,
+) -> Result<(), Error> {
+    let db = ctx.get_storage_mut();
+    for request in db.get_withdrawal_requests_by_id(request_id).await? {
+        let target = ContractCallTarget::Withdrawal { id: request.qualified_id() };
+        db.delete_inflight_contract_call(&target).await?;
+    }
+    Ok(())
+}
+
 /// Processes a withdrawal creation event by adding the event to the database.
 ///
 /// # Parameters
@@ -234,8 +333,16 @@ async fn handle_withdrawal_create(
     ctx: &impl Context,
     event: WithdrawalRequest,
 ) -> Result<(), Error> {
-    ctx.get_storage_mut()
-        .write_withdrawal_request(&event)
+    let db = ctx.get_storage_mut();
+    let outcome = db.write_withdrawal_request(&event).await?;
+    if !outcome.is_inserted() {
+        tracing::debug!(topic = "withdrawal-create", "event was already recorded");
+    }
+
+    let stage = RequestLifecycleStage::Seen {
+        first_seen_block: ctx.state().bitcoin_chain_tip().map(|tip| tip.block_hash),
+    };
+    db.write_withdrawal_request_timestamp(event.request_id, stage)
         .await?;
 
     tracing::debug!(topic = "withdrawal-create", "handled stacks event");
@@ -259,10 +366,16 @@ async fn handle_withdrawal_reject(
     ctx: &impl Context,
     event: WithdrawalRejectEvent,
 ) -> Result<(), Error> {
-    ctx.get_storage_mut()
-        .write_withdrawal_reject_event(&event)
+    let db = ctx.get_storage_mut();
+    let outcome = db.write_withdrawal_reject_event(&event).await?;
+    if !outcome.is_inserted() {
+        tracing::debug!(topic = "withdrawal-reject", "event was already recorded");
+    }
+    db.write_withdrawal_request_timestamp(event.request_id, RequestLifecycleStage::Completed)
         .await?;
 
+    delete_inflight_withdrawal_contract_calls(ctx, event.request_id).await?;
+
     tracing::debug!(topic = "withdrawal-reject", "handled stacks event");
 
     Ok(())
@@ -274,9 +387,13 @@ async fn handle_withdrawal_reject(
     aggregate_key = %event.aggregate_key
 ))]
 async fn handle_key_rotation(ctx: &impl Context, event: KeyRotationEvent) -> Result<(), Error> {
-    ctx.get_storage_mut()
+    let outcome = ctx
+        .get_storage_mut()
         .write_rotate_keys_transaction(&event)
         .await?;
+    if !outcome.is_inserted() {
+        tracing::debug!(topic = "key-rotation", "event was already recorded");
+    }
 
     tracing::debug!(topic = "key-rotation", "handled stacks event");
 
@@ -304,6 +421,7 @@ mod tests {
     use crate::api::get_router;
     use crate::storage::memory::Store;
     use crate::storage::model::DepositRequest;
+    use crate::storage::model::InflightContractCall;
     use crate::storage::model::StacksPrincipal;
     use crate::testing::context::*;
     use crate::testing::get_rng;
@@ -471,6 +589,19 @@ mod tests {
             .await
             .expect("Failed to write deposit request");
 
+        let contract_call = InflightContractCall {
+            target: ContractCallTarget::Deposit {
+                txid: deposit_request.txid,
+                output_index: deposit_request.output_index,
+            },
+            txid: stacks_txid,
+            bitcoin_block_height: bitcoin_block.block_height,
+            bitcoin_block_hash: bitcoin_block.block_hash,
+        };
+        db.write_inflight_contract_call(&contract_call)
+            .await
+            .expect("Failed to write in-flight contract call");
+
         let event = CompletedDepositEvent {
             outpoint: deposit_request.outpoint(),
             txid: stacks_txid,
@@ -488,6 +619,8 @@ mod tests {
             db.completed_deposit_events
                 .contains_key(&deposit_request.outpoint())
         );
+        // The contract call is confirmed, so it is no longer in flight.
+        assert!(db.inflight_contract_calls.is_empty());
     }
 
     /// Tests handling a withdrawal acceptance event.
@@ -520,10 +653,28 @@ mod tests {
         let bitcoin_block = &test_data.bitcoin_blocks[0];
 
         let request_id = 1;
+        let mut request: WithdrawalRequest = fake::Faker.fake_with_rng(&mut rng);
+        request.request_id = request_id;
+        request.block_hash = stacks_block.block_hash;
+        db.write_withdrawal_request(&request)
+            .await
+            .expect("Failed to write withdrawal request");
+
+        let stacks_txid = fake::Faker.fake_with_rng(&mut rng);
+        let contract_call = InflightContractCall {
+            target: ContractCallTarget::Withdrawal { id: request.qualified_id() },
+            txid: stacks_txid,
+            bitcoin_block_height: bitcoin_block.block_height,
+            bitcoin_block_hash: bitcoin_block.block_hash,
+        };
+        db.write_inflight_contract_call(&contract_call)
+            .await
+            .expect("Failed to write in-flight contract call");
+
         let event = WithdrawalAcceptEvent {
             request_id,
             outpoint: OutPoint { txid: *txid, vout: 0 },
-            txid: fake::Faker.fake_with_rng(&mut rng),
+            txid: stacks_txid,
             block_id: stacks_block.block_hash,
             fee: 1,
             signer_bitmap: BitArray::<_>::ZERO,
@@ -538,6 +689,7 @@ mod tests {
         let db = db.lock().await;
         assert_eq!(db.withdrawal_accept_events.len(), 1);
         assert!(db.withdrawal_accept_events.contains_key(&request_id));
+        assert!(db.inflight_contract_calls.is_empty());
     }
 
     /// Tests handling of a withdrawal request.
@@ -620,6 +772,29 @@ mod tests {
             .expect("STX block generation failed");
 
         let request_id = 1;
+
+        // The same request ID on two stacks forks, each with a contract
+        // call in flight.
+        for stacks_block in &test_data.stacks_blocks[..2] {
+            let mut request: WithdrawalRequest = fake::Faker.fake_with_rng(&mut rng);
+            request.request_id = request_id;
+            request.block_hash = stacks_block.block_hash;
+            db.write_withdrawal_request(&request)
+                .await
+                .expect("Failed to write withdrawal request");
+
+            let bitcoin_block = &test_data.bitcoin_blocks[0];
+            let contract_call = InflightContractCall {
+                target: ContractCallTarget::Withdrawal { id: request.qualified_id() },
+                txid: fake::Faker.fake_with_rng(&mut rng),
+                bitcoin_block_height: bitcoin_block.block_height,
+                bitcoin_block_hash: bitcoin_block.block_hash,
+            };
+            db.write_inflight_contract_call(&contract_call)
+                .await
+                .expect("Failed to write in-flight contract call");
+        }
+
         let event = WithdrawalRejectEvent {
             request_id,
             block_id: stacks_chaintip.block_hash,
@@ -633,6 +808,7 @@ mod tests {
         let db = db.lock().await;
         assert_eq!(db.withdrawal_reject_events.len(), 1);
         assert!(db.withdrawal_reject_events.contains_key(&request_id));
+        assert!(db.inflight_contract_calls.is_empty());
     }
 
     /// Tests handling a key rotation event.
@@ -741,5 +917,14 @@ mod tests {
         // But we expect the second (valid) event to be processed anyway
         assert_eq!(res, StatusCode::OK);
         assert!(!db.lock().await.rotate_keys_transactions.is_empty());
+
+        // And the invalid event is dead-lettered so that it can be
+        // inspected and replayed later.
+        let dead_letters = db.lock().await.stacks_event_dead_letters.clone();
+        assert_eq!(dead_letters.len(), 1);
+        let dead_letter = dead_letters.values().next().unwrap();
+        assert_eq!(dead_letter.event_index, 0);
+        assert_eq!(dead_letter.attempts, 1);
+        assert!(dead_letter.resolved_at.is_none());
     }
 }
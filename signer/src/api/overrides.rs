@@ -0,0 +1,315 @@
+//! Handlers for the `/admin/overrides` endpoints, which are for placing,
+//! listing and revoking operator overrides on specific deposit and
+//! withdrawal requests.
+//!
+//! An active override forces this signer to accept or reject the request
+//! regardless of its decision policies, both when the request decider
+//! votes on the request and when we validate a sweep transaction that
+//! fulfills it. Overrides are never deleted, so listing them doubles as an
+//! audit log.
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use bitcoin::OutPoint;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    context::Context,
+    storage::{
+        DbRead as _, DbWrite as _,
+        model::{NewRequestOverride, OverrideDirective, OverrideTarget, RequestOverride},
+    },
+};
+
+use super::ApiState;
+use super::auth::AdminAuth;
+
+/// An override that an operator placed on a request.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RequestOverrideInfo {
+    /// The ID of the override.
+    pub id: i64,
+    /// The outpoint of the deposit request that the override applies to,
+    /// if it applies to a deposit request.
+    pub deposit: Option<OutPoint>,
+    /// The ID of the withdrawal request that the override applies to, if
+    /// it applies to a withdrawal request.
+    pub withdrawal_request_id: Option<u64>,
+    /// Whether the override forces the request to be accepted or
+    /// rejected.
+    pub directive: OverrideDirective,
+    /// Why the operator placed the override.
+    pub reason: String,
+    /// When the override stops applying, if ever.
+    pub expires_at: Option<String>,
+    /// When the override was placed.
+    pub created_at: String,
+    /// When the override was revoked, if it was.
+    pub revoked_at: Option<String>,
+    /// Whether the override currently applies.
+    pub active: bool,
+}
+
+impl From<RequestOverride> for RequestOverrideInfo {
+    fn from(request_override: RequestOverride) -> Self {
+        let active = request_override.is_active_at(time::OffsetDateTime::now_utc());
+        let (deposit, withdrawal_request_id) = match request_override.target {
+            OverrideTarget::Deposit { txid, output_index } => {
+                (Some(OutPoint::new(txid.into(), output_index)), None)
+            }
+            OverrideTarget::Withdrawal { request_id } => (None, Some(request_id)),
+        };
+
+        Self {
+            id: request_override.id,
+            deposit,
+            withdrawal_request_id,
+            directive: request_override.directive,
+            reason: request_override.reason,
+            expires_at: request_override.expires_at.map(|at| at.to_string()),
+            created_at: request_override.created_at.to_string(),
+            revoked_at: request_override.revoked_at.map(|at| at.to_string()),
+            active,
+        }
+    }
+}
+
+/// The body of a request to place an override. Exactly one of `deposit`
+/// and `withdrawal_request_id` must be set.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateOverrideRequest {
+    /// The outpoint of the deposit request to override.
+    pub deposit: Option<OutPoint>,
+    /// The ID of the withdrawal request to override.
+    pub withdrawal_request_id: Option<u64>,
+    /// Whether to force the request to be accepted or rejected.
+    pub directive: OverrideDirective,
+    /// Why the override is placed. This is required so that every
+    /// override can be accounted for later.
+    pub reason: String,
+    /// How many seconds the override applies for. The override applies
+    /// until it is revoked when this is not set.
+    pub expires_in_secs: Option<u64>,
+}
+
+impl CreateOverrideRequest {
+    /// Convert the request into the override to store, or return `None`
+    /// if the request is invalid.
+    fn into_new_override(self) -> Option<NewRequestOverride> {
+        let target = match (self.deposit, self.withdrawal_request_id) {
+            (Some(outpoint), None) => OverrideTarget::Deposit {
+                txid: outpoint.txid.into(),
+                output_index: outpoint.vout,
+            },
+            (None, Some(request_id)) => OverrideTarget::Withdrawal { request_id },
+            _ => return None,
+        };
+
+        let reason = self.reason.trim().to_string();
+        if reason.is_empty() {
+            return None;
+        }
+
+        let expires_at = match self.expires_in_secs {
+            Some(secs) => {
+                let duration = time::Duration::seconds(i64::try_from(secs).ok()?);
+                let expires_at = time::OffsetDateTime::now_utc().checked_add(duration)?;
+                Some(expires_at.into())
+            }
+            None => None,
+        };
+
+        Some(NewRequestOverride {
+            target,
+            directive: self.directive,
+            reason,
+            expires_at,
+        })
+    }
+}
+
+/// The response listing every override that was ever placed.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OverridesResponse {
+    /// The overrides, newest first, including revoked and expired ones.
+    pub overrides: Vec<RequestOverrideInfo>,
+}
+
+/// Handler for `GET /admin/overrides`.
+pub async fn overrides_handler<C: Context>(
+    _: AdminAuth,
+    state: State<ApiState<C>>,
+) -> Result<Json<OverridesResponse>, StatusCode> {
+    let overrides = state
+        .ctx
+        .get_storage()
+        .get_request_overrides()
+        .await
+        .inspect_err(|error| tracing::warn!(%error, "could not fetch request overrides"))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .into_iter()
+        .map(RequestOverrideInfo::from)
+        .collect();
+
+    Ok(Json(OverridesResponse { overrides }))
+}
+
+/// Handler for `POST /admin/overrides`. It responds with a `400 Bad
+/// Request` unless the body identifies exactly one request and gives a
+/// reason for the override.
+pub async fn create_override_handler<C: Context>(
+    _: AdminAuth,
+    state: State<ApiState<C>>,
+    Json(body): Json<CreateOverrideRequest>,
+) -> Result<Json<RequestOverrideInfo>, StatusCode> {
+    let new_override = body.into_new_override().ok_or(StatusCode::BAD_REQUEST)?;
+
+    let request_override = state
+        .ctx
+        .get_storage_mut()
+        .write_request_override(&new_override)
+        .await
+        .inspect_err(|error| tracing::warn!(%error, "could not write a request override"))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    tracing::warn!(
+        override_id = %request_override.id,
+        target = ?request_override.target,
+        directive = %request_override.directive,
+        reason = %request_override.reason,
+        expires_at = ?request_override.expires_at,
+        "operator placed a request override"
+    );
+
+    Ok(Json(request_override.into()))
+}
+
+/// Handler for `POST /admin/overrides/{id}/revoke`. It responds with a
+/// `404 Not Found` if there is no override with the given ID that has
+/// not been revoked already.
+pub async fn revoke_override_handler<C: Context>(
+    _: AdminAuth,
+    state: State<ApiState<C>>,
+    Path(id): Path<i64>,
+) -> StatusCode {
+    let revoked = state
+        .ctx
+        .get_storage_mut()
+        .revoke_request_override(id)
+        .await
+        .inspect_err(|error| tracing::warn!(%error, "could not revoke a request override"));
+
+    match revoked {
+        Ok(true) => {
+            tracing::warn!(override_id = %id, "operator revoked a request override");
+            StatusCode::NO_CONTENT
+        }
+        Ok(false) => StatusCode::NOT_FOUND,
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fake::Fake as _;
+
+    use super::*;
+    use crate::storage::model::BitcoinTxId;
+    use crate::testing::context::*;
+
+    #[tokio::test]
+    async fn overrides_can_be_placed_listed_and_revoked() {
+        let ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .build();
+
+        let txid: BitcoinTxId = fake::Faker.fake();
+        let outpoint = OutPoint::new(txid.into(), 1);
+        let body = CreateOverrideRequest {
+            deposit: Some(outpoint),
+            withdrawal_request_id: None,
+            directive: OverrideDirective::ForceReject,
+            reason: "law enforcement request".to_string(),
+            expires_in_secs: None,
+        };
+
+        let state = State(ApiState { ctx: ctx.clone() });
+        let Json(placed) = create_override_handler(AdminAuth, state, Json(body))
+            .await
+            .unwrap();
+        assert_eq!(placed.deposit, Some(outpoint));
+        assert!(placed.active);
+
+        let target = OverrideTarget::Deposit {
+            txid: outpoint.txid.into(),
+            output_index: outpoint.vout,
+        };
+        let db = ctx.get_storage();
+        let active = db.get_active_request_override(&target).await.unwrap();
+        assert_eq!(active.map(|active| active.id), Some(placed.id));
+
+        let state = State(ApiState { ctx: ctx.clone() });
+        let status = revoke_override_handler(AdminAuth, state, Path(placed.id)).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+
+        // Revoking an override twice is not possible.
+        let state = State(ApiState { ctx: ctx.clone() });
+        let status = revoke_override_handler(AdminAuth, state, Path(placed.id)).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        // The revoked override no longer applies, but it is still listed.
+        let active = db.get_active_request_override(&target).await.unwrap();
+        assert!(active.is_none());
+
+        let state = State(ApiState { ctx: ctx.clone() });
+        let Json(listed) = overrides_handler(AdminAuth, state).await.unwrap();
+        assert_eq!(listed.overrides.len(), 1);
+        assert!(!listed.overrides[0].active);
+        assert!(listed.overrides[0].revoked_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn overrides_must_identify_one_request_and_have_a_reason() {
+        let ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .build();
+
+        let invalid_bodies = [
+            CreateOverrideRequest {
+                deposit: None,
+                withdrawal_request_id: None,
+                directive: OverrideDirective::ForceAccept,
+                reason: "no request".to_string(),
+                expires_in_secs: None,
+            },
+            CreateOverrideRequest {
+                deposit: Some(OutPoint::null()),
+                withdrawal_request_id: Some(1),
+                directive: OverrideDirective::ForceAccept,
+                reason: "two requests".to_string(),
+                expires_in_secs: None,
+            },
+            CreateOverrideRequest {
+                deposit: None,
+                withdrawal_request_id: Some(1),
+                directive: OverrideDirective::ForceReject,
+                reason: "  ".to_string(),
+                expires_in_secs: None,
+            },
+        ];
+
+        for body in invalid_bodies {
+            let state = State(ApiState { ctx: ctx.clone() });
+            let result = create_override_handler(AdminAuth, state, Json(body)).await;
+            assert_eq!(result.unwrap_err(), StatusCode::BAD_REQUEST);
+        }
+
+        let overrides = ctx.get_storage().get_request_overrides().await.unwrap();
+        assert!(overrides.is_empty());
+    }
+}
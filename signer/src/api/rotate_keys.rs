@@ -0,0 +1,62 @@
+//! Handler for the `/admin/rotate-keys/dry-run` endpoint, which tells
+//! operators whether the signers would accept the rotate-keys contract
+//! call for the latest DKG shares, before a key rotation is attempted.
+//!
+//! The dry run asks every signer to validate the contract call and
+//! reports each signer's verdict. Nothing is signed or broadcast to the
+//! stacks network.
+
+use axum::{Json, extract::State, http::StatusCode};
+
+use crate::{
+    context::Context,
+    error::Error,
+    transaction_coordinator::{RotateKeysDryRunReport, rotate_keys_dry_run},
+};
+
+use super::ApiState;
+use super::auth::AdminAuth;
+
+/// Handler for `POST /admin/rotate-keys/dry-run`. It responds with a `409
+/// Conflict` if there are no DKG shares to rotate into, and with a `503
+/// Service Unavailable` if the signer has not processed a bitcoin block
+/// yet.
+pub async fn rotate_keys_dry_run_handler<C: Context>(
+    _: AdminAuth,
+    state: State<ApiState<C>>,
+) -> Result<Json<RotateKeysDryRunReport>, StatusCode> {
+    let report = rotate_keys_dry_run(&state.ctx)
+        .await
+        .inspect_err(|error| tracing::warn!(%error, "rotate-keys dry run failed"))
+        .map_err(|error| match error {
+            Error::NoDkgShares | Error::MissingDkgShares(_) => StatusCode::CONFLICT,
+            Error::NoChainTip => StatusCode::SERVICE_UNAVAILABLE,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        })?;
+
+    tracing::info!(
+        aggregate_key = %report.aggregate_key,
+        would_pass = %report.would_pass,
+        "operator ran a rotate-keys dry run"
+    );
+
+    Ok(Json(report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::context::*;
+
+    #[tokio::test]
+    async fn dry_run_needs_dkg_shares() {
+        let ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .build();
+
+        let state = State(ApiState { ctx });
+        let result = rotate_keys_dry_run_handler(AdminAuth, state).await;
+        assert_eq!(result.unwrap_err(), StatusCode::CONFLICT);
+    }
+}
@@ -11,7 +11,10 @@ use crate::context::Context;
 
 use axum::http::StatusCode;
 
-use super::{ApiState, info, new_block, status};
+use super::{
+    ApiState, dead_letters, deposit_precheck, descriptors, health, info, new_block, overrides,
+    rotate_keys, status, sweep_tx_proofs, votes,
+};
 
 async fn new_attachment_handler() -> StatusCode {
     StatusCode::OK
@@ -22,6 +25,21 @@ pub fn get_router<C: Context + 'static>(new_block_limit: usize) -> Router<ApiSta
     Router::new()
         .route("/", get(status::status_handler))
         .route("/info", get(info::info_handler))
+        .route("/healthz", get(health::healthz_handler))
+        .route("/readyz", get(health::readyz_handler))
+        .route("/descriptors", get(descriptors::descriptors_handler))
+        .route(
+            "/deposits/{txid}/{output_index}/votes",
+            get(votes::deposit_votes_handler),
+        )
+        .route(
+            "/withdrawals/{request_id}/votes",
+            get(votes::withdrawal_votes_handler),
+        )
+        .route(
+            "/sweeps/{txid}/proof",
+            get(sweep_tx_proofs::sweep_tx_proof_handler),
+        )
         .route(
             "/new_block",
             post(new_block::new_block_handler).layer(DefaultBodyLimit::max(new_block_limit)),
@@ -31,6 +49,37 @@ pub fn get_router<C: Context + 'static>(new_block_limit: usize) -> Router<ApiSta
         .route("/attachments/new", post(new_attachment_handler))
 }
 
+/// Return the router for the `/admin` endpoints. These are served on their
+/// own listener, bound to `signer.event_observer.admin_bind`, and never
+/// alongside the routes of [`get_router`].
+pub fn get_admin_router<C: Context + 'static>() -> Router<ApiState<C>> {
+    Router::new()
+        .route(
+            "/admin/dead-letters",
+            get(dead_letters::dead_letters_handler),
+        )
+        .route(
+            "/admin/dead-letters/replay",
+            post(dead_letters::replay_dead_letters_handler),
+        )
+        .route(
+            "/admin/deposits/precheck",
+            post(deposit_precheck::deposit_precheck_handler),
+        )
+        .route(
+            "/admin/overrides",
+            get(overrides::overrides_handler).post(overrides::create_override_handler),
+        )
+        .route(
+            "/admin/overrides/{id}/revoke",
+            post(overrides::revoke_override_handler),
+        )
+        .route(
+            "/admin/rotate-keys/dry-run",
+            post(rotate_keys::rotate_keys_dry_run_handler),
+        )
+}
+
 #[cfg(test)]
 mod tests {
     use axum::{
@@ -41,7 +90,10 @@ mod tests {
     use tower::ServiceExt as _;
 
     use crate::{
-        api::{ApiState, router::get_router},
+        api::{
+            ApiState,
+            router::{get_admin_router, get_router},
+        },
         testing::context::TestContext,
     };
 
@@ -61,4 +113,37 @@ mod tests {
         let response = app.oneshot(request).await.unwrap();
         assert_eq!(response.status(), StatusCode::OK);
     }
+
+    #[tokio::test]
+    async fn admin_routes_are_not_served_by_the_public_router() {
+        let context = TestContext::default_mocked();
+
+        let state = ApiState { ctx: context.clone() };
+        let app: Router = get_router(crate::NEW_BLOCK_BODY_LIMIT).with_state(state.clone());
+        let admin: Router = get_admin_router().with_state(state);
+
+        for (method, uri) in [
+            (Method::GET, "/admin/dead-letters"),
+            (Method::POST, "/admin/dead-letters/replay"),
+            (Method::POST, "/admin/deposits/precheck"),
+            (Method::GET, "/admin/overrides"),
+            (Method::POST, "/admin/rotate-keys/dry-run"),
+        ] {
+            let request = || {
+                Request::builder()
+                    .uri(uri)
+                    .method(method.clone())
+                    .body(Body::empty())
+                    .unwrap()
+            };
+
+            let response = app.clone().oneshot(request()).await.unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+            // The admin router has the route, but we did not send the
+            // admin token.
+            let response = admin.clone().oneshot(request()).await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+    }
 }
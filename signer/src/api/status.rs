@@ -1,8 +1,72 @@
-//! This module is for the `GET /` endpoint, which just returns the status.
+//! This module is for the `GET /` endpoint, which returns the status of
+//! the signer's subsystems.
 
-use axum::http::StatusCode;
+use std::collections::BTreeMap;
 
-/// A basic handler that responds with 200 OK
-pub async fn status_handler() -> StatusCode {
-    StatusCode::OK
+use axum::{Json, extract::State};
+use serde::{Deserialize, Serialize};
+
+use crate::context::{Context, Subsystem, SubsystemHealth};
+
+use super::ApiState;
+
+/// The status of the signer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusResponse {
+    /// Whether any of the signer's subsystems is degraded, meaning that
+    /// the most recent outcome recorded for it was an error.
+    pub degraded: bool,
+    /// The most recent error, along with error and recovery counts, of
+    /// each of the signer's subsystems.
+    pub subsystems: BTreeMap<Subsystem, SubsystemHealth>,
+}
+
+/// A basic handler that responds with 200 OK and the status of the
+/// signer's subsystems. The signer is up even when some subsystems are
+/// degraded, so this always responds with 200 OK.
+pub async fn status_handler<C: Context>(state: State<ApiState<C>>) -> Json<StatusResponse> {
+    let errors = state.ctx.state().error_registry();
+    Json(StatusResponse {
+        degraded: errors.is_degraded(),
+        subsystems: errors.snapshot(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::testing::context::TestContext;
+
+    #[tokio::test]
+    async fn status_shows_degraded_subsystems() {
+        let ctx = TestContext::default_mocked();
+        let state = State(ApiState { ctx: ctx.clone() });
+
+        let Json(status) = status_handler(state.clone()).await;
+        assert!(!status.degraded);
+        assert!(status.subsystems.values().all(|health| !health.degraded));
+
+        let errors = ctx.state().error_registry();
+        errors.record_error(Subsystem::EmilyClient, &"emily is down");
+
+        let Json(status) = status_handler(state.clone()).await;
+        assert!(status.degraded);
+        let emily = &status.subsystems[&Subsystem::EmilyClient];
+        assert!(emily.degraded);
+        assert_eq!(emily.last_error.as_deref(), Some("emily is down"));
+        assert_eq!(emily.error_count, 1);
+
+        // The subsystem recovers once it succeeds again.
+        errors.record_success(Subsystem::EmilyClient);
+
+        let Json(status) = status_handler(state).await;
+        assert!(!status.degraded);
+        let emily = &status.subsystems[&Subsystem::EmilyClient];
+        assert_eq!(emily.recovery_count, 1);
+
+        // The status is keyed by the snake case subsystem names.
+        let json = serde_json::to_value(&status).unwrap();
+        assert_eq!(json["subsystems"]["emily_client"]["error_count"], 1);
+    }
 }
@@ -0,0 +1,388 @@
+//! Handler for the `/sweeps/{txid}/proof` endpoint, which serves proofs
+//! that sweep transactions were confirmed on the canonical bitcoin
+//! blockchain.
+//!
+//! The proofs are built from the merkle proofs and block headers that the
+//! block observer stores, so that light clients, usually through a gateway
+//! that relays their queries, can verify peg-outs without running a
+//! bitcoin node of their own. The endpoint is only open to clients with
+//! one of the configured proof API tokens, and each client is rate
+//! limited on its own.
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use bitcoin::MerkleBlock;
+use bitcoin::block::Header;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    context::Context,
+    error::Error,
+    metrics::Metrics,
+    storage::{
+        DbRead as _,
+        model::{BitcoinBlockHash, BitcoinBlockHeight, BitcoinTxId},
+    },
+};
+
+use super::ApiState;
+use super::auth::ProofApiClient;
+
+/// A proof that a sweep transaction was confirmed in a bitcoin block.
+///
+/// The merkle block commits to the transaction under the merkle root in
+/// the header of the block that confirmed it, and the headers that follow
+/// chain onto that header. A light client checks the proof of work of the
+/// headers itself, and the number of headers is the number of
+/// confirmations that the proof attests to beyond the first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SweepTxProof {
+    /// The ID of the sweep transaction.
+    pub txid: bitcoin::Txid,
+    /// The height of the bitcoin block that confirmed the transaction.
+    pub block_height: BitcoinBlockHeight,
+    /// The header of the bitcoin block that confirmed the transaction,
+    /// along with a partial merkle tree that matches the transaction,
+    /// consensus encoded as hex.
+    #[serde(with = "bitcoin::consensus::serde::With::<bitcoin::consensus::serde::Hex>")]
+    pub merkle_block: MerkleBlock,
+    /// The headers of the bitcoin blocks that build on the block that
+    /// confirmed the transaction, ordered by height.
+    pub headers: Vec<Header>,
+}
+
+impl SweepTxProof {
+    /// The maximum number of headers, of the blocks that build on the
+    /// block that confirmed the sweep transaction, in a proof. This is
+    /// about a day's worth of blocks.
+    pub const MAX_HEADERS: u16 = 144;
+
+    /// Whether the merkle block matches the transaction of the proof, and
+    /// only that transaction, under the merkle root of its header, and
+    /// each of the headers builds on the one before it.
+    pub fn is_valid(&self) -> bool {
+        let mut matches = Vec::new();
+        let mut indexes = Vec::new();
+        let proves_inclusion = self
+            .merkle_block
+            .extract_matches(&mut matches, &mut indexes)
+            .is_ok()
+            && matches == [self.txid];
+
+        let mut block_hash = self.merkle_block.header.block_hash();
+        let headers_link = self.headers.iter().all(|header| {
+            let links = header.prev_blockhash == block_hash;
+            block_hash = header.block_hash();
+            links
+        });
+
+        proves_inclusion && headers_link
+    }
+}
+
+/// Build the proof that the sweep transaction with the given ID was
+/// confirmed on the canonical bitcoin blockchain identified by the given
+/// chain tip, if we have what it takes.
+pub(crate) async fn get_sweep_tx_proof<C: Context>(
+    ctx: &C,
+    chain_tip: &BitcoinBlockHash,
+    txid: bitcoin::Txid,
+) -> Result<Option<SweepTxProof>, Error> {
+    let db = ctx.get_storage();
+    let txid_model = BitcoinTxId::from(txid);
+    let Some(merkle_proof) = db.get_sweep_tx_merkle_proof(chain_tip, &txid_model).await? else {
+        return Ok(None);
+    };
+
+    let headers = db
+        .get_bitcoin_block_headers(
+            chain_tip,
+            merkle_proof.block_height + 1,
+            SweepTxProof::MAX_HEADERS,
+        )
+        .await?;
+
+    // Headers of blocks that we processed before we started storing them
+    // are missing, so we only keep the headers up to the first gap in the
+    // chain.
+    let mut block_hash = merkle_proof.merkle_block.header.block_hash();
+    let headers = headers
+        .into_iter()
+        .take_while(|header| {
+            let links = header.prev_blockhash == block_hash;
+            block_hash = header.block_hash();
+            links
+        })
+        .collect();
+
+    Ok(Some(SweepTxProof {
+        txid,
+        block_height: merkle_proof.block_height,
+        merkle_block: merkle_proof.merkle_block,
+        headers,
+    }))
+}
+
+/// Handler for `GET /sweeps/{txid}/proof`. It responds with a `401
+/// Unauthorized` unless the request carries one of the configured proof
+/// API tokens, with a `429 Too Many Requests` if the client has exceeded
+/// its rate limit, with a `503 Service Unavailable` if the signer has not
+/// processed a bitcoin block yet, and with a `404 Not Found` if we do not
+/// have a record of the sweep transaction being confirmed on our
+/// canonical bitcoin blockchain.
+pub async fn sweep_tx_proof_handler<C: Context>(
+    ProofApiClient(client): ProofApiClient,
+    state: State<ApiState<C>>,
+    Path(txid): Path<bitcoin::Txid>,
+) -> Result<Json<SweepTxProof>, StatusCode> {
+    let max_per_minute = state
+        .ctx
+        .config()
+        .signer
+        .event_observer
+        .proof_api_requests_per_minute;
+    if !state
+        .ctx
+        .state()
+        .check_proof_request_rate(client, max_per_minute)
+    {
+        tracing::debug!(client, %txid, "rate limiting sweep transaction proof request");
+        Metrics::increment_sweep_tx_proof_requests("rate_limited");
+        return Err(StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    let chain_tip = state
+        .ctx
+        .state()
+        .bitcoin_chain_tip()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+
+    let proof = get_sweep_tx_proof(&state.ctx, &chain_tip.block_hash, txid)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, %txid, "could not build the sweep transaction proof");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let status = if proof.is_some() {
+        "found"
+    } else {
+        "not_found"
+    };
+    tracing::debug!(client, %txid, status, "answering sweep transaction proof request");
+    Metrics::increment_sweep_tx_proof_requests(status);
+
+    proof.map(Json).ok_or(StatusCode::NOT_FOUND)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::slice;
+
+    use axum::{
+        Router,
+        body::Body,
+        http::{Request, header::AUTHORIZATION},
+    };
+    use fake::Fake as _;
+    use fake::Faker;
+    use rand::SeedableRng as _;
+    use tower::ServiceExt as _;
+
+    use crate::api::client::SignerApiClient;
+    use crate::api::get_router;
+    use crate::storage::DbWrite as _;
+    use crate::storage::memory::SharedStore;
+    use crate::storage::model;
+    use crate::testing::context::*;
+    use crate::testing::dummy;
+
+    use super::*;
+
+    type MockedContext = TestContext<
+        SharedStore,
+        WrappedMockBitcoinInteract,
+        WrappedMockStacksInteract,
+        WrappedMockEmilyInteract,
+    >;
+
+    /// Mine a header on top of the given parent, without any proof of
+    /// work, which nothing here checks.
+    fn header(prev_blockhash: bitcoin::BlockHash, rng: &mut impl rand::RngCore) -> Header {
+        Header {
+            version: bitcoin::block::Version::TWO,
+            prev_blockhash,
+            merkle_root: dummy::merkle_root(&Faker, rng),
+            time: Faker.fake_with_rng(rng),
+            bits: bitcoin::CompactTarget::from_consensus(0x207fffff),
+            nonce: Faker.fake_with_rng(rng),
+        }
+    }
+
+    /// Store a chain of five bitcoin blocks, with a sweep transaction
+    /// confirmed in the second one, and make the last block the chain
+    /// tip. Returns the ID of the sweep transaction and the headers of
+    /// the blocks.
+    async fn store_sweep(ctx: &MockedContext) -> (bitcoin::Txid, Vec<Header>) {
+        let rng = &mut rand::rngs::StdRng::seed_from_u64(51);
+        let db = ctx.get_storage_mut();
+
+        let mut headers = vec![header(dummy::block_hash(&Faker, rng), rng)];
+        for _ in 1..5 {
+            let parent = headers.last().unwrap().block_hash();
+            headers.push(header(parent, rng));
+        }
+        for (height, header) in headers.iter().enumerate() {
+            let block = model::BitcoinBlock {
+                block_hash: header.block_hash().into(),
+                block_height: (height as u64).into(),
+                parent_hash: header.prev_blockhash.into(),
+            };
+            db.write_bitcoin_block(&block).await.unwrap();
+            db.write_bitcoin_block_header(header).await.unwrap();
+        }
+
+        let txid: bitcoin::Txid = dummy::txid(&Faker, rng);
+        let txids = [txid, dummy::txid(&Faker, rng)];
+        let proof = model::BitcoinTxMerkleProof {
+            txid: txid.into(),
+            block_hash: headers[1].block_hash().into(),
+            block_height: 1u64.into(),
+            merkle_block: MerkleBlock::from_header_txids_with_predicate(
+                &headers[1],
+                &txids,
+                |other| *other == txid,
+            ),
+        };
+        db.write_bitcoin_tx_merkle_proofs(slice::from_ref(&proof))
+            .await
+            .unwrap();
+
+        let prevout = model::TxPrevout {
+            txid: txid.into(),
+            prevout_type: model::TxPrevoutType::SignersInput,
+            ..Faker.fake_with_rng(rng)
+        };
+        db.write_tx_prevout(&prevout).await.unwrap();
+
+        ctx.state().set_bitcoin_chain_tip(model::BitcoinBlockRef {
+            block_hash: headers[4].block_hash().into(),
+            block_height: 4u64.into(),
+        });
+
+        (txid, headers)
+    }
+
+    fn context(tokens: &[&str], requests_per_minute: u32) -> MockedContext {
+        let mut ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .build();
+        let event_observer = &mut ctx.config_mut().signer.event_observer;
+        event_observer.proof_api_tokens = tokens.iter().map(ToString::to_string).collect();
+        event_observer.proof_api_requests_per_minute = requests_per_minute;
+        ctx
+    }
+
+    async fn get_status(app: &Router, txid: bitcoin::Txid, token: Option<&str>) -> StatusCode {
+        let mut request = Request::builder().uri(format!("/sweeps/{txid}/proof"));
+        if let Some(token) = token {
+            request = request.header(AUTHORIZATION, format!("Bearer {token}"));
+        }
+        let request = request.body(Body::empty()).unwrap();
+        app.clone().oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn sweep_tx_proofs_are_only_valid_for_their_transaction() {
+        let ctx = context(&["gateway"], 60);
+        let (txid, _) = store_sweep(&ctx).await;
+        let chain_tip = ctx.state().bitcoin_chain_tip().unwrap();
+
+        let mut proof = get_sweep_tx_proof(&ctx, &chain_tip.block_hash, txid)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(proof.is_valid());
+        // The proof is for a block at height 1, and we know of the
+        // headers up to height 4.
+        assert_eq!(proof.headers.len(), 3);
+
+        let mut other = proof.clone();
+        other.txid = dummy::txid(&Faker, &mut rand::rngs::OsRng);
+        assert!(!other.is_valid());
+
+        // Headers must chain onto the block that confirmed the
+        // transaction.
+        let header = proof.merkle_block.header;
+        proof.headers.insert(0, header);
+        assert!(!proof.is_valid());
+    }
+
+    #[tokio::test]
+    async fn sweep_tx_proofs_need_a_token_and_are_rate_limited() {
+        let ctx = context(&["gateway-1", "gateway-2"], 2);
+        let app: Router =
+            get_router(crate::NEW_BLOCK_BODY_LIMIT).with_state(ApiState { ctx: ctx.clone() });
+        let txid = dummy::txid(&Faker, &mut rand::rngs::OsRng);
+
+        assert_eq!(get_status(&app, txid, None).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            get_status(&app, txid, Some("gateway-3")).await,
+            StatusCode::UNAUTHORIZED
+        );
+
+        // There is no chain tip yet.
+        assert_eq!(
+            get_status(&app, txid, Some("gateway-1")).await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+
+        store_sweep(&ctx).await;
+        assert_eq!(
+            get_status(&app, txid, Some("gateway-1")).await,
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            get_status(&app, txid, Some("gateway-1")).await,
+            StatusCode::TOO_MANY_REQUESTS
+        );
+
+        // The other client has its own rate limit.
+        assert_eq!(
+            get_status(&app, txid, Some("gateway-2")).await,
+            StatusCode::NOT_FOUND
+        );
+    }
+
+    #[tokio::test]
+    async fn clients_can_fetch_and_check_sweep_tx_proofs() {
+        let ctx = context(&["gateway"], 60);
+        let (txid, headers) = store_sweep(&ctx).await;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let app: Router = get_router(crate::NEW_BLOCK_BODY_LIMIT).with_state(ApiState { ctx });
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let client = SignerApiClient::new(endpoint.parse().unwrap())
+            .unwrap()
+            .with_proof_api_token("gateway");
+
+        let proof = client.sweep_tx_proof(txid).await.unwrap().unwrap();
+        assert_eq!(proof.txid, txid);
+        assert_eq!(*proof.block_height, 1);
+        assert_eq!(proof.merkle_block.header, headers[1]);
+        assert_eq!(proof.headers, headers[2..]);
+
+        let other_txid = dummy::txid(&Faker, &mut rand::rngs::OsRng);
+        assert!(client.sweep_tx_proof(other_txid).await.unwrap().is_none());
+
+        let client = SignerApiClient::new(endpoint.parse().unwrap()).unwrap();
+        let error = client.sweep_tx_proof(txid).await.unwrap_err();
+        assert!(matches!(error, Error::SignerApiResponse(_)));
+    }
+}
@@ -0,0 +1,317 @@
+//! Handlers for the `/deposits/{txid}/{output_index}/votes` and
+//! `/withdrawals/{request_id}/votes` endpoints, which tally how the
+//! current signer set voted on a deposit or withdrawal request.
+//!
+//! The tallies are derived from the votes that this signer has received
+//! and stored, so a signer that has voted may show up as not having voted
+//! yet if its vote has not reached us. A request is only considered for a
+//! sweep transaction once enough signers in the current signer set have
+//! voted to accept it, so the tally shows what is holding a request back.
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use bitcoin::OutPoint;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    context::Context,
+    error::Error,
+    keys::PublicKey,
+    storage::{
+        DbRead as _,
+        model::{SignerVotes, StacksBlockHash, StacksTxId},
+    },
+};
+
+use super::ApiState;
+
+/// How the signers in the current signer set voted on a request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VoteTally {
+    /// The aggregate key of the signer set that the votes are tallied
+    /// for.
+    pub aggregate_key: PublicKey,
+    /// The number of signers that voted to accept the request.
+    pub accepted: u16,
+    /// The number of signers that voted to reject the request.
+    pub rejected: u16,
+    /// The number of votes to accept the request that are needed for it
+    /// to be swept.
+    pub signature_threshold: u16,
+    /// Whether enough signers voted to accept the request for it to be
+    /// swept.
+    pub threshold_met: bool,
+    /// The signers that voted to accept the request.
+    pub accepted_by: Vec<PublicKey>,
+    /// The signers that voted to reject the request.
+    pub rejected_by: Vec<PublicKey>,
+    /// The signers that we do not have a vote from.
+    pub not_voted: Vec<PublicKey>,
+}
+
+impl VoteTally {
+    /// Tally the given votes of the signer set with the given aggregate
+    /// key.
+    pub fn new(aggregate_key: PublicKey, votes: &SignerVotes, signature_threshold: u16) -> Self {
+        let mut accepted_by = Vec::new();
+        let mut rejected_by = Vec::new();
+        let mut not_voted = Vec::new();
+        for vote in votes.iter() {
+            match vote.is_accepted {
+                Some(true) => accepted_by.push(vote.signer_public_key),
+                Some(false) => rejected_by.push(vote.signer_public_key),
+                None => not_voted.push(vote.signer_public_key),
+            }
+        }
+
+        let accepted = u16::try_from(accepted_by.len()).unwrap_or(u16::MAX);
+        let rejected = u16::try_from(rejected_by.len()).unwrap_or(u16::MAX);
+        Self {
+            aggregate_key,
+            accepted,
+            rejected,
+            signature_threshold,
+            threshold_met: accepted >= signature_threshold,
+            accepted_by,
+            rejected_by,
+            not_voted,
+        }
+    }
+}
+
+/// The response to a request for the votes on a deposit request.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct DepositVotesResponse {
+    /// The outpoint of the deposit request.
+    pub outpoint: OutPoint,
+    /// How the current signer set voted on the deposit request.
+    pub tally: VoteTally,
+}
+
+/// How the current signer set voted on a withdrawal request that was
+/// created in a specific stacks block.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct WithdrawalVotes {
+    /// The ID of the stacks transaction that created the withdrawal
+    /// request.
+    pub txid: StacksTxId,
+    /// The stacks block that the withdrawal request was created in.
+    pub block_hash: StacksBlockHash,
+    /// How the current signer set voted on the withdrawal request.
+    pub tally: VoteTally,
+}
+
+/// The response to a request for the votes on a withdrawal request.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct WithdrawalVotesResponse {
+    /// The ID of the withdrawal request.
+    pub request_id: u64,
+    /// The votes on each withdrawal request with the ID. There is more
+    /// than one only if the request was created on different forks of the
+    /// stacks blockchain.
+    pub votes: Vec<WithdrawalVotes>,
+}
+
+/// Return the aggregate key of the current signer set along with the
+/// number of votes to accept a request that it needs, the same way that
+/// the coordinator does when it picks the requests to sweep. Returns
+/// `None` if the signers do not have an aggregate key yet.
+async fn current_signer_set<C: Context>(ctx: &C) -> Result<Option<(PublicKey, u16)>, Error> {
+    let signature_threshold = ctx.config().signer.bootstrap_signatures_required;
+    let aggregate_key = match ctx.state().registry_signer_set_info() {
+        Some(info) => Some(info.aggregate_key),
+        None => ctx
+            .get_storage()
+            .get_latest_encrypted_dkg_shares()
+            .await?
+            .map(|shares| shares.aggregate_key),
+    };
+
+    Ok(aggregate_key.map(|key| (key, signature_threshold)))
+}
+
+/// Log the error from tallying the votes on a request and convert it
+/// into a `500 Internal Server Error`.
+fn internal_error(error: Error) -> StatusCode {
+    tracing::warn!(%error, "could not tally the votes on a request");
+    StatusCode::INTERNAL_SERVER_ERROR
+}
+
+/// Handler for `GET /deposits/{txid}/{output_index}/votes`. It responds
+/// with a `404 Not Found` if we do not have a record of the deposit
+/// request, and with a `503 Service Unavailable` if the signers do not
+/// have an aggregate key yet.
+pub async fn deposit_votes_handler<C: Context>(
+    state: State<ApiState<C>>,
+    Path((txid, output_index)): Path<(bitcoin::Txid, u32)>,
+) -> Result<Json<DepositVotesResponse>, StatusCode> {
+    let db = state.ctx.get_storage();
+    let (aggregate_key, threshold) = current_signer_set(&state.ctx)
+        .await
+        .map_err(internal_error)?
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+
+    let request_txid = txid.into();
+    db.get_deposit_request(&request_txid, output_index)
+        .await
+        .map_err(internal_error)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let votes = db
+        .get_deposit_request_signer_votes(&request_txid, output_index, &aggregate_key)
+        .await
+        .map_err(internal_error)?;
+
+    Ok(Json(DepositVotesResponse {
+        outpoint: OutPoint::new(txid, output_index),
+        tally: VoteTally::new(aggregate_key, &votes, threshold),
+    }))
+}
+
+/// Handler for `GET /withdrawals/{request_id}/votes`. It responds with a
+/// `404 Not Found` if we do not have a record of the withdrawal request,
+/// and with a `503 Service Unavailable` if the signers do not have an
+/// aggregate key yet.
+pub async fn withdrawal_votes_handler<C: Context>(
+    state: State<ApiState<C>>,
+    Path(request_id): Path<u64>,
+) -> Result<Json<WithdrawalVotesResponse>, StatusCode> {
+    let db = state.ctx.get_storage();
+    let (aggregate_key, threshold) = current_signer_set(&state.ctx)
+        .await
+        .map_err(internal_error)?
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+
+    let requests = db
+        .get_withdrawal_requests_by_id(request_id)
+        .await
+        .map_err(internal_error)?;
+    if requests.is_empty() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let mut votes = Vec::with_capacity(requests.len());
+    for request in requests {
+        let id = request.qualified_id();
+        let signer_votes = db
+            .get_withdrawal_request_signer_votes(&id, &aggregate_key)
+            .await
+            .map_err(internal_error)?;
+
+        votes.push(WithdrawalVotes {
+            txid: id.txid,
+            block_hash: id.block_hash,
+            tally: VoteTally::new(aggregate_key, &signer_votes, threshold),
+        });
+    }
+
+    Ok(Json(WithdrawalVotesResponse { request_id, votes }))
+}
+
+#[cfg(test)]
+mod tests {
+    use fake::Fake as _;
+    use fake::Faker;
+
+    use crate::stacks::api::SignerSetInfo;
+    use crate::storage::DbWrite as _;
+    use crate::storage::model;
+    use crate::testing::context::*;
+
+    use super::*;
+
+    #[test]
+    fn vote_tally_counts_each_kind_of_vote() {
+        let signers: Vec<PublicKey> = std::iter::repeat_with(|| Faker.fake()).take(4).collect();
+        let votes = SignerVotes::from(vec![
+            model::SignerVote {
+                signer_public_key: signers[0],
+                is_accepted: Some(true),
+            },
+            model::SignerVote {
+                signer_public_key: signers[1],
+                is_accepted: Some(true),
+            },
+            model::SignerVote {
+                signer_public_key: signers[2],
+                is_accepted: Some(false),
+            },
+            model::SignerVote {
+                signer_public_key: signers[3],
+                is_accepted: None,
+            },
+        ]);
+        let aggregate_key: PublicKey = Faker.fake();
+
+        let tally = VoteTally::new(aggregate_key, &votes, 2);
+        assert_eq!(tally.accepted, 2);
+        assert_eq!(tally.rejected, 1);
+        assert!(tally.threshold_met);
+        assert_eq!(tally.rejected_by, vec![signers[2]]);
+        assert_eq!(tally.not_voted, vec![signers[3]]);
+
+        let tally = VoteTally::new(aggregate_key, &votes, 3);
+        assert!(!tally.threshold_met);
+    }
+
+    #[tokio::test]
+    async fn withdrawal_votes_show_missing_votes() {
+        let mut ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .build();
+        ctx.config_mut().signer.bootstrap_signatures_required = 2;
+        let state = State(ApiState { ctx: ctx.clone() });
+
+        // Without an aggregate key there is no signer set to tally.
+        let result = withdrawal_votes_handler(state.clone(), Path(1)).await;
+        assert_eq!(result.unwrap_err(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let signer_set: Vec<PublicKey> = std::iter::repeat_with(|| Faker.fake()).take(3).collect();
+        let rotate_keys = model::KeyRotationEvent {
+            signer_set: signer_set.clone(),
+            signatures_required: 2,
+            ..Faker.fake()
+        };
+        let db = ctx.get_storage_mut();
+        db.write_rotate_keys_transaction(&rotate_keys)
+            .await
+            .unwrap();
+        ctx.state().update_registry_signer_set_info(SignerSetInfo {
+            aggregate_key: rotate_keys.aggregate_key,
+            signer_set: signer_set.iter().copied().collect(),
+            signatures_required: 2,
+        });
+
+        let request: model::WithdrawalRequest = Faker.fake();
+        let result = withdrawal_votes_handler(state.clone(), Path(request.request_id)).await;
+        assert_eq!(result.unwrap_err(), StatusCode::NOT_FOUND);
+
+        db.write_withdrawal_request(&request).await.unwrap();
+        let decision = model::WithdrawalSigner {
+            request_id: request.request_id,
+            txid: request.txid,
+            block_hash: request.block_hash,
+            signer_pub_key: signer_set[0],
+            is_accepted: true,
+            ..Faker.fake()
+        };
+        db.write_withdrawal_signer_decision(&decision)
+            .await
+            .unwrap();
+
+        let Json(response) = withdrawal_votes_handler(state, Path(request.request_id))
+            .await
+            .unwrap();
+        assert_eq!(response.votes.len(), 1);
+
+        let votes = &response.votes[0];
+        assert_eq!(votes.txid, request.txid);
+        assert_eq!(votes.tally.accepted_by, vec![signer_set[0]]);
+        assert_eq!(votes.tally.not_voted.len(), 2);
+        assert!(!votes.tally.threshold_met);
+    }
+}
@@ -0,0 +1,45 @@
+//! CLI tool that reports the current state of a deposit request, as
+//! recorded in a signer's database.
+
+use std::path::PathBuf;
+
+use bitcoin::OutPoint;
+use clap::Parser;
+use signer::admin::deposit_status::DepositStatusQuery;
+use signer::config::Settings;
+use signer::storage::postgres::PgStore;
+
+/// Report the current state of a deposit request: its confirmations, the
+/// signers' votes, the sweep transaction that spent it, the stacks
+/// transaction that completed it, and the fee that it was charged.
+#[derive(Debug, Parser)]
+#[clap(name = "deposit-status")]
+struct CliArgs {
+    /// The outpoint of the deposit, formatted as `<txid>:<output index>`.
+    outpoint: OutPoint,
+
+    /// Optional path to the signer's configuration file. If not provided,
+    /// it is expected that all parameters are provided via environment
+    /// variables.
+    #[clap(short = 'c', long, required = false)]
+    config: Option<PathBuf>,
+}
+
+// The allowed clippy lint is necessary because the expanded version of the
+// function, the one produced because of the #[tokio::main] procedural
+// macro, uses unwrap or expect.
+#[allow(clippy::unwrap_in_result)]
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = CliArgs::parse();
+    let settings = Settings::new(args.config)?;
+    let db = PgStore::connect(settings.signer.db_endpoint.as_str()).await?;
+
+    let Some(status) = DepositStatusQuery::new(args.outpoint).execute(&db).await? else {
+        eprintln!("the signer has no record of deposit {}", args.outpoint);
+        std::process::exit(1);
+    };
+
+    println!("{}", serde_json::to_string_pretty(&status)?);
+    Ok(())
+}
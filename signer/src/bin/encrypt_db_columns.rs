@@ -0,0 +1,45 @@
+//! CLI tool that encrypts the sensitive columns of a signer's database
+//! that were written before column encryption was enabled.
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use signer::config::Settings;
+use signer::storage::postgres::PgStore;
+
+/// Encrypt the values of sensitive database columns that were written
+/// before `signer.db_column_encryption_key` was configured. Values that
+/// are already encrypted are left alone, so this is safe to run more than
+/// once.
+#[derive(Debug, Parser)]
+#[clap(name = "encrypt-db-columns")]
+struct CliArgs {
+    /// Optional path to the signer's configuration file. If not provided,
+    /// it is expected that all parameters are provided via environment
+    /// variables.
+    #[clap(short = 'c', long, required = false)]
+    config: Option<PathBuf>,
+}
+
+// The allowed clippy lint is necessary because the expanded version of the
+// function, the one produced because of the #[tokio::main] procedural
+// macro, uses unwrap or expect.
+#[allow(clippy::unwrap_in_result)]
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = CliArgs::parse();
+    let settings = Settings::new(args.config)?;
+
+    let Some(key) = settings.signer.db_column_encryption_key else {
+        eprintln!("signer.db_column_encryption_key is not configured");
+        std::process::exit(1);
+    };
+
+    let db = PgStore::connect(settings.signer.db_endpoint.as_str())
+        .await?
+        .with_column_encryption_key(key);
+
+    let encrypted = db.encrypt_plaintext_columns().await?;
+    println!("encrypted {encrypted} column values");
+    Ok(())
+}
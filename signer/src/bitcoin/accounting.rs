@@ -0,0 +1,357 @@
+//! Value accounting for sweep transactions created by the signers.
+//!
+//! Every sweep transaction spends the signers' UTXO as its first input,
+//! any swept deposits as the remaining inputs, and creates the new signers'
+//! UTXO as its first output, followed by the `OP_RETURN` output, any
+//! withdrawal outputs and, when the signers split their change, any
+//! additional signers' UTXOs. This means that the value of the new
+//! signers' UTXOs must always equal
+//!
+//! ```text
+//! signers' input + deposits swept - withdrawals paid - fee
+//! ```
+//!
+//! Anything else means that value left the signers' UTXO in a way that we
+//! did not account for, either because of a fee-accounting bug or because
+//! of an unexpected spend.
+//!
+//! The sweeps in a block form a chain, where each sweep spends the
+//! signers' UTXO created by the one before it, so the value of the
+//! signers' input of each sweep must also equal the value of the signers'
+//! UTXO that the previous sweep created, which is what
+//! [`SignerUtxoChain`] checks.
+
+use std::collections::HashMap;
+
+use bitcoin::Amount;
+
+use crate::storage::model;
+use crate::storage::model::TxOutput;
+use crate::storage::model::TxOutputType;
+use crate::storage::model::TxPrevout;
+use crate::storage::model::TxPrevoutType;
+
+/// The value flowing into and out of a sweep transaction created by the
+/// signers, broken down by its role in the transaction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SweepValueFlow {
+    /// The amount locked in the signers' UTXO that was spent by the
+    /// transaction.
+    pub signer_input: u64,
+    /// The total amount of all deposits swept in by the transaction.
+    pub deposits: u64,
+    /// The total amount paid out to withdrawal recipients.
+    pub withdrawals: u64,
+    /// The fee paid to bitcoin miners.
+    pub fee: u64,
+    /// The total amount locked in the new signers' UTXOs.
+    pub signer_output: u64,
+    /// The amount locked in the signers' UTXO spent by the transaction,
+    /// as recorded when it was created by the previous sweep transaction.
+    /// This is `None` if we do not know of the signers' UTXO being spent.
+    pub previous_signer_output: Option<u64>,
+}
+
+impl SweepValueFlow {
+    /// Build the value flow of a sweep transaction from its inputs and
+    /// outputs, as classified by [`TxDeconstructor`].
+    ///
+    /// Returns `None` if the transaction does not spend the signers' UTXO
+    /// or does not create a new one, in which case it is not a sweep
+    /// transaction.
+    ///
+    /// [`TxDeconstructor`]: crate::bitcoin::utxo::TxDeconstructor
+    pub fn new(prevouts: &[TxPrevout], outputs: &[TxOutput], fee: Amount) -> Option<Self> {
+        let signer_input = prevouts
+            .iter()
+            .find(|prevout| prevout.prevout_type == TxPrevoutType::SignersInput)?
+            .amount;
+        let mut signer_outputs = outputs
+            .iter()
+            .filter(|output| output.output_type == TxOutputType::SignersOutput)
+            .map(|output| output.amount)
+            .peekable();
+        signer_outputs.peek()?;
+        let signer_output = signer_outputs.sum();
+
+        let deposits = prevouts
+            .iter()
+            .filter(|prevout| prevout.prevout_type == TxPrevoutType::Deposit)
+            .map(|prevout| prevout.amount)
+            .sum();
+        // The `OP_RETURN` output is expected to be zero-valued, anything
+        // locked in it is value that left the signers' UTXO, so we do not
+        // count it here.
+        let withdrawals = outputs
+            .iter()
+            .filter(|output| output.output_type == TxOutputType::Withdrawal)
+            .map(|output| output.amount)
+            .sum();
+
+        Some(Self {
+            signer_input,
+            deposits,
+            withdrawals,
+            fee: fee.to_sat(),
+            signer_output,
+            previous_signer_output: None,
+        })
+    }
+
+    /// The value that we expect to be locked in the new signers' UTXO.
+    ///
+    /// This is signed since a transaction that fails the invariant can
+    /// pay out more than it takes in, according to our accounting.
+    pub fn expected_signer_output(&self) -> i128 {
+        i128::from(self.signer_input) + i128::from(self.deposits)
+            - i128::from(self.withdrawals)
+            - i128::from(self.fee)
+    }
+
+    /// The difference between the actual and the expected value of the
+    /// new signers' UTXO. This is zero for a well-formed sweep.
+    pub fn discrepancy(&self) -> i128 {
+        i128::from(self.signer_output) - self.expected_signer_output()
+    }
+
+    /// Whether the new signers' UTXO holds exactly the value we expect.
+    pub fn is_balanced(&self) -> bool {
+        self.discrepancy() == 0
+    }
+
+    /// Whether the signers' input holds exactly the value of the signers'
+    /// UTXO created by the previous sweep transaction. This is trivially
+    /// true if we do not know of that signers' UTXO.
+    pub fn is_chained(&self) -> bool {
+        self.previous_signer_output
+            .is_none_or(|previous| previous == self.signer_input)
+    }
+
+    /// Whether the transaction upholds the signers' UTXO invariants, so
+    /// that it is both balanced and chained to the previous sweep.
+    pub fn is_valid(&self) -> bool {
+        self.is_balanced() && self.is_chained()
+    }
+
+    /// Convert this value flow into a violation record for the given
+    /// transaction, for when the value flow is not balanced.
+    pub fn into_violation(
+        self,
+        txid: model::BitcoinTxId,
+        block_hash: model::BitcoinBlockHash,
+    ) -> model::SignerUtxoViolation {
+        model::SignerUtxoViolation {
+            txid,
+            block_hash,
+            signer_input: self.signer_input,
+            deposits: self.deposits,
+            withdrawals: self.withdrawals,
+            fee: self.fee,
+            signer_output: self.signer_output,
+            previous_signer_output: self.previous_signer_output,
+        }
+    }
+}
+
+/// The signers' UTXOs that may be spent by the next sweep transaction in
+/// a block, along with their value. This is used to chain the value of
+/// the signers' UTXO across all of the sweep transactions in the block.
+#[derive(Debug, Clone, Default)]
+pub struct SignerUtxoChain {
+    /// The amount locked in each of the signers' UTXOs, keyed by their
+    /// outpoint.
+    utxos: HashMap<(model::BitcoinTxId, u32), u64>,
+}
+
+impl SignerUtxoChain {
+    /// Start a chain from the given signers' UTXOs, which are usually the
+    /// ones that were unspent before the block.
+    pub fn new<I>(utxos: I) -> Self
+    where
+        I: IntoIterator<Item = ((model::BitcoinTxId, u32), u64)>,
+    {
+        Self {
+            utxos: utxos.into_iter().collect(),
+        }
+    }
+
+    /// Build the value flow of the next sweep transaction in the block,
+    /// linking its signers' input to the signers' UTXO that it spends,
+    /// which then takes the new signers' UTXOs created by the transaction
+    /// in its place.
+    ///
+    /// Returns `None` if the transaction is not a sweep transaction, see
+    /// [`SweepValueFlow::new`].
+    pub fn next_value_flow(
+        &mut self,
+        prevouts: &[TxPrevout],
+        outputs: &[TxOutput],
+        fee: Amount,
+    ) -> Option<SweepValueFlow> {
+        let mut value_flow = SweepValueFlow::new(prevouts, outputs, fee)?;
+
+        // `SweepValueFlow::new` takes the amount of the same prevout.
+        let signer_input = prevouts
+            .iter()
+            .find(|prevout| prevout.prevout_type == TxPrevoutType::SignersInput)?;
+        let outpoint = (signer_input.prevout_txid, signer_input.prevout_output_index);
+        value_flow.previous_signer_output = self.utxos.remove(&outpoint);
+
+        let signer_outputs = outputs
+            .iter()
+            .filter(|output| output.output_type == TxOutputType::SignersOutput)
+            .map(|output| ((output.txid, output.output_index), output.amount));
+        self.utxos.extend(signer_outputs);
+
+        Some(value_flow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fake::Fake as _;
+    use fake::Faker;
+    use test_case::test_case;
+
+    use super::*;
+
+    fn prevout(prevout_type: TxPrevoutType, amount: u64) -> TxPrevout {
+        TxPrevout {
+            prevout_type,
+            amount,
+            ..Faker.fake()
+        }
+    }
+
+    fn output(output_type: TxOutputType, amount: u64) -> TxOutput {
+        TxOutput {
+            output_type,
+            amount,
+            ..Faker.fake()
+        }
+    }
+
+    #[test_case(100_000, 0; "balanced")]
+    #[test_case(99_000, -1_000; "value missing from signers UTXO")]
+    #[test_case(101_000, 1_000; "value appearing in signers UTXO")]
+    fn sweep_value_flow_discrepancy(signer_output: u64, discrepancy: i128) {
+        let prevouts = [
+            prevout(TxPrevoutType::SignersInput, 50_000),
+            prevout(TxPrevoutType::Deposit, 40_000),
+            prevout(TxPrevoutType::Deposit, 30_000),
+        ];
+        let outputs = [
+            output(TxOutputType::SignersOutput, signer_output),
+            output(TxOutputType::SignersOpReturn, 0),
+            output(TxOutputType::Withdrawal, 15_000),
+        ];
+        let fee = Amount::from_sat(5_000);
+
+        let flow = SweepValueFlow::new(&prevouts, &outputs, fee).unwrap();
+        assert_eq!(flow.deposits, 70_000);
+        assert_eq!(flow.withdrawals, 15_000);
+        assert_eq!(flow.expected_signer_output(), 100_000);
+        assert_eq!(flow.discrepancy(), discrepancy);
+        assert_eq!(flow.is_balanced(), discrepancy == 0);
+    }
+
+    #[test]
+    fn value_locked_in_op_return_is_a_discrepancy() {
+        let prevouts = [prevout(TxPrevoutType::SignersInput, 50_000)];
+        let outputs = [
+            output(TxOutputType::SignersOutput, 44_000),
+            output(TxOutputType::SignersOpReturn, 1_000),
+        ];
+
+        let flow = SweepValueFlow::new(&prevouts, &outputs, Amount::from_sat(5_000)).unwrap();
+        assert_eq!(flow.discrepancy(), -1_000);
+    }
+
+    #[test]
+    fn split_signer_change_is_summed() {
+        let prevouts = [prevout(TxPrevoutType::SignersInput, 300_000)];
+        let outputs = [
+            output(TxOutputType::SignersOutput, 95_000),
+            output(TxOutputType::SignersOpReturn, 0),
+            output(TxOutputType::Withdrawal, 100_000),
+            output(TxOutputType::SignersOutput, 50_000),
+            output(TxOutputType::SignersOutput, 50_000),
+        ];
+
+        let flow = SweepValueFlow::new(&prevouts, &outputs, Amount::from_sat(5_000)).unwrap();
+        assert_eq!(flow.signer_output, 195_000);
+        assert!(flow.is_balanced());
+    }
+
+    #[test]
+    fn non_sweep_transactions_have_no_value_flow() {
+        let outputs = [output(TxOutputType::Donation, 1_000)];
+        assert!(SweepValueFlow::new(&[], &outputs, Amount::ZERO).is_none());
+    }
+
+    /// Return the prevouts and outputs of a balanced sweep transaction
+    /// that spends the given signers' UTXO and pays the given fee.
+    fn sweep(
+        signer_utxo: (model::BitcoinTxId, u32),
+        signer_input: u64,
+        fee: u64,
+    ) -> (Vec<TxPrevout>, Vec<TxOutput>) {
+        let txid: model::BitcoinTxId = Faker.fake();
+        let prevouts = vec![TxPrevout {
+            txid,
+            prevout_txid: signer_utxo.0,
+            prevout_output_index: signer_utxo.1,
+            ..prevout(TxPrevoutType::SignersInput, signer_input)
+        }];
+        let outputs = vec![
+            TxOutput {
+                txid,
+                output_index: 0,
+                ..output(TxOutputType::SignersOutput, signer_input - fee)
+            },
+            TxOutput {
+                txid,
+                output_index: 1,
+                ..output(TxOutputType::SignersOpReturn, 0)
+            },
+        ];
+        (prevouts, outputs)
+    }
+
+    #[test_case(90_000, true; "intact chain")]
+    #[test_case(95_000, false; "broken chain")]
+    fn signer_utxo_chain_links_sweeps_in_a_block(second_signer_input: u64, is_chained: bool) {
+        let fee = Amount::from_sat(10_000);
+        let signer_utxo = (Faker.fake(), 0);
+        let mut chain = SignerUtxoChain::new([(signer_utxo, 100_000)]);
+
+        let (prevouts, outputs) = sweep(signer_utxo, 100_000, fee.to_sat());
+        let flow = chain.next_value_flow(&prevouts, &outputs, fee).unwrap();
+        assert_eq!(flow.previous_signer_output, Some(100_000));
+        assert!(flow.is_valid());
+
+        // The second sweep spends the signers' UTXO created by the first,
+        // and is balanced on its own even when the chain is broken.
+        let new_signer_utxo = (outputs[0].txid, outputs[0].output_index);
+        let (prevouts, outputs) = sweep(new_signer_utxo, second_signer_input, fee.to_sat());
+        let flow = chain.next_value_flow(&prevouts, &outputs, fee).unwrap();
+        assert_eq!(flow.previous_signer_output, Some(90_000));
+        assert!(flow.is_balanced());
+        assert_eq!(flow.is_chained(), is_chained);
+        assert_eq!(flow.is_valid(), is_chained);
+
+        let violation = flow.into_violation(Faker.fake(), Faker.fake());
+        assert_eq!(violation.previous_signer_output, Some(90_000));
+    }
+
+    #[test]
+    fn unknown_signer_utxos_are_not_chained() {
+        let fee = Amount::from_sat(10_000);
+        let mut chain = SignerUtxoChain::default();
+
+        let (prevouts, outputs) = sweep((Faker.fake(), 0), 100_000, fee.to_sat());
+        let flow = chain.next_value_flow(&prevouts, &outputs, fee).unwrap();
+        assert_eq!(flow.previous_signer_output, None);
+        assert!(flow.is_chained());
+    }
+}
@@ -17,6 +17,7 @@
 use bitcoin::BlockHash;
 use bitcoin::OutPoint;
 use bitcoin::Txid;
+use bitcoin::bip158::BlockFilter;
 use bitcoincore_rpc_json::GetTxOutResult;
 
 use crate::{error::Error, util::ApiFallbackClient};
@@ -27,7 +28,6 @@ use super::rpc::BitcoinBlockInfo;
 use super::rpc::BitcoinCoreClient;
 use super::rpc::BitcoinCoreClientParams;
 use super::rpc::BitcoinTxInfo;
-#[cfg(any(test, feature = "testing"))]
 use super::rpc::GetTxResponse;
 use super::rpc::OutPointSummary;
 
@@ -62,7 +62,11 @@ impl BitcoinInteract for ApiFallbackClient<BitcoinCoreClient> {
             .await
     }
 
-    #[cfg(any(test, feature = "testing"))]
+    async fn get_block_filter(&self, block_hash: &BlockHash) -> Result<Option<BlockFilter>, Error> {
+        self.exec(|client, _| BitcoinInteract::get_block_filter(client, block_hash))
+            .await
+    }
+
     async fn get_tx(&self, txid: &Txid) -> Result<Option<GetTxResponse>, Error> {
         self.exec(|client, _| BitcoinInteract::get_tx(client, txid))
             .await
@@ -130,6 +134,15 @@ impl BitcoinInteract for ApiFallbackClient<BitcoinCoreClient> {
             .await
     }
 
+    async fn get_tx_out_proof(
+        &self,
+        txid: &Txid,
+        block_hash: &BlockHash,
+    ) -> Result<bitcoin::MerkleBlock, Error> {
+        self.exec(|client, _| async { client.get_tx_out_proof(txid, block_hash) })
+            .await
+    }
+
     async fn get_blockchain_info(
         &self,
     ) -> Result<bitcoincore_rpc_json::GetBlockchainInfoResult, Error> {
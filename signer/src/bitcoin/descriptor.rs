@@ -0,0 +1,115 @@
+//! Output descriptors for the signers' UTXO.
+//!
+//! The signers lock their UTXO with a key-spend only taproot output whose
+//! internal key is the aggregate key from DKG, so the output is described
+//! by `tr(<x-only aggregate key>)`. Descriptors are returned with their
+//! BIP-380 checksum appended, which is the form that bitcoin-core's
+//! `importdescriptors` RPC and BDK expect.
+
+use secp256k1::XOnlyPublicKey;
+
+/// The characters that may appear in a descriptor, ordered so that their
+/// positions are the values used when computing the checksum.
+const INPUT_CHARSET: &str = "0123456789()[],'/*abcdefgh@:$%{}\
+    IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~\
+    ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ";
+
+/// The characters used to encode the checksum.
+const CHECKSUM_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// The generator of the BCH code used for descriptor checksums.
+const GENERATOR: [u64; 5] = [
+    0xf5dee51989,
+    0xa9fdca3312,
+    0x1bab10e32d,
+    0x3706b1677a,
+    0x644d626ffd,
+];
+
+/// Return the output descriptor, with its checksum, of the taproot output
+/// that the signers lock their UTXO with when using the given aggregate
+/// key.
+pub fn signers_descriptor<K>(aggregate_key: K) -> String
+where
+    K: Into<XOnlyPublicKey>,
+{
+    let descriptor = format!("tr({})", aggregate_key.into());
+    let checksum = descriptor_checksum(&descriptor)
+        .expect("hex encoded keys only use characters in the input charset");
+
+    format!("{descriptor}#{checksum}")
+}
+
+/// Compute the BIP-380 checksum of the given descriptor, returning None
+/// if it contains characters that may not appear in a descriptor.
+pub fn descriptor_checksum(descriptor: &str) -> Option<String> {
+    let mut checksum = 1;
+    let mut class_count = 0;
+    let mut classes = 0;
+
+    for ch in descriptor.chars() {
+        let position = INPUT_CHARSET.find(ch)? as u64;
+        // The low five bits of each character are fed into the checksum
+        // directly, and the remaining bits of every group of three
+        // characters are combined into an extra symbol.
+        checksum = polymod(checksum, position & 31);
+        classes = classes * 3 + (position >> 5);
+        class_count += 1;
+        if class_count == 3 {
+            checksum = polymod(checksum, classes);
+            class_count = 0;
+            classes = 0;
+        }
+    }
+    if class_count > 0 {
+        checksum = polymod(checksum, classes);
+    }
+    for _ in 0..8 {
+        checksum = polymod(checksum, 0);
+    }
+    checksum ^= 1;
+
+    let encoded = (0..8)
+        .map(|i| CHECKSUM_CHARSET[((checksum >> (5 * (7 - i))) & 31) as usize] as char)
+        .collect();
+    Some(encoded)
+}
+
+/// Feed a single five-bit symbol into the checksum.
+fn polymod(checksum: u64, value: u64) -> u64 {
+    let top = checksum >> 35;
+    let mut checksum = ((checksum & 0x7ffffffff) << 5) ^ value;
+    for (i, generator) in GENERATOR.iter().enumerate() {
+        if (top >> i) & 1 == 1 {
+            checksum ^= generator;
+        }
+    }
+    checksum
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test_case::test_case("raw(deadbeef)", "89f8spxm"; "raw script")]
+    #[test_case::test_case("addr(mkmZxiEcEd8ZqjQWVZuC6so5dFMKEFpN2j)", "02wpgw69"; "address")]
+    fn checksums_match_bip_380(descriptor: &str, expected: &str) {
+        assert_eq!(descriptor_checksum(descriptor).unwrap(), expected);
+    }
+
+    #[test]
+    fn descriptors_with_invalid_characters_have_no_checksum() {
+        assert!(descriptor_checksum("raw(deadbeef)\u{e9}").is_none());
+    }
+
+    #[test]
+    fn signers_descriptor_is_a_key_spend_taproot_descriptor() {
+        let key = "c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5";
+        let aggregate_key = XOnlyPublicKey::from_str(key).unwrap();
+
+        let descriptor = signers_descriptor(aggregate_key);
+        assert_eq!(descriptor, format!("tr({key})#k6ze7ncu"));
+    }
+}
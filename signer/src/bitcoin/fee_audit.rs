@@ -0,0 +1,148 @@
+//! Cross-checks of the fees that the signers assess for sweep transactions
+//! against what bitcoin-core reports once the transactions are in its
+//! mempool.
+//!
+//! The fee of a sweep transaction is computed from an estimate of its
+//! virtual size, made before the transaction is signed. If that estimate
+//! is off, then the fee rate paid differs from the one that we intended,
+//! and the fees that we assess against each deposit and withdrawal are
+//! wrong. This module compares the assessed values with the mempool entry
+//! of each broadcast sweep, so that such estimation bugs are caught in
+//! production.
+
+use bitcoin::Txid;
+
+use crate::bitcoin::BitcoinInteract as _;
+use crate::context::Context;
+use crate::error::Error;
+use crate::metrics::Metrics;
+use crate::storage::DbWrite as _;
+use crate::storage::model::SweepFeeDiscrepancy;
+
+/// Compare the fee and virtual size that we assessed for a broadcast
+/// sweep transaction with the values in its bitcoin-core mempool entry.
+///
+/// Any difference is persisted and returned. The signer also logs an
+/// error when the fee differs at all or when the virtual size differs by
+/// more than the configured `sweep_vsize_tolerance`.
+#[tracing::instrument(skip_all, fields(%txid))]
+pub async fn audit_sweep_fees<C>(
+    ctx: &C,
+    txid: &Txid,
+    assessed_fee: u64,
+    assessed_vsize: u64,
+) -> Result<Option<SweepFeeDiscrepancy>, Error>
+where
+    C: Context,
+{
+    let actual = ctx.get_bitcoin_client().get_transaction_fee(txid).await?;
+
+    let discrepancy = SweepFeeDiscrepancy {
+        txid: (*txid).into(),
+        assessed_fee,
+        actual_fee: actual.fee,
+        assessed_vsize,
+        actual_vsize: actual.vsize,
+    };
+
+    let tolerance = ctx.config().signer.sweep_vsize_tolerance;
+    let within_tolerance = is_within_tolerance(&discrepancy, tolerance);
+    Metrics::record_sweep_fee_audit(within_tolerance);
+
+    if discrepancy.fee_difference() == 0 && discrepancy.vsize_difference() == 0 {
+        return Ok(None);
+    }
+
+    if within_tolerance {
+        tracing::warn!(
+            assessed_vsize,
+            actual_vsize = actual.vsize,
+            "the virtual size of the sweep transaction differs from our estimate"
+        );
+    } else {
+        tracing::error!(
+            assessed_fee,
+            actual_fee = actual.fee,
+            assessed_vsize,
+            actual_vsize = actual.vsize,
+            %tolerance,
+            "the fee or virtual size of the sweep transaction differs from our assessment"
+        );
+    }
+
+    ctx.get_storage_mut()
+        .write_sweep_fee_discrepancy(&discrepancy)
+        .await?;
+
+    Ok(Some(discrepancy))
+}
+
+/// Whether the discrepancy is small enough not to raise an alert. The fee
+/// is set by the amounts of the inputs and outputs, so it must match
+/// exactly, while the virtual size may differ by `vsize_tolerance` vbytes.
+fn is_within_tolerance(discrepancy: &SweepFeeDiscrepancy, vsize_tolerance: u64) -> bool {
+    discrepancy.fee_difference() == 0 && discrepancy.vsize_difference() <= vsize_tolerance
+}
+
+#[cfg(test)]
+mod tests {
+    use fake::Fake as _;
+    use fake::Faker;
+    use test_case::test_case;
+
+    use crate::bitcoin::GetTransactionFeeResult;
+    use crate::storage::model;
+    use crate::testing::context::*;
+
+    use super::*;
+
+    #[test_case(1_000, 250, 0, false; "exact match")]
+    #[test_case(1_000, 251, 1, false; "vsize within tolerance")]
+    #[test_case(1_000, 252, 1, true; "vsize beyond tolerance")]
+    #[test_case(1_001, 250, 10, true; "fee mismatch")]
+    #[tokio::test]
+    async fn audit_flags_discrepancies_beyond_tolerance(
+        actual_fee: u64,
+        actual_vsize: u64,
+        tolerance: u64,
+        expected_alert: bool,
+    ) {
+        let mut ctx = TestContext::default_mocked();
+        ctx.config_mut().signer.sweep_vsize_tolerance = tolerance;
+
+        ctx.with_bitcoin_client(move |client| {
+            client.expect_get_transaction_fee().returning(move |_| {
+                Box::pin(async move {
+                    Ok(GetTransactionFeeResult {
+                        fee: actual_fee,
+                        fee_rate: actual_fee as f64 / actual_vsize as f64,
+                        vsize: actual_vsize,
+                    })
+                })
+            });
+        })
+        .await;
+
+        let txid: model::BitcoinTxId = Faker.fake();
+        let discrepancy = audit_sweep_fees(&ctx, &txid.into(), 1_000, 250)
+            .await
+            .unwrap();
+
+        let store = ctx.get_storage().lock().await;
+        match discrepancy {
+            None => {
+                assert!(!expected_alert);
+                assert!(store.sweep_fee_discrepancies.is_empty());
+            }
+            Some(discrepancy) => {
+                assert_eq!(
+                    !is_within_tolerance(&discrepancy, tolerance),
+                    expected_alert
+                );
+                assert_eq!(discrepancy.actual_fee, actual_fee);
+                assert_eq!(discrepancy.actual_vsize, actual_vsize);
+                assert_eq!(store.sweep_fee_discrepancies, vec![discrepancy]);
+            }
+        }
+    }
+}
@@ -0,0 +1,560 @@
+//! Independent verification of bitcoin block headers.
+//!
+//! The block observer fetches block headers and blocks from bitcoin-core
+//! and would otherwise trust that they are valid. The checks here let the
+//! signer verify a chain of headers on its own before anything derived
+//! from them is written to the database, so that a buggy or compromised
+//! node cannot hand the signer blocks without proof-of-work behind them.
+//!
+//! A chain of new headers is verified against the stored headers of the
+//! blocks that it builds on, which we already trust. For each header, we
+//! check:
+//! 1. That the header hashes to the block hash reported by bitcoin-core.
+//! 2. That the header builds on the header before it, one block higher.
+//! 3. That the proof-of-work target encoded in the header is no easier
+//!    than the network allows, and that the block hash meets it.
+//! 4. That the proof-of-work target is the one that the difficulty
+//!    adjustment rules of the network require, given the headers before
+//!    it. Testnet deployments may run on testnet or signet, whose rules
+//!    differ, so this check is skipped there.
+//! 5. That the timestamp of the header is greater than the median time of
+//!    the previous [`MEDIAN_TIME_SPAN`] blocks.
+//!
+//! The checks that need headers from before the new ones are skipped when
+//! those headers are not stored, which is the case for the blocks before
+//! the sBTC start height.
+
+use bitcoin::BlockHash;
+use bitcoin::CompactTarget;
+use bitcoin::Target;
+use bitcoin::block::Header;
+use bitcoin::params::Params;
+
+use crate::bitcoin::rpc::BitcoinBlockHeader;
+use crate::config::NetworkKind;
+use crate::error::Error;
+use crate::storage::model::BitcoinBlockHeight;
+
+/// The number of preceding blocks whose median timestamp a new block's
+/// timestamp must exceed.
+pub const MEDIAN_TIME_SPAN: usize = 11;
+
+/// The ways that a bitcoin block header can fail verification.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum HeaderVerificationError {
+    /// The header hashes to a different block hash than the one that was
+    /// reported by bitcoin-core.
+    #[error("the header hashes to {0} rather than the reported block hash")]
+    HashMismatch(BlockHash),
+    /// The timestamp of the header does not fit in a block header.
+    #[error("the header timestamp {0} does not fit in a block header")]
+    InvalidTimestamp(u64),
+    /// The header does not build on the header before it.
+    #[error("the header does not build on the previous block {0}")]
+    BrokenLinkage(BlockHash),
+    /// The header builds on a block that is not in the database.
+    #[error("the header builds on block {0}, which is not in the database")]
+    UnknownParent(BlockHash),
+    /// The height of the header is not one more than the height of the
+    /// header before it.
+    #[error("the header height {0} does not follow the previous block")]
+    HeightMismatch(BitcoinBlockHeight),
+    /// The proof-of-work target in the header is easier than the network
+    /// allows.
+    #[error("the proof-of-work target in the header exceeds the network limit")]
+    TargetAboveLimit,
+    /// The proof-of-work target in the header is not the one required by
+    /// the difficulty adjustment rules of the network.
+    #[error("the header has target bits {actual:#010x} rather than {expected:#010x}")]
+    UnexpectedTarget {
+        /// The compact target that the header should have.
+        expected: u32,
+        /// The compact target in the header.
+        actual: u32,
+    },
+    /// The block hash does not meet the proof-of-work target in the
+    /// header.
+    #[error("the block hash does not meet the proof-of-work target")]
+    InsufficientWork,
+    /// The timestamp of the header is not greater than the median time of
+    /// the blocks before it.
+    #[error("the header timestamp {0} is not after the median time past {1}")]
+    TimestampTooOld(u64, u64),
+}
+
+impl HeaderVerificationError {
+    /// A short description of the failure, used as a metric label.
+    pub fn reason(&self) -> &'static str {
+        match self {
+            Self::HashMismatch(_) => "hash-mismatch",
+            Self::InvalidTimestamp(_) => "invalid-timestamp",
+            Self::BrokenLinkage(_) => "broken-linkage",
+            Self::UnknownParent(_) => "unknown-parent",
+            Self::HeightMismatch(_) => "height-mismatch",
+            Self::TargetAboveLimit => "target-above-limit",
+            Self::UnexpectedTarget { .. } => "unexpected-target",
+            Self::InsufficientWork => "insufficient-work",
+            Self::TimestampTooOld(_, _) => "timestamp-too-old",
+        }
+    }
+}
+
+/// Return the easiest proof-of-work target allowed on the given network.
+///
+/// Our testnet deployments may run on testnet or signet, so we use the
+/// more permissive signet limit for them.
+pub fn max_attainable_target(network: NetworkKind) -> Target {
+    match network {
+        NetworkKind::Mainnet => Params::MAINNET.max_attainable_target,
+        NetworkKind::Testnet => Params::SIGNET.max_attainable_target,
+        NetworkKind::Regtest => Params::REGTEST.max_attainable_target,
+    }
+}
+
+/// Return the consensus parameters whose difficulty adjustment rules we
+/// check on the given network, if we check them there.
+fn difficulty_params(network: NetworkKind) -> Option<&'static Params> {
+    match network {
+        NetworkKind::Mainnet => Some(&Params::MAINNET),
+        NetworkKind::Testnet => None,
+        NetworkKind::Regtest => Some(&Params::REGTEST),
+    }
+}
+
+/// Return the height of the lowest stored block whose header is needed to
+/// verify the new headers from `first_height` to `last_height`.
+///
+/// These are the blocks for the median-time-past check of the first new
+/// header and, if a difficulty adjustment happens within the new headers,
+/// the blocks back to the start of the period before the first one.
+pub fn first_ancestor_height(
+    first_height: BitcoinBlockHeight,
+    last_height: BitcoinBlockHeight,
+    network: NetworkKind,
+) -> BitcoinBlockHeight {
+    let median_time_start = first_height.saturating_sub(MEDIAN_TIME_SPAN as u64);
+    let Some(params) = difficulty_params(network).filter(|params| !params.no_pow_retargeting)
+    else {
+        return median_time_start;
+    };
+
+    let interval = params.difficulty_adjustment_interval();
+    let first_adjustment = first_height.div_ceil(interval) * interval;
+    if first_adjustment > *last_height {
+        return median_time_start;
+    }
+
+    first_adjustment
+        .saturating_sub(interval)
+        .min(*median_time_start)
+        .into()
+}
+
+/// Return the longest run at the end of the given headers, ordered from
+/// lowest to highest, that ends with the block with the given hash and
+/// where each header builds on the one before it.
+pub fn linked_suffix(headers: Vec<Header>, tip: BlockHash) -> Vec<Header> {
+    let mut expected = tip;
+    let mut suffix: Vec<Header> = headers
+        .into_iter()
+        .rev()
+        .take_while(|header| {
+            let linked = header.block_hash() == expected;
+            expected = header.prev_blockhash;
+            linked
+        })
+        .collect();
+
+    suffix.reverse();
+    suffix
+}
+
+/// Verify a chain of block headers, ordered from lowest to highest.
+///
+/// The `ancestors` are the stored headers of the blocks that precede the
+/// first header, ordered from lowest to highest and ending with its
+/// parent, with each one building on the one before it. The checks that
+/// need headers before the first one are skipped if they are not among
+/// the ancestors.
+pub fn verify_header_chain(
+    headers: &[BitcoinBlockHeader],
+    ancestors: &[Header],
+    network: NetworkKind,
+) -> Result<(), Error> {
+    let max_target = max_attainable_target(network);
+    let params = difficulty_params(network);
+    let mut chain: Vec<Header> = ancestors.to_vec();
+    let mut previous_height: Option<BitcoinBlockHeight> = None;
+
+    for header in headers {
+        let consensus_header =
+            verify_header(header, previous_height, &chain, max_target, params)
+                .map_err(|error| Error::BitcoinHeaderVerification(error, header.hash))?;
+
+        chain.push(consensus_header);
+        previous_height = Some(header.height);
+    }
+
+    Ok(())
+}
+
+/// Verify a single block header against the headers before it, ordered
+/// from lowest to highest, and return its consensus header.
+///
+/// The `previous_height` is the height of the last of the `chain`, if it
+/// is a new header rather than a stored one.
+fn verify_header(
+    header: &BitcoinBlockHeader,
+    previous_height: Option<BitcoinBlockHeight>,
+    chain: &[Header],
+    max_target: Target,
+    params: Option<&Params>,
+) -> Result<Header, HeaderVerificationError> {
+    if let Some(previous) = chain.last()
+        && header.previous_block_hash != previous.block_hash()
+    {
+        return Err(HeaderVerificationError::BrokenLinkage(
+            previous.block_hash(),
+        ));
+    }
+    if let Some(previous_height) = previous_height
+        && header.height != previous_height + 1
+    {
+        return Err(HeaderVerificationError::HeightMismatch(header.height));
+    }
+
+    let consensus_header = header
+        .to_consensus_header()
+        .ok_or(HeaderVerificationError::InvalidTimestamp(header.time))?;
+
+    let block_hash = consensus_header.block_hash();
+    if block_hash != header.hash {
+        return Err(HeaderVerificationError::HashMismatch(block_hash));
+    }
+
+    let target = consensus_header.target();
+    if target > max_target {
+        return Err(HeaderVerificationError::TargetAboveLimit);
+    }
+    if let Some(expected) = params.and_then(|params| expected_bits(params, header.height, chain))
+        && expected != consensus_header.bits
+    {
+        return Err(HeaderVerificationError::UnexpectedTarget {
+            expected: expected.to_consensus(),
+            actual: consensus_header.bits.to_consensus(),
+        });
+    }
+    consensus_header
+        .validate_pow(target)
+        .map_err(|_| HeaderVerificationError::InsufficientWork)?;
+
+    let skip = chain.len().saturating_sub(MEDIAN_TIME_SPAN);
+    match median_time(&chain[skip..]) {
+        Some(median) if header.time <= median => Err(HeaderVerificationError::TimestampTooOld(
+            header.time,
+            median,
+        )),
+        _ => Ok(consensus_header),
+    }
+}
+
+/// Return the compact proof-of-work target that the difficulty
+/// adjustment rules require for a block at the given height, given the
+/// headers of the blocks before it, ordered from lowest to highest. This
+/// returns `None` if the headers that the rules need are not known, or if
+/// the network allows minimum-difficulty blocks between adjustments.
+fn expected_bits(
+    params: &Params,
+    height: BitcoinBlockHeight,
+    chain: &[Header],
+) -> Option<CompactTarget> {
+    let previous = chain.last()?;
+    if params.no_pow_retargeting {
+        return Some(previous.bits);
+    }
+
+    let interval = params.difficulty_adjustment_interval();
+    if *height % interval != 0 {
+        return (!params.allow_min_difficulty_blocks).then_some(previous.bits);
+    }
+
+    // The first block of the period that ends with the previous block.
+    let period_start_index = chain.len().checked_sub(usize::try_from(interval).ok()?)?;
+    let period_start = chain[period_start_index];
+    Some(CompactTarget::from_header_difficulty_adjustment(
+        period_start,
+        *previous,
+        params,
+    ))
+}
+
+/// Return the median of the timestamps of the given headers, the way
+/// bitcoin-core computes the median time past.
+fn median_time(headers: &[Header]) -> Option<u64> {
+    let mut times: Vec<u64> = headers
+        .iter()
+        .map(|header| u64::from(header.time))
+        .collect();
+    times.sort_unstable();
+    times.get(times.len() / 2).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::TxMerkleNode;
+    use bitcoin::block::Version;
+    use bitcoin::hashes::Hash as _;
+    use test_case::test_case;
+
+    use super::*;
+
+    /// The compact proof-of-work target of regtest blocks.
+    const REGTEST_BITS: u32 = 0x207fffff;
+
+    /// Mine a regtest header with the given target on top of the given
+    /// parent.
+    fn mine_with_bits(parent: BlockHash, height: u64, time: u32, bits: u32) -> BitcoinBlockHeader {
+        let mut header = Header {
+            version: Version::TWO,
+            prev_blockhash: parent,
+            merkle_root: TxMerkleNode::all_zeros(),
+            time,
+            bits: CompactTarget::from_consensus(bits),
+            nonce: 0,
+        };
+        while header.validate_pow(header.target()).is_err() {
+            header.nonce += 1;
+        }
+
+        BitcoinBlockHeader {
+            hash: header.block_hash(),
+            height: height.into(),
+            time: time.into(),
+            previous_block_hash: parent,
+            version: header.version.to_consensus(),
+            merkle_root: header.merkle_root,
+            bits: header.bits,
+            nonce: header.nonce,
+        }
+    }
+
+    /// Mine a regtest header on top of the given parent.
+    fn mine(parent: BlockHash, height: u64, time: u32) -> BitcoinBlockHeader {
+        mine_with_bits(parent, height, time, REGTEST_BITS)
+    }
+
+    /// Mine a chain of regtest headers with the given timestamps, starting
+    /// at height 1.
+    fn chain_with_times(times: &[u32]) -> Vec<BitcoinBlockHeader> {
+        let mut headers: Vec<BitcoinBlockHeader> = Vec::new();
+        for (height, time) in (1..).zip(times) {
+            let parent = headers
+                .last()
+                .map_or(BlockHash::all_zeros(), |header| header.hash);
+            headers.push(mine(parent, height, *time));
+        }
+        headers
+    }
+
+    fn chain(length: u32) -> Vec<BitcoinBlockHeader> {
+        let times: Vec<u32> = (1..=length)
+            .map(|height| 1_700_000_000 + height * 600)
+            .collect();
+        chain_with_times(&times)
+    }
+
+    fn consensus_headers(headers: &[BitcoinBlockHeader]) -> Vec<Header> {
+        headers
+            .iter()
+            .map(|header| header.to_consensus_header().unwrap())
+            .collect()
+    }
+
+    fn verification_error(result: Result<(), Error>) -> HeaderVerificationError {
+        match result {
+            Err(Error::BitcoinHeaderVerification(error, _)) => error,
+            result => panic!("unexpected result: {result:?}"),
+        }
+    }
+
+    #[test]
+    fn valid_header_chain_passes() {
+        let headers = chain(6);
+        verify_header_chain(&headers, &[], NetworkKind::Regtest).unwrap();
+
+        let ancestors = consensus_headers(&headers[..2]);
+        verify_header_chain(&headers[2..], &ancestors, NetworkKind::Regtest).unwrap();
+    }
+
+    #[test]
+    fn mainnet_genesis_header_passes() {
+        let genesis = bitcoin::constants::genesis_block(bitcoin::Network::Bitcoin).header;
+        let header = BitcoinBlockHeader {
+            hash: genesis.block_hash(),
+            height: 0u64.into(),
+            time: genesis.time.into(),
+            previous_block_hash: genesis.prev_blockhash,
+            version: genesis.version.to_consensus(),
+            merkle_root: genesis.merkle_root,
+            bits: genesis.bits,
+            nonce: genesis.nonce,
+        };
+        verify_header_chain(&[header], &[], NetworkKind::Mainnet).unwrap();
+    }
+
+    #[test]
+    fn reported_hash_must_match_the_header() {
+        let mut headers = chain(1);
+        headers[0].nonce += 1;
+
+        let error = verification_error(verify_header_chain(&headers, &[], NetworkKind::Regtest));
+        assert!(matches!(error, HeaderVerificationError::HashMismatch(_)));
+    }
+
+    #[test]
+    fn headers_must_link_to_their_parent() {
+        let mut headers = chain(3);
+        headers.remove(1);
+
+        let error = verification_error(verify_header_chain(&headers, &[], NetworkKind::Regtest));
+        assert_eq!(
+            error,
+            HeaderVerificationError::BrokenLinkage(headers[0].hash)
+        );
+    }
+
+    #[test]
+    fn first_header_must_link_to_the_stored_ancestors() {
+        let headers = chain(3);
+        let ancestors = consensus_headers(&headers[..1]);
+
+        let error = verification_error(verify_header_chain(
+            &headers[2..],
+            &ancestors,
+            NetworkKind::Regtest,
+        ));
+        assert_eq!(
+            error,
+            HeaderVerificationError::BrokenLinkage(headers[0].hash)
+        );
+    }
+
+    #[test]
+    fn regtest_targets_exceed_the_mainnet_limit() {
+        let headers = chain(1);
+
+        let error = verification_error(verify_header_chain(&headers, &[], NetworkKind::Mainnet));
+        assert_eq!(error, HeaderVerificationError::TargetAboveLimit);
+    }
+
+    #[test]
+    fn target_must_not_change_between_adjustments() {
+        let mut headers = chain(1);
+        // A harder target that the block still meets, so that only the
+        // difficulty adjustment rules are violated.
+        let harder_bits = 0x1f7fffff;
+        headers.push(mine_with_bits(
+            headers[0].hash,
+            2,
+            1_700_001_200,
+            harder_bits,
+        ));
+
+        let error = verification_error(verify_header_chain(&headers, &[], NetworkKind::Regtest));
+        assert_eq!(
+            error,
+            HeaderVerificationError::UnexpectedTarget {
+                expected: REGTEST_BITS,
+                actual: harder_bits,
+            }
+        );
+    }
+
+    #[test]
+    fn mainnet_target_follows_the_difficulty_adjustment() {
+        let params = &Params::MAINNET;
+        let interval = params.difficulty_adjustment_interval();
+        let genesis = bitcoin::constants::genesis_block(bitcoin::Network::Bitcoin).header;
+
+        // A difficulty period that took a quarter of the target timespan,
+        // so that the target drops to a quarter of what it was.
+        let mut period = vec![genesis; interval as usize];
+        let last = period.last_mut().unwrap();
+        last.time = genesis.time + (params.pow_target_timespan / 4) as u32;
+
+        let expected = expected_bits(params, interval.into(), &period);
+        assert_eq!(expected, Some(CompactTarget::from_consensus(0x1c3fffc0)));
+
+        // Between adjustments the target stays the same.
+        let expected = expected_bits(params, (interval + 1).into(), &period);
+        assert_eq!(expected, Some(genesis.bits));
+
+        // Without the first header of the period, we cannot tell.
+        let expected = expected_bits(params, interval.into(), &period[1..]);
+        assert_eq!(expected, None);
+    }
+
+    #[test]
+    fn timestamps_must_exceed_the_median_time_past() {
+        let headers = chain_with_times(&[1_700_000_000, 1_700_000_600, 1_700_001_200]);
+        let header_time = headers[1].time;
+        let new_header = mine(headers[2].hash, 4, header_time as u32);
+
+        // The median of the ancestors is the new header's timestamp.
+        let ancestors = consensus_headers(&headers);
+        let error = verification_error(verify_header_chain(
+            &[new_header],
+            &ancestors,
+            NetworkKind::Regtest,
+        ));
+        assert_eq!(
+            error,
+            HeaderVerificationError::TimestampTooOld(header_time, header_time)
+        );
+    }
+
+    #[test]
+    fn median_time_past_only_uses_the_last_eleven_blocks() {
+        // The first ancestors are far in the future but fall outside of
+        // the median time span.
+        let mut times = vec![4_000_000_000; MEDIAN_TIME_SPAN];
+        times.extend(std::iter::repeat_n(1_700_000_000, MEDIAN_TIME_SPAN));
+        let headers = chain_with_times(&times);
+
+        let parent = headers.last().unwrap();
+        let new_header = mine(parent.hash, *parent.height + 1, 1_700_000_600);
+        let ancestors = consensus_headers(&headers);
+
+        verify_header_chain(&[new_header], &ancestors, NetworkKind::Regtest).unwrap();
+    }
+
+    #[test_case(100, 110, 89; "no adjustment")]
+    #[test_case(2010, 2020, 0; "adjustment within the new headers")]
+    #[test_case(2016, 2016, 0; "adjustment at the first new header")]
+    #[test_case(2017, 4000, 2006; "adjustment after the new headers")]
+    #[test_case(5, 5, 0; "near genesis")]
+    fn mainnet_ancestors_cover_the_adjustment_period(first: u64, last: u64, expected: u64) {
+        let height = first_ancestor_height(first.into(), last.into(), NetworkKind::Mainnet);
+        assert_eq!(height, expected.into());
+    }
+
+    #[test]
+    fn regtest_ancestors_only_cover_the_median_time_span() {
+        let height = first_ancestor_height(2010u64.into(), 2020u64.into(), NetworkKind::Regtest);
+        assert_eq!(height, 1999u64.into());
+    }
+
+    #[test]
+    fn linked_suffix_stops_at_a_gap() {
+        let headers = consensus_headers(&chain(5));
+        let tip = headers[4].block_hash();
+
+        assert_eq!(linked_suffix(headers.clone(), tip), headers);
+
+        let mut with_gap = headers.clone();
+        with_gap.remove(1);
+        assert_eq!(linked_suffix(with_gap, tip), headers[2..]);
+
+        assert!(linked_suffix(headers, BlockHash::all_zeros()).is_empty());
+    }
+}
@@ -3,21 +3,28 @@
 use std::future::Future;
 
 use bitcoin::BlockHash;
+use bitcoin::MerkleBlock;
 use bitcoin::OutPoint;
 use bitcoin::Txid;
+use bitcoin::bip158::BlockFilter;
 
 use bitcoincore_rpc_json::GetMempoolEntryResult;
 use bitcoincore_rpc_json::GetTxOutResult;
 use rpc::BitcoinBlockHeader;
 use rpc::BitcoinBlockInfo;
 use rpc::BitcoinTxInfo;
-#[cfg(any(test, feature = "testing"))]
 use rpc::GetTxResponse;
 
 use crate::bitcoin::rpc::OutPointSummary;
 use crate::error::Error;
 
+pub mod accounting;
 pub mod client;
+pub mod descriptor;
+pub mod fee_audit;
+pub mod headers;
+pub mod op_return;
+pub mod package_rules;
 pub mod packaging;
 pub mod poller;
 pub mod rpc;
@@ -50,8 +57,17 @@ pub trait BitcoinInteract: Sync + Send {
         block_hash: &BlockHash,
     ) -> impl Future<Output = Result<Option<BitcoinBlockHeader>, Error>> + Send;
 
-    /// get tx
-    #[cfg(any(test, feature = "testing"))]
+    /// Get the BIP-158 basic compact block filter of the block identified
+    /// by the given block hash. Returns `Ok(None)` if the block is unknown
+    /// or the node does not serve block filters.
+    fn get_block_filter(
+        &self,
+        block_hash: &BlockHash,
+    ) -> impl Future<Output = Result<Option<BlockFilter>, Error>> + Send;
+
+    /// Get the transaction with the given ID. This only reliably finds
+    /// transactions that are in the mempool, see
+    /// [`rpc::BitcoinCoreClient::get_tx`].
     fn get_tx(
         &self,
         txid: &Txid,
@@ -147,6 +163,14 @@ pub trait BitcoinInteract: Sync + Send {
         txid: &Txid,
     ) -> impl Future<Output = Result<Option<GetMempoolEntryResult>, Error>> + Send;
 
+    /// Gets the SPV proof that the transaction with the given ID is
+    /// included in the block with the given block hash.
+    fn get_tx_out_proof(
+        &self,
+        txid: &Txid,
+        block_hash: &BlockHash,
+    ) -> impl Future<Output = Result<MerkleBlock, Error>> + Send;
+
     /// Gets information about the blockchain from the Bitcoin node.
     fn get_blockchain_info(
         &self,
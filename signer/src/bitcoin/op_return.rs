@@ -0,0 +1,552 @@
+//! Serialization and parsing of the data that the signers commit to in the
+//! `OP_RETURN` output of their sweep transactions.
+//!
+//! Every payload starts with a three byte header: two magic bytes
+//! identifying the network, followed by a version byte. What comes after
+//! the header depends on the version:
+//!
+//! ```text
+//!  0       2    3                                           X<=80
+//!  |-------|----|--------------------------------------------|
+//!    magic   ver  [version specific body]
+//! ```
+//!
+//! - Version 0: no body.
+//! - Version 1: the body is the idpack encoded list of withdrawal request
+//!   IDs serviced by the transaction, or empty if there are none.
+//! - Version 2: the body is a sequence of type-length-value (TLV) fields,
+//!   where the type and the length are each a single byte. The known
+//!   field types are listed in [`FieldType`]. Each known field may appear
+//!   at most once, and fields with an unknown type are skipped, so that
+//!   new optional fields can be added without a version bump.
+//!
+//! Payloads with any other version byte are rejected with
+//! [`Error::OpReturnUnsupportedVersion`], which callers can use to
+//! distinguish a commitment from a newer signer release from a malformed
+//! one.
+
+use bitcoin::hashes::Hash as _;
+use bitcoin::hashes::sha256;
+use bitcoin::hashes::sha256d;
+use bitcoin::opcodes::all::OP_RETURN;
+use bitcoin::script::Instruction;
+use bitcoin::script::PushBytesBuf;
+use sbtc::idpack::BitmapSegmenter;
+use sbtc::idpack::Decodable as _;
+use sbtc::idpack::Encodable as _;
+use sbtc::idpack::Segmenter as _;
+use sbtc::idpack::Segments;
+use secp256k1::XOnlyPublicKey;
+
+use crate::error::Error;
+use crate::storage::model::QualifiedRequestId;
+
+/// The maximum total size of an OP_RETURN output
+pub const OP_RETURN_MAX_SIZE: usize = 80;
+
+/// The OP_RETURN header size (magic bytes + version)
+pub const OP_RETURN_HEADER_SIZE: usize = 3;
+
+/// The OP_RETURN version for sweep transactions that do not encode any
+/// data after the header.
+pub const OP_RETURN_VERSION_V0: u8 = 0;
+
+/// The OP_RETURN version for sweep transactions that encode withdrawal
+/// request IDs directly after the header.
+pub const OP_RETURN_VERSION_V1: u8 = 1;
+
+/// The OP_RETURN version for sweep transactions that encode TLV fields
+/// after the header.
+pub const OP_RETURN_VERSION_V2: u8 = 2;
+
+/// The OP_RETURN version byte for deposit or withdrawal sweep
+/// transactions created by this signer.
+///
+/// Signers must agree on the version in order to construct the same
+/// sweep transactions, so this only moves to a newer version once all
+/// signers can parse it.
+pub const OP_RETURN_VERSION: u8 = OP_RETURN_VERSION_V1;
+
+/// The size of the TLV header (type + length) of a version 2 field.
+const TLV_HEADER_SIZE: usize = 2;
+
+/// The size of the commitment to the signers' aggregate key.
+pub const AGGREGATE_KEY_COMMITMENT_SIZE: usize = 8;
+
+/// The type byte of the fields in a version 2 OP_RETURN body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum FieldType {
+    /// The idpack encoded withdrawal request IDs serviced by the
+    /// transaction.
+    WithdrawalIds = 1,
+    /// A commitment to the aggregate key that locks the signers' UTXO
+    /// spent by the transaction, see [`aggregate_key_commitment`].
+    AggregateKeyCommitment = 2,
+    /// The merkle root of the requests serviced by the transaction, see
+    /// [`requests_merkle_root`].
+    RequestsMerkleRoot = 3,
+}
+
+impl TryFrom<u8> for FieldType {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(FieldType::WithdrawalIds),
+            2 => Ok(FieldType::AggregateKeyCommitment),
+            3 => Ok(FieldType::RequestsMerkleRoot),
+            unknown => Err(unknown),
+        }
+    }
+}
+
+/// The fields of a version 2 OP_RETURN body.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommitmentV2 {
+    /// The withdrawal request IDs serviced by the transaction, in the
+    /// order of the withdrawal outputs.
+    pub withdrawal_ids: Vec<u64>,
+    /// A commitment to the aggregate key of the signers that created the
+    /// transaction.
+    pub aggregate_key_commitment: Option<[u8; AGGREGATE_KEY_COMMITMENT_SIZE]>,
+    /// The merkle root of the requests serviced by the transaction.
+    pub requests_merkle_root: Option<[u8; 32]>,
+}
+
+/// The data that the signers commit to in the OP_RETURN output of a sweep
+/// transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SweepCommitment {
+    /// A version 0 payload, which carries no data.
+    V0,
+    /// A version 1 payload.
+    V1 {
+        /// The withdrawal request IDs serviced by the transaction, in the
+        /// order of the withdrawal outputs.
+        withdrawal_ids: Vec<u64>,
+    },
+    /// A version 2 payload.
+    V2(CommitmentV2),
+}
+
+/// A parsed OP_RETURN payload of a sweep transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpReturnPayload {
+    /// The magic bytes identifying the network.
+    pub magic_bytes: [u8; 2],
+    /// The versioned commitment following the magic bytes.
+    pub commitment: SweepCommitment,
+}
+
+impl SweepCommitment {
+    /// The version byte of this commitment.
+    pub fn version(&self) -> u8 {
+        match self {
+            SweepCommitment::V0 => OP_RETURN_VERSION_V0,
+            SweepCommitment::V1 { .. } => OP_RETURN_VERSION_V1,
+            SweepCommitment::V2(_) => OP_RETURN_VERSION_V2,
+        }
+    }
+
+    /// The withdrawal request IDs serviced by the transaction.
+    pub fn withdrawal_ids(&self) -> &[u64] {
+        match self {
+            SweepCommitment::V0 => &[],
+            SweepCommitment::V1 { withdrawal_ids } => withdrawal_ids,
+            SweepCommitment::V2(commitment) => &commitment.withdrawal_ids,
+        }
+    }
+
+    /// Serialize this commitment into the data pushed by an OP_RETURN
+    /// script, returning an error if it does not fit in
+    /// [`OP_RETURN_MAX_SIZE`] bytes.
+    pub fn encode(&self, magic_bytes: [u8; 2]) -> Result<PushBytesBuf, Error> {
+        let mut data = Vec::with_capacity(OP_RETURN_MAX_SIZE);
+        data.extend_from_slice(&magic_bytes);
+        data.push(self.version());
+
+        match self {
+            SweepCommitment::V0 => {}
+            SweepCommitment::V1 { withdrawal_ids } => {
+                if !withdrawal_ids.is_empty() {
+                    let encoded = BitmapSegmenter.package(withdrawal_ids)?.encode();
+                    data.extend_from_slice(&encoded);
+                }
+            }
+            SweepCommitment::V2(commitment) => {
+                if !commitment.withdrawal_ids.is_empty() {
+                    let encoded = BitmapSegmenter
+                        .package(&commitment.withdrawal_ids)?
+                        .encode();
+                    push_field(&mut data, FieldType::WithdrawalIds, &encoded)?;
+                }
+                if let Some(key_commitment) = &commitment.aggregate_key_commitment {
+                    push_field(&mut data, FieldType::AggregateKeyCommitment, key_commitment)?;
+                }
+                if let Some(root) = &commitment.requests_merkle_root {
+                    push_field(&mut data, FieldType::RequestsMerkleRoot, root)?;
+                }
+            }
+        }
+
+        // Return an error if the data we intend on putting in the OP_RETURN
+        // output exceeds the maximum size.
+        if data.len() > OP_RETURN_MAX_SIZE {
+            return Err(Error::OpReturnSizeLimitExceeded {
+                size: data.len(),
+                max_size: OP_RETURN_MAX_SIZE,
+            });
+        }
+
+        Ok(PushBytesBuf::try_from(data)?)
+    }
+}
+
+impl OpReturnPayload {
+    /// Parse the data pushed by the OP_RETURN script of a sweep
+    /// transaction.
+    pub fn parse(data: &[u8]) -> Result<Self, Error> {
+        let [magic_0, magic_1, version, body @ ..] = data else {
+            return Err(Error::SbtcTxOpReturnFormatError);
+        };
+        let magic_bytes = [*magic_0, *magic_1];
+
+        let commitment = match *version {
+            // In version 0 we didn't store withdrawal ids, so we ignore
+            // anything that follows the header.
+            OP_RETURN_VERSION_V0 => SweepCommitment::V0,
+            OP_RETURN_VERSION_V1 => SweepCommitment::V1 {
+                withdrawal_ids: decode_withdrawal_ids(body)?,
+            },
+            OP_RETURN_VERSION_V2 => SweepCommitment::V2(parse_v2_body(body)?),
+            unknown => return Err(Error::OpReturnUnsupportedVersion(unknown)),
+        };
+
+        Ok(Self { magic_bytes, commitment })
+    }
+
+    /// Parse the script of the OP_RETURN output of a sweep transaction,
+    /// which pushes the payload as its only data.
+    pub fn from_script(script: &bitcoin::Script) -> Result<Self, Error> {
+        let instructions: Vec<_> = script.instructions().collect();
+        let [
+            Ok(Instruction::Op(OP_RETURN)),
+            Ok(Instruction::PushBytes(push_bytes)),
+        ] = instructions[..]
+        else {
+            return Err(Error::SbtcTxOpReturnFormatError);
+        };
+
+        Self::parse(push_bytes.as_bytes())
+    }
+}
+
+/// Append a TLV field to the given version 2 payload.
+fn push_field(body: &mut Vec<u8>, field_type: FieldType, value: &[u8]) -> Result<(), Error> {
+    let length = u8::try_from(value.len()).map_err(|_| Error::OpReturnSizeLimitExceeded {
+        size: value.len(),
+        max_size: u8::MAX as usize,
+    })?;
+    body.reserve(TLV_HEADER_SIZE + value.len());
+    body.push(field_type as u8);
+    body.push(length);
+    body.extend_from_slice(value);
+    Ok(())
+}
+
+/// Parse the TLV fields of a version 2 body.
+fn parse_v2_body(mut body: &[u8]) -> Result<CommitmentV2, Error> {
+    let mut withdrawal_ids = None;
+    let mut commitment = CommitmentV2::default();
+
+    while !body.is_empty() {
+        let [field_type, length, rest @ ..] = body else {
+            return Err(Error::SbtcTxOpReturnFormatError);
+        };
+        let length = *length as usize;
+        if rest.len() < length {
+            return Err(Error::SbtcTxOpReturnFormatError);
+        }
+        let (value, rest) = rest.split_at(length);
+        body = rest;
+
+        match FieldType::try_from(*field_type) {
+            Ok(FieldType::WithdrawalIds) if withdrawal_ids.is_none() => {
+                withdrawal_ids = Some(decode_withdrawal_ids(value)?);
+            }
+            Ok(FieldType::AggregateKeyCommitment)
+                if commitment.aggregate_key_commitment.is_none() =>
+            {
+                let value = value
+                    .try_into()
+                    .map_err(|_| Error::SbtcTxOpReturnFormatError)?;
+                commitment.aggregate_key_commitment = Some(value);
+            }
+            Ok(FieldType::RequestsMerkleRoot) if commitment.requests_merkle_root.is_none() => {
+                let value = value
+                    .try_into()
+                    .map_err(|_| Error::SbtcTxOpReturnFormatError)?;
+                commitment.requests_merkle_root = Some(value);
+            }
+            // A known field appeared more than once.
+            Ok(_) => return Err(Error::SbtcTxOpReturnFormatError),
+            // Unknown fields are reserved for future optional data.
+            Err(_) => {}
+        }
+    }
+
+    commitment.withdrawal_ids = withdrawal_ids.unwrap_or_default();
+    Ok(commitment)
+}
+
+/// Decode idpack encoded withdrawal IDs, where an empty slice means that
+/// there are no withdrawals.
+fn decode_withdrawal_ids(encoded: &[u8]) -> Result<Vec<u64>, Error> {
+    if encoded.is_empty() {
+        return Ok(Vec::new());
+    }
+    let segments = Segments::decode(encoded).map_err(Error::IdPackDecode)?;
+    Ok(segments.values().collect())
+}
+
+/// Compute the commitment to the signers' aggregate key that is included
+/// in version 2 payloads.
+///
+/// This is the first [`AGGREGATE_KEY_COMMITMENT_SIZE`] bytes of the
+/// SHA256 hash of the x-only aggregate key. It lets observers link a
+/// sweep to the key rotation that created it without paying for the full
+/// key in the OP_RETURN output.
+pub fn aggregate_key_commitment(
+    aggregate_key: &XOnlyPublicKey,
+) -> [u8; AGGREGATE_KEY_COMMITMENT_SIZE] {
+    let hash = sha256::Hash::hash(&aggregate_key.serialize()).to_byte_array();
+    let mut commitment = [0; AGGREGATE_KEY_COMMITMENT_SIZE];
+    commitment.copy_from_slice(&hash[..AGGREGATE_KEY_COMMITMENT_SIZE]);
+    commitment
+}
+
+/// A request serviced by a sweep transaction, as a leaf of the requests
+/// merkle tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RequestLeaf {
+    /// A deposit request, identified by its outpoint.
+    Deposit(bitcoin::OutPoint),
+    /// A withdrawal request, identified by its qualified ID.
+    Withdrawal(QualifiedRequestId),
+}
+
+impl RequestLeaf {
+    /// The hash of this leaf. It is prefixed with [`MERKLE_LEAF_PREFIX`]
+    /// so that it can never collide with the hash of an interior node,
+    /// and deposits and withdrawals are prefixed with a different byte so
+    /// that their hashes can never collide either.
+    fn leaf_hash(&self) -> sha256d::Hash {
+        let mut bytes = Vec::with_capacity(2 + 32 + 8 + 32);
+        bytes.push(MERKLE_LEAF_PREFIX);
+        match self {
+            RequestLeaf::Deposit(outpoint) => {
+                bytes.push(0);
+                bytes.extend_from_slice(&outpoint.txid.to_byte_array());
+                bytes.extend_from_slice(&outpoint.vout.to_le_bytes());
+            }
+            RequestLeaf::Withdrawal(id) => {
+                bytes.push(1);
+                bytes.extend_from_slice(&id.request_id.to_be_bytes());
+                bytes.extend_from_slice(id.txid.to_bytes());
+                bytes.extend_from_slice(id.block_hash.to_bytes());
+            }
+        }
+        sha256d::Hash::hash(&bytes)
+    }
+}
+
+/// The byte that prefixes the preimage of the hash of a leaf in the
+/// requests merkle tree.
+const MERKLE_LEAF_PREFIX: u8 = 0x00;
+
+/// The byte that prefixes the preimage of the hash of an interior node in
+/// the requests merkle tree.
+const MERKLE_NODE_PREFIX: u8 = 0x01;
+
+/// Compute the merkle root of the requests serviced by a sweep
+/// transaction, in the order that they appear in the transaction.
+///
+/// Unlike the transaction merkle tree of a bitcoin block, the last node
+/// on a level with an odd number of nodes is carried up to the next level
+/// as is, instead of being paired with itself. Otherwise the lists
+/// `[a, b, c]` and `[a, b, c, c]` would have the same root
+/// (CVE-2012-2459). Leaves and interior nodes are hashed with a different
+/// prefix byte, as in RFC 6962, so an interior node cannot be passed off
+/// as a leaf. Returns `None` if there are no requests.
+pub fn requests_merkle_root<I>(requests: I) -> Option<[u8; 32]>
+where
+    I: IntoIterator<Item = RequestLeaf>,
+{
+    let mut level: Vec<sha256d::Hash> = requests.into_iter().map(|leaf| leaf.leaf_hash()).collect();
+
+    if level.is_empty() {
+        return None;
+    }
+
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => {
+                    let mut concat = [MERKLE_NODE_PREFIX; 65];
+                    concat[1..33].copy_from_slice(left.as_byte_array());
+                    concat[33..].copy_from_slice(right.as_byte_array());
+                    sha256d::Hash::hash(&concat)
+                }
+                [node] => *node,
+                _ => unreachable!("chunks of two have one or two nodes"),
+            })
+            .collect();
+    }
+
+    Some(level[0].to_byte_array())
+}
+
+#[cfg(test)]
+mod tests {
+    use fake::Fake as _;
+    use fake::Faker;
+    use test_case::test_case;
+
+    use crate::keys::PublicKey;
+
+    use super::*;
+
+    fn v2_commitment(withdrawal_ids: Vec<u64>) -> CommitmentV2 {
+        let aggregate_key: XOnlyPublicKey = Faker.fake::<PublicKey>().into();
+        let requests = withdrawal_ids.iter().map(|&request_id| {
+            RequestLeaf::Withdrawal(QualifiedRequestId { request_id, ..Faker.fake() })
+        });
+        CommitmentV2 {
+            aggregate_key_commitment: Some(aggregate_key_commitment(&aggregate_key)),
+            requests_merkle_root: requests_merkle_root(requests),
+            withdrawal_ids,
+        }
+    }
+
+    #[test_case(SweepCommitment::V0; "v0")]
+    #[test_case(SweepCommitment::V1 { withdrawal_ids: Vec::new() }; "v1 no withdrawals")]
+    #[test_case(SweepCommitment::V1 { withdrawal_ids: vec![1, 2, 5, 100] }; "v1 withdrawals")]
+    #[test_case(SweepCommitment::V2(CommitmentV2::default()); "v2 empty")]
+    #[test_case(SweepCommitment::V2(v2_commitment(Vec::new())); "v2 no withdrawals")]
+    #[test_case(SweepCommitment::V2(v2_commitment(vec![3, 4, 7, 42])); "v2 withdrawals")]
+    fn commitment_round_trip(commitment: SweepCommitment) {
+        let magic_bytes = *b"T3";
+        let data = commitment.encode(magic_bytes).unwrap();
+        assert!(data.len() <= OP_RETURN_MAX_SIZE);
+
+        let payload = OpReturnPayload::parse(data.as_bytes()).unwrap();
+        assert_eq!(payload.magic_bytes, magic_bytes);
+        assert_eq!(payload.commitment, commitment);
+    }
+
+    #[test]
+    fn v1_encoding_matches_header_then_idpack() {
+        let ids = [42, 51];
+        let data = SweepCommitment::V1 { withdrawal_ids: ids.to_vec() }
+            .encode(*b"X2")
+            .unwrap();
+
+        let mut expected = vec![b'X', b'2', OP_RETURN_VERSION_V1];
+        expected.extend(BitmapSegmenter.package(&ids).unwrap().encode());
+        assert_eq!(data.as_bytes(), expected);
+    }
+
+    #[test_case(&[3, 4]; "future version")]
+    #[test_case(&[3, 4, 5, 6]; "future version with body")]
+    #[test_case(&[u8::MAX, 1]; "max version")]
+    fn unknown_versions_are_unsupported(body: &[u8]) {
+        let data = [&[b'T', b'3'], body].concat();
+        let error = OpReturnPayload::parse(&data).unwrap_err();
+        assert!(matches!(error, Error::OpReturnUnsupportedVersion(v) if v == body[0]));
+    }
+
+    #[test_case(&[]; "empty")]
+    #[test_case(&[b'T', b'3']; "no version")]
+    #[test_case(&[b'T', b'3', 2, 1]; "truncated tlv header")]
+    #[test_case(&[b'T', b'3', 2, 2, 8, 0, 0]; "truncated tlv value")]
+    #[test_case(&[b'T', b'3', 2, 2, 1, 0]; "wrong key commitment length")]
+    #[test_case(&[b'T', b'3', 2, 3, 1, 0]; "wrong merkle root length")]
+    #[test_case(&[b'T', b'3', 2, 2, 8, 0, 0, 0, 0, 0, 0, 0, 0, 2, 8, 0, 0, 0, 0, 0, 0, 0, 0]; "duplicate field")]
+    fn malformed_payloads_are_rejected(data: &[u8]) {
+        let error = OpReturnPayload::parse(data).unwrap_err();
+        assert!(matches!(error, Error::SbtcTxOpReturnFormatError));
+    }
+
+    #[test]
+    fn unknown_v2_fields_are_skipped() {
+        let data = [b'T', b'3', OP_RETURN_VERSION_V2, 200, 3, 1, 2, 3];
+        let payload = OpReturnPayload::parse(&data).unwrap();
+        assert_eq!(
+            payload.commitment,
+            SweepCommitment::V2(CommitmentV2::default())
+        );
+    }
+
+    #[test]
+    fn oversized_commitments_are_rejected() {
+        // Sparse IDs that cannot be compressed into 80 bytes.
+        let withdrawal_ids: Vec<u64> = (0..100).map(|i| i * 1_000_000).collect();
+        let commitment = SweepCommitment::V2(v2_commitment(withdrawal_ids));
+        let error = commitment.encode(*b"T3").unwrap_err();
+        assert!(matches!(error, Error::OpReturnSizeLimitExceeded { .. }));
+    }
+
+    #[test]
+    fn requests_merkle_root_depends_on_requests_and_order() {
+        let deposit = RequestLeaf::Deposit(bitcoin::OutPoint::null());
+        let withdrawal = RequestLeaf::Withdrawal(Faker.fake());
+
+        assert!(requests_merkle_root(std::iter::empty()).is_none());
+
+        let root = requests_merkle_root([deposit.clone(), withdrawal.clone()]).unwrap();
+        let reversed = requests_merkle_root([withdrawal, deposit.clone()]).unwrap();
+        let single = requests_merkle_root([deposit.clone()]).unwrap();
+        assert_ne!(root, reversed);
+        assert_ne!(root, single);
+        assert_eq!(single, deposit.leaf_hash().to_byte_array());
+    }
+
+    /// Duplicating the last request on a level with an odd number of
+    /// nodes gives a different root, unlike with the bitcoin block merkle
+    /// tree.
+    #[test]
+    fn requests_merkle_root_does_not_duplicate_odd_nodes() {
+        let leaves: Vec<RequestLeaf> = (0..3)
+            .map(|vout| {
+                RequestLeaf::Deposit(bitcoin::OutPoint {
+                    vout,
+                    ..bitcoin::OutPoint::null()
+                })
+            })
+            .collect();
+        let mut duplicated = leaves.clone();
+        duplicated.push(leaves[2].clone());
+
+        let root = requests_merkle_root(leaves).unwrap();
+        let duplicated_root = requests_merkle_root(duplicated).unwrap();
+        assert_ne!(root, duplicated_root);
+    }
+
+    /// The root of a two leaf tree is not the hash of a leaf, so that an
+    /// interior node cannot be presented as a leaf.
+    #[test]
+    fn requests_merkle_tree_separates_leaves_and_nodes() {
+        let deposit = RequestLeaf::Deposit(bitcoin::OutPoint::null());
+        let withdrawal = RequestLeaf::Withdrawal(Faker.fake());
+
+        let mut concat = Vec::new();
+        concat.extend_from_slice(deposit.leaf_hash().as_byte_array());
+        concat.extend_from_slice(withdrawal.leaf_hash().as_byte_array());
+        let undomained = sha256d::Hash::hash(&concat).to_byte_array();
+
+        let root = requests_merkle_root([deposit, withdrawal]).unwrap();
+        assert_ne!(root, undomained);
+    }
+}
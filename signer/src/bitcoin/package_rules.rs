@@ -0,0 +1,424 @@
+//! Static rules for sweep transaction packages.
+//!
+//! The coordinator constructs a package of sweep transactions and the
+//! signers validate that package during pre-sign. Both sides call the
+//! functions here, so that a package that the coordinator is willing to
+//! construct is exactly a package that the signers are willing to
+//! validate. Every function in this module is pure: they only look at the
+//! request package and the parameters that they are given.
+
+use std::collections::HashSet;
+
+use crate::BITCOIN_FEE_RATE_RANGE;
+use crate::MAX_BITCOIN_FEE_RATE;
+use crate::MAX_DEPOSITS_PER_BITCOIN_TX;
+use crate::MAX_MEMPOOL_PACKAGE_TX_COUNT;
+use crate::MIN_BITCOIN_FEE_RATE;
+use crate::error::Error;
+
+use super::utxo::SignerBtcState;
+use super::utxo::SignerUtxo;
+use super::utxo::SpareSignerUtxo;
+use super::validation::TxRequestIds;
+
+/// The fee rate that we fall back to when we are given a fee rate that is
+/// not a number.
+pub const FALLBACK_FEE_RATE: f64 = 1.0;
+
+/// Check that the package contains at least one transaction and that
+/// each transaction services at least one request.
+pub fn check_not_empty(package: &[TxRequestIds]) -> Result<(), Error> {
+    let no_requests = package
+        .iter()
+        .any(|reqs| reqs.deposits.is_empty() && reqs.withdrawals.is_empty());
+
+    if no_requests || package.is_empty() {
+        return Err(Error::PreSignContainsNoRequests);
+    }
+
+    Ok(())
+}
+
+/// Check that this does not contain duplicate deposits or withdrawals.
+pub fn is_unique(package: &[TxRequestIds]) -> bool {
+    let mut deposits_set = HashSet::new();
+    let mut withdrawal_request_id_set = HashSet::new();
+
+    package.iter().all(|reqs| {
+        let deposits = reqs.deposits.iter().all(|out| deposits_set.insert(out));
+        let withdrawal_requests = reqs
+            .withdrawals
+            .iter()
+            .all(|id| withdrawal_request_id_set.insert(id.request_id));
+
+        deposits && withdrawal_requests
+    })
+}
+
+/// Check that no deposit or withdrawal request is serviced more than once
+/// in the package.
+pub fn check_unique(package: &[TxRequestIds]) -> Result<(), Error> {
+    if !is_unique(package) {
+        return Err(Error::DuplicateRequests);
+    }
+
+    Ok(())
+}
+
+/// Check that the package fits within the bitcoin mempool package limits,
+/// that no transaction in it sweeps more than
+/// [`MAX_DEPOSITS_PER_BITCOIN_TX`] deposits and that none services more
+/// than `max_withdrawals_per_tx` withdrawals.
+pub fn check_package_limits(
+    package: &[TxRequestIds],
+    max_withdrawals_per_tx: u16,
+) -> Result<(), Error> {
+    if package.len() as u64 > MAX_MEMPOOL_PACKAGE_TX_COUNT {
+        return Err(Error::PackageTooLarge(package.len()));
+    }
+
+    let max_deposits = usize::from(MAX_DEPOSITS_PER_BITCOIN_TX);
+    if let Some(reqs) = package
+        .iter()
+        .find(|reqs| reqs.deposits.len() > max_deposits)
+    {
+        return Err(Error::PackageTooManyDeposits {
+            deposits: reqs.deposits.len(),
+            max_deposits: MAX_DEPOSITS_PER_BITCOIN_TX,
+        });
+    }
+
+    let max_withdrawals = usize::from(max_withdrawals_per_tx);
+    if let Some(reqs) = package
+        .iter()
+        .find(|reqs| reqs.withdrawals.len() > max_withdrawals)
+    {
+        return Err(Error::PackageTooManyWithdrawals {
+            withdrawals: reqs.withdrawals.len(),
+            max_withdrawals: max_withdrawals_per_tx,
+        });
+    }
+
+    Ok(())
+}
+
+/// Check that the total amount of the given withdrawals is at most
+/// `max_withdrawal_value`, in sats. The signers may sign more than one
+/// package for a bitcoin chain tip, for example when a deputy coordinator
+/// takes over, so the amounts must cover the withdrawals serviced by all
+/// of them for this to cap the withdrawal value serviced in each bitcoin
+/// block.
+pub fn check_withdrawal_value<I>(amounts: I, max_withdrawal_value: u64) -> Result<(), Error>
+where
+    I: IntoIterator<Item = u64>,
+{
+    let total_amount = amounts
+        .into_iter()
+        .fold(0u64, |total, amount| total.saturating_add(amount));
+
+    if total_amount > max_withdrawal_value {
+        return Err(Error::PackageWithdrawalValueTooLarge {
+            total_amount,
+            max_amount: max_withdrawal_value,
+        });
+    }
+
+    Ok(())
+}
+
+/// Check that the fee rate is within [`BITCOIN_FEE_RATE_RANGE`].
+pub fn check_fee_rate(fee_rate: f64) -> Result<(), Error> {
+    if !BITCOIN_FEE_RATE_RANGE.contains(&fee_rate) {
+        return Err(Error::PreSignInvalidFeeRate(fee_rate));
+    }
+
+    Ok(())
+}
+
+/// Return a fee rate that passes [`check_fee_rate`]. Fee rates that are
+/// not a number are replaced by [`FALLBACK_FEE_RATE`] and all others are
+/// clamped to [`BITCOIN_FEE_RATE_RANGE`].
+pub fn sanitize_fee_rate(fee_rate: f64) -> f64 {
+    if fee_rate.is_nan() {
+        return FALLBACK_FEE_RATE;
+    }
+    fee_rate.clamp(MIN_BITCOIN_FEE_RATE, MAX_BITCOIN_FEE_RATE)
+}
+
+/// Run all of the static checks on a request package with the given fee
+/// rate.
+pub fn check_request_package(
+    package: &[TxRequestIds],
+    fee_rate: f64,
+    max_withdrawals_per_tx: u16,
+) -> Result<(), Error> {
+    check_not_empty(package)?;
+    check_unique(package)?;
+    check_fee_rate(fee_rate)?;
+    check_package_limits(package, max_withdrawals_per_tx)
+}
+
+/// Return the signers' state for the next transaction in the package,
+/// given the state used for the current transaction, the signers' UTXO
+/// that the current transaction creates and the next spare signers' UTXO,
+/// if there is one left.
+///
+/// When there is a spare UTXO, the next transaction spends it, and it
+/// needs to replace any transactions in the mempool that already spend it.
+/// Otherwise, the next transaction spends the signers' UTXO created by its
+/// parent. The fees that the parent sets aside are enough to make up for
+/// the transactions that it replaces, so we do not need to bump the fees
+/// of its child in order for it to be accepted by the network. Only the
+/// first transaction splits the signers' change.
+pub fn chain_signer_state(
+    state: SignerBtcState,
+    new_utxo: SignerUtxo,
+    spare: Option<SpareSignerUtxo>,
+) -> SignerBtcState {
+    let (utxo, last_fees) = match spare {
+        Some(spare) => (spare.utxo, spare.last_fees),
+        None => (new_utxo, None),
+    };
+    SignerBtcState {
+        utxo,
+        last_fees,
+        change_outputs: 1,
+        ..state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::OutPoint;
+    use bitcoin::hashes::Hash as _;
+    use secp256k1::Keypair;
+    use test_case::test_case;
+
+    use crate::bitcoin::utxo::Fees;
+    use crate::storage::model::QualifiedRequestId;
+    use crate::storage::model::StacksBlockHash;
+    use crate::storage::model::StacksTxId;
+
+    use super::*;
+
+    fn outpoint(vout: u32) -> OutPoint {
+        OutPoint::new(bitcoin::Txid::all_zeros(), vout)
+    }
+
+    fn withdrawal(request_id: u64) -> QualifiedRequestId {
+        QualifiedRequestId {
+            request_id,
+            txid: StacksTxId::from([0; 32]),
+            block_hash: StacksBlockHash::from([0; 32]),
+        }
+    }
+
+    fn requests(deposits: &[u32], withdrawals: &[u64]) -> TxRequestIds {
+        TxRequestIds {
+            deposits: deposits.iter().copied().map(outpoint).collect(),
+            withdrawals: withdrawals.iter().copied().map(withdrawal).collect(),
+        }
+    }
+
+    #[test_case(vec![], false; "empty-package")]
+    #[test_case(vec![requests(&[], &[])], false; "empty-transaction")]
+    #[test_case(vec![requests(&[0], &[]), requests(&[], &[])], false; "one-empty-transaction")]
+    #[test_case(vec![requests(&[0], &[])], true; "one-deposit")]
+    #[test_case(vec![requests(&[], &[0])], true; "one-withdrawal")]
+    #[test_case(vec![requests(&[0], &[]), requests(&[], &[0])], true; "two-transactions")]
+    fn not_empty(package: Vec<TxRequestIds>, is_ok: bool) {
+        assert_eq!(check_not_empty(&package).is_ok(), is_ok);
+    }
+
+    #[test_case(vec![requests(&[0, 1], &[0, 1])], true; "unique-requests")]
+    #[test_case(vec![requests(&[0, 0], &[])], false; "duplicate-deposits-in-a-transaction")]
+    #[test_case(vec![requests(&[], &[0, 0])], false; "duplicate-withdrawals-in-a-transaction")]
+    #[test_case(vec![requests(&[0], &[]), requests(&[0], &[])], false; "duplicate-deposits-across-transactions")]
+    #[test_case(vec![requests(&[], &[0]), requests(&[], &[0])], false; "duplicate-withdrawals-across-transactions")]
+    #[test_case(vec![requests(&[0], &[]), requests(&[], &[0])], true; "same-index-different-kinds")]
+    fn unique(package: Vec<TxRequestIds>, is_ok: bool) {
+        assert_eq!(is_unique(&package), is_ok);
+        assert_eq!(check_unique(&package).is_ok(), is_ok);
+    }
+
+    #[test]
+    fn withdrawals_are_unique_by_request_id() {
+        let mut other_block = withdrawal(0);
+        other_block.block_hash = StacksBlockHash::from([1; 32]);
+        let package = vec![
+            requests(&[], &[0]),
+            TxRequestIds {
+                deposits: Vec::new(),
+                withdrawals: vec![other_block],
+            },
+        ];
+
+        assert!(matches!(
+            check_unique(&package),
+            Err(Error::DuplicateRequests)
+        ));
+    }
+
+    #[test_case(1, MAX_DEPOSITS_PER_BITCOIN_TX as u32, true; "one-transaction-at-the-deposit-limit")]
+    #[test_case(1, MAX_DEPOSITS_PER_BITCOIN_TX as u32 + 1, false; "one-transaction-over-the-deposit-limit")]
+    #[test_case(MAX_MEMPOOL_PACKAGE_TX_COUNT as usize, 1, true; "package-at-the-mempool-limit")]
+    #[test_case(MAX_MEMPOOL_PACKAGE_TX_COUNT as usize + 1, 1, false; "package-over-the-mempool-limit")]
+    fn package_limits(num_txs: usize, deposits_per_tx: u32, is_ok: bool) {
+        let package: Vec<TxRequestIds> = (0..num_txs as u32)
+            .map(|tx| {
+                let deposits: Vec<u32> = (0..deposits_per_tx)
+                    .map(|i| tx * deposits_per_tx + i)
+                    .collect();
+                requests(&deposits, &[])
+            })
+            .collect();
+
+        assert_eq!(check_package_limits(&package, u16::MAX).is_ok(), is_ok);
+    }
+
+    #[test]
+    fn package_limits_report_the_offending_transaction() {
+        let too_many = MAX_DEPOSITS_PER_BITCOIN_TX + 1;
+        let deposits: Vec<u32> = (1..=u32::from(too_many)).collect();
+        let package = vec![requests(&[0], &[]), requests(&deposits, &[])];
+
+        let result = check_package_limits(&package, u16::MAX);
+        assert!(matches!(
+            result,
+            Err(Error::PackageTooManyDeposits { deposits, max_deposits })
+                if deposits == usize::from(too_many)
+                    && max_deposits == MAX_DEPOSITS_PER_BITCOIN_TX
+        ));
+    }
+
+    #[test_case(&[0], 1, true; "at-the-withdrawal-limit")]
+    #[test_case(&[0, 1], 1, false; "over-the-withdrawal-limit")]
+    #[test_case(&[], 0, true; "no-withdrawals")]
+    fn withdrawal_limits(withdrawals: &[u64], max_withdrawals: u16, is_ok: bool) {
+        let package = vec![requests(&[0], &[]), requests(&[], withdrawals)];
+        let result = check_package_limits(&package, max_withdrawals);
+        assert_eq!(result.is_ok(), is_ok);
+        if !is_ok {
+            assert!(matches!(
+                result,
+                Err(Error::PackageTooManyWithdrawals {
+                    withdrawals: 2,
+                    max_withdrawals: 1
+                })
+            ));
+        }
+    }
+
+    #[test_case(&[], 0, true; "no-withdrawals")]
+    #[test_case(&[100, 200], 300, true; "at-the-value-limit")]
+    #[test_case(&[100, 201], 300, false; "over-the-value-limit")]
+    #[test_case(&[u64::MAX, 1], u64::MAX, true; "saturates-at-the-max")]
+    fn withdrawal_value(amounts: &[u64], max_value: u64, is_ok: bool) {
+        let result = check_withdrawal_value(amounts.iter().copied(), max_value);
+        assert_eq!(result.is_ok(), is_ok);
+    }
+
+    #[test_case(MIN_BITCOIN_FEE_RATE, true; "min-fee-rate")]
+    #[test_case(MAX_BITCOIN_FEE_RATE, true; "max-fee-rate")]
+    #[test_case(MIN_BITCOIN_FEE_RATE - f64::EPSILON, false; "below-min-fee-rate")]
+    #[test_case(MAX_BITCOIN_FEE_RATE + 1.0, false; "above-max-fee-rate")]
+    #[test_case(f64::NAN, false; "nan-fee-rate")]
+    #[test_case(f64::INFINITY, false; "infinite-fee-rate")]
+    #[test_case(f64::NEG_INFINITY, false; "negative-infinite-fee-rate")]
+    fn fee_rate(fee_rate: f64, is_ok: bool) {
+        assert_eq!(check_fee_rate(fee_rate).is_ok(), is_ok);
+        // Whatever the coordinator estimates, the sanitized fee rate must
+        // pass the signers' check.
+        assert!(check_fee_rate(sanitize_fee_rate(fee_rate)).is_ok());
+    }
+
+    #[test_case(f64::NAN, FALLBACK_FEE_RATE; "nan-uses-fallback")]
+    #[test_case(0.0, MIN_BITCOIN_FEE_RATE; "zero-clamps-to-min")]
+    #[test_case(f64::INFINITY, MAX_BITCOIN_FEE_RATE; "infinity-clamps-to-max")]
+    #[test_case(5.0, 5.0; "valid-fee-rate-is-unchanged")]
+    fn sanitized_fee_rate(fee_rate: f64, expected: f64) {
+        assert_eq!(sanitize_fee_rate(fee_rate), expected);
+    }
+
+    #[test]
+    fn request_package_checks_run_in_order() {
+        let empty = vec![requests(&[], &[])];
+        assert!(matches!(
+            check_request_package(&empty, f64::NAN, 0),
+            Err(Error::PreSignContainsNoRequests)
+        ));
+
+        let duplicates = vec![requests(&[0, 0], &[])];
+        assert!(matches!(
+            check_request_package(&duplicates, f64::NAN, 0),
+            Err(Error::DuplicateRequests)
+        ));
+
+        let deposits: Vec<u32> = (0..=u32::from(MAX_DEPOSITS_PER_BITCOIN_TX)).collect();
+        let package = vec![requests(&deposits, &[])];
+        assert!(matches!(
+            check_request_package(&package, f64::NAN, 0),
+            Err(Error::PreSignInvalidFeeRate(_))
+        ));
+        assert!(matches!(
+            check_request_package(&package, 1.0, u16::MAX),
+            Err(Error::PackageTooManyDeposits { .. })
+        ));
+        let package = vec![requests(&[0, 1], &[0, 1])];
+        assert!(matches!(
+            check_request_package(&package, 1.0, 1),
+            Err(Error::PackageTooManyWithdrawals { .. })
+        ));
+        assert!(check_request_package(&package, 1.0, u16::MAX).is_ok());
+    }
+
+    #[test]
+    fn chained_signer_state_spends_the_new_utxo() {
+        let public_key = Keypair::new_global(&mut rand::rngs::OsRng)
+            .x_only_public_key()
+            .0;
+        let state = SignerBtcState {
+            utxo: SignerUtxo {
+                outpoint: outpoint(0),
+                amount: 100_000,
+                public_key,
+            },
+            fee_rate: 5.0,
+            public_key,
+            last_fees: Some(Fees::new_unchecked(1_000, 100)),
+            magic_bytes: [0; 2],
+            change_outputs: 3,
+        };
+        let new_utxo = SignerUtxo {
+            outpoint: outpoint(1),
+            amount: 90_000,
+            public_key,
+        };
+
+        let next = chain_signer_state(state, new_utxo, None);
+
+        assert_eq!(next.utxo, new_utxo);
+        assert_eq!(next.last_fees, None);
+        assert_eq!(next.change_outputs, 1);
+        assert_eq!(next.fee_rate, state.fee_rate);
+        assert_eq!(next.public_key, state.public_key);
+        assert_eq!(next.magic_bytes, state.magic_bytes);
+
+        // A spare UTXO is spent instead of the new one, and the
+        // transactions in the mempool that spend it get replaced.
+        let spare = SpareSignerUtxo {
+            utxo: SignerUtxo {
+                outpoint: outpoint(2),
+                amount: 150_000,
+                public_key,
+            },
+            last_fees: Some(Fees::new_unchecked(500, 150)),
+        };
+
+        let next = chain_signer_state(state, new_utxo, Some(spare));
+
+        assert_eq!(next.utxo, spare.utxo);
+        assert_eq!(next.last_fees, spare.last_fees);
+        assert_eq!(next.change_outputs, 1);
+    }
+}
@@ -124,8 +124,9 @@ const PACKAGE_MAX_VSIZE: u64 =
 /// 1. The combined votes against cannot exceed `max_votes_against`
 /// 2. The number of items requiring signatures cannot exceed
 ///    `max_needs_signature`
-/// 3. Withdrawal IDs must fit within the OP_RETURN size limit (~77 bytes)
-/// 4. The total virtual size across all bags must not exceed
+/// 3. The number of withdrawals cannot exceed `max_withdrawals`
+/// 4. Withdrawal IDs must fit within the OP_RETURN size limit (~77 bytes)
+/// 5. The total virtual size across all bags must not exceed
 ///    [`PACKAGE_MAX_VSIZE`]
 ///
 /// ## Parameters
@@ -133,6 +134,7 @@ const PACKAGE_MAX_VSIZE: u64 =
 /// - `max_votes_against`: Maximum allowed votes against for any bag
 /// - `max_needs_signature`: Maximum number of items requiring signatures in a
 ///   bag
+/// - `max_withdrawals`: Maximum number of withdrawals in a bag
 ///
 /// ## Notes
 /// - Items that exceed constraints individually are silently ignored
@@ -144,6 +146,7 @@ pub fn compute_optimal_packages<I, T>(
     items: I,
     max_votes_against: u32,
     max_needs_signature: u16,
+    max_withdrawals: u16,
 ) -> impl Iterator<Item = Vec<T>>
 where
     I: IntoIterator<Item = T>,
@@ -152,7 +155,8 @@ where
     // Now we just add each item into a bag, and return the
     // collection of bags afterward.
     // Create config and packager
-    let config = PackagerConfig::new(max_votes_against, max_needs_signature);
+    let mut config = PackagerConfig::new(max_votes_against, max_needs_signature);
+    config.max_withdrawals = max_withdrawals;
     let mut packager = BestFitPackager::new(config);
 
     for item in items {
@@ -243,6 +247,11 @@ struct PackagerConfig {
     /// Due to performance and timing constraints, we limit the number of items
     /// that need signatures in a single bag.
     max_signatures: u16,
+    /// Maximum number of withdrawals in a bag.
+    ///
+    /// Operators may cap the number of withdrawal outputs in a single
+    /// sweep transaction.
+    max_withdrawals: u16,
     /// Maximum virtual size for all bags combined.
     ///
     /// Derived from Bitcoin Core's package relay limits to ensure transactions
@@ -271,10 +280,13 @@ impl PackagerConfig {
     ///
     /// ## Returns
     /// A new `PackagerConfig` with default values for other constraints.
+    /// The number of withdrawals in a bag is only limited by the OP_RETURN
+    /// size.
     fn new(max_votes_against: u32, max_signatures: u16) -> Self {
         Self {
             max_votes_against,
             max_signatures,
+            max_withdrawals: u16::MAX,
             max_total_vsize: PACKAGE_MAX_VSIZE,
             max_op_return_size: OP_RETURN_AVAILABLE_SIZE,
             max_total_presign_size: MAX_PRESIGN_REQUEST_SIZE,
@@ -370,7 +382,8 @@ where
     /// An item is compatible when:
     /// 1. Combined votes against ≤ max_votes_against
     /// 2. Combined signature requirements ≤ max_signatures
-    /// 3. Withdrawal ID (if any) fits within remaining OP_RETURN space
+    /// 3. Number of withdrawals ≤ max_withdrawals
+    /// 4. Withdrawal ID (if any) fits within remaining OP_RETURN space
     ///
     /// ## Parameters
     /// - `item`: Item to check for compatibility
@@ -380,6 +393,7 @@ where
     fn is_compatible(&self, item: &T) -> bool {
         self.votes_compatible(item)
             && self.signatures_compatible(item)
+            && self.withdrawal_count_compatible(item)
             && self.withdrawal_id_compatible(item)
     }
 
@@ -407,6 +421,19 @@ where
         self.items_needing_signatures + sig <= self.config.max_signatures
     }
 
+    /// Check if there is room for another withdrawal in this bag.
+    ///
+    /// ## Parameters
+    /// - `item`: Item to check for withdrawal count compatibility
+    ///
+    /// ## Returns
+    /// `true` if the item is not a withdrawal or adding it wouldn't exceed
+    /// the withdrawal limit.
+    fn withdrawal_count_compatible(&self, item: &T) -> bool {
+        item.withdrawal_id().is_none()
+            || self.withdrawal_ids.len() < usize::from(self.config.max_withdrawals)
+    }
+
     /// Check if an item's withdrawal ID is compatible with this bag.
     ///
     /// ## Parameters
@@ -906,8 +933,12 @@ mod tests {
         expected_bag_vsizes: [0, 0],
     } ; "votes-against-placement")]
     fn returns_optimal_placements<const N: usize>(case: VotesTestCase<N>) {
-        let ans =
-            compute_optimal_packages(case.items, case.max_votes_against, case.max_needs_signature);
+        let ans = compute_optimal_packages(
+            case.items,
+            case.max_votes_against,
+            case.max_needs_signature,
+            u16::MAX,
+        );
         let collection = ans.collect::<Vec<_>>();
         let iter = collection
             .iter()
@@ -952,14 +983,19 @@ mod tests {
 
         let max_needs_signature = 100;
         let max_votes_against = 3;
-        let packages1 =
-            compute_optimal_packages(items.clone(), max_votes_against, max_needs_signature)
-                .collect::<Vec<_>>();
+        let packages1 = compute_optimal_packages(
+            items.clone(),
+            max_votes_against,
+            max_needs_signature,
+            u16::MAX,
+        )
+        .collect::<Vec<_>>();
 
         items.shuffle(&mut rng);
 
-        let packages2 = compute_optimal_packages(items, max_votes_against, max_needs_signature)
-            .collect::<Vec<_>>();
+        let packages2 =
+            compute_optimal_packages(items, max_votes_against, max_needs_signature, u16::MAX)
+                .collect::<Vec<_>>();
 
         assert_ne!(packages1, packages2);
     }
@@ -1044,6 +1080,34 @@ mod tests {
         bag.signatures_compatible(&item)
     }
 
+    /// Tests that the number of withdrawals in a bag is capped, while
+    /// deposits are not affected by the cap.
+    #[test_case(0, None, 1 => true; "deposit_into_empty_bag")]
+    #[test_case(1, None, 1 => true; "deposit_into_full_bag")]
+    #[test_case(0, Some(1), 1 => true; "withdrawal_into_empty_bag")]
+    #[test_case(1, Some(2), 2 => true; "withdrawal_under_max")]
+    #[test_case(2, Some(3), 2 => false; "withdrawal_at_max")]
+    fn test_withdrawal_count_compatible(
+        bag_withdrawals: u64,
+        item_id: Option<u64>,
+        max_withdrawals: u16,
+    ) -> bool {
+        let mut config = PackagerConfig::new(2, 5);
+        config.max_withdrawals = max_withdrawals;
+
+        let mut bag = Bag::new(config);
+        for id in 0..bag_withdrawals {
+            bag.add_item(RequestItem::no_votes().wid(id).vsize(10));
+        }
+
+        let item = match item_id {
+            Some(id) => RequestItem::no_votes().wid(id).vsize(10),
+            None => RequestItem::no_votes().sig_required().vsize(10),
+        };
+
+        bag.withdrawal_count_compatible(&item)
+    }
+
     /// Tests withdrawal ID compatibility for various scenarios:
     /// - Empty withdrawal ID lists
     /// - Small ID ranges
@@ -1314,7 +1378,7 @@ mod tests {
         items.push(RequestItem::with_vote(1).wid(3000)); // Different vote pattern
         items.push(RequestItem::no_votes().wid(10000)); // Large ID
 
-        let bags = compute_optimal_packages(items, 1, 5).collect::<Vec<_>>();
+        let bags = compute_optimal_packages(items, 1, 5, u16::MAX).collect::<Vec<_>>();
 
         // Verify multiple bags were created due to both vote and withdrawal ID constraints
         assert!(bags.len() > 1);
@@ -1476,6 +1540,8 @@ mod tests {
                 inner: SignerMessage {
                     bitcoin_chain_tip: chain_tip,
                     payload: Payload::BitcoinPreSignRequest(presign_request),
+                    deadline: None,
+                    trace_context: None,
                 },
                 signature,
                 signer_public_key: public_key,
@@ -1489,6 +1555,8 @@ mod tests {
             request_package: Vec::new(),
             fee_rate: 0.0,
             last_fees: None,
+            parallel_sweeps: false,
+            magic_bytes: crate::LEGACY_MAGIC_BYTES,
         });
 
         // With a large PSR (~MAX_PRESIGN_REQUEST_SIZE bytes) the length
@@ -1510,6 +1578,8 @@ mod tests {
             }],
             fee_rate: 25.0,
             last_fees: Some(proto::Fees { total: u64::MAX, rate: 25.0 }),
+            parallel_sweeps: true,
+            magic_bytes: crate::LEGACY_MAGIC_BYTES,
         };
         let large_overhead = measure_overhead(large_presign_request);
 
@@ -1627,6 +1697,8 @@ mod tests {
             request_package,
             fee_rate: 0.0,
             last_fees: None,
+            parallel_sweeps: false,
+            magic_bytes: crate::LEGACY_MAGIC_BYTES,
         };
 
         let proto_presign = crate::proto::BitcoinPreSignRequest::from(presign.clone());
@@ -1669,6 +1741,8 @@ mod tests {
             inner: SignerMessage {
                 bitcoin_chain_tip: chain_tip,
                 payload: Payload::BitcoinPreSignRequest(presign),
+                deadline: None,
+                trace_context: None,
             },
             signature,
             signer_public_key: public_key,
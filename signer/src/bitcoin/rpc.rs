@@ -1,20 +1,28 @@
 //! Contains client wrappers for bitcoin core and electrum.
 
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Duration;
 
 use bitcoin::Amount;
 use bitcoin::BlockHash;
+use bitcoin::CompactTarget;
 use bitcoin::Denomination;
+use bitcoin::MerkleBlock;
 use bitcoin::OutPoint;
 use bitcoin::ScriptBuf;
 use bitcoin::Transaction;
+use bitcoin::TxMerkleNode;
 use bitcoin::Txid;
+use bitcoin::bip158::BlockFilter;
+use bitcoin::block::Header;
+use bitcoin::block::Version;
 use bitcoincore_rpc::Error as BtcRpcError;
 use bitcoincore_rpc::RpcApi as _;
 use bitcoincore_rpc::json::EstimateMode;
 use bitcoincore_rpc::jsonrpc::error::Error as JsonRpcError;
 use bitcoincore_rpc::jsonrpc::error::RpcError;
+use bitcoincore_rpc_json::GetBlockFilterResult;
 use bitcoincore_rpc_json::GetBlockchainInfoResult;
 use bitcoincore_rpc_json::GetMempoolEntryResult;
 use bitcoincore_rpc_json::GetNetworkInfoResult;
@@ -25,8 +33,14 @@ use serde::Deserialize;
 use url::Url;
 
 use crate::bitcoin::BitcoinInteract;
+use crate::bitcoin::op_return::OpReturnPayload;
+use crate::bitcoin::op_return::SweepCommitment;
 use crate::bitcoin::utxo::Fees;
+use crate::bitcoin::utxo::SIGNER_UTXO_TARGET;
+use crate::bitcoin::utxo::SignerBtcState;
 use crate::bitcoin::utxo::SignerUtxo;
+use crate::bitcoin::utxo::SpareSignerUtxo;
+use crate::bitcoin::validation::TxRequestIds;
 use crate::error::Error;
 use crate::storage::model::BitcoinBlockHeight;
 
@@ -206,6 +220,14 @@ impl BitcoinTxInfo {
     pub fn compute_txid(&self) -> Txid {
         self.tx.compute_txid()
     }
+
+    /// Return the fee paid by the transaction along with its virtual
+    /// size. This is `None` if bitcoin core did not include the fee in
+    /// its response, see [`BitcoinTxInfo::validate`].
+    pub fn fees(&self) -> Option<Fees> {
+        let fee = self.fee?;
+        Fees::new(fee.to_sat(), self.tx.vsize() as u64).ok()
+    }
 }
 
 /// The scriptPubKey of a transaction output
@@ -260,6 +282,21 @@ pub struct BitcoinBlockInfo {
     pub transactions: Vec<BitcoinTxInfo>,
 }
 
+impl BitcoinBlockInfo {
+    /// Create the block described by the given header, without any of its
+    /// transactions.
+    pub fn without_transactions(header: &BitcoinBlockHeader) -> Self {
+        Self {
+            block_hash: header.hash,
+            height: header.height,
+            time: header.time,
+            median_time: None,
+            previous_block_hash: header.previous_block_hash,
+            transactions: Vec::new(),
+        }
+    }
+}
+
 /// A struct containing the response from bitcoin-core for a
 /// `gettxspendingprevout` RPC call. The actual response is an array; this
 /// struct represents a single element of that array.
@@ -318,6 +355,41 @@ pub struct BitcoinBlockHeader {
     /// The block hash of this blocks parent block.
     #[serde(rename = "previousblockhash")]
     pub previous_block_hash: BlockHash,
+    /// The version field of the block header.
+    pub version: i32,
+    /// The merkle root of the transactions in the block.
+    #[serde(rename = "merkleroot")]
+    pub merkle_root: TxMerkleNode,
+    /// The compact encoding of the proof-of-work target of the block.
+    #[serde(deserialize_with = "compact_target_deserializer")]
+    pub bits: CompactTarget,
+    /// The nonce of the block header.
+    pub nonce: u32,
+}
+
+impl BitcoinBlockHeader {
+    /// Return the consensus header that this response describes, or
+    /// `None` if the time of the block does not fit in a consensus
+    /// header.
+    pub fn to_consensus_header(&self) -> Option<Header> {
+        Some(Header {
+            version: Version::from_consensus(self.version),
+            prev_blockhash: self.previous_block_hash,
+            merkle_root: self.merkle_root,
+            time: u32::try_from(self.time).ok()?,
+            bits: self.bits,
+            nonce: self.nonce,
+        })
+    }
+}
+
+/// Deserialize the hex encoded compact target returned by bitcoin-core.
+fn compact_target_deserializer<'de, D>(deserializer: D) -> Result<CompactTarget, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let bits = String::deserialize(deserializer)?;
+    CompactTarget::from_unprefixed_hex(&bits).map_err(serde::de::Error::custom)
 }
 
 /// A struct representing the recommended fee, in sats per vbyte, from a
@@ -429,6 +501,32 @@ impl BitcoinCoreClient {
         }
     }
 
+    /// Fetch the BIP-158 basic compact block filter of the block
+    /// identified by the given block hash. None is returned if the node
+    /// does not know about the block, or if it was not started with
+    /// `-blockfilterindex` and so has no filters to give.
+    ///
+    /// <https://bitcoincore.org/en/doc/25.0.0/rpc/blockchain/getblockfilter/>
+    pub fn get_block_filter(&self, block_hash: &BlockHash) -> Result<Option<BlockFilter>, Error> {
+        let args = [
+            serde_json::to_value(block_hash).map_err(Error::JsonSerialize)?,
+            serde_json::Value::String("basic".to_string()),
+        ];
+        // bitcoin-core responds with a -1 error code when the filter
+        // index is not enabled, and a -5 error code when the block is not
+        // known or its filter has not been indexed yet.
+        match self
+            .inner
+            .call::<GetBlockFilterResult>("getblockfilter", &args)
+        {
+            Ok(result) => Ok(Some(BlockFilter::new(&result.filter))),
+            Err(BtcRpcError::JsonRpc(JsonRpcError::Rpc(RpcError { code: -1 | -5, .. }))) => {
+                Ok(None)
+            }
+            Err(err) => Err(Error::BitcoinCoreGetBlockFilter(err, *block_hash)),
+        }
+    }
+
     /// Fetch and decode raw transaction from bitcoin-core using the
     /// getrawtransaction RPC with a verbosity of 1 [1]. None is returned
     /// if the node cannot find the transaction in a bitcoin block or the
@@ -445,7 +543,6 @@ impl BitcoinCoreClient {
     ///
     /// [1]: <https://bitcoincore.org/en/doc/25.0.0/rpc/rawtransactions/getrawtransaction/>
     /// [2]: <https://docs.stacks.co/operate/run-a-node/run-a-pruned-bitcoin-node>
-    #[cfg(any(test, feature = "testing"))]
     pub fn get_tx(&self, txid: &Txid) -> Result<Option<GetTxResponse>, Error> {
         let args = [
             serde_json::to_value(txid).map_err(Error::JsonSerialize)?,
@@ -697,6 +794,24 @@ impl BitcoinCoreClient {
         }
     }
 
+    /// Gets the SPV proof that the transaction with the given ID is
+    /// included in the block with the given block hash.
+    ///
+    /// Documentation for the `gettxoutproof` RPC call can be found here:
+    /// https://bitcoincore.org/en/doc/25.0.0/rpc/blockchain/gettxoutproof/
+    pub fn get_tx_out_proof(
+        &self,
+        txid: &Txid,
+        block_hash: &BlockHash,
+    ) -> Result<MerkleBlock, Error> {
+        let proof = self
+            .inner
+            .get_tx_out_proof(&[*txid], Some(block_hash))
+            .map_err(Error::BitcoinCoreRpc)?;
+
+        bitcoin::consensus::deserialize(&proof).map_err(Error::DecodeBitcoinMerkleBlock)
+    }
+
     /// Gets the blockchain info from the Bitcoin node.
     pub fn get_blockchain_info(&self) -> Result<GetBlockchainInfoResult, Error> {
         self.inner
@@ -740,7 +855,10 @@ impl BitcoinInteract for BitcoinCoreClient {
         self.get_block_header(block_hash)
     }
 
-    #[cfg(any(test, feature = "testing"))]
+    async fn get_block_filter(&self, block_hash: &BlockHash) -> Result<Option<BlockFilter>, Error> {
+        self.get_block_filter(block_hash)
+    }
+
     async fn get_tx(&self, txid: &Txid) -> Result<Option<GetTxResponse>, Error> {
         self.get_tx(txid)
     }
@@ -801,6 +919,14 @@ impl BitcoinInteract for BitcoinCoreClient {
         self.get_mempool_entry(txid)
     }
 
+    async fn get_tx_out_proof(
+        &self,
+        txid: &Txid,
+        block_hash: &BlockHash,
+    ) -> Result<MerkleBlock, Error> {
+        self.get_tx_out_proof(txid, block_hash)
+    }
+
     async fn get_blockchain_info(
         &self,
     ) -> Result<bitcoincore_rpc_json::GetBlockchainInfoResult, Error> {
@@ -911,6 +1037,176 @@ where
     Fees::new(total_fees, total_vsize).map(Some)
 }
 
+/// The signers' UTXOs that a parallel sweep package spends, other than
+/// the one that its first transaction spends, and the requests that it
+/// must leave alone.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParallelSweepUtxos {
+    /// The number of signers' outputs that the first transaction in the
+    /// package creates.
+    pub change_outputs: u8,
+    /// The spare signers' UTXOs, in the order that the transactions after
+    /// the first one spend them.
+    pub spare_utxos: Vec<SpareSignerUtxo>,
+    /// The requests serviced by the transactions in the mempool that
+    /// spend the spare UTXOs.
+    pub in_flight: InFlightRequests,
+}
+
+impl ParallelSweepUtxos {
+    /// Assess the signers' UTXOs for a parallel sweep package whose first
+    /// transaction spends `signer_utxo`, where `signer_utxos` are all of
+    /// the signers' UTXOs in the order returned by
+    /// [`crate::storage::DbRead::get_signer_utxos`].
+    ///
+    /// The coordinator and the signers that validate the package assess
+    /// the UTXOs the same way, so they agree on the transactions as long
+    /// as their mempools agree on the transactions that spend them.
+    pub async fn assess<B>(
+        bitcoin_client: &B,
+        signer_utxo: &SignerUtxo,
+        signer_utxos: &[SignerUtxo],
+    ) -> Result<Self, Error>
+    where
+        B: BitcoinInteract + Clone,
+    {
+        let change_outputs =
+            SignerBtcState::change_outputs_needed(SIGNER_UTXO_TARGET, signer_utxos.len());
+
+        let mut spare_utxos = Vec::with_capacity(signer_utxos.len());
+        let mut in_flight = InFlightRequests::default();
+        for utxo in signer_utxos {
+            if utxo.outpoint == signer_utxo.outpoint {
+                continue;
+            }
+            let last_fees = assess_mempool_sweep_transaction_fees(bitcoin_client, utxo).await?;
+            if last_fees.is_some() {
+                in_flight.add_spenders(bitcoin_client, utxo).await?;
+            }
+            spare_utxos.push(SpareSignerUtxo { utxo: *utxo, last_fees });
+        }
+
+        Ok(Self {
+            change_outputs,
+            spare_utxos,
+            in_flight,
+        })
+    }
+}
+
+/// The requests serviced by sweep transactions in the mempool that spend
+/// spare signers' UTXOs.
+///
+/// A transaction that spends a spare UTXO does not conflict with the
+/// transactions that spend the signers' other UTXOs, so it stays in the
+/// mempool unless a later transaction spends the same spare UTXO. The
+/// requests that it services must not be serviced by any other
+/// transaction until it is either replaced or confirmed, or they would be
+/// serviced twice.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InFlightRequests {
+    /// The outpoints of the deposits swept by the transactions.
+    pub deposits: HashSet<OutPoint>,
+    /// The IDs of the withdrawal requests serviced by the transactions.
+    pub withdrawal_ids: HashSet<u64>,
+}
+
+impl InFlightRequests {
+    /// Add the requests serviced by the transactions in the mempool that
+    /// spend the given UTXO, along with their descendants.
+    async fn add_spenders<B>(&mut self, bitcoin_client: &B, utxo: &SignerUtxo) -> Result<(), Error>
+    where
+        B: BitcoinInteract,
+    {
+        let roots = bitcoin_client
+            .find_mempool_transactions_spending_output(&utxo.outpoint)
+            .await?;
+
+        for root in roots {
+            // The transaction may have left the mempool since we found
+            // it, in which case it services nothing anymore.
+            let Some(response) = bitcoin_client.get_tx(&root).await? else {
+                continue;
+            };
+            let mut descendants = Vec::new();
+            for txid in bitcoin_client.find_mempool_descendants(&root).await? {
+                if let Some(response) = bitcoin_client.get_tx(&txid).await? {
+                    descendants.push(response.tx);
+                }
+            }
+
+            // Only the descendants that spend the signers' output of a
+            // sweep are sweeps themselves. The others spend withdrawal
+            // outputs, say, and service no requests.
+            let mut sweeps = vec![response.tx];
+            let mut signer_outputs = HashSet::from([OutPoint::new(root, 0)]);
+            loop {
+                let (chained, rest): (Vec<_>, Vec<_>) = descendants.into_iter().partition(|tx| {
+                    tx.input
+                        .first()
+                        .is_some_and(|tx_in| signer_outputs.contains(&tx_in.previous_output))
+                });
+                descendants = rest;
+                if chained.is_empty() {
+                    break;
+                }
+                for tx in chained {
+                    signer_outputs.insert(OutPoint::new(tx.compute_txid(), 0));
+                    sweeps.push(tx);
+                }
+            }
+
+            sweeps.iter().try_for_each(|tx| self.add_sweep(tx))?;
+        }
+        Ok(())
+    }
+
+    /// Add the requests serviced by the given sweep transaction, which
+    /// spends the signers' UTXO as its first input, followed by the
+    /// deposits, and whose OP_RETURN output commits to its withdrawals.
+    pub fn add_sweep(&mut self, tx: &Transaction) -> Result<(), Error> {
+        let op_return = tx.output.get(1).ok_or(Error::SbtcTxMalformed)?;
+        let payload = OpReturnPayload::from_script(&op_return.script_pubkey)?;
+        // Version 0 payloads do not say which withdrawals the transaction
+        // services, so we cannot tell them apart from other outputs.
+        if payload.commitment == SweepCommitment::V0 && tx.output.len() > 2 {
+            return Err(Error::SbtcTxMalformed);
+        }
+
+        let deposits = tx.input.iter().skip(1).map(|tx_in| tx_in.previous_output);
+        self.deposits.extend(deposits);
+        self.withdrawal_ids
+            .extend(payload.commitment.withdrawal_ids().iter().copied());
+        Ok(())
+    }
+
+    /// Whether the given deposit is serviced by a transaction in flight.
+    pub fn contains_deposit(&self, outpoint: &OutPoint) -> bool {
+        self.deposits.contains(outpoint)
+    }
+
+    /// Whether the given withdrawal is serviced by a transaction in
+    /// flight.
+    pub fn contains_withdrawal(&self, request_id: u64) -> bool {
+        self.withdrawal_ids.contains(&request_id)
+    }
+
+    /// Whether any request in the given package is serviced by a
+    /// transaction in flight.
+    pub fn intersects(&self, request_package: &[TxRequestIds]) -> bool {
+        request_package.iter().any(|requests| {
+            requests
+                .deposits
+                .iter()
+                .any(|outpoint| self.contains_deposit(outpoint))
+                || requests
+                    .withdrawals
+                    .iter()
+                    .any(|id| self.contains_withdrawal(id.request_id))
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use fake::Fake as _;
@@ -1003,4 +1299,22 @@ mod tests {
         tx_info.tx.input.reverse();
         tx_info.validate().unwrap();
     }
+
+    #[test]
+    fn bitcoin_tx_info_fees_use_the_tx_vsize() {
+        let mut rng = get_rng();
+
+        let mut tx_info: BitcoinTxInfo = Faker.fake_with_rng(&mut rng);
+        tx_info.fee = Some(Amount::from_sat(10_000));
+
+        let vsize = tx_info.tx.vsize() as u64;
+        let fees = tx_info.fees().unwrap();
+        assert_eq!(fees.total, 10_000);
+        assert_eq!(fees.vsize(), vsize);
+        assert_eq!(fees.rate(), 10_000.0 / vsize as f64);
+
+        // We cannot compute the fees if bitcoin core leaves out the fee.
+        tx_info.fee = None;
+        assert!(tx_info.fees().is_none());
+    }
 }
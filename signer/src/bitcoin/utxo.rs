@@ -21,7 +21,6 @@ use bitcoin::absolute::LockTime;
 use bitcoin::consensus::Encodable as _;
 use bitcoin::opcodes::all::OP_RETURN;
 use bitcoin::script::Instruction;
-use bitcoin::script::PushBytesBuf;
 use bitcoin::sighash::Prevouts;
 use bitcoin::sighash::SighashCache;
 use bitcoin::taproot::LeafVersion;
@@ -32,11 +31,6 @@ use bitcoin::transaction::Version;
 use bitvec::array::BitArray;
 use bitvec::field::BitField as _;
 use prost::Message as _;
-use sbtc::idpack::BitmapSegmenter;
-use sbtc::idpack::Decodable as _;
-use sbtc::idpack::Encodable as _;
-use sbtc::idpack::Segmenter as _;
-use sbtc::idpack::Segments;
 use secp256k1::SECP256K1;
 use secp256k1::XOnlyPublicKey;
 use serde::Deserialize;
@@ -44,10 +38,17 @@ use serde::Serialize;
 
 use crate::DEPOSIT_DUST_LIMIT;
 use crate::MAX_BITCOIN_BLOCK_VSIZE;
+use crate::MAX_BITCOIN_FEE_RATE;
 use crate::MAX_MEMPOOL_PACKAGE_TX_COUNT;
+use crate::bitcoin::op_return::OP_RETURN_HEADER_SIZE;
+use crate::bitcoin::op_return::OP_RETURN_MAX_SIZE;
+use crate::bitcoin::op_return::OpReturnPayload;
+use crate::bitcoin::op_return::SweepCommitment;
+use crate::bitcoin::package_rules;
 use crate::bitcoin::packaging::Weighted;
 use crate::bitcoin::packaging::compute_optimal_packages;
 use crate::bitcoin::rpc::BitcoinTxInfo;
+use crate::bitcoin::validation::TxRequestIds;
 use crate::context::SbtcLimits;
 use crate::error::Error;
 use crate::keys::SignerScriptPubKey as _;
@@ -89,22 +90,59 @@ pub const BASE_WITHDRAWAL_TX_VSIZE: f64 = MAX_BASE_TX_VSIZE as f64;
 /// transaction excluding withdrawals outputs and deposit inputs.
 pub const MAX_BASE_TX_VSIZE: u64 = 137;
 
+/// The number of UTXOs that the signers maintain when they construct
+/// parallel sweeps. The first transaction in a sweep package splits the
+/// signers' change until the signers control this many UTXOs, and the
+/// transactions after it spend the other UTXOs, so that they do not
+/// depend on the transactions before them.
+///
+/// This is part of the signer protocol, since the signers reconstruct
+/// each transaction when validating it, so it cannot be changed without
+/// bumping the [`crate::PARALLEL_SWEEPS_PROTOCOL_VERSION`].
+pub const SIGNER_UTXO_TARGET: u8 = 4;
+
+/// The minimum amount, in sats, that each output holds when the signers'
+/// change is split across more than one output. The number of outputs is
+/// reduced until each of them holds at least this amount.
+pub const MIN_SIGNER_CHANGE_OUTPUT_AMOUNT: u64 = 100_000;
+
+/// The maximum weight, in weight units, of a transaction that bitcoin core
+/// considers standard and will relay. This is `MAX_STANDARD_TX_WEIGHT` in
+/// bitcoin core's `policy/policy.h`.
+pub const MAX_STANDARD_TX_WEIGHT: u64 = 400_000;
+
+/// The maximum signature operation cost of a transaction that bitcoin core
+/// considers standard and will relay. This is `MAX_STANDARD_TX_SIGOPS_COST`
+/// in bitcoin core's `policy/policy.h`.
+pub const MAX_STANDARD_TX_SIGOPS_COST: u64 = 16_000;
+
+/// The number of weight units in a virtual byte.
+const WITNESS_SCALE_FACTOR: u64 = bitcoin::constants::WITNESS_SCALE_FACTOR as u64;
+
+/// The weight of a deposit input that the [`SweepCapacityPlanner`] assumes
+/// when there are no deposit requests to measure. It is the weight that a
+/// deposit input adds to the base transaction in
+/// [`SOLO_DEPOSIT_TX_VSIZE`].
+const DEFAULT_DEPOSIT_INPUT_WEIGHT: u64 =
+    (SOLO_DEPOSIT_TX_VSIZE as u64 - MAX_BASE_TX_VSIZE) * WITNESS_SCALE_FACTOR;
+
+/// The weight of a withdrawal output that the [`SweepCapacityPlanner`]
+/// assumes when there are no withdrawal requests to measure. It is the
+/// weight of an output locked by a 32 byte witness program, the largest
+/// standard output.
+const DEFAULT_WITHDRAWAL_OUTPUT_WEIGHT: u64 = 43 * WITNESS_SCALE_FACTOR;
+
+/// The signature operation cost of a withdrawal output that the
+/// [`SweepCapacityPlanner`] assumes when there are no withdrawal requests
+/// to measure. It is the cost of a pay-to-pubkey-hash output.
+const DEFAULT_WITHDRAWAL_OUTPUT_SIGOPS_COST: u64 = WITNESS_SCALE_FACTOR;
+
 /// It appears that bitcoin-core tracks fee rates in sats per kilo-vbyte
 /// (or BTC per kilo-vbyte). Since we work in sats per vbyte, this constant
 /// is the smallest detectable increment for bumping the fee rate in sats
 /// per vbyte.
 const SATS_PER_VBYTE_INCREMENT: f64 = 0.001;
 
-/// The OP_RETURN version byte for deposit or withdrawal sweep
-/// transactions.
-const OP_RETURN_VERSION: u8 = 1;
-
-/// The OP_RETURN header size (magic bytes + version)
-const OP_RETURN_HEADER_SIZE: usize = 3;
-
-/// The maximum total size of an OP_RETURN output
-const OP_RETURN_MAX_SIZE: usize = 80;
-
 /// Per-item protobuf overhead when an `OutPoint` or `QualifiedRequestId`
 /// is embedded as an element of a `repeated` field inside `TxRequestIds`.
 ///
@@ -124,7 +162,7 @@ static DUMMY_SIGNATURE: LazyLock<Signature> = LazyLock::new(|| Signature {
 });
 
 /// Describes the fees for a transaction package.
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct Fees {
     /// The total fee paid in sats for the transaction package.
     pub total: u64,
@@ -166,6 +204,11 @@ impl Fees {
     pub fn rate(&self) -> f64 {
         self.total as f64 / self.vsize.get() as f64
     }
+
+    /// The size of the transaction package in virtual bytes.
+    pub fn vsize(&self) -> u64 {
+        self.vsize.get()
+    }
 }
 
 /// A trait for getting the fees for a given instance.
@@ -324,6 +367,34 @@ pub struct SignerBtcState {
     /// Two byte prefix for BTC transactions that are related to the Stacks
     /// blockchain.
     pub magic_bytes: [u8; 2],
+    /// The number of outputs locked by the signers' aggregate key that the
+    /// next sweep transaction creates. Zero and one both mean that the
+    /// transaction has a single signers' output.
+    pub change_outputs: u8,
+}
+
+impl SignerBtcState {
+    /// Return the number of signers' outputs that the next sweep
+    /// transaction needs to create so that the signers control `target`
+    /// UTXOs once it confirms, given the number of UTXOs that the signers
+    /// control now, including the one that the transaction spends.
+    pub fn change_outputs_needed(target: u8, available: usize) -> u8 {
+        let remaining = u8::try_from(available.saturating_sub(1)).unwrap_or(u8::MAX);
+        target.saturating_sub(remaining).max(1)
+    }
+}
+
+/// One of the signers' UTXOs, other than the one spent by the first
+/// transaction in a sweep package, that a later transaction in the
+/// package spends instead of the signers' output of its parent.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpareSignerUtxo {
+    /// The signers' UTXO.
+    pub utxo: SignerUtxo,
+    /// The total fee amount and the fee rate of the transactions in the
+    /// mempool that already spend this UTXO, if any. The transaction
+    /// that spends it next needs to replace them.
+    pub last_fees: Option<Fees>,
 }
 
 /// The set of sBTC requests with additional relevant
@@ -337,6 +408,11 @@ pub struct SbtcRequests {
     /// Summary of the Signers' UTXO and information necessary for
     /// constructing their next UTXO.
     pub signer_state: SignerBtcState,
+    /// The signers' other UTXOs, which the transactions after the first
+    /// one in the package spend in order when the signers construct
+    /// parallel sweeps. Once they run out, each remaining transaction
+    /// spends the signers' output of its parent.
+    pub spare_signer_utxos: Vec<SpareSignerUtxo>,
     /// The minimum acceptable number of votes for any given request.
     pub accept_threshold: u16,
     /// The total number of signers.
@@ -349,9 +425,75 @@ pub struct SbtcRequests {
     /// that there is enough time for the signers to sign all the inputs
     /// during the tenure of a single bitcoin block.
     pub max_deposits_per_bitcoin_tx: u16,
+    /// The maximum number of withdrawal outputs that can be included in a
+    /// single bitcoin transaction.
+    pub max_withdrawals_per_bitcoin_tx: u16,
+    /// The maximum total amount, in sats, of the withdrawals serviced by
+    /// the transaction package. This is what remains of the per-block
+    /// withdrawal value cap after the packages that the signers already
+    /// agreed to sign for the bitcoin chain tip.
+    pub max_withdrawal_value: u64,
+    /// How requests are ordered when deciding which of them make it into
+    /// the transaction package.
+    pub prioritization: RequestPrioritization,
+}
+
+/// How requests are ordered when there are more of them than fit in a
+/// single transaction package.
+///
+/// Requests are handed to the packager in the order given here, and once
+/// the package is full the remaining requests are left for a later sweep.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RequestPrioritization {
+    /// Requests are considered in the order that they arrived in. Deposits
+    /// are taken in the order that they were given, followed by
+    /// withdrawals ordered by their request ID.
+    #[default]
+    Fifo,
+    /// Requests are considered in decreasing order of their fee
+    /// contribution, which is the maximum fee that they are willing to pay
+    /// divided by the number of vbytes that they add to a sweep
+    /// transaction.
+    FeeContribution {
+        /// The number of requests, taken in arrival order, that are
+        /// considered before any of the requests ordered by fee
+        /// contribution. This keeps requests with a low fee contribution
+        /// from being starved by a steady stream of higher paying ones.
+        fairness_floor: u16,
+    },
+}
+
+impl RequestPrioritization {
+    /// Order the given requests, which must be in arrival order, by their
+    /// priority. Requests with the same priority keep their arrival order.
+    pub fn order<'a>(&self, mut requests: Vec<RequestRef<'a>>) -> Vec<RequestRef<'a>> {
+        let Self::FeeContribution { fairness_floor } = *self else {
+            return requests;
+        };
+        let floor = usize::from(fairness_floor).min(requests.len());
+        requests[floor..].sort_by(|a, b| b.fee_contribution().total_cmp(&a.fee_contribution()));
+        requests
+    }
 }
 
 impl SbtcRequests {
+    /// Whether the transaction package is constructed as parallel sweeps,
+    /// which split the signers' change or spend more than one of the
+    /// signers' UTXOs. Signers running a protocol version before
+    /// [`crate::PARALLEL_SWEEPS_PROTOCOL_VERSION`] cannot validate them.
+    pub fn parallel_sweeps(&self) -> bool {
+        self.signer_state.change_outputs > 1 || !self.spare_signer_utxos.is_empty()
+    }
+
+    /// Return the outpoints of the spare signers' UTXOs, in the order that
+    /// the transactions of the package spend them.
+    pub fn spare_signer_outpoints(&self) -> Vec<OutPoint> {
+        self.spare_signer_utxos
+            .iter()
+            .map(|spare| spare.utxo.outpoint)
+            .collect()
+    }
+
     /// Construct the next transaction package given requests and the
     /// signers' UTXO.
     ///
@@ -371,29 +513,100 @@ impl SbtcRequests {
         let deposits = request_preprocessor.filter_deposits(&self.deposits);
         let withdrawals = request_preprocessor.preprocess_withdrawals(&self.withdrawals);
 
-        // Create a list of requests where each request can be approved on its own.
-        let items = deposits.into_iter().chain(withdrawals);
+        // Create a list of requests where each request can be approved on
+        // its own, ordered by how much we want them in the package.
+        let items = self
+            .prioritization
+            .order(deposits.into_iter().chain(withdrawals).collect());
+        let items = self.cap_withdrawal_value(items);
 
         let max_votes_against = self.reject_capacity();
         let max_needs_signature = self.max_deposits_per_bitcoin_tx;
-        compute_optimal_packages(items, max_votes_against, max_needs_signature)
-            .scan(self.signer_state, |state, request_refs| {
-                let requests = Requests::new(request_refs);
-                let tx = UnsignedTransaction::new(requests, state);
-                if let Ok(tx_ref) = tx.as_ref() {
-                    state.utxo = tx_ref.new_signer_utxo();
-                    // The first transaction is the only one whose input
-                    // UTXOs that have all been confirmed. Moreover, the
-                    // fees that it sets aside are enough to make up for
-                    // the remaining transactions in the transaction package.
-                    // With that in mind, we do not need to bump their fees
-                    // anymore in order for them to be accepted by the
-                    // network.
-                    state.last_fees = None;
+        let max_withdrawals = self.max_withdrawals_per_bitcoin_tx;
+        let mut spare_utxos = self.spare_signer_utxos.iter();
+        compute_optimal_packages(
+            items,
+            max_votes_against,
+            max_needs_signature,
+            max_withdrawals,
+        )
+        .scan(self.signer_state, |state, request_refs| {
+            let requests = Requests::new(request_refs);
+            let tx = UnsignedTransaction::new(requests, state);
+            if let Ok(tx_ref) = tx.as_ref() {
+                let spare = spare_utxos.next().copied();
+                *state = package_rules::chain_signer_state(*state, tx_ref.new_signer_utxo(), spare);
+            }
+            Some(tx)
+        })
+        .take(MAX_MEMPOOL_PACKAGE_TX_COUNT as usize)
+        .collect()
+    }
+
+    /// Reconstruct the transactions of a package that was constructed
+    /// earlier, where each element of `request_package` identifies the
+    /// requests serviced by one transaction in the package.
+    ///
+    /// Each transaction spends the next spare signers' UTXO, or the
+    /// signers' UTXO created by the one before it, just like in
+    /// [`SbtcRequests::construct_transactions`], so the reconstructed
+    /// transactions match the original ones as long as the signer state
+    /// and spare UTXOs are the ones that the package was constructed
+    /// with. This function returns an error if any of the requests is not
+    /// one of the requests in `self`.
+    pub fn reconstruct_transactions(
+        &self,
+        request_package: &[TxRequestIds],
+    ) -> Result<Vec<UnsignedTransaction<'_>>, Error> {
+        let mut state = self.signer_state;
+        let mut spare_utxos = self.spare_signer_utxos.iter();
+        let mut transactions = Vec::with_capacity(request_package.len());
+
+        for request_ids in request_package {
+            let deposits = request_ids.deposits.iter().map(|outpoint| {
+                self.deposits
+                    .iter()
+                    .find(|req| &req.outpoint == outpoint)
+                    .map(RequestRef::Deposit)
+                    .ok_or(Error::MissingDepositRequest(*outpoint))
+            });
+            let withdrawals = request_ids.withdrawals.iter().map(|id| {
+                self.withdrawals
+                    .iter()
+                    .find(|req| &req.qualified_id() == id)
+                    .map(RequestRef::Withdrawal)
+                    .ok_or_else(|| Error::MissingWithdrawalRequest(id.clone()))
+            });
+            let request_refs = deposits.chain(withdrawals).collect::<Result<_, _>>()?;
+
+            let tx = UnsignedTransaction::new(Requests::new(request_refs), &state)?;
+            let spare = spare_utxos.next().copied();
+            state = package_rules::chain_signer_state(state, tx.new_signer_utxo(), spare);
+            transactions.push(tx);
+        }
+
+        Ok(transactions)
+    }
+
+    /// Remove the withdrawals that do not fit within the withdrawal value
+    /// cap, taking them in the given order. The removed withdrawals are
+    /// left for a later bitcoin block.
+    fn cap_withdrawal_value<'a>(&self, items: Vec<RequestRef<'a>>) -> Vec<RequestRef<'a>> {
+        let mut remaining = self.max_withdrawal_value;
+        items
+            .into_iter()
+            .filter(|item| {
+                let Some(req) = item.as_withdrawal() else {
+                    return true;
+                };
+                match remaining.checked_sub(req.amount) {
+                    Some(rest) => {
+                        remaining = rest;
+                        true
+                    }
+                    None => false,
                 }
-                Some(tx)
             })
-            .take(MAX_MEMPOOL_PACKAGE_TX_COUNT as usize)
             .collect()
     }
 
@@ -402,6 +615,132 @@ impl SbtcRequests {
     }
 }
 
+/// Plans the capacity of sweep transactions given the current fee rate
+/// and the mix of pending requests.
+///
+/// The planner computes how many deposits and withdrawals fit within a
+/// single standard bitcoin transaction, that is, one that is within the
+/// [`MAX_STANDARD_TX_WEIGHT`] and [`MAX_STANDARD_TX_SIGOPS_COST`] limits.
+/// Requests are sized using the largest pending request of each kind, so
+/// the estimates are conservative.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SweepCapacityPlanner {
+    /// The current market fee rate in sat/vByte.
+    pub fee_rate: f64,
+    /// The number of pending deposit requests.
+    pub pending_deposits: u64,
+    /// The number of pending withdrawal requests.
+    pub pending_withdrawals: u64,
+    /// The weight of a deposit input, in weight units.
+    pub deposit_weight: u64,
+    /// The weight of a withdrawal output, in weight units.
+    pub withdrawal_weight: u64,
+    /// The signature operation cost of a withdrawal output.
+    pub withdrawal_sigops_cost: u64,
+}
+
+/// The capacity of a sweep transaction, as computed by the
+/// [`SweepCapacityPlanner`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SweepCapacity {
+    /// The maximum number of deposits in a sweep transaction that does not
+    /// service any withdrawals.
+    pub max_deposits: u64,
+    /// The maximum number of withdrawals in a sweep transaction that does
+    /// not sweep any deposits.
+    pub max_withdrawals: u64,
+    /// The number of pending deposits that fit in the next sweep
+    /// transaction.
+    pub planned_deposits: u64,
+    /// The number of pending withdrawals that fit in the next sweep
+    /// transaction alongside the planned deposits.
+    pub planned_withdrawals: u64,
+    /// The virtual size of the next sweep transaction, in vBytes.
+    pub planned_vsize: u64,
+    /// The fee, in sats, of the next sweep transaction at the planner's
+    /// fee rate.
+    pub planned_fee: u64,
+}
+
+impl SweepCapacityPlanner {
+    /// Create a planner for the given pending requests.
+    pub fn new(
+        fee_rate: f64,
+        deposits: &[DepositRequest],
+        withdrawals: &[WithdrawalRequest],
+    ) -> Self {
+        let deposit_weight = deposits
+            .iter()
+            .map(|req| req.as_tx_input(*DUMMY_SIGNATURE).segwit_weight().to_wu())
+            .max()
+            .unwrap_or(DEFAULT_DEPOSIT_INPUT_WEIGHT);
+        let withdrawal_weight = withdrawals
+            .iter()
+            .map(|req| req.as_tx_output().weight().to_wu())
+            .max()
+            .unwrap_or(DEFAULT_WITHDRAWAL_OUTPUT_WEIGHT);
+        // Only legacy signature operations in the output scripts count
+        // towards the limit. The inputs are all taproot inputs, and
+        // tapscript signature operations are budgeted separately.
+        let withdrawal_sigops_cost = withdrawals
+            .iter()
+            .map(|req| req.script_pubkey.count_sigops_legacy() as u64 * WITNESS_SCALE_FACTOR)
+            .max()
+            .unwrap_or(DEFAULT_WITHDRAWAL_OUTPUT_SIGOPS_COST);
+
+        Self {
+            fee_rate,
+            pending_deposits: deposits.len() as u64,
+            pending_withdrawals: withdrawals.len() as u64,
+            deposit_weight,
+            withdrawal_weight,
+            withdrawal_sigops_cost,
+        }
+    }
+
+    /// Compute the capacity of the next sweep transaction.
+    ///
+    /// Deposits are planned first, since they are the ones that need to
+    /// be signed, and withdrawals fill the rest of the transaction.
+    pub fn capacity(&self) -> SweepCapacity {
+        let base_weight = MAX_BASE_TX_VSIZE * WITNESS_SCALE_FACTOR;
+        let available = MAX_STANDARD_TX_WEIGHT.saturating_sub(base_weight);
+
+        let max_deposits = available / self.deposit_weight.max(1);
+        let max_withdrawals = self.withdrawals_within(available);
+
+        let planned_deposits = self.pending_deposits.min(max_deposits);
+        let remaining = available.saturating_sub(planned_deposits * self.deposit_weight);
+        let planned_withdrawals = self
+            .pending_withdrawals
+            .min(self.withdrawals_within(remaining));
+
+        let planned_weight = base_weight
+            + planned_deposits * self.deposit_weight
+            + planned_withdrawals * self.withdrawal_weight;
+        let planned_vsize = planned_weight.div_ceil(WITNESS_SCALE_FACTOR);
+
+        SweepCapacity {
+            max_deposits,
+            max_withdrawals,
+            planned_deposits,
+            planned_withdrawals,
+            planned_vsize,
+            planned_fee: (planned_vsize as f64 * self.fee_rate).ceil() as u64,
+        }
+    }
+
+    /// The number of withdrawal outputs that fit within the given weight
+    /// while keeping the transaction within the signature operation limit.
+    fn withdrawals_within(&self, weight: u64) -> u64 {
+        let by_weight = weight / self.withdrawal_weight.max(1);
+        let by_sigops = MAX_STANDARD_TX_SIGOPS_COST
+            .checked_div(self.withdrawal_sigops_cost)
+            .unwrap_or(u64::MAX);
+        by_weight.min(by_sigops)
+    }
+}
+
 /// Calculate the total fee necessary for a transaction of the given size
 /// to be accepted by the network. Supports computing the fee in case this
 /// is a replace-by-fee (RBF) transaction by specifying the fees paid
@@ -697,6 +1036,20 @@ impl<'a> RequestRef<'a> {
             RequestRef::Withdrawal(req) => req.signer_bitmap,
         }
     }
+
+    /// The maximum fee that the underlying request is willing to pay.
+    pub fn max_fee(&self) -> u64 {
+        match self {
+            RequestRef::Deposit(req) => req.max_fee,
+            RequestRef::Withdrawal(req) => req.max_fee,
+        }
+    }
+
+    /// The maximum fee per vbyte that the underlying request is willing to
+    /// pay for the space that it takes up in a sweep transaction.
+    pub fn fee_contribution(&self) -> f64 {
+        self.max_fee() as f64 / self.vsize().max(1) as f64
+    }
 }
 
 impl Weighted for RequestRef<'_> {
@@ -1024,7 +1377,8 @@ impl<'a> UnsignedTransaction<'a> {
     ///   1. The amounts for each output has taken fees into consideration.
     ///   2. The signer input UTXO is the first input.
     ///   3. The signer output UTXO is the first output. The second output
-    ///      is the OP_RETURN data output.
+    ///      is the OP_RETURN data output. Any additional signers' outputs
+    ///      come after the withdrawal outputs.
     ///   4. Each input needs a signature in the witness data.
     ///   5. There is no witness data for deposit UTXOs.
     pub fn new(requests: Requests<'a>, state: &SignerBtcState) -> Result<Self, Error> {
@@ -1048,7 +1402,8 @@ impl<'a> UnsignedTransaction<'a> {
     ///   1. The amounts for each output has taken fees into consideration.
     ///   2. The signer input UTXO is the first input.
     ///   3. The signer output UTXO is the first output. The second output
-    ///      is the OP_RETURN data output.
+    ///      is the OP_RETURN data output. Any additional signers' outputs
+    ///      come after the withdrawal outputs.
     ///   4. Each input has a fake signature in the witness data.
     ///   5. All witness data is correctly set, except for the fake
     ///      signatures from (4).
@@ -1147,6 +1502,14 @@ impl<'a> UnsignedTransaction<'a> {
             .sum()
     }
 
+    /// The most that this transaction may pay in fees. This is the fee at
+    /// the maximum fee rate that the signers accept, taking into account
+    /// any transaction that this one replaces.
+    pub fn max_tx_fee(&self) -> u64 {
+        let state = &self.signer_utxo;
+        compute_transaction_fee(self.tx_vsize as f64, MAX_BITCOIN_FEE_RATE, state.last_fees)
+    }
+
     /// Compute the sum of the output amounts of the transaction.
     pub fn output_amounts(&self) -> u64 {
         self.tx.output.iter().map(|out| out.value.to_sat()).sum()
@@ -1165,7 +1528,12 @@ impl<'a> UnsignedTransaction<'a> {
 
         let signer_input = state.utxo.as_tx_input(&signature);
         let signer_output_sats = Self::compute_signer_amount(reqs, state)?;
+        let (signer_output_sats, change_sats) =
+            Self::split_signer_amount(signer_output_sats, state.change_outputs);
         let signer_output = SignerUtxo::new_tx_output(state.public_key, signer_output_sats);
+        let change_outputs = change_sats
+            .into_iter()
+            .map(|amount| SignerUtxo::new_tx_output(state.public_key, amount));
 
         Ok(Transaction {
             version: Version::TWO,
@@ -1174,10 +1542,28 @@ impl<'a> UnsignedTransaction<'a> {
             output: std::iter::once(signer_output)
                 .chain(Some(Self::new_op_return_output(reqs, state)?))
                 .chain(reqs.tx_outs())
+                .chain(change_outputs)
                 .collect(),
         })
     }
 
+    /// Split the signers' amount across at most `change_outputs` outputs.
+    ///
+    /// This returns the amount for the first signers' output and the
+    /// amounts for any additional signers' outputs. The additional outputs
+    /// hold the same amount, and the first output holds the remainder,
+    /// since it pays the transaction fee. The number of outputs is reduced
+    /// until each output holds at least [`MIN_SIGNER_CHANGE_OUTPUT_AMOUNT`]
+    /// sats.
+    fn split_signer_amount(amount: u64, change_outputs: u8) -> (u64, Vec<u64>) {
+        let max_outputs = (amount / MIN_SIGNER_CHANGE_OUTPUT_AMOUNT).max(1);
+        let count = u64::from(change_outputs).clamp(1, max_outputs);
+        let share = amount / count;
+        let first = amount - share * (count - 1);
+
+        (first, vec![share; (count - 1) as usize])
+    }
+
     /// Create the new SignerUtxo for this transaction.
     pub fn new_signer_utxo(&self) -> SignerUtxo {
         SignerUtxo {
@@ -1212,32 +1598,19 @@ impl<'a> UnsignedTransaction<'a> {
     /// - encoded IDs: withdrawal request IDs encoded using idpack (variable
     ///   length, if there are withdrawals serviced by the transaction)
     ///
+    /// This is the version 1 layout, see [`crate::bitcoin::op_return`] for
+    /// all versions of the format.
+    ///
     /// ## Returns
     /// - `Some(TxOut)`: the resulting OP_RETURN output
     fn new_op_return_output(reqs: &Requests, state: &SignerBtcState) -> Result<TxOut, Error> {
-        // Create OP_RETURN data
-        let mut data = PushBytesBuf::with_capacity(OP_RETURN_MAX_SIZE);
-        data.extend_from_slice(&state.magic_bytes)?;
-        data.push(OP_RETURN_VERSION)?;
-
         // Extract all withdrawal request IDs
         let withdrawal_ids: Vec<u64> = reqs.iter().filter_map(|req| req.withdrawal_id()).collect();
 
-        // If there are any withdrawal ID's, encode them and add them to the
-        // OP_RETURN data.
-        if !withdrawal_ids.is_empty() {
-            let encoded = BitmapSegmenter.package(&withdrawal_ids)?.encode();
-            data.extend_from_slice(&encoded)?;
-        }
-
-        // Return an error if the data we intend on putting in the OP_RETURN
-        // output exceeds the maximum size.
-        if data.len() > OP_RETURN_MAX_SIZE {
-            return Err(Error::OpReturnSizeLimitExceeded {
-                size: data.len(),
-                max_size: OP_RETURN_MAX_SIZE,
-            });
-        }
+        // Create OP_RETURN data, this returns an error if the data we
+        // intend on putting in the OP_RETURN output exceeds the maximum
+        // size.
+        let data = SweepCommitment::V1 { withdrawal_ids }.encode(state.magic_bytes)?;
 
         // Create OP_RETURN script and output
         let script_pubkey = ScriptBuf::new_op_return(data);
@@ -1304,6 +1677,32 @@ impl<'a> UnsignedTransaction<'a> {
     }
 }
 
+/// Check the witness of a single input of a sweep transaction. The
+/// signers' input is the first input and is spent using the taproot key
+/// path, all other inputs are deposits spent using the deposit script
+/// path.
+fn check_input_witness(witness: &Witness, index: usize) -> Result<(), Error> {
+    if witness.taproot_annex().is_some() {
+        return Err(Error::SweepTaprootAnnex(index));
+    }
+
+    // BIP-341 signatures are 64 bytes, or 65 bytes when the sighash type
+    // is not the default one.
+    let signature_ok = witness
+        .nth(0)
+        .is_some_and(|sig| sig.len() == 64 || sig.len() == 65);
+    let shape_ok = match index {
+        0 => witness.len() == 1,
+        _ => witness.len() == 3,
+    };
+
+    if !signature_ok || !shape_ok {
+        return Err(Error::UnexpectedSweepWitness(index));
+    }
+
+    Ok(())
+}
+
 /// A trait where we return all inputs and outputs for a bitcoin
 /// transaction.
 pub trait BitcoinInputsOutputs {
@@ -1440,6 +1839,25 @@ impl BitcoinTxInfo {
     pub fn assess_output_fee(&self, vout: usize) -> Option<Amount> {
         FeeAssessment::assess_output_fee(self, vout, self.fee?)
     }
+
+    /// Check that the witness data of each input of this sweep transaction
+    /// has exactly the shape of the witness data that the signers create.
+    ///
+    /// The fees assessed against the requests depend on the weight of the
+    /// transaction as it was broadcast by the coordinator. The signers'
+    /// input is spent using the taproot key path, so its witness is a lone
+    /// signature, while deposit inputs are spent using the deposit script
+    /// path, so their witness is a signature, the deposit script, and a
+    /// control block. Anything else, such as a taproot annex, changes the
+    /// weight, and so the assessed fees, of the transaction, so we reject
+    /// it before assessing fees.
+    pub fn check_witness_data(&self) -> Result<(), Error> {
+        self.tx
+            .input
+            .iter()
+            .enumerate()
+            .try_for_each(|(index, tx_in)| check_input_witness(&tx_in.witness, index))
+    }
 }
 
 /// An output used as an input into a transaction, a previous output.
@@ -1523,17 +1941,54 @@ pub trait TxDeconstructor: BitcoinInputsOutputs {
                 .collect();
         }
 
+        // Outputs that the signers control and that come after the
+        // withdrawal outputs hold the rest of the signers' change.
+        let change_start = self.committed_withdrawal_count().map(|count| count + 2);
+        let is_change = |index: usize, tx_out: &TxOut| {
+            change_start.is_some_and(|start| index >= start)
+                && signer_script_pubkeys.contains(&tx_out.script_pubkey)
+        };
+
         self.outputs()
             .iter()
             .enumerate()
-            .filter_map(|(index, _)| match index {
+            .filter_map(|(index, tx_out)| match index {
                 0 => self.vout_to_output(index, TxOutputType::SignersOutput),
                 1 => self.vout_to_output(index, TxOutputType::SignersOpReturn),
+                _ if is_change(index, tx_out) => {
+                    self.vout_to_output(index, TxOutputType::SignersOutput)
+                }
                 _ => self.vout_to_output(index, TxOutputType::Withdrawal),
             })
             .collect()
     }
 
+    /// Return the number of withdrawal requests that the OP_RETURN output
+    /// of this transaction commits to.
+    ///
+    /// This returns `None` if the second output is not an OP_RETURN
+    /// output that we can parse, or if it is a version 0 OP_RETURN, since
+    /// those did not commit to the withdrawal requests.
+    fn committed_withdrawal_count(&self) -> Option<usize> {
+        let op_return_output = self.outputs().get(1)?;
+        let instructions: Vec<_> = op_return_output.script_pubkey.instructions().collect();
+        let [
+            Ok(Instruction::Op(OP_RETURN)),
+            Ok(Instruction::PushBytes(push_bytes)),
+        ] = instructions[..]
+        else {
+            return None;
+        };
+
+        match OpReturnPayload::parse(push_bytes.as_bytes())
+            .ok()?
+            .commitment
+        {
+            SweepCommitment::V0 => None,
+            commitment => Some(commitment.withdrawal_ids().len()),
+        }
+    }
+
     /// Return the withdrawal outputs, matching the tx outputs to the decoded
     /// withdrawal IDs
     fn to_withdrawal_outputs(
@@ -1558,13 +2013,19 @@ pub trait TxDeconstructor: BitcoinInputsOutputs {
         }
 
         // SAFETY: we checked that we have at least two outputs in the matches
-        let tx_withdrawals_outputs = &tx_outputs[2..];
+        let (tx_withdrawals_outputs, tx_change_outputs) = tx_outputs[2..].split_at(
+            tx_outputs[2..]
+                .iter()
+                .take_while(|out| out.output_type == TxOutputType::Withdrawal)
+                .count(),
+        );
 
-        // Sanity check: all the other outputs must be withdrawals
-        let is_all_withdrawals = tx_withdrawals_outputs
+        // Sanity check: all the other outputs must be withdrawals,
+        // followed by any additional signers' outputs.
+        let is_all_change = tx_change_outputs
             .iter()
-            .all(|out| out.output_type == TxOutputType::Withdrawal);
-        if !is_all_withdrawals {
+            .all(|out| out.output_type == TxOutputType::SignersOutput);
+        if !is_all_change {
             return Err(Error::SbtcTxMalformed);
         }
 
@@ -1583,44 +2044,26 @@ pub trait TxDeconstructor: BitcoinInputsOutputs {
             return Err(Error::SbtcTxOpReturnFormatError);
         };
 
-        let raw_bytes = push_bytes.as_bytes();
-        if raw_bytes.len() < OP_RETURN_HEADER_SIZE {
-            return Err(Error::SbtcTxOpReturnFormatError);
-        }
-
-        // First two bytes are magic bytes, we don't care about them.
-        // The third one is the version byte.
-        // SAFETY: 2 < OP_RETURN_HEADER_SIZE (3)
-        let version = raw_bytes[2];
+        // We don't care about the magic bytes here. Unknown versions are
+        // reported with `Error::OpReturnUnsupportedVersion`.
+        let payload = OpReturnPayload::parse(push_bytes.as_bytes())?;
 
-        if version == 0 {
-            // In version 0 we didn't store withdrawal ids
+        // In version 0 we didn't store withdrawal ids
+        if let SweepCommitment::V0 = payload.commitment {
             return Ok(Vec::new());
-        } else if version != OP_RETURN_VERSION {
-            // Unknown version byte
-            return Err(Error::SbtcTxOpReturnFormatError);
         }
-
-        // SAFETY: We've verified raw_bytes.len() >= OP_RETURN_HEADER_SIZE (3),
-        // so starting a slice at index 3 is safe due to slice behavior.
-        // If raw_bytes.len() is exactly 3, this produces an empty slice rather
-        // than panicking.
-        let encoded_withdrawal_ids = &raw_bytes[OP_RETURN_HEADER_SIZE..];
-        let withdrawal_ids: Vec<_> = Segments::decode(encoded_withdrawal_ids)
-            .map_err(Error::IdPackDecode)?
-            .values()
-            .collect();
+        let withdrawal_ids = payload.commitment.withdrawal_ids();
 
         // We checked that the first two outputs are signers output and op
-        // return, and that the rest of outputs are withdrawals.
-        if withdrawal_ids.len() != tx_outputs.len() - 2 {
+        // return, and that they are followed by the withdrawal outputs.
+        if withdrawal_ids.len() != tx_withdrawals_outputs.len() {
             return Err(Error::SbtcTxMalformed);
         }
 
         Ok(tx_withdrawals_outputs
             .iter()
             .zip(withdrawal_ids)
-            .map(|(out, request_id)| WithdrawalTxOutput {
+            .map(|(out, &request_id)| WithdrawalTxOutput {
                 txid: out.txid,
                 output_index: out.output_index,
                 request_id,
@@ -1694,6 +2137,7 @@ mod tests {
     use bitcoin::key::TapTweak as _;
     use bitcoin::opcodes::all::OP_RETURN;
     use bitcoin::script::Instruction;
+    use bitcoin::script::PushBytesBuf;
     use clarity::vm::types::PrincipalData;
     use fake::Fake as _;
     use model::SignerVote;
@@ -1702,6 +2146,9 @@ mod tests {
     use rand::distributions::Uniform;
     use rand::rngs::OsRng;
     use sbtc::deposits::DepositScriptInputs;
+    use sbtc::idpack::BitmapSegmenter;
+    use sbtc::idpack::Encodable as _;
+    use sbtc::idpack::Segmenter as _;
     use secp256k1::Keypair;
     use secp256k1::SecretKey;
     use stacks_common::types::chainstate::StacksAddress;
@@ -1709,6 +2156,8 @@ mod tests {
 
     use crate::DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX;
     use crate::MAX_MEMPOOL_PACKAGE_TX_COUNT;
+    use crate::bitcoin::op_return::CommitmentV2;
+    use crate::bitcoin::op_return::OP_RETURN_VERSION;
     use crate::context::RollingWithdrawalLimits;
     use crate::testing;
     use crate::testing::btc::base_signer_transaction;
@@ -1930,11 +2379,16 @@ mod tests {
                 public_key: generate_x_only_public_key(),
                 last_fees: None,
                 magic_bytes: [0; 2],
+                change_outputs: 1,
             },
             num_signers: 10,
+            spare_signer_utxos: Vec::new(),
             accept_threshold: 2,
             sbtc_limits: SbtcLimits::unlimited(),
             max_deposits_per_bitcoin_tx: DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX,
+            max_withdrawals_per_bitcoin_tx: u16::MAX,
+            max_withdrawal_value: u64::MAX,
+            prioritization: RequestPrioritization::Fifo,
         };
         let keypair = Keypair::new_global(&mut OsRng);
 
@@ -1989,6 +2443,64 @@ mod tests {
         println!("Min input vsize: {}", utxo.segwit_weight().to_vbytes_ceil());
     }
 
+    /// Only witness data with the shape that the signers create passes the
+    /// witness check of a broadcast sweep transaction.
+    #[test]
+    fn witness_data_check_rejects_annex_and_extra_elements() {
+        let public_key = XOnlyPublicKey::from_str(X_ONLY_PUBLIC_KEY1).unwrap();
+        let requests = SbtcRequests {
+            deposits: vec![create_deposit(123_456, 10_000, 0)],
+            withdrawals: vec![create_withdrawal(10_000, 10_000, 0)],
+            signer_state: SignerBtcState {
+                utxo: SignerUtxo {
+                    outpoint: OutPoint::null(),
+                    amount: 50_000,
+                    public_key,
+                },
+                fee_rate: 1.0,
+                public_key,
+                last_fees: None,
+                magic_bytes: [0; 2],
+                change_outputs: 1,
+            },
+            num_signers: 10,
+            spare_signer_utxos: Vec::new(),
+            accept_threshold: 0,
+            sbtc_limits: SbtcLimits::unlimited(),
+            max_deposits_per_bitcoin_tx: DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX,
+            max_withdrawals_per_bitcoin_tx: u16::MAX,
+            max_withdrawal_value: u64::MAX,
+            prioritization: RequestPrioritization::Fifo,
+        };
+        let keypair = Keypair::new_global(&mut OsRng);
+
+        let mut transactions = requests.construct_transactions().unwrap();
+        let mut unsigned = transactions.pop().unwrap();
+        let tx_info = BitcoinTxInfo::from_tx(unsigned.tx.clone(), Amount::ZERO);
+        let result = tx_info.check_witness_data();
+        assert!(matches!(result, Err(Error::UnexpectedSweepWitness(0))));
+
+        testing::set_witness_data(&mut unsigned, keypair);
+        let tx_info = BitcoinTxInfo::from_tx(unsigned.tx.clone(), Amount::ZERO);
+        tx_info.check_witness_data().unwrap();
+
+        // An annex is the last witness element when it starts with 0x50.
+        let mut tx_info = BitcoinTxInfo::from_tx(unsigned.tx.clone(), Amount::ZERO);
+        tx_info.tx.input[1].witness.push([0x50, 0x01]);
+        let result = tx_info.check_witness_data();
+        assert!(matches!(result, Err(Error::SweepTaprootAnnex(1))));
+
+        let mut tx_info = BitcoinTxInfo::from_tx(unsigned.tx.clone(), Amount::ZERO);
+        tx_info.tx.input[0].witness.push([0x01]);
+        let result = tx_info.check_witness_data();
+        assert!(matches!(result, Err(Error::UnexpectedSweepWitness(0))));
+
+        let mut tx_info = BitcoinTxInfo::from_tx(unsigned.tx.clone(), Amount::ZERO);
+        tx_info.tx.input[1].witness = tx_info.tx.input[0].witness.clone();
+        let result = tx_info.check_witness_data();
+        assert!(matches!(result, Err(Error::UnexpectedSweepWitness(1))));
+    }
+
     #[test_case(&[true, true, false, true, false, false, false], 3; "case 1")]
     #[test_case(&[true, true, false, false, false, false, false], 2; "case 2")]
     #[test_case(&[false, false, false, false, false, false, false], 0; "case 3")]
@@ -2054,11 +2566,16 @@ mod tests {
                 public_key: generate_x_only_public_key(),
                 last_fees: None,
                 magic_bytes: [0; 2],
+                change_outputs: 1,
             },
             num_signers: 10,
+            spare_signer_utxos: Vec::new(),
             accept_threshold: 0,
             sbtc_limits: SbtcLimits::unlimited(),
             max_deposits_per_bitcoin_tx: DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX,
+            max_withdrawals_per_bitcoin_tx: u16::MAX,
+            max_withdrawal_value: u64::MAX,
+            prioritization: RequestPrioritization::Fifo,
         };
 
         // This should all be in one transaction since there are no votes
@@ -2117,6 +2634,7 @@ mod tests {
             public_key,
             last_fees: None,
             magic_bytes: [0; 2],
+            change_outputs: 1,
         };
 
         let requests = Requests::new(Vec::new());
@@ -2150,11 +2668,16 @@ mod tests {
                 public_key,
                 last_fees: None,
                 magic_bytes: [b'S', b'T'],
+                change_outputs: 1,
             },
             num_signers: 10,
+            spare_signer_utxos: Vec::new(),
             accept_threshold: 8,
             sbtc_limits: SbtcLimits::unlimited(),
             max_deposits_per_bitcoin_tx: DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX,
+            max_withdrawals_per_bitcoin_tx: u16::MAX,
+            max_withdrawal_value: u64::MAX,
+            prioritization: RequestPrioritization::Fifo,
         };
 
         // Generate transactions
@@ -2264,11 +2787,16 @@ mod tests {
                 public_key,
                 last_fees: None,
                 magic_bytes: [0; 2],
+                change_outputs: 1,
             },
             num_signers: 10,
+            spare_signer_utxos: Vec::new(),
             accept_threshold: 0,
             sbtc_limits: SbtcLimits::unlimited(),
             max_deposits_per_bitcoin_tx: DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX,
+            max_withdrawals_per_bitcoin_tx: u16::MAX,
+            max_withdrawal_value: u64::MAX,
+            prioritization: RequestPrioritization::Fifo,
         };
 
         // This should all be in one transaction since there are no votes
@@ -2309,11 +2837,16 @@ mod tests {
                 public_key,
                 last_fees: None,
                 magic_bytes: [0; 2],
+                change_outputs: 1,
             },
             num_signers: 10,
+            spare_signer_utxos: Vec::new(),
             accept_threshold: 0,
             sbtc_limits: SbtcLimits::unlimited(),
             max_deposits_per_bitcoin_tx: DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX,
+            max_withdrawals_per_bitcoin_tx: u16::MAX,
+            max_withdrawal_value: u64::MAX,
+            prioritization: RequestPrioritization::Fifo,
         };
 
         // This should all be in one transaction since there are no votes
@@ -2360,11 +2893,16 @@ mod tests {
                 public_key,
                 last_fees: None,
                 magic_bytes: [0; 2],
+                change_outputs: 1,
             },
             num_signers: 10,
+            spare_signer_utxos: Vec::new(),
             accept_threshold: 0,
             sbtc_limits: SbtcLimits::unlimited(),
             max_deposits_per_bitcoin_tx: DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX,
+            max_withdrawals_per_bitcoin_tx: u16::MAX,
+            max_withdrawal_value: u64::MAX,
+            prioritization: RequestPrioritization::Fifo,
         };
 
         let mut transactions = requests.construct_transactions().unwrap();
@@ -2382,6 +2920,58 @@ mod tests {
         assert_eq!(signer_utxo.value.to_sat(), 9500 - 1000 - 2000 - 3000);
     }
 
+    /// Operators can cap the number of withdrawals in each transaction
+    /// and their total value in each package. Withdrawals that do not fit
+    /// within the value cap are left out, in priority order.
+    #[test]
+    fn withdrawal_caps_are_enforced_during_construction() {
+        let public_key = XOnlyPublicKey::from_str(X_ONLY_PUBLIC_KEY1).unwrap();
+        let requests = SbtcRequests {
+            deposits: Vec::new(),
+            withdrawals: vec![
+                create_withdrawal(1000, 0, 0),
+                create_withdrawal(2000, 0, 0),
+                create_withdrawal(5000, 0, 0),
+                create_withdrawal(3000, 0, 0),
+            ],
+            signer_state: SignerBtcState {
+                utxo: SignerUtxo {
+                    outpoint: OutPoint::null(),
+                    amount: 50_000,
+                    public_key,
+                },
+                fee_rate: 0.0,
+                public_key,
+                last_fees: None,
+                magic_bytes: [0; 2],
+                change_outputs: 1,
+            },
+            num_signers: 10,
+            spare_signer_utxos: Vec::new(),
+            accept_threshold: 0,
+            sbtc_limits: SbtcLimits::unlimited(),
+            max_deposits_per_bitcoin_tx: DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX,
+            max_withdrawals_per_bitcoin_tx: 2,
+            max_withdrawal_value: 6000,
+            prioritization: RequestPrioritization::Fifo,
+        };
+
+        let transactions = requests.construct_transactions().unwrap();
+
+        // The 5000 sat withdrawal does not fit after the 1000 and 2000 sat
+        // ones, but the later 3000 sat one does, so only three withdrawals
+        // are serviced, with at most two of them in each transaction.
+        let mut amounts: Vec<u64> = transactions
+            .iter()
+            .inspect(|unsigned| assert!(unsigned.requests.len() <= 2))
+            .flat_map(|unsigned| unsigned.requests.iter())
+            .filter_map(|req| req.as_withdrawal())
+            .map(|req| req.amount)
+            .collect();
+        amounts.sort();
+        assert_eq!(amounts, [1000, 2000, 3000]);
+    }
+
     /// We chain transactions so that we have a single signer UTXO at the end.
     #[test]
     fn returned_txs_form_a_tx_chain() {
@@ -2408,11 +2998,16 @@ mod tests {
                 public_key,
                 last_fees: None,
                 magic_bytes: [0; 2],
+                change_outputs: 1,
             },
             num_signers: 10,
+            spare_signer_utxos: Vec::new(),
             accept_threshold: 8,
             sbtc_limits: SbtcLimits::unlimited(),
             max_deposits_per_bitcoin_tx: DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX,
+            max_withdrawals_per_bitcoin_tx: u16::MAX,
+            max_withdrawal_value: u64::MAX,
+            prioritization: RequestPrioritization::Fifo,
         };
 
         let transactions = requests.construct_transactions().unwrap();
@@ -2434,6 +3029,261 @@ mod tests {
         })
     }
 
+    /// Reconstructing a package from the IDs of the requests in each of
+    /// its transactions gives back the same transactions.
+    #[test]
+    fn reconstructed_txs_match_constructed_txs() {
+        let public_key = XOnlyPublicKey::from_str(X_ONLY_PUBLIC_KEY1).unwrap();
+        let mut requests = SbtcRequests {
+            deposits: vec![
+                create_deposit(1234, 0, 1 << 1),
+                create_deposit(5678, 0, 1 << 2),
+                create_deposit(9012, 0, (1 << 3) | (1 << 4)),
+            ],
+            withdrawals: vec![
+                create_withdrawal(1000, 0, 1 << 5),
+                create_withdrawal(2000, 0, 1 << 6),
+                create_withdrawal(3000, 0, 1 << 7),
+                create_withdrawal(4000, 0, (1 << 8) | (1 << 9)),
+            ],
+            signer_state: SignerBtcState {
+                utxo: SignerUtxo {
+                    outpoint: generate_outpoint(300_000, 0),
+                    amount: 300_000,
+                    public_key,
+                },
+                fee_rate: 5.0,
+                public_key,
+                last_fees: None,
+                magic_bytes: [0; 2],
+                change_outputs: 1,
+            },
+            num_signers: 10,
+            spare_signer_utxos: Vec::new(),
+            accept_threshold: 8,
+            sbtc_limits: SbtcLimits::unlimited(),
+            max_deposits_per_bitcoin_tx: DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX,
+            max_withdrawals_per_bitcoin_tx: u16::MAX,
+            max_withdrawal_value: u64::MAX,
+            prioritization: RequestPrioritization::Fifo,
+        };
+
+        let transactions = requests.construct_transactions().unwrap();
+        more_asserts::assert_gt!(transactions.len(), 1);
+
+        let request_package: Vec<TxRequestIds> = transactions
+            .iter()
+            .map(|tx| TxRequestIds::from(&tx.requests))
+            .collect();
+        let expected: Vec<Txid> = transactions.iter().map(|tx| tx.tx.compute_txid()).collect();
+
+        let reconstructed = requests.reconstruct_transactions(&request_package).unwrap();
+        let actual: Vec<Txid> = reconstructed
+            .iter()
+            .map(|tx| tx.tx.compute_txid())
+            .collect();
+        assert_eq!(actual, expected);
+
+        // A package with a request that is no longer pending cannot be
+        // reconstructed.
+        let missing = request_package
+            .iter()
+            .find_map(|ids| ids.withdrawals.first())
+            .cloned()
+            .unwrap();
+        requests
+            .withdrawals
+            .retain(|req| req.qualified_id() != missing);
+        let result = requests.reconstruct_transactions(&request_package);
+        assert!(matches!(result, Err(Error::MissingWithdrawalRequest(_))));
+    }
+
+    #[test_case(1, 1, 1; "single output")]
+    #[test_case(0, 1, 1; "zero target means one output")]
+    #[test_case(3, 1, 3; "no spare utxos")]
+    #[test_case(3, 2, 2; "one spare utxo")]
+    #[test_case(3, 3, 1; "enough spare utxos")]
+    #[test_case(3, 10, 1; "more than enough spare utxos")]
+    fn change_outputs_needed_tops_up_signer_utxos(target: u8, available: usize, expected: u8) {
+        let needed = SignerBtcState::change_outputs_needed(target, available);
+        assert_eq!(needed, expected);
+    }
+
+    /// When asked to, the first transaction in the package splits the
+    /// signers' change across outputs after the withdrawal outputs, as
+    /// long as each of them holds enough sats.
+    #[test_case(1_000_000, 3, 3; "split into requested outputs")]
+    #[test_case(250_000, 3, 2; "split limited by amount")]
+    #[test_case(150_000, 3, 1; "amount too small to split")]
+    #[test_case(1_000_000, 1, 1; "no split requested")]
+    #[test_case(1_000_000, 0, 1; "zero means no split")]
+    fn signers_change_is_split_across_outputs(amount: u64, change_outputs: u8, expected: usize) {
+        let public_key = XOnlyPublicKey::from_str(X_ONLY_PUBLIC_KEY1).unwrap();
+        let signers_script_pubkey = public_key.signers_script_pubkey();
+        let requests = SbtcRequests {
+            deposits: Vec::new(),
+            withdrawals: vec![create_withdrawal(10_000, 0, 0)],
+            signer_state: SignerBtcState {
+                utxo: SignerUtxo {
+                    outpoint: generate_outpoint(amount, 0),
+                    amount,
+                    public_key,
+                },
+                fee_rate: 0.0,
+                public_key,
+                last_fees: None,
+                magic_bytes: [0; 2],
+                change_outputs,
+            },
+            num_signers: 10,
+            spare_signer_utxos: Vec::new(),
+            accept_threshold: 0,
+            sbtc_limits: SbtcLimits::unlimited(),
+            max_deposits_per_bitcoin_tx: DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX,
+            max_withdrawals_per_bitcoin_tx: u16::MAX,
+            max_withdrawal_value: u64::MAX,
+            prioritization: RequestPrioritization::Fifo,
+        };
+
+        let mut transactions = requests.construct_transactions().unwrap();
+        assert_eq!(transactions.len(), 1);
+        let unsigned_tx = transactions.pop().unwrap();
+
+        // The signers' output and the OP_RETURN output, then the
+        // withdrawal output, then the rest of the signers' outputs.
+        let outputs = &unsigned_tx.tx.output;
+        assert_eq!(outputs.len(), 2 + 1 + expected - 1);
+        assert!(outputs[1].script_pubkey.is_op_return());
+        assert_ne!(outputs[2].script_pubkey, signers_script_pubkey);
+
+        let signer_outputs: Vec<u64> = outputs
+            .iter()
+            .filter(|out| out.script_pubkey == signers_script_pubkey)
+            .map(|out| out.value.to_sat())
+            .collect();
+        assert_eq!(signer_outputs.len(), expected);
+        assert_eq!(signer_outputs.iter().sum::<u64>(), amount - 10_000);
+        signer_outputs[1..]
+            .iter()
+            .for_each(|&sats| assert_ge!(sats, MIN_SIGNER_CHANGE_OUTPUT_AMOUNT));
+
+        // Now check that we classify the outputs correctly once the
+        // transaction is confirmed.
+        let vin = unsigned_tx
+            .tx
+            .input
+            .iter()
+            .map(|tx_in| crate::bitcoin::rpc::BitcoinTxVin {
+                txid: Some(tx_in.previous_output.txid),
+                vout: Some(tx_in.previous_output.vout),
+                prevout: Some(crate::bitcoin::rpc::BitcoinTxVinPrevout {
+                    value: Amount::from_sat(amount),
+                    script_pubkey: crate::bitcoin::rpc::OutputScriptPubKey {
+                        script: signers_script_pubkey.clone(),
+                    },
+                }),
+            })
+            .collect();
+        let tx_info = BitcoinTxInfo {
+            fee: Some(Amount::ZERO),
+            tx: unsigned_tx.tx.clone(),
+            vin,
+        };
+
+        let signer_script_pubkeys = HashSet::from([signers_script_pubkey]);
+        let (tx_outputs, withdrawal_outputs) = tx_info.to_outputs(&signer_script_pubkeys).unwrap();
+        let output_types: Vec<TxOutputType> =
+            tx_outputs.iter().map(|out| out.output_type).collect();
+
+        let mut expected_types = vec![
+            TxOutputType::SignersOutput,
+            TxOutputType::SignersOpReturn,
+            TxOutputType::Withdrawal,
+        ];
+        expected_types.extend(std::iter::repeat_n(
+            TxOutputType::SignersOutput,
+            expected - 1,
+        ));
+        assert_eq!(output_types, expected_types);
+        assert_eq!(withdrawal_outputs.len(), 1);
+        assert_eq!(withdrawal_outputs[0].output_index, 2);
+    }
+
+    fn capacity_planner(pending_deposits: u64, pending_withdrawals: u64) -> SweepCapacityPlanner {
+        SweepCapacityPlanner {
+            fee_rate: 10.0,
+            pending_deposits,
+            pending_withdrawals,
+            deposit_weight: DEFAULT_DEPOSIT_INPUT_WEIGHT,
+            withdrawal_weight: DEFAULT_WITHDRAWAL_OUTPUT_WEIGHT,
+            withdrawal_sigops_cost: DEFAULT_WITHDRAWAL_OUTPUT_SIGOPS_COST,
+        }
+    }
+
+    #[test]
+    fn sweep_capacity_without_pending_requests() {
+        let capacity = capacity_planner(0, 0).capacity();
+
+        let available = MAX_STANDARD_TX_WEIGHT - MAX_BASE_TX_VSIZE * WITNESS_SCALE_FACTOR;
+        assert_eq!(
+            capacity.max_deposits,
+            available / DEFAULT_DEPOSIT_INPUT_WEIGHT
+        );
+        assert_eq!(
+            capacity.max_withdrawals,
+            available / DEFAULT_WITHDRAWAL_OUTPUT_WEIGHT
+        );
+        assert_eq!(capacity.planned_deposits, 0);
+        assert_eq!(capacity.planned_withdrawals, 0);
+        assert_eq!(capacity.planned_vsize, MAX_BASE_TX_VSIZE);
+        assert_eq!(capacity.planned_fee, MAX_BASE_TX_VSIZE * 10);
+    }
+
+    #[test]
+    fn sweep_capacity_plans_deposits_before_withdrawals() {
+        let capacity = capacity_planner(10_000, 10).capacity();
+
+        let available = MAX_STANDARD_TX_WEIGHT - MAX_BASE_TX_VSIZE * WITNESS_SCALE_FACTOR;
+        let remaining = available - capacity.max_deposits * DEFAULT_DEPOSIT_INPUT_WEIGHT;
+        assert_eq!(capacity.planned_deposits, capacity.max_deposits);
+        assert_eq!(
+            capacity.planned_withdrawals,
+            remaining / DEFAULT_WITHDRAWAL_OUTPUT_WEIGHT
+        );
+        more_asserts::assert_le!(
+            capacity.planned_vsize * WITNESS_SCALE_FACTOR,
+            MAX_STANDARD_TX_WEIGHT
+        );
+    }
+
+    #[test]
+    fn sweep_capacity_respects_sigops_limit() {
+        let planner = SweepCapacityPlanner {
+            withdrawal_sigops_cost: 20 * WITNESS_SCALE_FACTOR,
+            ..capacity_planner(0, 1_000)
+        };
+        let capacity = planner.capacity();
+
+        assert_eq!(capacity.max_withdrawals, 200);
+        assert_eq!(capacity.planned_withdrawals, 200);
+    }
+
+    #[test]
+    fn sweep_capacity_planner_measures_pending_requests() {
+        let deposits = vec![create_deposit(100_000, 10_000, 0); 3];
+        let withdrawals = vec![create_withdrawal(10_000, 1_000, 0); 2];
+        let planner = SweepCapacityPlanner::new(5.0, &deposits, &withdrawals);
+
+        assert_eq!(planner.pending_deposits, 3);
+        assert_eq!(planner.pending_withdrawals, 2);
+        more_asserts::assert_le!(planner.deposit_weight, deposits[0].vsize() * 4);
+        more_asserts::assert_le!(planner.withdrawal_weight, withdrawals[0].vsize() * 4);
+
+        let capacity = planner.capacity();
+        assert_eq!(capacity.planned_deposits, 3);
+        assert_eq!(capacity.planned_withdrawals, 2);
+    }
+
     /// Check that each deposit and withdrawal is included as an input or
     /// deposit in the transaction package.
     #[test]
@@ -2468,11 +3318,16 @@ mod tests {
                 public_key,
                 last_fees: None,
                 magic_bytes: [0; 2],
+                change_outputs: 1,
             },
             num_signers: 10,
+            spare_signer_utxos: Vec::new(),
             accept_threshold: 8,
             sbtc_limits: SbtcLimits::unlimited(),
             max_deposits_per_bitcoin_tx: DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX,
+            max_withdrawals_per_bitcoin_tx: u16::MAX,
+            max_withdrawal_value: u64::MAX,
+            prioritization: RequestPrioritization::Fifo,
         };
 
         let transactions = requests.construct_transactions().unwrap();
@@ -2568,11 +3423,16 @@ mod tests {
                 public_key,
                 last_fees: None,
                 magic_bytes: [0; 2],
+                change_outputs: 1,
             },
             num_signers: 10,
+            spare_signer_utxos: Vec::new(),
             accept_threshold: 8,
             sbtc_limits: SbtcLimits::unlimited(),
             max_deposits_per_bitcoin_tx: DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX,
+            max_withdrawals_per_bitcoin_tx: u16::MAX,
+            max_withdrawal_value: u64::MAX,
+            prioritization: RequestPrioritization::Fifo,
         };
 
         let mut transactions = requests.construct_transactions().unwrap();
@@ -2634,11 +3494,16 @@ mod tests {
                 public_key,
                 last_fees: None,
                 magic_bytes: [0; 2],
+                change_outputs: 1,
             },
             num_signers: 10,
+            spare_signer_utxos: Vec::new(),
             accept_threshold: 8,
             sbtc_limits: SbtcLimits::unlimited(),
             max_deposits_per_bitcoin_tx: DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX,
+            max_withdrawals_per_bitcoin_tx: u16::MAX,
+            max_withdrawal_value: u64::MAX,
+            prioritization: RequestPrioritization::Fifo,
         };
 
         // In the below code, we need to make sure that we take the _first_
@@ -2711,11 +3576,16 @@ mod tests {
                 public_key,
                 last_fees: None,
                 magic_bytes: [0; 2],
+                change_outputs: 1,
             },
             num_signers: 10,
+            spare_signer_utxos: Vec::new(),
             accept_threshold: 8,
             sbtc_limits: SbtcLimits::unlimited(),
             max_deposits_per_bitcoin_tx: DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX,
+            max_withdrawals_per_bitcoin_tx: u16::MAX,
+            max_withdrawal_value: u64::MAX,
+            prioritization: RequestPrioritization::Fifo,
         };
         // If multiple_txs is specified, we add a withdrawal that will
         // cause the transaction to be split into two.
@@ -2734,6 +3604,72 @@ mod tests {
         assert_eq!(sighashes.deposits.len(), num_deposits)
     }
 
+    /// The transactions after the first one in a parallel sweep package
+    /// spend the spare signers' UTXOs in order, and chain off of the
+    /// signers' output of their parent once the spares run out.
+    #[test]
+    fn parallel_sweeps_spend_the_spare_signer_utxos() {
+        let public_key = XOnlyPublicKey::from_str(X_ONLY_PUBLIC_KEY1).unwrap();
+        let spare = SpareSignerUtxo {
+            utxo: SignerUtxo {
+                outpoint: generate_outpoint(200_000, 1),
+                amount: 200_000,
+                public_key,
+            },
+            last_fees: Some(Fees::new_unchecked(1_000, 100)),
+        };
+        // A different signer votes against each withdrawal, and each
+        // transaction can only have one signer voting against it, so
+        // each withdrawal lands in its own transaction.
+        let requests = SbtcRequests {
+            deposits: Vec::new(),
+            withdrawals: (0..3)
+                .map(|id| create_withdrawal(10_000, 100_000, 1 << id).wid(id))
+                .collect(),
+            signer_state: SignerBtcState {
+                utxo: SignerUtxo {
+                    outpoint: generate_outpoint(300_000_000, 0),
+                    amount: 300_000_000,
+                    public_key,
+                },
+                fee_rate: 25.0,
+                public_key,
+                last_fees: None,
+                magic_bytes: [0; 2],
+                change_outputs: SIGNER_UTXO_TARGET,
+            },
+            num_signers: 10,
+            spare_signer_utxos: vec![spare],
+            accept_threshold: 9,
+            sbtc_limits: SbtcLimits::unlimited(),
+            max_deposits_per_bitcoin_tx: DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX,
+            max_withdrawals_per_bitcoin_tx: u16::MAX,
+            max_withdrawal_value: u64::MAX,
+            prioritization: RequestPrioritization::Fifo,
+        };
+        assert!(requests.parallel_sweeps());
+
+        let transactions = requests.construct_transactions().unwrap();
+        assert_eq!(transactions.len(), 3);
+
+        let spent = |tx: &UnsignedTransaction| tx.tx.input[0].previous_output;
+        assert_eq!(spent(&transactions[0]), requests.signer_state.utxo.outpoint);
+        assert_eq!(spent(&transactions[1]), spare.utxo.outpoint);
+        assert_eq!(
+            spent(&transactions[2]),
+            OutPoint::new(transactions[1].tx.compute_txid(), 0)
+        );
+
+        // The transaction spending the spare replaces the one that
+        // already spends it in the mempool, so it pays more in fees.
+        more_asserts::assert_gt!(transactions[1].tx_fee, 1_000);
+
+        let spares = requests.spare_signer_outpoints();
+        let package = transactions.iter().map(|utx| &utx.tx);
+        let outpoint = requests.signer_state.utxo.outpoint;
+        assert!(crate::invariants::check_utxo_chain(outpoint, &spares, package).is_ok());
+    }
+
     /// If the signer's UTXO does not have enough to cover the requests
     /// then we return an error.
     #[test]
@@ -2756,11 +3692,16 @@ mod tests {
                 public_key,
                 last_fees: None,
                 magic_bytes: [0; 2],
+                change_outputs: 1,
             },
             num_signers: 10,
+            spare_signer_utxos: Vec::new(),
             accept_threshold: 0,
             sbtc_limits: SbtcLimits::unlimited(),
             max_deposits_per_bitcoin_tx: DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX,
+            max_withdrawals_per_bitcoin_tx: u16::MAX,
+            max_withdrawal_value: u64::MAX,
+            prioritization: RequestPrioritization::Fifo,
         };
 
         let transactions = requests.construct_transactions();
@@ -2814,11 +3755,16 @@ mod tests {
                 public_key,
                 last_fees: None,
                 magic_bytes: [0; 2],
+                change_outputs: 1,
             },
             num_signers: 10,
+            spare_signer_utxos: Vec::new(),
             accept_threshold: 8,
             sbtc_limits: SbtcLimits::unlimited(),
             max_deposits_per_bitcoin_tx: DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX,
+            max_withdrawals_per_bitcoin_tx: u16::MAX,
+            max_withdrawal_value: u64::MAX,
+            prioritization: RequestPrioritization::Fifo,
         };
 
         let mut transactions = requests.construct_transactions().unwrap();
@@ -3117,11 +4063,16 @@ mod tests {
                 public_key,
                 last_fees: None,
                 magic_bytes: [0; 2],
+                change_outputs: 1,
             },
             num_signers: 11,
+            spare_signer_utxos: Vec::new(),
             accept_threshold: 6,
             sbtc_limits: SbtcLimits::unlimited(),
             max_deposits_per_bitcoin_tx: DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX,
+            max_withdrawals_per_bitcoin_tx: u16::MAX,
+            max_withdrawal_value: u64::MAX,
+            prioritization: RequestPrioritization::Fifo,
         };
 
         // Let's construct the unsigned transaction and check to see if we
@@ -3163,11 +4114,16 @@ mod tests {
                 public_key: generate_x_only_public_key(),
                 last_fees: None,
                 magic_bytes: [0; 2],
+                change_outputs: 1,
             },
+            spare_signer_utxos: Vec::new(),
             accept_threshold: 127,
             num_signers: 128,
             sbtc_limits: SbtcLimits::unlimited(),
             max_deposits_per_bitcoin_tx: DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX,
+            max_withdrawals_per_bitcoin_tx: u16::MAX,
+            max_withdrawal_value: u64::MAX,
+            prioritization: RequestPrioritization::Fifo,
         };
 
         let transactions = requests.construct_transactions().unwrap();
@@ -3219,11 +4175,16 @@ mod tests {
                 public_key: generate_x_only_public_key(),
                 last_fees: None,
                 magic_bytes: [0; 2],
+                change_outputs: 1,
             },
+            spare_signer_utxos: Vec::new(),
             accept_threshold: 10,
             num_signers: 14,
             sbtc_limits: SbtcLimits::unlimited(),
             max_deposits_per_bitcoin_tx: DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX,
+            max_withdrawals_per_bitcoin_tx: u16::MAX,
+            max_withdrawal_value: u64::MAX,
+            prioritization: RequestPrioritization::Fifo,
         };
 
         let mut transactions = requests.construct_transactions().unwrap();
@@ -3264,6 +4225,89 @@ mod tests {
         assert_eq!(package_vsize, total_vsize);
     }
 
+    #[test]
+    fn request_prioritization_orders_by_fee_contribution() {
+        let withdrawals: Vec<WithdrawalRequest> = [100, 1_000, 500, 5_000, 1_000]
+            .into_iter()
+            .enumerate()
+            .map(|(id, max_fee)| create_withdrawal(10_000, max_fee, 0).wid(id as u64))
+            .collect();
+        let requests: Vec<RequestRef> = withdrawals.iter().map(RequestRef::Withdrawal).collect();
+        let order = |prioritization: RequestPrioritization| {
+            prioritization
+                .order(requests.clone())
+                .iter()
+                .map(|req| req.as_withdrawal().unwrap().request_id)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(order(RequestPrioritization::Fifo), [0, 1, 2, 3, 4]);
+        // Requests with the same fee contribution keep their arrival order.
+        let by_fee = RequestPrioritization::FeeContribution { fairness_floor: 0 };
+        assert_eq!(order(by_fee), [3, 1, 4, 2, 0]);
+        // The oldest requests come first, regardless of what they pay.
+        let with_floor = RequestPrioritization::FeeContribution { fairness_floor: 2 };
+        assert_eq!(order(with_floor), [0, 1, 3, 4, 2]);
+        // A floor larger than the number of requests is just FIFO.
+        let large_floor = RequestPrioritization::FeeContribution { fairness_floor: 10 };
+        assert_eq!(order(large_floor), [0, 1, 2, 3, 4]);
+    }
+
+    #[test_case(RequestPrioritization::Fifo, false; "fifo")]
+    #[test_case(RequestPrioritization::FeeContribution { fairness_floor: 0 }, true; "fee contribution")]
+    fn construct_transactions_prioritizes_requests(
+        prioritization: RequestPrioritization,
+        expect_all_high_fee_swept: bool,
+    ) {
+        // There are more withdrawals here than fit in a transaction
+        // package, see `construct_transactions_limits_package_vsize`. The
+        // last quarter of them pay a lot more than the rest.
+        const NUM_WITHDRAWALS: u64 = 4000;
+        const HIGH_FEE_START: u64 = NUM_WITHDRAWALS * 3 / 4;
+        let withdrawals: Vec<WithdrawalRequest> = (0..NUM_WITHDRAWALS)
+            .map(|id| {
+                let max_fee = if id >= HIGH_FEE_START { 50_000 } else { 1_000 };
+                create_withdrawal(1_000, max_fee, 1 << (id % 14)).wid(id)
+            })
+            .collect();
+
+        let requests = SbtcRequests {
+            deposits: Vec::new(),
+            withdrawals,
+            signer_state: SignerBtcState {
+                utxo: SignerUtxo {
+                    outpoint: OutPoint::null(),
+                    amount: 100000000,
+                    public_key: generate_x_only_public_key(),
+                },
+                fee_rate: 1.0,
+                public_key: generate_x_only_public_key(),
+                last_fees: None,
+                magic_bytes: [0; 2],
+                change_outputs: 1,
+            },
+            spare_signer_utxos: Vec::new(),
+            accept_threshold: 10,
+            num_signers: 14,
+            sbtc_limits: SbtcLimits::unlimited(),
+            max_deposits_per_bitcoin_tx: DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX,
+            max_withdrawals_per_bitcoin_tx: u16::MAX,
+            max_withdrawal_value: u64::MAX,
+            prioritization,
+        };
+
+        let transactions = requests.construct_transactions().unwrap();
+        let swept_high_fee = transactions
+            .iter()
+            .flat_map(|tx| tx.requests.iter())
+            .filter_map(RequestRef::as_withdrawal)
+            .filter(|req| req.request_id >= HIGH_FEE_START)
+            .count() as u64;
+
+        let all_high_fee_swept = swept_high_fee == NUM_WITHDRAWALS - HIGH_FEE_START;
+        assert_eq!(all_high_fee_swept, expect_all_high_fee_swept);
+    }
+
     #[test_case(0, 1, true; "total is zero")]
     #[test_case(100, 1, true; "total is one hundred")]
     #[test_case(f64::exp2(f64::MANTISSA_DIGITS as f64) as u64, 1, false; "total is max f64 lossless integer")]
@@ -3654,21 +4698,51 @@ mod tests {
         }))
         .output(TxOutputType::Withdrawal)
     ; "short pushbytes")]
-    #[test_case(&TestTxOut::default()
-        .output(TxOutputType::SignersOutput)
-        .op_return(ScriptBuf::new_op_return({
-            let mut pb = PushBytesBuf::new();
-            pb.extend_from_slice(&[0, 0, 42]).unwrap();
-            pb
-        }))
-        .output(TxOutputType::Withdrawal)
-    ; "wrong version")]
     fn test_to_withdrawal_outputs_malformed_opreturn(tx: &TestTxOut) {
         let tx_info = tx.tx_info();
         let withdrawal_outs = tx_info.to_withdrawal_outputs(&tx.tx_outputs).unwrap_err();
         assert!(matches!(withdrawal_outs, Error::SbtcTxOpReturnFormatError));
     }
 
+    #[test]
+    fn test_to_withdrawal_outputs_unsupported_version() {
+        let mut pb = PushBytesBuf::new();
+        pb.extend_from_slice(&[0, 0, 42]).unwrap();
+
+        let mut tx = TestTxOut::default();
+        tx.output(TxOutputType::SignersOutput)
+            .op_return(ScriptBuf::new_op_return(pb))
+            .output(TxOutputType::Withdrawal);
+
+        let tx_info = tx.tx_info();
+        let withdrawal_outs = tx_info.to_withdrawal_outputs(&tx.tx_outputs).unwrap_err();
+        assert!(matches!(
+            withdrawal_outs,
+            Error::OpReturnUnsupportedVersion(42)
+        ));
+    }
+
+    #[test]
+    fn test_to_withdrawal_outputs_v2() {
+        let commitment = CommitmentV2 {
+            withdrawal_ids: vec![42, 51],
+            aggregate_key_commitment: Some([1; 8]),
+            requests_merkle_root: Some([2; 32]),
+        };
+        let pb = SweepCommitment::V2(commitment).encode(*b"T3").unwrap();
+
+        let mut tx = TestTxOut::default();
+        tx.output(TxOutputType::SignersOutput)
+            .op_return(ScriptBuf::new_op_return(pb))
+            .output(TxOutputType::Withdrawal)
+            .output(TxOutputType::Withdrawal);
+
+        let tx_info = tx.tx_info();
+        let withdrawal_outs = tx_info.to_withdrawal_outputs(&tx.tx_outputs).unwrap();
+        let request_ids: Vec<u64> = withdrawal_outs.iter().map(|out| out.request_id).collect();
+        assert_eq!(request_ids, vec![42, 51]);
+    }
+
     #[test]
     fn test_to_withdrawal_outputs_happy_path() {
         let mut pb = PushBytesBuf::new();
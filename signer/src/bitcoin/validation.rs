@@ -1,27 +1,33 @@
 //! validation of bitcoin transactions.
 
+use std::collections::BTreeMap;
 use std::collections::HashMap;
-use std::collections::HashSet;
+use std::str::FromStr;
 
 use bitcoin::Amount;
 use bitcoin::OutPoint;
 use bitcoin::ScriptBuf;
 use bitcoin::XOnlyPublicKey;
 use bitcoin::relative::LockTime;
+use futures::StreamExt as _;
+use futures::TryStreamExt as _;
 
-use crate::BITCOIN_FEE_RATE_RANGE;
 use crate::DEPOSIT_DUST_LIMIT;
 use crate::DEPOSIT_LOCKTIME_BLOCK_BUFFER;
 use crate::WITHDRAWAL_BLOCKS_EXPIRY;
+use crate::bitcoin::rpc::ParallelSweepUtxos;
 use crate::bitcoin::rpc::assess_mempool_sweep_transaction_fees;
 use crate::bitcoin::utxo::FeeAssessment;
 use crate::bitcoin::utxo::SignerBtcState;
 use crate::context::Context;
 use crate::context::SbtcLimits;
+use crate::deposit_precheck;
+use crate::deposit_precheck::DepositVerdict;
 use crate::error::Error;
+use crate::invariants;
 use crate::keys::PublicKey;
 use crate::message::BitcoinPreSignRequest;
-use crate::storage::DbRead as _;
+use crate::storage::DbRead;
 use crate::storage::model::BitcoinBlockHash;
 use crate::storage::model::BitcoinBlockHeight;
 use crate::storage::model::BitcoinTxId;
@@ -29,15 +35,19 @@ use crate::storage::model::BitcoinTxRef;
 use crate::storage::model::BitcoinTxSigHash;
 use crate::storage::model::BitcoinWithdrawalOutput;
 use crate::storage::model::DkgSharesStatus;
+use crate::storage::model::OverrideTarget;
 use crate::storage::model::QualifiedRequestId;
 use crate::storage::model::SignerVotes;
+use crate::storage::model::StacksBlockHash;
 use crate::storage::model::TaprootScriptHash;
 use sbtc::WITHDRAWAL_MIN_CONFIRMATIONS;
 
+use super::package_rules;
 use super::utxo::DepositRequest;
 use super::utxo::RequestRef;
 use super::utxo::Requests;
 use super::utxo::SignatureHash;
+use super::utxo::SpareSignerUtxo;
 use super::utxo::UnsignedTransaction;
 use super::utxo::WithdrawalRequest;
 
@@ -112,20 +122,70 @@ impl From<&Requests<'_>> for TxRequestIds {
     }
 }
 
-/// Check that this does not contain duplicate deposits or withdrawals.
-pub fn is_unique(package: &[TxRequestIds]) -> bool {
-    let mut deposits_set = HashSet::new();
-    let mut withdrawal_request_id_set = HashSet::new();
+/// Fetch the report and the votes of the other signers for the deposit
+/// request locked by the given outpoint.
+async fn fetch_deposit_report(
+    db: &impl DbRead,
+    btc_ctx: &BitcoinTxContext,
+    outpoint: &OutPoint,
+) -> Result<(DepositRequestReport, SignerVotes), Error> {
+    let txid = outpoint.txid.into();
+    let output_index = outpoint.vout;
+
+    let report_future = db.get_deposit_request_report(
+        &btc_ctx.chain_tip,
+        &txid,
+        output_index,
+        &btc_ctx.signer_public_key,
+    );
+    let Some(mut report) = report_future.await? else {
+        return Err(InputValidationResult::Unknown.into_error(btc_ctx));
+    };
+
+    // An operator override takes precedence over the vote that we cast on
+    // the request.
+    let target = OverrideTarget::Deposit { txid, output_index };
+    if let Some(request_override) = db.get_active_request_override(&target).await? {
+        report.can_accept = Some(request_override.forces_accept());
+    }
 
-    package.iter().all(|reqs| {
-        let deposits = reqs.deposits.iter().all(|out| deposits_set.insert(out));
-        let withdrawal_requests = reqs
-            .withdrawals
-            .iter()
-            .all(|id| withdrawal_request_id_set.insert(id.request_id));
+    let votes = db
+        .get_deposit_request_signer_votes(&txid, output_index, &btc_ctx.aggregate_key)
+        .await?;
+
+    Ok((report, votes))
+}
+
+/// Fetch the report and the votes of the other signers for the withdrawal
+/// request with the given ID.
+async fn fetch_withdrawal_report(
+    db: &impl DbRead,
+    btc_ctx: &BitcoinTxContext,
+    stacks_chain_tip: &StacksBlockHash,
+    qualified_id: &QualifiedRequestId,
+) -> Result<(WithdrawalRequestReport, SignerVotes), Error> {
+    let report = db.get_withdrawal_request_report(
+        &btc_ctx.chain_tip,
+        stacks_chain_tip,
+        qualified_id,
+        &btc_ctx.signer_public_key,
+    );
+    let Some(mut report) = report.await? else {
+        return Err(WithdrawalValidationResult::Unknown.into_error(btc_ctx));
+    };
+
+    let target = OverrideTarget::Withdrawal {
+        request_id: qualified_id.request_id,
+    };
+    if let Some(request_override) = db.get_active_request_override(&target).await? {
+        report.is_accepted = Some(request_override.forces_accept());
+    }
+
+    let votes = db
+        .get_withdrawal_request_signer_votes(qualified_id, &btc_ctx.aggregate_key)
+        .await?;
 
-        deposits && withdrawal_requests
-    })
+    Ok((report, votes))
 }
 
 impl BitcoinPreSignRequest {
@@ -135,24 +195,9 @@ impl BitcoinPreSignRequest {
     // NonEmptySet<Either<OutPoint, QualifiedRequestId>> with the
     // `request_package` field being a NonEmptySlice<TxRequestIds>.
     fn pre_validation(&self) -> Result<(), Error> {
-        let no_requests = self
-            .request_package
-            .iter()
-            .any(|x| x.deposits.is_empty() && x.withdrawals.is_empty());
-
-        if no_requests || self.request_package.is_empty() {
-            return Err(Error::PreSignContainsNoRequests);
-        }
-
-        if !is_unique(&self.request_package) {
-            return Err(Error::DuplicateRequests);
-        }
-
-        if !BITCOIN_FEE_RATE_RANGE.contains(&self.fee_rate) {
-            return Err(Error::PreSignInvalidFeeRate(self.fee_rate));
-        }
-
-        Ok(())
+        package_rules::check_not_empty(&self.request_package)?;
+        package_rules::check_unique(&self.request_package)?;
+        package_rules::check_fee_rate(self.fee_rate)
     }
 
     async fn fetch_all_reports<C>(
@@ -164,59 +209,50 @@ impl BitcoinPreSignRequest {
         C: Context + Send + Sync,
     {
         let db = ctx.get_storage();
-        let mut cache = ValidationCache::default();
-        let bitcoin_chain_tip = &btc_ctx.chain_tip;
 
         let maybe_stacks_chain_tip = ctx.state().stacks_chain_tip();
         let Some(stacks_chain_tip) = maybe_stacks_chain_tip.map(|b| b.block_hash) else {
             return Err(Error::NoStacksChainTip);
         };
 
-        for requests in &self.request_package {
-            // Fetch all deposit reports and votes
-            for outpoint in &requests.deposits {
-                let txid = outpoint.txid.into();
-                let output_index = outpoint.vout;
-
-                let report_future = db.get_deposit_request_report(
-                    bitcoin_chain_tip,
-                    &txid,
-                    output_index,
-                    &btc_ctx.signer_public_key,
-                );
-                let Some(report) = report_future.await? else {
-                    return Err(InputValidationResult::Unknown.into_error(btc_ctx));
-                };
-
-                let votes = db
-                    .get_deposit_request_signer_votes(&txid, output_index, &btc_ctx.aggregate_key)
-                    .await?;
-
-                cache.deposit_reports.insert(outpoint, (report, votes));
-            }
-
-            // Fetch all withdrawal reports and votes
-            for qualified_id in &requests.withdrawals {
-                let report = db.get_withdrawal_request_report(
-                    bitcoin_chain_tip,
-                    &stacks_chain_tip,
-                    qualified_id,
-                    &btc_ctx.signer_public_key,
-                );
-                let Some(report) = report.await? else {
-                    return Err(WithdrawalValidationResult::Unknown.into_error(btc_ctx));
-                };
+        // The reports are independent of one another, so we fetch up to
+        // `signer.presign_validation_concurrency` deposit reports, and as
+        // many withdrawal reports, at the same time. We use `buffered`
+        // rather than `buffer_unordered` so that each stream fails with
+        // the error of its first failing request in the package.
+        let concurrency = ctx.config().signer.presign_validation_concurrency.get() as usize;
+        let outpoints = self.request_package.iter().flat_map(|reqs| &reqs.deposits);
+        let qualified_ids = self
+            .request_package
+            .iter()
+            .flat_map(|reqs| &reqs.withdrawals);
+        let db = &db;
+        let stacks_chain_tip = &stacks_chain_tip;
+
+        let deposit_reports = futures::stream::iter(outpoints)
+            .map(|outpoint| async move {
+                let report = fetch_deposit_report(db, btc_ctx, outpoint).await?;
+                Ok::<_, Error>((outpoint, report))
+            })
+            .buffered(concurrency)
+            .try_collect::<HashMap<_, _>>();
+
+        let withdrawal_reports = futures::stream::iter(qualified_ids)
+            .map(|qualified_id| async move {
+                let report =
+                    fetch_withdrawal_report(db, btc_ctx, stacks_chain_tip, qualified_id).await?;
+                Ok::<_, Error>((qualified_id, report))
+            })
+            .buffered(concurrency)
+            .try_collect::<HashMap<_, _>>();
 
-                let votes = db
-                    .get_withdrawal_request_signer_votes(qualified_id, &btc_ctx.aggregate_key)
-                    .await?;
+        let (deposit_reports, withdrawal_reports) =
+            futures::try_join!(deposit_reports, withdrawal_reports)?;
 
-                cache
-                    .withdrawal_reports
-                    .insert(qualified_id, (report, votes));
-            }
-        }
-        Ok(cache)
+        Ok(ValidationCache {
+            deposit_reports,
+            withdrawal_reports,
+        })
     }
 
     fn assert_request_amount_limits(
@@ -280,9 +316,45 @@ impl BitcoinPreSignRequest {
     {
         // Let's do basic validation of the request object itself.
         self.pre_validation()?;
+        let signer_config = &ctx.config().signer;
+        let height = btc_ctx.chain_tip_height;
+        let signer_set = ctx.state().current_signer_set().get_signers();
+        let signers_upgraded = ctx.state().signers_support_protocol_version(
+            signer_set.iter().map(|signer| signer.public_key()),
+            &btc_ctx.signer_public_key,
+            crate::NETWORK_MAGIC_BYTES_PROTOCOL_VERSION,
+        );
+        if !signer_config.accepts_magic_bytes(self.magic_bytes, height, signers_upgraded) {
+            return Err(Error::UnexpectedMagicBytes(self.magic_bytes, height));
+        }
+        package_rules::check_package_limits(
+            &self.request_package,
+            signer_config.max_withdrawals_per_bitcoin_tx(),
+        )?;
         let db = ctx.get_storage();
         let cache = self.fetch_all_reports(ctx, btc_ctx).await?;
 
+        // A rogue coordinator could ask us to service more withdrawals in
+        // this bitcoin block than we are willing to. We may have already
+        // agreed to sign other packages for this chain tip, and their
+        // withdrawals count towards the cap too, but only once each.
+        let mut withdrawal_amounts: HashMap<QualifiedRequestId, u64> = db
+            .get_presigned_withdrawals(&btc_ctx.chain_tip)
+            .await?
+            .into_iter()
+            .map(|req| (req.qualified_id(), req.amount))
+            .collect();
+        withdrawal_amounts.extend(
+            cache
+                .withdrawal_reports
+                .iter()
+                .map(|(id, (report, _))| ((*id).clone(), report.amount)),
+        );
+        package_rules::check_withdrawal_value(
+            withdrawal_amounts.into_values(),
+            signer_config.max_withdrawal_value_per_bitcoin_block(),
+        )?;
+
         // We now check that the withdrawal amounts adhere to the rolling
         // limits. We check the individual withdrawal caps later.
         let limits = ctx.state().get_current_limits();
@@ -297,38 +369,74 @@ impl BitcoinPreSignRequest {
         let last_fees =
             assess_mempool_sweep_transaction_fees(&bitcoin_client, &signer_utxo).await?;
 
+        // Parallel sweeps split the signers' change and spend the
+        // signers' spare UTXOs, but they must leave alone the requests
+        // serviced by the transactions in the mempool that spend those
+        // UTXOs, or they would be serviced twice.
+        let parallel = if self.parallel_sweeps {
+            let signer_utxos = db.get_signer_utxos(&btc_ctx.chain_tip).await?;
+            let parallel =
+                ParallelSweepUtxos::assess(&bitcoin_client, &signer_utxo, &signer_utxos).await?;
+            if parallel.in_flight.intersects(&self.request_package) {
+                return Err(Error::SweepRequestsInFlight);
+            }
+            parallel
+        } else {
+            ParallelSweepUtxos {
+                change_outputs: 1,
+                ..Default::default()
+            }
+        };
+
+        let signer_outpoint = signer_utxo.outpoint;
         let mut signer_state = SignerBtcState {
             fee_rate: self.fee_rate,
             utxo: signer_utxo,
             public_key: bitcoin::XOnlyPublicKey::from(btc_ctx.aggregate_key),
             last_fees,
-            magic_bytes: [b'T', b'3'], //TODO(#472): Use the correct magic bytes.
+            magic_bytes: self.magic_bytes,
+            change_outputs: parallel.change_outputs,
         };
+        let mut spare_utxos = parallel.spare_utxos.iter().copied();
         let mut outputs = Vec::new();
 
         for requests in self.request_package.iter() {
+            let spare = spare_utxos.next();
             let (output, new_signer_state) = self
-                .construct_tx_sighashes(ctx, btc_ctx, requests, signer_state, &cache)
+                .construct_tx_sighashes(ctx, btc_ctx, requests, signer_state, spare, &cache)
                 .await?;
             signer_state = new_signer_state;
             outputs.push(output);
         }
 
+        let spare_outpoints: Vec<OutPoint> = parallel
+            .spare_utxos
+            .iter()
+            .map(|spare| spare.utxo.outpoint)
+            .collect();
+        invariants::enforce(invariants::check_utxo_chain(
+            signer_outpoint,
+            &spare_outpoints,
+            outputs.iter().map(|output| &output.tx),
+        ))?;
+
         Ok(outputs)
     }
 
     /// Construct the validation for each request that this transaction
     /// will service.
     ///
-    /// This function returns the new signer bitcoin state if we were to
-    /// sign and confirmed the bitcoin transaction created using the given
-    /// inputs and outputs.
+    /// This function returns the signer bitcoin state for the next
+    /// transaction in the package, which spends the given spare signers'
+    /// UTXO if there is one, or the signers' UTXO created by the bitcoin
+    /// transaction that we construct here.
     async fn construct_tx_sighashes<'a, C>(
         &self,
         ctx: &C,
         btc_ctx: &BitcoinTxContext,
         requests: &'a TxRequestIds,
         signer_state: SignerBtcState,
+        spare: Option<SpareSignerUtxo>,
         cache: &ValidationCache<'a>,
     ) -> Result<(BitcoinTxValidationData, SignerBtcState), Error>
     where
@@ -362,18 +470,12 @@ impl BitcoinPreSignRequest {
             withdrawals,
             signer_state,
         };
-        let mut signer_state = signer_state;
         let tx = reports.create_transaction()?;
+        invariants::enforce(invariants::check_fee_ceiling(&tx))?;
         let sighashes = tx.construct_digests()?;
 
-        signer_state.utxo = tx.new_signer_utxo();
-        // The first transaction is the only one whose input UTXOs that
-        // have all been confirmed. Moreover, the fees that it sets aside
-        // are enough to make up for the remaining transactions in the
-        // transaction package. With that in mind, we do not need to bump
-        // their fees anymore in order for them to be accepted by the
-        // network.
-        signer_state.last_fees = None;
+        let signer_state =
+            package_rules::chain_signer_state(signer_state, tx.new_signer_utxo(), spare);
         let out = BitcoinTxValidationData {
             signer_sighash: sighashes.signer_sighash(),
             deposit_sighashes: sighashes.deposit_sighashes(),
@@ -435,11 +537,11 @@ impl BitcoinTxValidationData {
         let is_valid_tx = self.is_valid_tx();
 
         let validation_results = self.reports.deposits.iter().map(|(_, report)| {
-            report.validate(
-                self.chain_tip_height,
-                &self.tx,
-                self.tx_fee,
-                &self.sbtc_limits,
+            let height = self.chain_tip_height;
+            let limits = &self.sbtc_limits;
+            (
+                report.validate(height, &self.tx, self.tx_fee, limits),
+                report.validation_checks(height, &self.tx, self.tx_fee, limits),
             )
         });
 
@@ -457,21 +559,25 @@ impl BitcoinTxValidationData {
         // each of the signer's inputs were created as part of a
         // transaction chain, so each one is unspent and locked by the
         // signers' "aggregate" private key.
-        [(self.signer_sighash, InputValidationResult::Ok)]
+        let signer_validation = (InputValidationResult::Ok, ValidationChecks::default());
+        [(self.signer_sighash, signer_validation)]
             .into_iter()
             .chain(deposit_sighashes)
-            .map(|(sighash, validation_result)| BitcoinTxSigHash {
-                txid: sighash.txid.into(),
-                sighash: sighash.sighash.into(),
-                chain_tip: self.chain_tip,
-                aggregate_key: sighash.aggregate_key.into(),
-                prevout_txid: sighash.outpoint.txid.into(),
-                prevout_output_index: sighash.outpoint.vout,
-                prevout_type: sighash.prevout_type,
-                validation_result,
-                is_valid_tx,
-                will_sign: is_valid_tx && validation_result == InputValidationResult::Ok,
-            })
+            .map(
+                |(sighash, (validation_result, validation_checks))| BitcoinTxSigHash {
+                    txid: sighash.txid.into(),
+                    sighash: sighash.sighash.into(),
+                    chain_tip: self.chain_tip,
+                    aggregate_key: sighash.aggregate_key.into(),
+                    prevout_txid: sighash.outpoint.txid.into(),
+                    prevout_output_index: sighash.outpoint.vout,
+                    prevout_type: sighash.prevout_type,
+                    validation_result,
+                    validation_checks,
+                    is_valid_tx,
+                    will_sign: is_valid_tx && validation_result == InputValidationResult::Ok,
+                },
+            )
             .collect()
     }
 
@@ -503,6 +609,13 @@ impl BitcoinTxValidationData {
                     self.tx_fee,
                     &self.sbtc_limits,
                 ),
+                validation_checks: report.validation_checks(
+                    self.chain_tip_height,
+                    output_index + 2,
+                    &self.tx,
+                    self.tx_fee,
+                    &self.sbtc_limits,
+                ),
                 is_valid_tx,
             })
             .collect()
@@ -636,10 +749,6 @@ pub enum InputValidationResult {
     /// The signer does not have a record of the deposit request in their
     /// database.
     Unknown,
-    /// The locktime in the reclaim script is in time units and that is not
-    /// supported. This shouldn't happen, since we will not put it in our
-    /// database is this is the case.
-    UnsupportedLockTime,
 }
 
 impl InputValidationResult {
@@ -649,6 +758,30 @@ impl InputValidationResult {
             context: ctx.clone(),
         }))
     }
+
+    /// Whether the result is a pass, or a failure that may or may not
+    /// resolve itself if the deposit request is validated again later.
+    pub fn outcome(&self) -> CheckOutcome {
+        match self {
+            Self::Ok => CheckOutcome::Pass,
+            // A reorg may confirm the deposit again, fee rates may come
+            // down, and we may yet vote on the request or verify our DKG
+            // shares.
+            Self::TxNotOnBestChain
+            | Self::FeeTooHigh
+            | Self::MintAmountBelowDustLimit
+            | Self::NoVote
+            | Self::DkgSharesUnverified
+            | Self::Unknown => CheckOutcome::TransientFail,
+            Self::AmountTooLow
+            | Self::AmountTooHigh
+            | Self::CannotSignUtxo
+            | Self::DepositUtxoSpent
+            | Self::DkgSharesVerifyFailed
+            | Self::LockTimeExpiry
+            | Self::RejectedRequest => CheckOutcome::PermanentFail,
+        }
+    }
 }
 
 /// The responses for validation of the outputs of a sweep transaction on
@@ -719,6 +852,27 @@ impl WithdrawalValidationResult {
             context: ctx.clone(),
         }))
     }
+
+    /// Whether the result is a pass, or a failure that may or may not
+    /// resolve itself if the withdrawal request is validated again later.
+    pub fn outcome(&self) -> CheckOutcome {
+        match self {
+            Self::Ok => CheckOutcome::Pass,
+            // More bitcoin blocks may be observed, a reorg may confirm
+            // the request again, fee rates may come down, and we may yet
+            // vote on the request.
+            Self::RequestNotFinal
+            | Self::TxNotOnBestChain
+            | Self::FeeTooHigh
+            | Self::NoVote
+            | Self::Unknown => CheckOutcome::TransientFail,
+            Self::AmountTooHigh
+            | Self::AmountIsDust
+            | Self::RequestExpired
+            | Self::RequestFulfilled
+            | Self::RequestRejected => CheckOutcome::PermanentFail,
+        }
+    }
 }
 
 /// The responses for validation of a sweep transaction on bitcoin.
@@ -757,6 +911,134 @@ impl std::error::Error for BitcoinValidationError {
     }
 }
 
+/// The individual checks done when validating deposit and withdrawal
+/// requests.
+#[derive(
+    Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, strum::Display, strum::EnumString,
+)]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "testing", derive(fake::Dummy))]
+pub enum ValidationCheck {
+    /// Whether the transaction creating the request is confirmed on the
+    /// canonical blockchain, and the request has not already been
+    /// fulfilled.
+    Confirmation,
+    /// Whether the request amount is within the sBTC limits.
+    Amount,
+    /// Whether the amount, less any fees, is above the dust limit.
+    DustLimit,
+    /// Whether the depositor could reclaim the deposit too soon for the
+    /// signers to safely sweep it.
+    LockTime,
+    /// Whether enough, but not too many, bitcoin blocks have been
+    /// observed since the withdrawal request was created.
+    Finality,
+    /// Whether the assessed fee is within the max fee of the request.
+    Fee,
+    /// Whether this signer has voted to accept the request.
+    Vote,
+    /// Whether this signer is part of the signing set locking the
+    /// deposited funds.
+    SigningSet,
+    /// Whether the DKG shares locking the deposited funds have been
+    /// verified.
+    DkgShares,
+}
+
+/// The outcome of a single validation check.
+#[derive(
+    Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, strum::Display, strum::EnumString,
+)]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "testing", derive(fake::Dummy))]
+pub enum CheckOutcome {
+    /// The check passed.
+    Pass,
+    /// The check failed, but it may pass later without the request
+    /// changing, for example once more blocks have been observed, once
+    /// this signer has voted on the request, or once fee rates come down.
+    /// Callers may retry the request.
+    TransientFail,
+    /// The check failed and will keep failing for this request, so
+    /// callers should reject it.
+    PermanentFail,
+}
+
+/// The outcome of each check done when validating a request. Checks that
+/// depend on an earlier check passing are omitted when it fails.
+///
+/// These are stored in the database as a comma separated list of
+/// `check=outcome` pairs, such as `confirmation=pass,fee=transient_fail`.
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "testing", derive(fake::Dummy))]
+pub struct ValidationChecks(BTreeMap<ValidationCheck, CheckOutcome>);
+
+impl ValidationChecks {
+    /// Return the outcome of the given check, if it was done.
+    pub fn get(&self, check: ValidationCheck) -> Option<CheckOutcome> {
+        self.0.get(&check).copied()
+    }
+
+    /// Return each of the checks that were done along with their
+    /// outcomes.
+    pub fn iter(&self) -> impl Iterator<Item = (ValidationCheck, CheckOutcome)> + '_ {
+        self.0.iter().map(|(check, outcome)| (*check, *outcome))
+    }
+
+    /// Return the overall outcome of the checks. This is the worst
+    /// outcome of any of the checks, so the request passes only if every
+    /// check passes, and it fails permanently if any check does.
+    pub fn outcome(&self) -> CheckOutcome {
+        self.0.values().copied().max().unwrap_or(CheckOutcome::Pass)
+    }
+}
+
+impl FromIterator<(ValidationCheck, CheckOutcome)> for ValidationChecks {
+    fn from_iter<I: IntoIterator<Item = (ValidationCheck, CheckOutcome)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl std::fmt::Display for ValidationChecks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pairs: Vec<String> = self
+            .iter()
+            .map(|(check, outcome)| format!("{check}={outcome}"))
+            .collect();
+        f.write_str(&pairs.join(","))
+    }
+}
+
+impl FromStr for ValidationChecks {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (check, outcome) = pair
+                    .split_once('=')
+                    .ok_or_else(|| Error::InvalidValidationChecks(s.to_string()))?;
+                let check: ValidationCheck = check
+                    .parse()
+                    .map_err(|_| Error::InvalidValidationChecks(s.to_string()))?;
+                let outcome: CheckOutcome = outcome
+                    .parse()
+                    .map_err(|_| Error::InvalidValidationChecks(s.to_string()))?;
+                Ok((check, outcome))
+            })
+            .collect()
+    }
+}
+
+impl TryFrom<String> for ValidationChecks {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
 /// An enum for the confirmation status of a deposit request.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DepositConfirmationStatus {
@@ -819,6 +1101,8 @@ pub struct DepositRequestReport {
 
 impl DepositRequestReport {
     /// Validate that the deposit request is okay given the report.
+    ///
+    /// This returns the result of the first check that fails.
     fn validate<F>(
         &self,
         chain_tip_height: BitcoinBlockHeight,
@@ -829,97 +1113,158 @@ impl DepositRequestReport {
     where
         F: FeeAssessment,
     {
+        self.run_checks(chain_tip_height, tx, tx_fee, sbtc_limits)
+            .into_iter()
+            .map(|(_, result)| result)
+            .find(|result| *result != InputValidationResult::Ok)
+            .unwrap_or(InputValidationResult::Ok)
+    }
+
+    /// Return the outcome of each of the checks done when validating the
+    /// deposit request, so that callers can tell whether a failed
+    /// request is worth retrying.
+    pub fn validation_checks<F>(
+        &self,
+        chain_tip_height: BitcoinBlockHeight,
+        tx: &F,
+        tx_fee: Amount,
+        sbtc_limits: &SbtcLimits,
+    ) -> ValidationChecks
+    where
+        F: FeeAssessment,
+    {
+        self.run_checks(chain_tip_height, tx, tx_fee, sbtc_limits)
+            .into_iter()
+            .map(|(check, result)| (check, result.outcome()))
+            .collect()
+    }
+
+    /// Run each of the validation checks, in order, returning the result
+    /// of each one. Checks that depend on an earlier check passing are
+    /// skipped when it fails.
+    fn run_checks<F>(
+        &self,
+        chain_tip_height: BitcoinBlockHeight,
+        tx: &F,
+        tx_fee: Amount,
+        sbtc_limits: &SbtcLimits,
+    ) -> Vec<(ValidationCheck, InputValidationResult)>
+    where
+        F: FeeAssessment,
+    {
+        let mut results = Vec::new();
+
         let confirmed_block_height = match self.status {
             // Deposit requests are only written to the database after they
             // have been confirmed, so this means that we have a record of
             // the request, but it has not been confirmed on the canonical
             // bitcoin blockchain.
             DepositConfirmationStatus::Unconfirmed => {
-                return InputValidationResult::TxNotOnBestChain;
+                results.push((
+                    ValidationCheck::Confirmation,
+                    InputValidationResult::TxNotOnBestChain,
+                ));
+                None
             }
             // This means that we have a record of the deposit UTXO being
             // spent in a sweep transaction that has been confirmed on the
             // canonical bitcoin blockchain.
             DepositConfirmationStatus::Spent(_) => {
-                return InputValidationResult::DepositUtxoSpent;
+                results.push((
+                    ValidationCheck::Confirmation,
+                    InputValidationResult::DepositUtxoSpent,
+                ));
+                None
             }
             // The deposit has been confirmed on the canonical bitcoin
             // blockchain and remains unspent by us.
-            DepositConfirmationStatus::Confirmed(block_height, _) => block_height,
+            DepositConfirmationStatus::Confirmed(block_height, _) => {
+                results.push((ValidationCheck::Confirmation, InputValidationResult::Ok));
+                Some(block_height)
+            }
         };
 
-        if self.amount < sbtc_limits.per_deposit_minimum().to_sat() {
-            return InputValidationResult::AmountTooLow;
-        }
-
-        if self.amount > sbtc_limits.per_deposit_cap().to_sat() {
-            return InputValidationResult::AmountTooHigh;
-        }
+        let amount = match deposit_precheck::check_deposit_amount(self.amount, sbtc_limits) {
+            DepositVerdict::AmountTooLow => InputValidationResult::AmountTooLow,
+            DepositVerdict::AmountTooHigh => InputValidationResult::AmountTooHigh,
+            _ => InputValidationResult::Ok,
+        };
+        results.push((ValidationCheck::Amount, amount));
 
         // We only sweep a deposit if the depositor cannot reclaim the
         // deposit within the next DEPOSIT_LOCKTIME_BLOCK_BUFFER blocks.
-        let deposit_age = chain_tip_height.saturating_sub(confirmed_block_height);
-
-        match self.lock_time {
-            LockTime::Blocks(height) => {
-                let max_age = height
-                    .value()
-                    .saturating_sub(DEPOSIT_LOCKTIME_BLOCK_BUFFER)
-                    .into();
-                if deposit_age >= max_age {
-                    return InputValidationResult::LockTimeExpiry;
-                }
-            }
-            LockTime::Time(_) => {
-                return InputValidationResult::UnsupportedLockTime;
-            }
-        }
-
-        let Some(assessed_fee) = tx.assess_input_fee(&self.outpoint, tx_fee) else {
-            return InputValidationResult::Unknown;
-        };
-
-        if assessed_fee.to_sat() > self.max_fee.min(self.amount) {
-            return InputValidationResult::FeeTooHigh;
+        if let Some(confirmed_block_height) = confirmed_block_height {
+            let deposit_age = chain_tip_height.saturating_sub(confirmed_block_height);
+
+            // Time-based lock times are converted into a conservative
+            // number of blocks, see `deposit_precheck::lock_time_in_blocks`.
+            let max_age = deposit_precheck::lock_time_in_blocks(self.lock_time)
+                .saturating_sub(DEPOSIT_LOCKTIME_BLOCK_BUFFER)
+                .into();
+            let lock_time = if deposit_age >= max_age {
+                InputValidationResult::LockTimeExpiry
+            } else {
+                InputValidationResult::Ok
+            };
+            results.push((ValidationCheck::LockTime, lock_time));
         }
 
-        if self.amount.saturating_sub(assessed_fee.to_sat()) < DEPOSIT_DUST_LIMIT {
-            return InputValidationResult::MintAmountBelowDustLimit;
+        match tx.assess_input_fee(&self.outpoint, tx_fee) {
+            None => results.push((ValidationCheck::Fee, InputValidationResult::Unknown)),
+            Some(assessed_fee) => {
+                let fee = if assessed_fee.to_sat() > self.max_fee.min(self.amount) {
+                    InputValidationResult::FeeTooHigh
+                } else {
+                    InputValidationResult::Ok
+                };
+                results.push((ValidationCheck::Fee, fee));
+
+                let dust_limit =
+                    if self.amount.saturating_sub(assessed_fee.to_sat()) < DEPOSIT_DUST_LIMIT {
+                        InputValidationResult::MintAmountBelowDustLimit
+                    } else {
+                        InputValidationResult::Ok
+                    };
+                results.push((ValidationCheck::DustLimit, dust_limit));
+            }
         }
 
         // Let's check whether we rejected this deposit.
-        match self.can_accept {
-            Some(true) => (),
+        let vote = match self.can_accept {
+            Some(true) => InputValidationResult::Ok,
             // If we are here, we know that we have a record for the
             // deposit request, but we have not voted on it yet, so we do
             // not know if we can sign for it.
-            None => return InputValidationResult::NoVote,
-            Some(false) => return InputValidationResult::RejectedRequest,
-        }
+            None => InputValidationResult::NoVote,
+            Some(false) => InputValidationResult::RejectedRequest,
+        };
+        results.push((ValidationCheck::Vote, vote));
 
-        match self.can_sign {
-            Some(true) => (),
+        let signing_set = match self.can_sign {
+            Some(true) => InputValidationResult::Ok,
             // In this case we know that we cannot sign for the deposit
             // because it is locked with a public key where the current
             // signer is not part of the signing set.
-            Some(false) => return InputValidationResult::CannotSignUtxo,
-            // We shouldn't ever get None here, since we know that we can
-            // accept the request. We do the check for whether we can sign
-            // the request at that the same time as the can_accept check.
-            None => return InputValidationResult::NoVote,
-        }
+            Some(false) => InputValidationResult::CannotSignUtxo,
+            // We shouldn't ever get None here if we can accept the
+            // request. We do the check for whether we can sign the
+            // request at that the same time as the can_accept check.
+            None => InputValidationResult::NoVote,
+        };
+        results.push((ValidationCheck::SigningSet, signing_set));
 
         // We do not sign for inputs where we have not verified the
         // aggregate key locking the UTXO. If our shares have not been
         // verified then sending signature shares could be harmful overall.
-        match self.dkg_shares_status {
-            Some(DkgSharesStatus::Verified) => {}
-            Some(DkgSharesStatus::Unverified) => return InputValidationResult::DkgSharesUnverified,
-            Some(DkgSharesStatus::Failed) => return InputValidationResult::DkgSharesVerifyFailed,
-            None => return InputValidationResult::CannotSignUtxo,
-        }
+        let dkg_shares = match self.dkg_shares_status {
+            Some(DkgSharesStatus::Verified) => InputValidationResult::Ok,
+            Some(DkgSharesStatus::Unverified) => InputValidationResult::DkgSharesUnverified,
+            Some(DkgSharesStatus::Failed) => InputValidationResult::DkgSharesVerifyFailed,
+            None => InputValidationResult::CannotSignUtxo,
+        };
+        results.push((ValidationCheck::DkgShares, dkg_shares));
 
-        InputValidationResult::Ok
+        results
     }
 
     /// As deposit request.
@@ -983,7 +1328,8 @@ pub struct WithdrawalRequestReport {
 impl WithdrawalRequestReport {
     /// Validate that the withdrawal request is okay given the report.
     ///
-    /// See https://github.com/stacks-network/sbtc/issues/741 for the
+    /// This returns the result of the first check that fails. See
+    /// https://github.com/stacks-network/sbtc/issues/741 for the
     /// validation rules for withdrawal requests.
     pub fn validate<F>(
         &self,
@@ -996,49 +1342,108 @@ impl WithdrawalRequestReport {
     where
         F: FeeAssessment,
     {
-        match self.status {
-            WithdrawalRequestStatus::Confirmed => {}
-            WithdrawalRequestStatus::Unconfirmed => {
-                return WithdrawalValidationResult::TxNotOnBestChain;
-            }
-            WithdrawalRequestStatus::Fulfilled(_) => {
-                return WithdrawalValidationResult::RequestFulfilled;
-            }
-        }
+        self.run_checks(
+            bitcoin_chain_tip_height,
+            output_index,
+            tx,
+            tx_fee,
+            sbtc_limits,
+        )
+        .into_iter()
+        .map(|(_, result)| result)
+        .find(|result| *result != WithdrawalValidationResult::Ok)
+        .unwrap_or(WithdrawalValidationResult::Ok)
+    }
 
-        match self.is_accepted {
-            Some(true) => (),
-            None => return WithdrawalValidationResult::NoVote,
-            Some(false) => return WithdrawalValidationResult::RequestRejected,
-        }
+    /// Return the outcome of each of the checks done when validating the
+    /// withdrawal request, so that callers can tell whether a failed
+    /// request is worth retrying.
+    pub fn validation_checks<F>(
+        &self,
+        bitcoin_chain_tip_height: BitcoinBlockHeight,
+        output_index: usize,
+        tx: &F,
+        tx_fee: Amount,
+        sbtc_limits: &SbtcLimits,
+    ) -> ValidationChecks
+    where
+        F: FeeAssessment,
+    {
+        self.run_checks(
+            bitcoin_chain_tip_height,
+            output_index,
+            tx,
+            tx_fee,
+            sbtc_limits,
+        )
+        .into_iter()
+        .map(|(check, result)| (check, result.outcome()))
+        .collect()
+    }
 
-        if self.amount > sbtc_limits.per_withdrawal_cap().to_sat() {
-            return WithdrawalValidationResult::AmountTooHigh;
-        }
+    /// Run each of the validation checks, in order, returning the result
+    /// of each one.
+    fn run_checks<F>(
+        &self,
+        bitcoin_chain_tip_height: BitcoinBlockHeight,
+        output_index: usize,
+        tx: &F,
+        tx_fee: Amount,
+        sbtc_limits: &SbtcLimits,
+    ) -> Vec<(ValidationCheck, WithdrawalValidationResult)>
+    where
+        F: FeeAssessment,
+    {
+        let confirmation = match self.status {
+            WithdrawalRequestStatus::Confirmed => WithdrawalValidationResult::Ok,
+            WithdrawalRequestStatus::Unconfirmed => WithdrawalValidationResult::TxNotOnBestChain,
+            WithdrawalRequestStatus::Fulfilled(_) => WithdrawalValidationResult::RequestFulfilled,
+        };
 
-        if self.amount < self.recipient.minimal_non_dust().to_sat() {
-            return WithdrawalValidationResult::AmountIsDust;
-        }
+        let vote = match self.is_accepted {
+            Some(true) => WithdrawalValidationResult::Ok,
+            None => WithdrawalValidationResult::NoVote,
+            Some(false) => WithdrawalValidationResult::RequestRejected,
+        };
 
-        let block_wait = *bitcoin_chain_tip_height.saturating_sub(self.bitcoin_block_height);
-        if block_wait < WITHDRAWAL_MIN_CONFIRMATIONS {
-            return WithdrawalValidationResult::RequestNotFinal;
-        }
+        let amount = if self.amount > sbtc_limits.per_withdrawal_cap().to_sat() {
+            WithdrawalValidationResult::AmountTooHigh
+        } else {
+            WithdrawalValidationResult::Ok
+        };
 
-        if block_wait > WITHDRAWAL_BLOCKS_EXPIRY {
-            return WithdrawalValidationResult::RequestExpired;
-        }
+        let dust_limit = if self.amount < self.recipient.minimal_non_dust().to_sat() {
+            WithdrawalValidationResult::AmountIsDust
+        } else {
+            WithdrawalValidationResult::Ok
+        };
 
-        let Some(assessed_fee) = tx.assess_output_fee(output_index, tx_fee) else {
-            // If we hit this, then there is a programming error somewhere
-            return WithdrawalValidationResult::Unknown;
+        let block_wait = *bitcoin_chain_tip_height.saturating_sub(self.bitcoin_block_height);
+        let finality = if block_wait < WITHDRAWAL_MIN_CONFIRMATIONS {
+            WithdrawalValidationResult::RequestNotFinal
+        } else if block_wait > WITHDRAWAL_BLOCKS_EXPIRY {
+            WithdrawalValidationResult::RequestExpired
+        } else {
+            WithdrawalValidationResult::Ok
         };
 
-        if assessed_fee.to_sat() > self.max_fee {
-            return WithdrawalValidationResult::FeeTooHigh;
-        }
+        let fee = match tx.assess_output_fee(output_index, tx_fee) {
+            // If we hit this, then there is a programming error somewhere
+            None => WithdrawalValidationResult::Unknown,
+            Some(assessed_fee) if assessed_fee.to_sat() > self.max_fee => {
+                WithdrawalValidationResult::FeeTooHigh
+            }
+            Some(_) => WithdrawalValidationResult::Ok,
+        };
 
-        WithdrawalValidationResult::Ok
+        vec![
+            (ValidationCheck::Confirmation, confirmation),
+            (ValidationCheck::Vote, vote),
+            (ValidationCheck::Amount, amount),
+            (ValidationCheck::DustLimit, dust_limit),
+            (ValidationCheck::Finality, finality),
+            (ValidationCheck::Fee, fee),
+        ]
     }
 
     fn to_withdrawal_request(&self, votes: &SignerVotes) -> WithdrawalRequest {
@@ -1222,14 +1627,15 @@ mod tests {
             can_accept: Some(true),
             amount: 100_000_000,
             max_fee: u64::MAX,
-            lock_time: LockTime::from_512_second_intervals(u16::MAX),
+            // 10 * 512 seconds is treated as 4 blocks.
+            lock_time: LockTime::from_512_second_intervals(10),
             outpoint: OutPoint::null(),
             deposit_script: ScriptBuf::new(),
             reclaim_script_hash: TaprootScriptHash::zeros(),
             signers_public_key: *sbtc::UNSPENDABLE_TAPROOT_KEY,
             dkg_shares_status: Some(DkgSharesStatus::Verified),
         },
-        status: InputValidationResult::UnsupportedLockTime,
+        status: InputValidationResult::LockTimeExpiry,
         chain_tip_height: 2u64.into(),
         limits: SbtcLimits::new_per_deposit(0, u64::MAX),
     } ; "lock-time-in-time-units-2")]
@@ -1743,6 +2149,146 @@ mod tests {
         assert_eq!(status, WithdrawalValidationResult::Unknown);
     }
 
+    #[test]
+    fn deposit_validation_checks_tell_transient_from_permanent_failures() {
+        let mut tx = crate::testing::btc::base_signer_transaction();
+        tx.input.push(TxIn {
+            previous_output: OutPoint::null(),
+            script_sig: ScriptBuf::new(),
+            sequence: Sequence::ZERO,
+            witness: Witness::new(),
+        });
+
+        let mut report = DepositRequestReport {
+            status: DepositConfirmationStatus::Confirmed(
+                0u64.into(),
+                BitcoinBlockHash::from([0; 32]),
+            ),
+            can_sign: Some(true),
+            can_accept: None,
+            amount: 100_000_000,
+            max_fee: u64::MAX,
+            lock_time: LockTime::from_height(DEPOSIT_LOCKTIME_BLOCK_BUFFER + 3),
+            outpoint: OutPoint::null(),
+            deposit_script: ScriptBuf::new(),
+            reclaim_script_hash: TaprootScriptHash::zeros(),
+            signers_public_key: *sbtc::UNSPENDABLE_TAPROOT_KEY,
+            dkg_shares_status: Some(DkgSharesStatus::Unverified),
+        };
+        let chain_tip_height = 2u64.into();
+        let limits = SbtcLimits::new_per_deposit(0, u64::MAX);
+
+        // We have not voted on the deposit yet and our DKG shares are
+        // unverified, both of which may change.
+        let checks = report.validation_checks(chain_tip_height, &tx, TX_FEE, &limits);
+        assert_eq!(
+            checks.get(ValidationCheck::Confirmation),
+            Some(CheckOutcome::Pass)
+        );
+        assert_eq!(
+            checks.get(ValidationCheck::LockTime),
+            Some(CheckOutcome::Pass)
+        );
+        assert_eq!(checks.get(ValidationCheck::Fee), Some(CheckOutcome::Pass));
+        assert_eq!(
+            checks.get(ValidationCheck::Vote),
+            Some(CheckOutcome::TransientFail)
+        );
+        assert_eq!(
+            checks.get(ValidationCheck::DkgShares),
+            Some(CheckOutcome::TransientFail)
+        );
+        assert_eq!(checks.outcome(), CheckOutcome::TransientFail);
+
+        // Rejecting the deposit is final.
+        report.can_accept = Some(false);
+        let checks = report.validation_checks(chain_tip_height, &tx, TX_FEE, &limits);
+        assert_eq!(
+            checks.get(ValidationCheck::Vote),
+            Some(CheckOutcome::PermanentFail)
+        );
+        assert_eq!(checks.outcome(), CheckOutcome::PermanentFail);
+
+        let status = report.validate(chain_tip_height, &tx, TX_FEE, &limits);
+        assert_eq!(status, InputValidationResult::RejectedRequest);
+        assert_eq!(status.outcome(), CheckOutcome::PermanentFail);
+
+        // The lock time check needs the confirmation height, so it is
+        // skipped when the deposit is not confirmed.
+        report.status = DepositConfirmationStatus::Unconfirmed;
+        let checks = report.validation_checks(chain_tip_height, &tx, TX_FEE, &limits);
+        assert_eq!(
+            checks.get(ValidationCheck::Confirmation),
+            Some(CheckOutcome::TransientFail)
+        );
+        assert_eq!(checks.get(ValidationCheck::LockTime), None);
+    }
+
+    #[test_case(WITHDRAWAL_MIN_CONFIRMATIONS - 1, CheckOutcome::TransientFail; "not-final")]
+    #[test_case(WITHDRAWAL_MIN_CONFIRMATIONS, CheckOutcome::Pass; "final")]
+    #[test_case(WITHDRAWAL_BLOCKS_EXPIRY + 1, CheckOutcome::PermanentFail; "expired")]
+    fn withdrawal_validation_checks_tell_transient_from_permanent_failures(
+        chain_tip_height: u64,
+        outcome: CheckOutcome,
+    ) {
+        let report = WithdrawalRequestReport {
+            status: WithdrawalRequestStatus::Confirmed,
+            id: QualifiedRequestId {
+                request_id: 0,
+                txid: StacksTxId::from([0; 32]),
+                block_hash: StacksBlockHash::from([0; 32]),
+            },
+            is_accepted: Some(true),
+            amount: Amount::ONE_BTC.to_sat(),
+            max_fee: TX_FEE.to_sat(),
+            recipient: TEST_RECIPIENT.clone(),
+            bitcoin_block_height: 0u64.into(),
+        };
+        let mut tx = crate::testing::btc::base_signer_transaction();
+        tx.output.push(TxOut {
+            value: Amount::from_sat(report.amount),
+            script_pubkey: report.recipient.clone(),
+        });
+
+        let output_index = tx.output.len() - 1;
+        let chain_tip_height = chain_tip_height.into();
+        let limits = &SbtcLimits::new_per_withdrawal(Amount::ONE_BTC.to_sat());
+
+        let checks = report.validation_checks(chain_tip_height, output_index, &tx, TX_FEE, limits);
+        assert_eq!(checks.get(ValidationCheck::Finality), Some(outcome));
+        assert_eq!(checks.get(ValidationCheck::Vote), Some(CheckOutcome::Pass));
+        assert_eq!(checks.outcome(), outcome);
+
+        let status = report.validate(chain_tip_height, output_index, &tx, TX_FEE, limits);
+        assert_eq!(status.outcome(), outcome);
+    }
+
+    #[test]
+    fn validation_checks_round_trip_through_strings() {
+        let checks: ValidationChecks = [
+            (ValidationCheck::Confirmation, CheckOutcome::Pass),
+            (ValidationCheck::Fee, CheckOutcome::TransientFail),
+            (ValidationCheck::DkgShares, CheckOutcome::PermanentFail),
+        ]
+        .into_iter()
+        .collect();
+
+        let encoded = checks.to_string();
+        assert_eq!(
+            encoded,
+            "confirmation=pass,fee=transient_fail,dkg_shares=permanent_fail"
+        );
+        assert_eq!(encoded.parse::<ValidationChecks>().unwrap(), checks);
+
+        // Rows written before we recorded the checks have none.
+        let empty: ValidationChecks = "".parse().unwrap();
+        assert_eq!(empty, ValidationChecks::default());
+        assert_eq!(empty.outcome(), CheckOutcome::Pass);
+
+        assert!("fee".parse::<ValidationChecks>().is_err());
+        assert!("fee=maybe".parse::<ValidationChecks>().is_err());
+    }
+
     #[test_case(
         BitcoinPreSignRequest {
             request_package: vec![TxRequestIds {
@@ -1771,6 +2317,8 @@ mod tests {
             }],
             fee_rate: 1.0,
             last_fees: None,
+            parallel_sweeps: false,
+            magic_bytes: crate::LEGACY_MAGIC_BYTES,
         }, true; "unique-requests")]
     #[test_case(
         BitcoinPreSignRequest {
@@ -1800,6 +2348,8 @@ mod tests {
             }],
             fee_rate: 0.0,
             last_fees: None,
+            parallel_sweeps: false,
+            magic_bytes: crate::LEGACY_MAGIC_BYTES,
         }, false; "unique-requests-zero-fee-rate")]
     #[test_case(
         BitcoinPreSignRequest {
@@ -1829,6 +2379,8 @@ mod tests {
             }],
             fee_rate: -1.0,
             last_fees: None,
+            parallel_sweeps: false,
+            magic_bytes: crate::LEGACY_MAGIC_BYTES,
         }, false; "unique-requests-negative-fee-rate")]
     #[test_case(
         BitcoinPreSignRequest {
@@ -1858,6 +2410,8 @@ mod tests {
             }],
             fee_rate: 1.0,
             last_fees: None,
+            parallel_sweeps: false,
+            magic_bytes: crate::LEGACY_MAGIC_BYTES,
         }, false; "duplicate-deposits-in-same-tx")]
     #[test_case(
         BitcoinPreSignRequest {
@@ -1887,6 +2441,8 @@ mod tests {
             }],
             fee_rate: 1.0,
             last_fees: None,
+            parallel_sweeps: false,
+            magic_bytes: crate::LEGACY_MAGIC_BYTES,
         }, false; "duplicate-withdrawals-in-same-tx")]
     #[test_case(
         BitcoinPreSignRequest {
@@ -1918,6 +2474,8 @@ mod tests {
             ],
             fee_rate: 1.0,
             last_fees: None,
+            parallel_sweeps: false,
+            magic_bytes: crate::LEGACY_MAGIC_BYTES,
         }, false; "duplicate-withdrawal-request-ids-in-same-tx")]
     #[test_case(
         BitcoinPreSignRequest {
@@ -1956,12 +2514,16 @@ mod tests {
             ],
             fee_rate: 1.0,
             last_fees: None,
+            parallel_sweeps: false,
+            magic_bytes: crate::LEGACY_MAGIC_BYTES,
         }, false; "duplicate-requests-in-different-txs")]
     #[test_case(
         BitcoinPreSignRequest {
             request_package: Vec::new(),
             fee_rate: 1.0,
             last_fees: None,
+            parallel_sweeps: false,
+            magic_bytes: crate::LEGACY_MAGIC_BYTES,
         }, false; "empty-package_requests")]
     #[test_case(
         BitcoinPreSignRequest {
@@ -1977,6 +2539,8 @@ mod tests {
             ],
             fee_rate: 1.0,
             last_fees: None,
+            parallel_sweeps: false,
+            magic_bytes: crate::LEGACY_MAGIC_BYTES,
         }, false; "basically-empty-package_requests")]
     #[test_case(
         BitcoinPreSignRequest {
@@ -2012,6 +2576,8 @@ mod tests {
             ],
             fee_rate: 1.0,
             last_fees: None,
+            parallel_sweeps: false,
+            magic_bytes: crate::LEGACY_MAGIC_BYTES,
         }, false; "contains-empty-tx-requests")]
     #[test_case(
         BitcoinPreSignRequest {
@@ -2021,6 +2587,8 @@ mod tests {
             }],
             fee_rate: MAX_BITCOIN_FEE_RATE,
             last_fees: None,
+            parallel_sweeps: false,
+            magic_bytes: crate::LEGACY_MAGIC_BYTES,
         }, true; "max-fee-rate-request")]
     #[test_case(
         BitcoinPreSignRequest {
@@ -2030,6 +2598,8 @@ mod tests {
             }],
             fee_rate: MAX_BITCOIN_FEE_RATE * (1.0 + f64::EPSILON * 2.0),
             last_fees: None,
+            parallel_sweeps: false,
+            magic_bytes: crate::LEGACY_MAGIC_BYTES,
         }, false; "max-fee-rate-request-plus-epsilon")]
     #[test_case(
         BitcoinPreSignRequest {
@@ -2039,6 +2609,8 @@ mod tests {
             }],
             fee_rate: MIN_BITCOIN_FEE_RATE,
             last_fees: None,
+            parallel_sweeps: false,
+            magic_bytes: crate::LEGACY_MAGIC_BYTES,
         }, true; "min-fee-rate-request")]
     #[test_case(
         BitcoinPreSignRequest {
@@ -2048,6 +2620,8 @@ mod tests {
             }],
             fee_rate: MIN_BITCOIN_FEE_RATE - f64::EPSILON,
             last_fees: None,
+            parallel_sweeps: false,
+            magic_bytes: crate::LEGACY_MAGIC_BYTES,
         }, false; "min-fee-rate-request-minus-epsilon")]
     #[test_case(
         BitcoinPreSignRequest {
@@ -2057,6 +2631,8 @@ mod tests {
             }],
             fee_rate: f64::NAN,
             last_fees: None,
+            parallel_sweeps: false,
+            magic_bytes: crate::LEGACY_MAGIC_BYTES,
         }, false; "unique-requests-nan-fee-rate")]
     #[test_case(
         BitcoinPreSignRequest {
@@ -2066,6 +2642,8 @@ mod tests {
             }],
             fee_rate: f64::NEG_INFINITY,
             last_fees: None,
+            parallel_sweeps: false,
+            magic_bytes: crate::LEGACY_MAGIC_BYTES,
         }, false; "unique-requests-negative-infinity-fee-rate")]
     #[test_case(
         BitcoinPreSignRequest {
@@ -2075,6 +2653,8 @@ mod tests {
             }],
             fee_rate: f64::INFINITY,
             last_fees: None,
+            parallel_sweeps: false,
+            magic_bytes: crate::LEGACY_MAGIC_BYTES,
         }, false; "unique-requests-positive-infinity-fee-rate")]
     fn test_pre_validation(requests: BitcoinPreSignRequest, result: bool) {
         assert_eq!(requests.pre_validation().is_ok(), result);
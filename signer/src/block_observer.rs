@@ -18,22 +18,31 @@
 //! - Set aggregate key transactions
 
 use std::future::Future;
+use std::num::NonZeroU16;
 use std::time::Duration;
 
 use crate::bitcoin::BitcoinBlockHashStreamProvider;
 use crate::bitcoin::BitcoinInteract;
+use crate::bitcoin::accounting::SignerUtxoChain;
+use crate::bitcoin::headers;
+use crate::bitcoin::headers::HeaderVerificationError;
 use crate::bitcoin::rpc::BitcoinBlockHeader;
+use crate::bitcoin::rpc::BitcoinBlockInfo;
 use crate::bitcoin::rpc::BitcoinTxInfo;
 use crate::bitcoin::utxo::TxDeconstructor as _;
 use crate::context::Context;
 use crate::context::SbtcLimits;
 use crate::context::SignerEvent;
+use crate::context::Subsystem;
+use crate::coordinator_latency;
 use crate::emily_client::EmilyInteract as _;
 use crate::error::Error;
 use crate::keys::PublicKey;
 use crate::keys::SignerScriptPubKey as _;
+use crate::logging::CorrelationId;
 use crate::metrics::BITCOIN_BLOCKCHAIN;
 use crate::metrics::Metrics;
+use crate::sbtc_limits;
 use crate::stacks::api::SignerSetInfo;
 use crate::stacks::api::StacksInteract as _;
 use crate::stacks::contracts::SMART_CONTRACTS;
@@ -44,14 +53,20 @@ use crate::storage::TransactionHandle as _;
 use crate::storage::model;
 use crate::storage::model::BitcoinBlockRef;
 use crate::storage::model::EncryptedDkgShares;
+use crate::storage::util::retry_transient;
 use crate::util::FutureExt as _;
 use bitcoin::Amount;
 use bitcoin::BlockHash;
+use bitcoin::MerkleBlock;
+use bitcoin::OutPoint;
 use bitcoin::ScriptBuf;
+use bitcoin::Txid;
 use futures::stream::StreamExt as _;
 use sbtc::deposits::CreateDepositRequest;
 use sbtc::deposits::DepositInfo;
+use sbtc::deposits::SiblingOutputPolicy;
 use std::collections::HashSet;
+use tracing::Instrument as _;
 
 /// Block observer
 #[derive(Debug)]
@@ -77,7 +92,12 @@ pub struct Deposit {
 }
 
 impl DepositRequestValidator for CreateDepositRequest {
-    async fn validate<C>(&self, client: &C, is_mainnet: bool) -> Result<Option<Deposit>, Error>
+    async fn validate<C>(
+        &self,
+        client: &C,
+        is_mainnet: bool,
+        policy: SiblingOutputPolicy,
+    ) -> Result<Option<Deposit>, Error>
     where
         C: BitcoinInteract,
     {
@@ -110,7 +130,7 @@ impl DepositRequestValidator for CreateDepositRequest {
         tx_info.validate()?;
 
         Ok(Some(Deposit {
-            info: self.validate_tx(&tx_info.tx, is_mainnet)?,
+            info: self.validate_tx_with_policy(&tx_info.tx, is_mainnet, policy)?,
             tx_info,
             block_hash,
         }))
@@ -124,11 +144,13 @@ pub trait DepositRequestValidator {
     ///
     /// This function fetches the transaction using the given client and
     /// checks that the transaction has been submitted. The transaction
-    /// need not be confirmed.
+    /// need not be confirmed. The outputs of the transaction other than
+    /// the deposit output are checked against the given policy.
     fn validate<C>(
         &self,
         client: &C,
         is_mainnet: bool,
+        policy: SiblingOutputPolicy,
     ) -> impl Future<Output = Result<Option<Deposit>, Error>>
     where
         C: BitcoinInteract;
@@ -166,6 +188,11 @@ where
 
             match poll.await {
                 Ok(Some(Ok(block_hash))) => {
+                    // Process the block once the database is reachable
+                    // again, rather than failing during a failover.
+                    if !self.context.wait_for_database().await {
+                        break;
+                    }
                     span.record("bitcoin_tip_hash", tracing::field::display(block_hash));
 
                     tracing::info!("observed new bitcoin block from stream");
@@ -175,16 +202,24 @@ where
                     )
                     .increment(1);
 
+                    let errors = self.context.state().error_registry();
+                    let mut healthy = true;
+
                     if let Err(error) = self.process_bitcoin_chain_tip(block_hash).await {
                         tracing::error!(%error, "could not process bitcoin blocks");
+                        errors.record_error(Subsystem::BlockObserver, &error);
+                        healthy = false;
                     }
 
                     if let Err(error) = self.process_stacks_blocks().await {
                         tracing::error!(%error, "could not process stacks blocks");
+                        errors.record_error(Subsystem::BlockObserver, &error);
+                        healthy = false;
                     }
 
                     if let Err(error) = self.check_pending_dkg_shares(block_hash).await {
                         tracing::error!(%error, "could not check pending dkg shares");
+                        errors.record_error(Subsystem::BlockObserver, &error);
                         continue;
                     }
 
@@ -193,20 +228,33 @@ where
                         Ok(chain_tip) => chain_tip,
                         Err(error) => {
                             tracing::error!(%error, "could not update the signer state");
+                            errors.record_error(Subsystem::BlockObserver, &error);
                             continue;
                         }
                     };
 
                     tracing::info!("loading latest deposit requests from Emily");
-                    if let Err(error) = self.load_latest_deposit_requests().await {
+                    let result = self.load_latest_deposit_requests().await;
+                    if let Err(error) = &result {
                         tracing::error!(%error, "could not load latest deposit requests from Emily");
                     }
+                    errors.record(Subsystem::EmilyClient, &result);
+
+                    if let Err(error) = self.record_request_counts(&chain_tip).await {
+                        tracing::warn!(%error, "could not record the request counts");
+                    }
+
+                    if healthy {
+                        errors.record_success(Subsystem::BlockObserver);
+                    }
 
                     self.context
                         .signal(SignerEvent::BitcoinBlockObserved(chain_tip).into())?;
                 }
                 Ok(Some(Err(error))) => {
                     tracing::error!(%error, "error decoding new bitcoin block hash from stream");
+                    let errors = self.context.state().error_registry();
+                    errors.record_error(Subsystem::BlockObserver, &error);
                     continue;
                 }
                 _ => continue,
@@ -244,21 +292,38 @@ impl<C: Context, B> BlockObserver<C, B> {
     pub async fn load_requests(&self, requests: &[CreateDepositRequest]) -> Result<(), Error> {
         let mut deposit_requests = Vec::new();
         let mut deposit_request_txs = Vec::new();
+        let mut new_deposits = Vec::new();
+        let db = self.context.get_storage_mut();
         let bitcoin_client = self.context.get_bitcoin_client();
         let is_mainnet = self.context.config().signer.network.is_mainnet();
+        let policy = self.context.config().signer.sibling_output_policy();
 
         for request in requests {
+            let correlation_id = CorrelationId::from(request.outpoint);
+            let span = tracing::debug_span!("deposit-request", %correlation_id);
             let deposit = request
-                .validate(&bitcoin_client, is_mainnet)
+                .validate(&bitcoin_client, is_mainnet, policy)
+                .instrument(span.clone())
                 .await
-                .inspect_err(|error| tracing::warn!(%error, "could not validate deposit request"));
+                .inspect_err(|error| {
+                    tracing::warn!(parent: &span, %error, "could not validate deposit request")
+                });
 
             // We log the error above, so we just need to extract the
             // deposit now.
             Metrics::increment_deposit_total(&deposit);
             let Ok(Some(deposit)) = deposit else { continue };
 
-            self.process_bitcoin_blocks_until(deposit.block_hash)
+            tracing::debug!(parent: &span, "validated deposit request");
+            // The deposit request is only written once its block has been
+            // processed, so we match block filters against its
+            // scriptPubKey directly.
+            let deposit_script_pubkey = sbtc::deposits::to_script_pubkey(
+                deposit.info.deposit_script.clone(),
+                deposit.info.reclaim_script.clone(),
+            );
+            self.process_bitcoin_blocks_until(deposit.block_hash, &[deposit_script_pubkey])
+                .instrument(span)
                 .await?;
 
             let tx = model::BitcoinTxRef {
@@ -266,18 +331,91 @@ impl<C: Context, B> BlockObserver<C, B> {
                 block_hash: deposit.block_hash.into(),
             };
 
+            // Emily only needs the proof of the deposit transaction once,
+            // so we only send it for deposits that are new to us.
+            let outpoint = request.outpoint;
+            let txid = outpoint.txid.into();
+            if !db.deposit_request_exists(&txid, outpoint.vout).await? {
+                new_deposits.push((outpoint, deposit.block_hash));
+            }
+
             deposit_requests.push(model::DepositRequest::from(deposit));
             deposit_request_txs.push(tx);
         }
 
-        let db = self.context.get_storage_mut();
-        db.write_bitcoin_transactions(deposit_request_txs).await?;
-        db.write_deposit_requests(deposit_requests).await?;
+        retry_transient(|| {
+            self.write_deposit_requests(&deposit_request_txs, &deposit_requests, &new_deposits)
+        })
+        .await?;
+
+        if let Err(error) = self.report_deposit_tx_proofs(&new_deposits).await {
+            tracing::warn!(%error, "could not report deposit transaction proofs to Emily");
+        }
 
         tracing::debug!("finished processing deposit requests");
         Ok(())
     }
 
+    /// Write the given deposit requests, the transactions that created
+    /// them, and when the new ones were first seen to the database in a
+    /// single storage transaction.
+    async fn write_deposit_requests(
+        &self,
+        deposit_request_txs: &[model::BitcoinTxRef],
+        deposit_requests: &[model::DepositRequest],
+        new_deposits: &[(OutPoint, BlockHash)],
+    ) -> Result<(), Error> {
+        let storage = self.context.get_storage_mut();
+        let storage_tx = storage.begin_transaction().await?;
+
+        storage_tx
+            .write_bitcoin_transactions(deposit_request_txs.to_vec())
+            .await?;
+        storage_tx
+            .write_deposit_requests(deposit_requests.to_vec())
+            .await?;
+
+        for (outpoint, block_hash) in new_deposits {
+            let stage = model::RequestLifecycleStage::Seen {
+                first_seen_block: Some((*block_hash).into()),
+            };
+            storage_tx
+                .write_deposit_request_timestamp(&outpoint.txid.into(), outpoint.vout, stage)
+                .await?;
+        }
+
+        storage_tx.commit().await
+    }
+
+    /// Send Emily the SPV proofs that the transactions of the deposit
+    /// requests with the given outpoints were confirmed in the given
+    /// bitcoin blocks.
+    async fn report_deposit_tx_proofs(
+        &self,
+        deposits: &[(OutPoint, BlockHash)],
+    ) -> Result<(), Error> {
+        if deposits.is_empty() {
+            return Ok(());
+        }
+
+        let bitcoin_client = self.context.get_bitcoin_client();
+        let mut updates = Vec::with_capacity(deposits.len());
+        for (outpoint, block_hash) in deposits {
+            let proof = bitcoin_client
+                .get_tx_out_proof(&outpoint.txid, block_hash)
+                .await?;
+            let update =
+                crate::emily_client::deposit_tx_proof((*outpoint).into(), *block_hash, &proof);
+            updates.push(update);
+        }
+
+        self.context
+            .get_emily_client()
+            .update_deposits(updates)
+            .await?;
+        Ok(())
+    }
+
     /// Set the sbtc start height, if it has not been set already.
     async fn set_sbtc_bitcoin_start_height(&self) -> Result<(), Error> {
         if self.context.state().is_sbtc_bitcoin_start_height_set() {
@@ -365,7 +503,7 @@ impl<C: Context, B> BlockObserver<C, B> {
     /// as canonical and may update blocks not reachable as non-canonical.
     #[tracing::instrument(skip_all)]
     async fn process_bitcoin_chain_tip(&self, chain_tip: BlockHash) -> Result<(), Error> {
-        self.process_bitcoin_blocks_until(chain_tip).await?;
+        self.process_bitcoin_blocks_until(chain_tip, &[]).await?;
 
         let db = self.context.get_storage_mut();
 
@@ -398,27 +536,200 @@ impl<C: Context, B> BlockObserver<C, B> {
     /// This means that if we stop processing blocks midway though,
     /// subsequent calls to this function will properly pick up from where
     /// we left off and update the database.
-    async fn process_bitcoin_blocks_until(&self, block_hash: BlockHash) -> Result<(), Error> {
+    ///
+    /// Up to `bitcoin.backfill_concurrency` blocks are fetched from
+    /// bitcoin-core concurrently, but they are written to the database in
+    /// order of their height. The progress of the backfill is persisted
+    /// after each block, so that it may be reported across restarts.
+    ///
+    /// With block filters enabled, blocks are only fetched in full if
+    /// their filter matches one of the `scriptPubKey`s returned by
+    /// [`Self::block_filter_script_pubkeys`], which includes the given
+    /// deposit `scriptPubKey`s.
+    async fn process_bitcoin_blocks_until(
+        &self,
+        block_hash: BlockHash,
+        deposit_script_pubkeys: &[ScriptBuf],
+    ) -> Result<(), Error> {
         let block_headers = self.next_headers_to_process(block_hash).await?;
 
-        for block_header in block_headers {
-            self.process_bitcoin_block(block_header).await?;
+        let network = self.context.config().signer.network;
+        if self
+            .context
+            .config()
+            .bitcoin
+            .should_verify_block_headers(network)
+        {
+            self.verify_header_chain(&block_headers).await?;
+        }
+
+        let (Some(first), Some(target)) = (block_headers.first(), block_headers.last()) else {
+            return Ok(());
+        };
+
+        let storage = self.context.get_storage_mut();
+        let previous = storage.get_bitcoin_backfill_progress().await?;
+        let now = model::Timestamp::from(time::OffsetDateTime::now_utc());
+        let target = BitcoinBlockRef {
+            block_hash: target.hash.into(),
+            block_height: target.height,
+        };
+        let mut progress = backfill_progress(previous, first.height, target, now);
+        let mut reporter = BackfillReporter::new(&progress);
+
+        let script_pubkeys = self
+            .block_filter_script_pubkeys(deposit_script_pubkeys)
+            .await?;
+        let script_pubkeys = script_pubkeys.as_ref();
+
+        let bitcoin_client = self.context.get_bitcoin_client();
+        let concurrency = self.context.config().bitcoin.backfill_concurrency.get();
+        let mut blocks = futures::stream::iter(block_headers)
+            .map(|block_header| {
+                let bitcoin_client = bitcoin_client.clone();
+                async move {
+                    let block =
+                        fetch_bitcoin_block(&bitcoin_client, &block_header, script_pubkeys).await?;
+                    Ok::<_, Error>((block_header, block))
+                }
+            })
+            .buffered(concurrency as usize);
+
+        while let Some(fetched) = blocks.next().await {
+            let (block_header, block) = fetched?;
+            retry_transient(|| self.process_bitcoin_block(&block_header, &block)).await?;
+
+            progress.processed_height = block_header.height;
+            progress.updated_at = time::OffsetDateTime::now_utc().into();
+            storage.write_bitcoin_backfill_progress(&progress).await?;
+            reporter.report(&progress);
         }
 
         Ok(())
     }
 
+    /// Return the `scriptPubKey`s that the compact block filters of new
+    /// blocks are matched against, or `None` if block filters are
+    /// disabled.
+    ///
+    /// These are the signers' `scriptPubKey`s, the given deposit
+    /// `scriptPubKey`s and those of the deposit requests confirmed within
+    /// the deposit lookback window, so blocks that only have deposits, or
+    /// reclaims of them, are still fetched in full. Sweeps that lock funds
+    /// with a new `scriptPubKey` also spend the signers' current UTXO, so
+    /// the `scriptPubKey`s known now suffice.
+    async fn block_filter_script_pubkeys(
+        &self,
+        deposit_script_pubkeys: &[ScriptBuf],
+    ) -> Result<Option<HashSet<ScriptBuf>>, Error> {
+        let config = self.context.config();
+        if !config.bitcoin.use_block_filters {
+            return Ok(None);
+        }
+
+        let storage = self.context.get_storage();
+        let bootstrap_aggregate_key = config.signer.bootstrap_aggregate_key;
+        let mut script_pubkeys =
+            get_signer_script_pubkeys(&storage, bootstrap_aggregate_key).await?;
+        script_pubkeys.extend(deposit_script_pubkeys.iter().cloned());
+
+        if let Some(chain_tip) = storage.get_bitcoin_canonical_chain_tip().await? {
+            let lookback = config
+                .signer
+                .deposit_lookback
+                .map_or(config.signer.context_window, NonZeroU16::get);
+            let deposits = storage
+                .get_recent_deposit_requests(&chain_tip, lookback)
+                .await?;
+            script_pubkeys.extend(deposits.iter().map(model::DepositRequest::script_pubkey));
+        }
+
+        Ok(Some(script_pubkeys))
+    }
+
+    /// Independently verify the given chain of block headers, ordered
+    /// from lowest to highest, before any of them are processed.
+    ///
+    /// The headers are verified against the stored headers of the blocks
+    /// that they build on, rather than anything that bitcoin-core tells
+    /// us about those blocks.
+    #[tracing::instrument(skip_all)]
+    async fn verify_header_chain(&self, headers: &[BitcoinBlockHeader]) -> Result<(), Error> {
+        let (Some(first), Some(last)) = (headers.first(), headers.last()) else {
+            return Ok(());
+        };
+
+        let network = self.context.config().signer.network;
+        let result = async {
+            let ancestors = self.stored_ancestor_headers(first, last).await?;
+            headers::verify_header_chain(headers, &ancestors, network)
+        };
+
+        result.await.inspect_err(|error| {
+            if let Error::BitcoinHeaderVerification(reason, block_hash) = error {
+                tracing::error!(%block_hash, %reason, "bitcoin block header failed verification");
+                Metrics::increment_header_verification_failures(reason.reason());
+            }
+        })
+    }
+
+    /// Return the stored headers of the blocks that the given chain of new
+    /// headers builds on, ordered from lowest to highest and ending with
+    /// the parent of the first new header.
+    ///
+    /// The parent of the first new header must be in the database at the
+    /// height below it, unless the first new header is at the sBTC start
+    /// height, where the chain that we verify begins. We return fewer
+    /// headers than verification could use if some of them are not
+    /// stored.
+    async fn stored_ancestor_headers(
+        &self,
+        first: &BitcoinBlockHeader,
+        last: &BitcoinBlockHeader,
+    ) -> Result<Vec<bitcoin::block::Header>, Error> {
+        let db = self.context.get_storage();
+        let start_height = self.context.state().get_sbtc_bitcoin_start_height();
+        let parent_hash = first.previous_block_hash;
+
+        let parent = match db.get_bitcoin_block(&parent_hash.into()).await? {
+            Some(parent) => parent,
+            None if first.height <= start_height => return Ok(Vec::new()),
+            None => {
+                let error = HeaderVerificationError::UnknownParent(parent_hash);
+                return Err(Error::BitcoinHeaderVerification(error, first.hash));
+            }
+        };
+        if parent.block_height + 1 != first.height {
+            let error = HeaderVerificationError::HeightMismatch(first.height);
+            return Err(Error::BitcoinHeaderVerification(error, first.hash));
+        }
+
+        let network = self.context.config().signer.network;
+        let from_height = headers::first_ancestor_height(first.height, last.height, network);
+        let limit = u16::try_from(*parent.block_height - *from_height + 1).unwrap_or(u16::MAX);
+        let stored = db
+            .get_bitcoin_block_headers(&parent.block_hash, from_height, limit)
+            .await?;
+
+        let ancestors = headers::linked_suffix(stored, parent_hash);
+        if ancestors.len() < limit as usize {
+            tracing::debug!(
+                num_ancestors = ancestors.len(),
+                "verifying bitcoin block headers without all of the stored ancestors"
+            );
+        }
+        Ok(ancestors)
+    }
+
     /// Write the bitcoin block and any transactions that spend to any of
     /// the signers `scriptPubKey`s to the database.
     #[tracing::instrument(skip_all, fields(block_hash = %block_header.hash))]
-    async fn process_bitcoin_block(&self, block_header: BitcoinBlockHeader) -> Result<(), Error> {
-        let block = self
-            .context
-            .get_bitcoin_client()
-            .get_block(&block_header.hash)
-            .await?
-            .ok_or(Error::BitcoinCoreMissingBlock(block_header.hash))?;
-        let db_block = model::BitcoinBlock::from(&block);
+    async fn process_bitcoin_block(
+        &self,
+        block_header: &BitcoinBlockHeader,
+        block: &BitcoinBlockInfo,
+    ) -> Result<(), Error> {
+        let db_block = model::BitcoinBlock::from(block);
 
         let storage = self.context.get_storage_mut();
 
@@ -436,7 +747,7 @@ impl<C: Context, B> BlockObserver<C, B> {
 
         // Extract the sBTC-related transactions from the block and write them
         // to the database (within the transaction).
-        extract_sbtc_transactions(
+        let sbtc_txids = extract_sbtc_transactions(
             &storage_tx,
             bootstrap_script_pubkey,
             block_header.hash,
@@ -444,6 +755,35 @@ impl<C: Context, B> BlockObserver<C, B> {
         )
         .await?;
 
+        // Keep the header of the block and a proof of inclusion for each
+        // of the sBTC transactions in it, so that we can answer requests
+        // for proofs of our sweep transactions from storage.
+        store_sbtc_transaction_proofs(&storage_tx, block_header, &block.transactions, &sbtc_txids)
+            .await?;
+
+        // Keep the full transaction bodies around, if configured to do so,
+        // so that validation does not need to go to bitcoin-core for them.
+        if let Some(retention) = self.context.config().signer.bitcoin_tx_body_retention {
+            cache_sbtc_transaction_bodies(
+                &storage_tx,
+                &db_block,
+                &block.transactions,
+                &sbtc_txids,
+                retention,
+            )
+            .await?;
+        }
+
+        // Make sure that the value of the signers' UTXO is accounted for in
+        // each of the sweep transactions in the block.
+        check_signer_utxo_invariants(
+            &storage_tx,
+            bootstrap_script_pubkey,
+            block_header,
+            &block.transactions,
+        )
+        .await?;
+
         // Commit the storage transaction.
         storage_tx.commit().await?;
 
@@ -475,17 +815,21 @@ impl<C: Context, B> BlockObserver<C, B> {
         let sbtc_deployed = self.context.state().sbtc_contracts_deployed();
 
         let max_mintable = if limits.total_cap_exists() && sbtc_deployed {
-            let sbtc_supply = self
-                .context
-                .get_stacks_client()
-                .get_sbtc_total_supply(&self.context.config().signer.deployer)
-                .await?;
-            // The maximum amount of sBTC that can be minted is the total cap
-            // minus the current supply.
-            limits
-                .total_cap()
-                .checked_sub(sbtc_supply)
-                .unwrap_or(Amount::ZERO)
+            // The maximum amount of sBTC that can be minted is the total
+            // cap minus the current supply and the mints in flight.
+            let mintable = sbtc_limits::compute_max_mintable(
+                &self.context,
+                &chain_tip.into(),
+                limits.total_cap(),
+            )
+            .await?
+            .amount();
+
+            let emily_client = self.context.get_emily_client();
+            if let Err(error) = emily_client.set_available_to_mint(mintable).await {
+                tracing::warn!(%error, "could not report the amount available to mint to Emily");
+            }
+            mintable
         } else {
             Amount::MAX_MONEY
         };
@@ -539,6 +883,46 @@ impl<C: Context, B> BlockObserver<C, B> {
         Ok(())
     }
 
+    /// Update the `SignerState` object with whether governance has paused
+    /// the sBTC smart contracts. The contracts cannot be paused before
+    /// they have been deployed.
+    async fn update_contract_pause_state(&self) -> Result<(), Error> {
+        let state = self.context.state();
+        if !state.sbtc_contracts_deployed() {
+            return Ok(());
+        }
+
+        let paused = self
+            .context
+            .get_stacks_client()
+            .is_sbtc_contract_paused(&self.context.config().signer.deployer)
+            .await?;
+
+        let was_paused = state.set_contract_paused(paused);
+        Metrics::record_sbtc_contract_paused(paused);
+
+        match (was_paused, paused) {
+            (false, true) => tracing::warn!("the sBTC contracts have been paused; halting sweeps"),
+            (true, false) => tracing::info!("the sBTC contracts have been unpaused"),
+            _ => tracing::trace!(%paused, "the sBTC contract pause state has not changed"),
+        }
+
+        Ok(())
+    }
+
+    /// Record the number of deposit and withdrawal requests in each stage
+    /// of processing on the blockchains identified by the chain tip.
+    async fn record_request_counts(&self, chain_tip: &BitcoinBlockRef) -> Result<(), Error> {
+        let counts = self
+            .context
+            .get_storage()
+            .get_request_counts(&chain_tip.block_hash)
+            .await?;
+
+        Metrics::record_request_counts(&counts);
+        Ok(())
+    }
+
     /// Set the `SignerState` object with current bitcoin chain tip.
     async fn set_bitcoin_chain_tip(&self, chain_tip: BlockHash) -> Result<BitcoinBlockRef, Error> {
         let db = self.context.get_storage();
@@ -553,14 +937,24 @@ impl<C: Context, B> BlockObserver<C, B> {
     }
 
     /// Set the `SignerState` object with current stacks chain tip.
+    ///
+    /// Ties between stacks blocks at the same height are broken by their
+    /// tenure, so that every signer settles on the same chain tip.
     async fn set_stacks_chain_tip(&self, chain_tip: BlockHash) -> Result<(), Error> {
         let db = self.context.get_storage();
         let chain_tip = db
-            .get_stacks_chain_tip(&chain_tip.into())
+            .get_stacks_chain_tip_v2(&chain_tip.into())
             .await?
-            .map(model::StacksBlockRef::from)
             .ok_or_else(|| Error::NoStacksChainTip)?;
 
+        tracing::debug!(
+            stacks_tip_hash = %chain_tip.block.block_hash,
+            stacks_tip_height = %chain_tip.block.block_height,
+            tenure_id = %chain_tip.tenure.tenure_id,
+            tenure_block_count = chain_tip.tenure.block_count,
+            "updating the stacks chain tip"
+        );
+        let chain_tip = model::StacksBlockRef::from(chain_tip.block);
         self.context.state().set_stacks_chain_tip(chain_tip);
         Ok(())
     }
@@ -574,6 +968,8 @@ impl<C: Context, B> BlockObserver<C, B> {
     /// * sBTC limits from Emily.
     /// * The current signer set.
     /// * The current aggregate key.
+    /// * Whether the sBTC contracts are paused.
+    /// * The signers deprioritized in the coordinator election.
     /// * The current stacks chain tip.
     /// * The current bitcoin chain tip.
     async fn update_signer_state(&self, chain_tip: BlockHash) -> Result<BitcoinBlockRef, Error> {
@@ -583,6 +979,25 @@ impl<C: Context, B> BlockObserver<C, B> {
         tracing::info!("updating the signer state with the current signer set");
         self.set_signer_set_info().await?;
 
+        tracing::info!("updating the signer state with the sBTC contract pause state");
+        self.update_contract_pause_state().await?;
+
+        // Without the deprioritized signers we fall back to the unweighted
+        // election, which is better than not processing the block at all.
+        // We only use the scanned transactions if all of the blocks were
+        // scanned, since a partial scan could deprioritize a signer that
+        // the other signers do not.
+        tracing::info!("updating the signer state with the deprioritized coordinators");
+        let chain_tip_hash: model::BitcoinBlockHash = chain_tip.into();
+        let deprioritized_fut = async {
+            coordinator_latency::scan_stacks_blocks(&self.context, &chain_tip_hash).await?;
+            coordinator_latency::update_deprioritized_coordinators(&self.context, &chain_tip_hash)
+                .await
+        };
+        if let Err(error) = deprioritized_fut.await {
+            tracing::warn!(%error, "could not determine the deprioritized coordinators");
+        }
+
         tracing::info!("updating the signer state with the current stacks chain tip");
         self.set_stacks_chain_tip(chain_tip).await?;
 
@@ -637,7 +1052,8 @@ impl<C: Context, B> BlockObserver<C, B> {
 }
 
 /// Extract all BTC transactions from the block where one of the UTXOs
-/// can be spent by the signers.
+/// can be spent by the signers, returning the IDs of the extracted
+/// transactions.
 ///
 /// # Note
 ///
@@ -649,27 +1065,14 @@ pub async fn extract_sbtc_transactions<Storage>(
     bootstrap_aggregate_key: Option<PublicKey>,
     block_hash: BlockHash,
     txs: &[BitcoinTxInfo],
-) -> Result<(), Error>
+) -> Result<HashSet<model::BitcoinTxId>, Error>
 where
     Storage: DbRead + DbWrite,
 {
-    // Convert the bootstrap script public key to a `ScriptBuf` if it is
-    // provided. This is used to check if the transaction outputs are
-    // spent to the bootstrap signers' addresses.
-    let bootstrap_script_pubkey = bootstrap_aggregate_key.map(|key| key.signers_script_pubkey());
-
     // Define a closure to extract the sBTC transactions from the given
     // transactions and write them to the database.
     let extract_fut = || async {
-        // We store all the scriptPubKeys associated with the signers'
-        // aggregate public key. Let's get the last years worth of them.
-        let signer_script_pubkeys: HashSet<ScriptBuf> = db
-            .get_signers_script_pubkeys()
-            .await?
-            .into_iter()
-            .map(ScriptBuf::from_bytes)
-            .chain(bootstrap_script_pubkey.clone())
-            .collect();
+        let signer_script_pubkeys = get_signer_script_pubkeys(db, bootstrap_aggregate_key).await?;
 
         // Look through all the UTXOs in the given transaction slice and
         // keep the transactions where a UTXO is locked with a
@@ -718,6 +1121,12 @@ where
             for prevout in tx_info.to_inputs(&signer_script_pubkeys) {
                 db.write_tx_prevout(&prevout).await?;
                 if prevout.prevout_type == model::TxPrevoutType::Deposit {
+                    db.write_deposit_request_timestamp(
+                        &prevout.prevout_txid,
+                        prevout.prevout_output_index,
+                        model::RequestLifecycleStage::Swept,
+                    )
+                    .await?;
                     metrics::counter!(
                         Metrics::DepositsSweptTotal,
                         "blockchain" => BITCOIN_BLOCKCHAIN,
@@ -726,18 +1135,38 @@ where
                 }
             }
 
-            let (tx_outputs, withdrawal_outputs) = tx_info.to_outputs(&signer_script_pubkeys)?;
+            // A sweep with an OP_RETURN version that we do not know about
+            // was created by signers running a newer release. We cannot
+            // tell which withdrawals it fulfilled, and carrying on without
+            // them would have us fulfill them again, so we fail the block
+            // until this signer is upgraded.
+            let outputs = tx_info.to_outputs(&signer_script_pubkeys);
+            if let Err(Error::OpReturnUnsupportedVersion(version)) = &outputs {
+                tracing::error!(
+                    %txid,
+                    %version,
+                    "sweep transaction uses an unsupported OP_RETURN version; upgrade the signer"
+                );
+            }
+            let (tx_outputs, withdrawal_outputs) = outputs?;
             for output in tx_outputs {
                 db.write_tx_output(&output).await?;
             }
             for output in withdrawal_outputs {
                 db.write_withdrawal_tx_output(&output).await?;
+                db.write_withdrawal_request_timestamp(
+                    output.request_id,
+                    model::RequestLifecycleStage::Swept,
+                )
+                .await?;
             }
         }
 
+        let txids: Vec<model::BitcoinTxId> = sbtc_txs.iter().map(|tx| tx.txid).collect();
+
         // Write these transactions into storage.
         db.write_bitcoin_transactions(sbtc_txs).await?;
-        Ok(())
+        Ok::<_, Error>(txids)
     };
 
     // The first time, we get all sweep transactions with inputs that
@@ -745,12 +1174,381 @@ where
     // scriptPubKey, and we have no way of knowing that ahead of time.
     // The first pass over will populate the database with the new
     // scriptPubKeys.
-    extract_fut().await?;
+    let mut txids: HashSet<model::BitcoinTxId> = extract_fut().await?.into_iter().collect();
 
     // This will catch cases where the signers have locked up their
     // UTXO with a new scriptPubKey and there are a chain of
     // transactions in the block.
-    extract_fut().await
+    txids.extend(extract_fut().await?);
+    Ok(txids)
+}
+
+/// Fetch the bitcoin block with the given header from bitcoin-core.
+///
+/// When `script_pubkeys` are given, the compact block filter of the block
+/// is checked first. If the filter shows that no transaction in the block
+/// pays to, or spends from, any of them, then the block is returned
+/// without its transactions instead of being fetched in full. Blocks are
+/// fetched in full whenever their filter cannot be used.
+async fn fetch_bitcoin_block<B>(
+    bitcoin_client: &B,
+    block_header: &BitcoinBlockHeader,
+    script_pubkeys: Option<&HashSet<ScriptBuf>>,
+) -> Result<BitcoinBlockInfo, Error>
+where
+    B: BitcoinInteract,
+{
+    let block_hash = block_header.hash;
+
+    if let Some(script_pubkeys) = script_pubkeys {
+        match block_filter_matches(bitcoin_client, block_hash, script_pubkeys).await {
+            Ok(Some(true)) => Metrics::increment_block_filter_checks("matched"),
+            Ok(Some(false)) => {
+                tracing::debug!(%block_hash, "block filter has no matches; skipping the block");
+                Metrics::increment_block_filter_checks("skipped");
+                return Ok(BitcoinBlockInfo::without_transactions(block_header));
+            }
+            Ok(None) => {
+                tracing::debug!(%block_hash, "block filter is unavailable; scanning the block");
+                Metrics::increment_block_filter_checks("unavailable");
+            }
+            Err(error) => {
+                tracing::warn!(%block_hash, %error, "could not check the block filter");
+                Metrics::increment_block_filter_checks("unavailable");
+            }
+        }
+    }
+
+    bitcoin_client
+        .get_block(&block_hash)
+        .await?
+        .ok_or(Error::BitcoinCoreMissingBlock(block_hash))
+}
+
+/// Check whether the compact block filter of the block with the given
+/// hash matches any of the given `scriptPubKey`s. Returns `Ok(None)` if
+/// bitcoin-core does not have a filter for the block.
+///
+/// Filters have false positives but no false negatives, so a block whose
+/// filter does not match cannot have a transaction with an output locked
+/// by, or an input spending a UTXO locked by, any of the `scriptPubKey`s.
+async fn block_filter_matches<B>(
+    bitcoin_client: &B,
+    block_hash: BlockHash,
+    script_pubkeys: &HashSet<ScriptBuf>,
+) -> Result<Option<bool>, Error>
+where
+    B: BitcoinInteract,
+{
+    let Some(filter) = bitcoin_client.get_block_filter(&block_hash).await? else {
+        return Ok(None);
+    };
+
+    let query = script_pubkeys.iter().map(|script| script.as_bytes());
+    filter
+        .match_any(block_hash, query)
+        .map(Some)
+        .map_err(|error| Error::BitcoinBlockFilter(error, block_hash))
+}
+
+/// Cache the full bodies of the given sBTC transactions from the block,
+/// along with the outputs that they spend, and delete cached bodies that
+/// are older than the retention window.
+async fn cache_sbtc_transaction_bodies<Storage>(
+    db: &Storage,
+    block: &model::BitcoinBlock,
+    txs: &[BitcoinTxInfo],
+    sbtc_txids: &HashSet<model::BitcoinTxId>,
+    retention: NonZeroU16,
+) -> Result<(), Error>
+where
+    Storage: DbWrite,
+{
+    let block_ref = model::BitcoinBlockRef::from(block);
+    let bodies: Vec<model::BitcoinTxBody> = txs
+        .iter()
+        .filter(|tx_info| sbtc_txids.contains(&tx_info.compute_txid().into()))
+        .filter_map(|tx_info| model::BitcoinTxBody::from_tx_info(tx_info, &block_ref))
+        .collect();
+
+    db.write_bitcoin_tx_bodies(&bodies).await?;
+
+    let min_block_height = block.block_height.saturating_sub(retention.get());
+    let pruned = db.prune_bitcoin_tx_bodies(min_block_height).await?;
+    tracing::trace!(cached = bodies.len(), %pruned, "cached sbtc transaction bodies");
+
+    Ok(())
+}
+
+/// Store the header of the given block, along with a merkle proof for
+/// each of the given sBTC transactions that is in the block.
+///
+/// Each proof is a merkle block that matches only its own transaction, so
+/// that it stays small no matter how many transactions are in the block.
+async fn store_sbtc_transaction_proofs<Storage>(
+    db: &Storage,
+    block_header: &BitcoinBlockHeader,
+    txs: &[BitcoinTxInfo],
+    sbtc_txids: &HashSet<model::BitcoinTxId>,
+) -> Result<(), Error>
+where
+    Storage: DbWrite,
+{
+    // Proofs built from a header that does not hash to the block hash
+    // would not verify, so there is no point in storing them.
+    let header = block_header.to_consensus_header();
+    let Some(header) = header.filter(|header| header.block_hash() == block_header.hash) else {
+        tracing::warn!("bitcoin block header does not match the block hash; not storing proofs");
+        return Ok(());
+    };
+    db.write_bitcoin_block_header(&header).await?;
+
+    let block_txids: Vec<Txid> = txs.iter().map(BitcoinTxInfo::compute_txid).collect();
+    let proofs: Vec<model::BitcoinTxMerkleProof> = block_txids
+        .iter()
+        .filter(|txid| sbtc_txids.contains(&model::BitcoinTxId::from(**txid)))
+        .map(|txid| model::BitcoinTxMerkleProof {
+            txid: (*txid).into(),
+            block_hash: block_header.hash.into(),
+            block_height: block_header.height,
+            merkle_block: MerkleBlock::from_header_txids_with_predicate(
+                &header,
+                &block_txids,
+                |other| other == txid,
+            ),
+        })
+        .collect();
+
+    db.write_bitcoin_tx_merkle_proofs(&proofs).await
+}
+
+/// Return the last year's worth of `scriptPubKey`s associated with the
+/// signers' aggregate public key, along with the bootstrap `scriptPubKey`
+/// if one is provided.
+async fn get_signer_script_pubkeys<Storage>(
+    db: &Storage,
+    bootstrap_aggregate_key: Option<PublicKey>,
+) -> Result<HashSet<ScriptBuf>, Error>
+where
+    Storage: DbRead,
+{
+    // Convert the bootstrap script public key to a `ScriptBuf` if it is
+    // provided. This is used to check if the transaction outputs are
+    // spent to the bootstrap signers' addresses.
+    let bootstrap_script_pubkey = bootstrap_aggregate_key.map(|key| key.signers_script_pubkey());
+
+    Ok(db
+        .get_signers_script_pubkeys()
+        .await?
+        .into_iter()
+        .map(ScriptBuf::from_bytes)
+        .chain(bootstrap_script_pubkey)
+        .collect())
+}
+
+/// Check that, for each sweep transaction in the block, the value of the
+/// new signers' UTXO equals the value of the signers' input, plus the
+/// deposits swept, minus the withdrawals paid and the fee, and that the
+/// value of the signers' input equals the value of the signers' UTXO
+/// created by the previous sweep transaction. The chain starts from the
+/// signers' UTXOs that were unspent as of the parent block. Any violation
+/// is logged, counted and written to the database.
+///
+/// # Note
+///
+/// This function must be called after [`extract_sbtc_transactions`], so
+/// that the signers' `scriptPubKey`s created in this block are known.
+pub async fn check_signer_utxo_invariants<Storage>(
+    db: &Storage,
+    bootstrap_aggregate_key: Option<PublicKey>,
+    block_header: &BitcoinBlockHeader,
+    txs: &[BitcoinTxInfo],
+) -> Result<(), Error>
+where
+    Storage: DbRead + DbWrite,
+{
+    let block_hash = block_header.hash;
+    let signer_script_pubkeys = get_signer_script_pubkeys(db, bootstrap_aggregate_key).await?;
+
+    let signer_utxos = db
+        .get_signer_utxos(&block_header.previous_block_hash.into())
+        .await?
+        .into_iter()
+        .map(|utxo| ((utxo.outpoint.txid.into(), utxo.outpoint.vout), utxo.amount));
+    let mut signer_utxo_chain = SignerUtxoChain::new(signer_utxos);
+
+    for tx_info in txs {
+        if tx_info.tx.is_coinbase() || !tx_info.is_signer_created(&signer_script_pubkeys) {
+            continue;
+        }
+        // The fee is always present for transactions that have passed
+        // through `extract_sbtc_transactions`, which validates them.
+        let Some(fee) = tx_info.fee else {
+            continue;
+        };
+
+        let prevouts = tx_info.to_inputs(&signer_script_pubkeys);
+        let (outputs, _) = tx_info.to_outputs(&signer_script_pubkeys)?;
+        let Some(value_flow) = signer_utxo_chain.next_value_flow(&prevouts, &outputs, fee) else {
+            continue;
+        };
+        if value_flow.is_valid() {
+            continue;
+        }
+
+        let txid = tx_info.compute_txid();
+        tracing::error!(
+            %txid,
+            %block_hash,
+            signer_input = %value_flow.signer_input,
+            deposits = %value_flow.deposits,
+            withdrawals = %value_flow.withdrawals,
+            fee = %value_flow.fee,
+            signer_output = %value_flow.signer_output,
+            previous_signer_output = ?value_flow.previous_signer_output,
+            discrepancy = %value_flow.discrepancy(),
+            "the signers' UTXO value is not accounted for in a sweep transaction"
+        );
+        Metrics::increment_signer_utxo_violations();
+
+        let violation = value_flow.into_violation(txid.into(), block_hash.into());
+        db.write_signer_utxo_violation(&violation).await?;
+    }
+
+    Ok(())
+}
+
+/// Return the progress of a backfill of the blocks from `start_height` up
+/// to the `target` chain tip, before any of them have been written.
+///
+/// If the previous backfill was interrupted before it completed and this
+/// backfill carries on from where it left off, then its start is kept so
+/// that the progress covers the whole backfill across restarts.
+fn backfill_progress(
+    previous: Option<model::BitcoinBackfillProgress>,
+    start_height: model::BitcoinBlockHeight,
+    target: BitcoinBlockRef,
+    now: model::Timestamp,
+) -> model::BitcoinBackfillProgress {
+    let (backfill_start_height, started_at) = match previous {
+        Some(previous)
+            if !previous.is_complete()
+                && (previous.start_height..=previous.processed_height + 1)
+                    .contains(&start_height) =>
+        {
+            (previous.start_height, previous.started_at)
+        }
+        _ => (start_height, now),
+    };
+
+    model::BitcoinBackfillProgress {
+        target_block_hash: target.block_hash,
+        target_block_height: target.block_height,
+        start_height: backfill_start_height,
+        processed_height: start_height.saturating_sub(1u64),
+        started_at,
+        updated_at: now,
+    }
+}
+
+/// Estimate how long it will take to write the remaining blocks, given
+/// how long it took to write the blocks written so far. Returns `None` if
+/// no blocks have been written yet.
+fn estimate_time_remaining(
+    blocks_written: u64,
+    elapsed: Duration,
+    blocks_remaining: u64,
+) -> Option<Duration> {
+    if blocks_written == 0 {
+        return None;
+    }
+    let per_block = elapsed.div_f64(blocks_written as f64);
+    Some(per_block.mul_f64(blocks_remaining as f64))
+}
+
+/// Reports the progress of a bitcoin block backfill in the logs and
+/// metrics, along with an estimate of when it will complete.
+struct BackfillReporter {
+    /// When this signer started writing the blocks in the backfill.
+    started: std::time::Instant,
+    /// The height of the last block that was written before this signer
+    /// started writing the blocks in the backfill.
+    initial_height: model::BitcoinBlockHeight,
+    /// When the progress was last logged.
+    last_logged: std::time::Instant,
+    /// Whether the progress is logged. It is not for the usual case of a
+    /// single new block.
+    enabled: bool,
+}
+
+impl BackfillReporter {
+    /// The minimum amount of time between logs of the progress.
+    const LOG_INTERVAL: Duration = Duration::from_secs(10);
+
+    fn new(progress: &model::BitcoinBackfillProgress) -> Self {
+        let enabled = progress.blocks_remaining() > 1;
+        if enabled {
+            tracing::info!(
+                start_height = %progress.start_height,
+                target_height = %progress.target_block_height,
+                blocks_remaining = progress.blocks_remaining(),
+                resumed = progress.blocks_processed() > 0,
+                "starting bitcoin block backfill"
+            );
+        }
+
+        let now = std::time::Instant::now();
+        Self {
+            started: now,
+            initial_height: progress.processed_height,
+            last_logged: now,
+            enabled,
+        }
+    }
+
+    /// Report the progress after a block has been written.
+    fn report(&mut self, progress: &model::BitcoinBackfillProgress) {
+        let blocks_written = *progress
+            .processed_height
+            .saturating_sub(self.initial_height);
+        let eta = estimate_time_remaining(
+            blocks_written,
+            self.started.elapsed(),
+            progress.blocks_remaining(),
+        );
+        Metrics::record_bitcoin_backfill_progress(progress, eta);
+
+        if !self.enabled {
+            return;
+        }
+
+        if progress.is_complete() {
+            let duration = (*progress.updated_at - *progress.started_at).whole_seconds();
+            tracing::info!(
+                target_height = %progress.target_block_height,
+                blocks_processed = progress.blocks_processed(),
+                duration_seconds = duration,
+                "finished bitcoin block backfill"
+            );
+            return;
+        }
+
+        if self.last_logged.elapsed() < Self::LOG_INTERVAL {
+            return;
+        }
+        self.last_logged = std::time::Instant::now();
+
+        let blocks_processed = progress.blocks_processed();
+        let blocks_total = blocks_processed + progress.blocks_remaining();
+        tracing::info!(
+            processed_height = %progress.processed_height,
+            target_height = %progress.target_block_height,
+            blocks_remaining = progress.blocks_remaining(),
+            percent_complete = blocks_processed * 100 / blocks_total.max(1),
+            eta_seconds = eta.map(|eta| eta.as_secs()),
+            "bitcoin block backfill progress"
+        );
+    }
 }
 
 /// Return the signing set that can make sBTC related contract calls along
@@ -812,7 +1610,9 @@ mod tests {
     use bitcoin::Amount;
     use bitcoin::BlockHash;
     use bitcoin::TxOut;
+    use bitcoin::bip158::BlockFilter;
     use bitcoin::hashes::Hash as _;
+    use emily_client::models::DepositStatus;
     use fake::Dummy as _;
     use fake::Fake as _;
     use model::BitcoinTxId;
@@ -822,6 +1622,7 @@ mod tests {
     use crate::context::SignerSignal;
     use crate::keys::PublicKey;
     use crate::keys::SignerScriptPubKey as _;
+    use crate::secret::Secret;
     use crate::storage;
     use crate::storage::model::BitcoinBlockHash;
     use crate::storage::model::DkgSharesStatus;
@@ -1059,6 +1860,74 @@ mod tests {
         assert_eq!(deposit3.max_fee, u64::MAX);
     }
 
+    /// Test that deposits whose funding transaction has an output with a
+    /// future witness version are only kept by signers that validate
+    /// sibling outputs permissively.
+    #[test_case::test_case(false, 1; "permissive signers keep the deposit")]
+    #[test_case::test_case(true, 0; "strict signers skip the deposit")]
+    #[tokio::test]
+    async fn deposits_with_future_witness_sibling_outputs(strict: bool, expected: usize) {
+        let mut rng = get_rng();
+        let mut test_harness = TestHarness::generate(&mut rng, 20, 0..5);
+        let block_hash = test_harness
+            .bitcoin_blocks()
+            .first()
+            .map(|block| block.block_hash);
+
+        // The funding transaction pays to a witness version 2 program
+        // alongside the deposit output.
+        let mut tx_setup = sbtc::testing::deposits::tx_setup(150, 15_000, &[500_000]);
+        let future_witness = bitcoin::ScriptBuf::builder()
+            .push_opcode(bitcoin::opcodes::all::OP_PUSHNUM_2)
+            .push_slice([0x01; 32])
+            .into_script();
+        tx_setup.tx.output.push(TxOut {
+            value: Amount::from_sat(1_000),
+            script_pubkey: future_witness,
+        });
+
+        let deposit_request = CreateDepositRequest {
+            outpoint: bitcoin::OutPoint {
+                txid: tx_setup.tx.compute_txid(),
+                vout: 0,
+            },
+            deposit_script: tx_setup.deposits.first().unwrap().deposit_script(),
+            reclaim_script: tx_setup.reclaims.first().unwrap().reclaim_script(),
+        };
+        let get_tx_resp = GetTxResponse {
+            tx: tx_setup.tx.clone(),
+            block_hash,
+            confirmations: None,
+            block_time: None,
+        };
+
+        test_harness.add_deposits(&[(get_tx_resp.tx.compute_txid(), get_tx_resp)]);
+        test_harness.add_pending_deposits(&[deposit_request]);
+        let min_height = test_harness.min_block_height();
+
+        let storage = storage::memory::Store::new_shared();
+        let ctx = TestContext::builder()
+            .with_storage(storage.clone())
+            .with_stacks_client(test_harness.clone())
+            .with_emily_client(test_harness.clone())
+            .with_bitcoin_client(test_harness.clone())
+            .modify_settings(|settings| {
+                settings.signer.sbtc_bitcoin_start_height = min_height;
+                settings.signer.strict_deposit_sibling_outputs = strict;
+            })
+            .build();
+
+        let block_observer = BlockObserver {
+            context: ctx,
+            bitcoin_block_source: (),
+        };
+
+        block_observer.load_latest_deposit_requests().await.unwrap();
+
+        let db = storage.lock().await;
+        assert_eq!(db.deposit_requests.len(), expected);
+    }
+
     /// Test that `BlockObserver::extract_deposit_requests` after
     /// `BlockObserver::load_latest_deposit_requests` stores validated
     /// deposit requests into "storage".
@@ -1139,6 +2008,77 @@ mod tests {
         );
     }
 
+    /// Test that `BlockObserver::load_latest_deposit_requests` sends Emily
+    /// the proofs of the transactions of deposit requests that are new to
+    /// the signer, and only those.
+    #[tokio::test]
+    async fn new_deposit_requests_get_their_tx_proofs_reported() {
+        let mut rng = get_rng();
+        let mut test_harness = TestHarness::generate(&mut rng, 20, 0..5);
+
+        let block_hash = test_harness
+            .bitcoin_blocks()
+            .first()
+            .map(|block| block.block_hash)
+            .unwrap();
+
+        let tx_setup = sbtc::testing::deposits::tx_setup(150, 32000, &[500_000]);
+        let txid = tx_setup.tx.compute_txid();
+        let deposit_request = CreateDepositRequest {
+            outpoint: bitcoin::OutPoint { txid, vout: 0 },
+            deposit_script: tx_setup.deposits.first().unwrap().deposit_script(),
+            reclaim_script: tx_setup.reclaims.first().unwrap().reclaim_script(),
+        };
+        let get_tx_resp = GetTxResponse {
+            tx: tx_setup.tx.clone(),
+            block_hash: Some(block_hash),
+            confirmations: None,
+            block_time: None,
+        };
+        test_harness.add_deposit(txid, get_tx_resp);
+        test_harness.add_pending_deposit(deposit_request);
+
+        let min_height = test_harness.min_block_height();
+        let ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_stacks_client(test_harness.clone())
+            .with_emily_client(test_harness.clone())
+            .with_bitcoin_client(test_harness.clone())
+            .modify_settings(|settings| settings.signer.sbtc_bitcoin_start_height = min_height)
+            .build();
+
+        let block_observer = BlockObserver {
+            context: ctx,
+            bitcoin_block_source: (),
+        };
+
+        block_observer.load_latest_deposit_requests().await.unwrap();
+
+        let updates = test_harness.deposit_updates();
+        assert_eq!(updates.len(), 1);
+        let update = &updates[0];
+        assert_eq!(update.bitcoin_txid, txid.to_string());
+        assert_eq!(update.bitcoin_tx_output_index, 0);
+        assert_eq!(update.status, DepositStatus::Pending);
+        assert_eq!(
+            update.deposit_block_hash,
+            Some(Some(block_hash.to_string()))
+        );
+
+        let proof_hex = update.deposit_tx_proof.clone().flatten().unwrap();
+        let proof: bitcoin::MerkleBlock =
+            bitcoin::consensus::encode::deserialize_hex(&proof_hex).unwrap();
+        let mut matches = Vec::new();
+        let mut indexes = Vec::new();
+        proof.extract_matches(&mut matches, &mut indexes).unwrap();
+        assert_eq!(matches, vec![txid]);
+
+        // Emily keeps returning the deposit request, but the signer has
+        // already reported its proof.
+        block_observer.load_latest_deposit_requests().await.unwrap();
+        assert_eq!(test_harness.deposit_updates().len(), 1);
+    }
+
     /// Test that `BlockObserver::extract_sbtc_transactions` takes the
     /// stored signer `scriptPubKey`s and stores all transactions from a
     /// bitcoin block that match one of those `scriptPubkey`s.
@@ -1169,7 +2109,7 @@ mod tests {
             aggregate_key,
             tweaked_aggregate_key: aggregate_key.signers_tweaked_pubkey().unwrap(),
             script_pubkey: signers_script_pubkey.clone(),
-            encrypted_private_shares: Vec::new(),
+            encrypted_private_shares: Secret::default(),
             public_shares: Vec::new(),
             signer_set_public_keys: vec![aggregate_key],
             signature_share_threshold: 1,
@@ -1249,4 +2189,353 @@ mod tests {
         assert_eq!(tx_ids.len(), 1);
         assert!(tx_ids.contains(&expected_tx_id));
     }
+
+    #[tokio::test]
+    async fn processing_blocks_persists_backfill_progress() {
+        let mut rng = get_rng();
+        let storage = storage::memory::Store::new_shared();
+        let test_harness = TestHarness::generate(&mut rng, 20, 0..5);
+        let min_height = test_harness.min_block_height();
+        let ctx = TestContext::builder()
+            .with_storage(storage.clone())
+            .with_stacks_client(test_harness.clone())
+            .with_emily_client(test_harness.clone())
+            .with_bitcoin_client(test_harness.clone())
+            .modify_settings(|settings| {
+                settings.signer.sbtc_bitcoin_start_height = min_height;
+                settings.bitcoin.backfill_concurrency = NonZeroU16::new(3).unwrap();
+            })
+            .build();
+
+        let block_observer = BlockObserver {
+            context: ctx.clone(),
+            bitcoin_block_source: test_harness.clone(),
+        };
+
+        let chain_tip = test_harness
+            .bitcoin_blocks()
+            .iter()
+            .max_by_key(|block| block.height)
+            .unwrap();
+
+        block_observer
+            .process_bitcoin_blocks_until(chain_tip.block_hash, &[])
+            .await
+            .unwrap();
+
+        // Every block is written, even though they were fetched
+        // concurrently.
+        for block in test_harness.bitcoin_blocks() {
+            let persisted = storage
+                .get_bitcoin_block(&block.block_hash.into())
+                .await
+                .unwrap();
+            assert!(persisted.is_some());
+        }
+
+        let progress = storage
+            .get_bitcoin_backfill_progress()
+            .await
+            .unwrap()
+            .expect("backfill progress wasn't persisted");
+
+        assert!(progress.is_complete());
+        assert_eq!(progress.target_block_hash, chain_tip.block_hash.into());
+        assert_eq!(progress.target_block_height, chain_tip.height);
+        assert_eq!(progress.start_height, min_height.unwrap());
+        assert_eq!(progress.blocks_remaining(), 0);
+        assert_eq!(
+            progress.blocks_processed(),
+            test_harness.bitcoin_blocks().len() as u64
+        );
+    }
+
+    #[test]
+    fn backfill_progress_resumes_an_interrupted_backfill() {
+        let mut rng = get_rng();
+        let now = model::Timestamp::from(time::OffsetDateTime::now_utc());
+        let earlier = model::Timestamp::from(*now - time::Duration::hours(1));
+        let target = BitcoinBlockRef {
+            block_hash: fake::Faker.fake_with_rng(&mut rng),
+            block_height: 1000u64.into(),
+        };
+
+        // A signer with no previous backfill starts a new one.
+        let progress = backfill_progress(None, 500u64.into(), target, now);
+        assert_eq!(progress.start_height, 500u64.into());
+        assert_eq!(progress.processed_height, 499u64.into());
+        assert_eq!(progress.started_at, now);
+        assert_eq!(progress.blocks_processed(), 0);
+        assert_eq!(progress.blocks_remaining(), 501);
+
+        // A signer that stopped partway through keeps the start of the
+        // backfill that was interrupted, even though the chain tip moved.
+        let interrupted = model::BitcoinBackfillProgress {
+            target_block_hash: fake::Faker.fake_with_rng(&mut rng),
+            target_block_height: 990u64.into(),
+            start_height: 100u64.into(),
+            processed_height: 499u64.into(),
+            started_at: earlier,
+            updated_at: earlier,
+        };
+        let progress = backfill_progress(Some(interrupted.clone()), 500u64.into(), target, now);
+        assert_eq!(progress.start_height, 100u64.into());
+        assert_eq!(progress.started_at, earlier);
+        assert_eq!(progress.target_block_hash, target.block_hash);
+        assert_eq!(progress.blocks_processed(), 400);
+        assert_eq!(progress.blocks_remaining(), 501);
+
+        // A backfill that does not carry on from the previous one starts
+        // afresh.
+        let progress = backfill_progress(Some(interrupted.clone()), 700u64.into(), target, now);
+        assert_eq!(progress.start_height, 700u64.into());
+        assert_eq!(progress.started_at, now);
+
+        // So does one after a backfill that completed.
+        let completed = model::BitcoinBackfillProgress {
+            processed_height: interrupted.target_block_height,
+            ..interrupted
+        };
+        let progress = backfill_progress(Some(completed), 991u64.into(), target, now);
+        assert_eq!(progress.start_height, 991u64.into());
+        assert_eq!(progress.started_at, now);
+    }
+
+    #[test_case::test_case(0, 10, 100 => None; "nothing written yet")]
+    #[test_case::test_case(10, 10, 0 => Some(0); "nothing remaining")]
+    #[test_case::test_case(10, 10, 100 => Some(100); "one block per second")]
+    #[test_case::test_case(40, 10, 100 => Some(25); "four blocks per second")]
+    fn estimated_time_remaining_follows_the_observed_rate(
+        blocks_written: u64,
+        elapsed_secs: u64,
+        blocks_remaining: u64,
+    ) -> Option<u64> {
+        let elapsed = Duration::from_secs(elapsed_secs);
+        estimate_time_remaining(blocks_written, elapsed, blocks_remaining)
+            .map(|eta| eta.as_secs_f64().round() as u64)
+    }
+
+    /// Return a block with a single coinbase transaction that pays to the
+    /// given `scriptPubKey`, along with its basic compact block filter.
+    fn block_with_filter(script_pubkey: ScriptBuf) -> (bitcoin::Block, BlockFilter) {
+        let coinbase = bitcoin::Transaction {
+            version: bitcoin::transaction::Version::TWO,
+            lock_time: bitcoin::absolute::LockTime::ZERO,
+            input: vec![bitcoin::TxIn::default()],
+            output: vec![TxOut {
+                value: Amount::from_sat(50_000),
+                script_pubkey,
+            }],
+        };
+        let block = bitcoin::Block {
+            header: bitcoin::block::Header {
+                version: bitcoin::block::Version::ONE,
+                prev_blockhash: BlockHash::all_zeros(),
+                merkle_root: bitcoin::TxMerkleNode::all_zeros(),
+                time: 0,
+                bits: bitcoin::CompactTarget::from_consensus(0),
+                nonce: 0,
+            },
+            txdata: vec![coinbase],
+        };
+        // A coinbase transaction has no prevouts to look up.
+        let filter = BlockFilter::new_script_filter(&block, |outpoint: &OutPoint| {
+            Err::<ScriptBuf, _>(bitcoin::bip158::Error::UtxoMissing(*outpoint))
+        })
+        .unwrap();
+        (block, filter)
+    }
+
+    #[test_case::test_case(Some(true), true; "matching filters fetch the block")]
+    #[test_case::test_case(Some(false), false; "filters without matches skip the block")]
+    #[test_case::test_case(None, true; "unavailable filters fetch the block")]
+    #[tokio::test]
+    async fn block_filters_decide_whether_blocks_are_fetched(
+        pays_to_signers: Option<bool>,
+        fetched: bool,
+    ) {
+        let mut rng = get_rng();
+        let signer_key: PublicKey = fake::Faker.fake_with_rng(&mut rng);
+        let other_key: PublicKey = fake::Faker.fake_with_rng(&mut rng);
+        let signer_script_pubkey = signer_key.signers_script_pubkey();
+        let other_script_pubkey = other_key.signers_script_pubkey();
+
+        let script_pubkey = match pays_to_signers {
+            Some(true) => signer_script_pubkey.clone(),
+            _ => other_script_pubkey,
+        };
+        let (block, filter) = block_with_filter(script_pubkey);
+        let filter = pays_to_signers.map(|_| filter);
+
+        let header = BitcoinBlockHeader {
+            hash: block.block_hash(),
+            height: 100u64.into(),
+            time: 0,
+            previous_block_hash: block.header.prev_blockhash,
+            version: 1,
+            merkle_root: block.header.merkle_root,
+            bits: block.header.bits,
+            nonce: 0,
+        };
+        let full_block = BitcoinBlockInfo::without_transactions(&header);
+
+        let mut client = crate::bitcoin::MockBitcoinInteract::new();
+        client
+            .expect_get_block_filter()
+            .once()
+            .returning(move |_| Box::pin(std::future::ready(Ok(filter.clone()))));
+        client
+            .expect_get_block()
+            .times(usize::from(fetched))
+            .returning(move |_| Box::pin(std::future::ready(Ok(Some(full_block.clone())))));
+
+        let script_pubkeys = HashSet::from([signer_script_pubkey]);
+        let block_info = fetch_bitcoin_block(&client, &header, Some(&script_pubkeys))
+            .await
+            .unwrap();
+
+        assert_eq!(block_info.block_hash, header.hash);
+        assert_eq!(block_info.height, header.height);
+        assert!(block_info.transactions.is_empty());
+    }
+
+    /// Check that, with block filters enabled, a block whose only sBTC
+    /// related transaction is a deposit is still fetched in full. The
+    /// deposit is either one that we are loading from Emily, or one that
+    /// is already in the database.
+    #[test_case::test_case(true; "new deposit")]
+    #[test_case::test_case(false; "stored deposit")]
+    #[tokio::test]
+    async fn blocks_with_only_a_deposit_are_fetched(new_deposit: bool) {
+        let mut rng = get_rng();
+        let storage = storage::memory::Store::new_shared();
+        let ctx = TestContext::builder()
+            .with_storage(storage.clone())
+            .with_mocked_clients()
+            .modify_settings(|settings| settings.bitcoin.use_block_filters = true)
+            .build();
+        let block_observer = BlockObserver {
+            context: ctx.clone(),
+            bitcoin_block_source: (),
+        };
+
+        let setup = sbtc::testing::deposits::tx_setup(300, 2_000, &[100_000]);
+        let deposit_script_pubkey = setup.tx.output[0].script_pubkey.clone();
+
+        // The stored deposit request only has the hash of the reclaim
+        // script, but we can still work out its scriptPubKey.
+        let chain_tip: model::BitcoinBlock = fake::Faker.fake_with_rng(&mut rng);
+        let request = model::DepositRequest {
+            txid: setup.tx.compute_txid().into(),
+            output_index: 0,
+            spend_script: setup.deposits[0].deposit_script().to_bytes(),
+            reclaim_script_hash: (&setup.reclaims[0].reclaim_script()).into(),
+            ..fake::Faker.fake_with_rng(&mut rng)
+        };
+        assert_eq!(request.script_pubkey(), deposit_script_pubkey);
+
+        storage.write_bitcoin_block(&chain_tip).await.unwrap();
+        let new_deposits = if new_deposit {
+            vec![deposit_script_pubkey.clone()]
+        } else {
+            let tx = model::BitcoinTxRef {
+                txid: request.txid,
+                block_hash: chain_tip.block_hash,
+            };
+            storage.write_bitcoin_transactions(vec![tx]).await.unwrap();
+            storage.write_deposit_requests(vec![request]).await.unwrap();
+            Vec::new()
+        };
+
+        let script_pubkeys = block_observer
+            .block_filter_script_pubkeys(&new_deposits)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(script_pubkeys.contains(&deposit_script_pubkey));
+
+        let (block, filter) = block_with_filter(deposit_script_pubkey);
+        let header = BitcoinBlockHeader {
+            hash: block.block_hash(),
+            height: 100u64.into(),
+            time: 0,
+            previous_block_hash: block.header.prev_blockhash,
+            version: 1,
+            merkle_root: block.header.merkle_root,
+            bits: block.header.bits,
+            nonce: 0,
+        };
+        let full_block = BitcoinBlockInfo::without_transactions(&header);
+
+        let mut client = crate::bitcoin::MockBitcoinInteract::new();
+        client
+            .expect_get_block_filter()
+            .once()
+            .returning(move |_| Box::pin(std::future::ready(Ok(Some(filter.clone())))));
+        client
+            .expect_get_block()
+            .once()
+            .returning(move |_| Box::pin(std::future::ready(Ok(Some(full_block.clone())))));
+
+        fetch_bitcoin_block(&client, &header, Some(&script_pubkeys))
+            .await
+            .unwrap();
+    }
+
+    /// Check that header verification rejects headers that do not build
+    /// on the block below them in the database.
+    #[tokio::test]
+    async fn header_chain_must_build_on_a_stored_block() {
+        let mut rng = get_rng();
+        let ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .build();
+        ctx.state().set_sbtc_bitcoin_start_height(100u64.into());
+
+        let parent = model::BitcoinBlock {
+            block_height: 150u64.into(),
+            ..fake::Faker.fake_with_rng(&mut rng)
+        };
+        ctx.get_storage_mut()
+            .write_bitcoin_block(&parent)
+            .await
+            .unwrap();
+
+        let block_observer = BlockObserver {
+            context: ctx,
+            bitcoin_block_source: (),
+        };
+
+        let header = |previous_block_hash: BlockHash, height: u64| BitcoinBlockHeader {
+            hash: BlockHash::all_zeros(),
+            height: height.into(),
+            time: 1_700_000_000,
+            previous_block_hash,
+            version: 0,
+            merkle_root: bitcoin::TxMerkleNode::all_zeros(),
+            bits: bitcoin::CompactTarget::from_consensus(0),
+            nonce: 0,
+        };
+
+        let unknown_parent = header(BlockHash::all_zeros(), 151);
+        let error = block_observer
+            .verify_header_chain(&[unknown_parent])
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            Error::BitcoinHeaderVerification(HeaderVerificationError::UnknownParent(_), _)
+        ));
+
+        let wrong_height = header(parent.block_hash.into(), 152);
+        let error = block_observer
+            .verify_header_chain(&[wrong_height])
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            Error::BitcoinHeaderVerification(HeaderVerificationError::HeightMismatch(_), _)
+        ));
+    }
 }
@@ -8,8 +8,14 @@
 use blocklist_api::apis::Error as ClientError;
 use blocklist_api::apis::address_api::{CheckAddressError, check_address};
 use blocklist_api::apis::configuration::Configuration;
+use lru::LruCache;
 use std::future::Future;
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
 use std::time::Duration;
+use std::time::Instant;
 
 use crate::config::BlocklistClientConfig;
 use crate::error::Error;
@@ -27,13 +33,33 @@ pub trait BlocklistChecker {
     /// Checks if the given address is blocklisted.
     /// Returns `true` if the address is blocklisted, otherwise `false`.
     fn can_accept(&self, address: &str) -> impl Future<Output = Result<bool, Error>> + Send;
+
+    /// Checks if the given withdrawal recipient address is blocklisted.
+    /// Implementations may cache the outcome, since the same recipient is
+    /// often screened again while the withdrawal request is pending.
+    fn can_accept_withdrawal_recipient(
+        &self,
+        address: &str,
+    ) -> impl Future<Output = Result<bool, Error>> + Send {
+        self.can_accept(address)
+    }
 }
 
+/// The maximum number of withdrawal recipient addresses whose screening
+/// outcome is cached by the [`BlocklistClient`].
+const RECIPIENT_CACHE_CAPACITY: NonZeroUsize =
+    NonZeroUsize::new(10_000).expect("10000 is non zero");
+
 /// A client for interacting with the blocklist service.
 #[derive(Clone, Debug)]
 pub struct BlocklistClient {
     config: Configuration,
     retry_delay: Duration,
+    /// How long the outcome of screening a withdrawal recipient is cached.
+    recipient_cache_ttl: Duration,
+    /// The outcome of screening withdrawal recipient addresses, along with
+    /// when the address was screened.
+    recipient_cache: Arc<Mutex<LruCache<String, (bool, Instant)>>>,
 }
 
 impl BlocklistChecker for BlocklistClient {
@@ -47,6 +73,23 @@ impl BlocklistChecker for BlocklistClient {
             response
         }
     }
+
+    async fn can_accept_withdrawal_recipient(&self, address: &str) -> Result<bool, Error> {
+        if let Some(can_accept) = self.cached_recipient_outcome(address) {
+            return Ok(can_accept);
+        }
+
+        let can_accept = self.can_accept(address).await?;
+        if !self.recipient_cache_ttl.is_zero() {
+            let mut cache = self
+                .recipient_cache
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            cache.put(address.to_string(), (can_accept, Instant::now()));
+        }
+
+        Ok(can_accept)
+    }
 }
 
 impl BlocklistClient {
@@ -68,6 +111,8 @@ impl BlocklistClient {
         BlocklistClient {
             config,
             retry_delay: client_config.retry_delay,
+            recipient_cache_ttl: client_config.recipient_cache_ttl,
+            recipient_cache: Arc::new(Mutex::new(LruCache::new(RECIPIENT_CACHE_CAPACITY))),
         }
     }
 
@@ -82,6 +127,27 @@ impl BlocklistClient {
         BlocklistClient {
             config,
             retry_delay: Duration::ZERO,
+            recipient_cache_ttl: Duration::from_secs(600),
+            recipient_cache: Arc::new(Mutex::new(LruCache::new(RECIPIENT_CACHE_CAPACITY))),
+        }
+    }
+
+    /// Return the cached outcome of screening the given withdrawal
+    /// recipient address, if it has not expired.
+    fn cached_recipient_outcome(&self, address: &str) -> Option<bool> {
+        let mut cache = self
+            .recipient_cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        match cache.get(address).copied() {
+            Some((can_accept, screened_at)) if screened_at.elapsed() < self.recipient_cache_ttl => {
+                Some(can_accept)
+            }
+            Some(_) => {
+                cache.pop(address);
+                None
+            }
+            None => None,
         }
     }
 
@@ -176,6 +242,35 @@ mod tests {
         mock.assert_async().await;
     }
 
+    #[tokio::test]
+    async fn withdrawal_recipient_screening_is_cached() {
+        let ctx = setup().await;
+        let mut guard = ctx.server_guard.lock().await;
+        let mock_json = json!({
+            "is_blocklisted": true,
+            "severity": "Severe",
+            "accept": false,
+            "reason": "Fraud"
+        })
+        .to_string();
+
+        let mock = guard
+            .mock("GET", format!("{SCREEN_PATH}/{ADDRESS}").as_str())
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(&mock_json)
+            .expect(1)
+            .create_async()
+            .await;
+
+        for _ in 0..2 {
+            let can_accept = ctx.client.can_accept_withdrawal_recipient(ADDRESS).await;
+            assert!(!can_accept.unwrap());
+        }
+
+        mock.assert_async().await;
+    }
+
     #[tokio::test]
     async fn test_check_address_http_error() {
         let ctx = setup().await;
@@ -200,6 +295,10 @@ mod tests {
         let client = BlocklistClient::new(&BlocklistClientConfig {
             endpoint,
             retry_delay: Duration::ZERO,
+            screen_withdrawal_recipients: true,
+            recipient_cache_ttl: Duration::ZERO,
+            auto_approve_below: None,
+            full_screening_above: None,
         });
 
         assert_eq!(client.config.base_path, "http://localhost:8080");
@@ -212,6 +311,10 @@ mod tests {
         let client = BlocklistClient::new(&BlocklistClientConfig {
             endpoint,
             retry_delay: Duration::ZERO,
+            screen_withdrawal_recipients: true,
+            recipient_cache_ttl: Duration::ZERO,
+            auto_approve_below: None,
+            full_screening_above: None,
         });
 
         assert_eq!(client.config.base_path, "http://localhost:8080");
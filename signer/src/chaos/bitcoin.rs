@@ -0,0 +1,135 @@
+//! Chaos implementation of the bitcoin client trait.
+
+use bitcoin::BlockHash;
+use bitcoin::OutPoint;
+use bitcoin::Txid;
+use bitcoin::bip158::BlockFilter;
+use bitcoincore_rpc_json::GetMempoolEntryResult;
+use bitcoincore_rpc_json::GetTxOutResult;
+
+use crate::bitcoin::BitcoinInteract;
+use crate::bitcoin::GetTransactionFeeResult;
+use crate::bitcoin::rpc::BitcoinBlockHeader;
+use crate::bitcoin::rpc::BitcoinBlockInfo;
+use crate::bitcoin::rpc::BitcoinTxInfo;
+use crate::bitcoin::rpc::GetTxResponse;
+use crate::bitcoin::rpc::OutPointSummary;
+use crate::error::Error;
+
+use super::Chaos;
+
+impl<C> BitcoinInteract for Chaos<C>
+where
+    C: BitcoinInteract,
+{
+    async fn get_block(&self, block_hash: &BlockHash) -> Result<Option<BitcoinBlockInfo>, Error> {
+        self.schedule.inject("get_block").await?;
+        self.inner.get_block(block_hash).await
+    }
+
+    async fn get_block_header(
+        &self,
+        block_hash: &BlockHash,
+    ) -> Result<Option<BitcoinBlockHeader>, Error> {
+        self.schedule.inject("get_block_header").await?;
+        self.inner.get_block_header(block_hash).await
+    }
+
+    async fn get_block_filter(&self, block_hash: &BlockHash) -> Result<Option<BlockFilter>, Error> {
+        self.schedule.inject("get_block_filter").await?;
+        self.inner.get_block_filter(block_hash).await
+    }
+
+    async fn get_tx(&self, txid: &Txid) -> Result<Option<GetTxResponse>, Error> {
+        self.schedule.inject("get_tx").await?;
+        self.inner.get_tx(txid).await
+    }
+
+    async fn get_utxo_info(&self, outpoint: &OutPoint) -> Result<Option<OutPointSummary>, Error> {
+        self.schedule.inject("get_utxo_info").await?;
+        self.inner.get_utxo_info(outpoint).await
+    }
+
+    async fn get_tx_info(
+        &self,
+        txid: &Txid,
+        block_hash: &BlockHash,
+    ) -> Result<Option<BitcoinTxInfo>, Error> {
+        self.schedule.inject("get_tx_info").await?;
+        self.inner.get_tx_info(txid, block_hash).await
+    }
+
+    async fn estimate_fee_rate(&self, num_blocks: u16) -> Result<f64, Error> {
+        self.schedule.inject("estimate_fee_rate").await?;
+        self.inner.estimate_fee_rate(num_blocks).await
+    }
+
+    async fn broadcast_transaction(&self, tx: &bitcoin::Transaction) -> Result<(), Error> {
+        self.schedule.inject("broadcast_transaction").await?;
+        self.inner.broadcast_transaction(tx).await
+    }
+
+    async fn find_mempool_transactions_spending_output(
+        &self,
+        outpoint: &bitcoin::OutPoint,
+    ) -> Result<Vec<Txid>, Error> {
+        self.schedule
+            .inject("find_mempool_transactions_spending_output")
+            .await?;
+        self.inner
+            .find_mempool_transactions_spending_output(outpoint)
+            .await
+    }
+
+    async fn find_mempool_descendants(&self, txid: &Txid) -> Result<Vec<Txid>, Error> {
+        self.schedule.inject("find_mempool_descendants").await?;
+        self.inner.find_mempool_descendants(txid).await
+    }
+
+    async fn get_transaction_output(
+        &self,
+        outpoint: &bitcoin::OutPoint,
+        include_mempool: bool,
+    ) -> Result<Option<GetTxOutResult>, Error> {
+        self.schedule.inject("get_transaction_output").await?;
+        self.inner
+            .get_transaction_output(outpoint, include_mempool)
+            .await
+    }
+
+    async fn get_transaction_fee(&self, txid: &Txid) -> Result<GetTransactionFeeResult, Error> {
+        self.schedule.inject("get_transaction_fee").await?;
+        self.inner.get_transaction_fee(txid).await
+    }
+
+    async fn get_mempool_entry(&self, txid: &Txid) -> Result<Option<GetMempoolEntryResult>, Error> {
+        self.schedule.inject("get_mempool_entry").await?;
+        self.inner.get_mempool_entry(txid).await
+    }
+
+    async fn get_tx_out_proof(
+        &self,
+        txid: &Txid,
+        block_hash: &BlockHash,
+    ) -> Result<bitcoin::MerkleBlock, Error> {
+        self.schedule.inject("get_tx_out_proof").await?;
+        self.inner.get_tx_out_proof(txid, block_hash).await
+    }
+
+    async fn get_blockchain_info(
+        &self,
+    ) -> Result<bitcoincore_rpc_json::GetBlockchainInfoResult, Error> {
+        self.schedule.inject("get_blockchain_info").await?;
+        self.inner.get_blockchain_info().await
+    }
+
+    async fn get_network_info(&self) -> Result<bitcoincore_rpc_json::GetNetworkInfoResult, Error> {
+        self.schedule.inject("get_network_info").await?;
+        self.inner.get_network_info().await
+    }
+
+    async fn get_best_block_hash(&self) -> Result<BlockHash, Error> {
+        self.schedule.inject("get_best_block_hash").await?;
+        self.inner.get_best_block_hash().await
+    }
+}
@@ -0,0 +1,272 @@
+//! # Chaos layer
+//!
+//! This module contains a wrapper, [`Chaos`], that can be put around the
+//! signer's storage, bitcoin client or stacks client to inject latency,
+//! timeouts and intermittent errors into every call made through it. The
+//! faults are drawn from a [`ChaosSchedule`] that is seeded up front, so a
+//! soak test that fails can be replayed with the same sequence of faults.
+//!
+//! The layer is only compiled in when the `chaos` feature is enabled.
+
+mod bitcoin;
+mod stacks;
+mod storage;
+
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use rand::Rng as _;
+use rand::SeedableRng as _;
+use rand_chacha::ChaCha20Rng;
+
+use crate::error::Error;
+
+/// Configuration for the faults injected by a [`ChaosSchedule`]. The
+/// probabilities are evaluated independently for every call, and the
+/// default configuration injects no faults at all.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChaosConfig {
+    /// The seed for the random number generator that decides which faults
+    /// to inject.
+    pub seed: u64,
+    /// The probability that a call fails immediately with an error.
+    pub error_probability: f64,
+    /// The probability that a call hangs for [`ChaosConfig::timeout`] and
+    /// then fails.
+    pub timeout_probability: f64,
+    /// How long a call hangs before failing when a timeout is injected.
+    pub timeout: Duration,
+    /// The probability that a call is delayed by a random amount of time,
+    /// up to [`ChaosConfig::max_latency`], before being made.
+    pub latency_probability: f64,
+    /// The maximum latency added to a delayed call.
+    pub max_latency: Duration,
+}
+
+impl Default for ChaosConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            error_probability: 0.0,
+            timeout_probability: 0.0,
+            timeout: Duration::from_secs(30),
+            latency_probability: 0.0,
+            max_latency: Duration::from_millis(500),
+        }
+    }
+}
+
+/// A fault injected into a single call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    /// The call is delayed by the given duration.
+    Latency(Duration),
+    /// The call hangs for the given duration and then fails.
+    Timeout(Duration),
+    /// The call fails immediately.
+    Error,
+}
+
+/// Decides which fault, if any, to inject into each call.
+///
+/// Clones share the same random number generator, so all components
+/// wrapped with clones of one schedule draw from a single sequence of
+/// faults.
+#[derive(Debug, Clone)]
+pub struct ChaosSchedule {
+    config: ChaosConfig,
+    rng: Arc<Mutex<ChaCha20Rng>>,
+    paused: Arc<AtomicBool>,
+}
+
+impl ChaosSchedule {
+    /// Create a new schedule with the given configuration.
+    pub fn new(config: ChaosConfig) -> Self {
+        Self {
+            rng: Arc::new(Mutex::new(ChaCha20Rng::seed_from_u64(config.seed))),
+            config,
+            paused: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// The configuration of this schedule.
+    pub fn config(&self) -> &ChaosConfig {
+        &self.config
+    }
+
+    /// Stop injecting faults until [`ChaosSchedule::resume`] is called.
+    /// Calls made while paused do not advance the schedule.
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::SeqCst);
+    }
+
+    /// Resume injecting faults after a call to [`ChaosSchedule::pause`].
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::SeqCst);
+    }
+
+    /// Draw the fault to inject into the next call.
+    pub fn next_fault(&self) -> Option<Fault> {
+        if self.paused.load(Ordering::SeqCst) {
+            return None;
+        }
+
+        let config = &self.config;
+        let mut rng = self.rng.lock().expect("chaos rng lock poisoned");
+        if rng.gen_bool(config.error_probability.clamp(0.0, 1.0)) {
+            return Some(Fault::Error);
+        }
+        if rng.gen_bool(config.timeout_probability.clamp(0.0, 1.0)) {
+            return Some(Fault::Timeout(config.timeout));
+        }
+        if rng.gen_bool(config.latency_probability.clamp(0.0, 1.0)) {
+            let max_millis = u64::try_from(config.max_latency.as_millis()).unwrap_or(u64::MAX);
+            let latency = Duration::from_millis(rng.gen_range(0..=max_millis));
+            return Some(Fault::Latency(latency));
+        }
+        None
+    }
+
+    /// Inject the next fault into the given operation, sleeping and
+    /// returning an error as dictated by the schedule.
+    pub async fn inject(&self, operation: &'static str) -> Result<(), Error> {
+        match self.next_fault() {
+            None => Ok(()),
+            Some(Fault::Latency(latency)) => {
+                tracing::debug!(%operation, ?latency, "chaos: delaying call");
+                tokio::time::sleep(latency).await;
+                Ok(())
+            }
+            Some(Fault::Timeout(timeout)) => {
+                tracing::debug!(%operation, ?timeout, "chaos: timing out call");
+                tokio::time::sleep(timeout).await;
+                Err(Error::ChaosInjectedTimeout(operation, timeout))
+            }
+            Some(Fault::Error) => {
+                tracing::debug!(%operation, "chaos: failing call");
+                Err(Error::ChaosInjectedError(operation))
+            }
+        }
+    }
+}
+
+/// Wraps a storage backend, bitcoin client or stacks client and injects
+/// faults from a [`ChaosSchedule`] before delegating every call to it.
+#[derive(Debug, Clone)]
+pub struct Chaos<T> {
+    inner: T,
+    schedule: ChaosSchedule,
+}
+
+impl<T> Chaos<T> {
+    /// Wrap the given component, injecting faults from the given schedule.
+    pub fn new(inner: T, schedule: ChaosSchedule) -> Self {
+        Self { inner, schedule }
+    }
+
+    /// The wrapped component.
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// The schedule that faults are drawn from.
+    pub fn schedule(&self) -> &ChaosSchedule {
+        &self.schedule
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use crate::storage::DbRead as _;
+    use crate::storage::memory::Store;
+
+    use super::*;
+
+    fn faults(schedule: &ChaosSchedule, n: usize) -> Vec<Option<Fault>> {
+        std::iter::repeat_with(|| schedule.next_fault())
+            .take(n)
+            .collect()
+    }
+
+    #[test]
+    fn default_schedule_injects_no_faults() {
+        let schedule = ChaosSchedule::new(ChaosConfig::default());
+        assert!(faults(&schedule, 1000).iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn schedule_is_reproducible_from_seed() {
+        let config = ChaosConfig {
+            seed: 7,
+            error_probability: 0.1,
+            timeout_probability: 0.1,
+            latency_probability: 0.3,
+            ..Default::default()
+        };
+        let faults1 = faults(&ChaosSchedule::new(config), 200);
+        let faults2 = faults(&ChaosSchedule::new(config), 200);
+        assert_eq!(faults1, faults2);
+
+        assert!(faults1.contains(&Some(Fault::Error)));
+        assert!(faults1.contains(&Some(Fault::Timeout(config.timeout))));
+        assert!(faults1.iter().any(|fault| match fault {
+            Some(Fault::Latency(latency)) => *latency <= config.max_latency,
+            _ => false,
+        }));
+    }
+
+    #[test]
+    fn paused_schedule_injects_no_faults() {
+        let config = ChaosConfig {
+            error_probability: 1.0,
+            ..Default::default()
+        };
+        let schedule = ChaosSchedule::new(config);
+        schedule.pause();
+        assert_eq!(schedule.next_fault(), None);
+        schedule.resume();
+        assert_eq!(schedule.next_fault(), Some(Fault::Error));
+    }
+
+    #[tokio::test]
+    async fn chaos_store_injects_errors() {
+        let config = ChaosConfig {
+            error_probability: 1.0,
+            ..Default::default()
+        };
+        let store = Chaos::new(Store::new_shared(), ChaosSchedule::new(config));
+
+        let result = store.get_bitcoin_canonical_chain_tip().await;
+        assert!(matches!(
+            result,
+            Err(Error::ChaosInjectedError("get_bitcoin_canonical_chain_tip"))
+        ));
+
+        store.schedule().pause();
+        assert!(
+            store
+                .get_bitcoin_canonical_chain_tip()
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn chaos_store_injects_timeouts() {
+        let config = ChaosConfig {
+            timeout_probability: 1.0,
+            timeout: Duration::from_millis(50),
+            ..Default::default()
+        };
+        let store = Chaos::new(Store::new_shared(), ChaosSchedule::new(config));
+
+        let start = std::time::Instant::now();
+        let result = store.get_bitcoin_canonical_chain_tip().await;
+        assert!(matches!(result, Err(Error::ChaosInjectedTimeout(_, _))));
+        assert!(start.elapsed() >= config.timeout);
+    }
+}
@@ -0,0 +1,166 @@
+//! Chaos implementation of the stacks client trait.
+
+use bitcoin::Amount;
+use bitcoin::OutPoint;
+use blockstack_lib::chainstate::nakamoto::NakamotoBlock;
+use blockstack_lib::chainstate::stacks::StacksTransaction;
+use blockstack_lib::net::api::getcontractsrc::ContractSrcResponse;
+use blockstack_lib::net::api::getsortition::SortitionInfo;
+use blockstack_lib::types::chainstate::StacksAddress;
+
+use crate::error::Error;
+use crate::keys::PublicKey;
+use crate::stacks::api::AccountInfo;
+use crate::stacks::api::FeePriority;
+use crate::stacks::api::GetNodeInfoResponse;
+use crate::stacks::api::GetTenureInfoResponse;
+use crate::stacks::api::RegistryWithdrawalRequest;
+use crate::stacks::api::SignerSetInfo;
+use crate::stacks::api::StacksEpochStatus;
+use crate::stacks::api::StacksInteract;
+use crate::stacks::api::SubmitTxResponse;
+use crate::stacks::api::TenureBlockHeaders;
+use crate::stacks::contracts::AsTxPayload;
+use crate::stacks::wallet::SignerWallet;
+use crate::storage::model::ConsensusHash;
+use crate::storage::model::StacksBlockHash;
+
+use super::Chaos;
+
+impl<C> StacksInteract for Chaos<C>
+where
+    C: StacksInteract,
+{
+    async fn get_current_signer_set_info(
+        &self,
+        contract_principal: &StacksAddress,
+    ) -> Result<Option<SignerSetInfo>, Error> {
+        self.schedule.inject("get_current_signer_set_info").await?;
+        self.inner
+            .get_current_signer_set_info(contract_principal)
+            .await
+    }
+
+    async fn get_current_signers_aggregate_key(
+        &self,
+        contract_principal: &StacksAddress,
+    ) -> Result<Option<PublicKey>, Error> {
+        self.schedule
+            .inject("get_current_signers_aggregate_key")
+            .await?;
+        self.inner
+            .get_current_signers_aggregate_key(contract_principal)
+            .await
+    }
+
+    async fn is_deposit_completed(
+        &self,
+        contract_principal: &StacksAddress,
+        outpoint: &OutPoint,
+    ) -> Result<bool, Error> {
+        self.schedule.inject("is_deposit_completed").await?;
+        self.inner
+            .is_deposit_completed(contract_principal, outpoint)
+            .await
+    }
+
+    async fn is_withdrawal_completed(
+        &self,
+        contract_principal: &StacksAddress,
+        request_id: u64,
+    ) -> Result<bool, Error> {
+        self.schedule.inject("is_withdrawal_completed").await?;
+        self.inner
+            .is_withdrawal_completed(contract_principal, request_id)
+            .await
+    }
+
+    async fn get_withdrawal_request(
+        &self,
+        contract_principal: &StacksAddress,
+        request_id: u64,
+    ) -> Result<Option<RegistryWithdrawalRequest>, Error> {
+        self.schedule.inject("get_withdrawal_request").await?;
+        self.inner
+            .get_withdrawal_request(contract_principal, request_id)
+            .await
+    }
+
+    async fn get_account(&self, address: &StacksAddress) -> Result<AccountInfo, Error> {
+        self.schedule.inject("get_account").await?;
+        self.inner.get_account(address).await
+    }
+
+    async fn submit_tx(&self, tx: &StacksTransaction) -> Result<SubmitTxResponse, Error> {
+        self.schedule.inject("submit_tx").await?;
+        self.inner.submit_tx(tx).await
+    }
+
+    async fn get_block(&self, block_id: &StacksBlockHash) -> Result<NakamotoBlock, Error> {
+        self.schedule.inject("get_block").await?;
+        self.inner.get_block(block_id).await
+    }
+
+    async fn get_tenure_headers(
+        &self,
+        consensus_hash: &ConsensusHash,
+    ) -> Result<TenureBlockHeaders, Error> {
+        self.schedule.inject("get_tenure_headers").await?;
+        self.inner.get_tenure_headers(consensus_hash).await
+    }
+
+    async fn get_tenure_info(&self) -> Result<GetTenureInfoResponse, Error> {
+        self.schedule.inject("get_tenure_info").await?;
+        self.inner.get_tenure_info().await
+    }
+
+    async fn get_sortition_info(
+        &self,
+        consensus_hash: &ConsensusHash,
+    ) -> Result<SortitionInfo, Error> {
+        self.schedule.inject("get_sortition_info").await?;
+        self.inner.get_sortition_info(consensus_hash).await
+    }
+
+    async fn estimate_fees<T>(
+        &self,
+        wallet: &SignerWallet,
+        payload: &T,
+        priority: FeePriority,
+    ) -> Result<u64, Error>
+    where
+        T: AsTxPayload + Send + Sync,
+    {
+        self.schedule.inject("estimate_fees").await?;
+        self.inner.estimate_fees(wallet, payload, priority).await
+    }
+
+    async fn get_epoch_status(&self) -> Result<StacksEpochStatus, Error> {
+        self.schedule.inject("get_epoch_status").await?;
+        self.inner.get_epoch_status().await
+    }
+
+    async fn get_node_info(&self) -> Result<GetNodeInfoResponse, Error> {
+        self.schedule.inject("get_node_info").await?;
+        self.inner.get_node_info().await
+    }
+
+    async fn get_contract_source(
+        &self,
+        address: &StacksAddress,
+        contract_name: &str,
+    ) -> Result<ContractSrcResponse, Error> {
+        self.schedule.inject("get_contract_source").await?;
+        self.inner.get_contract_source(address, contract_name).await
+    }
+
+    async fn get_sbtc_total_supply(&self, sender: &StacksAddress) -> Result<Amount, Error> {
+        self.schedule.inject("get_sbtc_total_supply").await?;
+        self.inner.get_sbtc_total_supply(sender).await
+    }
+
+    async fn is_sbtc_contract_paused(&self, deployer: &StacksAddress) -> Result<bool, Error> {
+        self.schedule.inject("is_sbtc_contract_paused").await?;
+        self.inner.is_sbtc_contract_paused(deployer).await
+    }
+}
@@ -0,0 +1,1422 @@
+//! Chaos implementations of the storage traits.
+
+use std::collections::BTreeSet;
+
+use libp2p::Multiaddr;
+use libp2p::PeerId;
+
+use crate::bitcoin::utxo::SignerUtxo;
+use crate::bitcoin::validation::DepositRequestReport;
+use crate::bitcoin::validation::WithdrawalRequestReport;
+use crate::config::DepositConfirmationPolicy;
+use crate::error::Error;
+use crate::keys::PublicKey;
+use crate::keys::PublicKeyXOnly;
+use crate::stacks::api::TenureBlockHeaders;
+use crate::storage::DbRead;
+use crate::storage::DbWrite;
+use crate::storage::Transactable;
+use crate::storage::model;
+use crate::storage::model::BitcoinBlockHeight;
+use crate::storage::model::CompletedDepositEvent;
+use crate::storage::model::StacksBlockHash;
+use crate::storage::model::WithdrawalAcceptEvent;
+use crate::storage::model::WithdrawalRejectEvent;
+
+use super::Chaos;
+
+/// Transactions are handed out from the wrapped store as-is; faults are
+/// only injected when beginning the transaction.
+impl<S> Transactable for Chaos<S>
+where
+    S: Transactable + Sync,
+{
+    type Tx<'a>
+        = S::Tx<'a>
+    where
+        Self: 'a;
+
+    async fn begin_transaction(&self) -> Result<Self::Tx<'_>, Error> {
+        self.schedule.inject("begin_transaction").await?;
+        self.inner.begin_transaction().await
+    }
+}
+
+impl<S> DbRead for Chaos<S>
+where
+    S: DbRead + Sync,
+{
+    async fn get_bitcoin_block(
+        &self,
+        block_hash: &model::BitcoinBlockHash,
+    ) -> Result<Option<model::BitcoinBlock>, Error> {
+        self.schedule.inject("get_bitcoin_block").await?;
+        self.inner.get_bitcoin_block(block_hash).await
+    }
+
+    async fn get_stacks_block(
+        &self,
+        block_hash: &model::StacksBlockHash,
+    ) -> Result<Option<model::StacksBlock>, Error> {
+        self.schedule.inject("get_stacks_block").await?;
+        self.inner.get_stacks_block(block_hash).await
+    }
+
+    #[cfg(any(test, feature = "testing"))]
+    async fn get_bitcoin_canonical_chain_tip(
+        &self,
+    ) -> Result<Option<model::BitcoinBlockHash>, Error> {
+        self.schedule
+            .inject("get_bitcoin_canonical_chain_tip")
+            .await?;
+        self.inner.get_bitcoin_canonical_chain_tip().await
+    }
+
+    #[cfg(any(test, feature = "testing"))]
+    async fn get_bitcoin_canonical_chain_tip_ref(
+        &self,
+    ) -> Result<Option<model::BitcoinBlockRef>, Error> {
+        self.schedule
+            .inject("get_bitcoin_canonical_chain_tip_ref")
+            .await?;
+        self.inner.get_bitcoin_canonical_chain_tip_ref().await
+    }
+
+    async fn get_stacks_chain_tip(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Option<model::StacksBlock>, Error> {
+        self.schedule.inject("get_stacks_chain_tip").await?;
+        self.inner.get_stacks_chain_tip(bitcoin_chain_tip).await
+    }
+
+    async fn get_stacks_chain_tip_v2(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Option<model::StacksChainTip>, Error> {
+        self.schedule.inject("get_stacks_chain_tip_v2").await?;
+        self.inner.get_stacks_chain_tip_v2(bitcoin_chain_tip).await
+    }
+
+    async fn get_unscanned_stacks_blocks(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+    ) -> Result<Vec<model::StacksBlockHash>, Error> {
+        self.schedule.inject("get_unscanned_stacks_blocks").await?;
+        self.inner
+            .get_unscanned_stacks_blocks(chain_tip, context_window)
+            .await
+    }
+
+    async fn get_multisig_stacks_transactions(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        stacks_chain_tip: &model::StacksBlockHash,
+        context_window: u16,
+    ) -> Result<Vec<model::MultisigStacksTransaction>, Error> {
+        self.schedule
+            .inject("get_multisig_stacks_transactions")
+            .await?;
+        self.inner
+            .get_multisig_stacks_transactions(bitcoin_chain_tip, stacks_chain_tip, context_window)
+            .await
+    }
+
+    async fn get_pending_deposit_requests(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+        signer_public_key: &PublicKey,
+    ) -> Result<Vec<model::DepositRequest>, Error> {
+        self.schedule.inject("get_pending_deposit_requests").await?;
+        self.inner
+            .get_pending_deposit_requests(chain_tip, context_window, signer_public_key)
+            .await
+    }
+
+    async fn get_recent_deposit_requests(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+    ) -> Result<Vec<model::DepositRequest>, Error> {
+        self.schedule.inject("get_recent_deposit_requests").await?;
+        self.inner
+            .get_recent_deposit_requests(chain_tip, context_window)
+            .await
+    }
+
+    async fn get_pending_accepted_deposit_requests(
+        &self,
+        chain_tip: &model::BitcoinBlockRef,
+        context_window: u16,
+        signatures_required: u16,
+        confirmation_policy: &DepositConfirmationPolicy,
+    ) -> Result<Vec<model::DepositRequest>, Error> {
+        self.schedule
+            .inject("get_pending_accepted_deposit_requests")
+            .await?;
+        self.inner
+            .get_pending_accepted_deposit_requests(
+                chain_tip,
+                context_window,
+                signatures_required,
+                confirmation_policy,
+            )
+            .await
+    }
+
+    async fn deposit_request_exists(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+    ) -> Result<bool, Error> {
+        self.schedule.inject("deposit_request_exists").await?;
+        self.inner.deposit_request_exists(txid, output_index).await
+    }
+
+    async fn get_deposit_request_report(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+        signer_public_key: &PublicKey,
+    ) -> Result<Option<DepositRequestReport>, Error> {
+        self.schedule.inject("get_deposit_request_report").await?;
+        self.inner
+            .get_deposit_request_report(chain_tip, txid, output_index, signer_public_key)
+            .await
+    }
+
+    async fn get_deposit_signers(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+    ) -> Result<Vec<model::DepositSigner>, Error> {
+        self.schedule.inject("get_deposit_signers").await?;
+        self.inner.get_deposit_signers(txid, output_index).await
+    }
+
+    async fn get_deposit_signer_decisions(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+        signer_public_key: &PublicKey,
+    ) -> Result<Vec<model::DepositSigner>, Error> {
+        self.schedule.inject("get_deposit_signer_decisions").await?;
+        self.inner
+            .get_deposit_signer_decisions(chain_tip, context_window, signer_public_key)
+            .await
+    }
+
+    async fn get_withdrawal_signer_decisions(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+        signer_public_key: &PublicKey,
+    ) -> Result<Vec<model::WithdrawalSigner>, Error> {
+        self.schedule
+            .inject("get_withdrawal_signer_decisions")
+            .await?;
+        self.inner
+            .get_withdrawal_signer_decisions(chain_tip, context_window, signer_public_key)
+            .await
+    }
+
+    async fn can_sign_deposit_tx(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+        signer_public_key: &PublicKey,
+    ) -> Result<Option<bool>, Error> {
+        self.schedule.inject("can_sign_deposit_tx").await?;
+        self.inner
+            .can_sign_deposit_tx(txid, output_index, signer_public_key)
+            .await
+    }
+
+    async fn get_withdrawal_signers(
+        &self,
+        request_id: u64,
+        block_hash: &model::StacksBlockHash,
+    ) -> Result<Vec<model::WithdrawalSigner>, Error> {
+        self.schedule.inject("get_withdrawal_signers").await?;
+        self.inner
+            .get_withdrawal_signers(request_id, block_hash)
+            .await
+    }
+
+    async fn get_pending_withdrawal_requests(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        stacks_chain_tip: &model::StacksBlockHash,
+        context_window: u16,
+        signer_public_key: &PublicKey,
+    ) -> Result<Vec<model::WithdrawalRequest>, Error> {
+        self.schedule
+            .inject("get_pending_withdrawal_requests")
+            .await?;
+        self.inner
+            .get_pending_withdrawal_requests(
+                bitcoin_chain_tip,
+                stacks_chain_tip,
+                context_window,
+                signer_public_key,
+            )
+            .await
+    }
+
+    async fn get_pending_accepted_withdrawal_requests(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        stacks_chain_tip: &model::StacksBlockHash,
+        min_bitcoin_height: BitcoinBlockHeight,
+        signature_threshold: u16,
+    ) -> Result<Vec<model::WithdrawalRequest>, Error> {
+        self.schedule
+            .inject("get_pending_accepted_withdrawal_requests")
+            .await?;
+        self.inner
+            .get_pending_accepted_withdrawal_requests(
+                bitcoin_chain_tip,
+                stacks_chain_tip,
+                min_bitcoin_height,
+                signature_threshold,
+            )
+            .await
+    }
+
+    async fn get_pending_rejected_withdrawal_requests(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockRef,
+        stacks_chain_tip: &model::StacksBlockHash,
+        context_window: u16,
+    ) -> Result<Vec<model::WithdrawalRequest>, Error> {
+        self.schedule
+            .inject("get_pending_rejected_withdrawal_requests")
+            .await?;
+        self.inner
+            .get_pending_rejected_withdrawal_requests(
+                bitcoin_chain_tip,
+                stacks_chain_tip,
+                context_window,
+            )
+            .await
+    }
+
+    async fn get_withdrawal_request_report(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        stacks_chain_tip: &model::StacksBlockHash,
+        id: &model::QualifiedRequestId,
+        signer_public_key: &PublicKey,
+    ) -> Result<Option<WithdrawalRequestReport>, Error> {
+        self.schedule
+            .inject("get_withdrawal_request_report")
+            .await?;
+        self.inner
+            .get_withdrawal_request_report(
+                bitcoin_chain_tip,
+                stacks_chain_tip,
+                id,
+                signer_public_key,
+            )
+            .await
+    }
+
+    async fn compute_withdrawn_total(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+    ) -> Result<u64, Error> {
+        self.schedule.inject("compute_withdrawn_total").await?;
+        self.inner
+            .compute_withdrawn_total(bitcoin_chain_tip, context_window)
+            .await
+    }
+
+    async fn get_caps_utilization(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+        rolling_withdrawal_cap: u64,
+    ) -> Result<model::CapsUtilization, Error> {
+        self.schedule.inject("get_caps_utilization").await?;
+        self.inner
+            .get_caps_utilization(bitcoin_chain_tip, context_window, rolling_withdrawal_cap)
+            .await
+    }
+
+    async fn get_bitcoin_blocks_with_transaction(
+        &self,
+        txid: &model::BitcoinTxId,
+    ) -> Result<Vec<model::BitcoinBlockHash>, Error> {
+        self.schedule
+            .inject("get_bitcoin_blocks_with_transaction")
+            .await?;
+        self.inner.get_bitcoin_blocks_with_transaction(txid).await
+    }
+
+    async fn stacks_block_exists(&self, block_id: &StacksBlockHash) -> Result<bool, Error> {
+        self.schedule.inject("stacks_block_exists").await?;
+        self.inner.stacks_block_exists(block_id).await
+    }
+
+    async fn get_encrypted_dkg_shares<X>(
+        &self,
+        aggregate_key: X,
+    ) -> Result<Option<model::EncryptedDkgShares>, Error>
+    where
+        X: Into<PublicKeyXOnly> + Send,
+    {
+        self.schedule.inject("get_encrypted_dkg_shares").await?;
+        self.inner.get_encrypted_dkg_shares(aggregate_key).await
+    }
+
+    async fn get_latest_encrypted_dkg_shares(
+        &self,
+    ) -> Result<Option<model::EncryptedDkgShares>, Error> {
+        self.schedule
+            .inject("get_latest_encrypted_dkg_shares")
+            .await?;
+        self.inner.get_latest_encrypted_dkg_shares().await
+    }
+
+    async fn get_latest_verified_dkg_shares(
+        &self,
+    ) -> Result<Option<model::EncryptedDkgShares>, Error> {
+        self.schedule
+            .inject("get_latest_verified_dkg_shares")
+            .await?;
+        self.inner.get_latest_verified_dkg_shares().await
+    }
+
+    async fn get_latest_non_failed_dkg_shares(
+        &self,
+    ) -> Result<Option<model::EncryptedDkgShares>, Error> {
+        self.schedule
+            .inject("get_latest_non_failed_dkg_shares")
+            .await?;
+        self.inner.get_latest_non_failed_dkg_shares().await
+    }
+
+    async fn get_encrypted_dkg_shares_count(&self) -> Result<u32, Error> {
+        self.schedule
+            .inject("get_encrypted_dkg_shares_count")
+            .await?;
+        self.inner.get_encrypted_dkg_shares_count().await
+    }
+
+    #[cfg(any(test, feature = "testing"))]
+    async fn get_last_key_rotation(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Option<model::KeyRotationEvent>, Error> {
+        self.schedule.inject("get_last_key_rotation").await?;
+        self.inner.get_last_key_rotation(chain_tip).await
+    }
+
+    async fn key_rotation_exists(
+        &self,
+        stacks_chain_tip: &model::StacksBlockHash,
+        signer_set: &BTreeSet<PublicKey>,
+        aggregate_key: &PublicKey,
+        signatures_required: u16,
+    ) -> Result<bool, Error> {
+        self.schedule.inject("key_rotation_exists").await?;
+        self.inner
+            .key_rotation_exists(
+                stacks_chain_tip,
+                signer_set,
+                aggregate_key,
+                signatures_required,
+            )
+            .await
+    }
+
+    async fn get_signers_script_pubkeys(&self) -> Result<Vec<model::Bytes>, Error> {
+        self.schedule.inject("get_signers_script_pubkeys").await?;
+        self.inner.get_signers_script_pubkeys().await
+    }
+
+    async fn get_signer_utxo(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Option<SignerUtxo>, Error> {
+        self.schedule.inject("get_signer_utxo").await?;
+        self.inner.get_signer_utxo(chain_tip).await
+    }
+
+    async fn get_signer_utxos(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Vec<SignerUtxo>, Error> {
+        self.schedule.inject("get_signer_utxos").await?;
+        self.inner.get_signer_utxos(chain_tip).await
+    }
+
+    async fn get_deposit_request_signer_votes(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+        aggregate_key: &PublicKey,
+    ) -> Result<model::SignerVotes, Error> {
+        self.schedule
+            .inject("get_deposit_request_signer_votes")
+            .await?;
+        self.inner
+            .get_deposit_request_signer_votes(txid, output_index, aggregate_key)
+            .await
+    }
+
+    async fn get_withdrawal_request_signer_votes(
+        &self,
+        id: &model::QualifiedRequestId,
+        aggregate_key: &PublicKey,
+    ) -> Result<model::SignerVotes, Error> {
+        self.schedule
+            .inject("get_withdrawal_request_signer_votes")
+            .await?;
+        self.inner
+            .get_withdrawal_request_signer_votes(id, aggregate_key)
+            .await
+    }
+
+    async fn get_withdrawal_requests_by_id(
+        &self,
+        request_id: u64,
+    ) -> Result<Vec<model::WithdrawalRequest>, Error> {
+        self.schedule
+            .inject("get_withdrawal_requests_by_id")
+            .await?;
+        self.inner.get_withdrawal_requests_by_id(request_id).await
+    }
+
+    async fn is_known_bitcoin_block_hash(
+        &self,
+        block_hash: &model::BitcoinBlockHash,
+    ) -> Result<bool, Error> {
+        self.schedule.inject("is_known_bitcoin_block_hash").await?;
+        self.inner.is_known_bitcoin_block_hash(block_hash).await
+    }
+
+    async fn in_canonical_bitcoin_blockchain(
+        &self,
+        chain_tip: &model::BitcoinBlockRef,
+        block_ref: &model::BitcoinBlockRef,
+    ) -> Result<bool, Error> {
+        self.schedule
+            .inject("in_canonical_bitcoin_blockchain")
+            .await?;
+        self.inner
+            .in_canonical_bitcoin_blockchain(chain_tip, block_ref)
+            .await
+    }
+
+    async fn is_signer_script_pub_key(&self, script: &model::ScriptPubKey) -> Result<bool, Error> {
+        self.schedule.inject("is_signer_script_pub_key").await?;
+        self.inner.is_signer_script_pub_key(script).await
+    }
+
+    async fn is_withdrawal_inflight(
+        &self,
+        id: &model::QualifiedRequestId,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<bool, Error> {
+        self.schedule.inject("is_withdrawal_inflight").await?;
+        self.inner
+            .is_withdrawal_inflight(id, bitcoin_chain_tip)
+            .await
+    }
+
+    async fn is_withdrawal_active(
+        &self,
+        id: &model::QualifiedRequestId,
+        bitcoin_chain_tip: &model::BitcoinBlockRef,
+        min_confirmations: u64,
+    ) -> Result<bool, Error> {
+        self.schedule.inject("is_withdrawal_active").await?;
+        self.inner
+            .is_withdrawal_active(id, bitcoin_chain_tip, min_confirmations)
+            .await
+    }
+
+    async fn get_swept_deposit_requests(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        stacks_chain_tip: &model::StacksBlockHash,
+        context_window: u16,
+    ) -> Result<Vec<model::SweptDepositRequest>, Error> {
+        self.schedule.inject("get_swept_deposit_requests").await?;
+        self.inner
+            .get_swept_deposit_requests(bitcoin_chain_tip, stacks_chain_tip, context_window)
+            .await
+    }
+
+    async fn get_swept_withdrawal_requests(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        stacks_chain_tip: &model::StacksBlockHash,
+        context_window: u16,
+    ) -> Result<Vec<model::SweptWithdrawalRequest>, Error> {
+        self.schedule
+            .inject("get_swept_withdrawal_requests")
+            .await?;
+        self.inner
+            .get_swept_withdrawal_requests(bitcoin_chain_tip, stacks_chain_tip, context_window)
+            .await
+    }
+
+    async fn get_deposit_request(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+    ) -> Result<Option<model::DepositRequest>, Error> {
+        self.schedule.inject("get_deposit_request").await?;
+        self.inner.get_deposit_request(txid, output_index).await
+    }
+
+    async fn will_sign_bitcoin_tx_sighash(
+        &self,
+        sighash: &model::SigHash,
+    ) -> Result<Option<(bool, PublicKeyXOnly)>, Error> {
+        self.schedule.inject("will_sign_bitcoin_tx_sighash").await?;
+        self.inner.will_sign_bitcoin_tx_sighash(sighash).await
+    }
+
+    async fn get_p2p_peers(&self) -> Result<Vec<model::P2PPeer>, Error> {
+        self.schedule.inject("get_p2p_peers").await?;
+        self.inner.get_p2p_peers().await
+    }
+
+    async fn get_sbtc_supply_totals(&self) -> Result<model::SbtcSupplyTotals, Error> {
+        self.schedule.inject("get_sbtc_supply_totals").await?;
+        self.inner.get_sbtc_supply_totals().await
+    }
+
+    async fn get_signer_utxo_violations(
+        &self,
+        limit: u16,
+    ) -> Result<Vec<model::SignerUtxoViolation>, Error> {
+        self.schedule.inject("get_signer_utxo_violations").await?;
+        self.inner.get_signer_utxo_violations(limit).await
+    }
+
+    async fn get_sweep_fee_discrepancies(
+        &self,
+        limit: u16,
+    ) -> Result<Vec<model::SweepFeeDiscrepancy>, Error> {
+        self.schedule.inject("get_sweep_fee_discrepancies").await?;
+        self.inner.get_sweep_fee_discrepancies(limit).await
+    }
+
+    async fn get_key_rotation_attestations(
+        &self,
+        aggregate_key: &PublicKey,
+    ) -> Result<Vec<model::KeyRotationAttestation>, Error> {
+        self.schedule
+            .inject("get_key_rotation_attestations")
+            .await?;
+        self.inner
+            .get_key_rotation_attestations(aggregate_key)
+            .await
+    }
+
+    async fn get_bitcoin_tx_body(
+        &self,
+        txid: &model::BitcoinTxId,
+        block_hash: &model::BitcoinBlockHash,
+    ) -> Result<Option<model::BitcoinTxBody>, Error> {
+        self.schedule.inject("get_bitcoin_tx_body").await?;
+        self.inner.get_bitcoin_tx_body(txid, block_hash).await
+    }
+
+    async fn get_block_activity(
+        &self,
+        block_hash: &model::BitcoinBlockHash,
+    ) -> Result<model::BlockActivitySummary, Error> {
+        self.schedule.inject("get_block_activity").await?;
+        self.inner.get_block_activity(block_hash).await
+    }
+
+    async fn is_emily_imported_deposit(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+    ) -> Result<bool, Error> {
+        self.schedule.inject("is_emily_imported_deposit").await?;
+        self.inner
+            .is_emily_imported_deposit(txid, output_index)
+            .await
+    }
+
+    async fn is_emily_imported_withdrawal(
+        &self,
+        id: &model::QualifiedRequestId,
+    ) -> Result<bool, Error> {
+        self.schedule.inject("is_emily_imported_withdrawal").await?;
+        self.inner.is_emily_imported_withdrawal(id).await
+    }
+
+    async fn get_p2p_peer_versions(&self) -> Result<Vec<model::P2PPeerVersion>, Error> {
+        self.schedule.inject("get_p2p_peer_versions").await?;
+        self.inner.get_p2p_peer_versions().await
+    }
+
+    async fn get_active_peer_bans(&self) -> Result<Vec<model::P2PPeerBan>, Error> {
+        self.schedule.inject("get_active_peer_bans").await?;
+        self.inner.get_active_peer_bans().await
+    }
+
+    async fn get_active_peer_address_records(
+        &self,
+    ) -> Result<Vec<model::P2PPeerAddressRecord>, Error> {
+        self.schedule
+            .inject("get_active_peer_address_records")
+            .await?;
+        self.inner.get_active_peer_address_records().await
+    }
+
+    async fn get_active_request_override(
+        &self,
+        target: &model::OverrideTarget,
+    ) -> Result<Option<model::RequestOverride>, Error> {
+        self.schedule.inject("get_active_request_override").await?;
+        self.inner.get_active_request_override(target).await
+    }
+
+    async fn get_request_overrides(&self) -> Result<Vec<model::RequestOverride>, Error> {
+        self.schedule.inject("get_request_overrides").await?;
+        self.inner.get_request_overrides().await
+    }
+
+    async fn get_inflight_contract_call(
+        &self,
+        target: &model::ContractCallTarget,
+    ) -> Result<Option<model::InflightContractCall>, Error> {
+        self.schedule.inject("get_inflight_contract_call").await?;
+        self.inner.get_inflight_contract_call(target).await
+    }
+
+    async fn get_chain_history(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        depth: u16,
+        start_height: model::BitcoinBlockHeight,
+    ) -> Result<model::ChainHistory, Error> {
+        self.schedule.inject("get_chain_history").await?;
+        self.inner
+            .get_chain_history(chain_tip, depth, start_height)
+            .await
+    }
+
+    async fn get_completed_deposit_event(
+        &self,
+        outpoint: &bitcoin::OutPoint,
+    ) -> Result<Option<model::CompletedDepositEvent>, Error> {
+        self.schedule.inject("get_completed_deposit_event").await?;
+        self.inner.get_completed_deposit_event(outpoint).await
+    }
+
+    async fn get_active_dkg_verification_rounds(
+        &self,
+    ) -> Result<Vec<model::DkgVerificationRound>, Error> {
+        self.schedule
+            .inject("get_active_dkg_verification_rounds")
+            .await?;
+        self.inner.get_active_dkg_verification_rounds().await
+    }
+
+    async fn get_unresolved_stacks_event_dead_letters(
+        &self,
+    ) -> Result<Vec<model::StacksEventDeadLetter>, Error> {
+        self.schedule
+            .inject("get_unresolved_stacks_event_dead_letters")
+            .await?;
+        self.inner.get_unresolved_stacks_event_dead_letters().await
+    }
+
+    async fn get_signed_sweep_transactions(
+        &self,
+    ) -> Result<Vec<model::SignedSweepTransaction>, Error> {
+        self.schedule
+            .inject("get_signed_sweep_transactions")
+            .await?;
+        self.inner.get_signed_sweep_transactions().await
+    }
+
+    async fn get_request_counts(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<model::RequestCounts, Error> {
+        self.schedule.inject("get_request_counts").await?;
+        self.inner.get_request_counts(chain_tip).await
+    }
+
+    async fn get_aggregate_key_history(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Vec<model::AggregateKeyPeriod>, Error> {
+        self.schedule.inject("get_aggregate_key_history").await?;
+        self.inner.get_aggregate_key_history(chain_tip).await
+    }
+
+    async fn get_sweep_package(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Option<model::SweepPackage>, Error> {
+        self.schedule.inject("get_sweep_package").await?;
+        self.inner.get_sweep_package(bitcoin_chain_tip).await
+    }
+
+    async fn get_presigned_withdrawals(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Vec<model::WithdrawalRequest>, Error> {
+        self.schedule.inject("get_presigned_withdrawals").await?;
+        self.inner
+            .get_presigned_withdrawals(bitcoin_chain_tip)
+            .await
+    }
+
+    async fn get_deposit_request_timestamps(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+    ) -> Result<Option<model::RequestTimestamps>, Error> {
+        self.schedule
+            .inject("get_deposit_request_timestamps")
+            .await?;
+        self.inner
+            .get_deposit_request_timestamps(txid, output_index)
+            .await
+    }
+
+    async fn get_withdrawal_request_timestamps(
+        &self,
+        request_id: u64,
+    ) -> Result<Option<model::RequestTimestamps>, Error> {
+        self.schedule
+            .inject("get_withdrawal_request_timestamps")
+            .await?;
+        self.inner
+            .get_withdrawal_request_timestamps(request_id)
+            .await
+    }
+
+    async fn get_deposit_signer_decisions_in_range(
+        &self,
+        signer_public_key: &PublicKey,
+        start_height: model::BitcoinBlockHeight,
+        end_height: model::BitcoinBlockHeight,
+        limit: u16,
+        offset: u32,
+    ) -> Result<Vec<model::DepositSigner>, Error> {
+        self.schedule
+            .inject("get_deposit_signer_decisions_in_range")
+            .await?;
+        self.inner
+            .get_deposit_signer_decisions_in_range(
+                signer_public_key,
+                start_height,
+                end_height,
+                limit,
+                offset,
+            )
+            .await
+    }
+
+    async fn get_withdrawal_signer_decisions_in_range(
+        &self,
+        signer_public_key: &PublicKey,
+        start_height: model::BitcoinBlockHeight,
+        end_height: model::BitcoinBlockHeight,
+        limit: u16,
+        offset: u32,
+    ) -> Result<Vec<model::WithdrawalSigner>, Error> {
+        self.schedule
+            .inject("get_withdrawal_signer_decisions_in_range")
+            .await?;
+        self.inner
+            .get_withdrawal_signer_decisions_in_range(
+                signer_public_key,
+                start_height,
+                end_height,
+                limit,
+                offset,
+            )
+            .await
+    }
+
+    async fn get_bitcoin_backfill_progress(
+        &self,
+    ) -> Result<Option<model::BitcoinBackfillProgress>, Error> {
+        self.schedule
+            .inject("get_bitcoin_backfill_progress")
+            .await?;
+        self.inner.get_bitcoin_backfill_progress().await
+    }
+
+    async fn get_coordinator_takeover(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Option<model::CoordinatorTakeover>, Error> {
+        self.schedule.inject("get_coordinator_takeover").await?;
+        self.inner.get_coordinator_takeover(bitcoin_chain_tip).await
+    }
+
+    async fn get_sweep_tx_merkle_proof(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        txid: &model::BitcoinTxId,
+    ) -> Result<Option<model::BitcoinTxMerkleProof>, Error> {
+        self.schedule.inject("get_sweep_tx_merkle_proof").await?;
+        self.inner.get_sweep_tx_merkle_proof(chain_tip, txid).await
+    }
+
+    async fn get_bitcoin_block_headers(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        start_height: model::BitcoinBlockHeight,
+        limit: u16,
+    ) -> Result<Vec<bitcoin::block::Header>, Error> {
+        self.schedule.inject("get_bitcoin_block_headers").await?;
+        self.inner
+            .get_bitcoin_block_headers(chain_tip, start_height, limit)
+            .await
+    }
+}
+
+impl<S> DbWrite for Chaos<S>
+where
+    S: DbWrite + Sync,
+{
+    async fn write_bitcoin_block(
+        &self,
+        block: &model::BitcoinBlock,
+    ) -> Result<model::WriteOutcome, Error> {
+        self.schedule.inject("write_bitcoin_block").await?;
+        self.inner.write_bitcoin_block(block).await
+    }
+
+    #[cfg(any(test, feature = "testing"))]
+    async fn write_stacks_block(
+        &self,
+        block: &model::StacksBlock,
+    ) -> Result<model::WriteOutcome, Error> {
+        self.schedule.inject("write_stacks_block").await?;
+        self.inner.write_stacks_block(block).await
+    }
+
+    async fn write_deposit_request(
+        &self,
+        deposit_request: &model::DepositRequest,
+    ) -> Result<model::WriteOutcome, Error> {
+        self.schedule.inject("write_deposit_request").await?;
+        self.inner.write_deposit_request(deposit_request).await
+    }
+
+    async fn write_deposit_requests(
+        &self,
+        deposit_requests: Vec<model::DepositRequest>,
+    ) -> Result<(), Error> {
+        self.schedule.inject("write_deposit_requests").await?;
+        self.inner.write_deposit_requests(deposit_requests).await
+    }
+
+    async fn write_withdrawal_request(
+        &self,
+        request: &model::WithdrawalRequest,
+    ) -> Result<model::WriteOutcome, Error> {
+        self.schedule.inject("write_withdrawal_request").await?;
+        self.inner.write_withdrawal_request(request).await
+    }
+
+    async fn write_deposit_signer_decision(
+        &self,
+        decision: &model::DepositSigner,
+    ) -> Result<model::WriteOutcome, Error> {
+        self.schedule
+            .inject("write_deposit_signer_decision")
+            .await?;
+        self.inner.write_deposit_signer_decision(decision).await
+    }
+
+    async fn write_withdrawal_signer_decision(
+        &self,
+        decision: &model::WithdrawalSigner,
+    ) -> Result<model::WriteOutcome, Error> {
+        self.schedule
+            .inject("write_withdrawal_signer_decision")
+            .await?;
+        self.inner.write_withdrawal_signer_decision(decision).await
+    }
+
+    async fn write_bitcoin_transaction(
+        &self,
+        bitcoin_transaction: &model::BitcoinTxRef,
+    ) -> Result<model::WriteOutcome, Error> {
+        self.schedule.inject("write_bitcoin_transaction").await?;
+        self.inner
+            .write_bitcoin_transaction(bitcoin_transaction)
+            .await
+    }
+
+    async fn write_bitcoin_transactions(&self, txs: Vec<model::BitcoinTxRef>) -> Result<(), Error> {
+        self.schedule.inject("write_bitcoin_transactions").await?;
+        self.inner.write_bitcoin_transactions(txs).await
+    }
+
+    async fn write_stacks_block_headers(&self, headers: &TenureBlockHeaders) -> Result<(), Error> {
+        self.schedule.inject("write_stacks_block_headers").await?;
+        self.inner.write_stacks_block_headers(headers).await
+    }
+
+    async fn write_encrypted_dkg_shares(
+        &self,
+        shares: &model::EncryptedDkgShares,
+    ) -> Result<model::WriteOutcome, Error> {
+        self.schedule.inject("write_encrypted_dkg_shares").await?;
+        self.inner.write_encrypted_dkg_shares(shares).await
+    }
+
+    async fn write_rotate_keys_transaction(
+        &self,
+        key_rotation: &model::KeyRotationEvent,
+    ) -> Result<model::WriteOutcome, Error> {
+        self.schedule
+            .inject("write_rotate_keys_transaction")
+            .await?;
+        self.inner.write_rotate_keys_transaction(key_rotation).await
+    }
+
+    async fn write_withdrawal_reject_event(
+        &self,
+        event: &WithdrawalRejectEvent,
+    ) -> Result<model::WriteOutcome, Error> {
+        self.schedule
+            .inject("write_withdrawal_reject_event")
+            .await?;
+        self.inner.write_withdrawal_reject_event(event).await
+    }
+
+    async fn write_withdrawal_accept_event(
+        &self,
+        event: &WithdrawalAcceptEvent,
+    ) -> Result<model::WriteOutcome, Error> {
+        self.schedule
+            .inject("write_withdrawal_accept_event")
+            .await?;
+        self.inner.write_withdrawal_accept_event(event).await
+    }
+
+    async fn write_completed_deposit_event(
+        &self,
+        event: &CompletedDepositEvent,
+    ) -> Result<model::WriteOutcome, Error> {
+        self.schedule
+            .inject("write_completed_deposit_event")
+            .await?;
+        self.inner.write_completed_deposit_event(event).await
+    }
+
+    async fn write_tx_output(
+        &self,
+        output: &model::TxOutput,
+    ) -> Result<model::WriteOutcome, Error> {
+        self.schedule.inject("write_tx_output").await?;
+        self.inner.write_tx_output(output).await
+    }
+
+    async fn write_withdrawal_tx_output(
+        &self,
+        output: &model::WithdrawalTxOutput,
+    ) -> Result<model::WriteOutcome, Error> {
+        self.schedule.inject("write_withdrawal_tx_output").await?;
+        self.inner.write_withdrawal_tx_output(output).await
+    }
+
+    async fn write_tx_prevout(
+        &self,
+        prevout: &model::TxPrevout,
+    ) -> Result<model::WriteOutcome, Error> {
+        self.schedule.inject("write_tx_prevout").await?;
+        self.inner.write_tx_prevout(prevout).await
+    }
+
+    async fn write_bitcoin_txs_sighashes(
+        &self,
+        sighashes: &[model::BitcoinTxSigHash],
+    ) -> Result<(), Error> {
+        self.schedule.inject("write_bitcoin_txs_sighashes").await?;
+        self.inner.write_bitcoin_txs_sighashes(sighashes).await
+    }
+
+    async fn write_bitcoin_withdrawals_outputs(
+        &self,
+        withdrawals_outputs: &[model::BitcoinWithdrawalOutput],
+    ) -> Result<(), Error> {
+        self.schedule
+            .inject("write_bitcoin_withdrawals_outputs")
+            .await?;
+        self.inner
+            .write_bitcoin_withdrawals_outputs(withdrawals_outputs)
+            .await
+    }
+
+    async fn revoke_dkg_shares<X>(&self, aggregate_key: X) -> Result<bool, Error>
+    where
+        X: Into<PublicKeyXOnly> + Send,
+    {
+        self.schedule.inject("revoke_dkg_shares").await?;
+        self.inner.revoke_dkg_shares(aggregate_key).await
+    }
+
+    async fn verify_dkg_shares<X>(&self, aggregate_key: X) -> Result<bool, Error>
+    where
+        X: Into<PublicKeyXOnly> + Send,
+    {
+        self.schedule.inject("verify_dkg_shares").await?;
+        self.inner.verify_dkg_shares(aggregate_key).await
+    }
+
+    async fn update_peer_connection(
+        &self,
+        pub_key: &PublicKey,
+        peer_id: &PeerId,
+        address: Multiaddr,
+    ) -> Result<(), Error> {
+        self.schedule.inject("update_peer_connection").await?;
+        self.inner
+            .update_peer_connection(pub_key, peer_id, address)
+            .await
+    }
+
+    async fn set_canonical_bitcoin_blockchain(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<(), Error> {
+        self.schedule
+            .inject("set_canonical_bitcoin_blockchain")
+            .await?;
+        self.inner.set_canonical_bitcoin_blockchain(chain_tip).await
+    }
+
+    async fn write_sbtc_supply_snapshot(
+        &self,
+        snapshot: &model::SbtcSupplySnapshot,
+    ) -> Result<(), Error> {
+        self.schedule.inject("write_sbtc_supply_snapshot").await?;
+        self.inner.write_sbtc_supply_snapshot(snapshot).await
+    }
+
+    async fn write_signer_utxo_violation(
+        &self,
+        violation: &model::SignerUtxoViolation,
+    ) -> Result<model::WriteOutcome, Error> {
+        self.schedule.inject("write_signer_utxo_violation").await?;
+        self.inner.write_signer_utxo_violation(violation).await
+    }
+
+    async fn write_sweep_fee_discrepancy(
+        &self,
+        discrepancy: &model::SweepFeeDiscrepancy,
+    ) -> Result<model::WriteOutcome, Error> {
+        self.schedule.inject("write_sweep_fee_discrepancy").await?;
+        self.inner.write_sweep_fee_discrepancy(discrepancy).await
+    }
+
+    async fn write_key_rotation_attestation(
+        &self,
+        attestation: &model::KeyRotationAttestation,
+    ) -> Result<model::WriteOutcome, Error> {
+        self.schedule
+            .inject("write_key_rotation_attestation")
+            .await?;
+        self.inner.write_key_rotation_attestation(attestation).await
+    }
+
+    async fn write_bitcoin_tx_bodies(&self, bodies: &[model::BitcoinTxBody]) -> Result<(), Error> {
+        self.schedule.inject("write_bitcoin_tx_bodies").await?;
+        self.inner.write_bitcoin_tx_bodies(bodies).await
+    }
+
+    async fn prune_bitcoin_tx_bodies(
+        &self,
+        min_block_height: model::BitcoinBlockHeight,
+    ) -> Result<u64, Error> {
+        self.schedule.inject("prune_bitcoin_tx_bodies").await?;
+        self.inner.prune_bitcoin_tx_bodies(min_block_height).await
+    }
+
+    async fn write_emily_imported_deposit(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+    ) -> Result<model::WriteOutcome, Error> {
+        self.schedule.inject("write_emily_imported_deposit").await?;
+        self.inner
+            .write_emily_imported_deposit(txid, output_index)
+            .await
+    }
+
+    async fn write_emily_imported_withdrawal(
+        &self,
+        id: &model::QualifiedRequestId,
+    ) -> Result<model::WriteOutcome, Error> {
+        self.schedule
+            .inject("write_emily_imported_withdrawal")
+            .await?;
+        self.inner.write_emily_imported_withdrawal(id).await
+    }
+
+    async fn update_peer_version(
+        &self,
+        peer_id: &PeerId,
+        agent_version: &str,
+        protocol_version: u32,
+    ) -> Result<(), Error> {
+        self.schedule.inject("update_peer_version").await?;
+        self.inner
+            .update_peer_version(peer_id, agent_version, protocol_version)
+            .await
+    }
+
+    async fn write_peer_ban(&self, ban: &model::P2PPeerBan) -> Result<(), Error> {
+        self.schedule.inject("write_peer_ban").await?;
+        self.inner.write_peer_ban(ban).await
+    }
+
+    async fn delete_peer_ban(&self, peer_id: &PeerId) -> Result<(), Error> {
+        self.schedule.inject("delete_peer_ban").await?;
+        self.inner.delete_peer_ban(peer_id).await
+    }
+
+    async fn write_peer_address_record(
+        &self,
+        record: &model::P2PPeerAddressRecord,
+    ) -> Result<bool, Error> {
+        self.schedule.inject("write_peer_address_record").await?;
+        self.inner.write_peer_address_record(record).await
+    }
+
+    async fn write_request_override(
+        &self,
+        request_override: &model::NewRequestOverride,
+    ) -> Result<model::RequestOverride, Error> {
+        self.schedule.inject("write_request_override").await?;
+        self.inner.write_request_override(request_override).await
+    }
+
+    async fn revoke_request_override(&self, id: i64) -> Result<bool, Error> {
+        self.schedule.inject("revoke_request_override").await?;
+        self.inner.revoke_request_override(id).await
+    }
+
+    async fn write_inflight_contract_call(
+        &self,
+        contract_call: &model::InflightContractCall,
+    ) -> Result<(), Error> {
+        self.schedule.inject("write_inflight_contract_call").await?;
+        self.inner.write_inflight_contract_call(contract_call).await
+    }
+
+    async fn delete_inflight_contract_call(
+        &self,
+        target: &model::ContractCallTarget,
+    ) -> Result<bool, Error> {
+        self.schedule
+            .inject("delete_inflight_contract_call")
+            .await?;
+        self.inner.delete_inflight_contract_call(target).await
+    }
+
+    async fn write_withdrawal_output_labels(
+        &self,
+        labels: &[model::WithdrawalOutputLabel],
+    ) -> Result<(), Error> {
+        self.schedule
+            .inject("write_withdrawal_output_labels")
+            .await?;
+        self.inner.write_withdrawal_output_labels(labels).await
+    }
+
+    async fn write_spilled_state_machine(
+        &self,
+        state_machine: &model::SpilledStateMachine,
+    ) -> Result<(), Error> {
+        self.schedule.inject("write_spilled_state_machine").await?;
+        self.inner.write_spilled_state_machine(state_machine).await
+    }
+
+    async fn take_spilled_state_machine(
+        &self,
+        state_machine_id: &str,
+    ) -> Result<Option<model::SpilledStateMachine>, Error> {
+        self.schedule.inject("take_spilled_state_machine").await?;
+        self.inner
+            .take_spilled_state_machine(state_machine_id)
+            .await
+    }
+
+    async fn prune_spilled_state_machines(
+        &self,
+        min_block_height: model::BitcoinBlockHeight,
+    ) -> Result<u64, Error> {
+        self.schedule.inject("prune_spilled_state_machines").await?;
+        self.inner
+            .prune_spilled_state_machines(min_block_height)
+            .await
+    }
+
+    async fn write_dkg_verification_round(
+        &self,
+        round: &model::DkgVerificationRound,
+    ) -> Result<(), Error> {
+        self.schedule.inject("write_dkg_verification_round").await?;
+        self.inner.write_dkg_verification_round(round).await
+    }
+
+    async fn write_stacks_event_dead_letter(
+        &self,
+        letter: &model::StacksEventDeadLetter,
+    ) -> Result<(), Error> {
+        self.schedule
+            .inject("write_stacks_event_dead_letter")
+            .await?;
+        self.inner.write_stacks_event_dead_letter(letter).await
+    }
+
+    async fn resolve_stacks_event_dead_letter(
+        &self,
+        block_hash: &model::StacksBlockHash,
+        txid: &model::StacksTxId,
+        event_index: u32,
+    ) -> Result<(), Error> {
+        self.schedule
+            .inject("resolve_stacks_event_dead_letter")
+            .await?;
+        self.inner
+            .resolve_stacks_event_dead_letter(block_hash, txid, event_index)
+            .await
+    }
+
+    async fn write_signed_sweep_transaction(
+        &self,
+        sweep: &model::SignedSweepTransaction,
+    ) -> Result<model::WriteOutcome, Error> {
+        self.schedule
+            .inject("write_signed_sweep_transaction")
+            .await?;
+        self.inner.write_signed_sweep_transaction(sweep).await
+    }
+
+    async fn delete_signed_sweep_transactions(
+        &self,
+        txids: &[model::BitcoinTxId],
+    ) -> Result<(), Error> {
+        self.schedule
+            .inject("delete_signed_sweep_transactions")
+            .await?;
+        self.inner.delete_signed_sweep_transactions(txids).await
+    }
+
+    async fn write_sweep_package(&self, package: &model::SweepPackage) -> Result<(), Error> {
+        self.schedule.inject("write_sweep_package").await?;
+        self.inner.write_sweep_package(package).await
+    }
+
+    async fn write_sweep_package_broadcast(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        txid: &model::BitcoinTxId,
+    ) -> Result<(), Error> {
+        self.schedule
+            .inject("write_sweep_package_broadcast")
+            .await?;
+        self.inner
+            .write_sweep_package_broadcast(bitcoin_chain_tip, txid)
+            .await
+    }
+
+    async fn write_deposit_request_timestamp(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+        stage: model::RequestLifecycleStage,
+    ) -> Result<(), Error> {
+        self.schedule
+            .inject("write_deposit_request_timestamp")
+            .await?;
+        self.inner
+            .write_deposit_request_timestamp(txid, output_index, stage)
+            .await
+    }
+
+    async fn write_withdrawal_request_timestamp(
+        &self,
+        request_id: u64,
+        stage: model::RequestLifecycleStage,
+    ) -> Result<(), Error> {
+        self.schedule
+            .inject("write_withdrawal_request_timestamp")
+            .await?;
+        self.inner
+            .write_withdrawal_request_timestamp(request_id, stage)
+            .await
+    }
+
+    async fn write_bitcoin_backfill_progress(
+        &self,
+        progress: &model::BitcoinBackfillProgress,
+    ) -> Result<(), Error> {
+        self.schedule
+            .inject("write_bitcoin_backfill_progress")
+            .await?;
+        self.inner.write_bitcoin_backfill_progress(progress).await
+    }
+
+    async fn write_coordinator_takeover(
+        &self,
+        takeover: &model::CoordinatorTakeover,
+    ) -> Result<model::WriteOutcome, Error> {
+        self.schedule.inject("write_coordinator_takeover").await?;
+        self.inner.write_coordinator_takeover(takeover).await
+    }
+
+    async fn write_bitcoin_block_header(
+        &self,
+        header: &bitcoin::block::Header,
+    ) -> Result<model::WriteOutcome, Error> {
+        self.schedule.inject("write_bitcoin_block_header").await?;
+        self.inner.write_bitcoin_block_header(header).await
+    }
+
+    async fn write_bitcoin_tx_merkle_proofs(
+        &self,
+        proofs: &[model::BitcoinTxMerkleProof],
+    ) -> Result<(), Error> {
+        self.schedule
+            .inject("write_bitcoin_tx_merkle_proofs")
+            .await?;
+        self.inner.write_bitcoin_tx_merkle_proofs(proofs).await
+    }
+
+    async fn write_multisig_stacks_transaction(
+        &self,
+        tx: &model::MultisigStacksTransaction,
+    ) -> Result<(), Error> {
+        self.schedule
+            .inject("write_multisig_stacks_transaction")
+            .await?;
+        self.inner.write_multisig_stacks_transaction(tx).await
+    }
+
+    async fn write_scanned_stacks_block(
+        &self,
+        block_hash: &model::StacksBlockHash,
+    ) -> Result<(), Error> {
+        self.schedule.inject("write_scanned_stacks_block").await?;
+        self.inner.write_scanned_stacks_block(block_hash).await
+    }
+}
@@ -1,5 +1,7 @@
 use libp2p::Multiaddr;
 
+use crate::config::NetworkKind;
+
 /// Configuration error variants.
 #[derive(Debug, thiserror::Error)]
 pub enum SignerConfigError {
@@ -50,6 +52,10 @@ pub enum SignerConfigError {
     #[error("Invalid P2P URI: Host is required")]
     P2PHostRequired,
 
+    /// The request decider needs at least one decision policy.
+    #[error("At least one decision policy must be configured")]
+    NoDecisionPolicies,
+
     /// When the network kind is 'mainnet' or 'testnet', at least one P2P seed peer is required.
     /// Otherwise, we'll allow mDNS to discover any local peers (for testing).
     #[error(
@@ -87,6 +93,51 @@ pub enum SignerConfigError {
     #[error("Bootstrap signer set must contain pubkey of this signer")]
     MissingPubkeyInBootstrapSignerSet,
 
+    /// The deposit confirmation policy requires more confirmations than
+    /// the deposit lookback window, so some deposits would never be swept.
+    #[error(
+        "The deposit confirmation policy requires up to {0} confirmations, which must not exceed the deposit lookback window of {1} blocks"
+    )]
+    DepositConfirmationsExceedContextWindow(u16, u16),
+
+    /// A lookback window is larger than the context window, which the
+    /// other signers use when validating the requests that the
+    /// coordinator picks from that window.
+    #[error("The {0} of {1} blocks must not exceed the context window of {2} blocks")]
+    LookbackExceedsContextWindow(&'static str, u16, u16),
+
+    /// The magic bytes activation height must be set on mainnet and
+    /// testnet.
+    #[error("The magic_bytes_activation_height must be set when the network is {0}")]
+    MissingMagicBytesActivationHeight(NetworkKind),
+
+    /// The maximum number of deposits per bitcoin transaction is larger
+    /// than the other signers accept during pre-sign.
+    #[error("The max_deposits_per_bitcoin_tx of {0} must not exceed the protocol limit of {1}")]
+    MaxDepositsPerBitcoinTxTooLarge(u16, u16),
+
+    /// An entry in the withdrawal output labels could not be parsed.
+    #[error(
+        "Invalid withdrawal output label entry: '{0}'. Expected '<label>:<hex-encoded scriptPubKey>' with a non-empty label and scriptPubKey."
+    )]
+    InvalidWithdrawalOutputLabel(String),
+
+    /// The same scriptPubKey was given more than one withdrawal output
+    /// label.
+    #[error("The scriptPubKey {0} has more than one withdrawal output label")]
+    DuplicateWithdrawalOutputLabel(String),
+
+    /// The blocklist client auto-approves requests for amounts that it
+    /// is also configured to screen in full.
+    #[error(
+        "The blocklist client auto_approve_below of {0} sats must not exceed its full_screening_above of {1} sats"
+    )]
+    OverlappingScreeningTiers(u64, u64),
+
+    /// The admin endpoints may only be served on a loopback address.
+    #[error("The event observer admin_bind must be a loopback address, got {0}")]
+    NonLoopbackAdminBind(std::net::SocketAddr),
+
     /// An error returned if bootstrap_signer_set contains more than 16 signers.
     /// Currently our stacks contracts don't allow more than 16 signers.
     /// See https://github.com/stacks-sbtc/sbtc/issues/1694
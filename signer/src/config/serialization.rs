@@ -6,7 +6,9 @@ use serde::{Deserialize as _, Deserializer};
 use url::Url;
 
 use crate::keys::PrivateKey;
+use crate::storage::model::WithdrawalOutputLabel;
 
+use super::DepositConfirmationPolicy;
 use super::error::SignerConfigError;
 
 /// A deserializer for the url::Url type. This will return an empty [`Vec`] if
@@ -22,6 +24,36 @@ where
     Ok(v)
 }
 
+/// A deserializer for the [`DepositConfirmationPolicy`] type, from a list
+/// of `<min_amount>:<confirmations>` strings.
+pub fn deposit_confirmation_policy_deserializer<'de, D>(
+    deserializer: D,
+) -> Result<DepositConfirmationPolicy, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let mut tiers = Vec::new();
+    for s in Vec::<String>::deserialize(deserializer)? {
+        tiers.push(s.parse().map_err(serde::de::Error::custom)?);
+    }
+    Ok(DepositConfirmationPolicy::new(tiers))
+}
+
+/// A deserializer for the withdrawal output labels, from a list of
+/// `<label>:<hex-encoded scriptPubKey>` strings.
+pub fn withdrawal_output_labels_deserializer<'de, D>(
+    deserializer: D,
+) -> Result<Vec<WithdrawalOutputLabel>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let mut labels = Vec::new();
+    for s in Vec::<String>::deserialize(deserializer)? {
+        labels.push(s.parse().map_err(serde::de::Error::custom)?);
+    }
+    Ok(labels)
+}
+
 /// A deserializer for the url::Url type. Does not support deserializing a list,
 /// only a single URL.
 pub fn url_deserializer_single<'de, D>(deserializer: D) -> Result<url::Url, D::Error>
@@ -33,6 +65,17 @@ where
         .map_err(serde::de::Error::custom)
 }
 
+/// A deserializer for an optional url::Url, where a missing value means
+/// that there is no URL.
+pub fn url_deserializer_option<'de, D>(deserializer: D) -> Result<Option<url::Url>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|s| s.parse().map_err(serde::de::Error::custom))
+        .transpose()
+}
+
 /// A deserializer for the std::time::Duration type.
 /// Serde includes a default deserializer, but it expects a struct.
 pub fn duration_seconds_deserializer<'de, D>(
@@ -0,0 +1,179 @@
+//! Module for tracking the most recent errors of the signer's subsystems.
+
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// The subsystems of the signer whose errors are tracked in the
+/// [`ErrorRegistry`].
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    strum::Display,
+    strum::EnumIter,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Subsystem {
+    /// The block observer.
+    BlockObserver,
+    /// The request decider event loop.
+    RequestDecider,
+    /// The transaction signer event loop.
+    TxSigner,
+    /// The transaction coordinator event loop.
+    TxCoordinator,
+    /// The client used to talk to Emily.
+    EmilyClient,
+}
+
+/// The error state of a single subsystem.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubsystemHealth {
+    /// Whether the most recent outcome recorded for the subsystem was an
+    /// error.
+    pub degraded: bool,
+    /// The most recent error of the subsystem, if any.
+    pub last_error: Option<String>,
+    /// When the most recent error happened, as a unix timestamp in
+    /// seconds.
+    pub last_error_at: Option<i64>,
+    /// The number of errors recorded since the signer started.
+    pub error_count: u64,
+    /// The number of times the subsystem succeeded right after an error.
+    pub recovery_count: u64,
+}
+
+/// A registry of the most recent error of each subsystem, so that
+/// degraded subsystems can be seen through the status API.
+#[derive(Debug, Default)]
+pub struct ErrorRegistry(RwLock<BTreeMap<Subsystem, SubsystemHealth>>);
+
+impl ErrorRegistry {
+    /// Record an error for the given subsystem.
+    pub fn record_error(&self, subsystem: Subsystem, error: &impl std::fmt::Display) {
+        let mut registry = self
+            .0
+            .write()
+            .expect("BUG: Failed to acquire write lock of the error registry");
+        let health = registry.entry(subsystem).or_default();
+
+        health.degraded = true;
+        health.last_error = Some(error.to_string());
+        health.last_error_at = Some(time::OffsetDateTime::now_utc().unix_timestamp());
+        health.error_count = health.error_count.saturating_add(1);
+    }
+
+    /// Record that the given subsystem did its work without errors. This
+    /// counts as a recovery if the subsystem was degraded.
+    pub fn record_success(&self, subsystem: Subsystem) {
+        let mut registry = self
+            .0
+            .write()
+            .expect("BUG: Failed to acquire write lock of the error registry");
+        let Some(health) = registry.get_mut(&subsystem) else {
+            return;
+        };
+
+        if health.degraded {
+            health.degraded = false;
+            health.recovery_count = health.recovery_count.saturating_add(1);
+        }
+    }
+
+    /// Record the outcome of some work done by the given subsystem.
+    pub fn record<T, E>(&self, subsystem: Subsystem, result: &Result<T, E>)
+    where
+        E: std::fmt::Display,
+    {
+        match result {
+            Ok(_) => self.record_success(subsystem),
+            Err(error) => self.record_error(subsystem, error),
+        }
+    }
+
+    /// Return the error state of every subsystem, including the ones that
+    /// have never had an error.
+    pub fn snapshot(&self) -> BTreeMap<Subsystem, SubsystemHealth> {
+        use strum::IntoEnumIterator as _;
+
+        let registry = self
+            .0
+            .read()
+            .expect("BUG: Failed to acquire read lock of the error registry");
+
+        Subsystem::iter()
+            .map(|subsystem| {
+                let health = registry.get(&subsystem).cloned().unwrap_or_default();
+                (subsystem, health)
+            })
+            .collect()
+    }
+
+    /// Whether any subsystem is currently degraded.
+    pub fn is_degraded(&self) -> bool {
+        self.0
+            .read()
+            .expect("BUG: Failed to acquire read lock of the error registry")
+            .values()
+            .any(|health| health.degraded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors_and_recoveries_are_counted() {
+        let registry = ErrorRegistry::default();
+
+        // Successes before any error are not recoveries.
+        registry.record_success(Subsystem::TxSigner);
+        assert!(!registry.is_degraded());
+        assert_eq!(
+            registry.snapshot()[&Subsystem::TxSigner],
+            SubsystemHealth::default()
+        );
+
+        registry.record_error(Subsystem::TxSigner, &"first");
+        registry.record::<(), _>(Subsystem::TxSigner, &Err("second"));
+        assert!(registry.is_degraded());
+
+        let health = registry.snapshot()[&Subsystem::TxSigner].clone();
+        assert!(health.degraded);
+        assert_eq!(health.last_error.as_deref(), Some("second"));
+        assert!(health.last_error_at.is_some());
+        assert_eq!(health.error_count, 2);
+        assert_eq!(health.recovery_count, 0);
+
+        // Only the first success after an error is a recovery.
+        registry.record::<_, String>(Subsystem::TxSigner, &Ok(()));
+        registry.record_success(Subsystem::TxSigner);
+        assert!(!registry.is_degraded());
+
+        let health = registry.snapshot()[&Subsystem::TxSigner].clone();
+        assert!(!health.degraded);
+        assert_eq!(health.last_error.as_deref(), Some("second"));
+        assert_eq!(health.error_count, 2);
+        assert_eq!(health.recovery_count, 1);
+
+        // The other subsystems are untouched.
+        let snapshot = registry.snapshot();
+        assert_eq!(snapshot.len(), 5);
+        assert_eq!(
+            snapshot[&Subsystem::EmilyClient],
+            SubsystemHealth::default()
+        );
+    }
+}
@@ -70,6 +70,12 @@ pub enum RequestDeciderEvent {
     ReceivedDepositDecision,
     /// Received a withdrawal decision
     ReceivedWithdrawalDecision,
+    /// Received a page of decisions in response to a decision sync
+    /// request, and stored them.
+    ReceivedDecisionSyncResponse,
+    /// Received a batch of decisions from another signer, and stored
+    /// them.
+    ReceivedDecisionBatch,
     /// A new pending withdrawal request has been handled.
     PendingWithdrawalRequestRegistered,
     /// A new pending deposit request has been handled.
@@ -1,6 +1,8 @@
 //! Context module for the signer binary.
 
+mod health;
 mod messaging;
+mod rng;
 mod signer_context;
 mod signer_state;
 mod termination;
@@ -21,7 +23,9 @@ use crate::storage::DbRead;
 use crate::storage::DbWrite;
 use crate::storage::Transactable;
 
+pub use health::*;
 pub use messaging::*;
+pub use rng::*;
 pub use signer_context::SignerContext;
 pub use signer_state::*;
 pub use termination::*;
@@ -53,6 +57,9 @@ pub trait Context: Clone + Sync + Send {
     fn get_stacks_client(&self) -> impl StacksInteract + Clone + 'static;
     /// Get a handle to an Emily client.
     fn get_emily_client(&self) -> impl EmilyInteract + Clone + 'static;
+    /// Get a new random number generator for the given stream. Each
+    /// component should use its own stream name.
+    fn get_rng(&self, stream: &str) -> SignerRng;
 
     /// Create a new signal stream containing signer messages from:
     /// 1. The signer network, as defined by the given network object
@@ -111,6 +118,32 @@ pub trait Context: Clone + Sync + Send {
         ReceiverStream::new(receiver)
     }
 
+    /// Wait until the database is reachable, which returns immediately
+    /// unless the database monitor has found it unreachable, such as
+    /// while the postgres primary fails over. Returns `false` if the
+    /// signer is shut down before the database becomes reachable again.
+    fn wait_for_database(&self) -> impl Future<Output = bool> + Send {
+        async move {
+            if self.state().is_database_available() {
+                return true;
+            }
+
+            let mut term = self.get_termination_handle();
+            if term.shutdown_signalled() {
+                return false;
+            }
+
+            tracing::warn!("database is unreachable; pausing until it is reachable again");
+            tokio::select! {
+                _ = self.state().wait_for_database() => {
+                    tracing::info!("database is reachable again; resuming");
+                    true
+                }
+                _ = term.wait_for_shutdown() => false,
+            }
+        }
+    }
+
     /// Return the signer set that is used when determining who is the
     /// coordinator.
     ///
@@ -129,4 +162,33 @@ pub trait Context: Clone + Sync + Send {
             .registry_signer_set_info()
             .map_or_else(default_signer_set, |info| info.signer_set)
     }
+
+    /// Return the number of signatures required by the signer set that
+    /// [`Context::coordinator_signer_set`] returns.
+    fn coordinator_signatures_required(&self) -> u16 {
+        let default_signatures_required = || self.config().signer.bootstrap_signatures_required;
+
+        self.state()
+            .registry_signer_set_info()
+            .map_or_else(default_signatures_required, |info| info.signatures_required)
+    }
+
+    /// Return the signers that the coordinator, and its deputy, are
+    /// elected from for the given bitcoin chain tip.
+    ///
+    /// This is the [`Context::coordinator_signer_set`] without the signers
+    /// that are deprioritized for rarely signing the signers' stacks
+    /// transactions, which is only ever non-empty when the coordinator
+    /// latency window is configured. See the [`crate::coordinator_latency`]
+    /// module for the details.
+    fn coordinator_candidates(
+        &self,
+        bitcoin_chain_tip: &crate::storage::model::BitcoinBlockHash,
+    ) -> BTreeSet<PublicKey> {
+        let deprioritized = self.state().deprioritized_coordinators(bitcoin_chain_tip);
+        crate::coordinator_latency::coordinator_candidates(
+            self.coordinator_signer_set(),
+            &deprioritized,
+        )
+    }
 }
@@ -0,0 +1,157 @@
+//! Module for the random number generators handed out by the context.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use rand::CryptoRng;
+use rand::Rng as _;
+use rand::RngCore;
+use rand::SeedableRng as _;
+use rand::rngs::OsRng;
+use rand_chacha::ChaCha20Rng;
+use sha2::Digest as _;
+
+/// A source of random number generators for the signer's components.
+///
+/// By default every generator handed out is backed by the operating
+/// system's CSPRNG. In tests and in the simulation harness the provider
+/// can instead be created from a seed, in which case each named stream
+/// yields the same sequence of generators on every run, regardless of how
+/// the components using the other streams are scheduled.
+#[derive(Debug, Clone, Default)]
+pub struct RngProvider {
+    /// The seeded streams, keyed by name. This is `None` when the
+    /// generators are backed by the operating system's CSPRNG.
+    seeded: Option<Arc<Mutex<SeededStreams>>>,
+}
+
+#[derive(Debug)]
+struct SeededStreams {
+    seed: u64,
+    streams: HashMap<String, ChaCha20Rng>,
+}
+
+impl RngProvider {
+    /// Create a provider where every generator is deterministically
+    /// derived from the given seed. This is meant for tests and
+    /// simulations only, the signer binary always uses the default.
+    pub fn from_seed(seed: u64) -> Self {
+        let streams = SeededStreams { seed, streams: HashMap::new() };
+        Self {
+            seeded: Some(Arc::new(Mutex::new(streams))),
+        }
+    }
+
+    /// Whether the generators handed out by this provider are derived
+    /// from a seed.
+    pub fn is_seeded(&self) -> bool {
+        self.seeded.is_some()
+    }
+
+    /// Return a new random number generator for the given stream.
+    ///
+    /// Components should use a stream name that is unique to them, so
+    /// that seeded runs do not depend on the order in which components
+    /// ask for generators.
+    pub fn rng(&self, stream: &str) -> SignerRng {
+        let Some(seeded) = &self.seeded else {
+            return SignerRng::Os(OsRng);
+        };
+
+        let mut seeded = seeded
+            .lock()
+            .expect("BUG: Failed to acquire lock of the seeded rng streams");
+        let seed = seeded.seed;
+        let parent = seeded.streams.entry(stream.to_string()).or_insert_with(|| {
+            let seed_bytes: [u8; 32] = sha2::Sha256::new_with_prefix("SIGNER_RNG")
+                .chain_update(seed.to_be_bytes())
+                .chain_update(stream.as_bytes())
+                .finalize()
+                .into();
+            ChaCha20Rng::from_seed(seed_bytes)
+        });
+
+        SignerRng::Seeded(ChaCha20Rng::from_seed(parent.r#gen()))
+    }
+}
+
+/// A cryptographically secure random number generator handed out by an
+/// [`RngProvider`].
+#[derive(Debug, Clone)]
+pub enum SignerRng {
+    /// The operating system's CSPRNG.
+    Os(OsRng),
+    /// A generator derived from the seed of the provider.
+    Seeded(ChaCha20Rng),
+}
+
+impl RngCore for SignerRng {
+    fn next_u32(&mut self) -> u32 {
+        match self {
+            SignerRng::Os(rng) => rng.next_u32(),
+            SignerRng::Seeded(rng) => rng.next_u32(),
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match self {
+            SignerRng::Os(rng) => rng.next_u64(),
+            SignerRng::Seeded(rng) => rng.next_u64(),
+        }
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        match self {
+            SignerRng::Os(rng) => rng.fill_bytes(dest),
+            SignerRng::Seeded(rng) => rng.fill_bytes(dest),
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        match self {
+            SignerRng::Os(rng) => rng.try_fill_bytes(dest),
+            SignerRng::Seeded(rng) => rng.try_fill_bytes(dest),
+        }
+    }
+}
+
+impl CryptoRng for SignerRng {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draw(provider: &RngProvider, stream: &str) -> [u64; 4] {
+        let mut rng = provider.rng(stream);
+        std::array::from_fn(|_| rng.next_u64())
+    }
+
+    #[test]
+    fn seeded_providers_are_deterministic() {
+        let provider1 = RngProvider::from_seed(42);
+        let provider2 = RngProvider::from_seed(42);
+        assert!(provider1.is_seeded());
+
+        // Interleaving requests for another stream does not change what
+        // the first stream hands out.
+        let first = draw(&provider1, "tx-signer");
+        draw(&provider1, "tx-coordinator");
+        let second = draw(&provider1, "tx-signer");
+
+        assert_eq!(first, draw(&provider2, "tx-signer"));
+        assert_eq!(second, draw(&provider2, "tx-signer"));
+        assert_ne!(first, second);
+
+        let provider3 = RngProvider::from_seed(43);
+        assert_ne!(first, draw(&provider3, "tx-signer"));
+    }
+
+    #[test]
+    fn default_provider_uses_the_os_rng() {
+        let provider = RngProvider::default();
+        assert!(!provider.is_seeded());
+        assert!(matches!(provider.rng("tx-signer"), SignerRng::Os(_)));
+        assert_ne!(draw(&provider, "tx-signer"), draw(&provider, "tx-signer"));
+    }
+}
@@ -11,7 +11,7 @@ use crate::{
     storage::{DbRead, DbWrite, Transactable},
 };
 
-use super::{Context, SignerSignal, SignerState, TerminationHandle};
+use super::{Context, RngProvider, SignerRng, SignerSignal, SignerState, TerminationHandle};
 
 /// Signer context which is passed to different components within the
 /// signer binary.
@@ -39,6 +39,8 @@ pub struct SignerContext<S, BC, ST, EM> {
     stacks_client: ST,
     /// Handle to a Emily-API fallback-client.
     emily_client: EM,
+    /// Source of the random number generators used by the event loops.
+    rng: RngProvider,
     // /// Handle to a Blocklist-API fallback-client.
     //blocklist_client: ApiFallbackClient<BL>,
 }
@@ -107,6 +109,7 @@ where
             bitcoin_client,
             stacks_client,
             emily_client,
+            rng: RngProvider::default(),
         }
     }
 }
@@ -173,6 +176,10 @@ where
     fn get_emily_client(&self) -> impl EmilyInteract + Clone + 'static {
         self.emily_client.clone()
     }
+
+    fn get_rng(&self, stream: &str) -> SignerRng {
+        self.rng.rng(stream)
+    }
 }
 
 #[cfg(any(test, feature = "testing"))]
@@ -182,6 +189,12 @@ impl<Storage, Bitcoin, Stacks, Emily> SignerContext<Storage, Bitcoin, Stacks, Em
         &mut self.config
     }
 
+    /// Replace the source of the random number generators, for example
+    /// with a seeded one so that runs are reproducible.
+    pub fn set_rng_provider(&mut self, rng: RngProvider) {
+        self.rng = rng;
+    }
+
     /// Resets the termination signal for this context.
     ///
     /// This sets the underlying termination state to `false`, allowing
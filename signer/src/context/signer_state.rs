@@ -1,21 +1,47 @@
 //! Module for signer state
 
+use std::collections::BTreeMap;
 use std::collections::BTreeSet;
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::sync::{
     RwLock,
     atomic::{AtomicBool, AtomicU64, Ordering},
 };
+use std::time::Duration;
+use std::time::Instant;
 
 use bitcoin::Amount;
 use libp2p::PeerId;
 
+use crate::context::ErrorRegistry;
+use crate::key_rotation_recovery::KeyRotationState;
 use crate::keys::PublicKey;
+use crate::message::CoordinatorSilenceAttestation;
+use crate::sbtc_limits::MaxMintable;
 use crate::stacks::api::SignerSetInfo;
+use crate::storage::model::BitcoinBlockHash;
 use crate::storage::model::BitcoinBlockHeight;
 use crate::storage::model::BitcoinBlockRef;
+use crate::storage::model::BitcoinTxId;
 use crate::storage::model::StacksBlockRef;
 
+/// The length of the window that requests to the sweep transaction proof
+/// and deposit pre-check endpoints, and sweep transaction proof requests
+/// from peers, are rate limited over.
+const API_REQUEST_RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// The maximum number of sweep transaction proof requests that we answer
+/// from each peer per minute. Our answers are gossiped to every peer, so
+/// this is much lower than the limit on the sweep transaction proof
+/// endpoint.
+const PEER_PROOF_REQUESTS_PER_MINUTE: u32 = 6;
+
+/// How long a signer has to wait after we answered one of its decision
+/// sync requests before we answer another one. Signers only ask for
+/// decisions once after they start, so this is generous.
+const DECISION_SYNC_REQUEST_COOLDOWN: Duration = Duration::from_secs(600);
+
 /// A struct for holding internal signer state. This struct is served by
 /// the [`SignerContext`] and can be used to cache global state instead of
 /// fetching it via I/O for frequently accessed information.
@@ -23,6 +49,9 @@ use crate::storage::model::StacksBlockRef;
 pub struct SignerState {
     current_signer_set: SignerSet,
     current_limits: RwLock<SbtcLimits>,
+    // The most recent maximum mintable calculation. This gets updated by
+    // the block observer when it refreshes the sBTC limits.
+    max_mintable: RwLock<Option<MaxMintable>>,
     registry_signing_set_info: RwLock<Option<SignerSetInfo>>,
     sbtc_contracts_deployed: AtomicBool,
     sbtc_bitcoin_start_height: AtomicU64,
@@ -34,6 +63,64 @@ pub struct SignerState {
     // chain tip. This gets updated at the end of the block observer's
     // duties when it observes a new bitcoin block.
     stacks_chain_tip: RwLock<Option<StacksBlockRef>>,
+    // The last time that we heard from each of our peers, either through
+    // a ping or a gossiped message.
+    peer_heartbeats: RwLock<HashMap<PeerId, Instant>>,
+    // The signer protocol version that each of our peers advertised when
+    // we last connected to them.
+    peer_protocol_versions: RwLock<HashMap<PeerId, u32>>,
+    // Whether the signer is in safe-mode because it cannot reach enough
+    // of its peers to reach the signing threshold.
+    safe_mode: AtomicBool,
+    // Whether governance has paused the sBTC smart contracts. This gets
+    // updated by the block observer when it observes a new bitcoin block.
+    contract_paused: AtomicBool,
+    // Whether the database is reachable. This gets updated by the
+    // database monitor, and the event loops wait on it before handling
+    // new events while the database is failing over.
+    database_available: tokio::sync::watch::Sender<bool>,
+    // The most recent error of each of the signer's subsystems.
+    error_registry: ErrorRegistry,
+    // The last time that we heard from the coordinator of the given
+    // bitcoin chain tip.
+    coordinator_activity: RwLock<Option<(BitcoinBlockHash, Instant)>>,
+    // The deputy coordinator that took over the tenure of the given
+    // bitcoin chain tip, if we accepted a takeover.
+    coordinator_takeover: RwLock<Option<(BitcoinBlockHash, PublicKey)>>,
+    // The attestations, by signer, that the coordinator of the given
+    // bitcoin chain tip went silent.
+    silence_attestations: RwLock<
+        Option<(
+            BitcoinBlockHash,
+            BTreeMap<PublicKey, CoordinatorSilenceAttestation>,
+        )>,
+    >,
+    // How far along the signers are with their most recent key rotation.
+    // This gets updated by the key rotation monitor.
+    key_rotation_state: RwLock<KeyRotationState>,
+    // When the current one minute window of requests to the sweep
+    // transaction proof endpoint started for each client, along with the
+    // number of requests the client made in it. Clients are identified
+    // by the index of their token in the configuration.
+    proof_request_windows: RwLock<HashMap<usize, (Instant, u32)>>,
+    // When the current one minute window of sweep transaction proof
+    // requests over the P2P network started for each peer, along with the
+    // number of requests the peer made in it.
+    peer_proof_request_windows: RwLock<HashMap<PublicKey, (Instant, u32)>>,
+    // When the current one minute window of requests to the deposit
+    // pre-check endpoint started, along with the number of requests made
+    // in it. The endpoint is open to anyone, so all requests share one
+    // window.
+    precheck_request_window: RwLock<(Instant, u32)>,
+    // When we last answered a decision sync request from each signer.
+    decision_sync_requests: RwLock<HashMap<PublicKey, Instant>>,
+    // The sweep transactions whose SPV proofs we reported to Emily for
+    // the withdrawals that they fulfilled.
+    reported_sweep_proofs: RwLock<HashSet<BitcoinTxId>>,
+    // The signers that are deprioritized in the coordinator election for
+    // the given bitcoin chain tip because they rarely sign the signers'
+    // stacks transactions. This gets updated by the block observer.
+    deprioritized_coordinators: RwLock<Option<(BitcoinBlockHash, BTreeSet<PublicKey>)>>,
 }
 
 impl SignerState {
@@ -121,6 +208,23 @@ impl SignerState {
         *limits = new_limits;
     }
 
+    /// Get the most recent maximum mintable calculation, if any.
+    #[allow(clippy::unwrap_in_result)]
+    pub fn max_mintable(&self) -> Option<MaxMintable> {
+        *self
+            .max_mintable
+            .read()
+            .expect("BUG: Failed to acquire read lock")
+    }
+
+    /// Replace the cached maximum mintable calculation.
+    pub fn set_max_mintable(&self, mintable: MaxMintable) {
+        self.max_mintable
+            .write()
+            .expect("BUG: Failed to acquire write lock")
+            .replace(mintable);
+    }
+
     /// Returns true if sbtc smart contracts are deployed
     pub fn sbtc_contracts_deployed(&self) -> bool {
         self.sbtc_contracts_deployed.load(Ordering::SeqCst)
@@ -148,6 +252,355 @@ impl SignerState {
     pub fn is_sbtc_bitcoin_start_height_set(&self) -> bool {
         self.is_sbtc_bitcoin_start_height_set.load(Ordering::SeqCst)
     }
+
+    /// Record that we have just heard from the given peer.
+    pub fn record_peer_heartbeat(&self, peer_id: PeerId) {
+        self.peer_heartbeats
+            .write()
+            .expect("BUG: Failed to acquire write lock")
+            .insert(peer_id, Instant::now());
+    }
+
+    /// Return the number of signers in the current signer set that we
+    /// have heard from within the given timeout.
+    #[allow(clippy::unwrap_in_result)]
+    pub fn num_live_peers(&self, timeout: Duration) -> usize {
+        let heartbeats = self
+            .peer_heartbeats
+            .read()
+            .expect("BUG: Failed to acquire read lock");
+
+        heartbeats
+            .iter()
+            .filter(|(peer_id, _)| self.current_signer_set.is_allowed_peer(peer_id))
+            .filter(|(_, last_seen)| last_seen.elapsed() <= timeout)
+            .count()
+    }
+
+    /// Record the signer protocol version advertised by the given peer.
+    pub fn record_peer_protocol_version(&self, peer_id: PeerId, version: u32) {
+        self.peer_protocol_versions
+            .write()
+            .expect("BUG: Failed to acquire write lock")
+            .insert(peer_id, version);
+    }
+
+    /// Return the signer protocol version last advertised by the given
+    /// peer, if it has advertised one.
+    #[allow(clippy::unwrap_in_result)]
+    pub fn peer_protocol_version(&self, peer_id: &PeerId) -> Option<u32> {
+        self.peer_protocol_versions
+            .read()
+            .expect("BUG: Failed to acquire read lock")
+            .get(peer_id)
+            .copied()
+    }
+
+    /// Whether each of the given signers, other than the one with the
+    /// public key `own_public_key`, has advertised a signer protocol
+    /// version of at least `version`. Signers that have not advertised a
+    /// version yet are assumed to run an older one.
+    pub fn signers_support_protocol_version<'a, I>(
+        &self,
+        signer_public_keys: I,
+        own_public_key: &PublicKey,
+        version: u32,
+    ) -> bool
+    where
+        I: IntoIterator<Item = &'a PublicKey>,
+    {
+        signer_public_keys
+            .into_iter()
+            .filter(|public_key| *public_key != own_public_key)
+            .all(|public_key| {
+                self.peer_protocol_version(&PeerId::from(*public_key))
+                    .is_some_and(|advertised| advertised >= version)
+            })
+    }
+
+    /// Returns true if the signer is in safe-mode.
+    pub fn is_in_safe_mode(&self) -> bool {
+        self.safe_mode.load(Ordering::SeqCst)
+    }
+
+    /// Set whether the signer is in safe-mode, returning the previous
+    /// value.
+    pub fn set_safe_mode(&self, enabled: bool) -> bool {
+        self.safe_mode.swap(enabled, Ordering::SeqCst)
+    }
+
+    /// Return whether the sBTC smart contracts are paused, in which case
+    /// the signer does not take part in new sweeps.
+    pub fn is_contract_paused(&self) -> bool {
+        self.contract_paused.load(Ordering::SeqCst)
+    }
+
+    /// Set whether the sBTC smart contracts are paused, returning the
+    /// previous value.
+    pub fn set_contract_paused(&self, paused: bool) -> bool {
+        self.contract_paused.swap(paused, Ordering::SeqCst)
+    }
+
+    /// Return whether the database was reachable the last time that the
+    /// database monitor checked.
+    pub fn is_database_available(&self) -> bool {
+        *self.database_available.borrow()
+    }
+
+    /// Set whether the database is reachable, returning the previous
+    /// value.
+    pub fn set_database_available(&self, available: bool) -> bool {
+        self.database_available.send_replace(available)
+    }
+
+    /// Wait until the database is reachable, returning immediately if it
+    /// already is.
+    pub async fn wait_for_database(&self) {
+        let mut receiver = self.database_available.subscribe();
+        // The sender lives as long as the state, so this cannot fail.
+        let _ = receiver.wait_for(|available| *available).await;
+    }
+
+    /// Return the registry where the signer's subsystems record their
+    /// errors.
+    pub fn error_registry(&self) -> &ErrorRegistry {
+        &self.error_registry
+    }
+
+    /// Record that we have just heard from the coordinator of the given
+    /// bitcoin chain tip.
+    pub fn record_coordinator_activity(&self, chain_tip: BitcoinBlockHash) {
+        self.coordinator_activity
+            .write()
+            .expect("BUG: Failed to acquire write lock")
+            .replace((chain_tip, Instant::now()));
+    }
+
+    /// Return the last time that we heard from the coordinator of the
+    /// given bitcoin chain tip, if we have heard from it at all.
+    #[allow(clippy::unwrap_in_result)]
+    pub fn coordinator_last_seen(&self, chain_tip: &BitcoinBlockHash) -> Option<Instant> {
+        self.coordinator_activity
+            .read()
+            .expect("BUG: Failed to acquire read lock")
+            .filter(|(block_hash, _)| block_hash == chain_tip)
+            .map(|(_, last_seen)| last_seen)
+    }
+
+    /// Record that the given deputy coordinator took over the tenure of
+    /// the given bitcoin chain tip.
+    pub fn set_coordinator_takeover(&self, chain_tip: BitcoinBlockHash, deputy: PublicKey) {
+        self.coordinator_takeover
+            .write()
+            .expect("BUG: Failed to acquire write lock")
+            .replace((chain_tip, deputy));
+    }
+
+    /// Return the deputy coordinator that took over the tenure of the
+    /// given bitcoin chain tip, if there is one.
+    #[allow(clippy::unwrap_in_result)]
+    pub fn coordinator_takeover(&self, chain_tip: &BitcoinBlockHash) -> Option<PublicKey> {
+        self.coordinator_takeover
+            .read()
+            .expect("BUG: Failed to acquire read lock")
+            .filter(|(block_hash, _)| block_hash == chain_tip)
+            .map(|(_, deputy)| deputy)
+    }
+
+    /// Record a signer's attestation that the coordinator of the given
+    /// bitcoin chain tip went silent. The attestations for any other chain
+    /// tip are dropped, and the first attestation of each signer is kept.
+    pub fn record_silence_attestation(
+        &self,
+        chain_tip: BitcoinBlockHash,
+        attestation: CoordinatorSilenceAttestation,
+    ) {
+        let mut attestations = self
+            .silence_attestations
+            .write()
+            .expect("BUG: Failed to acquire write lock");
+
+        if attestations
+            .as_ref()
+            .is_none_or(|(block_hash, _)| *block_hash != chain_tip)
+        {
+            *attestations = Some((chain_tip, BTreeMap::new()));
+        }
+        if let Some((_, by_signer)) = attestations.as_mut() {
+            by_signer
+                .entry(attestation.signer_public_key)
+                .or_insert(attestation);
+        }
+    }
+
+    /// Return the attestations that the coordinator of the given bitcoin
+    /// chain tip went silent, ordered by the public key of the signer.
+    #[allow(clippy::unwrap_in_result)]
+    pub fn silence_attestations(
+        &self,
+        chain_tip: &BitcoinBlockHash,
+    ) -> Vec<CoordinatorSilenceAttestation> {
+        self.silence_attestations
+            .read()
+            .expect("BUG: Failed to acquire read lock")
+            .as_ref()
+            .filter(|(block_hash, _)| block_hash == chain_tip)
+            .map(|(_, by_signer)| by_signer.values().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Return how far along the signers are with their most recent key
+    /// rotation, as of the last check by the key rotation monitor.
+    #[allow(clippy::unwrap_in_result)]
+    pub fn key_rotation_state(&self) -> KeyRotationState {
+        *self
+            .key_rotation_state
+            .read()
+            .expect("BUG: Failed to acquire read lock")
+    }
+
+    /// Set how far along the signers are with their most recent key
+    /// rotation, returning the previous value.
+    pub fn set_key_rotation_state(&self, state: KeyRotationState) -> KeyRotationState {
+        let mut current = self
+            .key_rotation_state
+            .write()
+            .expect("BUG: Failed to acquire write lock");
+        std::mem::replace(&mut *current, state)
+    }
+
+    /// Count a request to the sweep transaction proof endpoint against
+    /// the rate limit of the given client, returning whether the client
+    /// has made at most `max_per_minute` requests in the current one
+    /// minute window.
+    #[allow(clippy::unwrap_in_result)]
+    pub fn check_proof_request_rate(&self, client: usize, max_per_minute: u32) -> bool {
+        let mut windows = self
+            .proof_request_windows
+            .write()
+            .expect("BUG: Failed to acquire write lock");
+
+        let window = windows.entry(client).or_insert((Instant::now(), 0));
+        count_api_request(window, max_per_minute)
+    }
+
+    /// Count a sweep transaction proof request from the given peer,
+    /// returning whether the peer has made at most
+    /// [`PEER_PROOF_REQUESTS_PER_MINUTE`] requests in the current one
+    /// minute window.
+    pub fn check_peer_proof_request_rate(&self, peer: PublicKey) -> bool {
+        let mut windows = self
+            .peer_proof_request_windows
+            .write()
+            .expect("BUG: Failed to acquire write lock");
+
+        let window = windows.entry(peer).or_insert((Instant::now(), 0));
+        count_api_request(window, PEER_PROOF_REQUESTS_PER_MINUTE)
+    }
+
+    /// Count a request to the deposit pre-check endpoint, returning
+    /// whether at most `max_per_minute` requests have been made in the
+    /// current one minute window.
+    pub fn check_precheck_request_rate(&self, max_per_minute: u32) -> bool {
+        let mut window = self
+            .precheck_request_window
+            .write()
+            .expect("BUG: Failed to acquire write lock");
+
+        count_api_request(&mut window, max_per_minute)
+    }
+
+    /// Count a decision sync request from the given signer, returning
+    /// whether we should answer it. We answer at most one request from
+    /// each signer per [`DECISION_SYNC_REQUEST_COOLDOWN`].
+    pub fn check_decision_sync_request_rate(&self, signer: PublicKey) -> bool {
+        let mut requests = self
+            .decision_sync_requests
+            .write()
+            .expect("BUG: Failed to acquire write lock");
+
+        let now = Instant::now();
+        match requests.get(&signer) {
+            Some(answered_at)
+                if now.duration_since(*answered_at) < DECISION_SYNC_REQUEST_COOLDOWN =>
+            {
+                false
+            }
+            _ => {
+                requests.insert(signer, now);
+                true
+            }
+        }
+    }
+
+    /// Return whether we reported the SPV proof of the given sweep
+    /// transaction to Emily.
+    pub fn is_sweep_proof_reported(&self, txid: &BitcoinTxId) -> bool {
+        self.reported_sweep_proofs
+            .read()
+            .expect("BUG: Failed to acquire read lock")
+            .contains(txid)
+    }
+
+    /// Record that we reported the SPV proof of the given sweep
+    /// transaction to Emily.
+    pub fn set_sweep_proof_reported(&self, txid: BitcoinTxId) {
+        self.reported_sweep_proofs
+            .write()
+            .expect("BUG: Failed to acquire write lock")
+            .insert(txid);
+    }
+
+    /// Forget the reported sweep transactions that are not in the given
+    /// set, so that we only remember the ones that can still come up.
+    pub fn retain_reported_sweep_proofs(&self, txids: &HashSet<BitcoinTxId>) {
+        self.reported_sweep_proofs
+            .write()
+            .expect("BUG: Failed to acquire write lock")
+            .retain(|txid| txids.contains(txid));
+    }
+
+    /// Set the signers that are deprioritized in the coordinator election
+    /// for the given bitcoin chain tip.
+    pub fn set_deprioritized_coordinators(
+        &self,
+        chain_tip: BitcoinBlockHash,
+        signers: BTreeSet<PublicKey>,
+    ) {
+        self.deprioritized_coordinators
+            .write()
+            .expect("BUG: Failed to acquire write lock")
+            .replace((chain_tip, signers));
+    }
+
+    /// Return the signers that are deprioritized in the coordinator
+    /// election for the given bitcoin chain tip. This is empty unless they
+    /// were set for the given chain tip.
+    #[allow(clippy::unwrap_in_result)]
+    pub fn deprioritized_coordinators(&self, chain_tip: &BitcoinBlockHash) -> BTreeSet<PublicKey> {
+        self.deprioritized_coordinators
+            .read()
+            .expect("BUG: Failed to acquire read lock")
+            .as_ref()
+            .filter(|(block_hash, _)| block_hash == chain_tip)
+            .map(|(_, signers)| signers.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Count a request against the given rate limit window, starting a new
+/// window if the current one is over, and return whether at most
+/// `max_per_minute` requests have been made in the window.
+fn count_api_request(window: &mut (Instant, u32), max_per_minute: u32) -> bool {
+    let (started_at, count) = window;
+    let now = Instant::now();
+    if now.duration_since(*started_at) >= API_REQUEST_RATE_WINDOW {
+        *started_at = now;
+        *count = 0;
+    }
+
+    *count = count.saturating_add(1);
+    *count <= max_per_minute
 }
 
 impl Default for SignerState {
@@ -155,6 +608,7 @@ impl Default for SignerState {
         Self {
             current_signer_set: Default::default(),
             current_limits: RwLock::new(SbtcLimits::zero()),
+            max_mintable: RwLock::new(None),
             registry_signing_set_info: RwLock::new(None),
             sbtc_contracts_deployed: Default::default(),
             sbtc_bitcoin_start_height: Default::default(),
@@ -163,6 +617,22 @@ impl Default for SignerState {
             // of the genesis block on bitcoin.
             bitcoin_chain_tip: RwLock::new(None),
             stacks_chain_tip: RwLock::new(None),
+            peer_heartbeats: RwLock::new(HashMap::new()),
+            peer_protocol_versions: RwLock::new(HashMap::new()),
+            safe_mode: AtomicBool::new(false),
+            contract_paused: AtomicBool::new(false),
+            database_available: tokio::sync::watch::Sender::new(true),
+            error_registry: ErrorRegistry::default(),
+            coordinator_activity: RwLock::new(None),
+            coordinator_takeover: RwLock::new(None),
+            silence_attestations: RwLock::new(None),
+            key_rotation_state: RwLock::new(KeyRotationState::Complete),
+            proof_request_windows: RwLock::new(HashMap::new()),
+            peer_proof_request_windows: RwLock::new(HashMap::new()),
+            precheck_request_window: RwLock::new((Instant::now(), 0)),
+            decision_sync_requests: RwLock::new(HashMap::new()),
+            reported_sweep_proofs: RwLock::new(HashSet::new()),
+            deprioritized_coordinators: RwLock::new(None),
         }
     }
 }
@@ -585,6 +1055,97 @@ mod tests {
         assert!(!signer_set.is_signer(&public_key));
     }
 
+    #[test]
+    fn signers_support_protocol_version_ignores_own_key() {
+        use super::*;
+
+        let state = SignerState::default();
+        let own_key = PublicKey::from_private_key(&PrivateKey::new(&mut OsRng));
+        let peer_key = PublicKey::from_private_key(&PrivateKey::new(&mut OsRng));
+        let signers = [own_key, peer_key];
+
+        // Peers that have not advertised a version are not supported.
+        assert!(!state.signers_support_protocol_version(&signers, &own_key, 2));
+
+        state.record_peer_protocol_version(peer_key.into(), 1);
+        assert!(state.signers_support_protocol_version(&signers, &own_key, 1));
+        assert!(!state.signers_support_protocol_version(&signers, &own_key, 2));
+
+        state.record_peer_protocol_version(peer_key.into(), 3);
+        assert!(state.signers_support_protocol_version(&signers, &own_key, 2));
+    }
+
+    #[test]
+    fn proof_requests_are_rate_limited_per_client() {
+        use super::*;
+
+        let state = SignerState::default();
+        assert!(state.check_proof_request_rate(0, 2));
+        assert!(state.check_proof_request_rate(0, 2));
+        assert!(!state.check_proof_request_rate(0, 2));
+        assert!(!state.check_proof_request_rate(0, 2));
+
+        // Each client has its own window.
+        assert!(state.check_proof_request_rate(1, 2));
+
+        // The pre-check endpoint has a window of its own.
+        assert!(state.check_precheck_request_rate(1));
+        assert!(!state.check_precheck_request_rate(1));
+    }
+
+    #[test]
+    fn decision_sync_requests_are_rate_limited_per_signer() {
+        use super::*;
+
+        let state = SignerState::default();
+        let signer1 = PublicKey::from_private_key(&PrivateKey::new(&mut OsRng));
+        let signer2 = PublicKey::from_private_key(&PrivateKey::new(&mut OsRng));
+
+        assert!(state.check_decision_sync_request_rate(signer1));
+        assert!(!state.check_decision_sync_request_rate(signer1));
+
+        // Each signer has its own cooldown.
+        assert!(state.check_decision_sync_request_rate(signer2));
+        assert!(!state.check_decision_sync_request_rate(signer2));
+    }
+
+    #[test]
+    fn peer_proof_requests_are_rate_limited_per_peer() {
+        use super::*;
+
+        let state = SignerState::default();
+        let peer1 = PublicKey::from_private_key(&PrivateKey::new(&mut OsRng));
+        let peer2 = PublicKey::from_private_key(&PrivateKey::new(&mut OsRng));
+
+        for _ in 0..PEER_PROOF_REQUESTS_PER_MINUTE {
+            assert!(state.check_peer_proof_request_rate(peer1));
+        }
+        assert!(!state.check_peer_proof_request_rate(peer1));
+
+        // Each peer has its own window, separate from the API clients.
+        assert!(state.check_peer_proof_request_rate(peer2));
+        assert!(state.check_proof_request_rate(0, 1));
+    }
+
+    #[test]
+    fn reported_sweep_proofs_are_forgotten_once_out_of_view() {
+        use fake::Fake as _;
+
+        use super::*;
+
+        let state = SignerState::default();
+        let txid1: BitcoinTxId = fake::Faker.fake();
+        let txid2: BitcoinTxId = fake::Faker.fake();
+
+        state.set_sweep_proof_reported(txid1);
+        state.set_sweep_proof_reported(txid2);
+        assert!(state.is_sweep_proof_reported(&txid1));
+
+        state.retain_reported_sweep_proofs(&HashSet::from([txid2]));
+        assert!(!state.is_sweep_proof_reported(&txid1));
+        assert!(state.is_sweep_proof_reported(&txid2));
+    }
+
     #[test]
     fn test_is_allowed_peer() {
         use super::*;
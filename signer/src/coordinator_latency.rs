@@ -0,0 +1,472 @@
+//! Participation-weighted coordinator election.
+//!
+//! In a geographically distributed signer set, a signer with high latency
+//! to its peers slows down every signing round that it coordinates. When
+//! `signer.coordinator_latency_window` is set, the coordinator election
+//! skips the signers that are chronically slow, using only data that is
+//! committed on the Stacks chain so that every signer computes the same
+//! result:
+//!
+//! 1. The coordinator of a stacks transaction stops collecting signatures
+//!    once it has the number required by the signers' multi-sig wallet,
+//!    and [`MultisigTx::finalize_transaction`] records the public key of
+//!    each signer that did not sign in the transaction's auth. So the
+//!    keys recorded in a mined contract call from the signers' wallet are
+//!    the keys of the signers that were slowest to respond. These cannot
+//!    be forged, since the transaction would not be valid otherwise.
+//! 2. After it observes a new bitcoin block, each signer fetches the
+//!    stacks blocks that it has not scanned yet and stores the multi-sig
+//!    contract calls to the sBTC contracts in them.
+//! 3. When electing the coordinator for a bitcoin block, the signers look
+//!    at the contract calls from their wallet on the canonical stacks
+//!    chain as of the bitcoin block [`COMMITMENT_DEPTH`] blocks below it,
+//!    within the configured window of bitcoin blocks. A signer that did
+//!    not sign at least three quarters of them is deprioritized. The
+//!    coordinator and its deputy are then elected from the remaining
+//!    signers as usual, or from the whole signer set if every signer is
+//!    deprioritized.
+//!
+//! By the time a bitcoin block is [`COMMITMENT_DEPTH`] blocks deep, every
+//! signer has fetched the stacks blocks anchored to it, so all signers
+//! elect the coordinator from the same transactions. A signer cannot
+//! change who gets deprioritized by misreporting, since there is nothing
+//! to report: the input is the mined transactions themselves.
+//!
+//! [`MultisigTx::finalize_transaction`]: crate::stacks::wallet::MultisigTx::finalize_transaction
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use blockstack_lib::chainstate::stacks::StacksTransaction;
+use blockstack_lib::chainstate::stacks::TransactionAuth;
+use blockstack_lib::chainstate::stacks::TransactionAuthField;
+use blockstack_lib::chainstate::stacks::TransactionPayload;
+use blockstack_lib::chainstate::stacks::TransactionSpendingCondition;
+use blockstack_lib::types::chainstate::StacksAddress;
+
+use crate::context::Context;
+use crate::error::Error;
+use crate::keys::PublicKey;
+use crate::stacks::api::StacksInteract as _;
+use crate::stacks::wallet::SignerWallet;
+use crate::storage::DbRead as _;
+use crate::storage::DbWrite as _;
+use crate::storage::model;
+
+/// The number of bitcoin blocks below the chain tip of the bitcoin block
+/// whose stacks chain is used for the coordinator election. All signers
+/// have the stacks blocks anchored to it by the time the chain tip is
+/// observed.
+pub const COMMITMENT_DEPTH: u16 = 2;
+
+/// The minimum number of transactions from the signers' wallet in the
+/// window before any signer is deprioritized. With fewer transactions a
+/// signer can miss most of them by chance.
+pub const MIN_SIGNER_TRANSACTIONS: usize = 4;
+
+/// Return the contract call in the given transaction if it is a call to
+/// one of the contracts deployed by the given deployer that was signed by
+/// an order independent multi-sig wallet.
+pub fn multisig_stacks_transaction(
+    tx: &StacksTransaction,
+    block_hash: model::StacksBlockHash,
+    deployer: &StacksAddress,
+) -> Option<model::MultisigStacksTransaction> {
+    let TransactionPayload::ContractCall(contract_call) = &tx.payload else {
+        return None;
+    };
+    if &contract_call.address != deployer {
+        return None;
+    }
+
+    use TransactionSpendingCondition::OrderIndependentMultisig;
+    let TransactionAuth::Standard(OrderIndependentMultisig(cond)) = &tx.auth else {
+        return None;
+    };
+
+    let non_signers = cond
+        .fields
+        .iter()
+        .filter_map(|field| match field {
+            TransactionAuthField::PublicKey(public_key) => Some(PublicKey::from(public_key)),
+            TransactionAuthField::Signature(_, _) => None,
+        })
+        .collect();
+
+    Some(model::MultisigStacksTransaction {
+        txid: tx.txid().into(),
+        block_hash,
+        signer_hash: cond.signer.0,
+        non_signers,
+    })
+}
+
+/// Fetch the stacks blocks anchored to the bitcoin blocks that the
+/// coordinator election may look at, that we have not scanned yet, and
+/// store the multi-sig contract calls to the sBTC contracts in them. This
+/// does nothing if participation weighting is disabled.
+pub async fn scan_stacks_blocks(
+    ctx: &impl Context,
+    chain_tip: &model::BitcoinBlockHash,
+) -> Result<(), Error> {
+    let Some(window) = ctx.config().signer.coordinator_latency_window else {
+        return Ok(());
+    };
+
+    let db = ctx.get_storage_mut();
+    let stacks_client = ctx.get_stacks_client();
+    let deployer = &ctx.config().signer.deployer;
+
+    let context_window = window.get().saturating_add(COMMITMENT_DEPTH);
+    let block_hashes = db
+        .get_unscanned_stacks_blocks(chain_tip, context_window)
+        .await?;
+    tracing::debug!(
+        num_blocks = block_hashes.len(),
+        "scanning stacks blocks for multi-sig contract calls"
+    );
+
+    for block_hash in block_hashes {
+        let block = stacks_client.get_block(&block_hash).await?;
+        for tx in &block.txs {
+            if let Some(multisig_tx) = multisig_stacks_transaction(tx, block_hash, deployer) {
+                db.write_multisig_stacks_transaction(&multisig_tx).await?;
+            }
+        }
+        db.write_scanned_stacks_block(&block_hash).await?;
+    }
+
+    Ok(())
+}
+
+/// Return the signers in the signer set that did not sign at least three
+/// quarters of the given transactions from the wallet with the given
+/// signer hash. Transactions from other wallets are ignored, and nobody is
+/// deprioritized if there are fewer than [`MIN_SIGNER_TRANSACTIONS`]
+/// transactions from the wallet.
+pub fn deprioritized_signers(
+    transactions: &[model::MultisigStacksTransaction],
+    signer_set: &BTreeSet<PublicKey>,
+    signer_hash: &[u8; 20],
+) -> BTreeSet<PublicKey> {
+    // The same transaction can be included in more than one stacks block
+    // across forks, so we only count it once.
+    let mut seen = BTreeSet::new();
+    let mut misses: BTreeMap<PublicKey, usize> = BTreeMap::new();
+
+    let wallet_transactions = transactions
+        .iter()
+        .filter(|tx| &tx.signer_hash == signer_hash)
+        .filter(|tx| seen.insert(tx.txid));
+
+    let mut count = 0;
+    for tx in wallet_transactions {
+        count += 1;
+        let non_signers: BTreeSet<&PublicKey> = tx
+            .non_signers
+            .iter()
+            .filter(|public_key| signer_set.contains(public_key))
+            .collect();
+
+        for public_key in non_signers {
+            *misses.entry(*public_key).or_default() += 1;
+        }
+    }
+
+    if count < MIN_SIGNER_TRANSACTIONS {
+        return BTreeSet::new();
+    }
+
+    misses
+        .into_iter()
+        .filter(|(_, missed)| missed * 4 >= count * 3)
+        .map(|(public_key, _)| public_key)
+        .collect()
+}
+
+/// Return the signers that the coordinator, and its deputy, are elected
+/// from. These are the signers in the signer set that are not
+/// deprioritized, unless all of them are.
+pub fn coordinator_candidates(
+    signer_set: BTreeSet<PublicKey>,
+    deprioritized: &BTreeSet<PublicKey>,
+) -> BTreeSet<PublicKey> {
+    let candidates: BTreeSet<PublicKey> = signer_set.difference(deprioritized).copied().collect();
+    if candidates.is_empty() {
+        signer_set
+    } else {
+        candidates
+    }
+}
+
+/// Work out which signers are deprioritized in the coordinator election
+/// for the given bitcoin chain tip and record them in the signer state.
+/// This does nothing if participation weighting is disabled.
+pub async fn update_deprioritized_coordinators(
+    ctx: &impl Context,
+    chain_tip: &model::BitcoinBlockHash,
+) -> Result<(), Error> {
+    let Some(window) = ctx.config().signer.coordinator_latency_window else {
+        return Ok(());
+    };
+
+    let db = ctx.get_storage();
+    let mut ancestor = *chain_tip;
+    for _ in 0..COMMITMENT_DEPTH {
+        let block = db
+            .get_bitcoin_block(&ancestor)
+            .await?
+            .ok_or_else(|| Error::UnknownBitcoinBlock(ancestor.into()))?;
+        ancestor = block.parent_hash;
+    }
+
+    let deprioritized = match db.get_stacks_chain_tip(&ancestor).await? {
+        Some(stacks_chain_tip) => {
+            let transactions = db
+                .get_multisig_stacks_transactions(
+                    &ancestor,
+                    &stacks_chain_tip.block_hash,
+                    window.get(),
+                )
+                .await?;
+            let wallet = SignerWallet::load(ctx).await?;
+            let signer_hash = wallet.address().bytes().0;
+            deprioritized_signers(&transactions, wallet.public_keys(), &signer_hash)
+        }
+        None => BTreeSet::new(),
+    };
+
+    if !deprioritized.is_empty() {
+        tracing::info!(
+            ?deprioritized,
+            "deprioritizing slow signers in the coordinator election"
+        );
+    }
+
+    ctx.state()
+        .set_deprioritized_coordinators(*chain_tip, deprioritized);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use blockstack_lib::chainstate::nakamoto::NakamotoBlock;
+    use blockstack_lib::chainstate::nakamoto::NakamotoBlockHeader;
+    use blockstack_lib::chainstate::stacks::TransactionContractCall;
+    use blockstack_lib::clarity::vm::ClarityName;
+    use blockstack_lib::clarity::vm::ContractName;
+    use fake::Fake as _;
+    use fake::Faker;
+
+    use crate::config::NetworkKind;
+    use crate::keys::PrivateKey;
+    use crate::signature::sign_stacks_tx;
+    use crate::stacks::wallet::MultisigTx;
+    use crate::testing::context::*;
+    use crate::testing::get_rng;
+    use crate::transaction_coordinator::coordinator_public_key;
+
+    use super::*;
+
+    /// Return a contract call to the given deployer's contracts from the
+    /// given wallet, signed by the given signers.
+    fn signed_contract_call(
+        wallet: &SignerWallet,
+        deployer: &StacksAddress,
+        signers: &[PrivateKey],
+    ) -> StacksTransaction {
+        let payload = TransactionPayload::ContractCall(TransactionContractCall {
+            address: deployer.clone(),
+            contract_name: ContractName::from("sbtc-registry"),
+            function_name: ClarityName::from("rotate-keys"),
+            function_args: Vec::new(),
+        });
+        let mut multisig_tx = MultisigTx::new_tx(&payload, wallet, 1000);
+        for private_key in signers {
+            let signature = sign_stacks_tx(multisig_tx.tx(), private_key);
+            multisig_tx.add_signature(signature).unwrap();
+        }
+        multisig_tx.finalize_transaction()
+    }
+
+    fn multisig_tx(
+        signer_hash: [u8; 20],
+        non_signers: Vec<PublicKey>,
+    ) -> model::MultisigStacksTransaction {
+        model::MultisigStacksTransaction {
+            txid: Faker.fake(),
+            block_hash: Faker.fake(),
+            signer_hash,
+            non_signers,
+        }
+    }
+
+    #[test]
+    fn non_signers_are_read_from_multisig_contract_calls() {
+        let mut rng = get_rng();
+        let private_keys: Vec<PrivateKey> = std::iter::repeat_with(|| PrivateKey::new(&mut rng))
+            .take(3)
+            .collect();
+        let public_keys: Vec<PublicKey> = private_keys
+            .iter()
+            .map(PublicKey::from_private_key)
+            .collect();
+        let wallet = SignerWallet::new(&public_keys, 2, NetworkKind::Regtest, 0).unwrap();
+        let deployer = wallet.address().clone();
+        let block_hash: model::StacksBlockHash = Faker.fake();
+
+        let tx = signed_contract_call(&wallet, &deployer, &private_keys[..2]);
+        let multisig_tx = multisig_stacks_transaction(&tx, block_hash, &deployer).unwrap();
+
+        assert_eq!(multisig_tx.txid, tx.txid().into());
+        assert_eq!(multisig_tx.block_hash, block_hash);
+        assert_eq!(&multisig_tx.signer_hash, &wallet.address().bytes().0);
+        assert_eq!(multisig_tx.non_signers, vec![public_keys[2]]);
+
+        // Calls to contracts that were deployed by someone else are not
+        // ours.
+        let other_deployer = StacksAddress::burn_address(false);
+        assert!(multisig_stacks_transaction(&tx, block_hash, &other_deployer).is_none());
+    }
+
+    #[test]
+    fn signers_that_miss_most_transactions_are_deprioritized() {
+        let signer_set: BTreeSet<PublicKey> =
+            std::iter::repeat_with(|| Faker.fake()).take(5).collect();
+        let signers: Vec<PublicKey> = signer_set.iter().copied().collect();
+        let slow = signers[0];
+        let outsider: PublicKey = Faker.fake();
+        let signer_hash = [1; 20];
+
+        // The slow signer missed two of three transactions, and there are
+        // too few transactions to deprioritize anyone. Transactions from
+        // other wallets and keys outside of the signer set are ignored.
+        let mut transactions = vec![
+            multisig_tx(signer_hash, vec![slow, signers[1]]),
+            multisig_tx(signer_hash, vec![slow, outsider]),
+            multisig_tx(signer_hash, vec![signers[1], signers[2]]),
+            multisig_tx([2; 20], vec![slow, signers[2]]),
+        ];
+        let deprioritized = deprioritized_signers(&transactions, &signer_set, &signer_hash);
+        assert!(deprioritized.is_empty());
+
+        // The same transaction in another block does not count twice.
+        let duplicate = model::MultisigStacksTransaction {
+            block_hash: Faker.fake(),
+            ..transactions[0].clone()
+        };
+        transactions.push(duplicate);
+        let deprioritized = deprioritized_signers(&transactions, &signer_set, &signer_hash);
+        assert!(deprioritized.is_empty());
+
+        // Missing three of four transactions gets the slow signer
+        // deprioritized, while missing two of them does not.
+        transactions.push(multisig_tx(signer_hash, vec![slow, signers[2]]));
+        let deprioritized = deprioritized_signers(&transactions, &signer_set, &signer_hash);
+        assert_eq!(deprioritized, BTreeSet::from([slow]));
+
+        // The slow signer is never elected, unless everyone is slow.
+        let candidates = coordinator_candidates(signer_set.clone(), &deprioritized);
+        assert_eq!(candidates.len(), 4);
+        for _ in 0..20 {
+            let chain_tip: model::BitcoinBlockHash = Faker.fake();
+            assert_ne!(coordinator_public_key(&chain_tip, &candidates), Some(slow));
+        }
+        let candidates = coordinator_candidates(signer_set.clone(), &signer_set);
+        assert_eq!(candidates, signer_set);
+    }
+
+    #[tokio::test]
+    async fn deprioritized_coordinators_use_committed_stacks_transactions() {
+        let mut rng = get_rng();
+        let mut ctx = TestContext::default_mocked();
+
+        let private_keys: Vec<PrivateKey> = std::iter::repeat_with(|| PrivateKey::new(&mut rng))
+            .take(3)
+            .collect();
+        let public_keys: Vec<PublicKey> = private_keys
+            .iter()
+            .map(PublicKey::from_private_key)
+            .collect();
+        let config = &mut ctx.config_mut().signer;
+        config.coordinator_latency_window = std::num::NonZeroU16::new(10);
+        config.bootstrap_signing_set = public_keys.iter().copied().collect();
+        config.bootstrap_signatures_required = 2;
+        let wallet = SignerWallet::load(&ctx).await.unwrap();
+        let deployer = ctx.config().signer.deployer.clone();
+
+        // A chain of bitcoin blocks, with a stacks block anchored to the
+        // one that is COMMITMENT_DEPTH blocks below the chain tip.
+        let db = ctx.get_storage_mut();
+        let mut bitcoin_blocks: Vec<model::BitcoinBlock> = vec![Faker.fake()];
+        for _ in 0..COMMITMENT_DEPTH {
+            let parent = bitcoin_blocks.last().unwrap();
+            let block = model::BitcoinBlock {
+                parent_hash: parent.block_hash,
+                block_height: parent.block_height + 1,
+                ..Faker.fake()
+            };
+            bitcoin_blocks.push(block);
+        }
+        for block in &bitcoin_blocks {
+            db.write_bitcoin_block(block).await.unwrap();
+        }
+        let chain_tip = bitcoin_blocks.last().unwrap().block_hash;
+        let stacks_block = model::StacksBlock {
+            bitcoin_anchor: bitcoin_blocks[0].block_hash,
+            ..Faker.fake()
+        };
+        db.write_stacks_block(&stacks_block).await.unwrap();
+
+        // The last signer never signs, and the stacks block also has a
+        // contract call that is not ours.
+        let mut txs: Vec<StacksTransaction> = (0..MIN_SIGNER_TRANSACTIONS)
+            .map(|_| signed_contract_call(&wallet, &deployer, &private_keys[..2]))
+            .collect();
+        let other_deployer = StacksAddress::burn_address(false);
+        txs.push(signed_contract_call(
+            &wallet,
+            &other_deployer,
+            &private_keys[1..],
+        ));
+        let block = NakamotoBlock {
+            header: NakamotoBlockHeader::empty(),
+            txs,
+        };
+        ctx.with_stacks_client(|client| {
+            client
+                .expect_get_block()
+                .once()
+                .returning(move |_| Box::pin(std::future::ready(Ok(block.clone()))));
+        })
+        .await;
+
+        scan_stacks_blocks(&ctx, &chain_tip).await.unwrap();
+        // The block is only fetched once.
+        scan_stacks_blocks(&ctx, &chain_tip).await.unwrap();
+
+        let unscanned = db
+            .get_unscanned_stacks_blocks(&chain_tip, 10)
+            .await
+            .unwrap();
+        assert!(unscanned.is_empty());
+
+        update_deprioritized_coordinators(&ctx, &chain_tip)
+            .await
+            .unwrap();
+        let state = ctx.state();
+        assert_eq!(
+            state.deprioritized_coordinators(&chain_tip),
+            BTreeSet::from([public_keys[2]])
+        );
+        let parent = bitcoin_blocks[1].block_hash;
+        assert!(state.deprioritized_coordinators(&parent).is_empty());
+
+        let candidates = ctx.coordinator_candidates(&chain_tip);
+        assert!(!candidates.contains(&public_keys[2]));
+        assert_eq!(
+            ctx.coordinator_candidates(&parent),
+            ctx.coordinator_signer_set()
+        );
+    }
+}
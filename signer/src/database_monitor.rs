@@ -0,0 +1,187 @@
+//! This module contains a background task that detects when the database
+//! is unreachable and pauses the signer's event loops until it recovers.
+//!
+//! When the postgres primary fails over, the connections in the pool
+//! break, often in the middle of a transaction, and every query fails
+//! until the new primary accepts connections. Rather than having each
+//! event loop fail on every event it sees during the failover, the
+//! database monitor probes the database and records whether it is
+//! reachable in the signer state. The event loops wait on this before
+//! handling new events, so they pick up where they left off once the
+//! database is back without the signer having to be restarted.
+//!
+//! While the database is unreachable the monitor probes it with an
+//! exponential backoff, so that a failover is noticed as soon as it is
+//! over without hammering the database while it recovers.
+
+use std::time::Duration;
+
+use crate::context::Context;
+use crate::metrics::Metrics;
+use crate::storage::DbRead as _;
+
+/// How often the database monitor checks the database while it is
+/// reachable.
+const DATABASE_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long the database monitor waits before the first check after the
+/// database is found to be unreachable. The delay doubles after each
+/// failed check, up to [`MAX_RECONNECT_BACKOFF`].
+const MIN_RECONNECT_BACKOFF: Duration = Duration::from_millis(250);
+
+/// The longest that the database monitor waits between checks while the
+/// database is unreachable.
+const MAX_RECONNECT_BACKOFF: Duration = Duration::from_secs(10);
+
+/// A background task that tracks whether the database is reachable.
+pub struct DatabaseMonitor<C> {
+    /// Signer context.
+    context: C,
+}
+
+impl<C> DatabaseMonitor<C>
+where
+    C: Context,
+{
+    /// Creates a new DatabaseMonitor.
+    pub fn new(context: C) -> Self {
+        Self { context }
+    }
+
+    /// Runs the DatabaseMonitor, which checks the database every
+    /// [`DATABASE_CHECK_INTERVAL`] while it is reachable, and with an
+    /// exponential backoff while it is not, until the signer is shut down.
+    pub async fn run(self) {
+        let mut term = self.context.get_termination_handle();
+        let mut backoff = None;
+        loop {
+            let delay = backoff.unwrap_or(DATABASE_CHECK_INTERVAL);
+            tokio::select! {
+                _ = term.wait_for_shutdown() => {
+                    break;
+                }
+                _ = tokio::time::sleep(delay) => {
+                    backoff = next_backoff(self.check().await, backoff);
+                }
+            }
+        }
+        tracing::info!("database monitor has stopped");
+    }
+
+    /// Check whether the database is reachable, recording the outcome in
+    /// the signer state. Returns whether the database is reachable.
+    pub async fn check(&self) -> bool {
+        let result = self
+            .context
+            .get_storage()
+            .get_bitcoin_canonical_chain_tip()
+            .await;
+
+        let available = match result {
+            Ok(_) => true,
+            Err(error) if error.is_transient_storage_error() => {
+                tracing::debug!(%error, "database health check failed");
+                false
+            }
+            // Any other error means that we reached the database, so it
+            // is not a reason to pause the event loops.
+            Err(error) => {
+                tracing::warn!(%error, "unexpected error during the database health check");
+                true
+            }
+        };
+
+        let was_available = self.context.state().set_database_available(available);
+        Metrics::record_database_check(available, was_available);
+
+        match (was_available, available) {
+            (true, false) => {
+                tracing::warn!("database is unreachable; pausing the event loops");
+            }
+            (false, true) => {
+                tracing::info!("database is reachable again; resuming the event loops");
+            }
+            _ => {}
+        }
+
+        available
+    }
+}
+
+/// Return the backoff to use before the next check given the outcome of
+/// the last check and the backoff used before it, if any. There is no
+/// backoff while the database is reachable.
+fn next_backoff(available: bool, backoff: Option<Duration>) -> Option<Duration> {
+    if available {
+        return None;
+    }
+    let backoff = match backoff {
+        Some(backoff) => (backoff * 2).min(MAX_RECONNECT_BACKOFF),
+        None => MIN_RECONNECT_BACKOFF,
+    };
+    Some(backoff)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::context::*;
+
+    use super::*;
+
+    #[test]
+    fn backoff_grows_while_the_database_is_unreachable() {
+        let mut backoff = None;
+        let mut delays = Vec::new();
+        for _ in 0..8 {
+            backoff = next_backoff(false, backoff);
+            delays.push(backoff.unwrap().as_millis());
+        }
+
+        let expected = [250, 500, 1000, 2000, 4000, 8000, 10000, 10000];
+        assert_eq!(delays, expected);
+
+        // Once the database is reachable we go back to the usual interval.
+        assert_eq!(next_backoff(true, backoff), None);
+    }
+
+    #[tokio::test]
+    async fn event_loops_wait_for_the_database() {
+        let ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .build();
+
+        let monitor = DatabaseMonitor::new(ctx.clone());
+        assert!(monitor.check().await);
+        assert!(ctx.wait_for_database().await);
+
+        // Simulate a failover that the monitor has noticed.
+        ctx.state().set_database_available(false);
+        let waiter = tokio::spawn({
+            let ctx = ctx.clone();
+            async move { ctx.wait_for_database().await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!waiter.is_finished());
+
+        // The next successful check resumes the waiting event loops.
+        assert!(monitor.check().await);
+        assert!(ctx.state().is_database_available());
+        assert!(waiter.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn waiting_for_the_database_stops_on_shutdown() {
+        let ctx = TestContext::default_mocked();
+        ctx.state().set_database_available(false);
+
+        let waiter = tokio::spawn({
+            let ctx = ctx.clone();
+            async move { ctx.wait_for_database().await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        ctx.get_termination_handle().signal_shutdown();
+        assert!(!waiter.await.unwrap());
+    }
+}
@@ -0,0 +1,650 @@
+//! # Decision policies
+//!
+//! The request decider asks a chain of [`DecisionPolicy`]s whether this
+//! signer accepts each deposit and withdrawal request. The chain is
+//! selected by name with the `signer.decision_policies` setting and is
+//! evaluated in order. The first policy that rejects a request decides
+//! it, so a request is only accepted when every policy in the chain
+//! accepts it.
+//!
+//! The default chain only has the [`BlocklistPolicy`], which is how the
+//! request decider has always decided requests. Operators with other
+//! acceptance rules, such as jurisdiction based ones, can implement
+//! [`DecisionPolicy`], register their policy with the request decider, and
+//! add its name to the setting.
+//!
+//! Operators can trade screening cost against throughput with amount
+//! based [`ScreeningTiers`]: small requests can be accepted by the
+//! [`BlocklistPolicy`] without calling the blocklist client, while large
+//! ones can be screened in full.
+//!
+//! Operators can also place an override on a specific request, which
+//! forces this signer to accept or reject it. An active override takes
+//! precedence over the whole chain, see [`model::RequestOverride`].
+
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::blocklist_client::BlocklistChecker;
+use crate::config::BlocklistClientConfig;
+use crate::context::SbtcLimits;
+use crate::deposit_precheck::DepositPrecheck;
+use crate::deposit_precheck::DepositVerdict;
+use crate::error::Error;
+use crate::metrics::Metrics;
+use crate::storage::model;
+use crate::storage::model::BitcoinBlockHash;
+
+/// The name of the [`BlocklistPolicy`].
+pub const BLOCKLIST_POLICY_NAME: &str = "blocklist";
+
+/// The reason given by the [`BlocklistPolicy`] when it rejects a
+/// withdrawal request because of its recipient address.
+pub const BLOCKLISTED_RECIPIENT_REASON: &str =
+    "the blocklist client does not accept the recipient address";
+
+/// The reason given by the [`BlocklistPolicy`] when it rejects a deposit
+/// request because of one of its sender addresses.
+pub const BLOCKLISTED_SENDER_REASON: &str = "the blocklist client does not accept a sender address";
+
+/// The future returned when a [`DecisionPolicy`] evaluates a request.
+pub type PolicyFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<PolicyDecision, Error>> + Send + 'a>>;
+
+/// The outcome of evaluating a request with a [`DecisionPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyDecision {
+    /// The policy accepts the request.
+    Accept,
+    /// The policy rejects the request.
+    Reject {
+        /// Why the policy rejected the request.
+        reason: String,
+    },
+}
+
+impl From<&model::RequestOverride> for PolicyDecision {
+    fn from(request_override: &model::RequestOverride) -> Self {
+        match request_override.directive {
+            model::OverrideDirective::ForceAccept => PolicyDecision::Accept,
+            model::OverrideDirective::ForceReject => PolicyDecision::Reject {
+                reason: format!("operator override: {}", request_override.reason),
+            },
+        }
+    }
+}
+
+/// Everything that the request decider knows about a deposit request
+/// when it decides whether to accept it.
+#[derive(Debug)]
+pub struct DepositDecisionContext<'a> {
+    /// The deposit request.
+    pub request: &'a model::DepositRequest,
+    /// Whether this signer is part of the signing set associated with the
+    /// public key locking the deposit.
+    pub can_sign: bool,
+    /// The bitcoin chain tip used when deciding on the request.
+    pub chain_tip: &'a BitcoinBlockHash,
+    /// The bitcoin network that the signer is running on.
+    pub network: bitcoin::Network,
+}
+
+/// Everything that the request decider knows about a withdrawal request
+/// when it decides whether to accept it.
+#[derive(Debug)]
+pub struct WithdrawalDecisionContext<'a> {
+    /// The withdrawal request.
+    pub request: &'a model::WithdrawalRequest,
+    /// The bitcoin chain tip used when deciding on the request.
+    pub chain_tip: &'a BitcoinBlockHash,
+    /// The bitcoin network that the signer is running on.
+    pub network: bitcoin::Network,
+}
+
+/// A rule for deciding whether this signer accepts a request.
+pub trait DecisionPolicy: std::fmt::Debug + Send + Sync {
+    /// The name of the policy, as used in the `signer.decision_policies`
+    /// setting.
+    fn name(&self) -> &str;
+
+    /// Decide whether to accept the deposit request.
+    fn evaluate_deposit<'a>(&'a self, ctx: &'a DepositDecisionContext<'a>) -> PolicyFuture<'a>;
+
+    /// Decide whether to accept the withdrawal request.
+    fn evaluate_withdrawal<'a>(
+        &'a self,
+        ctx: &'a WithdrawalDecisionContext<'a>,
+    ) -> PolicyFuture<'a>;
+}
+
+/// How thoroughly the [`BlocklistPolicy`] screens a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum ScreeningTier {
+    /// The request is accepted without calling the blocklist client.
+    AutoApprove,
+    /// The request is screened as configured. Withdrawal recipients are
+    /// only screened when that is enabled, and their screening outcome
+    /// may be cached.
+    Standard,
+    /// The request is always screened with a fresh call to the blocklist
+    /// client, including the recipients of withdrawals.
+    Full,
+}
+
+/// The amount thresholds, in sats, that place requests in a
+/// [`ScreeningTier`]. Every request is in the standard tier by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ScreeningTiers {
+    /// Requests for fewer sats than this are auto-approved.
+    pub auto_approve_below: Option<u64>,
+    /// Requests for more sats than this are screened in full.
+    pub full_screening_above: Option<u64>,
+}
+
+impl ScreeningTiers {
+    /// Return the screening tier of a request for the given amount.
+    pub fn tier(&self, amount: u64) -> ScreeningTier {
+        if self
+            .full_screening_above
+            .is_some_and(|limit| amount > limit)
+        {
+            ScreeningTier::Full
+        } else if self.auto_approve_below.is_some_and(|limit| amount < limit) {
+            ScreeningTier::AutoApprove
+        } else {
+            ScreeningTier::Standard
+        }
+    }
+}
+
+impl From<&BlocklistClientConfig> for ScreeningTiers {
+    fn from(config: &BlocklistClientConfig) -> Self {
+        Self {
+            auto_approve_below: config.auto_approve_below,
+            full_screening_above: config.full_screening_above,
+        }
+    }
+}
+
+/// The default policy, which rejects requests involving addresses that the
+/// blocklist client does not accept.
+///
+/// For deposits these are the addresses of all inputs of the deposit
+/// transaction, while for withdrawals it is the recipient address.
+/// Deposits are decided with the [`DepositPrecheck`], so they are also
+/// rejected when they fail the deposit script policy. When the blocklist
+/// client is not configured, no address is rejected. Otherwise, how a
+/// request is screened depends on its [`ScreeningTier`].
+pub struct BlocklistPolicy<'b, B> {
+    blocklist_checker: Option<&'b B>,
+    screen_withdrawal_recipients: bool,
+    screening_tiers: ScreeningTiers,
+}
+
+impl<'b, B> BlocklistPolicy<'b, B> {
+    /// Create a new blocklist policy that screens withdrawal recipients
+    /// and puts every request in the standard screening tier.
+    pub fn new(blocklist_checker: Option<&'b B>) -> Self {
+        Self {
+            blocklist_checker,
+            screen_withdrawal_recipients: true,
+            screening_tiers: ScreeningTiers::default(),
+        }
+    }
+
+    /// Set whether the recipient addresses of withdrawal requests are
+    /// screened.
+    pub fn with_withdrawal_screening(mut self, enabled: bool) -> Self {
+        self.screen_withdrawal_recipients = enabled;
+        self
+    }
+
+    /// Set the amount thresholds of the screening tiers.
+    pub fn with_screening_tiers(mut self, screening_tiers: ScreeningTiers) -> Self {
+        self.screening_tiers = screening_tiers;
+        self
+    }
+}
+
+impl<B> std::fmt::Debug for BlocklistPolicy<'_, B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BlocklistPolicy")
+            .field("enabled", &self.blocklist_checker.is_some())
+            .field(
+                "screen_withdrawal_recipients",
+                &self.screen_withdrawal_recipients,
+            )
+            .field("screening_tiers", &self.screening_tiers)
+            .finish()
+    }
+}
+
+impl<B> DecisionPolicy for BlocklistPolicy<'_, B>
+where
+    B: BlocklistChecker + Sync,
+{
+    fn name(&self) -> &str {
+        BLOCKLIST_POLICY_NAME
+    }
+
+    fn evaluate_deposit<'a>(&'a self, ctx: &'a DepositDecisionContext<'a>) -> PolicyFuture<'a> {
+        Box::pin(async move {
+            let req = ctx.request;
+            let tier = self.screening_tiers.tier(req.amount);
+
+            // Auto-approved deposits skip the blocklist client but not
+            // the rest of the pre-check. The blocklist client never
+            // caches deposit screenings, so the standard and full tiers
+            // screen deposits the same way.
+            let blocklist_checker = self
+                .blocklist_checker
+                .filter(|_| tier != ScreeningTier::AutoApprove);
+            // The sBTC limits change over time and are enforced when the
+            // deposit is swept, so a decision must not depend on the
+            // limits at the time that it was made.
+            let limits = SbtcLimits::unlimited();
+            let precheck = DepositPrecheck {
+                limits: &limits,
+                blocklist_checker,
+                network: ctx.network,
+                // Whether the deposit is locked with a key that we can
+                // sign for is part of the decision context.
+                signers_public_key: None,
+            };
+            let verdict = precheck.can_accept_deposit_request(req).await?;
+
+            if self.blocklist_checker.is_some() {
+                let can_accept = verdict != DepositVerdict::Blocklisted;
+                Metrics::increment_screening_decisions("deposit", tier, can_accept);
+            }
+            Ok(deposit_precheck_decision(verdict))
+        })
+    }
+
+    fn evaluate_withdrawal<'a>(
+        &'a self,
+        ctx: &'a WithdrawalDecisionContext<'a>,
+    ) -> PolicyFuture<'a> {
+        Box::pin(async move {
+            let Some(client) = self.blocklist_checker else {
+                return Ok(PolicyDecision::Accept);
+            };
+
+            let req = ctx.request;
+            let tier = self.screening_tiers.tier(req.amount);
+            let skip_screening = match tier {
+                ScreeningTier::AutoApprove => true,
+                ScreeningTier::Standard => !self.screen_withdrawal_recipients,
+                ScreeningTier::Full => false,
+            };
+            if skip_screening {
+                Metrics::increment_screening_decisions("withdrawal", tier, true);
+                return Ok(PolicyDecision::Accept);
+            }
+
+            let receiver_address = bitcoin::Address::from_script(
+                &req.recipient,
+                ctx.network.params(),
+            )
+            .map_err(|err| {
+                Error::WithdrawalBitcoinAddressFromScript(err, req.request_id, req.block_hash)
+            })?;
+
+            let receiver_address = receiver_address.to_string();
+            let can_accept = if tier == ScreeningTier::Full {
+                client.can_accept(&receiver_address).await
+            } else {
+                client
+                    .can_accept_withdrawal_recipient(&receiver_address)
+                    .await
+            }
+            .inspect_err(|error| tracing::error!(%error, "blocklist client issue"))?;
+
+            Metrics::increment_screening_decisions("withdrawal", tier, can_accept);
+            if can_accept {
+                Ok(PolicyDecision::Accept)
+            } else {
+                Ok(PolicyDecision::Reject {
+                    reason: BLOCKLISTED_RECIPIENT_REASON.to_string(),
+                })
+            }
+        })
+    }
+}
+
+fn deposit_precheck_decision(verdict: DepositVerdict) -> PolicyDecision {
+    match verdict {
+        DepositVerdict::Ok => PolicyDecision::Accept,
+        DepositVerdict::Blocklisted => PolicyDecision::Reject {
+            reason: BLOCKLISTED_SENDER_REASON.to_string(),
+        },
+        verdict => PolicyDecision::Reject {
+            reason: format!("the deposit pre-check does not accept the deposit: {verdict:?}"),
+        },
+    }
+}
+
+/// An ordered chain of decision policies.
+#[derive(Debug)]
+pub struct DecisionPolicyChain<'a> {
+    policies: Vec<&'a dyn DecisionPolicy>,
+}
+
+impl<'a> DecisionPolicyChain<'a> {
+    /// Select the policies with the given names, in the given order, from
+    /// the available policies.
+    pub fn select<S>(names: &[S], available: &[&'a dyn DecisionPolicy]) -> Result<Self, Error>
+    where
+        S: AsRef<str>,
+    {
+        let policies = names
+            .iter()
+            .map(|name| {
+                available
+                    .iter()
+                    .find(|policy| policy.name() == name.as_ref())
+                    .copied()
+                    .ok_or_else(|| Error::UnknownDecisionPolicy(name.as_ref().to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { policies })
+    }
+
+    /// Evaluate the deposit request with each policy in order, stopping at
+    /// the first one that rejects it.
+    pub async fn evaluate_deposit(
+        &self,
+        ctx: &DepositDecisionContext<'_>,
+    ) -> Result<PolicyDecision, Error> {
+        for policy in &self.policies {
+            let decision = policy.evaluate_deposit(ctx).await?;
+            if let PolicyDecision::Reject { reason } = &decision {
+                tracing::info!(policy = %policy.name(), %reason, "deposit request rejected by policy");
+                return Ok(decision);
+            }
+        }
+
+        Ok(PolicyDecision::Accept)
+    }
+
+    /// Evaluate the withdrawal request with each policy in order, stopping
+    /// at the first one that rejects it.
+    pub async fn evaluate_withdrawal(
+        &self,
+        ctx: &WithdrawalDecisionContext<'_>,
+    ) -> Result<PolicyDecision, Error> {
+        for policy in &self.policies {
+            let decision = policy.evaluate_withdrawal(ctx).await?;
+            if let PolicyDecision::Reject { reason } = &decision {
+                tracing::info!(policy = %policy.name(), %reason, "withdrawal request rejected by policy");
+                return Ok(decision);
+            }
+        }
+
+        Ok(PolicyDecision::Accept)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use fake::Fake as _;
+    use test_case::test_case;
+
+    use super::*;
+
+    /// A policy that always returns the same decision and counts how many
+    /// times it was asked.
+    #[derive(Debug)]
+    struct FixedPolicy {
+        name: &'static str,
+        decision: PolicyDecision,
+        calls: AtomicUsize,
+    }
+
+    impl FixedPolicy {
+        fn new(name: &'static str, decision: PolicyDecision) -> Self {
+            Self {
+                name,
+                decision,
+                calls: AtomicUsize::new(0),
+            }
+        }
+    }
+
+    impl DecisionPolicy for FixedPolicy {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn evaluate_deposit<'a>(&'a self, _: &'a DepositDecisionContext<'a>) -> PolicyFuture<'a> {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            Box::pin(async move { Ok(self.decision.clone()) })
+        }
+
+        fn evaluate_withdrawal<'a>(
+            &'a self,
+            _: &'a WithdrawalDecisionContext<'a>,
+        ) -> PolicyFuture<'a> {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            Box::pin(async move { Ok(self.decision.clone()) })
+        }
+    }
+
+    fn reject(reason: &str) -> PolicyDecision {
+        PolicyDecision::Reject { reason: reason.to_string() }
+    }
+
+    #[tokio::test]
+    async fn chain_stops_at_the_first_rejection() {
+        let accept = FixedPolicy::new("accept", PolicyDecision::Accept);
+        let deny = FixedPolicy::new("deny", reject("jurisdiction"));
+        let never = FixedPolicy::new("never", reject("unreachable"));
+        let available: [&dyn DecisionPolicy; 3] = [&never, &deny, &accept];
+
+        let chain = DecisionPolicyChain::select(&["accept", "deny", "never"], &available).unwrap();
+
+        let request: model::WithdrawalRequest = fake::Faker.fake();
+        let ctx = WithdrawalDecisionContext {
+            request: &request,
+            chain_tip: &fake::Faker.fake(),
+            network: bitcoin::Network::Regtest,
+        };
+
+        let decision = chain.evaluate_withdrawal(&ctx).await.unwrap();
+        assert_eq!(decision, reject("jurisdiction"));
+        assert_eq!(accept.calls.load(Ordering::Relaxed), 1);
+        assert_eq!(deny.calls.load(Ordering::Relaxed), 1);
+        assert_eq!(never.calls.load(Ordering::Relaxed), 0);
+    }
+
+    #[tokio::test]
+    async fn chain_accepts_when_every_policy_accepts() {
+        let first = FixedPolicy::new("first", PolicyDecision::Accept);
+        let second = FixedPolicy::new("second", PolicyDecision::Accept);
+        let available: [&dyn DecisionPolicy; 2] = [&first, &second];
+
+        let chain = DecisionPolicyChain::select(&["second", "first"], &available).unwrap();
+
+        let request: model::DepositRequest = fake::Faker.fake();
+        let ctx = DepositDecisionContext {
+            request: &request,
+            can_sign: true,
+            chain_tip: &fake::Faker.fake(),
+            network: bitcoin::Network::Regtest,
+        };
+
+        let decision = chain.evaluate_deposit(&ctx).await.unwrap();
+        assert_eq!(decision, PolicyDecision::Accept);
+        assert_eq!(first.calls.load(Ordering::Relaxed), 1);
+        assert_eq!(second.calls.load(Ordering::Relaxed), 1);
+    }
+
+    /// A blocklist checker that does not accept any address.
+    struct RejectAll;
+
+    impl BlocklistChecker for RejectAll {
+        async fn can_accept(&self, _: &str) -> Result<bool, Error> {
+            Ok(false)
+        }
+    }
+
+    #[test_case(true, reject(BLOCKLISTED_RECIPIENT_REASON); "screening enabled")]
+    #[test_case(false, PolicyDecision::Accept; "screening disabled")]
+    #[tokio::test]
+    async fn blocklist_policy_withdrawal_screening(enabled: bool, expected: PolicyDecision) {
+        let policy = BlocklistPolicy::new(Some(&RejectAll)).with_withdrawal_screening(enabled);
+
+        let request: model::WithdrawalRequest = fake::Faker.fake();
+        let ctx = WithdrawalDecisionContext {
+            request: &request,
+            chain_tip: &fake::Faker.fake(),
+            network: bitcoin::Network::Regtest,
+        };
+
+        let decision = policy.evaluate_withdrawal(&ctx).await.unwrap();
+        assert_eq!(decision, expected);
+    }
+
+    /// A blocklist checker that does not accept any address and counts
+    /// how many times it was called, and whether the call could have used
+    /// the recipient cache.
+    #[derive(Default)]
+    struct CountingRejectAll {
+        calls: AtomicUsize,
+        cached_calls: AtomicUsize,
+    }
+
+    impl BlocklistChecker for CountingRejectAll {
+        async fn can_accept(&self, _: &str) -> Result<bool, Error> {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            Ok(false)
+        }
+
+        async fn can_accept_withdrawal_recipient(&self, address: &str) -> Result<bool, Error> {
+            self.cached_calls.fetch_add(1, Ordering::Relaxed);
+            self.can_accept(address).await
+        }
+    }
+
+    const TIERS: ScreeningTiers = ScreeningTiers {
+        auto_approve_below: Some(10_000),
+        full_screening_above: Some(1_000_000),
+    };
+
+    #[test_case(9_999, ScreeningTier::AutoApprove; "below the auto-approve limit")]
+    #[test_case(10_000, ScreeningTier::Standard; "at the auto-approve limit")]
+    #[test_case(1_000_000, ScreeningTier::Standard; "at the full screening limit")]
+    #[test_case(1_000_001, ScreeningTier::Full; "above the full screening limit")]
+    fn screening_tiers_are_chosen_by_amount(amount: u64, expected: ScreeningTier) {
+        assert_eq!(TIERS.tier(amount), expected);
+        assert_eq!(
+            ScreeningTiers::default().tier(amount),
+            ScreeningTier::Standard
+        );
+    }
+
+    #[test_case(9_999, PolicyDecision::Accept, 0; "auto-approved")]
+    #[test_case(10_000, reject(BLOCKLISTED_SENDER_REASON), 1; "standard")]
+    #[test_case(1_000_001, reject(BLOCKLISTED_SENDER_REASON), 1; "full")]
+    #[tokio::test]
+    async fn blocklist_policy_deposit_screening_tiers(
+        amount: u64,
+        expected: PolicyDecision,
+        expected_calls: usize,
+    ) {
+        let checker = CountingRejectAll::default();
+        let policy = BlocklistPolicy::new(Some(&checker)).with_screening_tiers(TIERS);
+
+        let request = model::DepositRequest {
+            amount,
+            lock_time: 1_000,
+            ..fake::Faker.fake()
+        };
+        let ctx = DepositDecisionContext {
+            request: &request,
+            can_sign: true,
+            chain_tip: &fake::Faker.fake(),
+            network: bitcoin::Network::Regtest,
+        };
+
+        let decision = policy.evaluate_deposit(&ctx).await.unwrap();
+        assert_eq!(decision, expected);
+        assert_eq!(checker.calls.load(Ordering::Relaxed), expected_calls);
+    }
+
+    #[test_case(None; "without a blocklist client")]
+    #[test_case(Some(&RejectAll); "with a blocklist client")]
+    #[tokio::test]
+    async fn blocklist_policy_applies_the_deposit_precheck(checker: Option<&RejectAll>) {
+        let policy = BlocklistPolicy::new(checker).with_screening_tiers(TIERS);
+
+        // The deposit is auto-approved by the screening tiers, but its
+        // lock time is too short for the signers to ever sweep it.
+        let request = model::DepositRequest {
+            amount: 9_999,
+            lock_time: crate::DEPOSIT_LOCKTIME_BLOCK_BUFFER as u32,
+            ..fake::Faker.fake()
+        };
+        let ctx = DepositDecisionContext {
+            request: &request,
+            can_sign: true,
+            chain_tip: &fake::Faker.fake(),
+            network: bitcoin::Network::Regtest,
+        };
+
+        let decision = policy.evaluate_deposit(&ctx).await.unwrap();
+        let expected = format!(
+            "the deposit pre-check does not accept the deposit: {:?}",
+            DepositVerdict::LockTimeTooShort
+        );
+        assert_eq!(decision, reject(&expected));
+    }
+
+    #[test_case(9_999, PolicyDecision::Accept, 0, 0; "auto-approved")]
+    #[test_case(10_000, PolicyDecision::Accept, 0, 0; "standard with screening disabled")]
+    #[test_case(1_000_001, reject(BLOCKLISTED_RECIPIENT_REASON), 1, 0; "full ignores the cache")]
+    #[tokio::test]
+    async fn blocklist_policy_withdrawal_screening_tiers(
+        amount: u64,
+        expected: PolicyDecision,
+        expected_calls: usize,
+        expected_cached_calls: usize,
+    ) {
+        let checker = CountingRejectAll::default();
+        let policy = BlocklistPolicy::new(Some(&checker))
+            .with_withdrawal_screening(false)
+            .with_screening_tiers(TIERS);
+
+        let request = model::WithdrawalRequest { amount, ..fake::Faker.fake() };
+        let ctx = WithdrawalDecisionContext {
+            request: &request,
+            chain_tip: &fake::Faker.fake(),
+            network: bitcoin::Network::Regtest,
+        };
+
+        let decision = policy.evaluate_withdrawal(&ctx).await.unwrap();
+        assert_eq!(decision, expected);
+        assert_eq!(checker.calls.load(Ordering::Relaxed), expected_calls);
+        assert_eq!(
+            checker.cached_calls.load(Ordering::Relaxed),
+            expected_cached_calls
+        );
+    }
+
+    #[test]
+    fn selecting_an_unknown_policy_is_an_error() {
+        let blocklist = BlocklistPolicy::<()>::new(None);
+        let available: [&dyn DecisionPolicy; 1] = [&blocklist];
+
+        assert!(DecisionPolicyChain::select(&[BLOCKLIST_POLICY_NAME], &available).is_ok());
+
+        let result = DecisionPolicyChain::select(&["blocklist", "jurisdiction"], &available);
+        assert!(
+            matches!(result, Err(Error::UnknownDecisionPolicy(name)) if name == "jurisdiction")
+        );
+    }
+}
@@ -0,0 +1,568 @@
+//! A batched pre-check of prospective deposits.
+//!
+//! Whether the signers will accept a deposit depends on several things:
+//! the deposit and reclaim scripts must follow the sBTC script policy,
+//! the amount must be within the current sBTC limits, the senders must
+//! not be blocklisted and there must be enough room under the total cap
+//! to mint the deposit. The request decider and the sweep validation
+//! each check a part of this, and this module puts all of these checks in
+//! one place. The request decider decides deposit requests with
+//! [`DepositPrecheck::can_accept_deposit_request`], and callers that want
+//! early feedback on a deposit, like Emily through the signer admin API,
+//! get the same answer the signers would give.
+
+use bitcoin::OutPoint;
+use bitcoin::Script;
+use bitcoin::ScriptBuf;
+use bitcoin::XOnlyPublicKey;
+use bitcoin::relative::LockTime;
+use sbtc::deposits::DepositScriptInputs;
+use sbtc::deposits::ReclaimScriptInputs;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::DEPOSIT_LOCKTIME_BLOCK_BUFFER;
+use crate::TIME_BASED_LOCKTIME_SECONDS_PER_BLOCK;
+use crate::blocklist_client::BlocklistChecker;
+use crate::context::SbtcLimits;
+use crate::error::Error;
+use crate::storage::model;
+
+/// A deposit that has not necessarily been confirmed on bitcoin yet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProspectiveDeposit {
+    /// The outpoint of the deposit UTXO.
+    pub outpoint: OutPoint,
+    /// The amount of the deposit UTXO, in sats.
+    pub amount: u64,
+    /// The deposit script used in the deposit UTXO.
+    pub deposit_script: ScriptBuf,
+    /// The reclaim script used in the deposit UTXO.
+    pub reclaim_script: ScriptBuf,
+    /// The scriptPubKeys of the UTXOs funding the deposit transaction.
+    #[serde(default)]
+    pub sender_script_pub_keys: Vec<ScriptBuf>,
+}
+
+/// The verdict of the pre-check for a single deposit. Checks are done in
+/// the order of the variants below, and the first one that fails is
+/// reported.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DepositVerdict {
+    /// The signers would accept the deposit.
+    Ok,
+    /// The deposit script does not follow the sBTC deposit script format.
+    InvalidDepositScript,
+    /// The reclaim script does not follow the sBTC reclaim script format.
+    InvalidReclaimScript,
+    /// The lock time in the reclaim script is too short for the signers
+    /// to ever sweep the deposit.
+    LockTimeTooShort,
+    /// The deposit is not locked with the current signers' public key.
+    SignersKeyMismatch,
+    /// The deposit amount is below the allowed per-deposit minimum.
+    AmountTooLow,
+    /// The deposit amount exceeds the allowed per-deposit cap.
+    AmountTooHigh,
+    /// At least one of the addresses funding the deposit is blocklisted.
+    Blocklisted,
+    /// Minting the deposit, together with the accepted deposits before
+    /// it in the batch, would exceed the total cap.
+    ExceedsMintableCap,
+}
+
+impl DepositVerdict {
+    /// Whether the signers would accept the deposit.
+    pub fn is_ok(&self) -> bool {
+        *self == DepositVerdict::Ok
+    }
+}
+
+/// Returns the number of blocks that the signers assume they have to sweep
+/// a deposit with the given reclaim lock time.
+///
+/// Block-based lock times are used as is, while time-based lock times are
+/// converted using [`TIME_BASED_LOCKTIME_SECONDS_PER_BLOCK`], rounding
+/// down.
+pub fn lock_time_in_blocks(lock_time: LockTime) -> u16 {
+    match lock_time {
+        LockTime::Blocks(height) => height.value(),
+        LockTime::Time(time) => {
+            let seconds = u32::from(time.value()) * 512;
+            // The quotient is at most u16::MAX * 512 / 1200, which always
+            // fits in a u16.
+            (seconds / TIME_BASED_LOCKTIME_SECONDS_PER_BLOCK) as u16
+        }
+    }
+}
+
+/// Check the deposit amount against the per-deposit limits.
+pub fn check_deposit_amount(amount: u64, limits: &SbtcLimits) -> DepositVerdict {
+    if amount < limits.per_deposit_minimum().to_sat() {
+        return DepositVerdict::AmountTooLow;
+    }
+
+    if amount > limits.per_deposit_cap().to_sat() {
+        return DepositVerdict::AmountTooHigh;
+    }
+
+    DepositVerdict::Ok
+}
+
+/// Check the lock time of a deposit's reclaim script.
+///
+/// The signers do not sweep deposits that can be reclaimed within the
+/// next [`DEPOSIT_LOCKTIME_BLOCK_BUFFER`] blocks, so a deposit with a
+/// shorter lock time would never be swept.
+pub fn check_lock_time(lock_time: LockTime) -> DepositVerdict {
+    if lock_time_in_blocks(lock_time) <= DEPOSIT_LOCKTIME_BLOCK_BUFFER {
+        return DepositVerdict::LockTimeTooShort;
+    }
+
+    DepositVerdict::Ok
+}
+
+/// Check that a deposit is locked with the expected signers' public key,
+/// if one is given.
+fn check_signers_public_key(
+    deposit_key: &XOnlyPublicKey,
+    signers_public_key: Option<&XOnlyPublicKey>,
+) -> DepositVerdict {
+    match signers_public_key {
+        Some(key) if key != deposit_key => DepositVerdict::SignersKeyMismatch,
+        _ => DepositVerdict::Ok,
+    }
+}
+
+/// Check the deposit and reclaim scripts against the sBTC script policy.
+///
+/// If `signers_public_key` is given then the deposit script must lock the
+/// deposit with it.
+pub fn check_deposit_scripts(
+    deposit_script: &ScriptBuf,
+    reclaim_script: &ScriptBuf,
+    signers_public_key: Option<&XOnlyPublicKey>,
+) -> DepositVerdict {
+    let (deposit_inputs, reclaim_inputs) =
+        match parse_deposit_scripts(deposit_script, reclaim_script) {
+            Ok(inputs) => inputs,
+            Err(verdict) => return verdict,
+        };
+
+    let verdict = check_lock_time(reclaim_inputs.relative_lock_time());
+    if !verdict.is_ok() {
+        return verdict;
+    }
+
+    check_signers_public_key(&deposit_inputs.signers_public_key, signers_public_key)
+}
+
+fn parse_deposit_scripts(
+    deposit_script: &ScriptBuf,
+    reclaim_script: &ScriptBuf,
+) -> Result<(DepositScriptInputs, ReclaimScriptInputs), DepositVerdict> {
+    let deposit_inputs = DepositScriptInputs::parse(deposit_script)
+        .map_err(|_| DepositVerdict::InvalidDepositScript)?;
+    let reclaim_inputs = ReclaimScriptInputs::parse(reclaim_script)
+        .map_err(|_| DepositVerdict::InvalidReclaimScript)?;
+
+    Ok((deposit_inputs, reclaim_inputs))
+}
+
+/// Check the addresses of the given scriptPubKeys with the blocklist
+/// client, returning whether all of them can be accepted.
+pub async fn check_sender_addresses<'a, B, I>(
+    client: &B,
+    script_pub_keys: I,
+    network: bitcoin::Network,
+    outpoint: OutPoint,
+) -> Result<bool, Error>
+where
+    B: BlocklistChecker,
+    I: IntoIterator<Item = &'a Script>,
+{
+    let params = network.params();
+    let addresses = script_pub_keys
+        .into_iter()
+        .map(|script_pubkey| bitcoin::Address::from_script(script_pubkey, params))
+        .collect::<Result<Vec<bitcoin::Address>, _>>()
+        .map_err(|err| Error::DepositBitcoinAddressFromScript(err, outpoint))?;
+
+    for address in addresses {
+        let can_accept = client
+            .can_accept(&address.to_string())
+            .await
+            .inspect_err(|error| tracing::error!(%error, "blocklist client issue"))?;
+
+        if !can_accept {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// The parts of a deposit that are checked once its scripts have been
+/// parsed.
+struct ParsedDeposit<'a> {
+    outpoint: OutPoint,
+    amount: u64,
+    lock_time: LockTime,
+    signers_public_key: XOnlyPublicKey,
+    sender_script_pub_keys: Vec<&'a Script>,
+}
+
+/// Runs the pre-check over a batch of prospective deposits.
+#[derive(Debug)]
+pub struct DepositPrecheck<'a, B> {
+    /// The current sBTC limits.
+    pub limits: &'a SbtcLimits,
+    /// The blocklist client. If not set then no address is considered
+    /// blocklisted.
+    pub blocklist_checker: Option<&'a B>,
+    /// The bitcoin network, used to turn sender scriptPubKeys into
+    /// addresses for the blocklist client.
+    pub network: bitcoin::Network,
+    /// The current signers' public key. If set then deposits must be
+    /// locked with it.
+    pub signers_public_key: Option<XOnlyPublicKey>,
+}
+
+impl<B> DepositPrecheck<'_, B>
+where
+    B: BlocklistChecker,
+{
+    /// Return a verdict for each of the given deposits, in the same order.
+    ///
+    /// Deposits that pass all other checks count against the mintable
+    /// cap in the order that they are given, so a deposit may be rejected
+    /// because of the deposits before it in the batch.
+    pub async fn can_accept_deposits(
+        &self,
+        deposits: &[ProspectiveDeposit],
+    ) -> Result<Vec<DepositVerdict>, Error> {
+        let mut mintable = self.limits.max_mintable_cap().to_sat();
+        let mut verdicts = Vec::with_capacity(deposits.len());
+
+        for deposit in deposits {
+            let parsed = parse_deposit_scripts(&deposit.deposit_script, &deposit.reclaim_script);
+            let verdict = match parsed {
+                Ok((deposit_inputs, reclaim_inputs)) => {
+                    let parsed = ParsedDeposit {
+                        outpoint: deposit.outpoint,
+                        amount: deposit.amount,
+                        lock_time: reclaim_inputs.relative_lock_time(),
+                        signers_public_key: deposit_inputs.signers_public_key,
+                        sender_script_pub_keys: deposit
+                            .sender_script_pub_keys
+                            .iter()
+                            .map(ScriptBuf::as_script)
+                            .collect(),
+                    };
+                    self.can_accept_deposit(&parsed, mintable).await?
+                }
+                Err(verdict) => verdict,
+            };
+            if verdict.is_ok() {
+                mintable = mintable.saturating_sub(deposit.amount);
+            }
+            verdicts.push(verdict);
+        }
+
+        Ok(verdicts)
+    }
+
+    /// Return the verdict for a deposit request that the signers have
+    /// recorded.
+    ///
+    /// This is how the request decider decides deposit requests. Recorded
+    /// requests only keep the hash of their reclaim script, but they are
+    /// only recorded once both of their scripts have been parsed, so the
+    /// script format checks are skipped while every other check is the
+    /// same as in [`DepositPrecheck::can_accept_deposits`].
+    pub async fn can_accept_deposit_request(
+        &self,
+        request: &model::DepositRequest,
+    ) -> Result<DepositVerdict, Error> {
+        let Ok(lock_time) = LockTime::from_consensus(request.lock_time) else {
+            return Ok(DepositVerdict::InvalidReclaimScript);
+        };
+        let parsed = ParsedDeposit {
+            outpoint: request.outpoint(),
+            amount: request.amount,
+            lock_time,
+            signers_public_key: (&request.signers_public_key).into(),
+            sender_script_pub_keys: request
+                .sender_script_pub_keys
+                .iter()
+                .map(|script_pub_key| script_pub_key.as_script())
+                .collect(),
+        };
+        let mintable = self.limits.max_mintable_cap().to_sat();
+
+        self.can_accept_deposit(&parsed, mintable).await
+    }
+
+    async fn can_accept_deposit(
+        &self,
+        deposit: &ParsedDeposit<'_>,
+        mintable: u64,
+    ) -> Result<DepositVerdict, Error> {
+        let verdict = check_lock_time(deposit.lock_time);
+        if !verdict.is_ok() {
+            return Ok(verdict);
+        }
+
+        let verdict = check_signers_public_key(
+            &deposit.signers_public_key,
+            self.signers_public_key.as_ref(),
+        );
+        if !verdict.is_ok() {
+            return Ok(verdict);
+        }
+
+        let verdict = check_deposit_amount(deposit.amount, self.limits);
+        if !verdict.is_ok() {
+            return Ok(verdict);
+        }
+
+        if let Some(client) = self.blocklist_checker {
+            let script_pub_keys = deposit.sender_script_pub_keys.iter().copied();
+            let can_accept =
+                check_sender_addresses(client, script_pub_keys, self.network, deposit.outpoint)
+                    .await?;
+            if !can_accept {
+                return Ok(DepositVerdict::Blocklisted);
+            }
+        }
+
+        if deposit.amount > mintable {
+            return Ok(DepositVerdict::ExceedsMintableCap);
+        }
+
+        Ok(DepositVerdict::Ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use bitcoin::Amount;
+    use bitcoin::CompressedPublicKey;
+    use bitcoin::key::Secp256k1;
+    use bitcoin::secp256k1::SecretKey;
+    use clarity::vm::types::PrincipalData;
+    use fake::Fake as _;
+    use rand::rngs::OsRng;
+    use test_case::test_case;
+
+    use crate::testing::dummy;
+
+    use super::*;
+
+    /// A blocklist client that blocks the given addresses.
+    struct Blocklist(HashSet<String>);
+
+    impl BlocklistChecker for Blocklist {
+        async fn can_accept(&self, address: &str) -> Result<bool, Error> {
+            Ok(!self.0.contains(address))
+        }
+    }
+
+    fn generate_x_only_public_key() -> XOnlyPublicKey {
+        let secret_key = SecretKey::new(&mut OsRng);
+        secret_key.x_only_public_key(&Secp256k1::new()).0
+    }
+
+    fn generate_script_pubkey() -> ScriptBuf {
+        let secret_key = SecretKey::new(&mut OsRng);
+        let pk = CompressedPublicKey(secret_key.public_key(&Secp256k1::new()));
+        ScriptBuf::new_p2wpkh(&pk.wpubkey_hash())
+    }
+
+    fn deposit(
+        signers_public_key: XOnlyPublicKey,
+        amount: u64,
+        lock_time: u32,
+    ) -> ProspectiveDeposit {
+        let deposit_inputs = DepositScriptInputs {
+            signers_public_key,
+            max_fee: 10_000,
+            recipient: PrincipalData::parse("ST1PQHQKV0RJXZFY1DGX8MNSNYVE3VGZJSRTPGZGM").unwrap(),
+        };
+        let reclaim_inputs = ReclaimScriptInputs::try_new(lock_time, ScriptBuf::new()).unwrap();
+
+        ProspectiveDeposit {
+            outpoint: OutPoint::new(dummy::txid(&fake::Faker, &mut OsRng), 0),
+            amount,
+            deposit_script: deposit_inputs.deposit_script(),
+            reclaim_script: reclaim_inputs.reclaim_script(),
+            sender_script_pub_keys: vec![generate_script_pubkey()],
+        }
+    }
+
+    fn limits(min: u64, max: u64, max_mintable: u64) -> SbtcLimits {
+        SbtcLimits::new(
+            None,
+            Some(Amount::from_sat(min)),
+            Some(Amount::from_sat(max)),
+            None,
+            None,
+            None,
+            None,
+            Some(Amount::from_sat(max_mintable)),
+        )
+    }
+
+    #[test_case(999, DepositVerdict::AmountTooLow; "below minimum")]
+    #[test_case(1_000, DepositVerdict::Ok; "at minimum")]
+    #[test_case(10_000, DepositVerdict::Ok; "at cap")]
+    #[test_case(10_001, DepositVerdict::AmountTooHigh; "above cap")]
+    fn deposit_amount_checks(amount: u64, expected: DepositVerdict) {
+        let limits = limits(1_000, 10_000, 21_000_000 * 100_000_000);
+        assert_eq!(check_deposit_amount(amount, &limits), expected);
+    }
+
+    #[test]
+    fn deposit_script_checks() {
+        let key = generate_x_only_public_key();
+        let good = deposit(key, 5_000, 10);
+
+        let verdict = check_deposit_scripts(&good.deposit_script, &good.reclaim_script, Some(&key));
+        assert_eq!(verdict, DepositVerdict::Ok);
+
+        let other_key = generate_x_only_public_key();
+        let verdict =
+            check_deposit_scripts(&good.deposit_script, &good.reclaim_script, Some(&other_key));
+        assert_eq!(verdict, DepositVerdict::SignersKeyMismatch);
+
+        let verdict = check_deposit_scripts(&ScriptBuf::new(), &good.reclaim_script, None);
+        assert_eq!(verdict, DepositVerdict::InvalidDepositScript);
+
+        let verdict = check_deposit_scripts(&good.deposit_script, &ScriptBuf::new(), None);
+        assert_eq!(verdict, DepositVerdict::InvalidReclaimScript);
+
+        let short = deposit(key, 5_000, DEPOSIT_LOCKTIME_BLOCK_BUFFER as u32);
+        let verdict = check_deposit_scripts(&short.deposit_script, &short.reclaim_script, None);
+        assert_eq!(verdict, DepositVerdict::LockTimeTooShort);
+
+        // A time-based lock time of 10 * 512 seconds is only 4 blocks by
+        // our reckoning, while 6 * 512 seconds is 2 blocks.
+        let time_based = LockTime::from_512_second_intervals(10).to_consensus_u32();
+        let good = deposit(key, 5_000, time_based);
+        let verdict = check_deposit_scripts(&good.deposit_script, &good.reclaim_script, None);
+        assert_eq!(verdict, DepositVerdict::Ok);
+
+        let time_based = LockTime::from_512_second_intervals(6).to_consensus_u32();
+        let short = deposit(key, 5_000, time_based);
+        let verdict = check_deposit_scripts(&short.deposit_script, &short.reclaim_script, None);
+        assert_eq!(verdict, DepositVerdict::LockTimeTooShort);
+    }
+
+    #[test_case(LockTime::from_height(0), 0; "zero blocks")]
+    #[test_case(LockTime::from_height(u16::MAX), u16::MAX; "max blocks")]
+    #[test_case(LockTime::from_512_second_intervals(2), 0; "less than a block")]
+    #[test_case(LockTime::from_512_second_intervals(75), 32; "whole blocks")]
+    #[test_case(LockTime::from_512_second_intervals(u16::MAX), 27_961; "max time")]
+    fn lock_times_are_converted_to_blocks(lock_time: LockTime, expected: u16) {
+        assert_eq!(lock_time_in_blocks(lock_time), expected);
+    }
+
+    #[tokio::test]
+    async fn batched_verdicts_combine_all_checks() {
+        let key = generate_x_only_public_key();
+        let network = bitcoin::Network::Regtest;
+
+        let blocked = deposit(key, 5_000, 10);
+        let blocked_address =
+            bitcoin::Address::from_script(&blocked.sender_script_pub_keys[0], network).unwrap();
+        let blocklist = Blocklist(HashSet::from([blocked_address.to_string()]));
+
+        let limits = limits(1_000, 10_000, 12_000);
+        let precheck = DepositPrecheck {
+            limits: &limits,
+            blocklist_checker: Some(&blocklist),
+            network,
+            signers_public_key: Some(key),
+        };
+
+        let deposits = [
+            deposit(key, 6_000, 10),
+            blocked,
+            deposit(key, 500, 10),
+            deposit(generate_x_only_public_key(), 6_000, 10),
+            deposit(key, 6_000, 10),
+            // The mintable cap is used up by the two accepted deposits
+            // above.
+            deposit(key, 1_000, 10),
+        ];
+        let verdicts = precheck.can_accept_deposits(&deposits).await.unwrap();
+
+        let expected = [
+            DepositVerdict::Ok,
+            DepositVerdict::Blocklisted,
+            DepositVerdict::AmountTooLow,
+            DepositVerdict::SignersKeyMismatch,
+            DepositVerdict::Ok,
+            DepositVerdict::ExceedsMintableCap,
+        ];
+        assert_eq!(verdicts, expected);
+    }
+
+    #[tokio::test]
+    async fn recorded_requests_get_the_same_verdicts() {
+        let key = generate_x_only_public_key();
+        let network = bitcoin::Network::Regtest;
+
+        let blocked = deposit(key, 5_000, 10);
+        let blocked_address =
+            bitcoin::Address::from_script(&blocked.sender_script_pub_keys[0], network).unwrap();
+        let blocklist = Blocklist(HashSet::from([blocked_address.to_string()]));
+
+        let limits = limits(1_000, 10_000, 21_000_000 * 100_000_000);
+        let precheck = DepositPrecheck {
+            limits: &limits,
+            blocklist_checker: Some(&blocklist),
+            network,
+            signers_public_key: Some(key),
+        };
+
+        let time_based = LockTime::from_512_second_intervals(6).to_consensus_u32();
+        let deposits = [
+            deposit(key, 6_000, 10),
+            blocked,
+            deposit(key, 500, 10),
+            deposit(key, 6_000, time_based),
+            deposit(generate_x_only_public_key(), 6_000, 10),
+        ];
+        let verdicts = precheck.can_accept_deposits(&deposits).await.unwrap();
+
+        for (deposit, verdict) in deposits.iter().zip(verdicts) {
+            let lock_time = ReclaimScriptInputs::parse(&deposit.reclaim_script)
+                .unwrap()
+                .lock_time();
+            let signers_public_key = DepositScriptInputs::parse(&deposit.deposit_script)
+                .unwrap()
+                .signers_public_key;
+            let request = model::DepositRequest {
+                txid: deposit.outpoint.txid.into(),
+                output_index: deposit.outpoint.vout,
+                amount: deposit.amount,
+                lock_time,
+                signers_public_key: signers_public_key.into(),
+                sender_script_pub_keys: deposit
+                    .sender_script_pub_keys
+                    .iter()
+                    .cloned()
+                    .map(Into::into)
+                    .collect(),
+                ..fake::Faker.fake()
+            };
+
+            let request_verdict = precheck.can_accept_deposit_request(&request).await.unwrap();
+            assert_eq!(request_verdict, verdict);
+        }
+    }
+}
@@ -0,0 +1,615 @@
+//! Programmatic local regtest environments for development and CI.
+//!
+//! A [`DevEnv`] wires together everything needed to run the sBTC signers
+//! end to end on a single machine:
+//!
+//! * bitcoin-core in regtest mode and, optionally, a stacks node, both
+//!   started as [`TestContainers`] from the compose files in
+//!   `docker/tests`,
+//! * an isolated Emily instance, backed by a fresh set of DynamoDB tables
+//!   on the Emily server from `docker/docker-compose.test.yml`,
+//! * N signers, each with its own postgres database, running all of their
+//!   event loops and connected to each other through a [`WanNetwork`].
+//!
+//! The environment hands out handles to mine bitcoin blocks, create
+//! deposits and wait for the signers to reach some state, so that tests
+//! and local tooling can drive the whole stack from Rust instead of shell
+//! scripts.
+//!
+//! The environment can run against a real stacks node, see
+//! [`DevEnvBuilder::start`], or against a mocked stacks client, see
+//! [`DevEnvBuilder::start_with_mocked_stacks`]. In the latter case the
+//! caller installs whatever expectations the signers need, since the
+//! block observer and the coordinator both talk to the stacks node.
+
+use std::collections::BTreeSet;
+use std::future::Future;
+use std::num::NonZeroUsize;
+use std::str::FromStr as _;
+use std::time::Duration;
+
+use bitcoin::ScriptBuf;
+use bitcoin::Transaction;
+use bitcoincore_rpc::RpcApi as _;
+use blockstack_lib::chainstate::stacks::SinglesigHashMode;
+use blockstack_lib::chainstate::stacks::SinglesigSpendingCondition;
+use blockstack_lib::chainstate::stacks::StacksTransaction;
+use blockstack_lib::chainstate::stacks::TokenTransferMemo;
+use blockstack_lib::chainstate::stacks::TransactionAnchorMode;
+use blockstack_lib::chainstate::stacks::TransactionAuth;
+use blockstack_lib::chainstate::stacks::TransactionPayload;
+use blockstack_lib::chainstate::stacks::TransactionPostConditionMode;
+use blockstack_lib::chainstate::stacks::TransactionPublicKeyEncoding;
+use blockstack_lib::chainstate::stacks::TransactionSpendingCondition;
+use blockstack_lib::chainstate::stacks::TransactionVersion;
+use clarity::consts::CHAIN_ID_TESTNET;
+use clarity::types::chainstate::StacksAddress;
+use clarity::util::secp256k1::MessageSignature;
+use clarity::vm::types::PrincipalData;
+use emily_client::apis::configuration::ApiKey;
+use emily_client::apis::configuration::Configuration as EmilyApiConfig;
+use emily_client::apis::deposit_api;
+use lru::LruCache;
+use rand::rngs::OsRng;
+use sbtc::deposits::CreateDepositRequest;
+use sbtc::deposits::DepositInfo;
+use sbtc::deposits::DepositScriptInputs;
+use sbtc::deposits::ReclaimScriptInputs;
+use sbtc::testing::containers::BitcoinContainer;
+use sbtc::testing::containers::TestContainers;
+use sbtc::testing::containers::TestContainersBuilder;
+use sbtc::testing::emily::EmilyTables;
+use stacks_common::address::AddressHashMode;
+use stacks_common::address::C32_ADDRESS_VERSION_TESTNET_SINGLESIG;
+use tokio::task::JoinHandle;
+use tokio::time::error::Elapsed;
+use url::Url;
+
+use crate::bitcoin::poller::BitcoinChainTipPoller;
+use crate::bitcoin::rpc::BitcoinCoreClient;
+use crate::bitcoin::rpc::BitcoinCoreClientParams;
+use crate::block_observer::BlockObserver;
+use crate::config::NetworkKind;
+use crate::context::Context as _;
+use crate::context::SignerCommand;
+use crate::context::SignerSignal;
+use crate::emily_client::EmilyClient;
+use crate::error::Error;
+use crate::keys::PrivateKey;
+use crate::keys::PublicKey;
+use crate::network::in_memory2::WanNetwork;
+use crate::request_decider::RequestDeciderEventLoop;
+use crate::signature::RecoverableEcdsaSignature as _;
+use crate::stacks::api::MockStacksInteract;
+use crate::stacks::api::StacksClient;
+use crate::stacks::api::StacksInteract;
+use crate::stacks::wallet::SignerWallet;
+use crate::storage::DbRead as _;
+use crate::storage::model::BitcoinBlockHash;
+use crate::storage::postgres::PgStore;
+use crate::testing;
+use crate::testing::btc::build_emily_request;
+use crate::testing::context::*;
+use crate::transaction_coordinator::TxCoordinatorEventLoop;
+use crate::transaction_signer::STACKS_SIGN_REQUEST_LRU_SIZE;
+use crate::transaction_signer::STACKS_VALIDATION_LRU_SIZE;
+use crate::transaction_signer::TxSignerEventLoop;
+
+/// The URL of the Emily server started by `docker/docker-compose.test.yml`.
+pub const DEFAULT_EMILY_URL: &str = "http://127.0.0.1:3031";
+
+/// The API key accepted by the Emily server started by
+/// `docker/docker-compose.test.yml`.
+const EMILY_API_KEY: &str = "testApiKey";
+
+/// This is one of the generic accounts defined in the stacks miner config
+/// used for tests. It funds the signers' wallet with STX.
+///
+/// Address: ST1YEHRRYJ4GF9CYBFFN0ZVCXX1APSBEEQ5KEDN7M
+const STACKS_FAUCET_PRIVATE_KEY: &str =
+    "e26e611fc92fe535c5e2e58a6a446375bb5e3b471440af21bbe327384befb50a";
+
+/// The amount of uSTX sent to the signers' wallet, so that the signers can
+/// pay for deploying the contracts and for their contract calls.
+const SIGNERS_WALLET_USTX: u64 = 100 * 1_000_000;
+
+/// The lock time of the reclaim script of deposits made through
+/// [`DevEnv::create_deposit`].
+const DEPOSIT_LOCK_TIME: u32 = 50;
+
+/// How long to wait for something to happen on the stacks node.
+const STACKS_NODE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The context type used by each signer in a [`DevEnv`].
+pub type DevEnvContext<Stacks> = TestContext<PgStore, BitcoinCoreClient, Stacks, EmilyClient>;
+
+/// A signer running in a [`DevEnv`].
+pub struct DevSigner<Stacks> {
+    /// The signer's context.
+    pub context: DevEnvContext<Stacks>,
+    /// The signer's private key.
+    pub private_key: PrivateKey,
+    /// The handles of the signer's event loops.
+    pub event_loops: Vec<JoinHandle<Result<(), Error>>>,
+}
+
+impl<Stacks> DevSigner<Stacks> {
+    /// The signer's public key.
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey::from_private_key(&self.private_key)
+    }
+}
+
+/// Builder for a [`DevEnv`].
+#[derive(Debug, Clone)]
+pub struct DevEnvBuilder {
+    /// The number of signers to run.
+    num_signers: usize,
+    /// The number of signatures required by the signer set.
+    signatures_required: u16,
+    /// The URL of the Emily server.
+    emily_url: Url,
+}
+
+impl Default for DevEnvBuilder {
+    fn default() -> Self {
+        Self {
+            num_signers: 3,
+            signatures_required: 2,
+            emily_url: Url::parse(DEFAULT_EMILY_URL).expect("the default Emily URL is valid"),
+        }
+    }
+}
+
+impl DevEnvBuilder {
+    /// Create a builder for an environment of three signers, two of which
+    /// are required to sign.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the number of signers to run.
+    pub fn num_signers(mut self, num_signers: usize) -> Self {
+        self.num_signers = num_signers;
+        self
+    }
+
+    /// Set the number of signatures required by the signer set.
+    pub fn signatures_required(mut self, signatures_required: u16) -> Self {
+        self.signatures_required = signatures_required;
+        self
+    }
+
+    /// Use the Emily server at the given URL.
+    pub fn emily_url(mut self, emily_url: Url) -> Self {
+        self.emily_url = emily_url;
+        self
+    }
+
+    /// Start bitcoin-core and a stacks node, fund the signers' wallet with
+    /// STX and start the signers.
+    pub async fn start(self) -> DevEnv<StacksClient> {
+        let containers = TestContainersBuilder::start_stacks().await;
+        let stacks_url = containers.stacks().await.url().clone();
+        let stacks_client = StacksClient::new(stacks_url).expect("cannot create stacks client");
+
+        let mut env = self.setup(containers, stacks_client).await;
+        fund_signers_wallet(&env.stacks_client, &env.wallet).await;
+        env.spawn_event_loops();
+        env
+    }
+
+    /// Start bitcoin-core and the signers, with a mocked stacks client
+    /// shared by all of the signers. The given function installs the
+    /// expectations on the mocked client before the signers start.
+    pub async fn start_with_mocked_stacks<F>(
+        self,
+        configure: F,
+    ) -> DevEnv<WrappedMockStacksInteract>
+    where
+        F: FnOnce(&mut MockStacksInteract),
+    {
+        let containers = TestContainersBuilder::start_bitcoin().await;
+        let stacks_client = WrappedMock::default();
+
+        let mut env = self.setup(containers, stacks_client).await;
+        configure(&mut *env.stacks_client.lock().await);
+        env.spawn_event_loops();
+        env
+    }
+
+    /// Create the Emily tables and the signers' contexts, without starting
+    /// any of the signers' event loops.
+    async fn setup<Stacks>(
+        self,
+        containers: TestContainers,
+        stacks_client: Stacks,
+    ) -> DevEnv<Stacks>
+    where
+        Stacks: StacksInteract + Clone + Send + Sync + 'static,
+    {
+        let bitcoin = containers.bitcoin().await;
+        let params = BitcoinCoreClientParams {
+            url: bitcoin.url().clone(),
+            timeout: Duration::from_secs(10),
+        };
+        let bitcoin_client =
+            BitcoinCoreClient::try_from(&params).expect("cannot create bitcoin client");
+
+        // Ensure that bitcoin-core can estimate fees.
+        bitcoin.get_faucet().generate_fee_data();
+
+        let emily_tables = EmilyTables::new().await;
+        let emily_client = new_emily_client(self.emily_url.clone(), &emily_tables);
+
+        let private_keys: Vec<PrivateKey> = std::iter::repeat_with(|| PrivateKey::new(&mut OsRng))
+            .take(self.num_signers)
+            .collect();
+        let signer_set: BTreeSet<PublicKey> = private_keys
+            .iter()
+            .map(PublicKey::from_private_key)
+            .collect();
+        let wallet = SignerWallet::new(
+            &signer_set,
+            self.signatures_required,
+            NetworkKind::Testnet,
+            0,
+        )
+        .expect("invalid signer set");
+
+        let mut signers = Vec::with_capacity(self.num_signers);
+        for private_key in private_keys {
+            let context = TestContext::builder()
+                .with_storage(testing::storage::new_test_database().await)
+                .with_bitcoin_client(bitcoin_client.clone())
+                .with_stacks_client(stacks_client.clone())
+                .with_emily_client(emily_client.clone())
+                .modify_settings(|settings| {
+                    settings.signer.private_key = private_key;
+                    settings.signer.bootstrap_signing_set = signer_set.clone();
+                    settings.signer.bootstrap_signatures_required = self.signatures_required;
+                    settings.signer.bitcoin_processing_delay = Duration::from_millis(500);
+                    settings.signer.deployer = wallet.address().clone();
+                })
+                .build();
+
+            signers.push(DevSigner {
+                context,
+                private_key,
+                event_loops: Vec::new(),
+            });
+        }
+
+        let bitcoin_chain_tip_poller =
+            BitcoinChainTipPoller::start_new(bitcoin_client.clone(), Duration::from_millis(100))
+                .await;
+
+        DevEnv {
+            signers,
+            bitcoin_client,
+            stacks_client,
+            emily_client,
+            wallet,
+            bitcoin_chain_tip_poller,
+            emily_tables,
+            network: WanNetwork::default(),
+            containers,
+        }
+    }
+}
+
+/// A running local regtest environment. See the module documentation.
+pub struct DevEnv<Stacks> {
+    /// The signers.
+    pub signers: Vec<DevSigner<Stacks>>,
+    /// A client for the bitcoin-core node.
+    pub bitcoin_client: BitcoinCoreClient,
+    /// The stacks client shared by the signers.
+    pub stacks_client: Stacks,
+    /// A client for the isolated Emily instance.
+    pub emily_client: EmilyClient,
+    /// The signers' multi-sig wallet on stacks, whose address is the
+    /// deployer of the sBTC contracts.
+    pub wallet: SignerWallet,
+    /// The poller that notifies the signers' block observers of new
+    /// bitcoin blocks.
+    bitcoin_chain_tip_poller: BitcoinChainTipPoller,
+    /// The DynamoDB tables backing the Emily instance.
+    emily_tables: EmilyTables,
+    /// The network connecting the signers.
+    network: WanNetwork,
+    /// The containers running bitcoin-core and the stacks node.
+    containers: TestContainers,
+}
+
+impl<Stacks> DevEnv<Stacks>
+where
+    Stacks: StacksInteract + Clone + Send + Sync + 'static,
+{
+    /// Spawn the event loops of every signer.
+    fn spawn_event_loops(&mut self) {
+        for signer in self.signers.iter_mut() {
+            let context = &signer.context;
+            let private_key = signer.private_key;
+            let net = self.network.connect(context);
+
+            let coordinator = TxCoordinatorEventLoop {
+                network: net.spawn(),
+                context: context.clone(),
+                context_window: 10000,
+                private_key,
+                signing_round_max_duration: Duration::from_secs(10),
+                bitcoin_presign_request_max_duration: Duration::from_secs(10),
+                dkg_max_duration: Duration::from_secs(10),
+                is_epoch3: true,
+            };
+            let tx_signer = TxSignerEventLoop {
+                network: net.spawn(),
+                context: context.clone(),
+                context_window: 10000,
+                wsts_state_machines: LruCache::new(NonZeroUsize::new(100).unwrap()),
+                signer_private_key: private_key,
+                last_presign_block: None,
+                dkg_begin_pause: None,
+                dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
+                stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+                stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
+            };
+            let decider = RequestDeciderEventLoop {
+                network: net.spawn(),
+                context: context.clone(),
+                context_window: 10000,
+                deposit_decisions_retry_window: 1,
+                withdrawal_decisions_retry_window: 1,
+                carried_over: Default::default(),
+                blocklist_checker: Some(()),
+                decision_policies: Vec::new(),
+                signer_private_key: private_key,
+            };
+            let block_observer = BlockObserver {
+                context: context.clone(),
+                bitcoin_block_source: self.bitcoin_chain_tip_poller.clone(),
+            };
+
+            signer.event_loops = vec![
+                tokio::spawn(coordinator.run()),
+                tokio::spawn(tx_signer.run()),
+                tokio::spawn(decider.run()),
+                tokio::spawn(block_observer.run()),
+            ];
+        }
+    }
+
+    /// The running bitcoin-core container, with an RPC client for the
+    /// wallet and the faucet.
+    pub async fn bitcoin(&self) -> &BitcoinContainer {
+        self.containers.bitcoin().await
+    }
+
+    /// Mine the given number of bitcoin blocks.
+    pub async fn mine_blocks(&self, num_blocks: u64) -> Vec<BitcoinBlockHash> {
+        let faucet = self.bitcoin().await.get_faucet();
+        faucet
+            .generate_blocks(num_blocks)
+            .into_iter()
+            .map(BitcoinBlockHash::from)
+            .collect()
+    }
+
+    /// Mine a bitcoin block and wait for every signer to store it as
+    /// their canonical chain tip.
+    pub async fn mine_block_and_wait(&self) -> BitcoinBlockHash {
+        let block_hash = self.bitcoin().await.get_faucet().generate_block().into();
+        for signer in self.signers.iter() {
+            let db = signer.context.get_storage();
+            testing::storage::wait_for_chain_tip(&db, block_hash).await;
+        }
+        block_hash
+    }
+
+    /// The aggregate key from the latest DKG run of the first signer, if
+    /// the signers have run DKG.
+    pub async fn aggregate_key(&self) -> Option<PublicKey> {
+        let db = self.signers.first()?.context.get_storage();
+        db.get_latest_encrypted_dkg_shares()
+            .await
+            .expect("cannot read the DKG shares")
+            .map(|shares| shares.aggregate_key)
+    }
+
+    /// Make a deposit of the given amount to the signers, funded by the
+    /// faucet, and register it with Emily. The deposit transaction is
+    /// broadcast but not confirmed.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the signers have not run DKG yet, or if bitcoin-core
+    /// or Emily reject the deposit.
+    pub async fn create_deposit(
+        &self,
+        amount: u64,
+        max_fee: u64,
+        recipient: PrincipalData,
+    ) -> (Transaction, DepositInfo) {
+        let aggregate_key = self
+            .aggregate_key()
+            .await
+            .expect("the signers have not run DKG yet");
+
+        let deposit_inputs = DepositScriptInputs {
+            signers_public_key: aggregate_key.into(),
+            max_fee,
+            recipient,
+        };
+        let reclaim_inputs = ReclaimScriptInputs::try_new(DEPOSIT_LOCK_TIME, ScriptBuf::new())
+            .expect("invalid reclaim script inputs");
+        let deposit_script = deposit_inputs.deposit_script();
+        let reclaim_script = reclaim_inputs.reclaim_script();
+        let script_pubkey =
+            sbtc::deposits::to_script_pubkey(deposit_script.clone(), reclaim_script.clone());
+
+        let bitcoin = self.bitcoin().await;
+        let outpoint = bitcoin.get_faucet().send_to_script(amount, script_pubkey);
+        let tx = bitcoin
+            .rpc()
+            .get_raw_transaction(&outpoint.txid, None)
+            .expect("cannot fetch the deposit transaction");
+
+        let request = CreateDepositRequest {
+            outpoint,
+            deposit_script,
+            reclaim_script,
+        };
+        let info = request
+            .validate_tx(&tx, false)
+            .expect("invalid deposit transaction");
+
+        let body = build_emily_request(&info, &tx);
+        deposit_api::create_deposit(self.emily_client.config(), body)
+            .await
+            .expect("cannot create the deposit in Emily");
+
+        (tx, info)
+    }
+
+    /// Poll every signer with the given predicate until it holds for all
+    /// of them, or until the timeout elapses.
+    pub async fn wait_until<F, Fut>(&self, timeout: Duration, predicate: F) -> Result<(), Elapsed>
+    where
+        F: Fn(DevEnvContext<Stacks>) -> Fut,
+        Fut: Future<Output = bool>,
+    {
+        tokio::time::timeout(timeout, async {
+            loop {
+                let mut all = true;
+                for signer in self.signers.iter() {
+                    all &= predicate(signer.context.clone()).await;
+                }
+                if all {
+                    return;
+                }
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        })
+        .await
+    }
+
+    /// Stop the signers, drop their databases and delete the Emily
+    /// tables. The containers are stopped when the environment is
+    /// dropped.
+    pub async fn shutdown(self) {
+        for signer in self.signers {
+            let _ = signer
+                .context
+                .signal(SignerSignal::Command(SignerCommand::Shutdown));
+            for handle in signer.event_loops {
+                handle.abort();
+                let _ = handle.await;
+            }
+            testing::storage::drop_db(signer.context.inner_storage()).await;
+        }
+        self.emily_tables.delete().await;
+    }
+}
+
+/// Create a client for the Emily server at the given URL that uses the
+/// given tables.
+fn new_emily_client(url: Url, tables: &EmilyTables) -> EmilyClient {
+    let mut headers = reqwest::header::HeaderMap::new();
+    for (shortname, table_name) in [
+        ("deposit", &tables.deposit),
+        ("withdrawal", &tables.withdrawal),
+        ("deposit-archive", &tables.deposit_archive),
+        ("withdrawal-archive", &tables.withdrawal_archive),
+        ("chainstate", &tables.chainstate),
+        ("limit", &tables.limit),
+        ("throttle", &tables.throttle),
+        ("api-key", &tables.api_key),
+    ] {
+        headers.insert(
+            reqwest::header::HeaderName::from_str(&format!("x-context-{shortname}")).unwrap(),
+            reqwest::header::HeaderValue::from_str(table_name).unwrap(),
+        );
+    }
+
+    let configuration = EmilyApiConfig {
+        base_path: url.to_string().trim_end_matches("/").to_string(),
+        api_key: Some(ApiKey {
+            prefix: None,
+            key: EMILY_API_KEY.to_string(),
+        }),
+        client: reqwest::ClientBuilder::new()
+            .default_headers(headers)
+            .build()
+            .expect("cannot create the Emily HTTP client"),
+        ..Default::default()
+    };
+
+    EmilyClient::new(configuration, Duration::from_secs(1), None)
+}
+
+/// Send STX from the stacks faucet to the signers' wallet and wait for
+/// the transfer to land.
+async fn fund_signers_wallet(stacks_client: &StacksClient, wallet: &SignerWallet) {
+    let private_key = PrivateKey::from_str(STACKS_FAUCET_PRIVATE_KEY).unwrap();
+    let public_key = PublicKey::from_private_key(&private_key);
+    let sender = StacksAddress::from_public_keys(
+        C32_ADDRESS_VERSION_TESTNET_SINGLESIG,
+        &AddressHashMode::SerializeP2PKH,
+        1,
+        &vec![public_key.into()],
+    )
+    .expect("failed to construct the stacks address");
+
+    let nonce = stacks_client
+        .get_account(&sender)
+        .await
+        .expect("cannot get the faucet account")
+        .nonce;
+
+    let recipient = PrincipalData::from(wallet.address().clone());
+    let auth = SinglesigSpendingCondition {
+        signer: sender.bytes().clone(),
+        nonce,
+        tx_fee: 1000,
+        hash_mode: SinglesigHashMode::P2PKH,
+        key_encoding: TransactionPublicKeyEncoding::Compressed,
+        signature: MessageSignature::empty(),
+    };
+    let mut tx = StacksTransaction {
+        version: TransactionVersion::Testnet,
+        chain_id: CHAIN_ID_TESTNET,
+        auth: TransactionAuth::Standard(TransactionSpendingCondition::Singlesig(auth)),
+        anchor_mode: TransactionAnchorMode::Any,
+        post_condition_mode: TransactionPostConditionMode::Allow,
+        post_conditions: Vec::new(),
+        payload: TransactionPayload::TokenTransfer(
+            recipient,
+            SIGNERS_WALLET_USTX,
+            TokenTransferMemo([0; 34]),
+        ),
+    };
+
+    let signature = crate::signature::sign_stacks_tx(&tx, &private_key).as_stacks_sig();
+    if let TransactionAuth::Standard(TransactionSpendingCondition::Singlesig(auth)) = &mut tx.auth {
+        auth.set_signature(signature);
+    }
+
+    stacks_client
+        .submit_tx(&tx)
+        .await
+        .expect("failed to send stacks transaction");
+
+    let funded = async {
+        loop {
+            let account = stacks_client.get_account(wallet.address()).await;
+            if account.is_ok_and(|account| account.balance > 0) {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+    };
+    tokio::time::timeout(STACKS_NODE_TIMEOUT, funded)
+        .await
+        .expect("the signers' wallet was not funded in time");
+}
@@ -6,3 +6,5 @@ mod testing;
 mod wsts;
 
 pub mod verification;
+
+pub use wsts::WstsNetMessageType;
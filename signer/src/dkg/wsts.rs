@@ -1,7 +1,8 @@
 //! Contains WSTS helper types and impls for use within the DKG module.
 
 /// A helper enum to represent the different types of WSTS messages.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display)]
+#[strum(serialize_all = "snake_case")]
 pub enum WstsNetMessageType {
     /// A DKG begin message.
     DkgBegin,
@@ -176,8 +176,17 @@ impl Signed<SignerMessage> {
             }
         }
 
-        // Okay now we transform the protobuf type into our local type.
-        let msg = Signed::<SignerMessage>::try_from(message)?;
+        // Okay now we transform the protobuf type into our local type. If
+        // that fails then the sender may be running the prior version of
+        // the protobuf schema, so we try decoding with that before giving
+        // up. The digest below is computed from the raw bytes either way.
+        let msg = match Signed::<SignerMessage>::try_from(message) {
+            Ok(msg) => msg,
+            Err(error) => proto::compat::decode_prior_version(data)
+                .ok()
+                .and_then(|message| Signed::<SignerMessage>::try_from(message).ok())
+                .ok_or(error)?,
+        };
         // Now we construct the digest that was signed over.
         let mut hasher = sha2::Sha256::new_with_prefix(msg.type_tag());
         hasher.update(pre_hash_data);
@@ -297,6 +306,8 @@ mod tests {
         let original_message = SignerMessage {
             bitcoin_chain_tip: BitcoinBlockHash::from([1; 32]),
             payload: Faker.fake_with_rng::<T, _>(&mut OsRng).into(),
+            deadline: None,
+            trace_context: None,
         };
 
         // We sign a payload digest. It should always be what this function
@@ -355,6 +366,8 @@ mod tests {
         let original_message = SignerMessage {
             bitcoin_chain_tip: BitcoinBlockHash::from([1; 32]),
             payload: Faker.fake_with_rng::<T, _>(&mut OsRng).into(),
+            deadline: None,
+            trace_context: None,
         };
 
         // We sign a payload digest. It should always be what this function
@@ -443,6 +456,8 @@ mod tests {
         let original_message = SignerMessage {
             bitcoin_chain_tip: BitcoinBlockHash::from([1; 32]),
             payload: Faker.fake_with_rng::<T, _>(&mut OsRng).into(),
+            deadline: None,
+            trace_context: None,
         };
 
         // The upgraded signer sends messages with an additional field.
@@ -622,6 +637,8 @@ mod tests {
         let signer_message = SignerMessage {
             bitcoin_chain_tip: fake::Faker.fake_with_rng(&mut rng),
             payload: message::Payload::SignerWithdrawalDecision(payload.clone()),
+            deadline: None,
+            trace_context: None,
         };
 
         let msg = signer_message.sign_ecdsa(&private_key);
@@ -1,30 +1,44 @@
 //! Emily API client module
 
-use std::str::FromStr as _;
+use std::pin::Pin;
+use std::task::Poll;
 use std::time::Duration;
-use std::time::Instant;
 
 use bitcoin::Amount;
-use bitcoin::OutPoint;
+use bitcoin::BlockHash;
+use bitcoin::MerkleBlock;
 use bitcoin::ScriptBuf;
-use bitcoin::Txid;
 use emily_client::apis::Error as EmilyError;
 use emily_client::apis::ResponseContent;
+use emily_client::apis::configuration;
 use emily_client::apis::configuration::ApiKey;
 use emily_client::apis::configuration::Configuration as EmilyApiConfig;
+use emily_client::apis::configuration::IncompatibleApiVersion;
 use emily_client::apis::deposit_api;
+use emily_client::apis::health_api;
 use emily_client::apis::limits_api;
 use emily_client::apis::withdrawal_api;
 use emily_client::models::DepositInfo;
 use emily_client::models::DepositUpdate;
 use emily_client::models::ExpectedFulfillmentInfo;
+use emily_client::models::Fulfillment;
+use emily_client::models::SetAvailableToMintRequestBody;
 use emily_client::models::UpdateDepositsRequestBody;
 use emily_client::models::UpdateDepositsResponse;
 use emily_client::models::UpdateWithdrawalsRequestBody;
 use emily_client::models::UpdateWithdrawalsResponse;
+use emily_client::models::Withdrawal;
+use emily_client::models::WithdrawalInfo;
 use emily_client::models::WithdrawalUpdate;
 use emily_client::models::{DepositStatus, WithdrawalStatus};
+use futures::Stream;
+use futures::StreamExt as _;
+use futures::TryStreamExt as _;
+use futures::stream::BoxStream;
 use sbtc::deposits::CreateDepositRequest;
+use sbtc::ids::DepositId;
+use sbtc::ids::WithdrawalId;
+use tokio::time::Instant;
 use url::Url;
 
 use crate::bitcoin::utxo::RequestRef;
@@ -32,8 +46,9 @@ use crate::bitcoin::utxo::UnsignedTransaction;
 use crate::config::EmilyClientConfig;
 use crate::context::SbtcLimits;
 use crate::error::Error;
-use crate::storage::model::BitcoinTxId;
+use crate::storage::model::SweptWithdrawalRequest;
 use crate::util::ApiFallbackClient;
+use crate::util::HealthCheck;
 
 /// Emily client error variants.
 #[derive(Debug, thiserror::Error)]
@@ -65,6 +80,31 @@ pub enum EmilyClientError {
     /// An error occurred while getting limits
     #[error("error getting limits: {0}")]
     GetLimits(EmilyError<limits_api::GetLimitsError>),
+
+    /// An error occurred while reporting the amount available to mint
+    #[error("error setting the amount available to mint: {0}")]
+    SetAvailableToMint(EmilyError<limits_api::SetAvailableToMintError>),
+
+    /// An error occurred while getting a withdrawal request
+    #[error("error getting a withdrawal: {0}")]
+    GetWithdrawal(EmilyError<withdrawal_api::GetWithdrawalError>),
+
+    /// An error occurred while getting withdrawals
+    #[error("error getting withdrawals: {0}")]
+    GetWithdrawals(EmilyError<withdrawal_api::GetWithdrawalsError>),
+
+    /// An error occurred while checking the health of Emily
+    #[error("error checking the health of Emily: {0}")]
+    CheckHealth(EmilyError<health_api::CheckHealthError>),
+
+    /// Emily does not serve the version of the API that we understand
+    #[error("incompatible Emily API version at {url}: {error}")]
+    IncompatibleApiVersion {
+        /// The url of the Emily endpoint.
+        url: String,
+        /// The versions of the API that Emily serves.
+        error: IncompatibleApiVersion,
+    },
 }
 
 /// Trait describing the interactions with Emily API.
@@ -73,8 +113,7 @@ pub trait EmilyInteract: Sync + Send {
     /// Get a deposit from Emily.
     fn get_deposit(
         &self,
-        txid: &BitcoinTxId,
-        output_index: u32,
+        id: &DepositId,
     ) -> impl std::future::Future<Output = Result<Option<CreateDepositRequest>, Error>> + Send;
 
     /// Get pending and accepted deposits to process from Emily.
@@ -118,6 +157,25 @@ pub trait EmilyInteract: Sync + Send {
 
     /// Gets the current sBTC-cap limits from Emily.
     fn get_limits(&self) -> impl std::future::Future<Output = Result<SbtcLimits, Error>> + Send;
+
+    /// Report the maximum amount of sBTC that can currently be minted to
+    /// Emily.
+    fn set_available_to_mint(
+        &self,
+        available_to_mint: Amount,
+    ) -> impl std::future::Future<Output = Result<(), Error>> + Send;
+
+    /// Get a withdrawal from Emily.
+    fn get_withdrawal(
+        &self,
+        id: WithdrawalId,
+    ) -> impl std::future::Future<Output = Result<Option<Withdrawal>, Error>> + Send;
+
+    /// Get withdrawals with a specific status from Emily.
+    fn get_withdrawals_with_status(
+        &self,
+        status: WithdrawalStatus,
+    ) -> impl std::future::Future<Output = Result<Vec<WithdrawalInfo>, Error>> + Send;
 }
 
 /// Emily API client.
@@ -137,6 +195,13 @@ impl EmilyClient {
         &self.config
     }
 
+    /// Authenticate every request with the given Emily api key, formatted
+    /// as `<name>:<secret>`.
+    pub fn with_api_key(mut self, api_key: Option<String>) -> Self {
+        self.config.bearer_access_token = api_key;
+        self
+    }
+
     /// Initialize a new Emily client and validate the url.
     pub fn try_new(
         url: &Url,
@@ -175,8 +240,11 @@ impl EmilyClient {
         config.base_path = url.to_string().trim_end_matches("/").to_string();
         config.api_key = api_key;
 
+        // Every request names the version of the API that we understand,
+        // so that Emily rejects them if it no longer serves it.
         let client = reqwest::Client::builder()
             .timeout(timeout)
+            .default_headers(configuration::api_version_headers())
             .build()
             .map_err(Error::EmilyReqwestClientCreation)?;
 
@@ -206,12 +274,44 @@ impl EmilyClient {
         }
     }
 
+    /// Check that Emily serves the version of the API that this client
+    /// understands, returning that version.
+    pub async fn check_api_version(&self) -> Result<u32, Error> {
+        let health = health_api::check_health(&self.config)
+            .await
+            .map_err(EmilyClientError::CheckHealth)?;
+
+        configuration::check_api_version(&health).map_err(|error| {
+            Error::EmilyApi(EmilyClientError::IncompatibleApiVersion {
+                url: self.config.base_path.clone(),
+                error,
+            })
+        })
+    }
+
+    /// Return a pager over the deposits in Emily with the given status.
+    pub fn deposits_pager(&self, status: DepositStatus) -> DepositsPager {
+        DepositsPager::new(&self.config, status, self.pager_options())
+    }
+
+    /// Return a pager over the withdrawals in Emily with the given
+    /// status.
+    pub fn withdrawals_pager(&self, status: WithdrawalStatus) -> WithdrawalsPager {
+        WithdrawalsPager::new(&self.config, status, self.pager_options())
+    }
+
+    fn pager_options(&self) -> PagerOptions {
+        PagerOptions {
+            page_size: self.page_size,
+            page_interval: EMILY_PAGE_INTERVAL,
+            timeout: Some(self.pagination_timeout),
+        }
+    }
+
     fn parse_deposit(deposit: &DepositInfo) -> Result<CreateDepositRequest, Error> {
+        let id = DepositId::from_hex(&deposit.bitcoin_txid, deposit.bitcoin_tx_output_index)?;
         Ok(CreateDepositRequest {
-            outpoint: OutPoint {
-                txid: Txid::from_str(&deposit.bitcoin_txid).map_err(Error::DecodeHexTxid)?,
-                vout: deposit.bitcoin_tx_output_index,
-            },
+            outpoint: id.outpoint(),
             reclaim_script: ScriptBuf::from_hex(&deposit.reclaim_script)
                 .map_err(Error::DecodeHexScript)?,
             deposit_script: ScriptBuf::from_hex(&deposit.deposit_script)
@@ -221,13 +321,9 @@ impl EmilyClient {
 }
 
 impl EmilyInteract for EmilyClient {
-    async fn get_deposit(
-        &self,
-        txid: &BitcoinTxId,
-        output_index: u32,
-    ) -> Result<Option<CreateDepositRequest>, Error> {
-        let txid_str = txid.to_string();
-        let index = output_index.to_string();
+    async fn get_deposit(&self, id: &DepositId) -> Result<Option<CreateDepositRequest>, Error> {
+        let txid_str = id.txid.to_string();
+        let index = id.vout.to_string();
 
         let resp = deposit_api::get_deposit(&self.config, &txid_str, &index).await;
 
@@ -241,11 +337,9 @@ impl EmilyInteract for EmilyClient {
             error => error.map_err(EmilyClientError::GetDeposit)?,
         };
 
+        let id = DepositId::from_hex(&deposit.bitcoin_txid, deposit.bitcoin_tx_output_index)?;
         Ok(Some(CreateDepositRequest {
-            outpoint: OutPoint {
-                txid: Txid::from_str(&deposit.bitcoin_txid).map_err(Error::DecodeHexTxid)?,
-                vout: deposit.bitcoin_tx_output_index,
-            },
+            outpoint: id.outpoint(),
             reclaim_script: ScriptBuf::from_hex(&deposit.reclaim_script)
                 .map_err(Error::DecodeHexScript)?,
             deposit_script: ScriptBuf::from_hex(&deposit.deposit_script)
@@ -285,51 +379,24 @@ impl EmilyInteract for EmilyClient {
         status: DepositStatus,
     ) -> Result<Vec<CreateDepositRequest>, Error> {
         let mut all_deposits = Vec::new();
-        let mut next_token: Option<String> = None;
-        let start_time = Instant::now();
-        loop {
-            let resp = match deposit_api::get_deposits(
-                &self.config,
-                status,
-                next_token.as_deref(),
-                self.page_size,
-            )
-            .await
-            {
-                Ok(resp) => resp,
-                Err(e) => {
-                    if all_deposits.is_empty() {
-                        return Err(Error::EmilyApi(EmilyClientError::GetDeposits(e)));
-                    }
-                    tracing::warn!("failed to fetch page of deposits: {:?}", e);
+        let mut pager = self.deposits_pager(status);
+        while let Some(item) = pager.next().await {
+            let deposit = match item {
+                Ok(deposit) => deposit,
+                Err(error) if all_deposits.is_empty() => return Err(error),
+                Err(error) => {
+                    tracing::warn!("failed to fetch page of deposits: {:?}", error);
                     break;
                 }
             };
             // Convert each DepositInfo to our CreateDepositRequest
-            for deposit in resp.deposits.iter() {
-                match Self::parse_deposit(deposit) {
-                    Ok(req) => all_deposits.push(req),
-                    Err(e) => tracing::warn!(
-                        "Skipping corrupted deposit (txid: {}): {:?}",
-                        deposit.bitcoin_txid,
-                        e
-                    ),
-                }
-            }
-
-            // If more pages exist, loop again; otherwise stop
-            match resp.next_token.flatten() {
-                Some(token) => next_token = Some(token),
-                None => break,
-            }
-
-            if start_time.elapsed() > self.pagination_timeout {
-                tracing::warn!(
-                    "timeout fetching deposits, breaking at page {:?}, fetched {} deposits",
-                    next_token,
-                    all_deposits.len()
-                );
-                break;
+            match Self::parse_deposit(&deposit) {
+                Ok(req) => all_deposits.push(req),
+                Err(e) => tracing::warn!(
+                    "Skipping corrupted deposit (txid: {}): {:?}",
+                    deposit.bitcoin_txid,
+                    e
+                ),
             }
         }
 
@@ -395,6 +462,8 @@ impl EmilyInteract for EmilyClient {
                 fulfillment: None,
                 status_message: "".to_string(),
                 replaced_by_tx: None,
+                deposit_block_hash: None,
+                deposit_tx_proof: None,
             })
             .collect();
 
@@ -445,27 +514,79 @@ impl EmilyInteract for EmilyClient {
             None,
         ))
     }
+
+    async fn set_available_to_mint(&self, available_to_mint: Amount) -> Result<(), Error> {
+        let body = SetAvailableToMintRequestBody {
+            available_to_mint: available_to_mint.to_sat(),
+        };
+        limits_api::set_available_to_mint(&self.config, body)
+            .await
+            .map_err(EmilyClientError::SetAvailableToMint)
+            .map_err(Error::EmilyApi)?;
+
+        Ok(())
+    }
+
+    async fn get_withdrawal(&self, id: WithdrawalId) -> Result<Option<Withdrawal>, Error> {
+        let resp = withdrawal_api::get_withdrawal(&self.config, id.get()).await;
+
+        match resp {
+            Ok(withdrawal) => Ok(Some(withdrawal)),
+            Err(EmilyError::ResponseError(ResponseContent { status, .. }))
+                if status.as_u16() == 404 =>
+            {
+                Ok(None)
+            }
+            Err(error) => Err(EmilyClientError::GetWithdrawal(error).into()),
+        }
+    }
+
+    async fn get_withdrawals_with_status(
+        &self,
+        status: WithdrawalStatus,
+    ) -> Result<Vec<WithdrawalInfo>, Error> {
+        let mut all_withdrawals = Vec::new();
+        let mut pager = self.withdrawals_pager(status);
+        while let Some(item) = pager.next().await {
+            match item {
+                Ok(withdrawal) => all_withdrawals.push(withdrawal),
+                Err(error) if all_withdrawals.is_empty() => return Err(error),
+                Err(error) => {
+                    tracing::warn!("failed to fetch page of withdrawals: {:?}", error);
+                    break;
+                }
+            }
+        }
+
+        Ok(all_withdrawals)
+    }
+}
+
+impl HealthCheck for EmilyClient {
+    async fn is_healthy(&self) -> bool {
+        health_api::check_health(&self.config)
+            .await
+            .inspect_err(|error| tracing::debug!(%error, "Emily health check failed"))
+            .is_ok()
+    }
 }
 
 impl EmilyInteract for ApiFallbackClient<EmilyClient> {
-    async fn get_deposit(
-        &self,
-        txid: &BitcoinTxId,
-        output_index: u32,
-    ) -> Result<Option<CreateDepositRequest>, Error> {
-        self.exec(|client, _| client.get_deposit(txid, output_index))
+    async fn get_deposit(&self, id: &DepositId) -> Result<Option<CreateDepositRequest>, Error> {
+        self.exec_preferring_primary(|client, _| client.get_deposit(id))
             .await
     }
 
     async fn get_deposits(&self) -> Result<Vec<CreateDepositRequest>, Error> {
-        self.exec(|client, _| client.get_deposits()).await
+        self.exec_preferring_primary(|client, _| client.get_deposits())
+            .await
     }
 
     async fn get_deposits_with_status(
         &self,
         status: DepositStatus,
     ) -> Result<Vec<CreateDepositRequest>, Error> {
-        self.exec(|client, _| client.get_deposits_with_status(status))
+        self.exec_preferring_primary(|client, _| client.get_deposits_with_status(status))
             .await
     }
 
@@ -473,7 +594,7 @@ impl EmilyInteract for ApiFallbackClient<EmilyClient> {
         &self,
         update_deposits: Vec<DepositUpdate>,
     ) -> Result<UpdateDepositsResponse, Error> {
-        self.exec(|client, _| client.update_deposits(update_deposits.clone()))
+        self.exec_preferring_primary(|client, _| client.update_deposits(update_deposits.clone()))
             .await
     }
 
@@ -481,7 +602,7 @@ impl EmilyInteract for ApiFallbackClient<EmilyClient> {
         &'a self,
         transaction: &'a UnsignedTransaction<'a>,
     ) -> Result<UpdateDepositsResponse, Error> {
-        self.exec(|client, _| client.accept_deposits(transaction))
+        self.exec_preferring_primary(|client, _| client.accept_deposits(transaction))
             .await
     }
 
@@ -489,7 +610,7 @@ impl EmilyInteract for ApiFallbackClient<EmilyClient> {
         &'a self,
         transaction: &'a UnsignedTransaction<'a>,
     ) -> Result<UpdateWithdrawalsResponse, Error> {
-        self.exec(|client, _| client.accept_withdrawals(transaction))
+        self.exec_preferring_primary(|client, _| client.accept_withdrawals(transaction))
             .await
     }
 
@@ -497,12 +618,89 @@ impl EmilyInteract for ApiFallbackClient<EmilyClient> {
         &self,
         update_withdrawals: Vec<WithdrawalUpdate>,
     ) -> Result<UpdateWithdrawalsResponse, Error> {
-        self.exec(|client, _| client.update_withdrawals(update_withdrawals.clone()))
-            .await
+        self.exec_preferring_primary(|client, _| {
+            client.update_withdrawals(update_withdrawals.clone())
+        })
+        .await
     }
 
     async fn get_limits(&self) -> Result<SbtcLimits, Error> {
-        self.exec(|client, _| client.get_limits()).await
+        self.exec_preferring_primary(|client, _| client.get_limits())
+            .await
+    }
+
+    async fn set_available_to_mint(&self, available_to_mint: Amount) -> Result<(), Error> {
+        self.exec_preferring_primary(|client, _| client.set_available_to_mint(available_to_mint))
+            .await
+    }
+
+    async fn get_withdrawal(&self, id: WithdrawalId) -> Result<Option<Withdrawal>, Error> {
+        self.exec_preferring_primary(|client, _| client.get_withdrawal(id))
+            .await
+    }
+
+    async fn get_withdrawals_with_status(
+        &self,
+        status: WithdrawalStatus,
+    ) -> Result<Vec<WithdrawalInfo>, Error> {
+        self.exec_preferring_primary(|client, _| client.get_withdrawals_with_status(status))
+            .await
+    }
+}
+
+/// Create the update that attaches the SPV proof of the sweep transaction,
+/// and the fee assessed against the withdrawal, to the fulfillment of a
+/// withdrawal that Emily has already confirmed.
+///
+/// Emily only accepts this update from the signers if the fulfillment
+/// that it has on record is for the same sweep transaction.
+pub fn withdrawal_fulfillment_proof(
+    request: &SweptWithdrawalRequest,
+    proof: &MerkleBlock,
+    assessed_fee: Amount,
+) -> WithdrawalUpdate {
+    let fulfillment = Fulfillment {
+        assessed_fee: Some(Some(assessed_fee.to_sat())),
+        bitcoin_block_hash: request.sweep_block_hash.to_string(),
+        bitcoin_block_height: *request.sweep_block_height,
+        bitcoin_tx_index: request.output_index,
+        bitcoin_txid: request.sweep_txid.to_string(),
+        // Emily keeps the fee and the stacks txid that it observed in the
+        // accept-withdrawal event.
+        btc_fee: 0,
+        stacks_txid: String::new(),
+        sweep_tx_proof: Some(Some(bitcoin::consensus::encode::serialize_hex(proof))),
+    };
+
+    WithdrawalUpdate {
+        request_id: request.request_id,
+        fulfillment: Some(Some(Box::new(fulfillment))),
+        status: WithdrawalStatus::Confirmed,
+        expected_fulfillment_info: None,
+        status_message: "".to_string(),
+    }
+}
+
+/// Create the update that attaches the SPV proof that the deposit
+/// transaction was confirmed in the given bitcoin block to the deposit
+/// request with the given ID.
+///
+/// The update carries the pending status, which Emily treats as leaving
+/// the status of the deposit as it is.
+pub fn deposit_tx_proof(
+    id: DepositId,
+    block_hash: BlockHash,
+    proof: &MerkleBlock,
+) -> DepositUpdate {
+    DepositUpdate {
+        bitcoin_tx_output_index: id.vout,
+        bitcoin_txid: id.txid.to_string(),
+        status: DepositStatus::Pending,
+        fulfillment: None,
+        status_message: "".to_string(),
+        replaced_by_tx: None,
+        deposit_block_hash: Some(Some(block_hash.to_string())),
+        deposit_tx_proof: Some(Some(bitcoin::consensus::encode::serialize_hex(proof))),
     }
 }
 
@@ -513,15 +711,180 @@ impl TryFrom<&EmilyClientConfig> for ApiFallbackClient<EmilyClient> {
         let clients = config
             .endpoints
             .iter()
-            .map(|url| EmilyClient::try_new(url, config.timeout, config.pagination_timeout, None))
+            .map(|url| {
+                EmilyClient::try_new(url, config.timeout, config.pagination_timeout, None)
+                    .map(|client| client.with_api_key(config.api_key.clone()))
+            })
             .collect::<Result<Vec<_>, _>>()?;
 
-        Self::new(clients).map_err(Into::into)
+        let client = Self::new(clients)?;
+        client.set_primary_recheck_interval(config.primary_recheck_interval);
+        Ok(client)
+    }
+}
+
+/// The minimum time between requests for consecutive pages when the
+/// signer follows the pages of a result from Emily.
+pub const EMILY_PAGE_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Options that control how a pager fetches pages from Emily.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PagerOptions {
+    /// The maximum number of items to request per page. If None, only
+    /// Emily's 1 MB cap on the size of each response applies.
+    pub page_size: Option<u32>,
+    /// The minimum time between requests for consecutive pages.
+    pub page_interval: Duration,
+    /// How long after the pager is created to keep requesting new pages.
+    /// Once it elapses the pager yields the items that it has already
+    /// fetched and then ends. If None, the pager follows every page.
+    pub timeout: Option<Duration>,
+}
+
+/// A stream over all deposits in Emily with a given status.
+///
+/// The pager requests the next page whenever it runs out of items,
+/// following the next-token of each page until Emily reports that there
+/// are no more. The stream ends after the first error.
+pub struct DepositsPager {
+    inner: BoxStream<'static, Result<DepositInfo, Error>>,
+}
+
+impl DepositsPager {
+    /// Create a pager over the deposits with the given status.
+    pub fn new(config: &EmilyApiConfig, status: DepositStatus, options: PagerOptions) -> Self {
+        let config = config.clone();
+        let fetch = move |next_token: Option<String>| {
+            let config = config.clone();
+            async move {
+                deposit_api::get_deposits(
+                    &config,
+                    status,
+                    next_token.as_deref(),
+                    options.page_size,
+                    None,
+                )
+                .await
+                .map(|resp| (resp.deposits, resp.next_token.flatten()))
+                .map_err(EmilyClientError::GetDeposits)
+                .map_err(Error::EmilyApi)
+            }
+        };
+
+        Self {
+            inner: paginate(fetch, options, "deposits"),
+        }
+    }
+}
+
+impl Stream for DepositsPager {
+    type Item = Result<DepositInfo, Error>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
     }
 }
 
+/// A stream over all withdrawals in Emily with a given status.
+///
+/// The pager requests the next page whenever it runs out of items,
+/// following the next-token of each page until Emily reports that there
+/// are no more. The stream ends after the first error.
+pub struct WithdrawalsPager {
+    inner: BoxStream<'static, Result<WithdrawalInfo, Error>>,
+}
+
+impl WithdrawalsPager {
+    /// Create a pager over the withdrawals with the given status.
+    pub fn new(config: &EmilyApiConfig, status: WithdrawalStatus, options: PagerOptions) -> Self {
+        let config = config.clone();
+        let fetch = move |next_token: Option<String>| {
+            let config = config.clone();
+            async move {
+                withdrawal_api::get_withdrawals(
+                    &config,
+                    status,
+                    next_token.as_deref(),
+                    options.page_size,
+                    None,
+                )
+                .await
+                .map(|resp| (resp.withdrawals, resp.next_token.flatten()))
+                .map_err(EmilyClientError::GetWithdrawals)
+                .map_err(Error::EmilyApi)
+            }
+        };
+
+        Self {
+            inner: paginate(fetch, options, "withdrawals"),
+        }
+    }
+}
+
+impl Stream for WithdrawalsPager {
+    type Item = Result<WithdrawalInfo, Error>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
+}
+
+/// Turn a function that fetches a page of items, given the next-token
+/// of the previous page, into a stream over the items of every page.
+fn paginate<T, F, Fut>(
+    mut fetch: F,
+    options: PagerOptions,
+    kind: &'static str,
+) -> BoxStream<'static, Result<T, Error>>
+where
+    T: Send + 'static,
+    F: FnMut(Option<String>) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<(Vec<T>, Option<String>), Error>> + Send + 'static,
+{
+    let deadline = options.timeout.map(|timeout| Instant::now() + timeout);
+    // The first element of the state is the next-token to request the
+    // next page with, and is None once there are no more pages. The
+    // second is when we requested the previous page.
+    let state: (Option<Option<String>>, Option<Instant>) = (Some(None), None);
+
+    futures::stream::try_unfold(state, move |(next_token, last_request)| {
+        let page = next_token.map(&mut fetch);
+        async move {
+            let Some(page) = page else {
+                return Ok::<_, Error>(None);
+            };
+            if let Some(last_request) = last_request {
+                tokio::time::sleep_until(last_request + options.page_interval).await;
+            }
+
+            let requested_at = Instant::now();
+            let (items, mut next_token) = page.await?;
+
+            if next_token.is_some() && deadline.is_some_and(|deadline| deadline <= Instant::now()) {
+                tracing::warn!("timeout fetching {kind}, breaking at page {:?}", next_token);
+                next_token = None;
+            }
+
+            Ok(Some((items, (next_token.map(Some), Some(requested_at)))))
+        }
+    })
+    .map_ok(|items| futures::stream::iter(items.into_iter().map(Ok)))
+    .try_flatten()
+    .boxed()
+}
+
 #[cfg(test)]
 mod tests {
+    use fake::Fake as _;
+
+    use crate::testing::get_rng;
+
     use super::*;
 
     #[test]
@@ -549,4 +912,192 @@ mod tests {
         assert_eq!(client.config.base_path, "http://localhost:8080");
         assert!(client.config.api_key.is_none());
     }
+
+    #[test]
+    fn try_from_config_sends_the_emily_api_key() {
+        let mut settings = crate::config::Settings::new_from_default_config().unwrap();
+        settings.emily.api_key = Some("signer:secret".to_string());
+
+        let client = ApiFallbackClient::<EmilyClient>::try_from(&settings.emily).unwrap();
+        let config = client.get_client().config();
+        assert_eq!(config.bearer_access_token.as_deref(), Some("signer:secret"));
+        // The API Gateway key is still taken from the endpoint URL.
+        assert_eq!(config.api_key.as_ref().unwrap().key, "testApiKey");
+    }
+
+    #[test]
+    fn withdrawal_fulfillment_proof_round_trips() {
+        let mut rng = get_rng();
+        let mut block = crate::testing::dummy::block(&fake::Faker, &mut rng, 100);
+        block.header.merkle_root = block.compute_merkle_root().unwrap();
+        let sweep_txid = block.txdata.last().unwrap().compute_txid();
+        let proof = MerkleBlock::from_block_with_predicate(&block, |txid| txid == &sweep_txid);
+
+        let request = SweptWithdrawalRequest {
+            sweep_txid: sweep_txid.into(),
+            sweep_block_hash: block.block_hash().into(),
+            ..fake::Faker.fake_with_rng(&mut rng)
+        };
+        let update = withdrawal_fulfillment_proof(&request, &proof, Amount::from_sat(1_234));
+
+        assert_eq!(update.request_id, request.request_id);
+        assert_eq!(update.status, WithdrawalStatus::Confirmed);
+        let fulfillment = update.fulfillment.flatten().unwrap();
+        assert_eq!(fulfillment.bitcoin_txid, sweep_txid.to_string());
+        assert_eq!(
+            fulfillment.bitcoin_block_hash,
+            block.block_hash().to_string()
+        );
+        assert_eq!(fulfillment.assessed_fee, Some(Some(1_234)));
+
+        let proof_hex = fulfillment.sweep_tx_proof.flatten().unwrap();
+        let decoded: MerkleBlock = bitcoin::consensus::encode::deserialize_hex(&proof_hex).unwrap();
+        let mut matches = Vec::new();
+        let mut indexes = Vec::new();
+        decoded.extract_matches(&mut matches, &mut indexes).unwrap();
+        assert_eq!(matches, vec![sweep_txid]);
+        assert_eq!(decoded.header.block_hash(), block.block_hash());
+    }
+
+    #[test]
+    fn deposit_tx_proof_round_trips() {
+        let mut rng = get_rng();
+        let mut block = crate::testing::dummy::block(&fake::Faker, &mut rng, 100);
+        block.header.merkle_root = block.compute_merkle_root().unwrap();
+        let deposit_txid = block.txdata.last().unwrap().compute_txid();
+        let proof = MerkleBlock::from_block_with_predicate(&block, |txid| txid == &deposit_txid);
+
+        let id = DepositId::new(deposit_txid, 2);
+        let update = deposit_tx_proof(id, block.block_hash(), &proof);
+
+        assert_eq!(update.bitcoin_txid, deposit_txid.to_string());
+        assert_eq!(update.bitcoin_tx_output_index, 2);
+        assert_eq!(update.status, DepositStatus::Pending);
+        assert_eq!(
+            update.deposit_block_hash,
+            Some(Some(block.block_hash().to_string()))
+        );
+
+        let proof_hex = update.deposit_tx_proof.flatten().unwrap();
+        let decoded: MerkleBlock = bitcoin::consensus::encode::deserialize_hex(&proof_hex).unwrap();
+        let mut matches = Vec::new();
+        let mut indexes = Vec::new();
+        decoded.extract_matches(&mut matches, &mut indexes).unwrap();
+        assert_eq!(matches, vec![deposit_txid]);
+        assert_eq!(decoded.header.block_hash(), block.block_hash());
+    }
+
+    #[test_case::test_case(1, 1, true; "current version")]
+    #[test_case::test_case(1, 2, true; "newer versions served")]
+    #[test_case::test_case(2, 3, false; "version no longer served")]
+    #[test_case::test_case(0, 0, false; "version not yet served")]
+    #[tokio::test]
+    async fn api_version_is_checked_against_emily(
+        min_api_version: u32,
+        api_version: u32,
+        compatible: bool,
+    ) {
+        let mut server = mockito::Server::new_async().await;
+        let body = serde_json::json!({
+            "version": "test",
+            "apiVersion": api_version,
+            "minApiVersion": min_api_version,
+        });
+        // The client names the version that it understands in every
+        // request.
+        let mock = server
+            .mock("GET", "/health")
+            .match_header(
+                configuration::ACCEPT_VERSION_HEADER,
+                configuration::EMILY_API_VERSION.to_string().as_str(),
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(body.to_string())
+            .create_async()
+            .await;
+
+        let url = Url::parse(&server.url()).unwrap();
+        let client =
+            EmilyClient::try_new(&url, Duration::from_secs(1), Duration::from_secs(1), None)
+                .unwrap();
+
+        match client.check_api_version().await {
+            Ok(version) => {
+                assert!(compatible);
+                assert_eq!(version, configuration::EMILY_API_VERSION);
+            }
+            Err(Error::EmilyApi(EmilyClientError::IncompatibleApiVersion { error, .. })) => {
+                assert!(!compatible);
+                assert_eq!(error.min_api_version, min_api_version);
+                assert_eq!(error.api_version, api_version);
+            }
+            Err(error) => panic!("unexpected error: {error}"),
+        }
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn deposits_pager_follows_next_tokens() {
+        let mut server = mockito::Server::new_async().await;
+        let deposit = |vout| DepositInfo {
+            bitcoin_tx_output_index: vout,
+            status: DepositStatus::Pending,
+            ..Default::default()
+        };
+        let first_page = emily_client::models::GetDepositsResponse {
+            deposits: vec![deposit(0), deposit(1)],
+            next_token: Some(Some("page-2".to_string())),
+        };
+        let second_page = emily_client::models::GetDepositsResponse {
+            deposits: vec![deposit(2)],
+            next_token: None,
+        };
+
+        let first_mock = server
+            .mock("GET", "/deposit")
+            .match_query(mockito::Matcher::Exact("status=pending".into()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&first_page).unwrap())
+            .expect(1)
+            .create_async()
+            .await;
+        let second_mock = server
+            .mock("GET", "/deposit")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "nextToken".into(),
+                "page-2".into(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&second_page).unwrap())
+            .expect(1)
+            .create_async()
+            .await;
+
+        let url = Url::parse(&server.url()).unwrap();
+        let client =
+            EmilyClient::try_new(&url, Duration::from_secs(1), Duration::from_secs(1), None)
+                .unwrap();
+
+        let options = PagerOptions {
+            page_interval: Duration::from_millis(100),
+            ..Default::default()
+        };
+        let start = std::time::Instant::now();
+        let deposits: Vec<DepositInfo> =
+            DepositsPager::new(client.config(), DepositStatus::Pending, options)
+                .try_collect()
+                .await
+                .unwrap();
+
+        let vouts: Vec<u32> = deposits.iter().map(|d| d.bitcoin_tx_output_index).collect();
+        assert_eq!(vouts, vec![0, 1, 2]);
+        // We waited between the requests for the two pages.
+        assert!(start.elapsed() >= options.page_interval);
+
+        first_mock.assert_async().await;
+        second_mock.assert_async().await;
+    }
 }
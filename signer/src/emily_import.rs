@@ -0,0 +1,474 @@
+//! This module contains a backfill tool for importing historical deposit
+//! and withdrawal requests from Emily into the signer's database.
+//!
+//! A signer that joins an existing deployment only learns about requests
+//! that were made after it started observing the bitcoin and stacks
+//! blockchains. Older requests are known to Emily, so the importer pages
+//! through Emily's APIs and translates what it finds into
+//! [`model::DepositRequest`] and [`model::WithdrawalRequest`] rows.
+//!
+//! Emily is not a trusted source of data, so deposit requests are only
+//! imported after validating them against bitcoin-core, and withdrawal
+//! requests are read from the sbtc-registry smart contract through the
+//! stacks node, and only imported if the stacks block that created them
+//! is already in our database. Requests that were imported this way are
+//! recorded as such, so that they can be told apart from requests that
+//! the signer observed directly.
+
+use emily_client::models::DepositStatus;
+use emily_client::models::WithdrawalInfo;
+use emily_client::models::WithdrawalStatus;
+use sbtc::deposits::CreateDepositRequest;
+
+use crate::bitcoin::BitcoinInteract as _;
+use crate::block_observer::Deposit;
+use crate::context::Context;
+use crate::emily_client::EmilyInteract as _;
+use crate::error::Error;
+use crate::logging::CorrelationId;
+use crate::stacks::api::StacksInteract as _;
+use crate::storage::DbRead as _;
+use crate::storage::DbWrite as _;
+use crate::storage::Transactable as _;
+use crate::storage::TransactionHandle as _;
+use crate::storage::model;
+
+/// All deposit statuses, in the order that the importer fetches them.
+const DEPOSIT_STATUSES: [DepositStatus; 5] = [
+    DepositStatus::Pending,
+    DepositStatus::Accepted,
+    DepositStatus::Confirmed,
+    DepositStatus::Failed,
+    DepositStatus::Rbf,
+];
+
+/// All withdrawal statuses, in the order that the importer fetches them.
+const WITHDRAWAL_STATUSES: [WithdrawalStatus; 4] = [
+    WithdrawalStatus::Pending,
+    WithdrawalStatus::Accepted,
+    WithdrawalStatus::Confirmed,
+    WithdrawalStatus::Failed,
+];
+
+/// A summary of the outcome of an import.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    /// The number of deposit requests that were written to the database.
+    pub deposits_imported: usize,
+    /// The number of deposit requests that were not written to the
+    /// database, either because we already knew about them or because
+    /// they could not be validated.
+    pub deposits_skipped: usize,
+    /// The number of withdrawal requests that were written to the
+    /// database.
+    pub withdrawals_imported: usize,
+    /// The number of withdrawal requests that were not written to the
+    /// database, because they could not be validated.
+    pub withdrawals_skipped: usize,
+}
+
+/// Imports historical deposit and withdrawal requests from Emily.
+pub struct EmilyImporter<C> {
+    /// Signer context.
+    context: C,
+}
+
+impl<C> EmilyImporter<C>
+where
+    C: Context,
+{
+    /// Create a new importer.
+    pub fn new(context: C) -> Self {
+        Self { context }
+    }
+
+    /// Import all deposit and withdrawal requests known to Emily.
+    ///
+    /// Requests that cannot be validated, or whose bitcoin or stacks
+    /// blocks are not in our database yet, are skipped. This means that
+    /// the importer is most useful once the block observer has caught up
+    /// with the bitcoin and stacks blockchains, and that it is safe to
+    /// run it more than once.
+    #[tracing::instrument(skip_all, name = "emily-import")]
+    pub async fn run(&self) -> Result<ImportSummary, Error> {
+        let mut summary = ImportSummary::default();
+        let emily_client = self.context.get_emily_client();
+
+        for status in DEPOSIT_STATUSES {
+            let requests = emily_client.get_deposits_with_status(status).await?;
+            tracing::debug!(
+                ?status,
+                count = requests.len(),
+                "fetched deposits from Emily"
+            );
+
+            for request in requests {
+                let correlation_id = CorrelationId::from(request.outpoint);
+                match self.import_deposit(&request).await {
+                    Ok(true) => summary.deposits_imported += 1,
+                    Ok(false) => summary.deposits_skipped += 1,
+                    Err(error) => {
+                        tracing::warn!(%error, %correlation_id, "could not import deposit request");
+                        summary.deposits_skipped += 1;
+                    }
+                }
+            }
+        }
+
+        for status in WITHDRAWAL_STATUSES {
+            let withdrawals = emily_client.get_withdrawals_with_status(status).await?;
+            tracing::debug!(
+                ?status,
+                count = withdrawals.len(),
+                "fetched withdrawals from Emily"
+            );
+
+            for withdrawal in withdrawals {
+                match self.import_withdrawal(&withdrawal).await {
+                    Ok(true) => summary.withdrawals_imported += 1,
+                    Ok(false) => summary.withdrawals_skipped += 1,
+                    Err(error) => {
+                        tracing::warn!(
+                            %error,
+                            request_id = withdrawal.request_id,
+                            "could not import withdrawal request"
+                        );
+                        summary.withdrawals_skipped += 1;
+                    }
+                }
+            }
+        }
+
+        tracing::info!(?summary, "finished importing requests from Emily");
+        Ok(summary)
+    }
+
+    /// Validate the deposit request against bitcoin-core and write it to
+    /// the database. Returns whether the request was written.
+    ///
+    /// Unlike the block observer, we cannot rely on the deposit UTXO being
+    /// unspent, since historical deposits have usually been swept already.
+    async fn import_deposit(&self, request: &CreateDepositRequest) -> Result<bool, Error> {
+        let db = self.context.get_storage_mut();
+        let txid = model::BitcoinTxId::from(request.outpoint.txid);
+        let output_index = request.outpoint.vout;
+
+        if db.get_deposit_request(&txid, output_index).await?.is_some() {
+            return Ok(false);
+        }
+
+        let bitcoin_client = self.context.get_bitcoin_client();
+        let Some(response) = bitcoin_client.get_tx(&request.outpoint.txid).await? else {
+            return Ok(false);
+        };
+        // Unconfirmed deposits will be picked up by the block observer.
+        let Some(block_hash) = response.block_hash else {
+            return Ok(false);
+        };
+        if db.get_bitcoin_block(&block_hash.into()).await?.is_none() {
+            return Ok(false);
+        }
+
+        let tx_info = bitcoin_client
+            .get_tx_info(&request.outpoint.txid, &block_hash)
+            .await?;
+        let Some(tx_info) = tx_info else {
+            return Ok(false);
+        };
+        tx_info.validate()?;
+
+        let is_mainnet = self.context.config().signer.network.is_mainnet();
+        let policy = self.context.config().signer.sibling_output_policy();
+        let deposit = Deposit {
+            info: request.validate_tx_with_policy(&tx_info.tx, is_mainnet, policy)?,
+            tx_info,
+            block_hash,
+        };
+        let tx_ref = model::BitcoinTxRef {
+            txid,
+            block_hash: block_hash.into(),
+        };
+        let deposit_request = model::DepositRequest::from(deposit);
+
+        let storage_tx = db.begin_transaction().await?;
+        storage_tx.write_bitcoin_transactions(vec![tx_ref]).await?;
+        storage_tx.write_deposit_request(&deposit_request).await?;
+        storage_tx
+            .write_emily_imported_deposit(&txid, output_index)
+            .await?;
+        storage_tx.commit().await?;
+
+        Ok(true)
+    }
+
+    /// Verify the withdrawal request against the stacks node and write it
+    /// to the database. Returns whether the request was written.
+    ///
+    /// Emily only tells us where to look. The request itself is read from
+    /// the `withdrawal-requests` map of the sbtc-registry smart contract,
+    /// and we check that the stacks block that Emily claims created the
+    /// request contains the transaction that Emily claims created it.
+    async fn import_withdrawal(&self, info: &WithdrawalInfo) -> Result<bool, Error> {
+        let db = self.context.get_storage_mut();
+        let block_hash = model::StacksBlockHash::from_hex(&info.stacks_block_hash)?;
+        let txid = model::StacksTxId::from_hex(&info.txid)?;
+
+        // Withdrawal requests are tied to the stacks block that created
+        // them, so we only import requests whose block we know about.
+        if db.get_stacks_block(&block_hash).await?.is_none() {
+            return Ok(false);
+        }
+
+        let stacks_client = self.context.get_stacks_client();
+        let deployer = &self.context.config().signer.deployer;
+        let Some(registry_request) = stacks_client
+            .get_withdrawal_request(deployer, info.request_id)
+            .await?
+        else {
+            tracing::warn!(
+                request_id = info.request_id,
+                "withdrawal request from Emily is not in the sbtc-registry"
+            );
+            return Ok(false);
+        };
+
+        let block = stacks_client.get_block(&block_hash).await?;
+        if !block
+            .txs
+            .iter()
+            .any(|tx| model::StacksTxId::from(tx.txid()) == txid)
+        {
+            tracing::warn!(
+                request_id = info.request_id,
+                %txid,
+                %block_hash,
+                "withdrawal request transaction from Emily is not in its stacks block"
+            );
+            return Ok(false);
+        }
+
+        let request = model::WithdrawalRequest {
+            request_id: info.request_id,
+            txid,
+            block_hash,
+            recipient: registry_request.recipient.into(),
+            amount: registry_request.amount,
+            max_fee: registry_request.max_fee,
+            sender_address: registry_request.sender.into(),
+            bitcoin_block_height: registry_request.block_height.into(),
+        };
+
+        let storage_tx = db.begin_transaction().await?;
+        storage_tx.write_withdrawal_request(&request).await?;
+        storage_tx
+            .write_emily_imported_withdrawal(&request.qualified_id())
+            .await?;
+        storage_tx.commit().await?;
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use blockstack_lib::chainstate::nakamoto::NakamotoBlock;
+    use blockstack_lib::chainstate::nakamoto::NakamotoBlockHeader;
+    use fake::Fake as _;
+    use fake::Faker;
+
+    use crate::stacks::api::RegistryWithdrawalRequest;
+    use crate::storage::memory::SharedStore;
+    use crate::testing::context::*;
+    use crate::testing::dummy;
+    use crate::testing::get_rng;
+
+    use super::*;
+
+    /// Set up Emily to return the given withdrawal, and no deposits.
+    async fn setup_emily<S, B, St>(
+        ctx: &TestContext<S, B, St, WrappedMockEmilyInteract>,
+        request: &model::WithdrawalRequest,
+    ) {
+        let info = WithdrawalInfo {
+            request_id: request.request_id,
+            stacks_block_hash: request.block_hash.to_hex(),
+            txid: hex::encode(request.txid.to_bytes()),
+            ..Default::default()
+        };
+        ctx.with_emily_client(|client| {
+            client
+                .expect_get_deposits_with_status()
+                .returning(|_| Box::pin(async { Ok(Vec::new()) }));
+            client
+                .expect_get_withdrawals_with_status()
+                .returning(move |status| {
+                    let withdrawals = match status {
+                        WithdrawalStatus::Pending => vec![info.clone()],
+                        _ => Vec::new(),
+                    };
+                    Box::pin(async move { Ok(withdrawals) })
+                });
+        })
+        .await;
+    }
+
+    /// Set up the stacks node to return the given registry entry, and a
+    /// block with the given transactions.
+    async fn setup_stacks<S, B, E>(
+        ctx: &TestContext<S, B, WrappedMockStacksInteract, E>,
+        entry: Option<RegistryWithdrawalRequest>,
+        block: NakamotoBlock,
+    ) {
+        ctx.with_stacks_client(|client| {
+            client
+                .expect_get_withdrawal_request()
+                .returning(move |_, _| Box::pin(std::future::ready(Ok(entry.clone()))));
+            client
+                .expect_get_block()
+                .returning(move |_| Box::pin(std::future::ready(Ok(block.clone()))));
+        })
+        .await;
+    }
+
+    fn registry_entry(request: &model::WithdrawalRequest) -> RegistryWithdrawalRequest {
+        RegistryWithdrawalRequest {
+            amount: request.amount,
+            max_fee: request.max_fee,
+            sender: request.sender_address.clone().into(),
+            recipient: request.recipient.to_owned().into(),
+            block_height: *request.bitcoin_block_height,
+        }
+    }
+
+    /// Create a withdrawal request in a stacks block that is in the given
+    /// database, along with the stacks block that created it.
+    async fn known_withdrawal(
+        db: &SharedStore,
+        rng: &mut impl rand::RngCore,
+    ) -> (model::WithdrawalRequest, NakamotoBlock) {
+        let stacks_block: model::StacksBlock = Faker.fake_with_rng(rng);
+        db.write_stacks_block(&stacks_block).await.unwrap();
+
+        let tx = dummy::stacks_tx(&Faker, rng);
+        let request = model::WithdrawalRequest {
+            block_hash: stacks_block.block_hash,
+            txid: tx.txid().into(),
+            ..Faker.fake_with_rng(rng)
+        };
+        let block = NakamotoBlock {
+            header: NakamotoBlockHeader::empty(),
+            txs: vec![tx],
+        };
+        (request, block)
+    }
+
+    #[tokio::test]
+    async fn imports_withdrawals_from_the_registry() {
+        let rng = &mut get_rng();
+        let ctx = TestContext::default_mocked();
+        let db = ctx.inner_storage();
+
+        let (request, block) = known_withdrawal(&ctx.inner_storage(), rng).await;
+        setup_emily(&ctx, &request).await;
+        setup_stacks(&ctx, Some(registry_entry(&request)), block).await;
+
+        let summary = EmilyImporter::new(ctx.clone()).run().await.unwrap();
+        assert_eq!(summary.withdrawals_imported, 1);
+        assert_eq!(summary.withdrawals_skipped, 0);
+
+        let id = request.qualified_id();
+        assert!(db.is_emily_imported_withdrawal(&id).await.unwrap());
+
+        let store = db.lock().await;
+        let stored = store
+            .withdrawal_requests
+            .get(&(id.request_id, id.block_hash))
+            .unwrap();
+        assert_eq!(stored, &request);
+    }
+
+    #[tokio::test]
+    async fn imported_withdrawals_use_the_registry_data() {
+        let rng = &mut get_rng();
+        let ctx = TestContext::default_mocked();
+        let db = ctx.inner_storage();
+
+        // Emily only tells us where the request is, so whatever it says
+        // about the amount or recipient is ignored.
+        let (request, block) = known_withdrawal(&ctx.inner_storage(), rng).await;
+        let entry = RegistryWithdrawalRequest {
+            amount: request.amount.wrapping_add(1),
+            ..registry_entry(&request)
+        };
+        setup_emily(&ctx, &request).await;
+        setup_stacks(&ctx, Some(entry.clone()), block).await;
+
+        let summary = EmilyImporter::new(ctx.clone()).run().await.unwrap();
+        assert_eq!(summary.withdrawals_imported, 1);
+
+        let id = request.qualified_id();
+        let store = db.lock().await;
+        let stored = store
+            .withdrawal_requests
+            .get(&(id.request_id, id.block_hash))
+            .unwrap();
+        assert_eq!(stored.amount, entry.amount);
+    }
+
+    #[tokio::test]
+    async fn skips_withdrawals_missing_from_the_registry() {
+        let rng = &mut get_rng();
+        let ctx = TestContext::default_mocked();
+
+        let (request, block) = known_withdrawal(&ctx.inner_storage(), rng).await;
+        setup_emily(&ctx, &request).await;
+        setup_stacks(&ctx, None, block).await;
+
+        let summary = EmilyImporter::new(ctx.clone()).run().await.unwrap();
+        assert_eq!(summary.withdrawals_imported, 0);
+        assert_eq!(summary.withdrawals_skipped, 1);
+
+        let id = request.qualified_id();
+        let db = ctx.get_storage();
+        assert!(!db.is_emily_imported_withdrawal(&id).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn skips_withdrawals_whose_transaction_is_not_in_the_block() {
+        let rng = &mut get_rng();
+        let ctx = TestContext::default_mocked();
+
+        let (request, _) = known_withdrawal(&ctx.inner_storage(), rng).await;
+        let other_block = NakamotoBlock {
+            header: NakamotoBlockHeader::empty(),
+            txs: vec![dummy::stacks_tx(&Faker, rng)],
+        };
+        setup_emily(&ctx, &request).await;
+        setup_stacks(&ctx, Some(registry_entry(&request)), other_block).await;
+
+        let summary = EmilyImporter::new(ctx.clone()).run().await.unwrap();
+        assert_eq!(summary.withdrawals_imported, 0);
+        assert_eq!(summary.withdrawals_skipped, 1);
+
+        let id = request.qualified_id();
+        let db = ctx.get_storage();
+        assert!(!db.is_emily_imported_withdrawal(&id).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn skips_withdrawals_with_unknown_stacks_blocks() {
+        let rng = &mut get_rng();
+        let ctx = TestContext::default_mocked();
+
+        let request: model::WithdrawalRequest = Faker.fake_with_rng(rng);
+        setup_emily(&ctx, &request).await;
+
+        let summary = EmilyImporter::new(ctx.clone()).run().await.unwrap();
+        assert_eq!(summary.withdrawals_imported, 0);
+        assert_eq!(summary.withdrawals_skipped, 1);
+
+        let id = request.qualified_id();
+        let db = ctx.get_storage();
+        assert!(!db.is_emily_imported_withdrawal(&id).await.unwrap());
+    }
+}
@@ -8,6 +8,7 @@ use crate::blocklist_client::BlocklistClientError;
 use crate::codec;
 use crate::dkg;
 use crate::emily_client::EmilyClientError;
+use crate::invariants::InvariantViolation;
 use crate::keys::PublicKey;
 use crate::keys::PublicKeyXOnly;
 use crate::stacks::contracts::DepositValidationError;
@@ -15,6 +16,9 @@ use crate::stacks::contracts::RotateKeysValidationError;
 use crate::stacks::contracts::WithdrawalAcceptValidationError;
 use crate::stacks::contracts::WithdrawalRejectValidationError;
 use crate::storage::model::BitcoinBlockHash;
+use crate::storage::model::BitcoinBlockHeight;
+use crate::storage::model::BitcoinTxId;
+use crate::storage::model::QualifiedRequestId;
 use crate::storage::model::SigHash;
 use crate::storage::model::StacksBlockHash;
 use crate::storage::model::StacksTxId;
@@ -84,6 +88,11 @@ pub enum Error {
     #[error("DKG verification signing failed for aggregate key: {0}")]
     DkgVerificationFailed(PublicKeyXOnly),
 
+    /// Too few signers of the new signer set attested to the key rotation
+    /// for the aggregate key before the coordinator gave up waiting.
+    #[error("too few key rotation attestations for aggregate key: {0}; got {1}, need {2}")]
+    InsufficientKeyRotationAttestations(PublicKey, usize, usize),
+
     /// Cannot verify the aggregate key outside the verification window
     #[error("cannot verify the aggregate key outside the verification window: {0}")]
     DkgVerificationWindowElapsed(PublicKey),
@@ -128,6 +137,10 @@ pub enum Error {
     #[error("deposit request not found: {0}")]
     MissingDepositRequest(bitcoin::OutPoint),
 
+    /// Indicates that a withdrawal request with the specified ID could not be found.
+    #[error("withdrawal request not found: {0}")]
+    MissingWithdrawalRequest(QualifiedRequestId),
+
     /// Received an error in response to gettxout RPC call
     #[error("bitcoin-core gettxout error for outpoint {1} (search mempool? {2}): {0}")]
     BitcoinCoreGetTxOut(#[source] bitcoincore_rpc::Error, bitcoin::OutPoint, bool),
@@ -162,6 +175,16 @@ pub enum Error {
     #[error("bitcoin-core getblockheader RPC error for hash {1}: {0}")]
     BitcoinCoreGetBlockHeader(#[source] bitcoincore_rpc::Error, bitcoin::BlockHash),
 
+    /// Attempt to fetch the compact block filter of a bitcoin block
+    /// resulted in an unexpected error. This is not triggered if the block
+    /// or its filter is missing.
+    #[error("bitcoin-core getblockfilter RPC error for hash {1}: {0}")]
+    BitcoinCoreGetBlockFilter(#[source] bitcoincore_rpc::Error, bitcoin::BlockHash),
+
+    /// The compact block filter of a bitcoin block could not be read.
+    #[error("could not read the compact block filter for block {1}: {0}")]
+    BitcoinBlockFilter(#[source] bitcoin::bip158::Error, bitcoin::BlockHash),
+
     /// Attempt to fetch a bitcoin block hash for a given height resulted in
     /// an unexpected error.
     #[error("bitcoin-core getblockhash RPC error for height {1}: {0}")]
@@ -181,6 +204,14 @@ pub enum Error {
     #[error("Unknown block hash response from bitcoin-core getblockheader RPC call: {0}")]
     BitcoinCoreUnknownBlockHeader(bitcoin::BlockHash),
 
+    /// A block header returned by bitcoin-core failed independent
+    /// verification of the header chain.
+    #[error("bitcoin block header {1} failed verification: {0}")]
+    BitcoinHeaderVerification(
+        #[source] crate::bitcoin::headers::HeaderVerificationError,
+        bitcoin::BlockHash,
+    ),
+
     /// Received an error in response to getrawtransaction RPC call
     #[error("failed to retrieve the raw transaction for txid {1} from bitcoin-core. {0}")]
     BitcoinCoreGetTransaction(#[source] bitcoincore_rpc::Error, bitcoin::Txid),
@@ -320,12 +351,25 @@ pub enum Error {
     #[error("receive error: {0:?}")]
     ClarityValueSerialization(Box<clarity::vm::errors::InterpreterError>),
 
+    /// Could not deserialize a hex encoded clarity value.
+    #[error("could not deserialize the clarity value: {0}")]
+    ClarityValueDeserialization(clarity::vm::types::serialization::SerializationError),
+
+    /// Could not parse an sbtc-registry event.
+    #[error("could not parse the sbtc-registry event: {0}")]
+    RegistryEvent(#[source] sbtc::events::EventError),
+
     /// Thrown when doing [`i64::try_from`] or [`i32::try_from`] before
     /// inserting a value into the database. This only happens if the value
     /// is greater than MAX for the signed type.
     #[error("could not convert integer type to the signed version for storing in postgres {0}")]
     ConversionDatabaseInt(#[source] std::num::TryFromIntError),
 
+    /// The outcomes of the validation checks stored in the database could
+    /// not be parsed.
+    #[error("could not parse the validation check outcomes {0:?}")]
+    InvalidValidationChecks(String),
+
     /// Parsing the Hex Error
     #[error("could not decode the bitcoin block: {0}")]
     DecodeBitcoinBlock(#[source] bitcoin::consensus::encode::Error),
@@ -334,6 +378,14 @@ pub enum Error {
     #[error("could not decode the bitcoin transaction: {0}")]
     DecodeBitcoinTransaction(#[source] bitcoin::consensus::encode::Error),
 
+    /// The merkle block returned by bitcoin-core could not be decoded.
+    #[error("could not decode the bitcoin merkle block: {0}")]
+    DecodeBitcoinMerkleBlock(#[source] bitcoin::consensus::encode::Error),
+
+    /// A stored bitcoin block header could not be decoded.
+    #[error("could not decode the bitcoin block header: {0}")]
+    DecodeBitcoinBlockHeader(#[source] bitcoin::consensus::encode::Error),
+
     /// Parsing the Hex Error
     #[error("could not decode the Nakamoto block with ID: {1}; {0}")]
     DecodeNakamotoBlock(#[source] blockstack_lib::codec::Error, StacksBlockHash),
@@ -418,6 +470,17 @@ pub enum Error {
     #[error("Recieved presign request for already processed block {0}")]
     InvalidPresignRequest(BitcoinBlockHash),
 
+    /// This is thrown when we receive a presign request while the sBTC
+    /// smart contracts are paused by governance.
+    #[error("the sBTC contracts are paused; not processing presign requests")]
+    SbtcContractPaused,
+
+    /// This is thrown when we receive a presign request but do not have
+    /// enough of the bitcoin blockchain in the database to validate it,
+    /// usually because the database was bootstrapped recently.
+    #[error("only {0} of the {1} bitcoin blocks needed for validation are stored")]
+    IncompleteChainHistory(u16, u16),
+
     /// This is thrown when we attempt to create a wallet with:
     /// 1. No public keys.
     /// 2. No required signatures.
@@ -531,6 +594,23 @@ pub enum Error {
     #[error("response from stacks node did not conform to the expected schema: {0}")]
     UnexpectedStacksResponse(#[source] reqwest::Error),
 
+    /// Could not make a request to the signer API.
+    #[error("failed to make a request to the signer API: {0}")]
+    SignerApiRequest(#[source] reqwest::Error),
+
+    /// The signer API responded with a non success status code.
+    #[error("received a non success status code response from the signer API: {0}")]
+    SignerApiResponse(#[source] reqwest::Error),
+
+    /// The response from the signer API did not match the expected schema.
+    #[error("response from the signer API did not conform to the expected schema: {0}")]
+    UnexpectedSignerApiResponse(#[source] reqwest::Error),
+
+    /// The signer API responded with a proof that does not prove that the
+    /// sweep transaction was confirmed.
+    #[error("the signer API responded with an invalid proof for sweep transaction {0}")]
+    InvalidSweepTxProof(bitcoin::Txid),
+
     /// The response from the Stacks node was invalid or malformed.
     #[error("invalid stacks response: {0}")]
     InvalidStacksResponse(&'static str),
@@ -579,6 +659,17 @@ pub enum Error {
     #[error("missing signer utxo")]
     MissingSignerUtxo,
 
+    /// A parallel sweep package services requests that are already
+    /// serviced by transactions in the mempool that spend the signers'
+    /// spare UTXOs.
+    #[error("the sweep package services requests of sweep transactions in the mempool")]
+    SweepRequestsInFlight,
+
+    /// The coordinator asked us to sign a sweep package with magic bytes
+    /// that are not accepted for its bitcoin chain tip.
+    #[error("unexpected magic bytes {0:?} for a sweep package at bitcoin block height {1}")]
+    UnexpectedMagicBytes([u8; 2], BitcoinBlockHeight),
+
     /// The public key indicated in the message does not match the sender
     /// public key.
     #[error("public key from sender does not match one in state machine {wsts} {sender}")]
@@ -634,6 +725,11 @@ pub enum Error {
     #[error("type conversion error")]
     TypeConversion,
 
+    /// The trace context in a signer message is not a W3C traceparent in
+    /// the form that signers send.
+    #[error("invalid trace context in signer message: {0}")]
+    InvalidTraceParent(String),
+
     /// An error thrown by `wsts::util::encrypt`, which encryptes the WSTS
     /// signer state machine's state before storing it in the database.
     #[error("could not encrypt the signer state for storage {0}; aggregate key {1}")]
@@ -643,6 +739,65 @@ pub enum Error {
     #[error("could not decrypt the signer state from storage {0}; aggregate key {1}")]
     WstsDecrypt(#[source] wsts::errors::EncryptionError, PublicKeyXOnly),
 
+    /// An error thrown by `wsts::util::encrypt` when encrypting a signer
+    /// state machine that was evicted from the in-memory cache before
+    /// spilling it to the database.
+    #[error("could not encrypt the evicted state machine {1}: {0}")]
+    StateMachineSpillEncrypt(#[source] wsts::errors::EncryptionError, StateMachineId),
+
+    /// Got an error when decrypting a spilled signer state machine read
+    /// from the database.
+    #[error("could not decrypt the spilled state machine {1}: {0}")]
+    StateMachineSpillDecrypt(#[source] wsts::errors::EncryptionError, StateMachineId),
+
+    /// The evicted state machine holds a private nonce, which is never
+    /// written to the database, so it cannot be spilled.
+    #[error("the evicted state machine {0} holds a private nonce and cannot be spilled")]
+    StateMachineSpillHoldsNonce(StateMachineId),
+
+    /// The signer state machine received a signature share request
+    /// without holding a private nonce for it. This happens after the
+    /// state machine was restored from the database, and signing without
+    /// the nonce that was sent to the coordinator would fail the round.
+    #[error("received a signature share request without holding a private nonce")]
+    MissingPrivateNonce,
+
+    /// An error thrown by `wsts::util::encrypt` when encrypting a
+    /// sensitive column value before writing it to the database.
+    #[error("could not encrypt a database column value: {0}")]
+    ColumnEncrypt(#[source] wsts::errors::EncryptionError),
+
+    /// An error thrown by `wsts::util::decrypt` when decrypting a
+    /// sensitive column value read from the database. This happens if
+    /// the configured column encryption key is not the one that was used
+    /// to encrypt the value.
+    #[error("could not decrypt a database column value: {0}")]
+    ColumnDecrypt(#[source] wsts::errors::EncryptionError),
+
+    /// An encrypted column value read from the database was not valid
+    /// hex.
+    #[error("the encrypted database column value is not valid hex: {0}")]
+    InvalidEncryptedColumn(#[source] hex::FromHexError),
+
+    /// A decrypted database column value was not valid UTF-8.
+    #[error("the decrypted database column value is not valid UTF-8: {0}")]
+    InvalidUtf8Column(#[source] std::string::FromUtf8Error),
+
+    /// The column encryption key was not 32 bytes of hex.
+    #[error("the database column encryption key must be 64 hex characters")]
+    InvalidColumnEncryptionKey,
+
+    /// An encrypted column value was read from the database, or the
+    /// columns were asked to be encrypted, but no column encryption key
+    /// is configured.
+    #[error("the database has encrypted column values but no column encryption key is configured")]
+    MissingColumnEncryptionKey,
+
+    /// The deadline of a message passed before the work that it asked
+    /// for was done, so the sender is no longer waiting for the result.
+    #[error("the deadline of the message passed before it was handled")]
+    DeadlineExceeded,
+
     /// Invalid configuration
     #[error("invalid configuration")]
     InvalidConfiguration,
@@ -720,6 +875,11 @@ pub enum Error {
         bitcoin::OutPoint,
     ),
 
+    /// The `signer.decision_policies` setting names a decision policy
+    /// that is neither built in nor registered with the request decider.
+    #[error("unknown decision policy: {0}")]
+    UnknownDecisionPolicy(String),
+
     /// Bitcoin error when attempting to construct an address from a
     /// scriptPubKey.
     #[error("bitcoin address parse error: {0}; Request id: {1}, BlockHash: {2}")]
@@ -783,6 +943,103 @@ pub enum Error {
     #[error("the fee rate in the BitcoinPreSignRequest object is out of bounds: {0}")]
     PreSignInvalidFeeRate(f64),
 
+    /// Indicates that a request package contains more transactions than
+    /// fit in a single bitcoin mempool package.
+    #[error(
+        "the request package contains {0} transactions, which exceeds the mempool package limit"
+    )]
+    PackageTooLarge(usize),
+
+    /// Indicates that a transaction in a request package sweeps more
+    /// deposits than a single transaction may contain.
+    #[error(
+        "a transaction in the request package sweeps {deposits} deposits, more than the maximum of {max_deposits}"
+    )]
+    PackageTooManyDeposits {
+        /// The number of deposits swept by the transaction.
+        deposits: usize,
+        /// The maximum number of deposits allowed in a transaction.
+        max_deposits: u16,
+    },
+
+    /// Indicates that a transaction in a request package services more
+    /// withdrawals than a single transaction may contain.
+    #[error(
+        "a transaction in the request package services {withdrawals} withdrawals, more than the maximum of {max_withdrawals}"
+    )]
+    PackageTooManyWithdrawals {
+        /// The number of withdrawals serviced by the transaction.
+        withdrawals: usize,
+        /// The maximum number of withdrawals allowed in a transaction.
+        max_withdrawals: u16,
+    },
+
+    /// Indicates that the request packages for a bitcoin chain tip service
+    /// withdrawals worth more than may be serviced in a single bitcoin
+    /// block.
+    #[error(
+        "the request packages for the chain tip service {total_amount} sats of withdrawals, more than the maximum of {max_amount} sats per bitcoin block"
+    )]
+    PackageWithdrawalValueTooLarge {
+        /// The total amount of the withdrawals in the packages, in sats.
+        total_amount: u64,
+        /// The maximum total amount of withdrawals per bitcoin block, in
+        /// sats.
+        max_amount: u64,
+    },
+
+    /// Indicates that an input of a sweep transaction has a taproot annex.
+    #[error("input {0} of the sweep transaction has a taproot annex")]
+    SweepTaprootAnnex(usize),
+
+    /// Indicates that an input of a sweep transaction has witness data
+    /// other than the witness data that the signers create when signing
+    /// it.
+    #[error("input {0} of the sweep transaction has unexpected witness data")]
+    UnexpectedSweepWitness(usize),
+
+    /// Indicates that a transaction reconstructed when resuming an
+    /// interrupted sweep package does not match the transaction that was
+    /// broadcast for it before the interruption.
+    #[error(
+        "reconstructed sweep transaction {actual} does not match the broadcast transaction {expected}"
+    )]
+    SweepPackageTxidMismatch {
+        /// The ID of the transaction that was broadcast.
+        expected: BitcoinTxId,
+        /// The ID of the reconstructed transaction.
+        actual: BitcoinTxId,
+    },
+
+    /// Indicates that we rejected a signer's announcement that it is
+    /// taking over the tenure of a bitcoin chain tip as the deputy
+    /// coordinator.
+    #[error("rejected the takeover of the tenure of {chain_tip} by {sender}: {reason}")]
+    RejectedCoordinatorTakeover {
+        /// The signer that announced the takeover.
+        sender: PublicKey,
+        /// The bitcoin chain tip of the tenure.
+        chain_tip: BitcoinBlockHash,
+        /// Why the takeover was rejected.
+        reason: &'static str,
+    },
+
+    /// Indicates that we rejected a signer's attestation that the
+    /// coordinator of a bitcoin chain tip went silent.
+    #[error("rejected the silence attestation of {sender} for the tenure of {chain_tip}: {reason}")]
+    RejectedSilenceAttestation {
+        /// The signer that sent the attestation.
+        sender: PublicKey,
+        /// The bitcoin chain tip of the tenure.
+        chain_tip: BitcoinBlockHash,
+        /// Why the attestation was rejected.
+        reason: &'static str,
+    },
+
+    /// One of the consensus-critical invariants of the signer was violated.
+    #[error("invariant violated: {0}")]
+    InvariantViolation(#[source] InvariantViolation),
+
     /// Error when deposit requests would exceed sBTC supply cap
     #[error(
         "total deposit amount ({total_amount} sats) would exceed sBTC supply cap (current max mintable is {max_mintable} sats)"
@@ -802,12 +1059,82 @@ pub enum Error {
     #[error("sbtc transaction op return format error")]
     SbtcTxOpReturnFormatError,
 
+    /// The OP_RETURN output of an sBTC transaction uses a version of the
+    /// commitment format that this signer does not know how to parse.
+    #[error("unsupported sbtc transaction op return version: {0}")]
+    OpReturnUnsupportedVersion(u8),
+
     /// Error when withdrawal requests would exceed sBTC's rolling withdrawal caps
     #[error("total withdrawal amounts ({amounts}) exceeds rolling caps ({cap} over
             {cap_blocks}) with the currently withdrawn total {withdrawn_total})",
             amounts = .0.amounts, cap = .0.cap, cap_blocks = .0.cap_blocks, withdrawn_total = .0.withdrawn_total)]
     ExceedsWithdrawalCap(WithdrawalCapContext),
 
+    /// The directory given for a new snapshot of the signer's database
+    /// already holds a snapshot.
+    #[error("a snapshot already exists in {}", .0.display())]
+    SnapshotAlreadyExists(std::path::PathBuf),
+
+    /// A snapshot can only be restored into a database that does not have
+    /// any of the signer's tables.
+    #[error("the database to restore the snapshot into is not empty")]
+    SnapshotRestoreTargetNotEmpty,
+
+    /// An external program used for creating or restoring a snapshot of
+    /// the signer's database could not be started.
+    #[error("could not run {0}: {1}")]
+    SnapshotCommandSpawn(&'static str, #[source] std::io::Error),
+
+    /// An external program used for creating or restoring a snapshot of
+    /// the signer's database exited unsuccessfully.
+    #[error("{program} exited with {status}: {stderr}")]
+    SnapshotCommandFailed {
+        /// The program that was run.
+        program: &'static str,
+        /// The exit status of the program.
+        status: std::process::ExitStatus,
+        /// What the program wrote to stderr.
+        stderr: String,
+    },
+
+    /// The manifest of a snapshot could not be parsed.
+    #[error("could not parse the snapshot manifest: {0}")]
+    SnapshotManifestDecode(#[source] serde_json::Error),
+
+    /// The restored database does not match the manifest of the snapshot
+    /// that it was restored from.
+    #[error("the restored database does not match the snapshot manifest: {0}")]
+    SnapshotVerification(String),
+
+    /// A peer advertised a signer protocol version below the configured
+    /// minimum, so its messages are rejected until it upgrades.
+    #[error(
+        "peer {peer_id} is running signer protocol version {version}, which is below the minimum supported version {minimum}; the peer must be upgraded"
+    )]
+    PeerProtocolVersionTooLow {
+        /// The peer ID of the outdated peer.
+        peer_id: Box<libp2p::PeerId>,
+        /// The protocol version advertised by the peer.
+        version: u32,
+        /// The minimum protocol version that we accept.
+        minimum: u32,
+    },
+
+    /// Some signer in the signer set has not advertised the protocol
+    /// version that the requested operation needs.
+    #[error("not every signer supports signer protocol version {0}")]
+    UnsupportedProtocolVersion(u32),
+
+    /// The chaos layer injected an error into the given operation.
+    #[cfg(feature = "chaos")]
+    #[error("chaos: injected error in {0}")]
+    ChaosInjectedError(&'static str),
+
+    /// The chaos layer made the given operation time out.
+    #[cfg(feature = "chaos")]
+    #[error("chaos: injected timeout in {0} after {1:?}")]
+    ChaosInjectedTimeout(&'static str, std::time::Duration),
+
     /// An error was raised by the in-memory database.
     #[cfg(any(test, feature = "testing"))]
     #[error("In-memory database error: {0}")]
@@ -849,4 +1176,39 @@ impl Error {
     pub fn wsts_coordinator(err: wsts::state_machine::coordinator::Error) -> Self {
         Error::WstsCoordinator(Box::new(err))
     }
+
+    /// Whether this is a database error that may succeed if the whole
+    /// storage transaction is tried again, such as a serialization
+    /// failure, a deadlock, a dropped connection or a failover of the
+    /// postgres primary.
+    pub fn is_transient_storage_error(&self) -> bool {
+        let sqlx_error = match self {
+            Error::SqlxQuery(error)
+            | Error::SqlxBeginTransaction(error)
+            | Error::SqlxCommitTransaction(error)
+            | Error::SqlxAcquireConnection(error) => error,
+            _ => return false,
+        };
+
+        match sqlx_error {
+            sqlx::Error::Io(_) | sqlx::Error::PoolTimedOut => true,
+            sqlx::Error::Database(error) => error.code().is_some_and(|code| {
+                // These are the `serialization_failure` and
+                // `deadlock_detected` error codes in postgres.
+                matches!(code.as_ref(), "40001" | "40P01")
+                    // These are the `admin_shutdown`, `crash_shutdown`
+                    // and `cannot_connect_now` error codes, which we get
+                    // while the primary is going away or a new one is
+                    // starting up.
+                    || matches!(code.as_ref(), "57P01" | "57P02" | "57P03")
+                    // The `read_only_sql_transaction` error code, which
+                    // we get when writing to a primary that has since
+                    // been demoted.
+                    || code == "25006"
+                    // The `connection_exception` class of error codes.
+                    || code.starts_with("08")
+            }),
+            _ => false,
+        }
+    }
 }
@@ -0,0 +1,337 @@
+//! This module contains the consensus-critical invariants of the signer.
+//!
+//! These are properties that must hold whenever the coordinator constructs
+//! a sweep package or a signer validates one, or whenever either of them
+//! acts on the signer set in the sbtc-registry contract:
+//!
+//! 1. The transactions in a sweep package form unbroken chains of
+//!    signers' UTXOs, starting with the signers' current UTXO and any
+//!    spare signers' UTXOs.
+//! 2. A sweep transaction never pays more in fees than the maximum fee
+//!    rate that the signers accept allows.
+//! 3. The number of signatures required by the signer set is positive
+//!    and no greater than the number of signers in the set.
+//!
+//! A violation of any of these means that there is a bug somewhere, so
+//! each check is enforced according to an [`InvariantMode`]. In debug
+//! builds a violation panics, so that it is caught as early as possible,
+//! while in production builds the violation is logged, counted in the
+//! [`Metrics::InvariantViolationsTotal`] metric, and returned as an error
+//! so that the signer abandons whatever it was doing.
+
+use bitcoin::OutPoint;
+use bitcoin::Transaction;
+use bitcoin::Txid;
+
+use crate::bitcoin::utxo::UnsignedTransaction;
+use crate::error::Error;
+use crate::metrics::Metrics;
+
+/// How a violated invariant is handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvariantMode {
+    /// Panic on a violation.
+    Panic,
+    /// Log the violation, record it in the metrics and return it as an
+    /// error.
+    Report,
+}
+
+impl InvariantMode {
+    /// The mode for the current build, which is [`InvariantMode::Panic`]
+    /// when debug assertions are enabled and [`InvariantMode::Report`]
+    /// otherwise.
+    pub const fn current() -> Self {
+        if cfg!(debug_assertions) {
+            Self::Panic
+        } else {
+            Self::Report
+        }
+    }
+
+    /// Enforce the outcome of an invariant check in this mode.
+    pub fn enforce(self, result: Result<(), InvariantViolation>) -> Result<(), Error> {
+        let Err(violation) = result else {
+            return Ok(());
+        };
+
+        match self {
+            Self::Panic => panic!("invariant violated: {violation}"),
+            Self::Report => {
+                tracing::error!(%violation, "invariant violated");
+                Metrics::increment_invariant_violations(violation.into());
+                Err(Error::InvariantViolation(violation))
+            }
+        }
+    }
+}
+
+impl Default for InvariantMode {
+    fn default() -> Self {
+        Self::current()
+    }
+}
+
+/// A violation of one of the consensus-critical invariants.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error, strum::IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum InvariantViolation {
+    /// A transaction in a sweep package does not spend the signers' UTXO
+    /// created by the transaction before it, or the signers' current UTXO
+    /// if it is the first transaction in the package.
+    #[error(
+        "transaction {index} of the sweep package spends {actual:?} instead of the signers' UTXO {expected}"
+    )]
+    UtxoChainContinuity {
+        /// The position of the transaction in the package.
+        index: usize,
+        /// The signers' UTXO that the transaction should spend.
+        expected: OutPoint,
+        /// The outpoint that the first input of the transaction spends.
+        actual: Option<OutPoint>,
+    },
+    /// A sweep transaction pays more in fees than the maximum fee rate
+    /// allows.
+    #[error("sweep transaction {txid} pays {fee} sats in fees, more than the ceiling of {ceiling}")]
+    FeeCeiling {
+        /// The ID of the transaction.
+        txid: Txid,
+        /// The fee that the transaction pays, in sats.
+        fee: u64,
+        /// The most that the transaction may pay in fees, in sats.
+        ceiling: u64,
+    },
+    /// The number of signatures required by a signer set is zero or
+    /// greater than the number of signers in the set.
+    #[error("{signatures_required} signatures required from a signer set of {num_signers}")]
+    SignatureThreshold {
+        /// The number of signatures required.
+        signatures_required: u16,
+        /// The number of signers in the set.
+        num_signers: usize,
+    },
+}
+
+/// Enforce the outcome of an invariant check in the mode for the current
+/// build.
+pub fn enforce(result: Result<(), InvariantViolation>) -> Result<(), Error> {
+    InvariantMode::current().enforce(result)
+}
+
+/// Check that the given transactions form unbroken chains of signers'
+/// UTXOs, where the first transaction spends the given signers' UTXO, the
+/// transactions after it spend the given spare signers' UTXOs in order,
+/// and once those run out every other transaction spends the first output
+/// of the one before it.
+pub fn check_utxo_chain<'a, I>(
+    signer_utxo: OutPoint,
+    spare_utxos: &[OutPoint],
+    transactions: I,
+) -> Result<(), InvariantViolation>
+where
+    I: IntoIterator<Item = &'a Transaction>,
+{
+    let mut spare_utxos = spare_utxos.iter();
+    let mut expected = signer_utxo;
+    for (index, tx) in transactions.into_iter().enumerate() {
+        let actual = tx.input.first().map(|tx_in| tx_in.previous_output);
+        if actual != Some(expected) {
+            return Err(InvariantViolation::UtxoChainContinuity { index, expected, actual });
+        }
+        let chained = OutPoint::new(tx.compute_txid(), 0);
+        expected = spare_utxos.next().copied().unwrap_or(chained);
+    }
+
+    Ok(())
+}
+
+/// Check that the given sweep transaction does not pay more in fees than
+/// [`UnsignedTransaction::max_tx_fee`].
+pub fn check_fee_ceiling(tx: &UnsignedTransaction) -> Result<(), InvariantViolation> {
+    let fee = tx.input_amounts().saturating_sub(tx.output_amounts());
+    let ceiling = tx.max_tx_fee();
+    if fee > ceiling {
+        let txid = tx.tx.compute_txid();
+        return Err(InvariantViolation::FeeCeiling { txid, fee, ceiling });
+    }
+
+    Ok(())
+}
+
+/// Check the invariants of a sweep package, whose first transaction
+/// spends the given signers' UTXO and whose later transactions spend the
+/// given spare signers' UTXOs.
+pub fn check_sweep_package(
+    signer_utxo: OutPoint,
+    spare_utxos: &[OutPoint],
+    transactions: &[UnsignedTransaction],
+) -> Result<(), InvariantViolation> {
+    check_utxo_chain(
+        signer_utxo,
+        spare_utxos,
+        transactions.iter().map(|tx| &tx.tx),
+    )?;
+    transactions.iter().try_for_each(check_fee_ceiling)
+}
+
+/// Check that the number of signatures required by a signer set is
+/// positive and no greater than the number of signers in the set.
+pub fn check_signature_threshold(
+    signatures_required: u16,
+    num_signers: usize,
+) -> Result<(), InvariantViolation> {
+    if signatures_required == 0 || usize::from(signatures_required) > num_signers {
+        return Err(InvariantViolation::SignatureThreshold {
+            signatures_required,
+            num_signers,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::Amount;
+    use bitcoin::ScriptBuf;
+    use bitcoin::TxIn;
+    use bitcoin::TxOut;
+    use bitcoin::XOnlyPublicKey;
+    use bitcoin::hashes::Hash as _;
+    use bitvec::array::BitArray;
+    use fake::Fake as _;
+    use fake::Faker;
+    use test_case::test_case;
+
+    use crate::bitcoin::utxo::RequestRef;
+    use crate::bitcoin::utxo::Requests;
+    use crate::bitcoin::utxo::SignerBtcState;
+    use crate::bitcoin::utxo::SignerUtxo;
+    use crate::bitcoin::utxo::WithdrawalRequest;
+    use crate::keys::PublicKey;
+    use crate::testing::get_rng;
+
+    use super::*;
+
+    fn tx_spending(outpoint: OutPoint) -> Transaction {
+        Transaction {
+            version: bitcoin::transaction::Version::TWO,
+            lock_time: bitcoin::absolute::LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: outpoint,
+                ..Default::default()
+            }],
+            output: vec![TxOut {
+                value: Amount::from_sat(1_000),
+                script_pubkey: ScriptBuf::new(),
+            }],
+        }
+    }
+
+    #[test]
+    fn utxo_chain_continuity() {
+        let signer_utxo = OutPoint::new(Txid::all_zeros(), 0);
+        let tx1 = tx_spending(signer_utxo);
+        let tx2 = tx_spending(OutPoint::new(tx1.compute_txid(), 0));
+        assert!(check_utxo_chain(signer_utxo, &[], [&tx1, &tx2]).is_ok());
+        assert!(check_utxo_chain(signer_utxo, &[], []).is_ok());
+
+        // The second transaction spends the wrong output of the first.
+        let tx3 = tx_spending(OutPoint::new(tx1.compute_txid(), 1));
+        let violation = check_utxo_chain(signer_utxo, &[], [&tx1, &tx3]).unwrap_err();
+        assert!(matches!(
+            violation,
+            InvariantViolation::UtxoChainContinuity { index: 1, .. }
+        ));
+
+        // The first transaction does not spend the signers' UTXO.
+        let other_utxo = OutPoint::new(Txid::all_zeros(), 1);
+        let violation = check_utxo_chain(other_utxo, &[], [&tx1]).unwrap_err();
+        assert!(matches!(
+            violation,
+            InvariantViolation::UtxoChainContinuity { index: 0, .. }
+        ));
+    }
+
+    #[test]
+    fn utxo_chains_spend_the_spare_utxos_first() {
+        let signer_utxo = OutPoint::new(Txid::all_zeros(), 0);
+        let spare_utxo = OutPoint::new(Txid::all_zeros(), 1);
+        let tx1 = tx_spending(signer_utxo);
+        let tx2 = tx_spending(spare_utxo);
+        let tx3 = tx_spending(OutPoint::new(tx2.compute_txid(), 0));
+        assert!(check_utxo_chain(signer_utxo, &[spare_utxo], [&tx1, &tx2, &tx3]).is_ok());
+
+        // The second transaction needs to spend the spare UTXO rather
+        // than the output of the first.
+        let chained = tx_spending(OutPoint::new(tx1.compute_txid(), 0));
+        let violation = check_utxo_chain(signer_utxo, &[spare_utxo], [&tx1, &chained]).unwrap_err();
+        assert!(matches!(
+            violation,
+            InvariantViolation::UtxoChainContinuity { index: 1, .. }
+        ));
+    }
+
+    #[test]
+    fn sweep_fees_above_the_ceiling_are_a_violation() {
+        let mut rng = get_rng();
+        let public_key: PublicKey = Faker.fake_with_rng(&mut rng);
+        let public_key = XOnlyPublicKey::from(public_key);
+        let withdrawal = WithdrawalRequest {
+            request_id: 1,
+            txid: Faker.fake_with_rng(&mut rng),
+            block_hash: Faker.fake_with_rng(&mut rng),
+            amount: 100_000,
+            max_fee: 10_000,
+            script_pubkey: ScriptBuf::new_p2tr(bitcoin::secp256k1::SECP256K1, public_key, None)
+                .into(),
+            signer_bitmap: BitArray::ZERO,
+        };
+        let state = SignerBtcState {
+            utxo: SignerUtxo {
+                outpoint: OutPoint::new(Txid::all_zeros(), 0),
+                amount: 1_000_000,
+                public_key,
+            },
+            fee_rate: 10.0,
+            public_key,
+            last_fees: None,
+            magic_bytes: [0; 2],
+            change_outputs: 1,
+        };
+        let requests = Requests::new(vec![RequestRef::Withdrawal(&withdrawal)]);
+        let mut tx = UnsignedTransaction::new(requests, &state).unwrap();
+        assert!(check_sweep_package(state.utxo.outpoint, &[], std::slice::from_ref(&tx)).is_ok());
+
+        // Shrinking the signers' output means that the difference goes to
+        // the miners.
+        tx.tx.output[0].value = Amount::from_sat(1_000);
+        let violation = check_fee_ceiling(&tx).unwrap_err();
+        assert!(matches!(violation, InvariantViolation::FeeCeiling { .. }));
+    }
+
+    #[test_case(1, 1, true; "one of one")]
+    #[test_case(2, 3, true; "two of three")]
+    #[test_case(3, 3, true; "three of three")]
+    #[test_case(0, 3, false; "zero of three")]
+    #[test_case(4, 3, false; "four of three")]
+    #[test_case(1, 0, false; "one of zero")]
+    fn signature_threshold(signatures_required: u16, num_signers: usize, valid: bool) {
+        let result = check_signature_threshold(signatures_required, num_signers);
+        assert_eq!(result.is_ok(), valid);
+    }
+
+    #[test]
+    fn report_mode_returns_the_violation() {
+        let violation = check_signature_threshold(0, 1).unwrap_err();
+        let error = InvariantMode::Report.enforce(Err(violation.clone()));
+        assert!(matches!(error, Err(Error::InvariantViolation(v)) if v == violation));
+        assert!(InvariantMode::Report.enforce(Ok(())).is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "invariant violated")]
+    fn panic_mode_panics_on_a_violation() {
+        let _ = InvariantMode::Panic.enforce(check_signature_threshold(0, 1));
+    }
+}
@@ -0,0 +1,338 @@
+//! Detection and recovery of partially-completed key rotations.
+//!
+//! Rotating the signers' aggregate key takes two steps that land on
+//! different blockchains:
+//!
+//! 1. A rotate-keys contract call registers the new aggregate key in the
+//!    sbtc-registry contract on Stacks.
+//! 2. A sweep transaction moves the signers' UTXO on bitcoin to the new
+//!    aggregate key.
+//!
+//! Either step can fail while the other succeeds, say because the sweep
+//! transaction is evicted from the mempool or because the rotate-keys
+//! contract call is dropped or reorged away after the signers' UTXO was
+//! swept to the new key. The key rotation monitor compares the aggregate
+//! key in the registry, and the rotate-keys contract calls on the
+//! canonical stacks blockchain, with the key that locks the signers' UTXO
+//! when the signer starts and periodically afterward. It records the
+//! outcome in the signer state as a [`KeyRotationState`], which the
+//! coordinator uses to drive the step that remains:
+//!
+//! * If the signers' UTXO is locked by an aggregate key that has not been
+//!   registered, the coordinator submits the rotate-keys contract call
+//!   for it and holds back new sweeps until the call is confirmed. The
+//!   signers sweep to the aggregate key in the registry, so sweeping in
+//!   the meantime would move the UTXO back to the previous key.
+//! * If the registry holds an aggregate key that does not lock the
+//!   signers' UTXO yet, the coordinator sweeps the signers' UTXO to the
+//!   registered key in its next sweep. The signers never sweep without
+//!   servicing a request, so the UTXO moves with the next sweep
+//!   transaction that services a deposit or withdrawal.
+//!
+//! The state is exposed on the `/info` endpoint and as a metric.
+
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::block_observer::get_signer_set_info;
+use crate::context::Context;
+use crate::error::Error;
+use crate::keys::PublicKey;
+use crate::metrics::Metrics;
+use crate::storage::DbRead as _;
+use crate::storage::model;
+
+/// How often the key rotation monitor checks whether a key rotation is
+/// only partially complete.
+const KEY_ROTATION_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How far along the signers are with their most recent key rotation.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, strum::IntoStaticStr,
+)]
+#[serde(tag = "state", rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum KeyRotationState {
+    /// The aggregate key in the registry contract locks the signers' UTXO,
+    /// or the signers do not have a UTXO yet.
+    #[default]
+    Complete,
+    /// The rotate-keys contract call for the aggregate key has been
+    /// confirmed, but the signers' UTXO is still locked by an earlier
+    /// aggregate key.
+    SweepPending {
+        /// The aggregate key in the registry contract.
+        registered_key: PublicKey,
+        /// The aggregate key that locks the signers' UTXO.
+        utxo_key: PublicKey,
+    },
+    /// The signers' UTXO is locked by an aggregate key that is not in the
+    /// registry contract and has not been registered by a rotate-keys
+    /// contract call on the canonical stacks blockchain.
+    ContractCallPending {
+        /// The aggregate key in the registry contract, if there is one.
+        registered_key: Option<PublicKey>,
+        /// The aggregate key that locks the signers' UTXO.
+        utxo_key: PublicKey,
+    },
+}
+
+impl KeyRotationState {
+    /// Return the state of the key rotation given the aggregate key in the
+    /// registry contract and the history of the aggregate keys that have
+    /// locked the signers' UTXO, oldest first.
+    pub fn new(registered_key: Option<PublicKey>, history: &[model::AggregateKeyPeriod]) -> Self {
+        let Some(period) = history.last().filter(|period| period.end_height.is_none()) else {
+            return Self::Complete;
+        };
+        let utxo_key = period.aggregate_key;
+
+        match registered_key {
+            Some(registered_key) if registered_key == utxo_key => Self::Complete,
+            _ if period.rotate_keys_txid.is_none() => {
+                Self::ContractCallPending { registered_key, utxo_key }
+            }
+            Some(registered_key) => Self::SweepPending { registered_key, utxo_key },
+            // The key that locks the signers' UTXO has been registered,
+            // and we do not know of a later one.
+            None => Self::Complete,
+        }
+    }
+
+    /// Return whether one of the two steps of the key rotation is still
+    /// outstanding.
+    pub fn is_incomplete(&self) -> bool {
+        !matches!(self, Self::Complete)
+    }
+
+    /// Return the aggregate key that locks the signers' UTXO but whose
+    /// rotate-keys contract call has not been confirmed, if any.
+    pub fn unregistered_utxo_key(&self) -> Option<PublicKey> {
+        match self {
+            Self::ContractCallPending { utxo_key, .. } => Some(*utxo_key),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for KeyRotationState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state: &'static str = self.into();
+        f.write_str(state)
+    }
+}
+
+/// Work out how far along the signers are with their most recent key
+/// rotation on the canonical bitcoin blockchain identified by the given
+/// chain tip.
+///
+/// The aggregate key in the registry is taken from the signer state,
+/// and from the stacks node if the block observer has not loaded it yet,
+/// such as when the signer has just started.
+pub async fn detect_key_rotation_state(
+    ctx: &impl Context,
+    chain_tip: &model::BitcoinBlockHash,
+) -> Result<KeyRotationState, Error> {
+    let registry_info = match ctx.state().registry_signer_set_info() {
+        Some(info) => Some(info),
+        None => get_signer_set_info(ctx).await?,
+    };
+    let registered_key = registry_info.map(|info| info.aggregate_key);
+
+    let history = ctx
+        .get_storage()
+        .get_aggregate_key_history(chain_tip)
+        .await?;
+    Ok(KeyRotationState::new(registered_key, &history))
+}
+
+/// A background task that detects partially-completed key rotations.
+pub struct KeyRotationMonitor<C> {
+    /// Signer context.
+    context: C,
+}
+
+impl<C> KeyRotationMonitor<C>
+where
+    C: Context,
+{
+    /// Creates a new KeyRotationMonitor.
+    pub fn new(context: C) -> Self {
+        Self { context }
+    }
+
+    /// Runs the KeyRotationMonitor, which checks the state of the key
+    /// rotation right away and then every [`KEY_ROTATION_CHECK_INTERVAL`]
+    /// until the signer is shut down.
+    pub async fn run(self) {
+        let mut term = self.context.get_termination_handle();
+        let mut delay = Duration::ZERO;
+        loop {
+            tokio::select! {
+                _ = term.wait_for_shutdown() => {
+                    break;
+                }
+                _ = tokio::time::sleep(delay) => {
+                    if let Err(error) = self.check().await {
+                        tracing::warn!(%error, "could not check the state of the key rotation");
+                    }
+                    delay = KEY_ROTATION_CHECK_INTERVAL;
+                }
+            }
+        }
+        tracing::info!("key rotation monitor has stopped");
+    }
+
+    /// Check the state of the key rotation on the canonical bitcoin
+    /// blockchain, recording it in the signer state. Returns the state.
+    pub async fn check(&self) -> Result<KeyRotationState, Error> {
+        let chain_tip = self
+            .context
+            .get_storage()
+            .get_bitcoin_canonical_chain_tip()
+            .await?;
+        let Some(chain_tip) = chain_tip else {
+            return Ok(self.context.state().key_rotation_state());
+        };
+
+        let key_rotation = detect_key_rotation_state(&self.context, &chain_tip).await?;
+        let previous = self.context.state().set_key_rotation_state(key_rotation);
+        Metrics::record_key_rotation_state(&key_rotation);
+
+        if previous != key_rotation {
+            match key_rotation {
+                KeyRotationState::Complete => {
+                    tracing::info!(%previous, "the key rotation is complete");
+                }
+                KeyRotationState::SweepPending { registered_key, utxo_key } => {
+                    tracing::warn!(
+                        %registered_key,
+                        %utxo_key,
+                        "the signers' UTXO has not been swept to the registered aggregate key"
+                    );
+                }
+                KeyRotationState::ContractCallPending { registered_key, utxo_key } => {
+                    tracing::warn!(
+                        registered_key = ?registered_key.map(|key| key.to_string()),
+                        %utxo_key,
+                        "the aggregate key locking the signers' UTXO has not been registered"
+                    );
+                }
+            }
+        }
+
+        Ok(key_rotation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fake::Fake as _;
+    use fake::Faker;
+
+    use crate::stacks::api::SignerSetInfo;
+    use crate::storage::DbWrite as _;
+    use crate::testing::context::*;
+
+    use super::*;
+
+    fn period(
+        aggregate_key: PublicKey,
+        start_height: u64,
+        end_height: Option<u64>,
+        registered: bool,
+    ) -> model::AggregateKeyPeriod {
+        model::AggregateKeyPeriod {
+            aggregate_key,
+            start_height: start_height.into(),
+            end_height: end_height.map(Into::into),
+            rotate_keys_txid: registered.then(|| Faker.fake()),
+        }
+    }
+
+    #[test]
+    fn key_rotation_state_compares_the_registry_with_the_utxo_key() {
+        let old_key: PublicKey = Faker.fake();
+        let new_key: PublicKey = Faker.fake();
+
+        // Without a signers' UTXO there is nothing to rotate.
+        let state = KeyRotationState::new(Some(new_key), &[]);
+        assert_eq!(state, KeyRotationState::Complete);
+
+        // The registered key locks the UTXO.
+        let history = [
+            period(old_key, 100, Some(110), true),
+            period(new_key, 110, None, true),
+        ];
+        let state = KeyRotationState::new(Some(new_key), &history);
+        assert_eq!(state, KeyRotationState::Complete);
+        assert!(!state.is_incomplete());
+
+        // The new key has been registered but the UTXO has not moved.
+        let history = [period(old_key, 100, None, true)];
+        let state = KeyRotationState::new(Some(new_key), &history);
+        let expected = KeyRotationState::SweepPending {
+            registered_key: new_key,
+            utxo_key: old_key,
+        };
+        assert_eq!(state, expected);
+        assert!(state.is_incomplete());
+        assert_eq!(state.unregistered_utxo_key(), None);
+
+        // The UTXO has moved but the new key was never registered.
+        let history = [
+            period(old_key, 100, Some(110), true),
+            period(new_key, 110, None, false),
+        ];
+        let state = KeyRotationState::new(Some(old_key), &history);
+        let expected = KeyRotationState::ContractCallPending {
+            registered_key: Some(old_key),
+            utxo_key: new_key,
+        };
+        assert_eq!(state, expected);
+        assert_eq!(state.unregistered_utxo_key(), Some(new_key));
+
+        // Before the first key rotation the UTXO is locked by a key that
+        // has not been registered.
+        let history = [period(new_key, 100, None, false)];
+        let state = KeyRotationState::new(None, &history);
+        assert_eq!(state.unregistered_utxo_key(), Some(new_key));
+        assert_eq!(state.to_string(), "contract_call_pending");
+    }
+
+    #[tokio::test]
+    async fn key_rotation_monitor_records_the_state() {
+        let ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .build();
+
+        let key: PublicKey = Faker.fake();
+        let stale = KeyRotationState::ContractCallPending {
+            registered_key: None,
+            utxo_key: key,
+        };
+        ctx.state().set_key_rotation_state(stale);
+
+        // Without a bitcoin chain tip we keep the state that we have.
+        let monitor = KeyRotationMonitor::new(ctx.clone());
+        assert_eq!(monitor.check().await.unwrap(), stale);
+
+        // With a chain tip but no signers' UTXO the rotation is complete.
+        let block: model::BitcoinBlock = Faker.fake();
+        ctx.get_storage_mut()
+            .write_bitcoin_block(&block)
+            .await
+            .unwrap();
+        ctx.state().update_registry_signer_set_info(SignerSetInfo {
+            aggregate_key: key,
+            signer_set: Default::default(),
+            signatures_required: 1,
+        });
+
+        assert_eq!(monitor.check().await.unwrap(), KeyRotationState::Complete);
+        assert_eq!(ctx.state().key_rotation_state(), KeyRotationState::Complete);
+    }
+}
@@ -38,6 +38,7 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::error::Error;
+use crate::secret::Secret;
 
 /// The public key type for the secp256k1 elliptic curve.
 #[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -235,7 +236,9 @@ impl PublicKey {
     /// Creates a new public key from a [`Private`] and the global
     /// [`SECP256K1`] context.
     pub fn from_private_key(key: &PrivateKey) -> Self {
-        Self(secp256k1::PublicKey::from_secret_key_global(&key.0))
+        Self(secp256k1::PublicKey::from_secret_key_global(
+            key.0.expose_secret(),
+        ))
     }
 
     /// Combine many keys into one aggregate key
@@ -327,9 +330,12 @@ impl PublicKeyXOnly {
 }
 
 /// A private key type for the secp256k1 elliptic curve.
+///
+/// The key is redacted when formatted, so types holding it can derive
+/// `Debug` without leaking it into the logs.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize)]
 #[serde(transparent)]
-pub struct PrivateKey(secp256k1::SecretKey);
+pub struct PrivateKey(Secret<secp256k1::SecretKey>);
 
 impl FromStr for PrivateKey {
     type Err = Error;
@@ -344,19 +350,19 @@ impl FromStr for PrivateKey {
 
 impl From<secp256k1::SecretKey> for PrivateKey {
     fn from(value: secp256k1::SecretKey) -> Self {
-        Self(value)
+        Self(Secret::new(value))
     }
 }
 
 impl From<PrivateKey> for secp256k1::SecretKey {
     fn from(value: PrivateKey) -> Self {
-        value.0
+        value.0.into_inner()
     }
 }
 
 impl From<PrivateKey> for libp2p::identity::Keypair {
     fn from(value: PrivateKey) -> Self {
-        let secret = libp2p::identity::secp256k1::SecretKey::try_from_bytes(value.0.secret_bytes())
+        let secret = libp2p::identity::secp256k1::SecretKey::try_from_bytes(value.to_bytes())
             .expect("BUG: secp256k1::SecretKey should be valid");
         libp2p::identity::secp256k1::Keypair::from(secret).into()
     }
@@ -367,14 +373,14 @@ impl TryFrom<&p256k1::scalar::Scalar> for PrivateKey {
     type Error = Error;
     fn try_from(value: &p256k1::scalar::Scalar) -> Result<Self, Self::Error> {
         secp256k1::SecretKey::from_slice(&value.to_bytes())
-            .map(Self)
+            .map(Self::from)
             .map_err(Error::InvalidPrivateKey)
     }
 }
 
 impl From<&PrivateKey> for p256k1::scalar::Scalar {
     fn from(value: &PrivateKey) -> Self {
-        p256k1::scalar::Scalar::from(value.0.secret_bytes())
+        p256k1::scalar::Scalar::from(value.to_bytes())
     }
 }
 
@@ -387,7 +393,7 @@ impl From<PrivateKey> for p256k1::scalar::Scalar {
 impl PrivateKey {
     /// Create a new one
     pub fn new<R: rand::Rng + ?Sized>(rng: &mut R) -> Self {
-        Self(secp256k1::SecretKey::new(rng))
+        Self::from(secp256k1::SecretKey::new(rng))
     }
     /// Creates a private key directly from a slice.
     pub fn from_slice(data: &[u8]) -> Result<Self, Error> {
@@ -396,19 +402,19 @@ impl PrivateKey {
         }
 
         secp256k1::SecretKey::from_slice(data)
-            .map(Self)
+            .map(Self::from)
             .map_err(Error::InvalidPrivateKey)
     }
 
     /// Returns the secret key as a byte array.
     pub fn to_bytes(&self) -> [u8; 32] {
-        self.0.secret_bytes()
+        self.0.expose_secret().secret_bytes()
     }
 
     /// Constructs an ECDSA signature for `message` using the global
     /// [`SECP256K1`] context and returns it in "low S" form.
     pub fn sign_ecdsa(&self, msg: &secp256k1::Message) -> secp256k1::ecdsa::Signature {
-        let mut sig = SECP256K1.sign_ecdsa(msg, &self.0);
+        let mut sig = SECP256K1.sign_ecdsa(msg, self.0.expose_secret());
         sig.normalize_s();
         sig
     }
@@ -419,7 +425,7 @@ impl PrivateKey {
         &self,
         msg: &secp256k1::Message,
     ) -> secp256k1::ecdsa::RecoverableSignature {
-        SECP256K1.sign_ecdsa_recoverable(msg, &self.0)
+        SECP256K1.sign_ecdsa_recoverable(msg, self.0.expose_secret())
     }
 }
 
@@ -634,6 +640,15 @@ mod tests {
         assert!(key.compressed())
     }
 
+    #[test]
+    fn private_keys_are_redacted_when_formatted() {
+        let private_key = PrivateKey::new(&mut OsRng);
+        let formatted = format!("{private_key:?}");
+
+        assert_eq!(formatted, "PrivateKey([REDACTED])");
+        assert!(!formatted.contains(&hex::encode(private_key.to_bytes())));
+    }
+
     #[test]
     fn selective_conversion_private_key() {
         // We test that we can go from a scalar to a PrivateKey with high
@@ -647,7 +662,7 @@ mod tests {
         let from_pk = p256k1::scalar::Scalar::from(&private_key);
         assert_eq!(from_pk, scalar);
 
-        let pk = PrivateKey(SecretKey::new(&mut OsRng));
+        let pk = PrivateKey::from(SecretKey::new(&mut OsRng));
         let scalar = p256k1::scalar::Scalar::from(&pk);
         let from_scalar = PrivateKey::try_from(&scalar).unwrap();
 
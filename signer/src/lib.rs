@@ -1,36 +1,113 @@
 #![doc = include_str!("../README.md")]
 
+pub mod admin;
 pub mod api;
 pub mod bitcoin;
 pub mod block_observer;
 pub mod blocklist_client;
+#[cfg(feature = "chaos")]
+pub mod chaos;
 pub mod codec;
 pub mod config;
 pub mod context;
+pub mod coordinator_latency;
+pub mod database_monitor;
+pub mod decision_policy;
+pub mod deposit_precheck;
+#[cfg(feature = "devenv")]
+pub mod devenv;
 pub mod dkg;
 pub mod ecdsa;
 pub mod emily_client;
+pub mod emily_import;
 pub mod error;
+pub mod invariants;
+pub mod key_rotation_recovery;
 pub mod keys;
 pub mod logging;
 pub mod message;
 pub mod metrics;
 pub mod network;
+pub mod partition_monitor;
 pub mod proto;
 pub mod request_decider;
+pub mod sbtc_limits;
+pub mod secret;
 pub mod signature;
 pub mod stacks;
 pub mod storage;
+pub mod supply_reconciliation;
+pub mod telemetry;
 #[cfg(any(test, feature = "testing"))]
 pub mod testing;
 pub mod transaction_coordinator;
 pub mod transaction_signer;
 pub mod util;
+pub mod watchtower;
 pub mod wsts_state_machine;
 
 /// Package version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// The version of the signer-to-signer protocol. It is advertised to
+/// peers when connecting, and should be bumped whenever a change is made
+/// that signers running older versions cannot interoperate with.
+pub const PROTOCOL_VERSION: u32 = 4;
+
+/// The first protocol version whose signers can validate parallel sweep
+/// packages, see [`bitcoin::utxo::SIGNER_UTXO_TARGET`]. The coordinator
+/// only constructs them once every other signer in the signer set has
+/// advertised at least this version.
+pub const PARALLEL_SWEEPS_PROTOCOL_VERSION: u32 = 2;
+
+/// The first protocol version whose signers handle
+/// [`message::SignerDecisionBatch`] messages. Signers only batch their
+/// decisions once every other signer has advertised at least this
+/// version, and send individual decision messages until then.
+pub const DECISION_BATCH_PROTOCOL_VERSION: u32 = 2;
+
+/// The first protocol version whose signers handle
+/// [`message::KeyRotationAttestation`] messages. Signers only broadcast
+/// their attestations once every other member of the new signer set has
+/// advertised at least this version.
+pub const KEY_ROTATION_ATTESTATION_PROTOCOL_VERSION: u32 = 3;
+
+/// The first protocol version whose signers handle
+/// [`message::DecisionSyncRequest`] messages. Signers only ask for the
+/// decisions of the others once every other signer has advertised at
+/// least this version.
+pub const DECISION_SYNC_PROTOCOL_VERSION: u32 = 3;
+
+/// The first protocol version whose signers handle
+/// [`message::CoordinatorSilenceAttestation`] and
+/// [`message::CoordinatorTakeover`] messages. The deputy coordinator only
+/// watches for coordinator silence once every other signer has
+/// advertised at least this version.
+pub const COORDINATOR_TAKEOVER_PROTOCOL_VERSION: u32 = 3;
+
+/// The first protocol version whose signers handle
+/// [`message::PeerAddressAnnouncement`] messages. Signers only announce
+/// their addresses once every other signer has advertised at least this
+/// version.
+pub const PEER_ADDRESS_ANNOUNCEMENT_PROTOCOL_VERSION: u32 = 3;
+
+/// The first protocol version whose signers handle
+/// [`message::RotateKeysDryRunRequest`] messages. A dry run is refused
+/// until every other signer has advertised at least this version.
+pub const ROTATE_KEYS_DRY_RUN_PROTOCOL_VERSION: u32 = 3;
+
+/// The first protocol version whose signers accept sweep packages that
+/// carry the network's own magic bytes instead of [`LEGACY_MAGIC_BYTES`].
+/// The coordinator keeps using the legacy magic bytes after the
+/// configured activation height until every other signer has advertised
+/// at least this version.
+pub const NETWORK_MAGIC_BYTES_PROTOCOL_VERSION: u32 = 4;
+
+/// The first protocol version whose signers answer
+/// [`message::SweepTxProofRequest`] messages. Peers should only expect an
+/// answer from signers that have advertised at least this version.
+pub const SWEEP_TX_PROOF_PROTOCOL_VERSION: u32 = 4;
+
 /// The maximum number of keys in the signers multi-sig wallet on Stacks.
 ///
 /// There are a few practical limits on the maximum number of distinct
@@ -46,12 +123,13 @@ pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 pub const MAX_KEYS: u16 = 128;
 
 /// Each deposit has a reclaim script spend path that can be executed after
-/// some "time". Right now this "time", the locktime, can only be
-/// denominated in bitcoin blocks. Once locktime number of blocks have been
-/// added to the blockchain after the deposit has been confirmed, the
-/// depositor can reclaim the deposit transaction. Signers will not attempt
-/// to sweep in the deposited funds if the number of blocks left is less
-/// than or equal to this value.
+/// some "time". This "time", the locktime, is denominated either in
+/// bitcoin blocks or in units of 512 seconds, and time-based locktimes are
+/// converted into blocks using [`TIME_BASED_LOCKTIME_SECONDS_PER_BLOCK`].
+/// Once locktime number of blocks have been added to the blockchain after
+/// the deposit has been confirmed, the depositor can reclaim the deposit
+/// transaction. Signers will not attempt to sweep in the deposited funds
+/// if the number of blocks left is less than or equal to this value.
 ///
 /// If the current chain tip is at height 1000, the reclaim script on a
 /// deposit can be spent on or after block 1001, and this constant value is
@@ -60,6 +138,17 @@ pub const MAX_KEYS: u16 = 128;
 /// the deposit.
 pub const DEPOSIT_LOCKTIME_BLOCK_BUFFER: u16 = 3;
 
+/// The number of seconds that we assume it takes to mine a bitcoin block
+/// when converting a time-based locktime into blocks.
+///
+/// A time-based locktime lets the depositor reclaim the deposit once the
+/// median time past has moved forward by the locktime, however many blocks
+/// that takes. Blocks take 600 seconds on average but can come in slower
+/// than that, so we assume twice the target block interval. This
+/// underestimates the number of blocks until the deposit can be
+/// reclaimed, which errs on the side of not sweeping the deposit.
+pub const TIME_BASED_LOCKTIME_SECONDS_PER_BLOCK: u32 = 1200;
+
 /// This is the capacity of the channel used for messages sent within the
 /// signer.
 pub const SIGNER_CHANNEL_CAPACITY: usize = 1024;
@@ -111,6 +200,15 @@ pub const MAX_MEMPOOL_PACKAGE_TX_COUNT: u64 = 25;
 /// next bitcoin block. This assumes signing rounds take ~16 seconds.
 pub const DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX: u16 = 25;
 
+/// The maximum number of deposit inputs that the signers accept in a
+/// single bitcoin transaction.
+///
+/// The signers reject sweep packages with more during pre-sign, so this
+/// is the same for every signer. A signer's `max_deposits_per_bitcoin_tx`
+/// setting only limits the transactions it constructs as the coordinator,
+/// and it must not exceed this value.
+pub const MAX_DEPOSITS_PER_BITCOIN_TX: u16 = 100;
+
 /// This is the dust limit for deposits in the sBTC smart contracts.
 /// Deposit amounts that is less than this amount will be rejected by the
 /// smart contract.
@@ -141,6 +239,18 @@ pub const WITHDRAWAL_BLOCKS_EXPIRY: u64 = 24;
 /// proposed "buffer for expiring requests" section.
 pub const WITHDRAWAL_EXPIRY_BUFFER: u64 = 6;
 
+/// These are the magic bytes that the signers wrote to the OP_RETURN
+/// output of their sweep transactions on every network, before each
+/// network got magic bytes of its own. They are also the magic bytes that
+/// stacks-core uses on regtest, so they never change there.
+pub const LEGACY_MAGIC_BYTES: [u8; 2] = *b"T3";
+
+/// This is the number of bitcoin blocks after the magic bytes activation
+/// height during which the signers still accept sweep packages that use
+/// [`LEGACY_MAGIC_BYTES`], so that coordinators that have not upgraded
+/// yet can still get their packages signed. This is about one week.
+pub const MAGIC_BYTES_TRANSITION_BLOCKS: u64 = 1008;
+
 /// This is the default maximum virtual size of a bitcoin transaction
 /// package. This value is the default limit set in bitcoin core, and
 /// corresponds to the `limitancestorsize` and/or `limitdescendantsize`
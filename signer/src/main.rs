@@ -20,18 +20,27 @@ use signer::blocklist_client::BlocklistClient;
 use signer::config::Settings;
 use signer::context::Context;
 use signer::context::SignerContext;
+use signer::database_monitor::DatabaseMonitor;
 use signer::emily_client::EmilyClient;
+use signer::emily_client::EmilyClientError;
+use signer::emily_import::EmilyImporter;
 use signer::error::Error;
+use signer::key_rotation_recovery::KeyRotationMonitor;
 use signer::logging::SignerInfoLogger;
 use signer::network::P2PNetwork;
 use signer::network::libp2p::SignerSwarmBuilder;
+use signer::partition_monitor::PartitionMonitor;
 use signer::request_decider::RequestDeciderEventLoop;
 use signer::stacks::api::StacksClient;
 use signer::storage::DbRead as _;
+use signer::storage::DbWrite as _;
 use signer::storage::postgres::PgStore;
+use signer::supply_reconciliation::SupplyReconciler;
+use signer::telemetry::OtlpExporter;
 use signer::transaction_coordinator;
 use signer::transaction_signer;
 use signer::util::ApiFallbackClient;
+use signer::watchtower::Watchtower;
 use time::OffsetDateTime;
 use tokio::signal;
 use tower_http::trace::TraceLayer;
@@ -77,6 +86,18 @@ struct SignerArgs {
     #[clap(long)]
     migrate_db: bool,
 
+    /// If this flag is set, the signer will import historical deposit and
+    /// withdrawal requests from Emily into its database on startup.
+    #[clap(long)]
+    import_from_emily: bool,
+
+    /// If this flag is set, the signer runs as a watchtower. It only runs
+    /// the block observer and the stacks event observer, and audits the
+    /// behavior of the signers from public data. It does not join the P2P
+    /// network and never uses its private key.
+    #[clap(long)]
+    watchtower: bool,
+
     #[clap(short = 'o', long = "output-format", default_value = "pretty")]
     output_format: Option<LogOutputFormat>,
 }
@@ -108,18 +129,37 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         tracing::error!(%error, "failed to construct the configuration");
     })?;
 
+    // Make sure that the secrets in the configuration never make it into
+    // the logs, even if they are part of a value that gets logged.
+    for secret in settings.secret_values() {
+        signer::logging::redact_from_logs(secret);
+    }
+
     let signer_public_key = settings.signer.public_key();
     tracing::info!(%signer_public_key, "config loaded successfully");
 
     signer::metrics::setup_metrics(settings.signer.prometheus_exporter_endpoint);
 
+    // Refuse to start against Emily endpoints that no longer serve the
+    // version of the API that we understand.
+    check_emily_api_versions(&settings)
+        .await
+        .inspect_err(|error| {
+            tracing::error!(%error, "an Emily endpoint is incompatible with this signer");
+        })?;
+
     // Open a connection to the signer db.
-    let db = PgStore::connect(settings.signer.db_endpoint.as_str())
+    let mut db = PgStore::connect(settings.signer.db_endpoint.as_str())
         .await
         .inspect_err(|err| {
             tracing::error!(%err, "failed to connect to the database");
         })?;
 
+    // Encrypt sensitive columns at rest if a key is configured.
+    if let Some(key) = settings.signer.db_column_encryption_key.clone() {
+        db = db.with_column_encryption_key(key);
+    }
+
     // Apply any pending migrations if automatic migrations are enabled.
     if args.migrate_db {
         db.apply_migrations().await.inspect_err(|err| {
@@ -127,6 +167,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         })?;
     }
 
+    // Replace the withdrawal output labels from a previous run with the
+    // configured ones.
+    db.write_withdrawal_output_labels(&settings.signer.withdrawal_output_labels)
+        .await
+        .inspect_err(|err| {
+            tracing::error!(%err, "failed to write the withdrawal output labels");
+        })?;
+
     // Initialize the signer context.
     let context = SignerContext::<
         _,
@@ -146,6 +194,29 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         context.state().current_signer_set().add_signer(*signer);
     }
 
+    // Backfill requests that predate this signer if asked to do so.
+    if args.import_from_emily {
+        EmilyImporter::new(context.clone())
+            .run()
+            .await
+            .inspect_err(|err| {
+                tracing::error!(%err, "failed to import requests from Emily");
+            })?;
+    }
+
+    if args.watchtower {
+        tracing::info!("running in watchtower mode");
+        let _ = tokio::join!(
+            run_shutdown_signal_watcher(context.clone()),
+            run_checked(run_api, &context),
+            run_checked(run_admin_api, &context),
+            run_checked(run_block_observer, &context),
+            run_checked(run_watchtower, &context),
+            run_supply_reconciler(context.clone()),
+        );
+        return Ok(());
+    }
+
     // Run the application components concurrently. We're `join!`ing them
     // here so that every component can shut itself down gracefully when
     // the shutdown signal is received.
@@ -163,6 +234,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         // The rest of our services which run concurrently, and must all be
         // running for the signer to be operational.
         run_checked(run_api, &context),
+        run_checked(run_admin_api, &context),
         run_checked(run_libp2p_swarm, &context),
         run_checked(run_block_observer, &context),
         run_checked(run_request_decider, &context),
@@ -171,11 +243,50 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         // Signer info logger intentionally runned in unchecked mode,
         // since it is not necessary for signer to be operational.
         run_signer_info_logger(context.clone()),
+        // The supply reconciler only raises alerts, so it also runs
+        // unchecked.
+        run_supply_reconciler(context.clone()),
+        // The partition monitor only toggles safe-mode, so it runs
+        // unchecked as well.
+        run_partition_monitor(context.clone()),
+        // The database monitor only pauses the event loops while the
+        // database is unreachable, so it runs unchecked too.
+        run_database_monitor(context.clone()),
+        // The key rotation monitor only reports on the key rotation, so it
+        // runs unchecked as well.
+        run_key_rotation_monitor(context.clone()),
+        // The OTLP exporter only exports tracing spans, so it runs
+        // unchecked as well.
+        run_otlp_exporter(context.clone()),
     );
 
     Ok(())
 }
 
+/// Checks that each Emily endpoint serves the version of the API that the
+/// signer understands. Endpoints that cannot be reached are skipped, since
+/// Emily being down is not a reason to stop the signer from starting.
+async fn check_emily_api_versions(settings: &Settings) -> Result<(), Error> {
+    let emily = &settings.emily;
+    for url in &emily.endpoints {
+        let client = EmilyClient::try_new(url, emily.timeout, emily.pagination_timeout, None)?;
+        match client.check_api_version().await {
+            Ok(api_version) => {
+                let url = &client.config().base_path;
+                tracing::debug!(%url, api_version, "Emily API version is compatible");
+            }
+            Err(error @ Error::EmilyApi(EmilyClientError::IncompatibleApiVersion { .. })) => {
+                return Err(error);
+            }
+            Err(error) => {
+                let url = &client.config().base_path;
+                tracing::warn!(%error, %url, "could not check the Emily API version");
+            }
+        }
+    }
+    Ok(())
+}
+
 /// A helper method that captures errors from the provided future and sends a
 /// shutdown signal to the application if an error is encountered. This is needed
 /// as otherwise the application would continue running indefinitely (since no
@@ -286,7 +397,7 @@ async fn run_libp2p_swarm(ctx: impl Context) -> Result<(), Error> {
     // and as such are dialed first. Known peers are gathered from the network
     // and are dialed using their explicit known/verified peer ID for added
     // security.
-    let known_peers = {
+    let mut known_peers = {
         // Fetch known peers from the database.
         let mut db_peers = ctx.get_storage()
             .get_p2p_peers()
@@ -324,6 +435,38 @@ async fn run_libp2p_swarm(ctx: impl Context) -> Result<(), Error> {
             .collect::<Vec<_>>()
     };
 
+    // Signers gossip signed announcements of their addresses, which we
+    // persist in our address book. We also dial the signers at the
+    // addresses that they last announced, so that we can reconnect to them
+    // even if their addresses changed since we last dialed them. There is
+    // at most one unexpired announcement per signer, so these are not
+    // limited to MAX_KNOWN_PEERS.
+    let announced_peers = ctx
+        .get_storage()
+        .get_active_peer_address_records()
+        .await
+        .inspect_err(|error| {
+            tracing::warn!(%error, "failed to fetch peer address records from the database; skipping announced peers");
+        })
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|record| {
+            ctx.state()
+                .current_signer_set()
+                .is_allowed_peer(&record.peer_id)
+        })
+        .flat_map(|record| {
+            let peer_id = *record.peer_id;
+            record
+                .addresses
+                .into_iter()
+                .filter(|address| !config.signer.p2p.seeds.contains(&**address))
+                .map(move |address| (peer_id, (*address).clone()))
+        })
+        .filter(|peer| !known_peers.contains(peer))
+        .collect::<Vec<_>>();
+    known_peers.extend(announced_peers);
+
     // Build the swarm.
     let mut swarm = SignerSwarmBuilder::new(&config.signer.private_key)
         .add_listen_endpoints(&ctx.config().signer.p2p.listen_on)
@@ -401,6 +544,47 @@ async fn run_api(ctx: impl Context + 'static) -> Result<(), Error> {
         })
 }
 
+/// Runs the signer's admin API server, which serves the `/admin` endpoints
+/// on a loopback address. The server is only started when an admin token
+/// is configured.
+#[tracing::instrument(skip_all, name = "admin-api")]
+async fn run_admin_api(ctx: impl Context + 'static) -> Result<(), Error> {
+    let config = &ctx.config().signer.event_observer;
+    if config.admin_token.as_deref().is_none_or(str::is_empty) {
+        tracing::info!("no admin token is configured; not serving the admin API");
+        return Ok(());
+    }
+    let socket_addr = config.admin_bind;
+    tracing::info!(%socket_addr, "initializing the signer admin API server");
+
+    let state = ApiState { ctx: ctx.clone() };
+    let app = api::get_admin_router()
+        .layer(
+            TraceLayer::new_for_http().make_span_with(|request: &Request<_>| {
+                tracing::info_span!("admin-api-request",
+                    uri = %request.uri(),
+                    method = %request.method(),
+                )
+            }),
+        )
+        .with_state(state);
+
+    let listener = tokio::net::TcpListener::bind(socket_addr).await?;
+    let mut term = ctx.get_termination_handle();
+
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async move {
+            term.wait_for_shutdown().await;
+            tracing::info!("stopping the signer admin API server");
+        })
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "error running the signer admin API server");
+            ctx.get_termination_handle().signal_shutdown();
+            error.into()
+        })
+}
+
 /// Run the block observer event-loop.
 async fn run_block_observer(ctx: impl Context) -> Result<(), Error> {
     let bitcoin_client = ctx.get_bitcoin_client();
@@ -429,6 +613,42 @@ async fn run_signer_info_logger(ctx: impl Context) {
         .await
 }
 
+/// Run the sBTC total-supply reconciler.
+async fn run_supply_reconciler(ctx: impl Context) {
+    SupplyReconciler::new(ctx).run().await
+}
+
+/// Run the watchtower, which audits the behavior of the signers.
+async fn run_watchtower(ctx: impl Context) -> Result<(), Error> {
+    Watchtower::new(ctx).run().await
+}
+
+/// Run the partition monitor, if partition detection is enabled.
+async fn run_partition_monitor(ctx: impl Context) {
+    if let Some(monitor) = PartitionMonitor::new(ctx) {
+        monitor.run().await
+    }
+}
+
+/// Run the database monitor, which pauses the event loops while the
+/// database is unreachable.
+async fn run_database_monitor(ctx: impl Context) {
+    DatabaseMonitor::new(ctx).run().await
+}
+
+/// Run the key rotation monitor, which detects partially-completed key
+/// rotations.
+async fn run_key_rotation_monitor(ctx: impl Context) {
+    KeyRotationMonitor::new(ctx).run().await
+}
+
+/// Run the OTLP exporter, if an OTLP traces endpoint is configured.
+async fn run_otlp_exporter(ctx: impl Context) {
+    if let Some(endpoint) = ctx.config().signer.otlp_traces_endpoint.clone() {
+        OtlpExporter::new(ctx, endpoint).run().await
+    }
+}
+
 /// Run the transaction signer event-loop.
 async fn run_transaction_signer(ctx: impl Context) -> Result<(), Error> {
     let network = P2PNetwork::new(&ctx);
@@ -469,7 +689,9 @@ async fn run_request_decider(ctx: impl Context) -> Result<(), Error> {
         context_window: config.signer.context_window,
         deposit_decisions_retry_window: config.signer.deposit_decisions_retry_window,
         withdrawal_decisions_retry_window: config.signer.withdrawal_decisions_retry_window,
+        carried_over: Default::default(),
         blocklist_checker: config.blocklist_client.as_ref().map(BlocklistClient::new),
+        decision_policies: Vec::new(),
         signer_private_key: config.signer.private_key,
     };
 
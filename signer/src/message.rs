@@ -1,15 +1,24 @@
 //! Signer message definition for network communication
 
+use std::collections::BTreeSet;
+
 use secp256k1::ecdsa::RecoverableSignature;
+use sha2::Digest as _;
 
 use crate::bitcoin::validation::TxRequestIds;
+use crate::error::Error;
+use crate::keys::PrivateKey;
 use crate::keys::PublicKey;
+use crate::logging::CorrelationId;
+use crate::logging::CorrelationIds;
 use crate::stacks::contracts::ContractCall;
+use crate::stacks::contracts::RotateKeysV1;
 use crate::stacks::contracts::StacksTx;
 use crate::storage::model;
 use crate::storage::model::BitcoinBlockHash;
 use crate::storage::model::StacksBlockHash;
 use crate::storage::model::StacksTxId;
+use crate::telemetry::TraceContext;
 
 /// Messages exchanged between signers
 #[derive(Debug, Clone, PartialEq)]
@@ -18,6 +27,81 @@ pub struct SignerMessage {
     pub bitcoin_chain_tip: BitcoinBlockHash,
     /// The message payload
     pub payload: Payload,
+    /// When set, the time by which the sender needs the work that the
+    /// message asks for to be done. Receivers stop working on the message
+    /// once it passes, since the sender has stopped waiting.
+    pub deadline: Option<Deadline>,
+    /// The trace context of the span that the sender created the message
+    /// in, if it was traced. Receivers handle the message in a child span
+    /// of it, so that a trace can follow work across signers.
+    pub trace_context: Option<TraceContext>,
+}
+
+impl SignerMessage {
+    /// Set the time by which the sender needs the work that the message
+    /// asks for to be done.
+    pub fn with_deadline(mut self, deadline: Deadline) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Whether the deadline of the message, if it has one, has passed.
+    pub fn is_expired(&self) -> bool {
+        self.deadline
+            .is_some_and(|deadline| deadline.remaining().is_none())
+    }
+}
+
+/// A point in time, with millisecond precision, by which some work needs
+/// to be done.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Deadline(u64);
+
+impl Deadline {
+    /// A deadline that is the given duration from now.
+    pub fn after(duration: std::time::Duration) -> Self {
+        let deadline = std::time::SystemTime::now() + duration;
+        let unix_millis = deadline
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        Self(u64::try_from(unix_millis).unwrap_or(u64::MAX))
+    }
+
+    /// A deadline at the given unix timestamp, in milliseconds.
+    pub fn from_unix_millis(unix_millis: u64) -> Self {
+        Self(unix_millis)
+    }
+
+    /// The unix timestamp of the deadline, in milliseconds.
+    pub fn as_unix_millis(&self) -> u64 {
+        self.0
+    }
+
+    /// The time left until the deadline, or `None` if it has passed.
+    pub fn remaining(&self) -> Option<std::time::Duration> {
+        let deadline = std::time::UNIX_EPOCH + std::time::Duration::from_millis(self.0);
+        deadline
+            .duration_since(std::time::SystemTime::now())
+            .ok()
+            .filter(|remaining| !remaining.is_zero())
+    }
+
+    /// Run the given future, giving up with [`Error::DeadlineExceeded`]
+    /// if the deadline, when there is one, passes before it completes.
+    pub async fn enforce<F, T>(deadline: Option<Self>, future: F) -> Result<T, Error>
+    where
+        F: Future<Output = Result<T, Error>>,
+    {
+        let Some(deadline) = deadline else {
+            return future.await;
+        };
+        let remaining = deadline.remaining().ok_or(Error::DeadlineExceeded)?;
+
+        tokio::time::timeout(remaining, future)
+            .await
+            .map_err(|_| Error::DeadlineExceeded)?
+    }
 }
 
 /// The different variants of signer messages
@@ -37,6 +121,28 @@ pub enum Payload {
     BitcoinPreSignRequest(BitcoinPreSignRequest),
     /// An acknowledgment of a BitconPreSignRequest
     BitcoinPreSignAck(BitcoinPreSignAck),
+    /// A signer's attestation to the outcome of a DKG round
+    KeyRotationAttestation(KeyRotationAttestation),
+    /// A request for the decisions made in a range of bitcoin blocks
+    DecisionSyncRequest(DecisionSyncRequest),
+    /// A page of decisions made in a range of bitcoin blocks
+    DecisionSyncResponse(DecisionSyncResponse),
+    /// A deputy coordinator's announcement that it is taking over a tenure
+    CoordinatorTakeover(CoordinatorTakeover),
+    /// A signer's attestation that the coordinator went silent
+    CoordinatorSilenceAttestation(CoordinatorSilenceAttestation),
+    /// A signer's announcement of the addresses that it can be reached at
+    PeerAddressAnnouncement(PeerAddressAnnouncement),
+    /// A batch of decisions on deposit and withdrawal requests
+    SignerDecisionBatch(SignerDecisionBatch),
+    /// A request to validate a rotate-keys contract call without signing it
+    RotateKeysDryRunRequest(RotateKeysDryRunRequest),
+    /// A signer's verdict on a rotate-keys dry run request
+    RotateKeysDryRunVerdict(RotateKeysDryRunVerdict),
+    /// A request for a proof that a sweep transaction was confirmed
+    SweepTxProofRequest(SweepTxProofRequest),
+    /// A proof that a sweep transaction was confirmed, if the sender has one
+    SweepTxProofResponse(SweepTxProofResponse),
 }
 
 impl std::fmt::Display for Payload {
@@ -68,6 +174,19 @@ impl std::fmt::Display for Payload {
             }
             Self::BitcoinPreSignRequest(_) => write!(f, "BitcoinPreSignRequest(..)"),
             Self::BitcoinPreSignAck(_) => write!(f, "BitcoinPreSignAck(..)"),
+            Self::KeyRotationAttestation(_) => write!(f, "KeyRotationAttestation(..)"),
+            Self::DecisionSyncRequest(_) => write!(f, "DecisionSyncRequest(..)"),
+            Self::DecisionSyncResponse(_) => write!(f, "DecisionSyncResponse(..)"),
+            Self::CoordinatorTakeover(_) => write!(f, "CoordinatorTakeover(..)"),
+            Self::CoordinatorSilenceAttestation(_) => {
+                write!(f, "CoordinatorSilenceAttestation(..)")
+            }
+            Self::PeerAddressAnnouncement(_) => write!(f, "PeerAddressAnnouncement(..)"),
+            Self::SignerDecisionBatch(_) => write!(f, "SignerDecisionBatch(..)"),
+            Self::RotateKeysDryRunRequest(_) => write!(f, "RotateKeysDryRunRequest(..)"),
+            Self::RotateKeysDryRunVerdict(_) => write!(f, "RotateKeysDryRunVerdict(..)"),
+            Self::SweepTxProofRequest(_) => write!(f, "SweepTxProofRequest(..)"),
+            Self::SweepTxProofResponse(_) => write!(f, "SweepTxProofResponse(..)"),
         }
     }
 }
@@ -78,6 +197,35 @@ impl Payload {
         SignerMessage {
             bitcoin_chain_tip,
             payload: self,
+            deadline: None,
+            trace_context: crate::telemetry::current_trace_context(),
+        }
+    }
+
+    /// Return the correlation IDs of the sBTC requests that this payload
+    /// is about. Since every signer derives them from the payload itself,
+    /// the same IDs show up in the logs of the sending and receiving
+    /// signers.
+    pub fn correlation_ids(&self) -> CorrelationIds {
+        match self {
+            Self::SignerDepositDecision(decision) => decision.correlation_id().into(),
+            Self::SignerWithdrawalDecision(decision) => decision.correlation_id().into(),
+            Self::StacksTransactionSignRequest(request) => request.correlation_ids(),
+            Self::BitcoinPreSignRequest(request) => request.correlation_ids(),
+            Self::SignerDecisionBatch(batch) => batch.correlation_ids(),
+            Self::StacksTransactionSignature(_)
+            | Self::WstsMessage(_)
+            | Self::BitcoinPreSignAck(_)
+            | Self::KeyRotationAttestation(_)
+            | Self::DecisionSyncRequest(_)
+            | Self::DecisionSyncResponse(_)
+            | Self::CoordinatorTakeover(_)
+            | Self::CoordinatorSilenceAttestation(_)
+            | Self::PeerAddressAnnouncement(_)
+            | Self::RotateKeysDryRunRequest(_)
+            | Self::RotateKeysDryRunVerdict(_)
+            | Self::SweepTxProofRequest(_)
+            | Self::SweepTxProofResponse(_) => CorrelationIds::default(),
         }
     }
 }
@@ -124,6 +272,72 @@ impl From<BitcoinPreSignAck> for Payload {
     }
 }
 
+impl From<KeyRotationAttestation> for Payload {
+    fn from(value: KeyRotationAttestation) -> Self {
+        Self::KeyRotationAttestation(value)
+    }
+}
+
+impl From<DecisionSyncRequest> for Payload {
+    fn from(value: DecisionSyncRequest) -> Self {
+        Self::DecisionSyncRequest(value)
+    }
+}
+
+impl From<DecisionSyncResponse> for Payload {
+    fn from(value: DecisionSyncResponse) -> Self {
+        Self::DecisionSyncResponse(value)
+    }
+}
+
+impl From<CoordinatorTakeover> for Payload {
+    fn from(value: CoordinatorTakeover) -> Self {
+        Self::CoordinatorTakeover(value)
+    }
+}
+
+impl From<CoordinatorSilenceAttestation> for Payload {
+    fn from(value: CoordinatorSilenceAttestation) -> Self {
+        Self::CoordinatorSilenceAttestation(value)
+    }
+}
+
+impl From<PeerAddressAnnouncement> for Payload {
+    fn from(value: PeerAddressAnnouncement) -> Self {
+        Self::PeerAddressAnnouncement(value)
+    }
+}
+
+impl From<SignerDecisionBatch> for Payload {
+    fn from(value: SignerDecisionBatch) -> Self {
+        Self::SignerDecisionBatch(value)
+    }
+}
+
+impl From<RotateKeysDryRunRequest> for Payload {
+    fn from(value: RotateKeysDryRunRequest) -> Self {
+        Self::RotateKeysDryRunRequest(value)
+    }
+}
+
+impl From<RotateKeysDryRunVerdict> for Payload {
+    fn from(value: RotateKeysDryRunVerdict) -> Self {
+        Self::RotateKeysDryRunVerdict(value)
+    }
+}
+
+impl From<SweepTxProofRequest> for Payload {
+    fn from(value: SweepTxProofRequest) -> Self {
+        Self::SweepTxProofRequest(value)
+    }
+}
+
+impl From<SweepTxProofResponse> for Payload {
+    fn from(value: SweepTxProofResponse) -> Self {
+        Self::SweepTxProofResponse(value)
+    }
+}
+
 /// Represents a decision related to signer deposit
 #[derive(Debug, Clone, PartialEq)]
 pub struct SignerDepositDecision {
@@ -140,6 +354,14 @@ pub struct SignerDepositDecision {
     pub can_sign: bool,
 }
 
+impl SignerDepositDecision {
+    /// Return the correlation ID of the deposit request that this decision
+    /// is about.
+    pub fn correlation_id(&self) -> CorrelationId {
+        bitcoin::OutPoint::new(self.txid, self.output_index).into()
+    }
+}
+
 impl From<model::DepositSigner> for SignerDepositDecision {
     fn from(signer: model::DepositSigner) -> Self {
         Self {
@@ -166,6 +388,19 @@ pub struct SignerWithdrawalDecision {
     pub accepted: bool,
 }
 
+impl SignerWithdrawalDecision {
+    /// Return the correlation ID of the withdrawal request that this
+    /// decision is about.
+    pub fn correlation_id(&self) -> CorrelationId {
+        model::QualifiedRequestId {
+            request_id: self.request_id,
+            txid: self.txid,
+            block_hash: self.block_hash,
+        }
+        .into()
+    }
+}
+
 impl From<model::WithdrawalSigner> for SignerWithdrawalDecision {
     fn from(signer: model::WithdrawalSigner) -> Self {
         Self {
@@ -194,6 +429,27 @@ pub struct StacksTransactionSignRequest {
     pub txid: StacksTxId,
 }
 
+impl StacksTransactionSignRequest {
+    /// Return the correlation ID of the sBTC request that the contract
+    /// call fulfills, if any.
+    pub fn correlation_ids(&self) -> CorrelationIds {
+        match &self.contract_tx {
+            StacksTx::ContractCall(ContractCall::CompleteDepositV1(call)) => {
+                CorrelationId::from(call.outpoint).into()
+            }
+            StacksTx::ContractCall(ContractCall::AcceptWithdrawalV1(call)) => {
+                CorrelationId::from(call.id.clone()).into()
+            }
+            StacksTx::ContractCall(ContractCall::RejectWithdrawalV1(call)) => {
+                CorrelationId::from(call.id.clone()).into()
+            }
+            StacksTx::ContractCall(ContractCall::RotateKeysV1(_)) | StacksTx::SmartContract(_) => {
+                CorrelationIds::default()
+            }
+        }
+    }
+}
+
 impl StacksTransactionSignRequest {
     /// Return the kind of transaction that that is being asked to be
     /// signed.
@@ -232,6 +488,31 @@ pub struct BitcoinPreSignRequest {
     ///
     /// This field is deprecated and will be removed in a future release.
     pub last_fees: Option<crate::proto::Fees>,
+    /// Whether the package is constructed as parallel sweeps, where the
+    /// first transaction splits the signers' change and the transactions
+    /// after it spend the signers' spare UTXOs. Signers running a protocol
+    /// version before [`crate::PARALLEL_SWEEPS_PROTOCOL_VERSION`] ignore
+    /// this field, so the coordinator only sets it once every signer has
+    /// upgraded.
+    pub parallel_sweeps: bool,
+    /// The magic bytes at the start of the OP_RETURN output of each
+    /// transaction in the package. The signers only accept the values
+    /// that [`crate::config::SignerConfig::accepts_magic_bytes`] allows
+    /// for the chain tip of the package.
+    pub magic_bytes: [u8; 2],
+}
+
+impl BitcoinPreSignRequest {
+    /// Return the correlation IDs of all requests in the transaction
+    /// package.
+    pub fn correlation_ids(&self) -> CorrelationIds {
+        let ids = self
+            .request_package
+            .iter()
+            .flat_map(|ids| CorrelationIds::from(ids).0)
+            .collect();
+        CorrelationIds(ids)
+    }
 }
 
 impl std::fmt::Display for BitcoinPreSignRequest {
@@ -245,8 +526,11 @@ impl std::fmt::Display for BitcoinPreSignRequest {
         }
         write!(
             f,
-            "], fee_rate={}, last_fees={:?})",
-            self.fee_rate, self.last_fees
+            "], fee_rate={}, last_fees={:?}, parallel_sweeps={}, magic_bytes={})",
+            self.fee_rate,
+            self.last_fees,
+            self.parallel_sweeps,
+            hex::encode(self.magic_bytes)
         )
     }
 }
@@ -255,6 +539,425 @@ impl std::fmt::Display for BitcoinPreSignRequest {
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct BitcoinPreSignAck;
 
+/// A signer's attestation to the outcome of a DKG round.
+///
+/// Each signer produces one of these after it has verified the shares of
+/// a new aggregate key. Before the new key is rotated into the
+/// sbtc-registry contract, the signers check that a quorum of the new
+/// signer set has attested to the same aggregate key, signer set and
+/// threshold.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeyRotationAttestation {
+    /// The aggregate key that the signers intend to rotate into.
+    pub aggregate_key: PublicKey,
+    /// The SHA-256 hash of the sorted, compressed public keys of the new
+    /// signer set. See [`KeyRotationAttestation::signer_set_hash`].
+    pub signer_set_hash: [u8; 32],
+    /// The number of signatures required for the new multi-sig wallet.
+    pub signatures_required: u16,
+    /// An ECDSA signature by the attesting signer over the above fields.
+    pub signature: secp256k1::ecdsa::Signature,
+}
+
+impl KeyRotationAttestation {
+    /// The prefix used when computing the digest that gets signed.
+    const TYPE_TAG: &'static str = "SBTC_KEY_ROTATION_ATTESTATION";
+
+    /// Create and sign a new attestation with the given private key.
+    pub fn new(
+        private_key: &PrivateKey,
+        aggregate_key: PublicKey,
+        signer_set: &BTreeSet<PublicKey>,
+        signatures_required: u16,
+    ) -> Self {
+        let signer_set_hash = Self::signer_set_hash(signer_set);
+        let digest = Self::digest(&aggregate_key, &signer_set_hash, signatures_required);
+        let msg = secp256k1::Message::from_digest(digest);
+
+        Self {
+            aggregate_key,
+            signer_set_hash,
+            signatures_required,
+            signature: private_key.sign_ecdsa(&msg),
+        }
+    }
+
+    /// Compute the hash of a signer set. The keys are hashed in sorted
+    /// order in their compressed form.
+    pub fn signer_set_hash(signer_set: &BTreeSet<PublicKey>) -> [u8; 32] {
+        let mut hasher = sha2::Sha256::new();
+        for public_key in signer_set {
+            hasher.update(public_key.serialize());
+        }
+        hasher.finalize().into()
+    }
+
+    /// Compute the digest that is signed over for an attestation.
+    fn digest(
+        aggregate_key: &PublicKey,
+        signer_set_hash: &[u8; 32],
+        signatures_required: u16,
+    ) -> [u8; 32] {
+        let mut hasher = sha2::Sha256::new_with_prefix(Self::TYPE_TAG);
+        hasher.update(aggregate_key.serialize());
+        hasher.update(signer_set_hash);
+        hasher.update(signatures_required.to_be_bytes());
+        hasher.finalize().into()
+    }
+
+    /// Verify that the attestation was signed by the given public key.
+    pub fn verify(&self, public_key: &PublicKey) -> Result<(), Error> {
+        let digest = Self::digest(
+            &self.aggregate_key,
+            &self.signer_set_hash,
+            self.signatures_required,
+        );
+        let msg = secp256k1::Message::from_digest(digest);
+
+        self.signature
+            .verify(&msg, &public_key.into())
+            .map_err(Error::InvalidEcdsaSignature)
+    }
+
+    /// Whether this attestation is for the given aggregate key, signer
+    /// set and signature threshold.
+    pub fn attests_to(
+        &self,
+        aggregate_key: &PublicKey,
+        signer_set: &BTreeSet<PublicKey>,
+        signatures_required: u16,
+    ) -> bool {
+        self.aggregate_key == *aggregate_key
+            && self.signer_set_hash == Self::signer_set_hash(signer_set)
+            && self.signatures_required == signatures_required
+    }
+}
+
+/// A request for the signers to validate a rotate-keys contract call
+/// without signing it.
+///
+/// Operators use this to find out whether a key rotation would pass
+/// validation before attempting one. Each signer runs the same checks
+/// that it runs on a request to sign the contract call and responds with
+/// a [`RotateKeysDryRunVerdict`], but nothing is signed or broadcast.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RotateKeysDryRunRequest {
+    /// An ID that the sender uses to match verdicts to this request.
+    pub id: u64,
+    /// The contract call to validate.
+    pub rotate_keys: RotateKeysV1,
+}
+
+/// A signer's verdict on a [`RotateKeysDryRunRequest`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RotateKeysDryRunVerdict {
+    /// The ID of the request that this verdict is for.
+    pub id: u64,
+    /// Why the signer would refuse to sign the contract call, or `None`
+    /// if the contract call passes the signer's validation.
+    pub rejection_reason: Option<String>,
+}
+
+/// A request for the decisions that the receiving signers made on the
+/// deposit and withdrawal requests confirmed in a range of bitcoin blocks.
+///
+/// Signers that were offline miss the decisions that were broadcast in
+/// the meantime. They send this request when they rejoin, and each of
+/// the other signers answers with its own decisions, one
+/// [`DecisionSyncResponse`] per page.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DecisionSyncRequest {
+    /// The height of the first bitcoin block in the range.
+    pub start_height: model::BitcoinBlockHeight,
+    /// The height of the last bitcoin block in the range, inclusive.
+    pub end_height: model::BitcoinBlockHeight,
+}
+
+impl DecisionSyncRequest {
+    /// The maximum number of deposit decisions, and separately the
+    /// maximum number of withdrawal decisions, in a single response. A
+    /// full page must fit within [`crate::GOSSIPSUB_MAX_TRANSMIT_SIZE`].
+    pub const PAGE_SIZE: u16 = 200;
+    /// The maximum number of bitcoin blocks that a single request may
+    /// span. Signers ignore requests for wider ranges.
+    pub const MAX_BLOCK_RANGE: u64 = 1000;
+
+    /// Whether the block range of this request is one that signers will
+    /// answer.
+    pub fn is_valid_range(&self) -> bool {
+        self.start_height <= self.end_height
+            && self.end_height - self.start_height < Self::MAX_BLOCK_RANGE
+    }
+}
+
+/// A page of the sending signer's own decisions on the deposit and
+/// withdrawal requests confirmed in a range of bitcoin blocks.
+///
+/// The decisions are authenticated by the signature over the enclosing
+/// [`SignerMessage`], so a signer may only answer with its own decisions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecisionSyncResponse {
+    /// The height of the first bitcoin block in the range.
+    pub start_height: model::BitcoinBlockHeight,
+    /// The height of the last bitcoin block in the range, inclusive.
+    pub end_height: model::BitcoinBlockHeight,
+    /// The page of decisions in this response, starting from zero.
+    pub page: u32,
+    /// The sending signer's decisions on deposit requests.
+    pub deposits: Vec<SignerDepositDecision>,
+    /// The sending signer's decisions on withdrawal requests.
+    pub withdrawals: Vec<SignerWithdrawalDecision>,
+    /// Whether the sending signer has more decisions in the range.
+    pub has_more: bool,
+    /// The public key of the signer that requested the decisions. There
+    /// is no way to send a message to a single signer, so responses go to
+    /// everyone, and the other signers drop them unread.
+    pub requester: PublicKey,
+}
+
+/// A batch of the sending signer's decisions on deposit and withdrawal
+/// requests.
+///
+/// Signers decide on all new requests when they observe a bitcoin block
+/// and broadcast those decisions together, instead of sending one message
+/// per decision. The receiving signers store all the decisions of a batch
+/// in a single storage transaction.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SignerDecisionBatch {
+    /// The sending signer's decisions on deposit requests.
+    pub deposits: Vec<SignerDepositDecision>,
+    /// The sending signer's decisions on withdrawal requests.
+    pub withdrawals: Vec<SignerWithdrawalDecision>,
+}
+
+impl SignerDecisionBatch {
+    /// The maximum number of deposit decisions, and separately the
+    /// maximum number of withdrawal decisions, in a single batch. Batches
+    /// are no larger than a page of a [`DecisionSyncResponse`], so a full
+    /// batch fits within [`crate::GOSSIPSUB_MAX_TRANSMIT_SIZE`] too.
+    pub const MAX_SIZE: usize = DecisionSyncRequest::PAGE_SIZE as usize;
+
+    /// Whether the batch holds no decisions at all.
+    pub fn is_empty(&self) -> bool {
+        self.deposits.is_empty() && self.withdrawals.is_empty()
+    }
+
+    /// Whether the batch holds more decisions than signers accept in a
+    /// single batch.
+    pub fn is_oversized(&self) -> bool {
+        self.deposits.len() > Self::MAX_SIZE || self.withdrawals.len() > Self::MAX_SIZE
+    }
+
+    /// Split the batch into batches that are each within
+    /// [`SignerDecisionBatch::MAX_SIZE`]. An empty batch splits into no
+    /// batches.
+    pub fn into_chunks(self) -> Vec<SignerDecisionBatch> {
+        let num_chunks = self
+            .deposits
+            .len()
+            .max(self.withdrawals.len())
+            .div_ceil(Self::MAX_SIZE);
+        let mut deposits = self.deposits.into_iter();
+        let mut withdrawals = self.withdrawals.into_iter();
+
+        (0..num_chunks)
+            .map(|_| SignerDecisionBatch {
+                deposits: deposits.by_ref().take(Self::MAX_SIZE).collect(),
+                withdrawals: withdrawals.by_ref().take(Self::MAX_SIZE).collect(),
+            })
+            .collect()
+    }
+
+    /// Return the correlation IDs of all requests decided in the batch.
+    pub fn correlation_ids(&self) -> CorrelationIds {
+        let deposits = self
+            .deposits
+            .iter()
+            .map(SignerDepositDecision::correlation_id);
+        let withdrawals = self
+            .withdrawals
+            .iter()
+            .map(SignerWithdrawalDecision::correlation_id);
+        CorrelationIds(deposits.chain(withdrawals).collect())
+    }
+}
+
+/// An announcement that the sending signer is taking over the tenure of
+/// the enclosing message's bitcoin chain tip from a coordinator that went
+/// silent.
+///
+/// Only the deputy coordinator of the chain tip, the signer that follows
+/// the coordinator in the coordinator ordering, may take over, and only
+/// with the [`CoordinatorSilenceAttestation`]s of at least a threshold of
+/// the signer set. The other signers accept the takeover based on these
+/// attestations alone, so that they all come to the same conclusion no
+/// matter when they last heard from the coordinator themselves. Once
+/// accepted, the signers treat the deputy as the coordinator for the rest
+/// of the tenure and ignore the original coordinator.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoordinatorTakeover {
+    /// The public key of the coordinator that went silent.
+    pub coordinator_public_key: PublicKey,
+    /// The attestations of the signers that have not heard from the
+    /// coordinator.
+    pub attestations: Vec<CoordinatorSilenceAttestation>,
+}
+
+/// A signer's signed statement that it has not heard from the coordinator
+/// of a bitcoin chain tip within the configured silence timeout.
+///
+/// The deputy coordinator sends one of these when the coordinator goes
+/// silent, and the other signers send their own in response if they have
+/// not heard from the coordinator either. The signature commits to the
+/// bitcoin chain tip, so the attestations can be collected into a
+/// [`CoordinatorTakeover`] and verified by every signer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CoordinatorSilenceAttestation {
+    /// The public key of the attesting signer.
+    pub signer_public_key: PublicKey,
+    /// The public key of the coordinator that went silent.
+    pub coordinator_public_key: PublicKey,
+    /// An ECDSA signature by the attesting signer over the bitcoin chain
+    /// tip and the public key of the coordinator.
+    pub signature: secp256k1::ecdsa::Signature,
+}
+
+impl CoordinatorSilenceAttestation {
+    /// The prefix used when computing the digest that gets signed.
+    const TYPE_TAG: &'static str = "SBTC_COORDINATOR_SILENCE_ATTESTATION";
+
+    /// Create and sign a new attestation with the given private key.
+    pub fn new(
+        private_key: &PrivateKey,
+        bitcoin_chain_tip: &BitcoinBlockHash,
+        coordinator_public_key: PublicKey,
+    ) -> Self {
+        let digest = Self::digest(bitcoin_chain_tip, &coordinator_public_key);
+        let msg = secp256k1::Message::from_digest(digest);
+
+        Self {
+            signer_public_key: PublicKey::from_private_key(private_key),
+            coordinator_public_key,
+            signature: private_key.sign_ecdsa(&msg),
+        }
+    }
+
+    /// Compute the digest that is signed over for an attestation.
+    fn digest(
+        bitcoin_chain_tip: &BitcoinBlockHash,
+        coordinator_public_key: &PublicKey,
+    ) -> [u8; 32] {
+        let mut hasher = sha2::Sha256::new_with_prefix(Self::TYPE_TAG);
+        hasher.update(bitcoin_chain_tip.into_bytes());
+        hasher.update(coordinator_public_key.serialize());
+        hasher.finalize().into()
+    }
+
+    /// Verify that the attestation was signed by its signer for the given
+    /// bitcoin chain tip.
+    pub fn verify(&self, bitcoin_chain_tip: &BitcoinBlockHash) -> Result<(), Error> {
+        let digest = Self::digest(bitcoin_chain_tip, &self.coordinator_public_key);
+        let msg = secp256k1::Message::from_digest(digest);
+
+        self.signature
+            .verify(&msg, &self.signer_public_key.into())
+            .map_err(Error::InvalidEcdsaSignature)
+    }
+}
+
+/// A signer's announcement of the addresses that it can be reached at on
+/// the P2P network.
+///
+/// Signers gossip these announcements periodically and persist the ones
+/// that they receive, so that they can reconnect to each other after a
+/// restart even if the addresses of their peers changed. The announcement
+/// is authenticated by the signature on the enclosing message, and a newer
+/// announcement, one that expires later, replaces an older one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeerAddressAnnouncement {
+    /// The addresses that the signer can be reached at.
+    pub addresses: Vec<libp2p::Multiaddr>,
+    /// The unix timestamp, in seconds, after which the announcement
+    /// expires.
+    pub expires_at: u64,
+}
+
+/// A request for a proof that the sweep transaction with the given ID was
+/// confirmed on the canonical bitcoin blockchain.
+///
+/// Signers answer with a [`SweepTxProofResponse`] built from what they
+/// have in storage, so a peer on the signer network, like a gateway
+/// relaying the queries of light clients, can verify peg-outs without
+/// running a bitcoin node of its own.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SweepTxProofRequest {
+    /// The ID of the sweep transaction.
+    pub txid: model::BitcoinTxId,
+}
+
+impl SweepTxProofRequest {
+    /// The maximum number of headers, of the blocks that build on the
+    /// block that confirmed the sweep transaction, in a proof. This is
+    /// about a day's worth of blocks, and keeps a full response well
+    /// within [`crate::GOSSIPSUB_MAX_TRANSMIT_SIZE`]. It is the same as
+    /// the limit on the proofs served by the signer API.
+    pub const MAX_HEADERS: u16 = crate::api::SweepTxProof::MAX_HEADERS;
+}
+
+/// The answer to a [`SweepTxProofRequest`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepTxProofResponse {
+    /// The ID of the sweep transaction that the proof is for.
+    pub txid: model::BitcoinTxId,
+    /// The proof, or `None` if the sending signer has no record of the
+    /// sweep transaction being confirmed on its canonical bitcoin
+    /// blockchain.
+    pub proof: Option<SweepTxProof>,
+}
+
+/// A proof that a sweep transaction was confirmed in a bitcoin block.
+///
+/// The merkle block commits to the transaction under the merkle root in
+/// the header of the block that confirmed it, and the headers that follow
+/// chain onto that header. A light client checks the proof of work of the
+/// headers itself, and the number of headers is the number of
+/// confirmations that the proof attests to beyond the first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepTxProof {
+    /// The height of the bitcoin block that confirmed the transaction.
+    pub block_height: model::BitcoinBlockHeight,
+    /// The header of the bitcoin block that confirmed the transaction,
+    /// along with a partial merkle tree that matches the transaction.
+    pub merkle_block: bitcoin::MerkleBlock,
+    /// The headers of the bitcoin blocks that build on the block that
+    /// confirmed the transaction, ordered by height.
+    pub headers: Vec<bitcoin::block::Header>,
+}
+
+impl SweepTxProof {
+    /// Whether the merkle block matches the transaction with the given ID
+    /// under the merkle root of its header, and each of the headers builds
+    /// on the one before it.
+    pub fn is_valid_for(&self, txid: &model::BitcoinTxId) -> bool {
+        let mut matches = Vec::new();
+        let mut indexes = Vec::new();
+        let proves_inclusion = self
+            .merkle_block
+            .extract_matches(&mut matches, &mut indexes)
+            .is_ok()
+            && matches == [bitcoin::Txid::from(*txid)];
+
+        let mut block_hash = self.merkle_block.header.block_hash();
+        let headers_link = self.headers.iter().all(|header| {
+            let links = header.prev_blockhash == block_hash;
+            block_hash = header.block_hash();
+            links
+        });
+
+        proves_inclusion && headers_link
+    }
+}
+
 /// The identifier for a WSTS message.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum WstsMessageId {
@@ -328,6 +1031,7 @@ mod tests {
     use crate::ecdsa::{SignEcdsa as _, Signed};
     use crate::keys::PrivateKey;
 
+    use fake::Fake as _;
     use rand::SeedableRng as _;
     use test_case::test_case;
 
@@ -337,6 +1041,17 @@ mod tests {
     #[test_case(PhantomData::<StacksTransactionSignature> ; "StacksTransactionSignature")]
     #[test_case(PhantomData::<WstsMessage> ; "WstsMessage")]
     #[test_case(PhantomData::<BitcoinPreSignRequest> ; "BitcoinPreSignRequest")]
+    #[test_case(PhantomData::<KeyRotationAttestation> ; "KeyRotationAttestation")]
+    #[test_case(PhantomData::<DecisionSyncRequest> ; "DecisionSyncRequest")]
+    #[test_case(PhantomData::<DecisionSyncResponse> ; "DecisionSyncResponse")]
+    #[test_case(PhantomData::<CoordinatorTakeover> ; "CoordinatorTakeover")]
+    #[test_case(PhantomData::<CoordinatorSilenceAttestation> ; "CoordinatorSilenceAttestation")]
+    #[test_case(PhantomData::<PeerAddressAnnouncement> ; "PeerAddressAnnouncement")]
+    #[test_case(PhantomData::<SignerDecisionBatch> ; "SignerDecisionBatch")]
+    #[test_case(PhantomData::<RotateKeysDryRunRequest> ; "RotateKeysDryRunRequest")]
+    #[test_case(PhantomData::<RotateKeysDryRunVerdict> ; "RotateKeysDryRunVerdict")]
+    #[test_case(PhantomData::<SweepTxProofRequest> ; "SweepTxProofRequest")]
+    #[test_case(PhantomData::<SweepTxProofResponse> ; "SweepTxProofResponse")]
     fn signer_messages_should_be_signable_with_type<P>(_: PhantomData<P>)
     where
         P: fake::Dummy<fake::Faker> + Into<Payload>,
@@ -356,6 +1071,17 @@ mod tests {
     #[test_case(PhantomData::<StacksTransactionSignature> ; "StacksTransactionSignature")]
     #[test_case(PhantomData::<WstsMessage> ; "WstsMessage")]
     #[test_case(PhantomData::<BitcoinPreSignRequest> ; "BitcoinPreSignRequest")]
+    #[test_case(PhantomData::<KeyRotationAttestation> ; "KeyRotationAttestation")]
+    #[test_case(PhantomData::<DecisionSyncRequest> ; "DecisionSyncRequest")]
+    #[test_case(PhantomData::<DecisionSyncResponse> ; "DecisionSyncResponse")]
+    #[test_case(PhantomData::<CoordinatorTakeover> ; "CoordinatorTakeover")]
+    #[test_case(PhantomData::<CoordinatorSilenceAttestation> ; "CoordinatorSilenceAttestation")]
+    #[test_case(PhantomData::<PeerAddressAnnouncement> ; "PeerAddressAnnouncement")]
+    #[test_case(PhantomData::<SignerDecisionBatch> ; "SignerDecisionBatch")]
+    #[test_case(PhantomData::<RotateKeysDryRunRequest> ; "RotateKeysDryRunRequest")]
+    #[test_case(PhantomData::<RotateKeysDryRunVerdict> ; "RotateKeysDryRunVerdict")]
+    #[test_case(PhantomData::<SweepTxProofRequest> ; "SweepTxProofRequest")]
+    #[test_case(PhantomData::<SweepTxProofResponse> ; "SweepTxProofResponse")]
     fn signer_messages_should_be_encodable_with_type<P>(_: PhantomData<P>)
     where
         P: fake::Dummy<fake::Faker> + Into<Payload>,
@@ -373,4 +1099,272 @@ mod tests {
 
         assert_eq!(decoded, signed_message);
     }
+
+    #[test]
+    fn deposit_decision_correlation_id_is_the_outpoint() {
+        let rng = &mut rand::rngs::StdRng::seed_from_u64(7);
+        let decision: SignerDepositDecision = fake::Faker.fake_with_rng(rng);
+        let payload = Payload::from(decision.clone());
+
+        let outpoint = bitcoin::OutPoint::new(decision.txid, decision.output_index);
+        let expected = CorrelationIds(vec![CorrelationId::Deposit(outpoint)]);
+        assert_eq!(payload.correlation_ids(), expected);
+    }
+
+    #[test]
+    fn key_rotation_attestation_verifies_against_the_signer() {
+        let rng = &mut rand::rngs::StdRng::seed_from_u64(11);
+        let private_key = PrivateKey::new(rng);
+        let other_key = PrivateKey::new(rng);
+        let signer_set: BTreeSet<PublicKey> = (0..3)
+            .map(|_| PublicKey::from_private_key(&PrivateKey::new(rng)))
+            .collect();
+        let aggregate_key: PublicKey = fake::Faker.fake_with_rng(rng);
+
+        let attestation = KeyRotationAttestation::new(&private_key, aggregate_key, &signer_set, 2);
+
+        assert!(
+            attestation
+                .verify(&PublicKey::from_private_key(&private_key))
+                .is_ok()
+        );
+        assert!(
+            attestation
+                .verify(&PublicKey::from_private_key(&other_key))
+                .is_err()
+        );
+        assert!(attestation.attests_to(&aggregate_key, &signer_set, 2));
+        assert!(!attestation.attests_to(&aggregate_key, &signer_set, 3));
+
+        let tampered = KeyRotationAttestation {
+            signatures_required: 1,
+            ..attestation
+        };
+        assert!(
+            tampered
+                .verify(&PublicKey::from_private_key(&private_key))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn coordinator_silence_attestation_commits_to_the_chain_tip() {
+        let rng = &mut rand::rngs::StdRng::seed_from_u64(12);
+        let private_key = PrivateKey::new(rng);
+        let chain_tip: BitcoinBlockHash = fake::Faker.fake_with_rng(rng);
+        let other_chain_tip: BitcoinBlockHash = fake::Faker.fake_with_rng(rng);
+        let coordinator: PublicKey = fake::Faker.fake_with_rng(rng);
+
+        let attestation = CoordinatorSilenceAttestation::new(&private_key, &chain_tip, coordinator);
+
+        assert_eq!(
+            attestation.signer_public_key,
+            PublicKey::from_private_key(&private_key)
+        );
+        assert!(attestation.verify(&chain_tip).is_ok());
+        assert!(attestation.verify(&other_chain_tip).is_err());
+
+        let tampered = CoordinatorSilenceAttestation {
+            coordinator_public_key: fake::Faker.fake_with_rng(rng),
+            ..attestation
+        };
+        assert!(tampered.verify(&chain_tip).is_err());
+
+        let tampered = CoordinatorSilenceAttestation {
+            signer_public_key: fake::Faker.fake_with_rng(rng),
+            ..attestation
+        };
+        assert!(tampered.verify(&chain_tip).is_err());
+    }
+
+    #[test]
+    fn full_decision_sync_response_fits_in_a_gossipsub_message() {
+        let rng = &mut rand::rngs::StdRng::seed_from_u64(5);
+        let private_key = PrivateKey::new(rng);
+        let page_size = DecisionSyncRequest::PAGE_SIZE as usize;
+
+        let deposits = (0..page_size)
+            .map(|_| SignerDepositDecision {
+                output_index: u32::MAX,
+                ..fake::Faker.fake_with_rng(rng)
+            })
+            .collect();
+        let withdrawals = (0..page_size)
+            .map(|_| SignerWithdrawalDecision {
+                request_id: u64::MAX,
+                ..fake::Faker.fake_with_rng(rng)
+            })
+            .collect();
+        let response = DecisionSyncResponse {
+            start_height: u64::MAX.into(),
+            end_height: u64::MAX.into(),
+            page: u32::MAX,
+            deposits,
+            withdrawals,
+            has_more: true,
+            requester: PublicKey::from_private_key(&private_key),
+        };
+
+        let encoded = Payload::from(response)
+            .to_message(fake::Faker.fake_with_rng(rng))
+            .sign_ecdsa(&private_key)
+            .encode_to_vec();
+
+        assert!(encoded.len() <= crate::GOSSIPSUB_MAX_TRANSMIT_SIZE);
+    }
+
+    #[test]
+    fn full_sweep_tx_proof_response_fits_in_a_gossipsub_message() {
+        let rng = &mut rand::rngs::StdRng::seed_from_u64(7);
+        let private_key = PrivateKey::new(rng);
+
+        // A block with far more transactions than fit in a real block,
+        // so that the partial merkle tree is as large as it gets.
+        let mut proof: SweepTxProof = fake::Faker.fake_with_rng(rng);
+        let txids: Vec<bitcoin::Txid> = (0..100_000u32)
+            .map(|_| model::BitcoinTxId::from(fake::Faker.fake_with_rng::<[u8; 32], _>(rng)).into())
+            .collect();
+        proof.merkle_block = bitcoin::MerkleBlock::from_header_txids_with_predicate(
+            &proof.merkle_block.header,
+            &txids,
+            |txid| *txid == txids[0],
+        );
+        let header = proof.merkle_block.header;
+        proof.headers = vec![header; SweepTxProofRequest::MAX_HEADERS as usize];
+
+        let response = SweepTxProofResponse {
+            txid: txids[0].into(),
+            proof: Some(proof),
+        };
+        let encoded = Payload::from(response)
+            .to_message(fake::Faker.fake_with_rng(rng))
+            .sign_ecdsa(&private_key)
+            .encode_to_vec();
+
+        assert!(encoded.len() <= crate::GOSSIPSUB_MAX_TRANSMIT_SIZE);
+    }
+
+    #[test]
+    fn sweep_tx_proofs_are_only_valid_for_their_transaction() {
+        let rng = &mut rand::rngs::StdRng::seed_from_u64(8);
+
+        let mut proof: SweepTxProof = fake::Faker.fake_with_rng(rng);
+        let mut matches = Vec::new();
+        proof
+            .merkle_block
+            .extract_matches(&mut matches, &mut Vec::new())
+            .unwrap();
+        let txid = model::BitcoinTxId::from(matches[0]);
+
+        assert!(proof.is_valid_for(&txid));
+        let other_txid: model::BitcoinTxId = fake::Faker.fake_with_rng(rng);
+        assert!(!proof.is_valid_for(&other_txid));
+
+        // Headers must chain onto the block that confirmed the
+        // transaction.
+        let header = proof.merkle_block.header;
+        proof.headers.insert(0, header);
+        assert!(!proof.is_valid_for(&txid));
+    }
+
+    #[test]
+    fn message_deadlines_are_encoded_and_signed() {
+        let rng = &mut rand::rngs::StdRng::seed_from_u64(3);
+        let private_key = PrivateKey::new(rng);
+
+        let deadline = Deadline::after(std::time::Duration::from_secs(30));
+        let message = SignerMessage::random_with_payload_type::<BitcoinPreSignRequest, _>(rng)
+            .with_deadline(deadline);
+        assert!(!message.is_expired());
+
+        let signed_message = message.sign_ecdsa(&private_key);
+        let encoded = signed_message.clone().encode_to_vec();
+        let decoded =
+            Signed::<SignerMessage>::decode(encoded.as_slice()).expect("Failed to decode");
+        assert_eq!(decoded.deadline, Some(deadline));
+        assert!(decoded.verify());
+
+        // Changing the deadline invalidates the signature.
+        let mut tampered = decoded;
+        tampered.inner.deadline = Some(Deadline::from_unix_millis(u64::MAX));
+        assert!(!tampered.verify());
+    }
+
+    #[test]
+    fn message_trace_contexts_are_encoded_and_signed() {
+        let rng = &mut rand::rngs::StdRng::seed_from_u64(4);
+        let private_key = PrivateKey::new(rng);
+
+        let trace_context = TraceContext { trace_id: 1 << 100, span_id: 7 };
+        let mut message = SignerMessage::random_with_payload_type::<BitcoinPreSignRequest, _>(rng);
+        message.trace_context = Some(trace_context);
+
+        let signed_message = message.sign_ecdsa(&private_key);
+        let encoded = signed_message.clone().encode_to_vec();
+        let decoded =
+            Signed::<SignerMessage>::decode(encoded.as_slice()).expect("Failed to decode");
+        assert_eq!(decoded.trace_context, Some(trace_context));
+        assert!(decoded.verify());
+
+        // Changing the trace context invalidates the signature.
+        let mut tampered = decoded;
+        tampered.inner.trace_context = None;
+        assert!(!tampered.verify());
+    }
+
+    #[tokio::test]
+    async fn deadlines_abort_work_that_does_not_complete_in_time() {
+        let passed = Deadline::from_unix_millis(1);
+        assert!(passed.remaining().is_none());
+
+        let result = Deadline::enforce(Some(passed), async { Ok(()) }).await;
+        assert!(matches!(result, Err(Error::DeadlineExceeded)));
+
+        let deadline = Deadline::after(std::time::Duration::from_millis(50));
+        let slow_work = async {
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+            Ok(())
+        };
+        let result = Deadline::enforce(Some(deadline), slow_work).await;
+        assert!(matches!(result, Err(Error::DeadlineExceeded)));
+
+        let result = Deadline::enforce(Some(deadline), async { Ok(1) }).await;
+        assert_eq!(result.unwrap(), 1);
+
+        // Messages without deadlines never expire.
+        let result = Deadline::enforce(None, async { Ok(2) }).await;
+        assert_eq!(result.unwrap(), 2);
+    }
+
+    #[test_case(0, 0, 0 ; "empty batch")]
+    #[test_case(1, 0, 1 ; "one decision")]
+    #[test_case(SignerDecisionBatch::MAX_SIZE, 3, 1 ; "full batch")]
+    #[test_case(SignerDecisionBatch::MAX_SIZE + 1, 3, 2 ; "oversized deposits")]
+    #[test_case(2, 2 * SignerDecisionBatch::MAX_SIZE + 1, 3 ; "oversized withdrawals")]
+    fn decision_batches_split_into_chunks_within_the_max_size(
+        num_deposits: usize,
+        num_withdrawals: usize,
+        expected_chunks: usize,
+    ) {
+        let batch = SignerDecisionBatch {
+            deposits: fake::vec![SignerDepositDecision; num_deposits],
+            withdrawals: fake::vec![SignerWithdrawalDecision; num_withdrawals],
+        };
+
+        let chunks = batch.clone().into_chunks();
+        assert_eq!(chunks.len(), expected_chunks);
+        assert!(chunks.iter().all(|chunk| !chunk.is_oversized()));
+        assert!(chunks.iter().all(|chunk| !chunk.is_empty()));
+
+        let deposits: Vec<_> = chunks
+            .iter()
+            .flat_map(|chunk| chunk.deposits.clone())
+            .collect();
+        let withdrawals: Vec<_> = chunks
+            .iter()
+            .flat_map(|chunk| chunk.withdrawals.clone())
+            .collect();
+        assert_eq!(deposits, batch.deposits);
+        assert_eq!(withdrawals, batch.withdrawals);
+    }
 }
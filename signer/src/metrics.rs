@@ -8,10 +8,16 @@ use metrics_exporter_prometheus::PrometheusBuilder;
 use reqwest::Response;
 
 use crate::block_observer::Deposit;
+use crate::decision_policy::ScreeningTier;
 use crate::error::Error;
+use crate::key_rotation_recovery::KeyRotationState;
 use crate::message::StacksTransactionSignRequest;
+use crate::request_decider::CarriedOverRequests;
 use crate::stacks::api::ClarityName;
 use crate::stacks::contracts::SmartContract;
+use crate::storage::model::BitcoinBackfillProgress;
+use crate::storage::model::RequestCounts;
+use crate::storage::model::SbtcSupplySnapshot;
 use crate::transaction_signer::AcceptedSigHash;
 
 /// The buckets used for metric histograms
@@ -44,6 +50,9 @@ pub enum Metrics {
     /// The total number of tenures that this signer has served as
     /// coordinator.
     CoordinatorTenuresTotal,
+    /// The total number of tenures that this signer has taken over as
+    /// the deputy coordinator after the coordinator went silent.
+    CoordinatorTakeoversTotal,
     /// The total number of sign requests received from the signer.
     SignRequestsTotal,
     /// The amount of time it took to complete a signing round in seconds.
@@ -73,6 +82,111 @@ pub enum Metrics {
     /// The total number of times that a request to read a map entry in a
     /// smart contract has been made to the stacks node.
     ReadMapEntryRequestsTotal,
+    /// The difference, in sats, between the total supply of sBTC reported
+    /// by the sBTC token contract and the supply that we expect given the
+    /// completed deposits and accepted withdrawals in our database.
+    SbtcSupplyDivergence,
+    /// The total number of sBTC supply reconciliations that have been
+    /// run. We use a label to distinguish between those that were within
+    /// the configured tolerance and those that were not.
+    SupplyReconciliationsTotal,
+    /// The total number of sweep transactions where the value of the new
+    /// signers' UTXO was not fully accounted for.
+    SignerUtxoViolationsTotal,
+    /// The total number of broadcast sweep transactions whose fee and
+    /// virtual size were compared with their bitcoin-core mempool entry.
+    /// We use a label to distinguish between those that were within the
+    /// configured tolerance and those that were not.
+    SweepFeeAuditsTotal,
+    /// The number of peers in the current signer set that this signer has
+    /// heard from within the configured heartbeat timeout.
+    LivePeers,
+    /// Whether the signer is in safe-mode, where 1 means that it is and 0
+    /// means that it is not.
+    SafeMode,
+    /// The total number of times that the signer has entered or left
+    /// safe-mode. We use a label to distinguish between the two.
+    SafeModeTransitionsTotal,
+    /// The total number of sbtc-registry events that could not be parsed
+    /// and were stored in the dead-letter table.
+    StacksEventsDeadLetteredTotal,
+    /// The total number of attempts to replay dead-lettered sbtc-registry
+    /// events. We use a label to distinguish between successful and failed
+    /// replays.
+    StacksEventsReplayedTotal,
+    /// Whether governance has paused the sBTC smart contracts, where 1
+    /// means that they are paused and 0 means that they are not.
+    SbtcContractPaused,
+    /// The number of deposit and withdrawal requests in each stage of
+    /// processing.
+    RequestsByStatus,
+    /// The total number of bitcoin block headers from bitcoin-core that
+    /// failed header-chain verification. We use a label to distinguish
+    /// between the reasons for the failure.
+    BitcoinHeaderVerificationFailuresTotal,
+    /// The total number of consensus-critical invariant violations that
+    /// were detected. We use a label to distinguish between the
+    /// invariants.
+    InvariantViolationsTotal,
+    /// The number of bitcoin blocks that remain to be written to the
+    /// database while the block observer catches up to a new chain tip.
+    BitcoinBackfillBlocksRemaining,
+    /// The estimated number of seconds until the block observer catches
+    /// up to a new chain tip.
+    BitcoinBackfillEtaSeconds,
+    /// The total number of discrepancies between the expected behavior of
+    /// the signers and what happened on chain that were detected by the
+    /// watchtower. We use a label to distinguish between the kinds of
+    /// discrepancies.
+    WatchtowerDiscrepanciesTotal,
+    /// The amount of time, in seconds, that the request decider spent
+    /// deciding on pending requests after a bitcoin block.
+    RequestDecisionDurationSeconds,
+    /// The number of pending requests that the request decider did not
+    /// get to within its latency budget after the last bitcoin block, and
+    /// carried over to the next one. We use a label to distinguish
+    /// between deposit and withdrawal requests.
+    RequestDecisionsCarriedOver,
+    /// Whether the database was reachable the last time the database
+    /// monitor checked. This is 1 when reachable and 0 otherwise.
+    DatabaseAvailable,
+    /// The total number of times that the database monitor found the
+    /// database unreachable after it had been reachable, such as when the
+    /// postgres primary fails over.
+    DatabaseOutagesTotal,
+    /// The total number of deposit and withdrawal requests decided by the
+    /// blocklist policy. We use labels to distinguish between the request
+    /// types, the screening tiers and whether the request was accepted.
+    ScreeningDecisionsTotal,
+    /// Whether a step of the signers' most recent key rotation is still
+    /// outstanding, where 1 means that it is and 0 means that it is not.
+    /// We use a label to distinguish between the sweep and the
+    /// rotate-keys contract call.
+    KeyRotationStepPending,
+    /// The total number of bitcoin blocks that the block observer checked
+    /// against their compact block filter before fetching them. We use a
+    /// label to distinguish between blocks whose filter matched, blocks
+    /// that were skipped, and blocks whose filter was unavailable.
+    BitcoinBlockFilterChecksTotal,
+    /// The total number of idempotent database writes that found the row
+    /// already written, such as when an event is delivered twice. We use
+    /// a label to distinguish between the tables.
+    StorageWriteConflictsTotal,
+    /// The total number of WSTS signer state machines that the
+    /// transaction signer evicted from its in-memory cache. We use a label
+    /// to distinguish between those that were spilled to the database and
+    /// those that could not be.
+    WstsStateMachineEvictionsTotal,
+    /// The total number of spilled WSTS signer state machines that the
+    /// transaction signer restored from the database. We use a label to
+    /// distinguish between successful and failed restorations.
+    WstsStateMachineRestorationsTotal,
+    /// The total number of authenticated requests to the sweep
+    /// transaction proof endpoint, and of sweep transaction proof requests
+    /// from peers. We use a label to distinguish between the requests that
+    /// we had a proof for, the ones that we did not, and the ones that
+    /// were rate limited.
+    SweepTxProofRequestsTotal,
 }
 
 impl From<Metrics> for metrics::KeyName {
@@ -275,6 +389,244 @@ impl Metrics {
         )
         .increment(1);
     }
+
+    /// Increment the number of sweep transactions that failed the signer
+    /// UTXO value invariant.
+    pub fn increment_signer_utxo_violations() {
+        metrics::counter!(
+            Metrics::SignerUtxoViolationsTotal,
+            "blockchain" => BITCOIN_BLOCKCHAIN,
+        )
+        .increment(1);
+    }
+
+    /// Increment the number of bitcoin block headers that failed
+    /// header-chain verification.
+    pub fn increment_header_verification_failures(reason: &'static str) {
+        metrics::counter!(
+            Metrics::BitcoinHeaderVerificationFailuresTotal,
+            "blockchain" => BITCOIN_BLOCKCHAIN,
+            "reason" => reason,
+        )
+        .increment(1);
+    }
+
+    /// Increment the number of bitcoin blocks that were checked against
+    /// their compact block filter, with the given outcome.
+    pub fn increment_block_filter_checks(outcome: &'static str) {
+        metrics::counter!(
+            Metrics::BitcoinBlockFilterChecksTotal,
+            "blockchain" => BITCOIN_BLOCKCHAIN,
+            "outcome" => outcome,
+        )
+        .increment(1);
+    }
+
+    /// Increment the number of idempotent writes to the given table that
+    /// found the row already written.
+    pub fn increment_write_conflicts(table: &'static str) {
+        metrics::counter!(Metrics::StorageWriteConflictsTotal, "table" => table).increment(1);
+    }
+
+    /// Increment the number of WSTS signer state machines evicted from the
+    /// in-memory cache, noting whether the evicted state machine was
+    /// spilled to the database.
+    pub fn increment_state_machine_evictions(spilled: bool) {
+        metrics::counter!(
+            Metrics::WstsStateMachineEvictionsTotal,
+            "status" => if spilled { "spilled" } else { "dropped" },
+        )
+        .increment(1);
+    }
+
+    /// Increment the number of spilled WSTS signer state machines that
+    /// were restored from the database.
+    pub fn increment_state_machine_restorations(restored: bool) {
+        metrics::counter!(
+            Metrics::WstsStateMachineRestorationsTotal,
+            "status" => if restored { "success" } else { "failure" },
+        )
+        .increment(1);
+    }
+
+    /// Increment the number of sweep transaction proof requests, from API
+    /// clients or peers, with the given status.
+    pub fn increment_sweep_tx_proof_requests(status: &'static str) {
+        metrics::counter!(Metrics::SweepTxProofRequestsTotal, "status" => status).increment(1);
+    }
+
+    /// Increment the number of violations of the given consensus-critical
+    /// invariant.
+    pub fn increment_invariant_violations(invariant: &'static str) {
+        metrics::counter!(
+            Metrics::InvariantViolationsTotal,
+            "invariant" => invariant,
+        )
+        .increment(1);
+    }
+
+    /// Increment the number of discrepancies of the given kind detected
+    /// by the watchtower.
+    pub fn increment_watchtower_discrepancies(discrepancy: &'static str) {
+        metrics::counter!(
+            Metrics::WatchtowerDiscrepanciesTotal,
+            "discrepancy" => discrepancy,
+        )
+        .increment(1);
+    }
+
+    /// Record the outcome of comparing the assessed fee of a sweep
+    /// transaction with its bitcoin-core mempool entry.
+    pub fn record_sweep_fee_audit(within_tolerance: bool) {
+        metrics::counter!(
+            Metrics::SweepFeeAuditsTotal,
+            "blockchain" => BITCOIN_BLOCKCHAIN,
+            "status" => if within_tolerance { "success" } else { "divergent" },
+        )
+        .increment(1);
+    }
+
+    /// Record the outcome of an sBTC total-supply reconciliation.
+    pub fn record_supply_reconciliation(snapshot: &SbtcSupplySnapshot) {
+        metrics::gauge!(
+            Metrics::SbtcSupplyDivergence,
+            "blockchain" => STACKS_BLOCKCHAIN,
+        )
+        .set(snapshot.divergence() as f64);
+
+        metrics::counter!(
+            Metrics::SupplyReconciliationsTotal,
+            "blockchain" => STACKS_BLOCKCHAIN,
+            "status" => if snapshot.within_tolerance { "success" } else { "divergent" },
+        )
+        .increment(1);
+    }
+
+    /// Record the outcome of a check for whether the signer has been
+    /// partitioned from its peers.
+    pub fn record_partition_check(live_peers: usize, safe_mode: bool) {
+        metrics::gauge!(Metrics::LivePeers).set(live_peers as f64);
+        metrics::gauge!(Metrics::SafeMode).set(if safe_mode { 1.0 } else { 0.0 });
+    }
+
+    /// Increment the counter for the number of times that the signer has
+    /// entered or left safe-mode.
+    pub fn increment_safe_mode_transitions(entered: bool) {
+        metrics::counter!(
+            Metrics::SafeModeTransitionsTotal,
+            "status" => if entered { "entered" } else { "exited" },
+        )
+        .increment(1);
+    }
+
+    /// Record whether the sBTC smart contracts are paused.
+    pub fn record_sbtc_contract_paused(paused: bool) {
+        metrics::gauge!(Metrics::SbtcContractPaused).set(if paused { 1.0 } else { 0.0 });
+    }
+
+    /// Record the number of deposit and withdrawal requests in each stage
+    /// of processing.
+    pub fn record_request_counts(counts: &RequestCounts) {
+        let request_types = [
+            ("deposit", &counts.deposits),
+            ("withdrawal", &counts.withdrawals),
+        ];
+        for (request_type, status_counts) in request_types {
+            for (status, count) in status_counts.by_status() {
+                metrics::gauge!(
+                    Metrics::RequestsByStatus,
+                    "request_type" => request_type,
+                    "status" => status,
+                )
+                .set(count as f64);
+            }
+        }
+    }
+
+    /// Record how long the request decider spent deciding on pending
+    /// requests after a bitcoin block, and how many of the requests it
+    /// carried over to the next one.
+    pub fn record_request_decisions(elapsed: Duration, carried_over: &CarriedOverRequests) {
+        metrics::histogram!(Metrics::RequestDecisionDurationSeconds).record(elapsed);
+
+        let request_types = [
+            ("deposit", carried_over.num_deposits()),
+            ("withdrawal", carried_over.num_withdrawals()),
+        ];
+        for (request_type, count) in request_types {
+            metrics::gauge!(
+                Metrics::RequestDecisionsCarriedOver,
+                "request_type" => request_type,
+            )
+            .set(count as f64);
+        }
+    }
+
+    /// Record whether the database is reachable, counting an outage if it
+    /// has just become unreachable.
+    pub fn record_database_check(available: bool, was_available: bool) {
+        metrics::gauge!(Metrics::DatabaseAvailable).set(if available { 1.0 } else { 0.0 });
+        if was_available && !available {
+            metrics::counter!(Metrics::DatabaseOutagesTotal).increment(1);
+        }
+    }
+
+    /// Increment the counter of requests decided by the blocklist policy
+    /// in the given screening tier.
+    pub fn increment_screening_decisions(
+        request_type: &'static str,
+        tier: ScreeningTier,
+        accepted: bool,
+    ) {
+        let tier: &'static str = tier.into();
+        metrics::counter!(
+            Metrics::ScreeningDecisionsTotal,
+            "request_type" => request_type,
+            "tier" => tier,
+            "status" => if accepted { "accepted" } else { "rejected" },
+        )
+        .increment(1);
+    }
+
+    /// Record which step of the signers' most recent key rotation is still
+    /// outstanding, if any.
+    pub fn record_key_rotation_state(state: &KeyRotationState) {
+        let steps = [
+            (
+                "sweep",
+                matches!(state, KeyRotationState::SweepPending { .. }),
+            ),
+            (
+                "contract_call",
+                matches!(state, KeyRotationState::ContractCallPending { .. }),
+            ),
+        ];
+        for (step, pending) in steps {
+            metrics::gauge!(Metrics::KeyRotationStepPending, "step" => step).set(if pending {
+                1.0
+            } else {
+                0.0
+            });
+        }
+    }
+
+    /// Record the progress of the block observer in catching up to a new
+    /// bitcoin chain tip.
+    pub fn record_bitcoin_backfill_progress(
+        progress: &BitcoinBackfillProgress,
+        eta: Option<Duration>,
+    ) {
+        metrics::gauge!(
+            Metrics::BitcoinBackfillBlocksRemaining,
+            "blockchain" => BITCOIN_BLOCKCHAIN,
+        )
+        .set(progress.blocks_remaining() as f64);
+        metrics::gauge!(
+            Metrics::BitcoinBackfillEtaSeconds,
+            "blockchain" => BITCOIN_BLOCKCHAIN,
+        )
+        .set(eta.unwrap_or_default().as_secs_f64());
+    }
 }
 
 /// Label for bitcoin blockchain based metrics
@@ -0,0 +1,265 @@
+//! Signed address announcements for the signer network's address book.
+//!
+//! Every signer periodically gossips the addresses that it can be reached
+//! at, in a [`PeerAddressAnnouncement`] that is authenticated by the
+//! signature on the enclosing message. The other signers persist the
+//! latest announcement of each signer, so that after a restart they can
+//! dial their peers at the addresses that the peers last announced,
+//! rather than relying on the configured seeds alone, which go stale when
+//! the addresses of signers change.
+
+use std::time::Duration;
+
+use libp2p::Multiaddr;
+use time::OffsetDateTime;
+
+use crate::config::P2PNetworkConfig;
+use crate::keys::PublicKey;
+use crate::message::PeerAddressAnnouncement;
+use crate::network::libp2p::MultiaddrExt as _;
+use crate::storage::model::P2PPeerAddressRecord;
+
+use super::misbehavior::Misbehavior;
+
+/// The maximum number of addresses that a signer may announce.
+pub const MAX_ANNOUNCED_ADDRESSES: usize = 16;
+
+/// How far past the record TTL an announcement may expire before we
+/// consider it invalid, to allow for clock skew between signers.
+const MAX_CLOCK_SKEW: Duration = Duration::from_secs(300);
+
+/// Tracks our own address announcements and validates the announcements
+/// of our peers.
+#[derive(Debug)]
+pub struct AddressBook {
+    record_ttl: Duration,
+    /// The addresses that we last announced, and when we announced them.
+    last_announcement: Option<(Vec<Multiaddr>, OffsetDateTime)>,
+}
+
+impl AddressBook {
+    /// Create a new address book using the record TTL in the given config.
+    pub fn new(config: &P2PNetworkConfig) -> Self {
+        Self {
+            record_ttl: config.address_record_ttl,
+            last_announcement: None,
+        }
+    }
+
+    /// Returns the announcement of our addresses that should be gossiped
+    /// now, if any.
+    ///
+    /// We announce our addresses whenever they change, and re-announce
+    /// them once half of the record TTL has passed so that our peers'
+    /// records of us never expire while we are online.
+    pub fn next_announcement(
+        &mut self,
+        addresses: Vec<Multiaddr>,
+        now: OffsetDateTime,
+    ) -> Option<PeerAddressAnnouncement> {
+        let addresses: Vec<Multiaddr> = addresses
+            .iter()
+            .map(|addr| addr.without_p2p_protocol())
+            .take(MAX_ANNOUNCED_ADDRESSES)
+            .collect();
+
+        if addresses.is_empty() {
+            return None;
+        }
+
+        let is_due = match &self.last_announcement {
+            Some((announced, announced_at)) => {
+                *announced != addresses || now - *announced_at >= self.record_ttl / 2
+            }
+            None => true,
+        };
+        if !is_due {
+            return None;
+        }
+
+        let expires_at = now + self.record_ttl;
+        self.last_announcement = Some((addresses.clone(), now));
+
+        Some(PeerAddressAnnouncement {
+            addresses,
+            expires_at: expires_at.unix_timestamp().max(0).unsigned_abs(),
+        })
+    }
+
+    /// Forget our last announcement, so that our addresses are announced
+    /// again as soon as possible.
+    pub fn reannounce(&mut self) {
+        self.last_announcement = None;
+    }
+
+    /// Validates an announcement made by the signer with the given public
+    /// key, returning the record that should be persisted for it.
+    ///
+    /// Announcements that have already expired, or that do not contain any
+    /// addresses, are ignored. Honest signers never announce more than
+    /// [`MAX_ANNOUNCED_ADDRESSES`] addresses or expire their announcements
+    /// further in the future than the record TTL, so such announcements
+    /// count as malformed payloads.
+    pub fn check_announcement(
+        &self,
+        public_key: PublicKey,
+        announcement: &PeerAddressAnnouncement,
+        now: OffsetDateTime,
+    ) -> Result<Option<P2PPeerAddressRecord>, Misbehavior> {
+        if announcement.addresses.len() > MAX_ANNOUNCED_ADDRESSES {
+            return Err(Misbehavior::MalformedPayload);
+        }
+
+        let expires_at = i64::try_from(announcement.expires_at)
+            .ok()
+            .and_then(|secs| OffsetDateTime::from_unix_timestamp(secs).ok())
+            .ok_or(Misbehavior::MalformedPayload)?;
+
+        if expires_at > now + self.record_ttl + MAX_CLOCK_SKEW {
+            return Err(Misbehavior::MalformedPayload);
+        }
+
+        if expires_at <= now || announcement.addresses.is_empty() {
+            return Ok(None);
+        }
+
+        let addresses = announcement
+            .addresses
+            .iter()
+            .map(|addr| addr.without_p2p_protocol().into())
+            .collect();
+
+        Ok(Some(P2PPeerAddressRecord {
+            public_key,
+            peer_id: libp2p::PeerId::from(public_key).into(),
+            addresses,
+            expires_at: expires_at.into(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fake::Fake as _;
+    use fake::Faker;
+    use libp2p::multiaddr::Protocol;
+
+    use super::*;
+
+    fn address_book() -> AddressBook {
+        AddressBook {
+            record_ttl: Duration::from_secs(3600),
+            last_announcement: None,
+        }
+    }
+
+    fn addr(port: u16) -> Multiaddr {
+        Multiaddr::from(std::net::Ipv4Addr::LOCALHOST).with(Protocol::Tcp(port))
+    }
+
+    #[test]
+    fn announcements_are_made_when_addresses_change_or_get_stale() {
+        let mut book = address_book();
+        let now = OffsetDateTime::now_utc();
+
+        assert!(book.next_announcement(Vec::new(), now).is_none());
+
+        let announcement = book.next_announcement(vec![addr(1)], now).unwrap();
+        assert_eq!(announcement.addresses, vec![addr(1)]);
+        assert_eq!(
+            announcement.expires_at,
+            (now + Duration::from_secs(3600)).unix_timestamp() as u64
+        );
+
+        // Nothing changed, so there is nothing to announce.
+        let later = now + Duration::from_secs(60);
+        assert!(book.next_announcement(vec![addr(1)], later).is_none());
+
+        // Our addresses changed, so we announce them right away.
+        let announcement = book.next_announcement(vec![addr(2)], later).unwrap();
+        assert_eq!(announcement.addresses, vec![addr(2)]);
+
+        // Once half of the TTL has passed we re-announce.
+        let much_later = later + Duration::from_secs(1800);
+        assert!(book.next_announcement(vec![addr(2)], much_later).is_some());
+
+        // Unless we are asked to announce them again right away.
+        assert!(book.next_announcement(vec![addr(2)], much_later).is_none());
+        book.reannounce();
+        assert!(book.next_announcement(vec![addr(2)], much_later).is_some());
+    }
+
+    #[test]
+    fn valid_announcements_are_turned_into_records() {
+        let book = address_book();
+        let now = OffsetDateTime::now_utc();
+        let public_key: PublicKey = Faker.fake();
+        let announcement = PeerAddressAnnouncement {
+            addresses: vec![addr(1), addr(2)],
+            expires_at: (now + Duration::from_secs(600)).unix_timestamp() as u64,
+        };
+
+        let record = book
+            .check_announcement(public_key, &announcement, now)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(record.public_key, public_key);
+        assert_eq!(*record.peer_id, libp2p::PeerId::from(public_key));
+        assert_eq!(record.addresses, vec![addr(1).into(), addr(2).into()]);
+        assert!(record.is_active_at(now));
+    }
+
+    #[test]
+    fn expired_and_empty_announcements_are_ignored() {
+        let book = address_book();
+        let now = OffsetDateTime::now_utc();
+        let public_key: PublicKey = Faker.fake();
+
+        let expired = PeerAddressAnnouncement {
+            addresses: vec![addr(1)],
+            expires_at: (now - Duration::from_secs(1)).unix_timestamp() as u64,
+        };
+        assert_eq!(book.check_announcement(public_key, &expired, now), Ok(None));
+
+        let empty = PeerAddressAnnouncement {
+            addresses: Vec::new(),
+            expires_at: (now + Duration::from_secs(600)).unix_timestamp() as u64,
+        };
+        assert_eq!(book.check_announcement(public_key, &empty, now), Ok(None));
+    }
+
+    #[test]
+    fn oversized_and_far_future_announcements_are_misbehavior() {
+        let book = address_book();
+        let now = OffsetDateTime::now_utc();
+        let public_key: PublicKey = Faker.fake();
+
+        let too_many = PeerAddressAnnouncement {
+            addresses: (0..=MAX_ANNOUNCED_ADDRESSES as u16).map(addr).collect(),
+            expires_at: (now + Duration::from_secs(600)).unix_timestamp() as u64,
+        };
+        assert_eq!(
+            book.check_announcement(public_key, &too_many, now),
+            Err(Misbehavior::MalformedPayload)
+        );
+
+        let far_future = PeerAddressAnnouncement {
+            addresses: vec![addr(1)],
+            expires_at: (now + Duration::from_secs(7200)).unix_timestamp() as u64,
+        };
+        assert_eq!(
+            book.check_announcement(public_key, &far_future, now),
+            Err(Misbehavior::MalformedPayload)
+        );
+
+        let out_of_range = PeerAddressAnnouncement {
+            addresses: vec![addr(1)],
+            expires_at: u64::MAX,
+        };
+        assert_eq!(
+            book.check_announcement(public_key, &out_of_range, now),
+            Err(Misbehavior::MalformedPayload)
+        );
+    }
+}
@@ -5,18 +5,25 @@ use futures::StreamExt as _;
 use libp2p::core::ConnectedPoint;
 use libp2p::kad::RoutingUpdate;
 use libp2p::swarm::SwarmEvent;
-use libp2p::{Swarm, gossipsub, identify, kad, mdns};
+use libp2p::swarm::dial_opts::DialOpts;
+use libp2p::{Multiaddr, PeerId, Swarm, gossipsub, identify, kad, mdns};
+use time::OffsetDateTime;
 use tokio::sync::Mutex;
 
 use crate::codec::Encode as _;
 use crate::context::{Context, P2PEvent, SignerCommand, SignerSignal};
-use crate::error::Error;
+use crate::keys::PublicKey;
+use crate::message::{Payload, PeerAddressAnnouncement};
 use crate::network::Msg;
 use crate::network::libp2p::MultiaddrExt as _;
+use crate::storage::DbRead as _;
 use crate::storage::DbWrite as _;
 
 use super::TOPIC;
+use super::address_book::AddressBook;
+use super::misbehavior::{Misbehavior, PeerScoreboard};
 use super::swarm::{SignerBehavior, SignerBehaviorEvent};
+use super::version;
 
 #[tracing::instrument(skip_all, name = "swarm")]
 pub async fn run(ctx: &impl Context, swarm: Arc<Mutex<Swarm<SignerBehavior>>>) {
@@ -61,6 +68,15 @@ pub async fn run(ctx: &impl Context, swarm: Arc<Mutex<Swarm<SignerBehavior>>>) {
             .inspect_err(|error| tracing::error!(%error, "error signalling event loop start"));
         tracing::debug!("p2p network polling started");
 
+        // Bans are persisted, so peers that were banned before a restart
+        // stay banned until their bans expire.
+        let mut scoreboard = PeerScoreboard::new(&ctx.config().signer.p2p);
+        match ctx.get_storage().get_active_peer_bans().await {
+            Ok(bans) => scoreboard.restore_bans(bans),
+            Err(error) => tracing::warn!(%error, "failed to load peer bans"),
+        }
+        let mut address_book = AddressBook::new(&ctx.config().signer.p2p);
+
         loop {
             // Poll the libp2p swarm for events, waiting for a maximum of 5ms
             // so that we don't starve the outbox.
@@ -71,6 +87,7 @@ pub async fn run(ctx: &impl Context, swarm: Arc<Mutex<Swarm<SignerBehavior>>>) {
             // Handle the event if one was received.
             if let Some(event) = event {
                 let mut swarm = swarm.lock().await;
+                lift_expired_bans(ctx, &mut scoreboard).await;
 
                 match event {
                     // mDNS autodiscovery events. These are used by the local
@@ -81,11 +98,18 @@ pub async fn run(ctx: &impl Context, swarm: Arc<Mutex<Swarm<SignerBehavior>>>) {
                     // Identify protocol events. These are used by the relay to
                     // help determine/verify its own address.
                     SwarmEvent::Behaviour(SignerBehaviorEvent::Identify(event)) => {
-                        handle_identify_event(&mut swarm, ctx, event)
+                        handle_identify_event(&mut swarm, ctx, event).await
                     }
                     // Gossipsub protocol events.
                     SwarmEvent::Behaviour(SignerBehaviorEvent::Gossipsub(event)) => {
-                        handle_gossipsub_event(&mut swarm, ctx, event)
+                        handle_gossipsub_event(
+                            &mut swarm,
+                            ctx,
+                            &mut scoreboard,
+                            &address_book,
+                            event,
+                        )
+                        .await
                     }
                     SwarmEvent::NewListenAddr { address, .. } => {
                         tracing::info!(%address, "listener started");
@@ -111,9 +135,15 @@ pub async fn run(ctx: &impl Context, swarm: Arc<Mutex<Swarm<SignerBehavior>>>) {
                         if !ctx.state().current_signer_set().is_allowed_peer(&peer_id) {
                             tracing::warn!(%connection_id, %peer_id, ?endpoint, "connected to peer, however it is not a known signer; disconnecting");
                             let _ = swarm.disconnect_peer_id(peer_id);
+                        } else if scoreboard.is_banned(&peer_id, OffsetDateTime::now_utc()) {
+                            tracing::debug!(%connection_id, %peer_id, ?endpoint, "connected to banned peer; disconnecting");
+                            let _ = swarm.disconnect_peer_id(peer_id);
                         } else {
                             tracing::debug!(%peer_id, ?endpoint, "connected to peer");
 
+                            // Make sure that the new peer learns our addresses.
+                            address_book.reannounce();
+
                             // Perform operations that are only needed/possible when we are the
                             // dialer and have peer's confirmed dialable address.
                             if let ConnectedPoint::Dialer { address, .. } = endpoint {
@@ -164,6 +194,13 @@ pub async fn run(ctx: &impl Context, swarm: Arc<Mutex<Swarm<SignerBehavior>>>) {
                     }
                     SwarmEvent::Behaviour(SignerBehaviorEvent::Ping(ping)) => {
                         tracing::trace!("ping received: {:?}", ping);
+                        // Successful pings from signers double as
+                        // heartbeats for partition detection.
+                        if ping.result.is_ok()
+                            && ctx.state().current_signer_set().is_allowed_peer(&ping.peer)
+                        {
+                            ctx.state().record_peer_heartbeat(ping.peer);
+                        }
                     }
                     SwarmEvent::OutgoingConnectionError { connection_id, error, peer_id } => {
                         tracing::trace!(%connection_id, %error, ?peer_id, "outgoing connection error");
@@ -239,10 +276,29 @@ pub async fn run(ctx: &impl Context, swarm: Arc<Mutex<Swarm<SignerBehavior>>>) {
                 }
             }
 
+            // Announce our addresses to the other signers if they changed
+            // or our last announcement is getting stale.
+            if let Some(msg) = next_address_announcement(ctx, &swarm, &mut address_book).await {
+                outbox.lock().await.push(msg);
+            }
+
             // Drain the outbox and publish the messages to the network.
             let outbox = outbox.lock().await.drain(..).collect::<Vec<_>>();
             for payload in outbox {
                 let msg_id = payload.id();
+
+                // Nobody is waiting for the responses to a message whose
+                // deadline has passed, so there is no point in sending it.
+                if payload.inner.is_expired() {
+                    tracing::debug!(
+                        message_id = hex::encode(msg_id),
+                        msg = %payload,
+                        "dropping message whose deadline has passed"
+                    );
+                    let _ = signal_tx.send(P2PEvent::PublishFailure(msg_id).into());
+                    continue;
+                }
+
                 tracing::trace!(
                     message_id = hex::encode(msg_id),
                     msg = %payload,
@@ -361,9 +417,9 @@ fn handle_mdns_event(swarm: &mut Swarm<SignerBehavior>, ctx: &impl Context, even
 }
 
 #[tracing::instrument(skip_all, name = "identify")]
-fn handle_identify_event(
-    _swarm: &mut Swarm<SignerBehavior>,
-    _: &impl Context,
+async fn handle_identify_event(
+    swarm: &mut Swarm<SignerBehavior>,
+    ctx: &impl Context,
     event: identify::Event,
 ) {
     use identify::Event;
@@ -371,6 +427,31 @@ fn handle_identify_event(
     match event {
         Event::Received { peer_id, info, .. } => {
             tracing::debug!(%peer_id, ?info, "received identify message from peer");
+            if !ctx.state().current_signer_set().is_allowed_peer(&peer_id) {
+                return;
+            }
+
+            let protocol_version = version::parse_protocol_version(&info.protocol_version);
+            let agent_version = info.agent_version;
+            tracing::info!(%peer_id, %agent_version, protocol_version, "peer advertised versions");
+
+            ctx.state()
+                .record_peer_protocol_version(peer_id, protocol_version);
+            let _ = ctx
+                .get_storage_mut()
+                .update_peer_version(&peer_id, &agent_version, protocol_version)
+                .await
+                .inspect_err(|error| tracing::warn!(%error, "failed to update peer version entry"));
+
+            // Peers that are too old to interoperate with us are
+            // disconnected. Their gossip is also rejected in case it
+            // reaches us through another peer.
+            let minimum = ctx.config().signer.p2p.min_protocol_version;
+            if let Err(error) = version::check_protocol_version(peer_id, protocol_version, minimum)
+            {
+                tracing::error!(%error, %agent_version, "disconnecting from outdated peer");
+                let _ = swarm.disconnect_peer_id(peer_id);
+            }
         }
         Event::Pushed { connection_id, peer_id, info } => {
             tracing::debug!(%connection_id, %peer_id, ?info, "pushed identify message to peer");
@@ -384,10 +465,157 @@ fn handle_identify_event(
     }
 }
 
+/// Lift any peer bans that have expired, removing them from storage.
+async fn lift_expired_bans(ctx: &impl Context, scoreboard: &mut PeerScoreboard) {
+    for peer_id in scoreboard.take_expired_bans(OffsetDateTime::now_utc()) {
+        tracing::info!(%peer_id, "peer ban expired; unbanning peer");
+        let _ = ctx
+            .get_storage_mut()
+            .delete_peer_ban(&peer_id)
+            .await
+            .inspect_err(|error| tracing::warn!(%error, "failed to delete peer ban"));
+    }
+}
+
+/// Record a misbehavior of the given peer, banning and disconnecting the
+/// peer if its misbehavior score reaches the ban threshold.
+async fn penalize_peer(
+    swarm: &mut Swarm<SignerBehavior>,
+    ctx: &impl Context,
+    scoreboard: &mut PeerScoreboard,
+    peer_id: PeerId,
+    misbehavior: Misbehavior,
+) {
+    let Some(ban) = scoreboard.record(peer_id, misbehavior, OffsetDateTime::now_utc()) else {
+        return;
+    };
+
+    tracing::warn!(
+        %peer_id,
+        reason = %ban.reason,
+        score = ban.score,
+        expires_at = %*ban.expires_at,
+        "banning misbehaving peer"
+    );
+    let _ = ctx
+        .get_storage_mut()
+        .write_peer_ban(&ban)
+        .await
+        .inspect_err(|error| tracing::warn!(%error, "failed to persist peer ban"));
+    let _ = swarm.disconnect_peer_id(peer_id);
+}
+
+/// Returns a signed announcement of our addresses if one is due.
+///
+/// We announce the configured public endpoints if there are any, and
+/// otherwise the external addresses that our peers confirmed. Nothing is
+/// announced until we are connected to a peer and know of a bitcoin chain
+/// tip, since the announcement is wrapped in a signer message.
+async fn next_address_announcement(
+    ctx: &impl Context,
+    swarm: &Mutex<Swarm<SignerBehavior>>,
+    address_book: &mut AddressBook,
+) -> Option<Msg> {
+    let chain_tip = ctx.state().bitcoin_chain_tip()?;
+
+    let config = &ctx.config().signer;
+    let signer_set = ctx.state().current_signer_set().get_signers();
+    let supported = ctx.state().signers_support_protocol_version(
+        signer_set.iter().map(|signer| signer.public_key()),
+        &PublicKey::from_private_key(&config.private_key),
+        crate::PEER_ADDRESS_ANNOUNCEMENT_PROTOCOL_VERSION,
+    );
+    if !supported {
+        return None;
+    }
+
+    let addresses: Vec<Multiaddr> = {
+        let swarm = swarm.lock().await;
+        // There is nobody to announce our addresses to.
+        if swarm.connected_peers().next().is_none() {
+            return None;
+        }
+        if config.p2p.public_endpoints.is_empty() {
+            swarm.external_addresses().cloned().collect()
+        } else {
+            config.p2p.public_endpoints.clone()
+        }
+    };
+
+    let announcement = address_book.next_announcement(addresses, OffsetDateTime::now_utc())?;
+    tracing::debug!(addresses = ?announcement.addresses, "announcing our addresses");
+
+    let msg = Payload::from(announcement)
+        .to_message(chain_tip.block_hash)
+        .sign_ecdsa(&config.private_key);
+    Some(msg)
+}
+
+/// Persist a peer's address announcement and make its addresses known to
+/// the swarm, dialing the peer if we are not connected to it.
+async fn handle_address_announcement(
+    swarm: &mut Swarm<SignerBehavior>,
+    ctx: &impl Context,
+    scoreboard: &mut PeerScoreboard,
+    address_book: &AddressBook,
+    public_key: PublicKey,
+    announcement: &PeerAddressAnnouncement,
+) {
+    let peer_id = PeerId::from(public_key);
+    let record = match address_book.check_announcement(
+        public_key,
+        announcement,
+        OffsetDateTime::now_utc(),
+    ) {
+        Ok(Some(record)) => record,
+        Ok(None) => {
+            tracing::debug!(%peer_id, "ignoring expired or empty address announcement");
+            return;
+        }
+        Err(misbehavior) => {
+            tracing::warn!(%peer_id, %misbehavior, "peer sent an invalid address announcement");
+            penalize_peer(swarm, ctx, scoreboard, peer_id, misbehavior).await;
+            return;
+        }
+    };
+
+    let is_newer = ctx
+        .get_storage_mut()
+        .write_peer_address_record(&record)
+        .await
+        .inspect_err(|error| tracing::warn!(%error, "failed to persist peer address record"))
+        .unwrap_or(false);
+
+    // Announcements are re-gossiped periodically, so we only act on the
+    // ones that tell us something new.
+    if !is_newer {
+        return;
+    }
+
+    tracing::debug!(%peer_id, addresses = ?record.addresses, "peer announced its addresses");
+    let addresses: Vec<Multiaddr> = record
+        .addresses
+        .iter()
+        .map(|addr| (**addr).clone())
+        .collect();
+    for address in &addresses {
+        swarm.add_peer_address(peer_id, address.clone());
+    }
+
+    if !swarm.is_connected(&peer_id) {
+        let opts = DialOpts::peer_id(peer_id).addresses(addresses).build();
+        let _ = swarm.dial(opts).inspect_err(
+            |error| tracing::debug!(%peer_id, %error, "failed to dial announced peer"),
+        );
+    }
+}
+
 #[tracing::instrument(skip_all, name = "gossipsub")]
-fn handle_gossipsub_event(
+async fn handle_gossipsub_event(
     swarm: &mut Swarm<SignerBehavior>,
     ctx: &impl Context,
+    scoreboard: &mut PeerScoreboard,
+    address_book: &AddressBook,
     event: gossipsub::Event,
 ) {
     use gossipsub::Event;
@@ -422,36 +650,92 @@ fn handle_gossipsub_event(
                 return;
             }
 
-            Msg::decode_with_digest(&message.data)
-                .and_then(|(msg, digest)| {
-                    tracing::trace!(
-                        local_peer_id = %swarm.local_peer_id(),
-                        %peer_id,
-                        message_id = hex::encode(msg.id()),
-                        %msg,
-                        "received message",
-                    );
+            let now = OffsetDateTime::now_utc();
+            if scoreboard.is_banned(&peer_id, now) || scoreboard.is_banned(&origin_peer_id, now) {
+                tracing::debug!(%peer_id, %origin_peer_id, "ignoring message from banned peer");
+                return;
+            }
 
-                    if origin_peer_id != msg.signer_public_key.into() {
-                        tracing::error!(%origin_peer_id, "connected peer sent an invalid message");
-                        return Err(Error::InvalidSignature)
-                    }
+            ctx.state().record_peer_heartbeat(origin_peer_id);
+
+            // Peers that have not advertised a version yet get the benefit
+            // of the doubt; they are checked once identify completes.
+            if let Some(version) = ctx.state().peer_protocol_version(&origin_peer_id) {
+                let minimum = ctx.config().signer.p2p.min_protocol_version;
+                if let Err(error) =
+                    version::check_protocol_version(origin_peer_id, version, minimum)
+                {
+                    tracing::warn!(%error, "rejecting message from outdated peer");
+                    return;
+                }
+            }
 
-                    if let Err(error) = msg.verify_digest(digest) {
-                        tracing::error!(%origin_peer_id, "connected peer sent an invalid signature");
-                        return Err(error)
-                    }
+            if let Err(misbehavior) = scoreboard.check_rate_limit(origin_peer_id, now) {
+                tracing::warn!(%origin_peer_id, "peer exceeded its message rate limit; dropping message");
+                penalize_peer(swarm, ctx, scoreboard, origin_peer_id, misbehavior).await;
+                return;
+            }
 
-                    let _ = ctx.get_signal_sender()
-                        .send(P2PEvent::MessageReceived(Box::new(msg)).into())
-                        .inspect_err(|error| {
-                            tracing::debug!(%error, "Failed to send message to application; we are likely shutting down.");
-                        });
+            // Signers running a newer protocol version may send payloads
+            // that we do not know about, so messages that we cannot decode
+            // are ignored rather than held against the peer.
+            let (msg, digest) = match Msg::decode_with_digest(&message.data) {
+                Ok(decoded) => decoded,
+                Err(error) => {
+                    tracing::debug!(%peer_id, %error, "failed to decode message; ignoring it");
+                    return;
+                }
+            };
+
+            tracing::trace!(
+                local_peer_id = %swarm.local_peer_id(),
+                %peer_id,
+                message_id = hex::encode(msg.id()),
+                %msg,
+                "received message",
+            );
+
+            if origin_peer_id != msg.signer_public_key.into() {
+                tracing::error!(%origin_peer_id, "connected peer sent an invalid message");
+                let misbehavior = Misbehavior::InvalidSignature;
+                penalize_peer(swarm, ctx, scoreboard, origin_peer_id, misbehavior).await;
+                return;
+            }
+
+            if let Err(error) = msg.verify_digest(digest) {
+                tracing::error!(%origin_peer_id, %error, "connected peer sent an invalid signature");
+                let misbehavior = Misbehavior::InvalidSignature;
+                penalize_peer(swarm, ctx, scoreboard, origin_peer_id, misbehavior).await;
+                return;
+            }
+
+            // Honest peers relay messages that gossipsub has forgotten
+            // about, so we drop replays without penalizing anyone.
+            if scoreboard.is_replay(msg.signer_public_key, msg.id()) {
+                tracing::debug!(%peer_id, %origin_peer_id, "dropping replayed message");
+                return;
+            }
+
+            // Address announcements are only of interest to the network
+            // layer, so they are not passed on to the application.
+            if let Payload::PeerAddressAnnouncement(announcement) = &msg.payload {
+                let public_key = msg.signer_public_key;
+                handle_address_announcement(
+                    swarm,
+                    ctx,
+                    scoreboard,
+                    address_book,
+                    public_key,
+                    announcement,
+                )
+                .await;
+                return;
+            }
 
-                    Ok(())
-                })
-                .unwrap_or_else(|error| {
-                    tracing::warn!(%peer_id, %error, "Failed to decode message");
+            let _ = ctx.get_signal_sender()
+                .send(P2PEvent::MessageReceived(Box::new(msg)).into())
+                .inspect_err(|error| {
+                    tracing::debug!(%error, "Failed to send message to application; we are likely shutting down.");
                 });
         }
         Event::Subscribed { peer_id, topic } => {
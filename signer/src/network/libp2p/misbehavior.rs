@@ -0,0 +1,388 @@
+//! Misbehavior scoring and timed bans for signer network peers.
+//!
+//! Peers accrue points whenever they send us something that an honest
+//! signer never would: messages with invalid signatures, more messages
+//! than the configured rate limit, or signed payloads with contents that
+//! break the protocol rules. Points decay over time, so that the odd
+//! glitch is forgiven, but a peer whose score reaches the configured ban
+//! threshold is banned for the configured ban duration. Bans are
+//! persisted by the caller so that they survive restarts, and they are
+//! lifted automatically once they expire.
+//!
+//! Messages that we have already received, and payloads that we cannot
+//! decode, are dropped without counting against anyone. Honest relays
+//! forward old messages after gossipsub forgets them, and signers running
+//! a newer protocol version send payloads that we do not know about.
+
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::time::Duration;
+
+use libp2p::PeerId;
+use lru::LruCache;
+use time::OffsetDateTime;
+
+use crate::config::P2PNetworkConfig;
+use crate::keys::PublicKey;
+use crate::network::MsgId;
+use crate::storage::model::P2PPeerBan;
+
+/// The amount of time that it takes for a misbehavior score to decay by
+/// one point.
+const SCORE_DECAY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The length of the window over which the per-peer message rate limit
+/// is enforced.
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(1);
+
+/// The number of recently received messages that are remembered for
+/// detecting replays.
+const RECENT_MESSAGES_CAPACITY: NonZeroUsize = NonZeroUsize::new(8192).expect("8192 is non zero");
+
+/// The kinds of peer misbehavior that count towards a ban.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Misbehavior {
+    /// The peer sent a message whose signature does not verify, or that
+    /// was signed by a signer other than the peer that published it.
+    InvalidSignature,
+    /// The peer sent more messages than the configured rate limit allows.
+    /// Gossipsub authenticates the peer that published each message, so
+    /// this is never counted against a peer that only relayed them.
+    RateLimitExceeded,
+    /// The peer sent a message, signed with its own key, whose contents
+    /// break the protocol rules.
+    MalformedPayload,
+}
+
+impl Misbehavior {
+    /// The number of points that the misbehavior adds to a peer's score.
+    ///
+    /// Rate-limit violations are counted per dropped message, so they
+    /// carry a small weight; a peer that floods us is banned quickly
+    /// while a peer that briefly bursts above the limit is not.
+    pub const fn weight(self) -> u32 {
+        match self {
+            Misbehavior::InvalidSignature => 50,
+            Misbehavior::MalformedPayload => 25,
+            Misbehavior::RateLimitExceeded => 1,
+        }
+    }
+
+    /// A short, stable name for the misbehavior, used as the reason of
+    /// persisted bans.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Misbehavior::InvalidSignature => "invalid-signature",
+            Misbehavior::RateLimitExceeded => "rate-limit-exceeded",
+            Misbehavior::MalformedPayload => "malformed-payload",
+        }
+    }
+}
+
+impl std::fmt::Display for Misbehavior {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The misbehavior score of a single peer.
+#[derive(Debug, Clone, Copy)]
+struct PeerScore {
+    /// The score as of `updated_at`.
+    score: u32,
+    /// When the score was last updated.
+    updated_at: OffsetDateTime,
+}
+
+impl PeerScore {
+    /// The score after decaying it up to the given time.
+    fn decayed(&self, now: OffsetDateTime) -> u32 {
+        let elapsed = (now - self.updated_at)
+            .whole_seconds()
+            .max(0)
+            .unsigned_abs();
+        let decay = elapsed / SCORE_DECAY_INTERVAL.as_secs();
+        self.score
+            .saturating_sub(u32::try_from(decay).unwrap_or(u32::MAX))
+    }
+}
+
+/// The number of messages received from a peer in the current rate-limit
+/// window.
+#[derive(Debug, Clone, Copy)]
+struct RateWindow {
+    started_at: OffsetDateTime,
+    count: u32,
+}
+
+/// Tracks the misbehavior scores and bans of our peers.
+#[derive(Debug)]
+pub struct PeerScoreboard {
+    ban_threshold: u32,
+    ban_duration: Duration,
+    max_messages_per_second: u32,
+    scores: HashMap<PeerId, PeerScore>,
+    rates: HashMap<PeerId, RateWindow>,
+    recent_messages: LruCache<(PublicKey, MsgId), ()>,
+    bans: HashMap<PeerId, P2PPeerBan>,
+}
+
+impl PeerScoreboard {
+    /// Create a new scoreboard using the thresholds in the given config.
+    pub fn new(config: &P2PNetworkConfig) -> Self {
+        Self {
+            ban_threshold: config.ban_threshold,
+            ban_duration: config.ban_duration,
+            max_messages_per_second: config.max_messages_per_second,
+            scores: HashMap::new(),
+            rates: HashMap::new(),
+            recent_messages: LruCache::new(RECENT_MESSAGES_CAPACITY),
+            bans: HashMap::new(),
+        }
+    }
+
+    /// Restore bans that were persisted before a restart.
+    pub fn restore_bans(&mut self, bans: impl IntoIterator<Item = P2PPeerBan>) {
+        self.bans
+            .extend(bans.into_iter().map(|ban| (*ban.peer_id, ban)));
+    }
+
+    /// Returns whether the given peer is banned at the given time.
+    pub fn is_banned(&self, peer_id: &PeerId, now: OffsetDateTime) -> bool {
+        self.bans
+            .get(peer_id)
+            .is_some_and(|ban| ban.is_active_at(now))
+    }
+
+    /// Lift all bans that have expired by the given time, returning the
+    /// peers that were unbanned.
+    pub fn take_expired_bans(&mut self, now: OffsetDateTime) -> Vec<PeerId> {
+        let expired: Vec<PeerId> = self
+            .bans
+            .values()
+            .filter(|ban| !ban.is_active_at(now))
+            .map(|ban| *ban.peer_id)
+            .collect();
+
+        for peer_id in &expired {
+            self.bans.remove(peer_id);
+        }
+        expired
+    }
+
+    /// Count a message from the given peer against its rate limit,
+    /// returning an error if the peer has exceeded it.
+    pub fn check_rate_limit(
+        &mut self,
+        peer_id: PeerId,
+        now: OffsetDateTime,
+    ) -> Result<(), Misbehavior> {
+        let window = self
+            .rates
+            .entry(peer_id)
+            .or_insert(RateWindow { started_at: now, count: 0 });
+
+        if now - window.started_at >= RATE_LIMIT_WINDOW {
+            *window = RateWindow { started_at: now, count: 0 };
+        }
+
+        window.count = window.count.saturating_add(1);
+        if window.count > self.max_messages_per_second {
+            return Err(Misbehavior::RateLimitExceeded);
+        }
+        Ok(())
+    }
+
+    /// Remember the message with the given ID signed by the given
+    /// signer, returning whether we have received it recently.
+    ///
+    /// Gossipsub only drops duplicates that arrive within its own
+    /// deduplication window, so an honest peer may still relay a message
+    /// that we have already received. Replays are therefore dropped, but
+    /// they do not count against the peer that relayed them, nor against
+    /// the signer of the message.
+    pub fn is_replay(&mut self, signer_public_key: PublicKey, message_id: MsgId) -> bool {
+        self.recent_messages
+            .put((signer_public_key, message_id), ())
+            .is_some()
+    }
+
+    /// Record a misbehavior of the given peer. If this pushes the peer's
+    /// score to the ban threshold then the peer is banned, its score is
+    /// reset, and the new ban is returned so that it can be persisted.
+    pub fn record(
+        &mut self,
+        peer_id: PeerId,
+        misbehavior: Misbehavior,
+        now: OffsetDateTime,
+    ) -> Option<P2PPeerBan> {
+        let score = self
+            .scores
+            .get(&peer_id)
+            .map_or(0, |score| score.decayed(now))
+            .saturating_add(misbehavior.weight());
+
+        if score < self.ban_threshold {
+            self.scores
+                .insert(peer_id, PeerScore { score, updated_at: now });
+            return None;
+        }
+
+        self.scores.remove(&peer_id);
+        let ban = P2PPeerBan {
+            peer_id: peer_id.into(),
+            reason: misbehavior.to_string(),
+            score,
+            banned_at: now.into(),
+            expires_at: (now + self.ban_duration).into(),
+        };
+        self.bans.insert(peer_id, ban.clone());
+        Some(ban)
+    }
+
+    /// The current misbehavior score of the given peer.
+    #[cfg(test)]
+    fn score(&self, peer_id: &PeerId, now: OffsetDateTime) -> u32 {
+        self.scores
+            .get(peer_id)
+            .map_or(0, |score| score.decayed(now))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fake::Fake as _;
+    use test_case::test_case;
+
+    use super::*;
+
+    fn scoreboard(ban_threshold: u32, max_messages_per_second: u32) -> PeerScoreboard {
+        PeerScoreboard {
+            ban_threshold,
+            ban_duration: Duration::from_secs(3600),
+            max_messages_per_second,
+            scores: HashMap::new(),
+            rates: HashMap::new(),
+            recent_messages: LruCache::new(RECENT_MESSAGES_CAPACITY),
+            bans: HashMap::new(),
+        }
+    }
+
+    #[test_case(Misbehavior::InvalidSignature, 2; "invalid signatures")]
+    #[test_case(Misbehavior::MalformedPayload, 4; "malformed payloads")]
+    #[test_case(Misbehavior::RateLimitExceeded, 100; "rate-limit violations")]
+    fn peers_are_banned_at_the_threshold(misbehavior: Misbehavior, offences: u32) {
+        let mut scoreboard = scoreboard(100, 10);
+        let peer_id = PeerId::random();
+        let now = OffsetDateTime::now_utc();
+
+        for _ in 1..offences {
+            assert!(scoreboard.record(peer_id, misbehavior, now).is_none());
+        }
+        assert!(!scoreboard.is_banned(&peer_id, now));
+
+        let ban = scoreboard.record(peer_id, misbehavior, now).unwrap();
+        assert_eq!(*ban.peer_id, peer_id);
+        assert_eq!(ban.reason, misbehavior.as_str());
+        assert_eq!(ban.score, 100);
+        assert_eq!(*ban.expires_at, now + Duration::from_secs(3600));
+
+        assert!(scoreboard.is_banned(&peer_id, now));
+        assert_eq!(scoreboard.score(&peer_id, now), 0);
+    }
+
+    #[test]
+    fn scores_decay_over_time() {
+        let mut scoreboard = scoreboard(100, 10);
+        let peer_id = PeerId::random();
+        let now = OffsetDateTime::now_utc();
+
+        scoreboard.record(peer_id, Misbehavior::InvalidSignature, now);
+        assert_eq!(scoreboard.score(&peer_id, now), 50);
+
+        let later = now + SCORE_DECAY_INTERVAL * 20;
+        assert_eq!(scoreboard.score(&peer_id, later), 30);
+
+        // The decayed score is what the next offence builds on, so the
+        // peer stays below the threshold.
+        assert!(
+            scoreboard
+                .record(peer_id, Misbehavior::InvalidSignature, later)
+                .is_none()
+        );
+        assert_eq!(scoreboard.score(&peer_id, later), 80);
+
+        let much_later = later + SCORE_DECAY_INTERVAL * 1000;
+        assert_eq!(scoreboard.score(&peer_id, much_later), 0);
+    }
+
+    #[test]
+    fn expired_bans_are_lifted() {
+        let mut scoreboard = scoreboard(1, 10);
+        let peer_id = PeerId::random();
+        let now = OffsetDateTime::now_utc();
+
+        scoreboard
+            .record(peer_id, Misbehavior::MalformedPayload, now)
+            .unwrap();
+        assert!(scoreboard.take_expired_bans(now).is_empty());
+        assert!(scoreboard.is_banned(&peer_id, now));
+
+        let expiry = now + Duration::from_secs(3600);
+        assert!(!scoreboard.is_banned(&peer_id, expiry));
+        assert_eq!(scoreboard.take_expired_bans(expiry), vec![peer_id]);
+        assert!(scoreboard.take_expired_bans(expiry).is_empty());
+    }
+
+    #[test]
+    fn restored_bans_are_enforced() {
+        let mut scoreboard = scoreboard(100, 10);
+        let peer_id = PeerId::random();
+        let now = OffsetDateTime::now_utc();
+
+        scoreboard.restore_bans([P2PPeerBan {
+            peer_id: peer_id.into(),
+            reason: Misbehavior::InvalidSignature.to_string(),
+            score: 100,
+            banned_at: now.into(),
+            expires_at: (now + Duration::from_secs(60)).into(),
+        }]);
+
+        assert!(scoreboard.is_banned(&peer_id, now));
+        assert!(!scoreboard.is_banned(&PeerId::random(), now));
+    }
+
+    #[test]
+    fn rate_limit_resets_every_window() {
+        let mut scoreboard = scoreboard(100, 3);
+        let peer_id = PeerId::random();
+        let other_peer_id = PeerId::random();
+        let now = OffsetDateTime::now_utc();
+
+        for _ in 0..3 {
+            assert!(scoreboard.check_rate_limit(peer_id, now).is_ok());
+        }
+        assert_eq!(
+            scoreboard.check_rate_limit(peer_id, now),
+            Err(Misbehavior::RateLimitExceeded)
+        );
+        // Each peer has its own rate limit.
+        assert!(scoreboard.check_rate_limit(other_peer_id, now).is_ok());
+
+        let next_window = now + RATE_LIMIT_WINDOW;
+        assert!(scoreboard.check_rate_limit(peer_id, next_window).is_ok());
+    }
+
+    #[test]
+    fn repeated_messages_are_replays() {
+        let mut scoreboard = scoreboard(100, 10);
+        let signer: PublicKey = fake::Faker.fake();
+        let other_signer: PublicKey = fake::Faker.fake();
+
+        assert!(!scoreboard.is_replay(signer, [1; 32]));
+        assert!(!scoreboard.is_replay(signer, [2; 32]));
+        assert!(scoreboard.is_replay(signer, [1; 32]));
+        // Replays are tracked for each signer.
+        assert!(!scoreboard.is_replay(other_signer, [1; 32]));
+    }
+}
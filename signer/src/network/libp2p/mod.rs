@@ -4,12 +4,15 @@ use std::sync::LazyLock;
 
 use libp2p::gossipsub::IdentTopic;
 
+mod address_book;
 mod bootstrap;
 mod errors;
 mod event_loop;
+mod misbehavior;
 mod multiaddr;
 mod network;
 mod swarm;
+mod version;
 
 pub use self::errors::SignerSwarmError;
 pub use self::multiaddr::MultiaddrExt;
@@ -19,7 +19,7 @@ use rand::rngs::StdRng;
 use tokio::sync::Mutex;
 
 use super::errors::SignerSwarmError;
-use super::{bootstrap, event_loop};
+use super::{bootstrap, event_loop, version};
 use crate::GOSSIPSUB_MAX_TRANSMIT_SIZE;
 use crate::context::Context;
 use crate::keys::PrivateKey;
@@ -99,10 +99,12 @@ impl SignerBehavior {
             (None.into(), None.into())
         };
 
-        let identify = identify::Behaviour::new(identify::Config::new(
-            identify::PUSH_PROTOCOL_NAME.to_string(),
-            keypair.public(),
-        ));
+        // Peers learn our crate and protocol versions through the identify
+        // protocol, which runs whenever a connection is established.
+        let identify = identify::Behaviour::new(
+            identify::Config::new(version::local_protocol_version(), keypair.public())
+                .with_agent_version(version::local_agent_version()),
+        );
 
         let bootstrap_config = bootstrap::Config::new(local_peer_id)
             .with_initial_delay(config.initial_bootstrap_delay)
@@ -1080,7 +1082,8 @@ mod tests {
 
         let max_votes_against = 3;
         let max_needs_signature = crate::DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX;
-        let bags = compute_optimal_packages(items, max_votes_against, max_needs_signature);
+        let bags =
+            compute_optimal_packages(items, max_votes_against, max_needs_signature, u16::MAX);
         let request_package: Vec<TxRequestIds> = bags
             .map(|bag| TxRequestIds::from(&crate::bitcoin::utxo::Requests::new(bag)))
             .collect();
@@ -1089,6 +1092,8 @@ mod tests {
             request_package: request_package.clone(),
             fee_rate: 0.0,
             last_fees: None,
+            parallel_sweeps: false,
+            magic_bytes: crate::LEGACY_MAGIC_BYTES,
         };
         let packager_presign_size =
             proto::BitcoinPreSignRequest::from(packager_presign).encoded_len();
@@ -1105,11 +1110,15 @@ mod tests {
                 total: u64::MAX,
                 rate: 25.1234567,
             }),
+            parallel_sweeps: true,
+            magic_bytes: crate::LEGACY_MAGIC_BYTES,
         };
 
         let signed = SignerMessage {
             bitcoin_chain_tip: BitcoinBlockHash::from([0xff; 32]),
             payload: Payload::BitcoinPreSignRequest(presign),
+            deadline: None,
+            trace_context: None,
         }
         .sign_ecdsa(&PrivateKey::new(rng));
 
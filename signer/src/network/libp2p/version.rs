@@ -0,0 +1,90 @@
+//! Helpers for advertising and checking signer versions over the libp2p
+//! identify protocol.
+//!
+//! Each signer advertises its crate version as the identify agent version
+//! and its [`PROTOCOL_VERSION`] as the identify protocol version, so that
+//! peers can refuse to talk to signers that are too old to interoperate
+//! with them.
+
+use libp2p::PeerId;
+
+use crate::PROTOCOL_VERSION;
+use crate::VERSION;
+use crate::error::Error;
+
+/// The prefix of the protocol and agent version strings advertised by
+/// signers.
+const VERSION_PREFIX: &str = "sbtc-signer/";
+
+/// The protocol version string that this signer advertises to its peers.
+pub fn local_protocol_version() -> String {
+    format!("{VERSION_PREFIX}{PROTOCOL_VERSION}")
+}
+
+/// The agent version string that this signer advertises to its peers.
+pub fn local_agent_version() -> String {
+    format!("{VERSION_PREFIX}{VERSION}")
+}
+
+/// Parse the signer protocol version out of the protocol version string
+/// advertised by a peer.
+///
+/// Signers that predate version gossip advertise a protocol version
+/// string that does not follow our format, and these are treated as
+/// protocol version 0.
+pub fn parse_protocol_version(protocol_version: &str) -> u32 {
+    protocol_version
+        .strip_prefix(VERSION_PREFIX)
+        .and_then(|version| version.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Check that the given peer's protocol version is at least the given
+/// minimum.
+pub fn check_protocol_version(peer_id: PeerId, version: u32, minimum: u32) -> Result<(), Error> {
+    if version < minimum {
+        return Err(Error::PeerProtocolVersionTooLow {
+            peer_id: Box::new(peer_id),
+            version,
+            minimum,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test]
+    fn local_protocol_version_round_trips() {
+        let version = parse_protocol_version(&local_protocol_version());
+        assert_eq!(version, PROTOCOL_VERSION);
+    }
+
+    #[test_case("sbtc-signer/7", 7; "current format")]
+    #[test_case("/ipfs/id/push/1.0.0", 0; "predates version gossip")]
+    #[test_case("sbtc-signer/latest", 0; "not a number")]
+    #[test_case("", 0; "empty")]
+    fn parsing_protocol_versions(protocol_version: &str, expected: u32) {
+        assert_eq!(parse_protocol_version(protocol_version), expected);
+    }
+
+    #[test]
+    fn outdated_peers_are_rejected() {
+        let peer_id = PeerId::random();
+
+        assert!(check_protocol_version(peer_id, 0, 0).is_ok());
+        assert!(check_protocol_version(peer_id, 2, 1).is_ok());
+        assert!(check_protocol_version(peer_id, 1, 1).is_ok());
+
+        let error = check_protocol_version(peer_id, 1, 2).unwrap_err();
+        assert!(matches!(
+            error,
+            Error::PeerProtocolVersionTooLow { version: 1, minimum: 2, .. }
+        ));
+        assert!(error.to_string().contains(&peer_id.to_string()));
+    }
+}
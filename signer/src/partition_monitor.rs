@@ -0,0 +1,148 @@
+//! This module contains a background task that detects when the signer
+//! has been partitioned from its peers and puts it into safe-mode.
+//!
+//! A signer that can see bitcoin blocks but cannot reach enough of its
+//! peers to reach the signing threshold may still make local decisions
+//! that diverge from the rest of the signers. To avoid this, the signer
+//! keeps track of when it last heard from each of its peers. Whenever
+//! fewer peers than are needed to reach the signing threshold have been
+//! heard from within the configured heartbeat timeout, the signer enters
+//! safe-mode. In safe-mode the signer does not submit decisions,
+//! coordinate or participate in signing rounds. It leaves safe-mode as
+//! soon as connectivity is restored.
+
+use std::time::Duration;
+
+use crate::context::Context;
+use crate::metrics::Metrics;
+
+/// How often the partition monitor checks connectivity with its peers.
+const PARTITION_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A background task that moves the signer in and out of safe-mode based
+/// on whether it can reach enough of its peers.
+pub struct PartitionMonitor<C> {
+    /// Signer context.
+    context: C,
+    /// The maximum amount of time since we last heard from a peer for it
+    /// to be considered reachable.
+    heartbeat_timeout: Duration,
+}
+
+impl<C> PartitionMonitor<C>
+where
+    C: Context,
+{
+    /// Creates a new PartitionMonitor using the heartbeat timeout in the
+    /// signer's config. Returns `None` if partition detection is disabled.
+    pub fn new(context: C) -> Option<Self> {
+        let timeout = context.config().signer.peer_heartbeat_timeout?;
+        let heartbeat_timeout = Duration::from_secs(timeout.get());
+        Some(Self { context, heartbeat_timeout })
+    }
+
+    /// Runs the PartitionMonitor, which checks connectivity with our peers
+    /// every [`PARTITION_CHECK_INTERVAL`] until the signer is shut down.
+    pub async fn run(self) {
+        let mut term = self.context.get_termination_handle();
+        loop {
+            tokio::select! {
+                _ = term.wait_for_shutdown() => {
+                    break;
+                }
+                _ = tokio::time::sleep(PARTITION_CHECK_INTERVAL) => {
+                    self.check();
+                }
+            }
+        }
+        tracing::info!("partition monitor has stopped");
+    }
+
+    /// Check whether we can reach enough peers to reach the signing
+    /// threshold, entering or leaving safe-mode as necessary. Returns
+    /// whether the signer is in safe-mode after the check.
+    pub fn check(&self) -> bool {
+        let state = self.context.state();
+        let signatures_required = state
+            .registry_signer_set_info()
+            .map(|info| info.signatures_required)
+            .unwrap_or(self.context.config().signer.bootstrap_signatures_required);
+
+        // We can always count on ourselves.
+        let live_peers = state.num_live_peers(self.heartbeat_timeout);
+        let reachable_signers = live_peers.saturating_add(1);
+        let partitioned = reachable_signers < usize::from(signatures_required);
+
+        let was_partitioned = state.set_safe_mode(partitioned);
+        Metrics::record_partition_check(live_peers, partitioned);
+
+        match (was_partitioned, partitioned) {
+            (false, true) => {
+                Metrics::increment_safe_mode_transitions(true);
+                tracing::warn!(
+                    %live_peers,
+                    %signatures_required,
+                    "cannot reach enough peers to reach the signing threshold; entering safe-mode"
+                );
+            }
+            (true, false) => {
+                Metrics::increment_safe_mode_transitions(false);
+                tracing::info!(
+                    %live_peers,
+                    %signatures_required,
+                    "connectivity with peers restored; leaving safe-mode"
+                );
+            }
+            _ => {}
+        }
+
+        partitioned
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::num::NonZeroU64;
+
+    use fake::Fake as _;
+    use fake::Faker;
+
+    use crate::keys::PublicKey;
+    use crate::testing::context::*;
+
+    use super::*;
+
+    #[test]
+    fn monitor_is_disabled_by_default() {
+        let ctx = TestContext::default_mocked();
+        assert!(PartitionMonitor::new(ctx).is_none());
+    }
+
+    #[test]
+    fn safe_mode_is_entered_and_left_with_peer_connectivity() {
+        let mut ctx = TestContext::default_mocked();
+        ctx.config_mut().signer.peer_heartbeat_timeout = NonZeroU64::new(60);
+        ctx.config_mut().signer.bootstrap_signatures_required = 2;
+
+        let peers: BTreeSet<PublicKey> = std::iter::repeat_with(|| Faker.fake()).take(3).collect();
+        ctx.state().update_current_signer_set(peers.clone());
+
+        let monitor = PartitionMonitor::new(ctx.clone()).unwrap();
+
+        // We have not heard from anyone, so we cannot reach the threshold.
+        assert!(monitor.check());
+        assert!(ctx.state().is_in_safe_mode());
+
+        // Peers that are not part of the signer set do not count.
+        let stranger: PublicKey = Faker.fake();
+        ctx.state().record_peer_heartbeat(stranger.into());
+        assert!(monitor.check());
+
+        // Once we hear from one of the signers we can reach the threshold.
+        let peer = peers.first().unwrap();
+        ctx.state().record_peer_heartbeat((*peer).into());
+        assert!(!monitor.check());
+        assert!(!ctx.state().is_in_safe_mode());
+    }
+}
@@ -0,0 +1,276 @@
+//! Compatibility shims for decoding messages from signers running the
+//! prior minor version of the protobuf schema.
+//!
+//! During a rolling upgrade the signer set is heterogeneous, and signers
+//! running the new version must still be able to decode the messages
+//! sent by signers that have not upgraded yet, otherwise signing rounds
+//! would stall until every signer is on the same version. The types here
+//! mirror the prior wire format and are only used when a message fails to
+//! decode under the current schema.
+//!
+//! The prior version differs from the current one in the `WstsMessage`
+//! type only: the signing round was identified by a bitcoin transaction ID
+//! in field 1, which has since been replaced by the `id` oneof. All other
+//! messages have the same encoding in both versions.
+
+use prost::Message as _;
+
+use crate::error::Error;
+use crate::proto;
+
+/// The `WstsMessage` type in the prior version of the schema.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct PriorWstsMessage {
+    /// The bitcoin transaction ID of the signing round that this message
+    /// is a part of.
+    #[prost(message, optional, tag = "1")]
+    pub txid: Option<proto::BitcoinTxid>,
+    /// The wsts message
+    #[prost(
+        oneof = "proto::wsts_message::Inner",
+        tags = "2, 3, 4, 5, 6, 7, 8, 9, 10, 11"
+    )]
+    pub inner: Option<proto::wsts_message::Inner>,
+}
+
+/// The `SignerMessage` type in the prior version of the schema.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct PriorSignerMessage {
+    /// The bitcoin chain tip defining the signers view of the blockchain
+    /// at the time the message was created
+    #[prost(message, optional, tag = "1")]
+    pub bitcoin_chain_tip: Option<proto::BitcoinBlockHash>,
+    /// The message payload
+    #[prost(oneof = "PriorPayload", tags = "2, 3, 4, 5, 8, 10, 11")]
+    pub payload: Option<PriorPayload>,
+}
+
+/// The payload of a [`PriorSignerMessage`].
+#[derive(Clone, PartialEq, prost::Oneof)]
+pub enum PriorPayload {
+    /// A decision related to signer deposit
+    #[prost(message, tag = "2")]
+    SignerDepositDecision(proto::SignerDepositDecision),
+    /// A decision related to signer withdrawal
+    #[prost(message, tag = "3")]
+    SignerWithdrawalDecision(proto::SignerWithdrawalDecision),
+    /// A request to sign a Stacks transaction
+    #[prost(message, tag = "4")]
+    StacksTransactionSignRequest(proto::StacksTransactionSignRequest),
+    /// A signature of a Stacks transaction
+    #[prost(message, tag = "5")]
+    StacksTransactionSignature(proto::StacksTransactionSignature),
+    /// Contains all variants for DKG and WSTS signing rounds
+    #[prost(message, tag = "8")]
+    WstsMessage(PriorWstsMessage),
+    /// Information about a new sweep transaction
+    #[prost(message, tag = "10")]
+    BitcoinPreSignRequest(proto::BitcoinPreSignRequest),
+    /// Represents an acknowledgment of a BitcoinPreSignRequest
+    #[prost(message, tag = "11")]
+    BitcoinPreSignAck(proto::BitcoinPreSignAck),
+}
+
+/// The `Signed` type in the prior version of the schema.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct PriorSigned {
+    /// A signature over the hash of the inner structure.
+    #[prost(message, optional, tag = "1")]
+    pub signature: Option<proto::EcdsaSignature>,
+    /// The public key of the signer that generated the signature.
+    #[prost(message, optional, tag = "2")]
+    pub signer_public_key: Option<proto::PublicKey>,
+    /// The signed structure.
+    #[prost(message, optional, tag = "3")]
+    pub signer_message: Option<PriorSignerMessage>,
+}
+
+impl From<PriorWstsMessage> for proto::WstsMessage {
+    fn from(value: PriorWstsMessage) -> Self {
+        proto::WstsMessage {
+            inner: value.inner,
+            id: value.txid.map(proto::wsts_message::Id::Sweep),
+        }
+    }
+}
+
+impl From<PriorPayload> for proto::Payload {
+    fn from(value: PriorPayload) -> Self {
+        match value {
+            PriorPayload::SignerDepositDecision(inner) => {
+                proto::Payload::SignerDepositDecision(inner)
+            }
+            PriorPayload::SignerWithdrawalDecision(inner) => {
+                proto::Payload::SignerWithdrawalDecision(inner)
+            }
+            PriorPayload::StacksTransactionSignRequest(inner) => {
+                proto::Payload::StacksTransactionSignRequest(inner)
+            }
+            PriorPayload::StacksTransactionSignature(inner) => {
+                proto::Payload::StacksTransactionSignature(inner)
+            }
+            PriorPayload::WstsMessage(inner) => proto::Payload::WstsMessage(inner.into()),
+            PriorPayload::BitcoinPreSignRequest(inner) => {
+                proto::Payload::BitcoinPreSignRequest(inner)
+            }
+            PriorPayload::BitcoinPreSignAck(inner) => proto::Payload::BitcoinPreSignAck(inner),
+        }
+    }
+}
+
+impl From<PriorSignerMessage> for proto::SignerMessage {
+    fn from(value: PriorSignerMessage) -> Self {
+        proto::SignerMessage {
+            bitcoin_chain_tip: value.bitcoin_chain_tip,
+            payload: value.payload.map(Into::into),
+            deadline: 0,
+            trace_parent: String::new(),
+        }
+    }
+}
+
+impl From<PriorSigned> for proto::Signed {
+    fn from(value: PriorSigned) -> Self {
+        proto::Signed {
+            signature: value.signature,
+            signer_public_key: value.signer_public_key,
+            signer_message: value.signer_message.map(Into::into),
+        }
+    }
+}
+
+/// Decode bytes encoded with the prior version of the schema, upgrading
+/// the result to the current protobuf types.
+///
+/// The upgraded message re-encodes to different bytes than the ones given
+/// here, so the digest that was signed must be computed from the given
+/// bytes rather than from the returned message.
+pub fn decode_prior_version(data: &[u8]) -> Result<proto::Signed, Error> {
+    PriorSigned::decode(data)
+        .map(proto::Signed::from)
+        .map_err(Error::DecodeProtobuf)
+}
+
+#[cfg(test)]
+mod tests {
+    use fake::Fake as _;
+    use fake::Faker;
+    use prost::Message as _;
+    use sha2::Digest as _;
+    use test_case::test_case;
+
+    use crate::codec::Encode as _;
+    use crate::codec::ProtoSerializable as _;
+    use crate::ecdsa::Signed;
+    use crate::keys::PrivateKey;
+    use crate::keys::PublicKey;
+    use crate::message::Payload;
+    use crate::message::SignerMessage;
+    use crate::message::WstsMessage;
+    use crate::message::WstsMessageId;
+    use crate::storage::model::BitcoinBlockHash;
+    use crate::storage::model::BitcoinTxId;
+    use crate::testing::dummy::Unit;
+    use crate::testing::get_rng;
+
+    use super::*;
+
+    /// The golden files were encoded and signed by the current version of
+    /// the schema and must never be regenerated. If a change makes one of
+    /// these tests fail then signers running the new code cannot talk to
+    /// signers running the old code, and the change needs a compatibility
+    /// shim in this module.
+    #[test_case(include_str!("../../tests/fixtures/codec/signer-deposit-decision.hex"), "SBTC_SIGNER_DEPOSIT_DECISION"; "SignerDepositDecision")]
+    #[test_case(include_str!("../../tests/fixtures/codec/signer-withdrawal-decision.hex"), "SBTC_SIGNER_WITHDRAWAL_DECISION"; "SignerWithdrawalDecision")]
+    #[test_case(include_str!("../../tests/fixtures/codec/stacks-transaction-sign-request.hex"), "SBTC_STACKS_TRANSACTION_SIGN_REQUEST"; "StacksTransactionSignRequest")]
+    #[test_case(include_str!("../../tests/fixtures/codec/stacks-transaction-signature.hex"), "SBTC_STACKS_TRANSACTION_SIGNATURE"; "StacksTransactionSignature")]
+    #[test_case(include_str!("../../tests/fixtures/codec/bitcoin-pre-sign-request.hex"), "SBTC_BITCOIN_PRE_SIGN_REQUEST"; "BitcoinPreSignRequest")]
+    #[test_case(include_str!("../../tests/fixtures/codec/bitcoin-pre-sign-ack.hex"), "SBTC_BITCOIN_PRE_SIGN_ACK"; "BitcoinPreSignAck")]
+    #[test_case(include_str!("../../tests/fixtures/codec/wsts-dkg-begin.hex"), "SBTC_WSTS_MESSAGE"; "DkgBegin")]
+    #[test_case(include_str!("../../tests/fixtures/codec/wsts-dkg-public-shares.hex"), "SBTC_WSTS_MESSAGE"; "DkgPublicShares")]
+    #[test_case(include_str!("../../tests/fixtures/codec/wsts-dkg-private-begin.hex"), "SBTC_WSTS_MESSAGE"; "DkgPrivateBegin")]
+    #[test_case(include_str!("../../tests/fixtures/codec/wsts-dkg-private-shares.hex"), "SBTC_WSTS_MESSAGE"; "DkgPrivateShares")]
+    #[test_case(include_str!("../../tests/fixtures/codec/wsts-dkg-end-begin.hex"), "SBTC_WSTS_MESSAGE"; "DkgEndBegin")]
+    #[test_case(include_str!("../../tests/fixtures/codec/wsts-dkg-end.hex"), "SBTC_WSTS_MESSAGE"; "DkgEnd")]
+    #[test_case(include_str!("../../tests/fixtures/codec/wsts-nonce-request.hex"), "SBTC_WSTS_MESSAGE"; "NonceRequest")]
+    #[test_case(include_str!("../../tests/fixtures/codec/wsts-nonce-response.hex"), "SBTC_WSTS_MESSAGE"; "NonceResponse")]
+    #[test_case(include_str!("../../tests/fixtures/codec/wsts-signature-share-request.hex"), "SBTC_WSTS_MESSAGE"; "SignatureShareRequest")]
+    #[test_case(include_str!("../../tests/fixtures/codec/wsts-signature-share-response.hex"), "SBTC_WSTS_MESSAGE"; "SignatureShareResponse")]
+    fn golden_messages_decode_and_reencode(golden: &str, type_tag: &str) {
+        let data = hex::decode(golden.trim()).unwrap();
+
+        let (msg, digest) = Signed::<SignerMessage>::decode_with_digest(&data).unwrap();
+        assert_eq!(msg.type_tag(), type_tag);
+        msg.verify_digest(digest).unwrap();
+
+        // The digest that we compute ourselves must match the one over the
+        // bytes on the wire, and re-encoding must give back those bytes.
+        assert_eq!(msg.id(), digest);
+        assert_eq!(msg.encode_to_vec(), data);
+    }
+
+    #[test]
+    fn prior_version_golden_wsts_message_decodes() {
+        let golden = include_str!("../../tests/fixtures/codec/prior-wsts-nonce-request.hex");
+        let data = hex::decode(golden.trim()).unwrap();
+
+        let (msg, digest) = Signed::<SignerMessage>::decode_with_digest(&data).unwrap();
+        msg.verify_digest(digest).unwrap();
+
+        let Payload::WstsMessage(wsts_msg) = &msg.payload else {
+            panic!("expected a WSTS message, got {:?}", msg.payload);
+        };
+        let txid: [u8; 32] =
+            hex::decode("381e8481cab985500ee966cb99cf742f91666739320bbc0c2996f32e99931632")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        assert_eq!(wsts_msg.id, WstsMessageId::from(BitcoinTxId::from(txid)));
+        assert!(matches!(
+            wsts_msg.inner,
+            wsts::net::Message::NonceRequest(_)
+        ));
+    }
+
+    #[test]
+    fn prior_version_messages_are_upgraded() {
+        let mut rng = get_rng();
+        let private_key = PrivateKey::new(&mut rng);
+        let public_key = PublicKey::from_private_key(&private_key);
+        let txid: BitcoinTxId = Faker.fake_with_rng(&mut rng);
+
+        let wsts_msg = WstsMessage {
+            id: txid.into(),
+            inner: wsts::net::Message::NonceRequest(Unit.fake_with_rng(&mut rng)),
+        };
+        let signer_message = PriorSignerMessage {
+            bitcoin_chain_tip: Some(BitcoinBlockHash::from([1; 32]).into()),
+            payload: Some(PriorPayload::WstsMessage(PriorWstsMessage {
+                txid: Some(txid.into()),
+                inner: proto::WstsMessage::from(wsts_msg.clone()).inner,
+            })),
+        };
+        let mut signed = PriorSigned {
+            signature: None,
+            signer_public_key: Some(public_key.into()),
+            signer_message: Some(signer_message),
+        };
+
+        // Signers on the prior version sign over the same bytes as we do,
+        // everything after the signature field.
+        let mut hasher = sha2::Sha256::new_with_prefix("SBTC_WSTS_MESSAGE");
+        hasher.update(signed.encode_to_vec());
+        let digest = secp256k1::Message::from_digest(hasher.finalize().into());
+        signed.signature = Some(private_key.sign_ecdsa(&digest).into());
+        let data = signed.encode_to_vec();
+
+        // The current schema alone cannot make sense of these bytes.
+        let message = proto::Signed::decode(data.as_slice()).unwrap();
+        assert!(Signed::<SignerMessage>::try_from(message).is_err());
+
+        let (msg, digest) = Signed::<SignerMessage>::decode_with_digest(&data).unwrap();
+        msg.verify_digest(digest).unwrap();
+        assert_eq!(msg.signer_public_key, public_key);
+        assert_eq!(msg.payload, Payload::WstsMessage(wsts_msg));
+    }
+}
@@ -16,6 +16,7 @@ use clarity::vm::types::PrincipalData;
 use p256k1::point::Point;
 use p256k1::scalar::Scalar;
 use polynomial::Polynomial;
+use sbtc::ids::DepositId;
 use secp256k1::ecdsa::RecoverableSignature;
 use stacks_common::types::chainstate::StacksAddress;
 use wsts::common::PolyCommitment;
@@ -36,8 +37,12 @@ use wsts::net::NonceResponse;
 use wsts::net::SignatureShareRequest;
 use wsts::net::SignatureShareResponse;
 use wsts::net::SignatureType;
+use wsts::state_machine::PublicKeys;
+use wsts::state_machine::signer::Signer;
+use wsts::state_machine::signer::State as WstsSignerState;
 use wsts::traits::PartyState;
 use wsts::traits::SignerState;
+use wsts::v2;
 
 use crate::bitcoin::utxo::Fees;
 use crate::bitcoin::validation::TxRequestIds;
@@ -47,12 +52,25 @@ use crate::error::Error;
 use crate::keys::PublicKey;
 use crate::message::BitcoinPreSignAck;
 use crate::message::BitcoinPreSignRequest;
+use crate::message::CoordinatorSilenceAttestation;
+use crate::message::CoordinatorTakeover;
+use crate::message::Deadline;
+use crate::message::DecisionSyncRequest;
+use crate::message::DecisionSyncResponse;
+use crate::message::KeyRotationAttestation;
 use crate::message::Payload;
+use crate::message::PeerAddressAnnouncement;
+use crate::message::RotateKeysDryRunRequest;
+use crate::message::RotateKeysDryRunVerdict;
+use crate::message::SignerDecisionBatch;
 use crate::message::SignerDepositDecision;
 use crate::message::SignerMessage;
 use crate::message::SignerWithdrawalDecision;
 use crate::message::StacksTransactionSignRequest;
 use crate::message::StacksTransactionSignature;
+use crate::message::SweepTxProof;
+use crate::message::SweepTxProofRequest;
+use crate::message::SweepTxProofResponse;
 use crate::message::WstsMessage;
 use crate::message::WstsMessageId;
 use crate::proto;
@@ -64,6 +82,7 @@ use crate::stacks::contracts::RotateKeysV1;
 use crate::stacks::contracts::SmartContract;
 use crate::stacks::contracts::StacksTx;
 use crate::storage::model::BitcoinBlockHash;
+use crate::storage::model::BitcoinBlockRef;
 use crate::storage::model::BitcoinTxId;
 use crate::storage::model::QualifiedRequestId;
 use crate::storage::model::StacksBlockHash;
@@ -266,6 +285,19 @@ impl TryFrom<proto::OutPoint> for bitcoin::OutPoint {
     }
 }
 
+impl From<DepositId> for proto::OutPoint {
+    fn from(value: DepositId) -> Self {
+        proto::OutPoint::from(value.outpoint())
+    }
+}
+
+impl TryFrom<proto::OutPoint> for DepositId {
+    type Error = Error;
+    fn try_from(value: proto::OutPoint) -> Result<Self, Self::Error> {
+        bitcoin::OutPoint::try_from(value).map(DepositId::from)
+    }
+}
+
 impl From<StacksTxId> for proto::StacksTxid {
     fn from(value: StacksTxId) -> Self {
         proto::StacksTxid {
@@ -380,6 +412,226 @@ impl TryFrom<proto::SignerWithdrawalDecision> for SignerWithdrawalDecision {
     }
 }
 
+impl From<DecisionSyncRequest> for proto::DecisionSyncRequest {
+    fn from(value: DecisionSyncRequest) -> Self {
+        proto::DecisionSyncRequest {
+            start_height: *value.start_height,
+            end_height: *value.end_height,
+        }
+    }
+}
+
+impl From<proto::DecisionSyncRequest> for DecisionSyncRequest {
+    fn from(value: proto::DecisionSyncRequest) -> Self {
+        DecisionSyncRequest {
+            start_height: value.start_height.into(),
+            end_height: value.end_height.into(),
+        }
+    }
+}
+
+impl From<DecisionSyncResponse> for proto::DecisionSyncResponse {
+    fn from(value: DecisionSyncResponse) -> Self {
+        proto::DecisionSyncResponse {
+            start_height: *value.start_height,
+            end_height: *value.end_height,
+            page: value.page,
+            deposits: value.deposits.into_iter().map(|v| v.into()).collect(),
+            withdrawals: value.withdrawals.into_iter().map(|v| v.into()).collect(),
+            has_more: value.has_more,
+            requester: Some(value.requester.into()),
+        }
+    }
+}
+
+impl TryFrom<proto::DecisionSyncResponse> for DecisionSyncResponse {
+    type Error = Error;
+    fn try_from(value: proto::DecisionSyncResponse) -> Result<Self, Self::Error> {
+        Ok(DecisionSyncResponse {
+            start_height: value.start_height.into(),
+            end_height: value.end_height.into(),
+            page: value.page,
+            deposits: value
+                .deposits
+                .into_iter()
+                .map(|v| v.try_into())
+                .collect::<Result<Vec<_>, _>>()?,
+            withdrawals: value
+                .withdrawals
+                .into_iter()
+                .map(|v| v.try_into())
+                .collect::<Result<Vec<_>, _>>()?,
+            has_more: value.has_more,
+            requester: value.requester.required()?.try_into()?,
+        })
+    }
+}
+
+impl From<CoordinatorTakeover> for proto::CoordinatorTakeover {
+    fn from(value: CoordinatorTakeover) -> Self {
+        proto::CoordinatorTakeover {
+            coordinator_public_key: Some(value.coordinator_public_key.into()),
+            attestations: value.attestations.into_iter().map(|v| v.into()).collect(),
+        }
+    }
+}
+
+impl TryFrom<proto::CoordinatorTakeover> for CoordinatorTakeover {
+    type Error = Error;
+    fn try_from(value: proto::CoordinatorTakeover) -> Result<Self, Self::Error> {
+        Ok(CoordinatorTakeover {
+            coordinator_public_key: value.coordinator_public_key.required()?.try_into()?,
+            attestations: value
+                .attestations
+                .into_iter()
+                .map(|v| v.try_into())
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<CoordinatorSilenceAttestation> for proto::CoordinatorSilenceAttestation {
+    fn from(value: CoordinatorSilenceAttestation) -> Self {
+        proto::CoordinatorSilenceAttestation {
+            signer_public_key: Some(value.signer_public_key.into()),
+            coordinator_public_key: Some(value.coordinator_public_key.into()),
+            signature: Some(value.signature.into()),
+        }
+    }
+}
+
+impl TryFrom<proto::CoordinatorSilenceAttestation> for CoordinatorSilenceAttestation {
+    type Error = Error;
+    fn try_from(value: proto::CoordinatorSilenceAttestation) -> Result<Self, Self::Error> {
+        Ok(CoordinatorSilenceAttestation {
+            signer_public_key: value.signer_public_key.required()?.try_into()?,
+            coordinator_public_key: value.coordinator_public_key.required()?.try_into()?,
+            signature: value.signature.required()?.try_into()?,
+        })
+    }
+}
+
+impl From<PeerAddressAnnouncement> for proto::PeerAddressAnnouncement {
+    fn from(value: PeerAddressAnnouncement) -> Self {
+        proto::PeerAddressAnnouncement {
+            addresses: value
+                .addresses
+                .into_iter()
+                .map(|addr| addr.to_vec())
+                .collect(),
+            expires_at: value.expires_at,
+        }
+    }
+}
+
+impl TryFrom<proto::PeerAddressAnnouncement> for PeerAddressAnnouncement {
+    type Error = Error;
+    fn try_from(value: proto::PeerAddressAnnouncement) -> Result<Self, Self::Error> {
+        let addresses = value
+            .addresses
+            .into_iter()
+            .map(|addr| libp2p::Multiaddr::try_from(addr).map_err(|_| Error::TypeConversion))
+            .collect::<Result<_, _>>()?;
+
+        Ok(PeerAddressAnnouncement {
+            addresses,
+            expires_at: value.expires_at,
+        })
+    }
+}
+
+impl From<SweepTxProofRequest> for proto::SweepTxProofRequest {
+    fn from(value: SweepTxProofRequest) -> Self {
+        proto::SweepTxProofRequest { txid: Some(value.txid.into()) }
+    }
+}
+
+impl TryFrom<proto::SweepTxProofRequest> for SweepTxProofRequest {
+    type Error = Error;
+    fn try_from(value: proto::SweepTxProofRequest) -> Result<Self, Self::Error> {
+        Ok(SweepTxProofRequest {
+            txid: value.txid.required()?.try_into()?,
+        })
+    }
+}
+
+impl From<SweepTxProofResponse> for proto::SweepTxProofResponse {
+    fn from(value: SweepTxProofResponse) -> Self {
+        proto::SweepTxProofResponse {
+            txid: Some(value.txid.into()),
+            proof: value.proof.map(|v| v.into()),
+        }
+    }
+}
+
+impl TryFrom<proto::SweepTxProofResponse> for SweepTxProofResponse {
+    type Error = Error;
+    fn try_from(value: proto::SweepTxProofResponse) -> Result<Self, Self::Error> {
+        Ok(SweepTxProofResponse {
+            txid: value.txid.required()?.try_into()?,
+            proof: value.proof.map(|v| v.try_into()).transpose()?,
+        })
+    }
+}
+
+impl From<SweepTxProof> for proto::SweepTxProof {
+    fn from(value: SweepTxProof) -> Self {
+        proto::SweepTxProof {
+            block_height: *value.block_height,
+            merkle_block: bitcoin::consensus::serialize(&value.merkle_block),
+            headers: value
+                .headers
+                .iter()
+                .map(bitcoin::consensus::serialize)
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<proto::SweepTxProof> for SweepTxProof {
+    type Error = Error;
+    fn try_from(value: proto::SweepTxProof) -> Result<Self, Self::Error> {
+        Ok(SweepTxProof {
+            block_height: value.block_height.into(),
+            merkle_block: bitcoin::consensus::deserialize(&value.merkle_block)
+                .map_err(Error::DecodeBitcoinMerkleBlock)?,
+            headers: value
+                .headers
+                .iter()
+                .map(|header| bitcoin::consensus::deserialize(header))
+                .collect::<Result<_, _>>()
+                .map_err(Error::DecodeBitcoinBlockHeader)?,
+        })
+    }
+}
+
+impl From<SignerDecisionBatch> for proto::SignerDecisionBatch {
+    fn from(value: SignerDecisionBatch) -> Self {
+        proto::SignerDecisionBatch {
+            deposits: value.deposits.into_iter().map(|v| v.into()).collect(),
+            withdrawals: value.withdrawals.into_iter().map(|v| v.into()).collect(),
+        }
+    }
+}
+
+impl TryFrom<proto::SignerDecisionBatch> for SignerDecisionBatch {
+    type Error = Error;
+    fn try_from(value: proto::SignerDecisionBatch) -> Result<Self, Self::Error> {
+        Ok(SignerDecisionBatch {
+            deposits: value
+                .deposits
+                .into_iter()
+                .map(|v| v.try_into())
+                .collect::<Result<Vec<_>, _>>()?,
+            withdrawals: value
+                .withdrawals
+                .into_iter()
+                .map(|v| v.try_into())
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+}
+
 impl From<CompleteDepositV1> for proto::CompleteDeposit {
     fn from(value: CompleteDepositV1) -> Self {
         proto::CompleteDeposit {
@@ -489,6 +741,44 @@ impl TryFrom<proto::RotateKeys> for RotateKeysV1 {
     }
 }
 
+impl From<RotateKeysDryRunRequest> for proto::RotateKeysDryRunRequest {
+    fn from(value: RotateKeysDryRunRequest) -> Self {
+        proto::RotateKeysDryRunRequest {
+            id: value.id,
+            rotate_keys: Some(value.rotate_keys.into()),
+        }
+    }
+}
+
+impl TryFrom<proto::RotateKeysDryRunRequest> for RotateKeysDryRunRequest {
+    type Error = Error;
+    fn try_from(value: proto::RotateKeysDryRunRequest) -> Result<Self, Self::Error> {
+        Ok(RotateKeysDryRunRequest {
+            id: value.id,
+            rotate_keys: value.rotate_keys.required()?.try_into()?,
+        })
+    }
+}
+
+impl From<RotateKeysDryRunVerdict> for proto::RotateKeysDryRunVerdict {
+    fn from(value: RotateKeysDryRunVerdict) -> Self {
+        proto::RotateKeysDryRunVerdict {
+            id: value.id,
+            rejection_reason: value.rejection_reason.unwrap_or_default(),
+        }
+    }
+}
+
+impl TryFrom<proto::RotateKeysDryRunVerdict> for RotateKeysDryRunVerdict {
+    type Error = Error;
+    fn try_from(value: proto::RotateKeysDryRunVerdict) -> Result<Self, Self::Error> {
+        Ok(RotateKeysDryRunVerdict {
+            id: value.id,
+            rejection_reason: Some(value.rejection_reason).filter(|reason| !reason.is_empty()),
+        })
+    }
+}
+
 impl From<SmartContract> for proto::SmartContract {
     fn from(value: SmartContract) -> Self {
         match value {
@@ -1153,6 +1443,32 @@ impl TryFrom<proto::StacksTransactionSignature> for StacksTransactionSignature {
     }
 }
 
+impl From<KeyRotationAttestation> for proto::KeyRotationAttestation {
+    fn from(value: KeyRotationAttestation) -> Self {
+        proto::KeyRotationAttestation {
+            aggregate_key: Some(value.aggregate_key.into()),
+            signer_set_hash: Some(value.signer_set_hash.into()),
+            signatures_required: value.signatures_required.into(),
+            signature: Some(value.signature.into()),
+        }
+    }
+}
+
+impl TryFrom<proto::KeyRotationAttestation> for KeyRotationAttestation {
+    type Error = Error;
+    fn try_from(value: proto::KeyRotationAttestation) -> Result<Self, Self::Error> {
+        Ok(KeyRotationAttestation {
+            aggregate_key: value.aggregate_key.required()?.try_into()?,
+            signer_set_hash: value.signer_set_hash.required()?.into(),
+            signatures_required: value
+                .signatures_required
+                .try_into()
+                .map_err(|_| Error::TypeConversion)?,
+            signature: value.signature.required()?.try_into()?,
+        })
+    }
+}
+
 impl From<QualifiedRequestId> for proto::QualifiedRequestId {
     fn from(value: QualifiedRequestId) -> Self {
         proto::QualifiedRequestId {
@@ -1240,6 +1556,8 @@ impl From<BitcoinPreSignRequest> for proto::BitcoinPreSignRequest {
             // We compute the last fees ourselves. In the next release,
             // there will be no need to require the sender include them.
             last_fees: value.last_fees,
+            parallel_sweeps: value.parallel_sweeps,
+            magic_bytes: value.magic_bytes.to_vec(),
         }
     }
 }
@@ -1258,6 +1576,13 @@ impl TryFrom<proto::BitcoinPreSignRequest> for BitcoinPreSignRequest {
             // there will be no need to require the sender include them,
             // and we can then remove this field.
             last_fees: value.last_fees,
+            parallel_sweeps: value.parallel_sweeps,
+            // Coordinators that predate the magic bytes field always
+            // used the legacy magic bytes.
+            magic_bytes: match value.magic_bytes.as_slice() {
+                [] => crate::LEGACY_MAGIC_BYTES,
+                bytes => bytes.try_into().map_err(|_| Error::TypeConversion)?,
+            },
         })
     }
 }
@@ -1279,6 +1604,14 @@ impl From<SignerMessage> for proto::SignerMessage {
         proto::SignerMessage {
             bitcoin_chain_tip: Some(value.bitcoin_chain_tip.into()),
             payload: Some(value.payload.into()),
+            deadline: value
+                .deadline
+                .map(|deadline| deadline.as_unix_millis())
+                .unwrap_or_default(),
+            trace_parent: value
+                .trace_context
+                .map(|context| context.to_string())
+                .unwrap_or_default(),
         }
     }
 }
@@ -1289,6 +1622,10 @@ impl TryFrom<proto::SignerMessage> for SignerMessage {
         Ok(SignerMessage {
             bitcoin_chain_tip: value.bitcoin_chain_tip.required()?.try_into()?,
             payload: value.payload.required()?.try_into()?,
+            deadline: (value.deadline != 0).then(|| Deadline::from_unix_millis(value.deadline)),
+            trace_context: (!value.trace_parent.is_empty())
+                .then(|| value.trace_parent.parse())
+                .transpose()?,
         })
     }
 }
@@ -1317,6 +1654,39 @@ impl From<Payload> for proto::Payload {
             Payload::BitcoinPreSignAck(inner) => {
                 proto::signer_message::Payload::BitcoinPreSignAck(inner.into())
             }
+            Payload::KeyRotationAttestation(inner) => {
+                proto::signer_message::Payload::KeyRotationAttestation(inner.into())
+            }
+            Payload::DecisionSyncRequest(inner) => {
+                proto::signer_message::Payload::DecisionSyncRequest(inner.into())
+            }
+            Payload::DecisionSyncResponse(inner) => {
+                proto::signer_message::Payload::DecisionSyncResponse(inner.into())
+            }
+            Payload::CoordinatorTakeover(inner) => {
+                proto::signer_message::Payload::CoordinatorTakeover(inner.into())
+            }
+            Payload::CoordinatorSilenceAttestation(inner) => {
+                proto::signer_message::Payload::CoordinatorSilenceAttestation(inner.into())
+            }
+            Payload::PeerAddressAnnouncement(inner) => {
+                proto::signer_message::Payload::PeerAddressAnnouncement(inner.into())
+            }
+            Payload::SignerDecisionBatch(inner) => {
+                proto::signer_message::Payload::SignerDecisionBatch(inner.into())
+            }
+            Payload::RotateKeysDryRunRequest(inner) => {
+                proto::signer_message::Payload::RotateKeysDryRunRequest(inner.into())
+            }
+            Payload::RotateKeysDryRunVerdict(inner) => {
+                proto::signer_message::Payload::RotateKeysDryRunVerdict(inner.into())
+            }
+            Payload::SweepTxProofRequest(inner) => {
+                proto::signer_message::Payload::SweepTxProofRequest(inner.into())
+            }
+            Payload::SweepTxProofResponse(inner) => {
+                proto::signer_message::Payload::SweepTxProofResponse(inner.into())
+            }
         }
     }
 }
@@ -1346,6 +1716,39 @@ impl TryFrom<proto::Payload> for Payload {
             proto::signer_message::Payload::BitcoinPreSignAck(inner) => {
                 Payload::BitcoinPreSignAck(inner.into())
             }
+            proto::signer_message::Payload::KeyRotationAttestation(inner) => {
+                Payload::KeyRotationAttestation(inner.try_into()?)
+            }
+            proto::signer_message::Payload::DecisionSyncRequest(inner) => {
+                Payload::DecisionSyncRequest(inner.into())
+            }
+            proto::signer_message::Payload::DecisionSyncResponse(inner) => {
+                Payload::DecisionSyncResponse(inner.try_into()?)
+            }
+            proto::signer_message::Payload::CoordinatorTakeover(inner) => {
+                Payload::CoordinatorTakeover(inner.try_into()?)
+            }
+            proto::signer_message::Payload::CoordinatorSilenceAttestation(inner) => {
+                Payload::CoordinatorSilenceAttestation(inner.try_into()?)
+            }
+            proto::signer_message::Payload::PeerAddressAnnouncement(inner) => {
+                Payload::PeerAddressAnnouncement(inner.try_into()?)
+            }
+            proto::signer_message::Payload::SignerDecisionBatch(inner) => {
+                Payload::SignerDecisionBatch(inner.try_into()?)
+            }
+            proto::signer_message::Payload::RotateKeysDryRunRequest(inner) => {
+                Payload::RotateKeysDryRunRequest(inner.try_into()?)
+            }
+            proto::signer_message::Payload::RotateKeysDryRunVerdict(inner) => {
+                Payload::RotateKeysDryRunVerdict(inner.try_into()?)
+            }
+            proto::signer_message::Payload::SweepTxProofRequest(inner) => {
+                Payload::SweepTxProofRequest(inner.try_into()?)
+            }
+            proto::signer_message::Payload::SweepTxProofResponse(inner) => {
+                Payload::SweepTxProofResponse(inner.try_into()?)
+            }
         };
         Ok(payload)
     }
@@ -1630,6 +2033,235 @@ impl TryFrom<proto::DkgPublicShares> for BTreeMap<u32, DkgPublicShares> {
     }
 }
 
+impl From<WstsSignerState> for proto::WstsSignerState {
+    fn from(value: WstsSignerState) -> Self {
+        match value {
+            WstsSignerState::Idle => proto::WstsSignerState::Idle,
+            WstsSignerState::DkgPublicDistribute => proto::WstsSignerState::DkgPublicDistribute,
+            WstsSignerState::DkgPublicGather => proto::WstsSignerState::DkgPublicGather,
+            WstsSignerState::DkgPrivateDistribute => proto::WstsSignerState::DkgPrivateDistribute,
+            WstsSignerState::DkgPrivateGather => proto::WstsSignerState::DkgPrivateGather,
+            WstsSignerState::SignGather => proto::WstsSignerState::SignGather,
+        }
+    }
+}
+
+impl TryFrom<proto::WstsSignerState> for WstsSignerState {
+    type Error = Error;
+    fn try_from(value: proto::WstsSignerState) -> Result<Self, Self::Error> {
+        Ok(match value {
+            proto::WstsSignerState::Idle => WstsSignerState::Idle,
+            proto::WstsSignerState::DkgPublicDistribute => WstsSignerState::DkgPublicDistribute,
+            proto::WstsSignerState::DkgPublicGather => WstsSignerState::DkgPublicGather,
+            proto::WstsSignerState::DkgPrivateDistribute => WstsSignerState::DkgPrivateDistribute,
+            proto::WstsSignerState::DkgPrivateGather => WstsSignerState::DkgPrivateGather,
+            proto::WstsSignerState::SignGather => WstsSignerState::SignGather,
+            proto::WstsSignerState::Unspecified => return Err(Error::TypeConversion),
+        })
+    }
+}
+
+impl From<HashMap<u32, Scalar>> for proto::DecryptedShares {
+    fn from(value: HashMap<u32, Scalar>) -> Self {
+        proto::DecryptedShares {
+            shares: value.into_iter().map(|(k, v)| (k, v.into())).collect(),
+        }
+    }
+}
+
+impl TryFrom<proto::DecryptedShares> for HashMap<u32, Scalar> {
+    type Error = Error;
+    fn try_from(value: proto::DecryptedShares) -> Result<Self, Self::Error> {
+        value
+            .shares
+            .into_iter()
+            .map(|(k, v)| Ok((k, v.try_into()?)))
+            .collect()
+    }
+}
+
+impl From<(u32, Point)> for proto::DecryptionKey {
+    fn from((signer_id, shared_key): (u32, Point)) -> Self {
+        proto::DecryptionKey {
+            signer_id,
+            shared_key: Some(shared_key.into()),
+        }
+    }
+}
+
+impl TryFrom<proto::DecryptionKey> for (u32, Point) {
+    type Error = Error;
+    fn try_from(value: proto::DecryptionKey) -> Result<Self, Self::Error> {
+        Ok((value.signer_id, value.shared_key.required()?.try_into()?))
+    }
+}
+
+impl From<PublicKeys> for proto::SignerPublicKeys {
+    fn from(value: PublicKeys) -> Self {
+        let to_proto = |keys: HashMap<u32, p256k1::keys::PublicKey>| {
+            keys.into_iter()
+                .map(|(id, key)| (id, proto::PublicKey::from(PublicKey::from(key))))
+                .collect::<BTreeMap<_, _>>()
+        };
+        proto::SignerPublicKeys {
+            signers: to_proto(value.signers),
+            key_ids: to_proto(value.key_ids),
+            signer_key_ids: value
+                .signer_key_ids
+                .into_iter()
+                .map(|(id, key_ids)| {
+                    let key_ids = proto::SignerKeyIds {
+                        key_ids: hashset_to_zst(key_ids),
+                    };
+                    (id, key_ids)
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<proto::SignerPublicKeys> for PublicKeys {
+    type Error = Error;
+    fn try_from(value: proto::SignerPublicKeys) -> Result<Self, Self::Error> {
+        let from_proto = |keys: BTreeMap<u32, proto::PublicKey>| {
+            keys.into_iter()
+                .map(|(id, key)| Ok((id, PublicKey::try_from(key)?.into())))
+                .collect::<Result<HashMap<u32, p256k1::keys::PublicKey>, Error>>()
+        };
+        Ok(PublicKeys {
+            signers: from_proto(value.signers)?,
+            key_ids: from_proto(value.key_ids)?,
+            signer_key_ids: value
+                .signer_key_ids
+                .into_iter()
+                .map(|(id, key_ids)| (id, zst_to_hashset(key_ids.key_ids)))
+                .collect(),
+        })
+    }
+}
+
+impl From<Signer> for proto::WstsSigner {
+    fn from(value: Signer) -> Self {
+        proto::WstsSigner {
+            dkg_id: value.dkg_id,
+            sign_id: value.sign_id,
+            sign_iter_id: value.sign_iter_id,
+            threshold: value.threshold,
+            dkg_threshold: value.dkg_threshold,
+            total_signers: value.total_signers,
+            total_keys: value.total_keys,
+            // Saving the party drops its private nonce. This is
+            // intentional, we never persist private nonces.
+            signer: Some(value.signer.save().into()),
+            signer_id: value.signer_id,
+            state: proto::WstsSignerState::from(value.state).into(),
+            commitments: value
+                .commitments
+                .into_iter()
+                .map(|(k, v)| (k, v.into()))
+                .collect(),
+            decrypted_shares: value
+                .decrypted_shares
+                .into_iter()
+                .map(|(k, v)| (k, v.into()))
+                .collect(),
+            decryption_keys: value
+                .decryption_keys
+                .into_iter()
+                .map(|(k, v)| (k, v.into()))
+                .collect(),
+            invalid_private_shares: Some(value.invalid_private_shares.into()),
+            public_nonces: value.public_nonces.into_iter().map(|v| v.into()).collect(),
+            network_private_key: Some(value.network_private_key.into()),
+            public_keys: Some(value.public_keys.into()),
+            dkg_public_shares: Some(value.dkg_public_shares.into()),
+            dkg_private_shares: value
+                .dkg_private_shares
+                .into_iter()
+                .map(|(k, v)| (k, v.into()))
+                .collect(),
+            dkg_private_begin_msg: value.dkg_private_begin_msg.map(|v| v.into()),
+            dkg_end_begin_msg: value.dkg_end_begin_msg.map(|v| v.into()),
+        }
+    }
+}
+
+impl TryFrom<proto::WstsSigner> for Signer {
+    type Error = Error;
+    fn try_from(value: proto::WstsSigner) -> Result<Self, Self::Error> {
+        let state = proto::WstsSignerState::try_from(value.state)
+            .map_err(|_| Error::TypeConversion)?
+            .try_into()?;
+        let signer_state: SignerState = value.signer.required()?.try_into()?;
+
+        Ok(Signer {
+            dkg_id: value.dkg_id,
+            sign_id: value.sign_id,
+            sign_iter_id: value.sign_iter_id,
+            threshold: value.threshold,
+            dkg_threshold: value.dkg_threshold,
+            total_signers: value.total_signers,
+            total_keys: value.total_keys,
+            signer: v2::Party::load(&signer_state),
+            signer_id: value.signer_id,
+            state,
+            commitments: value
+                .commitments
+                .into_iter()
+                .map(|(k, v)| Ok((k, v.try_into()?)))
+                .collect::<Result<_, Error>>()?,
+            decrypted_shares: value
+                .decrypted_shares
+                .into_iter()
+                .map(|(k, v)| Ok((k, v.try_into()?)))
+                .collect::<Result<_, Error>>()?,
+            decryption_keys: value
+                .decryption_keys
+                .into_iter()
+                .map(|(k, v)| Ok((k, v.try_into()?)))
+                .collect::<Result<_, Error>>()?,
+            invalid_private_shares: value.invalid_private_shares.required()?.try_into()?,
+            public_nonces: value
+                .public_nonces
+                .into_iter()
+                .map(|v| v.try_into())
+                .collect::<Result<_, Error>>()?,
+            network_private_key: value.network_private_key.required()?.try_into()?,
+            public_keys: value.public_keys.required()?.try_into()?,
+            dkg_public_shares: value.dkg_public_shares.required()?.try_into()?,
+            dkg_private_shares: value
+                .dkg_private_shares
+                .into_iter()
+                .map(|(k, v)| Ok((k, v.try_into()?)))
+                .collect::<Result<_, Error>>()?,
+            dkg_private_begin_msg: value.dkg_private_begin_msg.map(|v| v.into()),
+            dkg_end_begin_msg: value.dkg_end_begin_msg.map(|v| v.into()),
+        })
+    }
+}
+
+impl From<(BitcoinBlockRef, Signer)> for proto::SignerStateMachine {
+    fn from((started_at, signer): (BitcoinBlockRef, Signer)) -> Self {
+        proto::SignerStateMachine {
+            started_at_block_hash: Some(started_at.block_hash.into_bytes().into()),
+            started_at_block_height: *started_at.block_height,
+            signer: Some(signer.into()),
+        }
+    }
+}
+
+impl TryFrom<proto::SignerStateMachine> for (BitcoinBlockRef, Signer) {
+    type Error = Error;
+    fn try_from(value: proto::SignerStateMachine) -> Result<Self, Self::Error> {
+        let block_hash: [u8; 32] = value.started_at_block_hash.required()?.into();
+        let started_at = BitcoinBlockRef {
+            block_hash: block_hash.into(),
+            block_height: value.started_at_block_height.into(),
+        };
+        Ok((started_at, value.signer.required()?.try_into()?))
+    }
+}
+
 impl codec::ProtoSerializable for SignerMessage {
     type Message = proto::SignerMessage;
 
@@ -1642,6 +2274,17 @@ impl codec::ProtoSerializable for SignerMessage {
             Payload::WstsMessage(_) => "SBTC_WSTS_MESSAGE",
             Payload::BitcoinPreSignRequest(_) => "SBTC_BITCOIN_PRE_SIGN_REQUEST",
             Payload::BitcoinPreSignAck(_) => "SBTC_BITCOIN_PRE_SIGN_ACK",
+            Payload::KeyRotationAttestation(_) => "SBTC_KEY_ROTATION_ATTESTATION",
+            Payload::DecisionSyncRequest(_) => "SBTC_DECISION_SYNC_REQUEST",
+            Payload::DecisionSyncResponse(_) => "SBTC_DECISION_SYNC_RESPONSE",
+            Payload::CoordinatorTakeover(_) => "SBTC_COORDINATOR_TAKEOVER",
+            Payload::CoordinatorSilenceAttestation(_) => "SBTC_COORDINATOR_SILENCE_ATTESTATION",
+            Payload::PeerAddressAnnouncement(_) => "SBTC_PEER_ADDRESS_ANNOUNCEMENT",
+            Payload::SignerDecisionBatch(_) => "SBTC_SIGNER_DECISION_BATCH",
+            Payload::RotateKeysDryRunRequest(_) => "SBTC_ROTATE_KEYS_DRY_RUN_REQUEST",
+            Payload::RotateKeysDryRunVerdict(_) => "SBTC_ROTATE_KEYS_DRY_RUN_VERDICT",
+            Payload::SweepTxProofRequest(_) => "SBTC_SWEEP_TX_PROOF_REQUEST",
+            Payload::SweepTxProofResponse(_) => "SBTC_SWEEP_TX_PROOF_RESPONSE",
         }
     }
 }
@@ -1670,6 +2313,22 @@ impl codec::ProtoSerializable for BTreeMap<u32, DkgPublicShares> {
     }
 }
 
+impl codec::ProtoSerializable for (BitcoinBlockRef, Signer) {
+    type Message = proto::SignerStateMachine;
+
+    fn type_tag(&self) -> &'static str {
+        "SBTC_SIGNER_STATE_MACHINE"
+    }
+}
+
+impl codec::ProtoSerializable for BitcoinPreSignRequest {
+    type Message = proto::BitcoinPreSignRequest;
+
+    fn type_tag(&self) -> &'static str {
+        "SBTC_BITCOIN_PRE_SIGN_REQUEST"
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::testing::dummy::Unit;
@@ -1724,6 +2383,17 @@ mod tests {
     #[test_case(PhantomData::<(Fees, proto::Fees)>; "Fees")]
     #[test_case(PhantomData::<(BitcoinPreSignRequest, proto::BitcoinPreSignRequest)>; "BitcoinPreSignRequest")]
     #[test_case(PhantomData::<(BitcoinPreSignAck, proto::BitcoinPreSignAck)>; "BitcoinPreSignAck")]
+    #[test_case(PhantomData::<(KeyRotationAttestation, proto::KeyRotationAttestation)>; "KeyRotationAttestation")]
+    #[test_case(PhantomData::<(DecisionSyncRequest, proto::DecisionSyncRequest)>; "DecisionSyncRequest")]
+    #[test_case(PhantomData::<(DecisionSyncResponse, proto::DecisionSyncResponse)>; "DecisionSyncResponse")]
+    #[test_case(PhantomData::<(CoordinatorTakeover, proto::CoordinatorTakeover)>; "CoordinatorTakeover")]
+    #[test_case(PhantomData::<(CoordinatorSilenceAttestation, proto::CoordinatorSilenceAttestation)>; "CoordinatorSilenceAttestation")]
+    #[test_case(PhantomData::<(PeerAddressAnnouncement, proto::PeerAddressAnnouncement)>; "PeerAddressAnnouncement")]
+    #[test_case(PhantomData::<(SignerDecisionBatch, proto::SignerDecisionBatch)>; "SignerDecisionBatch")]
+    #[test_case(PhantomData::<(RotateKeysDryRunRequest, proto::RotateKeysDryRunRequest)>; "RotateKeysDryRunRequest")]
+    #[test_case(PhantomData::<(RotateKeysDryRunVerdict, proto::RotateKeysDryRunVerdict)>; "RotateKeysDryRunVerdict")]
+    #[test_case(PhantomData::<(SweepTxProofRequest, proto::SweepTxProofRequest)>; "SweepTxProofRequest")]
+    #[test_case(PhantomData::<(SweepTxProofResponse, proto::SweepTxProofResponse)>; "SweepTxProofResponse")]
     fn convert_protobuf_type<T, U, E>(_: PhantomData<(T, U)>)
     where
         // `.unwrap()` requires that `E` implement `std::fmt::Debug` and
@@ -1755,6 +2425,7 @@ mod tests {
     /// This test is identical to [`convert_protobuf_types`] tests above,
     /// except we cannot implement Dummy<Faker> on these types.
     #[test_case(PhantomData::<(bitcoin::OutPoint, proto::OutPoint)>; "OutPoint")]
+    #[test_case(PhantomData::<(DepositId, proto::OutPoint)>; "DepositId")]
     #[test_case(PhantomData::<(RecoverableSignature, proto::RecoverableSignature)>; "RecoverableSignature")]
     #[test_case(PhantomData::<(secp256k1::ecdsa::Signature, proto::EcdsaSignature)>; "EcdsaSignature")]
     #[test_case(PhantomData::<(StacksAddress, proto::StacksAddress)>; "StacksAddress")]
@@ -1801,6 +2472,16 @@ mod tests {
         }
     }
 
+    #[test_case(Vec::new(), Some(crate::LEGACY_MAGIC_BYTES); "missing")]
+    #[test_case(b"X2".to_vec(), Some(*b"X2"); "present")]
+    #[test_case(b"X".to_vec(), None; "too short")]
+    fn pre_sign_request_magic_bytes(magic_bytes: Vec<u8>, expected: Option<[u8; 2]>) {
+        let request: BitcoinPreSignRequest = Faker.fake_with_rng(&mut OsRng);
+        let request = proto::BitcoinPreSignRequest { magic_bytes, ..request.into() };
+        let request = BitcoinPreSignRequest::try_from(request).ok();
+        assert_eq!(request.map(|request| request.magic_bytes), expected);
+    }
+
     #[test]
     fn convert_protobuf_point() {
         let number = [
@@ -73,6 +73,180 @@ pub struct PrivateNonce {
     #[prost(message, optional, tag = "2")]
     pub nonce_e: ::core::option::Option<super::Scalar>,
 }
+/// The state of a WSTS signer state machine in the middle of a DKG or
+/// signing round. It is encrypted and stored in the database when the
+/// signer evicts the state machine from its in-memory cache, so that the
+/// round can continue once the state machine is needed again. The private
+/// nonce of the signer is never stored, so a signing round cannot continue
+/// after the state machine has been restored.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignerStateMachine {
+    /// The bitcoin block hash at the time that the state machine was
+    /// created.
+    #[prost(message, optional, tag = "1")]
+    pub started_at_block_hash: ::core::option::Option<super::Uint256>,
+    /// The bitcoin block height at the time that the state machine was
+    /// created.
+    #[prost(uint64, tag = "2")]
+    pub started_at_block_height: u64,
+    /// The inner WSTS signer state machine.
+    #[prost(message, optional, tag = "3")]
+    pub signer: ::core::option::Option<WstsSigner>,
+}
+/// The protobuf version of the WSTS signer state machine.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WstsSigner {
+    /// The current DKG round ID
+    #[prost(uint64, tag = "1")]
+    pub dkg_id: u64,
+    /// The current signing round ID
+    #[prost(uint64, tag = "2")]
+    pub sign_id: u64,
+    /// The current signing iteration ID
+    #[prost(uint64, tag = "3")]
+    pub sign_iter_id: u64,
+    /// The threshold of the keys needed for a valid signature
+    #[prost(uint32, tag = "4")]
+    pub threshold: u32,
+    /// The threshold of the keys needed for a valid DKG
+    #[prost(uint32, tag = "5")]
+    pub dkg_threshold: u32,
+    /// The total number of signers
+    #[prost(uint32, tag = "6")]
+    pub total_signers: u32,
+    /// The total number of keys
+    #[prost(uint32, tag = "7")]
+    pub total_keys: u32,
+    /// The saved state of the signer's party
+    #[prost(message, optional, tag = "8")]
+    pub signer: ::core::option::Option<SignerState>,
+    /// The signer ID
+    #[prost(uint32, tag = "9")]
+    pub signer_id: u32,
+    /// The current state of the state machine
+    #[prost(enumeration = "WstsSignerState", tag = "10")]
+    pub state: i32,
+    /// The polynomial commitments of each party
+    #[prost(btree_map = "uint32, message", tag = "11")]
+    pub commitments: ::prost::alloc::collections::BTreeMap<u32, PolyCommitment>,
+    /// The decrypted DKG private shares of each source party
+    #[prost(btree_map = "uint32, message", tag = "12")]
+    pub decrypted_shares: ::prost::alloc::collections::BTreeMap<u32, DecryptedShares>,
+    /// The shared secrets used to decrypt the private shares of each source
+    /// party
+    #[prost(btree_map = "uint32, message", tag = "13")]
+    pub decryption_keys: ::prost::alloc::collections::BTreeMap<u32, DecryptionKey>,
+    /// The invalid private shares of each signer
+    #[prost(message, optional, tag = "14")]
+    pub invalid_private_shares: ::core::option::Option<BadPrivateShares>,
+    /// The public nonces for the current signing round
+    #[prost(message, repeated, tag = "15")]
+    pub public_nonces: ::prost::alloc::vec::Vec<PublicNonce>,
+    /// The private key used to sign messages sent over the network
+    #[prost(message, optional, tag = "16")]
+    pub network_private_key: ::core::option::Option<super::Scalar>,
+    /// The public keys of all signers and the coordinator
+    #[prost(message, optional, tag = "17")]
+    pub public_keys: ::core::option::Option<SignerPublicKeys>,
+    /// The DKG public shares received in the current round
+    #[prost(message, optional, tag = "18")]
+    pub dkg_public_shares: ::core::option::Option<DkgPublicShares>,
+    /// The DKG private shares received in the current round
+    #[prost(btree_map = "uint32, message", tag = "19")]
+    pub dkg_private_shares: ::prost::alloc::collections::BTreeMap<u32, DkgPrivateShares>,
+    /// The DKG private begin message received in the current round
+    #[prost(message, optional, tag = "20")]
+    pub dkg_private_begin_msg: ::core::option::Option<DkgPrivateBegin>,
+    /// The DKG end begin message received in the current round
+    #[prost(message, optional, tag = "21")]
+    pub dkg_end_begin_msg: ::core::option::Option<DkgEndBegin>,
+}
+/// The decrypted DKG private shares from a source party.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DecryptedShares {
+    /// The private shares, keyed by the destination key ID
+    #[prost(btree_map = "uint32, message", tag = "1")]
+    pub shares: ::prost::alloc::collections::BTreeMap<u32, super::Scalar>,
+}
+/// The shared secret used to decrypt the private shares from a source
+/// party.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct DecryptionKey {
+    /// The ID of the signer that sent the private shares
+    #[prost(uint32, tag = "1")]
+    pub signer_id: u32,
+    /// The Diffie-Hellman shared key
+    #[prost(message, optional, tag = "2")]
+    pub shared_key: ::core::option::Option<super::Point>,
+}
+/// The public keys of the signers and the keys that they control.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignerPublicKeys {
+    /// The public keys of each signer ID
+    #[prost(btree_map = "uint32, message", tag = "1")]
+    pub signers: ::prost::alloc::collections::BTreeMap<u32, super::PublicKey>,
+    /// The public keys of each key ID
+    #[prost(btree_map = "uint32, message", tag = "2")]
+    pub key_ids: ::prost::alloc::collections::BTreeMap<u32, super::PublicKey>,
+    /// The key IDs controlled by each signer ID
+    #[prost(btree_map = "uint32, message", tag = "3")]
+    pub signer_key_ids: ::prost::alloc::collections::BTreeMap<u32, SignerKeyIds>,
+}
+/// The key IDs controlled by a signer.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignerKeyIds {
+    /// In WSTS this is a HashSet<u32>, so these should be unique.
+    #[prost(btree_map = "uint32, message", tag = "1")]
+    pub key_ids: ::prost::alloc::collections::BTreeMap<u32, super::SetValueZst>,
+}
+/// The states of a WSTS signer state machine.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum WstsSignerState {
+    Unspecified = 0,
+    /// The signer is idle
+    Idle = 1,
+    /// The signer is distributing DKG public shares
+    DkgPublicDistribute = 2,
+    /// The signer is gathering DKG public shares
+    DkgPublicGather = 3,
+    /// The signer is distributing DKG private shares
+    DkgPrivateDistribute = 4,
+    /// The signer is gathering DKG private shares
+    DkgPrivateGather = 5,
+    /// The signer is gathering signature shares
+    SignGather = 6,
+}
+impl WstsSignerState {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unspecified => "WSTS_SIGNER_STATE_UNSPECIFIED",
+            Self::Idle => "WSTS_SIGNER_STATE_IDLE",
+            Self::DkgPublicDistribute => "WSTS_SIGNER_STATE_DKG_PUBLIC_DISTRIBUTE",
+            Self::DkgPublicGather => "WSTS_SIGNER_STATE_DKG_PUBLIC_GATHER",
+            Self::DkgPrivateDistribute => "WSTS_SIGNER_STATE_DKG_PRIVATE_DISTRIBUTE",
+            Self::DkgPrivateGather => "WSTS_SIGNER_STATE_DKG_PRIVATE_GATHER",
+            Self::SignGather => "WSTS_SIGNER_STATE_SIGN_GATHER",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "WSTS_SIGNER_STATE_UNSPECIFIED" => Some(Self::Unspecified),
+            "WSTS_SIGNER_STATE_IDLE" => Some(Self::Idle),
+            "WSTS_SIGNER_STATE_DKG_PUBLIC_DISTRIBUTE" => Some(Self::DkgPublicDistribute),
+            "WSTS_SIGNER_STATE_DKG_PUBLIC_GATHER" => Some(Self::DkgPublicGather),
+            "WSTS_SIGNER_STATE_DKG_PRIVATE_DISTRIBUTE" => Some(Self::DkgPrivateDistribute),
+            "WSTS_SIGNER_STATE_DKG_PRIVATE_GATHER" => Some(Self::DkgPrivateGather),
+            "WSTS_SIGNER_STATE_SIGN_GATHER" => Some(Self::SignGather),
+            _ => None,
+        }
+    }
+}
 /// DKG begin message from coordinator to signers
 /// This maps to this type <<https://github.com/Trust-Machines/wsts/blob/2d6cb87218bb8dd9ed0519356afe57a0b9a697cb/src/net.rs#L123-L128>>
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
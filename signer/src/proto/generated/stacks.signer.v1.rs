@@ -61,6 +61,96 @@ pub struct SignerWithdrawalDecision {
     #[prost(bool, tag = "4")]
     pub accepted: bool,
 }
+/// A request for the decisions that the receiving signers made on the
+/// deposit and withdrawal requests confirmed in a range of bitcoin blocks.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct DecisionSyncRequest {
+    /// The height of the first bitcoin block in the range.
+    #[prost(uint64, tag = "1")]
+    pub start_height: u64,
+    /// The height of the last bitcoin block in the range, inclusive.
+    #[prost(uint64, tag = "2")]
+    pub end_height: u64,
+}
+/// A page of the sending signer's own decisions on the deposit and
+/// withdrawal requests confirmed in a range of bitcoin blocks.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DecisionSyncResponse {
+    /// The height of the first bitcoin block in the range.
+    #[prost(uint64, tag = "1")]
+    pub start_height: u64,
+    /// The height of the last bitcoin block in the range, inclusive.
+    #[prost(uint64, tag = "2")]
+    pub end_height: u64,
+    /// The page of decisions in this response, starting from zero.
+    #[prost(uint32, tag = "3")]
+    pub page: u32,
+    /// The sending signer's decisions on deposit requests.
+    #[prost(message, repeated, tag = "4")]
+    pub deposits: ::prost::alloc::vec::Vec<SignerDepositDecision>,
+    /// The sending signer's decisions on withdrawal requests.
+    #[prost(message, repeated, tag = "5")]
+    pub withdrawals: ::prost::alloc::vec::Vec<SignerWithdrawalDecision>,
+    /// Whether the sending signer has more decisions in the range.
+    #[prost(bool, tag = "6")]
+    pub has_more: bool,
+    /// The public key of the signer that requested the decisions. Only that
+    /// signer processes the response.
+    #[prost(message, optional, tag = "7")]
+    pub requester: ::core::option::Option<super::super::super::crypto::PublicKey>,
+}
+/// An announcement that the sending signer, the deputy coordinator of the
+/// enclosing message's bitcoin chain tip, is taking over the tenure because
+/// a threshold of the signer set attested that the coordinator went silent.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CoordinatorTakeover {
+    /// The public key of the coordinator that went silent.
+    #[prost(message, optional, tag = "1")]
+    pub coordinator_public_key: ::core::option::Option<super::super::super::crypto::PublicKey>,
+    /// The attestations of the signers that have not heard from the
+    /// coordinator, at least as many as the signature threshold.
+    #[prost(message, repeated, tag = "2")]
+    pub attestations: ::prost::alloc::vec::Vec<CoordinatorSilenceAttestation>,
+}
+/// A signer's signed statement that it has not heard from the coordinator of
+/// the enclosing message's bitcoin chain tip within the silence timeout.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct CoordinatorSilenceAttestation {
+    /// The public key of the attesting signer.
+    #[prost(message, optional, tag = "1")]
+    pub signer_public_key: ::core::option::Option<super::super::super::crypto::PublicKey>,
+    /// The public key of the coordinator that went silent.
+    #[prost(message, optional, tag = "2")]
+    pub coordinator_public_key: ::core::option::Option<super::super::super::crypto::PublicKey>,
+    /// An ECDSA signature by the attesting signer over the bitcoin chain tip
+    /// and the public key of the coordinator.
+    #[prost(message, optional, tag = "3")]
+    pub signature: ::core::option::Option<
+        super::super::super::crypto::EcdsaSignature,
+    >,
+}
+/// A signer's signed announcement of the addresses that it can be reached
+/// at on the P2P network.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PeerAddressAnnouncement {
+    /// The multiaddrs of the signer, in their binary encoding.
+    #[prost(bytes = "vec", repeated, tag = "1")]
+    pub addresses: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    /// The unix timestamp, in seconds, after which the announcement expires.
+    #[prost(uint64, tag = "2")]
+    pub expires_at: u64,
+}
+/// A batch of the sending signer's decisions on deposit and withdrawal
+/// requests.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignerDecisionBatch {
+    /// The sending signer's decisions on deposit requests.
+    #[prost(message, repeated, tag = "1")]
+    pub deposits: ::prost::alloc::vec::Vec<SignerDepositDecision>,
+    /// The sending signer's decisions on withdrawal requests.
+    #[prost(message, repeated, tag = "2")]
+    pub withdrawals: ::prost::alloc::vec::Vec<SignerWithdrawalDecision>,
+}
 /// Represents a signature of a Stacks transaction.
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
 pub struct StacksTransactionSignature {
@@ -73,6 +163,37 @@ pub struct StacksTransactionSignature {
         super::super::super::crypto::RecoverableSignature,
     >,
 }
+/// Represents a signer's attestation to the outcome of a DKG round, made
+/// before the new aggregate key is rotated into the sbtc-registry contract.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct KeyRotationAttestation {
+    /// The aggregate key that the signers intend to rotate into.
+    #[prost(message, optional, tag = "1")]
+    pub aggregate_key: ::core::option::Option<super::super::super::crypto::PublicKey>,
+    /// The SHA-256 hash of the sorted, compressed public keys of the new
+    /// signer set.
+    #[prost(message, optional, tag = "2")]
+    pub signer_set_hash: ::core::option::Option<super::super::super::crypto::Uint256>,
+    /// The number of signatures required for the new multi-sig wallet.
+    #[prost(uint32, tag = "3")]
+    pub signatures_required: u32,
+    /// An ECDSA signature by the attesting signer over the above fields.
+    #[prost(message, optional, tag = "4")]
+    pub signature: ::core::option::Option<
+        super::super::super::crypto::EcdsaSignature,
+    >,
+}
+/// A signer's verdict on a RotateKeysDryRunRequest.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RotateKeysDryRunVerdict {
+    /// The ID of the request that this verdict is for.
+    #[prost(uint64, tag = "1")]
+    pub id: u64,
+    /// Why the signer would refuse to sign the contract call. This is empty
+    /// when the contract call passes the signer's validation.
+    #[prost(string, tag = "2")]
+    pub rejection_reason: ::prost::alloc::string::String,
+}
 /// Represents a request to sign a Stacks transaction.
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct StacksTransactionSignRequest {
@@ -227,6 +348,17 @@ pub struct RotateKeys {
     #[prost(uint32, tag = "4")]
     pub signatures_required: u32,
 }
+/// A request for the signers to validate a `rotate-keys-wrapper` contract
+/// call without signing it.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RotateKeysDryRunRequest {
+    /// An ID that the sender uses to match verdicts to this request.
+    #[prost(uint64, tag = "1")]
+    pub id: u64,
+    /// The contract call to validate.
+    #[prost(message, optional, tag = "2")]
+    pub rotate_keys: ::core::option::Option<RotateKeys>,
+}
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
 pub enum SmartContract {
@@ -284,8 +416,20 @@ pub struct SignerMessage {
         super::super::super::bitcoin::BitcoinBlockHash,
     >,
     /// The message payload
-    #[prost(oneof = "signer_message::Payload", tags = "2, 3, 4, 5, 8, 10, 11")]
+    #[prost(
+        oneof = "signer_message::Payload",
+        tags = "2, 3, 4, 5, 8, 10, 11, 12, 13, 14, 15, 16, 17, 19, 20, 23, 24, 25"
+    )]
     pub payload: ::core::option::Option<signer_message::Payload>,
+    /// The unix timestamp, in milliseconds, by which the sender needs the work
+    /// that the message asks for to be done. Zero means there is no deadline.
+    #[prost(uint64, tag = "18")]
+    pub deadline: u64,
+    /// The W3C traceparent of the span that the sender created the message in,
+    /// in the form "00-<trace id>-<span id>-01" with lowercase hex IDs. Empty
+    /// means that the message was not traced.
+    #[prost(string, tag = "22")]
+    pub trace_parent: ::prost::alloc::string::String,
 }
 /// Nested message and enum types in `SignerMessage`.
 pub mod signer_message {
@@ -313,6 +457,39 @@ pub mod signer_message {
         /// Represents an acknowledgment of a BitcoinPreSignRequest
         #[prost(message, tag = "11")]
         BitcoinPreSignAck(super::BitcoinPreSignAck),
+        /// A signer's attestation to the outcome of a DKG round
+        #[prost(message, tag = "12")]
+        KeyRotationAttestation(super::KeyRotationAttestation),
+        /// A request for the decisions made in a range of bitcoin blocks
+        #[prost(message, tag = "13")]
+        DecisionSyncRequest(super::DecisionSyncRequest),
+        /// A page of decisions made in a range of bitcoin blocks
+        #[prost(message, tag = "14")]
+        DecisionSyncResponse(super::DecisionSyncResponse),
+        /// A deputy coordinator's announcement that it is taking over a tenure
+        #[prost(message, tag = "15")]
+        CoordinatorTakeover(super::CoordinatorTakeover),
+        /// A signer's announcement of the addresses that it can be reached at
+        #[prost(message, tag = "16")]
+        PeerAddressAnnouncement(super::PeerAddressAnnouncement),
+        /// A batch of decisions on deposit and withdrawal requests
+        #[prost(message, tag = "17")]
+        SignerDecisionBatch(super::SignerDecisionBatch),
+        /// A request to validate a rotate-keys contract call without signing it
+        #[prost(message, tag = "19")]
+        RotateKeysDryRunRequest(super::RotateKeysDryRunRequest),
+        /// A signer's verdict on a rotate-keys dry run request
+        #[prost(message, tag = "20")]
+        RotateKeysDryRunVerdict(super::RotateKeysDryRunVerdict),
+        /// A request for a proof that a sweep transaction was confirmed
+        #[prost(message, tag = "23")]
+        SweepTxProofRequest(super::SweepTxProofRequest),
+        /// A proof that a sweep transaction was confirmed, if the sender has one
+        #[prost(message, tag = "24")]
+        SweepTxProofResponse(super::SweepTxProofResponse),
+        /// A signer's attestation that the coordinator went silent
+        #[prost(message, tag = "25")]
+        CoordinatorSilenceAttestation(super::CoordinatorSilenceAttestation),
     }
 }
 /// A wsts message.
@@ -415,6 +592,16 @@ pub struct BitcoinPreSignRequest {
     /// used this UTXO as an input.
     #[prost(message, optional, tag = "3")]
     pub last_fees: ::core::option::Option<Fees>,
+    /// Whether the package is constructed as parallel sweeps, where the
+    /// first transaction splits the signers' change and the transactions
+    /// after it spend the signers' spare UTXOs.
+    #[prost(bool, tag = "4")]
+    pub parallel_sweeps: bool,
+    /// The magic bytes at the start of the OP_RETURN output of each
+    /// transaction in the package. Coordinators that predate this field
+    /// always used the legacy magic bytes.
+    #[prost(bytes = "vec", tag = "5")]
+    pub magic_bytes: ::prost::alloc::vec::Vec<u8>,
 }
 /// Represents an acknowledgment of a BitcoinPreSignRequest.
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
@@ -431,3 +618,37 @@ pub struct TxRequestIds {
     #[prost(message, repeated, tag = "2")]
     pub withdrawals: ::prost::alloc::vec::Vec<QualifiedRequestId>,
 }
+/// A request for a proof that the sweep transaction with the given ID was
+/// confirmed on the canonical bitcoin blockchain.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SweepTxProofRequest {
+    /// The ID of the sweep transaction.
+    #[prost(message, optional, tag = "1")]
+    pub txid: ::core::option::Option<super::super::super::bitcoin::BitcoinTxid>,
+}
+/// The answer to a SweepTxProofRequest.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SweepTxProofResponse {
+    /// The ID of the sweep transaction that the proof is for.
+    #[prost(message, optional, tag = "1")]
+    pub txid: ::core::option::Option<super::super::super::bitcoin::BitcoinTxid>,
+    /// The proof, if the sending signer has a record of the sweep
+    /// transaction being confirmed on its canonical bitcoin blockchain.
+    #[prost(message, optional, tag = "2")]
+    pub proof: ::core::option::Option<SweepTxProof>,
+}
+/// A proof that a sweep transaction was confirmed in a bitcoin block.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SweepTxProof {
+    /// The height of the bitcoin block that confirmed the transaction.
+    #[prost(uint64, tag = "1")]
+    pub block_height: u64,
+    /// The consensus encoded merkle block, which holds the header of the
+    /// bitcoin block and a partial merkle tree committing to the transaction.
+    #[prost(bytes = "vec", tag = "2")]
+    pub merkle_block: ::prost::alloc::vec::Vec<u8>,
+    /// The consensus encoded headers of the bitcoin blocks that build on the
+    /// block that confirmed the transaction, ordered by height.
+    #[prost(bytes = "vec", repeated, tag = "3")]
+    pub headers: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+}
@@ -2,6 +2,7 @@
 #![allow(missing_docs)]
 mod generated;
 
+pub mod compat;
 pub mod convert;
 
 pub use generated::bitcoin::*;
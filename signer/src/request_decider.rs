@@ -5,7 +5,14 @@
 //!
 //! For more details, see the [`RequestDeciderEventLoop`] documentation.
 
+use std::collections::HashSet;
+use std::num::NonZeroU16;
+use std::sync::Arc;
 use std::time::Duration;
+use std::time::Instant;
+
+use bitcoin::OutPoint;
+use sbtc::ids::DepositId;
 
 use crate::block_observer::BlockObserver;
 use crate::blocklist_client::BlocklistChecker;
@@ -15,27 +22,52 @@ use crate::context::RequestDeciderEvent;
 use crate::context::SignerCommand;
 use crate::context::SignerEvent;
 use crate::context::SignerSignal;
+use crate::context::Subsystem;
+use crate::decision_policy::BlocklistPolicy;
+use crate::decision_policy::DecisionPolicy;
+use crate::decision_policy::DecisionPolicyChain;
+use crate::decision_policy::DepositDecisionContext;
+use crate::decision_policy::PolicyDecision;
+use crate::decision_policy::ScreeningTiers;
+use crate::decision_policy::WithdrawalDecisionContext;
 use crate::ecdsa::SignEcdsa as _;
 use crate::ecdsa::Signed;
 use crate::emily_client::EmilyInteract as _;
 use crate::error::Error;
 use crate::keys::PrivateKey;
 use crate::keys::PublicKey;
+use crate::logging::CorrelationId;
+use crate::message::DecisionSyncRequest;
+use crate::message::DecisionSyncResponse;
 use crate::message::Payload;
+use crate::message::SignerDecisionBatch;
 use crate::message::SignerDepositDecision;
 use crate::message::SignerMessage;
 use crate::message::SignerWithdrawalDecision;
+use crate::message::SweepTxProof;
+use crate::message::SweepTxProofRequest;
+use crate::message::SweepTxProofResponse;
+use crate::metrics::Metrics;
 use crate::network::MessageTransfer;
-use crate::storage::DbRead as _;
+use crate::storage::DbRead;
 use crate::storage::DbWrite as _;
+use crate::storage::Transactable as _;
+use crate::storage::TransactionHandle as _;
 use crate::storage::model;
 use crate::storage::model::BitcoinBlockHash;
 use crate::storage::model::BitcoinBlockRef;
 use crate::storage::model::DepositSigner;
+use crate::storage::model::QualifiedRequestId;
 use crate::storage::model::WithdrawalSigner;
+use crate::storage::util;
+use crate::telemetry;
 
 use futures::StreamExt as _;
-use futures::TryStreamExt as _;
+use tracing::Instrument as _;
+
+/// The maximum number of deposit requests that are fetched from Emily at
+/// the same time while handling a decision batch.
+const MAX_CONCURRENT_EMILY_FETCHES: usize = 8;
 
 /// This struct is responsible for deciding whether to accept or reject
 /// requests and persisting requests from other signers.
@@ -47,9 +79,14 @@ pub struct RequestDeciderEventLoop<C, N, B> {
     pub network: N,
     /// Blocklist checker.
     pub blocklist_checker: Option<B>,
+    /// Decision policies registered in addition to the built in ones.
+    /// They are only evaluated when the `signer.decision_policies`
+    /// setting names them.
+    pub decision_policies: Vec<Arc<dyn DecisionPolicy>>,
     /// Private key of the signer for network communication.
     pub signer_private_key: PrivateKey,
-    /// How many bitcoin blocks back from the chain tip the signer will look for requests.
+    /// How many bitcoin blocks back from the chain tip the signer will look for requests,
+    /// unless a more specific lookback window is set in the config.
     pub context_window: u16,
     /// How many bitcoin blocks back from the chain tip the signer will look for deposit
     /// decisions to retry to propagate.
@@ -57,6 +94,73 @@ pub struct RequestDeciderEventLoop<C, N, B> {
     /// How many bitcoin blocks back from the chain tip the signer will look for withdrawal
     /// decisions to retry to propagate.
     pub withdrawal_decisions_retry_window: u16,
+    /// The pending requests that were not decided on within the latency
+    /// budget after the previous bitcoin block.
+    pub carried_over: CarriedOverRequests,
+}
+
+/// A pending request that the request decider needs to decide on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PendingRequest {
+    /// A deposit request.
+    Deposit(model::DepositRequest),
+    /// A withdrawal request.
+    Withdrawal(model::WithdrawalRequest),
+}
+
+/// The pending requests that the request decider did not get to within
+/// its latency budget after a bitcoin block. They remain pending in the
+/// database, and are decided on before any other request after the next
+/// bitcoin block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CarriedOverRequests {
+    deposits: HashSet<OutPoint>,
+    withdrawals: HashSet<QualifiedRequestId>,
+}
+
+impl CarriedOverRequests {
+    /// The number of deposit requests that were carried over.
+    pub fn num_deposits(&self) -> usize {
+        self.deposits.len()
+    }
+
+    /// The number of withdrawal requests that were carried over.
+    pub fn num_withdrawals(&self) -> usize {
+        self.withdrawals.len()
+    }
+
+    /// Whether no requests were carried over.
+    pub fn is_empty(&self) -> bool {
+        self.deposits.is_empty() && self.withdrawals.is_empty()
+    }
+
+    fn insert(&mut self, request: &PendingRequest) {
+        match request {
+            PendingRequest::Deposit(request) => {
+                self.deposits.insert(request.outpoint());
+            }
+            PendingRequest::Withdrawal(request) => {
+                self.withdrawals.insert(request.qualified_id());
+            }
+        }
+    }
+
+    /// Order the pending requests so that the ones that were carried
+    /// over come first, so that a steady stream of new requests cannot
+    /// starve them. The sort is stable, so the requests otherwise keep
+    /// their order, with deposits before withdrawals.
+    fn prioritize(&self, requests: &mut [PendingRequest]) {
+        requests.sort_by_key(|request| !self.contains(request));
+    }
+
+    fn contains(&self, request: &PendingRequest) -> bool {
+        match request {
+            PendingRequest::Deposit(request) => self.deposits.contains(&request.outpoint()),
+            PendingRequest::Withdrawal(request) => {
+                self.withdrawals.contains(&request.qualified_id())
+            }
+        }
+    }
 }
 
 /// This function defines which messages this event loop is interested
@@ -74,8 +178,28 @@ impl<C, N, B> RequestDeciderEventLoop<C, N, B>
 where
     C: Context,
     N: MessageTransfer,
-    B: BlocklistChecker,
+    B: BlocklistChecker + Sync,
 {
+    /// How many bitcoin blocks back from the chain tip to look for pending
+    /// deposit requests, falling back to the `context_window`.
+    fn deposit_lookback(&self) -> u16 {
+        self.context
+            .config()
+            .signer
+            .deposit_lookback
+            .map_or(self.context_window, NonZeroU16::get)
+    }
+
+    /// How many bitcoin blocks back from the chain tip to look for pending
+    /// withdrawal requests, falling back to the `context_window`.
+    fn withdrawal_lookback(&self) -> u16 {
+        self.context
+            .config()
+            .signer
+            .withdrawal_lookback
+            .map_or(self.context_window, NonZeroU16::get)
+    }
+
     /// Run the request decider event loop
     #[tracing::instrument(
         skip_all,
@@ -90,21 +214,42 @@ where
         };
 
         let mut signal_stream = self.context.as_signal_stream(run_loop_message_filter);
+        // We ask the other signers for the decisions that we may have
+        // missed while we were offline once we know the chain tip.
+        let mut decision_sync_requested = false;
 
         while let Some(message) = signal_stream.next().await {
+            // Hold on to the message until the database is reachable
+            // again, rather than failing to handle it during a failover.
+            if !self.context.wait_for_database().await {
+                break;
+            }
+
             match message {
                 SignerSignal::Command(SignerCommand::Shutdown) => break,
                 SignerSignal::Command(SignerCommand::P2PPublish(_)) => {}
                 SignerSignal::Event(event) => match event {
                     SignerEvent::P2P(P2PEvent::MessageReceived(msg)) => {
-                        if let Err(error) = self.handle_signer_message(&msg).await {
+                        let span = telemetry::message_span(&msg);
+                        let result = self.handle_signer_message(&msg).instrument(span).await;
+                        if let Err(error) = &result {
                             tracing::error!(%error, "error handling signer message");
                         }
+                        self.record_outcome(&result);
                     }
                     SignerEvent::BitcoinBlockObserved(chain_tip) => {
-                        if let Err(error) = self.handle_new_requests(chain_tip).await {
+                        if !decision_sync_requested {
+                            decision_sync_requested = true;
+                            let _ = self.request_decision_sync(chain_tip).await.inspect_err(
+                                |error| tracing::warn!(%error, "error requesting decision sync"),
+                            );
+                        }
+
+                        let result = self.handle_new_requests(chain_tip).await;
+                        if let Err(error) = &result {
                             tracing::warn!(%error, "error handling new requests; skipping this round");
                         }
+                        self.record_outcome(&result);
 
                         let message = RequestDeciderEvent::NewRequestsHandled(chain_tip).into();
                         // If there is an error here then the application
@@ -125,12 +270,27 @@ where
         Ok(())
     }
 
+    /// Record the outcome of handling an event in the error registry.
+    fn record_outcome(&self, result: &Result<(), Error>) {
+        self.context
+            .state()
+            .error_registry()
+            .record(Subsystem::RequestDecider, result);
+    }
+
     /// Vote on pending deposit requests
     #[tracing::instrument(skip_all, fields(
         bitcoin_tip_hash = %block_ref.block_hash,
         bitcoin_tip_height = %block_ref.block_height,
     ))]
     pub async fn handle_new_requests(&mut self, block_ref: BitcoinBlockRef) -> Result<(), Error> {
+        // We may not be able to reach the other signers, so any decisions
+        // that we make now could diverge from theirs.
+        if self.context.state().is_in_safe_mode() {
+            tracing::warn!("signer is in safe-mode; not submitting decisions");
+            return Ok(());
+        }
+
         let requests_processing_delay = self.context.config().signer.requests_processing_delay;
         if requests_processing_delay > Duration::ZERO {
             tracing::debug!("sleeping before processing new requests");
@@ -146,8 +306,34 @@ where
             .block_hash;
         let signer_public_key = self.signer_public_key();
         let db = self.context.get_storage();
-        // We retry the deposit decisions because some signers' bitcoin nodes might have
-        // been running behind and ignored the previous messages.
+
+        // On a recently bootstrapped database we may not have every block
+        // within our lookback windows yet. Missing blocks only hide
+        // requests from us, so we still decide on the ones that we can
+        // see, but we make the gap visible.
+        let state = self.context.state();
+        if state.is_sbtc_bitcoin_start_height_set() {
+            let depth = self.deposit_lookback().max(self.withdrawal_lookback());
+            let start_height = state.get_sbtc_bitcoin_start_height();
+            let history = db
+                .get_chain_history(&bitcoin_chain_tip, depth, start_height)
+                .await?;
+            if !history.history_complete {
+                tracing::warn!(
+                    available_blocks = history.available_blocks,
+                    requested_blocks = history.requested_blocks,
+                    "bitcoin history is incomplete; requests in missing blocks are not decided"
+                );
+            }
+        }
+
+        // We collect all of our decisions for this block, including the
+        // ones that we retry, and broadcast them together at the end
+        // instead of sending a message per decision.
+        let mut batch = SignerDecisionBatch::default();
+
+        // We retry the decisions because some signers' bitcoin nodes might
+        // have been running behind and ignored the previous messages.
         let deposit_decisions_to_retry = db
             .get_deposit_signer_decisions(
                 &bitcoin_chain_tip,
@@ -155,78 +341,167 @@ where
                 &signer_public_key,
             )
             .await?;
+        batch.deposits.extend(
+            deposit_decisions_to_retry
+                .into_iter()
+                .map(SignerDepositDecision::from),
+        );
 
-        let _ = self
-            .handle_deposit_decisions_to_retry(deposit_decisions_to_retry, &bitcoin_chain_tip)
-            .await
-            .inspect_err(
-                |error| tracing::warn!(%error, "error handling deposit decisions to retry"),
-            );
-
-        let deposit_requests = db
-            .get_pending_deposit_requests(
+        let withdrawal_decisions_to_retry = db
+            .get_withdrawal_signer_decisions(
                 &bitcoin_chain_tip,
-                self.context_window,
+                self.withdrawal_decisions_retry_window,
                 &signer_public_key,
             )
             .await?;
+        batch.withdrawals.extend(
+            withdrawal_decisions_to_retry
+                .into_iter()
+                .map(SignerWithdrawalDecision::from),
+        );
 
-        for deposit_request in deposit_requests {
-            let outpoint = deposit_request.outpoint();
-            let _ = self
-                .handle_pending_deposit_request(deposit_request, &bitcoin_chain_tip)
-                .await
-                .inspect_err(|error| {
-                    tracing::warn!(
-                        %error,
-                        %outpoint,
-                        "error handling new deposit request"
-                    )
-                });
-        }
-
-        let withdrawal_decisions_to_retry = db
-            .get_withdrawal_signer_decisions(
+        let deposit_requests = db
+            .get_pending_deposit_requests(
                 &bitcoin_chain_tip,
-                self.withdrawal_decisions_retry_window,
+                self.deposit_lookback(),
                 &signer_public_key,
             )
             .await?;
 
-        let _ = self
-            .handle_withdrawal_decisions_to_retry(withdrawal_decisions_to_retry, &bitcoin_chain_tip)
-            .await
-            .inspect_err(
-                |error| tracing::warn!(%error, "error handling withdrawal decisions to retry"),
-            );
-
         let withdraw_requests = db
             .get_pending_withdrawal_requests(
                 &bitcoin_chain_tip,
                 &stacks_chain_tip,
-                self.context_window,
+                self.withdrawal_lookback(),
                 &signer_public_key,
             )
-            .await?;
+            .await
+            .map(util::dedup_withdrawal_requests)?;
+
+        let mut pending_requests: Vec<PendingRequest> = deposit_requests
+            .into_iter()
+            .map(PendingRequest::Deposit)
+            .chain(
+                withdraw_requests
+                    .into_iter()
+                    .map(PendingRequest::Withdrawal),
+            )
+            .collect();
+        self.carried_over.prioritize(&mut pending_requests);
+
+        let budget = self
+            .context
+            .config()
+            .signer
+            .request_decision_budget
+            .map(|seconds| Duration::from_secs(seconds.get()));
+        let start = Instant::now();
+        let mut carried_over = CarriedOverRequests::default();
+
+        for request in pending_requests {
+            if budget.is_some_and(|budget| start.elapsed() >= budget) {
+                carried_over.insert(&request);
+                continue;
+            }
+
+            match request {
+                PendingRequest::Deposit(deposit_request) => {
+                    let outpoint = deposit_request.outpoint();
+                    let result = self
+                        .handle_pending_deposit_request(deposit_request, &bitcoin_chain_tip)
+                        .await;
+                    match result {
+                        Ok(decision) => batch.deposits.push(decision),
+                        Err(error) => tracing::warn!(
+                            %error,
+                            %outpoint,
+                            "error handling new deposit request"
+                        ),
+                    }
+                }
+                PendingRequest::Withdrawal(withdraw_request) => {
+                    let request_id = withdraw_request.request_id;
+                    let result = self
+                        .handle_pending_withdrawal_request(withdraw_request, &bitcoin_chain_tip)
+                        .await;
+                    match result {
+                        Ok(decision) => batch.withdrawals.push(decision),
+                        Err(error) => tracing::warn!(
+                            %error,
+                            %request_id,
+                            "error handling new withdrawal request"
+                        ),
+                    }
+                }
+            }
+        }
+
+        Metrics::record_request_decisions(start.elapsed(), &carried_over);
+        if !carried_over.is_empty() {
+            tracing::warn!(
+                num_deposits = carried_over.num_deposits(),
+                num_withdrawals = carried_over.num_withdrawals(),
+                "request decision budget exhausted; carrying requests over to the next block"
+            );
+        }
+        self.carried_over = carried_over;
+
+        self.send_decision_batch(batch, &bitcoin_chain_tip).await;
+
+        Ok(())
+    }
+
+    /// Broadcast our decisions to the other signers, split into as few
+    /// batches as fit within [`SignerDecisionBatch::MAX_SIZE`]. Signers
+    /// running a protocol version before
+    /// [`crate::DECISION_BATCH_PROTOCOL_VERSION`] ignore batches, so
+    /// until every signer has upgraded we send each decision in its own
+    /// message instead. Our decisions are already stored, so a message
+    /// that fails to send is logged and skipped; the decisions in it are
+    /// sent again as retries for as long as they are within the retry
+    /// window.
+    #[tracing::instrument(skip_all, fields(
+        num_deposits = batch.deposits.len(),
+        num_withdrawals = batch.withdrawals.len(),
+    ))]
+    async fn send_decision_batch(
+        &mut self,
+        batch: SignerDecisionBatch,
+        chain_tip: &BitcoinBlockHash,
+    ) {
+        let signer_public_key = self.signer_public_key();
+        let signer_set = self.context.state().current_signer_set().get_signers();
+        let batching = self.context.state().signers_support_protocol_version(
+            signer_set.iter().map(|signer| signer.public_key()),
+            &signer_public_key,
+            crate::DECISION_BATCH_PROTOCOL_VERSION,
+        );
+
+        if !batching {
+            let deposits = batch.deposits.into_iter().map(Payload::from);
+            let withdrawals = batch.withdrawals.into_iter().map(Payload::from);
+            for payload in deposits.chain(withdrawals) {
+                let _ = self
+                    .send_message(payload, chain_tip)
+                    .await
+                    .inspect_err(|error| {
+                        tracing::warn!(%error, "error sending decision, skipping");
+                    });
+            }
+            return;
+        }
 
-        for withdraw_request in withdraw_requests {
-            let request_id = withdraw_request.request_id;
+        for chunk in batch.into_chunks() {
             let _ = self
-                .handle_pending_withdrawal_request(withdraw_request, &bitcoin_chain_tip)
+                .send_message(chunk, chain_tip)
                 .await
                 .inspect_err(|error| {
-                    tracing::warn!(
-                        %error,
-                        %request_id,
-                        "error handling new withdrawal request"
-                    )
+                    tracing::warn!(%error, "error sending decision batch, skipping");
                 });
         }
-
-        Ok(())
     }
 
-    #[tracing::instrument(skip_all)]
+    #[tracing::instrument(skip_all, fields(correlation_id = %msg.inner.payload.correlation_ids()))]
     async fn handle_signer_message(&mut self, msg: &Signed<SignerMessage>) -> Result<(), Error> {
         tracing::trace!(payload = %msg.inner.payload, "handling message");
         match &msg.inner.payload {
@@ -238,9 +513,32 @@ where
                 self.persist_received_withdraw_decision(decision, msg.signer_public_key)
                     .await?;
             }
+            Payload::DecisionSyncRequest(request) => {
+                self.handle_decision_sync_request(request, msg.signer_public_key)
+                    .await?;
+            }
+            Payload::DecisionSyncResponse(response) => {
+                self.handle_decision_sync_response(response, msg.signer_public_key)
+                    .await?;
+            }
+            Payload::SignerDecisionBatch(batch) => {
+                self.handle_decision_batch(batch, msg.signer_public_key)
+                    .await?;
+            }
+            Payload::SweepTxProofRequest(request) => {
+                self.handle_sweep_tx_proof_request(request, msg.signer_public_key)
+                    .await?;
+            }
             Payload::StacksTransactionSignRequest(_)
             | Payload::BitcoinPreSignRequest(_)
             | Payload::BitcoinPreSignAck(_)
+            | Payload::KeyRotationAttestation(_)
+            | Payload::CoordinatorTakeover(_)
+            | Payload::CoordinatorSilenceAttestation(_)
+            | Payload::PeerAddressAnnouncement(_)
+            | Payload::RotateKeysDryRunRequest(_)
+            | Payload::RotateKeysDryRunVerdict(_)
+            | Payload::SweepTxProofResponse(_)
             | Payload::WstsMessage(_)
             | Payload::StacksTransactionSignature(_) => (),
         };
@@ -251,20 +549,23 @@ where
     /// Check whether this signer accepts the deposit request. This
     /// involves:
     ///
-    /// 1. Reach out to the blocklist client and find out whether we can
-    ///    accept the deposit given all the input `scriptPubKey`s of the
-    ///    transaction.
-    /// 2. Check if we are a part of the signing set associated with the
+    /// 1. Check if we are a part of the signing set associated with the
     ///    public key locking the funds.
+    /// 2. Check whether an operator placed an override on the request,
+    ///    in which case the override decides it.
+    /// 3. Otherwise, evaluate the configured decision policies. By default
+    ///    this runs the deposit pre-check, which reaches out to the
+    ///    blocklist client to find out whether we can accept the deposit
+    ///    given all the input `scriptPubKey`s of the transaction.
     ///
-    /// If the block list client is not configured then the first check
-    /// always passes.
-    #[tracing::instrument(skip_all)]
+    /// The decision is stored and returned, so that it can be broadcast
+    /// to the other signers along with the other decisions for the block.
+    #[tracing::instrument(skip_all, fields(correlation_id = %CorrelationId::from(request.outpoint())))]
     pub async fn handle_pending_deposit_request(
         &mut self,
         request: model::DepositRequest,
         chain_tip: &BitcoinBlockHash,
-    ) -> Result<(), Error> {
+    ) -> Result<SignerDepositDecision, Error> {
         let db = self.context.get_storage_mut();
 
         let signer_public_key = self.signer_public_key();
@@ -279,7 +580,9 @@ where
             .await?
             .unwrap_or(false);
 
-        let can_accept = self.can_accept_deposit_request(&request).await?;
+        let can_accept = self
+            .can_accept_deposit_request(&request, can_sign, chain_tip)
+            .await?;
 
         let msg = SignerDepositDecision {
             txid: request.txid.into(),
@@ -296,63 +599,29 @@ where
             can_sign,
         };
 
-        db.write_deposit_signer_decision(&signer_decision).await?;
-
-        self.send_message(msg, chain_tip).await?;
+        util::retry_transient(|| self.write_own_deposit_decision(&signer_decision)).await?;
 
         self.context
             .signal(RequestDeciderEvent::PendingDepositRequestRegistered.into())?;
 
-        Ok(())
-    }
-
-    /// Send the given withdrawal decisions to the other signers for redundancy.
-    #[tracing::instrument(skip_all)]
-    pub async fn handle_withdrawal_decisions_to_retry(
-        &mut self,
-        decisions: Vec<model::WithdrawalSigner>,
-        chain_tip: &BitcoinBlockHash,
-    ) -> Result<(), Error> {
-        for decision in decisions.into_iter().map(SignerWithdrawalDecision::from) {
-            let _ = self
-                .send_message(decision, chain_tip)
-                .await
-                .inspect_err(|error| {
-                    tracing::warn!(%error, "error sending withdrawal decision to retry, skipping");
-                });
-        }
-        Ok(())
-    }
-
-    /// Send the given deposit decisions to the other signers for redundancy.
-    #[tracing::instrument(skip_all)]
-    pub async fn handle_deposit_decisions_to_retry(
-        &mut self,
-        decisions: Vec<model::DepositSigner>,
-        chain_tip: &BitcoinBlockHash,
-    ) -> Result<(), Error> {
-        for decision in decisions.into_iter().map(SignerDepositDecision::from) {
-            let _ = self
-                .send_message(decision, chain_tip)
-                .await
-                .inspect_err(|error| {
-                    tracing::warn!(%error, "error sending deposit decision to retry, skipping");
-                });
-        }
-        Ok(())
+        Ok(msg)
     }
 
-    #[tracing::instrument(skip_all)]
+    #[tracing::instrument(skip_all, fields(
+        correlation_id = %CorrelationId::from(withdrawal_request.qualified_id())
+    ))]
     async fn handle_pending_withdrawal_request(
         &mut self,
         withdrawal_request: model::WithdrawalRequest,
         chain_tip: &BitcoinBlockHash,
-    ) -> Result<(), Error> {
-        // TODO: Do we want to do this on the sender address or the
-        // recipient address?
-        let is_accepted = self
-            .can_accept_withdrawal_request(&withdrawal_request)
+    ) -> Result<SignerWithdrawalDecision, Error> {
+        let decision = self
+            .can_accept_withdrawal_request(&withdrawal_request, chain_tip)
             .await?;
+        let (is_accepted, rejection_reason) = match decision {
+            PolicyDecision::Accept => (true, None),
+            PolicyDecision::Reject { reason } => (false, Some(reason)),
+        };
 
         let msg = SignerWithdrawalDecision {
             request_id: withdrawal_request.request_id,
@@ -367,75 +636,159 @@ where
             signer_pub_key: self.signer_public_key(),
             is_accepted,
             txid: withdrawal_request.txid,
+            rejection_reason,
         };
 
+        util::retry_transient(|| self.write_own_withdrawal_decision(&signer_decision)).await?;
+
         self.context
-            .get_storage_mut()
-            .write_withdrawal_signer_decision(&signer_decision)
+            .signal(RequestDeciderEvent::PendingWithdrawalRequestRegistered.into())?;
+
+        Ok(msg)
+    }
+
+    /// Write our decision on a deposit request, and when we made it, to
+    /// the database in a single storage transaction.
+    async fn write_own_deposit_decision(&self, decision: &DepositSigner) -> Result<(), Error> {
+        let storage = self.context.get_storage_mut();
+        let storage_tx = storage.begin_transaction().await?;
+
+        storage_tx.write_deposit_signer_decision(decision).await?;
+        storage_tx
+            .write_deposit_request_timestamp(
+                &decision.txid,
+                decision.output_index,
+                model::RequestLifecycleStage::Decided,
+            )
             .await?;
 
-        self.send_message(msg, chain_tip).await?;
+        storage_tx.commit().await
+    }
 
-        self.context
-            .signal(RequestDeciderEvent::PendingWithdrawalRequestRegistered.into())?;
+    /// Write our decision on a withdrawal request, and when we made it, to
+    /// the database in a single storage transaction.
+    async fn write_own_withdrawal_decision(
+        &self,
+        decision: &WithdrawalSigner,
+    ) -> Result<(), Error> {
+        let storage = self.context.get_storage_mut();
+        let storage_tx = storage.begin_transaction().await?;
 
-        Ok(())
+        storage_tx
+            .write_withdrawal_signer_decision(decision)
+            .await?;
+        storage_tx
+            .write_withdrawal_request_timestamp(
+                decision.request_id,
+                model::RequestLifecycleStage::Decided,
+            )
+            .await?;
+
+        storage_tx.commit().await
     }
 
     async fn can_accept_withdrawal_request(
         &self,
         req: &model::WithdrawalRequest,
+        chain_tip: &BitcoinBlockHash,
+    ) -> Result<PolicyDecision, Error> {
+        let target = model::OverrideTarget::Withdrawal { request_id: req.request_id };
+        if let Some(decision) = self.override_decision(&target).await? {
+            return Ok(decision);
+        }
+
+        let blocklist_config = self.context.config().blocklist_client.as_ref();
+        let screen_withdrawal_recipients =
+            blocklist_config.is_none_or(|config| config.screen_withdrawal_recipients);
+        let blocklist = BlocklistPolicy::new(self.blocklist_checker.as_ref())
+            .with_withdrawal_screening(screen_withdrawal_recipients)
+            .with_screening_tiers(self.screening_tiers());
+        let ctx = WithdrawalDecisionContext {
+            request: req,
+            chain_tip,
+            network: bitcoin::Network::from(self.context.config().signer.network),
+        };
+
+        self.decision_policy_chain(&blocklist)?
+            .evaluate_withdrawal(&ctx)
+            .await
+    }
+
+    async fn can_accept_deposit_request(
+        &self,
+        req: &model::DepositRequest,
+        can_sign: bool,
+        chain_tip: &BitcoinBlockHash,
     ) -> Result<bool, Error> {
-        // If we have not configured a blocklist checker, then we can
-        // return early.
-        let Some(client) = self.blocklist_checker.as_ref() else {
-            return Ok(true);
+        let target = model::OverrideTarget::Deposit {
+            txid: req.txid,
+            output_index: req.output_index,
         };
+        if let Some(decision) = self.override_decision(&target).await? {
+            return Ok(decision == PolicyDecision::Accept);
+        }
 
-        let network = bitcoin::Network::from(self.context.config().signer.network);
-        let receiver_address = bitcoin::Address::from_script(&req.recipient, network.params())
-            .map_err(|err| {
-                Error::WithdrawalBitcoinAddressFromScript(err, req.request_id, req.block_hash)
-            })?;
+        let blocklist = BlocklistPolicy::new(self.blocklist_checker.as_ref())
+            .with_screening_tiers(self.screening_tiers());
+        let ctx = DepositDecisionContext {
+            request: req,
+            can_sign,
+            chain_tip,
+            network: bitcoin::Network::from(self.context.config().signer.network),
+        };
 
-        let can_accept = client
-            .can_accept(&receiver_address.to_string())
-            .await
-            .inspect_err(|error| tracing::error!(%error, "blocklist client issue"))?;
+        let decision = self
+            .decision_policy_chain(&blocklist)?
+            .evaluate_deposit(&ctx)
+            .await?;
 
-        Ok(can_accept)
+        Ok(decision == PolicyDecision::Accept)
     }
 
-    async fn can_accept_deposit_request(&self, req: &model::DepositRequest) -> Result<bool, Error> {
-        // If we have not configured a blocklist checker, then we can
-        // return early.
-        let Some(client) = self.blocklist_checker.as_ref() else {
-            return Ok(true);
-        };
+    /// Return the amount thresholds of the blocklist policy's screening
+    /// tiers from the blocklist client config.
+    fn screening_tiers(&self) -> ScreeningTiers {
+        self.context
+            .config()
+            .blocklist_client
+            .as_ref()
+            .map(ScreeningTiers::from)
+            .unwrap_or_default()
+    }
 
-        // We turn all the input scriptPubKeys into addresses and check
-        // those with the blocklist client.
-        let bitcoin_network = bitcoin::Network::from(self.context.config().signer.network);
-        let params = bitcoin_network.params();
-        let addresses = req
-            .sender_script_pub_keys
-            .iter()
-            .map(|script_pubkey| bitcoin::Address::from_script(script_pubkey, params))
-            .collect::<Result<Vec<bitcoin::Address>, _>>()
-            .map_err(|err| Error::DepositBitcoinAddressFromScript(err, req.outpoint()))?;
-
-        let responses = futures::stream::iter(&addresses)
-            .then(|address| async { client.can_accept(&address.to_string()).await })
-            .inspect_err(|error| tracing::error!(%error, "blocklist client issue"))
-            .collect::<Vec<_>>()
-            .await
-            .into_iter()
-            .collect::<Result<Vec<_>, _>>()?;
+    /// Return the decision forced by an active operator override of the
+    /// given request, if there is one.
+    async fn override_decision(
+        &self,
+        target: &model::OverrideTarget,
+    ) -> Result<Option<PolicyDecision>, Error> {
+        let request_override = self
+            .context
+            .get_storage()
+            .get_active_request_override(target)
+            .await?;
 
-        // If all of the inputs addresses are fine then we pass the deposit
-        // request.
-        let can_accept = responses.into_iter().all(|res| res);
-        Ok(can_accept)
+        Ok(request_override.map(|request_override| {
+            tracing::info!(
+                override_id = %request_override.id,
+                directive = %request_override.directive,
+                reason = %request_override.reason,
+                "request decided by an operator override"
+            );
+            PolicyDecision::from(&request_override)
+        }))
+    }
+
+    /// Select the decision policies named in the config from the built in
+    /// blocklist policy and the registered policies.
+    fn decision_policy_chain<'a>(
+        &'a self,
+        blocklist: &'a BlocklistPolicy<'a, B>,
+    ) -> Result<DecisionPolicyChain<'a>, Error> {
+        let mut available: Vec<&dyn DecisionPolicy> = vec![blocklist];
+        available.extend(self.decision_policies.iter().map(AsRef::as_ref));
+
+        DecisionPolicyChain::select(&self.context.config().signer.decision_policies, &available)
     }
 
     /// Save the given decision into the database
@@ -449,37 +802,35 @@ where
         decision: &SignerDepositDecision,
         signer_pub_key: PublicKey,
     ) -> Result<(), Error> {
+        if self
+            .store_received_deposit_decision(decision, signer_pub_key)
+            .await?
+        {
+            self.context
+                .signal(RequestDeciderEvent::ReceivedDepositDecision.into())?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the given deposit decision to the database, fetching the
+    /// deposit request from Emily if necessary. Returns whether the
+    /// decision was written.
+    async fn store_received_deposit_decision(
+        &self,
+        decision: &SignerDepositDecision,
+        signer_pub_key: PublicKey,
+    ) -> Result<bool, Error> {
         let txid = decision.txid.into();
         let output_index = decision.output_index;
-        let signer_decision = DepositSigner {
-            txid,
-            output_index,
-            signer_pub_key,
-            can_accept: decision.can_accept,
-            can_sign: decision.can_sign,
-        };
+        let signer_decision = received_deposit_decision(decision, signer_pub_key);
 
         let db = self.context.get_storage_mut();
         // Before storing a decision in the database, we first check to see
         // if we have a record of the associated deposit request. If we
         // don't have a record then fetch it from Emily and store it before
         // storing the decision.
-        if !db.deposit_request_exists(&txid, output_index).await? {
-            tracing::debug!("no record of the deposit request, fetching from emily");
-            let processor = BlockObserver {
-                context: self.context.clone(),
-                bitcoin_block_source: (),
-            };
-            let deposit_request = self
-                .context
-                .get_emily_client()
-                .get_deposit(&txid, output_index)
-                .await?;
-
-            if let Some(request) = deposit_request {
-                processor.load_requests(&[request]).await?;
-            }
-        }
+        self.load_missing_deposit_request(decision).await?;
         // We still might not have a record of the deposit request (perhaps
         // it failed validation). In this case we do not persist the
         // decision and move on.
@@ -490,12 +841,41 @@ where
                 sender = %signer_pub_key,
                 "we still do not have a record of the deposit request"
             );
-            return Ok(());
+            return Ok(false);
         }
         db.write_deposit_signer_decision(&signer_decision).await?;
 
-        self.context
-            .signal(RequestDeciderEvent::ReceivedDepositDecision.into())?;
+        Ok(true)
+    }
+
+    /// Fetch the deposit request that the given decision is about from
+    /// Emily and store it, if we do not have a record of it already.
+    async fn load_missing_deposit_request(
+        &self,
+        decision: &SignerDepositDecision,
+    ) -> Result<(), Error> {
+        let txid = decision.txid.into();
+        let output_index = decision.output_index;
+
+        let db = self.context.get_storage();
+        if db.deposit_request_exists(&txid, output_index).await? {
+            return Ok(());
+        }
+
+        tracing::debug!("no record of the deposit request, fetching from emily");
+        let processor = BlockObserver {
+            context: self.context.clone(),
+            bitcoin_block_source: (),
+        };
+        let deposit_request = self
+            .context
+            .get_emily_client()
+            .get_deposit(&DepositId::new(txid.into(), output_index))
+            .await?;
+
+        if let Some(request) = deposit_request {
+            processor.load_requests(&[request]).await?;
+        }
 
         Ok(())
     }
@@ -506,28 +886,347 @@ where
         decision: &SignerWithdrawalDecision,
         signer_pub_key: PublicKey,
     ) -> Result<(), Error> {
-        let signer_decision = WithdrawalSigner {
-            request_id: decision.request_id,
-            block_hash: decision.block_hash,
-            signer_pub_key,
-            is_accepted: decision.accepted,
-            txid: decision.txid,
+        self.store_received_withdraw_decision(decision, signer_pub_key)
+            .await?;
+
+        self.context
+            .signal(RequestDeciderEvent::ReceivedWithdrawalDecision.into())?;
+
+        Ok(())
+    }
+
+    /// Write the given withdrawal decision to the database, if we have a
+    /// record of the withdrawal request that it is about.
+    async fn store_received_withdraw_decision(
+        &self,
+        decision: &SignerWithdrawalDecision,
+        signer_pub_key: PublicKey,
+    ) -> Result<(), Error> {
+        let signer_decision = received_withdrawal_decision(decision, signer_pub_key);
+
+        let db = self.context.get_storage_mut();
+        if !withdrawal_request_exists(&db, decision).await? {
+            tracing::debug!(
+                correlation_id = %decision.correlation_id(),
+                "we do not have a record of the withdrawal request, skipping its decision"
+            );
+            return Ok(());
+        }
+        db.write_withdrawal_signer_decision(&signer_decision)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Ask the other signers for the decisions that they made on the
+    /// requests confirmed within our lookback window of the given chain
+    /// tip.
+    #[tracing::instrument(skip_all, fields(
+        bitcoin_tip_hash = %block_ref.block_hash,
+        bitcoin_tip_height = %block_ref.block_height,
+    ))]
+    pub async fn request_decision_sync(&mut self, block_ref: BitcoinBlockRef) -> Result<(), Error> {
+        let signer_set = self.context.state().current_signer_set().get_signers();
+        let supported = self.context.state().signers_support_protocol_version(
+            signer_set.iter().map(|signer| signer.public_key()),
+            &self.signer_public_key(),
+            crate::DECISION_SYNC_PROTOCOL_VERSION,
+        );
+        if !supported {
+            tracing::debug!("not every signer supports decision sync; not requesting decisions");
+            return Ok(());
+        }
+
+        let lookback = u64::from(self.deposit_lookback().max(self.withdrawal_lookback()))
+            .min(DecisionSyncRequest::MAX_BLOCK_RANGE - 1);
+
+        let request = DecisionSyncRequest {
+            start_height: block_ref.block_height.saturating_sub(lookback),
+            end_height: block_ref.block_height,
+        };
+
+        tracing::debug!(
+            start_height = %request.start_height,
+            end_height = %request.end_height,
+            "requesting decisions from the other signers"
+        );
+        self.send_message(request, &block_ref.block_hash).await
+    }
+
+    /// Send our own decisions on the requests confirmed in the requested
+    /// range of bitcoin blocks, one page per message, addressed to the
+    /// requesting signer. We answer at most one request from each signer
+    /// per cooldown period, so that a signer cannot make us page through
+    /// our database and flood the network with responses at will.
+    #[tracing::instrument(skip_all, fields(
+        sender = %signer_pub_key,
+        start_height = %request.start_height,
+        end_height = %request.end_height,
+    ))]
+    async fn handle_decision_sync_request(
+        &mut self,
+        request: &DecisionSyncRequest,
+        signer_pub_key: PublicKey,
+    ) -> Result<(), Error> {
+        if !request.is_valid_range() {
+            tracing::warn!("ignoring decision sync request with an invalid block range");
+            return Ok(());
+        }
+        let Some(chain_tip) = self.context.state().bitcoin_chain_tip() else {
+            tracing::debug!("no bitcoin chain tip yet; ignoring decision sync request");
+            return Ok(());
+        };
+        let state = self.context.state();
+        if !state.check_decision_sync_request_rate(signer_pub_key) {
+            tracing::warn!("ignoring decision sync request from a signer that asked recently");
+            return Ok(());
+        }
+
+        let db = self.context.get_storage();
+        let signer_public_key = self.signer_public_key();
+        let page_size = DecisionSyncRequest::PAGE_SIZE;
+
+        for page in 0.. {
+            let offset = page.saturating_mul(u32::from(page_size));
+            let deposits = db
+                .get_deposit_signer_decisions_in_range(
+                    &signer_public_key,
+                    request.start_height,
+                    request.end_height,
+                    page_size,
+                    offset,
+                )
+                .await?;
+            let withdrawals = db
+                .get_withdrawal_signer_decisions_in_range(
+                    &signer_public_key,
+                    request.start_height,
+                    request.end_height,
+                    page_size,
+                    offset,
+                )
+                .await?;
+
+            let full_page = usize::from(page_size);
+            let has_more = deposits.len() == full_page || withdrawals.len() == full_page;
+            let response = DecisionSyncResponse {
+                start_height: request.start_height,
+                end_height: request.end_height,
+                page,
+                deposits: deposits
+                    .into_iter()
+                    .map(SignerDepositDecision::from)
+                    .collect(),
+                withdrawals: withdrawals
+                    .into_iter()
+                    .map(SignerWithdrawalDecision::from)
+                    .collect(),
+                has_more,
+                requester: signer_pub_key,
+            };
+
+            self.send_message(response, &chain_tip.block_hash).await?;
+
+            if !has_more {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Answer a request for a proof that a sweep transaction was
+    /// confirmed on our canonical bitcoin blockchain. The proof is built
+    /// from the merkle proofs and block headers that the block observer
+    /// stores, and we answer even if we do not have one, so that the
+    /// requester does not wait on us.
+    ///
+    /// Our answer is gossiped to every peer, so each peer may only make a
+    /// few requests per minute; we ignore the requests over the limit.
+    #[tracing::instrument(skip_all, fields(sender = %signer_pub_key, txid = %request.txid))]
+    async fn handle_sweep_tx_proof_request(
+        &mut self,
+        request: &SweepTxProofRequest,
+        signer_pub_key: PublicKey,
+    ) -> Result<(), Error> {
+        let Some(chain_tip) = self.context.state().bitcoin_chain_tip() else {
+            tracing::debug!("no bitcoin chain tip yet; ignoring sweep transaction proof request");
+            return Ok(());
         };
+        if !self
+            .context
+            .state()
+            .check_peer_proof_request_rate(signer_pub_key)
+        {
+            tracing::warn!("ignoring sweep transaction proof request from a peer over its limit");
+            Metrics::increment_sweep_tx_proof_requests("rate_limited");
+            return Ok(());
+        }
+
+        let proof = crate::api::get_sweep_tx_proof(
+            &self.context,
+            &chain_tip.block_hash,
+            request.txid.into(),
+        )
+        .await?
+        .map(|proof| SweepTxProof {
+            block_height: proof.block_height,
+            merkle_block: proof.merkle_block,
+            headers: proof.headers,
+        });
+        tracing::debug!(
+            found = proof.is_some(),
+            "answering sweep transaction proof request"
+        );
+        let status = if proof.is_some() {
+            "found"
+        } else {
+            "not_found"
+        };
+        Metrics::increment_sweep_tx_proof_requests(status);
+
+        let response = SweepTxProofResponse { txid: request.txid, proof };
+        self.send_message(response, &chain_tip.block_hash).await
+    }
+
+    /// Save the decisions in a page of a decision sync response that is
+    /// addressed to us. The decisions are authenticated by the signature
+    /// over the message, so they are stored as the decisions of the
+    /// sending signer. Most of them are usually known already, so instead
+    /// of signaling each decision we signal once per page.
+    #[tracing::instrument(skip_all, fields(sender = %signer_pub_key, page = response.page))]
+    async fn handle_decision_sync_response(
+        &mut self,
+        response: &DecisionSyncResponse,
+        signer_pub_key: PublicKey,
+    ) -> Result<(), Error> {
+        if response.requester != self.signer_public_key() {
+            tracing::trace!("ignoring decision sync response addressed to another signer");
+            return Ok(());
+        }
+        let page_size = usize::from(DecisionSyncRequest::PAGE_SIZE);
+        if response.deposits.len() > page_size || response.withdrawals.len() > page_size {
+            tracing::warn!("ignoring decision sync response with an oversized page");
+            return Ok(());
+        }
+
+        for decision in response.deposits.iter() {
+            let _ = self
+                .store_received_deposit_decision(decision, signer_pub_key)
+                .await
+                .inspect_err(|error| {
+                    tracing::warn!(%error, "error persisting synced deposit decision, skipping");
+                });
+        }
 
-        // TODO: we need to check to see if we have the withdrawal request
-        // first.
+        for decision in response.withdrawals.iter() {
+            let _ = self
+                .store_received_withdraw_decision(decision, signer_pub_key)
+                .await
+                .inspect_err(|error| {
+                    tracing::warn!(%error, "error persisting synced withdrawal decision, skipping");
+                });
+        }
 
         self.context
-            .get_storage_mut()
-            .write_withdrawal_signer_decision(&signer_decision)
-            .await?;
+            .signal(RequestDeciderEvent::ReceivedDecisionSyncResponse.into())?;
+
+        Ok(())
+    }
+
+    /// Save the decisions in a batch received from another signer. The
+    /// decisions are authenticated by the signature over the message, so
+    /// they are stored as the decisions of the sending signer.
+    ///
+    /// All decisions of the batch are written in a single storage
+    /// transaction, and we signal once for the whole batch. Deposit
+    /// requests that we do not have a record of are fetched from Emily,
+    /// [`MAX_CONCURRENT_EMILY_FETCHES`] at a time, before the transaction
+    /// begins, and decisions on deposit requests that we still do not
+    /// have a record of are skipped.
+    #[tracing::instrument(skip_all, fields(
+        sender = %signer_pub_key,
+        correlation_id = %batch.correlation_ids(),
+    ))]
+    async fn handle_decision_batch(
+        &mut self,
+        batch: &SignerDecisionBatch,
+        signer_pub_key: PublicKey,
+    ) -> Result<(), Error> {
+        if batch.is_oversized() {
+            tracing::warn!("ignoring oversized decision batch");
+            return Ok(());
+        }
+
+        let this = &*self;
+        futures::stream::iter(batch.deposits.iter())
+            .for_each_concurrent(MAX_CONCURRENT_EMILY_FETCHES, |decision| async move {
+                let _ = this
+                    .load_missing_deposit_request(decision)
+                    .await
+                    .inspect_err(|error| {
+                        tracing::warn!(
+                            %error,
+                            correlation_id = %decision.correlation_id(),
+                            "error fetching deposit request from emily"
+                        );
+                    });
+            })
+            .await;
+
+        util::retry_transient(|| self.write_received_decision_batch(batch, signer_pub_key)).await?;
 
         self.context
-            .signal(RequestDeciderEvent::ReceivedWithdrawalDecision.into())?;
+            .signal(RequestDeciderEvent::ReceivedDecisionBatch.into())?;
 
         Ok(())
     }
 
+    /// Write the decisions in the given batch to the database in a single
+    /// storage transaction, skipping decisions on deposit requests that we
+    /// do not have a record of.
+    async fn write_received_decision_batch(
+        &self,
+        batch: &SignerDecisionBatch,
+        signer_pub_key: PublicKey,
+    ) -> Result<(), Error> {
+        let storage = self.context.get_storage_mut();
+        let storage_tx = storage.begin_transaction().await?;
+
+        for decision in batch.deposits.iter() {
+            let signer_decision = received_deposit_decision(decision, signer_pub_key);
+            let exists = storage_tx
+                .deposit_request_exists(&signer_decision.txid, signer_decision.output_index)
+                .await?;
+            if !exists {
+                tracing::debug!(
+                    correlation_id = %decision.correlation_id(),
+                    "we do not have a record of the deposit request, skipping its decision"
+                );
+                continue;
+            }
+            storage_tx
+                .write_deposit_signer_decision(&signer_decision)
+                .await?;
+        }
+
+        for decision in batch.withdrawals.iter() {
+            if !withdrawal_request_exists(&storage_tx, decision).await? {
+                tracing::debug!(
+                    correlation_id = %decision.correlation_id(),
+                    "we do not have a record of the withdrawal request, skipping its decision"
+                );
+                continue;
+            }
+            let signer_decision = received_withdrawal_decision(decision, signer_pub_key);
+            storage_tx
+                .write_withdrawal_signer_decision(&signer_decision)
+                .await?;
+        }
+
+        storage_tx.commit().await
+    }
+
     #[tracing::instrument(skip_all)]
     async fn send_message(
         &mut self,
@@ -549,15 +1248,69 @@ where
     }
 }
 
+/// Check whether we have a record of the withdrawal request that the given
+/// decision is about.
+async fn withdrawal_request_exists(
+    db: &impl DbRead,
+    decision: &SignerWithdrawalDecision,
+) -> Result<bool, Error> {
+    let requests = db
+        .get_withdrawal_requests_by_id(decision.request_id)
+        .await?;
+    Ok(requests
+        .iter()
+        .any(|request| request.txid == decision.txid && request.block_hash == decision.block_hash))
+}
+
+/// Convert a deposit decision received from the given signer into the
+/// form that we store.
+fn received_deposit_decision(
+    decision: &SignerDepositDecision,
+    signer_pub_key: PublicKey,
+) -> DepositSigner {
+    DepositSigner {
+        txid: decision.txid.into(),
+        output_index: decision.output_index,
+        signer_pub_key,
+        can_accept: decision.can_accept,
+        can_sign: decision.can_sign,
+    }
+}
+
+/// Convert a withdrawal decision received from the given signer into the
+/// form that we store. Other signers do not tell us why they rejected a
+/// request.
+fn received_withdrawal_decision(
+    decision: &SignerWithdrawalDecision,
+    signer_pub_key: PublicKey,
+) -> WithdrawalSigner {
+    WithdrawalSigner {
+        request_id: decision.request_id,
+        block_hash: decision.block_hash,
+        signer_pub_key,
+        is_accepted: decision.accepted,
+        txid: decision.txid,
+        rejection_reason: None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::bitcoin::MockBitcoinInteract;
     use crate::emily_client::MockEmilyInteract;
     use crate::stacks::api::MockStacksInteract;
+    use fake::Fake as _;
+    use fake::Faker;
+    use rand::rngs::OsRng;
+
+    use crate::network::in_memory2::SignerNetworkInstance;
+    use crate::network::in_memory2::WanNetwork;
     use crate::storage::memory::SharedStore;
     use crate::testing;
     use crate::testing::context::*;
 
+    use super::*;
+
     #[allow(clippy::type_complexity)]
     fn test_environment() -> testing::request_decider::TestEnvironment<
         TestContext<
@@ -607,10 +1360,191 @@ mod tests {
             .await;
     }
 
+    #[tokio::test]
+    async fn should_send_individual_decisions_to_outdated_signers() {
+        test_environment()
+            .assert_should_send_individual_decisions_to_outdated_signers()
+            .await;
+    }
+
     #[tokio::test]
     async fn should_store_decisions_received_from_other_signers() {
         test_environment()
             .assert_should_store_decisions_received_from_other_signers()
             .await;
     }
+
+    #[tokio::test]
+    async fn should_sync_decisions_with_rejoining_signer() {
+        test_environment()
+            .assert_should_sync_decisions_with_rejoining_signer()
+            .await;
+    }
+
+    type MockedContext = TestContext<
+        SharedStore,
+        WrappedMock<MockBitcoinInteract>,
+        WrappedMock<MockStacksInteract>,
+        WrappedMock<MockEmilyInteract>,
+    >;
+
+    /// Build a request decider for the given signer on the given network.
+    fn event_loop(
+        ctx: MockedContext,
+        network: &WanNetwork,
+        signer_private_key: PrivateKey,
+    ) -> RequestDeciderEventLoop<MockedContext, SignerNetworkInstance, ()> {
+        RequestDeciderEventLoop {
+            network: network.connect(&ctx).spawn(),
+            context: ctx,
+            blocklist_checker: Some(()),
+            decision_policies: Vec::new(),
+            signer_private_key,
+            context_window: 6,
+            deposit_decisions_retry_window: 1,
+            withdrawal_decisions_retry_window: 1,
+            carried_over: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn decision_sync_requests_are_answered_once_per_cooldown() {
+        let network = WanNetwork::default();
+        let ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .build();
+        ctx.state().set_bitcoin_chain_tip(Faker.fake());
+        let mut decider = event_loop(ctx, &network, PrivateKey::new(&mut OsRng));
+
+        let requester_ctx = TestContext::default_mocked();
+        let mut requester = network.connect(&requester_ctx).spawn();
+        let requester_key = PublicKey::from_private_key(&PrivateKey::new(&mut OsRng));
+
+        let request = DecisionSyncRequest {
+            start_height: 0u64.into(),
+            end_height: 10u64.into(),
+        };
+        decider
+            .handle_decision_sync_request(&request, requester_key)
+            .await
+            .unwrap();
+        decider
+            .handle_decision_sync_request(&request, requester_key)
+            .await
+            .unwrap();
+
+        // We have no decisions, so the first request is answered with a
+        // single empty page, and the second one is not answered at all.
+        let msg = tokio::time::timeout(Duration::from_secs(1), requester.receive())
+            .await
+            .expect("the request was not answered")
+            .unwrap();
+        let Payload::DecisionSyncResponse(response) = &msg.inner.payload else {
+            panic!("expected a decision sync response");
+        };
+        assert_eq!(response.requester, requester_key);
+        assert!(!response.has_more);
+
+        tokio::time::timeout(Duration::from_millis(100), requester.receive())
+            .await
+            .expect_err("the second request was answered");
+    }
+
+    #[tokio::test]
+    async fn decision_sync_responses_are_only_stored_by_the_requester() {
+        let network = WanNetwork::default();
+        let ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .build();
+        // Keep the signal channel open, since the handler signals once
+        // per page.
+        let _signal_rx = ctx.get_signal_receiver();
+        let signer_private_key = PrivateKey::new(&mut OsRng);
+        let mut decider = event_loop(ctx.clone(), &network, signer_private_key);
+
+        let request: model::WithdrawalRequest = Faker.fake();
+        ctx.get_storage_mut()
+            .write_withdrawal_request(&request)
+            .await
+            .unwrap();
+
+        let sender = PublicKey::from_private_key(&PrivateKey::new(&mut OsRng));
+        let known = SignerWithdrawalDecision {
+            request_id: request.request_id,
+            block_hash: request.block_hash,
+            txid: request.txid,
+            accepted: true,
+        };
+        let unknown = SignerWithdrawalDecision {
+            request_id: request.request_id,
+            ..Faker.fake()
+        };
+        let mut response = DecisionSyncResponse {
+            start_height: 0u64.into(),
+            end_height: 10u64.into(),
+            page: 0,
+            deposits: Vec::new(),
+            withdrawals: vec![known, unknown.clone()],
+            has_more: false,
+            requester: PublicKey::from_private_key(&PrivateKey::new(&mut OsRng)),
+        };
+
+        // A response addressed to another signer is dropped.
+        decider
+            .handle_decision_sync_response(&response, sender)
+            .await
+            .unwrap();
+        let db = ctx.get_storage();
+        let stored = db
+            .get_withdrawal_signers(request.request_id, &request.block_hash)
+            .await
+            .unwrap();
+        assert!(stored.is_empty());
+
+        // A response addressed to us is stored, except for the decisions
+        // on withdrawal requests that we do not have a record of.
+        response.requester = PublicKey::from_private_key(&signer_private_key);
+        decider
+            .handle_decision_sync_response(&response, sender)
+            .await
+            .unwrap();
+        let stored = db
+            .get_withdrawal_signers(request.request_id, &request.block_hash)
+            .await
+            .unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].signer_pub_key, sender);
+        assert_eq!(stored[0].txid, request.txid);
+
+        let stored = db
+            .get_withdrawal_signers(request.request_id, &unknown.block_hash)
+            .await
+            .unwrap();
+        assert!(stored.is_empty());
+    }
+
+    #[test]
+    fn carried_over_requests_are_decided_first() {
+        let deposits: Vec<model::DepositRequest> = fake::vec![model::DepositRequest; 3];
+        let withdrawals: Vec<model::WithdrawalRequest> = fake::vec![model::WithdrawalRequest; 3];
+        let mut requests: Vec<PendingRequest> = deposits
+            .into_iter()
+            .map(PendingRequest::Deposit)
+            .chain(withdrawals.into_iter().map(PendingRequest::Withdrawal))
+            .collect();
+
+        let mut carried_over = CarriedOverRequests::default();
+        carried_over.insert(&requests[2]);
+        carried_over.insert(&requests[4]);
+        assert_eq!(carried_over.num_deposits(), 1);
+        assert_eq!(carried_over.num_withdrawals(), 1);
+
+        let original = requests.clone();
+        carried_over.prioritize(&mut requests);
+
+        let expected = [2, 4, 0, 1, 3, 5].map(|index| original[index].clone());
+        assert_eq!(requests, expected);
+    }
 }
@@ -0,0 +1,174 @@
+//! This module contains the calculation of the maximum amount of sBTC
+//! that may still be minted under the total cap.
+//!
+//! The maximum mintable amount is the total cap minus the current sBTC
+//! supply, minus the amounts of deposits that have been swept into the
+//! signers' UTXO but whose mints have not been confirmed on stacks yet.
+//! The in-flight deposits are subtracted so that the signers do not
+//! accept new deposits that would push the supply over the cap once
+//! those pending mints go through.
+//!
+//! The calculation requires a call to the stacks node and a database
+//! query, so the result is cached in the [`SignerState`] and reused for
+//! as long as the chain tips and the total cap stay the same. The block
+//! observer refreshes it on each new bitcoin block, uses it when
+//! validating deposits, and reports it to Emily. The `/info` endpoint
+//! serves the cached value.
+//!
+//! [`SignerState`]: crate::context::SignerState
+
+use bitcoin::Amount;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::context::Context;
+use crate::error::Error;
+use crate::stacks::api::StacksInteract as _;
+use crate::storage::DbRead as _;
+use crate::storage::model::BitcoinBlockHash;
+use crate::storage::model::StacksBlockHash;
+
+/// Return the maximum amount of sBTC that can be minted given the total
+/// cap, the current sBTC supply and the amount of sBTC that is in flight,
+/// that is, swept deposits whose mints have not been confirmed yet.
+///
+/// The result saturates at zero when the supply and the in-flight mints
+/// already meet or exceed the total cap.
+pub fn max_mintable(total_cap: Amount, sbtc_supply: Amount, in_flight: Amount) -> Amount {
+    total_cap
+        .checked_sub(sbtc_supply)
+        .and_then(|remaining| remaining.checked_sub(in_flight))
+        .unwrap_or(Amount::ZERO)
+}
+
+/// The inputs and the result of a maximum mintable calculation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaxMintable {
+    /// The bitcoin chain tip that the calculation was done against.
+    pub bitcoin_chain_tip: BitcoinBlockHash,
+    /// The stacks chain tip anchored to the bitcoin chain tip, if we know
+    /// of one.
+    pub stacks_chain_tip: Option<StacksBlockHash>,
+    /// The total cap on the sBTC supply, in sats.
+    pub total_cap: u64,
+    /// The sBTC supply reported by the sBTC token contract, in sats.
+    pub sbtc_supply: u64,
+    /// The total amount of swept deposits that have not been minted yet,
+    /// in sats.
+    pub in_flight: u64,
+    /// The maximum amount of sBTC that can be minted, in sats.
+    pub max_mintable: u64,
+}
+
+impl MaxMintable {
+    /// The maximum amount of sBTC that can be minted.
+    pub fn amount(&self) -> Amount {
+        Amount::from_sat(self.max_mintable)
+    }
+
+    /// Whether this calculation is still valid for the given chain tips
+    /// and total cap.
+    fn is_current(
+        &self,
+        bitcoin_chain_tip: &BitcoinBlockHash,
+        stacks_chain_tip: Option<&StacksBlockHash>,
+        total_cap: Amount,
+    ) -> bool {
+        &self.bitcoin_chain_tip == bitcoin_chain_tip
+            && self.stacks_chain_tip.as_ref() == stacks_chain_tip
+            && self.total_cap == total_cap.to_sat()
+    }
+}
+
+/// Compute the maximum amount of sBTC that can be minted as of the given
+/// bitcoin chain tip, reusing the cached result in the signer state if it
+/// was computed for the same chain tips and total cap.
+///
+/// The sBTC smart contracts must have been deployed for the sBTC supply
+/// to be readable.
+#[tracing::instrument(skip_all, fields(%bitcoin_chain_tip))]
+pub async fn compute_max_mintable<C: Context>(
+    ctx: &C,
+    bitcoin_chain_tip: &BitcoinBlockHash,
+    total_cap: Amount,
+) -> Result<MaxMintable, Error> {
+    let db = ctx.get_storage();
+    let stacks_chain_tip = db
+        .get_stacks_chain_tip(bitcoin_chain_tip)
+        .await?
+        .map(|block| block.block_hash);
+
+    let state = ctx.state();
+    let cached = state.max_mintable().filter(|cached| {
+        cached.is_current(bitcoin_chain_tip, stacks_chain_tip.as_ref(), total_cap)
+    });
+    if let Some(cached) = cached {
+        tracing::trace!("using the cached maximum mintable amount");
+        return Ok(cached);
+    }
+
+    let config = &ctx.config().signer;
+    let sbtc_supply = ctx
+        .get_stacks_client()
+        .get_sbtc_total_supply(&config.deployer)
+        .await?;
+
+    // Without a stacks chain tip we cannot have observed any sweeps that
+    // are waiting on their mints.
+    let in_flight: u64 = match stacks_chain_tip.as_ref() {
+        Some(stacks_chain_tip) => db
+            .get_swept_deposit_requests(
+                bitcoin_chain_tip,
+                stacks_chain_tip,
+                config.sweep_confirmation_window(),
+            )
+            .await?
+            .iter()
+            .map(|request| request.amount)
+            .sum(),
+        None => 0,
+    };
+    let in_flight = Amount::from_sat(in_flight);
+
+    let mintable = MaxMintable {
+        bitcoin_chain_tip: *bitcoin_chain_tip,
+        stacks_chain_tip,
+        total_cap: total_cap.to_sat(),
+        sbtc_supply: sbtc_supply.to_sat(),
+        in_flight: in_flight.to_sat(),
+        max_mintable: max_mintable(total_cap, sbtc_supply, in_flight).to_sat(),
+    };
+
+    tracing::debug!(
+        total_cap = mintable.total_cap,
+        sbtc_supply = mintable.sbtc_supply,
+        in_flight = mintable.in_flight,
+        max_mintable = mintable.max_mintable,
+        "computed the maximum mintable amount"
+    );
+    state.set_max_mintable(mintable);
+
+    Ok(mintable)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_case::test_case;
+
+    #[test_case(1_000, 0, 0, 1_000; "nothing minted")]
+    #[test_case(1_000, 400, 0, 600; "some minted")]
+    #[test_case(1_000, 400, 100, 500; "some minted and some in flight")]
+    #[test_case(1_000, 1_000, 0, 0; "cap reached")]
+    #[test_case(1_000, 900, 200, 0; "cap reached with in flight mints")]
+    #[test_case(1_000, 1_200, 0, 0; "supply over the cap")]
+    fn max_mintable_saturates(total_cap: u64, supply: u64, in_flight: u64, expected: u64) {
+        let mintable = max_mintable(
+            Amount::from_sat(total_cap),
+            Amount::from_sat(supply),
+            Amount::from_sat(in_flight),
+        );
+        assert_eq!(mintable, Amount::from_sat(expected));
+    }
+}
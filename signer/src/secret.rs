@@ -0,0 +1,107 @@
+//! Wrappers that keep secrets out of logs.
+//!
+//! The signer holds a few values that must never end up in log output:
+//! its private key, the plaintext of its DKG shares, and the encrypted DKG
+//! shares, which are only as safe as the private key that they are
+//! encrypted with. Types that hold such values keep them in a [`Secret`],
+//! whose `Debug` and `Display` implementations print [`REDACTED`] instead
+//! of the value, so that deriving `Debug` on the containing type or
+//! logging it with `?` is safe. The value itself has to be accessed
+//! explicitly with [`Secret::expose_secret`].
+//!
+//! Secrets that we do not control the type of, like the passwords in the
+//! configured endpoint URLs, are scrubbed from the log output instead; see
+//! [`crate::logging::redact_from_logs`].
+
+use serde::Deserialize;
+
+/// The text that is printed in place of a secret value.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// A value that is redacted whenever it is formatted.
+#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(transparent)]
+pub struct Secret<T>(T);
+
+impl<T> Secret<T> {
+    /// Wrap the given secret value.
+    pub const fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Returns a reference to the secret value.
+    ///
+    /// Take care that the returned value does not end up in a log message.
+    pub fn expose_secret(&self) -> &T {
+        &self.0
+    }
+
+    /// Unwraps the secret value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Secret<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> std::fmt::Debug for Secret<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl<T> std::fmt::Display for Secret<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+#[cfg(feature = "testing")]
+impl<T, F> fake::Dummy<F> for Secret<T>
+where
+    T: fake::Dummy<F>,
+{
+    fn dummy_with_rng<R: rand::RngCore + ?Sized>(config: &F, rng: &mut R) -> Self {
+        Self(T::dummy_with_rng(config, rng))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secrets_are_redacted_when_formatted() {
+        let secret = Secret::new(String::from("hunter2"));
+
+        assert_eq!(format!("{secret:?}"), REDACTED);
+        assert_eq!(format!("{secret:#?}"), REDACTED);
+        assert_eq!(format!("{secret}"), REDACTED);
+        assert_eq!(secret.expose_secret(), "hunter2");
+    }
+
+    #[test]
+    fn containers_of_secrets_are_redacted_when_formatted() {
+        #[derive(Debug)]
+        #[allow(dead_code)]
+        struct Container {
+            name: &'static str,
+            secret: Secret<Vec<u8>>,
+        }
+
+        let container = Container {
+            name: "shares",
+            secret: Secret::new(vec![0xde, 0xad, 0xbe, 0xef]),
+        };
+
+        let formatted = format!("{container:?}");
+        assert_eq!(
+            formatted,
+            r#"Container { name: "shares", secret: [REDACTED] }"#
+        );
+    }
+}
@@ -9,6 +9,7 @@ use std::time::Instant;
 
 use bitcoin::Amount;
 use bitcoin::OutPoint;
+use bitcoin::ScriptBuf;
 use blockstack_lib::burnchains::Txid;
 use blockstack_lib::chainstate::nakamoto::NakamotoBlock;
 #[cfg(any(test, feature = "testing"))]
@@ -76,6 +77,10 @@ const EPOCH_3_0_ID: &str = "Epoch30";
 /// stores the status of a withdrawal request.
 const WITHDRAWAL_STATUS_MAP_NAME: &str = "withdrawal-status";
 
+/// This is the name of the MAP in the sbtc-registry smart contract that
+/// stores the withdrawal requests, keyed by their request ID.
+const WITHDRAWAL_REQUESTS_MAP_NAME: &str = "withdrawal-requests";
+
 /// This is the name of the read-only function in the sbtc-registry smart
 /// contract that returns the status of a deposit request.
 const GET_DEPOSIT_STATUS_FN_NAME: &str = "get-deposit-status";
@@ -92,6 +97,10 @@ const GET_TOTAL_SUPPLY_FN_NAME: &str = "get-total-supply";
 /// that stores the current aggregate public key of the signers.
 const CURRENT_AGGREGATE_PUBKEY_DATA_VAR_NAME: &str = "current-aggregate-pubkey";
 
+/// This is the name of the data variable in the sbtc-registry smart contract
+/// that governance sets when the sBTC protocol is paused.
+const IS_PAUSED_DATA_VAR_NAME: &str = "is-paused";
+
 /// This is a dummy STX transfer payload used only for estimating STX
 /// transfer costs.
 static DUMMY_STX_TRANSFER_PAYLOAD: LazyLock<TransactionPayload> = LazyLock::new(|| {
@@ -188,6 +197,24 @@ pub struct SignerSetInfo {
     pub signatures_required: u16,
 }
 
+/// A withdrawal request, as it is stored in the `withdrawal-requests` map
+/// of the sbtc-registry smart contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistryWithdrawalRequest {
+    /// The amount of sBTC, in sats, that is locked for the request.
+    pub amount: u64,
+    /// The maximum fee, in sats, that the user is willing to pay for the
+    /// bitcoin transaction that fulfills the request.
+    pub max_fee: u64,
+    /// The principal whose sBTC is locked for the request.
+    pub sender: PrincipalData,
+    /// The scriptPubKey that the BTC is sent to.
+    pub recipient: ScriptBuf,
+    /// The height of the bitcoin block that anchored the stacks block
+    /// where the request was created.
+    pub block_height: u64,
+}
+
 /// A trait detailing the interface with the Stacks API and Stacks Nodes.
 #[cfg_attr(any(test, feature = "testing"), mockall::automock)]
 pub trait StacksInteract: Send + Sync {
@@ -233,6 +260,18 @@ pub trait StacksInteract: Send + Sync {
         request_id: u64,
     ) -> impl Future<Output = Result<bool, Error>> + Send;
 
+    /// Retrieve the withdrawal request with the given ID from the
+    /// `withdrawal-requests` map of the sbtc-registry smart contract, if
+    /// it exists on the canonical stacks blockchain.
+    ///
+    /// The request is made to `POST
+    /// /v2/map_entry/<contract-principal>/<contract-name>/<map-name>`
+    fn get_withdrawal_request(
+        &self,
+        contract_principal: &StacksAddress,
+        request_id: u64,
+    ) -> impl Future<Output = Result<Option<RegistryWithdrawalRequest>, Error>> + Send;
+
     /// Get the latest account info for the given address.
     fn get_account(
         &self,
@@ -317,6 +356,19 @@ pub trait StacksInteract: Send + Sync {
         &self,
         sender: &StacksAddress,
     ) -> impl Future<Output = Result<Amount, Error>> + Send;
+
+    /// Check whether governance has paused the sBTC smart contracts.
+    ///
+    /// # Notes
+    ///
+    /// The pause flag is read from the `is-paused` data variable in the
+    /// `sbtc-registry` smart contract, which governance sets through the
+    /// `update-is-paused-wrapper` function of the `sbtc-bootstrap-signers`
+    /// contract.
+    fn is_sbtc_contract_paused(
+        &self,
+        deployer: &StacksAddress,
+    ) -> impl Future<Output = Result<bool, Error>> + Send;
 }
 
 /// A slimmed down [`NakamotoBlockHeader`].
@@ -1342,6 +1394,59 @@ fn extract_aggregate_key(value: Value) -> Result<Option<PublicKey>, Error> {
     }
 }
 
+/// Extract a withdrawal request from a Clarity value.
+///
+/// In the sbtc-registry smart contract, withdrawal requests are stored as
+/// tuples in the `withdrawal-requests` map.
+fn extract_withdrawal_request(value: Value) -> Result<RegistryWithdrawalRequest, Error> {
+    let Value::Tuple(TupleData { mut data_map, .. }) = value else {
+        return Err(Error::InvalidStacksResponse(
+            "expected a tuple but got something else",
+        ));
+    };
+
+    let mut remove_u64 = |field: &str| match data_map.remove(field) {
+        Some(Value::UInt(value)) => u64::try_from(value).map_err(|_| Error::TypeConversion),
+        _ => Err(Error::InvalidStacksResponse(
+            "expected a uint field in the withdrawal request",
+        )),
+    };
+    let amount = remove_u64("amount")?;
+    let max_fee = remove_u64("max-fee")?;
+    let block_height = remove_u64("block-height")?;
+
+    let Some(Value::Principal(sender)) = data_map.remove("sender") else {
+        return Err(Error::InvalidStacksResponse(
+            "expected a principal sender in the withdrawal request",
+        ));
+    };
+    let Some(Value::Tuple(TupleData { data_map: mut recipient, .. })) =
+        data_map.remove("recipient")
+    else {
+        return Err(Error::InvalidStacksResponse(
+            "expected a tuple recipient in the withdrawal request",
+        ));
+    };
+    let mut remove_buff = |field: &str| match recipient.remove(field) {
+        Some(Value::Sequence(SequenceData::Buffer(BuffData { data }))) => Ok(data),
+        _ => Err(Error::InvalidStacksResponse(
+            "expected a buffer field in the withdrawal recipient",
+        )),
+    };
+    let version = remove_buff("version")?;
+    let hash_bytes = remove_buff("hashbytes")?;
+    let recipient = sbtc::events::recipient_script_pub_key(&version, &hash_bytes)
+        .map_err(Error::RegistryEvent)?;
+
+    Ok(RegistryWithdrawalRequest {
+        amount,
+        max_fee,
+        sender,
+        recipient,
+        block_height,
+    })
+}
+
 /// Extract a signature threshold from a Clarity value.
 ///
 /// In the sbtc-registry smart contract, the signature threshold is stored
@@ -1481,6 +1586,30 @@ impl StacksInteract for StacksClient {
         }
     }
 
+    async fn get_withdrawal_request(
+        &self,
+        deployer: &StacksAddress,
+        request_id: u64,
+    ) -> Result<Option<RegistryWithdrawalRequest>, Error> {
+        let contract_name = SmartContract::SbtcRegistry;
+        let map_name = ClarityName(WITHDRAWAL_REQUESTS_MAP_NAME);
+
+        let map_entry = Value::UInt(request_id as u128);
+        let result = self
+            .get_map_entry(deployer, contract_name, map_name, &map_entry)
+            .await?;
+
+        // Like above, the stacks node returns the result of `map-get?`,
+        // which is `none` when there is no request with the given ID.
+        match result {
+            Some(Value::Optional(OptionalData { data: Some(value) })) => {
+                extract_withdrawal_request(*value).map(Some)
+            }
+            Some(Value::Optional(OptionalData { data: None })) => Ok(None),
+            _ => Err(Error::InvalidStacksResponse("did not get optional data")),
+        }
+    }
+
     async fn get_account(&self, address: &StacksAddress) -> Result<AccountInfo, Error> {
         self.get_account(address).await
     }
@@ -1652,6 +1781,23 @@ impl StacksInteract for StacksClient {
             )),
         }
     }
+
+    async fn is_sbtc_contract_paused(&self, deployer: &StacksAddress) -> Result<bool, Error> {
+        let result = self
+            .get_data_var(
+                deployer,
+                SmartContract::SbtcRegistry,
+                ClarityName(IS_PAUSED_DATA_VAR_NAME),
+            )
+            .await;
+
+        match result? {
+            Value::Bool(paused) => Ok(paused),
+            _ => Err(Error::InvalidStacksResponse(
+                "expected a bool but got something else",
+            )),
+        }
+    }
 }
 
 impl StacksInteract for ApiFallbackClient<StacksClient> {
@@ -1711,6 +1857,26 @@ impl StacksInteract for ApiFallbackClient<StacksClient> {
         .await
     }
 
+    async fn get_withdrawal_request(
+        &self,
+        contract_principal: &StacksAddress,
+        request_id: u64,
+    ) -> Result<Option<RegistryWithdrawalRequest>, Error> {
+        self.exec(|client, retry| async move {
+            let result = client
+                .get_withdrawal_request(contract_principal, request_id)
+                .await;
+            retry.abort_if(|| {
+                matches!(
+                    result,
+                    Err(Error::InvalidStacksResponse(_) | Error::RegistryEvent(_))
+                )
+            });
+            result
+        })
+        .await
+    }
+
     async fn get_account(&self, address: &StacksAddress) -> Result<AccountInfo, Error> {
         self.exec(|client, _| client.get_account(address)).await
     }
@@ -1784,6 +1950,11 @@ impl StacksInteract for ApiFallbackClient<StacksClient> {
         self.exec(|client, _| client.get_sbtc_total_supply(deployer))
             .await
     }
+
+    async fn is_sbtc_contract_paused(&self, deployer: &StacksAddress) -> Result<bool, Error> {
+        self.exec(|client, _| client.is_sbtc_contract_paused(deployer))
+            .await
+    }
 }
 
 impl TryFrom<&Settings> for ApiFallbackClient<StacksClient> {
@@ -1990,6 +2161,44 @@ mod tests {
         mock.assert();
     }
 
+    #[test_case(200, Some(Value::Bool(true)), Some(true); "paused")]
+    #[test_case(200, Some(Value::Bool(false)), Some(false); "not-paused")]
+    #[test_case(200, Some(Value::UInt(1)), None; "not-a-bool")]
+    #[test_case(404, None, None; "variable-not-defined")]
+    #[tokio::test]
+    async fn is_sbtc_contract_paused_works(
+        status: usize,
+        value: Option<Value>,
+        expected: Option<bool>,
+    ) {
+        // The format of the response JSON is `{"data": "0x<serialized-value>"}` (excluding the proof).
+        let raw_json_response = match value {
+            Some(value) => format!(r#"{{"data":"0x{}"}}"#, value.serialize_to_hex().unwrap()),
+            None => String::new(),
+        };
+
+        let mut stacks_node_server = mockito::Server::new_async().await;
+        let mock = stacks_node_server
+            .mock("GET", "/v2/data_var/ST1PQHQKV0RJXZFY1DGX8MNSNYVE3VGZJSRTPGZGM/sbtc-registry/is-paused?proof=0")
+            .with_status(status)
+            .with_header("content-type", "application/json")
+            .with_body(raw_json_response)
+            .expect(1)
+            .create();
+
+        let client = StacksClient::new(stacks_node_server.url().parse().unwrap()).unwrap();
+        let paused = client
+            .is_sbtc_contract_paused(
+                &StacksAddress::from_string("ST1PQHQKV0RJXZFY1DGX8MNSNYVE3VGZJSRTPGZGM").unwrap(),
+            )
+            .await;
+
+        // A missing pause flag is an error rather than an unpaused
+        // protocol, since the node may be out of sync or misconfigured.
+        assert_eq!(paused.ok(), expected);
+        mock.assert();
+    }
+
     #[test_case(|url| StacksClient::new(url).unwrap(); "stacks-client")]
     #[test_case(|url| ApiFallbackClient::new(vec![StacksClient::new(url).unwrap()]).unwrap(); "fallback-client")]
     #[tokio::test]
@@ -2367,6 +2576,68 @@ mod tests {
         mock.assert();
     }
 
+    #[test_case(true; "existing-request")]
+    #[test_case(false; "missing-request")]
+    #[tokio::test]
+    async fn get_withdrawal_request_works(exists: bool) {
+        let sender = PrincipalData::from(StacksAddress::burn_address(false));
+        let hash_bytes = [7u8; 20];
+        let recipient = TupleData::from_data(vec![
+            ("version".into(), Value::buff_from_byte(0x04)),
+            (
+                "hashbytes".into(),
+                Value::buff_from(hash_bytes.to_vec()).unwrap(),
+            ),
+        ])
+        .unwrap();
+        let request = TupleData::from_data(vec![
+            ("amount".into(), Value::UInt(100_000)),
+            ("max-fee".into(), Value::UInt(1_000)),
+            ("sender".into(), Value::Principal(sender.clone())),
+            ("recipient".into(), Value::Tuple(recipient)),
+            ("block-height".into(), Value::UInt(250)),
+        ])
+        .unwrap();
+
+        let data = exists.then(|| Box::new(Value::Tuple(request)));
+        let clarity_value = Value::Optional(OptionalData { data });
+        let json_response = serde_json::json!({
+            "data": format!("0x{}", clarity_value.serialize_to_hex().unwrap()),
+        });
+        let raw_json_response = serde_json::to_string(&json_response).unwrap();
+
+        // Setup our mock server
+        // POST /v2/map_entry/<contract-principal>/sbtc-registry/withdrawal-requests
+        let mut stacks_node_server = mockito::Server::new_async().await;
+        let mock = stacks_node_server
+            .mock("POST", "/v2/map_entry/ST000000000000000000002AMW42H/sbtc-registry/withdrawal-requests?proof=0")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(&raw_json_response)
+            .expect(1)
+            .create();
+
+        let client =
+            StacksClient::new(url::Url::parse(stacks_node_server.url().as_str()).unwrap()).unwrap();
+
+        let response = client
+            .get_withdrawal_request(&StacksAddress::burn_address(false), 1)
+            .await
+            .unwrap();
+
+        let program =
+            bitcoin::WitnessProgram::new(bitcoin::WitnessVersion::V0, &hash_bytes).unwrap();
+        let expected = RegistryWithdrawalRequest {
+            amount: 100_000,
+            max_fee: 1_000,
+            sender,
+            recipient: ScriptBuf::new_witness_program(&program),
+            block_height: 250,
+        };
+        assert_eq!(response, exists.then_some(expected));
+        mock.assert();
+    }
+
     // Check that if we don't get valid responses from the Stacks node for both
     // the transaction and STX transfer fee estimation requests, we fallback to
     // estimating the fee based on the size of the transaction payload.
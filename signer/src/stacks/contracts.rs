@@ -15,6 +15,11 @@
 //! * [`RotateKeysV1`]: Used for calling the rotate-keys-wrapper function
 //!   in the sbtc-bootstrap-signers contract. This changes the valid caller
 //!   of most sBTC related functions to a new multi-sig wallet.
+//!
+//! The clarity arguments for each of these calls are built through the
+//! typed structs implementing [`ContractFunctionArgs`], one per contract
+//! function, so that argument counts and types are checked at compile
+//! time and the declared ABIs are tested against the contract sources.
 
 use std::collections::BTreeSet;
 use std::future::Future;
@@ -44,13 +49,14 @@ use clarity::vm::types::TypeSignature;
 
 use crate::DEPOSIT_DUST_LIMIT;
 use crate::WITHDRAWAL_BLOCKS_EXPIRY;
-use crate::bitcoin::BitcoinInteract as _;
 use crate::bitcoin::validation::WithdrawalRequestStatus;
 use crate::context::Context;
 use crate::error::Error;
 use crate::keys::PublicKey;
+use crate::message::KeyRotationAttestation;
 use crate::stacks::wallet::SignerWallet;
 use crate::storage::DbRead;
+use crate::storage::model;
 use crate::storage::model::BitcoinBlockHash;
 use crate::storage::model::BitcoinBlockHeight;
 use crate::storage::model::BitcoinBlockRef;
@@ -59,6 +65,7 @@ use crate::storage::model::DkgSharesStatus;
 use crate::storage::model::QualifiedRequestId;
 use crate::storage::model::StacksBlockHash;
 use crate::storage::model::ToLittleEndianOrder as _;
+use crate::storage::util;
 use sbtc::WITHDRAWAL_MIN_CONFIRMATIONS;
 
 use super::api::StacksInteract;
@@ -201,6 +208,210 @@ pub trait AsContractCall {
         C: Context + Send + Sync;
 }
 
+/// A clarity `(buff N)` value with a length that is fixed at compile
+/// time.
+///
+/// All the buffers that the signers pass to the sBTC contracts are either
+/// 32 byte hashes or 33 byte compressed public keys, so we use a fixed
+/// size array rather than a `Vec<u8>` to rule out length mismatches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ClarityBuff<const N: usize>(pub [u8; N]);
+
+impl<const N: usize> From<[u8; N]> for ClarityBuff<N> {
+    fn from(bytes: [u8; N]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl<const N: usize> From<ClarityBuff<N>> for ClarityValue {
+    fn from(buff: ClarityBuff<N>) -> Self {
+        let data = buff.0.to_vec();
+        ClarityValue::Sequence(SequenceData::Buffer(BuffData { data }))
+    }
+}
+
+/// A typed description of the arguments to a public function in one of
+/// the sBTC smart contracts.
+///
+/// The `N` parameter is the arity of the clarity function, so the
+/// declared ABI and the values produced by the builder must have the same
+/// number of arguments or the implementation will not compile. The types
+/// of each argument are enforced by the fields of the implementing
+/// struct.
+pub trait ContractFunctionArgs<const N: usize> {
+    /// The name of the clarity smart contract that defines the function.
+    const CONTRACT_NAME: &'static str;
+    /// The name of the public function in the smart contract.
+    const FUNCTION_NAME: &'static str;
+    /// The name and clarity type of each argument, in the order that
+    /// they appear in the function signature of the deployed contract.
+    const ABI: [(&'static str, &'static str); N];
+    /// Convert the arguments into clarity values, in the same order as
+    /// the [`ContractFunctionArgs::ABI`].
+    fn into_clarity_values(self) -> [ClarityValue; N];
+}
+
+/// The arguments to the complete-deposit-wrapper function in the
+/// sbtc-deposit contract.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompleteDepositWrapperArgs {
+    /// The txid of the deposit transaction, in little-endian byte order.
+    pub txid: ClarityBuff<32>,
+    /// The output index of the deposit UTXO.
+    pub vout_index: u32,
+    /// The amount of sBTC to mint, in sats.
+    pub amount: u64,
+    /// The principal that will receive the minted sBTC.
+    pub recipient: PrincipalData,
+    /// The block hash of the bitcoin block containing the sweep
+    /// transaction, in little-endian byte order.
+    pub burn_hash: ClarityBuff<32>,
+    /// The height of the bitcoin block containing the sweep transaction.
+    pub burn_height: u64,
+    /// The txid of the sweep transaction, in little-endian byte order.
+    pub sweep_txid: ClarityBuff<32>,
+}
+
+impl ContractFunctionArgs<7> for CompleteDepositWrapperArgs {
+    const CONTRACT_NAME: &'static str = "sbtc-deposit";
+    const FUNCTION_NAME: &'static str = "complete-deposit-wrapper";
+    const ABI: [(&'static str, &'static str); 7] = [
+        ("txid", "(buff 32)"),
+        ("vout-index", "uint"),
+        ("amount", "uint"),
+        ("recipient", "principal"),
+        ("burn-hash", "(buff 32)"),
+        ("burn-height", "uint"),
+        ("sweep-txid", "(buff 32)"),
+    ];
+
+    fn into_clarity_values(self) -> [ClarityValue; 7] {
+        [
+            self.txid.into(),
+            ClarityValue::UInt(self.vout_index.into()),
+            ClarityValue::UInt(self.amount.into()),
+            ClarityValue::Principal(self.recipient),
+            self.burn_hash.into(),
+            ClarityValue::UInt(self.burn_height.into()),
+            self.sweep_txid.into(),
+        ]
+    }
+}
+
+/// The arguments to the accept-withdrawal-request function in the
+/// sbtc-withdrawal contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AcceptWithdrawalRequestArgs {
+    /// The ID of the withdrawal request generated by the sbtc-registry
+    /// contract.
+    pub request_id: u64,
+    /// The txid of the sweep transaction that fulfilled the request, in
+    /// little-endian byte order.
+    pub bitcoin_txid: ClarityBuff<32>,
+    /// A bitmap of how the signers voted.
+    pub signer_bitmap: u128,
+    /// The index of the output that fulfilled the request.
+    pub output_index: u32,
+    /// The fee paid by the withdrawal output, in sats.
+    pub fee: u64,
+    /// The block hash of the bitcoin block containing the sweep
+    /// transaction, in little-endian byte order.
+    pub burn_hash: ClarityBuff<32>,
+    /// The height of the bitcoin block containing the sweep transaction.
+    pub burn_height: u64,
+    /// The txid of the sweep transaction, in little-endian byte order.
+    pub sweep_txid: ClarityBuff<32>,
+}
+
+impl ContractFunctionArgs<8> for AcceptWithdrawalRequestArgs {
+    const CONTRACT_NAME: &'static str = "sbtc-withdrawal";
+    const FUNCTION_NAME: &'static str = "accept-withdrawal-request";
+    const ABI: [(&'static str, &'static str); 8] = [
+        ("request-id", "uint"),
+        ("bitcoin-txid", "(buff 32)"),
+        ("signer-bitmap", "uint"),
+        ("output-index", "uint"),
+        ("fee", "uint"),
+        ("burn-hash", "(buff 32)"),
+        ("burn-height", "uint"),
+        ("sweep-txid", "(buff 32)"),
+    ];
+
+    fn into_clarity_values(self) -> [ClarityValue; 8] {
+        [
+            ClarityValue::UInt(self.request_id.into()),
+            self.bitcoin_txid.into(),
+            ClarityValue::UInt(self.signer_bitmap),
+            ClarityValue::UInt(self.output_index.into()),
+            ClarityValue::UInt(self.fee.into()),
+            self.burn_hash.into(),
+            ClarityValue::UInt(self.burn_height.into()),
+            self.sweep_txid.into(),
+        ]
+    }
+}
+
+/// The arguments to the reject-withdrawal-request function in the
+/// sbtc-withdrawal contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RejectWithdrawalRequestArgs {
+    /// The ID of the withdrawal request generated by the sbtc-registry
+    /// contract.
+    pub request_id: u64,
+    /// A bitmap of how the signers voted.
+    pub signer_bitmap: u128,
+}
+
+impl ContractFunctionArgs<2> for RejectWithdrawalRequestArgs {
+    const CONTRACT_NAME: &'static str = "sbtc-withdrawal";
+    const FUNCTION_NAME: &'static str = "reject-withdrawal-request";
+    const ABI: [(&'static str, &'static str); 2] =
+        [("request-id", "uint"), ("signer-bitmap", "uint")];
+
+    fn into_clarity_values(self) -> [ClarityValue; 2] {
+        [
+            ClarityValue::UInt(self.request_id.into()),
+            ClarityValue::UInt(self.signer_bitmap),
+        ]
+    }
+}
+
+/// The arguments to the rotate-keys-wrapper function in the
+/// sbtc-bootstrap-signers contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RotateKeysWrapperArgs {
+    /// The compressed public keys of the new signer set. The contract
+    /// accepts at most [`crate::MAX_KEYS`] keys.
+    pub new_keys: Vec<ClarityBuff<33>>,
+    /// The compressed aggregate public key of the new signer set.
+    pub new_aggregate_pubkey: ClarityBuff<33>,
+    /// The number of signatures required for the new multi-sig wallet.
+    pub new_signature_threshold: u16,
+}
+
+impl ContractFunctionArgs<3> for RotateKeysWrapperArgs {
+    const CONTRACT_NAME: &'static str = "sbtc-bootstrap-signers";
+    const FUNCTION_NAME: &'static str = "rotate-keys-wrapper";
+    const ABI: [(&'static str, &'static str); 3] = [
+        ("new-keys", "(list 128 (buff 33))"),
+        ("new-aggregate-pubkey", "(buff 33)"),
+        ("new-signature-threshold", "uint"),
+    ];
+
+    fn into_clarity_values(self) -> [ClarityValue; 3] {
+        let new_keys = ListData {
+            data: self.new_keys.into_iter().map(ClarityValue::from).collect(),
+            type_signature: RotateKeysV1::list_data_type().clone(),
+        };
+
+        [
+            ClarityValue::Sequence(SequenceData::List(new_keys)),
+            self.new_aggregate_pubkey.into(),
+            ClarityValue::UInt(self.new_signature_threshold.into()),
+        ]
+    }
+}
+
 /// An enum representing all Contract transaction types that the signers can make.
 /// Mainly used for creating StacksTransactionSignRequest messages.
 #[derive(Clone, Debug, Hash, PartialEq)]
@@ -239,7 +450,7 @@ impl From<SmartContract> for StacksTx {
 }
 
 /// An enum representing all contract calls that the signers can make.
-#[derive(Clone, Debug, Hash, PartialEq)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub enum ContractCall {
     /// Call the `complete-deposit-wrapper` function in the `sbtc-deposit`
     /// smart contract
@@ -285,7 +496,7 @@ impl AsTxPayload for ContractCall {
 /// This struct is used to generate a properly formatted Stacks transaction
 /// for calling the complete-deposit-wrapper function in the sbtc-deposit
 /// smart contract.
-#[derive(Clone, Debug, Hash, PartialEq)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub struct CompleteDepositV1 {
     /// The outpoint of the bitcoin UTXO that was spent as a deposit for
     /// sBTC. This is used to identify the deposit transaction when doing
@@ -325,8 +536,10 @@ impl AsTxPayload for CompleteDepositV1 {
 }
 
 impl AsContractCall for CompleteDepositV1 {
-    const CONTRACT_NAME: &'static str = "sbtc-deposit";
-    const FUNCTION_NAME: &'static str = "complete-deposit-wrapper";
+    const CONTRACT_NAME: &'static str =
+        <CompleteDepositWrapperArgs as ContractFunctionArgs<7>>::CONTRACT_NAME;
+    const FUNCTION_NAME: &'static str =
+        <CompleteDepositWrapperArgs as ContractFunctionArgs<7>>::FUNCTION_NAME;
 
     fn deployer_address(&self) -> &StacksAddress {
         &self.deployer
@@ -334,22 +547,16 @@ impl AsContractCall for CompleteDepositV1 {
     /// Construct the input arguments to the complete-deposit-wrapper
     /// contract call.
     fn as_contract_args(&self) -> Vec<ClarityValue> {
-        let txid_data = self.outpoint.txid.to_le_bytes().to_vec();
-        let txid = BuffData { data: txid_data };
-        let sweep_txid_data = self.sweep_txid.to_le_bytes().to_vec();
-        let sweep_txid = BuffData { data: sweep_txid_data };
-        let burn_hash_data = self.sweep_block_hash.to_le_bytes().to_vec();
-        let burn_hash_buff = BuffData { data: burn_hash_data };
-
-        vec![
-            ClarityValue::Sequence(SequenceData::Buffer(txid)),
-            ClarityValue::UInt(self.outpoint.vout as u128),
-            ClarityValue::UInt(self.amount as u128),
-            ClarityValue::Principal(self.recipient.clone()),
-            ClarityValue::Sequence(SequenceData::Buffer(burn_hash_buff)),
-            ClarityValue::UInt(self.sweep_block_height.into()),
-            ClarityValue::Sequence(SequenceData::Buffer(sweep_txid)),
-        ]
+        let args = CompleteDepositWrapperArgs {
+            txid: self.outpoint.txid.to_le_bytes().into(),
+            vout_index: self.outpoint.vout,
+            amount: self.amount,
+            recipient: self.recipient.clone(),
+            burn_hash: self.sweep_block_hash.to_le_bytes().into(),
+            burn_height: *self.sweep_block_height,
+            sweep_txid: self.sweep_txid.to_le_bytes().into(),
+        };
+        Vec::from(args.into_clarity_values())
     }
     /// Validates that the Complete deposit request satisfies the following
     /// criteria:
@@ -405,6 +612,21 @@ impl AsContractCall for CompleteDepositV1 {
 }
 
 impl CompleteDepositV1 {
+    /// Check a complete-deposit contract call that has already been
+    /// confirmed on Stacks.
+    ///
+    /// This runs all the checks in [`CompleteDepositV1::validate`] except
+    /// for the check that the deposit has not been completed already,
+    /// which necessarily fails for confirmed contract calls.
+    pub async fn audit<C>(&self, ctx: &C, req_ctx: &ReqContext) -> Result<(), Error>
+    where
+        C: Context + Send + Sync,
+    {
+        let fee = self.validate_sweep_tx(ctx, req_ctx).await?;
+        let db = ctx.get_storage();
+        self.validate_vars(&db, req_ctx, fee).await
+    }
+
     /// Validate the variables in this transaction match the input in the
     /// deposit request.
     ///
@@ -480,10 +702,11 @@ impl CompleteDepositV1 {
     {
         let db = ctx.get_storage();
         let rpc = ctx.get_bitcoin_client();
-        // First we check that bitcoin-core has a record of the transaction
-        // where we think it should be.
+        // First we check that we have a record of the transaction where we
+        // think it should be, either in our cache or in bitcoin-core.
         let txid = &self.sweep_txid;
-        let Some(sweep_tx) = rpc.get_tx_info(txid, &self.sweep_block_hash).await? else {
+        let sweep_tx = util::get_bitcoin_tx_info(&db, &rpc, txid, &self.sweep_block_hash).await?;
+        let Some(sweep_tx) = sweep_tx else {
             return Err(DepositErrorMsg::SweepTransactionMissing.into_error(req_ctx, self));
         };
         // 3. Check that the signer sweep transaction is on the canonical
@@ -531,6 +754,13 @@ impl CompleteDepositV1 {
             return Err(DepositErrorMsg::InvalidSweep.into_error(req_ctx, self));
         }
 
+        // The assessed fee depends on the weight of the sweep transaction
+        // as it was broadcast, so its witness data must have the shape
+        // that the signers create.
+        sweep_tx
+            .check_witness_data()
+            .map_err(|_| DepositErrorMsg::InvalidSweepWitness.into_error(req_ctx, self))?;
+
         // None is only returned from BitcoinTxInfo::assess_output_fee when:
         // a) The indicated output index is 0 or 1, since or those cannot
         //    be valid output indices for sweep transactions, or
@@ -600,6 +830,10 @@ pub enum DepositErrorMsg {
     /// signers control.
     #[error("the transaction that swept the funds was not one of the signers' transactions")]
     InvalidSweep,
+    /// The witness data of the transaction that swept in the funds does
+    /// not have the shape of the witness data that the signers create.
+    #[error("the transaction that swept the funds has unexpected witness data")]
+    InvalidSweepWitness,
     /// The recipient did not match the recipient in our deposit request
     /// records.
     #[error("recipient did not match the recipient in our deposit request")]
@@ -631,7 +865,7 @@ impl DepositErrorMsg {
 /// This struct is used to generate a properly formatted Stacks transaction
 /// for calling the accept-withdrawal-request function in the
 /// sbtc-withdrawal smart contract.
-#[derive(Clone, Debug, Hash, PartialEq)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub struct AcceptWithdrawalV1 {
     /// The ID of the withdrawal request generated by the
     /// `initiate-withdrawal-request` function in the sbtc-withdrawal smart
@@ -676,31 +910,30 @@ impl AsTxPayload for AcceptWithdrawalV1 {
 }
 
 impl AsContractCall for AcceptWithdrawalV1 {
-    const CONTRACT_NAME: &'static str = "sbtc-withdrawal";
-    const FUNCTION_NAME: &'static str = "accept-withdrawal-request";
+    const CONTRACT_NAME: &'static str =
+        <AcceptWithdrawalRequestArgs as ContractFunctionArgs<8>>::CONTRACT_NAME;
+    const FUNCTION_NAME: &'static str =
+        <AcceptWithdrawalRequestArgs as ContractFunctionArgs<8>>::FUNCTION_NAME;
 
     fn deployer_address(&self) -> &StacksAddress {
         &self.deployer
     }
     fn as_contract_args(&self) -> Vec<ClarityValue> {
-        let txid_data = self.outpoint.txid.to_le_bytes().to_vec();
-        let txid = BuffData { data: txid_data };
-        let burn_hash_data = self.sweep_block_hash.to_le_bytes().to_vec();
-        let burn_hash_buff = BuffData { data: burn_hash_data };
-
-        vec![
-            ClarityValue::UInt(self.id.request_id as u128),
-            ClarityValue::Sequence(SequenceData::Buffer(txid.clone())),
+        let txid: ClarityBuff<32> = self.outpoint.txid.to_le_bytes().into();
+        let args = AcceptWithdrawalRequestArgs {
+            request_id: self.id.request_id,
+            bitcoin_txid: txid,
             // This is the signer bitmap field. See the following for more
             // on why this is fixed at zero.
             // https://github.com/stacks-network/sbtc/issues/1505
-            ClarityValue::UInt(0),
-            ClarityValue::UInt(self.outpoint.vout as u128),
-            ClarityValue::UInt(self.tx_fee as u128),
-            ClarityValue::Sequence(SequenceData::Buffer(burn_hash_buff)),
-            ClarityValue::UInt(self.sweep_block_height.into()),
-            ClarityValue::Sequence(SequenceData::Buffer(txid)),
-        ]
+            signer_bitmap: 0,
+            output_index: self.outpoint.vout,
+            fee: self.tx_fee,
+            burn_hash: self.sweep_block_hash.to_le_bytes().into(),
+            burn_height: *self.sweep_block_height,
+            sweep_txid: txid,
+        };
+        Vec::from(args.into_clarity_values())
     }
     /// Validates that the accept-withdrawal-request satisfies the
     /// following criteria:
@@ -840,10 +1073,11 @@ impl AcceptWithdrawalV1 {
     {
         let db = ctx.get_storage();
         let rpc = ctx.get_bitcoin_client();
-        // First we check that bitcoin-core has a record of the transaction
-        // where we think it should be.
+        // First we check that we have a record of the transaction where we
+        // think it should be, either in our cache or in bitcoin-core.
         let txid = &self.outpoint.txid;
-        let Some(sweep_tx) = rpc.get_tx_info(txid, &self.sweep_block_hash).await? else {
+        let sweep_tx = util::get_bitcoin_tx_info(&db, &rpc, txid, &self.sweep_block_hash).await?;
+        let Some(sweep_tx) = sweep_tx else {
             return Err(WithdrawalErrorMsg::SweepTransactionMissing.into_error(req_ctx, self));
         };
         // 3. That the signer bitcoin transaction sweeping out the users'
@@ -863,6 +1097,13 @@ impl AcceptWithdrawalV1 {
         if !in_canonical_bitcoin_blockchain {
             return Err(WithdrawalErrorMsg::SweepTransactionReorged.into_error(req_ctx, self));
         }
+        // The assessed fee depends on the weight of the sweep transaction
+        // as it was broadcast, so its witness data must have the shape
+        // that the signers create.
+        sweep_tx
+            .check_witness_data()
+            .map_err(|_| WithdrawalErrorMsg::InvalidSweepWitness.into_error(req_ctx, self))?;
+
         // 4. That the sweep transaction has the UTXO indicated by the
         //    outpoint.
         //
@@ -986,6 +1227,10 @@ pub enum WithdrawalErrorMsg {
     /// signers control.
     #[error("the transaction that swept the funds was not one of the signers' transactions")]
     InvalidSweep,
+    /// The witness data of the transaction that swept out the funds does
+    /// not have the shape of the witness data that the signers create.
+    #[error("the transaction that swept the funds has unexpected witness data")]
+    InvalidSweepWitness,
     /// The recipient did not match the recipient in our withdrawal request
     /// records.
     #[error("recipient did not match the recipient in our withdrawal request")]
@@ -1074,7 +1319,7 @@ impl WithdrawalRejectErrorMsg {
 /// This struct is used to generate a properly formatted Stacks transaction
 /// for calling the reject-withdrawal-request function in the
 /// sbtc-withdrawal smart contract.
-#[derive(Clone, Debug, Hash, PartialEq)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub struct RejectWithdrawalV1 {
     /// The ID of the withdrawal request generated by the
     /// `initiate-withdrawal-request` function in the sbtc-withdrawal smart
@@ -1103,20 +1348,23 @@ impl AsTxPayload for RejectWithdrawalV1 {
 }
 
 impl AsContractCall for RejectWithdrawalV1 {
-    const CONTRACT_NAME: &'static str = "sbtc-withdrawal";
-    const FUNCTION_NAME: &'static str = "reject-withdrawal-request";
+    const CONTRACT_NAME: &'static str =
+        <RejectWithdrawalRequestArgs as ContractFunctionArgs<2>>::CONTRACT_NAME;
+    const FUNCTION_NAME: &'static str =
+        <RejectWithdrawalRequestArgs as ContractFunctionArgs<2>>::FUNCTION_NAME;
 
     fn deployer_address(&self) -> &StacksAddress {
         &self.deployer
     }
     fn as_contract_args(&self) -> Vec<ClarityValue> {
-        vec![
-            ClarityValue::UInt(self.id.request_id as u128),
+        let args = RejectWithdrawalRequestArgs {
+            request_id: self.id.request_id,
             // This is the signer bitmap field. See the following for more
             // on why this is fixed at zero.
             // https://github.com/stacks-network/sbtc/issues/1505
-            ClarityValue::UInt(0),
-        ]
+            signer_bitmap: 0,
+        };
+        Vec::from(args.into_clarity_values())
     }
     /// Validates that the reject-withdrawal-request satisfies the
     /// following criteria:
@@ -1217,7 +1465,7 @@ impl AsContractCall for RejectWithdrawalV1 {
 /// This struct is used to generate a properly formatted Stacks transaction
 /// for calling the rotate-keys-wrapper function in the
 /// sbtc-bootstrap-signers smart contract.
-#[derive(Clone, Debug, Hash, PartialEq)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub struct RotateKeysV1 {
     /// The new set of public keys for all known signers during this
     /// PoX cycle.
@@ -1266,6 +1514,30 @@ impl RotateKeysV1 {
         }
     }
 
+    /// Return the number of distinct members of the new signer set that
+    /// have made a valid attestation to this key rotation.
+    ///
+    /// Attestations made by signers outside of the new signer set, for
+    /// different key rotation details, or with invalid signatures are
+    /// ignored.
+    pub fn attestation_count(&self, attestations: &[model::KeyRotationAttestation]) -> usize {
+        attestations
+            .iter()
+            .filter(|entry| self.new_keys.contains(&entry.signer_public_key))
+            .filter(|entry| {
+                KeyRotationAttestation::try_from(*entry).is_ok_and(|attestation| {
+                    attestation.attests_to(
+                        &self.aggregate_key,
+                        &self.new_keys,
+                        self.signatures_required,
+                    ) && attestation.verify(&entry.signer_public_key).is_ok()
+                })
+            })
+            .map(|entry| entry.signer_public_key)
+            .collect::<BTreeSet<_>>()
+            .len()
+    }
+
     /// This function returns the clarity description of one of the inputs
     /// to the contract call.
     ///
@@ -1297,8 +1569,10 @@ impl AsTxPayload for RotateKeysV1 {
 }
 
 impl AsContractCall for RotateKeysV1 {
-    const CONTRACT_NAME: &'static str = "sbtc-bootstrap-signers";
-    const FUNCTION_NAME: &'static str = "rotate-keys-wrapper";
+    const CONTRACT_NAME: &'static str =
+        <RotateKeysWrapperArgs as ContractFunctionArgs<3>>::CONTRACT_NAME;
+    const FUNCTION_NAME: &'static str =
+        <RotateKeysWrapperArgs as ContractFunctionArgs<3>>::FUNCTION_NAME;
 
     fn deployer_address(&self) -> &StacksAddress {
         &self.deployer
@@ -1311,30 +1585,20 @@ impl AsContractCall for RotateKeysV1 {
     /// The signature to this function is:
     ///
     ///   (new-keys (list 128 (buff 33))) (new-aggregate-pubkey (buff 33))
+    ///   (new-signature-threshold uint)
     fn as_contract_args(&self) -> Vec<ClarityValue> {
-        let new_key_data: Vec<ClarityValue> = self
-            .new_keys
-            .iter()
-            .map(|pk| {
-                let data = pk.serialize().to_vec();
-                ClarityValue::Sequence(SequenceData::Buffer(BuffData { data }))
-            })
-            .collect();
-
-        let new_keys = ListData {
-            data: new_key_data,
-            type_signature: Self::list_data_type().clone(),
+        // The public keys need to be exactly 33 bytes in this contract
+        // call, which is what the compressed serialization gives us.
+        let args = RotateKeysWrapperArgs {
+            new_keys: self
+                .new_keys
+                .iter()
+                .map(|pk| pk.serialize().into())
+                .collect(),
+            new_aggregate_pubkey: self.aggregate_key.serialize().into(),
+            new_signature_threshold: self.signatures_required,
         };
-
-        // The public key needs to be exactly 33 bytes in this contract
-        // call.
-        let key: [u8; 33] = self.aggregate_key.serialize();
-
-        vec![
-            ClarityValue::Sequence(SequenceData::List(new_keys)),
-            ClarityValue::Sequence(SequenceData::Buffer(BuffData { data: key.to_vec() })),
-            ClarityValue::UInt(self.signatures_required as u128),
-        ]
+        Vec::from(args.into_clarity_values())
     }
 
     /// Validates that the rotate-keys-wrapper satisfies the following
@@ -1350,6 +1614,9 @@ impl AsContractCall for RotateKeysV1 {
     ///    most recent DKG.
     /// 6. That there are no other rotate-keys contract calls with these same
     ///    details already confirmed on the canonical Stacks blockchain.
+    /// 7. When key rotation attestations are required, that at least the
+    ///    signature threshold of the new signer set have attested to this
+    ///    key rotation.
     async fn validate<C>(&self, ctx: &C, req_ctx: &ReqContext) -> Result<(), Error>
     where
         C: Context + Send + Sync,
@@ -1403,6 +1670,18 @@ impl AsContractCall for RotateKeysV1 {
             return Err(RotateKeysErrorMsg::KeyRotationExists.into_error(req_ctx, self));
         }
 
+        // 7. When key rotation attestations are required, that at least the
+        //    signature threshold of the new signer set have attested to
+        //    this key rotation.
+        if ctx.config().signer.require_key_rotation_attestations {
+            let attestations = db
+                .get_key_rotation_attestations(&self.aggregate_key)
+                .await?;
+            if self.attestation_count(&attestations) < usize::from(self.signatures_required) {
+                return Err(RotateKeysErrorMsg::InsufficientAttestations.into_error(req_ctx, self));
+            }
+        }
+
         Ok(())
     }
 }
@@ -1457,6 +1736,10 @@ pub enum RotateKeysErrorMsg {
     /// not passed verification.
     #[error("the shares associated with the aggregate key have not passes verification")]
     DkgSharesNotVerified,
+    /// Fewer signers of the new signer set than the signature threshold
+    /// have attested to the key rotation.
+    #[error("too few signers have attested to the key rotation")]
+    InsufficientAttestations,
 }
 
 impl RotateKeysErrorMsg {
@@ -1602,6 +1885,7 @@ mod tests {
     use secp256k1::SecretKey;
 
     use crate::config::NetworkKind;
+    use crate::keys::PrivateKey;
     use crate::storage::model::StacksBlockHash;
     use crate::storage::model::StacksTxId;
     use crate::testing::get_rng;
@@ -1688,6 +1972,271 @@ mod tests {
         let _ = call.as_contract_call();
     }
 
+    #[test]
+    fn rotate_keys_attestation_count_only_counts_valid_attestations() {
+        let mut rng = get_rng();
+        let private_keys: Vec<PrivateKey> = (0..4).map(|_| PrivateKey::new(&mut rng)).collect();
+        let public_keys: Vec<PublicKey> = private_keys
+            .iter()
+            .map(PublicKey::from_private_key)
+            .collect();
+        // The last key is not part of the new signer set.
+        let wallet = SignerWallet::new(&public_keys[..3], 2, NetworkKind::Testnet, 0).unwrap();
+        let deployer = StacksAddress::burn_address(false);
+        let aggregate_key: PublicKey = fake::Faker.fake_with_rng(&mut rng);
+        let call = RotateKeysV1::new(&wallet, deployer, &aggregate_key);
+
+        let attest = |private_key: &PrivateKey, signatures_required: u16| {
+            let attestation = KeyRotationAttestation::new(
+                private_key,
+                aggregate_key,
+                &call.new_keys,
+                signatures_required,
+            );
+            let public_key = PublicKey::from_private_key(private_key);
+            model::KeyRotationAttestation::new(public_key, &attestation)
+        };
+
+        let mut attestations = vec![attest(&private_keys[0], 2)];
+        assert_eq!(call.attestation_count(&attestations), 1);
+
+        // Duplicates, outsiders and attestations to different details are
+        // not counted.
+        attestations.push(attest(&private_keys[0], 2));
+        attestations.push(attest(&private_keys[3], 2));
+        attestations.push(attest(&private_keys[1], 3));
+        assert_eq!(call.attestation_count(&attestations), 1);
+
+        // An attestation claimed by a signer that did not sign it is not
+        // counted either.
+        let mut forged = attest(&private_keys[0], 2);
+        forged.signer_public_key = public_keys[2];
+        attestations.push(forged);
+        assert_eq!(call.attestation_count(&attestations), 1);
+
+        attestations.push(attest(&private_keys[1], 2));
+        assert_eq!(call.attestation_count(&attestations), 2);
+    }
+
+    /// Return the name and type of each argument of the given public
+    /// function in the clarity source, with whitespace in the types
+    /// normalized to single spaces.
+    fn parse_public_function_args(source: &str, function_name: &str) -> Vec<(String, String)> {
+        let prefix = format!("(define-public ({function_name}");
+        let start = source
+            .match_indices(&prefix)
+            .map(|(idx, _)| idx + "(define-public ".len())
+            .find(|idx| {
+                let after = &source[idx + function_name.len() + 1..];
+                after.starts_with(char::is_whitespace)
+            })
+            .unwrap();
+
+        // Collect the top level parenthesized groups after the function
+        // name, each of which is a `(name type)` pair.
+        let mut args = Vec::new();
+        let mut depth = 0;
+        let mut group_start = 0;
+        for (idx, ch) in source[start..].char_indices() {
+            match ch {
+                '(' => {
+                    depth += 1;
+                    if depth == 2 {
+                        group_start = idx;
+                    }
+                }
+                ')' => {
+                    depth -= 1;
+                    if depth == 1 {
+                        let group = &source[start + group_start + 1..start + idx];
+                        let (name, ty) = group.trim().split_once(char::is_whitespace).unwrap();
+                        let ty = ty.split_whitespace().collect::<Vec<_>>().join(" ");
+                        args.push((name.to_string(), ty));
+                    }
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+        args
+    }
+
+    /// Check whether the given clarity value is admitted by the type in
+    /// one of our declared ABIs.
+    fn value_matches_type(value: &ClarityValue, ty: &str) -> bool {
+        if let Some(inner) = ty.strip_prefix("(list ").and_then(|t| t.strip_suffix(')')) {
+            let (max_len, item_type) = inner.split_once(' ').unwrap();
+            let max_len: usize = max_len.parse().unwrap();
+            return match value {
+                ClarityValue::Sequence(SequenceData::List(list)) => {
+                    list.data.len() <= max_len
+                        && list.data.iter().all(|v| value_matches_type(v, item_type))
+                }
+                _ => false,
+            };
+        }
+        if let Some(len) = ty.strip_prefix("(buff ").and_then(|t| t.strip_suffix(')')) {
+            let max_len: usize = len.parse().unwrap();
+            return match value {
+                ClarityValue::Sequence(SequenceData::Buffer(buff)) => buff.data.len() <= max_len,
+                _ => false,
+            };
+        }
+        match ty {
+            "uint" => matches!(value, ClarityValue::UInt(_)),
+            "principal" => matches!(value, ClarityValue::Principal(_)),
+            _ => panic!("unexpected clarity type in ABI: {ty}"),
+        }
+    }
+
+    fn assert_abi_matches_contract<const N: usize, T>(contract: SmartContract)
+    where
+        T: ContractFunctionArgs<N>,
+    {
+        assert_eq!(contract.contract_name(), T::CONTRACT_NAME);
+        let actual = parse_public_function_args(contract.contract_body(), T::FUNCTION_NAME);
+        let expected: Vec<(String, String)> = T::ABI
+            .iter()
+            .map(|(name, ty)| (name.to_string(), ty.to_string()))
+            .collect();
+        assert_eq!(actual, expected, "ABI mismatch for {}", T::FUNCTION_NAME);
+    }
+
+    fn assert_args_match_abi<const N: usize, T>(args: &[ClarityValue])
+    where
+        T: ContractFunctionArgs<N>,
+    {
+        assert_eq!(args.len(), N);
+        for (value, (name, ty)) in args.iter().zip(T::ABI) {
+            assert!(
+                value_matches_type(value, ty),
+                "bad value for {name}: {value:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn contract_function_args_match_deployed_contract_abis() {
+        assert_abi_matches_contract::<7, CompleteDepositWrapperArgs>(SmartContract::SbtcDeposit);
+        assert_abi_matches_contract::<8, AcceptWithdrawalRequestArgs>(
+            SmartContract::SbtcWithdrawal,
+        );
+        assert_abi_matches_contract::<2, RejectWithdrawalRequestArgs>(
+            SmartContract::SbtcWithdrawal,
+        );
+        assert_abi_matches_contract::<3, RotateKeysWrapperArgs>(
+            SmartContract::SbtcBootstrapSigners,
+        );
+    }
+
+    #[test]
+    fn contract_call_args_round_trip_through_typed_builders() {
+        let mut rng = get_rng();
+        let outpoint = OutPoint {
+            txid: BitcoinTxId::from([1; 32]).into(),
+            vout: 3,
+        };
+        let sweep_block_hash = BitcoinBlockHash::from([2; 32]);
+
+        let call = CompleteDepositV1 {
+            outpoint,
+            amount: 15000,
+            recipient: PrincipalData::from(StacksAddress::burn_address(true)),
+            deployer: StacksAddress::burn_address(false),
+            sweep_txid: BitcoinTxId::from([3; 32]),
+            sweep_block_hash,
+            sweep_block_height: 7u64.into(),
+        };
+        let args = call.as_contract_args();
+        assert_args_match_abi::<7, CompleteDepositWrapperArgs>(&args);
+        assert_eq!(
+            args[0],
+            ClarityValue::from(ClarityBuff(outpoint.txid.to_le_bytes()))
+        );
+        assert_eq!(args[1], ClarityValue::UInt(3));
+        assert_eq!(args[2], ClarityValue::UInt(15000));
+        assert_eq!(args[3], ClarityValue::Principal(call.recipient.clone()));
+        assert_eq!(
+            args[4],
+            ClarityValue::from(ClarityBuff(sweep_block_hash.to_le_bytes()))
+        );
+        assert_eq!(args[5], ClarityValue::UInt(7));
+        assert_eq!(
+            args[6],
+            ClarityValue::from(ClarityBuff(call.sweep_txid.to_le_bytes()))
+        );
+
+        let id = QualifiedRequestId {
+            request_id: 43,
+            txid: StacksTxId::from([0; 32]),
+            block_hash: StacksBlockHash::from([0; 32]),
+        };
+        let call = AcceptWithdrawalV1 {
+            id: id.clone(),
+            outpoint,
+            tx_fee: 125,
+            signer_bitmap: 0,
+            deployer: StacksAddress::burn_address(false),
+            sweep_block_hash,
+            sweep_block_height: 8u64.into(),
+        };
+        let args = call.as_contract_args();
+        assert_args_match_abi::<8, AcceptWithdrawalRequestArgs>(&args);
+        assert_eq!(args[0], ClarityValue::UInt(43));
+        assert_eq!(
+            args[1],
+            ClarityValue::from(ClarityBuff(outpoint.txid.to_le_bytes()))
+        );
+        assert_eq!(args[2], ClarityValue::UInt(0));
+        assert_eq!(args[3], ClarityValue::UInt(3));
+        assert_eq!(args[4], ClarityValue::UInt(125));
+        assert_eq!(
+            args[5],
+            ClarityValue::from(ClarityBuff(sweep_block_hash.to_le_bytes()))
+        );
+        assert_eq!(args[6], ClarityValue::UInt(8));
+        assert_eq!(args[7], args[1]);
+
+        let call = RejectWithdrawalV1 {
+            id,
+            signer_bitmap: 0,
+            deployer: StacksAddress::burn_address(false),
+        };
+        let args = call.as_contract_args();
+        assert_args_match_abi::<2, RejectWithdrawalRequestArgs>(&args);
+        assert_eq!(args, vec![ClarityValue::UInt(43), ClarityValue::UInt(0)]);
+
+        let secret_keys = [
+            SecretKey::new(&mut rng),
+            SecretKey::new(&mut rng),
+            SecretKey::new(&mut rng),
+        ];
+        let public_keys = secret_keys.map(|sk| sk.public_key(SECP256K1).into());
+        let wallet = SignerWallet::new(&public_keys, 2, NetworkKind::Testnet, 0).unwrap();
+        let aggregate_key: PublicKey = fake::Faker.fake_with_rng(&mut rng);
+        let call = RotateKeysV1::new(&wallet, StacksAddress::burn_address(false), &aggregate_key);
+
+        let args = call.as_contract_args();
+        assert_args_match_abi::<3, RotateKeysWrapperArgs>(&args);
+        let ClarityValue::Sequence(SequenceData::List(keys)) = &args[0] else {
+            panic!("new-keys is not a list");
+        };
+        assert_eq!(&keys.type_signature, RotateKeysV1::list_data_type());
+        let expected_keys: Vec<ClarityValue> = call
+            .new_keys
+            .iter()
+            .map(|pk| ClarityValue::from(ClarityBuff(pk.serialize())))
+            .collect();
+        assert_eq!(keys.data, expected_keys);
+        assert_eq!(
+            args[1],
+            ClarityValue::from(ClarityBuff(aggregate_key.serialize()))
+        );
+        assert_eq!(args[2], ClarityValue::UInt(2));
+    }
+
     #[test_case::test_case(SmartContract::SbtcBootstrapSigners; "sbtc-bootstrap")]
     #[test_case::test_case(SmartContract::SbtcRegistry; "sbtc-registry")]
     #[test_case::test_case(SmartContract::SbtcDeposit; "sbtc-deposit")]
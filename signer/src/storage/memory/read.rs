@@ -6,6 +6,8 @@ use crate::{
         utxo::SignerUtxo,
         validation::{DepositRequestReport, WithdrawalRequestReport},
     },
+    config::DepositConfirmationPolicy,
+    deposit_precheck,
     error::Error,
     keys::{PublicKey, PublicKeyXOnly, SignerScriptPubKey as _},
     storage::{
@@ -88,11 +90,21 @@ impl DbRead for SharedStore {
         Ok(result)
     }
 
+    async fn get_recent_deposit_requests(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+    ) -> Result<Vec<model::DepositRequest>, Error> {
+        let store = self.lock().await;
+        Ok(store.get_deposit_requests(chain_tip, context_window))
+    }
+
     async fn get_pending_accepted_deposit_requests(
         &self,
         chain_tip: &model::BitcoinBlockRef,
         context_window: u16,
         threshold: u16,
+        confirmation_policy: &DepositConfirmationPolicy,
     ) -> Result<Vec<model::DepositRequest>, Error> {
         let store = self.lock().await;
         let deposit_requests = store.get_deposit_requests(&chain_tip.block_hash, context_window);
@@ -127,9 +139,19 @@ impl DbRead for SharedStore {
                     .filter(|block_hash| canonical_bitcoin_blocks.contains(block_hash))
                     .filter_map(|block_hash| store.bitcoin_blocks.get(block_hash))
                     .map(|block_included: &model::BitcoinBlock| {
+                        // We never store deposits with a disabled lock time.
+                        let lock_time_in_blocks =
+                            bitcoin::relative::LockTime::from_consensus(deposit_request.lock_time)
+                                .map(deposit_precheck::lock_time_in_blocks)
+                                .unwrap_or_default();
                         let unlock_height =
-                            block_included.block_height + deposit_request.lock_time as u64;
+                            block_included.block_height + u64::from(lock_time_in_blocks);
+                        let confirmations = (*chain_tip.block_height + 1)
+                            .saturating_sub(*block_included.block_height);
+                        let required_confirmations =
+                            confirmation_policy.required_confirmations(deposit_request.amount);
                         unlock_height >= minimum_acceptable_unlock_height
+                            && confirmations >= u64::from(required_confirmations)
                     })
                     .next()
                     .unwrap_or(false)
@@ -463,6 +485,59 @@ impl DbRead for SharedStore {
         get_utxo(&aggregate_key, sbtc_txs)
     }
 
+    async fn get_signer_utxos(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Vec<SignerUtxo>, Error> {
+        let Some(dkg_shares) = self.get_latest_encrypted_dkg_shares().await? else {
+            return Ok(Vec::new());
+        };
+        let aggregate_key = dkg_shares.aggregate_key;
+        let script_pubkey = aggregate_key.signers_script_pubkey();
+        let store = self.lock().await;
+        let bitcoin_blocks = &store.bitcoin_blocks;
+        let first = bitcoin_blocks.get(chain_tip);
+
+        let context_window = 1000;
+        let txids: BTreeSet<&model::BitcoinTxId> =
+            std::iter::successors(first, |block| bitcoin_blocks.get(&block.parent_hash))
+                .take(context_window as usize)
+                .filter_map(|block| store.bitcoin_block_to_transactions.get(&block.block_hash))
+                .flatten()
+                .collect();
+
+        let spent: HashSet<(model::BitcoinTxId, u32)> = txids
+            .iter()
+            .filter_map(|txid| store.bitcoin_prevouts.get(*txid))
+            .flatten()
+            .map(|prevout| (prevout.prevout_txid, prevout.prevout_output_index))
+            .collect();
+
+        let mut utxos: Vec<SignerUtxo> = txids
+            .iter()
+            .filter_map(|txid| store.bitcoin_outputs.get(*txid))
+            .flatten()
+            .filter(|output| output.output_type == model::TxOutputType::SignersOutput)
+            .filter(|output| *output.script_pubkey == script_pubkey)
+            .filter(|output| !spent.contains(&(output.txid, output.output_index)))
+            .map(|output| SignerUtxo {
+                outpoint: bitcoin::OutPoint::new(output.txid.into(), output.output_index),
+                amount: output.amount,
+                public_key: bitcoin::XOnlyPublicKey::from(&aggregate_key),
+            })
+            .collect();
+
+        if utxos.is_empty() {
+            let donation = store
+                .get_utxo_from_donation(chain_tip, &aggregate_key, context_window)
+                .await?;
+            return Ok(donation.into_iter().collect());
+        }
+
+        utxos.sort_by(|a, b| b.amount.cmp(&a.amount).then(a.outpoint.cmp(&b.outpoint)));
+        Ok(utxos)
+    }
+
     async fn get_deposit_request_signer_votes(
         &self,
         txid: &model::BitcoinTxId,
@@ -540,6 +615,22 @@ impl DbRead for SharedStore {
         }
     }
 
+    async fn get_withdrawal_requests_by_id(
+        &self,
+        request_id: u64,
+    ) -> Result<Vec<model::WithdrawalRequest>, Error> {
+        let store = self.lock().await;
+        let mut requests: Vec<model::WithdrawalRequest> = store
+            .withdrawal_requests
+            .values()
+            .filter(|req| req.request_id == request_id)
+            .cloned()
+            .collect();
+
+        requests.sort_by_key(|req| (req.bitcoin_block_height, req.block_hash));
+        Ok(requests)
+    }
+
     async fn is_known_bitcoin_block_hash(
         &self,
         block_hash: &model::BitcoinBlockHash,
@@ -788,143 +879,932 @@ impl DbRead for SharedStore {
         let peers = store.p2p_peers.values().cloned().collect();
         Ok(peers)
     }
-}
 
-impl DbRead for InMemoryTransaction {
-    async fn get_bitcoin_block(
-        &self,
-        block_hash: &model::BitcoinBlockHash,
-    ) -> Result<Option<model::BitcoinBlock>, Error> {
-        self.store.get_bitcoin_block(block_hash).await
+    async fn get_sbtc_supply_totals(&self) -> Result<model::SbtcSupplyTotals, Error> {
+        let store = self.lock().await;
+
+        let minted = store
+            .completed_deposit_events
+            .values()
+            .map(|event| event.amount)
+            .sum();
+
+        let burned = store
+            .withdrawal_accept_events
+            .keys()
+            .filter_map(|request_id| {
+                store
+                    .withdrawal_requests
+                    .iter()
+                    .find(|(pk, _)| pk.0 == *request_id)
+                    .map(|(_, req)| req.amount)
+            })
+            .sum();
+
+        Ok(model::SbtcSupplyTotals { minted, burned })
     }
 
-    async fn get_stacks_block(
+    async fn get_signer_utxo_violations(
         &self,
-        block_hash: &model::StacksBlockHash,
-    ) -> Result<Option<model::StacksBlock>, Error> {
-        self.store.get_stacks_block(block_hash).await
+        limit: u16,
+    ) -> Result<Vec<model::SignerUtxoViolation>, Error> {
+        let store = self.lock().await;
+
+        Ok(store
+            .signer_utxo_violations
+            .iter()
+            .rev()
+            .take(limit as usize)
+            .cloned()
+            .collect())
     }
 
-    async fn get_bitcoin_canonical_chain_tip(
+    async fn get_bitcoin_tx_body(
         &self,
-    ) -> Result<Option<model::BitcoinBlockHash>, Error> {
-        self.store.get_bitcoin_canonical_chain_tip().await
+        txid: &model::BitcoinTxId,
+        block_hash: &model::BitcoinBlockHash,
+    ) -> Result<Option<model::BitcoinTxBody>, Error> {
+        let store = self.lock().await;
+
+        Ok(store.bitcoin_tx_bodies.get(&(*txid, *block_hash)).cloned())
     }
 
-    async fn get_bitcoin_canonical_chain_tip_ref(
+    async fn get_block_activity(
         &self,
-    ) -> Result<Option<model::BitcoinBlockRef>, Error> {
-        self.store.get_bitcoin_canonical_chain_tip_ref().await
+        block_hash: &model::BitcoinBlockHash,
+    ) -> Result<model::BlockActivitySummary, Error> {
+        let store = self.lock().await;
+
+        // This mirrors the `sweeps` CTE of the postgres query: the
+        // distinct transactions in the block that spend a signers' input,
+        // ordered by their txid bytes.
+        let mut sweep_txids: Vec<model::BitcoinTxId> = store
+            .bitcoin_block_to_transactions
+            .get(block_hash)
+            .into_iter()
+            .flatten()
+            .filter(|txid| {
+                store
+                    .bitcoin_prevouts
+                    .get(*txid)
+                    .into_iter()
+                    .flatten()
+                    .any(|prevout| prevout.prevout_type == model::TxPrevoutType::SignersInput)
+            })
+            .copied()
+            .collect();
+        sweep_txids.sort_by_key(model::BitcoinTxId::into_bytes);
+        sweep_txids.dedup();
+
+        // And these mirror the `inputs` and `outputs` CTEs.
+        let prevouts: Vec<&model::TxPrevout> = sweep_txids
+            .iter()
+            .filter_map(|txid| store.bitcoin_prevouts.get(txid))
+            .flatten()
+            .collect();
+        let outputs: Vec<&model::TxOutput> = sweep_txids
+            .iter()
+            .filter_map(|txid| store.bitcoin_outputs.get(txid))
+            .flatten()
+            .collect();
+
+        let deposits = prevouts
+            .iter()
+            .filter(|prevout| prevout.prevout_type == model::TxPrevoutType::Deposit);
+        let withdrawals = outputs
+            .iter()
+            .filter(|output| output.output_type == model::TxOutputType::Withdrawal);
+        let total_in: u64 = prevouts.iter().map(|prevout| prevout.amount).sum();
+        let total_out: u64 = outputs.iter().map(|output| output.amount).sum();
+
+        Ok(model::BlockActivitySummary {
+            sweep_txids,
+            deposits_swept: deposits.clone().count() as u64,
+            deposit_amount: deposits.map(|prevout| prevout.amount).sum(),
+            withdrawals_fulfilled: withdrawals.clone().count() as u64,
+            withdrawal_amount: withdrawals.map(|output| output.amount).sum(),
+            fees_paid: total_in.saturating_sub(total_out),
+        })
     }
 
-    async fn get_stacks_chain_tip(
+    async fn is_emily_imported_deposit(
         &self,
-        bitcoin_chain_tip: &model::BitcoinBlockHash,
-    ) -> Result<Option<model::StacksBlock>, Error> {
-        self.store.get_stacks_chain_tip(bitcoin_chain_tip).await
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+    ) -> Result<bool, Error> {
+        let store = self.lock().await;
+        Ok(store
+            .emily_imported_deposits
+            .contains(&(*txid, output_index)))
     }
 
-    async fn get_pending_deposit_requests(
+    async fn is_emily_imported_withdrawal(
         &self,
-        chain_tip: &model::BitcoinBlockHash,
-        context_window: u16,
-        signer_public_key: &PublicKey,
-    ) -> Result<Vec<model::DepositRequest>, Error> {
-        self.store
-            .get_pending_deposit_requests(chain_tip, context_window, signer_public_key)
-            .await
+        id: &model::QualifiedRequestId,
+    ) -> Result<bool, Error> {
+        let store = self.lock().await;
+        Ok(store
+            .emily_imported_withdrawals
+            .contains(&(id.request_id, id.block_hash)))
     }
 
-    async fn get_pending_accepted_deposit_requests(
-        &self,
-        chain_tip: &model::BitcoinBlockRef,
-        context_window: u16,
-        signatures_required: u16,
-    ) -> Result<Vec<model::DepositRequest>, Error> {
-        self.store
-            .get_pending_accepted_deposit_requests(chain_tip, context_window, signatures_required)
-            .await
+    async fn get_p2p_peer_versions(&self) -> Result<Vec<model::P2PPeerVersion>, Error> {
+        let store = self.lock().await;
+        let versions = store.p2p_peer_versions.values().cloned().collect();
+        Ok(versions)
     }
 
-    async fn deposit_request_exists(
+    async fn get_active_peer_bans(&self) -> Result<Vec<model::P2PPeerBan>, Error> {
+        let store = self.lock().await;
+        let now = time::OffsetDateTime::now_utc();
+        let mut bans: Vec<_> = store
+            .p2p_peer_bans
+            .values()
+            .filter(|ban| ban.is_active_at(now))
+            .cloned()
+            .collect();
+        bans.sort_by_key(|ban| ban.expires_at);
+        Ok(bans)
+    }
+
+    async fn get_active_dkg_verification_rounds(
         &self,
-        txid: &model::BitcoinTxId,
-        output_index: u32,
-    ) -> Result<bool, Error> {
-        self.store.deposit_request_exists(txid, output_index).await
+    ) -> Result<Vec<model::DkgVerificationRound>, Error> {
+        let store = self.lock().await;
+        let mut rounds: Vec<_> = store
+            .dkg_verification_rounds
+            .values()
+            .filter(|round| round.status == model::DkgVerificationRoundStatus::Active)
+            .cloned()
+            .collect();
+        rounds.sort_by_key(|round| round.bitcoin_block_height);
+        Ok(rounds)
     }
 
-    async fn get_deposit_request_report(
+    async fn get_unresolved_stacks_event_dead_letters(
         &self,
-        chain_tip: &model::BitcoinBlockHash,
-        txid: &model::BitcoinTxId,
-        output_index: u32,
-        signer_public_key: &PublicKey,
-    ) -> Result<Option<DepositRequestReport>, Error> {
-        self.store
-            .get_deposit_request_report(chain_tip, txid, output_index, signer_public_key)
-            .await
+    ) -> Result<Vec<model::StacksEventDeadLetter>, Error> {
+        let store = self.lock().await;
+        let mut letters: Vec<_> = store
+            .stacks_event_dead_letters
+            .values()
+            .filter(|letter| letter.resolved_at.is_none())
+            .cloned()
+            .collect();
+        letters.sort_by_key(|letter| letter.created_at);
+        Ok(letters)
     }
 
-    async fn get_deposit_signers(
+    async fn get_signed_sweep_transactions(
         &self,
-        txid: &model::BitcoinTxId,
-        output_index: u32,
-    ) -> Result<Vec<model::DepositSigner>, Error> {
-        self.store.get_deposit_signers(txid, output_index).await
+    ) -> Result<Vec<model::SignedSweepTransaction>, Error> {
+        let store = self.lock().await;
+        let mut sweeps: Vec<_> = store.signed_sweep_transactions.values().cloned().collect();
+        sweeps.sort_by_key(|sweep| (sweep.created_at, sweep.txid));
+        Ok(sweeps)
     }
 
-    async fn get_deposit_signer_decisions(
+    async fn get_aggregate_key_history(
         &self,
         chain_tip: &model::BitcoinBlockHash,
-        context_window: u16,
-        signer_public_key: &PublicKey,
-    ) -> Result<Vec<model::DepositSigner>, Error> {
-        self.store
-            .get_deposit_signer_decisions(chain_tip, context_window, signer_public_key)
-            .await
+    ) -> Result<Vec<model::AggregateKeyPeriod>, Error> {
+        let stacks_chain_tip = self.get_stacks_chain_tip(chain_tip).await?;
+        let store = self.lock().await;
+
+        let aggregate_keys: HashMap<&model::ScriptPubKey, PublicKey> = store
+            .encrypted_dkg_shares
+            .values()
+            .map(|(_, shares)| (&shares.script_pubkey, shares.aggregate_key))
+            .collect();
+
+        let bitcoin_blocks = &store.bitcoin_blocks;
+        let mut key_starts: HashMap<PublicKey, model::BitcoinBlockHeight> = HashMap::new();
+        let blockchain = std::iter::successors(bitcoin_blocks.get(chain_tip), |block| {
+            bitcoin_blocks.get(&block.parent_hash)
+        });
+
+        for block in blockchain {
+            let outputs = store
+                .bitcoin_block_to_transactions
+                .get(&block.block_hash)
+                .into_iter()
+                .flatten()
+                .filter_map(|txid| store.bitcoin_outputs.get(txid))
+                .flatten()
+                .filter(|output| {
+                    matches!(
+                        output.output_type,
+                        model::TxOutputType::SignersOutput | model::TxOutputType::Donation
+                    )
+                });
+
+            for output in outputs {
+                if let Some(aggregate_key) = aggregate_keys.get(&output.script_pubkey) {
+                    key_starts
+                        .entry(*aggregate_key)
+                        .and_modify(|height| *height = (*height).min(block.block_height))
+                        .or_insert(block.block_height);
+                }
+            }
+        }
+
+        let mut key_starts: Vec<_> = key_starts.into_iter().collect();
+        key_starts.sort_by_key(|(aggregate_key, height)| (*height, *aggregate_key));
+
+        let end_heights = key_starts
+            .iter()
+            .skip(1)
+            .map(|(_, height)| Some(*height))
+            .chain(std::iter::once(None));
+
+        let history = key_starts
+            .iter()
+            .zip(end_heights)
+            .map(|((aggregate_key, start_height), end_height)| {
+                // The stacks blockchain is walked from the tip, so the
+                // last matching event is the earliest one.
+                let rotate_keys_txid = stacks_chain_tip.as_ref().and_then(|tip| {
+                    store
+                        .stacks_blockchain(tip)
+                        .filter_map(|block| store.rotate_keys_transactions.get(&block.block_hash))
+                        .flat_map(|events| events.iter().rev())
+                        .filter(|event| &event.aggregate_key == aggregate_key)
+                        .map(|event| event.txid)
+                        .last()
+                });
+
+                model::AggregateKeyPeriod {
+                    aggregate_key: *aggregate_key,
+                    start_height: *start_height,
+                    end_height,
+                    rotate_keys_txid,
+                }
+            })
+            .collect();
+
+        Ok(history)
     }
 
-    async fn get_withdrawal_signer_decisions(
+    async fn get_request_counts(
         &self,
         chain_tip: &model::BitcoinBlockHash,
-        context_window: u16,
-        signer_public_key: &PublicKey,
-    ) -> Result<Vec<model::WithdrawalSigner>, Error> {
-        self.store
-            .get_withdrawal_signer_decisions(chain_tip, context_window, signer_public_key)
-            .await
+    ) -> Result<model::RequestCounts, Error> {
+        let stacks_chain_tip = self.get_stacks_chain_tip(chain_tip).await?;
+        let store = self.lock().await;
+
+        let stacks_blockchain: HashSet<StacksBlockHash> = stacks_chain_tip
+            .iter()
+            .flat_map(|tip| store.stacks_blockchain(tip))
+            .map(|block| block.block_hash)
+            .collect();
+
+        let bitcoin_blocks = &store.bitcoin_blocks;
+        let confirmed_txids: HashSet<model::BitcoinTxId> =
+            std::iter::successors(bitcoin_blocks.get(chain_tip), |block| {
+                bitcoin_blocks.get(&block.parent_hash)
+            })
+            .filter_map(|block| store.bitcoin_block_to_transactions.get(&block.block_hash))
+            .flatten()
+            .copied()
+            .collect();
+
+        let signer_set = store
+            .encrypted_dkg_shares
+            .values()
+            .filter(|(_, shares)| shares.dkg_shares_status == DkgSharesStatus::Verified)
+            .max_by_key(|(time, _)| time)
+            .map(|(_, shares)| {
+                let threshold = shares.signature_share_threshold as usize;
+                (threshold, shares.signer_set_public_keys.len())
+            });
+        let vote_status = |accepts: usize, rejects: usize| match signer_set {
+            Some((threshold, _)) if accepts >= threshold => model::RequestStatus::Accepted,
+            Some((threshold, num_signers)) if rejects + threshold > num_signers => {
+                model::RequestStatus::Rejected
+            }
+            _ => model::RequestStatus::Pending,
+        };
+
+        let mut counts = model::RequestCounts::default();
+
+        let deposits = store
+            .deposit_requests
+            .values()
+            .filter(|req| confirmed_txids.contains(&req.txid));
+        for req in deposits {
+            let outpoint = req.outpoint();
+            let is_completed = store
+                .completed_deposit_events
+                .get(&outpoint)
+                .is_some_and(|event| stacks_blockchain.contains(&event.block_id));
+            let is_swept = confirmed_txids
+                .iter()
+                .filter_map(|txid| store.bitcoin_prevouts.get(txid))
+                .flatten()
+                .any(|prevout| {
+                    prevout.prevout_txid == req.txid
+                        && prevout.prevout_output_index == req.output_index
+                });
+            let is_in_flight = store.bitcoin_sighashes.values().any(|sighash| {
+                sighash.prevout_txid == req.txid
+                    && sighash.prevout_output_index == req.output_index
+                    && sighash.will_sign
+            });
+
+            let status = if is_completed {
+                model::RequestStatus::Completed
+            } else if is_swept {
+                model::RequestStatus::SweptAwaitingStacks
+            } else if is_in_flight {
+                model::RequestStatus::InFlight
+            } else {
+                let votes = store
+                    .deposit_request_to_signers
+                    .get(&(req.txid, req.output_index))
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                let accepts = votes
+                    .iter()
+                    .filter(|vote| vote.can_accept && vote.can_sign)
+                    .count();
+                vote_status(accepts, votes.len() - accepts)
+            };
+            counts.deposits.increment(status);
+        }
+
+        let withdrawals = store
+            .withdrawal_requests
+            .values()
+            .filter(|req| stacks_blockchain.contains(&req.block_hash));
+        for req in withdrawals {
+            let is_completed = store
+                .withdrawal_accept_events
+                .get(&req.request_id)
+                .is_some_and(|event| stacks_blockchain.contains(&event.block_id));
+            let is_rejected = store
+                .withdrawal_reject_events
+                .get(&req.request_id)
+                .is_some_and(|event| stacks_blockchain.contains(&event.block_id));
+            let output = store
+                .bitcoin_withdrawal_outputs
+                .get(&(req.request_id, req.block_hash));
+
+            let status = if is_completed {
+                model::RequestStatus::Completed
+            } else if is_rejected {
+                model::RequestStatus::Rejected
+            } else if output.is_some_and(|out| confirmed_txids.contains(&out.bitcoin_txid)) {
+                model::RequestStatus::SweptAwaitingStacks
+            } else if output.is_some_and(|out| out.is_valid_tx) {
+                model::RequestStatus::InFlight
+            } else {
+                let votes = store
+                    .withdrawal_request_to_signers
+                    .get(&(req.request_id, req.block_hash))
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                let accepts = votes.iter().filter(|vote| vote.is_accepted).count();
+                vote_status(accepts, votes.len() - accepts)
+            };
+            counts.withdrawals.increment(status);
+        }
+
+        Ok(counts)
     }
 
-    async fn can_sign_deposit_tx(
+    async fn get_sweep_fee_discrepancies(
         &self,
-        txid: &model::BitcoinTxId,
-        output_index: u32,
-        signer_public_key: &PublicKey,
-    ) -> Result<Option<bool>, Error> {
-        self.store
-            .can_sign_deposit_tx(txid, output_index, signer_public_key)
-            .await
+        limit: u16,
+    ) -> Result<Vec<model::SweepFeeDiscrepancy>, Error> {
+        let store = self.lock().await;
+
+        Ok(store
+            .sweep_fee_discrepancies
+            .iter()
+            .rev()
+            .take(limit as usize)
+            .cloned()
+            .collect())
     }
 
-    async fn get_withdrawal_signers(
+    async fn get_key_rotation_attestations(
         &self,
-        request_id: u64,
-        block_hash: &model::StacksBlockHash,
-    ) -> Result<Vec<model::WithdrawalSigner>, Error> {
-        self.store
-            .get_withdrawal_signers(request_id, block_hash)
-            .await
+        aggregate_key: &PublicKey,
+    ) -> Result<Vec<model::KeyRotationAttestation>, Error> {
+        let store = self.lock().await;
+
+        Ok(store
+            .key_rotation_attestations
+            .iter()
+            .filter(|attestation| &attestation.aggregate_key == aggregate_key)
+            .cloned()
+            .collect())
     }
 
-    async fn get_pending_withdrawal_requests(
+    async fn get_caps_utilization(
         &self,
-        bitcoin_chain_tip: &model::BitcoinBlockHash,
-        stacks_chain_tip: &model::StacksBlockHash,
+        chain_tip: &model::BitcoinBlockHash,
         context_window: u16,
-        signer_public_key: &PublicKey,
-    ) -> Result<Vec<model::WithdrawalRequest>, Error> {
-        self.store
+        rolling_withdrawal_cap: u64,
+    ) -> Result<model::CapsUtilization, Error> {
+        let db = self.lock().await;
+        let chain_tip_height = db
+            .bitcoin_blocks
+            .get(chain_tip)
+            .map(|block| block.block_height)
+            .ok_or(Error::MissingBitcoinBlock(*chain_tip))?;
+
+        let bitcoin_blocks = std::iter::successors(db.bitcoin_blocks.get(chain_tip), |block| {
+            db.bitcoin_blocks.get(&block.parent_hash)
+        })
+        .take(context_window.max(1) as usize);
+
+        // Sum up the swept withdrawal requests in each block, the same way
+        // that `compute_withdrawn_total` does for the whole window.
+        let mut withdrawals = Vec::new();
+        for block in bitcoin_blocks {
+            let txs = db
+                .bitcoin_block_to_transactions
+                .get(&block.block_hash)
+                .into_iter()
+                .flatten()
+                .collect::<HashSet<_>>();
+
+            let amount: u64 = db
+                .bitcoin_withdrawal_outputs
+                .values()
+                .filter(|x| txs.contains(&x.bitcoin_txid))
+                .map(|x| (x.request_id, x.stacks_block_hash))
+                .collect::<HashSet<_>>()
+                .iter()
+                .filter_map(|id| db.withdrawal_requests.get(id))
+                .map(|req| req.amount)
+                .sum();
+
+            withdrawals.push((block.block_height, amount));
+        }
+
+        Ok(model::CapsUtilization::new(
+            chain_tip_height,
+            context_window,
+            rolling_withdrawal_cap,
+            &withdrawals,
+        ))
+    }
+
+    async fn get_sweep_package(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Option<model::SweepPackage>, Error> {
+        let store = self.lock().await;
+        Ok(store.sweep_packages.get(bitcoin_chain_tip).cloned())
+    }
+
+    async fn get_presigned_withdrawals(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Vec<model::WithdrawalRequest>, Error> {
+        let store = self.lock().await;
+        let mut withdrawals: Vec<model::WithdrawalRequest> = store
+            .bitcoin_withdrawal_outputs
+            .values()
+            .filter(|output| &output.bitcoin_chain_tip == bitcoin_chain_tip)
+            .filter(|output| output.is_valid_tx)
+            .filter_map(|output| {
+                let key = (output.request_id, output.stacks_block_hash);
+                store.withdrawal_requests.get(&key).cloned()
+            })
+            .collect();
+
+        withdrawals.sort_by_key(|req| (req.request_id, req.block_hash));
+        Ok(withdrawals)
+    }
+
+    async fn get_deposit_request_timestamps(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+    ) -> Result<Option<model::RequestTimestamps>, Error> {
+        let store = self.lock().await;
+        Ok(store
+            .deposit_request_timestamps
+            .get(&(*txid, output_index))
+            .cloned())
+    }
+
+    async fn get_withdrawal_request_timestamps(
+        &self,
+        request_id: u64,
+    ) -> Result<Option<model::RequestTimestamps>, Error> {
+        let store = self.lock().await;
+        Ok(store
+            .withdrawal_request_timestamps
+            .get(&request_id)
+            .cloned())
+    }
+
+    async fn get_deposit_signer_decisions_in_range(
+        &self,
+        signer_public_key: &PublicKey,
+        start_height: model::BitcoinBlockHeight,
+        end_height: model::BitcoinBlockHeight,
+        limit: u16,
+        offset: u32,
+    ) -> Result<Vec<model::DepositSigner>, Error> {
+        let store = self.lock().await;
+
+        let in_range = |txid: &model::BitcoinTxId| {
+            store
+                .bitcoin_transactions_to_blocks
+                .get(txid)
+                .into_iter()
+                .flatten()
+                .filter_map(|block_hash| store.bitcoin_blocks.get(block_hash))
+                .any(|block| (start_height..=end_height).contains(&block.block_height))
+        };
+
+        let mut decisions: Vec<model::DepositSigner> = store
+            .deposit_request_to_signers
+            .values()
+            .flatten()
+            .filter(|signer| signer.signer_pub_key == *signer_public_key)
+            .filter(|signer| in_range(&signer.txid))
+            .cloned()
+            .collect();
+        decisions.sort_by_key(|signer| (signer.txid, signer.output_index));
+
+        Ok(decisions
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect())
+    }
+
+    async fn get_withdrawal_signer_decisions_in_range(
+        &self,
+        signer_public_key: &PublicKey,
+        start_height: model::BitcoinBlockHeight,
+        end_height: model::BitcoinBlockHeight,
+        limit: u16,
+        offset: u32,
+    ) -> Result<Vec<model::WithdrawalSigner>, Error> {
+        let store = self.lock().await;
+
+        let mut decisions: Vec<model::WithdrawalSigner> = store
+            .withdrawal_request_to_signers
+            .iter()
+            .filter(|(key, _)| {
+                store.withdrawal_requests.get(*key).is_some_and(|request| {
+                    (start_height..=end_height).contains(&request.bitcoin_block_height)
+                })
+            })
+            .flat_map(|(_, signers)| signers)
+            .filter(|signer| signer.signer_pub_key == *signer_public_key)
+            .cloned()
+            .collect();
+        decisions.sort_by_key(|signer| (signer.request_id, signer.block_hash));
+
+        Ok(decisions
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect())
+    }
+
+    async fn get_bitcoin_backfill_progress(
+        &self,
+    ) -> Result<Option<model::BitcoinBackfillProgress>, Error> {
+        Ok(self.lock().await.bitcoin_backfill_progress.clone())
+    }
+
+    async fn get_coordinator_takeover(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Option<model::CoordinatorTakeover>, Error> {
+        Ok(self
+            .lock()
+            .await
+            .coordinator_takeovers
+            .get(bitcoin_chain_tip)
+            .copied())
+    }
+
+    async fn get_active_peer_address_records(
+        &self,
+    ) -> Result<Vec<model::P2PPeerAddressRecord>, Error> {
+        let store = self.lock().await;
+        let now = time::OffsetDateTime::now_utc();
+        let mut records: Vec<_> = store
+            .p2p_peer_address_records
+            .values()
+            .filter(|record| record.is_active_at(now))
+            .cloned()
+            .collect();
+        records.sort_by_key(|record| std::cmp::Reverse(record.expires_at));
+        Ok(records)
+    }
+
+    async fn get_active_request_override(
+        &self,
+        target: &model::OverrideTarget,
+    ) -> Result<Option<model::RequestOverride>, Error> {
+        let store = self.lock().await;
+        let now = time::OffsetDateTime::now_utc();
+        Ok(store
+            .request_overrides
+            .iter()
+            .rev()
+            .find(|request_override| {
+                &request_override.target == target && request_override.is_active_at(now)
+            })
+            .cloned())
+    }
+
+    async fn get_request_overrides(&self) -> Result<Vec<model::RequestOverride>, Error> {
+        let store = self.lock().await;
+        Ok(store.request_overrides.iter().rev().cloned().collect())
+    }
+
+    async fn get_inflight_contract_call(
+        &self,
+        target: &model::ContractCallTarget,
+    ) -> Result<Option<model::InflightContractCall>, Error> {
+        let store = self.lock().await;
+        Ok(store.inflight_contract_calls.get(target).cloned())
+    }
+
+    async fn get_chain_history(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        depth: u16,
+        start_height: model::BitcoinBlockHeight,
+    ) -> Result<model::ChainHistory, Error> {
+        let store = self.lock().await;
+        let bitcoin_blocks = &store.bitcoin_blocks;
+        let first = bitcoin_blocks.get(chain_tip);
+
+        let history: Vec<_> =
+            std::iter::successors(first, |block| bitcoin_blocks.get(&block.parent_hash))
+                .take(usize::from(depth))
+                .collect();
+        let available_blocks = u16::try_from(history.len()).map_err(|_| Error::TypeConversion)?;
+        let oldest_block_height = history.last().map(|block| block.block_height);
+
+        Ok(model::ChainHistory::new(
+            depth,
+            available_blocks,
+            oldest_block_height,
+            start_height,
+        ))
+    }
+
+    async fn get_completed_deposit_event(
+        &self,
+        outpoint: &bitcoin::OutPoint,
+    ) -> Result<Option<model::CompletedDepositEvent>, Error> {
+        Ok(self
+            .lock()
+            .await
+            .completed_deposit_events
+            .get(outpoint)
+            .cloned())
+    }
+
+    async fn get_sweep_tx_merkle_proof(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        txid: &model::BitcoinTxId,
+    ) -> Result<Option<model::BitcoinTxMerkleProof>, Error> {
+        let store = self.lock().await;
+
+        let is_sweep = store
+            .bitcoin_prevouts
+            .get(txid)
+            .into_iter()
+            .flatten()
+            .any(|prevout| prevout.prevout_type == model::TxPrevoutType::SignersInput);
+        if !is_sweep {
+            return Ok(None);
+        }
+
+        let bitcoin_blocks = &store.bitcoin_blocks;
+        let first = bitcoin_blocks.get(chain_tip);
+
+        Ok(
+            std::iter::successors(first, |block| bitcoin_blocks.get(&block.parent_hash))
+                .find_map(|block| {
+                    store
+                        .bitcoin_tx_merkle_proofs
+                        .get(&(*txid, block.block_hash))
+                })
+                .cloned(),
+        )
+    }
+
+    async fn get_bitcoin_block_headers(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        start_height: model::BitcoinBlockHeight,
+        limit: u16,
+    ) -> Result<Vec<bitcoin::block::Header>, Error> {
+        let store = self.lock().await;
+        let bitcoin_blocks = &store.bitcoin_blocks;
+        let first = bitcoin_blocks.get(chain_tip);
+
+        let mut headers: Vec<bitcoin::block::Header> =
+            std::iter::successors(first, |block| bitcoin_blocks.get(&block.parent_hash))
+                .take_while(|block| block.block_height >= start_height)
+                .filter_map(|block| store.bitcoin_block_headers.get(&block.block_hash))
+                .copied()
+                .collect();
+
+        headers.reverse();
+        headers.truncate(limit as usize);
+        Ok(headers)
+    }
+
+    async fn get_stacks_chain_tip_v2(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Option<model::StacksChainTip>, Error> {
+        Ok(self.lock().await.get_stacks_chain_tip_v2(bitcoin_chain_tip))
+    }
+
+    async fn get_unscanned_stacks_blocks(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+    ) -> Result<Vec<model::StacksBlockHash>, Error> {
+        let store = self.lock().await;
+        Ok(store.get_unscanned_stacks_blocks(chain_tip, context_window))
+    }
+
+    async fn get_multisig_stacks_transactions(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        stacks_chain_tip: &model::StacksBlockHash,
+        context_window: u16,
+    ) -> Result<Vec<model::MultisigStacksTransaction>, Error> {
+        let store = self.lock().await;
+        Ok(store.get_multisig_stacks_transactions(
+            bitcoin_chain_tip,
+            stacks_chain_tip,
+            context_window,
+        ))
+    }
+}
+
+impl DbRead for InMemoryTransaction {
+    async fn get_bitcoin_block(
+        &self,
+        block_hash: &model::BitcoinBlockHash,
+    ) -> Result<Option<model::BitcoinBlock>, Error> {
+        self.store.get_bitcoin_block(block_hash).await
+    }
+
+    async fn get_stacks_block(
+        &self,
+        block_hash: &model::StacksBlockHash,
+    ) -> Result<Option<model::StacksBlock>, Error> {
+        self.store.get_stacks_block(block_hash).await
+    }
+
+    async fn get_bitcoin_canonical_chain_tip(
+        &self,
+    ) -> Result<Option<model::BitcoinBlockHash>, Error> {
+        self.store.get_bitcoin_canonical_chain_tip().await
+    }
+
+    async fn get_bitcoin_canonical_chain_tip_ref(
+        &self,
+    ) -> Result<Option<model::BitcoinBlockRef>, Error> {
+        self.store.get_bitcoin_canonical_chain_tip_ref().await
+    }
+
+    async fn get_stacks_chain_tip(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Option<model::StacksBlock>, Error> {
+        self.store.get_stacks_chain_tip(bitcoin_chain_tip).await
+    }
+
+    async fn get_pending_deposit_requests(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+        signer_public_key: &PublicKey,
+    ) -> Result<Vec<model::DepositRequest>, Error> {
+        self.store
+            .get_pending_deposit_requests(chain_tip, context_window, signer_public_key)
+            .await
+    }
+
+    async fn get_recent_deposit_requests(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+    ) -> Result<Vec<model::DepositRequest>, Error> {
+        self.store
+            .get_recent_deposit_requests(chain_tip, context_window)
+            .await
+    }
+
+    async fn get_pending_accepted_deposit_requests(
+        &self,
+        chain_tip: &model::BitcoinBlockRef,
+        context_window: u16,
+        signatures_required: u16,
+        confirmation_policy: &DepositConfirmationPolicy,
+    ) -> Result<Vec<model::DepositRequest>, Error> {
+        self.store
+            .get_pending_accepted_deposit_requests(
+                chain_tip,
+                context_window,
+                signatures_required,
+                confirmation_policy,
+            )
+            .await
+    }
+
+    async fn deposit_request_exists(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+    ) -> Result<bool, Error> {
+        self.store.deposit_request_exists(txid, output_index).await
+    }
+
+    async fn get_deposit_request_report(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+        signer_public_key: &PublicKey,
+    ) -> Result<Option<DepositRequestReport>, Error> {
+        self.store
+            .get_deposit_request_report(chain_tip, txid, output_index, signer_public_key)
+            .await
+    }
+
+    async fn get_deposit_signers(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+    ) -> Result<Vec<model::DepositSigner>, Error> {
+        self.store.get_deposit_signers(txid, output_index).await
+    }
+
+    async fn get_deposit_signer_decisions(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+        signer_public_key: &PublicKey,
+    ) -> Result<Vec<model::DepositSigner>, Error> {
+        self.store
+            .get_deposit_signer_decisions(chain_tip, context_window, signer_public_key)
+            .await
+    }
+
+    async fn get_withdrawal_signer_decisions(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+        signer_public_key: &PublicKey,
+    ) -> Result<Vec<model::WithdrawalSigner>, Error> {
+        self.store
+            .get_withdrawal_signer_decisions(chain_tip, context_window, signer_public_key)
+            .await
+    }
+
+    async fn can_sign_deposit_tx(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+        signer_public_key: &PublicKey,
+    ) -> Result<Option<bool>, Error> {
+        self.store
+            .can_sign_deposit_tx(txid, output_index, signer_public_key)
+            .await
+    }
+
+    async fn get_withdrawal_signers(
+        &self,
+        request_id: u64,
+        block_hash: &model::StacksBlockHash,
+    ) -> Result<Vec<model::WithdrawalSigner>, Error> {
+        self.store
+            .get_withdrawal_signers(request_id, block_hash)
+            .await
+    }
+
+    async fn get_pending_withdrawal_requests(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        stacks_chain_tip: &model::StacksBlockHash,
+        context_window: u16,
+        signer_public_key: &PublicKey,
+    ) -> Result<Vec<model::WithdrawalRequest>, Error> {
+        self.store
             .get_pending_withdrawal_requests(
                 bitcoin_chain_tip,
                 stacks_chain_tip,
@@ -1071,6 +1951,13 @@ impl DbRead for InMemoryTransaction {
         self.store.get_signer_utxo(chain_tip).await
     }
 
+    async fn get_signer_utxos(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Vec<SignerUtxo>, Error> {
+        self.store.get_signer_utxos(chain_tip).await
+    }
+
     async fn get_deposit_request_signer_votes(
         &self,
         txid: &model::BitcoinTxId,
@@ -1092,6 +1979,13 @@ impl DbRead for InMemoryTransaction {
             .await
     }
 
+    async fn get_withdrawal_requests_by_id(
+        &self,
+        request_id: u64,
+    ) -> Result<Vec<model::WithdrawalRequest>, Error> {
+        self.store.get_withdrawal_requests_by_id(request_id).await
+    }
+
     async fn is_known_bitcoin_block_hash(
         &self,
         block_hash: &model::BitcoinBlockHash,
@@ -1174,4 +2068,289 @@ impl DbRead for InMemoryTransaction {
     async fn get_p2p_peers(&self) -> Result<Vec<model::P2PPeer>, Error> {
         self.store.get_p2p_peers().await
     }
+
+    async fn get_sbtc_supply_totals(&self) -> Result<model::SbtcSupplyTotals, Error> {
+        self.store.get_sbtc_supply_totals().await
+    }
+
+    async fn get_signer_utxo_violations(
+        &self,
+        limit: u16,
+    ) -> Result<Vec<model::SignerUtxoViolation>, Error> {
+        self.store.get_signer_utxo_violations(limit).await
+    }
+
+    async fn get_bitcoin_tx_body(
+        &self,
+        txid: &model::BitcoinTxId,
+        block_hash: &model::BitcoinBlockHash,
+    ) -> Result<Option<model::BitcoinTxBody>, Error> {
+        self.store.get_bitcoin_tx_body(txid, block_hash).await
+    }
+
+    async fn get_block_activity(
+        &self,
+        block_hash: &model::BitcoinBlockHash,
+    ) -> Result<model::BlockActivitySummary, Error> {
+        self.store.get_block_activity(block_hash).await
+    }
+
+    async fn is_emily_imported_deposit(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+    ) -> Result<bool, Error> {
+        self.store
+            .is_emily_imported_deposit(txid, output_index)
+            .await
+    }
+
+    async fn is_emily_imported_withdrawal(
+        &self,
+        id: &model::QualifiedRequestId,
+    ) -> Result<bool, Error> {
+        self.store.is_emily_imported_withdrawal(id).await
+    }
+
+    async fn get_p2p_peer_versions(&self) -> Result<Vec<model::P2PPeerVersion>, Error> {
+        self.store.get_p2p_peer_versions().await
+    }
+
+    async fn get_active_peer_bans(&self) -> Result<Vec<model::P2PPeerBan>, Error> {
+        self.store.get_active_peer_bans().await
+    }
+
+    async fn get_active_dkg_verification_rounds(
+        &self,
+    ) -> Result<Vec<model::DkgVerificationRound>, Error> {
+        self.store.get_active_dkg_verification_rounds().await
+    }
+
+    async fn get_unresolved_stacks_event_dead_letters(
+        &self,
+    ) -> Result<Vec<model::StacksEventDeadLetter>, Error> {
+        self.store.get_unresolved_stacks_event_dead_letters().await
+    }
+
+    async fn get_signed_sweep_transactions(
+        &self,
+    ) -> Result<Vec<model::SignedSweepTransaction>, Error> {
+        self.store.get_signed_sweep_transactions().await
+    }
+
+    async fn get_aggregate_key_history(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Vec<model::AggregateKeyPeriod>, Error> {
+        self.store.get_aggregate_key_history(chain_tip).await
+    }
+
+    async fn get_request_counts(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<model::RequestCounts, Error> {
+        self.store.get_request_counts(chain_tip).await
+    }
+
+    async fn get_sweep_fee_discrepancies(
+        &self,
+        limit: u16,
+    ) -> Result<Vec<model::SweepFeeDiscrepancy>, Error> {
+        self.store.get_sweep_fee_discrepancies(limit).await
+    }
+
+    async fn get_key_rotation_attestations(
+        &self,
+        aggregate_key: &PublicKey,
+    ) -> Result<Vec<model::KeyRotationAttestation>, Error> {
+        self.store
+            .get_key_rotation_attestations(aggregate_key)
+            .await
+    }
+
+    async fn get_caps_utilization(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+        rolling_withdrawal_cap: u64,
+    ) -> Result<model::CapsUtilization, Error> {
+        self.store
+            .get_caps_utilization(bitcoin_chain_tip, context_window, rolling_withdrawal_cap)
+            .await
+    }
+
+    async fn get_sweep_package(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Option<model::SweepPackage>, Error> {
+        self.store.get_sweep_package(bitcoin_chain_tip).await
+    }
+
+    async fn get_presigned_withdrawals(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Vec<model::WithdrawalRequest>, Error> {
+        self.store
+            .get_presigned_withdrawals(bitcoin_chain_tip)
+            .await
+    }
+
+    async fn get_deposit_request_timestamps(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+    ) -> Result<Option<model::RequestTimestamps>, Error> {
+        self.store
+            .get_deposit_request_timestamps(txid, output_index)
+            .await
+    }
+
+    async fn get_withdrawal_request_timestamps(
+        &self,
+        request_id: u64,
+    ) -> Result<Option<model::RequestTimestamps>, Error> {
+        self.store
+            .get_withdrawal_request_timestamps(request_id)
+            .await
+    }
+
+    async fn get_deposit_signer_decisions_in_range(
+        &self,
+        signer_public_key: &PublicKey,
+        start_height: model::BitcoinBlockHeight,
+        end_height: model::BitcoinBlockHeight,
+        limit: u16,
+        offset: u32,
+    ) -> Result<Vec<model::DepositSigner>, Error> {
+        self.store
+            .get_deposit_signer_decisions_in_range(
+                signer_public_key,
+                start_height,
+                end_height,
+                limit,
+                offset,
+            )
+            .await
+    }
+
+    async fn get_withdrawal_signer_decisions_in_range(
+        &self,
+        signer_public_key: &PublicKey,
+        start_height: model::BitcoinBlockHeight,
+        end_height: model::BitcoinBlockHeight,
+        limit: u16,
+        offset: u32,
+    ) -> Result<Vec<model::WithdrawalSigner>, Error> {
+        self.store
+            .get_withdrawal_signer_decisions_in_range(
+                signer_public_key,
+                start_height,
+                end_height,
+                limit,
+                offset,
+            )
+            .await
+    }
+
+    async fn get_bitcoin_backfill_progress(
+        &self,
+    ) -> Result<Option<model::BitcoinBackfillProgress>, Error> {
+        self.store.get_bitcoin_backfill_progress().await
+    }
+
+    async fn get_coordinator_takeover(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Option<model::CoordinatorTakeover>, Error> {
+        self.store.get_coordinator_takeover(bitcoin_chain_tip).await
+    }
+
+    async fn get_active_peer_address_records(
+        &self,
+    ) -> Result<Vec<model::P2PPeerAddressRecord>, Error> {
+        self.store.get_active_peer_address_records().await
+    }
+
+    async fn get_active_request_override(
+        &self,
+        target: &model::OverrideTarget,
+    ) -> Result<Option<model::RequestOverride>, Error> {
+        self.store.get_active_request_override(target).await
+    }
+
+    async fn get_request_overrides(&self) -> Result<Vec<model::RequestOverride>, Error> {
+        self.store.get_request_overrides().await
+    }
+
+    async fn get_inflight_contract_call(
+        &self,
+        target: &model::ContractCallTarget,
+    ) -> Result<Option<model::InflightContractCall>, Error> {
+        self.store.get_inflight_contract_call(target).await
+    }
+
+    async fn get_chain_history(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        depth: u16,
+        start_height: model::BitcoinBlockHeight,
+    ) -> Result<model::ChainHistory, Error> {
+        self.store
+            .get_chain_history(chain_tip, depth, start_height)
+            .await
+    }
+
+    async fn get_completed_deposit_event(
+        &self,
+        outpoint: &bitcoin::OutPoint,
+    ) -> Result<Option<model::CompletedDepositEvent>, Error> {
+        self.store.get_completed_deposit_event(outpoint).await
+    }
+
+    async fn get_sweep_tx_merkle_proof(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        txid: &model::BitcoinTxId,
+    ) -> Result<Option<model::BitcoinTxMerkleProof>, Error> {
+        self.store.get_sweep_tx_merkle_proof(chain_tip, txid).await
+    }
+
+    async fn get_bitcoin_block_headers(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        start_height: model::BitcoinBlockHeight,
+        limit: u16,
+    ) -> Result<Vec<bitcoin::block::Header>, Error> {
+        self.store
+            .get_bitcoin_block_headers(chain_tip, start_height, limit)
+            .await
+    }
+
+    async fn get_stacks_chain_tip_v2(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Option<model::StacksChainTip>, Error> {
+        self.store.get_stacks_chain_tip_v2(bitcoin_chain_tip).await
+    }
+
+    async fn get_unscanned_stacks_blocks(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+    ) -> Result<Vec<model::StacksBlockHash>, Error> {
+        self.store
+            .get_unscanned_stacks_blocks(chain_tip, context_window)
+            .await
+    }
+
+    async fn get_multisig_stacks_transactions(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        stacks_chain_tip: &model::StacksBlockHash,
+        context_window: u16,
+    ) -> Result<Vec<model::MultisigStacksTransaction>, Error> {
+        self.store
+            .get_multisig_stacks_transactions(bitcoin_chain_tip, stacks_chain_tip, context_window)
+            .await
+    }
 }
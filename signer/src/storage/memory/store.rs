@@ -5,6 +5,7 @@ use libp2p::PeerId;
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use time::OffsetDateTime;
@@ -122,6 +123,96 @@ pub struct Store {
 
     /// Stored P2P peers
     pub p2p_peers: HashMap<(PeerId, PublicKey), model::P2PPeer>,
+
+    /// The versions advertised by P2P peers
+    pub p2p_peer_versions: HashMap<PeerId, model::P2PPeerVersion>,
+
+    /// The bans of misbehaving P2P peers
+    pub p2p_peer_bans: HashMap<PeerId, model::P2PPeerBan>,
+
+    /// The latest address announcements of P2P peers
+    pub p2p_peer_address_records: HashMap<PublicKey, model::P2PPeerAddressRecord>,
+
+    /// Overrides that operators placed on requests, in the order that
+    /// they were placed
+    pub request_overrides: Vec<model::RequestOverride>,
+
+    /// Contract calls that the coordinator submitted and that have been
+    /// neither confirmed nor rejected yet, keyed by the request that they
+    /// fulfill
+    pub inflight_contract_calls: HashMap<model::ContractCallTarget, model::InflightContractCall>,
+
+    /// The labels of known withdrawal scriptPubKeys
+    pub withdrawal_output_labels: HashMap<model::ScriptPubKey, String>,
+
+    /// Signer state machines that were evicted from the in-memory cache
+    /// of the transaction signer, keyed by their identifier
+    pub spilled_state_machines: HashMap<String, model::SpilledStateMachine>,
+
+    /// DKG verification rounds that this signer has taken part in
+    pub dkg_verification_rounds:
+        HashMap<(PublicKeyXOnly, model::BitcoinBlockHash), model::DkgVerificationRound>,
+
+    /// sbtc-registry events that could not be parsed
+    pub stacks_event_dead_letters:
+        HashMap<(model::StacksBlockHash, model::StacksTxId, u32), model::StacksEventDeadLetter>,
+
+    /// Sweep transactions that were fully signed by the signers
+    pub signed_sweep_transactions: HashMap<model::BitcoinTxId, model::SignedSweepTransaction>,
+
+    /// The sweep packages constructed by the coordinator of each tenure
+    pub sweep_packages: HashMap<model::BitcoinBlockHash, model::SweepPackage>,
+
+    /// When deposit requests reached each stage of their lifecycle
+    pub deposit_request_timestamps: HashMap<(model::BitcoinTxId, u32), model::RequestTimestamps>,
+
+    /// When withdrawal requests reached each stage of their lifecycle
+    pub withdrawal_request_timestamps: HashMap<u64, model::RequestTimestamps>,
+
+    /// The progress of the most recent bitcoin block backfill
+    pub bitcoin_backfill_progress: Option<model::BitcoinBackfillProgress>,
+
+    /// The tenures that a deputy coordinator took over
+    pub coordinator_takeovers: HashMap<model::BitcoinBlockHash, model::CoordinatorTakeover>,
+
+    /// sBTC total-supply reconciliation snapshots, in the order that they
+    /// were written.
+    pub sbtc_supply_snapshots: Vec<model::SbtcSupplySnapshot>,
+
+    /// Signer UTXO invariant violations, in the order that they were
+    /// written.
+    pub signer_utxo_violations: Vec<model::SignerUtxoViolation>,
+
+    /// Sweep fee discrepancies, in the order that they were written.
+    pub sweep_fee_discrepancies: Vec<model::SweepFeeDiscrepancy>,
+
+    /// Key rotation attestations, in the order that they were written.
+    pub key_rotation_attestations: Vec<model::KeyRotationAttestation>,
+
+    /// Cached bitcoin transaction bodies
+    pub bitcoin_tx_bodies:
+        HashMap<(model::BitcoinTxId, model::BitcoinBlockHash), model::BitcoinTxBody>,
+
+    /// Bitcoin block headers
+    pub bitcoin_block_headers: HashMap<model::BitcoinBlockHash, bitcoin::block::Header>,
+
+    /// Proofs that bitcoin transactions were included in bitcoin blocks
+    pub bitcoin_tx_merkle_proofs:
+        HashMap<(model::BitcoinTxId, model::BitcoinBlockHash), model::BitcoinTxMerkleProof>,
+
+    /// Deposit requests that were imported from Emily
+    pub emily_imported_deposits: HashSet<(model::BitcoinTxId, u32)>,
+
+    /// Withdrawal requests that were imported from Emily
+    pub emily_imported_withdrawals: HashSet<(u64, model::StacksBlockHash)>,
+
+    /// Multi-sig contract calls found in the scanned stacks blocks
+    pub multisig_stacks_transactions:
+        BTreeMap<(model::StacksTxId, model::StacksBlockHash), model::MultisigStacksTransaction>,
+
+    /// Stacks blocks whose transactions have been scanned for multi-sig
+    /// contract calls
+    pub scanned_stacks_blocks: HashSet<model::StacksBlockHash>,
 }
 
 impl Store {
@@ -293,6 +384,114 @@ impl Store {
         .cloned()
     }
 
+    pub(super) fn get_stacks_chain_tip_v2(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Option<model::StacksChainTip> {
+        let bitcoin_chain_tip = self.bitcoin_blocks.get(bitcoin_chain_tip)?;
+
+        let block = std::iter::successors(Some(bitcoin_chain_tip), |block| {
+            self.bitcoin_blocks.get(&block.parent_hash)
+        })
+        .filter_map(|block| {
+            let stacks_blocks = self
+                .bitcoin_anchor_to_stacks_blocks
+                .get(&block.block_hash)?;
+            Some(
+                stacks_blocks
+                    .iter()
+                    .map(move |hash| (block.block_height, hash)),
+            )
+        })
+        .flatten()
+        .filter_map(|(anchor_height, hash)| Some((anchor_height, self.stacks_blocks.get(hash)?)))
+        .max_by_key(|(anchor_height, block)| {
+            (
+                block.block_height,
+                *anchor_height,
+                block.block_hash.into_bytes(),
+            )
+        })
+        .map(|(_, block)| block.clone())?;
+
+        let tenure = self.get_stacks_tenure(&block.bitcoin_anchor)?;
+        Some(model::StacksChainTip { block, tenure })
+    }
+
+    pub(super) fn get_unscanned_stacks_blocks(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+    ) -> Vec<model::StacksBlockHash> {
+        let first_block = self.bitcoin_blocks.get(chain_tip);
+
+        let mut blocks: Vec<&model::StacksBlock> = std::iter::successors(first_block, |block| {
+            self.bitcoin_blocks.get(&block.parent_hash)
+        })
+        .take(context_window as usize)
+        .filter_map(|block| self.bitcoin_anchor_to_stacks_blocks.get(&block.block_hash))
+        .flatten()
+        .filter(|block_hash| !self.scanned_stacks_blocks.contains(*block_hash))
+        .filter_map(|block_hash| self.stacks_blocks.get(block_hash))
+        .collect();
+
+        blocks.sort_by_key(|block| (block.block_height, block.block_hash));
+        blocks.iter().map(|block| block.block_hash).collect()
+    }
+
+    pub(super) fn get_multisig_stacks_transactions(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        stacks_chain_tip: &model::StacksBlockHash,
+        context_window: u16,
+    ) -> Vec<model::MultisigStacksTransaction> {
+        let first_block = self.bitcoin_blocks.get(bitcoin_chain_tip);
+        let context_window_blocks: HashSet<&model::BitcoinBlockHash> =
+            std::iter::successors(first_block, |block| {
+                self.bitcoin_blocks.get(&block.parent_hash)
+            })
+            .take(context_window as usize)
+            .map(|block| &block.block_hash)
+            .collect();
+
+        let stacks_blocks: HashSet<model::StacksBlockHash> =
+            std::iter::successors(self.stacks_blocks.get(stacks_chain_tip), |block| {
+                self.stacks_blocks.get(&block.parent_hash)
+            })
+            .take_while(|block| context_window_blocks.contains(&block.bitcoin_anchor))
+            .map(|block| block.block_hash)
+            .collect();
+
+        self.multisig_stacks_transactions
+            .values()
+            .filter(|tx| stacks_blocks.contains(&tx.block_hash))
+            .cloned()
+            .collect()
+    }
+
+    /// Summarize the stacks blocks that are anchored to the given bitcoin
+    /// block, which identifies their tenure.
+    fn get_stacks_tenure(
+        &self,
+        tenure_id: &model::BitcoinBlockHash,
+    ) -> Option<model::StacksTenure> {
+        let anchor_block = self.bitcoin_blocks.get(tenure_id)?;
+        let heights: Vec<model::StacksBlockHeight> = self
+            .stacks_blocks
+            .values()
+            .filter(|block| &block.bitcoin_anchor == tenure_id)
+            .map(|block| block.block_height)
+            .collect();
+
+        Some(model::StacksTenure {
+            tenure_id: *tenure_id,
+            anchor_height: anchor_block.block_height,
+            block_count: heights.len() as u64,
+            first_block_height: heights.iter().min().copied()?,
+            last_block_height: heights.iter().max().copied()?,
+        })
+    }
+
     pub(super) fn get_withdrawal_requests(
         &self,
         chain_tip: &model::BitcoinBlockHash,
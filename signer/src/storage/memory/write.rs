@@ -8,7 +8,7 @@ use crate::{
         DbWrite,
         model::{
             self, CompletedDepositEvent, DkgSharesStatus, WithdrawalAcceptEvent,
-            WithdrawalRejectEvent,
+            WithdrawalRejectEvent, WriteOutcome,
         },
     },
 };
@@ -16,13 +16,19 @@ use crate::{
 use super::{SharedStore, store::InMemoryTransaction};
 
 impl DbWrite for SharedStore {
-    async fn write_bitcoin_block(&self, block: &model::BitcoinBlock) -> Result<(), Error> {
+    async fn write_bitcoin_block(
+        &self,
+        block: &model::BitcoinBlock,
+    ) -> Result<WriteOutcome, Error> {
         let mut store = self.lock().await;
         store.version += 1;
 
+        if store.bitcoin_blocks.contains_key(&block.block_hash) {
+            return Ok(WriteOutcome::AlreadyExists);
+        }
         store.bitcoin_blocks.insert(block.block_hash, block.clone());
 
-        Ok(())
+        Ok(WriteOutcome::Inserted)
     }
 
     async fn write_bitcoin_transactions(&self, txs: Vec<model::BitcoinTxRef>) -> Result<(), Error> {
@@ -46,32 +52,36 @@ impl DbWrite for SharedStore {
         Ok(())
     }
 
-    async fn write_stacks_block(&self, block: &model::StacksBlock) -> Result<(), Error> {
+    async fn write_stacks_block(&self, block: &model::StacksBlock) -> Result<WriteOutcome, Error> {
         let mut store = self.lock().await;
         store.version += 1;
 
+        if store.stacks_blocks.contains_key(&block.block_hash) {
+            return Ok(WriteOutcome::AlreadyExists);
+        }
         store.stacks_blocks.insert(block.block_hash, block.clone());
         store
             .bitcoin_anchor_to_stacks_blocks
             .entry(block.bitcoin_anchor)
             .or_default()
             .push(block.block_hash);
-        Ok(())
+        Ok(WriteOutcome::Inserted)
     }
 
     async fn write_deposit_request(
         &self,
         deposit_request: &model::DepositRequest,
-    ) -> Result<(), Error> {
+    ) -> Result<WriteOutcome, Error> {
         let mut store = self.lock().await;
         store.version += 1;
 
-        store.deposit_requests.insert(
-            (deposit_request.txid, deposit_request.output_index),
-            deposit_request.clone(),
-        );
+        let pk = (deposit_request.txid, deposit_request.output_index);
+        if store.deposit_requests.contains_key(&pk) {
+            return Ok(WriteOutcome::AlreadyExists);
+        }
+        store.deposit_requests.insert(pk, deposit_request.clone());
 
-        Ok(())
+        Ok(WriteOutcome::Inserted)
     }
 
     async fn write_deposit_requests(
@@ -92,11 +102,14 @@ impl DbWrite for SharedStore {
     async fn write_withdrawal_request(
         &self,
         withdraw_request: &model::WithdrawalRequest,
-    ) -> Result<(), Error> {
+    ) -> Result<WriteOutcome, Error> {
         let mut store = self.lock().await;
         store.version += 1;
 
         let pk = (withdraw_request.request_id, withdraw_request.block_hash);
+        if store.withdrawal_requests.contains_key(&pk) {
+            return Ok(WriteOutcome::AlreadyExists);
+        }
 
         store
             .stacks_block_to_withdrawal_requests
@@ -108,23 +121,29 @@ impl DbWrite for SharedStore {
             .withdrawal_requests
             .insert(pk, withdraw_request.clone());
 
-        Ok(())
+        Ok(WriteOutcome::Inserted)
     }
 
     async fn write_deposit_signer_decision(
         &self,
         decision: &model::DepositSigner,
-    ) -> Result<(), Error> {
+    ) -> Result<WriteOutcome, Error> {
         let mut store = self.lock().await;
         store.version += 1;
 
         let deposit_request_pk = (decision.txid, decision.output_index);
 
-        store
+        let decisions = store
             .deposit_request_to_signers
             .entry(deposit_request_pk)
-            .or_default()
-            .push(decision.clone());
+            .or_default();
+        if decisions
+            .iter()
+            .any(|existing| existing.signer_pub_key == decision.signer_pub_key)
+        {
+            return Ok(WriteOutcome::AlreadyExists);
+        }
+        decisions.push(decision.clone());
 
         store
             .signer_to_deposit_request
@@ -132,37 +151,46 @@ impl DbWrite for SharedStore {
             .or_default()
             .push(deposit_request_pk);
 
-        Ok(())
+        Ok(WriteOutcome::Inserted)
     }
 
     async fn write_withdrawal_signer_decision(
         &self,
         decision: &model::WithdrawalSigner,
-    ) -> Result<(), Error> {
+    ) -> Result<WriteOutcome, Error> {
         let mut store = self.lock().await;
         store.version += 1;
 
-        store
+        let decisions = store
             .withdrawal_request_to_signers
             .entry((decision.request_id, decision.block_hash))
-            .or_default()
-            .push(decision.clone());
+            .or_default();
+        if decisions
+            .iter()
+            .any(|existing| existing.signer_pub_key == decision.signer_pub_key)
+        {
+            return Ok(WriteOutcome::AlreadyExists);
+        }
+        decisions.push(decision.clone());
 
-        Ok(())
+        Ok(WriteOutcome::Inserted)
     }
 
     async fn write_bitcoin_transaction(
         &self,
         bitcoin_transaction: &model::BitcoinTxRef,
-    ) -> Result<(), Error> {
+    ) -> Result<WriteOutcome, Error> {
         let mut store = self.lock().await;
         store.version += 1;
 
-        store
+        let inserted = store
             .bitcoin_block_to_transactions
             .entry(bitcoin_transaction.block_hash)
             .or_default()
             .insert(bitcoin_transaction.txid);
+        if !inserted {
+            return Ok(WriteOutcome::AlreadyExists);
+        }
 
         store
             .bitcoin_transactions_to_blocks
@@ -170,7 +198,7 @@ impl DbWrite for SharedStore {
             .or_default()
             .push(bitcoin_transaction.block_hash);
 
-        Ok(())
+        Ok(WriteOutcome::Inserted)
     }
 
     async fn write_stacks_block_headers(&self, headers: &TenureBlockHeaders) -> Result<(), Error> {
@@ -192,107 +220,140 @@ impl DbWrite for SharedStore {
     async fn write_encrypted_dkg_shares(
         &self,
         shares: &model::EncryptedDkgShares,
-    ) -> Result<(), Error> {
+    ) -> Result<WriteOutcome, Error> {
         let mut store = self.lock().await;
         store.version += 1;
 
-        store.encrypted_dkg_shares.insert(
-            shares.aggregate_key.into(),
-            (time::OffsetDateTime::now_utc(), shares.clone()),
-        );
+        let key: PublicKeyXOnly = shares.aggregate_key.into();
+        if store.encrypted_dkg_shares.contains_key(&key) {
+            return Ok(WriteOutcome::AlreadyExists);
+        }
+        store
+            .encrypted_dkg_shares
+            .insert(key, (time::OffsetDateTime::now_utc(), shares.clone()));
 
-        Ok(())
+        Ok(WriteOutcome::Inserted)
     }
 
     async fn write_rotate_keys_transaction(
         &self,
         key_rotation: &model::KeyRotationEvent,
-    ) -> Result<(), Error> {
+    ) -> Result<WriteOutcome, Error> {
         let mut store = self.lock().await;
         store.version += 1;
 
-        store
+        let key_rotations = store
             .rotate_keys_transactions
             .entry(key_rotation.block_hash)
-            .or_default()
-            .push(key_rotation.clone());
+            .or_default();
+        if key_rotations
+            .iter()
+            .any(|existing| existing.txid == key_rotation.txid)
+        {
+            return Ok(WriteOutcome::AlreadyExists);
+        }
+        key_rotations.push(key_rotation.clone());
 
-        Ok(())
+        Ok(WriteOutcome::Inserted)
     }
 
     async fn write_withdrawal_accept_event(
         &self,
         event: &WithdrawalAcceptEvent,
-    ) -> Result<(), Error> {
+    ) -> Result<WriteOutcome, Error> {
         let mut store = self.lock().await;
         store.version += 1;
 
+        // Events are keyed by their request here, so only the same event
+        // from the same stacks block counts as a duplicate.
+        let existing = store.withdrawal_accept_events.get(&event.request_id);
+        if existing.is_some_and(|e| e.txid == event.txid && e.block_id == event.block_id) {
+            return Ok(WriteOutcome::AlreadyExists);
+        }
         store
             .withdrawal_accept_events
             .insert(event.request_id, event.clone());
 
-        Ok(())
+        Ok(WriteOutcome::Inserted)
     }
 
     async fn write_withdrawal_reject_event(
         &self,
         event: &WithdrawalRejectEvent,
-    ) -> Result<(), Error> {
+    ) -> Result<WriteOutcome, Error> {
         let mut store = self.lock().await;
         store.version += 1;
 
+        // Events are keyed by their request here, so only the same event
+        // from the same stacks block counts as a duplicate.
+        let existing = store.withdrawal_reject_events.get(&event.request_id);
+        if existing.is_some_and(|e| e.txid == event.txid && e.block_id == event.block_id) {
+            return Ok(WriteOutcome::AlreadyExists);
+        }
         store
             .withdrawal_reject_events
             .insert(event.request_id, event.clone());
 
-        Ok(())
+        Ok(WriteOutcome::Inserted)
     }
 
     async fn write_completed_deposit_event(
         &self,
         event: &CompletedDepositEvent,
-    ) -> Result<(), Error> {
+    ) -> Result<WriteOutcome, Error> {
         let mut store = self.lock().await;
         store.version += 1;
 
+        // Events are keyed by their request here, so only the same event
+        // from the same stacks block counts as a duplicate.
+        let existing = store.completed_deposit_events.get(&event.outpoint);
+        if existing.is_some_and(|e| e.txid == event.txid && e.block_id == event.block_id) {
+            return Ok(WriteOutcome::AlreadyExists);
+        }
         store
             .completed_deposit_events
             .insert(event.outpoint, event.clone());
 
-        Ok(())
+        Ok(WriteOutcome::Inserted)
     }
 
-    async fn write_tx_output(&self, output: &model::TxOutput) -> Result<(), Error> {
+    async fn write_tx_output(&self, output: &model::TxOutput) -> Result<WriteOutcome, Error> {
         let mut store = self.lock().await;
         store.version += 1;
 
-        store
-            .bitcoin_outputs
-            .entry(output.txid)
-            .or_default()
-            .push(output.clone());
+        let outputs = store.bitcoin_outputs.entry(output.txid).or_default();
+        if outputs
+            .iter()
+            .any(|existing| existing.output_index == output.output_index)
+        {
+            return Ok(WriteOutcome::AlreadyExists);
+        }
+        outputs.push(output.clone());
 
-        Ok(())
+        Ok(WriteOutcome::Inserted)
     }
 
     async fn write_withdrawal_tx_output(
         &self,
         _output: &model::WithdrawalTxOutput,
-    ) -> Result<(), Error> {
+    ) -> Result<WriteOutcome, Error> {
         unimplemented!()
     }
 
-    async fn write_tx_prevout(&self, prevout: &model::TxPrevout) -> Result<(), Error> {
+    async fn write_tx_prevout(&self, prevout: &model::TxPrevout) -> Result<WriteOutcome, Error> {
         let mut store = self.lock().await;
         store.version += 1;
 
-        store
-            .bitcoin_prevouts
-            .entry(prevout.txid)
-            .or_default()
-            .push(prevout.clone());
+        let prevouts = store.bitcoin_prevouts.entry(prevout.txid).or_default();
+        if prevouts.iter().any(|existing| {
+            existing.prevout_txid == prevout.prevout_txid
+                && existing.prevout_output_index == prevout.prevout_output_index
+        }) {
+            return Ok(WriteOutcome::AlreadyExists);
+        }
+        prevouts.push(prevout.clone());
 
-        Ok(())
+        Ok(WriteOutcome::Inserted)
     }
 
     async fn write_bitcoin_withdrawals_outputs(
@@ -420,22 +481,548 @@ impl DbWrite for SharedStore {
 
         Ok(())
     }
+
+    async fn write_sbtc_supply_snapshot(
+        &self,
+        snapshot: &model::SbtcSupplySnapshot,
+    ) -> Result<(), Error> {
+        let mut store = self.lock().await;
+        store.version += 1;
+
+        store.sbtc_supply_snapshots.push(snapshot.clone());
+
+        Ok(())
+    }
+
+    async fn write_signer_utxo_violation(
+        &self,
+        violation: &model::SignerUtxoViolation,
+    ) -> Result<WriteOutcome, Error> {
+        let mut store = self.lock().await;
+        store.version += 1;
+
+        let exists = store
+            .signer_utxo_violations
+            .iter()
+            .any(|v| v.txid == violation.txid && v.block_hash == violation.block_hash);
+        if exists {
+            return Ok(WriteOutcome::AlreadyExists);
+        }
+        store.signer_utxo_violations.push(violation.clone());
+
+        Ok(WriteOutcome::Inserted)
+    }
+
+    async fn write_bitcoin_tx_bodies(&self, bodies: &[model::BitcoinTxBody]) -> Result<(), Error> {
+        let mut store = self.lock().await;
+        store.version += 1;
+
+        for body in bodies {
+            store
+                .bitcoin_tx_bodies
+                .entry((body.txid, body.block_hash))
+                .or_insert_with(|| body.clone());
+        }
+
+        Ok(())
+    }
+
+    async fn prune_bitcoin_tx_bodies(
+        &self,
+        min_block_height: model::BitcoinBlockHeight,
+    ) -> Result<u64, Error> {
+        let mut store = self.lock().await;
+        store.version += 1;
+
+        let count_before = store.bitcoin_tx_bodies.len();
+        store
+            .bitcoin_tx_bodies
+            .retain(|_, body| body.block_height >= min_block_height);
+
+        Ok((count_before - store.bitcoin_tx_bodies.len()) as u64)
+    }
+
+    async fn write_emily_imported_deposit(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+    ) -> Result<WriteOutcome, Error> {
+        let mut store = self.lock().await;
+        store.version += 1;
+
+        let inserted = store.emily_imported_deposits.insert((*txid, output_index));
+
+        Ok(WriteOutcome::from(inserted))
+    }
+
+    async fn write_emily_imported_withdrawal(
+        &self,
+        id: &model::QualifiedRequestId,
+    ) -> Result<WriteOutcome, Error> {
+        let mut store = self.lock().await;
+        store.version += 1;
+
+        let inserted = store
+            .emily_imported_withdrawals
+            .insert((id.request_id, id.block_hash));
+
+        Ok(WriteOutcome::from(inserted))
+    }
+
+    async fn update_peer_version(
+        &self,
+        peer_id: &PeerId,
+        agent_version: &str,
+        protocol_version: u32,
+    ) -> Result<(), Error> {
+        let mut store = self.lock().await;
+
+        let version = model::P2PPeerVersion {
+            peer_id: (*peer_id).into(),
+            agent_version: agent_version.to_string(),
+            protocol_version,
+            updated_at: time::OffsetDateTime::now_utc().into(),
+        };
+        store.p2p_peer_versions.insert(*peer_id, version);
+
+        Ok(())
+    }
+
+    async fn write_peer_ban(&self, ban: &model::P2PPeerBan) -> Result<(), Error> {
+        let mut store = self.lock().await;
+        store.p2p_peer_bans.insert(*ban.peer_id, ban.clone());
+
+        Ok(())
+    }
+
+    async fn delete_peer_ban(&self, peer_id: &PeerId) -> Result<(), Error> {
+        let mut store = self.lock().await;
+        store.p2p_peer_bans.remove(peer_id);
+
+        Ok(())
+    }
+
+    async fn write_dkg_verification_round(
+        &self,
+        round: &model::DkgVerificationRound,
+    ) -> Result<(), Error> {
+        let mut store = self.lock().await;
+        let key = (round.aggregate_key, round.bitcoin_block_hash);
+        let last_message_type = round.last_message_type.clone().or_else(|| {
+            store
+                .dkg_verification_rounds
+                .get(&key)
+                .and_then(|existing| existing.last_message_type.clone())
+        });
+        let round = model::DkgVerificationRound {
+            last_message_type,
+            updated_at: time::OffsetDateTime::now_utc().into(),
+            ..round.clone()
+        };
+        store.dkg_verification_rounds.insert(key, round);
+
+        Ok(())
+    }
+
+    async fn write_stacks_event_dead_letter(
+        &self,
+        letter: &model::StacksEventDeadLetter,
+    ) -> Result<(), Error> {
+        let mut store = self.lock().await;
+        let key = (letter.block_hash, letter.txid, letter.event_index);
+        store
+            .stacks_event_dead_letters
+            .entry(key)
+            .and_modify(|existing| {
+                existing.error = letter.error.clone();
+                existing.attempts += 1;
+            })
+            .or_insert_with(|| model::StacksEventDeadLetter {
+                attempts: 1,
+                created_at: time::OffsetDateTime::now_utc().into(),
+                resolved_at: None,
+                ..letter.clone()
+            });
+
+        Ok(())
+    }
+
+    async fn resolve_stacks_event_dead_letter(
+        &self,
+        block_hash: &model::StacksBlockHash,
+        txid: &model::StacksTxId,
+        event_index: u32,
+    ) -> Result<(), Error> {
+        let mut store = self.lock().await;
+        let key = (*block_hash, *txid, event_index);
+        if let Some(letter) = store.stacks_event_dead_letters.get_mut(&key) {
+            letter
+                .resolved_at
+                .get_or_insert_with(|| time::OffsetDateTime::now_utc().into());
+        }
+
+        Ok(())
+    }
+
+    async fn write_signed_sweep_transaction(
+        &self,
+        sweep: &model::SignedSweepTransaction,
+    ) -> Result<WriteOutcome, Error> {
+        let mut store = self.lock().await;
+        if store.signed_sweep_transactions.contains_key(&sweep.txid) {
+            return Ok(WriteOutcome::AlreadyExists);
+        }
+        store
+            .signed_sweep_transactions
+            .insert(sweep.txid, sweep.clone());
+
+        Ok(WriteOutcome::Inserted)
+    }
+
+    async fn delete_signed_sweep_transactions(
+        &self,
+        txids: &[model::BitcoinTxId],
+    ) -> Result<(), Error> {
+        let mut store = self.lock().await;
+        for txid in txids {
+            store.signed_sweep_transactions.remove(txid);
+        }
+
+        Ok(())
+    }
+
+    async fn write_sweep_fee_discrepancy(
+        &self,
+        discrepancy: &model::SweepFeeDiscrepancy,
+    ) -> Result<WriteOutcome, Error> {
+        let mut store = self.lock().await;
+        store.version += 1;
+
+        let exists = store
+            .sweep_fee_discrepancies
+            .iter()
+            .any(|d| d.txid == discrepancy.txid);
+        if exists {
+            return Ok(WriteOutcome::AlreadyExists);
+        }
+        store.sweep_fee_discrepancies.push(discrepancy.clone());
+
+        Ok(WriteOutcome::Inserted)
+    }
+
+    async fn write_key_rotation_attestation(
+        &self,
+        attestation: &model::KeyRotationAttestation,
+    ) -> Result<WriteOutcome, Error> {
+        let mut store = self.lock().await;
+        store.version += 1;
+
+        let exists = store.key_rotation_attestations.iter().any(|a| {
+            a.aggregate_key == attestation.aggregate_key
+                && a.signer_public_key == attestation.signer_public_key
+        });
+        if exists {
+            return Ok(WriteOutcome::AlreadyExists);
+        }
+        store.key_rotation_attestations.push(attestation.clone());
+
+        Ok(WriteOutcome::Inserted)
+    }
+
+    async fn write_sweep_package(&self, package: &model::SweepPackage) -> Result<(), Error> {
+        let mut store = self.lock().await;
+        store
+            .sweep_packages
+            .retain(|chain_tip, _| chain_tip == &package.bitcoin_chain_tip);
+        store
+            .sweep_packages
+            .insert(package.bitcoin_chain_tip, package.clone());
+
+        Ok(())
+    }
+
+    async fn write_sweep_package_broadcast(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        txid: &model::BitcoinTxId,
+    ) -> Result<(), Error> {
+        let mut store = self.lock().await;
+        let package = store
+            .sweep_packages
+            .get_mut(bitcoin_chain_tip)
+            .filter(|package| !package.broadcast_txids.contains(txid));
+        if let Some(package) = package {
+            package.broadcast_txids.push(*txid);
+        }
+
+        Ok(())
+    }
+
+    async fn write_deposit_request_timestamp(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+        stage: model::RequestLifecycleStage,
+    ) -> Result<(), Error> {
+        let now = time::OffsetDateTime::now_utc().into();
+        let mut store = self.lock().await;
+        store
+            .deposit_request_timestamps
+            .entry((*txid, output_index))
+            .and_modify(|timestamps| timestamps.record(stage, now))
+            .or_insert_with(|| model::RequestTimestamps::new(stage, now));
+
+        Ok(())
+    }
+
+    async fn write_withdrawal_request_timestamp(
+        &self,
+        request_id: u64,
+        stage: model::RequestLifecycleStage,
+    ) -> Result<(), Error> {
+        let now = time::OffsetDateTime::now_utc().into();
+        let mut store = self.lock().await;
+        store
+            .withdrawal_request_timestamps
+            .entry(request_id)
+            .and_modify(|timestamps| timestamps.record(stage, now))
+            .or_insert_with(|| model::RequestTimestamps::new(stage, now));
+
+        Ok(())
+    }
+
+    async fn write_bitcoin_backfill_progress(
+        &self,
+        progress: &model::BitcoinBackfillProgress,
+    ) -> Result<(), Error> {
+        self.lock().await.bitcoin_backfill_progress = Some(progress.clone());
+
+        Ok(())
+    }
+
+    async fn write_coordinator_takeover(
+        &self,
+        takeover: &model::CoordinatorTakeover,
+    ) -> Result<WriteOutcome, Error> {
+        let mut store = self.lock().await;
+        if store
+            .coordinator_takeovers
+            .contains_key(&takeover.bitcoin_chain_tip)
+        {
+            return Ok(WriteOutcome::AlreadyExists);
+        }
+        store
+            .coordinator_takeovers
+            .insert(takeover.bitcoin_chain_tip, *takeover);
+
+        Ok(WriteOutcome::Inserted)
+    }
+
+    async fn write_peer_address_record(
+        &self,
+        record: &model::P2PPeerAddressRecord,
+    ) -> Result<bool, Error> {
+        let mut store = self.lock().await;
+        let is_newer = store
+            .p2p_peer_address_records
+            .get(&record.public_key)
+            .is_none_or(|existing| existing.expires_at < record.expires_at);
+
+        if is_newer {
+            store
+                .p2p_peer_address_records
+                .insert(record.public_key, record.clone());
+        }
+
+        Ok(is_newer)
+    }
+
+    async fn write_request_override(
+        &self,
+        request_override: &model::NewRequestOverride,
+    ) -> Result<model::RequestOverride, Error> {
+        let mut store = self.lock().await;
+        let stored = model::RequestOverride {
+            id: store.request_overrides.len() as i64 + 1,
+            target: request_override.target,
+            directive: request_override.directive,
+            reason: request_override.reason.clone(),
+            expires_at: request_override.expires_at,
+            created_at: time::OffsetDateTime::now_utc().into(),
+            revoked_at: None,
+        };
+        store.request_overrides.push(stored.clone());
+
+        Ok(stored)
+    }
+
+    async fn revoke_request_override(&self, id: i64) -> Result<bool, Error> {
+        let mut store = self.lock().await;
+        let request_override = store.request_overrides.iter_mut().find(|request_override| {
+            request_override.id == id && request_override.revoked_at.is_none()
+        });
+
+        let Some(request_override) = request_override else {
+            return Ok(false);
+        };
+        request_override.revoked_at = Some(time::OffsetDateTime::now_utc().into());
+
+        Ok(true)
+    }
+
+    async fn write_inflight_contract_call(
+        &self,
+        contract_call: &model::InflightContractCall,
+    ) -> Result<(), Error> {
+        let mut store = self.lock().await;
+        store
+            .inflight_contract_calls
+            .insert(contract_call.target.clone(), contract_call.clone());
+
+        Ok(())
+    }
+
+    async fn delete_inflight_contract_call(
+        &self,
+        target: &model::ContractCallTarget,
+    ) -> Result<bool, Error> {
+        let mut store = self.lock().await;
+        Ok(store.inflight_contract_calls.remove(target).is_some())
+    }
+
+    async fn write_withdrawal_output_labels(
+        &self,
+        labels: &[model::WithdrawalOutputLabel],
+    ) -> Result<(), Error> {
+        let mut store = self.lock().await;
+        store.version += 1;
+
+        store.withdrawal_output_labels = labels
+            .iter()
+            .map(|label| (label.script_pubkey.clone(), label.label.clone()))
+            .collect();
+
+        Ok(())
+    }
+
+    async fn write_spilled_state_machine(
+        &self,
+        state_machine: &model::SpilledStateMachine,
+    ) -> Result<(), Error> {
+        let mut store = self.lock().await;
+        store.version += 1;
+
+        store.spilled_state_machines.insert(
+            state_machine.state_machine_id.clone(),
+            state_machine.clone(),
+        );
+
+        Ok(())
+    }
+
+    async fn take_spilled_state_machine(
+        &self,
+        state_machine_id: &str,
+    ) -> Result<Option<model::SpilledStateMachine>, Error> {
+        let mut store = self.lock().await;
+        store.version += 1;
+
+        Ok(store.spilled_state_machines.remove(state_machine_id))
+    }
+
+    async fn prune_spilled_state_machines(
+        &self,
+        min_block_height: model::BitcoinBlockHeight,
+    ) -> Result<u64, Error> {
+        let mut store = self.lock().await;
+        store.version += 1;
+
+        let count_before = store.spilled_state_machines.len();
+        store
+            .spilled_state_machines
+            .retain(|_, state_machine| state_machine.bitcoin_block_height >= min_block_height);
+
+        Ok((count_before - store.spilled_state_machines.len()) as u64)
+    }
+
+    async fn write_bitcoin_block_header(
+        &self,
+        header: &bitcoin::block::Header,
+    ) -> Result<WriteOutcome, Error> {
+        let mut store = self.lock().await;
+        store.version += 1;
+
+        let block_hash = model::BitcoinBlockHash::from(header.block_hash());
+        if store.bitcoin_block_headers.contains_key(&block_hash) {
+            return Ok(WriteOutcome::AlreadyExists);
+        }
+        store.bitcoin_block_headers.insert(block_hash, *header);
+
+        Ok(WriteOutcome::Inserted)
+    }
+
+    async fn write_bitcoin_tx_merkle_proofs(
+        &self,
+        proofs: &[model::BitcoinTxMerkleProof],
+    ) -> Result<(), Error> {
+        let mut store = self.lock().await;
+        store.version += 1;
+
+        for proof in proofs {
+            store
+                .bitcoin_tx_merkle_proofs
+                .entry((proof.txid, proof.block_hash))
+                .or_insert_with(|| proof.clone());
+        }
+
+        Ok(())
+    }
+
+    async fn write_multisig_stacks_transaction(
+        &self,
+        tx: &model::MultisigStacksTransaction,
+    ) -> Result<(), Error> {
+        let mut store = self.lock().await;
+        store.version += 1;
+
+        store
+            .multisig_stacks_transactions
+            .entry((tx.txid, tx.block_hash))
+            .or_insert_with(|| tx.clone());
+
+        Ok(())
+    }
+
+    async fn write_scanned_stacks_block(
+        &self,
+        block_hash: &model::StacksBlockHash,
+    ) -> Result<(), Error> {
+        let mut store = self.lock().await;
+        store.version += 1;
+
+        store.scanned_stacks_blocks.insert(*block_hash);
+
+        Ok(())
+    }
 }
 
 impl DbWrite for InMemoryTransaction {
-    async fn write_bitcoin_block(&self, block: &model::BitcoinBlock) -> Result<(), Error> {
+    async fn write_bitcoin_block(
+        &self,
+        block: &model::BitcoinBlock,
+    ) -> Result<WriteOutcome, Error> {
         self.store.write_bitcoin_block(block).await
     }
 
     #[cfg(any(test, feature = "testing"))]
-    async fn write_stacks_block(&self, block: &model::StacksBlock) -> Result<(), Error> {
+    async fn write_stacks_block(&self, block: &model::StacksBlock) -> Result<WriteOutcome, Error> {
         self.store.write_stacks_block(block).await
     }
 
     async fn write_deposit_request(
         &self,
         deposit_request: &model::DepositRequest,
-    ) -> Result<(), Error> {
+    ) -> Result<WriteOutcome, Error> {
         self.store.write_deposit_request(deposit_request).await
     }
 
@@ -449,28 +1036,28 @@ impl DbWrite for InMemoryTransaction {
     async fn write_withdrawal_request(
         &self,
         request: &model::WithdrawalRequest,
-    ) -> Result<(), Error> {
+    ) -> Result<WriteOutcome, Error> {
         self.store.write_withdrawal_request(request).await
     }
 
     async fn write_deposit_signer_decision(
         &self,
         decision: &model::DepositSigner,
-    ) -> Result<(), Error> {
+    ) -> Result<WriteOutcome, Error> {
         self.store.write_deposit_signer_decision(decision).await
     }
 
     async fn write_withdrawal_signer_decision(
         &self,
         decision: &model::WithdrawalSigner,
-    ) -> Result<(), Error> {
+    ) -> Result<WriteOutcome, Error> {
         self.store.write_withdrawal_signer_decision(decision).await
     }
 
     async fn write_bitcoin_transaction(
         &self,
         bitcoin_transaction: &model::BitcoinTxRef,
-    ) -> Result<(), Error> {
+    ) -> Result<WriteOutcome, Error> {
         self.store
             .write_bitcoin_transaction(bitcoin_transaction)
             .await
@@ -487,50 +1074,50 @@ impl DbWrite for InMemoryTransaction {
     async fn write_encrypted_dkg_shares(
         &self,
         shares: &model::EncryptedDkgShares,
-    ) -> Result<(), Error> {
+    ) -> Result<WriteOutcome, Error> {
         self.store.write_encrypted_dkg_shares(shares).await
     }
 
     async fn write_rotate_keys_transaction(
         &self,
         key_rotation: &model::KeyRotationEvent,
-    ) -> Result<(), Error> {
+    ) -> Result<WriteOutcome, Error> {
         self.store.write_rotate_keys_transaction(key_rotation).await
     }
 
     async fn write_withdrawal_reject_event(
         &self,
         event: &WithdrawalRejectEvent,
-    ) -> Result<(), Error> {
+    ) -> Result<WriteOutcome, Error> {
         self.store.write_withdrawal_reject_event(event).await
     }
 
     async fn write_withdrawal_accept_event(
         &self,
         event: &WithdrawalAcceptEvent,
-    ) -> Result<(), Error> {
+    ) -> Result<WriteOutcome, Error> {
         self.store.write_withdrawal_accept_event(event).await
     }
 
     async fn write_completed_deposit_event(
         &self,
         event: &CompletedDepositEvent,
-    ) -> Result<(), Error> {
+    ) -> Result<WriteOutcome, Error> {
         self.store.write_completed_deposit_event(event).await
     }
 
-    async fn write_tx_output(&self, output: &model::TxOutput) -> Result<(), Error> {
+    async fn write_tx_output(&self, output: &model::TxOutput) -> Result<WriteOutcome, Error> {
         self.store.write_tx_output(output).await
     }
 
     async fn write_withdrawal_tx_output(
         &self,
         output: &model::WithdrawalTxOutput,
-    ) -> Result<(), Error> {
+    ) -> Result<WriteOutcome, Error> {
         self.store.write_withdrawal_tx_output(output).await
     }
 
-    async fn write_tx_prevout(&self, prevout: &model::TxPrevout) -> Result<(), Error> {
+    async fn write_tx_prevout(&self, prevout: &model::TxPrevout) -> Result<WriteOutcome, Error> {
         self.store.write_tx_prevout(prevout).await
     }
 
@@ -581,4 +1168,259 @@ impl DbWrite for InMemoryTransaction {
     ) -> Result<(), Error> {
         self.store.set_canonical_bitcoin_blockchain(chain_tip).await
     }
+
+    async fn write_sbtc_supply_snapshot(
+        &self,
+        snapshot: &model::SbtcSupplySnapshot,
+    ) -> Result<(), Error> {
+        self.store.write_sbtc_supply_snapshot(snapshot).await
+    }
+
+    async fn write_signer_utxo_violation(
+        &self,
+        violation: &model::SignerUtxoViolation,
+    ) -> Result<WriteOutcome, Error> {
+        self.store.write_signer_utxo_violation(violation).await
+    }
+
+    async fn write_bitcoin_tx_bodies(&self, bodies: &[model::BitcoinTxBody]) -> Result<(), Error> {
+        self.store.write_bitcoin_tx_bodies(bodies).await
+    }
+
+    async fn prune_bitcoin_tx_bodies(
+        &self,
+        min_block_height: model::BitcoinBlockHeight,
+    ) -> Result<u64, Error> {
+        self.store.prune_bitcoin_tx_bodies(min_block_height).await
+    }
+
+    async fn write_emily_imported_deposit(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+    ) -> Result<WriteOutcome, Error> {
+        self.store
+            .write_emily_imported_deposit(txid, output_index)
+            .await
+    }
+
+    async fn write_emily_imported_withdrawal(
+        &self,
+        id: &model::QualifiedRequestId,
+    ) -> Result<WriteOutcome, Error> {
+        self.store.write_emily_imported_withdrawal(id).await
+    }
+
+    async fn update_peer_version(
+        &self,
+        peer_id: &PeerId,
+        agent_version: &str,
+        protocol_version: u32,
+    ) -> Result<(), Error> {
+        self.store
+            .update_peer_version(peer_id, agent_version, protocol_version)
+            .await
+    }
+
+    async fn write_peer_ban(&self, ban: &model::P2PPeerBan) -> Result<(), Error> {
+        self.store.write_peer_ban(ban).await
+    }
+
+    async fn delete_peer_ban(&self, peer_id: &PeerId) -> Result<(), Error> {
+        self.store.delete_peer_ban(peer_id).await
+    }
+
+    async fn write_dkg_verification_round(
+        &self,
+        round: &model::DkgVerificationRound,
+    ) -> Result<(), Error> {
+        self.store.write_dkg_verification_round(round).await
+    }
+
+    async fn write_stacks_event_dead_letter(
+        &self,
+        letter: &model::StacksEventDeadLetter,
+    ) -> Result<(), Error> {
+        self.store.write_stacks_event_dead_letter(letter).await
+    }
+
+    async fn resolve_stacks_event_dead_letter(
+        &self,
+        block_hash: &model::StacksBlockHash,
+        txid: &model::StacksTxId,
+        event_index: u32,
+    ) -> Result<(), Error> {
+        self.store
+            .resolve_stacks_event_dead_letter(block_hash, txid, event_index)
+            .await
+    }
+
+    async fn write_signed_sweep_transaction(
+        &self,
+        sweep: &model::SignedSweepTransaction,
+    ) -> Result<WriteOutcome, Error> {
+        self.store.write_signed_sweep_transaction(sweep).await
+    }
+
+    async fn delete_signed_sweep_transactions(
+        &self,
+        txids: &[model::BitcoinTxId],
+    ) -> Result<(), Error> {
+        self.store.delete_signed_sweep_transactions(txids).await
+    }
+
+    async fn write_sweep_fee_discrepancy(
+        &self,
+        discrepancy: &model::SweepFeeDiscrepancy,
+    ) -> Result<WriteOutcome, Error> {
+        self.store.write_sweep_fee_discrepancy(discrepancy).await
+    }
+
+    async fn write_key_rotation_attestation(
+        &self,
+        attestation: &model::KeyRotationAttestation,
+    ) -> Result<WriteOutcome, Error> {
+        self.store.write_key_rotation_attestation(attestation).await
+    }
+
+    async fn write_sweep_package(&self, package: &model::SweepPackage) -> Result<(), Error> {
+        self.store.write_sweep_package(package).await
+    }
+
+    async fn write_sweep_package_broadcast(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        txid: &model::BitcoinTxId,
+    ) -> Result<(), Error> {
+        self.store
+            .write_sweep_package_broadcast(bitcoin_chain_tip, txid)
+            .await
+    }
+
+    async fn write_deposit_request_timestamp(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+        stage: model::RequestLifecycleStage,
+    ) -> Result<(), Error> {
+        self.store
+            .write_deposit_request_timestamp(txid, output_index, stage)
+            .await
+    }
+
+    async fn write_withdrawal_request_timestamp(
+        &self,
+        request_id: u64,
+        stage: model::RequestLifecycleStage,
+    ) -> Result<(), Error> {
+        self.store
+            .write_withdrawal_request_timestamp(request_id, stage)
+            .await
+    }
+
+    async fn write_bitcoin_backfill_progress(
+        &self,
+        progress: &model::BitcoinBackfillProgress,
+    ) -> Result<(), Error> {
+        self.store.write_bitcoin_backfill_progress(progress).await
+    }
+
+    async fn write_coordinator_takeover(
+        &self,
+        takeover: &model::CoordinatorTakeover,
+    ) -> Result<WriteOutcome, Error> {
+        self.store.write_coordinator_takeover(takeover).await
+    }
+
+    async fn write_peer_address_record(
+        &self,
+        record: &model::P2PPeerAddressRecord,
+    ) -> Result<bool, Error> {
+        self.store.write_peer_address_record(record).await
+    }
+
+    async fn write_request_override(
+        &self,
+        request_override: &model::NewRequestOverride,
+    ) -> Result<model::RequestOverride, Error> {
+        self.store.write_request_override(request_override).await
+    }
+
+    async fn revoke_request_override(&self, id: i64) -> Result<bool, Error> {
+        self.store.revoke_request_override(id).await
+    }
+
+    async fn write_inflight_contract_call(
+        &self,
+        contract_call: &model::InflightContractCall,
+    ) -> Result<(), Error> {
+        self.store.write_inflight_contract_call(contract_call).await
+    }
+
+    async fn delete_inflight_contract_call(
+        &self,
+        target: &model::ContractCallTarget,
+    ) -> Result<bool, Error> {
+        self.store.delete_inflight_contract_call(target).await
+    }
+
+    async fn write_withdrawal_output_labels(
+        &self,
+        labels: &[model::WithdrawalOutputLabel],
+    ) -> Result<(), Error> {
+        self.store.write_withdrawal_output_labels(labels).await
+    }
+
+    async fn write_spilled_state_machine(
+        &self,
+        state_machine: &model::SpilledStateMachine,
+    ) -> Result<(), Error> {
+        self.store.write_spilled_state_machine(state_machine).await
+    }
+
+    async fn take_spilled_state_machine(
+        &self,
+        state_machine_id: &str,
+    ) -> Result<Option<model::SpilledStateMachine>, Error> {
+        self.store
+            .take_spilled_state_machine(state_machine_id)
+            .await
+    }
+
+    async fn prune_spilled_state_machines(
+        &self,
+        min_block_height: model::BitcoinBlockHeight,
+    ) -> Result<u64, Error> {
+        self.store
+            .prune_spilled_state_machines(min_block_height)
+            .await
+    }
+
+    async fn write_bitcoin_block_header(
+        &self,
+        header: &bitcoin::block::Header,
+    ) -> Result<WriteOutcome, Error> {
+        self.store.write_bitcoin_block_header(header).await
+    }
+
+    async fn write_bitcoin_tx_merkle_proofs(
+        &self,
+        proofs: &[model::BitcoinTxMerkleProof],
+    ) -> Result<(), Error> {
+        self.store.write_bitcoin_tx_merkle_proofs(proofs).await
+    }
+
+    async fn write_multisig_stacks_transaction(
+        &self,
+        tx: &model::MultisigStacksTransaction,
+    ) -> Result<(), Error> {
+        self.store.write_multisig_stacks_transaction(tx).await
+    }
+
+    async fn write_scanned_stacks_block(
+        &self,
+        block_hash: &model::StacksBlockHash,
+    ) -> Result<(), Error> {
+        self.store.write_scanned_stacks_block(block_hash).await
+    }
 }
@@ -22,6 +22,7 @@ use libp2p::PeerId;
 use crate::bitcoin::utxo::SignerUtxo;
 use crate::bitcoin::validation::DepositRequestReport;
 use crate::bitcoin::validation::WithdrawalRequestReport;
+use crate::config::DepositConfirmationPolicy;
 use crate::error::Error;
 use crate::keys::PublicKey;
 use crate::keys::PublicKeyXOnly;
@@ -99,17 +100,30 @@ pub trait DbRead {
         signer_public_key: &PublicKey,
     ) -> impl Future<Output = Result<Vec<model::DepositRequest>, Error>> + Send;
 
+    /// Get all deposit requests whose transactions are confirmed in the
+    /// `context_window` blocks ending at the given chain tip, whether or
+    /// not the signers have voted on them or swept them.
+    fn get_recent_deposit_requests(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+    ) -> impl Future<Output = Result<Vec<model::DepositRequest>, Error>> + Send;
+
     /// Get pending deposit requests that have been accepted by at least
     /// `signatures_required` signers and has no responses.
     ///
     /// For an individual signer, 'accepted' means their blocklist client
     /// hasn't blocked the request and they are part of the signing set
     /// that generated the aggregate key locking the deposit.
+    ///
+    /// Deposits that do not yet have the number of confirmations required
+    /// for their amount by the `confirmation_policy` are excluded.
     fn get_pending_accepted_deposit_requests(
         &self,
         chain_tip: &model::BitcoinBlockRef,
         context_window: u16,
         signatures_required: u16,
+        confirmation_policy: &DepositConfirmationPolicy,
     ) -> impl Future<Output = Result<Vec<model::DepositRequest>, Error>> + Send;
 
     /// Check whether we have a record of the deposit request in our
@@ -356,11 +370,24 @@ pub trait DbRead {
     ///    transaction within the same block to satisfy points 1-3, but if
     ///    the signers have one or more transactions within a block,
     ///    exactly one output satisfying points 1-2 will be unspent.
+    ///
+    /// When sweep transactions split the signers' change across more than
+    /// one output, this returns the largest of the UTXOs returned by
+    /// [`DbRead::get_signer_utxos`].
     fn get_signer_utxo(
         &self,
         chain_tip: &model::BitcoinBlockHash,
     ) -> impl Future<Output = Result<Option<SignerUtxo>, Error>> + Send;
 
+    /// Get all unspent signer UTXOs on the canonical bitcoin blockchain
+    /// identified by the given chain tip, ordered by amount, largest
+    /// first. If there are no such outputs from sweep transactions, this
+    /// returns the unspent donation UTXOs instead.
+    fn get_signer_utxos(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+    ) -> impl Future<Output = Result<Vec<SignerUtxo>, Error>> + Send;
+
     /// For the given outpoint and aggregate key, get the list all signer
     /// votes in the signer set.
     fn get_deposit_request_signer_votes(
@@ -378,6 +405,15 @@ pub trait DbRead {
         aggregate_key: &PublicKey,
     ) -> impl Future<Output = Result<model::SignerVotes, Error>> + Send;
 
+    /// Get the withdrawal requests with the given request ID, ordered by
+    /// the height of the bitcoin block that they were created at. There is
+    /// more than one only if the request was created in stacks blocks on
+    /// different forks of the stacks blockchain.
+    fn get_withdrawal_requests_by_id(
+        &self,
+        request_id: u64,
+    ) -> impl Future<Output = Result<Vec<model::WithdrawalRequest>, Error>> + Send;
+
     /// Check for whether  the given block hash is in the database.
     fn is_known_bitcoin_block_hash(
         &self,
@@ -469,28 +505,348 @@ pub trait DbRead {
 
     /// Returns the list of stored peers.
     fn get_p2p_peers(&self) -> impl Future<Output = Result<Vec<model::P2PPeer>, Error>> + Send;
+
+    /// Return the total amount of sBTC minted by completed deposits and
+    /// burned by accepted withdrawals.
+    ///
+    /// # Notes
+    ///
+    /// Events are deduplicated by the deposit outpoint and the withdrawal
+    /// request ID, so an event that was confirmed in more than one stacks
+    /// fork is only counted once.
+    fn get_sbtc_supply_totals(
+        &self,
+    ) -> impl Future<Output = Result<model::SbtcSupplyTotals, Error>> + Send;
+
+    /// Return the most recent signer UTXO invariant violations, newest
+    /// first, returning at most `limit` of them.
+    fn get_signer_utxo_violations(
+        &self,
+        limit: u16,
+    ) -> impl Future<Output = Result<Vec<model::SignerUtxoViolation>, Error>> + Send;
+
+    /// Get the full body of the bitcoin transaction with the given ID, as
+    /// confirmed in the bitcoin block with the given hash, if it has been
+    /// cached. Transaction bodies are only cached when the
+    /// `bitcoin_tx_body_retention` config is set.
+    fn get_bitcoin_tx_body(
+        &self,
+        txid: &model::BitcoinTxId,
+        block_hash: &model::BitcoinBlockHash,
+    ) -> impl Future<Output = Result<Option<model::BitcoinTxBody>, Error>> + Send;
+
+    /// Return a summary of the sBTC activity in the bitcoin block with the
+    /// given hash: the sweep transactions in the block, the deposits that
+    /// they swept, the withdrawals that they fulfilled and the fees that
+    /// they paid. The summary is empty if there are no sweep transactions
+    /// in the block or if the block is unknown.
+    fn get_block_activity(
+        &self,
+        block_hash: &model::BitcoinBlockHash,
+    ) -> impl Future<Output = Result<model::BlockActivitySummary, Error>> + Send;
+
+    /// Returns whether the deposit request with the given outpoint was
+    /// imported from Emily.
+    fn is_emily_imported_deposit(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+    ) -> impl Future<Output = Result<bool, Error>> + Send;
+
+    /// Returns whether the withdrawal request with the given ID was
+    /// imported from Emily.
+    fn is_emily_imported_withdrawal(
+        &self,
+        id: &model::QualifiedRequestId,
+    ) -> impl Future<Output = Result<bool, Error>> + Send;
+
+    /// Returns the versions most recently advertised by each P2P peer.
+    fn get_p2p_peer_versions(
+        &self,
+    ) -> impl Future<Output = Result<Vec<model::P2PPeerVersion>, Error>> + Send;
+
+    /// Returns the P2P peer bans that have not expired yet.
+    fn get_active_peer_bans(
+        &self,
+    ) -> impl Future<Output = Result<Vec<model::P2PPeerBan>, Error>> + Send;
+
+    /// Returns the DKG verification rounds that are still marked as
+    /// active.
+    fn get_active_dkg_verification_rounds(
+        &self,
+    ) -> impl Future<Output = Result<Vec<model::DkgVerificationRound>, Error>> + Send;
+
+    /// Returns the sbtc-registry events that could not be parsed and that
+    /// have not been successfully replayed yet, oldest first.
+    fn get_unresolved_stacks_event_dead_letters(
+        &self,
+    ) -> impl Future<Output = Result<Vec<model::StacksEventDeadLetter>, Error>> + Send;
+
+    /// Returns the sweep transactions that were fully signed by the
+    /// signers and persisted before being broadcast, oldest first.
+    fn get_signed_sweep_transactions(
+        &self,
+    ) -> impl Future<Output = Result<Vec<model::SignedSweepTransaction>, Error>> + Send;
+
+    /// Returns each aggregate key that has locked the signers' UTXO on
+    /// the canonical bitcoin blockchain identified by the given chain
+    /// tip, along with the range of bitcoin block heights during which it
+    /// did so, ordered by the start of the range.
+    ///
+    /// # Notes
+    ///
+    /// An aggregate key starts locking the signers' UTXO in the first
+    /// block that confirms a signers' output or donation locked by it,
+    /// and stops once a signers' output locked by another key is
+    /// confirmed. Aggregate keys that have been registered in a
+    /// rotate-keys transaction but that have not locked a signers' UTXO
+    /// yet are not returned.
+    fn get_aggregate_key_history(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+    ) -> impl Future<Output = Result<Vec<model::AggregateKeyPeriod>, Error>> + Send;
+
+    /// Get the number of deposit and withdrawal requests in each stage of
+    /// processing, for deposits confirmed on the canonical bitcoin
+    /// blockchain identified by the given chain tip and withdrawals
+    /// confirmed on the stacks blockchain anchored to it.
+    ///
+    /// Signer votes are weighed against the threshold of the latest
+    /// verified DKG shares. See [`model::RequestStatus`] for what each
+    /// stage means.
+    fn get_request_counts(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+    ) -> impl Future<Output = Result<model::RequestCounts, Error>> + Send;
+
+    /// Return the most recent sweep fee discrepancies, newest first,
+    /// returning at most `limit` of them.
+    fn get_sweep_fee_discrepancies(
+        &self,
+        limit: u16,
+    ) -> impl Future<Output = Result<Vec<model::SweepFeeDiscrepancy>, Error>> + Send;
+
+    /// Return all key rotation attestations that were made for the given
+    /// aggregate key.
+    fn get_key_rotation_attestations(
+        &self,
+        aggregate_key: &PublicKey,
+    ) -> impl Future<Output = Result<Vec<model::KeyRotationAttestation>, Error>> + Send;
+
+    /// Return how much of the given rolling withdrawal cap has been used
+    /// by sweep transactions confirmed on the bitcoin blockchain
+    /// identified by the given chain tip and context window.
+    ///
+    /// The withdrawn total is the same one returned by
+    /// [`DbRead::compute_withdrawn_total`], broken down by block so that
+    /// we can tell when it leaves the window.
+    fn get_caps_utilization(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+        rolling_withdrawal_cap: u64,
+    ) -> impl Future<Output = Result<model::CapsUtilization, Error>> + Send;
+
+    /// Returns the sweep package that this signer constructed as the
+    /// coordinator for the given bitcoin chain tip, if any.
+    fn get_sweep_package(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> impl Future<Output = Result<Option<model::SweepPackage>, Error>> + Send;
+
+    /// Returns the withdrawal requests serviced by a valid transaction in
+    /// any of the sweep transaction packages that this signer agreed to
+    /// sign for the given bitcoin chain tip, ordered by request ID and
+    /// stacks block hash.
+    fn get_presigned_withdrawals(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> impl Future<Output = Result<Vec<model::WithdrawalRequest>, Error>> + Send;
+
+    /// Returns when the deposit request reached each stage of its
+    /// lifecycle, if this signer recorded any of them.
+    fn get_deposit_request_timestamps(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+    ) -> impl Future<Output = Result<Option<model::RequestTimestamps>, Error>> + Send;
+
+    /// Returns when the withdrawal request with the given request ID
+    /// reached each stage of its lifecycle, if this signer recorded any of
+    /// them.
+    fn get_withdrawal_request_timestamps(
+        &self,
+        request_id: u64,
+    ) -> impl Future<Output = Result<Option<model::RequestTimestamps>, Error>> + Send;
+
+    /// Returns a page of the given signer's decisions on the deposit
+    /// requests confirmed in bitcoin blocks with heights between
+    /// `start_height` and `end_height`, inclusive. The decisions are
+    /// ordered by outpoint, so consecutive pages do not overlap.
+    fn get_deposit_signer_decisions_in_range(
+        &self,
+        signer_public_key: &PublicKey,
+        start_height: model::BitcoinBlockHeight,
+        end_height: model::BitcoinBlockHeight,
+        limit: u16,
+        offset: u32,
+    ) -> impl Future<Output = Result<Vec<model::DepositSigner>, Error>> + Send;
+
+    /// Returns a page of the given signer's decisions on the withdrawal
+    /// requests created in stacks blocks anchored to bitcoin blocks with
+    /// heights between `start_height` and `end_height`, inclusive. The
+    /// decisions are ordered by request ID and stacks block hash, so
+    /// consecutive pages do not overlap.
+    fn get_withdrawal_signer_decisions_in_range(
+        &self,
+        signer_public_key: &PublicKey,
+        start_height: model::BitcoinBlockHeight,
+        end_height: model::BitcoinBlockHeight,
+        limit: u16,
+        offset: u32,
+    ) -> impl Future<Output = Result<Vec<model::WithdrawalSigner>, Error>> + Send;
+
+    /// Returns the progress of the most recent bitcoin block backfill, if
+    /// the block observer has started one.
+    fn get_bitcoin_backfill_progress(
+        &self,
+    ) -> impl Future<Output = Result<Option<model::BitcoinBackfillProgress>, Error>> + Send;
+
+    /// Returns the record of the deputy coordinator taking over the tenure
+    /// of the given bitcoin chain tip, if a deputy took it over.
+    fn get_coordinator_takeover(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> impl Future<Output = Result<Option<model::CoordinatorTakeover>, Error>> + Send;
+
+    /// Returns the address announcements of P2P peers that have not
+    /// expired yet.
+    fn get_active_peer_address_records(
+        &self,
+    ) -> impl Future<Output = Result<Vec<model::P2PPeerAddressRecord>, Error>> + Send;
+
+    /// Returns the most recently placed override of the given request
+    /// that has neither been revoked nor expired, if there is one.
+    fn get_active_request_override(
+        &self,
+        target: &model::OverrideTarget,
+    ) -> impl Future<Output = Result<Option<model::RequestOverride>, Error>> + Send;
+
+    /// Returns every override that was ever placed on a request,
+    /// including revoked and expired ones, newest first.
+    fn get_request_overrides(
+        &self,
+    ) -> impl Future<Output = Result<Vec<model::RequestOverride>, Error>> + Send;
+
+    /// Returns the contract call that the coordinator submitted for the
+    /// given request and that has been neither confirmed nor rejected
+    /// yet, if there is one.
+    fn get_inflight_contract_call(
+        &self,
+        target: &model::ContractCallTarget,
+    ) -> impl Future<Output = Result<Option<model::InflightContractCall>, Error>> + Send;
+
+    /// Return how much of the canonical bitcoin blockchain ending at the
+    /// given chain tip is in the database, looking back at most `depth`
+    /// blocks, counting the chain tip. The history counts as complete if
+    /// all `depth` blocks are stored, or if the stored blocks reach back
+    /// to the given `start_height`.
+    fn get_chain_history(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        depth: u16,
+        start_height: model::BitcoinBlockHeight,
+    ) -> impl Future<Output = Result<model::ChainHistory, Error>> + Send;
+
+    /// Get the completed-deposit event for the deposit with the given
+    /// outpoint, if the deposit has been completed on Stacks. If the event
+    /// was observed in more than one stacks fork, the most recently
+    /// observed one is returned.
+    fn get_completed_deposit_event(
+        &self,
+        outpoint: &bitcoin::OutPoint,
+    ) -> impl Future<Output = Result<Option<model::CompletedDepositEvent>, Error>> + Send;
+
+    /// Return the proof that the sweep transaction with the given ID was
+    /// included in a block on the canonical bitcoin blockchain identified
+    /// by the given chain tip, if we have one.
+    fn get_sweep_tx_merkle_proof(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        txid: &model::BitcoinTxId,
+    ) -> impl Future<Output = Result<Option<model::BitcoinTxMerkleProof>, Error>> + Send;
+
+    /// Return the stored headers of the blocks on the canonical bitcoin
+    /// blockchain identified by the given chain tip, starting at the given
+    /// height and ordered by height, with at most `limit` headers. Blocks
+    /// whose header is not stored are skipped.
+    fn get_bitcoin_block_headers(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        start_height: model::BitcoinBlockHeight,
+        limit: u16,
+    ) -> impl Future<Output = Result<Vec<bitcoin::block::Header>, Error>> + Send;
+
+    /// Get the stacks chain tip along with the tenure that produced it.
+    ///
+    /// Like [`DbRead::get_stacks_chain_tip`], this is the highest stacks
+    /// block anchored to the canonical bitcoin blockchain identified by
+    /// the given chain tip. When more than one block has that height, as
+    /// can happen after a tenure change forks off the end of the previous
+    /// tenure, the block from the tenure with the highest bitcoin anchor
+    /// wins, and any remaining tie is broken by the highest block hash.
+    fn get_stacks_chain_tip_v2(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> impl Future<Output = Result<Option<model::StacksChainTip>, Error>> + Send;
+
+    /// Get the hashes of the stacks blocks, anchored to the bitcoin blocks
+    /// in the context window ending at the given chain tip, whose
+    /// transactions have not been scanned for multi-sig contract calls.
+    fn get_unscanned_stacks_blocks(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+    ) -> impl Future<Output = Result<Vec<model::StacksBlockHash>, Error>> + Send;
+
+    /// Get the multi-sig contract calls in the stacks blocks of the
+    /// canonical stacks chain ending at the given stacks chain tip, that
+    /// are anchored to the bitcoin blocks in the context window ending at
+    /// the given bitcoin chain tip.
+    fn get_multisig_stacks_transactions(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        stacks_chain_tip: &model::StacksBlockHash,
+        context_window: u16,
+    ) -> impl Future<Output = Result<Vec<model::MultisigStacksTransaction>, Error>> + Send;
 }
 
 /// Represents the ability to write data to the signer storage.
+///
+/// Every method that inserts a single row, and leaves the row alone if
+/// one with the same key already exists, returns a
+/// [`model::WriteOutcome`]. Writing such a row a second time is a no-op
+/// that returns [`model::WriteOutcome::AlreadyExists`]. Batch writes,
+/// upserts, updates and deletes do not report an outcome.
 pub trait DbWrite {
     /// Write a bitcoin block.
     fn write_bitcoin_block(
         &self,
         block: &model::BitcoinBlock,
-    ) -> impl Future<Output = Result<(), Error>> + Send;
+    ) -> impl Future<Output = Result<model::WriteOutcome, Error>> + Send;
 
     /// Write a stacks block.
     #[cfg(any(test, feature = "testing"))]
     fn write_stacks_block(
         &self,
         block: &model::StacksBlock,
-    ) -> impl Future<Output = Result<(), Error>> + Send;
+    ) -> impl Future<Output = Result<model::WriteOutcome, Error>> + Send;
 
     /// Write a deposit request.
     fn write_deposit_request(
         &self,
         deposit_request: &model::DepositRequest,
-    ) -> impl Future<Output = Result<(), Error>> + Send;
+    ) -> impl Future<Output = Result<model::WriteOutcome, Error>> + Send;
 
     /// Write many deposit requests.
     fn write_deposit_requests(
@@ -502,25 +858,25 @@ pub trait DbWrite {
     fn write_withdrawal_request(
         &self,
         request: &model::WithdrawalRequest,
-    ) -> impl Future<Output = Result<(), Error>> + Send;
+    ) -> impl Future<Output = Result<model::WriteOutcome, Error>> + Send;
 
     /// Write a signer decision for a deposit request.
     fn write_deposit_signer_decision(
         &self,
         decision: &model::DepositSigner,
-    ) -> impl Future<Output = Result<(), Error>> + Send;
+    ) -> impl Future<Output = Result<model::WriteOutcome, Error>> + Send;
 
     /// Write a signer decision for a withdrawal request.
     fn write_withdrawal_signer_decision(
         &self,
         decision: &model::WithdrawalSigner,
-    ) -> impl Future<Output = Result<(), Error>> + Send;
+    ) -> impl Future<Output = Result<model::WriteOutcome, Error>> + Send;
 
     /// Write a connection between a bitcoin block and a transaction
     fn write_bitcoin_transaction(
         &self,
         bitcoin_transaction: &model::BitcoinTxRef,
-    ) -> impl Future<Output = Result<(), Error>> + Send;
+    ) -> impl Future<Output = Result<model::WriteOutcome, Error>> + Send;
 
     /// Write the bitcoin transactions to the data store.
     fn write_bitcoin_transactions(
@@ -538,49 +894,49 @@ pub trait DbWrite {
     fn write_encrypted_dkg_shares(
         &self,
         shares: &model::EncryptedDkgShares,
-    ) -> impl Future<Output = Result<(), Error>> + Send;
+    ) -> impl Future<Output = Result<model::WriteOutcome, Error>> + Send;
 
     /// Write rotate-keys transaction
     fn write_rotate_keys_transaction(
         &self,
         key_rotation: &model::KeyRotationEvent,
-    ) -> impl Future<Output = Result<(), Error>> + Send;
+    ) -> impl Future<Output = Result<model::WriteOutcome, Error>> + Send;
 
     /// Write the withdrawal-reject event to the database.
     fn write_withdrawal_reject_event(
         &self,
         event: &WithdrawalRejectEvent,
-    ) -> impl Future<Output = Result<(), Error>> + Send;
+    ) -> impl Future<Output = Result<model::WriteOutcome, Error>> + Send;
 
     /// Write the withdrawal-accept event to the database.
     fn write_withdrawal_accept_event(
         &self,
         event: &WithdrawalAcceptEvent,
-    ) -> impl Future<Output = Result<(), Error>> + Send;
+    ) -> impl Future<Output = Result<model::WriteOutcome, Error>> + Send;
 
     /// Write the completed deposit event to the database.
     fn write_completed_deposit_event(
         &self,
         event: &CompletedDepositEvent,
-    ) -> impl Future<Output = Result<(), Error>> + Send;
+    ) -> impl Future<Output = Result<model::WriteOutcome, Error>> + Send;
 
     /// Write the bitcoin transaction output to the database.
     fn write_tx_output(
         &self,
         output: &model::TxOutput,
-    ) -> impl Future<Output = Result<(), Error>> + Send;
+    ) -> impl Future<Output = Result<model::WriteOutcome, Error>> + Send;
 
     /// Write the withdrawal bitcoin transaction output to the database.
     fn write_withdrawal_tx_output(
         &self,
         output: &model::WithdrawalTxOutput,
-    ) -> impl Future<Output = Result<(), Error>> + Send;
+    ) -> impl Future<Output = Result<model::WriteOutcome, Error>> + Send;
 
     /// Write the bitcoin transaction input to the database.
     fn write_tx_prevout(
         &self,
         prevout: &model::TxPrevout,
-    ) -> impl Future<Output = Result<(), Error>> + Send;
+    ) -> impl Future<Output = Result<model::WriteOutcome, Error>> + Send;
 
     /// Write the bitcoin transactions sighashes to the database.
     fn write_bitcoin_txs_sighashes(
@@ -640,4 +996,262 @@ pub trait DbWrite {
         &self,
         chain_tip: &model::BitcoinBlockHash,
     ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Write a snapshot of an sBTC total-supply reconciliation.
+    fn write_sbtc_supply_snapshot(
+        &self,
+        snapshot: &model::SbtcSupplySnapshot,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Write a signer UTXO invariant violation.
+    fn write_signer_utxo_violation(
+        &self,
+        violation: &model::SignerUtxoViolation,
+    ) -> impl Future<Output = Result<model::WriteOutcome, Error>> + Send;
+
+    /// Cache the full bodies of the given bitcoin transactions. Bodies of
+    /// transactions that are already cached are left untouched.
+    fn write_bitcoin_tx_bodies(
+        &self,
+        bodies: &[model::BitcoinTxBody],
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Delete the cached bodies of transactions included in bitcoin
+    /// blocks below the given height, returning the number of bodies
+    /// deleted.
+    fn prune_bitcoin_tx_bodies(
+        &self,
+        min_block_height: model::BitcoinBlockHeight,
+    ) -> impl Future<Output = Result<u64, Error>> + Send;
+
+    /// Record that the deposit request with the given outpoint was
+    /// imported from Emily. The deposit request must already be stored.
+    fn write_emily_imported_deposit(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+    ) -> impl Future<Output = Result<model::WriteOutcome, Error>> + Send;
+
+    /// Record that the withdrawal request with the given ID was imported
+    /// from Emily. The withdrawal request must already be stored.
+    fn write_emily_imported_withdrawal(
+        &self,
+        id: &model::QualifiedRequestId,
+    ) -> impl Future<Output = Result<model::WriteOutcome, Error>> + Send;
+
+    /// Upserts the versions advertised by a P2P peer, updating the
+    /// timestamp if the peer already has an entry.
+    fn update_peer_version(
+        &self,
+        peer_id: &PeerId,
+        agent_version: &str,
+        protocol_version: u32,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Upserts a ban of a P2P peer, replacing any earlier ban of the same
+    /// peer.
+    fn write_peer_ban(
+        &self,
+        ban: &model::P2PPeerBan,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Deletes any ban of the given P2P peer.
+    fn delete_peer_ban(&self, peer_id: &PeerId) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Upserts a DKG verification round, replacing the status and the
+    /// last processed message type of any earlier entry for the same
+    /// aggregate key and bitcoin chain tip.
+    fn write_dkg_verification_round(
+        &self,
+        round: &model::DkgVerificationRound,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Upserts an sbtc-registry event that could not be parsed. If the
+    /// event was dead-lettered before then its error is replaced and its
+    /// number of attempts is incremented.
+    fn write_stacks_event_dead_letter(
+        &self,
+        letter: &model::StacksEventDeadLetter,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Marks a dead-lettered sbtc-registry event as successfully replayed.
+    fn resolve_stacks_event_dead_letter(
+        &self,
+        block_hash: &model::StacksBlockHash,
+        txid: &model::StacksTxId,
+        event_index: u32,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Persists a sweep transaction that has been fully signed by the
+    /// signers. Writing a transaction that already exists is a no-op.
+    fn write_signed_sweep_transaction(
+        &self,
+        sweep: &model::SignedSweepTransaction,
+    ) -> impl Future<Output = Result<model::WriteOutcome, Error>> + Send;
+
+    /// Deletes the persisted signed sweep transactions with the given IDs.
+    fn delete_signed_sweep_transactions(
+        &self,
+        txids: &[model::BitcoinTxId],
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Write a discrepancy between the assessed and actual fees of a
+    /// broadcast sweep transaction.
+    fn write_sweep_fee_discrepancy(
+        &self,
+        discrepancy: &model::SweepFeeDiscrepancy,
+    ) -> impl Future<Output = Result<model::WriteOutcome, Error>> + Send;
+
+    /// Write a signer's attestation to the outcome of a DKG round. Only
+    /// the first attestation from a signer for an aggregate key is kept.
+    fn write_key_rotation_attestation(
+        &self,
+        attestation: &model::KeyRotationAttestation,
+    ) -> impl Future<Output = Result<model::WriteOutcome, Error>> + Send;
+
+    /// Persists the sweep package that this signer constructed as the
+    /// coordinator, replacing any package persisted for the same bitcoin
+    /// chain tip. Only the package of the current tenure can be resumed,
+    /// so packages for other bitcoin chain tips are deleted.
+    fn write_sweep_package(
+        &self,
+        package: &model::SweepPackage,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Records that the transaction with the given ID, from the sweep
+    /// package for the given bitcoin chain tip, has been broadcast.
+    /// Recording the same transaction twice is a no-op.
+    fn write_sweep_package_broadcast(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        txid: &model::BitcoinTxId,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Records that the deposit request reached the given stage of its
+    /// lifecycle now. Only the first time that a stage is reached is
+    /// recorded, so recording a stage again is a no-op.
+    fn write_deposit_request_timestamp(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+        stage: model::RequestLifecycleStage,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Records that the withdrawal request with the given request ID
+    /// reached the given stage of its lifecycle now. Only the first time
+    /// that a stage is reached is recorded, so recording a stage again is
+    /// a no-op.
+    fn write_withdrawal_request_timestamp(
+        &self,
+        request_id: u64,
+        stage: model::RequestLifecycleStage,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Records the progress of a bitcoin block backfill, replacing the
+    /// progress of any earlier backfill.
+    fn write_bitcoin_backfill_progress(
+        &self,
+        progress: &model::BitcoinBackfillProgress,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Records that a deputy coordinator took over the tenure of the
+    /// takeover's bitcoin chain tip. Nothing changes if a takeover of the
+    /// tenure was already recorded.
+    fn write_coordinator_takeover(
+        &self,
+        takeover: &model::CoordinatorTakeover,
+    ) -> impl Future<Output = Result<model::WriteOutcome, Error>> + Send;
+
+    /// Upserts the address announcement of a P2P peer, unless the stored
+    /// announcement of the same peer expires at the same time or later.
+    /// Returns whether the announcement was written.
+    fn write_peer_address_record(
+        &self,
+        record: &model::P2PPeerAddressRecord,
+    ) -> impl Future<Output = Result<bool, Error>> + Send;
+
+    /// Writes a new override on a request and returns it as stored.
+    fn write_request_override(
+        &self,
+        request_override: &model::NewRequestOverride,
+    ) -> impl Future<Output = Result<model::RequestOverride, Error>> + Send;
+
+    /// Revokes the override with the given ID. Returns whether an
+    /// override that had not been revoked already was found.
+    fn revoke_request_override(&self, id: i64) -> impl Future<Output = Result<bool, Error>> + Send;
+
+    /// Record a contract call that the coordinator submitted for a
+    /// request, replacing any contract call recorded for the request
+    /// before.
+    fn write_inflight_contract_call(
+        &self,
+        contract_call: &model::InflightContractCall,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Forget the contract call recorded for the given request, because
+    /// it was confirmed or rejected. Returns whether there was a contract
+    /// call to forget.
+    fn delete_inflight_contract_call(
+        &self,
+        target: &model::ContractCallTarget,
+    ) -> impl Future<Output = Result<bool, Error>> + Send;
+
+    /// Replace the registry of withdrawal output labels with the given
+    /// labels, and update the labels recorded for the withdrawal outputs
+    /// that have already been written to match.
+    fn write_withdrawal_output_labels(
+        &self,
+        labels: &[model::WithdrawalOutputLabel],
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Store a signer state machine that was evicted from the in-memory
+    /// cache, replacing any state stored for the same state machine
+    /// before.
+    fn write_spilled_state_machine(
+        &self,
+        state_machine: &model::SpilledStateMachine,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Remove and return the stored state of the signer state machine
+    /// with the given identifier, if there is one.
+    fn take_spilled_state_machine(
+        &self,
+        state_machine_id: &str,
+    ) -> impl Future<Output = Result<Option<model::SpilledStateMachine>, Error>> + Send;
+
+    /// Delete the signer state machines that were evicted when the
+    /// bitcoin chain tip was below the given height, returning the number
+    /// of state machines deleted.
+    fn prune_spilled_state_machines(
+        &self,
+        min_block_height: model::BitcoinBlockHeight,
+    ) -> impl Future<Output = Result<u64, Error>> + Send;
+
+    /// Store the header of a bitcoin block. The block itself must already
+    /// be stored.
+    fn write_bitcoin_block_header(
+        &self,
+        header: &bitcoin::block::Header,
+    ) -> impl Future<Output = Result<model::WriteOutcome, Error>> + Send;
+
+    /// Store the given proofs that bitcoin transactions were included in
+    /// bitcoin blocks. Proofs that are already stored are left untouched.
+    fn write_bitcoin_tx_merkle_proofs(
+        &self,
+        proofs: &[model::BitcoinTxMerkleProof],
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Store a multi-sig contract call found in a stacks block. Calls
+    /// that are already stored are left untouched.
+    fn write_multisig_stacks_transaction(
+        &self,
+        tx: &model::MultisigStacksTransaction,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Record that the transactions in the given stacks block have been
+    /// scanned for multi-sig contract calls.
+    fn write_scanned_stacks_block(
+        &self,
+        block_hash: &model::StacksBlockHash,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
 }
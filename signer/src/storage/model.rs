@@ -21,12 +21,20 @@ use stacks_common::types::chainstate::StacksBlockId;
 
 use crate::bitcoin::rpc::BitcoinBlockHeader;
 use crate::bitcoin::rpc::BitcoinBlockInfo;
+use crate::bitcoin::rpc::BitcoinTxInfo;
+use crate::bitcoin::rpc::BitcoinTxVin;
+use crate::bitcoin::rpc::BitcoinTxVinPrevout;
+use crate::bitcoin::rpc::OutputScriptPubKey;
+use crate::bitcoin::utxo::Fees;
 use crate::bitcoin::validation::InputValidationResult;
+use crate::bitcoin::validation::ValidationChecks;
 use crate::bitcoin::validation::WithdrawalValidationResult;
 use crate::block_observer::Deposit;
 use crate::error::Error;
 use crate::keys::PublicKey;
 use crate::keys::PublicKeyXOnly;
+use crate::message;
+use crate::secret::Secret;
 use crate::stacks::api::SignerSetInfo;
 
 /// A P2P peer which the signer has successfully connected to.
@@ -42,6 +50,826 @@ pub struct P2PPeer {
     pub last_dialed_at: Timestamp,
 }
 
+/// The versions that a P2P peer advertised when we last connected to it.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct P2PPeerVersion {
+    /// The peer ID of the peer.
+    pub peer_id: DbPeerId,
+    /// The agent version string advertised by the peer.
+    pub agent_version: String,
+    /// The signer protocol version advertised by the peer.
+    #[sqlx(try_from = "i32")]
+    pub protocol_version: u32,
+    /// The timestamp of the last time the peer advertised its versions.
+    pub updated_at: Timestamp,
+}
+
+/// A timed ban of a P2P peer that misbehaved on the signer network.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct P2PPeerBan {
+    /// The peer ID of the banned peer.
+    pub peer_id: DbPeerId,
+    /// The kind of misbehavior that pushed the peer over the ban
+    /// threshold.
+    pub reason: String,
+    /// The misbehavior score of the peer when it was banned.
+    #[sqlx(try_from = "i32")]
+    pub score: u32,
+    /// The timestamp of when the peer was banned.
+    pub banned_at: Timestamp,
+    /// The timestamp of when the ban is lifted.
+    pub expires_at: Timestamp,
+}
+
+impl P2PPeerBan {
+    /// Returns whether the ban is still in effect at the given time.
+    pub fn is_active_at(&self, now: time::OffsetDateTime) -> bool {
+        *self.expires_at > now
+    }
+}
+
+/// The latest signed address announcement of a signer on the P2P
+/// network.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct P2PPeerAddressRecord {
+    /// The public key of the signer that announced the addresses.
+    pub public_key: PublicKey,
+    /// The peer ID of the signer, derived from its public key.
+    pub peer_id: DbPeerId,
+    /// The addresses that the signer can be reached at.
+    pub addresses: Vec<DbMultiaddr>,
+    /// The timestamp of when the announcement expires.
+    pub expires_at: Timestamp,
+}
+
+impl P2PPeerAddressRecord {
+    /// Returns whether the announcement is still valid at the given time.
+    pub fn is_active_at(&self, now: time::OffsetDateTime) -> bool {
+        *self.expires_at > now
+    }
+}
+
+/// Whether an operator override forces this signer to accept or reject
+/// a request.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, sqlx::Type, strum::Display)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "testing", derive(fake::Dummy))]
+pub enum OverrideDirective {
+    /// Accept the request regardless of the decision policies.
+    ForceAccept,
+    /// Reject the request regardless of the decision policies.
+    ForceReject,
+}
+
+/// The request that an operator override applies to.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "testing", derive(fake::Dummy))]
+pub enum OverrideTarget {
+    /// A deposit request, identified by its outpoint.
+    Deposit {
+        /// The transaction ID of the deposit transaction.
+        txid: BitcoinTxId,
+        /// The index of the deposit output in the deposit transaction.
+        #[cfg_attr(feature = "testing", dummy(faker = "0..i32::MAX as u32"))]
+        output_index: u32,
+    },
+    /// A withdrawal request, identified by its request ID.
+    Withdrawal {
+        /// The ID of the withdrawal request.
+        #[cfg_attr(feature = "testing", dummy(faker = "0..i64::MAX as u64"))]
+        request_id: u64,
+    },
+}
+
+/// An override that an operator wants to place on a request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "testing", derive(fake::Dummy))]
+pub struct NewRequestOverride {
+    /// The request that the override applies to.
+    pub target: OverrideTarget,
+    /// Whether the override forces the request to be accepted or
+    /// rejected.
+    pub directive: OverrideDirective,
+    /// Why the operator placed the override.
+    pub reason: String,
+    /// When the override stops applying, if ever.
+    #[cfg_attr(feature = "testing", dummy(default))]
+    pub expires_at: Option<Timestamp>,
+}
+
+/// An override, placed by an operator, that forces this signer to accept
+/// or reject a specific request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestOverride {
+    /// The ID of the override. This is set by the database when the
+    /// override is written.
+    pub id: i64,
+    /// The request that the override applies to.
+    pub target: OverrideTarget,
+    /// Whether the override forces the request to be accepted or
+    /// rejected.
+    pub directive: OverrideDirective,
+    /// Why the operator placed the override.
+    pub reason: String,
+    /// When the override stops applying, if ever.
+    pub expires_at: Option<Timestamp>,
+    /// When the override was placed. This is set by the database when the
+    /// override is written.
+    pub created_at: Timestamp,
+    /// When an operator revoked the override, if they did.
+    pub revoked_at: Option<Timestamp>,
+}
+
+impl RequestOverride {
+    /// Returns whether the override applies at the given time, that is,
+    /// whether it has neither been revoked nor expired.
+    pub fn is_active_at(&self, now: time::OffsetDateTime) -> bool {
+        self.revoked_at.is_none() && self.expires_at.is_none_or(|expires_at| *expires_at > now)
+    }
+
+    /// Returns whether the override forces the request to be accepted.
+    pub fn forces_accept(&self) -> bool {
+        self.directive == OverrideDirective::ForceAccept
+    }
+}
+
+/// The request that a contract call submitted by the coordinator
+/// fulfills.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "testing", derive(fake::Dummy))]
+pub enum ContractCallTarget {
+    /// A complete-deposit contract call for a deposit request, identified
+    /// by its outpoint.
+    Deposit {
+        /// The transaction ID of the deposit transaction.
+        txid: BitcoinTxId,
+        /// The index of the deposit output in the deposit transaction.
+        #[cfg_attr(feature = "testing", dummy(faker = "0..i32::MAX as u32"))]
+        output_index: u32,
+    },
+    /// An accept-withdrawal or reject-withdrawal contract call for a
+    /// withdrawal request, identified by its qualified request ID. The
+    /// request ID alone is not enough, since the same request ID can be
+    /// reused by a different request on a stacks fork.
+    Withdrawal {
+        /// The qualified ID of the withdrawal request.
+        id: QualifiedRequestId,
+    },
+}
+
+/// A contract call that the coordinator submitted to the stacks node but
+/// that has been neither confirmed nor rejected yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "testing", derive(fake::Dummy))]
+pub struct InflightContractCall {
+    /// The request that the contract call fulfills.
+    pub target: ContractCallTarget,
+    /// The ID of the stacks transaction with the contract call.
+    pub txid: StacksTxId,
+    /// The height of the bitcoin chain tip when the contract call was
+    /// submitted.
+    pub bitcoin_block_height: BitcoinBlockHeight,
+    /// The hash of the bitcoin chain tip when the contract call was
+    /// submitted.
+    pub bitcoin_block_hash: BitcoinBlockHash,
+}
+
+impl InflightContractCall {
+    /// Returns whether the contract call was submitted fewer than
+    /// `expiry` bitcoin blocks before the given bitcoin block height, so
+    /// that it may still be confirmed. A contract call submitted at a
+    /// height above the given one was submitted on a fork that has since
+    /// been abandoned, so it is not pending either.
+    pub fn is_pending_at(&self, bitcoin_block_height: BitcoinBlockHeight, expiry: u64) -> bool {
+        self.bitcoin_block_height <= bitcoin_block_height
+            && bitcoin_block_height < self.bitcoin_block_height.saturating_add(expiry)
+    }
+
+    /// The bitcoin chain tip when the contract call was submitted.
+    pub fn bitcoin_block_ref(&self) -> BitcoinBlockRef {
+        BitcoinBlockRef {
+            block_height: self.bitcoin_block_height,
+            block_hash: self.bitcoin_block_hash,
+        }
+    }
+}
+
+/// The outcome of an idempotent write of a single row to the database.
+///
+/// Writing a row whose key is already in the database leaves the existing
+/// row untouched, which is what we want when the same event is delivered
+/// twice. This type lets the caller tell the two cases apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteOutcome {
+    /// The row was written.
+    Inserted,
+    /// A row with the same key was already in the database, so nothing
+    /// was written.
+    AlreadyExists,
+}
+
+impl WriteOutcome {
+    /// Returns whether the row was written.
+    pub fn is_inserted(&self) -> bool {
+        matches!(self, Self::Inserted)
+    }
+}
+
+impl From<bool> for WriteOutcome {
+    fn from(inserted: bool) -> Self {
+        if inserted {
+            Self::Inserted
+        } else {
+            Self::AlreadyExists
+        }
+    }
+}
+
+/// An sbtc-registry event that was sent by the stacks node but that we
+/// could not parse.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct StacksEventDeadLetter {
+    /// The index block hash of the stacks block that contains the event.
+    pub block_hash: StacksBlockHash,
+    /// The ID of the stacks transaction that emitted the event.
+    pub txid: StacksTxId,
+    /// The position of the event within the events of the new block
+    /// webhook.
+    #[sqlx(try_from = "i32")]
+    pub event_index: u32,
+    /// The hex encoded consensus serialization of the clarity value of the
+    /// event.
+    pub raw_value: String,
+    /// The error returned the last time that we tried to parse the event.
+    pub error: String,
+    /// The number of times that we tried to parse the event. This is set
+    /// by the database when the event is written.
+    #[sqlx(try_from = "i32")]
+    pub attempts: u32,
+    /// The timestamp of when the event was first dead-lettered. This is
+    /// set by the database when the event is written.
+    pub created_at: Timestamp,
+    /// The timestamp of when the event was successfully replayed, if it
+    /// was.
+    pub resolved_at: Option<Timestamp>,
+}
+
+/// The total amount of sBTC minted by completed deposits and burned by
+/// accepted withdrawals, as recorded in the signer's database.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, sqlx::FromRow)]
+pub struct SbtcSupplyTotals {
+    /// The total amount of sBTC, in sats, minted by `complete-deposit`
+    /// contract calls.
+    #[sqlx(try_from = "i64")]
+    pub minted: u64,
+    /// The total amount of sBTC, in sats, burned by
+    /// `accept-withdrawal-request` contract calls.
+    #[sqlx(try_from = "i64")]
+    pub burned: u64,
+}
+
+impl SbtcSupplyTotals {
+    /// The amount of sBTC, in sats, that we expect the sBTC token contract
+    /// to report as its total supply.
+    ///
+    /// This can be negative if our records are inconsistent, which is
+    /// itself something worth alerting on.
+    pub fn expected_supply(&self) -> i128 {
+        i128::from(self.minted) - i128::from(self.burned)
+    }
+}
+
+/// How much of the rolling withdrawal cap has been used on the bitcoin
+/// blockchain identified by a chain tip.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapsUtilization {
+    /// The height of the bitcoin chain tip.
+    pub bitcoin_block_height: BitcoinBlockHeight,
+    /// The number of bitcoin blocks in the rolling withdrawal window.
+    pub rolling_withdrawal_blocks: u16,
+    /// The maximum total amount, in sats, that can be withdrawn within
+    /// the rolling withdrawal window.
+    pub rolling_withdrawal_cap: u64,
+    /// The total amount, in sats, swept out to withdrawal recipients in
+    /// the rolling withdrawal window.
+    pub withdrawn_total: u64,
+    /// The amount, in sats, that can still be withdrawn in the rolling
+    /// withdrawal window.
+    pub available: u64,
+    /// The number of bitcoin blocks until there is headroom under the
+    /// rolling withdrawal cap. This is zero if there is headroom now, and
+    /// `None` if there never will be, which happens when the cap is zero.
+    pub blocks_until_headroom: Option<u64>,
+}
+
+impl CapsUtilization {
+    /// Compute the utilization of the rolling withdrawal cap from the
+    /// amounts withdrawn in each bitcoin block of the rolling withdrawal
+    /// window ending at the chain tip.
+    ///
+    /// A window of zero blocks is treated as a window of one block, the
+    /// same way that [`DbRead::compute_withdrawn_total`] treats it.
+    ///
+    /// [`DbRead::compute_withdrawn_total`]: crate::storage::DbRead::compute_withdrawn_total
+    pub fn new(
+        chain_tip_height: BitcoinBlockHeight,
+        rolling_withdrawal_blocks: u16,
+        rolling_withdrawal_cap: u64,
+        withdrawals: &[(BitcoinBlockHeight, u64)],
+    ) -> Self {
+        let withdrawn_total = withdrawals
+            .iter()
+            .fold(0u64, |total, (_, amount)| total.saturating_add(*amount));
+        let available = rolling_withdrawal_cap.saturating_sub(withdrawn_total);
+
+        // Withdrawals leave the window, oldest first, once the chain tip is
+        // a full window above the block that confirmed them.
+        let window = u64::from(rolling_withdrawal_blocks.max(1));
+        let mut withdrawals = withdrawals.to_vec();
+        withdrawals.sort_unstable();
+
+        let mut remaining = withdrawn_total;
+        let blocks_until_headroom = if available > 0 {
+            Some(0)
+        } else {
+            withdrawals.iter().find_map(|(height, amount)| {
+                remaining = remaining.saturating_sub(*amount);
+                (remaining < rolling_withdrawal_cap)
+                    .then(|| (*(*height + window).saturating_sub(chain_tip_height)).max(1))
+            })
+        };
+
+        Self {
+            bitcoin_block_height: chain_tip_height,
+            rolling_withdrawal_blocks,
+            rolling_withdrawal_cap,
+            withdrawn_total,
+            available,
+            blocks_until_headroom,
+        }
+    }
+}
+
+/// A summary of the sBTC activity in a single bitcoin block.
+#[derive(Debug, Clone, Default, PartialEq, Eq, sqlx::FromRow)]
+pub struct BlockActivitySummary {
+    /// The IDs of the sweep transactions in the block, in ascending
+    /// order.
+    pub sweep_txids: Vec<BitcoinTxId>,
+    /// The number of deposit requests that were swept in the block.
+    #[sqlx(try_from = "i64")]
+    pub deposits_swept: u64,
+    /// The total amount, in sats, of the deposits that were swept in the
+    /// block.
+    #[sqlx(try_from = "i64")]
+    pub deposit_amount: u64,
+    /// The number of withdrawal requests that were fulfilled in the block.
+    #[sqlx(try_from = "i64")]
+    pub withdrawals_fulfilled: u64,
+    /// The total amount, in sats, sent to withdrawal recipients in the
+    /// block.
+    #[sqlx(try_from = "i64")]
+    pub withdrawal_amount: u64,
+    /// The total amount of fees, in sats, paid by the sweep transactions
+    /// in the block.
+    #[sqlx(try_from = "i64")]
+    pub fees_paid: u64,
+}
+
+/// A snapshot of a reconciliation between the sBTC supply that we expect
+/// given our records and the total supply reported by the sBTC token
+/// contract.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct SbtcSupplySnapshot {
+    /// The bitcoin chain tip at the time of the reconciliation.
+    pub bitcoin_block_hash: BitcoinBlockHash,
+    /// The height of the bitcoin chain tip at the time of the
+    /// reconciliation.
+    pub bitcoin_block_height: BitcoinBlockHeight,
+    /// The sBTC totals computed from the signer's database.
+    #[sqlx(flatten)]
+    pub totals: SbtcSupplyTotals,
+    /// The total supply of sBTC, in sats, reported by the sBTC token
+    /// contract.
+    #[sqlx(try_from = "i64")]
+    pub reported_supply: u64,
+    /// Whether the difference between the expected and the reported
+    /// supply was within the configured tolerance.
+    pub within_tolerance: bool,
+}
+
+/// The full body of an sBTC related bitcoin transaction, along with the
+/// outputs that it spends, cached so that it does not need to be fetched
+/// from bitcoin-core.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitcoinTxBody {
+    /// The ID of the transaction.
+    pub txid: BitcoinTxId,
+    /// The hash of the bitcoin block that included the transaction.
+    pub block_hash: BitcoinBlockHash,
+    /// The height of the bitcoin block that included the transaction.
+    pub block_height: BitcoinBlockHeight,
+    /// The transaction.
+    pub tx: bitcoin::Transaction,
+    /// The outputs spent by the inputs of the transaction, in the order
+    /// of the inputs.
+    pub prevouts: Vec<bitcoin::TxOut>,
+}
+
+impl BitcoinTxBody {
+    /// Create the body of the given transaction, confirmed in the given
+    /// block. This is `None` if bitcoin-core did not tell us about the
+    /// outputs that the transaction spends, see
+    /// [`BitcoinTxInfo::validate`].
+    pub fn from_tx_info(tx_info: &BitcoinTxInfo, block: &BitcoinBlockRef) -> Option<Self> {
+        tx_info.validate().ok()?;
+        let prevouts = tx_info
+            .vin
+            .iter()
+            .map(|vin| {
+                let prevout = vin.prevout.as_ref()?;
+                Some(bitcoin::TxOut {
+                    value: prevout.value,
+                    script_pubkey: prevout.script_pubkey.script.clone(),
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Self {
+            txid: tx_info.compute_txid().into(),
+            block_hash: block.block_hash,
+            block_height: block.block_height,
+            tx: tx_info.tx.clone(),
+            prevouts,
+        })
+    }
+
+    /// Return the transaction in the form that bitcoin-core gives it to
+    /// us. The fee is `None` if the transaction spends less than its
+    /// outputs are worth, which never happens for a confirmed one.
+    pub fn into_tx_info(self) -> BitcoinTxInfo {
+        let spent: u64 = self.prevouts.iter().map(|txout| txout.value.to_sat()).sum();
+        let paid: u64 = self
+            .tx
+            .output
+            .iter()
+            .map(|txout| txout.value.to_sat())
+            .sum();
+        let vin = self
+            .tx
+            .input
+            .iter()
+            .zip(self.prevouts)
+            .map(|(tx_in, prevout)| BitcoinTxVin {
+                txid: Some(tx_in.previous_output.txid),
+                vout: Some(tx_in.previous_output.vout),
+                prevout: Some(BitcoinTxVinPrevout {
+                    value: prevout.value,
+                    script_pubkey: OutputScriptPubKey { script: prevout.script_pubkey },
+                }),
+            })
+            .collect();
+
+        BitcoinTxInfo {
+            fee: spent.checked_sub(paid).map(bitcoin::Amount::from_sat),
+            tx: self.tx,
+            vin,
+        }
+    }
+}
+
+/// A proof that an sBTC related bitcoin transaction was included in a
+/// bitcoin block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitcoinTxMerkleProof {
+    /// The ID of the transaction.
+    pub txid: BitcoinTxId,
+    /// The hash of the bitcoin block that included the transaction.
+    pub block_hash: BitcoinBlockHash,
+    /// The height of the bitcoin block that included the transaction.
+    pub block_height: BitcoinBlockHeight,
+    /// The header of the bitcoin block, along with a partial merkle tree
+    /// that matches only this transaction.
+    pub merkle_block: bitcoin::MerkleBlock,
+}
+
+/// A range of bitcoin blocks during which an aggregate key locked the
+/// signers' UTXO.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct AggregateKeyPeriod {
+    /// The aggregate key of the signers.
+    pub aggregate_key: PublicKey,
+    /// The height of the first bitcoin block with a signers' UTXO locked
+    /// by the aggregate key.
+    pub start_height: BitcoinBlockHeight,
+    /// The height of the first bitcoin block with a signers' UTXO locked
+    /// by the next aggregate key. This is `None` if the aggregate key
+    /// still locks the signers' UTXO.
+    pub end_height: Option<BitcoinBlockHeight>,
+    /// The ID of the earliest rotate-keys transaction on the canonical
+    /// stacks blockchain that registered the aggregate key, if any.
+    pub rotate_keys_txid: Option<StacksTxId>,
+}
+
+/// A sweep transaction that was fully signed by the signers. It is
+/// persisted before it is broadcast so that it can be broadcast again if
+/// the coordinator stops before the transaction reaches the bitcoin
+/// network.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedSweepTransaction {
+    /// The ID of the transaction.
+    pub txid: BitcoinTxId,
+    /// The signed transaction.
+    pub tx: bitcoin::Transaction,
+    /// The bitcoin chain tip when the transaction was signed.
+    pub bitcoin_chain_tip: BitcoinBlockHash,
+    /// When the transaction was persisted.
+    pub created_at: Timestamp,
+}
+
+/// The progress of the sweep transaction package that the coordinator
+/// constructed in a tenure. It is persisted so that a coordinator that
+/// stops partway through the package can continue with the remaining
+/// transactions after it restarts. The other signers persist the package
+/// when they accept its pre-sign request, so that the deputy coordinator
+/// can continue with it if the coordinator goes silent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepPackage {
+    /// The bitcoin chain tip when the package was constructed.
+    pub bitcoin_chain_tip: BitcoinBlockHash,
+    /// The pre-sign request that was sent to the signers for the package.
+    pub presign_request: message::BitcoinPreSignRequest,
+    /// The fees of the transactions in the mempool that the first
+    /// transaction in the package replaces, if any.
+    pub last_fees: Option<Fees>,
+    /// The IDs of the transactions in the package that have been
+    /// broadcast, in package order.
+    pub broadcast_txids: Vec<BitcoinTxId>,
+}
+
+impl SweepPackage {
+    /// Whether every transaction in the package has been broadcast.
+    pub fn is_complete(&self) -> bool {
+        self.broadcast_txids.len() >= self.presign_request.request_package.len()
+    }
+}
+
+/// A tenure that a deputy coordinator took over from a coordinator that
+/// went silent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::FromRow)]
+#[cfg_attr(feature = "testing", derive(fake::Dummy))]
+pub struct CoordinatorTakeover {
+    /// The bitcoin chain tip of the tenure that was taken over.
+    pub bitcoin_chain_tip: BitcoinBlockHash,
+    /// The public key of the coordinator that went silent.
+    pub coordinator_public_key: PublicKey,
+    /// The public key of the deputy coordinator that took over.
+    pub deputy_public_key: PublicKey,
+}
+
+/// A stage in the lifecycle of a deposit or withdrawal request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RequestLifecycleStage {
+    /// The signer stored the request for the first time.
+    Seen {
+        /// The bitcoin block in which the signer first saw the request.
+        /// For deposits this is the block that confirmed the deposit
+        /// transaction, and for withdrawals this is the signer's bitcoin
+        /// chain tip at the time, if it knows about one.
+        first_seen_block: Option<BitcoinBlockHash>,
+    },
+    /// The signer made its own decision on the request.
+    Decided,
+    /// The signer observed a sweep transaction that fulfilled the request
+    /// on bitcoin.
+    Swept,
+    /// The signer observed the event completing the request on stacks.
+    Completed,
+}
+
+/// When a deposit or withdrawal request reached each stage of its
+/// lifecycle, as seen by this signer. Each timestamp is set the first time
+/// that the signer observes the stage.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct RequestTimestamps {
+    /// The bitcoin block in which the signer first saw the request, see
+    /// [`RequestLifecycleStage::Seen`].
+    pub first_seen_block: Option<BitcoinBlockHash>,
+    /// When the signer first recorded a stage of the request.
+    pub created_at: Timestamp,
+    /// When the signer made its own decision on the request.
+    pub decided_at: Option<Timestamp>,
+    /// When the signer observed a sweep transaction that fulfilled the
+    /// request.
+    pub swept_at: Option<Timestamp>,
+    /// When the signer observed the event completing the request on
+    /// stacks.
+    pub completed_at: Option<Timestamp>,
+}
+
+impl RequestTimestamps {
+    /// Create the timestamps of a request whose first recorded stage was
+    /// reached at the given time.
+    pub fn new(stage: RequestLifecycleStage, now: Timestamp) -> Self {
+        let mut timestamps = Self {
+            first_seen_block: None,
+            created_at: now,
+            decided_at: None,
+            swept_at: None,
+            completed_at: None,
+        };
+        timestamps.record(stage, now);
+        timestamps
+    }
+
+    /// Record that the request reached the given stage at the given time,
+    /// unless it was already recorded.
+    pub fn record(&mut self, stage: RequestLifecycleStage, now: Timestamp) {
+        let timestamp = match stage {
+            RequestLifecycleStage::Seen { first_seen_block } => {
+                self.first_seen_block = self.first_seen_block.or(first_seen_block);
+                self.created_at = self.created_at.min(now);
+                return;
+            }
+            RequestLifecycleStage::Decided => &mut self.decided_at,
+            RequestLifecycleStage::Swept => &mut self.swept_at,
+            RequestLifecycleStage::Completed => &mut self.completed_at,
+        };
+        timestamp.get_or_insert(now);
+    }
+
+    /// How long it took from when the signer first saw the request until
+    /// the request was completed, if it has been completed.
+    pub fn time_to_completion(&self) -> Option<time::Duration> {
+        self.completed_at
+            .map(|completed_at| *completed_at - *self.created_at)
+    }
+}
+
+/// The progress of the block observer in writing the bitcoin blocks
+/// between the blocks in the database and a new chain tip.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct BitcoinBackfillProgress {
+    /// The chain tip that the block observer is catching up to.
+    pub target_block_hash: BitcoinBlockHash,
+    /// The height of the chain tip that the block observer is catching up
+    /// to.
+    pub target_block_height: BitcoinBlockHeight,
+    /// The height of the first block written in the backfill.
+    pub start_height: BitcoinBlockHeight,
+    /// The height of the last block written to the database.
+    pub processed_height: BitcoinBlockHeight,
+    /// When the backfill started.
+    pub started_at: Timestamp,
+    /// When the progress was last updated.
+    pub updated_at: Timestamp,
+}
+
+impl BitcoinBackfillProgress {
+    /// The number of blocks that have been written in the backfill.
+    pub fn blocks_processed(&self) -> u64 {
+        *(self.processed_height + 1).saturating_sub(self.start_height)
+    }
+
+    /// The number of blocks that remain to be written in the backfill.
+    pub fn blocks_remaining(&self) -> u64 {
+        *self
+            .target_block_height
+            .saturating_sub(self.processed_height)
+    }
+
+    /// Whether every block up to the target chain tip has been written.
+    pub fn is_complete(&self) -> bool {
+        self.processed_height >= self.target_block_height
+    }
+}
+
+/// A sweep transaction where the value of the new signers' UTXO did not
+/// equal the value of the signers' input, plus the deposits swept, minus
+/// the withdrawals paid and the fee, or where the value of the signers'
+/// input did not equal the value of the signers' UTXO created by the
+/// previous sweep transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignerUtxoViolation {
+    /// The ID of the sweep transaction.
+    pub txid: BitcoinTxId,
+    /// The block hash of the bitcoin block that included the transaction.
+    pub block_hash: BitcoinBlockHash,
+    /// The amount locked in the signers' UTXO that was spent by the
+    /// transaction.
+    pub signer_input: u64,
+    /// The total amount of all deposits swept in by the transaction.
+    pub deposits: u64,
+    /// The total amount paid out to withdrawal recipients.
+    pub withdrawals: u64,
+    /// The fee paid to bitcoin miners.
+    pub fee: u64,
+    /// The amount locked in the new signers' UTXO.
+    pub signer_output: u64,
+    /// The amount locked in the signers' UTXO spent by the transaction,
+    /// as recorded when it was created by the previous sweep transaction,
+    /// if we know of it.
+    pub previous_signer_output: Option<u64>,
+}
+
+/// A broadcast sweep transaction where the fee or virtual size reported
+/// by bitcoin-core differed from the values that the signers assessed
+/// when they validated it.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct SweepFeeDiscrepancy {
+    /// The ID of the sweep transaction.
+    pub txid: BitcoinTxId,
+    /// The fee that the signers assessed for the transaction, in sats.
+    #[sqlx(try_from = "i64")]
+    pub assessed_fee: u64,
+    /// The fee of the transaction as reported by bitcoin-core, in sats.
+    #[sqlx(try_from = "i64")]
+    pub actual_fee: u64,
+    /// The virtual size that the signers assessed for the transaction.
+    #[sqlx(try_from = "i64")]
+    pub assessed_vsize: u64,
+    /// The virtual size of the transaction as reported by bitcoin-core.
+    #[sqlx(try_from = "i64")]
+    pub actual_vsize: u64,
+}
+
+impl SweepFeeDiscrepancy {
+    /// The absolute difference between the actual and assessed fees, in
+    /// sats.
+    pub fn fee_difference(&self) -> u64 {
+        self.actual_fee.abs_diff(self.assessed_fee)
+    }
+
+    /// The absolute difference between the actual and assessed virtual
+    /// sizes, in vbytes.
+    pub fn vsize_difference(&self) -> u64 {
+        self.actual_vsize.abs_diff(self.assessed_vsize)
+    }
+}
+
+/// A signer's attestation to the outcome of a DKG round, as stored in
+/// the database.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct KeyRotationAttestation {
+    /// The aggregate key that the signer attests to.
+    pub aggregate_key: PublicKey,
+    /// The public key of the signer that made the attestation.
+    pub signer_public_key: PublicKey,
+    /// The SHA-256 hash of the sorted, compressed public keys of the new
+    /// signer set.
+    pub signer_set_hash: Bytes,
+    /// The number of signatures required for the new multi-sig wallet.
+    #[sqlx(try_from = "i32")]
+    pub signatures_required: u16,
+    /// The compact ECDSA signature of the signer over the attestation.
+    pub signature: Bytes,
+}
+
+impl KeyRotationAttestation {
+    /// Create a new database entry for an attestation made by the signer
+    /// with the given public key.
+    pub fn new(
+        signer_public_key: PublicKey,
+        attestation: &message::KeyRotationAttestation,
+    ) -> Self {
+        Self {
+            aggregate_key: attestation.aggregate_key,
+            signer_public_key,
+            signer_set_hash: attestation.signer_set_hash.to_vec(),
+            signatures_required: attestation.signatures_required,
+            signature: attestation.signature.serialize_compact().to_vec(),
+        }
+    }
+}
+
+impl TryFrom<&KeyRotationAttestation> for message::KeyRotationAttestation {
+    type Error = Error;
+    fn try_from(value: &KeyRotationAttestation) -> Result<Self, Self::Error> {
+        Ok(Self {
+            aggregate_key: value.aggregate_key,
+            signer_set_hash: value
+                .signer_set_hash
+                .as_slice()
+                .try_into()
+                .map_err(|_| Error::TypeConversion)?,
+            signatures_required: value.signatures_required,
+            signature: secp256k1::ecdsa::Signature::from_compact(&value.signature)
+                .map_err(Error::InvalidEcdsaSignature)?,
+        })
+    }
+}
+
+impl SbtcSupplySnapshot {
+    /// The difference between the reported supply and the expected
+    /// supply, in sats.
+    pub fn divergence(&self) -> i128 {
+        i128::from(self.reported_supply) - self.totals.expected_supply()
+    }
+}
+
 /// A bitcoin transaction output (TXO) relevant for the sBTC signers.
 ///
 /// This object can have a few different meanings, all of them identified
@@ -86,6 +914,35 @@ pub struct WithdrawalTxOutput {
     pub request_id: u64,
 }
 
+/// A human-readable label for withdrawal outputs locked by a known
+/// scriptPubKey, such as the hot wallet of an exchange or a custodian.
+///
+/// Labels are only used for reporting and have no effect on whether the
+/// signers accept or service a withdrawal request.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, sqlx::FromRow)]
+pub struct WithdrawalOutputLabel {
+    /// The scriptPubKey that the label applies to.
+    pub script_pubkey: ScriptPubKey,
+    /// The label given to withdrawal outputs locked by the scriptPubKey.
+    pub label: String,
+}
+
+/// A WSTS signer state machine that was evicted from the transaction
+/// signer's in-memory cache while its round was still in progress.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct SpilledStateMachine {
+    /// The identifier of the state machine, as formatted by its `Display`
+    /// implementation.
+    pub state_machine_id: String,
+    /// The state of the state machine, encrypted with the signer's private
+    /// key. This is redacted when formatted since it holds the signer's
+    /// secret polynomial and any private DKG shares that it has received.
+    pub encrypted_state: Secret<Bytes>,
+    /// The height of the bitcoin chain tip when the state machine was
+    /// evicted.
+    pub bitcoin_block_height: BitcoinBlockHeight,
+}
+
 /// A bitcoin transaction output being spent as an input in a transaction.
 ///
 /// This object can have two different meanings: whether or not this is a
@@ -170,6 +1027,135 @@ impl From<bitcoin::Block> for BitcoinBlock {
     }
 }
 
+/// The stage of processing that a deposit or withdrawal request is in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RequestStatus {
+    /// The signers have not decided on the request yet.
+    Pending,
+    /// Enough signers accepted the request, but it has not been included
+    /// in a sweep transaction that the signers signed.
+    Accepted,
+    /// The request is in a sweep transaction that the signers signed, but
+    /// that transaction is not confirmed on the canonical bitcoin
+    /// blockchain.
+    InFlight,
+    /// The request was swept on the canonical bitcoin blockchain, but it
+    /// has not been finalized on the canonical stacks blockchain.
+    SweptAwaitingStacks,
+    /// The request was finalized on the canonical stacks blockchain.
+    Completed,
+    /// The request was rejected on the canonical stacks blockchain, or
+    /// enough signers rejected it that it can never be accepted.
+    Rejected,
+}
+
+/// The number of requests in each stage of processing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RequestStatusCounts {
+    /// The number of requests that are [`RequestStatus::Pending`].
+    pub pending: u64,
+    /// The number of requests that are [`RequestStatus::Accepted`].
+    pub accepted: u64,
+    /// The number of requests that are [`RequestStatus::InFlight`].
+    pub in_flight: u64,
+    /// The number of requests that are
+    /// [`RequestStatus::SweptAwaitingStacks`].
+    pub swept_awaiting_stacks: u64,
+    /// The number of requests that are [`RequestStatus::Completed`].
+    pub completed: u64,
+    /// The number of requests that are [`RequestStatus::Rejected`].
+    pub rejected: u64,
+}
+
+impl RequestStatusCounts {
+    /// Count one more request with the given status.
+    pub fn increment(&mut self, status: RequestStatus) {
+        let count = match status {
+            RequestStatus::Pending => &mut self.pending,
+            RequestStatus::Accepted => &mut self.accepted,
+            RequestStatus::InFlight => &mut self.in_flight,
+            RequestStatus::SweptAwaitingStacks => &mut self.swept_awaiting_stacks,
+            RequestStatus::Completed => &mut self.completed,
+            RequestStatus::Rejected => &mut self.rejected,
+        };
+        *count += 1;
+    }
+
+    /// Return the count for each status, along with the name of the
+    /// status.
+    pub fn by_status(&self) -> [(&'static str, u64); 6] {
+        [
+            ("pending", self.pending),
+            ("accepted", self.accepted),
+            ("in_flight", self.in_flight),
+            ("swept_awaiting_stacks", self.swept_awaiting_stacks),
+            ("completed", self.completed),
+            ("rejected", self.rejected),
+        ]
+    }
+}
+
+/// The number of deposit and withdrawal requests in each stage of
+/// processing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RequestCounts {
+    /// The counts for deposit requests confirmed on the canonical bitcoin
+    /// blockchain.
+    pub deposits: RequestStatusCounts,
+    /// The counts for withdrawal requests confirmed on the canonical
+    /// stacks blockchain.
+    pub withdrawals: RequestStatusCounts,
+}
+
+/// A Nakamoto tenure, as seen through the stacks blocks that we have
+/// stored.
+///
+/// All stacks blocks produced during a tenure are anchored to the bitcoin
+/// block whose sortition started the tenure, so the hash of that bitcoin
+/// block identifies the tenure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::FromRow)]
+pub struct StacksTenure {
+    /// The hash of the bitcoin block that the tenure is anchored to.
+    pub tenure_id: BitcoinBlockHash,
+    /// The height of the bitcoin block that the tenure is anchored to.
+    pub anchor_height: BitcoinBlockHeight,
+    /// The number of stacks blocks that we know about in the tenure. This
+    /// includes blocks on forks within the tenure.
+    #[sqlx(try_from = "i64")]
+    pub block_count: u64,
+    /// The height of the lowest stacks block in the tenure.
+    pub first_block_height: StacksBlockHeight,
+    /// The height of the highest stacks block in the tenure.
+    pub last_block_height: StacksBlockHeight,
+}
+
+/// The stacks chain tip along with the tenure that produced it.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct StacksChainTip {
+    /// The stacks block at the tip of the chain.
+    #[sqlx(flatten)]
+    pub block: StacksBlock,
+    /// The tenure that produced the chain tip block.
+    #[sqlx(flatten)]
+    pub tenure: StacksTenure,
+}
+
+/// A contract call to one of the sBTC contracts that was signed by an
+/// order independent multi-sig wallet, along with the public keys of the
+/// wallet that did not sign it.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct MultisigStacksTransaction {
+    /// The ID of the stacks transaction.
+    pub txid: StacksTxId,
+    /// The hash of the stacks block that includes the transaction.
+    pub block_hash: StacksBlockHash,
+    /// The hash160 of the multi-sig spending condition, which is the hash
+    /// in the stacks address of the wallet.
+    pub signer_hash: [u8; 20],
+    /// The public keys of the wallet that did not sign the transaction.
+    pub non_signers: Vec<PublicKey>,
+}
+
 /// Stacks block.
 #[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, sqlx::FromRow)]
 #[cfg_attr(feature = "testing", derive(fake::Dummy))]
@@ -240,7 +1226,10 @@ pub struct DepositRequest {
     #[cfg_attr(feature = "testing", dummy(faker = "100..100_000"))]
     pub max_fee: u64,
     /// The relative lock time in the reclaim script.
-    #[cfg_attr(feature = "testing", dummy(faker = "3..u16::MAX as u32"))]
+    #[cfg_attr(
+        feature = "testing",
+        dummy(faker = "(crate::DEPOSIT_LOCKTIME_BLOCK_BUFFER as u32 + 1)..u16::MAX as u32")
+    )]
     pub lock_time: u32,
     /// The public key used in the deposit script. The signers public key
     /// is for Schnorr signatures.
@@ -251,6 +1240,11 @@ pub struct DepositRequest {
         dummy(faker = "crate::testing::dummy::BitcoinAddresses(1..5)")
     )]
     pub sender_script_pub_keys: Vec<ScriptPubKey>,
+    /// The fee paid by the transaction that funded the deposit, along
+    /// with its virtual size. The fee rate tells us how urgently the
+    /// depositor treated the deposit. This is `None` for deposits that
+    /// were recorded before we started capturing it.
+    pub funding_fees: Option<Fees>,
 }
 
 impl From<Deposit> for DepositRequest {
@@ -263,6 +1257,7 @@ impl From<Deposit> for DepositRequest {
             .collect();
 
         let reclaim_script_hash = TaprootScriptHash::from(&deposit.info.reclaim_script);
+        let funding_fees = deposit.tx_info.fees();
 
         Self {
             txid: deposit.info.outpoint.txid.into(),
@@ -275,6 +1270,7 @@ impl From<Deposit> for DepositRequest {
             lock_time: deposit.info.lock_time.to_consensus_u32(),
             signers_public_key: deposit.info.signers_public_key.into(),
             sender_script_pub_keys: sender_script_pub_keys.into_iter().collect(),
+            funding_fees,
         }
     }
 }
@@ -287,6 +1283,20 @@ impl DepositRequest {
             vout: self.output_index,
         }
     }
+
+    /// Return the `scriptPubKey` locking the deposit UTXO. It commits to
+    /// the deposit script and the hash of the reclaim script, using the
+    /// same unspendable internal key as every deposit.
+    pub fn script_pubkey(&self) -> ScriptBuf {
+        let deposit_script = bitcoin::Script::from_bytes(&self.spend_script);
+        let leaf_version = bitcoin::taproot::LeafVersion::TapScript;
+        let deposit_leaf = bitcoin::TapNodeHash::from_script(deposit_script, leaf_version);
+        let merkle_root =
+            bitcoin::TapNodeHash::from_node_hashes(deposit_leaf, *self.reclaim_script_hash);
+
+        let internal_key = *sbtc::UNSPENDABLE_TAPROOT_KEY;
+        ScriptBuf::new_p2tr(secp256k1::SECP256K1, internal_key, Some(merkle_root))
+    }
 }
 
 /// A signer acknowledging a deposit request.
@@ -387,6 +1397,12 @@ pub struct WithdrawalSigner {
     pub signer_pub_key: PublicKey,
     /// Signals if the signer is prepared to sign for this request.
     pub is_accepted: bool,
+    /// Why this signer rejected the request, if it did. This is only
+    /// known for our own decisions, since the reason is not shared with
+    /// the other signers.
+    #[sqlx(default)]
+    #[cfg_attr(feature = "testing", dummy(default))]
+    pub rejection_reason: Option<String>,
 }
 
 impl WithdrawalSigner {
@@ -522,8 +1538,9 @@ pub struct EncryptedDkgShares {
     pub tweaked_aggregate_key: PublicKey,
     /// The `scriptPubKey` for the aggregate public key.
     pub script_pubkey: ScriptPubKey,
-    /// The encrypted DKG shares
-    pub encrypted_private_shares: Bytes,
+    /// The encrypted DKG shares. These are redacted when formatted since
+    /// they are only as safe as the private key that encrypted them.
+    pub encrypted_private_shares: Secret<Bytes>,
     /// The public DKG shares
     pub public_shares: Bytes,
     /// The set of public keys that were a party to the DKG.
@@ -668,6 +1685,59 @@ pub enum DkgSharesStatus {
     Failed,
 }
 
+/// A DKG verification signing round that this signer has taken part in.
+///
+/// These are persisted so that a signer that restarts in the middle of a
+/// round can rejoin it, or mark it as aborted, instead of leaving its
+/// peers waiting until the round times out.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct DkgVerificationRound {
+    /// The x-only aggregate key that is being verified.
+    pub aggregate_key: PublicKeyXOnly,
+    /// The block hash of the bitcoin chain tip that identifies the
+    /// coordinator tenure in which the round takes place.
+    pub bitcoin_block_hash: BitcoinBlockHash,
+    /// The block height of the bitcoin chain tip that identifies the
+    /// coordinator tenure in which the round takes place.
+    pub bitcoin_block_height: BitcoinBlockHeight,
+    /// The current status of the round.
+    pub status: DkgVerificationRoundStatus,
+    /// The type of the last WSTS message that was processed for the
+    /// round, if any.
+    pub last_message_type: Option<String>,
+    /// The timestamp of the last time the round was updated. This is set
+    /// by the database when the round is written.
+    pub updated_at: Timestamp,
+}
+
+impl DkgVerificationRound {
+    /// The bitcoin chain tip that identifies the coordinator tenure in
+    /// which the round takes place.
+    pub fn bitcoin_chain_tip(&self) -> BitcoinBlockRef {
+        BitcoinBlockRef {
+            block_hash: self.bitcoin_block_hash,
+            block_height: self.bitcoin_block_height,
+        }
+    }
+}
+
+/// The status of a DKG verification signing round.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, sqlx::Type)]
+#[sqlx(type_name = "dkg_verification_round_status", rename_all = "snake_case")]
+#[cfg_attr(feature = "testing", derive(fake::Dummy))]
+pub enum DkgVerificationRoundStatus {
+    /// The round is in progress.
+    Active,
+    /// The round completed with a signature, which may or may not have
+    /// passed verification.
+    Completed,
+    /// The round ended in an error or expired state.
+    Failed,
+    /// The round was abandoned because the signer restarted and could not
+    /// rejoin it.
+    Aborted,
+}
+
 /// The types of Bitcoin transaction input or outputs that the signer may
 /// be interested in.
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, sqlx::Type, strum::Display)]
@@ -927,6 +1997,53 @@ impl AsRef<BitcoinBlockHash> for BitcoinBlockRef {
     }
 }
 
+/// How much of the canonical bitcoin blockchain leading up to a chain tip
+/// is in the database, compared to how much of it was asked for.
+///
+/// Signers only have the bitcoin blocks since their database was
+/// bootstrapped, so queries that look back a number of blocks from the
+/// chain tip may see fewer blocks than they ask for. Such a query cannot
+/// tell a request that does not exist from one in a block that we do not
+/// have. The stored history is still complete when it reaches back to the
+/// start of sBTC, since there is nothing for the signers to find before
+/// it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ChainHistory {
+    /// The number of blocks, counting the chain tip, that were asked for.
+    pub requested_blocks: u16,
+    /// The number of blocks, counting the chain tip, that are in the
+    /// database. This is never more than `requested_blocks`.
+    pub available_blocks: u16,
+    /// The height of the oldest of the available blocks, if any block is
+    /// available.
+    pub oldest_block_height: Option<BitcoinBlockHeight>,
+    /// Whether the available blocks hold all of the requested history
+    /// that the signers care about.
+    pub history_complete: bool,
+}
+
+impl ChainHistory {
+    /// Describe the history given the number of blocks asked for and
+    /// found, and the height of the first block that the signers care
+    /// about.
+    pub fn new(
+        requested_blocks: u16,
+        available_blocks: u16,
+        oldest_block_height: Option<BitcoinBlockHeight>,
+        start_height: BitcoinBlockHeight,
+    ) -> Self {
+        let history_complete = available_blocks >= requested_blocks
+            || oldest_block_height.is_some_and(|height| height <= start_height);
+
+        Self {
+            requested_blocks,
+            available_blocks: available_blocks.min(requested_blocks),
+            oldest_block_height,
+            history_complete,
+        }
+    }
+}
+
 /// The Stacks block ID. This type mirrors the `StacksBlockId` type in
 /// stacks-core, not the `BlockHeaderHash` type.
 ///
@@ -1382,6 +2499,10 @@ pub struct BitcoinTxSigHash {
     /// that failed during validation. The signers' input is always valid,
     /// since it is unconfirmed.
     pub validation_result: InputValidationResult,
+    /// The outcome of each check done when validating the input, so that
+    /// we can tell which failures may resolve themselves later. This is
+    /// empty for the signers' input.
+    pub validation_checks: ValidationChecks,
     /// Whether the transaction is valid. A transaction is invalid if any
     /// of the inputs or outputs failed validation.
     pub is_valid_tx: bool,
@@ -1419,6 +2540,11 @@ pub struct BitcoinWithdrawalOutput {
     pub stacks_block_hash: StacksBlockHash,
     /// The outcome of validation of the withdrawal request.
     pub validation_result: WithdrawalValidationResult,
+    /// The outcome of each check done when validating the withdrawal
+    /// request, so that we can tell which failures may resolve themselves
+    /// later.
+    #[sqlx(try_from = "String")]
+    pub validation_checks: ValidationChecks,
     /// Whether the transaction is valid. A transaction is invalid if any
     /// of the inputs or outputs failed validation.
     pub is_valid_tx: bool,
@@ -1430,6 +2556,12 @@ impl From<sbtc::events::StacksTxid> for StacksTxId {
     }
 }
 
+impl From<StacksTxId> for sbtc::events::StacksTxid {
+    fn from(value: StacksTxId) -> Self {
+        Self(value.0)
+    }
+}
+
 impl From<sbtc::events::CompletedDepositEvent> for CompletedDepositEvent {
     fn from(sbtc_event: sbtc::events::CompletedDepositEvent) -> CompletedDepositEvent {
         let sweep_hash = BitcoinBlockHash::from(sbtc_event.sweep_block_hash);
@@ -1879,6 +3011,53 @@ mod tests {
 
     use super::*;
 
+    #[test_case(1000, &[], Some(0); "nothing withdrawn")]
+    #[test_case(0, &[(95, 600)], None; "zero cap")]
+    #[test_case(1000, &[(95, 600), (100, 300)], Some(0); "headroom now")]
+    #[test_case(1000, &[(95, 600), (100, 400)], Some(5); "oldest frees headroom")]
+    #[test_case(1000, &[(100, 400), (95, 900)], Some(5); "unordered withdrawals")]
+    #[test_case(500, &[(95, 100), (98, 300), (100, 400)], Some(8); "several must leave")]
+    fn caps_utilization_blocks_until_headroom(
+        cap: u64,
+        withdrawals: &[(u64, u64)],
+        expected: Option<u64>,
+    ) {
+        let withdrawals: Vec<(BitcoinBlockHeight, u64)> = withdrawals
+            .iter()
+            .map(|(height, amount)| ((*height).into(), *amount))
+            .collect();
+        // The window holds blocks 91 through 100.
+        let utilization = CapsUtilization::new(100u64.into(), 10, cap, &withdrawals);
+
+        let withdrawn_total: u64 = withdrawals.iter().map(|(_, amount)| amount).sum();
+        assert_eq!(utilization.withdrawn_total, withdrawn_total);
+        assert_eq!(utilization.available, cap.saturating_sub(withdrawn_total));
+        assert_eq!(utilization.blocks_until_headroom, expected);
+    }
+
+    #[test_case(10, 10, Some(91), 0, true; "all blocks stored")]
+    #[test_case(10, 4, Some(97), 0, false; "blocks missing")]
+    #[test_case(10, 4, Some(97), 97, true; "reaches the start height")]
+    #[test_case(10, 4, Some(97), 100, true; "starts after the chain tip")]
+    #[test_case(10, 0, None, 0, false; "chain tip missing")]
+    #[test_case(0, 0, None, 0, true; "nothing requested")]
+    fn chain_history_completeness(
+        requested: u16,
+        available: u16,
+        oldest_height: Option<u64>,
+        start_height: u64,
+        complete: bool,
+    ) {
+        let history = ChainHistory::new(
+            requested,
+            available,
+            oldest_height.map(BitcoinBlockHeight::from),
+            start_height.into(),
+        );
+        assert_eq!(history.history_complete, complete);
+        assert!(history.available_blocks <= history.requested_blocks);
+    }
+
     #[test]
     fn conversion_bitcoin_header_hashes() {
         let mut rng = get_rng();
@@ -1956,4 +3135,27 @@ mod tests {
         assert_eq!(local_type, local_type_des);
         assert_eq!(foreign_type_des.to_string(), local_type_des.to_string());
     }
+
+    #[test_case(100, true; "same block")]
+    #[test_case(101, true; "one block later")]
+    #[test_case(102, false; "expired")]
+    #[test_case(99, false; "earlier block after a reorg")]
+    fn inflight_contract_call_expires_after_blocks(chain_tip_height: u64, pending: bool) {
+        let contract_call = InflightContractCall {
+            target: ContractCallTarget::Withdrawal {
+                id: QualifiedRequestId {
+                    request_id: 1,
+                    txid: StacksTxId::from([2; 32]),
+                    block_hash: StacksBlockHash::from([3; 32]),
+                },
+            },
+            txid: StacksTxId::from([1; 32]),
+            bitcoin_block_height: 100u64.into(),
+            bitcoin_block_hash: BitcoinBlockHash::from([4; 32]),
+        };
+        assert_eq!(
+            contract_call.is_pending_at(chain_tip_height.into(), 2),
+            pending
+        );
+    }
 }
@@ -0,0 +1,172 @@
+//! Application-level encryption of sensitive columns.
+//!
+//! Some columns hold information that operators may not want readable by
+//! anyone with access to the database or its backups, such as the reason
+//! that an operator placed a request override, or why this signer
+//! rejected a withdrawal request. When a [`ColumnEncryptionKey`] is
+//! configured, these values are encrypted with AES-256-GCM before they
+//! are written and decrypted after they are read.
+//!
+//! The blocklist verdicts themselves, the `can_accept` column of
+//! `deposit_signers` and the `is_accepted` column of
+//! `withdrawal_signers`, are left in plaintext. The queries that count
+//! the signers' votes filter and aggregate on them in SQL, and every
+//! signer broadcasts its verdicts to the others anyway. Why this signer
+//! rejected a withdrawal is only known locally, so its `rejection_reason`
+//! is encrypted.
+//!
+//! Encrypted values are stored as [`ENCRYPTED_COLUMN_PREFIX`] followed by
+//! the hex encoding of the nonce and ciphertext. Values without the
+//! prefix were written before encryption was enabled and are returned as
+//! is; the `encrypt-db-columns` binary encrypts them in place.
+
+use std::str::FromStr;
+
+use rand::rngs::OsRng;
+use serde::Deserialize;
+use serde::Deserializer;
+
+use crate::error::Error;
+
+/// The prefix of encrypted column values. The version lets us change the
+/// encryption scheme later while still reading values written with this
+/// one.
+pub const ENCRYPTED_COLUMN_PREFIX: &str = "enc:v1:";
+
+/// The 256-bit key used to encrypt sensitive columns in the database.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ColumnEncryptionKey([u8; 32]);
+
+impl ColumnEncryptionKey {
+    /// Create a new key from the given bytes.
+    pub fn new(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Return the hex encoding of the key.
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.0)
+    }
+
+    /// Encrypt the given plaintext column value.
+    pub fn encrypt(&self, plaintext: &str) -> Result<String, Error> {
+        let ciphertext = wsts::util::encrypt(&self.0, plaintext.as_bytes(), &mut OsRng)
+            .map_err(Error::ColumnEncrypt)?;
+
+        Ok(format!(
+            "{ENCRYPTED_COLUMN_PREFIX}{}",
+            hex::encode(ciphertext)
+        ))
+    }
+
+    /// Decrypt the given column value. Values that are not encrypted are
+    /// returned unchanged.
+    pub fn decrypt(&self, value: String) -> Result<String, Error> {
+        let Some(encoded) = value.strip_prefix(ENCRYPTED_COLUMN_PREFIX) else {
+            return Ok(value);
+        };
+
+        let ciphertext = hex::decode(encoded).map_err(Error::InvalidEncryptedColumn)?;
+        let plaintext = wsts::util::decrypt(&self.0, &ciphertext).map_err(Error::ColumnDecrypt)?;
+
+        String::from_utf8(plaintext).map_err(Error::InvalidUtf8Column)
+    }
+}
+
+impl std::fmt::Debug for ColumnEncryptionKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ColumnEncryptionKey(<redacted>)")
+    }
+}
+
+impl FromStr for ColumnEncryptionKey {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut bytes = [0u8; 32];
+        hex::decode_to_slice(s, &mut bytes).map_err(|_| Error::InvalidColumnEncryptionKey)?;
+        Ok(Self(bytes))
+    }
+}
+
+impl<'de> Deserialize<'de> for ColumnEncryptionKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Encrypt the column value if a key is given, otherwise return it as is.
+pub fn encrypt_column(key: Option<&ColumnEncryptionKey>, value: &str) -> Result<String, Error> {
+    match key {
+        Some(key) => key.encrypt(value),
+        None => Ok(value.to_string()),
+    }
+}
+
+/// Decrypt the column value if it is encrypted. Returns an error if the
+/// value is encrypted and no key is given.
+pub fn decrypt_column(key: Option<&ColumnEncryptionKey>, value: String) -> Result<String, Error> {
+    match key {
+        Some(key) => key.decrypt(value),
+        None if value.starts_with(ENCRYPTED_COLUMN_PREFIX) => {
+            Err(Error::MissingColumnEncryptionKey)
+        }
+        None => Ok(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "5d5a9c2fd8c4f1b7e0a3d6c9b2e5f8a1c4d7e0b3a6f9c2d5e8b1a4c7d0e3f6a9";
+
+    #[test]
+    fn encrypted_values_round_trip() {
+        let key: ColumnEncryptionKey = KEY.parse().unwrap();
+
+        let encrypted = key.encrypt("sanctioned address").unwrap();
+        assert!(encrypted.starts_with(ENCRYPTED_COLUMN_PREFIX));
+        assert!(!encrypted.contains("sanctioned"));
+
+        let decrypted = decrypt_column(Some(&key), encrypted).unwrap();
+        assert_eq!(decrypted, "sanctioned address");
+    }
+
+    #[test]
+    fn plaintext_values_are_returned_unchanged() {
+        let key: ColumnEncryptionKey = KEY.parse().unwrap();
+
+        let value = "written before encryption".to_string();
+        assert_eq!(decrypt_column(Some(&key), value.clone()).unwrap(), value);
+        assert_eq!(decrypt_column(None, value.clone()).unwrap(), value);
+        assert_eq!(encrypt_column(None, &value).unwrap(), value);
+    }
+
+    #[test]
+    fn decrypting_needs_the_right_key() {
+        let key: ColumnEncryptionKey = KEY.parse().unwrap();
+        let other_key = ColumnEncryptionKey::new([7; 32]);
+
+        let encrypted = key.encrypt("reason").unwrap();
+
+        let result = decrypt_column(Some(&other_key), encrypted.clone());
+        assert!(matches!(result, Err(Error::ColumnDecrypt(_))));
+
+        let result = decrypt_column(None, encrypted);
+        assert!(matches!(result, Err(Error::MissingColumnEncryptionKey)));
+    }
+
+    #[test]
+    fn keys_must_be_32_bytes_of_hex() {
+        assert!(KEY[..62].parse::<ColumnEncryptionKey>().is_err());
+        assert!("zz".repeat(32).parse::<ColumnEncryptionKey>().is_err());
+
+        let key: ColumnEncryptionKey = KEY.parse().unwrap();
+        assert!(!format!("{key:?}").contains(KEY));
+    }
+}
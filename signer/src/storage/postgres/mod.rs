@@ -1,9 +1,11 @@
 //! Postgres storage implementation.
 
+pub mod encryption;
 mod read;
 mod store;
 mod write;
 
+pub use encryption::ColumnEncryptionKey;
 pub use store::PgStore;
 pub use store::PgTransaction;
 
@@ -4,16 +4,19 @@ use bitcoin::OutPoint;
 
 use crate::{
     DEPOSIT_LOCKTIME_BLOCK_BUFFER, MAX_MEMPOOL_PACKAGE_TX_COUNT, MAX_REORG_BLOCK_COUNT,
-    WITHDRAWAL_BLOCKS_EXPIRY,
+    TIME_BASED_LOCKTIME_SECONDS_PER_BLOCK, WITHDRAWAL_BLOCKS_EXPIRY,
     bitcoin::{
-        utxo::SignerUtxo,
+        utxo::{Fees, SignerUtxo},
         validation::{
             DepositConfirmationStatus, DepositRequestReport, WithdrawalRequestReport,
             WithdrawalRequestStatus,
         },
     },
+    codec::Decode as _,
+    config::DepositConfirmationPolicy,
     error::Error,
     keys::{PublicKey, PublicKeyXOnly},
+    message::BitcoinPreSignRequest,
     storage::{
         DbRead,
         model::{
@@ -23,6 +26,9 @@ use crate::{
     },
 };
 
+use super::encryption::{ColumnEncryptionKey, decrypt_column};
+use super::write::ContractCallTargetColumns;
+use super::write::override_target_columns;
 use super::{PgStore, PgTransaction};
 
 /// A convenience struct for retrieving a deposit request report
@@ -104,6 +110,16 @@ impl From<PgSignerUtxo> for SignerUtxo {
         }
     }
 }
+
+/// Decrypt the reason of a request override read from the database.
+fn decrypt_override(
+    column_key: Option<&ColumnEncryptionKey>,
+    mut request_override: model::RequestOverride,
+) -> Result<model::RequestOverride, Error> {
+    request_override.reason = decrypt_column(column_key, request_override.reason)?;
+    Ok(request_override)
+}
+
 /// Read-accessors to the Postgres database.
 pub struct PgRead;
 
@@ -142,16 +158,16 @@ impl PgRead {
         .map_err(Error::SqlxQuery)
     }
 
-    async fn get_utxo<'e, E>(
+    async fn get_utxos<'e, E>(
         executor: &'e mut E,
         chain_tip: &model::BitcoinBlockHash,
         output_type: model::TxOutputType,
         min_block_height: BitcoinBlockHeight,
-    ) -> Result<Option<SignerUtxo>, Error>
+    ) -> Result<Vec<SignerUtxo>, Error>
     where
         &'e mut E: sqlx::PgExecutor<'e>,
     {
-        let pg_utxo = sqlx::query_as::<_, PgSignerUtxo>(
+        let pg_utxos = sqlx::query_as::<_, PgSignerUtxo>(
             r#"
             WITH bitcoin_blockchain AS (
                 SELECT block_hash
@@ -180,18 +196,17 @@ impl PgRead {
               AND cs.prevout_output_index = bo.output_index
             WHERE cs.prevout_txid IS NULL
               AND bo.output_type = $3
-            ORDER BY bo.amount DESC
-            LIMIT 1;
+            ORDER BY bo.amount DESC, bo.txid, bo.output_index;
             "#,
         )
         .bind(chain_tip)
         .bind(i64::try_from(min_block_height).map_err(Error::ConversionDatabaseInt)?)
         .bind(output_type)
-        .fetch_optional(executor)
+        .fetch_all(executor)
         .await
         .map_err(Error::SqlxQuery)?;
 
-        Ok(pg_utxo.map(SignerUtxo::from))
+        Ok(pg_utxos.into_iter().map(SignerUtxo::from).collect())
     }
 
     /// Return the height of the earliest block in which a donation UTXO
@@ -223,20 +238,21 @@ impl PgRead {
         .map_err(Error::SqlxQuery)
     }
 
-    /// Return a donation UTXO with minimum height.
-    async fn get_donation_utxo<'e, E>(
+    /// Return the unspent donation UTXOs with minimum height, largest
+    /// first.
+    async fn get_donation_utxos<'e, E>(
         executor: &'e mut E,
         chain_tip: &model::BitcoinBlockHash,
-    ) -> Result<Option<SignerUtxo>, Error>
+    ) -> Result<Vec<SignerUtxo>, Error>
     where
         E: 'static,
         for<'c> &'c mut E: sqlx::PgExecutor<'c>,
     {
         let Some(min_block_height) = Self::minimum_donation_txo_height(executor).await? else {
-            return Ok(None);
+            return Ok(Vec::new());
         };
         let output_type = model::TxOutputType::Donation;
-        Self::get_utxo(executor, chain_tip, output_type, min_block_height).await
+        Self::get_utxos(executor, chain_tip, output_type, min_block_height).await
     }
 
     /// Fetch the bitcoin transaction ID that swept the withdrawal along
@@ -831,6 +847,161 @@ impl PgRead {
         .map_err(Error::SqlxQuery)
     }
 
+    pub async fn get_stacks_chain_tip_v2<'e, E>(
+        executor: &'e mut E,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Option<model::StacksChainTip>, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query_as::<_, model::StacksChainTip>(
+            r#"
+            WITH RECURSIVE context_window AS (
+                SELECT
+                    block_hash
+                  , block_height
+                  , parent_hash
+                FROM sbtc_signer.bitcoin_blocks
+                WHERE block_hash = $1
+
+                UNION ALL
+
+                SELECT
+                    parent.block_hash
+                  , parent.block_height
+                  , parent.parent_hash
+                FROM sbtc_signer.bitcoin_blocks AS parent
+                JOIN context_window AS child
+                  ON parent.block_hash = child.parent_hash
+            )
+            SELECT
+                stacks_blocks.block_hash
+              , stacks_blocks.block_height
+              , stacks_blocks.parent_hash
+              , stacks_blocks.bitcoin_anchor
+              , tenures.tenure_id
+              , tenures.anchor_height
+              , tenures.block_count
+              , tenures.first_block_height
+              , tenures.last_block_height
+            FROM context_window AS bitcoin_blocks
+            JOIN sbtc_signer.stacks_blocks AS stacks_blocks
+              ON bitcoin_blocks.block_hash = stacks_blocks.bitcoin_anchor
+            JOIN sbtc_signer.stacks_tenures AS tenures
+              ON tenures.tenure_id = stacks_blocks.bitcoin_anchor
+            ORDER BY
+                stacks_blocks.block_height DESC
+              , tenures.anchor_height DESC
+              , stacks_blocks.block_hash DESC
+            LIMIT 1;
+            "#,
+        )
+        .bind(bitcoin_chain_tip)
+        .fetch_optional(executor)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
+    pub async fn get_unscanned_stacks_blocks<'e, E>(
+        executor: &'e mut E,
+        chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+    ) -> Result<Vec<model::StacksBlockHash>, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query_scalar::<_, model::StacksBlockHash>(
+            r#"
+            WITH RECURSIVE context_window AS (
+                SELECT block_hash, parent_hash, 1 AS depth
+                FROM sbtc_signer.bitcoin_blocks
+                WHERE block_hash = $1
+
+                UNION ALL
+
+                SELECT parent.block_hash, parent.parent_hash, last.depth + 1
+                FROM sbtc_signer.bitcoin_blocks parent
+                JOIN context_window last ON parent.block_hash = last.parent_hash
+                WHERE last.depth < $2
+            )
+            SELECT stacks_blocks.block_hash
+            FROM context_window AS bitcoin_blocks
+            JOIN sbtc_signer.stacks_blocks AS stacks_blocks
+              ON stacks_blocks.bitcoin_anchor = bitcoin_blocks.block_hash
+            LEFT JOIN sbtc_signer.scanned_stacks_blocks AS scanned
+              ON scanned.block_hash = stacks_blocks.block_hash
+            WHERE scanned.block_hash IS NULL
+            ORDER BY stacks_blocks.block_height, stacks_blocks.block_hash
+            "#,
+        )
+        .bind(chain_tip)
+        .bind(i32::from(context_window))
+        .fetch_all(executor)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
+    pub async fn get_multisig_stacks_transactions<'e, E>(
+        executor: &'e mut E,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        stacks_chain_tip: &model::StacksBlockHash,
+        context_window: u16,
+    ) -> Result<Vec<model::MultisigStacksTransaction>, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query_as::<_, model::MultisigStacksTransaction>(
+            r#"
+            WITH RECURSIVE context_window AS (
+                SELECT block_hash, parent_hash, 1 AS depth
+                FROM sbtc_signer.bitcoin_blocks
+                WHERE block_hash = $1
+
+                UNION ALL
+
+                SELECT parent.block_hash, parent.parent_hash, last.depth + 1
+                FROM sbtc_signer.bitcoin_blocks parent
+                JOIN context_window last ON parent.block_hash = last.parent_hash
+                WHERE last.depth < $3
+            ),
+            stacks_context_window AS (
+                SELECT
+                    stacks_blocks.block_hash
+                  , stacks_blocks.parent_hash
+                FROM sbtc_signer.stacks_blocks stacks_blocks
+                JOIN context_window block
+                  ON block.block_hash = stacks_blocks.bitcoin_anchor
+                WHERE stacks_blocks.block_hash = $2
+
+                UNION ALL
+
+                SELECT
+                    parent.block_hash
+                  , parent.parent_hash
+                FROM sbtc_signer.stacks_blocks parent
+                JOIN stacks_context_window last
+                  ON parent.block_hash = last.parent_hash
+                JOIN context_window block
+                  ON block.block_hash = parent.bitcoin_anchor
+            )
+            SELECT
+                txs.txid
+              , txs.block_hash
+              , txs.signer_hash
+              , txs.non_signers
+            FROM stacks_context_window
+            JOIN sbtc_signer.multisig_stacks_transactions AS txs USING (block_hash)
+            ORDER BY txs.txid, txs.block_hash
+            "#,
+        )
+        .bind(bitcoin_chain_tip)
+        .bind(stacks_chain_tip)
+        .bind(i32::from(context_window))
+        .fetch_all(executor)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
     pub async fn get_pending_deposit_requests<'e, E>(
         executor: &'e mut E,
         chain_tip: &model::BitcoinBlockHash,
@@ -874,6 +1045,8 @@ impl PgRead {
               , deposit_requests.lock_time
               , deposit_requests.signers_public_key
               , deposit_requests.sender_script_pub_keys
+              , deposit_requests.funding_fees_total
+              , deposit_requests.funding_fees_vsize
             FROM transactions_in_window transactions
             JOIN sbtc_signer.deposit_requests AS deposit_requests USING (txid)
             LEFT JOIN sbtc_signer.deposit_signers AS ds
@@ -891,11 +1064,64 @@ impl PgRead {
         .map_err(Error::SqlxQuery)
     }
 
+    pub async fn get_recent_deposit_requests<'e, E>(
+        executor: &'e mut E,
+        chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+    ) -> Result<Vec<model::DepositRequest>, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query_as::<_, model::DepositRequest>(
+            r#"
+            WITH RECURSIVE context_window AS (
+                SELECT block_hash, parent_hash, 1 AS depth
+                FROM sbtc_signer.bitcoin_blocks
+                WHERE block_hash = $1
+
+                UNION ALL
+
+                SELECT parent.block_hash, parent.parent_hash, last.depth + 1
+                FROM sbtc_signer.bitcoin_blocks parent
+                JOIN context_window last ON parent.block_hash = last.parent_hash
+                WHERE last.depth < $2
+            ),
+            transactions_in_window AS (
+                SELECT DISTINCT transactions.txid
+                FROM context_window blocks_in_window
+                JOIN sbtc_signer.bitcoin_transactions transactions ON
+                    transactions.block_hash = blocks_in_window.block_hash
+            )
+            SELECT
+                deposit_requests.txid
+              , deposit_requests.output_index
+              , deposit_requests.spend_script
+              , deposit_requests.reclaim_script_hash
+              , deposit_requests.recipient
+              , deposit_requests.amount
+              , deposit_requests.max_fee
+              , deposit_requests.lock_time
+              , deposit_requests.signers_public_key
+              , deposit_requests.sender_script_pub_keys
+              , deposit_requests.funding_fees_total
+              , deposit_requests.funding_fees_vsize
+            FROM transactions_in_window transactions
+            JOIN sbtc_signer.deposit_requests AS deposit_requests USING (txid)
+            "#,
+        )
+        .bind(chain_tip)
+        .bind(i32::from(context_window))
+        .fetch_all(executor)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
     pub async fn get_pending_accepted_deposit_requests<'e, E>(
         executor: &'e mut E,
         chain_tip: &model::BitcoinBlockRef,
         context_window: u16,
         threshold: u16,
+        confirmation_policy: &DepositConfirmationPolicy,
     ) -> Result<Vec<model::DepositRequest>, Error>
     where
         E: 'static,
@@ -909,6 +1135,20 @@ impl PgRead {
         let minimum_acceptable_unlock_height =
             *chain_tip.block_height as i32 + DEPOSIT_LOCKTIME_BLOCK_BUFFER as i32 + 1;
 
+        // The confirmation policy is passed in as two parallel arrays. Tiers
+        // with a minimum amount that does not fit in an i64 cannot apply
+        // to any deposit, so we clamp them.
+        let (policy_min_amounts, policy_confirmations): (Vec<i64>, Vec<i32>) = confirmation_policy
+            .tiers()
+            .iter()
+            .map(|tier| {
+                let min_amount = i64::try_from(tier.min_amount).unwrap_or(i64::MAX);
+                (min_amount, i32::from(tier.confirmations.get()))
+            })
+            .unzip();
+        let chain_tip_height =
+            i64::try_from(*chain_tip.block_height).map_err(Error::ConversionDatabaseInt)?;
+
         sqlx::query_as::<_, model::DepositRequest>(
             r#"
             WITH transactions_in_window AS (
@@ -932,13 +1172,30 @@ impl PgRead {
                   , deposit_requests.lock_time
                   , deposit_requests.signers_public_key
                   , deposit_requests.sender_script_pub_keys
+                  , deposit_requests.funding_fees_total
+                  , deposit_requests.funding_fees_vsize
                 FROM transactions_in_window transactions
                 JOIN sbtc_signer.deposit_requests deposit_requests USING(txid)
                 JOIN sbtc_signer.deposit_signers signers USING(txid, output_index)
                 WHERE
                     signers.can_accept
                     AND signers.can_sign
-                    AND (transactions.block_height + deposit_requests.lock_time) >= $4
+                    -- Time-based lock times have bit 22 set and count
+                    -- units of 512 seconds, which we convert to blocks
+                    -- the same way that `lock_time_in_blocks` does.
+                    AND transactions.block_height + CASE
+                        WHEN deposit_requests.lock_time & 4194304 = 0
+                            THEN deposit_requests.lock_time & 65535
+                        ELSE (deposit_requests.lock_time & 65535) * 512 / $8
+                    END >= $4
+                    -- The deposit must have the number of confirmations
+                    -- required for its amount, or one if no tier applies.
+                    AND $5 - transactions.block_height + 1 >= (
+                        SELECT COALESCE(MAX(policy.confirmations), 1)
+                        FROM UNNEST($6::BIGINT[], $7::INTEGER[])
+                            AS policy(min_amount, confirmations)
+                        WHERE policy.min_amount <= deposit_requests.amount
+                    )
                 GROUP BY deposit_requests.txid, deposit_requests.output_index
                 HAVING COUNT(signers.txid) >= $3
             )
@@ -961,6 +1218,8 @@ impl PgRead {
               , accepted_deposits.lock_time
               , accepted_deposits.signers_public_key
               , accepted_deposits.sender_script_pub_keys
+              , accepted_deposits.funding_fees_total
+              , accepted_deposits.funding_fees_vsize
             HAVING
                 COUNT(transactions_in_window.txid) = 0
             "#,
@@ -969,6 +1228,10 @@ impl PgRead {
         .bind(i32::from(context_window))
         .bind(i32::from(threshold))
         .bind(minimum_acceptable_unlock_height)
+        .bind(chain_tip_height)
+        .bind(policy_min_amounts)
+        .bind(policy_confirmations)
+        .bind(i64::from(TIME_BASED_LOCKTIME_SECONDS_PER_BLOCK))
         .fetch_all(&mut *executor)
         .await
         .map_err(Error::SqlxQuery)
@@ -1015,6 +1278,35 @@ impl PgRead {
         .map_err(Error::SqlxQuery)
     }
 
+    async fn get_withdrawal_requests_by_id<'e, E>(
+        executor: &'e mut E,
+        request_id: u64,
+    ) -> Result<Vec<model::WithdrawalRequest>, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query_as::<_, model::WithdrawalRequest>(
+            r#"
+            SELECT
+                request_id
+              , txid
+              , block_hash
+              , recipient
+              , amount
+              , max_fee
+              , sender_address
+              , bitcoin_block_height
+            FROM sbtc_signer.withdrawal_requests
+            WHERE request_id = $1
+            ORDER BY bitcoin_block_height, block_hash
+            "#,
+        )
+        .bind(i64::try_from(request_id).map_err(Error::ConversionDatabaseInt)?)
+        .fetch_all(executor)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
     async fn get_withdrawal_request_signer_votes<'e, E>(
         executor: &'e mut E,
         id: &model::QualifiedRequestId,
@@ -1219,17 +1511,19 @@ impl PgRead {
         executor: &'e mut E,
         request_id: u64,
         block_hash: &model::StacksBlockHash,
+        column_key: Option<&ColumnEncryptionKey>,
     ) -> Result<Vec<model::WithdrawalSigner>, Error>
     where
         &'e mut E: sqlx::PgExecutor<'e>,
     {
-        sqlx::query_as::<_, model::WithdrawalSigner>(
+        let signers = sqlx::query_as::<_, model::WithdrawalSigner>(
             "SELECT
                 request_id
               , txid
               , block_hash
               , signer_pub_key
               , is_accepted
+              , rejection_reason
               , created_at
             FROM sbtc_signer.withdrawal_signers
             WHERE request_id = $1 AND block_hash = $2",
@@ -1238,7 +1532,18 @@ impl PgRead {
         .bind(block_hash)
         .fetch_all(executor)
         .await
-        .map_err(Error::SqlxQuery)
+        .map_err(Error::SqlxQuery)?;
+
+        signers
+            .into_iter()
+            .map(|mut signer| {
+                signer.rejection_reason = signer
+                    .rejection_reason
+                    .map(|reason| decrypt_column(column_key, reason))
+                    .transpose()?;
+                Ok(signer)
+            })
+            .collect()
     }
 
     async fn get_pending_withdrawal_requests<'e, E>(
@@ -1292,7 +1597,9 @@ impl PgRead {
                 JOIN extended_context_window block
                         ON block.block_hash = parent.bitcoin_anchor
             )
-            SELECT
+            -- The same withdrawal can be confirmed in more than one stacks
+            -- block across forks, so we only keep the most recent one.
+            SELECT DISTINCT ON (wr.request_id)
                 wr.request_id
               , wr.txid
               , wr.block_hash
@@ -1308,6 +1615,7 @@ impl PgRead {
              AND ws.block_hash = wr.block_hash
              AND ws.signer_pub_key = $4
             WHERE ws.request_id IS NULL
+            ORDER BY wr.request_id, sc.block_height DESC
             "#,
         )
         .bind(bitcoin_chain_tip)
@@ -1350,13 +1658,27 @@ impl PgRead {
                   , wre.block_hash as reject_block_hash
                 FROM sbtc_signer.withdrawal_requests wr
 
-                -- Join in any sweep transactions we know about.
+                -- Join in any sweep transactions we know about. A withdrawal
+                -- swept while it was confirmed in a stacks block that was
+                -- later forked out has still been paid, so we do not match
+                -- on the stacks block hash. But a request ID can be reused
+                -- by a different withdrawal on another stacks fork, so we
+                -- match on the stacks transaction ID for outputs that we
+                -- validated, and on the amount and recipient for outputs
+                -- that we only know the request ID of.
                 LEFT JOIN sbtc_signer.bitcoin_withdrawals_outputs bwo
                     ON bwo.request_id = wr.request_id
-                    AND bwo.stacks_block_hash = wr.block_hash
+                    AND bwo.stacks_txid = wr.txid
 
-                LEFT JOIN sbtc_signer.bitcoin_withdrawal_tx_outputs bwto
+                LEFT JOIN (
+                    sbtc_signer.bitcoin_withdrawal_tx_outputs bwto
+                    JOIN sbtc_signer.bitcoin_tx_outputs bto
+                        ON bto.txid = bwto.txid
+                        AND bto.output_index = bwto.output_index
+                )
                     ON bwto.request_id = wr.request_id
+                    AND bto.amount = wr.amount
+                    AND bto.script_pubkey = wr.recipient
 
                 LEFT JOIN sbtc_signer.bitcoin_transactions bt
                     ON bt.txid = bwo.bitcoin_txid
@@ -1984,6 +2306,18 @@ impl PgRead {
         executor: &'e mut E,
         chain_tip: &model::BitcoinBlockHash,
     ) -> Result<Option<SignerUtxo>, Error>
+    where
+        E: 'static,
+        for<'c> &'c mut E: sqlx::PgExecutor<'c>,
+    {
+        let utxos = Self::get_signer_utxos(executor, chain_tip).await?;
+        Ok(utxos.into_iter().next())
+    }
+
+    async fn get_signer_utxos<'e, E>(
+        executor: &'e mut E,
+        chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Vec<SignerUtxo>, Error>
     where
         E: 'static,
         for<'c> &'c mut E: sqlx::PgExecutor<'c>,
@@ -1994,19 +2328,19 @@ impl PgRead {
             // If the above function returns None then we know that there
             // have been no confirmed sweep transactions thus far, so let's
             // try looking for a donation UTXO.
-            return Self::get_donation_utxo(executor, chain_tip).await;
+            return Self::get_donation_utxos(executor, chain_tip).await;
         };
         // Okay, so we know that there has been at least one sweep
-        // transaction. Let's look for the UTXO in a block after our
-        // min_block_height. Note that `Self::get_utxo` returns `None` only
-        // when a reorg has affected all sweep transactions. If this
+        // transaction. Let's look for the UTXOs in blocks after our
+        // min_block_height. Note that `Self::get_utxos` returns nothing
+        // only when a reorg has affected all sweep transactions. If this
         // happens we try searching for a donation.
         let output_type = model::TxOutputType::SignersOutput;
-        let fut = Self::get_utxo(executor, chain_tip, output_type, min_block_height);
-        match fut.await? {
-            res @ Some(_) => Ok(res),
-            None => Self::get_donation_utxo(executor, chain_tip).await,
+        let utxos = Self::get_utxos(executor, chain_tip, output_type, min_block_height).await?;
+        if utxos.is_empty() {
+            return Self::get_donation_utxos(executor, chain_tip).await;
         }
+        Ok(utxos)
     }
 
     async fn is_known_bitcoin_block_hash<'e, E>(
@@ -2454,6 +2788,8 @@ impl PgRead {
                  , lock_time
                  , signers_public_key
                  , sender_script_pub_keys
+                 , funding_fees_total
+                 , funding_fees_vsize
             FROM sbtc_signer.deposit_requests
             WHERE txid = $1
               AND output_index = $2
@@ -2582,39 +2918,1375 @@ impl PgRead {
         .await
         .map_err(Error::SqlxQuery)
     }
-}
-
-impl DbRead for PgStore {
-    async fn get_bitcoin_block(
-        &self,
-        block_hash: &model::BitcoinBlockHash,
-    ) -> Result<Option<model::BitcoinBlock>, Error> {
-        PgRead::get_bitcoin_block(self.get_connection().await?.as_mut(), block_hash).await
-    }
-
-    async fn get_stacks_block(
-        &self,
-        block_hash: &model::StacksBlockHash,
-    ) -> Result<Option<model::StacksBlock>, Error> {
-        PgRead::get_stacks_block(self.get_connection().await?.as_mut(), block_hash).await
-    }
 
-    #[cfg(any(test, feature = "testing"))]
-    async fn get_bitcoin_canonical_chain_tip(
-        &self,
-    ) -> Result<Option<model::BitcoinBlockHash>, Error> {
-        PgRead::get_bitcoin_canonical_chain_tip(self.get_connection().await?.as_mut()).await
+    async fn get_sbtc_supply_totals<'e, E>(
+        executor: &'e mut E,
+    ) -> Result<model::SbtcSupplyTotals, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query_as::<_, model::SbtcSupplyTotals>(
+            r#"
+            WITH completed_deposits AS (
+                SELECT DISTINCT ON (bitcoin_txid, output_index)
+                    amount
+                FROM sbtc_signer.completed_deposit_events
+                ORDER BY bitcoin_txid, output_index
+            ),
+            accepted_withdrawals AS (
+                SELECT DISTINCT ON (wae.request_id)
+                    wr.amount
+                FROM sbtc_signer.withdrawal_accept_events AS wae
+                JOIN sbtc_signer.withdrawal_requests AS wr
+                  ON wr.request_id = wae.request_id
+                ORDER BY wae.request_id
+            )
+            SELECT
+                (SELECT COALESCE(SUM(amount), 0) FROM completed_deposits)::BIGINT AS minted
+              , (SELECT COALESCE(SUM(amount), 0) FROM accepted_withdrawals)::BIGINT AS burned
+            "#,
+        )
+        .fetch_one(executor)
+        .await
+        .map_err(Error::SqlxQuery)
     }
 
-    #[cfg(any(test, feature = "testing"))]
-    async fn get_bitcoin_canonical_chain_tip_ref(
-        &self,
-    ) -> Result<Option<model::BitcoinBlockRef>, Error> {
-        PgRead::get_bitcoin_canonical_chain_tip_ref(self.get_connection().await?.as_mut()).await
+    async fn get_signer_utxo_violations<'e, E>(
+        executor: &'e mut E,
+        limit: u16,
+    ) -> Result<Vec<model::SignerUtxoViolation>, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query_as::<_, model::SignerUtxoViolation>(
+            r#"
+            SELECT
+                txid
+              , block_hash
+              , signer_input
+              , deposits
+              , withdrawals
+              , fee
+              , signer_output
+              , previous_signer_output
+            FROM sbtc_signer.signer_utxo_violations
+            ORDER BY created_at DESC
+            LIMIT $1
+            "#,
+        )
+        .bind(i32::from(limit))
+        .fetch_all(executor)
+        .await
+        .map_err(Error::SqlxQuery)
     }
 
-    async fn get_stacks_chain_tip(
-        &self,
+    async fn get_bitcoin_tx_body<'e, E>(
+        executor: &'e mut E,
+        txid: &model::BitcoinTxId,
+        block_hash: &model::BitcoinBlockHash,
+    ) -> Result<Option<model::BitcoinTxBody>, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        let row = sqlx::query_as::<_, (model::BitcoinBlockHeight, Vec<u8>, Vec<u8>)>(
+            r#"
+            SELECT block_height, tx, prevouts
+            FROM sbtc_signer.bitcoin_tx_bodies
+            WHERE txid = $1
+              AND block_hash = $2
+            "#,
+        )
+        .bind(txid)
+        .bind(block_hash)
+        .fetch_optional(executor)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        let Some((block_height, tx, prevouts)) = row else {
+            return Ok(None);
+        };
+
+        Ok(Some(model::BitcoinTxBody {
+            txid: *txid,
+            block_hash: *block_hash,
+            block_height,
+            tx: bitcoin::consensus::deserialize(&tx).map_err(Error::DecodeBitcoinTransaction)?,
+            prevouts: bitcoin::consensus::deserialize(&prevouts)
+                .map_err(Error::DecodeBitcoinTransaction)?,
+        }))
+    }
+
+    async fn get_block_activity<'e, E>(
+        executor: &'e mut E,
+        block_hash: &model::BitcoinBlockHash,
+    ) -> Result<model::BlockActivitySummary, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query_as::<_, model::BlockActivitySummary>(
+            r#"
+            WITH sweeps AS (
+                SELECT DISTINCT bt.txid
+                FROM sbtc_signer.bitcoin_transactions AS bt
+                JOIN sbtc_signer.bitcoin_tx_inputs AS bti
+                  ON bti.txid = bt.txid
+                WHERE bt.block_hash = $1
+                  AND bti.prevout_type = 'signers_input'
+            ),
+            inputs AS (
+                SELECT
+                    COUNT(*) FILTER (WHERE bti.prevout_type = 'deposit') AS deposits_swept
+                  , COALESCE(SUM(bti.amount) FILTER (WHERE bti.prevout_type = 'deposit'), 0) AS deposit_amount
+                  , COALESCE(SUM(bti.amount), 0) AS total_in
+                FROM sbtc_signer.bitcoin_tx_inputs AS bti
+                JOIN sweeps USING (txid)
+            ),
+            outputs AS (
+                SELECT
+                    COUNT(*) FILTER (WHERE bto.output_type = 'withdrawal') AS withdrawals_fulfilled
+                  , COALESCE(SUM(bto.amount) FILTER (WHERE bto.output_type = 'withdrawal'), 0) AS withdrawal_amount
+                  , COALESCE(SUM(bto.amount), 0) AS total_out
+                FROM sbtc_signer.bitcoin_tx_outputs AS bto
+                JOIN sweeps USING (txid)
+            )
+            SELECT
+                COALESCE((SELECT ARRAY_AGG(txid ORDER BY txid) FROM sweeps), '{}') AS sweep_txids
+              , inputs.deposits_swept
+              , inputs.deposit_amount::BIGINT AS deposit_amount
+              , outputs.withdrawals_fulfilled
+              , outputs.withdrawal_amount::BIGINT AS withdrawal_amount
+              , GREATEST(inputs.total_in - outputs.total_out, 0)::BIGINT AS fees_paid
+            FROM inputs, outputs
+            "#,
+        )
+        .bind(block_hash)
+        .fetch_one(executor)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
+    async fn is_emily_imported_deposit<'e, E>(
+        executor: &'e mut E,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+    ) -> Result<bool, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS (
+                SELECT 1
+                FROM sbtc_signer.emily_imported_deposits
+                WHERE txid = $1
+                  AND output_index = $2
+            )",
+        )
+        .bind(txid)
+        .bind(i32::try_from(output_index).map_err(Error::ConversionDatabaseInt)?)
+        .fetch_one(executor)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
+    async fn is_emily_imported_withdrawal<'e, E>(
+        executor: &'e mut E,
+        id: &model::QualifiedRequestId,
+    ) -> Result<bool, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS (
+                SELECT 1
+                FROM sbtc_signer.emily_imported_withdrawals
+                WHERE request_id = $1
+                  AND block_hash = $2
+            )",
+        )
+        .bind(i64::try_from(id.request_id).map_err(Error::ConversionDatabaseInt)?)
+        .bind(id.block_hash)
+        .fetch_one(executor)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
+    async fn get_p2p_peer_versions<'e, E>(
+        executor: &'e mut E,
+    ) -> Result<Vec<model::P2PPeerVersion>, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query_as::<_, model::P2PPeerVersion>(
+            r#"
+            SELECT
+                peer_id
+              , agent_version
+              , protocol_version
+              , updated_at
+            FROM sbtc_signer.p2p_peer_versions
+            "#,
+        )
+        .fetch_all(executor)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
+    async fn get_active_peer_bans<'e, E>(
+        executor: &'e mut E,
+    ) -> Result<Vec<model::P2PPeerBan>, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query_as::<_, model::P2PPeerBan>(
+            r#"
+            SELECT
+                peer_id
+              , reason
+              , score
+              , banned_at
+              , expires_at
+            FROM sbtc_signer.p2p_peer_bans
+            WHERE expires_at > NOW()
+            ORDER BY expires_at
+            "#,
+        )
+        .fetch_all(executor)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
+    async fn get_active_dkg_verification_rounds<'e, E>(
+        executor: &'e mut E,
+    ) -> Result<Vec<model::DkgVerificationRound>, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query_as::<_, model::DkgVerificationRound>(
+            r#"
+            SELECT
+                aggregate_key
+              , bitcoin_block_hash
+              , bitcoin_block_height
+              , status
+              , last_message_type
+              , updated_at
+            FROM sbtc_signer.dkg_verification_rounds
+            WHERE status = 'active'
+            ORDER BY bitcoin_block_height ASC
+            "#,
+        )
+        .fetch_all(executor)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
+    async fn get_unresolved_stacks_event_dead_letters<'e, E>(
+        executor: &'e mut E,
+    ) -> Result<Vec<model::StacksEventDeadLetter>, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query_as::<_, model::StacksEventDeadLetter>(
+            r#"
+            SELECT
+                block_hash
+              , txid
+              , event_index
+              , raw_value
+              , error
+              , attempts
+              , created_at
+              , resolved_at
+            FROM sbtc_signer.stacks_event_dead_letters
+            WHERE resolved_at IS NULL
+            ORDER BY created_at ASC
+            "#,
+        )
+        .fetch_all(executor)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
+    async fn get_signed_sweep_transactions<'e, E>(
+        executor: &'e mut E,
+    ) -> Result<Vec<model::SignedSweepTransaction>, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        let rows = sqlx::query_as::<
+            _,
+            (
+                model::BitcoinTxId,
+                Vec<u8>,
+                model::BitcoinBlockHash,
+                model::Timestamp,
+            ),
+        >(
+            r#"
+            SELECT
+                txid
+              , tx
+              , bitcoin_chain_tip
+              , created_at
+            FROM sbtc_signer.signed_sweep_transactions
+            ORDER BY created_at ASC, txid ASC
+            "#,
+        )
+        .fetch_all(executor)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        rows.into_iter()
+            .map(|(txid, tx, bitcoin_chain_tip, created_at)| {
+                Ok(model::SignedSweepTransaction {
+                    txid,
+                    tx: bitcoin::consensus::deserialize(&tx)
+                        .map_err(Error::DecodeBitcoinTransaction)?,
+                    bitcoin_chain_tip,
+                    created_at,
+                })
+            })
+            .collect()
+    }
+
+    async fn get_aggregate_key_history<'e, E>(
+        executor: &'e mut E,
+        chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Vec<model::AggregateKeyPeriod>, Error>
+    where
+        E: 'static,
+        for<'c> &'c mut E: sqlx::PgExecutor<'c>,
+    {
+        let stacks_chain_tip = Self::get_stacks_chain_tip(executor, chain_tip)
+            .await?
+            .map(|block| block.block_hash);
+
+        sqlx::query_as::<_, model::AggregateKeyPeriod>(
+            r#"
+            WITH RECURSIVE stacks_blockchain AS (
+                SELECT
+                    block_hash
+                  , parent_hash
+                  , block_height
+                FROM sbtc_signer.stacks_blocks
+                WHERE block_hash = $2
+
+                UNION ALL
+
+                SELECT
+                    parent.block_hash
+                  , parent.parent_hash
+                  , parent.block_height
+                FROM sbtc_signer.stacks_blocks AS parent
+                JOIN stacks_blockchain AS child
+                  ON parent.block_hash = child.parent_hash
+            ),
+            bitcoin_blockchain AS (
+                SELECT
+                    block_hash
+                  , block_height
+                FROM bitcoin_blockchain_until($1, 0)
+            ),
+            key_starts AS (
+                SELECT
+                    ds.aggregate_key
+                  , MIN(bb.block_height) AS start_height
+                FROM sbtc_signer.bitcoin_tx_outputs AS bo
+                JOIN sbtc_signer.bitcoin_transactions AS bt USING (txid)
+                JOIN bitcoin_blockchain AS bb USING (block_hash)
+                JOIN sbtc_signer.dkg_shares AS ds USING (script_pubkey)
+                WHERE bo.output_type IN ('signers_output', 'donation')
+                GROUP BY ds.aggregate_key
+            )
+            SELECT
+                ks.aggregate_key
+              , ks.start_height
+              , LEAD(ks.start_height) OVER (
+                    ORDER BY ks.start_height, ks.aggregate_key
+                ) AS end_height
+              , (
+                    SELECT rkt.txid
+                    FROM sbtc_signer.rotate_keys_transactions AS rkt
+                    JOIN stacks_blockchain AS sb
+                      ON rkt.block_hash = sb.block_hash
+                    WHERE rkt.aggregate_key = ks.aggregate_key
+                    ORDER BY sb.block_height ASC, rkt.created_at ASC
+                    LIMIT 1
+                ) AS rotate_keys_txid
+            FROM key_starts AS ks
+            ORDER BY ks.start_height ASC, ks.aggregate_key ASC
+            "#,
+        )
+        .bind(chain_tip)
+        .bind(stacks_chain_tip)
+        .fetch_all(executor)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
+    async fn get_request_counts<'e, E>(
+        executor: &'e mut E,
+        chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<model::RequestCounts, Error>
+    where
+        E: 'static,
+        for<'c> &'c mut E: sqlx::PgExecutor<'c>,
+    {
+        let stacks_chain_tip = Self::get_stacks_chain_tip(executor, chain_tip)
+            .await?
+            .map(|block| block.block_hash);
+
+        // The counts for each deposit status followed by the counts for
+        // each withdrawal status.
+        type CountsRow = (i64, i64, i64, i64, i64, i64, i64, i64, i64, i64, i64, i64);
+
+        // A request takes the first status, in the order below, whose
+        // criteria it matches.
+        let row = sqlx::query_as::<_, CountsRow>(
+            r#"
+            WITH RECURSIVE stacks_blockchain AS (
+                SELECT
+                    block_hash
+                  , parent_hash
+                FROM sbtc_signer.stacks_blocks
+                WHERE block_hash = $2
+
+                UNION ALL
+
+                SELECT
+                    parent.block_hash
+                  , parent.parent_hash
+                FROM sbtc_signer.stacks_blocks AS parent
+                JOIN stacks_blockchain AS child
+                  ON parent.block_hash = child.parent_hash
+            ),
+            confirmed_txids AS (
+                SELECT DISTINCT bt.txid
+                FROM bitcoin_blockchain_until($1, 0) AS bb
+                JOIN sbtc_signer.bitcoin_transactions AS bt
+                  ON bt.block_hash = bb.block_hash
+            ),
+            signer_set AS (
+                SELECT
+                    signature_share_threshold AS threshold
+                  , CARDINALITY(signer_set_public_keys) AS num_signers
+                FROM sbtc_signer.dkg_shares
+                WHERE dkg_shares_status = 'verified'
+                ORDER BY created_at DESC
+                LIMIT 1
+            ),
+            deposit_statuses AS (
+                SELECT
+                    CASE
+                        WHEN EXISTS (
+                            SELECT 1
+                            FROM sbtc_signer.completed_deposit_events AS cde
+                            JOIN stacks_blockchain AS sb
+                              ON sb.block_hash = cde.block_hash
+                            WHERE cde.bitcoin_txid = dr.txid
+                              AND cde.output_index = dr.output_index
+                        ) THEN 'completed'
+                        WHEN EXISTS (
+                            SELECT 1
+                            FROM sbtc_signer.bitcoin_tx_inputs AS bti
+                            JOIN confirmed_txids AS ct
+                              ON ct.txid = bti.txid
+                            WHERE bti.prevout_txid = dr.txid
+                              AND bti.prevout_output_index = dr.output_index
+                        ) THEN 'swept_awaiting_stacks'
+                        WHEN EXISTS (
+                            SELECT 1
+                            FROM sbtc_signer.bitcoin_tx_sighashes AS bts
+                            WHERE bts.prevout_txid = dr.txid
+                              AND bts.prevout_output_index = dr.output_index
+                              AND bts.will_sign
+                        ) THEN 'in_flight'
+                        WHEN votes.accepts >= ss.threshold THEN 'accepted'
+                        WHEN votes.rejects > ss.num_signers - ss.threshold THEN 'rejected'
+                        ELSE 'pending'
+                    END AS status
+                FROM sbtc_signer.deposit_requests AS dr
+                JOIN confirmed_txids AS ct
+                  ON ct.txid = dr.txid
+                LEFT JOIN signer_set AS ss
+                  ON TRUE
+                CROSS JOIN LATERAL (
+                    SELECT
+                        COUNT(*) FILTER (WHERE ds.can_accept AND ds.can_sign) AS accepts
+                      , COUNT(*) FILTER (WHERE NOT (ds.can_accept AND ds.can_sign)) AS rejects
+                    FROM sbtc_signer.deposit_signers AS ds
+                    WHERE ds.txid = dr.txid
+                      AND ds.output_index = dr.output_index
+                ) AS votes
+            ),
+            withdrawal_statuses AS (
+                SELECT
+                    CASE
+                        WHEN EXISTS (
+                            SELECT 1
+                            FROM sbtc_signer.withdrawal_accept_events AS wae
+                            JOIN stacks_blockchain AS sb
+                              ON sb.block_hash = wae.block_hash
+                            WHERE wae.request_id = wr.request_id
+                        ) THEN 'completed'
+                        WHEN EXISTS (
+                            SELECT 1
+                            FROM sbtc_signer.withdrawal_reject_events AS wre
+                            JOIN stacks_blockchain AS sb
+                              ON sb.block_hash = wre.block_hash
+                            WHERE wre.request_id = wr.request_id
+                        ) THEN 'rejected'
+                        WHEN EXISTS (
+                            SELECT 1
+                            FROM sbtc_signer.bitcoin_withdrawal_tx_outputs AS bwto
+                            JOIN confirmed_txids AS ct
+                              ON ct.txid = bwto.txid
+                            WHERE bwto.request_id = wr.request_id
+                        ) OR EXISTS (
+                            SELECT 1
+                            FROM sbtc_signer.bitcoin_withdrawals_outputs AS bwo
+                            JOIN confirmed_txids AS ct
+                              ON ct.txid = bwo.bitcoin_txid
+                            WHERE bwo.request_id = wr.request_id
+                              AND bwo.stacks_block_hash = wr.block_hash
+                        ) THEN 'swept_awaiting_stacks'
+                        WHEN EXISTS (
+                            SELECT 1
+                            FROM sbtc_signer.bitcoin_withdrawals_outputs AS bwo
+                            WHERE bwo.request_id = wr.request_id
+                              AND bwo.stacks_block_hash = wr.block_hash
+                              AND bwo.is_valid_tx
+                        ) THEN 'in_flight'
+                        WHEN votes.accepts >= ss.threshold THEN 'accepted'
+                        WHEN votes.rejects > ss.num_signers - ss.threshold THEN 'rejected'
+                        ELSE 'pending'
+                    END AS status
+                FROM sbtc_signer.withdrawal_requests AS wr
+                JOIN stacks_blockchain AS sb
+                  ON sb.block_hash = wr.block_hash
+                LEFT JOIN signer_set AS ss
+                  ON TRUE
+                CROSS JOIN LATERAL (
+                    SELECT
+                        COUNT(*) FILTER (WHERE ws.is_accepted) AS accepts
+                      , COUNT(*) FILTER (WHERE NOT ws.is_accepted) AS rejects
+                    FROM sbtc_signer.withdrawal_signers AS ws
+                    WHERE ws.request_id = wr.request_id
+                      AND ws.block_hash = wr.block_hash
+                ) AS votes
+            )
+            SELECT
+                (SELECT COUNT(*) FROM deposit_statuses WHERE status = 'pending')
+              , (SELECT COUNT(*) FROM deposit_statuses WHERE status = 'accepted')
+              , (SELECT COUNT(*) FROM deposit_statuses WHERE status = 'in_flight')
+              , (SELECT COUNT(*) FROM deposit_statuses WHERE status = 'swept_awaiting_stacks')
+              , (SELECT COUNT(*) FROM deposit_statuses WHERE status = 'completed')
+              , (SELECT COUNT(*) FROM deposit_statuses WHERE status = 'rejected')
+              , (SELECT COUNT(*) FROM withdrawal_statuses WHERE status = 'pending')
+              , (SELECT COUNT(*) FROM withdrawal_statuses WHERE status = 'accepted')
+              , (SELECT COUNT(*) FROM withdrawal_statuses WHERE status = 'in_flight')
+              , (SELECT COUNT(*) FROM withdrawal_statuses WHERE status = 'swept_awaiting_stacks')
+              , (SELECT COUNT(*) FROM withdrawal_statuses WHERE status = 'completed')
+              , (SELECT COUNT(*) FROM withdrawal_statuses WHERE status = 'rejected')
+            "#,
+        )
+        .bind(chain_tip)
+        .bind(stacks_chain_tip)
+        .fetch_one(executor)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        let to_u64 = |count: i64| u64::try_from(count).map_err(Error::ConversionDatabaseInt);
+        Ok(model::RequestCounts {
+            deposits: model::RequestStatusCounts {
+                pending: to_u64(row.0)?,
+                accepted: to_u64(row.1)?,
+                in_flight: to_u64(row.2)?,
+                swept_awaiting_stacks: to_u64(row.3)?,
+                completed: to_u64(row.4)?,
+                rejected: to_u64(row.5)?,
+            },
+            withdrawals: model::RequestStatusCounts {
+                pending: to_u64(row.6)?,
+                accepted: to_u64(row.7)?,
+                in_flight: to_u64(row.8)?,
+                swept_awaiting_stacks: to_u64(row.9)?,
+                completed: to_u64(row.10)?,
+                rejected: to_u64(row.11)?,
+            },
+        })
+    }
+
+    async fn get_sweep_fee_discrepancies<'e, E>(
+        executor: &'e mut E,
+        limit: u16,
+    ) -> Result<Vec<model::SweepFeeDiscrepancy>, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query_as::<_, model::SweepFeeDiscrepancy>(
+            r#"
+            SELECT
+                txid
+              , assessed_fee
+              , actual_fee
+              , assessed_vsize
+              , actual_vsize
+            FROM sbtc_signer.sweep_fee_discrepancies
+            ORDER BY created_at DESC
+            LIMIT $1
+            "#,
+        )
+        .bind(i32::from(limit))
+        .fetch_all(executor)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
+    async fn get_key_rotation_attestations<'e, E>(
+        executor: &'e mut E,
+        aggregate_key: &PublicKey,
+    ) -> Result<Vec<model::KeyRotationAttestation>, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query_as::<_, model::KeyRotationAttestation>(
+            r#"
+            SELECT
+                aggregate_key
+              , signer_public_key
+              , signer_set_hash
+              , signatures_required
+              , signature
+            FROM sbtc_signer.key_rotation_attestations
+            WHERE aggregate_key = $1
+            "#,
+        )
+        .bind(aggregate_key)
+        .fetch_all(executor)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
+    async fn get_caps_utilization<'e, E>(
+        executor: &'e mut E,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+        rolling_withdrawal_cap: u64,
+    ) -> Result<model::CapsUtilization, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        let rows = sqlx::query_as::<_, (i64, i64)>(
+            r#"
+            SELECT
+                bbo.block_height
+              , COALESCE(SUM(bto.amount), 0)::BIGINT
+            FROM bitcoin_blockchain_of($1, $2) AS bbo
+            LEFT JOIN sbtc_signer.bitcoin_transactions AS bt
+              ON bt.block_hash = bbo.block_hash
+            LEFT JOIN sbtc_signer.bitcoin_tx_outputs AS bto
+              ON bto.txid = bt.txid
+             AND bto.output_type = 'withdrawal'
+            GROUP BY bbo.block_height
+            "#,
+        )
+        .bind(bitcoin_chain_tip)
+        .bind(i32::from(context_window))
+        .fetch_all(executor)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        let withdrawals = rows
+            .into_iter()
+            .map(|(height, amount)| {
+                let height =
+                    BitcoinBlockHeight::try_from(height).map_err(Error::ConversionDatabaseInt)?;
+                let amount = u64::try_from(amount).map_err(|_| Error::TypeConversion)?;
+                Ok((height, amount))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        // The chain tip is the highest block in the window, and the window
+        // is empty only if we do not know about the chain tip.
+        let chain_tip_height = withdrawals
+            .iter()
+            .map(|(height, _)| *height)
+            .max()
+            .ok_or(Error::MissingBitcoinBlock(*bitcoin_chain_tip))?;
+
+        Ok(model::CapsUtilization::new(
+            chain_tip_height,
+            context_window,
+            rolling_withdrawal_cap,
+            &withdrawals,
+        ))
+    }
+
+    async fn get_sweep_package<'e, E>(
+        executor: &'e mut E,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Option<model::SweepPackage>, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        let row = sqlx::query_as::<
+            _,
+            (
+                model::BitcoinBlockHash,
+                Vec<u8>,
+                Option<i64>,
+                Option<i64>,
+                Vec<model::BitcoinTxId>,
+            ),
+        >(
+            r#"
+            SELECT
+                bitcoin_chain_tip
+              , presign_request
+              , last_fees_total
+              , last_fees_vsize
+              , broadcast_txids
+            FROM sbtc_signer.sweep_packages
+            WHERE bitcoin_chain_tip = $1
+            "#,
+        )
+        .bind(bitcoin_chain_tip)
+        .fetch_optional(executor)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        let Some((bitcoin_chain_tip, presign_request, total, vsize, broadcast_txids)) = row else {
+            return Ok(None);
+        };
+
+        let last_fees = match (total, vsize) {
+            (Some(total), Some(vsize)) => {
+                let total = u64::try_from(total).map_err(Error::ConversionDatabaseInt)?;
+                let vsize = u64::try_from(vsize).map_err(Error::ConversionDatabaseInt)?;
+                Some(Fees::new(total, vsize)?)
+            }
+            _ => None,
+        };
+
+        Ok(Some(model::SweepPackage {
+            bitcoin_chain_tip,
+            presign_request: BitcoinPreSignRequest::decode(presign_request.as_slice())?,
+            last_fees,
+            broadcast_txids,
+        }))
+    }
+
+    async fn get_presigned_withdrawals<'e, E>(
+        executor: &'e mut E,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Vec<model::WithdrawalRequest>, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query_as::<_, model::WithdrawalRequest>(
+            r#"
+            SELECT
+                wr.request_id
+              , wr.txid
+              , wr.block_hash
+              , wr.recipient
+              , wr.amount
+              , wr.max_fee
+              , wr.sender_address
+              , wr.bitcoin_block_height
+            FROM sbtc_signer.withdrawal_requests AS wr
+            WHERE EXISTS (
+                SELECT TRUE
+                FROM sbtc_signer.bitcoin_withdrawals_outputs AS bwo
+                WHERE bwo.request_id = wr.request_id
+                  AND bwo.stacks_block_hash = wr.block_hash
+                  AND bwo.bitcoin_chain_tip = $1
+                  AND bwo.is_valid_tx
+            )
+            ORDER BY wr.request_id, wr.block_hash
+            "#,
+        )
+        .bind(bitcoin_chain_tip)
+        .fetch_all(executor)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
+    async fn get_deposit_request_timestamps<'e, E>(
+        executor: &'e mut E,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+    ) -> Result<Option<model::RequestTimestamps>, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query_as::<_, model::RequestTimestamps>(
+            r#"
+            SELECT
+                first_seen_block
+              , created_at
+              , decided_at
+              , swept_at
+              , completed_at
+            FROM sbtc_signer.deposit_request_timestamps
+            WHERE txid = $1
+              AND output_index = $2
+            "#,
+        )
+        .bind(txid)
+        .bind(i32::try_from(output_index).map_err(Error::ConversionDatabaseInt)?)
+        .fetch_optional(executor)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
+    async fn get_withdrawal_request_timestamps<'e, E>(
+        executor: &'e mut E,
+        request_id: u64,
+    ) -> Result<Option<model::RequestTimestamps>, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query_as::<_, model::RequestTimestamps>(
+            r#"
+            SELECT
+                first_seen_block
+              , created_at
+              , decided_at
+              , swept_at
+              , completed_at
+            FROM sbtc_signer.withdrawal_request_timestamps
+            WHERE request_id = $1
+            "#,
+        )
+        .bind(i64::try_from(request_id).map_err(Error::ConversionDatabaseInt)?)
+        .fetch_optional(executor)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
+    async fn get_deposit_signer_decisions_in_range<'e, E>(
+        executor: &'e mut E,
+        signer_public_key: &PublicKey,
+        start_height: model::BitcoinBlockHeight,
+        end_height: model::BitcoinBlockHeight,
+        limit: u16,
+        offset: u32,
+    ) -> Result<Vec<model::DepositSigner>, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query_as::<_, model::DepositSigner>(
+            r#"
+            SELECT
+                ds.txid
+              , ds.output_index
+              , ds.signer_pub_key
+              , ds.can_sign
+              , ds.can_accept
+            FROM sbtc_signer.deposit_signers ds
+            WHERE ds.signer_pub_key = $1
+              AND EXISTS (
+                SELECT 1
+                FROM sbtc_signer.bitcoin_transactions bt
+                JOIN sbtc_signer.bitcoin_blocks bb USING (block_hash)
+                WHERE bt.txid = ds.txid
+                  AND bb.block_height BETWEEN $2 AND $3
+              )
+            ORDER BY ds.txid, ds.output_index
+            LIMIT $4
+            OFFSET $5
+            "#,
+        )
+        .bind(signer_public_key)
+        .bind(i64::try_from(start_height).map_err(Error::ConversionDatabaseInt)?)
+        .bind(i64::try_from(end_height).map_err(Error::ConversionDatabaseInt)?)
+        .bind(i32::from(limit))
+        .bind(i64::from(offset))
+        .fetch_all(executor)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
+    async fn get_withdrawal_signer_decisions_in_range<'e, E>(
+        executor: &'e mut E,
+        signer_public_key: &PublicKey,
+        start_height: model::BitcoinBlockHeight,
+        end_height: model::BitcoinBlockHeight,
+        limit: u16,
+        offset: u32,
+    ) -> Result<Vec<model::WithdrawalSigner>, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query_as::<_, model::WithdrawalSigner>(
+            r#"
+            SELECT
+                ws.request_id
+              , ws.txid
+              , ws.block_hash
+              , ws.signer_pub_key
+              , ws.is_accepted
+            FROM sbtc_signer.withdrawal_signers ws
+            JOIN sbtc_signer.withdrawal_requests wr USING (request_id, block_hash)
+            WHERE ws.signer_pub_key = $1
+              AND wr.bitcoin_block_height BETWEEN $2 AND $3
+            ORDER BY ws.request_id, ws.block_hash
+            LIMIT $4
+            OFFSET $5
+            "#,
+        )
+        .bind(signer_public_key)
+        .bind(i64::try_from(start_height).map_err(Error::ConversionDatabaseInt)?)
+        .bind(i64::try_from(end_height).map_err(Error::ConversionDatabaseInt)?)
+        .bind(i32::from(limit))
+        .bind(i64::from(offset))
+        .fetch_all(executor)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
+    async fn get_bitcoin_backfill_progress<'e, E>(
+        executor: &'e mut E,
+    ) -> Result<Option<model::BitcoinBackfillProgress>, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query_as::<_, model::BitcoinBackfillProgress>(
+            r#"
+            SELECT
+                target_block_hash
+              , target_block_height
+              , start_height
+              , processed_height
+              , started_at
+              , updated_at
+            FROM sbtc_signer.bitcoin_backfill_progress
+            ORDER BY updated_at DESC
+            LIMIT 1
+            "#,
+        )
+        .fetch_optional(executor)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
+    async fn get_coordinator_takeover<'e, E>(
+        executor: &'e mut E,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Option<model::CoordinatorTakeover>, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query_as::<_, model::CoordinatorTakeover>(
+            r#"
+            SELECT
+                bitcoin_chain_tip
+              , coordinator_public_key
+              , deputy_public_key
+            FROM sbtc_signer.coordinator_takeovers
+            WHERE bitcoin_chain_tip = $1
+            "#,
+        )
+        .bind(bitcoin_chain_tip)
+        .fetch_optional(executor)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
+    async fn get_active_peer_address_records<'e, E>(
+        executor: &'e mut E,
+    ) -> Result<Vec<model::P2PPeerAddressRecord>, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query_as::<_, model::P2PPeerAddressRecord>(
+            r#"
+            SELECT
+                public_key
+              , peer_id
+              , addresses
+              , expires_at
+            FROM sbtc_signer.p2p_peer_address_records
+            WHERE expires_at > NOW()
+            ORDER BY expires_at DESC
+            "#,
+        )
+        .fetch_all(executor)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
+    async fn get_active_request_override<'e, E>(
+        executor: &'e mut E,
+        target: &model::OverrideTarget,
+        column_key: Option<&ColumnEncryptionKey>,
+    ) -> Result<Option<model::RequestOverride>, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        let (deposit_txid, deposit_output_index, withdrawal_request_id) =
+            override_target_columns(target)?;
+
+        let request_override = sqlx::query_as::<_, model::RequestOverride>(
+            r#"
+            SELECT
+                id
+              , deposit_txid
+              , deposit_output_index
+              , withdrawal_request_id
+              , directive
+              , reason
+              , expires_at
+              , created_at
+              , revoked_at
+            FROM sbtc_signer.request_overrides
+            WHERE revoked_at IS NULL
+              AND (expires_at IS NULL OR expires_at > NOW())
+              AND (
+                (deposit_txid = $1 AND deposit_output_index = $2)
+                OR withdrawal_request_id = $3
+              )
+            ORDER BY created_at DESC, id DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(deposit_txid)
+        .bind(deposit_output_index)
+        .bind(withdrawal_request_id)
+        .fetch_optional(executor)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        request_override
+            .map(|request_override| decrypt_override(column_key, request_override))
+            .transpose()
+    }
+
+    async fn get_request_overrides<'e, E>(
+        executor: &'e mut E,
+        column_key: Option<&ColumnEncryptionKey>,
+    ) -> Result<Vec<model::RequestOverride>, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        let request_overrides = sqlx::query_as::<_, model::RequestOverride>(
+            r#"
+            SELECT
+                id
+              , deposit_txid
+              , deposit_output_index
+              , withdrawal_request_id
+              , directive
+              , reason
+              , expires_at
+              , created_at
+              , revoked_at
+            FROM sbtc_signer.request_overrides
+            ORDER BY created_at DESC, id DESC
+            "#,
+        )
+        .fetch_all(executor)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        request_overrides
+            .into_iter()
+            .map(|request_override| decrypt_override(column_key, request_override))
+            .collect()
+    }
+
+    async fn get_inflight_contract_call<'e, E>(
+        executor: &'e mut E,
+        target: &model::ContractCallTarget,
+    ) -> Result<Option<model::InflightContractCall>, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        let columns = ContractCallTargetColumns::new(target)?;
+
+        sqlx::query_as::<_, model::InflightContractCall>(
+            r#"
+            SELECT
+                deposit_txid
+              , deposit_output_index
+              , withdrawal_request_id
+              , withdrawal_txid
+              , withdrawal_block_hash
+              , txid
+              , bitcoin_block_height
+              , bitcoin_block_hash
+            FROM sbtc_signer.inflight_contract_calls
+            WHERE (deposit_txid = $1 AND deposit_output_index = $2)
+               OR (withdrawal_request_id = $3
+                   AND withdrawal_txid = $4
+                   AND withdrawal_block_hash = $5)
+            "#,
+        )
+        .bind(columns.deposit_txid)
+        .bind(columns.deposit_output_index)
+        .bind(columns.withdrawal_request_id)
+        .bind(columns.withdrawal_txid)
+        .bind(columns.withdrawal_block_hash)
+        .fetch_optional(executor)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
+    async fn get_chain_history<'e, E>(
+        executor: &'e mut E,
+        chain_tip: &model::BitcoinBlockHash,
+        depth: u16,
+        start_height: model::BitcoinBlockHeight,
+    ) -> Result<model::ChainHistory, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        let (available_blocks, oldest_block_height) =
+            sqlx::query_as::<_, (i64, Option<model::BitcoinBlockHeight>)>(
+                r#"
+                WITH RECURSIVE history AS (
+                    SELECT block_hash, block_height, parent_hash, 1 AS depth
+                    FROM sbtc_signer.bitcoin_blocks
+                    WHERE block_hash = $1
+
+                    UNION ALL
+
+                    SELECT parent.block_hash, parent.block_height, parent.parent_hash,
+                           last.depth + 1
+                    FROM sbtc_signer.bitcoin_blocks parent
+                    JOIN history last ON parent.block_hash = last.parent_hash
+                    WHERE last.depth < $2
+                )
+                SELECT COUNT(*), MIN(block_height)
+                FROM history
+                "#,
+            )
+            .bind(chain_tip)
+            .bind(i32::from(depth))
+            .fetch_one(executor)
+            .await
+            .map_err(Error::SqlxQuery)?;
+
+        let available_blocks =
+            u16::try_from(available_blocks).map_err(|_| Error::TypeConversion)?;
+
+        Ok(model::ChainHistory::new(
+            depth,
+            available_blocks,
+            oldest_block_height,
+            start_height,
+        ))
+    }
+
+    async fn get_completed_deposit_event<'e, E>(
+        executor: &'e mut E,
+        outpoint: &OutPoint,
+    ) -> Result<Option<model::CompletedDepositEvent>, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        let row = sqlx::query_as::<
+            _,
+            (
+                model::StacksTxId,
+                model::StacksBlockHash,
+                i64,
+                model::BitcoinBlockHash,
+                model::BitcoinBlockHeight,
+                model::BitcoinTxId,
+            ),
+        >(
+            r#"
+            SELECT txid
+                 , block_hash
+                 , amount
+                 , sweep_block_hash
+                 , sweep_block_height
+                 , sweep_txid
+            FROM sbtc_signer.completed_deposit_events
+            WHERE bitcoin_txid = $1
+              AND output_index = $2
+            ORDER BY id DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(model::BitcoinTxId::from(outpoint.txid))
+        .bind(i64::from(outpoint.vout))
+        .fetch_optional(executor)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        let Some((txid, block_id, amount, sweep_block_hash, sweep_block_height, sweep_txid)) = row
+        else {
+            return Ok(None);
+        };
+
+        Ok(Some(model::CompletedDepositEvent {
+            txid,
+            block_id,
+            amount: u64::try_from(amount).map_err(Error::ConversionDatabaseInt)?,
+            outpoint: *outpoint,
+            sweep_block_hash,
+            sweep_block_height,
+            sweep_txid,
+        }))
+    }
+
+    async fn get_sweep_tx_merkle_proof<'e, E>(
+        executor: &'e mut E,
+        chain_tip: &model::BitcoinBlockHash,
+        txid: &model::BitcoinTxId,
+    ) -> Result<Option<model::BitcoinTxMerkleProof>, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        let row =
+            sqlx::query_as::<_, (model::BitcoinBlockHash, model::BitcoinBlockHeight, Vec<u8>)>(
+                r#"
+            WITH RECURSIVE proofs AS (
+                SELECT DISTINCT
+                    proofs.block_hash
+                  , proofs.block_height
+                  , proofs.merkle_block
+                FROM sbtc_signer.bitcoin_tx_merkle_proofs AS proofs
+                JOIN sbtc_signer.bitcoin_tx_inputs AS bti
+                  ON bti.txid = proofs.txid
+                WHERE proofs.txid = $2
+                  AND bti.prevout_type = 'signers_input'
+            ),
+            canonical_chain AS (
+                SELECT
+                    block_hash
+                  , block_height
+                  , parent_hash
+                FROM sbtc_signer.bitcoin_blocks
+                WHERE block_hash = $1
+
+                UNION ALL
+
+                SELECT
+                    parent.block_hash
+                  , parent.block_height
+                  , parent.parent_hash
+                FROM sbtc_signer.bitcoin_blocks AS parent
+                JOIN canonical_chain AS child
+                  ON parent.block_hash = child.parent_hash
+                WHERE child.block_height > (SELECT MIN(block_height) FROM proofs)
+            )
+            SELECT
+                proofs.block_hash
+              , proofs.block_height
+              , proofs.merkle_block
+            FROM proofs
+            JOIN canonical_chain AS cc
+              ON cc.block_hash = proofs.block_hash
+            LIMIT 1
+            "#,
+            )
+            .bind(chain_tip)
+            .bind(txid)
+            .fetch_optional(executor)
+            .await
+            .map_err(Error::SqlxQuery)?;
+
+        let Some((block_hash, block_height, merkle_block)) = row else {
+            return Ok(None);
+        };
+
+        Ok(Some(model::BitcoinTxMerkleProof {
+            txid: *txid,
+            block_hash,
+            block_height,
+            merkle_block: bitcoin::consensus::deserialize(&merkle_block)
+                .map_err(Error::DecodeBitcoinMerkleBlock)?,
+        }))
+    }
+
+    async fn get_bitcoin_block_headers<'e, E>(
+        executor: &'e mut E,
+        chain_tip: &model::BitcoinBlockHash,
+        start_height: model::BitcoinBlockHeight,
+        limit: u16,
+    ) -> Result<Vec<bitcoin::block::Header>, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query_scalar::<_, Vec<u8>>(
+            r#"
+            WITH RECURSIVE canonical_chain AS (
+                SELECT
+                    block_hash
+                  , block_height
+                  , parent_hash
+                FROM sbtc_signer.bitcoin_blocks
+                WHERE block_hash = $1
+
+                UNION ALL
+
+                SELECT
+                    parent.block_hash
+                  , parent.block_height
+                  , parent.parent_hash
+                FROM sbtc_signer.bitcoin_blocks AS parent
+                JOIN canonical_chain AS child
+                  ON parent.block_hash = child.parent_hash
+                WHERE child.block_height > $2
+            )
+            SELECT headers.header
+            FROM canonical_chain AS cc
+            JOIN sbtc_signer.bitcoin_block_headers AS headers
+              ON headers.block_hash = cc.block_hash
+            WHERE cc.block_height >= $2
+            ORDER BY cc.block_height ASC
+            LIMIT $3
+            "#,
+        )
+        .bind(chain_tip)
+        .bind(start_height)
+        .bind(i32::from(limit))
+        .fetch_all(executor)
+        .await
+        .map_err(Error::SqlxQuery)?
+        .iter()
+        .map(|header| bitcoin::consensus::deserialize(header))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(Error::DecodeBitcoinBlockHeader)
+    }
+}
+
+impl DbRead for PgStore {
+    async fn get_bitcoin_block(
+        &self,
+        block_hash: &model::BitcoinBlockHash,
+    ) -> Result<Option<model::BitcoinBlock>, Error> {
+        PgRead::get_bitcoin_block(self.get_connection().await?.as_mut(), block_hash).await
+    }
+
+    async fn get_stacks_block(
+        &self,
+        block_hash: &model::StacksBlockHash,
+    ) -> Result<Option<model::StacksBlock>, Error> {
+        PgRead::get_stacks_block(self.get_connection().await?.as_mut(), block_hash).await
+    }
+
+    #[cfg(any(test, feature = "testing"))]
+    async fn get_bitcoin_canonical_chain_tip(
+        &self,
+    ) -> Result<Option<model::BitcoinBlockHash>, Error> {
+        PgRead::get_bitcoin_canonical_chain_tip(self.get_connection().await?.as_mut()).await
+    }
+
+    #[cfg(any(test, feature = "testing"))]
+    async fn get_bitcoin_canonical_chain_tip_ref(
+        &self,
+    ) -> Result<Option<model::BitcoinBlockRef>, Error> {
+        PgRead::get_bitcoin_canonical_chain_tip_ref(self.get_connection().await?.as_mut()).await
+    }
+
+    async fn get_stacks_chain_tip(
+        &self,
         bitcoin_chain_tip: &model::BitcoinBlockHash,
     ) -> Result<Option<model::StacksBlock>, Error> {
         PgRead::get_stacks_chain_tip(self.get_connection().await?.as_mut(), bitcoin_chain_tip).await
@@ -2635,17 +4307,32 @@ impl DbRead for PgStore {
         .await
     }
 
+    async fn get_recent_deposit_requests(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+    ) -> Result<Vec<model::DepositRequest>, Error> {
+        PgRead::get_recent_deposit_requests(
+            self.get_connection().await?.as_mut(),
+            chain_tip,
+            context_window,
+        )
+        .await
+    }
+
     async fn get_pending_accepted_deposit_requests(
         &self,
         chain_tip: &model::BitcoinBlockRef,
         context_window: u16,
         threshold: u16,
+        confirmation_policy: &DepositConfirmationPolicy,
     ) -> Result<Vec<model::DepositRequest>, Error> {
         PgRead::get_pending_accepted_deposit_requests(
             self.get_connection().await?.as_mut(),
             chain_tip,
             context_window,
             threshold,
+            confirmation_policy,
         )
         .await
     }
@@ -2678,6 +4365,14 @@ impl DbRead for PgStore {
         .await
     }
 
+    async fn get_withdrawal_requests_by_id(
+        &self,
+        request_id: u64,
+    ) -> Result<Vec<model::WithdrawalRequest>, Error> {
+        PgRead::get_withdrawal_requests_by_id(self.get_connection().await?.as_mut(), request_id)
+            .await
+    }
+
     async fn get_deposit_request_report(
         &self,
         chain_tip: &model::BitcoinBlockHash,
@@ -2736,6 +4431,7 @@ impl DbRead for PgStore {
             self.get_connection().await?.as_mut(),
             request_id,
             block_hash,
+            self.column_key(),
         )
         .await
     }
@@ -2899,6 +4595,13 @@ impl DbRead for PgStore {
         PgRead::get_signer_utxo(self.get_connection().await?.as_mut(), chain_tip).await
     }
 
+    async fn get_signer_utxos(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Vec<SignerUtxo>, Error> {
+        PgRead::get_signer_utxos(self.get_connection().await?.as_mut(), chain_tip).await
+    }
+
     async fn is_known_bitcoin_block_hash(
         &self,
         block_hash: &model::BitcoinBlockHash,
@@ -2964,66 +4667,379 @@ impl DbRead for PgStore {
 
     async fn get_swept_withdrawal_requests(
         &self,
-        bitcoin_chain_tip: &BitcoinBlockHash,
-        stacks_chain_tip: &StacksBlockHash,
-        context_window: u16,
-    ) -> Result<Vec<model::SweptWithdrawalRequest>, Error> {
-        PgRead::get_swept_withdrawal_requests(
+        bitcoin_chain_tip: &BitcoinBlockHash,
+        stacks_chain_tip: &StacksBlockHash,
+        context_window: u16,
+    ) -> Result<Vec<model::SweptWithdrawalRequest>, Error> {
+        PgRead::get_swept_withdrawal_requests(
+            self.get_connection().await?.as_mut(),
+            bitcoin_chain_tip,
+            stacks_chain_tip,
+            context_window,
+        )
+        .await
+    }
+
+    async fn get_deposit_request(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+    ) -> Result<Option<model::DepositRequest>, Error> {
+        PgRead::get_deposit_request(self.get_connection().await?.as_mut(), txid, output_index).await
+    }
+
+    async fn will_sign_bitcoin_tx_sighash(
+        &self,
+        sighash: &model::SigHash,
+    ) -> Result<Option<(bool, PublicKeyXOnly)>, Error> {
+        PgRead::will_sign_bitcoin_tx_sighash(self.get_connection().await?.as_mut(), sighash).await
+    }
+
+    async fn get_withdrawal_signer_decisions(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+        signer_public_key: &PublicKey,
+    ) -> Result<Vec<model::WithdrawalSigner>, Error> {
+        PgRead::get_withdrawal_signer_decisions(
+            self.get_connection().await?.as_mut(),
+            chain_tip,
+            context_window,
+            signer_public_key,
+        )
+        .await
+    }
+
+    async fn get_deposit_signer_decisions(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+        signer_public_key: &PublicKey,
+    ) -> Result<Vec<model::DepositSigner>, Error> {
+        PgRead::get_deposit_signer_decisions(
+            self.get_connection().await?.as_mut(),
+            chain_tip,
+            context_window,
+            signer_public_key,
+        )
+        .await
+    }
+
+    async fn get_p2p_peers(&self) -> Result<Vec<model::P2PPeer>, Error> {
+        PgRead::get_p2p_peers(self.get_connection().await?.as_mut()).await
+    }
+
+    async fn get_sbtc_supply_totals(&self) -> Result<model::SbtcSupplyTotals, Error> {
+        PgRead::get_sbtc_supply_totals(self.get_connection().await?.as_mut()).await
+    }
+
+    async fn get_signer_utxo_violations(
+        &self,
+        limit: u16,
+    ) -> Result<Vec<model::SignerUtxoViolation>, Error> {
+        PgRead::get_signer_utxo_violations(self.get_connection().await?.as_mut(), limit).await
+    }
+
+    async fn get_bitcoin_tx_body(
+        &self,
+        txid: &model::BitcoinTxId,
+        block_hash: &model::BitcoinBlockHash,
+    ) -> Result<Option<model::BitcoinTxBody>, Error> {
+        PgRead::get_bitcoin_tx_body(self.get_connection().await?.as_mut(), txid, block_hash).await
+    }
+
+    async fn get_block_activity(
+        &self,
+        block_hash: &model::BitcoinBlockHash,
+    ) -> Result<model::BlockActivitySummary, Error> {
+        PgRead::get_block_activity(self.get_connection().await?.as_mut(), block_hash).await
+    }
+
+    async fn is_emily_imported_deposit(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+    ) -> Result<bool, Error> {
+        PgRead::is_emily_imported_deposit(self.get_connection().await?.as_mut(), txid, output_index)
+            .await
+    }
+
+    async fn is_emily_imported_withdrawal(
+        &self,
+        id: &model::QualifiedRequestId,
+    ) -> Result<bool, Error> {
+        PgRead::is_emily_imported_withdrawal(self.get_connection().await?.as_mut(), id).await
+    }
+
+    async fn get_p2p_peer_versions(&self) -> Result<Vec<model::P2PPeerVersion>, Error> {
+        PgRead::get_p2p_peer_versions(self.get_connection().await?.as_mut()).await
+    }
+
+    async fn get_active_peer_bans(&self) -> Result<Vec<model::P2PPeerBan>, Error> {
+        PgRead::get_active_peer_bans(self.get_connection().await?.as_mut()).await
+    }
+
+    async fn get_active_dkg_verification_rounds(
+        &self,
+    ) -> Result<Vec<model::DkgVerificationRound>, Error> {
+        PgRead::get_active_dkg_verification_rounds(self.get_connection().await?.as_mut()).await
+    }
+
+    async fn get_unresolved_stacks_event_dead_letters(
+        &self,
+    ) -> Result<Vec<model::StacksEventDeadLetter>, Error> {
+        PgRead::get_unresolved_stacks_event_dead_letters(self.get_connection().await?.as_mut())
+            .await
+    }
+
+    async fn get_signed_sweep_transactions(
+        &self,
+    ) -> Result<Vec<model::SignedSweepTransaction>, Error> {
+        PgRead::get_signed_sweep_transactions(self.get_connection().await?.as_mut()).await
+    }
+
+    async fn get_aggregate_key_history(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Vec<model::AggregateKeyPeriod>, Error> {
+        PgRead::get_aggregate_key_history(self.get_connection().await?.as_mut(), chain_tip).await
+    }
+
+    async fn get_request_counts(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<model::RequestCounts, Error> {
+        PgRead::get_request_counts(self.get_connection().await?.as_mut(), chain_tip).await
+    }
+
+    async fn get_sweep_fee_discrepancies(
+        &self,
+        limit: u16,
+    ) -> Result<Vec<model::SweepFeeDiscrepancy>, Error> {
+        PgRead::get_sweep_fee_discrepancies(self.get_connection().await?.as_mut(), limit).await
+    }
+
+    async fn get_key_rotation_attestations(
+        &self,
+        aggregate_key: &PublicKey,
+    ) -> Result<Vec<model::KeyRotationAttestation>, Error> {
+        PgRead::get_key_rotation_attestations(self.get_connection().await?.as_mut(), aggregate_key)
+            .await
+    }
+
+    async fn get_caps_utilization(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+        rolling_withdrawal_cap: u64,
+    ) -> Result<model::CapsUtilization, Error> {
+        PgRead::get_caps_utilization(
+            self.get_connection().await?.as_mut(),
+            bitcoin_chain_tip,
+            context_window,
+            rolling_withdrawal_cap,
+        )
+        .await
+    }
+
+    async fn get_sweep_package(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Option<model::SweepPackage>, Error> {
+        PgRead::get_sweep_package(self.get_connection().await?.as_mut(), bitcoin_chain_tip).await
+    }
+
+    async fn get_presigned_withdrawals(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Vec<model::WithdrawalRequest>, Error> {
+        let mut conn = self.get_connection().await?;
+        PgRead::get_presigned_withdrawals(conn.as_mut(), bitcoin_chain_tip).await
+    }
+
+    async fn get_deposit_request_timestamps(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+    ) -> Result<Option<model::RequestTimestamps>, Error> {
+        PgRead::get_deposit_request_timestamps(
+            self.get_connection().await?.as_mut(),
+            txid,
+            output_index,
+        )
+        .await
+    }
+
+    async fn get_withdrawal_request_timestamps(
+        &self,
+        request_id: u64,
+    ) -> Result<Option<model::RequestTimestamps>, Error> {
+        PgRead::get_withdrawal_request_timestamps(self.get_connection().await?.as_mut(), request_id)
+            .await
+    }
+
+    async fn get_deposit_signer_decisions_in_range(
+        &self,
+        signer_public_key: &PublicKey,
+        start_height: model::BitcoinBlockHeight,
+        end_height: model::BitcoinBlockHeight,
+        limit: u16,
+        offset: u32,
+    ) -> Result<Vec<model::DepositSigner>, Error> {
+        PgRead::get_deposit_signer_decisions_in_range(
+            self.get_connection().await?.as_mut(),
+            signer_public_key,
+            start_height,
+            end_height,
+            limit,
+            offset,
+        )
+        .await
+    }
+
+    async fn get_withdrawal_signer_decisions_in_range(
+        &self,
+        signer_public_key: &PublicKey,
+        start_height: model::BitcoinBlockHeight,
+        end_height: model::BitcoinBlockHeight,
+        limit: u16,
+        offset: u32,
+    ) -> Result<Vec<model::WithdrawalSigner>, Error> {
+        PgRead::get_withdrawal_signer_decisions_in_range(
+            self.get_connection().await?.as_mut(),
+            signer_public_key,
+            start_height,
+            end_height,
+            limit,
+            offset,
+        )
+        .await
+    }
+
+    async fn get_bitcoin_backfill_progress(
+        &self,
+    ) -> Result<Option<model::BitcoinBackfillProgress>, Error> {
+        PgRead::get_bitcoin_backfill_progress(self.get_connection().await?.as_mut()).await
+    }
+
+    async fn get_coordinator_takeover(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Option<model::CoordinatorTakeover>, Error> {
+        PgRead::get_coordinator_takeover(self.get_connection().await?.as_mut(), bitcoin_chain_tip)
+            .await
+    }
+
+    async fn get_active_peer_address_records(
+        &self,
+    ) -> Result<Vec<model::P2PPeerAddressRecord>, Error> {
+        PgRead::get_active_peer_address_records(self.get_connection().await?.as_mut()).await
+    }
+
+    async fn get_active_request_override(
+        &self,
+        target: &model::OverrideTarget,
+    ) -> Result<Option<model::RequestOverride>, Error> {
+        PgRead::get_active_request_override(
+            self.get_connection().await?.as_mut(),
+            target,
+            self.column_key(),
+        )
+        .await
+    }
+
+    async fn get_request_overrides(&self) -> Result<Vec<model::RequestOverride>, Error> {
+        PgRead::get_request_overrides(self.get_connection().await?.as_mut(), self.column_key())
+            .await
+    }
+
+    async fn get_inflight_contract_call(
+        &self,
+        target: &model::ContractCallTarget,
+    ) -> Result<Option<model::InflightContractCall>, Error> {
+        PgRead::get_inflight_contract_call(self.get_connection().await?.as_mut(), target).await
+    }
+
+    async fn get_chain_history(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        depth: u16,
+        start_height: model::BitcoinBlockHeight,
+    ) -> Result<model::ChainHistory, Error> {
+        PgRead::get_chain_history(
             self.get_connection().await?.as_mut(),
-            bitcoin_chain_tip,
-            stacks_chain_tip,
-            context_window,
+            chain_tip,
+            depth,
+            start_height,
         )
         .await
     }
 
-    async fn get_deposit_request(
+    async fn get_completed_deposit_event(
         &self,
-        txid: &model::BitcoinTxId,
-        output_index: u32,
-    ) -> Result<Option<model::DepositRequest>, Error> {
-        PgRead::get_deposit_request(self.get_connection().await?.as_mut(), txid, output_index).await
+        outpoint: &OutPoint,
+    ) -> Result<Option<model::CompletedDepositEvent>, Error> {
+        PgRead::get_completed_deposit_event(self.get_connection().await?.as_mut(), outpoint).await
     }
 
-    async fn will_sign_bitcoin_tx_sighash(
+    async fn get_sweep_tx_merkle_proof(
         &self,
-        sighash: &model::SigHash,
-    ) -> Result<Option<(bool, PublicKeyXOnly)>, Error> {
-        PgRead::will_sign_bitcoin_tx_sighash(self.get_connection().await?.as_mut(), sighash).await
+        chain_tip: &model::BitcoinBlockHash,
+        txid: &model::BitcoinTxId,
+    ) -> Result<Option<model::BitcoinTxMerkleProof>, Error> {
+        PgRead::get_sweep_tx_merkle_proof(self.get_connection().await?.as_mut(), chain_tip, txid)
+            .await
     }
 
-    async fn get_withdrawal_signer_decisions(
+    async fn get_bitcoin_block_headers(
         &self,
         chain_tip: &model::BitcoinBlockHash,
-        context_window: u16,
-        signer_public_key: &PublicKey,
-    ) -> Result<Vec<model::WithdrawalSigner>, Error> {
-        PgRead::get_withdrawal_signer_decisions(
+        start_height: model::BitcoinBlockHeight,
+        limit: u16,
+    ) -> Result<Vec<bitcoin::block::Header>, Error> {
+        PgRead::get_bitcoin_block_headers(
             self.get_connection().await?.as_mut(),
             chain_tip,
-            context_window,
-            signer_public_key,
+            start_height,
+            limit,
         )
         .await
     }
 
-    async fn get_deposit_signer_decisions(
+    async fn get_stacks_chain_tip_v2(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Option<model::StacksChainTip>, Error> {
+        PgRead::get_stacks_chain_tip_v2(self.get_connection().await?.as_mut(), bitcoin_chain_tip)
+            .await
+    }
+
+    async fn get_unscanned_stacks_blocks(
         &self,
         chain_tip: &model::BitcoinBlockHash,
         context_window: u16,
-        signer_public_key: &PublicKey,
-    ) -> Result<Vec<model::DepositSigner>, Error> {
-        PgRead::get_deposit_signer_decisions(
+    ) -> Result<Vec<model::StacksBlockHash>, Error> {
+        PgRead::get_unscanned_stacks_blocks(
             self.get_connection().await?.as_mut(),
             chain_tip,
             context_window,
-            signer_public_key,
         )
         .await
     }
 
-    async fn get_p2p_peers(&self) -> Result<Vec<model::P2PPeer>, Error> {
-        PgRead::get_p2p_peers(self.get_connection().await?.as_mut()).await
+    async fn get_multisig_stacks_transactions(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        stacks_chain_tip: &model::StacksBlockHash,
+        context_window: u16,
+    ) -> Result<Vec<model::MultisigStacksTransaction>, Error> {
+        PgRead::get_multisig_stacks_transactions(
+            self.get_connection().await?.as_mut(),
+            bitcoin_chain_tip,
+            stacks_chain_tip,
+            context_window,
+        )
+        .await
     }
 }
 
@@ -3083,17 +5099,28 @@ impl DbRead for PgTransaction<'_> {
         .await
     }
 
+    async fn get_recent_deposit_requests(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+    ) -> Result<Vec<model::DepositRequest>, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_recent_deposit_requests(tx.as_mut(), chain_tip, context_window).await
+    }
+
     async fn get_pending_accepted_deposit_requests(
         &self,
         chain_tip: &model::BitcoinBlockRef,
         context_window: u16,
         signatures_required: u16,
+        confirmation_policy: &DepositConfirmationPolicy,
     ) -> Result<Vec<model::DepositRequest>, Error> {
         PgRead::get_pending_accepted_deposit_requests(
             self.tx.lock().await.as_mut(),
             chain_tip,
             context_window,
             signatures_required,
+            confirmation_policy,
         )
         .await
     }
@@ -3179,7 +5206,7 @@ impl DbRead for PgTransaction<'_> {
         block_hash: &model::StacksBlockHash,
     ) -> Result<Vec<model::WithdrawalSigner>, Error> {
         let mut tx = self.tx.lock().await;
-        PgRead::get_withdrawal_signers(tx.as_mut(), request_id, block_hash).await
+        PgRead::get_withdrawal_signers(tx.as_mut(), request_id, block_hash, self.column_key()).await
     }
 
     async fn get_pending_withdrawal_requests(
@@ -3345,6 +5372,13 @@ impl DbRead for PgTransaction<'_> {
         PgRead::get_signer_utxo(self.tx.lock().await.as_mut(), chain_tip).await
     }
 
+    async fn get_signer_utxos(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Vec<crate::bitcoin::utxo::SignerUtxo>, Error> {
+        PgRead::get_signer_utxos(self.tx.lock().await.as_mut(), chain_tip).await
+    }
+
     async fn get_deposit_request_signer_votes(
         &self,
         txid: &model::BitcoinTxId,
@@ -3373,6 +5407,13 @@ impl DbRead for PgTransaction<'_> {
         .await
     }
 
+    async fn get_withdrawal_requests_by_id(
+        &self,
+        request_id: u64,
+    ) -> Result<Vec<model::WithdrawalRequest>, Error> {
+        PgRead::get_withdrawal_requests_by_id(self.tx.lock().await.as_mut(), request_id).await
+    }
+
     async fn is_known_bitcoin_block_hash(
         &self,
         block_hash: &model::BitcoinBlockHash,
@@ -3469,4 +5510,316 @@ impl DbRead for PgTransaction<'_> {
         let mut tx = self.tx.lock().await;
         PgRead::get_p2p_peers(tx.as_mut()).await
     }
+
+    async fn get_sbtc_supply_totals(&self) -> Result<model::SbtcSupplyTotals, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_sbtc_supply_totals(tx.as_mut()).await
+    }
+
+    async fn get_signer_utxo_violations(
+        &self,
+        limit: u16,
+    ) -> Result<Vec<model::SignerUtxoViolation>, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_signer_utxo_violations(tx.as_mut(), limit).await
+    }
+
+    async fn get_bitcoin_tx_body(
+        &self,
+        txid: &model::BitcoinTxId,
+        block_hash: &model::BitcoinBlockHash,
+    ) -> Result<Option<model::BitcoinTxBody>, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_bitcoin_tx_body(tx.as_mut(), txid, block_hash).await
+    }
+
+    async fn get_block_activity(
+        &self,
+        block_hash: &model::BitcoinBlockHash,
+    ) -> Result<model::BlockActivitySummary, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_block_activity(tx.as_mut(), block_hash).await
+    }
+
+    async fn is_emily_imported_deposit(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+    ) -> Result<bool, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::is_emily_imported_deposit(tx.as_mut(), txid, output_index).await
+    }
+
+    async fn is_emily_imported_withdrawal(
+        &self,
+        id: &model::QualifiedRequestId,
+    ) -> Result<bool, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::is_emily_imported_withdrawal(tx.as_mut(), id).await
+    }
+
+    async fn get_p2p_peer_versions(&self) -> Result<Vec<model::P2PPeerVersion>, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_p2p_peer_versions(tx.as_mut()).await
+    }
+
+    async fn get_active_peer_bans(&self) -> Result<Vec<model::P2PPeerBan>, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_active_peer_bans(tx.as_mut()).await
+    }
+
+    async fn get_active_dkg_verification_rounds(
+        &self,
+    ) -> Result<Vec<model::DkgVerificationRound>, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_active_dkg_verification_rounds(tx.as_mut()).await
+    }
+
+    async fn get_unresolved_stacks_event_dead_letters(
+        &self,
+    ) -> Result<Vec<model::StacksEventDeadLetter>, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_unresolved_stacks_event_dead_letters(tx.as_mut()).await
+    }
+
+    async fn get_signed_sweep_transactions(
+        &self,
+    ) -> Result<Vec<model::SignedSweepTransaction>, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_signed_sweep_transactions(tx.as_mut()).await
+    }
+
+    async fn get_aggregate_key_history(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Vec<model::AggregateKeyPeriod>, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_aggregate_key_history(tx.as_mut(), chain_tip).await
+    }
+
+    async fn get_request_counts(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<model::RequestCounts, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_request_counts(tx.as_mut(), chain_tip).await
+    }
+
+    async fn get_sweep_fee_discrepancies(
+        &self,
+        limit: u16,
+    ) -> Result<Vec<model::SweepFeeDiscrepancy>, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_sweep_fee_discrepancies(tx.as_mut(), limit).await
+    }
+
+    async fn get_key_rotation_attestations(
+        &self,
+        aggregate_key: &PublicKey,
+    ) -> Result<Vec<model::KeyRotationAttestation>, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_key_rotation_attestations(tx.as_mut(), aggregate_key).await
+    }
+
+    async fn get_caps_utilization(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+        rolling_withdrawal_cap: u64,
+    ) -> Result<model::CapsUtilization, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_caps_utilization(
+            tx.as_mut(),
+            bitcoin_chain_tip,
+            context_window,
+            rolling_withdrawal_cap,
+        )
+        .await
+    }
+
+    async fn get_sweep_package(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Option<model::SweepPackage>, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_sweep_package(tx.as_mut(), bitcoin_chain_tip).await
+    }
+
+    async fn get_presigned_withdrawals(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Vec<model::WithdrawalRequest>, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_presigned_withdrawals(tx.as_mut(), bitcoin_chain_tip).await
+    }
+
+    async fn get_deposit_request_timestamps(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+    ) -> Result<Option<model::RequestTimestamps>, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_deposit_request_timestamps(tx.as_mut(), txid, output_index).await
+    }
+
+    async fn get_withdrawal_request_timestamps(
+        &self,
+        request_id: u64,
+    ) -> Result<Option<model::RequestTimestamps>, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_withdrawal_request_timestamps(tx.as_mut(), request_id).await
+    }
+
+    async fn get_deposit_signer_decisions_in_range(
+        &self,
+        signer_public_key: &PublicKey,
+        start_height: model::BitcoinBlockHeight,
+        end_height: model::BitcoinBlockHeight,
+        limit: u16,
+        offset: u32,
+    ) -> Result<Vec<model::DepositSigner>, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_deposit_signer_decisions_in_range(
+            tx.as_mut(),
+            signer_public_key,
+            start_height,
+            end_height,
+            limit,
+            offset,
+        )
+        .await
+    }
+
+    async fn get_withdrawal_signer_decisions_in_range(
+        &self,
+        signer_public_key: &PublicKey,
+        start_height: model::BitcoinBlockHeight,
+        end_height: model::BitcoinBlockHeight,
+        limit: u16,
+        offset: u32,
+    ) -> Result<Vec<model::WithdrawalSigner>, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_withdrawal_signer_decisions_in_range(
+            tx.as_mut(),
+            signer_public_key,
+            start_height,
+            end_height,
+            limit,
+            offset,
+        )
+        .await
+    }
+
+    async fn get_bitcoin_backfill_progress(
+        &self,
+    ) -> Result<Option<model::BitcoinBackfillProgress>, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_bitcoin_backfill_progress(tx.as_mut()).await
+    }
+
+    async fn get_coordinator_takeover(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Option<model::CoordinatorTakeover>, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_coordinator_takeover(tx.as_mut(), bitcoin_chain_tip).await
+    }
+
+    async fn get_active_peer_address_records(
+        &self,
+    ) -> Result<Vec<model::P2PPeerAddressRecord>, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_active_peer_address_records(tx.as_mut()).await
+    }
+
+    async fn get_active_request_override(
+        &self,
+        target: &model::OverrideTarget,
+    ) -> Result<Option<model::RequestOverride>, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_active_request_override(tx.as_mut(), target, self.column_key()).await
+    }
+
+    async fn get_request_overrides(&self) -> Result<Vec<model::RequestOverride>, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_request_overrides(tx.as_mut(), self.column_key()).await
+    }
+
+    async fn get_inflight_contract_call(
+        &self,
+        target: &model::ContractCallTarget,
+    ) -> Result<Option<model::InflightContractCall>, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_inflight_contract_call(tx.as_mut(), target).await
+    }
+
+    async fn get_chain_history(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        depth: u16,
+        start_height: model::BitcoinBlockHeight,
+    ) -> Result<model::ChainHistory, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_chain_history(tx.as_mut(), chain_tip, depth, start_height).await
+    }
+
+    async fn get_completed_deposit_event(
+        &self,
+        outpoint: &OutPoint,
+    ) -> Result<Option<model::CompletedDepositEvent>, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_completed_deposit_event(tx.as_mut(), outpoint).await
+    }
+
+    async fn get_sweep_tx_merkle_proof(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        txid: &model::BitcoinTxId,
+    ) -> Result<Option<model::BitcoinTxMerkleProof>, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_sweep_tx_merkle_proof(tx.as_mut(), chain_tip, txid).await
+    }
+
+    async fn get_bitcoin_block_headers(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        start_height: model::BitcoinBlockHeight,
+        limit: u16,
+    ) -> Result<Vec<bitcoin::block::Header>, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_bitcoin_block_headers(tx.as_mut(), chain_tip, start_height, limit).await
+    }
+
+    async fn get_stacks_chain_tip_v2(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Option<model::StacksChainTip>, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_stacks_chain_tip_v2(tx.as_mut(), bitcoin_chain_tip).await
+    }
+
+    async fn get_unscanned_stacks_blocks(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        context_window: u16,
+    ) -> Result<Vec<model::StacksBlockHash>, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_unscanned_stacks_blocks(tx.as_mut(), chain_tip, context_window).await
+    }
+
+    async fn get_multisig_stacks_transactions(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        stacks_chain_tip: &model::StacksBlockHash,
+        context_window: u16,
+    ) -> Result<Vec<model::MultisigStacksTransaction>, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_multisig_stacks_transactions(
+            tx.as_mut(),
+            bitcoin_chain_tip,
+            stacks_chain_tip,
+            context_window,
+        )
+        .await
+    }
 }
@@ -1,5 +1,12 @@
+use std::time::Duration;
+
+use crate::keys::PublicKey;
+use crate::storage::model::StacksBlockHash;
 #[cfg(any(test, feature = "testing"))]
-use crate::storage::model::{StacksBlockHash, StacksBlockHeight};
+use crate::storage::model::StacksBlockHeight;
+use crate::storage::postgres::ColumnEncryptionKey;
+use crate::storage::postgres::encryption::ENCRYPTED_COLUMN_PREFIX;
+use crate::storage::util::retry_transient;
 use crate::storage::{Transactable, TransactionHandle};
 use crate::{error::Error, storage::postgres::PGSQL_MIGRATIONS};
 use sqlx::Executor as _;
@@ -7,15 +14,30 @@ use sqlx::pool::PoolConnection;
 use sqlx::{PgExecutor, postgres::PgPoolOptions};
 use tokio::sync::Mutex;
 
+/// How long to wait for a connection from the pool before giving up. This
+/// is shorter than the sqlx default so that a failover of the postgres
+/// primary is noticed, and retried, quickly.
+const DATABASE_ACQUIRE_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// A wrapper around a [`sqlx::PgPool`] which implements
 /// [`crate::storage::DbRead`] and [`crate::storage::DbWrite`].
 #[derive(Debug, Clone)]
-pub struct PgStore(sqlx::PgPool);
+pub struct PgStore {
+    pool: sqlx::PgPool,
+    /// The key used to encrypt sensitive columns, if column encryption
+    /// is enabled.
+    column_key: Option<ColumnEncryptionKey>,
+}
 
 impl PgStore {
     /// Connect to the Postgres database at `url`.
     pub async fn connect(url: &str) -> Result<Self, Error> {
         let pool = PgPoolOptions::new()
+            .acquire_timeout(DATABASE_ACQUIRE_TIMEOUT)
+            // Connections to a primary that has since failed over may
+            // look healthy while idle, so we check each one before handing
+            // it out.
+            .test_before_acquire(true)
             .after_connect(|conn, _meta| Box::pin(async move {
                 conn.execute("SET application_name = 'sbtc-signer'; SET search_path = sbtc_signer,public;")
                     .await?;
@@ -25,7 +47,19 @@ impl PgStore {
             .await
             .map_err(Error::SqlxConnect)?;
 
-        Ok(Self(pool))
+        Ok(Self::from(pool))
+    }
+
+    /// Encrypt sensitive columns with the given key when writing them,
+    /// and decrypt them when reading them.
+    pub fn with_column_encryption_key(mut self, key: ColumnEncryptionKey) -> Self {
+        self.column_key = Some(key);
+        self
+    }
+
+    /// The key used to encrypt sensitive columns, if any.
+    pub(super) fn column_key(&self) -> Option<&ColumnEncryptionKey> {
+        self.column_key.as_ref()
     }
 
     /// Apply the migrations to the database.
@@ -47,7 +81,7 @@ impl PgStore {
                 );
             "#,
         )
-        .execute(&self.0)
+        .execute(&self.pool)
         .await
         .map_err(Error::SqlxMigrate)?;
 
@@ -116,6 +150,82 @@ impl PgStore {
         Ok(())
     }
 
+    /// Encrypt the values of sensitive columns that were written before
+    /// column encryption was enabled, returning the number of values that
+    /// were encrypted. Values that are already encrypted are left alone,
+    /// so this is safe to run more than once.
+    ///
+    /// Returns an error if no column encryption key is configured.
+    pub async fn encrypt_plaintext_columns(&self) -> Result<u64, Error> {
+        let key = self.column_key().ok_or(Error::MissingColumnEncryptionKey)?;
+        let encrypted_pattern = format!("{ENCRYPTED_COLUMN_PREFIX}%");
+
+        let mut trx = self
+            .pool()
+            .begin()
+            .await
+            .map_err(Error::SqlxBeginTransaction)?;
+
+        let overrides = sqlx::query_as::<_, (i64, String)>(
+            r#"
+            SELECT id, reason
+            FROM sbtc_signer.request_overrides
+            WHERE reason NOT LIKE $1
+            "#,
+        )
+        .bind(&encrypted_pattern)
+        .fetch_all(&mut *trx)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        let mut encrypted = 0;
+        for (id, reason) in overrides {
+            sqlx::query("UPDATE sbtc_signer.request_overrides SET reason = $1 WHERE id = $2")
+                .bind(key.encrypt(&reason)?)
+                .bind(id)
+                .execute(&mut *trx)
+                .await
+                .map_err(Error::SqlxQuery)?;
+            encrypted += 1;
+        }
+
+        let decisions = sqlx::query_as::<_, (i64, StacksBlockHash, PublicKey, String)>(
+            r#"
+            SELECT request_id, block_hash, signer_pub_key, rejection_reason
+            FROM sbtc_signer.withdrawal_signers
+            WHERE rejection_reason NOT LIKE $1
+            "#,
+        )
+        .bind(&encrypted_pattern)
+        .fetch_all(&mut *trx)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        for (request_id, block_hash, signer_pub_key, reason) in decisions {
+            sqlx::query(
+                r#"
+                UPDATE sbtc_signer.withdrawal_signers
+                SET rejection_reason = $1
+                WHERE request_id = $2
+                  AND block_hash = $3
+                  AND signer_pub_key = $4
+                "#,
+            )
+            .bind(key.encrypt(&reason)?)
+            .bind(request_id)
+            .bind(block_hash)
+            .bind(signer_pub_key)
+            .execute(&mut *trx)
+            .await
+            .map_err(Error::SqlxQuery)?;
+            encrypted += 1;
+        }
+
+        trx.commit().await.map_err(Error::SqlxCommitTransaction)?;
+
+        Ok(encrypted)
+    }
+
     /// Check if a migration with the given `key` exists.
     async fn check_migration_existence(
         &self,
@@ -161,12 +271,20 @@ impl PgStore {
 
     /// Get a reference to the underlying pool.
     pub fn pool(&self) -> &sqlx::PgPool {
-        &self.0
+        &self.pool
     }
 
     /// Get a connection from the pool.
     pub async fn get_connection(&self) -> Result<PoolConnection<sqlx::Postgres>, Error> {
-        self.0.acquire().await.map_err(Error::SqlxAcquireConnection)
+        // Acquiring a connection has no side effects, so it is always safe
+        // to try again when the pool is reconnecting after a failover.
+        retry_transient(|| async {
+            self.pool
+                .acquire()
+                .await
+                .map_err(Error::SqlxAcquireConnection)
+        })
+        .await
     }
 
     /// Check whether the given block hash is a part of the stacks
@@ -192,7 +310,7 @@ impl PgStore {
 
 impl From<sqlx::PgPool> for PgStore {
     fn from(value: sqlx::PgPool) -> Self {
-        Self(value)
+        Self { pool: value, column_key: None }
     }
 }
 
@@ -200,12 +318,14 @@ impl Transactable for PgStore {
     type Tx<'a> = PgTransaction<'a>;
 
     async fn begin_transaction(&self) -> Result<Self::Tx<'_>, Error> {
-        let tx = self
-            .pool()
-            .begin()
-            .await
-            .map_err(Error::SqlxBeginTransaction)?;
-        Ok(PgTransaction::new(tx))
+        let tx = retry_transient(|| async {
+            self.pool()
+                .begin()
+                .await
+                .map_err(Error::SqlxBeginTransaction)
+        })
+        .await?;
+        Ok(PgTransaction::new(tx, self.column_key.clone()))
     }
 }
 
@@ -214,11 +334,22 @@ impl Transactable for PgStore {
 pub struct PgTransaction<'a> {
     /// The underlying transaction.
     pub tx: Mutex<sqlx::PgTransaction<'a>>,
+    /// The key used to encrypt sensitive columns, if column encryption
+    /// is enabled.
+    column_key: Option<ColumnEncryptionKey>,
 }
 
 impl<'a> PgTransaction<'a> {
-    pub(super) fn new(tx: sqlx::Transaction<'a, sqlx::Postgres>) -> Self {
-        Self { tx: Mutex::new(tx) }
+    pub(super) fn new(
+        tx: sqlx::Transaction<'a, sqlx::Postgres>,
+        column_key: Option<ColumnEncryptionKey>,
+    ) -> Self {
+        Self { tx: Mutex::new(tx), column_key }
+    }
+
+    /// The key used to encrypt sensitive columns, if any.
+    pub(super) fn column_key(&self) -> Option<&ColumnEncryptionKey> {
+        self.column_key.as_ref()
     }
 }
 
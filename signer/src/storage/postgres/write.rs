@@ -1,17 +1,22 @@
+use super::encryption::{ColumnEncryptionKey, decrypt_column, encrypt_column};
 use super::{PgStore, PgTransaction};
 use crate::{
+    bitcoin::utxo::Fees,
+    codec::Encode as _,
     error::Error,
     keys::{PublicKey, PublicKeyXOnly},
+    metrics::Metrics,
     stacks::api::TenureBlockHeaders,
     storage::{
         DbWrite,
         model::{
             self, CompletedDepositEvent, DbMultiaddr, DbPeerId, WithdrawalAcceptEvent,
-            WithdrawalRejectEvent,
+            WithdrawalRejectEvent, WriteOutcome,
         },
     },
 };
 use bitcoin::hashes::Hash as _;
+use sqlx::postgres::PgQueryResult;
 
 pub struct PgWrite;
 
@@ -19,7 +24,7 @@ impl PgWrite {
     async fn write_bitcoin_block<'e, E>(
         executor: &'e mut E,
         block: &model::BitcoinBlock,
-    ) -> Result<(), Error>
+    ) -> Result<WriteOutcome, Error>
     where
         &'e mut E: sqlx::PgExecutor<'e>,
     {
@@ -38,16 +43,15 @@ impl PgWrite {
         .bind(block.parent_hash)
         .execute(executor)
         .await
-        .map_err(Error::SqlxQuery)?;
-
-        Ok(())
+        .map(|result| write_outcome(&result, "bitcoin_blocks"))
+        .map_err(Error::SqlxQuery)
     }
 
     #[cfg(any(test, feature = "testing"))]
     async fn write_stacks_block<'e, E>(
         executor: &'e mut E,
         block: &model::StacksBlock,
-    ) -> Result<(), Error>
+    ) -> Result<WriteOutcome, Error>
     where
         &'e mut E: sqlx::PgExecutor<'e>,
     {
@@ -67,18 +71,20 @@ impl PgWrite {
         .bind(block.bitcoin_anchor)
         .execute(executor)
         .await
-        .map_err(Error::SqlxQuery)?;
-
-        Ok(())
+        .map(|result| write_outcome(&result, "stacks_blocks"))
+        .map_err(Error::SqlxQuery)
     }
 
     async fn write_deposit_request<'e, E>(
         executor: &'e mut E,
         deposit_request: &model::DepositRequest,
-    ) -> Result<(), Error>
+    ) -> Result<WriteOutcome, Error>
     where
         &'e mut E: sqlx::PgExecutor<'e>,
     {
+        let (funding_fees_total, funding_fees_vsize) =
+            funding_fees_columns(deposit_request.funding_fees)?;
+
         sqlx::query(
             "INSERT INTO sbtc_signer.deposit_requests
               ( txid
@@ -91,8 +97,10 @@ impl PgWrite {
               , lock_time
               , signers_public_key
               , sender_script_pub_keys
+              , funding_fees_total
+              , funding_fees_vsize
               )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
             ON CONFLICT DO NOTHING",
         )
         .bind(deposit_request.txid)
@@ -105,11 +113,12 @@ impl PgWrite {
         .bind(i64::from(deposit_request.lock_time))
         .bind(deposit_request.signers_public_key)
         .bind(&deposit_request.sender_script_pub_keys)
+        .bind(funding_fees_total)
+        .bind(funding_fees_vsize)
         .execute(executor)
         .await
-        .map_err(Error::SqlxQuery)?;
-
-        Ok(())
+        .map(|result| write_outcome(&result, "deposit_requests"))
+        .map_err(Error::SqlxQuery)
     }
 
     async fn write_deposit_requests<'e, E>(
@@ -133,6 +142,8 @@ impl PgWrite {
         let mut lock_time = Vec::with_capacity(deposit_requests.len());
         let mut signers_public_key = Vec::with_capacity(deposit_requests.len());
         let mut sender_script_pubkeys = Vec::with_capacity(deposit_requests.len());
+        let mut funding_fees_total = Vec::with_capacity(deposit_requests.len());
+        let mut funding_fees_vsize = Vec::with_capacity(deposit_requests.len());
 
         for req in deposit_requests {
             let vout = i32::try_from(req.output_index).map_err(Error::ConversionDatabaseInt)?;
@@ -156,6 +167,9 @@ impl PgWrite {
                 .map(|x| x.to_hex_string())
                 .collect();
             sender_script_pubkeys.push(addresses.join(","));
+            let (fees_total, fees_vsize) = funding_fees_columns(req.funding_fees)?;
+            funding_fees_total.push(fees_total);
+            funding_fees_vsize.push(fees_vsize);
         }
 
         sqlx::query(
@@ -170,6 +184,8 @@ impl PgWrite {
             , lock_time           AS (SELECT ROW_NUMBER() OVER (), lock_time FROM UNNEST($8::BIGINT[]) AS lock_time)
             , signer_pub_keys     AS (SELECT ROW_NUMBER() OVER (), signers_public_key FROM UNNEST($9::BYTEA[]) AS signers_public_key)
             , script_pub_keys     AS (SELECT ROW_NUMBER() OVER (), senders FROM UNNEST($10::VARCHAR[]) AS senders)
+            , fees_total          AS (SELECT ROW_NUMBER() OVER (), funding_fees_total FROM UNNEST($11::BIGINT[]) AS funding_fees_total)
+            , fees_vsize          AS (SELECT ROW_NUMBER() OVER (), funding_fees_vsize FROM UNNEST($12::BIGINT[]) AS funding_fees_vsize)
             INSERT INTO sbtc_signer.deposit_requests (
                   txid
                 , output_index
@@ -180,7 +196,9 @@ impl PgWrite {
                 , max_fee
                 , lock_time
                 , signers_public_key
-                , sender_script_pub_keys)
+                , sender_script_pub_keys
+                , funding_fees_total
+                , funding_fees_vsize)
             SELECT
                 txid
               , output_index
@@ -192,6 +210,8 @@ impl PgWrite {
               , lock_time
               , signers_public_key
               , ARRAY(SELECT decode(UNNEST(regexp_split_to_array(senders, ',')), 'hex'))
+              , funding_fees_total
+              , funding_fees_vsize
             FROM tx_ids
             JOIN output_index USING (row_number)
             JOIN spend_script USING (row_number)
@@ -202,6 +222,8 @@ impl PgWrite {
             JOIN lock_time USING (row_number)
             JOIN signer_pub_keys USING (row_number)
             JOIN script_pub_keys USING (row_number)
+            JOIN fees_total USING (row_number)
+            JOIN fees_vsize USING (row_number)
             ON CONFLICT DO NOTHING"#,
         )
         .bind(txid)
@@ -214,6 +236,8 @@ impl PgWrite {
         .bind(lock_time)
         .bind(signers_public_key)
         .bind(sender_script_pubkeys)
+        .bind(funding_fees_total)
+        .bind(funding_fees_vsize)
         .execute(executor)
         .await
         .map_err(Error::SqlxQuery)?;
@@ -224,7 +248,7 @@ impl PgWrite {
     async fn write_withdrawal_request<'e, E>(
         executor: &'e mut E,
         request: &model::WithdrawalRequest,
-    ) -> Result<(), Error>
+    ) -> Result<WriteOutcome, Error>
     where
         &'e mut E: sqlx::PgExecutor<'e>,
     {
@@ -252,16 +276,15 @@ impl PgWrite {
         .bind(i64::try_from(request.bitcoin_block_height).map_err(Error::ConversionDatabaseInt)?)
         .execute(executor)
         .await
-        .map_err(Error::SqlxQuery)?;
-
-        Ok(())
+        .map(|result| write_outcome(&result, "withdrawal_requests"))
+        .map_err(Error::SqlxQuery)
     }
 
     #[tracing::instrument(skip(executor))]
     async fn write_deposit_signer_decision<'e, E>(
         executor: &'e mut E,
         decision: &model::DepositSigner,
-    ) -> Result<(), Error>
+    ) -> Result<WriteOutcome, Error>
     where
         &'e mut E: sqlx::PgExecutor<'e>,
     {
@@ -283,18 +306,24 @@ impl PgWrite {
         .bind(decision.can_sign)
         .execute(executor)
         .await
-        .map_err(Error::SqlxQuery)?;
-
-        Ok(())
+        .map(|result| write_outcome(&result, "deposit_signers"))
+        .map_err(Error::SqlxQuery)
     }
 
     async fn write_withdrawal_signer_decision<'e, E>(
         executor: &'e mut E,
         decision: &model::WithdrawalSigner,
-    ) -> Result<(), Error>
+        column_key: Option<&ColumnEncryptionKey>,
+    ) -> Result<WriteOutcome, Error>
     where
         &'e mut E: sqlx::PgExecutor<'e>,
     {
+        let rejection_reason = decision
+            .rejection_reason
+            .as_deref()
+            .map(|reason| encrypt_column(column_key, reason))
+            .transpose()?;
+
         sqlx::query(
             "INSERT INTO sbtc_signer.withdrawal_signers
               ( request_id
@@ -302,8 +331,9 @@ impl PgWrite {
               , block_hash
               , signer_pub_key
               , is_accepted
+              , rejection_reason
               )
-            VALUES ($1, $2, $3, $4, $5)
+            VALUES ($1, $2, $3, $4, $5, $6)
             ON CONFLICT DO NOTHING",
         )
         .bind(i64::try_from(decision.request_id).map_err(Error::ConversionDatabaseInt)?)
@@ -311,17 +341,17 @@ impl PgWrite {
         .bind(decision.block_hash)
         .bind(decision.signer_pub_key)
         .bind(decision.is_accepted)
+        .bind(rejection_reason)
         .execute(executor)
         .await
-        .map_err(Error::SqlxQuery)?;
-
-        Ok(())
+        .map(|result| write_outcome(&result, "withdrawal_signers"))
+        .map_err(Error::SqlxQuery)
     }
 
     async fn write_bitcoin_transaction<'e, E>(
         executor: &'e mut E,
         tx_ref: &model::BitcoinTxRef,
-    ) -> Result<(), Error>
+    ) -> Result<WriteOutcome, Error>
     where
         &'e mut E: sqlx::PgExecutor<'e>,
     {
@@ -334,9 +364,8 @@ impl PgWrite {
         .bind(tx_ref.block_hash)
         .execute(executor)
         .await
-        .map_err(Error::SqlxQuery)?;
-
-        Ok(())
+        .map(|result| write_outcome(&result, "bitcoin_transactions"))
+        .map_err(Error::SqlxQuery)
     }
 
     async fn write_bitcoin_transactions<'e, E>(
@@ -460,7 +489,7 @@ impl PgWrite {
     async fn write_encrypted_dkg_shares<'e, E>(
         executor: &'e mut E,
         shares: &model::EncryptedDkgShares,
-    ) -> Result<(), Error>
+    ) -> Result<WriteOutcome, Error>
     where
         &'e mut E: sqlx::PgExecutor<'e>,
     {
@@ -496,15 +525,14 @@ impl PgWrite {
         .bind(started_at_bitcoin_block_height)
         .execute(executor)
         .await
-        .map_err(Error::SqlxQuery)?;
-
-        Ok(())
+        .map(|result| write_outcome(&result, "dkg_shares"))
+        .map_err(Error::SqlxQuery)
     }
 
     async fn write_rotate_keys_transaction<'e, E>(
         executor: &'e mut E,
         key_rotation: &model::KeyRotationEvent,
-    ) -> Result<(), Error>
+    ) -> Result<WriteOutcome, Error>
     where
         &'e mut E: sqlx::PgExecutor<'e>,
     {
@@ -529,15 +557,14 @@ impl PgWrite {
         .bind(i32::from(key_rotation.signatures_required))
         .execute(executor)
         .await
-        .map_err(Error::SqlxQuery)?;
-
-        Ok(())
+        .map(|result| write_outcome(&result, "rotate_keys_transactions"))
+        .map_err(Error::SqlxQuery)
     }
 
     async fn write_completed_deposit_event<'e, E>(
         executor: &'e mut E,
         event: &CompletedDepositEvent,
-    ) -> Result<(), Error>
+    ) -> Result<WriteOutcome, Error>
     where
         &'e mut E: sqlx::PgExecutor<'e>,
     {
@@ -553,7 +580,8 @@ impl PgWrite {
           , sweep_block_height
           , sweep_txid
         )
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        ON CONFLICT DO NOTHING",
         )
         .bind(event.txid)
         .bind(event.block_id)
@@ -565,15 +593,14 @@ impl PgWrite {
         .bind(event.sweep_txid.to_byte_array())
         .execute(executor)
         .await
-        .map_err(Error::SqlxQuery)?;
-
-        Ok(())
+        .map(|result| write_outcome(&result, "completed_deposit_events"))
+        .map_err(Error::SqlxQuery)
     }
 
     async fn write_withdrawal_accept_event<'e, E>(
         executor: &'e mut E,
         event: &WithdrawalAcceptEvent,
-    ) -> Result<(), Error>
+    ) -> Result<WriteOutcome, Error>
     where
         &'e mut E: sqlx::PgExecutor<'e>,
     {
@@ -591,7 +618,8 @@ impl PgWrite {
           , sweep_block_height
           , sweep_txid
         )
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        ON CONFLICT DO NOTHING",
         )
         .bind(event.txid)
         .bind(event.block_id)
@@ -605,15 +633,14 @@ impl PgWrite {
         .bind(event.sweep_txid.to_byte_array())
         .execute(executor)
         .await
-        .map_err(Error::SqlxQuery)?;
-
-        Ok(())
+        .map(|result| write_outcome(&result, "withdrawal_accept_events"))
+        .map_err(Error::SqlxQuery)
     }
 
     async fn write_withdrawal_reject_event<'e, E>(
         executor: &'e mut E,
         event: &WithdrawalRejectEvent,
-    ) -> Result<(), Error>
+    ) -> Result<WriteOutcome, Error>
     where
         &'e mut E: sqlx::PgExecutor<'e>,
     {
@@ -625,7 +652,8 @@ impl PgWrite {
           , request_id
           , signer_bitmap
         )
-        VALUES ($1, $2, $3, $4)",
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT DO NOTHING",
         )
         .bind(event.txid)
         .bind(event.block_id)
@@ -633,15 +661,14 @@ impl PgWrite {
         .bind(event.signer_bitmap.into_inner())
         .execute(executor)
         .await
-        .map_err(Error::SqlxQuery)?;
-
-        Ok(())
+        .map(|result| write_outcome(&result, "withdrawal_reject_events"))
+        .map_err(Error::SqlxQuery)
     }
 
     async fn write_tx_output<'e, E>(
         executor: &'e mut E,
         output: &model::TxOutput,
-    ) -> Result<(), Error>
+    ) -> Result<WriteOutcome, Error>
     where
         &'e mut E: sqlx::PgExecutor<'e>,
     {
@@ -665,15 +692,14 @@ impl PgWrite {
         .bind(output.output_type)
         .execute(executor)
         .await
-        .map_err(Error::SqlxQuery)?;
-
-        Ok(())
+        .map(|result| write_outcome(&result, "bitcoin_tx_outputs"))
+        .map_err(Error::SqlxQuery)
     }
 
     async fn write_withdrawal_tx_output<'e, E>(
         executor: &'e mut E,
         output: &model::WithdrawalTxOutput,
-    ) -> Result<(), Error>
+    ) -> Result<WriteOutcome, Error>
     where
         &'e mut E: sqlx::PgExecutor<'e>,
     {
@@ -683,8 +709,16 @@ impl PgWrite {
                 txid
               , output_index
               , request_id
+              , label
             )
-            VALUES ($1, $2, $3)
+            VALUES ($1, $2, $3, (
+                SELECT wol.label
+                FROM bitcoin_tx_outputs AS bto
+                JOIN withdrawal_output_labels AS wol
+                  ON wol.script_pubkey = bto.script_pubkey
+                WHERE bto.txid = $1
+                  AND bto.output_index = $2
+            ))
             ON CONFLICT DO NOTHING;
             "#,
         )
@@ -693,15 +727,14 @@ impl PgWrite {
         .bind(i64::try_from(output.request_id).map_err(Error::ConversionDatabaseInt)?)
         .execute(executor)
         .await
-        .map_err(Error::SqlxQuery)?;
-
-        Ok(())
+        .map(|result| write_outcome(&result, "bitcoin_withdrawal_tx_outputs"))
+        .map_err(Error::SqlxQuery)
     }
 
     async fn write_tx_prevout<'e, E>(
         executor: &'e mut E,
         prevout: &model::TxPrevout,
-    ) -> Result<(), Error>
+    ) -> Result<WriteOutcome, Error>
     where
         &'e mut E: sqlx::PgExecutor<'e>,
     {
@@ -727,9 +760,8 @@ impl PgWrite {
         .bind(prevout.prevout_type)
         .execute(executor)
         .await
-        .map_err(Error::SqlxQuery)?;
-
-        Ok(())
+        .map(|result| write_outcome(&result, "bitcoin_tx_inputs"))
+        .map_err(Error::SqlxQuery)
     }
 
     async fn write_bitcoin_txs_sighashes<'e, E>(
@@ -750,6 +782,7 @@ impl PgWrite {
         let mut sighash = Vec::with_capacity(sighashes.len());
         let mut prevout_type = Vec::with_capacity(sighashes.len());
         let mut validation_result = Vec::with_capacity(sighashes.len());
+        let mut validation_checks = Vec::with_capacity(sighashes.len());
         let mut is_valid_tx = Vec::with_capacity(sighashes.len());
         let mut will_sign = Vec::with_capacity(sighashes.len());
         let mut aggregate_key = Vec::with_capacity(sighashes.len());
@@ -765,6 +798,7 @@ impl PgWrite {
             sighash.push(tx_sighash.sighash);
             prevout_type.push(tx_sighash.prevout_type);
             validation_result.push(tx_sighash.validation_result);
+            validation_checks.push(tx_sighash.validation_checks.to_string());
             is_valid_tx.push(tx_sighash.is_valid_tx);
             will_sign.push(tx_sighash.will_sign);
             aggregate_key.push(tx_sighash.aggregate_key);
@@ -782,6 +816,7 @@ impl PgWrite {
             , is_valid_tx           AS (SELECT ROW_NUMBER() OVER (), is_valid_tx FROM UNNEST($8::BOOLEAN[]) AS is_valid_tx)
             , will_sign             AS (SELECT ROW_NUMBER() OVER (), will_sign FROM UNNEST($9::BOOLEAN[]) AS will_sign)
             , x_only_public_key     AS (SELECT ROW_NUMBER() OVER (), x_only_public_key FROM UNNEST($10::BYTEA[]) AS x_only_public_key)
+            , validation_checks     AS (SELECT ROW_NUMBER() OVER (), validation_checks FROM UNNEST($11::TEXT[]) AS validation_checks)
             INSERT INTO sbtc_signer.bitcoin_tx_sighashes (
                   txid
                 , chain_tip
@@ -793,6 +828,7 @@ impl PgWrite {
                 , is_valid_tx
                 , will_sign
                 , x_only_public_key
+                , validation_checks
             )
             SELECT
                 txid
@@ -805,6 +841,7 @@ impl PgWrite {
               , is_valid_tx
               , will_sign
               , x_only_public_key
+              , validation_checks
             FROM tx_ids
             JOIN chain_tip USING (row_number)
             JOIN prevout_txid USING (row_number)
@@ -815,6 +852,7 @@ impl PgWrite {
             JOIN is_valid_tx USING (row_number)
             JOIN will_sign USING (row_number)
             JOIN x_only_public_key USING (row_number)
+            JOIN validation_checks USING (row_number)
             ON CONFLICT DO NOTHING"#,
         )
         .bind(txid)
@@ -827,6 +865,7 @@ impl PgWrite {
         .bind(is_valid_tx)
         .bind(will_sign)
         .bind(aggregate_key)
+        .bind(validation_checks)
         .execute(executor)
         .await
         .map_err(Error::SqlxQuery)?;
@@ -852,6 +891,7 @@ impl PgWrite {
         let mut stacks_txid = Vec::with_capacity(withdrawal_outputs.len());
         let mut stacks_block_hash = Vec::with_capacity(withdrawal_outputs.len());
         let mut validation_result = Vec::with_capacity(withdrawal_outputs.len());
+        let mut validation_checks = Vec::with_capacity(withdrawal_outputs.len());
         let mut is_valid_tx = Vec::with_capacity(withdrawal_outputs.len());
 
         for withdrawal_output in withdrawal_outputs {
@@ -868,6 +908,7 @@ impl PgWrite {
             stacks_txid.push(withdrawal_output.stacks_txid);
             stacks_block_hash.push(withdrawal_output.stacks_block_hash);
             validation_result.push(withdrawal_output.validation_result);
+            validation_checks.push(withdrawal_output.validation_checks.to_string());
             is_valid_tx.push(withdrawal_output.is_valid_tx);
         }
 
@@ -881,6 +922,7 @@ impl PgWrite {
             , stacks_block_hash     AS (SELECT ROW_NUMBER() OVER (), stacks_block_hash FROM UNNEST($6::BYTEA[]) AS stacks_block_hash)
             , validation_result     AS (SELECT ROW_NUMBER() OVER (), validation_result FROM UNNEST($7::TEXT[]) AS validation_result)
             , is_valid_tx           AS (SELECT ROW_NUMBER() OVER (), is_valid_tx FROM UNNEST($8::BOOLEAN[]) AS is_valid_tx)
+            , validation_checks     AS (SELECT ROW_NUMBER() OVER (), validation_checks FROM UNNEST($9::TEXT[]) AS validation_checks)
             INSERT INTO sbtc_signer.bitcoin_withdrawals_outputs (
                   bitcoin_txid
                 , bitcoin_chain_tip
@@ -889,7 +931,8 @@ impl PgWrite {
                 , stacks_txid
                 , stacks_block_hash
                 , validation_result
-                , is_valid_tx)
+                , is_valid_tx
+                , validation_checks)
             SELECT
                 bitcoin_txid
               , bitcoin_chain_tip
@@ -899,6 +942,7 @@ impl PgWrite {
               , stacks_block_hash
               , validation_result
               , is_valid_tx
+              , validation_checks
             FROM bitcoin_tx_ids
             JOIN bitcoin_chain_tip USING (row_number)
             JOIN output_index USING (row_number)
@@ -907,6 +951,7 @@ impl PgWrite {
             JOIN stacks_block_hash USING (row_number)
             JOIN validation_result USING (row_number)
             JOIN is_valid_tx USING (row_number)
+            JOIN validation_checks USING (row_number)
             ON CONFLICT DO NOTHING"#,
         )
         .bind(bitcoin_txid)
@@ -917,6 +962,7 @@ impl PgWrite {
         .bind(stacks_block_hash)
         .bind(validation_result)
         .bind(is_valid_tx)
+        .bind(validation_checks)
         .execute(executor)
         .await
         .map_err(Error::SqlxQuery)?;
@@ -1069,184 +1115,1758 @@ impl PgWrite {
 
         Ok(())
     }
-}
 
-impl DbWrite for PgStore {
-    async fn write_bitcoin_block(&self, block: &model::BitcoinBlock) -> Result<(), Error> {
-        PgWrite::write_bitcoin_block(self.get_connection().await?.as_mut(), block).await
-    }
+    async fn write_sbtc_supply_snapshot<'e, E>(
+        executor: &'e mut E,
+        snapshot: &model::SbtcSupplySnapshot,
+    ) -> Result<(), Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query(
+            r#"
+            INSERT INTO sbtc_signer.sbtc_supply_snapshots (
+                bitcoin_block_hash
+              , bitcoin_block_height
+              , minted
+              , burned
+              , reported_supply
+              , within_tolerance
+            )
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(snapshot.bitcoin_block_hash)
+        .bind(i64::try_from(snapshot.bitcoin_block_height).map_err(Error::ConversionDatabaseInt)?)
+        .bind(i64::try_from(snapshot.totals.minted).map_err(Error::ConversionDatabaseInt)?)
+        .bind(i64::try_from(snapshot.totals.burned).map_err(Error::ConversionDatabaseInt)?)
+        .bind(i64::try_from(snapshot.reported_supply).map_err(Error::ConversionDatabaseInt)?)
+        .bind(snapshot.within_tolerance)
+        .execute(executor)
+        .await
+        .map_err(Error::SqlxQuery)?;
 
-    #[cfg(any(test, feature = "testing"))]
-    async fn write_stacks_block(&self, block: &model::StacksBlock) -> Result<(), Error> {
-        PgWrite::write_stacks_block(self.get_connection().await?.as_mut(), block).await
+        Ok(())
     }
 
-    async fn write_deposit_request(
-        &self,
-        deposit_request: &model::DepositRequest,
-    ) -> Result<(), Error> {
-        PgWrite::write_deposit_request(self.get_connection().await?.as_mut(), deposit_request).await
+    async fn write_signer_utxo_violation<'e, E>(
+        executor: &'e mut E,
+        violation: &model::SignerUtxoViolation,
+    ) -> Result<WriteOutcome, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query(
+            r#"
+            INSERT INTO sbtc_signer.signer_utxo_violations (
+                txid
+              , block_hash
+              , signer_input
+              , deposits
+              , withdrawals
+              , fee
+              , signer_output
+              , previous_signer_output
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(violation.txid)
+        .bind(violation.block_hash)
+        .bind(i64::try_from(violation.signer_input).map_err(Error::ConversionDatabaseInt)?)
+        .bind(i64::try_from(violation.deposits).map_err(Error::ConversionDatabaseInt)?)
+        .bind(i64::try_from(violation.withdrawals).map_err(Error::ConversionDatabaseInt)?)
+        .bind(i64::try_from(violation.fee).map_err(Error::ConversionDatabaseInt)?)
+        .bind(i64::try_from(violation.signer_output).map_err(Error::ConversionDatabaseInt)?)
+        .bind(
+            violation
+                .previous_signer_output
+                .map(i64::try_from)
+                .transpose()
+                .map_err(Error::ConversionDatabaseInt)?,
+        )
+        .execute(executor)
+        .await
+        .map(|result| write_outcome(&result, "signer_utxo_violations"))
+        .map_err(Error::SqlxQuery)
     }
 
-    async fn write_deposit_requests(
-        &self,
-        deposit_requests: Vec<model::DepositRequest>,
-    ) -> Result<(), Error> {
-        PgWrite::write_deposit_requests(self.get_connection().await?.as_mut(), deposit_requests)
-            .await
-    }
+    async fn write_bitcoin_tx_bodies<'e, E>(
+        executor: &'e mut E,
+        bodies: &[model::BitcoinTxBody],
+    ) -> Result<(), Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        if bodies.is_empty() {
+            return Ok(());
+        }
 
-    async fn write_withdrawal_request(
-        &self,
-        request: &model::WithdrawalRequest,
-    ) -> Result<(), Error> {
-        PgWrite::write_withdrawal_request(self.get_connection().await?.as_mut(), request).await
-    }
+        let mut txids = Vec::with_capacity(bodies.len());
+        let mut block_hashes = Vec::with_capacity(bodies.len());
+        let mut block_heights = Vec::with_capacity(bodies.len());
+        let mut txs = Vec::with_capacity(bodies.len());
+        let mut prevouts = Vec::with_capacity(bodies.len());
+
+        for body in bodies {
+            txids.push(body.txid);
+            block_hashes.push(body.block_hash);
+            block_heights.push(body.block_height);
+            txs.push(bitcoin::consensus::serialize(&body.tx));
+            prevouts.push(bitcoin::consensus::serialize(&body.prevouts));
+        }
 
-    #[tracing::instrument(skip(self))]
-    async fn write_deposit_signer_decision(
-        &self,
-        decision: &model::DepositSigner,
-    ) -> Result<(), Error> {
-        PgWrite::write_deposit_signer_decision(self.get_connection().await?.as_mut(), decision)
-            .await
-    }
+        sqlx::query(
+            r#"
+            INSERT INTO sbtc_signer.bitcoin_tx_bodies
+                (txid, block_hash, block_height, tx, prevouts)
+            SELECT txid, block_hash, block_height, tx, prevouts
+            FROM UNNEST($1::BYTEA[], $2::BYTEA[], $3::BIGINT[], $4::BYTEA[], $5::BYTEA[])
+                AS bodies(txid, block_hash, block_height, tx, prevouts)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(&txids)
+        .bind(&block_hashes)
+        .bind(&block_heights)
+        .bind(&txs)
+        .bind(&prevouts)
+        .execute(executor)
+        .await
+        .map_err(Error::SqlxQuery)?;
 
-    async fn write_withdrawal_signer_decision(
-        &self,
-        decision: &model::WithdrawalSigner,
-    ) -> Result<(), Error> {
-        PgWrite::write_withdrawal_signer_decision(self.get_connection().await?.as_mut(), decision)
-            .await
+        Ok(())
     }
 
-    async fn write_bitcoin_transaction(&self, tx_ref: &model::BitcoinTxRef) -> Result<(), Error> {
-        PgWrite::write_bitcoin_transaction(self.get_connection().await?.as_mut(), tx_ref).await
+    async fn prune_bitcoin_tx_bodies<'e, E>(
+        executor: &'e mut E,
+        min_block_height: model::BitcoinBlockHeight,
+    ) -> Result<u64, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query(
+            r#"
+            DELETE FROM sbtc_signer.bitcoin_tx_bodies
+            WHERE block_height < $1
+            "#,
+        )
+        .bind(min_block_height)
+        .execute(executor)
+        .await
+        .map(|result| result.rows_affected())
+        .map_err(Error::SqlxQuery)
     }
 
-    async fn write_bitcoin_transactions(&self, txs: Vec<model::BitcoinTxRef>) -> Result<(), Error> {
-        PgWrite::write_bitcoin_transactions(self.get_connection().await?.as_mut(), txs).await
+    async fn write_emily_imported_deposit<'e, E>(
+        executor: &'e mut E,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+    ) -> Result<WriteOutcome, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query(
+            "INSERT INTO sbtc_signer.emily_imported_deposits (txid, output_index)
+            VALUES ($1, $2)
+            ON CONFLICT DO NOTHING",
+        )
+        .bind(txid)
+        .bind(i32::try_from(output_index).map_err(Error::ConversionDatabaseInt)?)
+        .execute(executor)
+        .await
+        .map(|result| write_outcome(&result, "emily_imported_deposits"))
+        .map_err(Error::SqlxQuery)
     }
 
-    async fn write_stacks_block_headers(&self, headers: &TenureBlockHeaders) -> Result<(), Error> {
-        PgWrite::write_stacks_block_headers(self.get_connection().await?.as_mut(), headers).await
+    async fn write_emily_imported_withdrawal<'e, E>(
+        executor: &'e mut E,
+        id: &model::QualifiedRequestId,
+    ) -> Result<WriteOutcome, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query(
+            "INSERT INTO sbtc_signer.emily_imported_withdrawals (request_id, block_hash)
+            VALUES ($1, $2)
+            ON CONFLICT DO NOTHING",
+        )
+        .bind(i64::try_from(id.request_id).map_err(Error::ConversionDatabaseInt)?)
+        .bind(id.block_hash)
+        .execute(executor)
+        .await
+        .map(|result| write_outcome(&result, "emily_imported_withdrawals"))
+        .map_err(Error::SqlxQuery)
     }
 
-    async fn write_encrypted_dkg_shares(
-        &self,
-        shares: &model::EncryptedDkgShares,
-    ) -> Result<(), Error> {
-        PgWrite::write_encrypted_dkg_shares(self.get_connection().await?.as_mut(), shares).await
+    async fn update_peer_version<'e, E>(
+        executor: &'e mut E,
+        peer_id: &libp2p::PeerId,
+        agent_version: &str,
+        protocol_version: u32,
+    ) -> Result<(), Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query(
+            r#"
+            INSERT INTO sbtc_signer.p2p_peer_versions (
+                peer_id
+              , agent_version
+              , protocol_version
+            )
+            VALUES ($1, $2, $3)
+            ON CONFLICT (peer_id) DO UPDATE SET
+                agent_version = EXCLUDED.agent_version
+              , protocol_version = EXCLUDED.protocol_version
+              , updated_at = NOW()
+            "#,
+        )
+        .bind(DbPeerId::from(*peer_id))
+        .bind(agent_version)
+        .bind(i32::try_from(protocol_version).map_err(Error::ConversionDatabaseInt)?)
+        .execute(executor)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        Ok(())
     }
 
-    async fn write_rotate_keys_transaction(
-        &self,
-        key_rotation: &model::KeyRotationEvent,
-    ) -> Result<(), Error> {
-        PgWrite::write_rotate_keys_transaction(self.get_connection().await?.as_mut(), key_rotation)
-            .await
+    async fn write_peer_ban<'e, E>(
+        executor: &'e mut E,
+        ban: &model::P2PPeerBan,
+    ) -> Result<(), Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query(
+            r#"
+            INSERT INTO sbtc_signer.p2p_peer_bans (
+                peer_id
+              , reason
+              , score
+              , banned_at
+              , expires_at
+            )
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (peer_id) DO UPDATE SET
+                reason = EXCLUDED.reason
+              , score = EXCLUDED.score
+              , banned_at = EXCLUDED.banned_at
+              , expires_at = EXCLUDED.expires_at
+            "#,
+        )
+        .bind(ban.peer_id)
+        .bind(&ban.reason)
+        .bind(i32::try_from(ban.score).map_err(Error::ConversionDatabaseInt)?)
+        .bind(ban.banned_at)
+        .bind(ban.expires_at)
+        .execute(executor)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        Ok(())
     }
 
-    async fn write_completed_deposit_event(
-        &self,
-        event: &CompletedDepositEvent,
+    async fn delete_peer_ban<'e, E>(
+        executor: &'e mut E,
+        peer_id: &libp2p::PeerId,
+    ) -> Result<(), Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query(
+            r#"
+            DELETE FROM sbtc_signer.p2p_peer_bans
+            WHERE peer_id = $1
+            "#,
+        )
+        .bind(DbPeerId::from(*peer_id))
+        .execute(executor)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        Ok(())
+    }
+
+    async fn write_dkg_verification_round<'e, E>(
+        executor: &'e mut E,
+        round: &model::DkgVerificationRound,
+    ) -> Result<(), Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query(
+            r#"
+            INSERT INTO sbtc_signer.dkg_verification_rounds (
+                aggregate_key
+              , bitcoin_block_hash
+              , bitcoin_block_height
+              , status
+              , last_message_type
+            )
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (aggregate_key, bitcoin_block_hash) DO UPDATE SET
+                status = EXCLUDED.status
+              , last_message_type = COALESCE(
+                    EXCLUDED.last_message_type
+                  , dkg_verification_rounds.last_message_type
+                )
+              , updated_at = NOW()
+            "#,
+        )
+        .bind(round.aggregate_key)
+        .bind(round.bitcoin_block_hash)
+        .bind(round.bitcoin_block_height)
+        .bind(round.status)
+        .bind(&round.last_message_type)
+        .execute(executor)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        Ok(())
+    }
+
+    async fn write_stacks_event_dead_letter<'e, E>(
+        executor: &'e mut E,
+        letter: &model::StacksEventDeadLetter,
+    ) -> Result<(), Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query(
+            r#"
+            INSERT INTO sbtc_signer.stacks_event_dead_letters (
+                block_hash
+              , txid
+              , event_index
+              , raw_value
+              , error
+            )
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (block_hash, txid, event_index) DO UPDATE SET
+                error = EXCLUDED.error
+              , attempts = stacks_event_dead_letters.attempts + 1
+            "#,
+        )
+        .bind(letter.block_hash)
+        .bind(letter.txid)
+        .bind(i32::try_from(letter.event_index).map_err(Error::ConversionDatabaseInt)?)
+        .bind(&letter.raw_value)
+        .bind(&letter.error)
+        .execute(executor)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        Ok(())
+    }
+
+    async fn resolve_stacks_event_dead_letter<'e, E>(
+        executor: &'e mut E,
+        block_hash: &model::StacksBlockHash,
+        txid: &model::StacksTxId,
+        event_index: u32,
+    ) -> Result<(), Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query(
+            r#"
+            UPDATE sbtc_signer.stacks_event_dead_letters
+            SET resolved_at = NOW()
+            WHERE block_hash = $1
+              AND txid = $2
+              AND event_index = $3
+              AND resolved_at IS NULL
+            "#,
+        )
+        .bind(block_hash)
+        .bind(txid)
+        .bind(i32::try_from(event_index).map_err(Error::ConversionDatabaseInt)?)
+        .execute(executor)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        Ok(())
+    }
+
+    async fn write_signed_sweep_transaction<'e, E>(
+        executor: &'e mut E,
+        sweep: &model::SignedSweepTransaction,
+    ) -> Result<WriteOutcome, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query(
+            r#"
+            INSERT INTO sbtc_signer.signed_sweep_transactions (
+                txid
+              , tx
+              , bitcoin_chain_tip
+            )
+            VALUES ($1, $2, $3)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(sweep.txid)
+        .bind(bitcoin::consensus::serialize(&sweep.tx))
+        .bind(sweep.bitcoin_chain_tip)
+        .execute(executor)
+        .await
+        .map(|result| write_outcome(&result, "signed_sweep_transactions"))
+        .map_err(Error::SqlxQuery)
+    }
+
+    async fn delete_signed_sweep_transactions<'e, E>(
+        executor: &'e mut E,
+        txids: &[model::BitcoinTxId],
+    ) -> Result<(), Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        if txids.is_empty() {
+            return Ok(());
+        }
+
+        sqlx::query(
+            r#"
+            DELETE FROM sbtc_signer.signed_sweep_transactions
+            WHERE txid = ANY($1)
+            "#,
+        )
+        .bind(txids)
+        .execute(executor)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        Ok(())
+    }
+
+    async fn write_sweep_fee_discrepancy<'e, E>(
+        executor: &'e mut E,
+        discrepancy: &model::SweepFeeDiscrepancy,
+    ) -> Result<WriteOutcome, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query(
+            r#"
+            INSERT INTO sbtc_signer.sweep_fee_discrepancies (
+                txid
+              , assessed_fee
+              , actual_fee
+              , assessed_vsize
+              , actual_vsize
+            )
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(discrepancy.txid)
+        .bind(i64::try_from(discrepancy.assessed_fee).map_err(Error::ConversionDatabaseInt)?)
+        .bind(i64::try_from(discrepancy.actual_fee).map_err(Error::ConversionDatabaseInt)?)
+        .bind(i64::try_from(discrepancy.assessed_vsize).map_err(Error::ConversionDatabaseInt)?)
+        .bind(i64::try_from(discrepancy.actual_vsize).map_err(Error::ConversionDatabaseInt)?)
+        .execute(executor)
+        .await
+        .map(|result| write_outcome(&result, "sweep_fee_discrepancies"))
+        .map_err(Error::SqlxQuery)
+    }
+
+    async fn write_key_rotation_attestation<'e, E>(
+        executor: &'e mut E,
+        attestation: &model::KeyRotationAttestation,
+    ) -> Result<WriteOutcome, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query(
+            r#"
+            INSERT INTO sbtc_signer.key_rotation_attestations (
+                aggregate_key
+              , signer_public_key
+              , signer_set_hash
+              , signatures_required
+              , signature
+            )
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(attestation.aggregate_key)
+        .bind(attestation.signer_public_key)
+        .bind(&attestation.signer_set_hash)
+        .bind(i32::from(attestation.signatures_required))
+        .bind(&attestation.signature)
+        .execute(executor)
+        .await
+        .map(|result| write_outcome(&result, "key_rotation_attestations"))
+        .map_err(Error::SqlxQuery)
+    }
+
+    async fn write_sweep_package<'e, E>(
+        executor: &'e mut E,
+        package: &model::SweepPackage,
+    ) -> Result<(), Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        let last_fees_total = package
+            .last_fees
+            .map(|fees| i64::try_from(fees.total))
+            .transpose()
+            .map_err(Error::ConversionDatabaseInt)?;
+        let last_fees_vsize = package
+            .last_fees
+            .map(|fees| i64::try_from(fees.vsize()))
+            .transpose()
+            .map_err(Error::ConversionDatabaseInt)?;
+
+        sqlx::query(
+            r#"
+            WITH deleted AS (
+                DELETE FROM sbtc_signer.sweep_packages
+                WHERE bitcoin_chain_tip <> $1
+            )
+            INSERT INTO sbtc_signer.sweep_packages (
+                bitcoin_chain_tip
+              , presign_request
+              , last_fees_total
+              , last_fees_vsize
+              , broadcast_txids
+            )
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (bitcoin_chain_tip) DO UPDATE
+            SET
+                presign_request = EXCLUDED.presign_request
+              , last_fees_total = EXCLUDED.last_fees_total
+              , last_fees_vsize = EXCLUDED.last_fees_vsize
+              , broadcast_txids = EXCLUDED.broadcast_txids
+            "#,
+        )
+        .bind(package.bitcoin_chain_tip)
+        .bind(package.presign_request.clone().encode_to_vec())
+        .bind(last_fees_total)
+        .bind(last_fees_vsize)
+        .bind(package.broadcast_txids.as_slice())
+        .execute(executor)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        Ok(())
+    }
+
+    async fn write_sweep_package_broadcast<'e, E>(
+        executor: &'e mut E,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        txid: &model::BitcoinTxId,
+    ) -> Result<(), Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query(
+            r#"
+            UPDATE sbtc_signer.sweep_packages
+            SET broadcast_txids = array_append(broadcast_txids, $2)
+            WHERE bitcoin_chain_tip = $1
+              AND NOT ($2 = ANY(broadcast_txids))
+            "#,
+        )
+        .bind(bitcoin_chain_tip)
+        .bind(txid)
+        .execute(executor)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        Ok(())
+    }
+
+    async fn write_deposit_request_timestamp<'e, E>(
+        executor: &'e mut E,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+        stage: model::RequestLifecycleStage,
+    ) -> Result<(), Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        let (first_seen_block, decided, swept, completed) = stage_columns(stage);
+        sqlx::query(
+            r#"
+            INSERT INTO sbtc_signer.deposit_request_timestamps AS t (
+                txid
+              , output_index
+              , first_seen_block
+              , decided_at
+              , swept_at
+              , completed_at
+            )
+            VALUES (
+                $1
+              , $2
+              , $3
+              , CASE WHEN $4 THEN NOW() END
+              , CASE WHEN $5 THEN NOW() END
+              , CASE WHEN $6 THEN NOW() END
+            )
+            ON CONFLICT (txid, output_index) DO UPDATE
+            SET
+                first_seen_block = COALESCE(t.first_seen_block, EXCLUDED.first_seen_block)
+              , decided_at = COALESCE(t.decided_at, EXCLUDED.decided_at)
+              , swept_at = COALESCE(t.swept_at, EXCLUDED.swept_at)
+              , completed_at = COALESCE(t.completed_at, EXCLUDED.completed_at)
+            "#,
+        )
+        .bind(txid)
+        .bind(i32::try_from(output_index).map_err(Error::ConversionDatabaseInt)?)
+        .bind(first_seen_block)
+        .bind(decided)
+        .bind(swept)
+        .bind(completed)
+        .execute(executor)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        Ok(())
+    }
+
+    async fn write_withdrawal_request_timestamp<'e, E>(
+        executor: &'e mut E,
+        request_id: u64,
+        stage: model::RequestLifecycleStage,
+    ) -> Result<(), Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        let (first_seen_block, decided, swept, completed) = stage_columns(stage);
+        sqlx::query(
+            r#"
+            INSERT INTO sbtc_signer.withdrawal_request_timestamps AS t (
+                request_id
+              , first_seen_block
+              , decided_at
+              , swept_at
+              , completed_at
+            )
+            VALUES (
+                $1
+              , $2
+              , CASE WHEN $3 THEN NOW() END
+              , CASE WHEN $4 THEN NOW() END
+              , CASE WHEN $5 THEN NOW() END
+            )
+            ON CONFLICT (request_id) DO UPDATE
+            SET
+                first_seen_block = COALESCE(t.first_seen_block, EXCLUDED.first_seen_block)
+              , decided_at = COALESCE(t.decided_at, EXCLUDED.decided_at)
+              , swept_at = COALESCE(t.swept_at, EXCLUDED.swept_at)
+              , completed_at = COALESCE(t.completed_at, EXCLUDED.completed_at)
+            "#,
+        )
+        .bind(i64::try_from(request_id).map_err(Error::ConversionDatabaseInt)?)
+        .bind(first_seen_block)
+        .bind(decided)
+        .bind(swept)
+        .bind(completed)
+        .execute(executor)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        Ok(())
+    }
+
+    async fn write_bitcoin_backfill_progress<'e, E>(
+        executor: &'e mut E,
+        progress: &model::BitcoinBackfillProgress,
+    ) -> Result<(), Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query(
+            r#"
+            WITH deleted AS (
+                DELETE FROM sbtc_signer.bitcoin_backfill_progress
+                WHERE target_block_hash <> $1
+            )
+            INSERT INTO sbtc_signer.bitcoin_backfill_progress (
+                target_block_hash
+              , target_block_height
+              , start_height
+              , processed_height
+              , started_at
+              , updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (target_block_hash) DO UPDATE
+            SET
+                start_height = EXCLUDED.start_height
+              , processed_height = EXCLUDED.processed_height
+              , started_at = EXCLUDED.started_at
+              , updated_at = EXCLUDED.updated_at
+            "#,
+        )
+        .bind(progress.target_block_hash)
+        .bind(progress.target_block_height)
+        .bind(progress.start_height)
+        .bind(progress.processed_height)
+        .bind(progress.started_at)
+        .bind(progress.updated_at)
+        .execute(executor)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        Ok(())
+    }
+
+    async fn write_coordinator_takeover<'e, E>(
+        executor: &'e mut E,
+        takeover: &model::CoordinatorTakeover,
+    ) -> Result<WriteOutcome, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query(
+            r#"
+            INSERT INTO sbtc_signer.coordinator_takeovers (
+                bitcoin_chain_tip
+              , coordinator_public_key
+              , deputy_public_key
+            )
+            VALUES ($1, $2, $3)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(takeover.bitcoin_chain_tip)
+        .bind(takeover.coordinator_public_key)
+        .bind(takeover.deputy_public_key)
+        .execute(executor)
+        .await
+        .map(|result| write_outcome(&result, "coordinator_takeovers"))
+        .map_err(Error::SqlxQuery)
+    }
+
+    async fn write_peer_address_record<'e, E>(
+        executor: &'e mut E,
+        record: &model::P2PPeerAddressRecord,
+    ) -> Result<bool, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO sbtc_signer.p2p_peer_address_records (
+                public_key
+              , peer_id
+              , addresses
+              , expires_at
+            )
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (public_key) DO UPDATE SET
+                peer_id = EXCLUDED.peer_id
+              , addresses = EXCLUDED.addresses
+              , expires_at = EXCLUDED.expires_at
+              , updated_at = NOW()
+            WHERE p2p_peer_address_records.expires_at < EXCLUDED.expires_at
+            "#,
+        )
+        .bind(record.public_key)
+        .bind(record.peer_id)
+        .bind(&record.addresses)
+        .bind(record.expires_at)
+        .execute(executor)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn write_request_override<'e, E>(
+        executor: &'e mut E,
+        request_override: &model::NewRequestOverride,
+        column_key: Option<&ColumnEncryptionKey>,
+    ) -> Result<model::RequestOverride, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        let (deposit_txid, deposit_output_index, withdrawal_request_id) =
+            override_target_columns(&request_override.target)?;
+
+        let mut written = sqlx::query_as::<_, model::RequestOverride>(
+            r#"
+            INSERT INTO sbtc_signer.request_overrides (
+                deposit_txid
+              , deposit_output_index
+              , withdrawal_request_id
+              , directive
+              , reason
+              , expires_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING
+                id
+              , deposit_txid
+              , deposit_output_index
+              , withdrawal_request_id
+              , directive
+              , reason
+              , expires_at
+              , created_at
+              , revoked_at
+            "#,
+        )
+        .bind(deposit_txid)
+        .bind(deposit_output_index)
+        .bind(withdrawal_request_id)
+        .bind(request_override.directive)
+        .bind(encrypt_column(column_key, &request_override.reason)?)
+        .bind(request_override.expires_at)
+        .fetch_one(executor)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        written.reason = decrypt_column(column_key, written.reason)?;
+        Ok(written)
+    }
+
+    async fn revoke_request_override<'e, E>(executor: &'e mut E, id: i64) -> Result<bool, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        let result = sqlx::query(
+            r#"
+            UPDATE sbtc_signer.request_overrides
+            SET revoked_at = NOW()
+            WHERE id = $1
+              AND revoked_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .execute(executor)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn write_inflight_contract_call<'e, E>(
+        executor: &'e mut E,
+        contract_call: &model::InflightContractCall,
+    ) -> Result<(), Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        let columns = ContractCallTargetColumns::new(&contract_call.target)?;
+
+        // Each kind of request has its own partial unique index, so the
+        // conflict target depends on the kind of request.
+        let query = match contract_call.target {
+            model::ContractCallTarget::Deposit { .. } => {
+                r#"
+                INSERT INTO sbtc_signer.inflight_contract_calls (
+                    deposit_txid
+                  , deposit_output_index
+                  , withdrawal_request_id
+                  , withdrawal_txid
+                  , withdrawal_block_hash
+                  , txid
+                  , bitcoin_block_height
+                  , bitcoin_block_hash
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                ON CONFLICT (deposit_txid, deposit_output_index)
+                    WHERE deposit_txid IS NOT NULL
+                DO UPDATE SET
+                    txid = EXCLUDED.txid
+                  , bitcoin_block_height = EXCLUDED.bitcoin_block_height
+                  , bitcoin_block_hash = EXCLUDED.bitcoin_block_hash
+                  , created_at = NOW()
+                "#
+            }
+            model::ContractCallTarget::Withdrawal { .. } => {
+                r#"
+                INSERT INTO sbtc_signer.inflight_contract_calls (
+                    deposit_txid
+                  , deposit_output_index
+                  , withdrawal_request_id
+                  , withdrawal_txid
+                  , withdrawal_block_hash
+                  , txid
+                  , bitcoin_block_height
+                  , bitcoin_block_hash
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                ON CONFLICT (withdrawal_request_id, withdrawal_txid, withdrawal_block_hash)
+                    WHERE withdrawal_request_id IS NOT NULL
+                DO UPDATE SET
+                    txid = EXCLUDED.txid
+                  , bitcoin_block_height = EXCLUDED.bitcoin_block_height
+                  , bitcoin_block_hash = EXCLUDED.bitcoin_block_hash
+                  , created_at = NOW()
+                "#
+            }
+        };
+
+        sqlx::query(query)
+            .bind(columns.deposit_txid)
+            .bind(columns.deposit_output_index)
+            .bind(columns.withdrawal_request_id)
+            .bind(columns.withdrawal_txid)
+            .bind(columns.withdrawal_block_hash)
+            .bind(contract_call.txid)
+            .bind(contract_call.bitcoin_block_height)
+            .bind(contract_call.bitcoin_block_hash)
+            .execute(executor)
+            .await
+            .map_err(Error::SqlxQuery)?;
+
+        Ok(())
+    }
+
+    async fn delete_inflight_contract_call<'e, E>(
+        executor: &'e mut E,
+        target: &model::ContractCallTarget,
+    ) -> Result<bool, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        let columns = ContractCallTargetColumns::new(target)?;
+
+        let result = sqlx::query(
+            r#"
+            DELETE FROM sbtc_signer.inflight_contract_calls
+            WHERE (deposit_txid = $1 AND deposit_output_index = $2)
+               OR (withdrawal_request_id = $3
+                   AND withdrawal_txid = $4
+                   AND withdrawal_block_hash = $5)
+            "#,
+        )
+        .bind(columns.deposit_txid)
+        .bind(columns.deposit_output_index)
+        .bind(columns.withdrawal_request_id)
+        .bind(columns.withdrawal_txid)
+        .bind(columns.withdrawal_block_hash)
+        .execute(executor)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn write_withdrawal_output_labels<'e, E>(
+        executor: &'e mut E,
+        labels: &[model::WithdrawalOutputLabel],
+    ) -> Result<(), Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        let (script_pubkeys, names): (Vec<_>, Vec<_>) = labels
+            .iter()
+            .map(|label| (label.script_pubkey.clone(), label.label.as_str()))
+            .unzip();
+
+        // All parts of the statement see the same snapshot of the
+        // database, so the withdrawal outputs are relabeled using the new
+        // labels directly rather than the updated registry.
+        sqlx::query(
+            r#"
+            WITH new_labels AS (
+                SELECT script_pubkey, label
+                FROM UNNEST($1::BYTEA[], $2::TEXT[]) AS t(script_pubkey, label)
+            )
+            , deleted AS (
+                DELETE FROM sbtc_signer.withdrawal_output_labels
+                WHERE script_pubkey NOT IN (SELECT script_pubkey FROM new_labels)
+            )
+            , upserted AS (
+                INSERT INTO sbtc_signer.withdrawal_output_labels (script_pubkey, label)
+                SELECT script_pubkey, label
+                FROM new_labels
+                ON CONFLICT (script_pubkey) DO UPDATE
+                SET label = EXCLUDED.label
+            )
+            UPDATE sbtc_signer.bitcoin_withdrawal_tx_outputs AS bwo
+            SET label = nl.label
+            FROM sbtc_signer.bitcoin_tx_outputs AS bto
+            LEFT JOIN new_labels AS nl
+              ON nl.script_pubkey = bto.script_pubkey
+            WHERE bto.txid = bwo.txid
+              AND bto.output_index = bwo.output_index
+              AND bwo.label IS DISTINCT FROM nl.label
+            "#,
+        )
+        .bind(script_pubkeys)
+        .bind(names)
+        .execute(executor)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        Ok(())
+    }
+
+    async fn write_spilled_state_machine<'e, E>(
+        executor: &'e mut E,
+        state_machine: &model::SpilledStateMachine,
+    ) -> Result<(), Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query(
+            r#"
+            INSERT INTO sbtc_signer.spilled_state_machines (
+                state_machine_id
+              , encrypted_state
+              , bitcoin_block_height
+            )
+            VALUES ($1, $2, $3)
+            ON CONFLICT (state_machine_id) DO UPDATE
+            SET encrypted_state = EXCLUDED.encrypted_state
+              , bitcoin_block_height = EXCLUDED.bitcoin_block_height
+              , created_at = NOW()
+            "#,
+        )
+        .bind(&state_machine.state_machine_id)
+        .bind(&state_machine.encrypted_state)
+        .bind(state_machine.bitcoin_block_height)
+        .execute(executor)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        Ok(())
+    }
+
+    async fn take_spilled_state_machine<'e, E>(
+        executor: &'e mut E,
+        state_machine_id: &str,
+    ) -> Result<Option<model::SpilledStateMachine>, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query_as::<_, model::SpilledStateMachine>(
+            r#"
+            DELETE FROM sbtc_signer.spilled_state_machines
+            WHERE state_machine_id = $1
+            RETURNING
+                state_machine_id
+              , encrypted_state
+              , bitcoin_block_height
+            "#,
+        )
+        .bind(state_machine_id)
+        .fetch_optional(executor)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
+    async fn prune_spilled_state_machines<'e, E>(
+        executor: &'e mut E,
+        min_block_height: model::BitcoinBlockHeight,
+    ) -> Result<u64, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query(
+            r#"
+            DELETE FROM sbtc_signer.spilled_state_machines
+            WHERE bitcoin_block_height < $1
+            "#,
+        )
+        .bind(min_block_height)
+        .execute(executor)
+        .await
+        .map(|result| result.rows_affected())
+        .map_err(Error::SqlxQuery)
+    }
+
+    async fn write_bitcoin_block_header<'e, E>(
+        executor: &'e mut E,
+        header: &bitcoin::block::Header,
+    ) -> Result<WriteOutcome, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        let block_hash = model::BitcoinBlockHash::from(header.block_hash());
+        sqlx::query(
+            r#"
+            INSERT INTO sbtc_signer.bitcoin_block_headers (block_hash, header)
+            VALUES ($1, $2)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(block_hash)
+        .bind(bitcoin::consensus::serialize(header))
+        .execute(executor)
+        .await
+        .map(|result| write_outcome(&result, "bitcoin_block_headers"))
+        .map_err(Error::SqlxQuery)
+    }
+
+    async fn write_bitcoin_tx_merkle_proofs<'e, E>(
+        executor: &'e mut E,
+        proofs: &[model::BitcoinTxMerkleProof],
+    ) -> Result<(), Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        if proofs.is_empty() {
+            return Ok(());
+        }
+
+        let mut txids = Vec::with_capacity(proofs.len());
+        let mut block_hashes = Vec::with_capacity(proofs.len());
+        let mut block_heights = Vec::with_capacity(proofs.len());
+        let mut merkle_blocks = Vec::with_capacity(proofs.len());
+
+        for proof in proofs {
+            txids.push(proof.txid);
+            block_hashes.push(proof.block_hash);
+            block_heights.push(proof.block_height);
+            merkle_blocks.push(bitcoin::consensus::serialize(&proof.merkle_block));
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO sbtc_signer.bitcoin_tx_merkle_proofs
+                (txid, block_hash, block_height, merkle_block)
+            SELECT txid, block_hash, block_height, merkle_block
+            FROM UNNEST($1::BYTEA[], $2::BYTEA[], $3::BIGINT[], $4::BYTEA[])
+                AS proofs(txid, block_hash, block_height, merkle_block)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(&txids)
+        .bind(&block_hashes)
+        .bind(&block_heights)
+        .bind(&merkle_blocks)
+        .execute(executor)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        Ok(())
+    }
+
+    async fn write_multisig_stacks_transaction<'e, E>(
+        executor: &'e mut E,
+        tx: &model::MultisigStacksTransaction,
+    ) -> Result<(), Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query(
+            r#"
+            INSERT INTO sbtc_signer.multisig_stacks_transactions
+                (txid, block_hash, signer_hash, non_signers)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(tx.txid)
+        .bind(tx.block_hash)
+        .bind(tx.signer_hash)
+        .bind(&tx.non_signers)
+        .execute(executor)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        Ok(())
+    }
+
+    async fn write_scanned_stacks_block<'e, E>(
+        executor: &'e mut E,
+        block_hash: &model::StacksBlockHash,
+    ) -> Result<(), Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query(
+            r#"
+            INSERT INTO sbtc_signer.scanned_stacks_blocks (block_hash)
+            VALUES ($1)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(block_hash)
+        .execute(executor)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        Ok(())
+    }
+}
+
+/// The values of the columns of the request timestamp tables that record
+/// the given lifecycle stage: the first seen block, and whether the
+/// decided, swept and completed timestamps should be set.
+fn stage_columns(
+    stage: model::RequestLifecycleStage,
+) -> (Option<model::BitcoinBlockHash>, bool, bool, bool) {
+    match stage {
+        model::RequestLifecycleStage::Seen { first_seen_block } => {
+            (first_seen_block, false, false, false)
+        }
+        model::RequestLifecycleStage::Decided => (None, true, false, false),
+        model::RequestLifecycleStage::Swept => (None, false, true, false),
+        model::RequestLifecycleStage::Completed => (None, false, false, true),
+    }
+}
+
+/// The values of the columns of the request overrides table that identify
+/// the request that an override applies to: the deposit transaction ID
+/// and output index, and the withdrawal request ID.
+pub(super) fn override_target_columns(
+    target: &model::OverrideTarget,
+) -> Result<(Option<model::BitcoinTxId>, Option<i32>, Option<i64>), Error> {
+    match *target {
+        model::OverrideTarget::Deposit { txid, output_index } => {
+            let output_index = i32::try_from(output_index).map_err(Error::ConversionDatabaseInt)?;
+            Ok((Some(txid), Some(output_index), None))
+        }
+        model::OverrideTarget::Withdrawal { request_id } => {
+            let request_id = i64::try_from(request_id).map_err(Error::ConversionDatabaseInt)?;
+            Ok((None, None, Some(request_id)))
+        }
+    }
+}
+
+/// The values of the columns of the in-flight contract calls table that
+/// identify the request that a contract call fulfills.
+#[derive(Debug, Default)]
+pub(super) struct ContractCallTargetColumns {
+    /// The transaction ID of the deposit request.
+    pub deposit_txid: Option<model::BitcoinTxId>,
+    /// The output index of the deposit request.
+    pub deposit_output_index: Option<i32>,
+    /// The request ID of the withdrawal request.
+    pub withdrawal_request_id: Option<i64>,
+    /// The stacks transaction ID that created the withdrawal request.
+    pub withdrawal_txid: Option<model::StacksTxId>,
+    /// The stacks block that created the withdrawal request.
+    pub withdrawal_block_hash: Option<model::StacksBlockHash>,
+}
+
+impl ContractCallTargetColumns {
+    /// Return the column values for the given request.
+    pub fn new(target: &model::ContractCallTarget) -> Result<Self, Error> {
+        match target {
+            model::ContractCallTarget::Deposit { txid, output_index } => Ok(Self {
+                deposit_txid: Some(*txid),
+                deposit_output_index: Some(
+                    i32::try_from(*output_index).map_err(Error::ConversionDatabaseInt)?,
+                ),
+                ..Default::default()
+            }),
+            model::ContractCallTarget::Withdrawal { id } => Ok(Self {
+                withdrawal_request_id: Some(
+                    i64::try_from(id.request_id).map_err(Error::ConversionDatabaseInt)?,
+                ),
+                withdrawal_txid: Some(id.txid),
+                withdrawal_block_hash: Some(id.block_hash),
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+/// Return the outcome of an `INSERT ... ON CONFLICT DO NOTHING` of a
+/// single row into the given table, counting a conflict if the row was
+/// already there.
+fn write_outcome(result: &PgQueryResult, table: &'static str) -> WriteOutcome {
+    let outcome = WriteOutcome::from(result.rows_affected() > 0);
+    if !outcome.is_inserted() {
+        Metrics::increment_write_conflicts(table);
+    }
+    outcome
+}
+
+/// The values of the `funding_fees_total` and `funding_fees_vsize`
+/// columns of the deposit requests table for the given fees.
+fn funding_fees_columns(fees: Option<Fees>) -> Result<(Option<i64>, Option<i64>), Error> {
+    let Some(fees) = fees else {
+        return Ok((None, None));
+    };
+    let total = i64::try_from(fees.total).map_err(Error::ConversionDatabaseInt)?;
+    let vsize = i64::try_from(fees.vsize()).map_err(Error::ConversionDatabaseInt)?;
+    Ok((Some(total), Some(vsize)))
+}
+
+impl DbWrite for PgStore {
+    async fn write_bitcoin_block(
+        &self,
+        block: &model::BitcoinBlock,
+    ) -> Result<WriteOutcome, Error> {
+        PgWrite::write_bitcoin_block(self.get_connection().await?.as_mut(), block).await
+    }
+
+    #[cfg(any(test, feature = "testing"))]
+    async fn write_stacks_block(&self, block: &model::StacksBlock) -> Result<WriteOutcome, Error> {
+        PgWrite::write_stacks_block(self.get_connection().await?.as_mut(), block).await
+    }
+
+    async fn write_deposit_request(
+        &self,
+        deposit_request: &model::DepositRequest,
+    ) -> Result<WriteOutcome, Error> {
+        PgWrite::write_deposit_request(self.get_connection().await?.as_mut(), deposit_request).await
+    }
+
+    async fn write_deposit_requests(
+        &self,
+        deposit_requests: Vec<model::DepositRequest>,
     ) -> Result<(), Error> {
+        PgWrite::write_deposit_requests(self.get_connection().await?.as_mut(), deposit_requests)
+            .await
+    }
+
+    async fn write_withdrawal_request(
+        &self,
+        request: &model::WithdrawalRequest,
+    ) -> Result<WriteOutcome, Error> {
+        PgWrite::write_withdrawal_request(self.get_connection().await?.as_mut(), request).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn write_deposit_signer_decision(
+        &self,
+        decision: &model::DepositSigner,
+    ) -> Result<WriteOutcome, Error> {
+        PgWrite::write_deposit_signer_decision(self.get_connection().await?.as_mut(), decision)
+            .await
+    }
+
+    async fn write_withdrawal_signer_decision(
+        &self,
+        decision: &model::WithdrawalSigner,
+    ) -> Result<WriteOutcome, Error> {
+        PgWrite::write_withdrawal_signer_decision(
+            self.get_connection().await?.as_mut(),
+            decision,
+            self.column_key(),
+        )
+        .await
+    }
+
+    async fn write_bitcoin_transaction(
+        &self,
+        tx_ref: &model::BitcoinTxRef,
+    ) -> Result<WriteOutcome, Error> {
+        PgWrite::write_bitcoin_transaction(self.get_connection().await?.as_mut(), tx_ref).await
+    }
+
+    async fn write_bitcoin_transactions(&self, txs: Vec<model::BitcoinTxRef>) -> Result<(), Error> {
+        PgWrite::write_bitcoin_transactions(self.get_connection().await?.as_mut(), txs).await
+    }
+
+    async fn write_stacks_block_headers(&self, headers: &TenureBlockHeaders) -> Result<(), Error> {
+        PgWrite::write_stacks_block_headers(self.get_connection().await?.as_mut(), headers).await
+    }
+
+    async fn write_encrypted_dkg_shares(
+        &self,
+        shares: &model::EncryptedDkgShares,
+    ) -> Result<WriteOutcome, Error> {
+        PgWrite::write_encrypted_dkg_shares(self.get_connection().await?.as_mut(), shares).await
+    }
+
+    async fn write_rotate_keys_transaction(
+        &self,
+        key_rotation: &model::KeyRotationEvent,
+    ) -> Result<WriteOutcome, Error> {
+        PgWrite::write_rotate_keys_transaction(self.get_connection().await?.as_mut(), key_rotation)
+            .await
+    }
+
+    async fn write_completed_deposit_event(
+        &self,
+        event: &CompletedDepositEvent,
+    ) -> Result<WriteOutcome, Error> {
         PgWrite::write_completed_deposit_event(self.get_connection().await?.as_mut(), event).await
     }
 
-    async fn write_withdrawal_accept_event(
+    async fn write_withdrawal_accept_event(
+        &self,
+        event: &WithdrawalAcceptEvent,
+    ) -> Result<WriteOutcome, Error> {
+        PgWrite::write_withdrawal_accept_event(self.get_connection().await?.as_mut(), event).await
+    }
+
+    async fn write_withdrawal_reject_event(
+        &self,
+        event: &WithdrawalRejectEvent,
+    ) -> Result<WriteOutcome, Error> {
+        PgWrite::write_withdrawal_reject_event(self.get_connection().await?.as_mut(), event).await
+    }
+
+    async fn write_tx_output(&self, output: &model::TxOutput) -> Result<WriteOutcome, Error> {
+        PgWrite::write_tx_output(self.get_connection().await?.as_mut(), output).await
+    }
+
+    async fn write_withdrawal_tx_output(
+        &self,
+        output: &model::WithdrawalTxOutput,
+    ) -> Result<WriteOutcome, Error> {
+        PgWrite::write_withdrawal_tx_output(self.get_connection().await?.as_mut(), output).await
+    }
+
+    async fn write_tx_prevout(&self, prevout: &model::TxPrevout) -> Result<WriteOutcome, Error> {
+        PgWrite::write_tx_prevout(self.get_connection().await?.as_mut(), prevout).await
+    }
+
+    async fn write_bitcoin_txs_sighashes(
+        &self,
+        sighashes: &[model::BitcoinTxSigHash],
+    ) -> Result<(), Error> {
+        PgWrite::write_bitcoin_txs_sighashes(self.get_connection().await?.as_mut(), sighashes).await
+    }
+
+    async fn write_bitcoin_withdrawals_outputs(
+        &self,
+        withdrawal_outputs: &[model::BitcoinWithdrawalOutput],
+    ) -> Result<(), Error> {
+        PgWrite::write_bitcoin_withdrawals_outputs(
+            self.get_connection().await?.as_mut(),
+            withdrawal_outputs,
+        )
+        .await
+    }
+
+    async fn revoke_dkg_shares<X>(&self, aggregate_key: X) -> Result<bool, Error>
+    where
+        X: Into<PublicKeyXOnly>,
+    {
+        PgWrite::revoke_dkg_shares(self.get_connection().await?.as_mut(), aggregate_key).await
+    }
+
+    async fn verify_dkg_shares<X>(&self, aggregate_key: X) -> Result<bool, Error>
+    where
+        X: Into<PublicKeyXOnly>,
+    {
+        PgWrite::verify_dkg_shares(self.get_connection().await?.as_mut(), aggregate_key).await
+    }
+
+    async fn update_peer_connection(
+        &self,
+        pub_key: &PublicKey,
+        peer_id: &libp2p::PeerId,
+        address: libp2p::Multiaddr,
+    ) -> Result<(), Error> {
+        PgWrite::update_peer_connection(
+            self.get_connection().await?.as_mut(),
+            pub_key,
+            peer_id,
+            address,
+        )
+        .await
+    }
+
+    async fn set_canonical_bitcoin_blockchain(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<(), Error> {
+        PgWrite::set_canonical_bitcoin_blockchain(self.get_connection().await?.as_mut(), chain_tip)
+            .await
+    }
+
+    async fn write_sbtc_supply_snapshot(
+        &self,
+        snapshot: &model::SbtcSupplySnapshot,
+    ) -> Result<(), Error> {
+        PgWrite::write_sbtc_supply_snapshot(self.get_connection().await?.as_mut(), snapshot).await
+    }
+
+    async fn write_signer_utxo_violation(
+        &self,
+        violation: &model::SignerUtxoViolation,
+    ) -> Result<WriteOutcome, Error> {
+        PgWrite::write_signer_utxo_violation(self.get_connection().await?.as_mut(), violation).await
+    }
+
+    async fn write_bitcoin_tx_bodies(&self, bodies: &[model::BitcoinTxBody]) -> Result<(), Error> {
+        PgWrite::write_bitcoin_tx_bodies(self.get_connection().await?.as_mut(), bodies).await
+    }
+
+    async fn prune_bitcoin_tx_bodies(
+        &self,
+        min_block_height: model::BitcoinBlockHeight,
+    ) -> Result<u64, Error> {
+        PgWrite::prune_bitcoin_tx_bodies(self.get_connection().await?.as_mut(), min_block_height)
+            .await
+    }
+
+    async fn write_emily_imported_deposit(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+    ) -> Result<WriteOutcome, Error> {
+        PgWrite::write_emily_imported_deposit(
+            self.get_connection().await?.as_mut(),
+            txid,
+            output_index,
+        )
+        .await
+    }
+
+    async fn write_emily_imported_withdrawal(
+        &self,
+        id: &model::QualifiedRequestId,
+    ) -> Result<WriteOutcome, Error> {
+        PgWrite::write_emily_imported_withdrawal(self.get_connection().await?.as_mut(), id).await
+    }
+
+    async fn update_peer_version(
+        &self,
+        peer_id: &libp2p::PeerId,
+        agent_version: &str,
+        protocol_version: u32,
+    ) -> Result<(), Error> {
+        PgWrite::update_peer_version(
+            self.get_connection().await?.as_mut(),
+            peer_id,
+            agent_version,
+            protocol_version,
+        )
+        .await
+    }
+
+    async fn write_peer_ban(&self, ban: &model::P2PPeerBan) -> Result<(), Error> {
+        PgWrite::write_peer_ban(self.get_connection().await?.as_mut(), ban).await
+    }
+
+    async fn delete_peer_ban(&self, peer_id: &libp2p::PeerId) -> Result<(), Error> {
+        PgWrite::delete_peer_ban(self.get_connection().await?.as_mut(), peer_id).await
+    }
+
+    async fn write_dkg_verification_round(
+        &self,
+        round: &model::DkgVerificationRound,
+    ) -> Result<(), Error> {
+        PgWrite::write_dkg_verification_round(self.get_connection().await?.as_mut(), round).await
+    }
+
+    async fn write_stacks_event_dead_letter(
+        &self,
+        letter: &model::StacksEventDeadLetter,
+    ) -> Result<(), Error> {
+        PgWrite::write_stacks_event_dead_letter(self.get_connection().await?.as_mut(), letter).await
+    }
+
+    async fn resolve_stacks_event_dead_letter(
+        &self,
+        block_hash: &model::StacksBlockHash,
+        txid: &model::StacksTxId,
+        event_index: u32,
+    ) -> Result<(), Error> {
+        PgWrite::resolve_stacks_event_dead_letter(
+            self.get_connection().await?.as_mut(),
+            block_hash,
+            txid,
+            event_index,
+        )
+        .await
+    }
+
+    async fn write_signed_sweep_transaction(
+        &self,
+        sweep: &model::SignedSweepTransaction,
+    ) -> Result<WriteOutcome, Error> {
+        PgWrite::write_signed_sweep_transaction(self.get_connection().await?.as_mut(), sweep).await
+    }
+
+    async fn delete_signed_sweep_transactions(
+        &self,
+        txids: &[model::BitcoinTxId],
+    ) -> Result<(), Error> {
+        PgWrite::delete_signed_sweep_transactions(self.get_connection().await?.as_mut(), txids)
+            .await
+    }
+
+    async fn write_sweep_fee_discrepancy(
+        &self,
+        discrepancy: &model::SweepFeeDiscrepancy,
+    ) -> Result<WriteOutcome, Error> {
+        PgWrite::write_sweep_fee_discrepancy(self.get_connection().await?.as_mut(), discrepancy)
+            .await
+    }
+
+    async fn write_key_rotation_attestation(
         &self,
-        event: &WithdrawalAcceptEvent,
+        attestation: &model::KeyRotationAttestation,
+    ) -> Result<WriteOutcome, Error> {
+        PgWrite::write_key_rotation_attestation(self.get_connection().await?.as_mut(), attestation)
+            .await
+    }
+
+    async fn write_sweep_package(&self, package: &model::SweepPackage) -> Result<(), Error> {
+        PgWrite::write_sweep_package(self.get_connection().await?.as_mut(), package).await
+    }
+
+    async fn write_sweep_package_broadcast(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        txid: &model::BitcoinTxId,
     ) -> Result<(), Error> {
-        PgWrite::write_withdrawal_accept_event(self.get_connection().await?.as_mut(), event).await
+        PgWrite::write_sweep_package_broadcast(
+            self.get_connection().await?.as_mut(),
+            bitcoin_chain_tip,
+            txid,
+        )
+        .await
     }
 
-    async fn write_withdrawal_reject_event(
+    async fn write_deposit_request_timestamp(
         &self,
-        event: &WithdrawalRejectEvent,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+        stage: model::RequestLifecycleStage,
     ) -> Result<(), Error> {
-        PgWrite::write_withdrawal_reject_event(self.get_connection().await?.as_mut(), event).await
+        PgWrite::write_deposit_request_timestamp(
+            self.get_connection().await?.as_mut(),
+            txid,
+            output_index,
+            stage,
+        )
+        .await
     }
 
-    async fn write_tx_output(&self, output: &model::TxOutput) -> Result<(), Error> {
-        PgWrite::write_tx_output(self.get_connection().await?.as_mut(), output).await
+    async fn write_withdrawal_request_timestamp(
+        &self,
+        request_id: u64,
+        stage: model::RequestLifecycleStage,
+    ) -> Result<(), Error> {
+        PgWrite::write_withdrawal_request_timestamp(
+            self.get_connection().await?.as_mut(),
+            request_id,
+            stage,
+        )
+        .await
     }
 
-    async fn write_withdrawal_tx_output(
+    async fn write_bitcoin_backfill_progress(
         &self,
-        output: &model::WithdrawalTxOutput,
+        progress: &model::BitcoinBackfillProgress,
     ) -> Result<(), Error> {
-        PgWrite::write_withdrawal_tx_output(self.get_connection().await?.as_mut(), output).await
+        PgWrite::write_bitcoin_backfill_progress(self.get_connection().await?.as_mut(), progress)
+            .await
     }
 
-    async fn write_tx_prevout(&self, prevout: &model::TxPrevout) -> Result<(), Error> {
-        PgWrite::write_tx_prevout(self.get_connection().await?.as_mut(), prevout).await
+    async fn write_coordinator_takeover(
+        &self,
+        takeover: &model::CoordinatorTakeover,
+    ) -> Result<WriteOutcome, Error> {
+        PgWrite::write_coordinator_takeover(self.get_connection().await?.as_mut(), takeover).await
     }
 
-    async fn write_bitcoin_txs_sighashes(
+    async fn write_peer_address_record(
         &self,
-        sighashes: &[model::BitcoinTxSigHash],
-    ) -> Result<(), Error> {
-        PgWrite::write_bitcoin_txs_sighashes(self.get_connection().await?.as_mut(), sighashes).await
+        record: &model::P2PPeerAddressRecord,
+    ) -> Result<bool, Error> {
+        PgWrite::write_peer_address_record(self.get_connection().await?.as_mut(), record).await
     }
 
-    async fn write_bitcoin_withdrawals_outputs(
+    async fn write_request_override(
         &self,
-        withdrawal_outputs: &[model::BitcoinWithdrawalOutput],
-    ) -> Result<(), Error> {
-        PgWrite::write_bitcoin_withdrawals_outputs(
+        request_override: &model::NewRequestOverride,
+    ) -> Result<model::RequestOverride, Error> {
+        PgWrite::write_request_override(
             self.get_connection().await?.as_mut(),
-            withdrawal_outputs,
+            request_override,
+            self.column_key(),
         )
         .await
     }
 
-    async fn revoke_dkg_shares<X>(&self, aggregate_key: X) -> Result<bool, Error>
-    where
-        X: Into<PublicKeyXOnly>,
-    {
-        PgWrite::revoke_dkg_shares(self.get_connection().await?.as_mut(), aggregate_key).await
+    async fn revoke_request_override(&self, id: i64) -> Result<bool, Error> {
+        PgWrite::revoke_request_override(self.get_connection().await?.as_mut(), id).await
     }
 
-    async fn verify_dkg_shares<X>(&self, aggregate_key: X) -> Result<bool, Error>
-    where
-        X: Into<PublicKeyXOnly>,
-    {
-        PgWrite::verify_dkg_shares(self.get_connection().await?.as_mut(), aggregate_key).await
+    async fn write_inflight_contract_call(
+        &self,
+        contract_call: &model::InflightContractCall,
+    ) -> Result<(), Error> {
+        PgWrite::write_inflight_contract_call(self.get_connection().await?.as_mut(), contract_call)
+            .await
     }
 
-    async fn update_peer_connection(
+    async fn delete_inflight_contract_call(
         &self,
-        pub_key: &PublicKey,
-        peer_id: &libp2p::PeerId,
-        address: libp2p::Multiaddr,
+        target: &model::ContractCallTarget,
+    ) -> Result<bool, Error> {
+        PgWrite::delete_inflight_contract_call(self.get_connection().await?.as_mut(), target).await
+    }
+
+    async fn write_withdrawal_output_labels(
+        &self,
+        labels: &[model::WithdrawalOutputLabel],
     ) -> Result<(), Error> {
-        PgWrite::update_peer_connection(
+        PgWrite::write_withdrawal_output_labels(self.get_connection().await?.as_mut(), labels).await
+    }
+
+    async fn write_spilled_state_machine(
+        &self,
+        state_machine: &model::SpilledStateMachine,
+    ) -> Result<(), Error> {
+        PgWrite::write_spilled_state_machine(self.get_connection().await?.as_mut(), state_machine)
+            .await
+    }
+
+    async fn take_spilled_state_machine(
+        &self,
+        state_machine_id: &str,
+    ) -> Result<Option<model::SpilledStateMachine>, Error> {
+        PgWrite::take_spilled_state_machine(self.get_connection().await?.as_mut(), state_machine_id)
+            .await
+    }
+
+    async fn prune_spilled_state_machines(
+        &self,
+        min_block_height: model::BitcoinBlockHeight,
+    ) -> Result<u64, Error> {
+        PgWrite::prune_spilled_state_machines(
             self.get_connection().await?.as_mut(),
-            pub_key,
-            peer_id,
-            address,
+            min_block_height,
         )
         .await
     }
 
-    async fn set_canonical_bitcoin_blockchain(
+    async fn write_bitcoin_block_header(
         &self,
-        chain_tip: &model::BitcoinBlockHash,
+        header: &bitcoin::block::Header,
+    ) -> Result<WriteOutcome, Error> {
+        PgWrite::write_bitcoin_block_header(self.get_connection().await?.as_mut(), header).await
+    }
+
+    async fn write_bitcoin_tx_merkle_proofs(
+        &self,
+        proofs: &[model::BitcoinTxMerkleProof],
     ) -> Result<(), Error> {
-        PgWrite::set_canonical_bitcoin_blockchain(self.get_connection().await?.as_mut(), chain_tip)
-            .await
+        PgWrite::write_bitcoin_tx_merkle_proofs(self.get_connection().await?.as_mut(), proofs).await
+    }
+
+    async fn write_multisig_stacks_transaction(
+        &self,
+        tx: &model::MultisigStacksTransaction,
+    ) -> Result<(), Error> {
+        PgWrite::write_multisig_stacks_transaction(self.get_connection().await?.as_mut(), tx).await
+    }
+
+    async fn write_scanned_stacks_block(
+        &self,
+        block_hash: &model::StacksBlockHash,
+    ) -> Result<(), Error> {
+        PgWrite::write_scanned_stacks_block(self.get_connection().await?.as_mut(), block_hash).await
     }
 }
 
 impl DbWrite for PgTransaction<'_> {
-    async fn write_bitcoin_block(&self, block: &model::BitcoinBlock) -> Result<(), Error> {
+    async fn write_bitcoin_block(
+        &self,
+        block: &model::BitcoinBlock,
+    ) -> Result<WriteOutcome, Error> {
         let mut tx = self.tx.lock().await;
         PgWrite::write_bitcoin_block(tx.as_mut(), block).await
     }
 
     #[cfg(any(test, feature = "testing"))]
-    async fn write_stacks_block(&self, block: &model::StacksBlock) -> Result<(), Error> {
+    async fn write_stacks_block(&self, block: &model::StacksBlock) -> Result<WriteOutcome, Error> {
         let mut tx = self.tx.lock().await;
         PgWrite::write_stacks_block(tx.as_mut(), block).await
     }
@@ -1254,7 +2874,7 @@ impl DbWrite for PgTransaction<'_> {
     async fn write_deposit_request(
         &self,
         deposit_request: &model::DepositRequest,
-    ) -> Result<(), Error> {
+    ) -> Result<WriteOutcome, Error> {
         let mut tx = self.tx.lock().await;
         PgWrite::write_deposit_request(tx.as_mut(), deposit_request).await
     }
@@ -1270,7 +2890,7 @@ impl DbWrite for PgTransaction<'_> {
     async fn write_withdrawal_request(
         &self,
         request: &model::WithdrawalRequest,
-    ) -> Result<(), Error> {
+    ) -> Result<WriteOutcome, Error> {
         let mut tx = self.tx.lock().await;
         PgWrite::write_withdrawal_request(tx.as_mut(), request).await
     }
@@ -1278,7 +2898,7 @@ impl DbWrite for PgTransaction<'_> {
     async fn write_deposit_signer_decision(
         &self,
         decision: &model::DepositSigner,
-    ) -> Result<(), Error> {
+    ) -> Result<WriteOutcome, Error> {
         let mut tx = self.tx.lock().await;
         PgWrite::write_deposit_signer_decision(tx.as_mut(), decision).await
     }
@@ -1286,15 +2906,15 @@ impl DbWrite for PgTransaction<'_> {
     async fn write_withdrawal_signer_decision(
         &self,
         decision: &model::WithdrawalSigner,
-    ) -> Result<(), Error> {
+    ) -> Result<WriteOutcome, Error> {
         let mut tx = self.tx.lock().await;
-        PgWrite::write_withdrawal_signer_decision(tx.as_mut(), decision).await
+        PgWrite::write_withdrawal_signer_decision(tx.as_mut(), decision, self.column_key()).await
     }
 
     async fn write_bitcoin_transaction(
         &self,
         bitcoin_transaction: &model::BitcoinTxRef,
-    ) -> Result<(), Error> {
+    ) -> Result<WriteOutcome, Error> {
         let mut tx = self.tx.lock().await;
         PgWrite::write_bitcoin_transaction(tx.as_mut(), bitcoin_transaction).await
     }
@@ -1312,7 +2932,7 @@ impl DbWrite for PgTransaction<'_> {
     async fn write_encrypted_dkg_shares(
         &self,
         shares: &model::EncryptedDkgShares,
-    ) -> Result<(), Error> {
+    ) -> Result<WriteOutcome, Error> {
         let mut tx = self.tx.lock().await;
         PgWrite::write_encrypted_dkg_shares(tx.as_mut(), shares).await
     }
@@ -1320,7 +2940,7 @@ impl DbWrite for PgTransaction<'_> {
     async fn write_rotate_keys_transaction(
         &self,
         key_rotation: &model::KeyRotationEvent,
-    ) -> Result<(), Error> {
+    ) -> Result<WriteOutcome, Error> {
         let mut tx = self.tx.lock().await;
         PgWrite::write_rotate_keys_transaction(tx.as_mut(), key_rotation).await
     }
@@ -1328,7 +2948,7 @@ impl DbWrite for PgTransaction<'_> {
     async fn write_withdrawal_reject_event(
         &self,
         event: &model::WithdrawalRejectEvent,
-    ) -> Result<(), Error> {
+    ) -> Result<WriteOutcome, Error> {
         let mut tx = self.tx.lock().await;
         PgWrite::write_withdrawal_reject_event(tx.as_mut(), event).await
     }
@@ -1336,7 +2956,7 @@ impl DbWrite for PgTransaction<'_> {
     async fn write_withdrawal_accept_event(
         &self,
         event: &model::WithdrawalAcceptEvent,
-    ) -> Result<(), Error> {
+    ) -> Result<WriteOutcome, Error> {
         let mut tx = self.tx.lock().await;
         PgWrite::write_withdrawal_accept_event(tx.as_mut(), event).await
     }
@@ -1344,12 +2964,12 @@ impl DbWrite for PgTransaction<'_> {
     async fn write_completed_deposit_event(
         &self,
         event: &model::CompletedDepositEvent,
-    ) -> Result<(), Error> {
+    ) -> Result<WriteOutcome, Error> {
         let mut tx = self.tx.lock().await;
         PgWrite::write_completed_deposit_event(tx.as_mut(), event).await
     }
 
-    async fn write_tx_output(&self, output: &model::TxOutput) -> Result<(), Error> {
+    async fn write_tx_output(&self, output: &model::TxOutput) -> Result<WriteOutcome, Error> {
         let mut tx = self.tx.lock().await;
         PgWrite::write_tx_output(tx.as_mut(), output).await
     }
@@ -1357,12 +2977,12 @@ impl DbWrite for PgTransaction<'_> {
     async fn write_withdrawal_tx_output(
         &self,
         output: &model::WithdrawalTxOutput,
-    ) -> Result<(), Error> {
+    ) -> Result<WriteOutcome, Error> {
         let mut tx = self.tx.lock().await;
         PgWrite::write_withdrawal_tx_output(tx.as_mut(), output).await
     }
 
-    async fn write_tx_prevout(&self, prevout: &model::TxPrevout) -> Result<(), Error> {
+    async fn write_tx_prevout(&self, prevout: &model::TxPrevout) -> Result<WriteOutcome, Error> {
         let mut tx = self.tx.lock().await;
         PgWrite::write_tx_prevout(tx.as_mut(), prevout).await
     }
@@ -1416,4 +3036,278 @@ impl DbWrite for PgTransaction<'_> {
         let mut tx = self.tx.lock().await;
         PgWrite::set_canonical_bitcoin_blockchain(tx.as_mut(), chain_tip).await
     }
+
+    async fn write_sbtc_supply_snapshot(
+        &self,
+        snapshot: &model::SbtcSupplySnapshot,
+    ) -> Result<(), Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::write_sbtc_supply_snapshot(tx.as_mut(), snapshot).await
+    }
+
+    async fn write_signer_utxo_violation(
+        &self,
+        violation: &model::SignerUtxoViolation,
+    ) -> Result<WriteOutcome, Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::write_signer_utxo_violation(tx.as_mut(), violation).await
+    }
+
+    async fn write_bitcoin_tx_bodies(&self, bodies: &[model::BitcoinTxBody]) -> Result<(), Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::write_bitcoin_tx_bodies(tx.as_mut(), bodies).await
+    }
+
+    async fn prune_bitcoin_tx_bodies(
+        &self,
+        min_block_height: model::BitcoinBlockHeight,
+    ) -> Result<u64, Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::prune_bitcoin_tx_bodies(tx.as_mut(), min_block_height).await
+    }
+
+    async fn write_emily_imported_deposit(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+    ) -> Result<WriteOutcome, Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::write_emily_imported_deposit(tx.as_mut(), txid, output_index).await
+    }
+
+    async fn write_emily_imported_withdrawal(
+        &self,
+        id: &model::QualifiedRequestId,
+    ) -> Result<WriteOutcome, Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::write_emily_imported_withdrawal(tx.as_mut(), id).await
+    }
+
+    async fn update_peer_version(
+        &self,
+        peer_id: &libp2p::PeerId,
+        agent_version: &str,
+        protocol_version: u32,
+    ) -> Result<(), Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::update_peer_version(tx.as_mut(), peer_id, agent_version, protocol_version).await
+    }
+
+    async fn write_peer_ban(&self, ban: &model::P2PPeerBan) -> Result<(), Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::write_peer_ban(tx.as_mut(), ban).await
+    }
+
+    async fn delete_peer_ban(&self, peer_id: &libp2p::PeerId) -> Result<(), Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::delete_peer_ban(tx.as_mut(), peer_id).await
+    }
+
+    async fn write_dkg_verification_round(
+        &self,
+        round: &model::DkgVerificationRound,
+    ) -> Result<(), Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::write_dkg_verification_round(tx.as_mut(), round).await
+    }
+
+    async fn write_stacks_event_dead_letter(
+        &self,
+        letter: &model::StacksEventDeadLetter,
+    ) -> Result<(), Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::write_stacks_event_dead_letter(tx.as_mut(), letter).await
+    }
+
+    async fn resolve_stacks_event_dead_letter(
+        &self,
+        block_hash: &model::StacksBlockHash,
+        txid: &model::StacksTxId,
+        event_index: u32,
+    ) -> Result<(), Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::resolve_stacks_event_dead_letter(tx.as_mut(), block_hash, txid, event_index).await
+    }
+
+    async fn write_signed_sweep_transaction(
+        &self,
+        sweep: &model::SignedSweepTransaction,
+    ) -> Result<WriteOutcome, Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::write_signed_sweep_transaction(tx.as_mut(), sweep).await
+    }
+
+    async fn delete_signed_sweep_transactions(
+        &self,
+        txids: &[model::BitcoinTxId],
+    ) -> Result<(), Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::delete_signed_sweep_transactions(tx.as_mut(), txids).await
+    }
+
+    async fn write_sweep_fee_discrepancy(
+        &self,
+        discrepancy: &model::SweepFeeDiscrepancy,
+    ) -> Result<WriteOutcome, Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::write_sweep_fee_discrepancy(tx.as_mut(), discrepancy).await
+    }
+
+    async fn write_key_rotation_attestation(
+        &self,
+        attestation: &model::KeyRotationAttestation,
+    ) -> Result<WriteOutcome, Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::write_key_rotation_attestation(tx.as_mut(), attestation).await
+    }
+
+    async fn write_sweep_package(&self, package: &model::SweepPackage) -> Result<(), Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::write_sweep_package(tx.as_mut(), package).await
+    }
+
+    async fn write_sweep_package_broadcast(
+        &self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        txid: &model::BitcoinTxId,
+    ) -> Result<(), Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::write_sweep_package_broadcast(tx.as_mut(), bitcoin_chain_tip, txid).await
+    }
+
+    async fn write_deposit_request_timestamp(
+        &self,
+        txid: &model::BitcoinTxId,
+        output_index: u32,
+        stage: model::RequestLifecycleStage,
+    ) -> Result<(), Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::write_deposit_request_timestamp(tx.as_mut(), txid, output_index, stage).await
+    }
+
+    async fn write_withdrawal_request_timestamp(
+        &self,
+        request_id: u64,
+        stage: model::RequestLifecycleStage,
+    ) -> Result<(), Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::write_withdrawal_request_timestamp(tx.as_mut(), request_id, stage).await
+    }
+
+    async fn write_bitcoin_backfill_progress(
+        &self,
+        progress: &model::BitcoinBackfillProgress,
+    ) -> Result<(), Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::write_bitcoin_backfill_progress(tx.as_mut(), progress).await
+    }
+
+    async fn write_coordinator_takeover(
+        &self,
+        takeover: &model::CoordinatorTakeover,
+    ) -> Result<WriteOutcome, Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::write_coordinator_takeover(tx.as_mut(), takeover).await
+    }
+
+    async fn write_peer_address_record(
+        &self,
+        record: &model::P2PPeerAddressRecord,
+    ) -> Result<bool, Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::write_peer_address_record(tx.as_mut(), record).await
+    }
+
+    async fn write_request_override(
+        &self,
+        request_override: &model::NewRequestOverride,
+    ) -> Result<model::RequestOverride, Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::write_request_override(tx.as_mut(), request_override, self.column_key()).await
+    }
+
+    async fn revoke_request_override(&self, id: i64) -> Result<bool, Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::revoke_request_override(tx.as_mut(), id).await
+    }
+
+    async fn write_inflight_contract_call(
+        &self,
+        contract_call: &model::InflightContractCall,
+    ) -> Result<(), Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::write_inflight_contract_call(tx.as_mut(), contract_call).await
+    }
+
+    async fn delete_inflight_contract_call(
+        &self,
+        target: &model::ContractCallTarget,
+    ) -> Result<bool, Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::delete_inflight_contract_call(tx.as_mut(), target).await
+    }
+
+    async fn write_withdrawal_output_labels(
+        &self,
+        labels: &[model::WithdrawalOutputLabel],
+    ) -> Result<(), Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::write_withdrawal_output_labels(tx.as_mut(), labels).await
+    }
+
+    async fn write_spilled_state_machine(
+        &self,
+        state_machine: &model::SpilledStateMachine,
+    ) -> Result<(), Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::write_spilled_state_machine(tx.as_mut(), state_machine).await
+    }
+
+    async fn take_spilled_state_machine(
+        &self,
+        state_machine_id: &str,
+    ) -> Result<Option<model::SpilledStateMachine>, Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::take_spilled_state_machine(tx.as_mut(), state_machine_id).await
+    }
+
+    async fn prune_spilled_state_machines(
+        &self,
+        min_block_height: model::BitcoinBlockHeight,
+    ) -> Result<u64, Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::prune_spilled_state_machines(tx.as_mut(), min_block_height).await
+    }
+
+    async fn write_bitcoin_block_header(
+        &self,
+        header: &bitcoin::block::Header,
+    ) -> Result<WriteOutcome, Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::write_bitcoin_block_header(tx.as_mut(), header).await
+    }
+
+    async fn write_bitcoin_tx_merkle_proofs(
+        &self,
+        proofs: &[model::BitcoinTxMerkleProof],
+    ) -> Result<(), Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::write_bitcoin_tx_merkle_proofs(tx.as_mut(), proofs).await
+    }
+
+    async fn write_multisig_stacks_transaction(
+        &self,
+        multisig_tx: &model::MultisigStacksTransaction,
+    ) -> Result<(), Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::write_multisig_stacks_transaction(tx.as_mut(), multisig_tx).await
+    }
+
+    async fn write_scanned_stacks_block(
+        &self,
+        block_hash: &model::StacksBlockHash,
+    ) -> Result<(), Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::write_scanned_stacks_block(tx.as_mut(), block_hash).await
+    }
 }
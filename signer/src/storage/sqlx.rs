@@ -17,8 +17,10 @@ use sqlx::postgres::types::Oid;
 use time::OffsetDateTime;
 use time::macros::datetime;
 
+use crate::bitcoin::utxo::Fees;
 use crate::keys::PublicKey;
 use crate::keys::PublicKeyXOnly;
+use crate::secret::Secret;
 use crate::storage::model::BitcoinBlockHash;
 use crate::storage::model::BitcoinBlockHeight;
 use crate::storage::model::BitcoinTxId;
@@ -30,9 +32,15 @@ use crate::storage::model::StacksPrincipal;
 use crate::storage::model::StacksTxId;
 use crate::storage::model::TaprootScriptHash;
 
+use super::model::ContractCallTarget;
 use super::model::DbMultiaddr;
 use super::model::DbPeerId;
 use super::model::DepositRequest;
+use super::model::InflightContractCall;
+use super::model::OverrideTarget;
+use super::model::QualifiedRequestId;
+use super::model::RequestOverride;
+use super::model::SignerUtxoViolation;
 use super::model::Timestamp;
 
 /// The PostgreSQL epoch is 2000-01-01 00:00:00 UTC
@@ -472,6 +480,43 @@ impl<'r> sqlx::Decode<'r, sqlx::Postgres> for DbPeerId {
     }
 }
 
+// For the [`Secret`] wrapper, which is stored as the value that it wraps.
+
+impl<T> sqlx::Type<sqlx::Postgres> for Secret<T>
+where
+    T: sqlx::Type<sqlx::Postgres>,
+{
+    fn type_info() -> sqlx::postgres::PgTypeInfo {
+        T::type_info()
+    }
+
+    fn compatible(ty: &sqlx::postgres::PgTypeInfo) -> bool {
+        T::compatible(ty)
+    }
+}
+
+impl<'q, T> sqlx::Encode<'q, sqlx::Postgres> for Secret<T>
+where
+    T: sqlx::Encode<'q, sqlx::Postgres>,
+{
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        self.expose_secret().encode_by_ref(buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.expose_secret().size_hint()
+    }
+}
+
+impl<'r, T> sqlx::Decode<'r, sqlx::Postgres> for Secret<T>
+where
+    T: sqlx::Decode<'r, sqlx::Postgres>,
+{
+    fn decode(value: sqlx::postgres::PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        T::decode(value).map(Secret::new)
+    }
+}
+
 // --- sqlx Type implementations for DbMultiaddr ---
 
 impl sqlx::Type<sqlx::Postgres> for DbMultiaddr {
@@ -485,6 +530,12 @@ impl sqlx::Type<sqlx::Postgres> for DbMultiaddr {
     }
 }
 
+impl sqlx::postgres::PgHasArrayType for DbMultiaddr {
+    fn array_type_info() -> sqlx::postgres::PgTypeInfo {
+        <String as sqlx::postgres::PgHasArrayType>::array_type_info()
+    }
+}
+
 impl<'q> sqlx::Encode<'q, sqlx::Postgres> for DbMultiaddr {
     fn encode_by_ref(
         &self,
@@ -525,6 +576,18 @@ impl<'a> sqlx::FromRow<'a, PgRow> for DepositRequest {
             .try_get::<i64, _>("lock_time")
             .and_then(|value| try_from_t::<i64, u32>(value, "lock_time"))?;
         let max_fee_bytes: [u8; 8] = row.try_get("max_fee")?;
+        let funding_fees_total: Option<i64> = row.try_get("funding_fees_total")?;
+        let funding_fees_vsize: Option<i64> = row.try_get("funding_fees_vsize")?;
+        let funding_fees = match (funding_fees_total, funding_fees_vsize) {
+            (Some(total), Some(vsize)) => {
+                let total = try_from_t::<i64, u64>(total, "funding_fees_total")?;
+                let vsize = try_from_t::<i64, u64>(vsize, "funding_fees_vsize")?;
+                let fees =
+                    Fees::new(total, vsize).map_err(|err| sqlx::Error::Decode(err.into()))?;
+                Some(fees)
+            }
+            _ => None,
+        };
 
         Ok(DepositRequest {
             txid: row.try_get("txid")?,
@@ -537,6 +600,111 @@ impl<'a> sqlx::FromRow<'a, PgRow> for DepositRequest {
             lock_time,
             signers_public_key: row.try_get("signers_public_key")?,
             sender_script_pub_keys: row.try_get("sender_script_pub_keys")?,
+            funding_fees,
+        })
+    }
+}
+
+impl<'a> sqlx::FromRow<'a, PgRow> for RequestOverride {
+    fn from_row(row: &'a PgRow) -> sqlx::Result<Self> {
+        let deposit_txid: Option<BitcoinTxId> = row.try_get("deposit_txid")?;
+        let deposit_output_index: Option<i32> = row.try_get("deposit_output_index")?;
+        let withdrawal_request_id: Option<i64> = row.try_get("withdrawal_request_id")?;
+
+        let target = match (deposit_txid, deposit_output_index, withdrawal_request_id) {
+            (Some(txid), Some(output_index), None) => OverrideTarget::Deposit {
+                txid,
+                output_index: try_from_t::<i32, u32>(output_index, "deposit_output_index")?,
+            },
+            (None, None, Some(request_id)) => OverrideTarget::Withdrawal {
+                request_id: try_from_t::<i64, u64>(request_id, "withdrawal_request_id")?,
+            },
+            _ => {
+                return Err(sqlx::Error::ColumnDecode {
+                    index: "withdrawal_request_id".to_string(),
+                    source: "request override does not identify exactly one request".into(),
+                });
+            }
+        };
+
+        Ok(RequestOverride {
+            id: row.try_get("id")?,
+            target,
+            directive: row.try_get("directive")?,
+            reason: row.try_get("reason")?,
+            expires_at: row.try_get("expires_at")?,
+            created_at: row.try_get("created_at")?,
+            revoked_at: row.try_get("revoked_at")?,
+        })
+    }
+}
+
+impl<'a> sqlx::FromRow<'a, PgRow> for InflightContractCall {
+    fn from_row(row: &'a PgRow) -> sqlx::Result<Self> {
+        let deposit_txid: Option<BitcoinTxId> = row.try_get("deposit_txid")?;
+        let deposit_output_index: Option<i32> = row.try_get("deposit_output_index")?;
+        let withdrawal_request_id: Option<i64> = row.try_get("withdrawal_request_id")?;
+        let withdrawal_txid: Option<StacksTxId> = row.try_get("withdrawal_txid")?;
+        let withdrawal_block_hash: Option<StacksBlockHash> =
+            row.try_get("withdrawal_block_hash")?;
+
+        let deposit = (deposit_txid, deposit_output_index);
+        let withdrawal = (
+            withdrawal_request_id,
+            withdrawal_txid,
+            withdrawal_block_hash,
+        );
+        let target = match (deposit, withdrawal) {
+            ((Some(txid), Some(output_index)), (None, None, None)) => ContractCallTarget::Deposit {
+                txid,
+                output_index: try_from_t::<i32, u32>(output_index, "deposit_output_index")?,
+            },
+            ((None, None), (Some(request_id), Some(txid), Some(block_hash))) => {
+                ContractCallTarget::Withdrawal {
+                    id: QualifiedRequestId {
+                        request_id: try_from_t::<i64, u64>(request_id, "withdrawal_request_id")?,
+                        txid,
+                        block_hash,
+                    },
+                }
+            }
+            _ => {
+                return Err(sqlx::Error::ColumnDecode {
+                    index: "withdrawal_request_id".to_string(),
+                    source: "contract call does not identify exactly one request".into(),
+                });
+            }
+        };
+
+        Ok(InflightContractCall {
+            target,
+            txid: row.try_get("txid")?,
+            bitcoin_block_height: row.try_get("bitcoin_block_height")?,
+            bitcoin_block_hash: row.try_get("bitcoin_block_hash")?,
+        })
+    }
+}
+
+impl<'a> sqlx::FromRow<'a, PgRow> for SignerUtxoViolation {
+    fn from_row(row: &'a PgRow) -> sqlx::Result<Self> {
+        let amount = |column: &str| {
+            row.try_get::<i64, _>(column)
+                .and_then(|value| try_from_t::<i64, u64>(value, column))
+        };
+        let previous_signer_output = row
+            .try_get::<Option<i64>, _>("previous_signer_output")?
+            .map(|value| try_from_t::<i64, u64>(value, "previous_signer_output"))
+            .transpose()?;
+
+        Ok(SignerUtxoViolation {
+            txid: row.try_get("txid")?,
+            block_hash: row.try_get("block_hash")?,
+            signer_input: amount("signer_input")?,
+            deposits: amount("deposits")?,
+            withdrawals: amount("withdrawals")?,
+            fee: amount("fee")?,
+            signer_output: amount("signer_output")?,
+            previous_signer_output,
         })
     }
 }
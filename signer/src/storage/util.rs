@@ -1,11 +1,20 @@
 //! General utilities for the storage.
 
+use std::collections::BTreeMap;
 use std::collections::HashSet;
+use std::future::Future;
+use std::time::Duration;
 
+use crate::bitcoin::BitcoinInteract;
+use crate::bitcoin::rpc::BitcoinTxInfo;
 use crate::bitcoin::utxo::SignerUtxo;
 use crate::error::Error;
 use crate::keys::PublicKey;
 use crate::keys::SignerScriptPubKey as _;
+use crate::storage::DbRead;
+use crate::storage::model;
+use crate::storage::model::BitcoinBlockHash;
+use crate::storage::model::BitcoinTxId;
 
 /// Given the sbtc txs in a block, returns the `aggregate_key` utxo (if there's exactly one)
 pub fn get_utxo(
@@ -44,3 +53,266 @@ pub fn get_utxo(
         _ => Err(Error::TooManySignerUtxos),
     }
 }
+
+/// Fetch the bitcoin transaction with the given ID, as confirmed in the
+/// bitcoin block with the given hash, looking in the `bitcoin_tx_bodies`
+/// cache first and falling back to bitcoin-core when it is not there.
+pub async fn get_bitcoin_tx_info<D, B>(
+    db: &D,
+    bitcoin_client: &B,
+    txid: &bitcoin::Txid,
+    block_hash: &bitcoin::BlockHash,
+) -> Result<Option<BitcoinTxInfo>, Error>
+where
+    D: DbRead,
+    B: BitcoinInteract,
+{
+    let cached = db
+        .get_bitcoin_tx_body(
+            &BitcoinTxId::from(*txid),
+            &BitcoinBlockHash::from(*block_hash),
+        )
+        .await?;
+    if let Some(body) = cached {
+        return Ok(Some(body.into_tx_info()));
+    }
+
+    bitcoin_client.get_tx_info(txid, block_hash).await
+}
+
+/// Deduplicate withdrawal requests by their request ID.
+///
+/// A withdrawal that was confirmed in a stacks block which was later
+/// forked out may be confirmed again in a different stacks block, giving
+/// two [`model::QualifiedRequestId`]s for the same logical request. When
+/// that happens we keep the one anchored to the highest bitcoin block,
+/// using the stacks block hash as a tie-breaker so that every signer
+/// picks the same one. The returned requests are ordered by request ID.
+pub fn dedup_withdrawal_requests<I>(requests: I) -> Vec<model::WithdrawalRequest>
+where
+    I: IntoIterator<Item = model::WithdrawalRequest>,
+{
+    let mut deduped: BTreeMap<u64, model::WithdrawalRequest> = BTreeMap::new();
+
+    for request in requests {
+        match deduped.get(&request.request_id) {
+            Some(existing)
+                if (existing.bitcoin_block_height, existing.block_hash)
+                    >= (request.bitcoin_block_height, request.block_hash) =>
+            {
+                tracing::debug!(
+                    request_id = request.request_id,
+                    stacks_block_hash = %request.block_hash,
+                    "ignoring duplicate withdrawal request from another stacks block"
+                );
+            }
+            _ => {
+                deduped.insert(request.request_id, request);
+            }
+        }
+    }
+
+    deduped.into_values().collect()
+}
+
+/// The number of times that [`retry_transient`] tries a storage operation
+/// before giving up.
+pub const STORAGE_RETRY_ATTEMPTS: u32 = 3;
+
+/// How long [`retry_transient`] waits before the first retry of a storage
+/// operation. The delay grows linearly with each attempt.
+const STORAGE_RETRY_DELAY: Duration = Duration::from_millis(50);
+
+/// Run the given storage operation, trying it again from the start if it
+/// fails with a transient database error.
+///
+/// The operation is expected to write everything within a single storage
+/// transaction, so a failed attempt leaves nothing behind and the whole
+/// batch of writes is retried.
+pub async fn retry_transient<F, Fut, T>(mut operation: F) -> Result<T, Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, Error>>,
+{
+    let mut attempt = 1;
+    loop {
+        match operation().await {
+            Err(error)
+                if attempt < STORAGE_RETRY_ATTEMPTS && error.is_transient_storage_error() =>
+            {
+                tracing::warn!(%error, attempt, "transient storage error; retrying the transaction");
+                tokio::time::sleep(STORAGE_RETRY_DELAY * attempt).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fake::Fake as _;
+    use fake::Faker;
+
+    use crate::context::Context as _;
+    use crate::storage::DbWrite as _;
+    use crate::storage::model::BitcoinBlockHeight;
+    use crate::storage::model::StacksBlockHash;
+    use crate::testing::context::*;
+
+    use super::*;
+
+    fn request(request_id: u64, height: u64, block_hash: [u8; 32]) -> model::WithdrawalRequest {
+        model::WithdrawalRequest {
+            request_id,
+            bitcoin_block_height: BitcoinBlockHeight::from(height),
+            block_hash: StacksBlockHash::from(block_hash),
+            ..Faker.fake_with_rng(&mut rand::rngs::OsRng)
+        }
+    }
+
+    #[test]
+    fn dedup_withdrawal_requests_keeps_one_request_per_id() {
+        let requests = vec![
+            request(2, 10, [1; 32]),
+            request(1, 10, [1; 32]),
+            request(2, 12, [2; 32]),
+            request(1, 10, [3; 32]),
+            request(3, 11, [4; 32]),
+        ];
+
+        let deduped = dedup_withdrawal_requests(requests);
+
+        let ids: Vec<_> = deduped
+            .iter()
+            .map(|req| (req.request_id, req.block_hash))
+            .collect();
+        let expected = vec![
+            (1, StacksBlockHash::from([3; 32])),
+            (2, StacksBlockHash::from([2; 32])),
+            (3, StacksBlockHash::from([4; 32])),
+        ];
+        assert_eq!(ids, expected);
+    }
+
+    #[tokio::test]
+    async fn retry_transient_retries_transient_errors() {
+        let counter = std::sync::atomic::AtomicU32::new(0);
+        let attempts = &counter;
+
+        let result = retry_transient(move || async move {
+            let attempt = attempts.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+            if attempt < STORAGE_RETRY_ATTEMPTS {
+                Err(Error::SqlxQuery(sqlx::Error::PoolTimedOut))
+            } else {
+                Ok(attempt)
+            }
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), STORAGE_RETRY_ATTEMPTS);
+    }
+
+    #[tokio::test]
+    async fn retry_transient_gives_up_eventually() {
+        let counter = std::sync::atomic::AtomicU32::new(0);
+        let attempts = &counter;
+
+        let result: Result<(), Error> = retry_transient(move || async move {
+            attempts.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Err(Error::SqlxCommitTransaction(sqlx::Error::PoolTimedOut))
+        })
+        .await;
+
+        assert!(result.unwrap_err().is_transient_storage_error());
+        assert_eq!(counter.into_inner(), STORAGE_RETRY_ATTEMPTS);
+    }
+
+    #[tokio::test]
+    async fn retry_transient_does_not_retry_other_errors() {
+        let counter = std::sync::atomic::AtomicU32::new(0);
+        let attempts = &counter;
+
+        let result: Result<(), Error> = retry_transient(move || async move {
+            attempts.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Err(Error::SqlxQuery(sqlx::Error::RowNotFound))
+        })
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(Error::SqlxQuery(sqlx::Error::RowNotFound))
+        ));
+        assert_eq!(counter.into_inner(), 1);
+    }
+
+    /// A transaction as bitcoin-core gives it to us, with a fee that
+    /// matches the outputs that it spends.
+    fn tx_info() -> BitcoinTxInfo {
+        let mut tx_info: BitcoinTxInfo = Faker.fake_with_rng(&mut rand::rngs::OsRng);
+        let spent: u64 = tx_info
+            .vin
+            .iter()
+            .filter_map(|vin| vin.prevout.as_ref())
+            .map(|prevout| prevout.value.to_sat())
+            .sum();
+        tx_info.tx.output[0].value = bitcoin::Amount::from_sat(spent / 2);
+        tx_info.fee = Some(bitcoin::Amount::from_sat(spent - spent / 2));
+        tx_info
+    }
+
+    #[test]
+    fn cached_tx_bodies_convert_back_to_the_same_tx_info() {
+        let tx_info = tx_info();
+        let block_ref: model::BitcoinBlockRef = Faker.fake();
+
+        let body = model::BitcoinTxBody::from_tx_info(&tx_info, &block_ref).unwrap();
+        assert_eq!(body.block_hash, block_ref.block_hash);
+        assert_eq!(body.into_tx_info(), tx_info);
+
+        // We need the outputs that the transaction spends.
+        let mut incomplete = tx_info.clone();
+        incomplete.vin[0].prevout = None;
+        assert!(model::BitcoinTxBody::from_tx_info(&incomplete, &block_ref).is_none());
+    }
+
+    #[tokio::test]
+    async fn get_bitcoin_tx_info_prefers_the_cache() {
+        let ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .build();
+        let db = ctx.get_storage_mut();
+
+        let cached = tx_info();
+        let cached_block: model::BitcoinBlockRef = Faker.fake();
+        let body = model::BitcoinTxBody::from_tx_info(&cached, &cached_block).unwrap();
+        db.write_bitcoin_tx_bodies(&[body]).await.unwrap();
+
+        let uncached = tx_info();
+        let uncached_block: bitcoin::BlockHash = Faker.fake::<BitcoinBlockHash>().into();
+        let response = uncached.clone();
+        ctx.with_bitcoin_client(move |client| {
+            client
+                .expect_get_tx_info()
+                .once()
+                .returning(move |_, _| Box::pin(std::future::ready(Ok(Some(response.clone())))));
+        })
+        .await;
+
+        // The cached transaction does not touch bitcoin-core, which
+        // answers only once.
+        let bitcoin_client = ctx.get_bitcoin_client();
+        let txid = cached.compute_txid();
+        let tx = get_bitcoin_tx_info(&db, &bitcoin_client, &txid, &cached_block.block_hash)
+            .await
+            .unwrap();
+        assert_eq!(tx, Some(cached));
+
+        let txid = uncached.compute_txid();
+        let tx = get_bitcoin_tx_info(&db, &bitcoin_client, &txid, &uncached_block)
+            .await
+            .unwrap();
+        assert_eq!(tx, Some(uncached));
+    }
+}
@@ -0,0 +1,206 @@
+//! This module contains a background task that periodically reconciles
+//! the sBTC supply that the signer expects with the total supply reported
+//! by the sBTC token contract.
+//!
+//! The expected supply is computed from the signer's database as the sum
+//! of all completed deposits minus the sum of all accepted withdrawals.
+//! Each reconciliation is persisted as a snapshot, and the signer logs an
+//! error whenever the two values diverge by more than the configured
+//! tolerance.
+//!
+//! Note that the signer learns about completed deposits and accepted
+//! withdrawals through the stacks event observer, so the reported supply
+//! may briefly run ahead of our records. A single divergent snapshot is
+//! therefore not necessarily a problem, but a persistent one is.
+
+use std::time::Duration;
+
+use crate::context::Context;
+use crate::error::Error;
+use crate::metrics::Metrics;
+use crate::stacks::api::StacksInteract as _;
+use crate::storage::DbRead as _;
+use crate::storage::DbWrite as _;
+use crate::storage::model::SbtcSupplySnapshot;
+
+/// A background task that reconciles the expected sBTC supply with the
+/// total supply reported by the sBTC token contract.
+pub struct SupplyReconciler<C> {
+    /// Signer context.
+    context: C,
+    /// How often to run the reconciliation.
+    interval: Duration,
+    /// The maximum difference, in sats, between the expected and the
+    /// reported supply that is not considered an anomaly.
+    tolerance: u64,
+}
+
+impl<C> SupplyReconciler<C>
+where
+    C: Context,
+{
+    /// Creates a new SupplyReconciler using the interval and tolerance in
+    /// the signer's config.
+    pub fn new(context: C) -> Self {
+        let config = &context.config().signer;
+        let interval = config.supply_reconciliation_interval;
+        let tolerance = config.supply_reconciliation_tolerance;
+        Self { context, interval, tolerance }
+    }
+
+    /// Runs the SupplyReconciler, which reconciles the sBTC supply each
+    /// [`interval`] until the signer is shut down.
+    pub async fn run(self) {
+        let mut term = self.context.get_termination_handle();
+        loop {
+            tokio::select! {
+                _ = term.wait_for_shutdown() => {
+                    break;
+                }
+                _ = tokio::time::sleep(self.interval) => {
+                    if let Err(error) = self.reconcile().await {
+                        tracing::warn!(%error, "could not reconcile the sBTC supply");
+                    }
+                }
+            }
+        }
+        tracing::info!("sBTC supply reconciler has stopped");
+    }
+
+    /// Compute the expected sBTC supply, compare it with the supply
+    /// reported by the sBTC token contract and persist the result.
+    ///
+    /// Returns `Ok(None)` if the reconciliation cannot run yet, either
+    /// because the sBTC contracts have not been deployed or because we
+    /// have not observed a bitcoin chain tip.
+    #[tracing::instrument(skip_all)]
+    pub async fn reconcile(&self) -> Result<Option<SbtcSupplySnapshot>, Error> {
+        let state = self.context.state();
+        if !state.sbtc_contracts_deployed() {
+            return Ok(None);
+        }
+        let Some(chain_tip) = state.bitcoin_chain_tip() else {
+            return Ok(None);
+        };
+
+        let deployer = &self.context.config().signer.deployer;
+        let reported_supply = self
+            .context
+            .get_stacks_client()
+            .get_sbtc_total_supply(deployer)
+            .await?;
+
+        let db = self.context.get_storage_mut();
+        let totals = db.get_sbtc_supply_totals().await?;
+
+        let mut snapshot = SbtcSupplySnapshot {
+            bitcoin_block_hash: chain_tip.block_hash,
+            bitcoin_block_height: chain_tip.block_height,
+            totals,
+            reported_supply: reported_supply.to_sat(),
+            within_tolerance: true,
+        };
+        snapshot.within_tolerance =
+            snapshot.divergence().unsigned_abs() <= u128::from(self.tolerance);
+
+        db.write_sbtc_supply_snapshot(&snapshot).await?;
+        Metrics::record_supply_reconciliation(&snapshot);
+
+        if snapshot.within_tolerance {
+            tracing::debug!(
+                expected_supply = %snapshot.totals.expected_supply(),
+                reported_supply = %snapshot.reported_supply,
+                "sBTC supply reconciled"
+            );
+        } else {
+            tracing::error!(
+                minted = %snapshot.totals.minted,
+                burned = %snapshot.totals.burned,
+                expected_supply = %snapshot.totals.expected_supply(),
+                reported_supply = %snapshot.reported_supply,
+                divergence = %snapshot.divergence(),
+                tolerance = %self.tolerance,
+                bitcoin_block_hash = %snapshot.bitcoin_block_hash,
+                bitcoin_block_height = %snapshot.bitcoin_block_height,
+                "the reported sBTC supply diverges from the expected supply"
+            );
+        }
+
+        Ok(Some(snapshot))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::Amount;
+    use fake::Fake as _;
+    use fake::Faker;
+    use test_case::test_case;
+
+    use crate::storage::model;
+    use crate::testing::context::*;
+
+    use super::*;
+
+    #[test_case(1_000, 0, 0; "exact match")]
+    #[test_case(1_005, 5, 0; "within tolerance")]
+    #[test_case(994, 5, 1; "below tolerance")]
+    #[test_case(1_006, 5, 1; "above tolerance")]
+    #[tokio::test]
+    async fn reconcile_flags_divergence_beyond_tolerance(
+        reported_supply: u64,
+        tolerance: u64,
+        expected_alerts: usize,
+    ) {
+        let mut ctx = TestContext::default_mocked();
+        ctx.config_mut().signer.supply_reconciliation_tolerance = tolerance;
+        ctx.state().set_sbtc_contracts_deployed();
+        ctx.state().set_bitcoin_chain_tip(Faker.fake());
+
+        let mut deposit: model::CompletedDepositEvent = Faker.fake();
+        deposit.amount = 1_000;
+        ctx.get_storage_mut()
+            .write_completed_deposit_event(&deposit)
+            .await
+            .unwrap();
+
+        ctx.with_stacks_client(move |client| {
+            client
+                .expect_get_sbtc_total_supply()
+                .returning(move |_| Box::pin(async move { Ok(Amount::from_sat(reported_supply)) }));
+        })
+        .await;
+
+        let reconciler = SupplyReconciler::new(ctx.clone());
+        let snapshot = reconciler.reconcile().await.unwrap().unwrap();
+
+        assert_eq!(snapshot.totals.minted, 1_000);
+        assert_eq!(snapshot.totals.burned, 0);
+        assert_eq!(snapshot.reported_supply, reported_supply);
+
+        let store = ctx.get_storage().lock().await;
+        assert_eq!(store.sbtc_supply_snapshots, vec![snapshot]);
+        let alerts = store
+            .sbtc_supply_snapshots
+            .iter()
+            .filter(|snapshot| !snapshot.within_tolerance)
+            .count();
+        assert_eq!(alerts, expected_alerts);
+    }
+
+    #[tokio::test]
+    async fn reconcile_skips_when_contracts_are_not_deployed() {
+        let ctx = TestContext::default_mocked();
+        ctx.state().set_bitcoin_chain_tip(Faker.fake());
+
+        let reconciler = SupplyReconciler::new(ctx.clone());
+        assert!(reconciler.reconcile().await.unwrap().is_none());
+        assert!(
+            ctx.get_storage()
+                .lock()
+                .await
+                .sbtc_supply_snapshots
+                .is_empty()
+        );
+    }
+}
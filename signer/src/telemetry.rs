@@ -0,0 +1,462 @@
+//! Distributed tracing across signers, and the export of the signer's
+//! tracing spans to an OpenTelemetry collector.
+//!
+//! Every span gets a W3C trace context: a trace ID that it shares with
+//! its parent span, and a span ID of its own. When a signer creates a
+//! message for the other signers, the trace context of the current span
+//! goes along with it in the message metadata, and the signers that
+//! receive the message handle it in a span whose parent is the sender's
+//! span, see [`message_span`]. This way the spans of a sweep on all of the
+//! participating signers end up in a single trace.
+//!
+//! When `signer.otlp_traces_endpoint` is set, the [`OtlpExporter`]
+//! periodically sends the spans that have closed to an OpenTelemetry
+//! collector, using OTLP over HTTP with JSON encoding.
+
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use serde_json::Value;
+use serde_json::json;
+use tracing::Subscriber;
+use tracing::field::Field;
+use tracing::span;
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer;
+use tracing_subscriber::registry::LookupSpan;
+use url::Url;
+
+use crate::context::Context;
+use crate::ecdsa::Signed;
+use crate::error::Error;
+use crate::message::SignerMessage;
+
+/// The name of the span field that sets the remote parent of a span. Its
+/// value is the W3C traceparent of the parent span.
+pub const TRACE_PARENT_FIELD: &str = "trace_parent";
+
+/// The service name that the signer's spans are exported under.
+const SERVICE_NAME: &str = "sbtc-signer";
+
+/// How often the [`OtlpExporter`] exports the spans that have closed.
+const EXPORT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The maximum number of closed spans that are kept until the next export.
+/// Spans that close while the buffer is full are dropped, so that an
+/// unreachable collector cannot make the signer run out of memory.
+const MAX_BUFFERED_SPANS: usize = 10_000;
+
+/// The OTLP span kind for spans that represent internal operations.
+const SPAN_KIND_INTERNAL: u8 = 1;
+
+/// Whether the spans that close are buffered for export, which is the case
+/// once an [`OtlpExporter`] has been created.
+static EXPORT_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// The OTLP encoded spans that have closed since the last export.
+static FINISHED_SPANS: Mutex<Vec<Value>> = Mutex::new(Vec::new());
+
+/// The W3C trace context of a span.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TraceContext {
+    /// The ID of the trace that the span is a part of.
+    pub trace_id: u128,
+    /// The ID of the span.
+    pub span_id: u64,
+}
+
+impl TraceContext {
+    /// The trace context of a span that starts a new trace.
+    fn new_root() -> Self {
+        Self {
+            trace_id: rand::random::<u128>().max(1),
+            span_id: rand::random::<u64>().max(1),
+        }
+    }
+
+    /// The trace context of a new child span of this span.
+    fn child(&self) -> Self {
+        Self {
+            trace_id: self.trace_id,
+            span_id: rand::random::<u64>().max(1),
+        }
+    }
+}
+
+/// Formats the trace context as a W3C traceparent, with the sampled flag
+/// set.
+impl std::fmt::Display for TraceContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "00-{:032x}-{:016x}-01", self.trace_id, self.span_id)
+    }
+}
+
+/// Parses a W3C traceparent in the form that [`TraceContext`] formats it.
+/// Since trace contexts are part of signed messages, other forms are
+/// rejected, as they would not survive being encoded again.
+impl std::str::FromStr for TraceContext {
+    type Err = Error;
+
+    fn from_str(traceparent: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidTraceParent(traceparent.to_string());
+
+        let mut parts = traceparent.split('-');
+        let (Some(_), Some(trace_id), Some(span_id), Some(_), None) = (
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+        ) else {
+            return Err(invalid());
+        };
+
+        let context = Self {
+            trace_id: u128::from_str_radix(trace_id, 16).map_err(|_| invalid())?,
+            span_id: u64::from_str_radix(span_id, 16).map_err(|_| invalid())?,
+        };
+        if context.trace_id == 0 || context.span_id == 0 || context.to_string() != traceparent {
+            return Err(invalid());
+        }
+        Ok(context)
+    }
+}
+
+/// Return the trace context of the current span, if there is one.
+pub fn current_trace_context() -> Option<TraceContext> {
+    tracing::Span::current()
+        .with_subscriber(|(id, dispatch)| {
+            let registry = dispatch.downcast_ref::<tracing_subscriber::Registry>()?;
+            let span = registry.span(id)?;
+            let extensions = span.extensions();
+            extensions.get::<SpanData>().map(|data| data.context)
+        })
+        .flatten()
+}
+
+/// Return a span for handling a message from another signer. If the
+/// sender attached its trace context to the message, then the span is a
+/// child of the sender's span.
+pub fn message_span(msg: &Signed<SignerMessage>) -> tracing::Span {
+    let sender = msg.signer_public_key;
+    match msg.trace_context {
+        Some(trace_parent) => {
+            tracing::info_span!("signer_message", %sender, trace_parent = %trace_parent)
+        }
+        None => tracing::info_span!("signer_message", %sender),
+    }
+}
+
+/// The trace context of a span and the other data that is exported for
+/// it, which is kept in the span's extensions.
+#[derive(Debug)]
+struct SpanData {
+    context: TraceContext,
+    parent_span_id: Option<u64>,
+    start: SystemTime,
+    attributes: Vec<(&'static str, String)>,
+}
+
+/// A [`Layer`] that assigns a [`TraceContext`] to every span and, once
+/// export is enabled, buffers the spans that close for the
+/// [`OtlpExporter`].
+#[derive(Debug, Default)]
+pub struct TraceContextLayer;
+
+impl<S> Layer<S> for TraceContextLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: layer::Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut visitor = SpanFieldVisitor::new();
+        attrs.record(&mut visitor);
+
+        let parent = visitor.remote_parent.or_else(|| {
+            let parent = span.parent()?;
+            let extensions = parent.extensions();
+            extensions.get::<SpanData>().map(|data| data.context)
+        });
+
+        let data = SpanData {
+            context: parent.map_or_else(TraceContext::new_root, |parent| parent.child()),
+            parent_span_id: parent.map(|parent| parent.span_id),
+            start: SystemTime::now(),
+            attributes: visitor.attributes,
+        };
+        span.extensions_mut().insert(data);
+    }
+
+    fn on_record(&self, id: &span::Id, values: &span::Record<'_>, ctx: layer::Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut visitor = SpanFieldVisitor::new();
+        values.record(&mut visitor);
+
+        if let Some(data) = span.extensions_mut().get_mut::<SpanData>() {
+            data.attributes.extend(visitor.attributes);
+        }
+    }
+
+    fn on_close(&self, id: span::Id, ctx: layer::Context<'_, S>) {
+        if !EXPORT_ENABLED.load(Ordering::Relaxed) {
+            return;
+        }
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let Some(data) = span.extensions_mut().remove::<SpanData>() else {
+            return;
+        };
+
+        let otlp_span = otlp_span(span.name(), &data, SystemTime::now());
+        let mut spans = FINISHED_SPANS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if spans.len() < MAX_BUFFERED_SPANS {
+            spans.push(otlp_span);
+        }
+    }
+}
+
+/// Collects the fields of a span as attributes, other than the remote
+/// parent, which it parses. Attributes are only collected when export is
+/// enabled.
+struct SpanFieldVisitor {
+    collect_attributes: bool,
+    remote_parent: Option<TraceContext>,
+    attributes: Vec<(&'static str, String)>,
+}
+
+impl SpanFieldVisitor {
+    fn new() -> Self {
+        Self {
+            collect_attributes: EXPORT_ENABLED.load(Ordering::Relaxed),
+            remote_parent: None,
+            attributes: Vec::new(),
+        }
+    }
+}
+
+impl tracing::field::Visit for SpanFieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == TRACE_PARENT_FIELD {
+            self.remote_parent = value.parse().ok();
+        } else if self.collect_attributes {
+            self.attributes.push((field.name(), value.to_string()));
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == TRACE_PARENT_FIELD || self.collect_attributes {
+            self.record_str(field, &format!("{value:?}"));
+        }
+    }
+}
+
+/// Return the nanoseconds since the unix epoch of the given time as a
+/// string, which is how OTLP over HTTP encodes timestamps in JSON.
+fn unix_nanos(time: SystemTime) -> String {
+    let nanos = time
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    nanos.to_string()
+}
+
+/// Encode a closed span as an OTLP span in JSON.
+fn otlp_span(name: &str, data: &SpanData, end: SystemTime) -> Value {
+    let attributes: Vec<Value> = data
+        .attributes
+        .iter()
+        .map(|(key, value)| json!({ "key": key, "value": { "stringValue": value } }))
+        .collect();
+
+    let mut span = json!({
+        "traceId": format!("{:032x}", data.context.trace_id),
+        "spanId": format!("{:016x}", data.context.span_id),
+        "name": name,
+        "kind": SPAN_KIND_INTERNAL,
+        "startTimeUnixNano": unix_nanos(data.start),
+        "endTimeUnixNano": unix_nanos(end),
+        "attributes": attributes,
+    });
+    if let Some(parent_span_id) = data.parent_span_id {
+        span["parentSpanId"] = Value::String(format!("{parent_span_id:016x}"));
+    }
+    span
+}
+
+/// A background task that exports the signer's spans to an OpenTelemetry
+/// collector.
+pub struct OtlpExporter<C> {
+    /// Signer context.
+    context: C,
+    /// The OTLP over HTTP endpoint of the collector.
+    endpoint: Url,
+    /// The HTTP client used to send the spans to the collector.
+    client: reqwest::Client,
+}
+
+impl<C> OtlpExporter<C>
+where
+    C: Context,
+{
+    /// Creates a new OtlpExporter, which enables the buffering of spans
+    /// for export.
+    pub fn new(context: C, endpoint: Url) -> Self {
+        EXPORT_ENABLED.store(true, Ordering::Relaxed);
+        Self {
+            context,
+            endpoint,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Runs the OtlpExporter, which exports the spans that have closed
+    /// every [`EXPORT_INTERVAL`] until the signer is shut down, and once
+    /// more after that.
+    pub async fn run(self) {
+        let mut term = self.context.get_termination_handle();
+        loop {
+            tokio::select! {
+                _ = term.wait_for_shutdown() => {
+                    break;
+                }
+                _ = tokio::time::sleep(EXPORT_INTERVAL) => {
+                    self.export().await;
+                }
+            }
+        }
+        self.export().await;
+        tracing::info!("OTLP exporter has stopped");
+    }
+
+    /// Send the spans that have closed since the last export to the
+    /// collector. Spans that fail to be exported are dropped.
+    pub async fn export(&self) {
+        let spans = std::mem::take(
+            &mut *FINISHED_SPANS
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner),
+        );
+        if spans.is_empty() {
+            return;
+        }
+
+        let num_spans = spans.len();
+        let request = self.export_request(spans);
+        let result = self
+            .client
+            .post(self.endpoint.clone())
+            .json(&request)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status);
+
+        if let Err(error) = result {
+            tracing::warn!(%error, num_spans, "failed to export spans to the collector");
+        }
+    }
+
+    /// Wrap the given OTLP spans in an export request for the signer.
+    fn export_request(&self, spans: Vec<Value>) -> Value {
+        let public_key = self.context.config().signer.public_key().to_string();
+
+        json!({
+            "resourceSpans": [{
+                "resource": {
+                    "attributes": [
+                        { "key": "service.name", "value": { "stringValue": SERVICE_NAME } },
+                        { "key": "service.version", "value": { "stringValue": crate::GIT_COMMIT } },
+                        { "key": "service.instance.id", "value": { "stringValue": public_key } },
+                    ],
+                },
+                "scopeSpans": [{
+                    "scope": { "name": "signer" },
+                    "spans": spans,
+                }],
+            }],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing_subscriber::layer::SubscriberExt as _;
+
+    use super::*;
+
+    #[test]
+    fn trace_contexts_round_trip_as_traceparents() {
+        let context = TraceContext::new_root();
+        let traceparent = context.to_string();
+        assert_eq!(traceparent.len(), 55);
+        assert_eq!(traceparent.parse::<TraceContext>().unwrap(), context);
+
+        let child = context.child();
+        assert_eq!(child.trace_id, context.trace_id);
+        assert_ne!(child.span_id, context.span_id);
+    }
+
+    #[test_case::test_case("00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-00"; "not sampled")]
+    #[test_case::test_case("00-0AF7651916CD43DD8448EB211C80319C-b7ad6b7169203331-01"; "uppercase")]
+    #[test_case::test_case("00-00000000000000000000000000000000-b7ad6b7169203331-01"; "zero trace id")]
+    #[test_case::test_case("00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331"; "missing flags")]
+    #[test_case::test_case("00-0af7651916cd43dd-b7ad6b7169203331-01"; "short trace id")]
+    #[test_case::test_case(""; "empty")]
+    fn other_traceparents_are_rejected(traceparent: &str) {
+        assert!(traceparent.parse::<TraceContext>().is_err());
+    }
+
+    #[test]
+    fn spans_inherit_local_and_remote_trace_contexts() {
+        let subscriber = tracing_subscriber::registry().with(TraceContextLayer);
+        tracing::subscriber::with_default(subscriber, || {
+            assert!(current_trace_context().is_none());
+
+            let root = tracing::info_span!("root");
+            let root_context = root.in_scope(current_trace_context).unwrap();
+
+            let child = root.in_scope(|| tracing::info_span!("child"));
+            let child_context = child.in_scope(current_trace_context).unwrap();
+            assert_eq!(child_context.trace_id, root_context.trace_id);
+            assert_ne!(child_context.span_id, root_context.span_id);
+
+            // A span with a remote parent joins the remote trace, even if
+            // it is created within a local span.
+            let remote = TraceContext::new_root();
+            let handler = child.in_scope(|| tracing::info_span!("handler", trace_parent = %remote));
+            let handler_context = handler.in_scope(current_trace_context).unwrap();
+            assert_eq!(handler_context.trace_id, remote.trace_id);
+            assert_ne!(handler_context.span_id, remote.span_id);
+        });
+    }
+
+    #[test]
+    fn closed_spans_are_encoded_as_otlp_spans() {
+        let parent = TraceContext::new_root();
+        let data = SpanData {
+            context: parent.child(),
+            parent_span_id: Some(parent.span_id),
+            start: SystemTime::UNIX_EPOCH + Duration::from_secs(1),
+            attributes: vec![("correlation_id", "deposit".to_string())],
+        };
+        let end = SystemTime::UNIX_EPOCH + Duration::from_secs(2);
+
+        let span = otlp_span("sign", &data, end);
+        assert_eq!(span["traceId"], format!("{:032x}", parent.trace_id));
+        assert_eq!(span["parentSpanId"], format!("{:016x}", parent.span_id));
+        assert_eq!(span["startTimeUnixNano"], "1000000000");
+        assert_eq!(span["endTimeUnixNano"], "2000000000");
+        assert_eq!(span["attributes"][0]["key"], "correlation_id");
+        assert_eq!(span["attributes"][0]["value"]["stringValue"], "deposit");
+    }
+}
@@ -1,11 +1,14 @@
 //! Test utilities for the block observer
 
 use std::collections::HashMap;
-use std::ops::Deref as _;
+use std::sync::Arc;
+use std::sync::Mutex;
 
 use bitcoin::Amount;
 use bitcoin::BlockHash;
+use bitcoin::CompactTarget;
 use bitcoin::OutPoint;
+use bitcoin::TxMerkleNode;
 use bitcoin::Txid;
 use bitcoin::hashes::Hash as _;
 use bitcoincore_rpc_json::GetTxOutResult;
@@ -19,9 +22,12 @@ use blockstack_lib::types::chainstate::StacksBlockId;
 use clarity::types::chainstate::BurnchainHeaderHash;
 use clarity::types::chainstate::SortitionId;
 use emily_client::models::DepositStatus;
+use emily_client::models::DepositUpdate;
 use fake::Fake as _;
 use rand::seq::IteratorRandom as _;
 use sbtc::deposits::CreateDepositRequest;
+use sbtc::ids::DepositId;
+use sbtc::ids::WithdrawalId;
 
 use crate::bitcoin::BitcoinBlockHashStreamProvider;
 use crate::bitcoin::BitcoinInteract;
@@ -40,6 +46,7 @@ use crate::stacks::api::AccountInfo;
 use crate::stacks::api::FeePriority;
 use crate::stacks::api::GetNodeInfoResponse;
 use crate::stacks::api::GetTenureInfoResponse;
+use crate::stacks::api::RegistryWithdrawalRequest;
 use crate::stacks::api::SignerSetInfo;
 use crate::stacks::api::StacksBlockHeader;
 use crate::stacks::api::StacksEpochStatus;
@@ -69,6 +76,8 @@ pub struct TestHarness {
     /// This represents deposit requests that have not been processed, i.e.
     /// they are received from the Emily API.
     pending_deposits: Vec<CreateDepositRequest>,
+    /// The deposit updates that were sent to the Emily API.
+    deposit_updates: Arc<Mutex<Vec<DepositUpdate>>>,
 }
 
 impl TestHarness {
@@ -124,6 +133,14 @@ impl TestHarness {
         self.pending_deposits.extend(deposits.iter().cloned());
     }
 
+    /// Get the deposit updates that were sent to Emily.
+    pub fn deposit_updates(&self) -> Vec<DepositUpdate> {
+        self.deposit_updates
+            .lock()
+            .expect("BUG: Failed to acquire lock of the deposit updates")
+            .clone()
+    }
+
     /// Generate a new test harness with random data.
     pub fn generate(
         rng: &mut impl rand::RngCore,
@@ -186,6 +203,7 @@ impl TestHarness {
             stacks_blocks,
             deposits: HashMap::new(),
             pending_deposits: Vec::new(),
+            deposit_updates: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
@@ -261,9 +279,22 @@ impl BitcoinInteract for TestHarness {
                 height: block.height,
                 time: block.time,
                 previous_block_hash: block.previous_block_hash,
+                version: 0,
+                merkle_root: TxMerkleNode::all_zeros(),
+                bits: CompactTarget::from_consensus(0),
+                nonce: 0,
             }))
     }
 
+    async fn get_block_filter(
+        &self,
+        _block_hash: &BlockHash,
+    ) -> Result<Option<bitcoin::bip158::BlockFilter>, Error> {
+        // The test harness behaves like a node without a block filter
+        // index, so the block observer always scans full blocks.
+        Ok(None)
+    }
+
     async fn get_tx_info(
         &self,
         txid: &Txid,
@@ -324,6 +355,31 @@ impl BitcoinInteract for TestHarness {
         unimplemented!()
     }
 
+    async fn get_tx_out_proof(
+        &self,
+        txid: &bitcoin::Txid,
+        block_hash: &BlockHash,
+    ) -> Result<bitcoin::MerkleBlock, Error> {
+        if !self.deposits.contains_key(txid) {
+            return Err(Error::BitcoinTxMissing(*txid, Some(*block_hash)));
+        }
+        // The blocks in the harness do not hold their transactions, so we
+        // prove the inclusion of the transaction in a block of its own.
+        let header = bitcoin::block::Header {
+            version: bitcoin::block::Version::TWO,
+            prev_blockhash: *block_hash,
+            merkle_root: TxMerkleNode::from_raw_hash(txid.to_raw_hash()),
+            time: 0,
+            bits: CompactTarget::from_consensus(0),
+            nonce: 0,
+        };
+        let proof =
+            bitcoin::MerkleBlock::from_header_txids_with_predicate(&header, &[*txid], |id| {
+                id == txid
+            });
+        Ok(proof)
+    }
+
     async fn get_blockchain_info(
         &self,
     ) -> Result<bitcoincore_rpc_json::GetBlockchainInfoResult, Error> {
@@ -363,6 +419,13 @@ impl StacksInteract for TestHarness {
     async fn is_withdrawal_completed(&self, _: &StacksAddress, _: u64) -> Result<bool, Error> {
         unimplemented!()
     }
+    async fn get_withdrawal_request(
+        &self,
+        _: &StacksAddress,
+        _: u64,
+    ) -> Result<Option<RegistryWithdrawalRequest>, Error> {
+        unimplemented!()
+    }
     async fn get_account(&self, _address: &StacksAddress) -> Result<AccountInfo, Error> {
         // issue #118
         todo!()
@@ -533,20 +596,18 @@ impl StacksInteract for TestHarness {
     async fn get_sbtc_total_supply(&self, _: &StacksAddress) -> Result<Amount, Error> {
         Ok(Amount::from_sat(u64::MAX))
     }
+
+    async fn is_sbtc_contract_paused(&self, _: &StacksAddress) -> Result<bool, Error> {
+        Ok(false)
+    }
 }
 
 impl EmilyInteract for TestHarness {
-    async fn get_deposit(
-        &self,
-        txid: &model::BitcoinTxId,
-        output_index: u32,
-    ) -> Result<Option<CreateDepositRequest>, Error> {
+    async fn get_deposit(&self, id: &DepositId) -> Result<Option<CreateDepositRequest>, Error> {
         let deposit = self
             .pending_deposits
             .iter()
-            .find(|request| {
-                &request.outpoint.txid == txid.deref() && request.outpoint.vout == output_index
-            })
+            .find(|request| request.outpoint == id.outpoint())
             .cloned();
         Ok(deposit)
     }
@@ -566,9 +627,13 @@ impl EmilyInteract for TestHarness {
 
     async fn update_deposits(
         &self,
-        _update_deposits: Vec<emily_client::models::DepositUpdate>,
+        update_deposits: Vec<emily_client::models::DepositUpdate>,
     ) -> Result<emily_client::models::UpdateDepositsResponse, Error> {
-        unimplemented!()
+        self.deposit_updates
+            .lock()
+            .expect("BUG: Failed to acquire lock of the deposit updates")
+            .extend(update_deposits);
+        Ok(emily_client::models::UpdateDepositsResponse { deposits: Vec::new() })
     }
 
     async fn accept_deposits<'a>(
@@ -595,6 +660,24 @@ impl EmilyInteract for TestHarness {
     async fn get_limits(&self) -> Result<SbtcLimits, Error> {
         Ok(SbtcLimits::unlimited())
     }
+
+    async fn set_available_to_mint(&self, _available_to_mint: Amount) -> Result<(), Error> {
+        Ok(())
+    }
+
+    async fn get_withdrawal(
+        &self,
+        _id: WithdrawalId,
+    ) -> Result<Option<emily_client::models::Withdrawal>, Error> {
+        Ok(None)
+    }
+
+    async fn get_withdrawals_with_status(
+        &self,
+        _status: emily_client::models::WithdrawalStatus,
+    ) -> Result<Vec<emily_client::models::WithdrawalInfo>, Error> {
+        Ok(Vec::new())
+    }
 }
 
 fn get_node_info_data() -> GetNodeInfoResponse {
@@ -0,0 +1,446 @@
+//! Test utilities for injecting byzantine behavior into a signer.
+//!
+//! A [`ByzantineNetwork`] wraps a signer's [`MessageTransfer`] handle and
+//! tampers with the messages that the signer broadcasts, according to the
+//! [`ByzantineBehavior`]s that are currently enabled. Since the signer's
+//! event loops only ever talk to their peers through this handle, wrapping
+//! it is enough to turn an otherwise honest signer into a byzantine one,
+//! which lets tests check that the honest majority still completes DKG and
+//! signing rounds, or fails safely.
+//!
+//! Behaviors are kept in a shared [`ByzantineBehaviors`] set, so tests can
+//! turn them on and off while the event loops are running.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::sync::RwLock;
+
+use crate::ecdsa::SignEcdsa as _;
+use crate::error::Error;
+use crate::keys::PrivateKey;
+use crate::message::Payload;
+use crate::message::SignerMessage;
+use crate::network::MessageTransfer;
+use crate::network::Msg;
+use crate::storage::model::BitcoinBlockHash;
+
+/// A misbehavior that a byzantine signer can exhibit when broadcasting
+/// messages to its peers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ByzantineBehavior {
+    /// Follow every decision and WSTS message with a conflicting version
+    /// of the same message. Decisions have their vote flipped, while WSTS
+    /// messages are followed by a malformed copy.
+    Equivocate,
+    /// Silently drop DKG private shares and signature shares, while
+    /// sending every other message as usual.
+    WithholdShares,
+    /// Corrupt the contents of WSTS messages that carry key material,
+    /// nonces or signature shares.
+    MalformedWsts,
+    /// Claim that the given block is the bitcoin chain tip in every
+    /// message.
+    LieAboutChainTip(BitcoinBlockHash),
+}
+
+/// A set of enabled byzantine behaviors that can be shared between many
+/// [`ByzantineNetwork`]s and modified while they are in use.
+#[derive(Debug, Clone, Default)]
+pub struct ByzantineBehaviors(Arc<RwLock<HashSet<ByzantineBehavior>>>);
+
+impl ByzantineBehaviors {
+    /// Enable the given behavior.
+    pub fn enable(&self, behavior: ByzantineBehavior) {
+        self.0.write().unwrap().insert(behavior);
+    }
+
+    /// Disable the given behavior.
+    pub fn disable(&self, behavior: ByzantineBehavior) {
+        self.0.write().unwrap().remove(&behavior);
+    }
+
+    /// Disable all behaviors, making the signer honest again.
+    pub fn clear(&self) {
+        self.0.write().unwrap().clear();
+    }
+
+    /// Whether the given behavior is enabled.
+    pub fn is_enabled(&self, behavior: &ByzantineBehavior) -> bool {
+        self.0.read().unwrap().contains(behavior)
+    }
+
+    /// The bitcoin chain tip that we are lying about, if any.
+    fn fake_chain_tip(&self) -> Option<BitcoinBlockHash> {
+        self.0
+            .read()
+            .unwrap()
+            .iter()
+            .find_map(|behavior| match behavior {
+                ByzantineBehavior::LieAboutChainTip(block_hash) => Some(*block_hash),
+                _ => None,
+            })
+    }
+}
+
+/// A [`MessageTransfer`] implementation that wraps another one and
+/// tampers with outgoing messages according to the enabled
+/// [`ByzantineBehaviors`]. Incoming messages are passed through untouched.
+#[derive(Debug, Clone)]
+pub struct ByzantineNetwork<N> {
+    inner: N,
+    /// The private key used to re-sign tampered messages, so that they
+    /// pass signature verification on the receiving end.
+    private_key: PrivateKey,
+    behaviors: ByzantineBehaviors,
+}
+
+impl<N> ByzantineNetwork<N> {
+    /// Wrap the given network handle.
+    pub fn new(inner: N, private_key: PrivateKey, behaviors: ByzantineBehaviors) -> Self {
+        Self { inner, private_key, behaviors }
+    }
+
+    /// The behaviors that this network is subject to.
+    pub fn behaviors(&self) -> &ByzantineBehaviors {
+        &self.behaviors
+    }
+}
+
+impl<N> ByzantineNetwork<N>
+where
+    N: MessageTransfer + Send,
+{
+    async fn send(&mut self, msg: SignerMessage) -> Result<(), Error> {
+        self.inner
+            .broadcast(msg.sign_ecdsa(&self.private_key))
+            .await
+    }
+}
+
+impl<N> MessageTransfer for ByzantineNetwork<N>
+where
+    N: MessageTransfer + Send,
+{
+    async fn broadcast(&mut self, msg: Msg) -> Result<(), Error> {
+        let behaviors = &self.behaviors;
+        let mut message = msg.inner.clone();
+
+        if behaviors.is_enabled(&ByzantineBehavior::WithholdShares) && carries_shares(&message) {
+            tracing::debug!(payload = %message.payload, "byzantine signer withholding shares");
+            return Ok(());
+        }
+
+        let mut tampered = false;
+        if let Some(block_hash) = behaviors.fake_chain_tip() {
+            message.bitcoin_chain_tip = block_hash;
+            tampered = true;
+        }
+        if behaviors.is_enabled(&ByzantineBehavior::MalformedWsts) {
+            tampered |= malform(&mut message.payload);
+        }
+
+        let conflicting = behaviors
+            .is_enabled(&ByzantineBehavior::Equivocate)
+            .then(|| conflicting_payload(&message.payload))
+            .flatten()
+            .map(|payload| SignerMessage {
+                bitcoin_chain_tip: message.bitcoin_chain_tip,
+                payload,
+                deadline: message.deadline,
+                trace_context: message.trace_context,
+            });
+
+        if tampered {
+            self.send(message).await?;
+        } else {
+            self.inner.broadcast(msg).await?;
+        }
+
+        if let Some(conflicting) = conflicting {
+            tracing::debug!(payload = %conflicting.payload, "byzantine signer equivocating");
+            self.send(conflicting).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn receive(&mut self) -> Result<Msg, Error> {
+        self.inner.receive().await
+    }
+}
+
+/// Whether the message carries DKG private shares or signature shares.
+fn carries_shares(msg: &SignerMessage) -> bool {
+    let Payload::WstsMessage(wsts_msg) = &msg.payload else {
+        return false;
+    };
+    matches!(
+        wsts_msg.inner,
+        wsts::net::Message::DkgPrivateShares(_) | wsts::net::Message::SignatureShareResponse(_)
+    )
+}
+
+/// Corrupt the contents of a WSTS message. Returns whether the payload was
+/// modified.
+fn malform(payload: &mut Payload) -> bool {
+    let Payload::WstsMessage(wsts_msg) = payload else {
+        return false;
+    };
+    match &mut wsts_msg.inner {
+        wsts::net::Message::DkgPublicShares(msg) => msg.comms.clear(),
+        wsts::net::Message::DkgPrivateShares(msg) => msg
+            .shares
+            .iter_mut()
+            .flat_map(|(_, shares)| shares.values_mut())
+            .flat_map(|bytes| bytes.iter_mut())
+            .for_each(|byte| *byte = !*byte),
+        wsts::net::Message::NonceResponse(msg) => msg.nonces.clear(),
+        wsts::net::Message::SignatureShareResponse(msg) => msg.signature_shares.clear(),
+        wsts::net::Message::DkgBegin(_)
+        | wsts::net::Message::DkgPrivateBegin(_)
+        | wsts::net::Message::DkgEndBegin(_)
+        | wsts::net::Message::DkgEnd(_)
+        | wsts::net::Message::NonceRequest(_)
+        | wsts::net::Message::SignatureShareRequest(_) => return false,
+    }
+    true
+}
+
+/// Construct a payload that conflicts with the given one, if we know how
+/// to equivocate on this kind of payload.
+fn conflicting_payload(payload: &Payload) -> Option<Payload> {
+    match payload {
+        Payload::SignerDepositDecision(decision) => {
+            let mut decision = decision.clone();
+            decision.can_accept = !decision.can_accept;
+            Some(Payload::SignerDepositDecision(decision))
+        }
+        Payload::SignerWithdrawalDecision(decision) => {
+            let mut decision = decision.clone();
+            decision.accepted = !decision.accepted;
+            Some(Payload::SignerWithdrawalDecision(decision))
+        }
+        Payload::DecisionSyncResponse(response) => {
+            let mut response = response.clone();
+            response
+                .deposits
+                .iter_mut()
+                .for_each(|decision| decision.can_accept = !decision.can_accept);
+            response
+                .withdrawals
+                .iter_mut()
+                .for_each(|decision| decision.accepted = !decision.accepted);
+            Some(Payload::DecisionSyncResponse(response))
+        }
+        Payload::SignerDecisionBatch(batch) => {
+            let mut batch = batch.clone();
+            batch
+                .deposits
+                .iter_mut()
+                .for_each(|decision| decision.can_accept = !decision.can_accept);
+            batch
+                .withdrawals
+                .iter_mut()
+                .for_each(|decision| decision.accepted = !decision.accepted);
+            Some(Payload::SignerDecisionBatch(batch))
+        }
+        Payload::SweepTxProofResponse(response) if response.proof.is_some() => {
+            let mut response = response.clone();
+            response.proof = None;
+            Some(Payload::SweepTxProofResponse(response))
+        }
+        Payload::WstsMessage(_) => {
+            let mut payload = payload.clone();
+            malform(&mut payload).then_some(payload)
+        }
+        Payload::StacksTransactionSignRequest(_)
+        | Payload::StacksTransactionSignature(_)
+        | Payload::BitcoinPreSignRequest(_)
+        | Payload::BitcoinPreSignAck(_)
+        | Payload::KeyRotationAttestation(_)
+        | Payload::DecisionSyncRequest(_)
+        | Payload::CoordinatorTakeover(_)
+        | Payload::CoordinatorSilenceAttestation(_)
+        | Payload::PeerAddressAnnouncement(_)
+        | Payload::RotateKeysDryRunRequest(_)
+        | Payload::RotateKeysDryRunVerdict(_)
+        | Payload::SweepTxProofRequest(_)
+        | Payload::SweepTxProofResponse(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    use fake::Fake as _;
+    use fake::Faker;
+
+    use crate::keys::PublicKey;
+    use crate::message::SignerDepositDecision;
+    use crate::message::WstsMessage;
+    use crate::message::WstsMessageId;
+    use crate::network::in_memory2::SignerNetworkInstance;
+    use crate::network::in_memory2::WanNetwork;
+    use crate::storage::model;
+    use crate::testing::context::*;
+    use crate::testing::get_rng;
+
+    use super::*;
+
+    struct Setup {
+        byzantine: ByzantineNetwork<SignerNetworkInstance>,
+        honest: SignerNetworkInstance,
+        private_key: PrivateKey,
+        chain_tip: BitcoinBlockHash,
+    }
+
+    fn setup() -> Setup {
+        let mut rng = get_rng();
+        let network = WanNetwork::default();
+        let private_key = PrivateKey::new(&mut rng);
+
+        let byzantine_ctx = TestContext::default_mocked();
+        let honest_ctx = TestContext::default_mocked();
+        let byzantine = ByzantineNetwork::new(
+            network.connect(&byzantine_ctx).spawn(),
+            private_key,
+            ByzantineBehaviors::default(),
+        );
+        let honest = network.connect(&honest_ctx).spawn();
+
+        Setup {
+            byzantine,
+            honest,
+            private_key,
+            chain_tip: Faker.fake_with_rng(&mut rng),
+        }
+    }
+
+    fn deposit_decision(setup: &Setup, can_accept: bool) -> Msg {
+        let signer: model::DepositSigner = Faker.fake_with_rng(&mut get_rng());
+        let mut decision = SignerDepositDecision::from(signer);
+        decision.can_accept = can_accept;
+        SignerMessage {
+            bitcoin_chain_tip: setup.chain_tip,
+            payload: Payload::SignerDepositDecision(decision),
+            deadline: None,
+            trace_context: None,
+        }
+        .sign_ecdsa(&setup.private_key)
+    }
+
+    fn private_shares(setup: &Setup) -> Msg {
+        let shares = wsts::net::DkgPrivateShares {
+            dkg_id: 1,
+            signer_id: 0,
+            shares: vec![(1, HashMap::from([(1, vec![1, 2, 3])]))],
+        };
+        let wsts_msg = WstsMessage {
+            id: WstsMessageId::Dkg([1; 32]),
+            inner: wsts::net::Message::DkgPrivateShares(shares),
+        };
+        SignerMessage {
+            bitcoin_chain_tip: setup.chain_tip,
+            payload: Payload::WstsMessage(wsts_msg),
+            deadline: None,
+            trace_context: None,
+        }
+        .sign_ecdsa(&setup.private_key)
+    }
+
+    async fn receive(network: &mut SignerNetworkInstance) -> Msg {
+        tokio::time::timeout(Duration::from_secs(1), network.receive())
+            .await
+            .expect("timed out waiting for message")
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn honest_by_default() {
+        let mut setup = setup();
+        let msg = deposit_decision(&setup, true);
+
+        setup.byzantine.broadcast(msg.clone()).await.unwrap();
+        assert_eq!(receive(&mut setup.honest).await, msg);
+    }
+
+    #[tokio::test]
+    async fn withholds_shares() {
+        let mut setup = setup();
+        let behaviors = setup.byzantine.behaviors().clone();
+        behaviors.enable(ByzantineBehavior::WithholdShares);
+
+        let shares = private_shares(&setup);
+        let decision = deposit_decision(&setup, true);
+        setup.byzantine.broadcast(shares).await.unwrap();
+        setup.byzantine.broadcast(decision.clone()).await.unwrap();
+
+        // The shares were dropped, so the first thing we see is the
+        // decision.
+        assert_eq!(receive(&mut setup.honest).await, decision);
+    }
+
+    #[tokio::test]
+    async fn lies_about_chain_tip_with_valid_signature() {
+        let mut setup = setup();
+        let fake_tip: BitcoinBlockHash = Faker.fake_with_rng(&mut get_rng());
+        let behaviors = setup.byzantine.behaviors().clone();
+        behaviors.enable(ByzantineBehavior::LieAboutChainTip(fake_tip));
+
+        let msg = deposit_decision(&setup, true);
+        setup.byzantine.broadcast(msg.clone()).await.unwrap();
+
+        let received = receive(&mut setup.honest).await;
+        assert_eq!(received.bitcoin_chain_tip, fake_tip);
+        assert_eq!(received.payload, msg.payload);
+        assert_eq!(
+            received.signer_public_key,
+            PublicKey::from_private_key(&setup.private_key)
+        );
+    }
+
+    #[tokio::test]
+    async fn equivocates_on_decisions() {
+        let mut setup = setup();
+        let behaviors = setup.byzantine.behaviors().clone();
+        behaviors.enable(ByzantineBehavior::Equivocate);
+
+        let msg = deposit_decision(&setup, true);
+        setup.byzantine.broadcast(msg.clone()).await.unwrap();
+
+        assert_eq!(receive(&mut setup.honest).await, msg);
+        let conflicting = receive(&mut setup.honest).await;
+        let Payload::SignerDepositDecision(decision) = conflicting.inner.payload else {
+            panic!("expected a deposit decision");
+        };
+        assert!(!decision.can_accept);
+
+        // Once disabled, we go back to sending messages once.
+        behaviors.clear();
+        let msg = deposit_decision(&setup, false);
+        setup.byzantine.broadcast(msg.clone()).await.unwrap();
+        assert_eq!(receive(&mut setup.honest).await, msg);
+    }
+
+    #[tokio::test]
+    async fn malforms_wsts_messages() {
+        let mut setup = setup();
+        let behaviors = setup.byzantine.behaviors().clone();
+        behaviors.enable(ByzantineBehavior::MalformedWsts);
+
+        let msg = private_shares(&setup);
+        setup.byzantine.broadcast(msg.clone()).await.unwrap();
+
+        let received = receive(&mut setup.honest).await;
+        assert_ne!(received.payload, msg.payload);
+        let Payload::WstsMessage(wsts_msg) = &received.payload else {
+            panic!("expected a WSTS message");
+        };
+        let wsts::net::Message::DkgPrivateShares(shares) = &wsts_msg.inner else {
+            panic!("expected DKG private shares");
+        };
+        assert_eq!(shares.shares[0].1[&1], vec![!1, !2, !3]);
+    }
+}
@@ -12,6 +12,7 @@ use blockstack_lib::{
 };
 use clarity::types::chainstate::StacksAddress;
 use emily_client::models::DepositStatus;
+use sbtc::ids::{DepositId, WithdrawalId};
 use tokio::sync::{Mutex, broadcast};
 use tokio::time::error::Elapsed;
 
@@ -22,20 +23,24 @@ use crate::context::SbtcLimits;
 use crate::keys::PrivateKey;
 use crate::stacks::api::GetNodeInfoResponse;
 use crate::stacks::api::GetTenureInfoResponse;
+use crate::stacks::api::RegistryWithdrawalRequest;
 use crate::stacks::api::SignerSetInfo;
 use crate::stacks::api::StacksEpochStatus;
 use crate::stacks::api::TenureBlockHeaders;
 use crate::stacks::wallet::SignerWallet;
 use crate::storage::Transactable;
 use crate::storage::model::ConsensusHash;
-use crate::storage::model::{BitcoinTxId, StacksBlockHash};
+use crate::storage::model::StacksBlockHash;
 use crate::{
     bitcoin::{
         BitcoinInteract, MockBitcoinInteract, rpc::BitcoinCoreClientParams, rpc::GetTxResponse,
         utxo::UnsignedTransaction,
     },
     config::Settings,
-    context::{Context, SignerContext, SignerSignal, SignerState, TerminationHandle},
+    context::{
+        Context, RngProvider, SignerContext, SignerRng, SignerSignal, SignerState,
+        TerminationHandle,
+    },
     emily_client::{EmilyInteract, MockEmilyInteract},
     error::Error,
     keys::PublicKey,
@@ -305,6 +310,10 @@ where
     fn get_emily_client(&self) -> impl EmilyInteract + Clone + 'static {
         self.inner.get_emily_client()
     }
+
+    fn get_rng(&self, stream: &str) -> SignerRng {
+        self.inner.get_rng(stream)
+    }
 }
 
 /// A wrapper around a mock which can be cloned and shared between threads.
@@ -359,6 +368,13 @@ impl BitcoinInteract for WrappedMockBitcoinInteract {
         self.inner.lock().await.get_block_header(block_hash).await
     }
 
+    async fn get_block_filter(
+        &self,
+        block_hash: &bitcoin::BlockHash,
+    ) -> Result<Option<bitcoin::bip158::BlockFilter>, Error> {
+        self.inner.lock().await.get_block_filter(block_hash).await
+    }
+
     async fn get_tx(&self, txid: &Txid) -> Result<Option<GetTxResponse>, Error> {
         self.inner.lock().await.get_tx(txid).await
     }
@@ -418,6 +434,18 @@ impl BitcoinInteract for WrappedMockBitcoinInteract {
         unimplemented!()
     }
 
+    async fn get_tx_out_proof(
+        &self,
+        txid: &bitcoin::Txid,
+        block_hash: &bitcoin::BlockHash,
+    ) -> Result<bitcoin::MerkleBlock, Error> {
+        self.inner
+            .lock()
+            .await
+            .get_tx_out_proof(txid, block_hash)
+            .await
+    }
+
     async fn get_blockchain_info(
         &self,
     ) -> Result<bitcoincore_rpc_json::GetBlockchainInfoResult, Error> {
@@ -480,6 +508,18 @@ impl StacksInteract for WrappedMockStacksInteract {
             .await
     }
 
+    async fn get_withdrawal_request(
+        &self,
+        contract_principal: &StacksAddress,
+        request_id: u64,
+    ) -> Result<Option<RegistryWithdrawalRequest>, Error> {
+        self.inner
+            .lock()
+            .await
+            .get_withdrawal_request(contract_principal, request_id)
+            .await
+    }
+
     async fn get_account(&self, address: &StacksAddress) -> Result<AccountInfo, Error> {
         self.inner.lock().await.get_account(address).await
     }
@@ -557,19 +597,22 @@ impl StacksInteract for WrappedMockStacksInteract {
     async fn get_sbtc_total_supply(&self, sender: &StacksAddress) -> Result<Amount, Error> {
         self.inner.lock().await.get_sbtc_total_supply(sender).await
     }
+
+    async fn is_sbtc_contract_paused(&self, deployer: &StacksAddress) -> Result<bool, Error> {
+        self.inner
+            .lock()
+            .await
+            .is_sbtc_contract_paused(deployer)
+            .await
+    }
 }
 
 impl EmilyInteract for WrappedMockEmilyInteract {
     async fn get_deposit(
         &self,
-        txid: &BitcoinTxId,
-        output_index: u32,
+        id: &DepositId,
     ) -> Result<Option<sbtc::deposits::CreateDepositRequest>, Error> {
-        self.inner
-            .lock()
-            .await
-            .get_deposit(txid, output_index)
-            .await
+        self.inner.lock().await.get_deposit(id).await
     }
 
     async fn get_deposits(&self) -> Result<Vec<sbtc::deposits::CreateDepositRequest>, Error> {
@@ -630,11 +673,38 @@ impl EmilyInteract for WrappedMockEmilyInteract {
     async fn get_limits(&self) -> Result<SbtcLimits, Error> {
         self.inner.lock().await.get_limits().await
     }
+
+    async fn set_available_to_mint(&self, available_to_mint: bitcoin::Amount) -> Result<(), Error> {
+        self.inner
+            .lock()
+            .await
+            .set_available_to_mint(available_to_mint)
+            .await
+    }
+
+    async fn get_withdrawal(
+        &self,
+        id: WithdrawalId,
+    ) -> Result<Option<emily_client::models::Withdrawal>, Error> {
+        self.inner.lock().await.get_withdrawal(id).await
+    }
+
+    async fn get_withdrawals_with_status(
+        &self,
+        status: emily_client::models::WithdrawalStatus,
+    ) -> Result<Vec<emily_client::models::WithdrawalInfo>, Error> {
+        self.inner
+            .lock()
+            .await
+            .get_withdrawals_with_status(status)
+            .await
+    }
 }
 
 /// Struct which holds the current configuration of the context builder.
 pub struct ContextConfig<Storage, Bitcoin, Stacks, Emily> {
     settings: crate::config::Settings,
+    rng: RngProvider,
     storage: Storage,
     bitcoin: Bitcoin,
     stacks: Stacks,
@@ -645,6 +715,7 @@ impl Default for ContextConfig<(), (), (), ()> {
     fn default() -> Self {
         Self {
             settings: Settings::new_from_default_config().expect("failed to load default config"),
+            rng: RngProvider::default(),
             storage: (),
             bitcoin: (),
             stacks: (),
@@ -708,6 +779,18 @@ where
         ContextBuilder { config }
     }
 
+    /// Configure the context to hand out random number generators that
+    /// are derived from the given seed.
+    fn with_rng_seed(self, seed: u64) -> ContextBuilder<Storage, Bitcoin, Stacks, Emily> {
+        let config = self.get_config();
+        ContextBuilder {
+            config: ContextConfig {
+                rng: RngProvider::from_seed(seed),
+                ..config
+            },
+        }
+    }
+
     /// Helper for configuring the context's settings with the specified signer
     /// private key.
     fn with_private_key(
@@ -739,6 +822,7 @@ where
         ContextBuilder {
             config: ContextConfig {
                 settings: config.settings,
+                rng: config.rng,
                 storage: Store::new_shared(),
                 bitcoin: config.bitcoin,
                 stacks: config.stacks,
@@ -756,6 +840,7 @@ where
         ContextBuilder {
             config: ContextConfig {
                 settings: config.settings,
+                rng: config.rng,
                 storage,
                 bitcoin: config.bitcoin,
                 stacks: config.stacks,
@@ -787,6 +872,7 @@ where
         ContextBuilder {
             config: ContextConfig {
                 settings: config.settings,
+                rng: config.rng,
                 storage: config.storage,
                 bitcoin: bitcoin_client,
                 stacks: config.stacks,
@@ -812,6 +898,7 @@ where
         ContextBuilder {
             config: ContextConfig {
                 settings: config.settings,
+                rng: config.rng,
                 storage: config.storage,
                 bitcoin: bitcoin_client,
                 stacks: config.stacks,
@@ -850,6 +937,7 @@ where
         ContextBuilder {
             config: ContextConfig {
                 settings: config.settings,
+                rng: config.rng,
                 storage: config.storage,
                 bitcoin: config.bitcoin,
                 stacks: stacks_client,
@@ -888,6 +976,7 @@ where
         ContextBuilder {
             config: ContextConfig {
                 settings: config.settings,
+                rng: config.rng,
                 storage: config.storage,
                 bitcoin: config.bitcoin,
                 stacks: config.stacks,
@@ -930,6 +1019,7 @@ where
         ContextBuilder {
             config: ContextConfig {
                 settings: config.settings,
+                rng: config.rng,
                 storage: config.storage,
                 bitcoin: WrappedMock::default(),
                 stacks: WrappedMock::default(),
@@ -968,13 +1058,15 @@ where
 {
     fn build(self) -> TestContext<Storage, Bitcoin, Stacks, Emily> {
         let config = self.get_config();
-        TestContext::new(
+        let mut context = TestContext::new(
             config.settings,
             config.storage,
             config.bitcoin,
             config.stacks,
             config.emily,
-        )
+        );
+        context.inner.set_rng_provider(config.rng);
+        context
     }
 }
 
@@ -24,6 +24,7 @@ use p256k1::scalar::Scalar;
 use polynomial::Polynomial;
 use rand::Rng;
 use rand::seq::IteratorRandom as _;
+use sbtc::ids::DepositId;
 use secp256k1::ecdsa::RecoverableSignature;
 use stacks_common::address::AddressHashMode;
 use stacks_common::address::C32_ADDRESS_VERSION_TESTNET_MULTISIG;
@@ -68,6 +69,7 @@ use crate::keys::SignerScriptPubKey as _;
 use crate::message::BitcoinPreSignAck;
 use crate::message::BitcoinPreSignRequest;
 use crate::message::SignerMessage;
+use crate::secret::Secret;
 use crate::stacks::contracts::AcceptWithdrawalV1;
 use crate::stacks::contracts::CompleteDepositV1;
 use crate::stacks::contracts::RejectWithdrawalV1;
@@ -366,7 +368,7 @@ pub fn encrypted_dkg_shares<R: rand::RngCore + rand::CryptoRng>(
 
     model::EncryptedDkgShares {
         aggregate_key: group_key,
-        encrypted_private_shares,
+        encrypted_private_shares: Secret::new(encrypted_private_shares),
         public_shares,
         tweaked_aggregate_key: group_key.signers_tweaked_pubkey().unwrap(),
         script_pubkey: group_key.signers_script_pubkey().into(),
@@ -526,7 +528,7 @@ impl fake::Dummy<SignerSetConfig> for EncryptedDkgShares {
             aggregate_key: PublicKey::combine_keys(&signer_set_public_keys).unwrap(),
             tweaked_aggregate_key: aggregate_key.signers_tweaked_pubkey().unwrap(),
             script_pubkey: aggregate_key.signers_script_pubkey().into(),
-            encrypted_private_shares: Vec::new(),
+            encrypted_private_shares: Secret::default(),
             public_shares: Vec::new(),
             signer_set_public_keys,
             signature_share_threshold: config.signatures_required,
@@ -546,6 +548,7 @@ impl fake::Dummy<&[PublicKey]> for SignerBtcState {
             fee_rate: Faker.fake_with_rng(rng),
             last_fees: Faker.fake_with_rng(rng),
             magic_bytes: [1, 2],
+            change_outputs: 1,
             public_key: aggregate_key_x_only,
             utxo: SignerUtxo {
                 amount: Faker.fake_with_rng(rng),
@@ -766,6 +769,8 @@ impl fake::Dummy<fake::Faker> for BitcoinPreSignRequest {
             request_package: fake::vec![TxRequestIds; 0..20],
             fee_rate: config.fake_with_rng(rng),
             last_fees: Some(config.fake_with_rng::<Fees, _>(rng).into()),
+            parallel_sweeps: config.fake_with_rng(rng),
+            magic_bytes: config.fake_with_rng(rng),
         }
     }
 }
@@ -824,6 +829,12 @@ impl Dummy<Unit> for bitcoin::OutPoint {
     }
 }
 
+impl Dummy<Unit> for DepositId {
+    fn dummy_with_rng<R: rand::Rng + ?Sized>(config: &Unit, rng: &mut R) -> Self {
+        <bitcoin::OutPoint as Dummy<Unit>>::dummy_with_rng(config, rng).into()
+    }
+}
+
 impl Dummy<Unit> for RecoverableSignature {
     fn dummy_with_rng<R: rand::Rng + ?Sized>(_: &Unit, rng: &mut R) -> Self {
         let private_key = PrivateKey::new(rng);
@@ -50,6 +50,17 @@ impl fake::Dummy<fake::Faker> for message::Payload {
             dummy_payload::<message::StacksTransactionSignature, _>,
             dummy_payload::<message::WstsMessage, _>,
             dummy_payload::<message::BitcoinPreSignRequest, _>,
+            dummy_payload::<message::KeyRotationAttestation, _>,
+            dummy_payload::<message::DecisionSyncRequest, _>,
+            dummy_payload::<message::DecisionSyncResponse, _>,
+            dummy_payload::<message::CoordinatorTakeover, _>,
+            dummy_payload::<message::CoordinatorSilenceAttestation, _>,
+            dummy_payload::<message::PeerAddressAnnouncement, _>,
+            dummy_payload::<message::SignerDecisionBatch, _>,
+            dummy_payload::<message::RotateKeysDryRunRequest, _>,
+            dummy_payload::<message::RotateKeysDryRunVerdict, _>,
+            dummy_payload::<message::SweepTxProofRequest, _>,
+            dummy_payload::<message::SweepTxProofResponse, _>,
         ];
         variants.choose(rng).unwrap()(config, rng)
     }
@@ -115,6 +126,170 @@ impl fake::Dummy<fake::Faker> for message::WstsMessage {
     }
 }
 
+impl fake::Dummy<fake::Faker> for message::KeyRotationAttestation {
+    fn dummy_with_rng<R: rand::RngCore + ?Sized>(config: &fake::Faker, rng: &mut R) -> Self {
+        Self {
+            aggregate_key: config.fake_with_rng(rng),
+            signer_set_hash: config.fake_with_rng(rng),
+            signatures_required: config.fake_with_rng(rng),
+            signature: dummy::Unit.fake_with_rng(rng),
+        }
+    }
+}
+
+impl fake::Dummy<fake::Faker> for message::DecisionSyncRequest {
+    fn dummy_with_rng<R: rand::RngCore + ?Sized>(config: &fake::Faker, rng: &mut R) -> Self {
+        let start_height: u64 = config.fake_with_rng(rng);
+        Self {
+            start_height: start_height.into(),
+            end_height: start_height
+                .saturating_add((0..100).fake_with_rng(rng))
+                .into(),
+        }
+    }
+}
+
+impl fake::Dummy<fake::Faker> for message::DecisionSyncResponse {
+    fn dummy_with_rng<R: rand::RngCore + ?Sized>(config: &fake::Faker, rng: &mut R) -> Self {
+        let request: message::DecisionSyncRequest = config.fake_with_rng(rng);
+        Self {
+            start_height: request.start_height,
+            end_height: request.end_height,
+            page: config.fake_with_rng(rng),
+            deposits: fake::vec![message::SignerDepositDecision; 0..5],
+            withdrawals: fake::vec![message::SignerWithdrawalDecision; 0..5],
+            has_more: config.fake_with_rng(rng),
+            requester: config.fake_with_rng(rng),
+        }
+    }
+}
+
+impl fake::Dummy<fake::Faker> for message::CoordinatorTakeover {
+    fn dummy_with_rng<R: rand::RngCore + ?Sized>(config: &fake::Faker, rng: &mut R) -> Self {
+        Self {
+            coordinator_public_key: config.fake_with_rng(rng),
+            attestations: fake::vec![message::CoordinatorSilenceAttestation; 0..5],
+        }
+    }
+}
+
+impl fake::Dummy<fake::Faker> for message::CoordinatorSilenceAttestation {
+    fn dummy_with_rng<R: rand::RngCore + ?Sized>(config: &fake::Faker, rng: &mut R) -> Self {
+        Self {
+            signer_public_key: config.fake_with_rng(rng),
+            coordinator_public_key: config.fake_with_rng(rng),
+            signature: dummy::Unit.fake_with_rng(rng),
+        }
+    }
+}
+
+impl fake::Dummy<fake::Faker> for message::PeerAddressAnnouncement {
+    fn dummy_with_rng<R: rand::RngCore + ?Sized>(config: &fake::Faker, rng: &mut R) -> Self {
+        let num_addresses: usize = (1..4).fake_with_rng(rng);
+        let addresses = (0..num_addresses)
+            .map(|_| {
+                let ip: std::net::Ipv4Addr = config.fake_with_rng(rng);
+                let port: u16 = config.fake_with_rng(rng);
+                libp2p::Multiaddr::from(ip).with(libp2p::multiaddr::Protocol::Tcp(port))
+            })
+            .collect();
+
+        Self {
+            addresses,
+            expires_at: config.fake_with_rng(rng),
+        }
+    }
+}
+
+impl fake::Dummy<fake::Faker> for message::SignerDecisionBatch {
+    fn dummy_with_rng<R: rand::RngCore + ?Sized>(_: &fake::Faker, _: &mut R) -> Self {
+        Self {
+            deposits: fake::vec![message::SignerDepositDecision; 0..5],
+            withdrawals: fake::vec![message::SignerWithdrawalDecision; 0..5],
+        }
+    }
+}
+
+impl fake::Dummy<fake::Faker> for message::RotateKeysDryRunRequest {
+    fn dummy_with_rng<R: rand::RngCore + ?Sized>(config: &fake::Faker, rng: &mut R) -> Self {
+        Self {
+            id: config.fake_with_rng(rng),
+            rotate_keys: config.fake_with_rng(rng),
+        }
+    }
+}
+
+impl fake::Dummy<fake::Faker> for message::RotateKeysDryRunVerdict {
+    fn dummy_with_rng<R: rand::RngCore + ?Sized>(config: &fake::Faker, rng: &mut R) -> Self {
+        Self {
+            id: config.fake_with_rng(rng),
+            rejection_reason: config.fake_with_rng(rng),
+        }
+    }
+}
+
+impl fake::Dummy<fake::Faker> for message::SweepTxProofRequest {
+    fn dummy_with_rng<R: rand::RngCore + ?Sized>(config: &fake::Faker, rng: &mut R) -> Self {
+        Self {
+            txid: config.fake_with_rng(rng),
+        }
+    }
+}
+
+impl fake::Dummy<fake::Faker> for message::SweepTxProofResponse {
+    fn dummy_with_rng<R: rand::RngCore + ?Sized>(config: &fake::Faker, rng: &mut R) -> Self {
+        let proof: Option<message::SweepTxProof> = config.fake_with_rng(rng);
+        // A proof matches exactly one transaction, the one that it is for.
+        let txid = match &proof {
+            Some(proof) => {
+                let mut matches = Vec::new();
+                proof
+                    .merkle_block
+                    .extract_matches(&mut matches, &mut Vec::new())
+                    .unwrap();
+                matches[0].into()
+            }
+            None => config.fake_with_rng(rng),
+        };
+        Self { txid, proof }
+    }
+}
+
+impl fake::Dummy<fake::Faker> for message::SweepTxProof {
+    fn dummy_with_rng<R: rand::RngCore + ?Sized>(config: &fake::Faker, rng: &mut R) -> Self {
+        let height: u32 = (1..1_000_000).fake_with_rng(rng);
+        let mut block = dummy::block(config, rng, i64::from(height));
+        block.header.merkle_root = block.compute_merkle_root().unwrap();
+
+        let txid = block.txdata.choose(rng).unwrap().compute_txid();
+        let merkle_block =
+            bitcoin::MerkleBlock::from_block_with_predicate(&block, |other| *other == txid);
+
+        let num_headers: usize = (0..10).fake_with_rng(rng);
+        let mut prev_blockhash = block.block_hash();
+        let headers = std::iter::repeat_with(|| {
+            let header = bitcoin::block::Header {
+                version: bitcoin::block::Version::TWO,
+                prev_blockhash,
+                merkle_root: dummy::merkle_root(config, rng),
+                time: config.fake_with_rng(rng),
+                bits: bitcoin::CompactTarget::from_consensus(config.fake_with_rng(rng)),
+                nonce: config.fake_with_rng(rng),
+            };
+            prev_blockhash = header.block_hash();
+            header
+        })
+        .take(num_headers)
+        .collect();
+
+        Self {
+            block_height: u64::from(height).into(),
+            merkle_block,
+            headers,
+        }
+    }
+}
+
 fn dummy_payload<P, R>(config: &fake::Faker, rng: &mut R) -> message::Payload
 where
     P: Into<message::Payload> + fake::Dummy<fake::Faker>,
@@ -6,11 +6,13 @@ pub mod api_clients;
 pub mod block_observer;
 pub mod blocks;
 pub mod btc;
+pub mod byzantine;
 pub mod context;
 pub mod dummy;
 pub mod message;
 pub mod network;
 pub mod request_decider;
+pub mod simulation;
 pub mod stacks;
 pub mod storage;
 pub mod transaction_coordinator;
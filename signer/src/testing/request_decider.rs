@@ -12,6 +12,7 @@ use crate::error::Error;
 use crate::keys::PrivateKey;
 use crate::keys::PublicKey;
 use crate::message::Payload;
+use crate::message::SignerDecisionBatch;
 use crate::network::MessageTransfer as _;
 use crate::network::in_memory2::SignerNetwork;
 use crate::network::in_memory2::SignerNetworkInstance;
@@ -53,10 +54,12 @@ impl<C: Context + 'static> RequestDeciderEventLoopHarness<C> {
                 context: context.clone(),
                 network: network.spawn(),
                 blocklist_checker: Some(()),
+                decision_policies: Vec::new(),
                 signer_private_key,
                 context_window,
                 deposit_decisions_retry_window,
                 withdrawal_decisions_retry_window,
+                carried_over: Default::default(),
             },
             context,
         }
@@ -147,6 +150,22 @@ where
     /// Assert that the transaction signer will make and store decisions
     /// for pending deposit requests.
     pub async fn assert_should_store_decisions_for_pending_deposit_requests(self) {
+        self.assert_deposit_decisions_are_stored_and_broadcast(true)
+            .await;
+    }
+
+    /// Assert that the transaction signer sends its decisions in
+    /// individual messages while some of the other signers have not
+    /// advertised a protocol version that handles decision batches.
+    pub async fn assert_should_send_individual_decisions_to_outdated_signers(self) {
+        self.assert_deposit_decisions_are_stored_and_broadcast(false)
+            .await;
+    }
+
+    /// Assert that the transaction signer will make and store decisions
+    /// for pending deposit requests, and broadcast them in a batch if the
+    /// other signers are `upgraded`, or in individual messages otherwise.
+    async fn assert_deposit_decisions_are_stored_and_broadcast(self, upgraded: bool) {
         let mut rng = get_rng();
         let wan_network = WanNetwork::default();
 
@@ -174,6 +193,14 @@ where
         let handle = event_loop_harness.start();
 
         let signer_set = &coordinator_signer_info.signer_public_keys;
+        if upgraded {
+            set_upgraded_signer_set(&handle.context, signer_set);
+        } else {
+            handle
+                .context
+                .state()
+                .update_current_signer_set(signer_set.clone());
+        }
         let test_data = self.generate_test_data(&mut rng, signer_set);
         Self::write_test_data(&handle.context.get_storage_mut(), &test_data).await;
 
@@ -239,7 +266,12 @@ where
 
         tokio::time::timeout(Duration::from_secs(1), async move {
             while let Ok(msg) = network_rx.receive().await {
-                if matches!(msg.payload, Payload::SignerDepositDecision(_)) {
+                let has_deposit_decisions = match &msg.payload {
+                    Payload::SignerDecisionBatch(batch) => upgraded && !batch.deposits.is_empty(),
+                    Payload::SignerDepositDecision(_) => !upgraded,
+                    _ => false,
+                };
+                if has_deposit_decisions {
                     break;
                 }
             }
@@ -279,6 +311,7 @@ where
         let handle = event_loop_harness.start();
 
         let signer_set = &coordinator_signer_info.signer_public_keys;
+        set_upgraded_signer_set(&handle.context, signer_set);
         let test_data = self.generate_test_data(&mut rng, signer_set);
         Self::write_test_data(&handle.context.get_storage_mut(), &test_data).await;
 
@@ -342,7 +375,11 @@ where
 
         tokio::time::timeout(Duration::from_secs(1), async move {
             while let Ok(msg) = network_rx.receive().await {
-                if matches!(msg.payload, Payload::SignerWithdrawalDecision(_)) {
+                let has_withdrawal_decisions = match &msg.payload {
+                    Payload::SignerDecisionBatch(batch) => !batch.withdrawals.is_empty(),
+                    _ => false,
+                };
+                if has_withdrawal_decisions {
                     break;
                 }
             }
@@ -387,6 +424,7 @@ where
         let signer_set = &coordinator_signer_info.signer_public_keys;
         let test_data = self.generate_test_data(&mut rng, signer_set);
         for handle in event_loop_handles.iter_mut() {
+            set_upgraded_signer_set(&handle.context, signer_set);
             test_data.write_to(&handle.context.get_storage_mut()).await;
 
             let db = handle.context.get_storage();
@@ -442,13 +480,16 @@ where
                 .expect("failed to send signal");
         }
 
-        let num_expected_decisions = ((self.num_signers - 1) * pending_deposits_count) as u16;
+        // Each signer broadcasts all of its decisions for the block in as
+        // few batches as possible.
+        let batches_per_signer = pending_deposits_count.div_ceil(SignerDecisionBatch::MAX_SIZE);
+        let num_expected_batches = ((self.num_signers - 1) * batches_per_signer) as u16;
 
-        // Wait for the expected number of decisions to be received by each signer.
+        // Wait for the expected number of batches to be received by each signer.
         for handle in event_loop_handles.iter_mut() {
-            let msg = RequestDeciderEvent::ReceivedDepositDecision;
+            let msg = RequestDeciderEvent::ReceivedDecisionBatch;
             handle
-                .wait_for_events(msg, num_expected_decisions, Duration::from_secs(10))
+                .wait_for_events(msg, num_expected_batches, Duration::from_secs(10))
                 .await
                 .expect("timed out waiting for events");
         }
@@ -464,6 +505,155 @@ where
         }
     }
 
+    /// Assert that a signer that rejoins the network asks the other
+    /// signers for their decisions and stores the decisions that it
+    /// receives in response.
+    pub async fn assert_should_sync_decisions_with_rejoining_signer(self) {
+        let mut rng = get_rng();
+        let network = WanNetwork::default();
+        let signer_info = testing::wsts::generate_signer_info(&mut rng, 2);
+        let signer_set = &signer_info.first().unwrap().signer_public_keys;
+        let test_data = self.generate_test_data(&mut rng, signer_set);
+
+        let mut handles = Vec::new();
+        for info in signer_info.iter() {
+            let ctx = TestContext::default_mocked();
+            let net = network.connect(&ctx);
+
+            test_data.write_to(&ctx.get_storage_mut()).await;
+            let db = ctx.get_storage();
+            let chain_tip_ref = db
+                .get_bitcoin_canonical_chain_tip_ref()
+                .await
+                .unwrap()
+                .unwrap();
+            let stacks_chain_tip = db
+                .get_stacks_chain_tip(&chain_tip_ref.block_hash)
+                .await
+                .unwrap()
+                .unwrap();
+            ctx.state().set_bitcoin_chain_tip(chain_tip_ref);
+            ctx.state().set_stacks_chain_tip(stacks_chain_tip.into());
+
+            let harness = RequestDeciderEventLoopHarness::create(
+                ctx,
+                net,
+                self.context_window,
+                self.deposit_decisions_retry_window,
+                self.withdrawal_decisions_retry_window,
+                info.signer_private_key,
+            );
+            handles.push(harness.start());
+        }
+
+        // The first signer has been online and made decisions on all of
+        // the requests, while the second signer has not seen any of them.
+        let online_signer = PublicKey::from_private_key(&signer_info[0].signer_private_key);
+        let online_db = handles[0].context.get_storage_mut();
+        for request in test_data.deposit_requests.iter() {
+            let decision = model::DepositSigner {
+                txid: request.txid,
+                output_index: request.output_index,
+                signer_pub_key: online_signer,
+                can_accept: true,
+                can_sign: true,
+            };
+            online_db
+                .write_deposit_signer_decision(&decision)
+                .await
+                .unwrap();
+        }
+        for request in test_data.withdraw_requests.iter() {
+            let decision = model::WithdrawalSigner {
+                request_id: request.request_id,
+                block_hash: request.block_hash,
+                txid: request.txid,
+                signer_pub_key: online_signer,
+                is_accepted: true,
+                rejection_reason: None,
+            };
+            online_db
+                .write_withdrawal_signer_decision(&decision)
+                .await
+                .unwrap();
+        }
+
+        // The rejoining signer requests a sync once it observes a block.
+        let rejoining = &mut handles[1];
+        let chain_tip_ref = rejoining.context.state().bitcoin_chain_tip().unwrap();
+        rejoining
+            .context
+            .signal(SignerSignal::Event(SignerEvent::BitcoinBlockObserved(
+                chain_tip_ref,
+            )))
+            .expect("failed to send signal");
+
+        rejoining
+            .wait_for_events(
+                RequestDeciderEvent::ReceivedDecisionSyncResponse,
+                1,
+                Duration::from_secs(10),
+            )
+            .await
+            .expect("timed out waiting for the decision sync response");
+
+        let start_height = chain_tip_ref
+            .block_height
+            .saturating_sub(self.context_window);
+        let end_height = chain_tip_ref.block_height;
+        let rejoining_db = rejoining.context.get_storage();
+
+        let expected_deposits = online_db
+            .get_deposit_signer_decisions_in_range(
+                &online_signer,
+                start_height,
+                end_height,
+                u16::MAX,
+                0,
+            )
+            .await
+            .unwrap();
+        let synced_deposits = rejoining_db
+            .get_deposit_signer_decisions_in_range(
+                &online_signer,
+                start_height,
+                end_height,
+                u16::MAX,
+                0,
+            )
+            .await
+            .unwrap();
+        assert!(!expected_deposits.is_empty());
+        assert_eq!(synced_deposits, expected_deposits);
+
+        let expected_withdrawals = online_db
+            .get_withdrawal_signer_decisions_in_range(
+                &online_signer,
+                start_height,
+                end_height,
+                u16::MAX,
+                0,
+            )
+            .await
+            .unwrap();
+        let synced_withdrawals = rejoining_db
+            .get_withdrawal_signer_decisions_in_range(
+                &online_signer,
+                start_height,
+                end_height,
+                u16::MAX,
+                0,
+            )
+            .await
+            .unwrap();
+        assert!(!expected_withdrawals.is_empty());
+        assert_eq!(synced_withdrawals, expected_withdrawals);
+
+        for handle in handles {
+            handle.abort();
+        }
+    }
+
     async fn write_test_data<S>(storage: &S, test_data: &TestData)
     where
         S: DbWrite,
@@ -619,6 +809,16 @@ where
     }
 }
 
+/// Make the given signers the current signer set of the context, with
+/// each of them having advertised the current protocol version.
+fn set_upgraded_signer_set<C: Context>(context: &C, signer_set: &BTreeSet<PublicKey>) {
+    let state = context.state();
+    state.update_current_signer_set(signer_set.clone());
+    for public_key in signer_set {
+        state.record_peer_protocol_version((*public_key).into(), crate::PROTOCOL_VERSION);
+    }
+}
+
 async fn store_dummy_dkg_shares<R, S>(
     rng: &mut R,
     signer_private_key: &[u8; 32],
@@ -0,0 +1,579 @@
+//! A deterministic, in-process simulation of a full signer network.
+//!
+//! The [`Simulation`] spins up N signers, each with its own in-memory
+//! store, mocked clients and the full set of signer event loops (request
+//! decider, transaction signer and transaction coordinator), all connected
+//! through a [`WanNetwork`]. Instead of real bitcoin and stacks nodes, the
+//! signers are driven by a [`SimulatedChain`], which produces blocks,
+//! deposit requests, withdrawal requests and reorgs from a seeded RNG, so
+//! a given seed always produces the same sequence of events. The signers'
+//! contexts hand out random number generators derived from the same seed.
+//!
+//! The simulated stacks node reports that Nakamoto has not activated, so
+//! the coordinators stand by by default. Tests that exercise signing rounds
+//! can install the bitcoin and stacks expectations they need on each
+//! signer's mocked clients.
+//!
+//! Every signer's network handle is wrapped in a [`ByzantineNetwork`], so
+//! tests can make any of the signers misbehave through
+//! [`SimulatedSigner::behaviors`].
+
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::future::Future;
+use std::num::NonZeroUsize;
+use std::time::Duration;
+
+use fake::Fake as _;
+use lru::LruCache;
+use rand::RngCore as _;
+use rand::SeedableRng as _;
+use rand::rngs::StdRng;
+use tokio::task::JoinHandle;
+use tokio::time::error::Elapsed;
+
+use crate::context::Context as _;
+use crate::context::SignerCommand;
+use crate::context::SignerEvent;
+use crate::context::SignerSignal;
+use crate::error::Error;
+use crate::keys::PrivateKey;
+use crate::keys::PublicKey;
+use crate::network::in_memory2::WanNetwork;
+use crate::request_decider::RequestDeciderEventLoop;
+use crate::stacks::api::StacksEpochStatus;
+use crate::storage::DbWrite;
+use crate::storage::memory::SharedStore;
+use crate::storage::model;
+use crate::storage::model::BitcoinBlockHash;
+use crate::storage::model::BitcoinBlockRef;
+use crate::storage::model::DkgSharesStatus;
+use crate::testing;
+use crate::testing::byzantine::ByzantineBehaviors;
+use crate::testing::byzantine::ByzantineNetwork;
+use crate::testing::context::*;
+use crate::transaction_coordinator::TxCoordinatorEventLoop;
+use crate::transaction_signer::STACKS_SIGN_REQUEST_LRU_SIZE;
+use crate::transaction_signer::STACKS_VALIDATION_LRU_SIZE;
+use crate::transaction_signer::TxSignerEventLoop;
+
+/// The context type used by each simulated signer.
+pub type SimulationContext = TestContext<
+    SharedStore,
+    WrappedMockBitcoinInteract,
+    WrappedMockStacksInteract,
+    WrappedMockEmilyInteract,
+>;
+
+/// Everything that was produced when the simulated chain mined a bitcoin
+/// block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulatedBlock {
+    /// The bitcoin block.
+    pub bitcoin_block: model::BitcoinBlock,
+    /// The stacks block anchored to the bitcoin block.
+    pub stacks_block: model::StacksBlock,
+    /// The deposit requests confirmed in the bitcoin block.
+    pub deposit_requests: Vec<model::DepositRequest>,
+    /// The bitcoin transactions that created the deposit requests.
+    pub bitcoin_transactions: Vec<model::BitcoinTxRef>,
+    /// The withdrawal requests created in the stacks block.
+    pub withdrawal_requests: Vec<model::WithdrawalRequest>,
+}
+
+impl SimulatedBlock {
+    /// A reference to the bitcoin block.
+    pub fn block_ref(&self) -> BitcoinBlockRef {
+        BitcoinBlockRef::from(&self.bitcoin_block)
+    }
+
+    /// Write the block and its requests to the given store.
+    pub async fn write_to<Db: DbWrite>(&self, db: &Db) {
+        db.write_bitcoin_block(&self.bitcoin_block).await.unwrap();
+        db.write_stacks_block(&self.stacks_block).await.unwrap();
+        for tx in self.bitcoin_transactions.iter() {
+            db.write_bitcoin_transaction(tx).await.unwrap();
+        }
+        for req in self.deposit_requests.iter() {
+            db.write_deposit_request(req).await.unwrap();
+        }
+        for req in self.withdrawal_requests.iter() {
+            db.write_withdrawal_request(req).await.unwrap();
+        }
+    }
+}
+
+/// A deterministic chain driver that stands in for the bitcoin and stacks
+/// nodes.
+///
+/// Blocks are only produced when [`SimulatedChain::mine_block`] is called,
+/// and every random value is drawn from an RNG seeded at construction.
+pub struct SimulatedChain {
+    rng: StdRng,
+    /// The aggregate key that deposit requests are locked to.
+    aggregate_key: PublicKey,
+    /// All blocks ever mined, including those on abandoned forks.
+    blocks: HashMap<BitcoinBlockHash, SimulatedBlock>,
+    /// The block that the next block will be mined on top of.
+    tip: BitcoinBlockHash,
+    /// The number of deposit requests to include in the next block.
+    queued_deposits: usize,
+    /// The number of withdrawal requests to include in the next block.
+    queued_withdrawals: usize,
+    /// The request ID of the next withdrawal request.
+    next_withdrawal_id: u64,
+}
+
+impl SimulatedChain {
+    /// Create a new chain with a single genesis block. Deposit requests
+    /// will be locked to the given aggregate key.
+    pub fn new(seed: u64, aggregate_key: PublicKey) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        let mut bitcoin_block: model::BitcoinBlock = fake::Faker.fake_with_rng(&mut rng);
+        bitcoin_block.block_height = 0u64.into();
+        bitcoin_block.parent_hash = BitcoinBlockHash::from([0; 32]);
+
+        let mut stacks_block: model::StacksBlock = fake::Faker.fake_with_rng(&mut rng);
+        stacks_block.block_height = 0u64.into();
+        stacks_block.bitcoin_anchor = bitcoin_block.block_hash;
+
+        let genesis = SimulatedBlock {
+            bitcoin_block,
+            stacks_block,
+            deposit_requests: Vec::new(),
+            bitcoin_transactions: Vec::new(),
+            withdrawal_requests: Vec::new(),
+        };
+        let tip = genesis.bitcoin_block.block_hash;
+
+        Self {
+            rng,
+            aggregate_key,
+            blocks: HashMap::from([(tip, genesis)]),
+            tip,
+            queued_deposits: 0,
+            queued_withdrawals: 0,
+            next_withdrawal_id: 1,
+        }
+    }
+
+    /// The block at the tip of the chain.
+    pub fn tip(&self) -> &SimulatedBlock {
+        &self.blocks[&self.tip]
+    }
+
+    /// Fetch a block by its hash, including blocks on abandoned forks.
+    pub fn get_block(&self, block_hash: &BitcoinBlockHash) -> Option<&SimulatedBlock> {
+        self.blocks.get(block_hash)
+    }
+
+    /// Include the given number of deposit requests in the next block.
+    pub fn queue_deposits(&mut self, count: usize) {
+        self.queued_deposits += count;
+    }
+
+    /// Include the given number of withdrawal requests in the next block.
+    pub fn queue_withdrawals(&mut self, count: usize) {
+        self.queued_withdrawals += count;
+    }
+
+    /// Mine a new block on top of the current tip, including any queued
+    /// requests, and make it the new tip.
+    pub fn mine_block(&mut self) -> &SimulatedBlock {
+        let parent = self.tip();
+        let parent_ref = parent.block_ref();
+        let parent_stacks_block = parent.stacks_block.clone();
+
+        let mut bitcoin_block: model::BitcoinBlock = fake::Faker.fake_with_rng(&mut self.rng);
+        bitcoin_block.parent_hash = parent_ref.block_hash;
+        bitcoin_block.block_height = parent_ref.block_height + 1;
+
+        let mut stacks_block: model::StacksBlock = fake::Faker.fake_with_rng(&mut self.rng);
+        stacks_block.parent_hash = parent_stacks_block.block_hash;
+        stacks_block.block_height = parent_stacks_block.block_height + 1;
+        stacks_block.bitcoin_anchor = bitcoin_block.block_hash;
+
+        let mut deposit_requests = Vec::new();
+        let mut bitcoin_transactions = Vec::new();
+        for _ in 0..std::mem::take(&mut self.queued_deposits) {
+            let mut request: model::DepositRequest = fake::Faker.fake_with_rng(&mut self.rng);
+            request.signers_public_key = self.aggregate_key.into();
+            bitcoin_transactions.push(model::BitcoinTxRef {
+                txid: request.txid,
+                block_hash: bitcoin_block.block_hash,
+            });
+            deposit_requests.push(request);
+        }
+
+        let mut withdrawal_requests = Vec::new();
+        for _ in 0..std::mem::take(&mut self.queued_withdrawals) {
+            let mut request: model::WithdrawalRequest = fake::Faker.fake_with_rng(&mut self.rng);
+            request.request_id = self.next_withdrawal_id;
+            request.block_hash = stacks_block.block_hash;
+            request.bitcoin_block_height = bitcoin_block.block_height;
+            self.next_withdrawal_id += 1;
+            withdrawal_requests.push(request);
+        }
+
+        let block = SimulatedBlock {
+            bitcoin_block,
+            stacks_block,
+            deposit_requests,
+            bitcoin_transactions,
+            withdrawal_requests,
+        };
+        self.tip = block.bitcoin_block.block_hash;
+        self.blocks.entry(self.tip).or_insert(block)
+    }
+
+    /// Rewind the tip by `depth` blocks, so that the next mined block
+    /// forks off from that ancestor. The abandoned blocks are kept around
+    /// and can still be fetched with [`SimulatedChain::get_block`].
+    pub fn reorg(&mut self, depth: u64) -> BitcoinBlockRef {
+        for _ in 0..depth {
+            let parent_hash = self.tip().bitcoin_block.parent_hash;
+            assert!(
+                self.blocks.contains_key(&parent_hash),
+                "cannot reorg past the genesis block"
+            );
+            self.tip = parent_hash;
+        }
+        self.tip().block_ref()
+    }
+}
+
+/// Parameters for a [`Simulation`].
+#[derive(Debug, Clone, Copy)]
+pub struct SimulationConfig {
+    /// The number of signers to run.
+    pub num_signers: usize,
+    /// The number of signatures required to sign for a transaction.
+    pub signatures_required: u16,
+    /// The seed for all randomness in the simulation.
+    pub seed: u64,
+    /// The bitcoin context window used by each event loop.
+    pub context_window: u16,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            num_signers: 3,
+            signatures_required: 2,
+            seed: 0,
+            context_window: 10,
+        }
+    }
+}
+
+/// A single signer in a [`Simulation`].
+pub struct SimulatedSigner {
+    /// The signer's context.
+    pub context: SimulationContext,
+    /// The signer's private key.
+    pub private_key: PrivateKey,
+    /// The byzantine behaviors of the signer. The signer is honest unless
+    /// behaviors are enabled here.
+    pub behaviors: ByzantineBehaviors,
+    /// Handles to the signer's event loops.
+    event_loops: Vec<JoinHandle<Result<(), Error>>>,
+}
+
+impl SimulatedSigner {
+    /// The signer's public key.
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey::from_private_key(&self.private_key)
+    }
+}
+
+/// N signers running their event loops against a [`SimulatedChain`].
+pub struct Simulation {
+    /// The chain driving the simulation.
+    pub chain: SimulatedChain,
+    /// The simulated signers.
+    pub signers: Vec<SimulatedSigner>,
+    /// The network connecting the signers. Kept so that it lives as long
+    /// as the simulation.
+    _network: WanNetwork,
+}
+
+impl Simulation {
+    /// Start a simulation with the given config. Each signer gets an
+    /// in-memory store seeded with verified DKG shares for the signer
+    /// set, and the genesis block is delivered before this returns.
+    pub async fn start(config: SimulationConfig) -> Self {
+        let mut rng = StdRng::seed_from_u64(config.seed);
+        let private_keys: Vec<PrivateKey> = std::iter::repeat_with(|| PrivateKey::new(&mut rng))
+            .take(config.num_signers)
+            .collect();
+        let signer_set: BTreeSet<PublicKey> = private_keys
+            .iter()
+            .map(PublicKey::from_private_key)
+            .collect();
+        let aggregate_key = PublicKey::combine_keys(&signer_set).unwrap();
+
+        let network = WanNetwork::default();
+        let mut signers = Vec::with_capacity(config.num_signers);
+
+        for private_key in private_keys {
+            let context = TestContext::builder()
+                .with_in_memory_storage()
+                .with_mocked_clients()
+                .modify_settings(|settings| {
+                    settings.signer.private_key = private_key;
+                    settings.signer.bootstrap_signing_set = signer_set.clone();
+                    settings.signer.bootstrap_signatures_required = config.signatures_required;
+                    settings.signer.bitcoin_processing_delay = Duration::ZERO;
+                    settings.signer.requests_processing_delay = Duration::ZERO;
+                })
+                .with_rng_seed(rng.next_u64())
+                .build();
+
+            context
+                .state()
+                .update_current_signer_set(signer_set.clone());
+            context.state().set_sbtc_contracts_deployed();
+
+            context
+                .with_stacks_client(|client| {
+                    client.expect_get_epoch_status().returning(|| {
+                        Box::pin(async {
+                            Ok(StacksEpochStatus::PreNakamoto {
+                                reported_bitcoin_height: 0u64.into(),
+                                nakamoto_start_height: u64::MAX.into(),
+                            })
+                        })
+                    });
+                })
+                .await;
+
+            let mut shares = testing::dummy::encrypted_dkg_shares(
+                &fake::Faker,
+                &mut rng,
+                &private_key.to_bytes(),
+                aggregate_key,
+                DkgSharesStatus::Verified,
+            );
+            shares.signer_set_public_keys = signer_set.iter().copied().collect();
+            context
+                .get_storage_mut()
+                .write_encrypted_dkg_shares(&shares)
+                .await
+                .unwrap();
+
+            let behaviors = ByzantineBehaviors::default();
+            let event_loops =
+                Self::spawn_event_loops(&network, &context, private_key, &behaviors, &config);
+            signers.push(SimulatedSigner {
+                context,
+                private_key,
+                behaviors,
+                event_loops,
+            });
+        }
+
+        let simulation = Self {
+            chain: SimulatedChain::new(config.seed, aggregate_key),
+            signers,
+            _network: network,
+        };
+        let genesis = simulation.chain.tip().bitcoin_block.block_hash;
+        simulation.deliver(&genesis).await;
+        simulation
+    }
+
+    fn spawn_event_loops(
+        network: &WanNetwork,
+        context: &SimulationContext,
+        private_key: PrivateKey,
+        behaviors: &ByzantineBehaviors,
+        config: &SimulationConfig,
+    ) -> Vec<JoinHandle<Result<(), Error>>> {
+        let net = network.connect(context);
+        let spawn = || ByzantineNetwork::new(net.spawn(), private_key, behaviors.clone());
+
+        let coordinator = TxCoordinatorEventLoop {
+            network: spawn(),
+            context: context.clone(),
+            context_window: config.context_window,
+            private_key,
+            signing_round_max_duration: Duration::from_secs(10),
+            bitcoin_presign_request_max_duration: Duration::from_secs(10),
+            dkg_max_duration: Duration::from_secs(10),
+            is_epoch3: false,
+        };
+        let signer = TxSignerEventLoop {
+            network: spawn(),
+            context: context.clone(),
+            context_window: config.context_window,
+            wsts_state_machines: LruCache::new(NonZeroUsize::new(100).unwrap()),
+            signer_private_key: private_key,
+            last_presign_block: None,
+            dkg_begin_pause: None,
+            dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
+            stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+            stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
+        };
+        let decider = RequestDeciderEventLoop {
+            network: spawn(),
+            context: context.clone(),
+            context_window: config.context_window,
+            deposit_decisions_retry_window: 1,
+            withdrawal_decisions_retry_window: 1,
+            carried_over: Default::default(),
+            blocklist_checker: Some(()),
+            decision_policies: Vec::new(),
+            signer_private_key: private_key,
+        };
+
+        vec![
+            tokio::spawn(coordinator.run()),
+            tokio::spawn(signer.run()),
+            tokio::spawn(decider.run()),
+        ]
+    }
+
+    /// Mine a new block and deliver it to every signer.
+    pub async fn advance(&mut self) -> BitcoinBlockRef {
+        let block_hash = self.chain.mine_block().bitcoin_block.block_hash;
+        self.deliver(&block_hash).await;
+        self.chain.tip().block_ref()
+    }
+
+    /// Deliver a block that the chain has already mined to every signer,
+    /// as if each signer's block observer had just processed it.
+    ///
+    /// The block is written to every signer's store before any signer is
+    /// notified, so that decisions gossiped by fast signers always refer
+    /// to requests that the slower signers already know about.
+    pub async fn deliver(&self, block_hash: &BitcoinBlockHash) {
+        let block = self.chain.get_block(block_hash).unwrap();
+        let block_ref = block.block_ref();
+
+        for signer in self.signers.iter() {
+            block.write_to(&signer.context.get_storage_mut()).await;
+        }
+
+        for signer in self.signers.iter() {
+            let ctx = &signer.context;
+            ctx.state().set_bitcoin_chain_tip(block_ref);
+            ctx.state()
+                .set_stacks_chain_tip(block.stacks_block.clone().into());
+            ctx.signal(SignerSignal::Event(SignerEvent::BitcoinBlockObserved(
+                block_ref,
+            )))
+            .unwrap();
+        }
+    }
+
+    /// Poll every signer with the given predicate until it holds for all
+    /// of them, or until the timeout elapses.
+    pub async fn wait_until<F, Fut>(&self, timeout: Duration, predicate: F) -> Result<(), Elapsed>
+    where
+        F: Fn(SimulationContext) -> Fut,
+        Fut: Future<Output = bool>,
+    {
+        tokio::time::timeout(timeout, async {
+            loop {
+                let mut all = true;
+                for signer in self.signers.iter() {
+                    all &= predicate(signer.context.clone()).await;
+                }
+                if all {
+                    return;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+    }
+
+    /// Stop all of the signers' event loops.
+    pub async fn shutdown(self) {
+        for signer in self.signers {
+            let _ = signer
+                .context
+                .signal(SignerSignal::Command(SignerCommand::Shutdown));
+            for handle in signer.event_loops {
+                handle.abort();
+                let _ = handle.await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::DbRead as _;
+
+    use super::*;
+
+    #[test]
+    fn simulated_chain_is_deterministic() {
+        let aggregate_key = PublicKey::from_private_key(&PrivateKey::new(&mut rand::rngs::OsRng));
+        let mut chain1 = SimulatedChain::new(42, aggregate_key);
+        let mut chain2 = SimulatedChain::new(42, aggregate_key);
+
+        for _ in 0..5 {
+            chain1.queue_deposits(2);
+            chain1.queue_withdrawals(1);
+            chain2.queue_deposits(2);
+            chain2.queue_withdrawals(1);
+            assert_eq!(chain1.mine_block(), chain2.mine_block());
+        }
+
+        let mut chain3 = SimulatedChain::new(43, aggregate_key);
+        assert_ne!(chain1.tip(), chain3.mine_block());
+    }
+
+    #[test]
+    fn simulated_chain_reorgs_fork_from_ancestor() {
+        let aggregate_key = PublicKey::from_private_key(&PrivateKey::new(&mut rand::rngs::OsRng));
+        let mut chain = SimulatedChain::new(0, aggregate_key);
+
+        let ancestor = chain.mine_block().block_ref();
+        chain.mine_block();
+        let abandoned = chain.mine_block().block_ref();
+
+        assert_eq!(chain.reorg(2), ancestor);
+
+        let fork = chain.mine_block().clone();
+        assert_eq!(fork.bitcoin_block.parent_hash, ancestor.block_hash);
+        assert_eq!(fork.bitcoin_block.block_height, ancestor.block_height + 1);
+        assert!(chain.get_block(&abandoned.block_hash).is_some());
+    }
+
+    #[tokio::test]
+    async fn signers_exchange_deposit_decisions() {
+        let config = SimulationConfig::default();
+        let mut simulation = Simulation::start(config).await;
+
+        simulation.chain.queue_deposits(2);
+        simulation.advance().await;
+        let deposits = simulation.chain.tip().deposit_requests.clone();
+        let num_signers = config.num_signers;
+
+        simulation
+            .wait_until(Duration::from_secs(10), |ctx| {
+                let deposits = deposits.clone();
+                async move {
+                    let db = ctx.get_storage();
+                    for deposit in deposits.iter() {
+                        let votes = db
+                            .get_deposit_signers(&deposit.txid, deposit.output_index)
+                            .await
+                            .unwrap();
+                        if votes.len() != num_signers {
+                            return false;
+                        }
+                    }
+                    true
+                }
+            })
+            .await
+            .expect("timed out waiting for deposit decisions");
+
+        simulation.shutdown().await;
+    }
+}
@@ -13,6 +13,7 @@ use crate::storage::{DbRead, DbWrite};
 use crate::testing::TestUtilityError;
 use crate::util::{FutureExt as _, SleepAsyncExt as _};
 
+pub mod load;
 pub mod model;
 pub mod postgres;
 
@@ -46,6 +47,16 @@ fn get_connection_pool(url: &str) -> sqlx::PgPool {
 ///    database. This actually works, and it's not clear why.
 /// 3. Have each test use a new pool to a new database. This works as well.
 pub async fn new_test_database() -> PgStore {
+    let store = new_empty_test_database().await;
+    store
+        .apply_migrations()
+        .await
+        .expect("failed to apply db migrations");
+    store
+}
+
+/// Create a new test database without applying any migrations to it.
+pub async fn new_empty_test_database() -> PgStore {
     // We create a new connection to the default database each time this
     // function is called, because we depend on all connections to this
     // database being closed before it begins.
@@ -87,12 +98,7 @@ pub async fn new_test_database() -> PgStore {
     // <https://www.postgresql.org/docs/16/sql-createdatabase.html>
     pool.close().await;
 
-    let store = PgStore::connect(&test_db_url).await.unwrap();
-    store
-        .apply_migrations()
-        .await
-        .expect("failed to apply db migrations");
-    store
+    PgStore::connect(&test_db_url).await.unwrap()
 }
 
 /// When we are done with the test, we need to delete any test databases
@@ -0,0 +1,148 @@
+//! Large-scale fixtures for load testing the storage layer.
+//!
+//! The generators in [`super::model`] hold all of the data in memory and
+//! look up parent blocks by scanning everything generated so far, which
+//! is fine for the small fixtures used in tests but does not scale to
+//! millions of rows. The seeder here generates a single canonical chain
+//! in chunks and writes each chunk in its own database transaction, so
+//! memory use stays bounded by the chunk size.
+
+use fake::Fake as _;
+
+use crate::keys::PublicKey;
+use crate::storage::DbWrite as _;
+use crate::storage::Transactable as _;
+use crate::storage::TransactionHandle as _;
+use crate::storage::model;
+use crate::storage::model::BitcoinBlockRef;
+use crate::storage::model::StacksBlockHash;
+use crate::storage::postgres::PgStore;
+use crate::testing::storage::model::Params;
+use crate::testing::storage::model::TestData;
+
+/// Parameters for seeding a database with a large amount of data.
+#[derive(Debug, Clone)]
+pub struct LoadParams {
+    /// The parameters used to generate each bitcoin block and its
+    /// associated data. The `num_bitcoin_blocks` field sets the length of
+    /// the generated chain and `consecutive_blocks` is ignored, since the
+    /// seeder always generates a single chain.
+    pub data: Params,
+    /// The number of bitcoin blocks, along with their associated data,
+    /// that are written to the database in a single transaction.
+    pub chunk_size: usize,
+}
+
+/// A summary of the data that was written to the database by
+/// [`seed_database`].
+#[derive(Debug, Clone)]
+pub struct LoadFixture {
+    /// The bitcoin block at the tip of the generated chain.
+    pub bitcoin_chain_tip: BitcoinBlockRef,
+    /// The stacks block at the tip of the generated chain.
+    pub stacks_chain_tip: StacksBlockHash,
+    /// The aggregate key of the signer set. Verified DKG shares for this
+    /// key are written to the database, so that signer votes can be
+    /// looked up for the generated requests.
+    pub aggregate_key: PublicKey,
+    /// The total number of stacks blocks written.
+    pub num_stacks_blocks: usize,
+    /// The total number of deposit requests written.
+    pub num_deposit_requests: usize,
+    /// The total number of withdrawal requests written.
+    pub num_withdrawal_requests: usize,
+}
+
+/// Seed the given database with a single chain of bitcoin blocks, each
+/// with their own stacks blocks, deposit and withdrawal requests and
+/// signer decisions. The signer decisions are made by the given signers,
+/// who are also recorded as the signer set of a verified DKG round.
+///
+/// Returns `None` if no blocks were generated or if the signer keys
+/// cannot be combined into an aggregate key.
+pub async fn seed_database<R>(
+    store: &PgStore,
+    rng: &mut R,
+    signer_keys: &[PublicKey],
+    params: &LoadParams,
+) -> Option<LoadFixture>
+where
+    R: rand::RngCore,
+{
+    let chunk_size = params.chunk_size.max(1);
+    // Block generation only needs the parent bitcoin block, the stacks
+    // blocks anchored to it and the last withdrawal request, so this is
+    // all we keep around between blocks.
+    let mut window = TestData::default();
+    let mut chunk = TestData::default();
+    let mut chunk_blocks = 0;
+    let mut parent: Option<BitcoinBlockRef> = None;
+    let mut fixture: Option<LoadFixture> = None;
+    let aggregate_key = PublicKey::combine_keys(signer_keys).ok()?;
+
+    for _ in 0..params.data.num_bitcoin_blocks {
+        let (data, block_ref) = window.new_block(rng, signer_keys, &params.data, parent.as_ref());
+
+        window.bitcoin_blocks = data.bitcoin_blocks.clone();
+        window.stacks_blocks = data.stacks_blocks.clone();
+        if let Some(last) = data.withdraw_requests.last() {
+            window.withdraw_requests = vec![last.clone()];
+        }
+
+        let summary = fixture.get_or_insert_with(|| LoadFixture {
+            bitcoin_chain_tip: block_ref,
+            stacks_chain_tip: StacksBlockHash::from([0; 32]),
+            aggregate_key,
+            num_stacks_blocks: 0,
+            num_deposit_requests: 0,
+            num_withdrawal_requests: 0,
+        });
+        summary.bitcoin_chain_tip = block_ref;
+        if let Some(stacks_block) = data.stacks_blocks.last() {
+            summary.stacks_chain_tip = stacks_block.block_hash;
+        }
+        summary.num_stacks_blocks += data.stacks_blocks.len();
+        summary.num_deposit_requests += data.deposit_requests.len();
+        summary.num_withdrawal_requests += data.withdraw_requests.len();
+
+        parent = Some(block_ref);
+        chunk.push(data);
+        chunk_blocks += 1;
+
+        if chunk_blocks == chunk_size {
+            write_chunk(store, &chunk).await;
+            chunk = TestData::default();
+            chunk_blocks = 0;
+        }
+    }
+
+    if chunk_blocks > 0 {
+        write_chunk(store, &chunk).await;
+    }
+
+    let fixture = fixture?;
+    let shares = model::EncryptedDkgShares {
+        aggregate_key,
+        signer_set_public_keys: signer_keys.to_vec(),
+        dkg_shares_status: model::DkgSharesStatus::Verified,
+        started_at_bitcoin_block_hash: fixture.bitcoin_chain_tip.block_hash,
+        started_at_bitcoin_block_height: fixture.bitcoin_chain_tip.block_height,
+        ..fake::Faker.fake_with_rng(rng)
+    };
+    store
+        .write_encrypted_dkg_shares(&shares)
+        .await
+        .expect("failed to write dkg shares");
+
+    Some(fixture)
+}
+
+/// Write the given data to the database in a single transaction.
+async fn write_chunk(store: &PgStore, chunk: &TestData) {
+    let tx = store
+        .begin_transaction()
+        .await
+        .expect("failed to begin db transaction");
+    chunk.write_to(&tx).await;
+    tx.commit().await.expect("failed to commit db transaction");
+}
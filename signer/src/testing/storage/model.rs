@@ -494,6 +494,7 @@ impl WithdrawData {
                             txid: withdraw_request.txid,
                             signer_pub_key,
                             is_accepted: fake::Faker.fake_with_rng(rng),
+                            rejection_reason: None,
                         })
                         .collect();
 
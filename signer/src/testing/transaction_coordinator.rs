@@ -9,6 +9,7 @@ use std::time::Duration;
 
 use super::get_rng;
 use crate::bitcoin::MockBitcoinInteract;
+use crate::bitcoin::op_return::SweepCommitment;
 use crate::bitcoin::rpc::BitcoinTxInfo;
 use crate::bitcoin::utxo::SignerUtxo;
 use crate::context::Context;
@@ -430,6 +431,9 @@ where
                             ..fake::Faker.fake_with_rng(&mut rng)
                         }))))
                     });
+                client
+                    .expect_is_sbtc_contract_paused()
+                    .returning(|_| Box::pin(std::future::ready(Ok(false))));
             })
             .await;
 
@@ -451,6 +455,12 @@ where
         // channel.
         self.context
             .with_bitcoin_client(|client| {
+                // The coordinator audits the fees of each sweep that it
+                // broadcasts, which is best effort.
+                client.expect_get_transaction_fee().returning(|txid| {
+                    let txid = *txid;
+                    Box::pin(async move { Err(error::Error::BitcoinTxMissing(txid, None)) })
+                });
                 client
                     .expect_broadcast_transaction()
                     .times(1..)
@@ -587,6 +597,9 @@ where
                             ..fake::Faker.fake_with_rng(&mut rng)
                         }))))
                     });
+                client
+                    .expect_is_sbtc_contract_paused()
+                    .returning(|_| Box::pin(std::future::ready(Ok(false))));
             })
             .await;
 
@@ -608,6 +621,12 @@ where
         // channel.
         self.context
             .with_bitcoin_client(|client| {
+                // The coordinator audits the fees of each sweep that it
+                // broadcasts, which is best effort.
+                client.expect_get_transaction_fee().returning(|txid| {
+                    let txid = *txid;
+                    Box::pin(async move { Err(error::Error::BitcoinTxMissing(txid, None)) })
+                });
                 client
                     .expect_broadcast_transaction()
                     .times(1..)
@@ -1026,6 +1045,81 @@ where
         assert_eq!(signer_utxo, expected);
     }
 
+    /// Assert we get all of the signers' UTXOs when a sweep transaction
+    /// splits the signers' change across more than one output.
+    pub async fn assert_get_signer_utxos_split_change(mut self) {
+        let mut rng = get_rng();
+        let network = network::InMemoryNetwork::new();
+        let signer_info = testing::wsts::generate_signer_info(&mut rng, self.num_signers as usize);
+
+        let mut signer_set =
+            testing::wsts::SignerSet::new(&signer_info, self.signing_threshold as u32, || {
+                network.connect()
+            });
+
+        let (aggregate_key, bitcoin_chain_tip, mut test_data) = self
+            .prepare_database_and_run_dkg(&mut rng, &mut signer_set)
+            .await;
+
+        let original_test_data = test_data.clone();
+
+        let signers_output = |sats| bitcoin::TxOut {
+            value: bitcoin::Amount::from_sat(sats),
+            script_pubkey: aggregate_key.signers_script_pubkey(),
+        };
+        let commitment = SweepCommitment::V1 { withdrawal_ids: Vec::new() }
+            .encode([0; 2])
+            .unwrap();
+        let op_return = bitcoin::TxOut {
+            value: bitcoin::Amount::ZERO,
+            script_pubkey: bitcoin::ScriptBuf::new_op_return(commitment),
+        };
+        let tx = bitcoin::Transaction {
+            output: vec![
+                signers_output(42_000),
+                op_return,
+                signers_output(100_000),
+                signers_output(100_000),
+            ],
+            input: vec![TestBitcoinTxInfo::random_prevout(&mut rng)],
+            ..EMPTY_BITCOIN_TX
+        };
+
+        let (block, block_ref) = test_data.new_block(
+            &mut rng,
+            &signer_set.signer_keys(),
+            &self.test_model_parameters,
+            Some(&bitcoin_chain_tip),
+        );
+
+        let signer_script_pubkeys = HashSet::from([aggregate_key.signers_script_pubkey()]);
+        let tx_info = TestBitcoinTxInfo {
+            tx: tx.clone(),
+            prevouts: vec![signers_output(250_000)],
+        };
+        test_data.push_bitcoin_txs(&block_ref, vec![tx_info], &signer_script_pubkeys);
+        test_data.push(block);
+
+        test_data.remove(original_test_data);
+        self.write_test_data(&test_data).await;
+
+        let storage = self.context.get_storage();
+        let signer_utxos = storage
+            .get_signer_utxos(&block_ref.block_hash)
+            .await
+            .unwrap();
+
+        let expected: Vec<SignerUtxo> = [(2, 100_000), (3, 100_000), (0, 42_000)]
+            .into_iter()
+            .map(|(vout, amount)| SignerUtxo {
+                outpoint: bitcoin::OutPoint::new(tx.compute_txid(), vout),
+                amount,
+                public_key: bitcoin::XOnlyPublicKey::from(aggregate_key),
+            })
+            .collect();
+        assert_eq!(signer_utxos, expected);
+    }
+
     /// Assert we get the correct UTXO in a fork
     pub async fn assert_get_signer_utxo_fork(mut self) {
         let mut rng = get_rng();
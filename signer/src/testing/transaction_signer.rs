@@ -23,6 +23,7 @@ use crate::testing::get_rng;
 use crate::testing::storage::model::TestData;
 use crate::transaction_signer;
 use crate::transaction_signer::STACKS_SIGN_REQUEST_LRU_SIZE;
+use crate::transaction_signer::STACKS_VALIDATION_LRU_SIZE;
 
 use lru::LruCache;
 use tokio::sync::broadcast;
@@ -59,6 +60,7 @@ where
                 dkg_begin_pause: None,
                 dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
                 stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+                stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
             },
             context,
         }
@@ -8,6 +8,7 @@ use std::time::Duration;
 use clarity::util::secp256k1::Secp256k1PublicKey;
 use clarity::vm::types::PrincipalData;
 use fake::Fake as _;
+use rand::rngs::OsRng;
 use stacks_common::address::AddressHashMode;
 use stacks_common::address::C32_ADDRESS_VERSION_TESTNET_MULTISIG;
 use stacks_common::types::chainstate::StacksAddress;
@@ -232,6 +233,7 @@ impl Signer {
             threshold,
             created_at,
             signer_info.signer_private_key,
+            &mut OsRng,
         )
         .expect("failed to construct state machine");
 
@@ -255,12 +257,12 @@ impl Signer {
 
                 let outbound_messages = self
                     .wsts_signer
-                    .process(&wsts_msg.inner)
+                    .process(&wsts_msg.inner, &mut OsRng)
                     .expect("message processing failed");
 
                 for message in outbound_messages {
                     self.wsts_signer
-                        .process(&message)
+                        .process(&message, &mut OsRng)
                         .expect("message processing failed");
 
                     let dkg_has_ended = matches!(message, WstsNetMessage::DkgEnd(_));
@@ -292,12 +294,12 @@ impl Signer {
 
                 let outbound_messages = self
                     .wsts_signer
-                    .process(&wsts_msg.inner)
+                    .process(&wsts_msg.inner, &mut OsRng)
                     .expect("message processing failed");
 
                 for message in outbound_messages {
                     self.wsts_signer
-                        .process(&message)
+                        .process(&message, &mut OsRng)
                         .expect("message processing failed");
 
                     let signing_round_ended =
@@ -417,7 +419,7 @@ impl SignerSet {
                 .map(|signer| {
                     let mut shares = signer
                         .wsts_signer
-                        .get_encrypted_dkg_shares()
+                        .get_encrypted_dkg_shares(&mut OsRng)
                         .expect("failed to get encrypted shares");
                     shares.dkg_shares_status = dkg_shares_status;
                     shares
@@ -8,24 +8,31 @@
 use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::num::NonZeroU16;
 use std::time::Duration;
 
 use blockstack_lib::chainstate::stacks::StacksTransaction;
 use futures::Stream;
 use futures::StreamExt as _;
+use rand::RngCore as _;
+use rand::rngs::OsRng;
+use serde::Deserialize;
+use serde::Serialize;
 use sha2::Digest as _;
 
-use crate::BITCOIN_FEE_RATE_RANGE;
 use crate::DEPOSIT_LOCKTIME_BLOCK_BUFFER;
-use crate::MAX_BITCOIN_FEE_RATE;
-use crate::MIN_BITCOIN_FEE_RATE;
 use crate::WITHDRAWAL_BLOCKS_EXPIRY;
 use crate::WITHDRAWAL_DUST_LIMIT;
 use crate::WITHDRAWAL_EXPIRY_BUFFER;
 use crate::bitcoin::BitcoinInteract as _;
+use crate::bitcoin::fee_audit;
+use crate::bitcoin::package_rules;
+use crate::bitcoin::rpc::ParallelSweepUtxos;
 use crate::bitcoin::rpc::assess_mempool_sweep_transaction_fees;
 use crate::bitcoin::utxo;
 use crate::bitcoin::utxo::UnsignedMockTransaction;
+use crate::bitcoin::validation::TxRequestIds;
+use crate::config::DepositConfirmationPolicy;
 use crate::context::Context;
 use crate::context::P2PEvent;
 use crate::context::RequestDeciderEvent;
@@ -33,16 +40,21 @@ use crate::context::SbtcLimits;
 use crate::context::SignerCommand;
 use crate::context::SignerEvent;
 use crate::context::SignerSignal;
+use crate::context::Subsystem;
 use crate::context::TxCoordinatorEvent;
 use crate::context::TxSignerEvent;
 use crate::ecdsa::SignEcdsa as _;
 use crate::ecdsa::Signed;
+use crate::emily_client;
 use crate::emily_client::EmilyInteract as _;
 use crate::error::Error;
+use crate::invariants;
 use crate::keys::PrivateKey;
 use crate::keys::PublicKey;
+use crate::logging::CorrelationIds;
 use crate::message;
 use crate::message::BitcoinPreSignRequest;
+use crate::message::Deadline;
 use crate::message::Payload;
 use crate::message::SignerMessage;
 use crate::message::StacksTransactionSignRequest;
@@ -68,14 +80,17 @@ use crate::stacks::contracts::SmartContract;
 use crate::stacks::wallet::MultisigTx;
 use crate::stacks::wallet::SignerWallet;
 use crate::storage::DbRead;
+use crate::storage::DbWrite as _;
 use crate::storage::model;
 use crate::storage::model::BitcoinBlockRef;
 use crate::storage::model::StacksTxId;
+use crate::storage::util;
 use crate::wsts_state_machine::FireCoordinator;
 use crate::wsts_state_machine::FrostCoordinator;
 use crate::wsts_state_machine::WstsCoordinator;
 use sbtc::WITHDRAWAL_MIN_CONFIRMATIONS;
 
+use bitcoin::TapSighash;
 use bitcoin::hashes::Hash as _;
 use wsts::net::SignatureType;
 use wsts::state_machine::OperationResult as WstsOperationResult;
@@ -86,6 +101,15 @@ use wsts::state_machine::coordinator::State as WstsCoordinatorState;
 /// because of a conflicting nonce.
 const REJECTION_REASON_CONFLICTING_NONCE_IN_MEMPOOL: &str = "ConflictingNonceInMempool";
 
+/// The number of bitcoin blocks after which the coordinator stops waiting
+/// for a contract call that it submitted for a request to be confirmed or
+/// rejected, and submits a new contract call for the request.
+const INFLIGHT_CONTRACT_CALL_EXPIRY_BLOCKS: u64 = 2;
+
+/// How often the coordinator checks the database for key rotation
+/// attestations from the other signers while waiting for a quorum.
+const KEY_ROTATION_ATTESTATION_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
 /// The target confirmation block used in case of package construction retry.
 ///
 /// Target a confirmation block that will not impact the requests cancellation.
@@ -189,7 +213,8 @@ pub struct TxCoordinatorEventLoop<Context, Network> {
     /// Private key of the coordinator for network communication.
     pub private_key: PrivateKey,
     /// How many bitcoin blocks back from the chain tip the signer will
-    /// look for requests.
+    /// look for requests, unless a more specific lookback window is set
+    /// in the config.
     pub context_window: u16,
     /// The maximum duration of a signing round before the coordinator will
     /// time out and return an error.
@@ -206,6 +231,77 @@ pub struct TxCoordinatorEventLoop<Context, Network> {
     pub is_epoch3: bool,
 }
 
+/// The signing rounds of a sweep transaction, prepared before they are
+/// coordinated.
+struct PreparedSweep {
+    /// The ID of the sweep transaction.
+    txid: bitcoin::Txid,
+    /// The sighash of the signers' input, along with the coordinator
+    /// state machine for signing it.
+    signers: (TapSighash, FireCoordinator),
+    /// The deposit requests swept by the transaction, along with the
+    /// sighashes of their inputs and the coordinator state machines for
+    /// signing them, in input order.
+    deposits: Vec<(utxo::DepositRequest, TapSighash, FireCoordinator)>,
+}
+
+impl PreparedSweep {
+    /// Compute the sighashes of the given sweep transaction and load the
+    /// coordinator state machines for the aggregate keys locking each of
+    /// its inputs.
+    async fn new<S>(
+        storage: &S,
+        private_key: PrivateKey,
+        transaction: &utxo::UnsignedTransaction<'_>,
+    ) -> Result<Self, Error>
+    where
+        S: DbRead + Send + Sync,
+    {
+        let sighashes = transaction.construct_digests()?;
+        let locking_public_key = sighashes.signers_aggregate_key.into();
+        let coordinator = FireCoordinator::load(storage, locking_public_key, private_key).await?;
+
+        let mut deposits = Vec::with_capacity(sighashes.deposits.len());
+        for (deposit, sighash) in sighashes.deposits {
+            let locking_public_key = deposit.signers_public_key.into();
+            let coordinator =
+                FireCoordinator::load(storage, locking_public_key, private_key).await?;
+            deposits.push((deposit.clone(), sighash, coordinator));
+        }
+
+        Ok(Self {
+            txid: sighashes.txid,
+            signers: (sighashes.signers, coordinator),
+            deposits,
+        })
+    }
+}
+
+/// The tenure that we watch for coordinator silence, as its deputy
+/// coordinator.
+#[derive(Debug, Clone, Copy)]
+struct DeputyWatch {
+    /// The bitcoin chain tip of the tenure.
+    chain_tip: BitcoinBlockRef,
+    /// When we start counting the silence of the coordinator.
+    silent_since: std::time::Instant,
+    /// When we next check whether a threshold of the signers attested to
+    /// the coordinator's silence, once we have attested to it ourselves.
+    quorum_check_at: Option<std::time::Instant>,
+}
+
+/// How often the deputy coordinator checks whether a threshold of the
+/// signers attested to the silence of the coordinator.
+const SILENCE_QUORUM_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Sleep until the given deadline, or forever if there is none.
+async fn sleep_until(deadline: Option<tokio::time::Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline).await,
+        None => std::future::pending().await,
+    }
+}
+
 /// The parameters for the [`TxCoordinatorEventLoop::get_pending_requests`] function.
 #[derive(Debug)]
 pub struct GetPendingRequestsParams<'a> {
@@ -221,6 +317,9 @@ pub struct GetPendingRequestsParams<'a> {
     /// request to be considered for the sweep transaction package, and the
     /// number of signatures required for each transaction.
     pub signature_threshold: u16,
+    /// The number of confirmations a deposit needs, based on its amount,
+    /// before it is considered for the sweep transaction package.
+    pub deposit_confirmation_policy: &'a DepositConfirmationPolicy,
 }
 
 /// This function defines which messages this event loop is interested
@@ -249,13 +348,107 @@ where
     C: Context,
     N: network::MessageTransfer,
 {
+    /// How many bitcoin blocks back from the chain tip to look for pending
+    /// deposit requests, falling back to the `context_window`.
+    fn deposit_lookback(&self) -> u16 {
+        self.context
+            .config()
+            .signer
+            .deposit_lookback
+            .map_or(self.context_window, NonZeroU16::get)
+    }
+
+    /// How many bitcoin blocks back from the chain tip to look for pending
+    /// withdrawal requests, falling back to the `context_window`.
+    fn withdrawal_lookback(&self) -> u16 {
+        self.context
+            .config()
+            .signer
+            .withdrawal_lookback
+            .map_or(self.context_window, NonZeroU16::get)
+    }
+
+    /// How many bitcoin blocks back from the chain tip to look for
+    /// confirmed sweep transactions, falling back to the `context_window`.
+    fn sweep_confirmation_window(&self) -> u16 {
+        self.context
+            .config()
+            .signer
+            .sweep_confirmation_window
+            .map_or(self.context_window, NonZeroU16::get)
+    }
+
     /// Run the coordinator event loop
     #[tracing::instrument(skip_all, name = "tx-coordinator")]
     pub async fn run(mut self) -> Result<(), Error> {
         tracing::info!("starting transaction coordinator event loop");
         let mut signal_stream = self.context.as_signal_stream(run_loop_message_filter);
 
-        while let Some(message) = signal_stream.next().await {
+        if let Err(error) = self.rebroadcast_signed_sweeps().await {
+            tracing::warn!(%error, "could not rebroadcast the persisted signed sweeps");
+        }
+
+        // When we are the deputy coordinator of the current tenure, this
+        // is the tenure that we watch for coordinator silence.
+        let mut deputy_watch: Option<DeputyWatch> = None;
+
+        loop {
+            let silence_deadline =
+                deputy_watch.and_then(|watch| self.coordinator_silence_deadline(&watch));
+            let message = tokio::select! {
+                message = signal_stream.next() => message,
+                () = sleep_until(silence_deadline), if silence_deadline.is_some() => {
+                    let Some(watch) = deputy_watch.take() else {
+                        continue;
+                    };
+                    // We may have heard from the coordinator while we
+                    // were sleeping, in which case we keep watching.
+                    match self.coordinator_silence_deadline(&watch) {
+                        None => continue,
+                        Some(deadline) if deadline > tokio::time::Instant::now() => {
+                            deputy_watch = Some(watch);
+                            continue;
+                        }
+                        Some(_) => {}
+                    }
+                    // We only take over once a threshold of the signers
+                    // agree that the coordinator went silent.
+                    let quorum = self.coordinator_silence_quorum(&watch.chain_tip.block_hash).await;
+                    let result = match quorum {
+                        Ok(Some(attestations)) => {
+                            self.take_over_tenure(watch.chain_tip, attestations).await
+                        }
+                        Ok(None) => {
+                            let now = std::time::Instant::now();
+                            deputy_watch = Some(DeputyWatch {
+                                quorum_check_at: Some(now + SILENCE_QUORUM_CHECK_INTERVAL),
+                                ..watch
+                            });
+                            continue;
+                        }
+                        Err(error) => Err(error),
+                    };
+                    if let Err(error) = &result {
+                        tracing::error!(%error, "could not take over the tenure of the silent coordinator");
+                    }
+                    self.context
+                        .state()
+                        .error_registry()
+                        .record(Subsystem::TxCoordinator, &result);
+                    continue;
+                }
+            };
+
+            let Some(message) = message else {
+                break;
+            };
+
+            // Hold on to the message until the database is reachable
+            // again, rather than failing to handle it during a failover.
+            if !self.context.wait_for_database().await {
+                break;
+            }
+
             match message {
                 SignerSignal::Command(SignerCommand::Shutdown) => break,
                 SignerSignal::Command(SignerCommand::P2PPublish(_)) => {}
@@ -263,12 +456,19 @@ where
                     RequestDeciderEvent::NewRequestsHandled(chain_tip),
                 )) => {
                     tracing::debug!("received signal; processing requests");
-                    if let Err(error) = self.process_new_blocks(chain_tip).await {
+                    let result = self.process_new_blocks(chain_tip).await;
+                    if let Err(error) = &result {
                         tracing::error!(%error, "error processing requests; skipping this round");
                     }
+                    self.context
+                        .state()
+                        .error_registry()
+                        .record(Subsystem::TxCoordinator, &result);
                     tracing::trace!("sending tenure completed signal");
                     self.context
                         .signal(TxCoordinatorEvent::TenureCompleted(chain_tip).into())?;
+
+                    deputy_watch = self.deputy_watch(chain_tip);
                 }
                 SignerSignal::Event(_) => {}
             }
@@ -279,6 +479,97 @@ where
         Ok(())
     }
 
+    /// Broadcast the sweep transactions that were fully signed but that
+    /// may not have reached the bitcoin network, say because we stopped
+    /// right after aggregating their signatures.
+    ///
+    /// Of the persisted sweeps that spend the same signers' input, only
+    /// the latest one is broadcast, since it replaced the others with
+    /// RBF, and the others are deleted. The latest sweep is only broadcast
+    /// if the bitcoin chain tip that it was signed at is still on the
+    /// canonical bitcoin blockchain, and its signers' input is still an
+    /// unspent signers' UTXO on it, or the signers' output of a sweep
+    /// that comes before it in the same package. Sweeps that do not meet
+    /// these conditions have been confirmed, or their requests may no
+    /// longer be valid, so they are deleted instead.
+    #[tracing::instrument(skip_all)]
+    pub async fn rebroadcast_signed_sweeps(&self) -> Result<(), Error> {
+        let db = self.context.get_storage_mut();
+        let sweeps = db.get_signed_sweep_transactions().await?;
+        if sweeps.is_empty() {
+            return Ok(());
+        }
+
+        let Some(chain_tip) = db.get_bitcoin_canonical_chain_tip_ref().await? else {
+            tracing::debug!("no bitcoin chain tip; not rebroadcasting signed sweeps");
+            return Ok(());
+        };
+
+        // The sweeps are ordered by when they were signed, so each RBF
+        // replacement comes after the sweeps that it replaces.
+        let mut stale = Vec::new();
+        let mut latest: HashMap<bitcoin::OutPoint, model::SignedSweepTransaction> = HashMap::new();
+        for sweep in sweeps {
+            let Some(signer_input) = sweep.tx.input.first().map(|tx_in| tx_in.previous_output)
+            else {
+                stale.push(sweep.txid);
+                continue;
+            };
+            if let Some(replaced) = latest.insert(signer_input, sweep) {
+                tracing::debug!(txid = %replaced.txid, "signed sweep was replaced; deleting it");
+                stale.push(replaced.txid);
+            }
+        }
+        let mut sweeps: Vec<_> = latest.into_iter().collect();
+        sweeps.sort_by_key(|(_, sweep)| (sweep.created_at, sweep.txid));
+
+        let mut spendable: HashSet<bitcoin::OutPoint> = db
+            .get_signer_utxos(&chain_tip.block_hash)
+            .await?
+            .into_iter()
+            .map(|utxo| utxo.outpoint)
+            .collect();
+
+        let bitcoin_client = self.context.get_bitcoin_client();
+        let mut canonical_tips: HashMap<model::BitcoinBlockHash, bool> = HashMap::new();
+
+        for (signer_input, sweep) in sweeps {
+            let is_canonical = match canonical_tips.get(&sweep.bitcoin_chain_tip) {
+                Some(is_canonical) => *is_canonical,
+                None => {
+                    let block = db.get_bitcoin_block(&sweep.bitcoin_chain_tip).await?;
+                    let is_canonical = match block {
+                        Some(block) => {
+                            let block_ref = block.into();
+                            db.in_canonical_bitcoin_blockchain(&chain_tip, &block_ref)
+                                .await?
+                        }
+                        None => false,
+                    };
+                    canonical_tips.insert(sweep.bitcoin_chain_tip, is_canonical);
+                    is_canonical
+                }
+            };
+            if !is_canonical || !spendable.remove(&signer_input) {
+                tracing::debug!(txid = %sweep.txid, "signed sweep is no longer valid; deleting it");
+                stale.push(sweep.txid);
+                continue;
+            }
+            // The next sweep in the package spends the signers' output of
+            // this one.
+            spendable.insert(bitcoin::OutPoint::new(sweep.txid.into(), 0));
+
+            match bitcoin_client.broadcast_transaction(&sweep.tx).await {
+                Ok(()) => tracing::info!(txid = %sweep.txid, "rebroadcast a signed sweep"),
+                Err(error) => {
+                    tracing::warn!(%error, txid = %sweep.txid, "could not rebroadcast a signed sweep")
+                }
+            }
+        }
+
+        db.delete_signed_sweep_transactions(&stale).await
+    }
+
     /// A function that filters the [`Context::as_signal_stream`] stream
     /// for items that the coordinator might care about, which includes
     /// some network messages and transaction signer messages.
@@ -332,6 +623,16 @@ where
             return Ok(());
         }
 
+        if self.context.state().is_in_safe_mode() {
+            tracing::warn!("signer is in safe-mode; not coordinating");
+            return Ok(());
+        }
+
+        if self.context.state().is_contract_paused() {
+            tracing::warn!("the sBTC contracts are paused; not coordinating");
+            return Ok(());
+        }
+
         // If we are not the coordinator, then we have no business
         // coordinating DKG or constructing bitcoin and stacks
         // transactions, might as well return early.
@@ -368,6 +669,12 @@ where
         }
 
         let maybe_registry_signer_set_info = self.context.state().registry_signer_set_info();
+        if let Some(info) = maybe_registry_signer_set_info.as_ref() {
+            invariants::enforce(invariants::check_signature_threshold(
+                info.signatures_required,
+                info.signer_set.len(),
+            ))?;
+        }
 
         tracing::debug!("we are the coordinator");
         metrics::counter!(Metrics::CoordinatorTenuresTotal).increment(1);
@@ -438,14 +745,26 @@ where
             .map(|info| info.signer_set)
             .ok_or_else(|| Error::NoKeyRotationEvent)?;
 
-        let bitcoin_processing_fut = self.construct_and_sign_bitcoin_sbtc_transactions(
-            &bitcoin_chain_tip,
-            &aggregate_key,
-            &signer_public_keys,
-        );
+        // The signers sweep to the aggregate key in the registry, so if
+        // the signers' UTXO has been swept to our latest aggregate key
+        // before the rotate-keys contract call for it was confirmed, then
+        // sweeping now would move the UTXO back to the previous key. We
+        // hold back sweeps until the contract call, which we submit above,
+        // is confirmed.
+        if self.is_rotate_keys_confirmation_pending().await? {
+            tracing::warn!(
+                "the signers' UTXO is locked by an unregistered aggregate key; holding back sweeps until the rotate-keys contract call is confirmed"
+            );
+        } else {
+            let bitcoin_processing_fut = self.construct_and_sign_bitcoin_sbtc_transactions(
+                &bitcoin_chain_tip,
+                &aggregate_key,
+                &signer_public_keys,
+            );
 
-        if let Err(error) = bitcoin_processing_fut.await {
-            tracing::error!(%error, "failed to construct and sign bitcoin transactions");
+            if let Err(error) = bitcoin_processing_fut.await {
+                tracing::error!(%error, "failed to construct and sign bitcoin transactions");
+            }
         }
 
         self.construct_and_sign_stacks_response_transactions(
@@ -540,23 +859,43 @@ where
         Ok(None)
     }
 
+    /// Return whether the signers' UTXO is locked by the aggregate key of
+    /// our latest DKG shares while the rotate-keys contract call for it
+    /// has not been confirmed. See the [`crate::key_rotation_recovery`]
+    /// module for how this is detected.
+    async fn is_rotate_keys_confirmation_pending(&self) -> Result<bool, Error> {
+        let key_rotation = self.context.state().key_rotation_state();
+        let Some(utxo_key) = key_rotation.unregistered_utxo_key() else {
+            return Ok(false);
+        };
+
+        let last_dkg = self
+            .context
+            .get_storage()
+            .get_latest_encrypted_dkg_shares()
+            .await?;
+        Ok(last_dkg.is_some_and(|shares| shares.aggregate_key == utxo_key))
+    }
+
     /// Constructs a BitcoinPreSignRequest from the given transaction package and
     /// sends it to the signers. Waits for acknowledgments from the signers until
-    /// the threshold is met or a timeout occurs.
+    /// the threshold is met or a timeout occurs, and returns the request
+    /// that was sent, if any.
     /// If the signal stream closes unexpectedly, triggers a shutdown.
     #[tracing::instrument(skip_all)]
     async fn construct_and_send_bitcoin_presign_request(
         &mut self,
         bitcoin_chain_tip: &model::BitcoinBlockHash,
         signer_btc_state: &utxo::SignerBtcState,
+        parallel_sweeps: bool,
         transaction_package: &[utxo::UnsignedTransaction<'_>],
-    ) -> Result<(), Error> {
+    ) -> Result<Option<BitcoinPreSignRequest>, Error> {
         // Constructing a pre-sign request with empty request IDs is
         // invalid. The other signers should reject the message if we send
         // one, so let's not create it.
         if transaction_package.is_empty() {
             tracing::debug!("no requests to handle this tenure, exiting");
-            return Ok(());
+            return Ok(None);
         }
         // Create the BitcoinPreSignRequest from the transaction package
         let sbtc_requests = BitcoinPreSignRequest {
@@ -566,8 +905,22 @@ where
                 .collect(),
             fee_rate: signer_btc_state.fee_rate,
             last_fees: signer_btc_state.last_fees.map(Into::into),
+            parallel_sweeps,
+            magic_bytes: signer_btc_state.magic_bytes,
         };
 
+        // The signers run the same static checks on the request package
+        // during pre-sign, so there is no point in sending a package that
+        // fails them.
+        package_rules::check_request_package(
+            &sbtc_requests.request_package,
+            sbtc_requests.fee_rate,
+            self.context
+                .config()
+                .signer
+                .max_withdrawals_per_bitcoin_tx(),
+        )?;
+
         let presign_ack_filter = |event: &SignerSignal| {
             matches!(
                 event,
@@ -582,8 +935,14 @@ where
         let signature_threshold = self.context.config().signer.bootstrap_signatures_required;
 
         // Send the presign request message
-        tracing::debug!(request = %sbtc_requests, "sending pre-sign request");
-        self.send_message(sbtc_requests, bitcoin_chain_tip).await?;
+        tracing::debug!(
+            request = %sbtc_requests,
+            correlation_id = %sbtc_requests.correlation_ids(),
+            "sending pre-sign request"
+        );
+        let max_duration = self.bitcoin_presign_request_max_duration;
+        self.send_message_with_deadline(sbtc_requests.clone(), bitcoin_chain_tip, max_duration)
+            .await?;
 
         tokio::pin!(signal_stream);
         let future = async {
@@ -660,7 +1019,8 @@ where
         )
         .increment(1);
 
-        res?
+        res??;
+        Ok(Some(sbtc_requests))
     }
 
     /// Construct and coordinate WSTS signing rounds for sBTC transactions on Bitcoin,
@@ -702,6 +1062,25 @@ where
             return Ok(());
         };
 
+        // If we stopped partway through the package that we constructed
+        // for this chain tip, then we continue with it instead of
+        // constructing a new one. Parallel packages are the exception,
+        // since their transactions depend on the fees of the mempool
+        // transactions that spend the spare UTXOs, which we do not
+        // persist. We construct a new package instead, whose transactions
+        // replace the ones that were broadcast.
+        let sweep_package = self
+            .context
+            .get_storage()
+            .get_sweep_package(bitcoin_chain_tip.as_ref())
+            .await?;
+        let is_resumable = |package: &model::SweepPackage| {
+            !package.is_complete() && !package.presign_request.parallel_sweeps
+        };
+        if let Some(package) = sweep_package.filter(is_resumable) {
+            return self.resume_sweep_package(pending_requests, package).await;
+        }
+
         tracing::debug!(
             num_deposits = %pending_requests.deposits.len(),
             num_withdrawals = pending_requests.withdrawals.len(),
@@ -736,19 +1115,185 @@ where
             }
         }
 
+        invariants::enforce(invariants::check_sweep_package(
+            pending_requests.signer_state.utxo.outpoint,
+            &pending_requests.spare_signer_outpoints(),
+            &transaction_package,
+        ))?;
+
         // Send the pre-sign request to the signers and wait for their
         // acknowledgments.
-        self.construct_and_send_bitcoin_presign_request(
-            bitcoin_chain_tip.as_ref(),
-            &pending_requests.signer_state,
-            &transaction_package,
-        )
-        .await?;
+        let presign_request = self
+            .construct_and_send_bitcoin_presign_request(
+                bitcoin_chain_tip.as_ref(),
+                &pending_requests.signer_state,
+                pending_requests.parallel_sweeps(),
+                &transaction_package,
+            )
+            .await?;
+
+        // Persist the package before signing any of its transactions, so
+        // that we can continue with it if we stop partway through.
+        if let Some(presign_request) = presign_request {
+            let package = model::SweepPackage {
+                bitcoin_chain_tip: bitcoin_chain_tip.block_hash,
+                presign_request,
+                last_fees: pending_requests.signer_state.last_fees,
+                broadcast_txids: Vec::new(),
+            };
+            let db = self.context.get_storage_mut();
+            if let Err(error) = db.write_sweep_package(&package).await {
+                tracing::warn!(%error, "could not persist the sweep package");
+            }
+        }
+
+        self.sign_and_broadcast_package(bitcoin_chain_tip.as_ref(), transaction_package)
+            .await
+    }
+
+    /// Continue with the sweep package that we constructed for the
+    /// package's bitcoin chain tip but stopped partway through, say
+    /// because we restarted after broadcasting some of its transactions.
+    ///
+    /// The signers validated every transaction in the package when they
+    /// received the original pre-sign request, so we reconstruct the
+    /// transactions from the persisted request IDs and fee details, and
+    /// coordinate signing rounds for the ones that have not been
+    /// broadcast without sending another pre-sign request. The package is
+    /// abandoned if the reconstructed transactions do not match the ones
+    /// that were already broadcast.
+    #[tracing::instrument(skip_all, fields(bitcoin_chain_tip = %package.bitcoin_chain_tip))]
+    async fn resume_sweep_package(
+        &mut self,
+        mut pending_requests: utxo::SbtcRequests,
+        mut package: model::SweepPackage,
+    ) -> Result<(), Error> {
+        let transactions = self
+            .reconstruct_sweep_package(&mut pending_requests, &mut package)
+            .await?;
 
-        // Construct, sign and broadcast the bitcoin transactions.
-        for mut transaction in transaction_package {
-            self.sign_and_broadcast(bitcoin_chain_tip.as_ref(), &mut transaction)
+        let num_broadcast = package.broadcast_txids.len();
+        tracing::info!(
+            num_broadcast,
+            num_transactions = transactions.len(),
+            "resuming an interrupted sweep package"
+        );
+
+        let remaining = transactions.into_iter().skip(num_broadcast).collect();
+        self.sign_and_broadcast_package(&package.bitcoin_chain_tip, remaining)
+            .await
+    }
+
+    /// Reconstruct the transactions of a persisted sweep package, and
+    /// bring the package's record of the broadcast transactions up to
+    /// date.
+    ///
+    /// Transactions in the package that are in the mempool, but that are
+    /// not recorded as broadcast, were broadcast by a coordinator that
+    /// stopped before recording them, or by the coordinator whose tenure
+    /// we are taking over. They are recorded as broadcast here, so that
+    /// they are not signed and broadcast a second time.
+    async fn reconstruct_sweep_package<'a>(
+        &self,
+        pending_requests: &'a mut utxo::SbtcRequests,
+        package: &mut model::SweepPackage,
+    ) -> Result<Vec<utxo::UnsignedTransaction<'a>>, Error> {
+        // The signers' UTXO is the same as when the package was
+        // constructed, since the chain tip is the same, but the fee
+        // details need to be the ones that the signers validated.
+        pending_requests.signer_state.fee_rate = package.presign_request.fee_rate;
+        pending_requests.signer_state.last_fees = package.last_fees;
+        // Only packages that are not parallel sweeps are resumed.
+        pending_requests.signer_state.change_outputs = 1;
+        pending_requests.spare_signer_utxos.clear();
+        let pending_requests: &'a utxo::SbtcRequests = pending_requests;
+
+        let request_package = &package.presign_request.request_package;
+        let transactions = pending_requests.reconstruct_transactions(request_package)?;
+        invariants::enforce(invariants::check_sweep_package(
+            pending_requests.signer_state.utxo.outpoint,
+            &[],
+            &transactions,
+        ))?;
+
+        for (transaction, expected) in transactions.iter().zip(&package.broadcast_txids) {
+            let actual: model::BitcoinTxId = transaction.tx.compute_txid().into();
+            if actual != *expected {
+                return Err(Error::SweepPackageTxidMismatch { expected: *expected, actual });
+            }
+        }
+
+        // Each transaction in the package spends the signers' output of
+        // the one before it, so only a prefix of the remaining
+        // transactions can be in the mempool.
+        let bitcoin_client = self.context.get_bitcoin_client();
+        let db = self.context.get_storage_mut();
+        for transaction in transactions.iter().skip(package.broadcast_txids.len()) {
+            let txid = transaction.tx.compute_txid();
+            if bitcoin_client.get_mempool_entry(&txid).await?.is_none() {
+                break;
+            }
+            tracing::debug!(%txid, "sweep package transaction is already in the mempool");
+            let txid = model::BitcoinTxId::from(txid);
+            db.write_sweep_package_broadcast(&package.bitcoin_chain_tip, &txid)
                 .await?;
+            package.broadcast_txids.push(txid);
+        }
+
+        Ok(transactions)
+    }
+
+    /// Sign and broadcast the bitcoin transactions of a sweep package,
+    /// recording the progress of the package as each transaction is
+    /// broadcast.
+    ///
+    /// Each transaction in the package spends the signers' UTXO created
+    /// by the previous one, so the signing rounds must happen in order.
+    /// However, the sighashes of a transaction commit to the txid of the
+    /// previous one, which does not depend on its witness data. So we
+    /// prepare the signing rounds of the next transaction in the package
+    /// while we coordinate the signing rounds of the current one. If the
+    /// current transaction cannot be signed or broadcast then preparing
+    /// the next one is cancelled, while an error preparing the next one is
+    /// only returned once the current one has been broadcast.
+    async fn sign_and_broadcast_package(
+        &mut self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        transaction_package: Vec<utxo::UnsignedTransaction<'_>>,
+    ) -> Result<(), Error> {
+        let storage = self.context.get_storage();
+        let private_key = self.private_key;
+
+        let mut transactions = transaction_package.into_iter();
+        let Some(mut transaction) = transactions.next() else {
+            return Ok(());
+        };
+        let mut prepared = PreparedSweep::new(&storage, private_key, &transaction).await?;
+
+        loop {
+            let next_transaction = transactions.next();
+            let prepare_next = async {
+                let prepared = match &next_transaction {
+                    Some(next) => PreparedSweep::new(&storage, private_key, next)
+                        .await
+                        .map(Some),
+                    None => Ok(None),
+                };
+                Ok::<_, Error>(prepared)
+            };
+            let sign_current =
+                self.sign_and_broadcast(bitcoin_chain_tip, &mut transaction, prepared);
+
+            let ((), next_prepared) = futures::future::try_join(sign_current, prepare_next).await?;
+
+            let txid: model::BitcoinTxId = transaction.tx.compute_txid().into();
+            let db = self.context.get_storage_mut();
+            if let Err(error) = db
+                .write_sweep_package_broadcast(bitcoin_chain_tip, &txid)
+                .await
+            {
+                tracing::warn!(%error, %txid, "could not record the sweep package progress");
+            }
 
             // TODO: if this (considering also fallback clients) fails, we will
             // need to handle the inconsistency of having the sweep tx confirmed
@@ -770,9 +1315,15 @@ where
                 .inspect_err(|error| {
                     tracing::warn!(%error, "could not accept withdrawals on Emily");
                 });
-        }
 
-        Ok(())
+            match (next_transaction, next_prepared?) {
+                (Some(next), Some(next_prepared)) => {
+                    transaction = next;
+                    prepared = next_prepared;
+                }
+                _ => return Ok(()),
+            }
+        }
     }
 
     /// Construct and coordinate signing rounds for `deposit-accept`,
@@ -848,7 +1399,11 @@ where
             .ok_or(Error::NoStacksChainTip)?
             .block_hash;
         let swept_deposits = db
-            .get_swept_deposit_requests(chain_tip.as_ref(), &stacks_chain_tip, self.context_window)
+            .get_swept_deposit_requests(
+                chain_tip.as_ref(),
+                &stacks_chain_tip,
+                self.sweep_confirmation_window(),
+            )
             .await?;
 
         if swept_deposits.is_empty() {
@@ -882,6 +1437,26 @@ where
                 Ok(false) => (),
             };
 
+            let target = model::ContractCallTarget::Deposit {
+                txid: outpoint.txid.into(),
+                output_index: outpoint.vout,
+            };
+            match self.pending_contract_call(&target, chain_tip).await {
+                Err(error) => {
+                    tracing::warn!(%error, %outpoint, "could not check for an in-flight contract call");
+                    continue;
+                }
+                Ok(Some(contract_call)) => {
+                    tracing::debug!(
+                        txid = %contract_call.txid,
+                        %outpoint,
+                        "a complete-deposit contract call is already in flight"
+                    );
+                    continue;
+                }
+                Ok(None) => (),
+            };
+
             let sign_request_fut =
                 self.construct_deposit_stacks_sign_request(req, bitcoin_aggregate_key, wallet);
 
@@ -901,7 +1476,10 @@ where
             let process_request_fut =
                 self.process_sign_request(sign_request, chain_tip.as_ref(), multi_tx, wallet);
 
-            let status = match process_request_fut.await {
+            let result = process_request_fut.await;
+            self.record_contract_call(&target, chain_tip, &result).await;
+
+            let status = match result {
                 Ok(txid) => {
                     tracing::info!(%txid, "successfully submitted complete-deposit transaction");
                     "success"
@@ -946,7 +1524,7 @@ where
             .get_swept_withdrawal_requests(
                 &chain_tip.block_hash,
                 &stacks_chain_tip,
-                self.context_window,
+                self.sweep_confirmation_window(),
             )
             .await
             .inspect_err(|error| tracing::error!(%error, "could not fetch swept withdrawals"))
@@ -958,7 +1536,7 @@ where
             .get_pending_rejected_withdrawal_requests(
                 chain_tip,
                 &stacks_chain_tip,
-                self.context_window,
+                self.withdrawal_lookback(),
             )
             .await
             .inspect_err(|error| tracing::error!(%error, "could not fetch rejected withdrawals"))
@@ -975,6 +1553,25 @@ where
             "we have withdrawals requests that may need completion"
         );
 
+        // We report the SPV proof of a sweep transaction to Emily once all
+        // of the withdrawals that it fulfilled have been completed.
+        let mut sweeps: HashMap<model::BitcoinTxId, Vec<model::SweptWithdrawalRequest>> =
+            HashMap::new();
+        for request in swept_withdrawals.iter() {
+            sweeps
+                .entry(request.sweep_txid)
+                .or_default()
+                .push(request.clone());
+        }
+        let sweep_txids: HashSet<_> = sweeps.keys().copied().collect();
+        self.context
+            .state()
+            .retain_reported_sweep_proofs(&sweep_txids);
+
+        let stacks = self.context.get_stacks_client();
+        let deployer = self.context.config().signer.deployer.clone();
+        let mut completed_withdrawals = HashSet::new();
+
         for swept_request in swept_withdrawals {
             if self.context.state().bitcoin_chain_tip().as_ref() != Some(chain_tip) {
                 tracing::info!("new bitcoin chain tip, stopping coordinator activities");
@@ -982,6 +1579,26 @@ where
             }
 
             let withdrawal_id = swept_request.qualified_id();
+            let is_completed = stacks
+                .is_withdrawal_completed(&deployer, swept_request.request_id)
+                .await;
+            match is_completed {
+                Ok(true) => {
+                    tracing::warn!(%withdrawal_id, "swept withdrawal request already processed");
+                    completed_withdrawals.insert(withdrawal_id);
+                    continue;
+                }
+                Ok(false) => {}
+                Err(error) => {
+                    tracing::warn!(
+                        %error,
+                        %withdrawal_id,
+                        "could not check whether the withdrawal request was completed"
+                    );
+                    continue;
+                }
+            }
+
             let fut = self.construct_and_sign_withdrawal_accept(
                 chain_tip,
                 wallet,
@@ -998,6 +1615,24 @@ where
             }
         }
 
+        for (sweep_txid, requests) in sweeps {
+            let is_completed = requests
+                .iter()
+                .all(|request| completed_withdrawals.contains(&request.qualified_id()));
+            if !is_completed || self.context.state().is_sweep_proof_reported(&sweep_txid) {
+                continue;
+            }
+
+            match self.report_withdrawal_fulfillment_proofs(&requests).await {
+                Ok(()) => self.context.state().set_sweep_proof_reported(sweep_txid),
+                Err(error) => tracing::warn!(
+                    %error,
+                    %sweep_txid,
+                    "could not report the withdrawal fulfillment proofs to Emily"
+                ),
+            }
+        }
+
         for withdrawal in rejected_withdrawals {
             if self.context.state().bitcoin_chain_tip().as_ref() != Some(chain_tip) {
                 tracing::info!("new bitcoin chain tip, stopping coordinator activities");
@@ -1031,15 +1666,12 @@ where
         bitcoin_aggregate_key: &PublicKey,
         request: model::SweptWithdrawalRequest,
     ) -> Result<(), Error> {
-        let stacks = self.context.get_stacks_client();
-        let deployer = self.context.config().signer.deployer.clone();
-
-        let is_completed = stacks
-            .is_withdrawal_completed(&deployer, request.request_id)
-            .await?;
-
-        if is_completed {
-            tracing::warn!("swept withdrawal request already processed");
+        let target = model::ContractCallTarget::Withdrawal { id: request.qualified_id() };
+        if let Some(contract_call) = self.pending_contract_call(&target, chain_tip).await? {
+            tracing::debug!(
+                txid = %contract_call.txid,
+                "a withdrawal contract call is already in flight"
+            );
             return Ok(());
         }
 
@@ -1061,9 +1693,11 @@ where
         let process_request_fut =
             self.process_sign_request(sign_request, &chain_tip.block_hash, multi_tx, wallet);
 
+        let result = process_request_fut.await;
+        self.record_contract_call(&target, chain_tip, &result).await;
         tracing::debug!("processed withdrawal request");
 
-        let status = match process_request_fut.await {
+        let status = match result {
             Ok(txid) => {
                 tracing::info!(%txid, "successfully submitted accept-withdrawal transaction");
                 "success"
@@ -1088,6 +1722,65 @@ where
         Ok(())
     }
 
+    /// Attach the SPV proof of the sweep transaction, and the fee assessed
+    /// against each withdrawal, to the fulfillments that Emily has recorded
+    /// for the given completed withdrawal requests. All of the requests
+    /// must have been fulfilled by the same sweep transaction.
+    async fn report_withdrawal_fulfillment_proofs(
+        &self,
+        requests: &[model::SweptWithdrawalRequest],
+    ) -> Result<(), Error> {
+        let Some(request) = requests.first() else {
+            return Ok(());
+        };
+        let db = self.context.get_storage();
+        let btc_client = self.context.get_bitcoin_client();
+        let sweep_block_hash = request.sweep_block_hash.into();
+
+        let tx_info = util::get_bitcoin_tx_info(
+            &db,
+            &btc_client,
+            &request.sweep_txid,
+            &request.sweep_block_hash,
+        )
+        .await?
+        .ok_or_else(|| {
+            Error::BitcoinTxMissing(request.sweep_txid.into(), Some(sweep_block_hash))
+        })?;
+
+        tx_info.check_witness_data()?;
+
+        let proof = btc_client
+            .get_tx_out_proof(&request.sweep_txid, &sweep_block_hash)
+            .await?;
+
+        let updates = requests
+            .iter()
+            .map(|request| {
+                let outpoint = request.withdrawal_outpoint();
+                let assessed_fee = tx_info
+                    .assess_output_fee(outpoint.vout as usize)
+                    .ok_or_else(|| Error::VoutMissing(outpoint.txid, outpoint.vout))?;
+                Ok(emily_client::withdrawal_fulfillment_proof(
+                    request,
+                    &proof,
+                    assessed_fee,
+                ))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        self.context
+            .get_emily_client()
+            .update_withdrawals(updates)
+            .await?;
+
+        tracing::debug!(
+            sweep_txid = %request.sweep_txid,
+            "reported the withdrawal fulfillment proofs to Emily"
+        );
+        Ok(())
+    }
+
     #[tracing::instrument(skip_all, fields(withdrawal_id = %request.qualified_id()))]
     async fn construct_and_sign_withdrawal_reject(
         &mut self,
@@ -1132,6 +1825,15 @@ where
             return Ok(());
         }
 
+        let target = model::ContractCallTarget::Withdrawal { id: qualified_id };
+        if let Some(contract_call) = self.pending_contract_call(&target, chain_tip).await? {
+            tracing::debug!(
+                txid = %contract_call.txid,
+                "a withdrawal contract call is already in flight"
+            );
+            return Ok(());
+        }
+
         let sign_request_fut = self.construct_withdrawal_reject_stacks_sign_request(
             &request,
             bitcoin_aggregate_key,
@@ -1148,7 +1850,10 @@ where
         let process_request_fut =
             self.process_sign_request(sign_request, chain_tip.as_ref(), multi_tx, wallet);
 
-        let status = match process_request_fut.await {
+        let result = process_request_fut.await;
+        self.record_contract_call(&target, chain_tip, &result).await;
+
+        let status = match result {
             Ok(txid) => {
                 tracing::info!(%txid, "successfully submitted withdrawal reject transaction");
                 "success"
@@ -1171,6 +1876,81 @@ where
         Ok(())
     }
 
+    /// Return the contract call that we submitted for the given request
+    /// and that may still be confirmed, if there is one.
+    ///
+    /// When two tenures overlap, say because of a quick reorg, both of
+    /// them could otherwise broadcast a contract call for the same
+    /// request, and one of them would be wasted. A contract call stops
+    /// counting once it is confirmed or rejected, or once
+    /// [`INFLIGHT_CONTRACT_CALL_EXPIRY_BLOCKS`] bitcoin blocks have passed
+    /// since it was submitted.
+    ///
+    /// A contract call submitted while the chain tip was on a bitcoin
+    /// fork that has since been abandoned will not be confirmed on the
+    /// canonical chain, so we clear it so that it does not block a new
+    /// contract call for the request.
+    async fn pending_contract_call(
+        &self,
+        target: &model::ContractCallTarget,
+        chain_tip: &model::BitcoinBlockRef,
+    ) -> Result<Option<model::InflightContractCall>, Error> {
+        let db = self.context.get_storage_mut();
+        let Some(contract_call) = db.get_inflight_contract_call(target).await? else {
+            return Ok(None);
+        };
+
+        let block_ref = contract_call.bitcoin_block_ref();
+        if !db
+            .in_canonical_bitcoin_blockchain(chain_tip, &block_ref)
+            .await?
+        {
+            tracing::debug!(
+                txid = %contract_call.txid,
+                bitcoin_block_hash = %block_ref.block_hash,
+                "clearing a contract call submitted on an abandoned bitcoin fork"
+            );
+            db.delete_inflight_contract_call(target).await?;
+            return Ok(None);
+        }
+
+        let is_pending = contract_call
+            .is_pending_at(chain_tip.block_height, INFLIGHT_CONTRACT_CALL_EXPIRY_BLOCKS);
+        Ok(is_pending.then_some(contract_call))
+    }
+
+    /// Record the outcome of submitting a contract call for the given
+    /// request. A contract call that the stacks node accepted is in flight
+    /// until it is confirmed, while one that it rejected is not, so
+    /// another one may be submitted for the request right away.
+    async fn record_contract_call(
+        &self,
+        target: &model::ContractCallTarget,
+        chain_tip: &model::BitcoinBlockRef,
+        result: &Result<StacksTxId, Error>,
+    ) {
+        let db = self.context.get_storage_mut();
+        let outcome = match result {
+            Ok(txid) => {
+                let contract_call = model::InflightContractCall {
+                    target: target.clone(),
+                    txid: *txid,
+                    bitcoin_block_height: chain_tip.block_height,
+                    bitcoin_block_hash: chain_tip.block_hash,
+                };
+                db.write_inflight_contract_call(&contract_call).await
+            }
+            Err(Error::StacksTxRejection(_)) => {
+                db.delete_inflight_contract_call(target).await.map(|_| ())
+            }
+            Err(_) => Ok(()),
+        };
+
+        if let Err(error) = outcome {
+            tracing::warn!(%error, "could not record the outcome of the contract call");
+        }
+    }
+
     /// Performs verification of the DKG process by running a FROST signing
     /// round using the new key. This is done to assert that all signers have
     /// successfully signed with the new aggregate key before proceeding with
@@ -1259,6 +2039,15 @@ where
         // TODO: we should validate the contract call before asking others
         // to sign it.
         let rotate_keys_v1 = RotateKeysV1::load(&self.context, rotate_key_aggregate_key).await?;
+        if self
+            .context
+            .config()
+            .signer
+            .require_key_rotation_attestations
+        {
+            self.wait_for_key_rotation_attestations(&rotate_keys_v1)
+                .await?;
+        }
         let contract_call = ContractCall::RotateKeysV1(Box::new(rotate_keys_v1));
 
         // Rotate key transactions should be done as soon as possible, so
@@ -1286,8 +2075,42 @@ where
             .await
     }
 
+    /// Wait until enough members of the new signer set have attested to
+    /// the given key rotation for it to pass validation, giving up after
+    /// the signing round max duration.
+    #[tracing::instrument(skip_all, fields(aggregate_key = %rotate_keys.aggregate_key))]
+    async fn wait_for_key_rotation_attestations(
+        &self,
+        rotate_keys: &RotateKeysV1,
+    ) -> Result<(), Error> {
+        let db = self.context.get_storage();
+        let required = usize::from(rotate_keys.signatures_required);
+        let deadline = tokio::time::Instant::now() + self.signing_round_max_duration;
+
+        loop {
+            let attestations = db
+                .get_key_rotation_attestations(&rotate_keys.aggregate_key)
+                .await?;
+            let count = rotate_keys.attestation_count(&attestations);
+            if count >= required {
+                tracing::debug!(%count, %required, "collected key rotation attestations");
+                return Ok(());
+            }
+
+            if tokio::time::Instant::now() >= deadline {
+                tracing::warn!(%count, %required, "too few key rotation attestations");
+                return Err(Error::InsufficientKeyRotationAttestations(
+                    rotate_keys.aggregate_key,
+                    count,
+                    required,
+                ));
+            }
+            tokio::time::sleep(KEY_ROTATION_ATTESTATION_POLL_INTERVAL).await;
+        }
+    }
+
     /// Sign and broadcast the stacks transaction
-    #[tracing::instrument(skip_all)]
+    #[tracing::instrument(skip_all, fields(correlation_id = %sign_request.correlation_ids()))]
     async fn process_sign_request(
         &mut self,
         sign_request: StacksTransactionSignRequest,
@@ -1342,17 +2165,23 @@ where
         bitcoin_aggregate_key: &PublicKey,
         wallet: &SignerWallet,
     ) -> Result<(StacksTransactionSignRequest, MultisigTx), Error> {
-        // Retrieve the Bitcoin sweep transaction from the Bitcoin node. We
-        // can't get it from the database because the transaction is
-        // only in the node's mempool at this point.
-        let tx_info = self
-            .context
-            .get_bitcoin_client()
-            .get_tx_info(&req.sweep_txid, &req.sweep_block_hash)
-            .await?
-            .ok_or_else(|| {
-                Error::BitcoinTxMissing(req.sweep_txid.into(), Some(req.sweep_block_hash.into()))
-            })?;
+        // Retrieve the Bitcoin sweep transaction from our cache, or from
+        // the Bitcoin node if we have not cached it.
+        let db = self.context.get_storage();
+        let btc_client = self.context.get_bitcoin_client();
+        let tx_info =
+            util::get_bitcoin_tx_info(&db, &btc_client, &req.sweep_txid, &req.sweep_block_hash)
+                .await?
+                .ok_or_else(|| {
+                    Error::BitcoinTxMissing(
+                        req.sweep_txid.into(),
+                        Some(req.sweep_block_hash.into()),
+                    )
+                })?;
+
+        // Other signers reject the contract call if the sweep has witness
+        // data that changes the fees assessed against the request.
+        tx_info.check_witness_data()?;
 
         let outpoint = req.deposit_outpoint();
         let assessed_bitcoin_fee = tx_info
@@ -1404,16 +2233,24 @@ where
         wallet: &SignerWallet,
     ) -> Result<(StacksTransactionSignRequest, MultisigTx), Error> {
         tracing::debug!("constructing withdrawal accept sign request");
-        // Retrieve the Bitcoin sweep transaction and compute the assessed fee
-        // from the Bitcoin node
+        // Retrieve the Bitcoin sweep transaction, from our cache or from the
+        // Bitcoin node, and compute the assessed fee
+        let db = self.context.get_storage();
         let btc_client = self.context.get_bitcoin_client();
 
-        let tx_info = btc_client
-            .get_tx_info(&req.sweep_txid, &req.sweep_block_hash)
-            .await?
-            .ok_or_else(|| {
-                Error::BitcoinTxMissing(req.sweep_txid.into(), Some(req.sweep_block_hash.into()))
-            })?;
+        let tx_info =
+            util::get_bitcoin_tx_info(&db, &btc_client, &req.sweep_txid, &req.sweep_block_hash)
+                .await?
+                .ok_or_else(|| {
+                    Error::BitcoinTxMissing(
+                        req.sweep_txid.into(),
+                        Some(req.sweep_block_hash.into()),
+                    )
+                })?;
+
+        // Other signers reject the contract call if the sweep has witness
+        // data that changes the fees assessed against the request.
+        tx_info.check_witness_data()?;
 
         let outpoint = req.withdrawal_outpoint();
         let qualified_id = req.qualified_id();
@@ -1506,9 +2343,9 @@ where
 
         // We ask for the signers to sign our transaction (including
         // ourselves, via our tx signer event loop)
-        self.send_message(req, chain_tip).await?;
-
         let max_duration = self.signing_round_max_duration;
+        self.send_message_with_deadline(req, chain_tip, max_duration)
+            .await?;
 
         let future = async {
             while multi_tx.num_signatures() < wallet.signatures_required() {
@@ -1554,23 +2391,21 @@ where
             .map_err(|_| Error::SignatureTimeout(txid))?
     }
 
-    /// Coordinate a signing round for the given request
-    /// and broadcast it once it's signed.
-    #[tracing::instrument(skip_all)]
+    /// Coordinate the prepared signing rounds for the given sweep
+    /// transaction and broadcast it once it's signed.
+    #[tracing::instrument(skip_all, fields(
+        correlation_id = %CorrelationIds::from(&TxRequestIds::from(&transaction.requests))
+    ))]
     async fn sign_and_broadcast(
         &mut self,
         bitcoin_chain_tip: &model::BitcoinBlockHash,
         transaction: &mut utxo::UnsignedTransaction<'_>,
+        prepared: PreparedSweep,
     ) -> Result<(), Error> {
-        let db = self.context.get_storage();
-        let sighashes = transaction.construct_digests()?;
-        let locking_public_key = sighashes.signers_aggregate_key.into();
-        let mut fire_coordinator =
-            FireCoordinator::load(&db, locking_public_key, self.private_key).await?;
-
-        let msg = sighashes.signers.to_raw_hash().to_byte_array();
+        let PreparedSweep { txid, signers, deposits } = prepared;
+        let (sighash, mut fire_coordinator) = signers;
+        let msg = sighash.to_raw_hash().to_byte_array();
 
-        let txid = transaction.tx.compute_txid();
         let message_id = txid.into();
         let instant = std::time::Instant::now();
         let signature = self
@@ -1601,13 +2436,9 @@ where
 
         let mut deposit_witness = Vec::new();
 
-        for (deposit, sighash) in sighashes.deposits.into_iter() {
+        for (deposit, sighash, mut fire_coordinator) in deposits {
             let msg = sighash.to_raw_hash().to_byte_array();
 
-            let locking_public_key = deposit.signers_public_key.into();
-            let mut fire_coordinator =
-                FireCoordinator::load(&db, locking_public_key, self.private_key).await?;
-
             let instant = std::time::Instant::now();
             let signature = self
                 .coordinate_signing_round(
@@ -1650,6 +2481,23 @@ where
                 tx_in.witness = witness;
             });
 
+        // Persist the signed transaction before broadcasting it, so that
+        // it is not lost if we stop before it reaches the bitcoin network.
+        let signed_sweep = model::SignedSweepTransaction {
+            txid: txid.into(),
+            tx: transaction.tx.clone(),
+            bitcoin_chain_tip: *bitcoin_chain_tip,
+            created_at: time::OffsetDateTime::now_utc().into(),
+        };
+        if let Err(error) = self
+            .context
+            .get_storage_mut()
+            .write_signed_sweep_transaction(&signed_sweep)
+            .await
+        {
+            tracing::warn!(%error, "could not persist the signed sweep transaction");
+        }
+
         tracing::info!("broadcasting bitcoin transaction");
         // Broadcast the transaction to the Bitcoin network.
         let response = self
@@ -1660,6 +2508,14 @@ where
 
         let status = if response.is_ok() {
             tracing::info!("bitcoin transaction accepted by bitcoin-core");
+            let assessed_fee = transaction.tx_fee;
+            let assessed_vsize = u64::from(transaction.tx_vsize);
+            if let Err(error) =
+                fee_audit::audit_sweep_fees(&self.context, &txid, assessed_fee, assessed_vsize)
+                    .await
+            {
+                tracing::warn!(%error, "could not audit the fees of the sweep transaction");
+            }
             "success"
         } else {
             "failure"
@@ -1785,12 +2641,6 @@ where
         S: Stream<Item = Signed<SignerMessage>>,
         Coordinator: WstsCoordinator,
     {
-        // We only allow the coordinator to send us certain kinds of
-        // messages. So later, we need to check whether the sender is the
-        // coordinator, and for that we need to use the "coordinator signer
-        // set", which may differ from the set of signers sending us
-        // messages right now.
-        let signer_set = self.context.coordinator_signer_set();
         tokio::pin!(signal_stream);
 
         // Let's get the next message from the network or the
@@ -1812,8 +2662,13 @@ where
 
             let msg_public_key = msg.signer_public_key;
 
+            // We only allow the coordinator to send us certain kinds of
+            // messages. Whether the sender is the coordinator is decided
+            // using the "coordinator signer set", which may differ from
+            // the set of signers sending us messages right now, or by a
+            // takeover of the tenure by the deputy coordinator.
             let sender_is_coordinator =
-                given_key_is_coordinator(msg_public_key, bitcoin_chain_tip, &signer_set);
+                given_key_is_acting_coordinator(&self.context, msg_public_key, bitcoin_chain_tip);
 
             let public_keys = &coordinator.get_config().signer_public_keys;
             let public_key_point = p256k1::point::Point::from(msg_public_key);
@@ -1920,22 +2775,299 @@ where
     /// specified bitcoin block hash.
     ///
     /// The coordinator is decided using the hash of the bitcoin chain tip
-    /// and signer set info from the registry if present. We don't use the
-    /// chain tip directly because it typically starts with a lot of
-    /// leading zeros.
+    /// and signer set info from the registry if present, less any signers
+    /// deprioritized for being slow. We don't use the chain tip directly
+    /// because it typically starts with a lot of leading zeros.
     pub fn is_coordinator(&self, bitcoin_chain_tip: &model::BitcoinBlockHash) -> bool {
-        let signer_public_keys = self.context.coordinator_signer_set();
+        let signer_public_keys = self.context.coordinator_candidates(bitcoin_chain_tip);
 
         let signer_public_key = self.signer_public_key();
         given_key_is_coordinator(signer_public_key, bitcoin_chain_tip, &signer_public_keys)
     }
 
+    /// Determine if this signer is the signer set's deputy coordinator
+    /// for the specified bitcoin block hash.
+    pub fn is_deputy_coordinator(&self, bitcoin_chain_tip: &model::BitcoinBlockHash) -> bool {
+        let signer_public_keys = self.context.coordinator_candidates(bitcoin_chain_tip);
+
+        deputy_public_key(bitcoin_chain_tip, &signer_public_keys) == Some(self.signer_public_key())
+    }
+
+    /// Start watching the coordinator of the tenure of the given bitcoin
+    /// chain tip for silence, if we are its deputy and takeovers are
+    /// enabled.
+    fn deputy_watch(&self, bitcoin_chain_tip: BitcoinBlockRef) -> Option<DeputyWatch> {
+        self.context.config().signer.coordinator_silence_timeout?;
+        if !self.is_deputy_coordinator(&bitcoin_chain_tip.block_hash) {
+            return None;
+        }
+        // The other signers must understand our silence attestations and
+        // takeover announcement for a takeover to work.
+        let supported = self.context.state().signers_support_protocol_version(
+            &self.context.coordinator_signer_set(),
+            &self.signer_public_key(),
+            crate::COORDINATOR_TAKEOVER_PROTOCOL_VERSION,
+        );
+        if !supported {
+            tracing::debug!("not every signer supports coordinator takeovers; not watching");
+            return None;
+        }
+
+        // The coordinator does not send anything before it has waited
+        // for its bitcoin processing delay, so that is when we start
+        // counting its silence.
+        let bitcoin_processing_delay = self.context.config().signer.bitcoin_processing_delay;
+        Some(DeputyWatch {
+            chain_tip: bitcoin_chain_tip,
+            silent_since: std::time::Instant::now() + bitcoin_processing_delay,
+            quorum_check_at: None,
+        })
+    }
+
+    /// The time at which we will have not heard from the coordinator of
+    /// the watched tenure for the configured silence timeout, if takeovers
+    /// are enabled, or the time of the next check for a quorum of silence
+    /// attestations if that is later.
+    fn coordinator_silence_deadline(&self, watch: &DeputyWatch) -> Option<tokio::time::Instant> {
+        let timeout = self.context.config().signer.coordinator_silence_timeout?;
+        let last_seen = self
+            .context
+            .state()
+            .coordinator_last_seen(&watch.chain_tip.block_hash)
+            .map_or(watch.silent_since, |last_seen| {
+                last_seen.max(watch.silent_since)
+            });
+
+        let deadline = last_seen.checked_add(Duration::from_secs(timeout.get()))?;
+        let deadline = watch
+            .quorum_check_at
+            .map_or(deadline, |at| at.max(deadline));
+        let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+        Some(tokio::time::Instant::now() + remaining)
+    }
+
+    /// Attest that the coordinator of the given bitcoin chain tip went
+    /// silent, unless we already have, and return the attestations of the
+    /// signers once at least a threshold of the signer set agrees.
+    ///
+    /// Every signer decides on its own whether the coordinator went
+    /// silent, so the other signers send their attestations in response
+    /// to ours only if they have not heard from the coordinator either.
+    async fn coordinator_silence_quorum(
+        &mut self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<Option<Vec<message::CoordinatorSilenceAttestation>>, Error> {
+        let signer_public_keys = self.context.coordinator_signer_set();
+        let candidates = self.context.coordinator_candidates(bitcoin_chain_tip);
+        let coordinator = coordinator_public_key(bitcoin_chain_tip, &candidates)
+            .ok_or(Error::NoKeyRotationEvent)?;
+
+        let state = self.context.state();
+        let public_key = self.signer_public_key();
+        let already_attested = state
+            .silence_attestations(bitcoin_chain_tip)
+            .iter()
+            .any(|attestation| attestation.signer_public_key == public_key);
+        if !already_attested {
+            tracing::info!(%coordinator, "the coordinator went silent; attesting to its silence");
+            let attestation = message::CoordinatorSilenceAttestation::new(
+                &self.private_key,
+                bitcoin_chain_tip,
+                coordinator,
+            );
+            state.record_silence_attestation(*bitcoin_chain_tip, attestation);
+            self.send_message(attestation, bitcoin_chain_tip).await?;
+        }
+
+        let attestations = self.context.state().silence_attestations(bitcoin_chain_tip);
+        let attesters = silence_attesters(
+            &attestations,
+            bitcoin_chain_tip,
+            &coordinator,
+            &signer_public_keys,
+        );
+        let signatures_required = usize::from(self.context.coordinator_signatures_required());
+        if attesters.len() < signatures_required {
+            tracing::debug!(
+                num_attesters = attesters.len(),
+                %signatures_required,
+                "not enough signers attested to the silence of the coordinator yet"
+            );
+            return Ok(None);
+        }
+
+        let attestations = attestations
+            .into_iter()
+            .filter(|attestation| attesters.contains(&attestation.signer_public_key))
+            .collect();
+        Ok(Some(attestations))
+    }
+
+    /// Take over the tenure of the given bitcoin chain tip from its
+    /// coordinator, which a threshold of the signers, given by the
+    /// attestations, have not heard from within the configured silence
+    /// timeout.
+    ///
+    /// We only take over the sweeps of the tenure, and only if there is
+    /// something left to do. If the coordinator sent us a pre-sign
+    /// request then we continue with its sweep package, skipping the
+    /// transactions that it has already broadcast, and otherwise we
+    /// construct a package of our own. Either way, the takeover is
+    /// recorded in the database before it is announced, so that we never
+    /// take over the same tenure twice.
+    #[tracing::instrument(skip_all, fields(
+        public_key = %self.signer_public_key(),
+        bitcoin_tip_hash = %bitcoin_chain_tip.block_hash,
+        bitcoin_tip_height = %bitcoin_chain_tip.block_height,
+    ))]
+    pub async fn take_over_tenure(
+        &mut self,
+        bitcoin_chain_tip: BitcoinBlockRef,
+        attestations: Vec<message::CoordinatorSilenceAttestation>,
+    ) -> Result<(), Error> {
+        let state = self.context.state();
+        // If there is a new chain tip then there is a new coordinator, and
+        // the requests will be handled in its tenure.
+        if state.bitcoin_chain_tip() != Some(bitcoin_chain_tip) {
+            tracing::debug!("the bitcoin chain tip has changed; not taking over");
+            return Ok(());
+        }
+        if state.is_in_safe_mode() || state.is_contract_paused() || !state.sbtc_contracts_deployed()
+        {
+            tracing::debug!("not in a position to sweep; not taking over");
+            return Ok(());
+        }
+        let Some(signer_set_info) = state.registry_signer_set_info() else {
+            tracing::debug!("no signer set in the registry; not taking over");
+            return Ok(());
+        };
+        let stacks_chain_tip = state.stacks_chain_tip().ok_or(Error::NoStacksChainTip)?;
+
+        let signer_public_keys = signer_set_info.signer_set;
+        let aggregate_key = signer_set_info.aggregate_key;
+        let candidates = self
+            .context
+            .coordinator_candidates(&bitcoin_chain_tip.block_hash);
+        let coordinator_public_key =
+            coordinator_public_key(&bitcoin_chain_tip.block_hash, &candidates)
+                .ok_or(Error::NoKeyRotationEvent)?;
+
+        let pending_requests_fut = self.get_pending_requests(
+            &bitcoin_chain_tip,
+            &stacks_chain_tip.block_hash,
+            &aggregate_key,
+            &signer_public_keys,
+        );
+        let Some(mut pending_requests) = pending_requests_fut.await? else {
+            tracing::debug!("no requests to handle on bitcoin; not taking over");
+            return Ok(());
+        };
+
+        let sweep_package = self
+            .context
+            .get_storage()
+            .get_sweep_package(&bitcoin_chain_tip.block_hash)
+            .await?;
+
+        let Some(mut package) = sweep_package else {
+            // We accepted no pre-sign request from the coordinator, so it
+            // went silent before sending one and we construct the package
+            // ourselves.
+            if !self
+                .announce_takeover(
+                    &bitcoin_chain_tip.block_hash,
+                    coordinator_public_key,
+                    attestations,
+                )
+                .await?
+            {
+                return Ok(());
+            }
+            return self
+                .construct_and_sign_bitcoin_sbtc_transactions(
+                    &bitcoin_chain_tip,
+                    &aggregate_key,
+                    &signer_public_keys,
+                )
+                .await;
+        };
+
+        let transactions = self
+            .reconstruct_sweep_package(&mut pending_requests, &mut package)
+            .await?;
+        if package.is_complete() {
+            tracing::debug!("the coordinator broadcast its whole sweep package; not taking over");
+            return Ok(());
+        }
+        if !self
+            .announce_takeover(
+                &bitcoin_chain_tip.block_hash,
+                coordinator_public_key,
+                attestations,
+            )
+            .await?
+        {
+            return Ok(());
+        }
+
+        let num_broadcast = package.broadcast_txids.len();
+        tracing::info!(
+            num_broadcast,
+            num_transactions = transactions.len(),
+            "resuming the sweep package of the silent coordinator"
+        );
+        let remaining = transactions.into_iter().skip(num_broadcast).collect();
+        self.sign_and_broadcast_package(&package.bitcoin_chain_tip, remaining)
+            .await
+    }
+
+    /// Record that we are taking over the tenure of the given bitcoin
+    /// chain tip from the given coordinator, and announce it to the other
+    /// signers along with the attestations to the coordinator's silence.
+    /// Returns `false` if we have already taken over the tenure.
+    async fn announce_takeover(
+        &mut self,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        coordinator_public_key: PublicKey,
+        attestations: Vec<message::CoordinatorSilenceAttestation>,
+    ) -> Result<bool, Error> {
+        let deputy_public_key = self.signer_public_key();
+        let takeover = model::CoordinatorTakeover {
+            bitcoin_chain_tip: *bitcoin_chain_tip,
+            coordinator_public_key,
+            deputy_public_key,
+        };
+        let db = self.context.get_storage_mut();
+        if !db
+            .write_coordinator_takeover(&takeover)
+            .await?
+            .is_inserted()
+        {
+            tracing::info!("we have already taken over this tenure");
+            return Ok(false);
+        }
+
+        tracing::warn!(%coordinator_public_key, "the coordinator went silent; taking over its tenure");
+        metrics::counter!(Metrics::CoordinatorTakeoversTotal).increment(1);
+        self.context
+            .state()
+            .set_coordinator_takeover(*bitcoin_chain_tip, deputy_public_key);
+
+        let msg = message::CoordinatorTakeover {
+            coordinator_public_key,
+            attestations,
+        };
+        self.send_message(msg, bitcoin_chain_tip).await?;
+
+        Ok(true)
+    }
+
     /// Constructs a new [`utxo::SignerBtcState`] based on the current market
     /// fee rate, the signer's UTXO, and the last sweep package.
     #[tracing::instrument(skip_all)]
     pub async fn get_btc_state(
         &self,
-        chain_tip: &model::BitcoinBlockHash,
+        chain_tip: &model::BitcoinBlockRef,
         aggregate_key: &PublicKey,
     ) -> Result<utxo::SignerBtcState, Error> {
         let bitcoin_client = self.context.get_bitcoin_client();
@@ -1946,23 +3078,45 @@ where
         let utxo = self
             .context
             .get_storage()
-            .get_signer_utxo(chain_tip)
+            .get_signer_utxo(&chain_tip.block_hash)
             .await?
             .ok_or(Error::MissingSignerUtxo)?;
 
         // We still need to send these to the other signers, since they may
         // not have upgraded their binaries yet.
         let last_fees = assess_mempool_sweep_transaction_fees(&bitcoin_client, &utxo).await?;
+        let signer_set = self.context.state().current_signer_set().get_signers();
+        let signers_upgraded = self.context.state().signers_support_protocol_version(
+            signer_set.iter().map(|signer| signer.public_key()),
+            &self.signer_public_key(),
+            crate::NETWORK_MAGIC_BYTES_PROTOCOL_VERSION,
+        );
+        let magic_bytes = self
+            .context
+            .config()
+            .signer
+            .magic_bytes(chain_tip.block_height, signers_upgraded);
 
         Ok(utxo::SignerBtcState {
             fee_rate,
             utxo,
             public_key: bitcoin::XOnlyPublicKey::from(aggregate_key),
             last_fees,
-            magic_bytes: [b'T', b'3'], //TODO(#472): Use the correct magic bytes.
+            magic_bytes,
+            change_outputs: 1,
         })
     }
 
+    /// Whether every other signer in the given signer set has advertised a
+    /// protocol version that can validate parallel sweep packages.
+    fn signers_support_parallel_sweeps(&self, signer_public_keys: &BTreeSet<PublicKey>) -> bool {
+        self.context.state().signers_support_protocol_version(
+            signer_public_keys,
+            &self.signer_public_key(),
+            crate::PARALLEL_SWEEPS_PROTOCOL_VERSION,
+        )
+    }
+
     /// Fetches pending withdrawal requests from storage and filters them based
     /// on the remaining consensus rules as defined in #741.
     ///
@@ -2041,6 +3195,10 @@ where
         // - [4a] Is accepted by >= `threshold` signers (pre-filter),
         // - [7]  Is not expired; we only retrieve requests whose bitcoin block
         //        height is greater than `min_bitcoin_height`.
+        //
+        // We then make sure that each request ID shows up at most once, so
+        // a withdrawal confirmed in more than one stacks block across forks
+        // cannot be swept twice.
         let pending_withdraw_requests = storage
             .get_pending_accepted_withdrawal_requests(
                 params.bitcoin_chain_tip.as_ref(),
@@ -2048,7 +3206,8 @@ where
                 min_bitcoin_height,
                 params.signature_threshold,
             )
-            .await?;
+            .await
+            .map(util::dedup_withdrawal_requests)?;
 
         // If we didn't find any pending withdrawal requests, we can exit early.
         if pending_withdraw_requests.is_empty() {
@@ -2186,6 +3345,7 @@ where
                 params.bitcoin_chain_tip,
                 context_window,
                 params.signature_threshold,
+                params.deposit_confirmation_policy,
             )
             .await?;
 
@@ -2237,15 +3397,16 @@ where
             aggregate_key,
             signature_threshold,
             sbtc_limits: &sbtc_limits,
+            deposit_confirmation_policy: &config.signer.deposit_confirmation_policy,
         };
 
         // Fetch eligible deposit requests from storage.
-        let deposits =
-            Self::get_eligible_pending_deposit_requests(&storage, self.context_window, &params)
+        let mut deposits =
+            Self::get_eligible_pending_deposit_requests(&storage, self.deposit_lookback(), &params)
                 .await?;
 
         // Fetch eligible withdrawal requests from storage.
-        let withdrawals = Self::get_eligible_pending_withdrawal_requests(
+        let mut withdrawals = Self::get_eligible_pending_withdrawal_requests(
             &storage,
             WITHDRAWAL_BLOCKS_EXPIRY,
             WITHDRAWAL_EXPIRY_BUFFER,
@@ -2261,9 +3422,31 @@ where
         }
 
         // Get the current signers' BTC state.
-        let signer_state = self
-            .get_btc_state(&bitcoin_chain_tip.block_hash, aggregate_key)
-            .await?;
+        let mut signer_state = self.get_btc_state(bitcoin_chain_tip, aggregate_key).await?;
+
+        // We only construct parallel sweeps once all of the other signers
+        // can validate them. They leave alone the requests that are
+        // serviced by the transactions in the mempool that spend the
+        // signers' spare UTXOs, since those are not replaced by the
+        // package.
+        let mut spare_signer_utxos = Vec::new();
+        if self.signers_support_parallel_sweeps(signer_public_keys) {
+            let signer_utxos = storage
+                .get_signer_utxos(&bitcoin_chain_tip.block_hash)
+                .await?;
+            let bitcoin_client = self.context.get_bitcoin_client();
+            let parallel =
+                ParallelSweepUtxos::assess(&bitcoin_client, &signer_state.utxo, &signer_utxos)
+                    .await?;
+
+            deposits.retain(|req| !parallel.in_flight.contains_deposit(&req.outpoint));
+            withdrawals.retain(|req| !parallel.in_flight.contains_withdrawal(req.request_id));
+            if deposits.is_empty() && withdrawals.is_empty() {
+                return Ok(None);
+            }
+            signer_state.change_outputs = parallel.change_outputs;
+            spare_signer_utxos = parallel.spare_utxos;
+        }
 
         // Count the number of signers in the current signer set.
         let num_signers = signer_public_keys
@@ -2271,17 +3454,53 @@ where
             .try_into()
             .map_err(|_| Error::TypeConversion)?;
 
-        let max_deposits_per_bitcoin_tx = config.signer.max_deposits_per_bitcoin_tx.get();
+        // Make sure that we never put more deposits into a transaction
+        // than fit within the bitcoin standardness limits.
+        let capacity =
+            utxo::SweepCapacityPlanner::new(signer_state.fee_rate, &deposits, &withdrawals)
+                .capacity();
+        tracing::debug!(
+            max_deposits = %capacity.max_deposits,
+            max_withdrawals = %capacity.max_withdrawals,
+            planned_deposits = %capacity.planned_deposits,
+            planned_withdrawals = %capacity.planned_withdrawals,
+            planned_fee = %capacity.planned_fee,
+            "computed the capacity of the next sweep transaction"
+        );
+        let max_deposits_per_bitcoin_tx = config
+            .signer
+            .max_deposits_per_bitcoin_tx
+            .get()
+            .min(u16::try_from(capacity.max_deposits).unwrap_or(u16::MAX));
+
+        // The withdrawals in the packages that the signers already agreed
+        // to sign for this chain tip count towards the per-block value
+        // cap. A withdrawal that we service again would be counted only
+        // once by the signers, so subtracting all of them errs on the
+        // side of a package that the signers accept.
+        let presigned_withdrawal_value = storage
+            .get_presigned_withdrawals(&bitcoin_chain_tip.block_hash)
+            .await?
+            .iter()
+            .fold(0u64, |total, req| total.saturating_add(req.amount));
+        let max_withdrawal_value = config
+            .signer
+            .max_withdrawal_value_per_bitcoin_block()
+            .saturating_sub(presigned_withdrawal_value);
 
         // Construct and return the `utxo::SbtcRequests` object.
         Ok(Some(utxo::SbtcRequests {
             deposits,
             withdrawals,
             signer_state,
+            spare_signer_utxos,
             accept_threshold: signature_threshold,
             num_signers,
             sbtc_limits,
             max_deposits_per_bitcoin_tx,
+            max_withdrawals_per_bitcoin_tx: config.signer.max_withdrawals_per_bitcoin_tx(),
+            max_withdrawal_value,
+            prioritization: config.signer.request_prioritization(),
         }))
     }
 
@@ -2295,11 +3514,32 @@ where
         &mut self,
         msg: impl Into<Payload>,
         bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<(), Error> {
+        let msg = msg.into().to_message(*bitcoin_chain_tip);
+        self.broadcast_message(msg).await
+    }
+
+    /// Like [`Self::send_message`], but tells the other signers that we
+    /// stop waiting for their responses after `max_duration`, so that
+    /// they do not keep working on the message after we have given up.
+    #[tracing::instrument(skip_all)]
+    async fn send_message_with_deadline(
+        &mut self,
+        msg: impl Into<Payload>,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        max_duration: Duration,
     ) -> Result<(), Error> {
         let msg = msg
             .into()
             .to_message(*bitcoin_chain_tip)
-            .sign_ecdsa(&self.private_key);
+            .with_deadline(Deadline::after(max_duration));
+        self.broadcast_message(msg).await
+    }
+
+    /// Sign the message with the signer's private key and broadcast it to
+    /// the network.
+    async fn broadcast_message(&mut self, msg: SignerMessage) -> Result<(), Error> {
+        let msg = msg.sign_ecdsa(&self.private_key);
 
         self.network.broadcast(msg.clone()).await?;
         self.context
@@ -2436,7 +3676,7 @@ where
     /// - NaN fee rates returned by bitcoin-core are set to 1.0.
     #[tracing::instrument(skip_all, fields(%num_blocks))]
     async fn estimate_bitcoin_tx_fee(&self, num_blocks: u16) -> Result<f64, Error> {
-        let mut fee_rate = self
+        let fee_rate = self
             .context
             .get_bitcoin_client()
             .estimate_fee_rate(num_blocks)
@@ -2446,15 +3686,13 @@ where
             // This really shouldn't happen, but if it does there is
             // probably a bug in bitcoin-core so we want to know about it.
             tracing::error!(%fee_rate, "bitcoin-core returned a NaN fee rate, using 1");
-            fee_rate = 1.0;
-        }
-
-        if !BITCOIN_FEE_RATE_RANGE.contains(&fee_rate) {
+        } else if package_rules::check_fee_rate(fee_rate).is_err() {
             tracing::warn!(%fee_rate, "invalid fee rate, clamping it");
-            fee_rate = fee_rate.clamp(MIN_BITCOIN_FEE_RATE, MAX_BITCOIN_FEE_RATE);
         }
 
-        Ok(fee_rate)
+        // The signers reject pre-sign requests with fee rates that fail
+        // the same check, so we always hand them a sanitized one.
+        Ok(package_rules::sanitize_fee_rate(fee_rate))
     }
 
     /// Estimate transaction fees for a Stacks contract call. This function
@@ -2494,6 +3732,61 @@ pub fn given_key_is_coordinator(
     coordinator_public_key(bitcoin_chain_tip, signer_public_keys) == Some(pub_key)
 }
 
+/// Check if the provided public key is acting as the coordinator for the
+/// provided chain tip. This is the coordinator, unless its deputy has
+/// taken over the tenure of the chain tip.
+pub fn given_key_is_acting_coordinator(
+    context: &impl Context,
+    pub_key: PublicKey,
+    bitcoin_chain_tip: &model::BitcoinBlockHash,
+) -> bool {
+    match context.state().coordinator_takeover(bitcoin_chain_tip) {
+        Some(deputy_public_key) => deputy_public_key == pub_key,
+        None => {
+            let signer_public_keys = context.coordinator_candidates(bitcoin_chain_tip);
+            given_key_is_coordinator(pub_key, bitcoin_chain_tip, &signer_public_keys)
+        }
+    }
+}
+
+/// Find the deputy coordinator public key, which is the key that follows
+/// the coordinator public key in the signer set, wrapping around at the
+/// end. There is no deputy if there is only one signer.
+pub fn deputy_public_key(
+    bitcoin_chain_tip: &model::BitcoinBlockHash,
+    signer_public_keys: &BTreeSet<PublicKey>,
+) -> Option<PublicKey> {
+    if signer_public_keys.len() < 2 {
+        return None;
+    }
+    let coordinator = coordinator_public_key(bitcoin_chain_tip, signer_public_keys)?;
+
+    signer_public_keys
+        .range(coordinator..)
+        .nth(1)
+        .or_else(|| signer_public_keys.first())
+        .copied()
+}
+
+/// Return the signers in the given signer set with a valid attestation,
+/// among the given ones, that the given coordinator of the bitcoin chain
+/// tip went silent. The coordinator's own attestation does not count.
+pub fn silence_attesters(
+    attestations: &[message::CoordinatorSilenceAttestation],
+    bitcoin_chain_tip: &model::BitcoinBlockHash,
+    coordinator_public_key: &PublicKey,
+    signer_public_keys: &BTreeSet<PublicKey>,
+) -> BTreeSet<PublicKey> {
+    attestations
+        .iter()
+        .filter(|attestation| attestation.coordinator_public_key == *coordinator_public_key)
+        .filter(|attestation| attestation.signer_public_key != *coordinator_public_key)
+        .filter(|attestation| signer_public_keys.contains(&attestation.signer_public_key))
+        .filter(|attestation| attestation.verify(bitcoin_chain_tip).is_ok())
+        .map(|attestation| attestation.signer_public_key)
+        .collect()
+}
+
 /// Find the coordinator public key
 pub fn coordinator_public_key(
     bitcoin_chain_tip: &model::BitcoinBlockHash,
@@ -2640,6 +3933,177 @@ pub fn adjust_nonce(wallet: &SignerWallet, error: &Error) {
     }
 }
 
+/// A signer's verdict in a rotate-keys dry run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "verdict", rename_all = "snake_case")]
+pub enum DryRunVerdict {
+    /// The contract call passed the signer's validation.
+    Accepted,
+    /// The contract call failed the signer's validation.
+    Rejected {
+        /// Why the signer would refuse to sign the contract call.
+        reason: String,
+    },
+    /// The signer did not respond before we stopped waiting.
+    NoResponse,
+}
+
+/// The verdict of one signer in a rotate-keys dry run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignerDryRunVerdict {
+    /// The public key of the signer.
+    pub signer_public_key: PublicKey,
+    /// The verdict of the signer.
+    #[serde(flatten)]
+    pub verdict: DryRunVerdict,
+}
+
+/// The outcome of a rotate-keys dry run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RotateKeysDryRunReport {
+    /// The aggregate key that the contract call would rotate into.
+    pub aggregate_key: PublicKey,
+    /// The signer set that the contract call would rotate into.
+    pub new_keys: BTreeSet<PublicKey>,
+    /// The number of signatures that the new signer set would require.
+    pub new_signatures_required: u16,
+    /// The number of signatures from the current signer set that the
+    /// rotate-keys transaction needs.
+    pub signatures_required: u16,
+    /// Whether enough signers of the current signer set accepted the
+    /// contract call for the rotate-keys transaction to be signed.
+    pub would_pass: bool,
+    /// The verdict of each signer in the current signer set.
+    pub verdicts: Vec<SignerDryRunVerdict>,
+}
+
+/// Validate the rotate-keys contract call for the latest DKG shares on
+/// all signers, without signing or broadcasting anything.
+///
+/// The contract call is assembled in the same way that the coordinator
+/// assembles it when rotating keys, and is sent to the signers in a
+/// [`message::RotateKeysDryRunRequest`]. We then collect their verdicts
+/// until every signer in the current signer set has responded or
+/// `signer.signer_round_max_duration` passes, whichever comes first.
+#[tracing::instrument(skip_all)]
+pub async fn rotate_keys_dry_run<C>(ctx: &C) -> Result<RotateKeysDryRunReport, Error>
+where
+    C: Context,
+{
+    let last_dkg = ctx
+        .get_storage()
+        .get_latest_encrypted_dkg_shares()
+        .await?
+        .ok_or(Error::NoDkgShares)?;
+    let rotate_keys = RotateKeysV1::load(ctx, &last_dkg.aggregate_key).await?;
+    let chain_tip = ctx.state().bitcoin_chain_tip().ok_or(Error::NoChainTip)?;
+
+    // The rotate-keys transaction is signed by the wallet of the current
+    // signer set, so those are the signers whose verdicts matter.
+    let wallet = SignerWallet::load(ctx).await?;
+    let signer_set = wallet.public_keys();
+
+    let config = &ctx.config().signer;
+    let public_key = PublicKey::from_private_key(&config.private_key);
+    let version = crate::ROTATE_KEYS_DRY_RUN_PROTOCOL_VERSION;
+    if !ctx
+        .state()
+        .signers_support_protocol_version(signer_set, &public_key, version)
+    {
+        return Err(Error::UnsupportedProtocolVersion(version));
+    }
+    let max_duration = config.signer_round_max_duration;
+    let request = message::RotateKeysDryRunRequest {
+        id: OsRng.next_u64(),
+        rotate_keys: rotate_keys.clone(),
+    };
+    let request_id = request.id;
+    let msg = Payload::from(request)
+        .to_message(chain_tip.block_hash)
+        .with_deadline(Deadline::after(max_duration))
+        .sign_ecdsa(&config.private_key);
+
+    let verdict_filter = |signal: &SignerSignal| {
+        matches!(
+            signal,
+            SignerSignal::Event(SignerEvent::TxSigner(TxSignerEvent::MessageGenerated(_)))
+                | SignerSignal::Event(SignerEvent::P2P(P2PEvent::MessageReceived(_)))
+                | SignerSignal::Command(SignerCommand::Shutdown)
+        )
+    };
+    let signal_stream = ctx.as_signal_stream(verdict_filter);
+
+    tracing::info!(%request_id, "sending rotate-keys dry run request");
+    // Our own transaction signer only hears about messages that our
+    // coordinator generates, so we tell it about the request as well.
+    ctx.signal(SignerCommand::P2PPublish(Box::new(msg.clone())).into())?;
+    ctx.signal(TxCoordinatorEvent::MessageGenerated(Box::new(msg)).into())?;
+
+    let mut responses: HashMap<PublicKey, Option<String>> = HashMap::new();
+    let future = async {
+        tokio::pin!(signal_stream);
+        while responses.len() < signer_set.len() {
+            let msg = match signal_stream.next().await {
+                None | Some(SignerSignal::Command(SignerCommand::Shutdown)) => {
+                    return Err(Error::SignerShutdown);
+                }
+                Some(SignerSignal::Event(SignerEvent::TxSigner(
+                    TxSignerEvent::MessageGenerated(msg),
+                )))
+                | Some(SignerSignal::Event(SignerEvent::P2P(P2PEvent::MessageReceived(msg)))) => {
+                    msg
+                }
+                Some(_) => continue,
+            };
+
+            let Payload::RotateKeysDryRunVerdict(verdict) = &msg.payload else {
+                continue;
+            };
+            if verdict.id == request_id && signer_set.contains(&msg.signer_public_key) {
+                responses.insert(msg.signer_public_key, verdict.rejection_reason.clone());
+            }
+        }
+        Ok(())
+    };
+
+    // Signers that do not respond in time are reported as such, so a
+    // timeout is not an error here.
+    if let Ok(result) = tokio::time::timeout(max_duration, future).await {
+        result?;
+    }
+
+    let verdicts: Vec<SignerDryRunVerdict> = signer_set
+        .iter()
+        .map(|signer_public_key| {
+            let verdict = match responses.remove(signer_public_key) {
+                Some(None) => DryRunVerdict::Accepted,
+                Some(Some(reason)) => DryRunVerdict::Rejected { reason },
+                None => DryRunVerdict::NoResponse,
+            };
+            SignerDryRunVerdict {
+                signer_public_key: *signer_public_key,
+                verdict,
+            }
+        })
+        .collect();
+
+    let accepted = verdicts
+        .iter()
+        .filter(|entry| entry.verdict == DryRunVerdict::Accepted)
+        .count();
+    let signatures_required = wallet.signatures_required();
+    tracing::info!(%request_id, %accepted, %signatures_required, "rotate-keys dry run finished");
+
+    Ok(RotateKeysDryRunReport {
+        aggregate_key: rotate_keys.aggregate_key,
+        new_keys: rotate_keys.new_keys,
+        new_signatures_required: rotate_keys.signatures_required,
+        signatures_required,
+        would_pass: accepted >= usize::from(signatures_required),
+        verdicts,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use crate::bitcoin::MockBitcoinInteract;
@@ -2906,11 +4370,243 @@ mod tests {
         }
     }
 
+    #[test_case(1; "one signer")]
+    #[test_case(2; "two signers")]
+    #[test_case(7; "seven signers")]
+    fn deputy_coordinator_is_the_signer_after_the_coordinator(num_signers: usize) {
+        let mut rng = testing::get_rng();
+        let signer_set: BTreeSet<PublicKey> =
+            std::iter::repeat_with(|| Faker.fake_with_rng(&mut rng))
+                .take(num_signers)
+                .collect();
+
+        for _ in 0..100 {
+            let chain_tip: model::BitcoinBlockHash = Faker.fake_with_rng(&mut rng);
+            let coordinator = coordinator_public_key(&chain_tip, &signer_set).unwrap();
+            let deputy = deputy_public_key(&chain_tip, &signer_set);
+
+            if num_signers == 1 {
+                assert!(deputy.is_none());
+                continue;
+            }
+
+            // The deputy follows the coordinator in the signer set,
+            // wrapping around at the end.
+            let keys: Vec<PublicKey> = signer_set.iter().copied().collect();
+            let index = keys.iter().position(|key| *key == coordinator).unwrap();
+            assert_eq!(deputy, Some(keys[(index + 1) % num_signers]));
+            assert_ne!(deputy, Some(coordinator));
+        }
+    }
+
+    #[tokio::test]
+    async fn deputy_coordinator_acts_as_coordinator_after_a_takeover() {
+        let mut rng = testing::get_rng();
+        let ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .build();
+
+        let signer_set: BTreeSet<PublicKey> =
+            std::iter::repeat_with(|| Faker.fake_with_rng(&mut rng))
+                .take(5)
+                .collect();
+        ctx.state()
+            .update_registry_signer_set_info(crate::stacks::api::SignerSetInfo {
+                aggregate_key: Faker.fake_with_rng(&mut rng),
+                signer_set: signer_set.clone(),
+                signatures_required: 3,
+            });
+
+        let chain_tip: model::BitcoinBlockHash = Faker.fake_with_rng(&mut rng);
+        let other_chain_tip: model::BitcoinBlockHash = Faker.fake_with_rng(&mut rng);
+        let coordinator = coordinator_public_key(&chain_tip, &signer_set).unwrap();
+        let deputy = deputy_public_key(&chain_tip, &signer_set).unwrap();
+
+        assert!(given_key_is_acting_coordinator(
+            &ctx,
+            coordinator,
+            &chain_tip
+        ));
+        assert!(!given_key_is_acting_coordinator(&ctx, deputy, &chain_tip));
+
+        ctx.state().set_coordinator_takeover(chain_tip, deputy);
+
+        assert!(!given_key_is_acting_coordinator(
+            &ctx,
+            coordinator,
+            &chain_tip
+        ));
+        assert!(given_key_is_acting_coordinator(&ctx, deputy, &chain_tip));
+
+        // The takeover only applies to the tenure that was taken over.
+        let other_coordinator = coordinator_public_key(&other_chain_tip, &signer_set).unwrap();
+        assert!(given_key_is_acting_coordinator(
+            &ctx,
+            other_coordinator,
+            &other_chain_tip
+        ));
+    }
+
+    #[tokio::test]
+    async fn rotate_keys_dry_run_reports_the_verdict_of_each_signer() {
+        let mut rng = get_rng();
+        let ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .modify_settings(|settings| {
+                settings.signer.signer_round_max_duration = Duration::from_millis(500);
+            })
+            .build();
+
+        let private_keys: Vec<PrivateKey> = std::iter::repeat_with(|| PrivateKey::new(&mut rng))
+            .take(3)
+            .collect();
+        let signer_set: BTreeSet<PublicKey> = private_keys
+            .iter()
+            .map(PublicKey::from_private_key)
+            .collect();
+        ctx.state()
+            .update_registry_signer_set_info(crate::stacks::api::SignerSetInfo {
+                aggregate_key: Faker.fake_with_rng(&mut rng),
+                signer_set: signer_set.clone(),
+                signatures_required: 2,
+            });
+        ctx.state().set_bitcoin_chain_tip(model::BitcoinBlockRef {
+            block_hash: Faker.fake_with_rng(&mut rng),
+            block_height: 100u64.into(),
+        });
+        for public_key in &signer_set {
+            ctx.state()
+                .record_peer_protocol_version((*public_key).into(), crate::PROTOCOL_VERSION);
+        }
+
+        let shares: model::EncryptedDkgShares = Faker.fake_with_rng(&mut rng);
+        ctx.get_storage_mut()
+            .write_encrypted_dkg_shares(&shares)
+            .await
+            .unwrap();
+
+        // The first signer accepts the contract call and the second
+        // rejects it. The third only responds to some other dry run, so
+        // it is reported as not having responded.
+        let mut signal_rx = ctx.get_signal_receiver();
+        let responder_ctx = ctx.clone();
+        let responder = tokio::spawn(async move {
+            let (request, chain_tip) = loop {
+                let signal = signal_rx.recv().await.unwrap();
+                if let SignerSignal::Event(SignerEvent::TxCoordinator(
+                    TxCoordinatorEvent::MessageGenerated(msg),
+                )) = signal
+                    && let Payload::RotateKeysDryRunRequest(request) = &msg.payload
+                {
+                    break (request.clone(), msg.bitcoin_chain_tip);
+                }
+            };
+
+            let verdicts = [
+                (request.id, None),
+                (
+                    request.id,
+                    Some("the aggregate key does not match".to_string()),
+                ),
+                (request.id.wrapping_add(1), None),
+            ];
+            for (private_key, (id, rejection_reason)) in private_keys.iter().zip(verdicts) {
+                let verdict = message::RotateKeysDryRunVerdict { id, rejection_reason };
+                let msg = Payload::from(verdict)
+                    .to_message(chain_tip)
+                    .sign_ecdsa(private_key);
+                responder_ctx
+                    .signal(P2PEvent::MessageReceived(Box::new(msg)).into())
+                    .unwrap();
+            }
+            private_keys
+        });
+
+        let report = rotate_keys_dry_run(&ctx).await.unwrap();
+        let private_keys = responder.await.unwrap();
+
+        assert_eq!(report.aggregate_key, shares.aggregate_key);
+        assert_eq!(report.new_keys, shares.signer_set_public_keys());
+        assert_eq!(report.signatures_required, 2);
+        // Only one of the signers accepted, but two signatures are needed.
+        assert!(!report.would_pass);
+
+        let verdict_of = |private_key: &PrivateKey| {
+            let public_key = PublicKey::from_private_key(private_key);
+            report
+                .verdicts
+                .iter()
+                .find(|entry| entry.signer_public_key == public_key)
+                .map(|entry| entry.verdict.clone())
+                .unwrap()
+        };
+        assert_eq!(report.verdicts.len(), 3);
+        assert_eq!(verdict_of(&private_keys[0]), DryRunVerdict::Accepted);
+        assert_eq!(
+            verdict_of(&private_keys[1]),
+            DryRunVerdict::Rejected {
+                reason: "the aggregate key does not match".to_string()
+            }
+        );
+        assert_eq!(verdict_of(&private_keys[2]), DryRunVerdict::NoResponse);
+    }
+
+    #[tokio::test]
+    async fn rotate_keys_dry_run_is_refused_until_every_signer_upgrades() {
+        let mut rng = get_rng();
+        let ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .build();
+
+        let signer_set: BTreeSet<PublicKey> =
+            std::iter::repeat_with(|| Faker.fake_with_rng(&mut rng))
+                .take(3)
+                .collect();
+        ctx.state()
+            .update_registry_signer_set_info(crate::stacks::api::SignerSetInfo {
+                aggregate_key: Faker.fake_with_rng(&mut rng),
+                signer_set: signer_set.clone(),
+                signatures_required: 2,
+            });
+        ctx.state().set_bitcoin_chain_tip(model::BitcoinBlockRef {
+            block_hash: Faker.fake_with_rng(&mut rng),
+            block_height: 100u64.into(),
+        });
+
+        let shares: model::EncryptedDkgShares = Faker.fake_with_rng(&mut rng);
+        ctx.get_storage_mut()
+            .write_encrypted_dkg_shares(&shares)
+            .await
+            .unwrap();
+
+        // One of the signers still runs the protocol version before dry
+        // runs were introduced.
+        let version = crate::ROTATE_KEYS_DRY_RUN_PROTOCOL_VERSION;
+        for (index, public_key) in signer_set.iter().enumerate() {
+            let advertised = if index == 0 { version - 1 } else { version };
+            ctx.state()
+                .record_peer_protocol_version((*public_key).into(), advertised);
+        }
+
+        let error = rotate_keys_dry_run(&ctx).await.unwrap_err();
+        assert!(matches!(error, Error::UnsupportedProtocolVersion(v) if v == version));
+    }
+
     #[tokio::test]
     async fn should_get_signer_utxo_simple() {
         test_environment().assert_get_signer_utxo_simple().await;
     }
 
+    #[tokio::test]
+    async fn should_get_signer_utxos_split_change() {
+        test_environment()
+            .assert_get_signer_utxos_split_change()
+            .await;
+    }
+
     #[tokio::test]
     async fn should_get_signer_utxo_fork() {
         test_environment().assert_get_signer_utxo_fork().await;
@@ -3252,4 +4948,229 @@ mod tests {
             }
         }
     }
+
+    /// Check that persisted signed sweeps whose signers' input is not an
+    /// unspent signers' UTXO are deleted rather than broadcast.
+    #[tokio::test]
+    async fn rebroadcast_signed_sweeps_deletes_stale_sweeps() {
+        let mut rng = testing::get_rng();
+        // The mocked bitcoin client has no expectations, so the test
+        // fails if we try to broadcast the stale sweep.
+        let ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .build();
+        let storage = ctx.get_storage_mut();
+
+        let chain_tip: model::BitcoinBlock = Faker.fake_with_rng(&mut rng);
+        storage.write_bitcoin_block(&chain_tip).await.unwrap();
+
+        let tx = bitcoin::Transaction {
+            version: bitcoin::transaction::Version::TWO,
+            lock_time: bitcoin::absolute::LockTime::ZERO,
+            input: vec![bitcoin::TxIn {
+                previous_output: bitcoin::OutPoint::new(Faker.fake_with_rng(&mut rng), 0),
+                ..Default::default()
+            }],
+            output: Vec::new(),
+        };
+        let sweep = model::SignedSweepTransaction {
+            txid: tx.compute_txid().into(),
+            tx,
+            bitcoin_chain_tip: chain_tip.block_hash,
+            created_at: time::OffsetDateTime::now_utc().into(),
+        };
+        storage
+            .write_signed_sweep_transaction(&sweep)
+            .await
+            .unwrap();
+
+        let network = WanNetwork::default();
+        let ev = TxCoordinatorEventLoop {
+            network: network.connect(&ctx).spawn(),
+            context: ctx.clone(),
+            context_window: 10000,
+            private_key: PrivateKey::new(&mut rng),
+            signing_round_max_duration: Duration::from_secs(10),
+            bitcoin_presign_request_max_duration: Duration::from_secs(10),
+            dkg_max_duration: Duration::from_secs(10),
+            is_epoch3: true,
+        };
+
+        ev.rebroadcast_signed_sweeps().await.unwrap();
+
+        let sweeps = storage.get_signed_sweep_transactions().await.unwrap();
+        assert!(sweeps.is_empty());
+    }
+
+    /// Check that only the latest of the persisted signed sweeps that
+    /// spend the same signers' UTXO is broadcast, and that the sweeps that
+    /// it replaced are deleted.
+    #[tokio::test]
+    async fn rebroadcast_signed_sweeps_only_broadcasts_the_replacement() {
+        use crate::keys::SignerScriptPubKey as _;
+
+        let mut rng = testing::get_rng();
+        let ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .build();
+        let storage = ctx.get_storage_mut();
+
+        let chain_tip: model::BitcoinBlock = Faker.fake_with_rng(&mut rng);
+        storage.write_bitcoin_block(&chain_tip).await.unwrap();
+
+        // Give the signers a UTXO in the chain tip block.
+        let shares: model::EncryptedDkgShares = Faker.fake_with_rng(&mut rng);
+        storage.write_encrypted_dkg_shares(&shares).await.unwrap();
+        let funding_tx = model::BitcoinTxRef {
+            txid: Faker.fake_with_rng(&mut rng),
+            block_hash: chain_tip.block_hash,
+        };
+        storage
+            .write_bitcoin_transaction(&funding_tx)
+            .await
+            .unwrap();
+        let signer_output = model::TxOutput {
+            txid: funding_tx.txid,
+            output_index: 0,
+            script_pubkey: shares.aggregate_key.signers_script_pubkey().into(),
+            amount: 1_000_000,
+            output_type: model::TxOutputType::SignersOutput,
+        };
+        storage.write_tx_output(&signer_output).await.unwrap();
+
+        // The replacement is signed after the sweep that it replaces.
+        let now = time::OffsetDateTime::now_utc();
+        let sweeps: Vec<_> = [(1, now - Duration::from_secs(60)), (2, now)]
+            .into_iter()
+            .map(|(lock_time, created_at)| {
+                let tx = bitcoin::Transaction {
+                    version: bitcoin::transaction::Version::TWO,
+                    lock_time: bitcoin::absolute::LockTime::from_consensus(lock_time),
+                    input: vec![bitcoin::TxIn {
+                        previous_output: bitcoin::OutPoint::new(funding_tx.txid.into(), 0),
+                        ..Default::default()
+                    }],
+                    output: Vec::new(),
+                };
+                model::SignedSweepTransaction {
+                    txid: tx.compute_txid().into(),
+                    tx,
+                    bitcoin_chain_tip: chain_tip.block_hash,
+                    created_at: created_at.into(),
+                }
+            })
+            .collect();
+        for sweep in sweeps.iter() {
+            storage.write_signed_sweep_transaction(sweep).await.unwrap();
+        }
+
+        let replacement = sweeps[1].tx.clone();
+        ctx.with_bitcoin_client(|client| {
+            client
+                .expect_broadcast_transaction()
+                .withf(move |tx| *tx == replacement)
+                .times(1)
+                .returning(|_| Box::pin(async { Ok(()) }));
+        })
+        .await;
+
+        let network = WanNetwork::default();
+        let ev = TxCoordinatorEventLoop {
+            network: network.connect(&ctx).spawn(),
+            context: ctx.clone(),
+            context_window: 10000,
+            private_key: PrivateKey::new(&mut rng),
+            signing_round_max_duration: Duration::from_secs(10),
+            bitcoin_presign_request_max_duration: Duration::from_secs(10),
+            dkg_max_duration: Duration::from_secs(10),
+            is_epoch3: true,
+        };
+
+        ev.rebroadcast_signed_sweeps().await.unwrap();
+
+        let remaining = storage.get_signed_sweep_transactions().await.unwrap();
+        assert_eq!(remaining, [sweeps[1].clone()]);
+    }
+
+    /// Check that a contract call submitted while the chain tip was on a
+    /// bitcoin fork that has since been abandoned is cleared rather than
+    /// treated as in flight, while one submitted on the canonical chain
+    /// is still in flight.
+    #[tokio::test]
+    async fn pending_contract_call_clears_contract_calls_on_abandoned_forks() {
+        let mut rng = testing::get_rng();
+        let ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .build();
+        let storage = ctx.get_storage_mut();
+
+        // The chain tip and an abandoned fork block at the same height,
+        // both children of the same parent.
+        let parent: model::BitcoinBlock = Faker.fake_with_rng(&mut rng);
+        let [chain_tip, fork]: [model::BitcoinBlock; 2] =
+            std::array::from_fn(|_| model::BitcoinBlock {
+                block_hash: Faker.fake_with_rng(&mut rng),
+                block_height: parent.block_height + 1,
+                parent_hash: parent.block_hash,
+            });
+        for block in [&parent, &chain_tip, &fork] {
+            storage.write_bitcoin_block(block).await.unwrap();
+        }
+
+        let mut contract_calls = Vec::new();
+        for block in [&parent, &fork] {
+            let id: model::QualifiedRequestId = Faker.fake_with_rng(&mut rng);
+            let contract_call = model::InflightContractCall {
+                target: model::ContractCallTarget::Withdrawal { id },
+                txid: Faker.fake_with_rng(&mut rng),
+                bitcoin_block_height: block.block_height,
+                bitcoin_block_hash: block.block_hash,
+            };
+            storage
+                .write_inflight_contract_call(&contract_call)
+                .await
+                .unwrap();
+            contract_calls.push(contract_call);
+        }
+        let [canonical, abandoned] = contract_calls.try_into().unwrap();
+
+        let network = WanNetwork::default();
+        let ev = TxCoordinatorEventLoop {
+            network: network.connect(&ctx).spawn(),
+            context: ctx.clone(),
+            context_window: 10000,
+            private_key: PrivateKey::new(&mut rng),
+            signing_round_max_duration: Duration::from_secs(10),
+            bitcoin_presign_request_max_duration: Duration::from_secs(10),
+            dkg_max_duration: Duration::from_secs(10),
+            is_epoch3: true,
+        };
+
+        let chain_tip = model::BitcoinBlockRef::from(&chain_tip);
+        let pending = ev
+            .pending_contract_call(&canonical.target, &chain_tip)
+            .await
+            .unwrap();
+        assert_eq!(pending, Some(canonical.clone()));
+
+        let pending = ev
+            .pending_contract_call(&abandoned.target, &chain_tip)
+            .await
+            .unwrap();
+        assert_eq!(pending, None);
+
+        let stored = storage
+            .get_inflight_contract_call(&abandoned.target)
+            .await
+            .unwrap();
+        assert_eq!(stored, None);
+        let stored = storage
+            .get_inflight_contract_call(&canonical.target)
+            .await
+            .unwrap();
+        assert_eq!(stored, Some(canonical));
+    }
 }
@@ -17,17 +17,23 @@ use crate::context::P2PEvent;
 use crate::context::SignerCommand;
 use crate::context::SignerEvent;
 use crate::context::SignerSignal;
+use crate::context::Subsystem;
 use crate::context::TxCoordinatorEvent;
 use crate::context::TxSignerEvent;
 use crate::dkg;
 use crate::ecdsa::SignEcdsa as _;
 use crate::error::Error;
+use crate::invariants;
 use crate::keys::PrivateKey;
 use crate::keys::PublicKey;
 use crate::keys::PublicKeyXOnly;
 use crate::message;
 use crate::message::BitcoinPreSignAck;
+use crate::message::Deadline;
+use crate::message::KeyRotationAttestation;
 use crate::message::Payload;
+use crate::message::RotateKeysDryRunRequest;
+use crate::message::RotateKeysDryRunVerdict;
 use crate::message::StacksTransactionSignRequest;
 use crate::message::WstsMessageId;
 use crate::metrics::Metrics;
@@ -42,11 +48,16 @@ use crate::stacks::wallet::MultisigTx;
 use crate::stacks::wallet::SignerWallet;
 use crate::storage::DbRead;
 use crate::storage::DbWrite as _;
+use crate::storage::Transactable as _;
+use crate::storage::TransactionHandle as _;
 use crate::storage::model;
 use crate::storage::model::BitcoinBlockHash;
 use crate::storage::model::DkgSharesStatus;
+use crate::storage::model::DkgVerificationRoundStatus;
 use crate::storage::model::SigHash;
 use crate::storage::model::StacksTxId;
+use crate::storage::util::retry_transient;
+use crate::telemetry;
 use crate::transaction_coordinator::should_run_dkg;
 use crate::wsts_state_machine::FrostCoordinator;
 use crate::wsts_state_machine::SignerStateMachine;
@@ -57,6 +68,7 @@ use bitcoin::TapSighash;
 use bitcoin::hashes::Hash as _;
 use futures::StreamExt as _;
 use lru::LruCache;
+use tracing::Instrument as _;
 use wsts::net::DkgEnd;
 use wsts::net::DkgStatus;
 use wsts::net::Message as WstsNetMessage;
@@ -65,6 +77,20 @@ use wsts::net::Message as WstsNetMessage;
 /// bitcoin tenures for which we keep track of the signed stacks transactions.
 pub const STACKS_SIGN_REQUEST_LRU_SIZE: NonZeroUsize = NonZeroUsize::new(2).expect("2 is non zero");
 
+/// LRU cache max size for the validated stacks contract calls. This is the
+/// number of (bitcoin, stacks) chain tip pairs for which we keep track of
+/// contract calls that passed validation.
+pub const STACKS_VALIDATION_LRU_SIZE: NonZeroUsize = NonZeroUsize::new(2).expect("2 is non zero");
+
+/// The name of the stream of random number generators, handed out by the
+/// context, that the transaction signer uses for its WSTS state machines.
+pub const TX_SIGNER_RNG_STREAM: &str = "tx-signer";
+
+/// How much earlier than the deputy coordinator we may have last heard
+/// from the coordinator and still attest to its silence, since the
+/// coordinator's messages do not reach all signers at the same time.
+pub const COORDINATOR_SILENCE_TOLERANCE: Duration = Duration::from_secs(5);
+
 #[cfg_attr(doc, aquamarine::aquamarine)]
 /// # Transaction signer event loop
 ///
@@ -155,6 +181,12 @@ pub struct TxSignerEventLoop<Context, Network> {
     /// Stacks transactions signed during a bitcoin tenure. We don't allow
     /// signing for the same request multiple times in a tenure.
     pub stacks_sign_request: LruCache<model::BitcoinBlockHash, HashSet<StacksSignRequestId>>,
+    /// Contract calls that passed validation, keyed by the bitcoin and
+    /// stacks chain tips that they were validated against. Only successful
+    /// validations are cached, and the cache is cleared whenever we
+    /// observe a new bitcoin block.
+    pub stacks_validations:
+        LruCache<(model::BitcoinBlockHash, model::StacksBlockHash), HashSet<ContractCall>>,
 }
 
 /// This struct represents a signature hash and the public key that locks
@@ -231,8 +263,10 @@ fn run_loop_message_filter(signal: &SignerSignal) -> bool {
                 | message::Payload::SignerWithdrawalDecision(_)
                 | message::Payload::StacksTransactionSignature(_)
                 | message::Payload::BitcoinPreSignAck(_)
+                | message::Payload::RotateKeysDryRunVerdict(_)
         ),
         SignerSignal::Command(SignerCommand::Shutdown)
+        | SignerSignal::Event(SignerEvent::BitcoinBlockObserved(_))
         | SignerSignal::Event(SignerEvent::TxCoordinator(TxCoordinatorEvent::MessageGenerated(
             _,
         ))) => true,
@@ -272,6 +306,7 @@ where
                 NonZeroUsize::new(5).ok_or(Error::TypeConversion)?,
             ),
             stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+            stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
         })
     }
 
@@ -288,15 +323,28 @@ where
         };
         let mut signal_stream = self.context.as_signal_stream(run_loop_message_filter);
 
+        if let Err(error) = self.recover_dkg_verification_rounds().await {
+            tracing::warn!(%error, "could not recover DKG verification rounds");
+        }
+
         while let Some(message) = signal_stream.next().await {
+            // Hold on to the message until the database is reachable
+            // again, rather than failing to handle it during a failover.
+            if !self.context.wait_for_database().await {
+                break;
+            }
+
             match message {
                 SignerSignal::Command(SignerCommand::Shutdown) => break,
                 SignerSignal::Command(SignerCommand::P2PPublish(_)) => {}
                 SignerSignal::Event(event) => match event {
                     SignerEvent::TxCoordinator(TxCoordinatorEvent::MessageGenerated(msg))
                     | SignerEvent::P2P(P2PEvent::MessageReceived(msg)) => {
-                        match self.handle_signer_message(&msg).await {
-                            Ok(()) => {}
+                        let span = telemetry::message_span(&msg);
+                        let result = self.handle_signer_message(&msg).instrument(span).await;
+                        let errors = self.context.state().error_registry();
+                        match result {
+                            Ok(()) => errors.record_success(Subsystem::TxSigner),
                             // These errors can occur when we receive a duplicate message that has
                             // already been processed, resulting in a harmless rejection. It's nice
                             // to know when it happens, but it isn't a problem that requires action,
@@ -304,15 +352,23 @@ where
                             Err(
                                 error @ (Error::InvalidPresignRequest(_)
                                 | Error::StacksRequestAlreadySigned(..)
-                                | Error::MissingStateMachine(_)),
+                                | Error::MissingStateMachine(_)
+                                | Error::DeadlineExceeded),
                             ) => {
                                 tracing::warn!(%error, "minor error processing signer message");
                             }
                             Err(error) => {
                                 tracing::error!(%error, "error processing signer message");
+                                errors.record_error(Subsystem::TxSigner, &error);
                             }
                         }
                     }
+                    // Validation results are only good for the chain tip
+                    // that they were computed against.
+                    SignerEvent::BitcoinBlockObserved(block) => {
+                        self.stacks_validations.clear();
+                        self.prune_spilled_state_machines(&block).await;
+                    }
                     _ => {}
                 },
             }
@@ -322,11 +378,50 @@ where
         Ok(())
     }
 
+    /// Return an error unless the database holds all of the bitcoin
+    /// blocks within our context window of the given chain tip that the
+    /// signers care about.
+    ///
+    /// The block observer sets the sBTC start height before it backfills
+    /// any blocks, so until it is set we have nothing to measure the
+    /// history against and skip the check.
+    async fn ensure_chain_history(&self, chain_tip: &model::BitcoinBlockRef) -> Result<(), Error> {
+        let state = self.context.state();
+        if !state.is_sbtc_bitcoin_start_height_set() {
+            return Ok(());
+        }
+
+        let history = self
+            .context
+            .get_storage()
+            .get_chain_history(
+                &chain_tip.block_hash,
+                self.context_window,
+                state.get_sbtc_bitcoin_start_height(),
+            )
+            .await?;
+
+        if history.history_complete {
+            return Ok(());
+        }
+
+        Err(Error::IncompleteChainHistory(
+            history.available_blocks,
+            history.requested_blocks,
+        ))
+    }
+
     #[tracing::instrument(skip_all, fields(
         bitcoin_tip_hash = tracing::field::Empty,
         bitcoin_tip_height = tracing::field::Empty,
+        correlation_id = %msg.payload.correlation_ids(),
     ))]
     async fn handle_signer_message(&mut self, msg: &network::Msg) -> Result<(), Error> {
+        if self.context.state().is_in_safe_mode() {
+            tracing::debug!("signer is in safe-mode; ignoring signer message");
+            return Ok(());
+        }
+
         let chain_tip_report = self
             .inspect_msg_chain_tip(msg.signer_public_key, &msg.bitcoin_chain_tip)
             .await?;
@@ -336,6 +431,12 @@ where
             chain_tip,
         } = chain_tip_report;
 
+        if sender_is_coordinator && chain_tip_status == ChainTipStatus::Canonical {
+            self.context
+                .state()
+                .record_coordinator_activity(chain_tip.block_hash);
+        }
+
         let span = tracing::Span::current();
         let tracing_chain_tip = tracing::field::display(chain_tip.block_hash);
         span.record("bitcoin_tip_hash", tracing_chain_tip);
@@ -349,15 +450,18 @@ where
             "handling message from signer"
         );
 
+        // Requests may carry the time after which the coordinator stops
+        // waiting for our response, so we give up on them at that point.
+        let deadline = msg.inner.deadline;
         let payload = &msg.inner.payload;
         match (payload, sender_is_coordinator, chain_tip_status) {
             (Payload::StacksTransactionSignRequest(request), true, ChainTipStatus::Canonical) => {
-                self.handle_stacks_transaction_sign_request(
+                let future = self.handle_stacks_transaction_sign_request(
                     request,
                     &chain_tip,
                     &msg.signer_public_key,
-                )
-                .await?;
+                );
+                Deadline::enforce(deadline, future).await?;
             }
 
             (Payload::WstsMessage(wsts_msg), _, ChainTipStatus::Canonical) => {
@@ -367,17 +471,51 @@ where
 
             (Payload::BitcoinPreSignRequest(requests), true, ChainTipStatus::Canonical) => {
                 let instant = std::time::Instant::now();
-                let presign_result = self
-                    .handle_bitcoin_pre_sign_request(requests, &chain_tip)
-                    .await;
+                let future = self.handle_bitcoin_pre_sign_request(requests, &chain_tip);
+                let presign_result = Deadline::enforce(deadline, future).await;
 
                 Metrics::increment_presign_validation(instant.elapsed(), &presign_result);
                 presign_result?;
             }
+
+            (Payload::KeyRotationAttestation(attestation), _, ChainTipStatus::Canonical) => {
+                self.handle_key_rotation_attestation(attestation, msg.signer_public_key)
+                    .await?;
+            }
+
+            (Payload::CoordinatorTakeover(takeover), _, ChainTipStatus::Canonical) => {
+                self.handle_coordinator_takeover(takeover, msg.signer_public_key, &chain_tip)
+                    .await?;
+            }
+
+            (Payload::CoordinatorSilenceAttestation(attestation), _, ChainTipStatus::Canonical) => {
+                self.handle_coordinator_silence_attestation(
+                    attestation,
+                    msg.signer_public_key,
+                    &chain_tip,
+                )
+                .await?;
+            }
+
+            (Payload::RotateKeysDryRunRequest(request), _, ChainTipStatus::Canonical) => {
+                let future = self.handle_rotate_keys_dry_run_request(
+                    request,
+                    &chain_tip,
+                    &msg.signer_public_key,
+                );
+                Deadline::enforce(deadline, future).await?;
+            }
             // Message types ignored by the transaction signer
             (Payload::StacksTransactionSignature(_), _, _)
             | (Payload::SignerDepositDecision(_), _, _)
-            | (Payload::SignerWithdrawalDecision(_), _, _) => (),
+            | (Payload::SignerWithdrawalDecision(_), _, _)
+            | (Payload::DecisionSyncRequest(_), _, _)
+            | (Payload::DecisionSyncResponse(_), _, _)
+            | (Payload::SignerDecisionBatch(_), _, _)
+            | (Payload::RotateKeysDryRunVerdict(_), _, _)
+            | (Payload::PeerAddressAnnouncement(_), _, _)
+            | (Payload::SweepTxProofRequest(_), _, _)
+            | (Payload::SweepTxProofResponse(_), _, _) => (),
 
             // Any other combination should be logged
             _ => {
@@ -401,11 +539,10 @@ where
             .bitcoin_chain_tip()
             .ok_or(Error::NoChainTip)?;
 
-        let signer_set = self.context.coordinator_signer_set();
-        let sender_is_coordinator = crate::transaction_coordinator::given_key_is_coordinator(
+        let sender_is_coordinator = crate::transaction_coordinator::given_key_is_acting_coordinator(
+            &self.context,
             msg_sender,
             &chain_tip.block_hash,
-            &signer_set,
         );
 
         let chain_tip_status = if msg_bitcoin_chain_tip == &chain_tip.block_hash {
@@ -435,17 +572,33 @@ where
     ) -> Result<(), Error> {
         let db = self.context.get_storage_mut();
 
+        // Governance has paused the sBTC contracts, so we do not take part
+        // in any new sweeps until they are unpaused.
+        if self.context.state().is_contract_paused() {
+            return Err(Error::SbtcContractPaused);
+        }
+
+        // Validation looks for earlier sweeps of the requests within our
+        // context window, so with blocks missing from the database we
+        // could fail to notice them. We wait for the block observer to
+        // backfill those blocks instead.
+        self.ensure_chain_history(chain_tip).await?;
+
         if self.last_presign_block == Some(chain_tip.block_hash) {
             return Err(Error::InvalidPresignRequest(chain_tip.block_hash));
         }
         self.last_presign_block = Some(chain_tip.block_hash);
 
-        let aggregate_key = self
+        let signer_set_info = self
             .context
             .state()
             .registry_signer_set_info()
-            .map(|info| info.aggregate_key)
             .ok_or(Error::NoDkgShares)?;
+        invariants::enforce(invariants::check_signature_threshold(
+            signer_set_info.signatures_required,
+            signer_set_info.signer_set.len(),
+        ))?;
+        let aggregate_key = signer_set_info.aggregate_key;
 
         let dkg_shares = db.get_encrypted_dkg_shares(aggregate_key).await?;
         let aggregate_key = match dkg_shares.map(|shares| shares.dkg_shares_status) {
@@ -478,18 +631,207 @@ where
             .flat_map(|s| s.to_withdrawal_rows())
             .collect();
 
+        // We keep the package so that we can continue with it if we are
+        // the deputy coordinator and the coordinator goes silent.
+        let package = model::SweepPackage {
+            bitcoin_chain_tip: chain_tip.block_hash,
+            presign_request: request.clone(),
+            last_fees: sighashes
+                .first()
+                .and_then(|data| data.reports.signer_state.last_fees),
+            broadcast_txids: Vec::new(),
+        };
+
         tracing::debug!("storing sighashes to the database");
-        db.write_bitcoin_txs_sighashes(&deposits_sighashes).await?;
+        retry_transient(|| {
+            self.write_pre_sign_rows(&deposits_sighashes, &withdrawals_outputs, &package)
+        })
+        .await?;
 
-        db.write_bitcoin_withdrawals_outputs(&withdrawals_outputs)
+        self.send_message(BitcoinPreSignAck, &chain_tip.block_hash)
             .await?;
 
-        self.send_message(BitcoinPreSignAck, &chain_tip.block_hash)
+        Ok(())
+    }
+
+    /// Write the sighashes, withdrawal outputs and sweep package of a
+    /// validated pre-sign request to the database in a single storage
+    /// transaction, so that we never record our intent to sign only part
+    /// of a sweep package.
+    async fn write_pre_sign_rows(
+        &self,
+        deposits_sighashes: &[model::BitcoinTxSigHash],
+        withdrawals_outputs: &[model::BitcoinWithdrawalOutput],
+        package: &model::SweepPackage,
+    ) -> Result<(), Error> {
+        let storage = self.context.get_storage_mut();
+        let storage_tx = storage.begin_transaction().await?;
+
+        storage_tx
+            .write_bitcoin_txs_sighashes(deposits_sighashes)
+            .await?;
+        storage_tx
+            .write_bitcoin_withdrawals_outputs(withdrawals_outputs)
+            .await?;
+        storage_tx.write_sweep_package(package).await?;
+
+        storage_tx.commit().await
+    }
+
+    /// Processes the [`message::CoordinatorTakeover`] message.
+    ///
+    /// We accept the takeover if the sender is the deputy coordinator of
+    /// the chain tip, the silent coordinator is the coordinator of the
+    /// chain tip, and at least a threshold of the signer set attested that
+    /// the coordinator went silent. We do not consider when we last heard
+    /// from the coordinator ourselves, so that all signers make the same
+    /// decision. Once accepted, we treat the sender as the coordinator for
+    /// the rest of the tenure.
+    #[tracing::instrument(skip_all, fields(%sender))]
+    pub async fn handle_coordinator_takeover(
+        &mut self,
+        takeover: &message::CoordinatorTakeover,
+        sender: PublicKey,
+        chain_tip: &model::BitcoinBlockRef,
+    ) -> Result<(), Error> {
+        let reject = |reason| Error::RejectedCoordinatorTakeover {
+            sender,
+            chain_tip: chain_tip.block_hash,
+            reason,
+        };
+
+        if self
+            .context
+            .config()
+            .signer
+            .coordinator_silence_timeout
+            .is_none()
+        {
+            return Err(reject("coordinator takeovers are disabled"));
+        }
+
+        let signer_set = self.context.coordinator_signer_set();
+        let candidates = self.context.coordinator_candidates(&chain_tip.block_hash);
+        let coordinator = crate::transaction_coordinator::coordinator_public_key(
+            &chain_tip.block_hash,
+            &candidates,
+        );
+        if coordinator != Some(takeover.coordinator_public_key) {
+            return Err(reject("the silent signer is not the coordinator"));
+        }
+        let deputy =
+            crate::transaction_coordinator::deputy_public_key(&chain_tip.block_hash, &candidates);
+        if deputy != Some(sender) {
+            return Err(reject("the sender is not the deputy coordinator"));
+        }
+
+        let attesters = crate::transaction_coordinator::silence_attesters(
+            &takeover.attestations,
+            &chain_tip.block_hash,
+            &takeover.coordinator_public_key,
+            &signer_set,
+        );
+        let signatures_required = usize::from(self.context.coordinator_signatures_required());
+        if attesters.len() < signatures_required {
+            return Err(reject(
+                "too few signers attested to the silence of the coordinator",
+            ));
+        }
+
+        let state = self.context.state();
+
+        tracing::info!(coordinator = %takeover.coordinator_public_key, "accepting a coordinator takeover");
+        state.set_coordinator_takeover(chain_tip.block_hash, sender);
+
+        let record = model::CoordinatorTakeover {
+            bitcoin_chain_tip: chain_tip.block_hash,
+            coordinator_public_key: takeover.coordinator_public_key,
+            deputy_public_key: sender,
+        };
+        self.context
+            .get_storage_mut()
+            .write_coordinator_takeover(&record)
             .await?;
 
         Ok(())
     }
 
+    /// Processes the [`message::CoordinatorSilenceAttestation`] message.
+    ///
+    /// We keep valid attestations from the signer set so that we can take
+    /// over the tenure with them if we are the deputy coordinator. If we
+    /// have not heard from the coordinator within the configured silence
+    /// timeout either, then we send an attestation of our own.
+    #[tracing::instrument(skip_all, fields(%sender))]
+    pub async fn handle_coordinator_silence_attestation(
+        &mut self,
+        attestation: &message::CoordinatorSilenceAttestation,
+        sender: PublicKey,
+        chain_tip: &model::BitcoinBlockRef,
+    ) -> Result<(), Error> {
+        let reject = |reason| Error::RejectedSilenceAttestation {
+            sender,
+            chain_tip: chain_tip.block_hash,
+            reason,
+        };
+
+        let Some(timeout) = self.context.config().signer.coordinator_silence_timeout else {
+            return Err(reject("coordinator takeovers are disabled"));
+        };
+
+        let signer_set = self.context.coordinator_signer_set();
+        let candidates = self.context.coordinator_candidates(&chain_tip.block_hash);
+        let coordinator = crate::transaction_coordinator::coordinator_public_key(
+            &chain_tip.block_hash,
+            &candidates,
+        );
+        if coordinator != Some(attestation.coordinator_public_key) {
+            return Err(reject("the silent signer is not the coordinator"));
+        }
+        if attestation.signer_public_key != sender {
+            return Err(reject("the attestation was not signed by the sender"));
+        }
+        if !signer_set.contains(&sender) || sender == attestation.coordinator_public_key {
+            return Err(reject("the sender may not attest to the silence"));
+        }
+        attestation
+            .verify(&chain_tip.block_hash)
+            .map_err(|_| reject("the attestation has an invalid signature"))?;
+
+        let state = self.context.state();
+        state.record_silence_attestation(chain_tip.block_hash, *attestation);
+
+        // We join in if we have not heard from the coordinator either, and
+        // have not already attested to its silence.
+        let public_key = self.signer_public_key();
+        let already_attested = state
+            .silence_attestations(&chain_tip.block_hash)
+            .iter()
+            .any(|attestation| attestation.signer_public_key == public_key);
+        if already_attested || coordinator == Some(public_key) {
+            return Ok(());
+        }
+
+        let timeout =
+            Duration::from_secs(timeout.get()).saturating_sub(COORDINATOR_SILENCE_TOLERANCE);
+        let coordinator_last_seen = state.coordinator_last_seen(&chain_tip.block_hash);
+        if coordinator_last_seen.is_some_and(|last_seen| last_seen.elapsed() < timeout) {
+            tracing::debug!("we have heard from the coordinator recently; not attesting");
+            return Ok(());
+        }
+
+        let coordinator = attestation.coordinator_public_key;
+        tracing::info!(%coordinator, "attesting to the silence of the coordinator");
+        let own_attestation = message::CoordinatorSilenceAttestation::new(
+            &self.signer_private_key,
+            &chain_tip.block_hash,
+            coordinator,
+        );
+        state.record_silence_attestation(chain_tip.block_hash, own_attestation);
+        self.send_message(own_attestation, &chain_tip.block_hash)
+            .await
+    }
+
     /// Processes the [`StacksTransactionSignRequest`] message.
     /// Validate the request and if valid then sign and broadcast the signed tx.
     #[tracing::instrument(skip_all)]
@@ -534,6 +876,37 @@ where
         Ok(())
     }
 
+    /// Processes the [`RotateKeysDryRunRequest`] message.
+    ///
+    /// We validate the rotate-keys contract call in the same way that we
+    /// would validate a request to sign it, and tell the sender whether it
+    /// passed. Nothing is signed, so the sender does not need to be the
+    /// coordinator.
+    #[tracing::instrument(skip_all, fields(request_id = %request.id, sender = %origin_public_key))]
+    pub async fn handle_rotate_keys_dry_run_request(
+        &mut self,
+        request: &RotateKeysDryRunRequest,
+        chain_tip: &model::BitcoinBlockRef,
+        origin_public_key: &PublicKey,
+    ) -> Result<(), Error> {
+        let rotate_keys = Box::new(request.rotate_keys.clone());
+        let contract_tx = StacksTx::ContractCall(ContractCall::RotateKeysV1(rotate_keys));
+
+        let validation_result = self
+            .assert_valid_stacks_tx(&contract_tx, chain_tip, origin_public_key)
+            .await;
+        if let Err(error) = &validation_result {
+            tracing::info!(%error, "rotate-keys contract call failed dry run validation");
+        }
+
+        let verdict = RotateKeysDryRunVerdict {
+            id: request.id,
+            rejection_reason: validation_result.err().map(|error| error.to_string()),
+        };
+
+        self.send_message(verdict, &chain_tip.block_hash).await
+    }
+
     /// Check that the transaction is indeed valid. We specific checks that
     /// are run depend on the transaction being signed.
     #[tracing::instrument(skip_all, fields(sender = %origin_public_key, txid = %request.txid), err)]
@@ -566,6 +939,18 @@ where
             ));
         }
 
+        self.assert_valid_stacks_tx(&request.contract_tx, chain_tip, origin_public_key)
+            .await
+    }
+
+    /// Check that the given stacks transaction passes validation against
+    /// the current state of the bitcoin and stacks blockchains.
+    async fn assert_valid_stacks_tx(
+        &mut self,
+        contract_tx: &StacksTx,
+        chain_tip: &model::BitcoinBlockRef,
+        origin_public_key: &PublicKey,
+    ) -> Result<(), Error> {
         let db = self.context.get_storage();
         let public_key = self.signer_public_key();
         let state = self.context.state();
@@ -581,7 +966,7 @@ where
             Some(info) => info,
             None => match db.get_latest_verified_dkg_shares().await? {
                 Some(info) => info.into(),
-                None if matches!(request.contract_tx, StacksTx::SmartContract(_))
+                None if matches!(contract_tx, StacksTx::SmartContract(_))
                     && !state.sbtc_contracts_deployed() =>
                 {
                     db.get_latest_encrypted_dkg_shares()
@@ -599,6 +984,22 @@ where
 
         let stacks_chain_tip = state.stacks_chain_tip().ok_or(Error::NoStacksChainTip)?;
 
+        // The contract call validation only depends on the call itself and
+        // the state of the blockchains, so if we have already validated
+        // this call against these chain tips then there is no need to hit
+        // bitcoin-core or the database again.
+        let validation_key = (chain_tip.block_hash, stacks_chain_tip.block_hash);
+        if let StacksTx::ContractCall(contract_call) = contract_tx {
+            let already_validated = self
+                .stacks_validations
+                .get(&validation_key)
+                .is_some_and(|calls| calls.contains(contract_call));
+            if already_validated {
+                tracing::info!("stacks contract call already validated for these chain tips");
+                return Ok(());
+            }
+        }
+
         let req_ctx = ReqContext {
             chain_tip: *chain_tip,
             stacks_chain_tip: stacks_chain_tip.block_hash,
@@ -611,7 +1012,7 @@ where
         let ctx = &self.context;
 
         tracing::info!("running validation on stacks transaction");
-        match &request.contract_tx {
+        match contract_tx {
             StacksTx::ContractCall(ContractCall::AcceptWithdrawalV1(contract)) => {
                 contract.validate(ctx, &req_ctx).await?
             }
@@ -629,6 +1030,12 @@ where
             }
         };
 
+        if let StacksTx::ContractCall(contract_call) = contract_tx {
+            self.stacks_validations
+                .get_or_insert_mut(validation_key, Default::default)
+                .insert(contract_call.clone());
+        }
+
         tracing::info!("stacks validation finished successfully");
         Ok(())
     }
@@ -687,10 +1094,11 @@ where
                     threshold,
                     *chain_tip,
                     self.signer_private_key,
+                    &mut self.context.get_rng(TX_SIGNER_RNG_STREAM),
                 )?;
                 let state_machine_id = StateMachineId::Dkg(*chain_tip);
-                self.wsts_state_machines
-                    .put(state_machine_id, state_machine);
+                self.put_state_machine(state_machine_id, state_machine)
+                    .await;
 
                 // If a DKG-begin pause is configured, sleep for a bit before
                 // processing the message and broadcasting our responses.
@@ -887,12 +1295,14 @@ where
                 };
 
                 // Create a new `SignerStateMachine`.
+                let mut rng = self.context.get_rng(TX_SIGNER_RNG_STREAM);
                 let state_machine =
-                    SignerStateMachine::load(&db, aggregate_key, self.signer_private_key).await?;
+                    SignerStateMachine::load(&db, aggregate_key, self.signer_private_key, &mut rng)
+                        .await?;
 
                 // Put the state machine into the cache.
-                self.wsts_state_machines
-                    .put(state_machine_id, state_machine);
+                self.put_state_machine(state_machine_id, state_machine)
+                    .await;
 
                 // Process the message.
                 self.relay_message(
@@ -1226,7 +1636,8 @@ where
             return Err(Error::UnexpectedStateMachineId(*state_machine_id));
         };
 
-        let encrypted_dkg_shares = state_machine.get_encrypted_dkg_shares()?;
+        let mut rng = self.context.get_rng(TX_SIGNER_RNG_STREAM);
+        let encrypted_dkg_shares = state_machine.get_encrypted_dkg_shares(&mut rng)?;
 
         tracing::debug!("🔐 storing DKG shares");
         self.context
@@ -1311,6 +1722,12 @@ where
             .await?;
             self.dkg_verification_state_machines
                 .put(state_machine_id, coordinator);
+            self.record_dkg_verification_round(
+                &state_machine_id,
+                DkgVerificationRoundStatus::Active,
+                None,
+            )
+            .await;
         } else {
             self.assert_dkg_verification_state_machine_state(&state_machine_id)?;
         }
@@ -1421,6 +1838,8 @@ where
             .inspect_err(|error| tracing::warn!(?error, %sender, "🔐 failed to process FROST coordinator message"))
             .map_err(Error::DkgVerification)?;
 
+        let message_type = Some(dkg::WstsNetMessageType::from(msg));
+
         // Check if the state machine is in an end-state and handle it
         // accordingly.
         match state_machine.state() {
@@ -1432,6 +1851,12 @@ where
                 // We're at an end-state, so remove the state machines.
                 self.wsts_state_machines.pop(&state_machine_id);
                 self.dkg_verification_state_machines.pop(&state_machine_id);
+                self.record_dkg_verification_round(
+                    &state_machine_id,
+                    DkgVerificationRoundStatus::Completed,
+                    message_type,
+                )
+                .await;
 
                 // Perform verification of the signature.
                 tracing::info!(
@@ -1444,6 +1869,7 @@ where
                         tracing::info!("🔐 signature verification successful");
                         db.verify_dkg_shares(aggregate_key).await?;
                         tracing::info!("🔐 DKG shares entry has been marked as verified");
+                        self.attest_key_rotation(aggregate_key).await?;
                     }
                     Err(error) => {
                         tracing::warn!(%error, "🔐 signature verification failed");
@@ -1462,70 +1888,376 @@ where
                 // state machines and return an error.
                 self.dkg_verification_state_machines.pop(&state_machine_id);
                 self.wsts_state_machines.pop(&state_machine_id);
+                self.record_dkg_verification_round(
+                    &state_machine_id,
+                    DkgVerificationRoundStatus::Failed,
+                    message_type,
+                )
+                .await;
 
                 return Err(Error::DkgVerificationFailed(aggregate_key));
             }
-            dkg::verification::State::Idle | dkg::verification::State::Signing => {}
+            dkg::verification::State::Idle | dkg::verification::State::Signing => {
+                self.record_dkg_verification_round(
+                    &state_machine_id,
+                    DkgVerificationRoundStatus::Active,
+                    message_type,
+                )
+                .await;
+            }
         }
 
         Ok(())
     }
 
-    #[tracing::instrument(skip_all)]
-    async fn relay_message(
-        &mut self,
-        state_machine_id: &StateMachineId,
-        wsts_id: WstsMessageId,
+    /// Sign an attestation to the outcome of the DKG round for the given
+    /// aggregate key, store it and broadcast it to the other signers.
+    #[tracing::instrument(skip_all, fields(%aggregate_key))]
+    async fn attest_key_rotation(&mut self, aggregate_key: PublicKey) -> Result<(), Error> {
+        let db = self.context.get_storage_mut();
+        let Some(shares) = db.get_encrypted_dkg_shares(aggregate_key).await? else {
+            return Err(Error::MissingDkgShares(aggregate_key.into()));
+        };
+
+        let attestation = KeyRotationAttestation::new(
+            &self.signer_private_key,
+            shares.aggregate_key,
+            &shares.signer_set_public_keys(),
+            shares.signature_share_threshold,
+        );
+        let entry = model::KeyRotationAttestation::new(self.signer_public_key(), &attestation);
+        db.write_key_rotation_attestation(&entry).await?;
+
+        let Some(chain_tip) = self.context.state().bitcoin_chain_tip() else {
+            tracing::warn!("🔐 no bitcoin chain tip; not broadcasting key rotation attestation");
+            return Ok(());
+        };
+        let supported = self.context.state().signers_support_protocol_version(
+            &shares.signer_set_public_keys(),
+            &self.signer_public_key(),
+            crate::KEY_ROTATION_ATTESTATION_PROTOCOL_VERSION,
+        );
+        if !supported {
+            tracing::warn!(
+                "🔐 not every signer supports key rotation attestations; not broadcasting ours"
+            );
+            return Ok(());
+        }
+        tracing::info!("🔐 broadcasting key rotation attestation");
+        self.send_message(attestation, &chain_tip.block_hash).await
+    }
+
+    /// Store the key rotation attestation of another signer, as long as
+    /// it was signed by the sender and the sender is a member of the
+    /// signer set of the attested aggregate key.
+    #[tracing::instrument(skip_all, fields(%sender, aggregate_key = %attestation.aggregate_key))]
+    async fn handle_key_rotation_attestation(
+        &self,
+        attestation: &KeyRotationAttestation,
         sender: PublicKey,
-        signer_id: Option<u32>,
-        msg: &WstsNetMessage,
-        bitcoin_chain_tip: &model::BitcoinBlockHash,
     ) -> Result<(), Error> {
-        // Validate that the sender is a valid member of the signing set and
-        // has the correct id according to the signer state machine.
-        if let Some(signer_id) = signer_id {
-            self.validate_sender(state_machine_id, signer_id, &sender)?;
+        attestation.verify(&sender)?;
+
+        let db = self.context.get_storage_mut();
+        let shares = db
+            .get_encrypted_dkg_shares(attestation.aggregate_key)
+            .await?;
+        let Some(shares) = shares else {
+            tracing::warn!("received key rotation attestation for an unknown aggregate key");
+            return Ok(());
+        };
+        if !shares.signer_set_public_keys.contains(&sender) {
+            tracing::warn!(
+                "received key rotation attestation from a signer outside the signer set"
+            );
+            return Ok(());
         }
 
-        // Process the message in the WSTS signer state machine.
-        let outbound_messages = match self.wsts_state_machines.get_mut(state_machine_id) {
-            Some(state_machine) => state_machine.process(msg)?,
-            None => {
-                tracing::warn!("missing signing round");
-                return Err(Error::MissingStateMachine(*state_machine_id));
-            }
+        let entry = model::KeyRotationAttestation::new(sender, attestation);
+        if !db
+            .write_key_rotation_attestation(&entry)
+            .await?
+            .is_inserted()
+        {
+            tracing::debug!("already have a key rotation attestation from this signer");
+        }
+
+        Ok(())
+    }
+
+    /// Records the status of the DKG verification round with the given
+    /// state machine id in the database, so that the round can be
+    /// recovered if this signer restarts. Failing to record the round does
+    /// not affect the round itself, so errors are only logged.
+    async fn record_dkg_verification_round(
+        &self,
+        state_machine_id: &StateMachineId,
+        status: DkgVerificationRoundStatus,
+        last_message_type: Option<dkg::WstsNetMessageType>,
+    ) {
+        let StateMachineId::DkgVerification(aggregate_key, chain_tip) = state_machine_id else {
+            return;
         };
 
-        // Check and store if this is a DKG verification-related message.
-        let is_dkg_verification = matches!(
-            state_machine_id,
-            StateMachineId::DkgVerification(_, chain_tip) if chain_tip.block_hash == *bitcoin_chain_tip
-        );
+        let round = model::DkgVerificationRound {
+            aggregate_key: *aggregate_key,
+            bitcoin_block_hash: chain_tip.block_hash,
+            bitcoin_block_height: chain_tip.block_height,
+            status,
+            last_message_type: last_message_type.map(|message_type| message_type.to_string()),
+            updated_at: time::OffsetDateTime::now_utc().into(),
+        };
 
-        // If this is a DKG verification then we need to process the message in
-        // the frost coordinator as well to be able to properly follow the
-        // signing round (which is otherwise handled by the signer state
-        // machine). We pass `None` as the `signer_id` because we have just
-        // validated the sender above.
-        if is_dkg_verification {
-            self.process_dkg_verification_message(*state_machine_id, sender, None, msg)
-                .await?;
+        let db = self.context.get_storage_mut();
+        if let Err(error) = db.write_dkg_verification_round(&round).await {
+            tracing::warn!(%error, ?status, "🔐 could not record the DKG verification round");
         }
+    }
 
-        // The WSTS state machines assume we read our own messages, so if the
-        // state machine emitted any outbound messages then we need to process
-        // them manually. We ignore any extra messages emitted from these calls.
-        for outbound_message in outbound_messages.iter() {
-            // Process in the signer state machine.
-            self.wsts_state_machines
-                .get_mut(state_machine_id)
-                .ok_or_else(|| Error::MissingStateMachine(*state_machine_id))?
-                .process(outbound_message)?;
+    /// Recovers the DKG verification rounds that were still active when
+    /// this signer last stopped.
+    ///
+    /// The DKG verification state machines only live in memory, so a
+    /// restarted signer has lost track of any round that it was taking
+    /// part in. A round is rejoined when it belongs to the current
+    /// canonical bitcoin chain tip, the shares being verified are still
+    /// unverified, and we had not processed any messages for it yet. In
+    /// every other case our WSTS nonces are gone, so the round cannot be
+    /// completed and it is marked as aborted; the coordinator of the next
+    /// tenure starts a fresh round.
+    #[tracing::instrument(skip_all)]
+    pub async fn recover_dkg_verification_rounds(&mut self) -> Result<(), Error> {
+        let db = self.context.get_storage();
+        let rounds = db.get_active_dkg_verification_rounds().await?;
+        if rounds.is_empty() {
+            return Ok(());
+        }
 
-            // If this is a DKG verification then we need to process the message
-            // in the FROST state machine as well for it to properly follow
-            // the signing round.
-            if is_dkg_verification {
+        let canonical_chain_tip = db.get_bitcoin_canonical_chain_tip_ref().await?;
+
+        for mut round in rounds {
+            let round_chain_tip = round.bitcoin_chain_tip();
+            let shares_status = db
+                .get_encrypted_dkg_shares(round.aggregate_key)
+                .await?
+                .map(|shares| shares.dkg_shares_status);
+
+            let can_rejoin = canonical_chain_tip == Some(round_chain_tip)
+                && shares_status == Some(DkgSharesStatus::Unverified)
+                && round.last_message_type.is_none();
+
+            if can_rejoin {
+                self.ensure_dkg_verification_state_machine(round.aggregate_key, &round_chain_tip)
+                    .await?;
+                tracing::info!(
+                    aggregate_key = %round.aggregate_key,
+                    bitcoin_block_hash = %round.bitcoin_block_hash,
+                    "🔐 rejoined an active DKG verification round after restart"
+                );
+                continue;
+            }
+
+            tracing::info!(
+                aggregate_key = %round.aggregate_key,
+                bitcoin_block_hash = %round.bitcoin_block_hash,
+                last_message_type = ?round.last_message_type,
+                "🔐 aborting a DKG verification round that cannot be rejoined after restart"
+            );
+            round.status = DkgVerificationRoundStatus::Aborted;
+            self.context
+                .get_storage_mut()
+                .write_dkg_verification_round(&round)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Put the given state machine into the cache.
+    ///
+    /// If the cache is full then a state machine is evicted to make room.
+    /// We evict the least recently used state machine that does not hold
+    /// a private nonce, and spill it to the database so that its round
+    /// can continue when the next message for it arrives. State machines
+    /// that hold a private nonce are only evicted when all of them do,
+    /// and they are dropped rather than spilled, since private nonces are
+    /// never written to the database.
+    async fn put_state_machine(&mut self, id: StateMachineId, state_machine: SignerStateMachine) {
+        let cache = &self.wsts_state_machines;
+        if !cache.contains(&id) && cache.len() >= cache.cap().get() {
+            self.evict_state_machine().await;
+        }
+
+        self.wsts_state_machines.put(id, state_machine);
+    }
+
+    /// Evict a state machine from the cache and spill it to the database,
+    /// unless it holds a private nonce.
+    async fn evict_state_machine(&mut self) {
+        // The cache iterates from the most to the least recently used
+        // state machine.
+        let victim = self
+            .wsts_state_machines
+            .iter()
+            .rev()
+            .find(|(_, state_machine)| !state_machine.holds_nonce())
+            .map(|(id, _)| *id);
+
+        let evicted = match victim {
+            Some(id) => self.wsts_state_machines.pop_entry(&id),
+            None => self.wsts_state_machines.pop_lru(),
+        };
+        let Some((id, state_machine)) = evicted else {
+            return;
+        };
+
+        // A restored state machine would not have the private nonce that
+        // its signature share must be computed with, so we drop it and
+        // let its signing round fail for us.
+        if state_machine.holds_nonce() {
+            tracing::warn!(
+                state_machine_id = %id,
+                "dropped evicted state machine that holds a private nonce"
+            );
+            Metrics::increment_state_machine_evictions(false);
+            return;
+        }
+
+        // The spilled state machine is kept until the chain tip moves
+        // past our context window, so we record the height of the chain
+        // tip rather than the height that the state machine started at.
+        let bitcoin_block_height = self
+            .context
+            .state()
+            .bitcoin_chain_tip()
+            .map(|chain_tip| chain_tip.block_height)
+            .unwrap_or_default();
+
+        let mut rng = self.context.get_rng(TX_SIGNER_RNG_STREAM);
+        let result = match state_machine.to_spilled(&id, bitcoin_block_height, &mut rng) {
+            Ok(spilled) => {
+                self.context
+                    .get_storage_mut()
+                    .write_spilled_state_machine(&spilled)
+                    .await
+            }
+            Err(error) => Err(error),
+        };
+
+        match &result {
+            Ok(()) => tracing::debug!(state_machine_id = %id, "spilled evicted state machine"),
+            Err(error) => tracing::warn!(
+                %error,
+                state_machine_id = %id,
+                "could not spill evicted state machine"
+            ),
+        }
+        Metrics::increment_state_machine_evictions(result.is_ok());
+    }
+
+    /// Restore the state machine with the given ID into the cache if it
+    /// was evicted and spilled to the database.
+    async fn restore_state_machine(&mut self, id: &StateMachineId) -> Result<(), Error> {
+        if self.wsts_state_machines.contains(id) {
+            return Ok(());
+        }
+
+        let spilled = self
+            .context
+            .get_storage_mut()
+            .take_spilled_state_machine(&id.to_string())
+            .await?;
+        let Some(spilled) = spilled else {
+            return Ok(());
+        };
+
+        let result = SignerStateMachine::from_spilled(&spilled, id, self.signer_private_key);
+        Metrics::increment_state_machine_restorations(result.is_ok());
+        let state_machine = result?;
+
+        tracing::debug!(state_machine_id = %id, "restored spilled state machine");
+        self.put_state_machine(*id, state_machine).await;
+        Ok(())
+    }
+
+    /// Delete the spilled state machines that were evicted before the
+    /// start of our context window, since their rounds are long over.
+    async fn prune_spilled_state_machines(&self, chain_tip: &model::BitcoinBlockRef) {
+        let min_block_height = chain_tip.block_height.saturating_sub(self.context_window);
+
+        let result = self
+            .context
+            .get_storage_mut()
+            .prune_spilled_state_machines(min_block_height)
+            .await;
+
+        match result {
+            Ok(0) => {}
+            Ok(pruned) => tracing::debug!(%pruned, "pruned spilled state machines"),
+            Err(error) => tracing::warn!(%error, "could not prune spilled state machines"),
+        }
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn relay_message(
+        &mut self,
+        state_machine_id: &StateMachineId,
+        wsts_id: WstsMessageId,
+        sender: PublicKey,
+        signer_id: Option<u32>,
+        msg: &WstsNetMessage,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+    ) -> Result<(), Error> {
+        // The state machine may have been evicted from the cache and
+        // spilled to the database, in which case we bring it back.
+        self.restore_state_machine(state_machine_id).await?;
+
+        // Validate that the sender is a valid member of the signing set and
+        // has the correct id according to the signer state machine.
+        if let Some(signer_id) = signer_id {
+            self.validate_sender(state_machine_id, signer_id, &sender)?;
+        }
+
+        // Process the message in the WSTS signer state machine.
+        let mut rng = self.context.get_rng(TX_SIGNER_RNG_STREAM);
+        let outbound_messages = match self.wsts_state_machines.get_mut(state_machine_id) {
+            Some(state_machine) => state_machine.process(msg, &mut rng)?,
+            None => {
+                tracing::warn!("missing signing round");
+                return Err(Error::MissingStateMachine(*state_machine_id));
+            }
+        };
+
+        // Check and store if this is a DKG verification-related message.
+        let is_dkg_verification = matches!(
+            state_machine_id,
+            StateMachineId::DkgVerification(_, chain_tip) if chain_tip.block_hash == *bitcoin_chain_tip
+        );
+
+        // If this is a DKG verification then we need to process the message in
+        // the frost coordinator as well to be able to properly follow the
+        // signing round (which is otherwise handled by the signer state
+        // machine). We pass `None` as the `signer_id` because we have just
+        // validated the sender above.
+        if is_dkg_verification {
+            self.process_dkg_verification_message(*state_machine_id, sender, None, msg)
+                .await?;
+        }
+
+        // The WSTS state machines assume we read our own messages, so if the
+        // state machine emitted any outbound messages then we need to process
+        // them manually. We ignore any extra messages emitted from these calls.
+        for outbound_message in outbound_messages.iter() {
+            // Process in the signer state machine.
+            self.wsts_state_machines
+                .get_mut(state_machine_id)
+                .ok_or_else(|| Error::MissingStateMachine(*state_machine_id))?
+                .process(outbound_message)?;
+
+            // If this is a DKG verification then we need to process the message
+            // in the FROST state machine as well for it to properly follow
+            // the signing round.
+            if is_dkg_verification {
                 self.process_dkg_verification_message(
                     *state_machine_id,
                     sender,
@@ -1834,6 +2566,7 @@ mod tests {
             dkg_begin_pause: None,
             dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
             stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+            stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
         };
 
         // Create a DkgBegin message to be handled by the signer.
@@ -1860,6 +2593,565 @@ mod tests {
         assert!(matches!(result, Err(Error::DkgHasAlreadyRun)));
     }
 
+    #[test_case(false, DkgSharesStatus::Unverified, None; "different chain tip")]
+    #[test_case(true, DkgSharesStatus::Verified, None; "shares already verified")]
+    #[test_case(
+        true,
+        DkgSharesStatus::Unverified,
+        Some(dkg::WstsNetMessageType::NonceRequest);
+        "round already in progress"
+    )]
+    #[tokio::test]
+    async fn recover_dkg_verification_rounds_aborts_rounds_that_cannot_be_rejoined(
+        at_chain_tip: bool,
+        shares_status: DkgSharesStatus,
+        last_message_type: Option<dkg::WstsNetMessageType>,
+    ) {
+        let context = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .build();
+
+        let storage = context.get_storage_mut();
+        let network = InMemoryNetwork::new();
+
+        let shares = model::EncryptedDkgShares {
+            dkg_shares_status: shares_status,
+            ..Faker.fake()
+        };
+        storage.write_encrypted_dkg_shares(&shares).await.unwrap();
+
+        let chain_tip = model::BitcoinBlock {
+            block_height: 100u64.into(),
+            parent_hash: Faker.fake(),
+            block_hash: Faker.fake(),
+        };
+        storage.write_bitcoin_block(&chain_tip).await.unwrap();
+
+        // Record a round that was still active when the signer stopped.
+        let round = model::DkgVerificationRound {
+            aggregate_key: shares.aggregate_key.into(),
+            bitcoin_block_hash: if at_chain_tip {
+                chain_tip.block_hash
+            } else {
+                Faker.fake()
+            },
+            bitcoin_block_height: chain_tip.block_height,
+            status: DkgVerificationRoundStatus::Active,
+            last_message_type: last_message_type.map(|message_type| message_type.to_string()),
+            updated_at: time::OffsetDateTime::now_utc().into(),
+        };
+        storage.write_dkg_verification_round(&round).await.unwrap();
+
+        let mut signer = TxSignerEventLoop {
+            context,
+            network: network.connect(),
+            signer_private_key: PrivateKey::new(&mut rand::rngs::OsRng),
+            context_window: 1,
+            wsts_state_machines: LruCache::new(NonZeroUsize::new(100).unwrap()),
+            last_presign_block: None,
+            dkg_begin_pause: None,
+            dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
+            stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+            stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
+        };
+
+        signer.recover_dkg_verification_rounds().await.unwrap();
+
+        // The round could not be rejoined, so no state machine was created
+        // and it is no longer active.
+        assert!(signer.dkg_verification_state_machines.is_empty());
+        let active_rounds = storage.get_active_dkg_verification_rounds().await.unwrap();
+        assert!(active_rounds.is_empty());
+    }
+
+    #[tokio::test]
+    async fn presign_requests_wait_for_missing_chain_history() {
+        let context = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .build();
+
+        // We only have the chain tip and its parent, while sBTC started
+        // well before them.
+        let parent = model::BitcoinBlock {
+            block_height: 99u64.into(),
+            parent_hash: Faker.fake(),
+            block_hash: Faker.fake(),
+        };
+        let chain_tip = model::BitcoinBlock {
+            block_height: 100u64.into(),
+            parent_hash: parent.block_hash,
+            block_hash: Faker.fake(),
+        };
+        let storage = context.get_storage_mut();
+        storage.write_bitcoin_block(&parent).await.unwrap();
+        storage.write_bitcoin_block(&chain_tip).await.unwrap();
+        context.state().set_sbtc_bitcoin_start_height(50u64.into());
+
+        let network = InMemoryNetwork::new();
+        let mut signer = TxSignerEventLoop {
+            context: context.clone(),
+            network: network.connect(),
+            signer_private_key: PrivateKey::new(&mut rand::rngs::OsRng),
+            context_window: 10,
+            wsts_state_machines: LruCache::new(NonZeroUsize::new(100).unwrap()),
+            last_presign_block: None,
+            dkg_begin_pause: None,
+            dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
+            stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+            stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
+        };
+
+        let request = message::BitcoinPreSignRequest {
+            request_package: Vec::new(),
+            fee_rate: 1.0,
+            last_fees: None,
+            parallel_sweeps: false,
+            magic_bytes: crate::LEGACY_MAGIC_BYTES,
+        };
+        let chain_tip_ref = model::BitcoinBlockRef::from(&chain_tip);
+
+        let result = signer
+            .handle_bitcoin_pre_sign_request(&request, &chain_tip_ref)
+            .await;
+        assert!(matches!(result, Err(Error::IncompleteChainHistory(2, 10))));
+        // The request was rejected before it was recorded, so the signer
+        // can process a presign request for this block once the missing
+        // blocks are backfilled.
+        assert!(signer.last_presign_block.is_none());
+
+        // Once the history reaches back to the start of sBTC, it is
+        // complete even though there are fewer blocks than the context
+        // window.
+        context.state().set_sbtc_bitcoin_start_height(99u64.into());
+        signer.ensure_chain_history(&chain_tip_ref).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn presign_requests_are_rejected_while_contracts_are_paused() {
+        let context = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .build();
+        context.state().set_contract_paused(true);
+
+        let network = InMemoryNetwork::new();
+        let mut signer = TxSignerEventLoop {
+            context,
+            network: network.connect(),
+            signer_private_key: PrivateKey::new(&mut rand::rngs::OsRng),
+            context_window: 1,
+            wsts_state_machines: LruCache::new(NonZeroUsize::new(100).unwrap()),
+            last_presign_block: None,
+            dkg_begin_pause: None,
+            dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
+            stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+            stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
+        };
+
+        let request = message::BitcoinPreSignRequest {
+            request_package: Vec::new(),
+            fee_rate: 1.0,
+            last_fees: None,
+            parallel_sweeps: false,
+            magic_bytes: crate::LEGACY_MAGIC_BYTES,
+        };
+        let chain_tip = model::BitcoinBlockRef {
+            block_hash: Faker.fake(),
+            block_height: 100u64.into(),
+        };
+
+        let result = signer
+            .handle_bitcoin_pre_sign_request(&request, &chain_tip)
+            .await;
+        assert!(matches!(result, Err(Error::SbtcContractPaused)));
+        // The request was rejected before it was recorded, so the signer
+        // can still process a presign request for this block once the
+        // contracts are unpaused.
+        assert!(signer.last_presign_block.is_none());
+    }
+
+    #[tokio::test]
+    async fn contract_call_validations_are_cached_per_chain_tip() {
+        let mut rng = get_rng();
+        let context = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .build();
+
+        // The deposit has already been completed, so validation against
+        // the blockchains fails.
+        context
+            .with_stacks_client(|client| {
+                client
+                    .expect_is_deposit_completed()
+                    .returning(|_, _| Box::pin(std::future::ready(Ok(true))));
+            })
+            .await;
+
+        let signer_private_key = PrivateKey::new(&mut rng);
+        context
+            .state()
+            .update_registry_signer_set_info(SignerSetInfo {
+                aggregate_key: Faker.fake_with_rng(&mut rng),
+                signer_set: BTreeSet::from([PublicKey::from_private_key(&signer_private_key)]),
+                signatures_required: 1,
+            });
+        let stacks_chain_tip: model::StacksBlockRef = Faker.fake_with_rng(&mut rng);
+        context
+            .state()
+            .set_stacks_chain_tip(stacks_chain_tip.clone());
+
+        let network = InMemoryNetwork::new();
+        let mut signer = TxSignerEventLoop {
+            context: context.clone(),
+            network: network.connect(),
+            signer_private_key,
+            context_window: 1,
+            wsts_state_machines: LruCache::new(NonZeroUsize::new(100).unwrap()),
+            last_presign_block: None,
+            dkg_begin_pause: None,
+            dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
+            stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+            stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
+        };
+
+        let contract_call =
+            ContractCall::CompleteDepositV1(Box::new(Faker.fake_with_rng(&mut rng)));
+        let request = StacksTransactionSignRequest {
+            aggregate_key: None,
+            contract_tx: contract_call.clone().into(),
+            nonce: 1,
+            tx_fee: 1,
+            txid: Faker.fake_with_rng(&mut rng),
+        };
+        let chain_tip: model::BitcoinBlockRef = Faker.fake_with_rng(&mut rng);
+        let origin: PublicKey = Faker.fake_with_rng(&mut rng);
+
+        let result = signer
+            .assert_valid_stacks_tx_sign_request(&request, &chain_tip, &origin)
+            .await;
+        assert!(result.is_err());
+        // Failed validations are not cached.
+        assert!(signer.stacks_validations.is_empty());
+
+        // Once the call is known to be valid for these chain tips, we
+        // skip the validation.
+        let key = (chain_tip.block_hash, stacks_chain_tip.block_hash);
+        signer
+            .stacks_validations
+            .get_or_insert_mut(key, Default::default)
+            .insert(contract_call);
+        signer
+            .assert_valid_stacks_tx_sign_request(&request, &chain_tip, &origin)
+            .await
+            .unwrap();
+
+        // But a new stacks chain tip means that we need to validate the
+        // call again.
+        let new_stacks_chain_tip: model::StacksBlockRef = Faker.fake_with_rng(&mut rng);
+        context.state().set_stacks_chain_tip(new_stacks_chain_tip);
+        let result = signer
+            .assert_valid_stacks_tx_sign_request(&request, &chain_tip, &origin)
+            .await;
+        assert!(result.is_err());
+
+        // And the cache does not survive a new bitcoin block.
+        context.state().set_stacks_chain_tip(stacks_chain_tip);
+        signer.stacks_validations.clear();
+        let result = signer
+            .assert_valid_stacks_tx_sign_request(&request, &chain_tip, &origin)
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn coordinator_takeovers_are_only_accepted_with_a_quorum_of_silence_attestations() {
+        let mut rng = get_rng();
+        let context = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .modify_settings(|settings| {
+                settings.signer.coordinator_silence_timeout = std::num::NonZeroU64::new(30);
+            })
+            .build();
+
+        let private_keys: Vec<PrivateKey> = std::iter::repeat_with(|| PrivateKey::new(&mut rng))
+            .take(3)
+            .collect();
+        let signer_set: BTreeSet<PublicKey> = private_keys
+            .iter()
+            .map(PublicKey::from_private_key)
+            .collect();
+        context
+            .state()
+            .update_registry_signer_set_info(SignerSetInfo {
+                aggregate_key: Faker.fake_with_rng(&mut rng),
+                signer_set: signer_set.clone(),
+                signatures_required: 2,
+            });
+        let private_key_of = |public_key: PublicKey| {
+            *private_keys
+                .iter()
+                .find(|key| PublicKey::from_private_key(key) == public_key)
+                .unwrap()
+        };
+
+        let network = InMemoryNetwork::new();
+        let mut signer = TxSignerEventLoop {
+            context: context.clone(),
+            network: network.connect(),
+            signer_private_key: PrivateKey::new(&mut rand::rngs::OsRng),
+            context_window: 1,
+            wsts_state_machines: LruCache::new(NonZeroUsize::new(100).unwrap()),
+            last_presign_block: None,
+            dkg_begin_pause: None,
+            dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
+            stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+            stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
+        };
+
+        let chain_tip: model::BitcoinBlockRef = Faker.fake_with_rng(&mut rng);
+        let tip = &chain_tip.block_hash;
+        let coordinator =
+            crate::transaction_coordinator::coordinator_public_key(tip, &signer_set).unwrap();
+        let deputy = crate::transaction_coordinator::deputy_public_key(tip, &signer_set).unwrap();
+        let third = *signer_set
+            .iter()
+            .find(|key| **key != coordinator && **key != deputy)
+            .unwrap();
+
+        let attest = |signer: PublicKey, chain_tip: &model::BitcoinBlockHash| {
+            message::CoordinatorSilenceAttestation::new(
+                &private_key_of(signer),
+                chain_tip,
+                coordinator,
+            )
+        };
+        let takeover_with = |attestations: Vec<message::CoordinatorSilenceAttestation>| {
+            message::CoordinatorTakeover {
+                coordinator_public_key: coordinator,
+                attestations,
+            }
+        };
+        let quorum = vec![attest(deputy, tip), attest(third, tip)];
+
+        // Only the deputy may take over.
+        let takeover = takeover_with(quorum.clone());
+        let result = signer
+            .handle_coordinator_takeover(&takeover, coordinator, &chain_tip)
+            .await;
+        assert!(matches!(
+            result,
+            Err(Error::RejectedCoordinatorTakeover { .. })
+        ));
+
+        // And only from the coordinator of the chain tip.
+        let other_takeover = message::CoordinatorTakeover {
+            coordinator_public_key: deputy,
+            attestations: quorum.clone(),
+        };
+        let result = signer
+            .handle_coordinator_takeover(&other_takeover, deputy, &chain_tip)
+            .await;
+        assert!(matches!(
+            result,
+            Err(Error::RejectedCoordinatorTakeover { .. })
+        ));
+
+        // We have never heard from the coordinator, but the deputy's own
+        // attestation is not enough, and neither is the coordinator's,
+        // nor an attestation made for another chain tip.
+        let other_tip: model::BitcoinBlockHash = Faker.fake_with_rng(&mut rng);
+        let too_few = [
+            vec![attest(deputy, tip)],
+            vec![attest(deputy, tip), attest(deputy, tip)],
+            vec![attest(deputy, tip), attest(coordinator, tip)],
+            vec![attest(deputy, tip), attest(third, &other_tip)],
+        ];
+        for attestations in too_few {
+            let result = signer
+                .handle_coordinator_takeover(&takeover_with(attestations), deputy, &chain_tip)
+                .await;
+            assert!(matches!(
+                result,
+                Err(Error::RejectedCoordinatorTakeover { .. })
+            ));
+        }
+        assert!(context.state().coordinator_takeover(tip).is_none());
+        assert!(
+            crate::transaction_coordinator::given_key_is_acting_coordinator(
+                &context,
+                coordinator,
+                tip
+            )
+        );
+
+        // A quorum of attestations is accepted even though we have just
+        // heard from the coordinator ourselves, so that every signer makes
+        // the same decision.
+        context.state().record_coordinator_activity(*tip);
+        signer
+            .handle_coordinator_takeover(&takeover, deputy, &chain_tip)
+            .await
+            .unwrap();
+
+        assert_eq!(context.state().coordinator_takeover(tip), Some(deputy));
+        assert!(
+            crate::transaction_coordinator::given_key_is_acting_coordinator(&context, deputy, tip)
+        );
+        assert!(
+            !crate::transaction_coordinator::given_key_is_acting_coordinator(
+                &context,
+                coordinator,
+                tip
+            )
+        );
+
+        let record = context
+            .get_storage()
+            .get_coordinator_takeover(tip)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(record.coordinator_public_key, coordinator);
+        assert_eq!(record.deputy_public_key, deputy);
+    }
+
+    #[tokio::test]
+    async fn signers_attest_to_coordinator_silence_only_if_they_saw_it_too() {
+        let mut rng = get_rng();
+        let context = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .modify_settings(|settings| {
+                settings.signer.coordinator_silence_timeout = std::num::NonZeroU64::new(30);
+            })
+            .build();
+
+        let private_keys: Vec<PrivateKey> = std::iter::repeat_with(|| PrivateKey::new(&mut rng))
+            .take(3)
+            .collect();
+        let signer_set: BTreeSet<PublicKey> = private_keys
+            .iter()
+            .map(PublicKey::from_private_key)
+            .collect();
+        context
+            .state()
+            .update_registry_signer_set_info(SignerSetInfo {
+                aggregate_key: Faker.fake_with_rng(&mut rng),
+                signer_set: signer_set.clone(),
+                signatures_required: 2,
+            });
+
+        // Find a chain tip where our signer is neither the coordinator nor
+        // the deputy.
+        let our_key = private_keys[0];
+        let our_public_key = PublicKey::from_private_key(&our_key);
+        let (chain_tip, coordinator, deputy) = loop {
+            let chain_tip: model::BitcoinBlockRef = Faker.fake_with_rng(&mut rng);
+            let tip = &chain_tip.block_hash;
+            let coordinator =
+                crate::transaction_coordinator::coordinator_public_key(tip, &signer_set).unwrap();
+            let deputy =
+                crate::transaction_coordinator::deputy_public_key(tip, &signer_set).unwrap();
+            if coordinator != our_public_key && deputy != our_public_key {
+                break (chain_tip, coordinator, deputy);
+            }
+        };
+        let deputy_key = *private_keys
+            .iter()
+            .find(|key| PublicKey::from_private_key(key) == deputy)
+            .unwrap();
+
+        let network = InMemoryNetwork::new();
+        let mut signer = TxSignerEventLoop {
+            context: context.clone(),
+            network: network.connect(),
+            signer_private_key: our_key,
+            context_window: 1,
+            wsts_state_machines: LruCache::new(NonZeroUsize::new(100).unwrap()),
+            last_presign_block: None,
+            dkg_begin_pause: None,
+            dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
+            stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+            stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
+        };
+
+        let tip = &chain_tip.block_hash;
+        let attestation =
+            message::CoordinatorSilenceAttestation::new(&deputy_key, tip, coordinator);
+
+        // An attestation must come from the signer that signed it.
+        let result = signer
+            .handle_coordinator_silence_attestation(&attestation, coordinator, &chain_tip)
+            .await;
+        assert!(matches!(
+            result,
+            Err(Error::RejectedSilenceAttestation { .. })
+        ));
+
+        // We have just heard from the coordinator, so we keep the deputy's
+        // attestation but do not add our own.
+        context.state().record_coordinator_activity(*tip);
+        signer
+            .handle_coordinator_silence_attestation(&attestation, deputy, &chain_tip)
+            .await
+            .unwrap();
+        let attesters: Vec<PublicKey> = context
+            .state()
+            .silence_attestations(tip)
+            .iter()
+            .map(|attestation| attestation.signer_public_key)
+            .collect();
+        assert_eq!(attesters, vec![deputy]);
+
+        // At another chain tip we have not heard from the coordinator at
+        // all, so we attest to its silence as well.
+        let (other_chain_tip, coordinator) = loop {
+            let other_chain_tip: model::BitcoinBlockRef = Faker.fake_with_rng(&mut rng);
+            let coordinator = crate::transaction_coordinator::coordinator_public_key(
+                &other_chain_tip.block_hash,
+                &signer_set,
+            )
+            .unwrap();
+            if coordinator != our_public_key {
+                break (other_chain_tip, coordinator);
+            }
+        };
+        let other_tip = &other_chain_tip.block_hash;
+        let attester = *private_keys
+            .iter()
+            .find(|key| {
+                let public_key = PublicKey::from_private_key(key);
+                public_key != coordinator && public_key != our_public_key
+            })
+            .unwrap();
+        let attestation =
+            message::CoordinatorSilenceAttestation::new(&attester, other_tip, coordinator);
+        let sender = PublicKey::from_private_key(&attester);
+        signer
+            .handle_coordinator_silence_attestation(&attestation, sender, &other_chain_tip)
+            .await
+            .unwrap();
+
+        let attestations = context.state().silence_attestations(other_tip);
+        let attesters: BTreeSet<PublicKey> = attestations
+            .iter()
+            .map(|attestation| attestation.signer_public_key)
+            .collect();
+        assert_eq!(attesters, BTreeSet::from([sender, our_public_key]));
+
+        let attesters = crate::transaction_coordinator::silence_attesters(
+            &attestations,
+            other_tip,
+            &coordinator,
+            &signer_set,
+        );
+        assert_eq!(attesters.len(), 2);
+    }
+
     #[tokio::test]
     async fn test_handle_wsts_message_non_canonical_dkg_begin() {
         let context = TestContext::builder()
@@ -1904,6 +3196,7 @@ mod tests {
             dkg_begin_pause: None,
             dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
             stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+            stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
         };
 
         // Create a DkgBegin message to be handled by the signer.
@@ -1992,6 +3285,7 @@ mod tests {
             dkg_begin_pause: None,
             dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
             stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+            stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
         };
 
         let msg = message::WstsMessage {
@@ -2106,6 +3400,7 @@ mod tests {
                     dkg_begin_pause: None,
                     dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
                     stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+                    stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
                 };
 
                 // The is_coordinator function checks whether the
@@ -2193,6 +3488,7 @@ mod tests {
                 dkg_begin_pause: None,
                 dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
                 stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+                stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
             };
 
             // Check if the coordinator event loop says this signer is coordinator
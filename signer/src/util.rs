@@ -6,9 +6,9 @@ use std::{
     ops::Deref,
     sync::{
         Arc,
-        atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering},
+        atomic::{AtomicBool, AtomicU8, AtomicU64, AtomicUsize, Ordering},
     },
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use thiserror::Error;
@@ -87,6 +87,13 @@ pub enum FallbackClientError {
     NoEndpoints,
 }
 
+/// A client whose endpoint can be probed for liveness.
+pub trait HealthCheck {
+    /// Return whether the endpoint of the client is up and serving
+    /// requests.
+    fn is_healthy(&self) -> impl Future<Output = bool> + Send;
+}
+
 /// A fallback-wrapper that can failover to other clients if the current client fails.
 pub struct ApiFallbackClient<T> {
     inner: Arc<InnerApiFallbackClient<T>>,
@@ -111,6 +118,15 @@ pub struct InnerApiFallbackClient<T> {
     inner_clients: Vec<T>,
     last_client_index: AtomicUsize,
     retry_count: AtomicU8,
+    /// How long, in milliseconds, to stay on a fallback client before
+    /// checking whether the primary client is healthy again. Zero means
+    /// that we never switch back to the primary client on our own.
+    primary_recheck_interval_ms: AtomicU64,
+    /// When we last moved off of the primary client, or last checked its
+    /// health, in milliseconds since `created_at`.
+    primary_checked_at_ms: AtomicU64,
+    /// When the fallback client was created.
+    created_at: Instant,
 }
 
 /// A context that provides information about the current retry attempt and
@@ -194,6 +210,19 @@ impl<T> InnerApiFallbackClient<T> {
         self.retry_count.store(retry_count, Ordering::Relaxed);
     }
 
+    /// Set how long to stay on a fallback client before checking whether
+    /// the primary client, the first one given to
+    /// [`ApiFallbackClient::new`], is healthy again. A zero duration
+    /// disables switching back to the primary client.
+    ///
+    /// This only has an effect on calls made through
+    /// [`Self::exec_preferring_primary`].
+    pub fn set_primary_recheck_interval(&self, interval: Duration) {
+        let interval_ms = u64::try_from(interval.as_millis()).unwrap_or(u64::MAX);
+        self.primary_recheck_interval_ms
+            .store(interval_ms, Ordering::Relaxed);
+    }
+
     /// Get a reference to the current inner API client.
     pub fn get_client(&self) -> &T {
         &self.inner_clients[self.last_client_index.load(Ordering::Relaxed)]
@@ -225,6 +254,11 @@ impl<T> InnerApiFallbackClient<T> {
                     return Err(error.into());
                 }
 
+                if client_index == 0 {
+                    self.primary_checked_at_ms
+                        .store(self.elapsed_ms(), Ordering::Relaxed);
+                }
+
                 self.last_client_index.store(
                     (client_index + 1) % self.inner_clients.len(),
                     Ordering::Relaxed,
@@ -238,6 +272,69 @@ impl<T> InnerApiFallbackClient<T> {
 
         Err(FallbackClientError::AllClientsFailed.into())
     }
+
+    /// The number of milliseconds since the fallback client was created.
+    fn elapsed_ms(&self) -> u64 {
+        u64::try_from(self.created_at.elapsed().as_millis()).unwrap_or(u64::MAX)
+    }
+}
+
+impl<T: HealthCheck> InnerApiFallbackClient<T> {
+    /// Switch back to the primary client if we have been using a fallback
+    /// client for at least the primary recheck interval and the primary
+    /// client is healthy again.
+    ///
+    /// If the primary client is still unhealthy then we stay on the
+    /// current client and check again after another interval.
+    pub async fn restore_primary(&self) {
+        let interval_ms = self.primary_recheck_interval_ms.load(Ordering::Relaxed);
+        if interval_ms == 0 || self.last_client_index.load(Ordering::Relaxed) == 0 {
+            return;
+        }
+
+        let now_ms = self.elapsed_ms();
+        let checked_at_ms = self.primary_checked_at_ms.load(Ordering::Relaxed);
+        if now_ms.saturating_sub(checked_at_ms) < interval_ms {
+            return;
+        }
+
+        // Only one caller probes the primary client for each interval.
+        let claimed = self.primary_checked_at_ms.compare_exchange(
+            checked_at_ms,
+            now_ms,
+            Ordering::Relaxed,
+            Ordering::Relaxed,
+        );
+        if claimed.is_err() {
+            return;
+        }
+
+        if self.inner_clients[0].is_healthy().await {
+            tracing::info!("primary client is healthy again, switching back to it");
+            self.last_client_index.store(0, Ordering::Relaxed);
+        } else {
+            tracing::debug!("primary client is still unhealthy, staying on the fallback client");
+        }
+    }
+
+    /// Execute a closure like [`Self::exec`], except that the current
+    /// client sticks to the primary client: after failing over, we switch
+    /// back to the primary client once it passes a health check.
+    ///
+    /// See [`Self::set_primary_recheck_interval`] for how often the health
+    /// of the primary client is checked.
+    pub async fn exec_preferring_primary<'a, R, E, F>(
+        &'a self,
+        f: impl Fn(&'a T, RetryContext) -> F,
+    ) -> Result<R, Error>
+    where
+        E: std::error::Error + std::fmt::Debug,
+        E: Into<Error>,
+        F: Future<Output = Result<R, E>> + 'a,
+    {
+        self.restore_primary().await;
+        self.exec(f).await
+    }
 }
 
 impl<T> ApiFallbackClient<T> {
@@ -253,6 +350,9 @@ impl<T> ApiFallbackClient<T> {
             inner_clients: clients,
             last_client_index: AtomicUsize::new(0),
             retry_count: AtomicU8::new(retry_count as u8),
+            primary_recheck_interval_ms: AtomicU64::new(0),
+            primary_checked_at_ms: AtomicU64::new(0),
+            created_at: Instant::now(),
         };
 
         Ok(Self { inner: Arc::new(inner) })
@@ -283,6 +383,12 @@ mod tests {
         }
     }
 
+    impl HealthCheck for MockClient {
+        async fn is_healthy(&self) -> bool {
+            self.should_succeed
+        }
+    }
+
     impl From<Url> for MockClient {
         fn from(url: Url) -> Self {
             Self {
@@ -474,4 +580,69 @@ mod tests {
         // (which was just randomly chosen, it has no significance)
         assert!(matches!(result.unwrap_err(), Error::Dummy));
     }
+
+    #[tokio::test]
+    async fn client_switches_back_to_healthy_primary() {
+        let client = ApiFallbackClient::<MockClient>::from(
+            &[
+                Url::parse("http://ok/1").unwrap(),
+                Url::parse("http://ok/2").unwrap(),
+            ][..],
+        );
+        client.set_primary_recheck_interval(Duration::from_millis(50));
+
+        // The primary client has a transient failure, so we fail over to
+        // the second client.
+        let result = client
+            .exec_preferring_primary(|client, retry| async move {
+                match retry.current_attempt() {
+                    1 => Err(Error::Dummy),
+                    _ => client.call().await,
+                }
+            })
+            .await;
+        assert!(result.is_ok());
+        assert_eq!(client.last_client_index.load(Ordering::Relaxed), 1);
+
+        // We stick with the fallback client until the recheck interval
+        // has elapsed.
+        client
+            .exec_preferring_primary(|client, _| client.call())
+            .await
+            .unwrap();
+        assert_eq!(client.last_client_index.load(Ordering::Relaxed), 1);
+
+        // Once it has elapsed, the primary client passes its health check
+        // and we switch back to it.
+        Duration::from_millis(60).sleep().await;
+        client
+            .exec_preferring_primary(|client, _| client.call())
+            .await
+            .unwrap();
+        assert_eq!(client.last_client_index.load(Ordering::Relaxed), 0);
+    }
+
+    #[tokio::test]
+    async fn client_stays_on_fallback_while_primary_is_unhealthy() {
+        let client = ApiFallbackClient::<MockClient>::from(
+            &[
+                Url::parse("http://fail/1").unwrap(),
+                Url::parse("http://ok/2").unwrap(),
+            ][..],
+        );
+        client.set_primary_recheck_interval(Duration::from_millis(1));
+
+        client
+            .exec_preferring_primary(|client, _| client.call())
+            .await
+            .unwrap();
+        assert_eq!(client.last_client_index.load(Ordering::Relaxed), 1);
+
+        Duration::from_millis(10).sleep().await;
+        client
+            .exec_preferring_primary(|client, _| client.call())
+            .await
+            .unwrap();
+        assert_eq!(client.last_client_index.load(Ordering::Relaxed), 1);
+    }
 }
@@ -0,0 +1,528 @@
+//! This module contains the watchtower, a background task that audits the
+//! behavior of the signers using only public data.
+//!
+//! The watchtower does not hold any keys and does not take part in any
+//! signing round. Instead, it follows the bitcoin blockchain through the
+//! block observer and, for each new bitcoin block, reconstructs what a
+//! correct signer set should have done and compares it with what
+//! actually happened on chain:
+//!
+//! 1. Each deposit swept by a sweep transaction in the block must be a
+//!    known deposit request, and the fee assessed against it must not
+//!    exceed the max fee of the request.
+//! 2. Each deposit swept by a sweep transaction in the block that is
+//!    [`WATCHTOWER_MINT_DEPTH`] blocks below the chain tip must have been
+//!    completed on Stacks by then, and the complete-deposit contract call
+//!    must pass the same validation that the signers run before signing
+//!    it.
+//!
+//! Any discrepancy is logged and counted in the
+//! [`Metrics::WatchtowerDiscrepanciesTotal`] metric, so that third parties
+//! can audit the bridge without running a signer.
+
+use std::ops::Deref as _;
+
+use bitcoin::OutPoint;
+use bitcoin::Txid;
+use futures::StreamExt as _;
+
+use crate::bitcoin::rpc::BitcoinTxInfo;
+use crate::context::Context;
+use crate::context::SignerCommand;
+use crate::context::SignerEvent;
+use crate::context::SignerSignal;
+use crate::error::Error;
+use crate::metrics::Metrics;
+use crate::stacks::contracts::CompleteDepositV1;
+use crate::stacks::contracts::ReqContext;
+use crate::storage::DbRead as _;
+use crate::storage::model::BitcoinBlockRef;
+use crate::storage::model::StacksTxId;
+use crate::storage::util;
+
+/// The number of bitcoin blocks after a deposit is swept by which it is
+/// expected to have been completed on Stacks.
+pub const WATCHTOWER_MINT_DEPTH: u64 = 6;
+
+/// A difference between what a correct signer set should have done and
+/// what happened on chain.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error, strum::IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum Discrepancy {
+    /// A sweep transaction spent an outpoint that is not a known deposit
+    /// request.
+    #[error("sweep transaction {sweep_txid} spent {outpoint}, which is not a deposit request")]
+    UnknownDepositSwept {
+        /// The ID of the sweep transaction.
+        sweep_txid: Txid,
+        /// The outpoint that was spent.
+        outpoint: OutPoint,
+    },
+    /// The fee assessed against a swept deposit exceeds the max fee of
+    /// the deposit request.
+    #[error(
+        "sweep transaction {sweep_txid} assessed a fee of {assessed_fee} sats against deposit {outpoint}, more than its max fee of {max_fee}"
+    )]
+    DepositFeeTooHigh {
+        /// The ID of the sweep transaction.
+        sweep_txid: Txid,
+        /// The outpoint of the deposit.
+        outpoint: OutPoint,
+        /// The fee assessed against the deposit, in sats.
+        assessed_fee: u64,
+        /// The max fee of the deposit request, in sats.
+        max_fee: u64,
+    },
+    /// A swept deposit has not been completed on Stacks within
+    /// [`WATCHTOWER_MINT_DEPTH`] bitcoin blocks.
+    #[error("deposit {outpoint} swept by {sweep_txid} has not been completed on Stacks")]
+    DepositNotCompleted {
+        /// The ID of the sweep transaction.
+        sweep_txid: Txid,
+        /// The outpoint of the deposit.
+        outpoint: OutPoint,
+    },
+    /// A complete-deposit contract call on Stacks failed validation.
+    #[error("complete-deposit call {stacks_txid} for deposit {outpoint} is invalid: {reason}")]
+    InvalidCompleteDeposit {
+        /// The ID of the stacks transaction that completed the deposit.
+        stacks_txid: StacksTxId,
+        /// The outpoint of the deposit.
+        outpoint: OutPoint,
+        /// Why the contract call failed validation.
+        reason: String,
+    },
+}
+
+/// The outcome of auditing a bitcoin block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockAudit {
+    /// The bitcoin block that was audited.
+    pub block: BitcoinBlockRef,
+    /// The number of sweep transactions that were audited.
+    pub sweeps_audited: usize,
+    /// The discrepancies that were found.
+    pub discrepancies: Vec<Discrepancy>,
+}
+
+/// A background task that audits the behavior of the signers each time
+/// the block observer observes a new bitcoin block.
+pub struct Watchtower<C> {
+    /// Signer context.
+    context: C,
+}
+
+/// This function defines which messages the watchtower is interested in.
+fn run_loop_message_filter(signal: &SignerSignal) -> bool {
+    matches!(
+        signal,
+        SignerSignal::Command(SignerCommand::Shutdown)
+            | SignerSignal::Event(SignerEvent::BitcoinBlockObserved(_))
+    )
+}
+
+impl<C> Watchtower<C>
+where
+    C: Context,
+{
+    /// Creates a new Watchtower.
+    pub fn new(context: C) -> Self {
+        Self { context }
+    }
+
+    /// Runs the Watchtower, which audits each bitcoin block observed by
+    /// the block observer until the signer is shut down.
+    #[tracing::instrument(skip_all, name = "watchtower")]
+    pub async fn run(self) -> Result<(), Error> {
+        let mut signal_stream = self.context.as_signal_stream(run_loop_message_filter);
+
+        while let Some(message) = signal_stream.next().await {
+            match message {
+                SignerSignal::Command(SignerCommand::Shutdown) => break,
+                SignerSignal::Event(SignerEvent::BitcoinBlockObserved(block_ref)) => {
+                    if let Err(error) = self.audit_block(&block_ref).await {
+                        tracing::warn!(%error, "could not audit the bitcoin block");
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        tracing::info!("watchtower has stopped");
+        Ok(())
+    }
+
+    /// Audit the sweep transactions in the given bitcoin chain tip, and
+    /// the complete-deposit contract calls for the sweep transactions in
+    /// the block that is [`WATCHTOWER_MINT_DEPTH`] blocks below it.
+    #[tracing::instrument(skip_all, fields(
+        block_hash = %chain_tip.block_hash,
+        block_height = %chain_tip.block_height,
+    ))]
+    pub async fn audit_block(&self, chain_tip: &BitcoinBlockRef) -> Result<BlockAudit, Error> {
+        let sweeps = self.get_sweeps(chain_tip).await?;
+        let mut discrepancies = Vec::new();
+
+        for sweep in &sweeps {
+            discrepancies.extend(self.audit_sweep(sweep).await?);
+        }
+
+        if let Some(block) = self.get_ancestor(chain_tip, WATCHTOWER_MINT_DEPTH).await? {
+            for sweep in self.get_sweeps(&block).await? {
+                discrepancies.extend(self.audit_mints(chain_tip, &sweep).await?);
+            }
+        }
+
+        for discrepancy in &discrepancies {
+            tracing::error!(%discrepancy, "the signers deviated from the expected behavior");
+            Metrics::increment_watchtower_discrepancies(discrepancy.into());
+        }
+
+        Ok(BlockAudit {
+            block: *chain_tip,
+            sweeps_audited: sweeps.len(),
+            discrepancies,
+        })
+    }
+
+    /// Check that each deposit swept by the given sweep transaction is a
+    /// known deposit request and that the fee assessed against it does
+    /// not exceed its max fee.
+    async fn audit_sweep(&self, sweep: &BitcoinTxInfo) -> Result<Vec<Discrepancy>, Error> {
+        let db = self.context.get_storage();
+        let sweep_txid = sweep.tx.compute_txid();
+        let mut discrepancies = Vec::new();
+
+        // The first input is always the signers' UTXO.
+        for tx_in in sweep.tx.input.iter().skip(1) {
+            let outpoint = tx_in.previous_output;
+            let txid = outpoint.txid.into();
+            let Some(deposit) = db.get_deposit_request(&txid, outpoint.vout).await? else {
+                discrepancies.push(Discrepancy::UnknownDepositSwept { sweep_txid, outpoint });
+                continue;
+            };
+
+            let Some(assessed_fee) = sweep.assess_input_fee(&outpoint) else {
+                tracing::warn!(%sweep_txid, "bitcoin-core did not report the fee of the sweep");
+                break;
+            };
+            if assessed_fee.to_sat() > deposit.max_fee {
+                discrepancies.push(Discrepancy::DepositFeeTooHigh {
+                    sweep_txid,
+                    outpoint,
+                    assessed_fee: assessed_fee.to_sat(),
+                    max_fee: deposit.max_fee,
+                });
+            }
+        }
+
+        Ok(discrepancies)
+    }
+
+    /// Check that each known deposit swept by the given sweep transaction
+    /// has been completed on Stacks with a valid complete-deposit
+    /// contract call.
+    async fn audit_mints(
+        &self,
+        chain_tip: &BitcoinBlockRef,
+        sweep: &BitcoinTxInfo,
+    ) -> Result<Vec<Discrepancy>, Error> {
+        let state = self.context.state();
+        let (Some(signer_set_info), Some(stacks_chain_tip)) =
+            (state.registry_signer_set_info(), state.stacks_chain_tip())
+        else {
+            tracing::debug!("no signer set or stacks chain tip yet; skipping the mint audit");
+            return Ok(Vec::new());
+        };
+
+        let config = self.context.config();
+        let req_ctx = ReqContext {
+            chain_tip: *chain_tip,
+            stacks_chain_tip: stacks_chain_tip.block_hash,
+            context_window: config.signer.context_window,
+            origin: signer_set_info.aggregate_key,
+            aggregate_key: signer_set_info.aggregate_key,
+            signatures_required: signer_set_info.signatures_required,
+            deployer: config.signer.deployer.clone(),
+        };
+
+        let db = self.context.get_storage();
+        let sweep_txid = sweep.tx.compute_txid();
+        let mut discrepancies = Vec::new();
+
+        for tx_in in sweep.tx.input.iter().skip(1) {
+            let outpoint = tx_in.previous_output;
+            let txid = outpoint.txid.into();
+            // Unknown deposits were reported when the sweep was audited.
+            let Some(deposit) = db.get_deposit_request(&txid, outpoint.vout).await? else {
+                continue;
+            };
+            let Some(event) = db.get_completed_deposit_event(&outpoint).await? else {
+                discrepancies.push(Discrepancy::DepositNotCompleted { sweep_txid, outpoint });
+                continue;
+            };
+
+            let contract_call = CompleteDepositV1 {
+                outpoint,
+                amount: event.amount,
+                recipient: deposit.recipient.deref().clone(),
+                deployer: req_ctx.deployer.clone(),
+                sweep_txid: event.sweep_txid,
+                sweep_block_hash: event.sweep_block_hash,
+                sweep_block_height: event.sweep_block_height,
+            };
+            if let Err(error) = contract_call.audit(&self.context, &req_ctx).await {
+                discrepancies.push(Discrepancy::InvalidCompleteDeposit {
+                    stacks_txid: event.txid,
+                    outpoint,
+                    reason: error.to_string(),
+                });
+            }
+        }
+
+        Ok(discrepancies)
+    }
+
+    /// Fetch the sweep transactions in the given bitcoin block.
+    async fn get_sweeps(&self, block: &BitcoinBlockRef) -> Result<Vec<BitcoinTxInfo>, Error> {
+        let db = self.context.get_storage();
+        let bitcoin_client = self.context.get_bitcoin_client();
+        let activity = db.get_block_activity(&block.block_hash).await?;
+
+        let mut sweeps = Vec::with_capacity(activity.sweep_txids.len());
+        for txid in &activity.sweep_txids {
+            match util::get_bitcoin_tx_info(&db, &bitcoin_client, txid, &block.block_hash).await? {
+                Some(sweep) => sweeps.push(sweep),
+                None => tracing::warn!(%txid, "bitcoin-core does not know about the sweep"),
+            }
+        }
+        Ok(sweeps)
+    }
+
+    /// Find the ancestor of the given block that is `depth` blocks below
+    /// it, returning `None` if we do not have that block.
+    async fn get_ancestor(
+        &self,
+        block: &BitcoinBlockRef,
+        depth: u64,
+    ) -> Result<Option<BitcoinBlockRef>, Error> {
+        let db = self.context.get_storage();
+        let mut block_hash = block.block_hash;
+        for _ in 0..depth {
+            let Some(block) = db.get_bitcoin_block(&block_hash).await? else {
+                return Ok(None);
+            };
+            block_hash = block.parent_hash;
+        }
+
+        Ok(db
+            .get_bitcoin_block(&block_hash)
+            .await?
+            .map(|block| BitcoinBlockRef::from(&block)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::Amount;
+    use bitcoin::ScriptBuf;
+    use bitcoin::Transaction;
+    use bitcoin::TxIn;
+    use bitcoin::TxOut;
+    use fake::Fake as _;
+    use fake::Faker;
+
+    use crate::stacks::api::SignerSetInfo;
+    use crate::storage::DbWrite as _;
+    use crate::storage::memory::SharedStore;
+    use crate::storage::model;
+    use crate::testing::context::*;
+    use crate::testing::get_rng;
+
+    use super::*;
+
+    type MockedContext = TestContext<
+        SharedStore,
+        WrappedMockBitcoinInteract,
+        WrappedMockStacksInteract,
+        WrappedMockEmilyInteract,
+    >;
+
+    /// Record a sweep transaction in the given block that spends the
+    /// signers' UTXO and the given outpoints, and return its ID.
+    async fn write_sweep(
+        ctx: &MockedContext,
+        block: &model::BitcoinBlock,
+        outpoints: &[OutPoint],
+    ) -> Txid {
+        let signers_outpoint = OutPoint::new(Faker.fake::<model::BitcoinTxId>().into(), 0);
+        let input = std::iter::once(signers_outpoint)
+            .chain(outpoints.iter().copied())
+            .map(|previous_output| TxIn {
+                previous_output,
+                ..Default::default()
+            })
+            .collect();
+        let output = TxOut {
+            value: Amount::from_sat(1_000),
+            script_pubkey: ScriptBuf::new(),
+        };
+        let tx = Transaction {
+            version: bitcoin::transaction::Version::TWO,
+            lock_time: bitcoin::absolute::LockTime::ZERO,
+            input,
+            output: vec![output.clone(), output],
+        };
+        let txid = tx.compute_txid();
+
+        let db = ctx.get_storage_mut();
+        let tx_ref = model::BitcoinTxRef {
+            txid: txid.into(),
+            block_hash: block.block_hash,
+        };
+        db.write_bitcoin_transaction(&tx_ref).await.unwrap();
+        let prevout = model::TxPrevout {
+            txid: txid.into(),
+            prevout_txid: signers_outpoint.txid.into(),
+            prevout_output_index: signers_outpoint.vout,
+            prevout_type: model::TxPrevoutType::SignersInput,
+            ..Faker.fake()
+        };
+        db.write_tx_prevout(&prevout).await.unwrap();
+
+        let info = BitcoinTxInfo {
+            fee: Some(Amount::from_sat(10_000)),
+            tx,
+            vin: Vec::new(),
+        };
+        ctx.with_bitcoin_client(move |client| {
+            client
+                .expect_get_tx_info()
+                .returning(move |_, _| Box::pin(std::future::ready(Ok(Some(info.clone())))));
+        })
+        .await;
+
+        txid
+    }
+
+    /// Write a chain of `length` bitcoin blocks and return them, lowest
+    /// first.
+    async fn write_blocks(ctx: &MockedContext, length: u64) -> Vec<model::BitcoinBlock> {
+        let mut blocks: Vec<model::BitcoinBlock> = Vec::new();
+        for height in 100..100 + length {
+            let block = model::BitcoinBlock {
+                block_hash: Faker.fake(),
+                block_height: height.into(),
+                parent_hash: blocks.last().map_or_else(|| Faker.fake(), |b| b.block_hash),
+            };
+            ctx.get_storage_mut()
+                .write_bitcoin_block(&block)
+                .await
+                .unwrap();
+            blocks.push(block);
+        }
+        blocks
+    }
+
+    #[tokio::test]
+    async fn audit_block_flags_unknown_deposits_and_excessive_fees() {
+        let ctx = TestContext::default_mocked();
+        let blocks = write_blocks(&ctx, 1).await;
+
+        // The signers know about this deposit, but it only allows them to
+        // charge a single sat in fees.
+        let mut deposit: model::DepositRequest = Faker.fake();
+        deposit.max_fee = 1;
+        ctx.get_storage_mut()
+            .write_deposit_request(&deposit)
+            .await
+            .unwrap();
+
+        let deposit_outpoint = deposit.outpoint();
+        let unknown_outpoint = OutPoint::new(Faker.fake::<model::BitcoinTxId>().into(), 1);
+        let sweep_txid = write_sweep(&ctx, &blocks[0], &[deposit_outpoint, unknown_outpoint]).await;
+
+        let watchtower = Watchtower::new(ctx.clone());
+        let chain_tip = BitcoinBlockRef::from(&blocks[0]);
+        let audit = watchtower.audit_block(&chain_tip).await.unwrap();
+
+        assert_eq!(audit.block, chain_tip);
+        assert_eq!(audit.sweeps_audited, 1);
+        assert_eq!(audit.discrepancies.len(), 2);
+        assert!(matches!(
+            audit.discrepancies[0],
+            Discrepancy::DepositFeeTooHigh { sweep_txid: txid, outpoint, max_fee: 1, .. }
+                if txid == sweep_txid && outpoint == deposit_outpoint
+        ));
+        assert_eq!(
+            audit.discrepancies[1],
+            Discrepancy::UnknownDepositSwept {
+                sweep_txid,
+                outpoint: unknown_outpoint,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn audit_block_checks_deposits_were_completed() {
+        let mut rng = get_rng();
+        let ctx = TestContext::default_mocked();
+        let blocks = write_blocks(&ctx, WATCHTOWER_MINT_DEPTH + 1).await;
+
+        ctx.state().update_registry_signer_set_info(SignerSetInfo {
+            aggregate_key: Faker.fake_with_rng(&mut rng),
+            signer_set: Default::default(),
+            signatures_required: 1,
+        });
+        ctx.state()
+            .set_stacks_chain_tip(Faker.fake_with_rng(&mut rng));
+
+        let mut deposit: model::DepositRequest = Faker.fake_with_rng(&mut rng);
+        deposit.max_fee = deposit.amount;
+        ctx.get_storage_mut()
+            .write_deposit_request(&deposit)
+            .await
+            .unwrap();
+
+        let sweep_block = &blocks[0];
+        let outpoint = deposit.outpoint();
+        let sweep_txid = write_sweep(&ctx, sweep_block, &[outpoint]).await;
+
+        let watchtower = Watchtower::new(ctx.clone());
+        let chain_tip = BitcoinBlockRef::from(blocks.last().unwrap());
+
+        // The deposit was swept long enough ago that it should have been
+        // completed by now.
+        let audit = watchtower.audit_block(&chain_tip).await.unwrap();
+        assert_eq!(audit.sweeps_audited, 0);
+        assert_eq!(
+            audit.discrepancies,
+            vec![Discrepancy::DepositNotCompleted { sweep_txid, outpoint }]
+        );
+
+        // The deposit was completed, but the sweep transaction that
+        // bitcoin-core returns does not have the details needed to tie
+        // it to the signers.
+        let event = model::CompletedDepositEvent {
+            txid: Faker.fake_with_rng(&mut rng),
+            block_id: Faker.fake_with_rng(&mut rng),
+            amount: deposit.amount,
+            outpoint,
+            sweep_block_hash: sweep_block.block_hash,
+            sweep_block_height: sweep_block.block_height,
+            sweep_txid: sweep_txid.into(),
+        };
+        ctx.get_storage_mut()
+            .write_completed_deposit_event(&event)
+            .await
+            .unwrap();
+
+        let audit = watchtower.audit_block(&chain_tip).await.unwrap();
+        assert_eq!(audit.discrepancies.len(), 1);
+        assert!(matches!(
+            &audit.discrepancies[0],
+            Discrepancy::InvalidCompleteDeposit { stacks_txid, outpoint: op, .. }
+                if *stacks_txid == event.txid && *op == outpoint
+        ));
+    }
+}
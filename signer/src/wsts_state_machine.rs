@@ -13,6 +13,8 @@ use crate::keys::PrivateKey;
 use crate::keys::PublicKey;
 use crate::keys::PublicKeyXOnly;
 use crate::keys::SignerScriptPubKey as _;
+use crate::secret::REDACTED;
+use crate::secret::Secret;
 use crate::storage;
 use crate::storage::model;
 use crate::storage::model::BitcoinBlockHash;
@@ -21,8 +23,9 @@ use crate::storage::model::BitcoinBlockRef;
 use crate::storage::model::DkgSharesStatus;
 use crate::storage::model::SigHash;
 
+use rand::CryptoRng;
+use rand::RngCore;
 use rand::SeedableRng as _;
-use rand::rngs::OsRng;
 use rand_chacha::ChaCha20Rng;
 use sha2::Digest as _;
 use sha2::Sha256;
@@ -442,7 +445,10 @@ impl WstsCoordinator for FrostCoordinator {
 }
 
 /// Wrapper around a WSTS signer state machine
-#[derive(Debug, Clone, PartialEq)]
+///
+/// The inner state machine holds the signer's private DKG shares, so it
+/// is redacted when formatted.
+#[derive(Clone, PartialEq)]
 pub struct SignerStateMachine {
     /// The inner WSTS state machine that this type wraps
     inner: wsts::state_machine::signer::Signer,
@@ -453,6 +459,23 @@ pub struct SignerStateMachine {
     /// The signer's private key. This is also used to seed the random
     /// number generated used to create the secret polynomial during DKG.
     private_key: PrivateKey,
+    /// Whether the inner state machine holds a private nonce for a
+    /// signing round that has not received its signature share request.
+    /// Private nonces are never written to the database, so a state
+    /// machine that holds one cannot be spilled without failing its
+    /// round.
+    holds_nonce: bool,
+}
+
+impl std::fmt::Debug for SignerStateMachine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SignerStateMachine")
+            .field("inner", &format_args!("{REDACTED}"))
+            .field("started_at", &self.started_at)
+            .field("private_key", &self.private_key)
+            .field("holds_nonce", &self.holds_nonce)
+            .finish()
+    }
 }
 
 type WstsSigner = wsts::state_machine::signer::Signer;
@@ -463,13 +486,18 @@ impl SignerStateMachine {
     /// # Notes
     ///
     /// When a new state machine is created, a new private polynomial is
-    /// generated, however this polynomial is regenerated during DKG.
-    pub fn new(
+    /// generated using the given random number generator, however this
+    /// polynomial is regenerated during DKG.
+    pub fn new<R>(
         signers: impl IntoIterator<Item = PublicKey>,
         threshold: u32,
         started_at: BitcoinBlockRef,
         private_key: PrivateKey,
-    ) -> Result<Self, Error> {
+        rng: &mut R,
+    ) -> Result<Self, Error>
+    where
+        R: RngCore + CryptoRng,
+    {
         let signer_pub_key = PublicKey::from_private_key(&private_key);
         let signers: HashMap<u32, _> = signers
             .into_iter()
@@ -526,11 +554,16 @@ impl SignerStateMachine {
             key_ids,
             private_key.into(),
             public_keys,
-            &mut OsRng,
+            rng,
         )
         .map_err(Error::Wsts)?;
 
-        Ok(Self { inner, started_at, private_key })
+        Ok(Self {
+            inner,
+            started_at,
+            private_key,
+            holds_nonce: false,
+        })
     }
 
     /// Create a random number generator seeded with the given bitcoin
@@ -563,20 +596,41 @@ impl SignerStateMachine {
     /// bitcoin block height, and the signer's private key. This ensures
     /// that secret shares are generated in a pseudo-random way.
     ///
-    /// All other messages are processed with the OS random number
-    /// generated.
-    pub fn process(&mut self, message: &Message) -> Result<Vec<Message>, Error> {
+    /// All other messages are processed with the given random number
+    /// generator, except that signature share requests are rejected
+    /// unless the state machine holds a private nonce from an earlier
+    /// nonce request.
+    pub fn process<R>(&mut self, message: &Message, rng: &mut R) -> Result<Vec<Message>, Error>
+    where
+        R: RngCore + CryptoRng,
+    {
+        if matches!(message, Message::SignatureShareRequest(_)) && !self.holds_nonce {
+            return Err(Error::MissingPrivateNonce);
+        }
+
         let response = match message {
             Message::DkgBegin(_) => {
                 let mut rng = Self::create_rng(&self.started_at.block_hash, self.private_key);
                 self.inner.process(message, &mut rng)
             }
-            _ => self.inner.process(message, &mut OsRng),
+            _ => self.inner.process(message, rng),
         };
 
+        match message {
+            Message::NonceRequest(_) if response.is_ok() => self.holds_nonce = true,
+            Message::SignatureShareRequest(_) => self.holds_nonce = false,
+            _ => {}
+        }
+
         response.map_err(Error::Wsts)
     }
 
+    /// Whether this state machine holds a private nonce for a signing
+    /// round that is still in progress.
+    pub fn holds_nonce(&self) -> bool {
+        self.holds_nonce
+    }
+
     /// Return the public key for the given signer ID.
     pub fn get_signer_public_key(&self, signer_id: u32) -> Option<PublicKey> {
         self.inner
@@ -601,13 +655,15 @@ impl SignerStateMachine {
     /// not for DKG, since they will always create the same secret shares.
     /// TODO: Make it so that we have separate state machines for signing
     /// and DKG.
-    pub async fn load<S>(
+    pub async fn load<S, R>(
         storage: &S,
         aggregate_key: PublicKeyXOnly,
         signer_private_key: PrivateKey,
+        rng: &mut R,
     ) -> Result<Self, Error>
     where
         S: storage::DbRead,
+        R: RngCore + CryptoRng,
     {
         let encrypted_shares = storage
             .get_encrypted_dkg_shares(aggregate_key)
@@ -616,11 +672,12 @@ impl SignerStateMachine {
 
         let decrypted = wsts::util::decrypt(
             &signer_private_key.to_bytes(),
-            &encrypted_shares.encrypted_private_shares,
+            encrypted_shares.encrypted_private_shares.expose_secret(),
         )
+        .map(Secret::new)
         .map_err(|error| Error::WstsDecrypt(error, aggregate_key))?;
 
-        let saved_state = wsts::traits::SignerState::decode(decrypted.as_slice())?;
+        let saved_state = wsts::traits::SignerState::decode(decrypted.expose_secret().as_slice())?;
 
         // This may panic if the saved state doesn't contain exactly one party,
         // however, that should never be the case since wsts maintains this invariant
@@ -635,15 +692,22 @@ impl SignerStateMachine {
             block_height: encrypted_shares.started_at_bitcoin_block_height,
         };
 
-        let mut state_machine = Self::new(signers, threshold, created_at, signer_private_key)?;
+        let mut state_machine = Self::new(signers, threshold, created_at, signer_private_key, rng)?;
 
         state_machine.inner.signer = signer;
 
         Ok(state_machine)
     }
 
-    /// Get the encrypted DKG shares
-    pub fn get_encrypted_dkg_shares(&self) -> Result<model::EncryptedDkgShares, Error> {
+    /// Get the encrypted DKG shares, encrypting the private shares using
+    /// the given random number generator.
+    pub fn get_encrypted_dkg_shares<R>(
+        &self,
+        rng: &mut R,
+    ) -> Result<model::EncryptedDkgShares, Error>
+    where
+        R: RngCore + CryptoRng,
+    {
         let saved_state = self.inner.signer.save();
         let aggregate_key = PublicKey::try_from(&saved_state.group_key)?;
 
@@ -662,18 +726,17 @@ impl SignerStateMachine {
         // We require the public keys to be stored sorted in db
         signer_set_public_keys.sort();
 
-        let encoded = saved_state.encode_to_vec();
+        let encoded = Secret::new(saved_state.encode_to_vec());
         let public_shares = self.inner.dkg_public_shares.clone().encode_to_vec();
 
         // After DKG, each of the signers will have "new public keys". The
         // call to `wsts::util::encrypt` can error if we are encrypting
         // more than 68719476752 bytes.
-        let encrypted_private_shares = wsts::util::encrypt(
-            &self.inner.network_private_key.to_bytes(),
-            &encoded,
-            &mut OsRng,
-        )
-        .map_err(|error| Error::WstsEncrypt(error, aggregate_key))?;
+        let private_key = self.inner.network_private_key.to_bytes();
+        let encrypted_private_shares =
+            wsts::util::encrypt(&private_key, encoded.expose_secret(), rng)
+                .map(Secret::new)
+                .map_err(|error| Error::WstsEncrypt(error, aggregate_key))?;
 
         let signature_share_threshold: u16 = self
             .inner
@@ -694,4 +757,372 @@ impl SignerStateMachine {
             started_at_bitcoin_block_height: self.started_at.block_height,
         })
     }
+
+    /// Encrypt the state of this state machine, using the given random
+    /// number generator, so that it can be spilled to the database after
+    /// being evicted from the in-memory cache.
+    ///
+    /// # Notes
+    ///
+    /// The private nonce of the inner state machine is never saved, so
+    /// this returns an error if the state machine holds one, and a
+    /// restored state machine rejects signature share requests until it
+    /// receives a new nonce request. DKG rounds can continue after a
+    /// restore.
+    pub fn to_spilled<R>(
+        &self,
+        state_machine_id: &StateMachineId,
+        bitcoin_block_height: BitcoinBlockHeight,
+        rng: &mut R,
+    ) -> Result<model::SpilledStateMachine, Error>
+    where
+        R: RngCore + CryptoRng,
+    {
+        if self.holds_nonce {
+            return Err(Error::StateMachineSpillHoldsNonce(*state_machine_id));
+        }
+
+        let encoded = Secret::new((self.started_at, self.inner.clone()).encode_to_vec());
+        let key = spill_encryption_key(&self.private_key);
+
+        let encrypted_state =
+            wsts::util::encrypt(key.expose_secret(), encoded.expose_secret(), rng)
+                .map(Secret::new)
+                .map_err(|error| Error::StateMachineSpillEncrypt(error, *state_machine_id))?;
+
+        Ok(model::SpilledStateMachine {
+            state_machine_id: state_machine_id.to_string(),
+            encrypted_state,
+            bitcoin_block_height,
+        })
+    }
+
+    /// Restore a state machine that was spilled to the database with
+    /// [`SignerStateMachine::to_spilled`].
+    pub fn from_spilled(
+        spilled: &model::SpilledStateMachine,
+        state_machine_id: &StateMachineId,
+        private_key: PrivateKey,
+    ) -> Result<Self, Error> {
+        let key = spill_encryption_key(&private_key);
+        let decrypted =
+            wsts::util::decrypt(key.expose_secret(), spilled.encrypted_state.expose_secret())
+                .map(Secret::new)
+                .map_err(|error| Error::StateMachineSpillDecrypt(error, *state_machine_id))?;
+
+        let (started_at, inner) =
+            <(BitcoinBlockRef, WstsSigner)>::decode(decrypted.expose_secret().as_slice())?;
+
+        Ok(Self {
+            inner,
+            started_at,
+            private_key,
+            holds_nonce: false,
+        })
+    }
+}
+
+/// The HKDF info label for the key that encrypts spilled state machines.
+const SPILL_KEY_LABEL: &[u8] = b"SBTC_SIGNER_SPILLED_STATE_MACHINE_KEY_V1";
+
+/// Derive the key that encrypts spilled state machines from the signer's
+/// private key using HKDF-SHA256, so that the private key itself is never
+/// used as an AES key and the derived key is only good for this one use.
+fn spill_encryption_key(private_key: &PrivateKey) -> Secret<[u8; 32]> {
+    use bitcoin::hashes::Hash as _;
+    use bitcoin::hashes::HashEngine as _;
+    use bitcoin::hashes::hmac::Hmac;
+    use bitcoin::hashes::hmac::HmacEngine;
+    use bitcoin::hashes::sha256;
+
+    // HKDF-Extract, with an empty salt, which RFC 5869 treats as a
+    // string of zeros as long as the hash output.
+    let mut engine = HmacEngine::<sha256::Hash>::new(&[0; 32]);
+    engine.input(&private_key.to_bytes());
+    let prk = Hmac::from_engine(engine);
+
+    // HKDF-Expand, where a single block is enough for a 32 byte key.
+    let mut engine = HmacEngine::<sha256::Hash>::new(&prk.to_byte_array());
+    engine.input(SPILL_KEY_LABEL);
+    engine.input(&[1]);
+    Secret::new(Hmac::from_engine(engine).to_byte_array())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use rand::rngs::StdRng;
+
+    use crate::context::RngProvider;
+    use crate::transaction_signer::TX_SIGNER_RNG_STREAM;
+
+    use super::*;
+
+    /// Deliver the given coordinator message and every message that
+    /// follows from it until the coordinator returns a result. Each signer
+    /// processes its own messages when it sends them, like the transaction
+    /// signer does, and every message that is sent is recorded.
+    fn run_round(
+        coordinator: &mut FireCoordinator,
+        signers: &mut [(SignerStateMachine, RngProvider)],
+        message: Message,
+        sent: &mut Vec<Message>,
+    ) -> OperationResult {
+        // Each message is paired with the index of the signer that sent
+        // it, or `None` if it came from the coordinator.
+        let mut queue = VecDeque::from([(None, message)]);
+
+        while let Some((sender, message)) = queue.pop_front() {
+            sent.push(message.clone());
+
+            for (index, (signer, provider)) in signers.iter_mut().enumerate() {
+                if sender == Some(index) {
+                    continue;
+                }
+                let mut rng = provider.rng(TX_SIGNER_RNG_STREAM);
+                for outbound in signer.process(&message, &mut rng).unwrap() {
+                    signer.process(&outbound, &mut rng).unwrap();
+                    queue.push_back((Some(index), outbound));
+                }
+            }
+
+            if sender.is_none() {
+                continue;
+            }
+            let (outbound, result) = coordinator.process_message(&message).unwrap();
+            if let Some(result) = result {
+                return result;
+            }
+            queue.extend(outbound.map(|outbound| (None, outbound)));
+        }
+
+        panic!("the round ended without a result");
+    }
+
+    /// Run DKG and then a signing round, where the random number
+    /// generators of each signer come from a provider derived from the
+    /// given seed. Returns the messages sent during the signing round.
+    fn run_dkg_and_signing_round(seed: u64) -> Vec<Message> {
+        let mut rng = StdRng::seed_from_u64(1);
+        let private_keys: Vec<PrivateKey> = std::iter::repeat_with(|| PrivateKey::new(&mut rng))
+            .take(3)
+            .collect();
+        let mut public_keys: Vec<PublicKey> = private_keys
+            .iter()
+            .map(PublicKey::from_private_key)
+            .collect();
+        public_keys.sort();
+
+        let started_at = BitcoinBlockRef {
+            block_hash: BitcoinBlockHash::from([1; 32]),
+            block_height: 1u64.into(),
+        };
+
+        let mut signers: Vec<(SignerStateMachine, RngProvider)> = private_keys
+            .iter()
+            .zip(0u64..)
+            .map(|(private_key, index)| {
+                let provider = RngProvider::from_seed(seed.wrapping_add(index));
+                let mut rng = provider.rng(TX_SIGNER_RNG_STREAM);
+                let signer = SignerStateMachine::new(
+                    public_keys.clone(),
+                    2,
+                    started_at,
+                    *private_key,
+                    &mut rng,
+                )
+                .unwrap();
+                (signer, provider)
+            })
+            .collect();
+
+        let mut coordinator = FireCoordinator::new(
+            public_keys.clone(),
+            2,
+            private_keys[0],
+            started_at.block_height,
+        );
+
+        coordinator.move_to(WstsState::DkgPublicDistribute).unwrap();
+        let dkg_begin = coordinator.start_public_shares().unwrap();
+        let result = run_round(&mut coordinator, &mut signers, dkg_begin, &mut Vec::new());
+        assert!(matches!(result, OperationResult::Dkg(_)));
+
+        let sign_request = coordinator
+            .start_signing_round(b"message", &started_at.block_hash, SignatureType::Schnorr)
+            .unwrap();
+        let mut sent = Vec::new();
+        let result = run_round(&mut coordinator, &mut signers, sign_request, &mut sent);
+        assert!(matches!(result, OperationResult::SignSchnorr(_)));
+
+        sent
+    }
+
+    // The messages sent during DKG are not compared since WSTS encrypts
+    // the private shares while iterating over a `HashMap`, so the order
+    // in which the random number generator is used differs between runs.
+    #[test]
+    fn same_seed_produces_identical_message_sequences() {
+        let messages1 = run_dkg_and_signing_round(42);
+        let messages2 = run_dkg_and_signing_round(42);
+
+        assert!(
+            messages1
+                .iter()
+                .any(|msg| matches!(msg, Message::NonceResponse(_)))
+        );
+        assert_eq!(messages1, messages2);
+
+        // The nonces come from the seeded random number generators, so a
+        // different seed leads to different messages.
+        let messages3 = run_dkg_and_signing_round(43);
+        assert_ne!(messages1, messages3);
+    }
+
+    #[test]
+    fn spilled_state_machine_can_be_restored_mid_dkg() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let private_keys: Vec<PrivateKey> = std::iter::repeat_with(|| PrivateKey::new(&mut rng))
+            .take(3)
+            .collect();
+        let public_keys = private_keys.iter().map(PublicKey::from_private_key);
+
+        let started_at = BitcoinBlockRef {
+            block_hash: BitcoinBlockHash::from([1; 32]),
+            block_height: 1u64.into(),
+        };
+        let mut state_machine =
+            SignerStateMachine::new(public_keys, 2, started_at, private_keys[0], &mut rng).unwrap();
+
+        // Start DKG, so that the state machine holds its own public shares
+        // and is waiting on the shares of the other signers.
+        let dkg_begin = Message::DkgBegin(wsts::net::DkgBegin { dkg_id: 1 });
+        for outbound in state_machine.process(&dkg_begin, &mut rng).unwrap() {
+            state_machine.process(&outbound, &mut rng).unwrap();
+        }
+        assert!(!state_machine.inner.dkg_public_shares.is_empty());
+
+        let id = StateMachineId::Dkg(started_at);
+        let spilled = state_machine
+            .to_spilled(&id, 5u64.into(), &mut rng)
+            .unwrap();
+        assert_eq!(spilled.state_machine_id, id.to_string());
+        assert_eq!(spilled.bitcoin_block_height, 5u64.into());
+
+        let restored = SignerStateMachine::from_spilled(&spilled, &id, private_keys[0]).unwrap();
+        assert_eq!(restored, state_machine);
+
+        // The state is encrypted with the private key of the signer that
+        // spilled it, so no other signer can restore it.
+        let result = SignerStateMachine::from_spilled(&spilled, &id, private_keys[1]);
+        assert!(matches!(result, Err(Error::StateMachineSpillDecrypt(..))));
+    }
+
+    #[test]
+    fn spilled_state_machine_cannot_sign_without_a_new_nonce() {
+        use bitcoin::hashes::Hash as _;
+
+        let mut rng = StdRng::seed_from_u64(1);
+        let private_keys: Vec<PrivateKey> = std::iter::repeat_with(|| PrivateKey::new(&mut rng))
+            .take(3)
+            .collect();
+        let mut public_keys: Vec<PublicKey> = private_keys
+            .iter()
+            .map(PublicKey::from_private_key)
+            .collect();
+        public_keys.sort();
+
+        let started_at = BitcoinBlockRef {
+            block_hash: BitcoinBlockHash::from([1; 32]),
+            block_height: 1u64.into(),
+        };
+
+        let mut signers: Vec<(SignerStateMachine, RngProvider)> = private_keys
+            .iter()
+            .zip(0u64..)
+            .map(|(private_key, index)| {
+                let provider = RngProvider::from_seed(index);
+                let mut rng = provider.rng(TX_SIGNER_RNG_STREAM);
+                let signer = SignerStateMachine::new(
+                    public_keys.clone(),
+                    2,
+                    started_at,
+                    *private_key,
+                    &mut rng,
+                )
+                .unwrap();
+                (signer, provider)
+            })
+            .collect();
+
+        let mut coordinator = FireCoordinator::new(
+            public_keys.clone(),
+            2,
+            private_keys[0],
+            started_at.block_height,
+        );
+
+        coordinator.move_to(WstsState::DkgPublicDistribute).unwrap();
+        let dkg_begin = coordinator.start_public_shares().unwrap();
+        let result = run_round(&mut coordinator, &mut signers, dkg_begin, &mut Vec::new());
+        assert!(matches!(result, OperationResult::Dkg(_)));
+
+        // Spill the first signer's state machine before the signing round
+        // starts, which is allowed since it does not hold a nonce yet.
+        let id = StateMachineId::BitcoinSign(bitcoin::TapSighash::from_byte_array([2; 32]).into());
+        let spilled = signers[0].0.to_spilled(&id, 5u64.into(), &mut rng).unwrap();
+
+        // Run the nonce phase of a signing round until the coordinator
+        // asks for signature shares.
+        let nonce_request = coordinator
+            .start_signing_round(b"message", &started_at.block_hash, SignatureType::Schnorr)
+            .unwrap();
+        let mut sign_request = None;
+        for (signer, provider) in signers.iter_mut() {
+            let mut rng = provider.rng(TX_SIGNER_RNG_STREAM);
+            for outbound in signer.process(&nonce_request, &mut rng).unwrap() {
+                let (outbound, _) = coordinator.process_message(&outbound).unwrap();
+                sign_request = sign_request.or(outbound);
+            }
+            assert!(signer.holds_nonce());
+        }
+        let sign_request = sign_request.unwrap();
+        assert!(matches!(sign_request, Message::SignatureShareRequest(_)));
+
+        // A state machine that holds a private nonce is never spilled.
+        let result = signers[0].0.to_spilled(&id, 5u64.into(), &mut rng);
+        assert!(matches!(result, Err(Error::StateMachineSpillHoldsNonce(_))));
+
+        // A restored state machine does not have the nonce that was sent
+        // to the coordinator, so it refuses to compute a signature share.
+        let mut restored =
+            SignerStateMachine::from_spilled(&spilled, &id, private_keys[0]).unwrap();
+        assert!(!restored.holds_nonce());
+        let result = restored.process(&sign_request, &mut rng);
+        assert!(matches!(result, Err(Error::MissingPrivateNonce)));
+
+        // The signers that kept their nonces finish the round.
+        let result = run_round(
+            &mut coordinator,
+            &mut signers,
+            sign_request,
+            &mut Vec::new(),
+        );
+        assert!(matches!(result, OperationResult::SignSchnorr(_)));
+
+        // After a new nonce request, the restored state machine takes
+        // part in signing rounds again.
+        signers[0].0 = restored;
+        let nonce_request = coordinator
+            .start_signing_round(b"message", &started_at.block_hash, SignatureType::Schnorr)
+            .unwrap();
+        let result = run_round(
+            &mut coordinator,
+            &mut signers,
+            nonce_request,
+            &mut Vec::new(),
+        );
+        assert!(matches!(result, OperationResult::SignSchnorr(_)));
+    }
 }
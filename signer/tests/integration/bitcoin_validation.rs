@@ -1,4 +1,5 @@
 use std::collections::HashSet;
+use std::num::NonZeroU16;
 use std::ops::Deref as _;
 
 use bitcoin::hashes::Hash as _;
@@ -8,6 +9,7 @@ use sbtc::testing::containers::TestContainersBuilder;
 use test_case::test_case;
 
 use sbtc::WITHDRAWAL_MIN_CONFIRMATIONS;
+use signer::bitcoin::utxo::RequestPrioritization;
 use signer::bitcoin::utxo::SbtcRequests;
 use signer::bitcoin::utxo::SignerBtcState;
 use signer::bitcoin::validation::BitcoinTxContext;
@@ -57,7 +59,8 @@ where
             .map(TryInto::try_into)
             .transpose()
             .unwrap(),
-        magic_bytes: [b'T', b'3'],
+        magic_bytes: signer::LEGACY_MAGIC_BYTES,
+        change_outputs: 1,
     }
 }
 
@@ -147,6 +150,8 @@ async fn one_tx_per_request_set() {
         }],
         fee_rate: TEST_FEE_RATE,
         last_fees: None,
+        parallel_sweeps: false,
+        magic_bytes: signer::LEGACY_MAGIC_BYTES,
     };
 
     let btc_ctx = BitcoinTxContext {
@@ -259,6 +264,8 @@ async fn one_invalid_deposit_invalidates_tx() {
         }],
         fee_rate: TEST_FEE_RATE,
         last_fees: None,
+        parallel_sweeps: false,
+        magic_bytes: signer::LEGACY_MAGIC_BYTES,
     };
 
     let btc_ctx = BitcoinTxContext {
@@ -436,6 +443,8 @@ async fn withdrawals_and_deposits_can_pass_validation(amounts: Vec<SweepAmounts>
         }],
         fee_rate: TEST_FEE_RATE,
         last_fees: None,
+        parallel_sweeps: false,
+        magic_bytes: signer::LEGACY_MAGIC_BYTES,
     };
 
     let btc_ctx = BitcoinTxContext {
@@ -554,6 +563,8 @@ async fn swept_withdrawals_fail_validation() {
         }],
         fee_rate: TEST_FEE_RATE,
         last_fees: None,
+        parallel_sweeps: false,
+        magic_bytes: signer::LEGACY_MAGIC_BYTES,
     };
 
     let btc_ctx = BitcoinTxContext {
@@ -683,6 +694,8 @@ async fn cannot_sign_deposit_is_ok() {
         }],
         fee_rate: TEST_FEE_RATE,
         last_fees: None,
+        parallel_sweeps: false,
+        magic_bytes: signer::LEGACY_MAGIC_BYTES,
     };
 
     let btc_ctx = BitcoinTxContext {
@@ -751,10 +764,14 @@ async fn cannot_sign_deposit_is_ok() {
             .collect(),
         withdrawals: Vec::new(),
         signer_state: signer_btc_state(&ctx, &request, &btc_ctx).await,
+        spare_signer_utxos: Vec::new(),
         accept_threshold: 2,
         num_signers: 3,
         sbtc_limits: SbtcLimits::unlimited(),
         max_deposits_per_bitcoin_tx: ctx.config().signer.max_deposits_per_bitcoin_tx.get(),
+        max_withdrawals_per_bitcoin_tx: u16::MAX,
+        max_withdrawal_value: u64::MAX,
+        prioritization: RequestPrioritization::Fifo,
     };
     let txs = sbtc_requests.construct_transactions().unwrap();
     assert_eq!(txs.len(), 1);
@@ -828,6 +845,8 @@ async fn sighashes_match_from_sbtc_requests_object() {
         }],
         fee_rate: TEST_FEE_RATE,
         last_fees: None,
+        parallel_sweeps: false,
+        magic_bytes: signer::LEGACY_MAGIC_BYTES,
     };
 
     let btc_ctx = BitcoinTxContext {
@@ -892,10 +911,14 @@ async fn sighashes_match_from_sbtc_requests_object() {
             .collect(),
         withdrawals: Vec::new(),
         signer_state: signer_btc_state(&ctx, &request, &btc_ctx).await,
+        spare_signer_utxos: Vec::new(),
         accept_threshold: 2,
         num_signers: 3,
         sbtc_limits: SbtcLimits::unlimited(),
         max_deposits_per_bitcoin_tx: ctx.config().signer.max_deposits_per_bitcoin_tx.get(),
+        max_withdrawals_per_bitcoin_tx: u16::MAX,
+        max_withdrawal_value: u64::MAX,
+        prioritization: RequestPrioritization::Fifo,
     };
     let txs = sbtc_requests.construct_transactions().unwrap();
     assert_eq!(txs.len(), 1);
@@ -979,6 +1002,8 @@ async fn outcome_is_independent_of_input_order() {
         }],
         fee_rate: TEST_FEE_RATE,
         last_fees: None,
+        parallel_sweeps: false,
+        magic_bytes: signer::LEGACY_MAGIC_BYTES,
     };
 
     let btc_ctx = BitcoinTxContext {
@@ -1007,3 +1032,134 @@ async fn outcome_is_independent_of_input_order() {
 
     testing::storage::drop_db(db).await;
 }
+
+#[tokio::test]
+async fn outcome_is_independent_of_fetch_concurrency() {
+    let db = testing::storage::new_test_database().await;
+    let mut rng = get_rng();
+
+    let stack = TestContainersBuilder::start_bitcoin().await;
+    let bitcoin = stack.bitcoin().await;
+    let rpc = bitcoin.rpc();
+    let faucet = &bitcoin.get_faucet();
+
+    let make_context = |concurrency: u16| {
+        TestContext::builder()
+            .with_storage(db.clone())
+            .with_bitcoin_client(bitcoin.get_client())
+            .with_mocked_stacks_client()
+            .with_mocked_emily_client()
+            .modify_settings(|settings| {
+                settings.signer.presign_validation_concurrency =
+                    NonZeroU16::new(concurrency).unwrap();
+            })
+            .build()
+    };
+    let serial_ctx = make_context(1);
+    let concurrent_ctx = make_context(8);
+
+    let signers = TestSignerSet::new(&mut rng);
+    let amounts = [
+        SweepAmounts {
+            amount: 1_500_000,
+            max_fee: 500_000,
+            is_deposit: true,
+        },
+        SweepAmounts {
+            amount: 700_000,
+            max_fee: 500_000,
+            is_deposit: true,
+        },
+        SweepAmounts {
+            amount: 1_000_000,
+            max_fee: 500_000,
+            is_deposit: true,
+        },
+        SweepAmounts {
+            amount: 123_456,
+            max_fee: 50_000,
+            is_deposit: false,
+        },
+        SweepAmounts {
+            amount: 456_789,
+            max_fee: 900_000,
+            is_deposit: false,
+        },
+    ];
+
+    let mut setup = TestSweepSetup2::new_setup(signers, bitcoin.get_client(), faucet, &amounts);
+    setup.deposits.sort_by_key(|(x, _, _)| x.outpoint);
+    backfill_bitcoin_blocks(&db, rpc, &setup.deposit_block_hash).await;
+
+    setup.store_stacks_genesis_block(&db).await;
+    setup.store_dkg_shares(&db).await;
+    setup.store_donation(&db).await;
+    setup.store_deposit_txs(&db).await;
+    setup.store_deposit_request(&db).await;
+    setup.store_deposit_decisions(&db).await;
+    setup.store_withdrawal_requests(&db).await;
+    setup.store_withdrawal_decisions(&db).await;
+
+    let chain_tip = faucet
+        .generate_blocks(WITHDRAWAL_MIN_CONFIRMATIONS)
+        .pop()
+        .unwrap();
+    backfill_bitcoin_blocks(&db, rpc, &chain_tip).await;
+
+    let chain_tip_ref = db
+        .get_bitcoin_canonical_chain_tip_ref()
+        .await
+        .unwrap()
+        .unwrap();
+    let stacks_chain_tip = db
+        .get_stacks_chain_tip(&chain_tip_ref.block_hash)
+        .await
+        .unwrap()
+        .unwrap();
+
+    for ctx in [&serial_ctx, &concurrent_ctx] {
+        ctx.state().update_current_limits(SbtcLimits::unlimited());
+        ctx.state()
+            .set_stacks_chain_tip(stacks_chain_tip.clone().into());
+    }
+
+    let request = BitcoinPreSignRequest {
+        request_package: vec![TxRequestIds {
+            deposits: setup.deposit_outpoints(),
+            withdrawals: setup.withdrawal_ids(),
+        }],
+        fee_rate: TEST_FEE_RATE,
+        last_fees: None,
+        parallel_sweeps: false,
+        magic_bytes: signer::LEGACY_MAGIC_BYTES,
+    };
+
+    let btc_ctx = BitcoinTxContext {
+        chain_tip: chain_tip_ref.block_hash,
+        chain_tip_height: chain_tip_ref.block_height,
+        signer_public_key: setup.signers.keys[0],
+        aggregate_key: setup.signers.signer.keypair.public_key().into(),
+    };
+
+    let serial_data = request
+        .construct_package_sighashes(&serial_ctx, &btc_ctx)
+        .await
+        .unwrap();
+    let concurrent_data = request
+        .construct_package_sighashes(&concurrent_ctx, &btc_ctx)
+        .await
+        .unwrap();
+
+    assert_eq!(serial_data.len(), 1);
+    assert_eq!(concurrent_data.len(), 1);
+    assert_eq!(
+        serial_data[0].to_input_rows(),
+        concurrent_data[0].to_input_rows()
+    );
+    assert_eq!(
+        serial_data[0].to_withdrawal_rows(),
+        concurrent_data[0].to_withdrawal_rows()
+    );
+
+    testing::storage::drop_db(db).await;
+}
@@ -17,6 +17,8 @@ use clarity::types::chainstate::StacksAddress;
 use clarity::vm::types::PrincipalData;
 use emily_client::apis::deposit_api;
 use emily_client::models::CreateDepositRequestBody;
+use emily_client::models::DepositStatus;
+use emily_client::models::UpdateDepositsResponse;
 use fake::Fake as _;
 use fake::Faker;
 use rand::seq::SliceRandom as _;
@@ -24,12 +26,14 @@ use sbtc::deposits::CreateDepositRequest;
 use sbtc::deposits::DepositInfo;
 use sbtc::deposits::DepositScriptInputs;
 use sbtc::deposits::ReclaimScriptInputs;
+use sbtc::deposits::SiblingOutputPolicy;
 use sbtc::testing::containers::TestContainersBuilder;
 use sbtc::testing::regtest::Faucet;
 use sbtc::testing::regtest::Recipient;
 use signer::bitcoin::BitcoinInteract;
 use signer::bitcoin::poller::BitcoinChainTipPoller;
 use signer::bitcoin::utxo::DepositRequest;
+use signer::bitcoin::utxo::RequestPrioritization;
 use signer::bitcoin::utxo::SbtcRequests;
 use signer::bitcoin::utxo::SignerBtcState;
 use signer::bitcoin::utxo::SignerUtxo;
@@ -160,6 +164,17 @@ async fn load_latest_deposit_requests_persists_requests_from_past(blocks_ago: u6
             .times(1..)
             .returning(move || Box::pin(std::future::ready(Ok(emily_client_response.clone()))));
 
+        // The block observer sends Emily the proofs of the deposit
+        // transactions that it has not seen before.
+        client.expect_update_deposits().returning(|updates| {
+            assert!(updates.iter().all(|update| {
+                update.status == DepositStatus::Pending
+                    && update.deposit_tx_proof.clone().flatten().is_some()
+            }));
+            let response = UpdateDepositsResponse { deposits: Vec::new() };
+            Box::pin(std::future::ready(Ok(response)))
+        });
+
         client
             .expect_get_limits()
             .times(1..)
@@ -455,6 +470,9 @@ async fn block_observer_stores_donation_and_sbtc_utxos() {
         client
             .expect_get_current_signer_set_info()
             .returning(|_| Box::pin(std::future::ready(Ok(None))));
+        client
+            .expect_is_sbtc_contract_paused()
+            .returning(|_| Box::pin(std::future::ready(Ok(false))));
     })
     .await;
 
@@ -598,12 +616,17 @@ async fn block_observer_stores_donation_and_sbtc_utxos() {
             fee_rate: 10.0,
             public_key: signers_public_key,
             last_fees: None,
-            magic_bytes: [b'T', b'3'],
+            magic_bytes: signer::LEGACY_MAGIC_BYTES,
+            change_outputs: 1,
         },
+        spare_signer_utxos: Vec::new(),
         accept_threshold: 4,
         num_signers: 7,
         sbtc_limits: SbtcLimits::unlimited(),
         max_deposits_per_bitcoin_tx: ctx.config().signer.max_deposits_per_bitcoin_tx.get(),
+        max_withdrawals_per_bitcoin_tx: u16::MAX,
+        max_withdrawal_value: u64::MAX,
+        prioritization: RequestPrioritization::Fifo,
     };
 
     let mut transactions = requests.construct_transactions().unwrap();
@@ -773,12 +796,17 @@ async fn block_observer_picks_up_chained_unordered_sweeps() {
             // aggregate key.
             public_key: signers_public_key2,
             last_fees: None,
-            magic_bytes: [b'T', b'3'],
+            magic_bytes: signer::LEGACY_MAGIC_BYTES,
+            change_outputs: 1,
         },
+        spare_signer_utxos: Vec::new(),
         accept_threshold: 2,
         num_signers: 3,
         sbtc_limits: SbtcLimits::unlimited(),
         max_deposits_per_bitcoin_tx: 25,
+        max_withdrawals_per_bitcoin_tx: u16::MAX,
+        max_withdrawal_value: u64::MAX,
+        prioritization: RequestPrioritization::Fifo,
     };
 
     // By playing around with the votes above, we set things up so that we
@@ -938,6 +966,9 @@ async fn block_observer_handles_update_limits(deployed: bool, sbtc_limits: SbtcL
                     Ok(shares.map(SignerSetInfo::from))
                 })
             });
+        client
+            .expect_is_sbtc_contract_paused()
+            .returning(|_| Box::pin(std::future::ready(Ok(false))));
     })
     .await;
 
@@ -965,6 +996,14 @@ async fn block_observer_handles_update_limits(deployed: bool, sbtc_limits: SbtcL
                 .returning(|_| Box::pin(std::future::ready(Ok(Amount::from_sat(1)))));
         })
         .await;
+        // With the contracts deployed, the block observer reports the
+        // amount available to mint back to Emily.
+        ctx.with_emily_client(|client| {
+            client
+                .expect_set_available_to_mint()
+                .returning(|_| Box::pin(std::future::ready(Ok(()))));
+        })
+        .await;
         ctx.state().set_sbtc_contracts_deployed();
     } else {
         ctx.with_stacks_client(|client| {
@@ -1170,6 +1209,9 @@ async fn get_signer_set_info_checks_for_contract_deployment() {
         client
             .expect_get_current_signer_set_info()
             .returning(move |_| Box::pin(std::future::ready(Ok(Some(signer_set_info2.clone())))));
+        client
+            .expect_is_sbtc_contract_paused()
+            .returning(|_| Box::pin(std::future::ready(Ok(false))));
 
         client.expect_get_contract_source().returning(|_, _| {
             Box::pin(async {
@@ -1259,6 +1301,9 @@ async fn block_observer_updates_state_after_observing_bitcoin_block() {
                     Ok(info.map(Into::into))
                 })
             });
+        client
+            .expect_is_sbtc_contract_paused()
+            .returning(|_| Box::pin(std::future::ready(Ok(false))));
 
         client.expect_get_contract_source().returning(|_, _| {
             Box::pin(async {
@@ -1278,6 +1323,10 @@ async fn block_observer_updates_state_after_observing_bitcoin_block() {
         client
             .expect_get_limits()
             .returning(|| Box::pin(std::future::ready(Ok(SbtcLimits::unlimited()))));
+
+        client
+            .expect_set_available_to_mint()
+            .returning(|_| Box::pin(std::future::ready(Ok(()))));
     })
     .await;
 
@@ -1776,8 +1825,12 @@ async fn block_observer_ignores_coinbase() {
         deposit_script: deposit_request.deposit_script.clone(),
     };
     let bitcoin_client = ctx.get_bitcoin_client();
-    let validate_result =
-        signer::block_observer::DepositRequestValidator::validate(&request, &bitcoin_client, false);
+    let validate_result = signer::block_observer::DepositRequestValidator::validate(
+        &request,
+        &bitcoin_client,
+        false,
+        SiblingOutputPolicy::Permissive,
+    );
     match validate_result.await {
         Err(Error::BitcoinTxCoinbase(tx)) if tx == deposit_request.outpoint.txid => {}
         _ => panic!("Expected a err, got something else"),
@@ -1945,6 +1998,11 @@ async fn block_observer_handles_deposits_with_high_max_fee() {
             .expect_get_deposits()
             .returning(move || Box::pin(std::future::ready(Ok(deposit_requests.clone()))));
 
+        client.expect_update_deposits().returning(|_| {
+            let response = UpdateDepositsResponse { deposits: Vec::new() };
+            Box::pin(std::future::ready(Ok(response)))
+        });
+
         client
             .expect_get_limits()
             .once()
@@ -2014,9 +2072,14 @@ async fn block_observer_handles_deposits_with_high_max_fee() {
         deposit_script: deposit_info.deposit_script.clone(),
     };
     let bitcoin_client = ctx.get_bitcoin_client();
-    signer::block_observer::DepositRequestValidator::validate(&request, &bitcoin_client, false)
-        .await
-        .unwrap();
+    signer::block_observer::DepositRequestValidator::validate(
+        &request,
+        &bitcoin_client,
+        false,
+        SiblingOutputPolicy::Permissive,
+    )
+    .await
+    .unwrap();
 
     testing::storage::drop_db(db).await;
 }
@@ -0,0 +1,33 @@
+use std::time::Duration;
+
+use signer::context::Context as _;
+use signer::devenv::DevEnvBuilder;
+use signer::storage::DbRead as _;
+
+/// The signers in a devenv observe the blocks that we mine and run DKG,
+/// just like the signers in the end to end tests.
+#[test_log::test(tokio::test)]
+async fn devenv_signers_observe_blocks_and_run_dkg() {
+    let env = DevEnvBuilder::new().start().await;
+
+    let chain_tip = env.mine_block_and_wait().await;
+    for signer in env.signers.iter() {
+        let db = signer.context.get_storage();
+        let tip = db.get_bitcoin_canonical_chain_tip().await.unwrap();
+        assert_eq!(tip, Some(chain_tip));
+    }
+
+    env.wait_until(Duration::from_secs(30), |ctx| async move {
+        let db = ctx.get_storage();
+        db.get_latest_encrypted_dkg_shares()
+            .await
+            .unwrap()
+            .is_some()
+    })
+    .await
+    .expect("the signers did not run DKG in time");
+
+    assert!(env.aggregate_key().await.is_some());
+
+    env.shutdown().await;
+}
@@ -47,7 +47,9 @@ use signer::{
     storage::postgres::PgStore,
     testing::{self, context::*},
     transaction_coordinator::TxCoordinatorEventLoop,
-    transaction_signer::{STACKS_SIGN_REQUEST_LRU_SIZE, TxSignerEventLoop},
+    transaction_signer::{
+        STACKS_SIGN_REQUEST_LRU_SIZE, STACKS_VALIDATION_LRU_SIZE, TxSignerEventLoop,
+    },
     util::{FutureExt as _, Sleep},
 };
 
@@ -154,6 +156,7 @@ async fn start_signers(
             dkg_begin_pause: None,
             dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
             stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+            stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
         };
         let counter = start_count.clone();
         tokio::spawn(async move {
@@ -167,7 +170,9 @@ async fn start_signers(
             context_window: 10000,
             deposit_decisions_retry_window: 1,
             withdrawal_decisions_retry_window: 1,
+            carried_over: Default::default(),
             blocklist_checker: Some(()),
+            decision_policies: Vec::new(),
             signer_private_key: kp.secret_key().into(),
         };
         let counter = start_count.clone();
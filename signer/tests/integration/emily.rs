@@ -27,6 +27,7 @@ use emily_client::models::CreateDepositRequestBody;
 use emily_client::models::DepositStatus;
 use emily_client::models::DepositUpdate;
 use emily_client::models::UpdateDepositsRequestBody;
+use sbtc::ids::DepositId;
 use sbtc::testing::regtest::Recipient;
 use signer::bitcoin::rpc::BitcoinBlockInfo;
 use signer::bitcoin::rpc::BitcoinTxInfo;
@@ -251,6 +252,12 @@ async fn deposit_flow() {
         .with_bitcoin_client(|client| {
             // Setup the bitcoin client mock to broadcast the transaction to our
             // channel.
+            // The coordinator audits the fees of each sweep that it
+            // broadcasts, which is best effort.
+            client.expect_get_transaction_fee().returning(|txid| {
+                let txid = *txid;
+                Box::pin(async move { Err(Error::BitcoinTxMissing(txid, None)) })
+            });
             client
                 .expect_broadcast_transaction()
                 .once()
@@ -555,8 +562,9 @@ async fn get_deposit_request_works() {
         .await
         .expect("cannot create emily deposit");
 
-    let txid = setup.tx.compute_txid().into();
-    let request = emily_client.get_deposit(&txid, 0).await.unwrap().unwrap();
+    let txid = setup.tx.compute_txid();
+    let id = DepositId::new(txid, 0);
+    let request = emily_client.get_deposit(&id).await.unwrap().unwrap();
 
     assert_eq!(request.deposit_script, deposit.deposit_script());
     assert_eq!(request.reclaim_script, reclaim.reclaim_script());
@@ -564,7 +572,8 @@ async fn get_deposit_request_works() {
     assert_eq!(request.outpoint.vout, 0);
 
     // This one doesn't exist
-    let request = emily_client.get_deposit(&txid, 50).await.unwrap();
+    let id = DepositId::new(txid, 50);
+    let request = emily_client.get_deposit(&id).await.unwrap();
     assert!(request.is_none());
 
     clean_emily_setup(emily_tables).await;
@@ -672,6 +681,8 @@ async fn test_get_deposits_returns_pending_and_accepted() {
             status: DepositStatus::Accepted,
             status_message: "accepted".to_string(),
             replaced_by_tx: None,
+            deposit_block_hash: None,
+            deposit_tx_proof: None,
         })
         .collect();
 
@@ -2,6 +2,7 @@ use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 use std::collections::HashSet;
 use std::io::Read as _;
+use std::num::NonZeroU16;
 use std::ops::Deref as _;
 use std::slice;
 use std::time::Duration;
@@ -22,6 +23,7 @@ use rand::seq::IteratorRandom as _;
 use rand::seq::SliceRandom as _;
 use sbtc::testing::containers::TestContainersBuilder;
 use signer::WITHDRAWAL_BLOCKS_EXPIRY;
+use signer::bitcoin::validation::ValidationChecks;
 use signer::bitcoin::validation::WithdrawalRequestStatus;
 use signer::bitcoin::validation::WithdrawalValidationResult;
 use signer::context::SbtcLimits;
@@ -39,7 +41,10 @@ use strum::IntoEnumIterator as _;
 use time::OffsetDateTime;
 
 use signer::bitcoin::MockBitcoinInteract;
+use signer::bitcoin::rpc::BitcoinTxInfo;
 use signer::bitcoin::validation::DepositConfirmationStatus;
+use signer::config::DepositConfirmationPolicy;
+use signer::config::DepositConfirmationTier;
 use signer::context::Context;
 use signer::emily_client::MockEmilyInteract;
 use signer::error::Error;
@@ -455,6 +460,92 @@ async fn get_pending_deposit_requests_only_pending() {
     signer::testing::storage::drop_db(db).await;
 }
 
+/// Test that signer UTXO violations round-trip through the database,
+/// whether or not we know of the signers' UTXO that the sweep spent.
+#[test_case(Some(60_000); "chained")]
+#[test_case(None; "unchained")]
+#[tokio::test]
+async fn signer_utxo_violations_round_trip(previous_signer_output: Option<u64>) {
+    let db = testing::storage::new_test_database().await;
+    let mut rng = get_rng();
+
+    let block: BitcoinBlock = Faker.fake_with_rng(&mut rng);
+    db.write_bitcoin_block(&block).await.unwrap();
+
+    let violation = model::SignerUtxoViolation {
+        txid: Faker.fake_with_rng(&mut rng),
+        block_hash: block.block_hash,
+        signer_input: 50_000,
+        deposits: 40_000,
+        withdrawals: 15_000,
+        fee: 5_000,
+        signer_output: 70_000,
+        previous_signer_output,
+    };
+    db.write_signer_utxo_violation(&violation).await.unwrap();
+
+    let violations = db.get_signer_utxo_violations(10).await.unwrap();
+    assert_eq!(violations, vec![violation]);
+
+    signer::testing::storage::drop_db(db).await;
+}
+
+/// Test that [`DbRead::get_recent_deposit_requests`] returns the deposit
+/// requests in the context window whether or not they have votes.
+#[tokio::test]
+async fn get_recent_deposit_requests_ignores_votes() {
+    let db = testing::storage::new_test_database().await;
+
+    let stack = TestContainersBuilder::start_bitcoin().await;
+    let bitcoin = stack.bitcoin().await;
+    let rpc = bitcoin.rpc();
+    let faucet = &bitcoin.get_faucet();
+
+    let mut rng = get_rng();
+
+    let amounts = SweepAmounts {
+        amount: 123456,
+        max_fee: 12345,
+        is_deposit: true,
+    };
+    let signers = TestSignerSet::new(&mut rng);
+    let setup = TestSweepSetup2::new_setup(signers, bitcoin.get_client(), faucet, &[amounts]);
+
+    backfill_bitcoin_blocks(&db, rpc, &setup.deposit_block_hash).await;
+    let chain_tip = db.get_bitcoin_canonical_chain_tip().await.unwrap().unwrap();
+
+    let requests = db
+        .get_recent_deposit_requests(&chain_tip, 1000)
+        .await
+        .unwrap();
+    assert!(requests.is_empty());
+
+    setup.store_deposit_txs(&db).await;
+    setup.store_deposit_request(&db).await;
+
+    let requests = db
+        .get_recent_deposit_requests(&chain_tip, 1000)
+        .await
+        .unwrap();
+    assert_eq!(requests.len(), 1);
+
+    // Votes on the request do not change whether it is returned.
+    setup.store_deposit_decisions(&db).await;
+
+    let requests = db
+        .get_recent_deposit_requests(&chain_tip, 1000)
+        .await
+        .unwrap();
+    assert_eq!(requests.len(), 1);
+
+    // The deposit transaction is in the chain tip, so a window of one
+    // block still includes it.
+    let requests = db.get_recent_deposit_requests(&chain_tip, 1).await.unwrap();
+    assert_eq!(requests.len(), 1);
+
+    signer::testing::storage::drop_db(db).await;
+}
+
 /// Test that [`DbRead::get_pending_withdrawal_requests`] returns
 /// withdrawal requests that do not have a vote on them yet.
 #[tokio::test]
@@ -663,7 +754,12 @@ async fn should_return_the_same_pending_accepted_deposit_requests_as_in_memory_s
     );
 
     let mut pending_accepted_deposit_requests = in_memory_store
-        .get_pending_accepted_deposit_requests(&chain_tip, context_window, threshold)
+        .get_pending_accepted_deposit_requests(
+            &chain_tip,
+            context_window,
+            threshold,
+            &DepositConfirmationPolicy::default(),
+        )
         .await
         .expect("failed to get pending deposit requests");
 
@@ -672,7 +768,12 @@ async fn should_return_the_same_pending_accepted_deposit_requests_as_in_memory_s
     assert!(!pending_accepted_deposit_requests.is_empty());
 
     let mut pg_pending_accepted_deposit_requests = pg_store
-        .get_pending_accepted_deposit_requests(&chain_tip, context_window, threshold)
+        .get_pending_accepted_deposit_requests(
+            &chain_tip,
+            context_window,
+            threshold,
+            &DepositConfirmationPolicy::default(),
+        )
         .await
         .expect("failed to get pending deposit requests");
 
@@ -685,6 +786,76 @@ async fn should_return_the_same_pending_accepted_deposit_requests_as_in_memory_s
     signer::testing::storage::drop_db(pg_store).await;
 }
 
+/// This ensures that the postgres store and the in memory stores apply the
+/// deposit confirmation policy in the same way, and that deposits without
+/// enough confirmations for their amount are excluded.
+#[tokio::test]
+async fn pending_accepted_deposit_requests_respect_confirmation_policy() {
+    let pg_store = testing::storage::new_test_database().await;
+    let in_memory_store = storage::memory::Store::new_shared();
+
+    let mut rng = get_rng();
+
+    let num_signers = 7;
+    let context_window = 9;
+    let test_model_params = testing::storage::model::Params {
+        num_bitcoin_blocks: 20,
+        num_stacks_blocks_per_bitcoin_block: 3,
+        num_deposit_requests_per_block: 5,
+        num_withdraw_requests_per_block: 5,
+        num_signers_per_request: num_signers,
+        consecutive_blocks: false,
+    };
+    let threshold = 4;
+
+    let signer_set = testing::wsts::generate_signer_set_public_keys(&mut rng, num_signers);
+    let test_data = TestData::generate(&mut rng, &signer_set, &test_model_params);
+
+    test_data.write_to(&in_memory_store).await;
+    test_data.write_to(&pg_store).await;
+
+    let chain_tip = in_memory_store
+        .get_bitcoin_canonical_chain_tip_ref()
+        .await
+        .expect("failed to get canonical chain tip")
+        .expect("no chain tip");
+
+    let default_policy = DepositConfirmationPolicy::default();
+    let strict_policy = DepositConfirmationPolicy::new(vec![DepositConfirmationTier {
+        min_amount: 0,
+        confirmations: NonZeroU16::new(4).unwrap(),
+    }]);
+    let impossible_policy = DepositConfirmationPolicy::new(vec![DepositConfirmationTier {
+        min_amount: 0,
+        confirmations: NonZeroU16::new(context_window + 1).unwrap(),
+    }]);
+
+    let mut results = Vec::new();
+    for policy in [&default_policy, &strict_policy, &impossible_policy] {
+        let mut in_memory_requests = in_memory_store
+            .get_pending_accepted_deposit_requests(&chain_tip, context_window, threshold, policy)
+            .await
+            .expect("failed to get pending deposit requests");
+        let mut pg_requests = pg_store
+            .get_pending_accepted_deposit_requests(&chain_tip, context_window, threshold, policy)
+            .await
+            .expect("failed to get pending deposit requests");
+
+        in_memory_requests.sort();
+        pg_requests.sort();
+        assert_eq!(in_memory_requests, pg_requests);
+        results.push(pg_requests);
+    }
+
+    let [all, confirmed, none] = results.try_into().unwrap();
+    assert!(!all.is_empty());
+    assert!(confirmed.len() <= all.len());
+    assert!(confirmed.iter().all(|req| all.contains(req)));
+    assert!(none.is_empty());
+
+    signer::testing::storage::drop_db(pg_store).await;
+}
+
 /// This tests that when fetching pending accepted deposits we ignore swept ones.
 #[tokio::test]
 async fn should_not_return_swept_deposits_as_pending_accepted() {
@@ -729,7 +900,12 @@ async fn should_not_return_swept_deposits_as_pending_accepted() {
     setup.store_deposit_decisions(&db).await;
 
     let requests = db
-        .get_pending_accepted_deposit_requests(&chain_tip, context_window, threshold)
+        .get_pending_accepted_deposit_requests(
+            &chain_tip,
+            context_window,
+            threshold,
+            &DepositConfirmationPolicy::default(),
+        )
         .await
         .unwrap();
 
@@ -740,7 +916,12 @@ async fn should_not_return_swept_deposits_as_pending_accepted() {
     setup.store_sweep_tx(&db).await;
 
     let requests = db
-        .get_pending_accepted_deposit_requests(&chain_tip, context_window, threshold)
+        .get_pending_accepted_deposit_requests(
+            &chain_tip,
+            context_window,
+            threshold,
+            &DepositConfirmationPolicy::default(),
+        )
         .await
         .unwrap();
 
@@ -759,6 +940,7 @@ async fn should_not_return_swept_deposits_as_pending_accepted() {
             &deposit_block_ref,
             context_window,
             threshold,
+            &DepositConfirmationPolicy::default(),
         )
         .await
         .unwrap();
@@ -825,12 +1007,22 @@ async fn should_return_only_accepted_pending_deposits_that_are_within_reclaim_bo
     // First ensure that we didn't break the main pending accepted deposit requests functionality
     // since all the lock times are the maximum possible value and thus should be accepted.
     let mut pending_accepted_deposit_requests = pg_store
-        .get_pending_accepted_deposit_requests(&chain_tip, context_window, threshold)
+        .get_pending_accepted_deposit_requests(
+            &chain_tip,
+            context_window,
+            threshold,
+            &DepositConfirmationPolicy::default(),
+        )
         .await
         .expect("failed to get pending deposit requests from pg store.");
 
     let mut in_memory_pending_accepted_deposit_requests = in_memory_store
-        .get_pending_accepted_deposit_requests(&chain_tip, context_window, threshold)
+        .get_pending_accepted_deposit_requests(
+            &chain_tip,
+            context_window,
+            threshold,
+            &DepositConfirmationPolicy::default(),
+        )
         .await
         .expect("failed to get pending deposit requests from in memory store.");
 
@@ -933,12 +1125,22 @@ async fn should_return_only_accepted_pending_deposits_that_are_within_reclaim_bo
     test_data.write_to(&in_memory_store).await;
 
     let mut pending_accepted_deposit_requests_in_memory = in_memory_store
-        .get_pending_accepted_deposit_requests(&chain_tip, context_window, threshold)
+        .get_pending_accepted_deposit_requests(
+            &chain_tip,
+            context_window,
+            threshold,
+            &DepositConfirmationPolicy::default(),
+        )
         .await
         .expect("failed to get pending deposit requests");
 
     let mut pending_accepted_deposit_requests_pg_store = pg_store
-        .get_pending_accepted_deposit_requests(&chain_tip, context_window, threshold)
+        .get_pending_accepted_deposit_requests(
+            &chain_tip,
+            context_window,
+            threshold,
+            &DepositConfirmationPolicy::default(),
+        )
         .await
         .expect("failed to get pending deposit requests");
 
@@ -1651,6 +1853,7 @@ async fn fetching_withdrawal_request_votes() {
             request_id,
             signer_pub_key: shares.signer_set_public_keys[0],
             is_accepted: true,
+            rejection_reason: None,
         },
         WithdrawalSigner {
             txid,
@@ -1658,6 +1861,7 @@ async fn fetching_withdrawal_request_votes() {
             request_id,
             signer_pub_key: shares.signer_set_public_keys[1],
             is_accepted: false,
+            rejection_reason: None,
         },
         WithdrawalSigner {
             txid,
@@ -1665,6 +1869,7 @@ async fn fetching_withdrawal_request_votes() {
             request_id,
             signer_pub_key: shares.signer_set_public_keys[2],
             is_accepted: true,
+            rejection_reason: None,
         },
         WithdrawalSigner {
             txid,
@@ -1672,6 +1877,7 @@ async fn fetching_withdrawal_request_votes() {
             request_id,
             signer_pub_key: shares.signer_set_public_keys[3],
             is_accepted: true,
+            rejection_reason: None,
         },
     ];
 
@@ -1818,7 +2024,7 @@ async fn is_signer_script_pub_key_checks_dkg_shares_for_script_pubkeys() {
     let shares = EncryptedDkgShares {
         script_pubkey: script_pubkey.clone(),
         tweaked_aggregate_key: aggregate_key.signers_tweaked_pubkey().unwrap(),
-        encrypted_private_shares: Vec::new(),
+        encrypted_private_shares: Vec::new().into(),
         public_shares: Vec::new(),
         aggregate_key,
         signer_set_public_keys: vec![fake::Faker.fake_with_rng(&mut rng)],
@@ -3499,6 +3705,18 @@ async fn should_get_signer_utxo_simple() {
     signer::testing::storage::drop_db(store).await;
 }
 
+#[tokio::test]
+async fn should_get_signer_utxos_split_change() {
+    let store = testing::storage::new_test_database().await;
+
+    transaction_coordinator_test_environment(store.clone())
+        .await
+        .assert_get_signer_utxos_split_change()
+        .await;
+
+    signer::testing::storage::drop_db(store).await;
+}
+
 #[tokio::test]
 async fn should_get_signer_utxo_fork() {
     let store = testing::storage::new_test_database().await;
@@ -4148,6 +4366,7 @@ async fn withdrawal_report_with_no_withdrawal_votes() {
         txid: qualified_id.txid,
         signer_pub_key: *signer_public_key,
         is_accepted: true,
+        rejection_reason: None,
     };
     db.write_withdrawal_signer_decision(&withdrawal_decision)
         .await
@@ -4739,6 +4958,7 @@ async fn withdrawal_report_with_withdrawal_request_confirmed() {
         txid: qualified_id.txid,
         signer_pub_key: *signer_public_key,
         is_accepted: false,
+        rejection_reason: None,
     };
     db.write_withdrawal_signer_decision(&withdrawal_decision)
         .await
@@ -4863,9 +5083,16 @@ async fn deposit_requests_max_fee_migration() {
     let mut rng = get_rng();
 
     // Let's create some deposits requests and write them to the usual
-    // table in the database.
-    let deposit_request1: model::DepositRequest = fake::Faker.fake_with_rng(&mut rng);
-    let deposit_request2: model::DepositRequest = fake::Faker.fake_with_rng(&mut rng);
+    // table in the database. The old table predates the funding fees
+    // columns, so we leave them out.
+    let deposit_request1 = model::DepositRequest {
+        funding_fees: None,
+        ..fake::Faker.fake_with_rng(&mut rng)
+    };
+    let deposit_request2 = model::DepositRequest {
+        funding_fees: None,
+        ..fake::Faker.fake_with_rng(&mut rng)
+    };
 
     // Let's create a new deposit_requests2 table that is just like
     // deposit_requests table before the max_fee migration.
@@ -4909,6 +5136,8 @@ async fn deposit_requests_max_fee_migration() {
                  , lock_time
                  , signers_public_key
                  , sender_script_pub_keys
+                 , NULL::BIGINT AS funding_fees_total
+                 , NULL::BIGINT AS funding_fees_vsize
             FROM sbtc_signer.deposit_requests2
             WHERE txid = $1
               AND output_index = $2
@@ -5359,7 +5588,7 @@ async fn write_and_get_dkg_shares_is_pending() {
     let insert = EncryptedDkgShares {
         aggregate_key: fake::Faker.fake(),
         tweaked_aggregate_key: fake::Faker.fake(),
-        encrypted_private_shares: vec![],
+        encrypted_private_shares: vec![].into(),
         script_pubkey: fake::Faker.fake(),
         public_shares: vec![],
         signer_set_public_keys: vec![],
@@ -6145,6 +6374,7 @@ async fn is_withdrawal_inflight_catches_withdrawals_with_rows_in_table() {
         bitcoin_txid,
         is_valid_tx: true,
         validation_result: WithdrawalValidationResult::Ok,
+        validation_checks: ValidationChecks::default(),
         output_index: 2,
     };
     db.write_bitcoin_withdrawals_outputs(&[output])
@@ -6159,6 +6389,7 @@ async fn is_withdrawal_inflight_catches_withdrawals_with_rows_in_table() {
         prevout_txid: setup.donation.txid.into(),
         prevout_output_index: setup.donation.vout,
         validation_result: signer::bitcoin::validation::InputValidationResult::Ok,
+        validation_checks: ValidationChecks::default(),
         aggregate_key: setup.signers.aggregate_key().into(),
         is_valid_tx: false,
         will_sign: false,
@@ -6224,6 +6455,7 @@ async fn is_withdrawal_inflight_catches_withdrawals_in_package() {
         bitcoin_txid: bitcoin_txid3,
         is_valid_tx: true,
         validation_result: WithdrawalValidationResult::Ok,
+        validation_checks: ValidationChecks::default(),
         output_index: 2,
     };
     db.write_bitcoin_withdrawals_outputs(&[output])
@@ -6239,6 +6471,7 @@ async fn is_withdrawal_inflight_catches_withdrawals_in_package() {
         prevout_txid: bitcoin_txid2,
         prevout_output_index: 0,
         validation_result: signer::bitcoin::validation::InputValidationResult::Ok,
+        validation_checks: ValidationChecks::default(),
         aggregate_key: setup.signers.aggregate_key().into(),
         is_valid_tx: false,
         will_sign: false,
@@ -6255,6 +6488,7 @@ async fn is_withdrawal_inflight_catches_withdrawals_in_package() {
         prevout_txid: bitcoin_txid1,
         prevout_output_index: 0,
         validation_result: signer::bitcoin::validation::InputValidationResult::Ok,
+        validation_checks: ValidationChecks::default(),
         aggregate_key: setup.signers.aggregate_key().into(),
         is_valid_tx: false,
         will_sign: false,
@@ -6273,6 +6507,7 @@ async fn is_withdrawal_inflight_catches_withdrawals_in_package() {
         prevout_txid: setup.donation.txid.into(),
         prevout_output_index: setup.donation.vout,
         validation_result: signer::bitcoin::validation::InputValidationResult::Ok,
+        validation_checks: ValidationChecks::default(),
         aggregate_key: setup.signers.aggregate_key().into(),
         is_valid_tx: false,
         will_sign: false,
@@ -6321,6 +6556,7 @@ async fn is_withdrawal_active_for_considered_withdrawal() {
         bitcoin_chain_tip: chain_tip.block_hash,
         is_valid_tx: true,
         validation_result: WithdrawalValidationResult::Ok,
+        validation_checks: ValidationChecks::default(),
         output_index: 2,
         bitcoin_txid: Faker.fake_with_rng(&mut rng),
     };
@@ -6432,6 +6668,100 @@ async fn compute_withdrawn_total_gets_all_amounts_in_chain() {
     signer::testing::storage::drop_db(db).await;
 }
 
+/// Check that `get_caps_utilization` agrees with `compute_withdrawn_total`
+/// and tells us when the withdrawals leave the rolling window.
+#[tokio::test]
+async fn get_caps_utilization_tracks_withdrawals_leaving_the_window() {
+    let db = testing::storage::new_test_database().await;
+    let mut rng = get_rng();
+
+    let test_model_params = testing::storage::model::Params {
+        num_bitcoin_blocks: 30,
+        num_stacks_blocks_per_bitcoin_block: 1,
+        num_deposit_requests_per_block: 0,
+        num_withdraw_requests_per_block: 0,
+        num_signers_per_request: 0,
+        consecutive_blocks: true,
+    };
+    let signer_set = testing::wsts::generate_signer_set_public_keys(&mut rng, 1);
+    let test_data = TestData::generate(&mut rng, &signer_set, &test_model_params);
+    test_data.write_to(&db).await;
+
+    let context_window = 10;
+    let bitcoin_chain_tip = db
+        .get_bitcoin_canonical_chain_tip_ref()
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Sweep out a withdrawal in each of the three most recent blocks.
+    let amount = 1_000;
+    let mut block = db
+        .get_bitcoin_block(&bitcoin_chain_tip.block_hash)
+        .await
+        .unwrap()
+        .unwrap();
+    for _ in 0..3 {
+        let output = model::TxOutput {
+            amount,
+            output_type: model::TxOutputType::Withdrawal,
+            ..Faker.fake_with_rng(&mut rng)
+        };
+        let tx = model::BitcoinTxRef {
+            txid: output.txid,
+            block_hash: block.block_hash,
+        };
+        db.write_bitcoin_transactions(vec![tx]).await.unwrap();
+        db.write_tx_output(&output).await.unwrap();
+
+        block = db
+            .get_bitcoin_block(&block.parent_hash)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    let withdrawn_total = db
+        .compute_withdrawn_total(&bitcoin_chain_tip.block_hash, context_window)
+        .await
+        .unwrap();
+    assert_eq!(withdrawn_total, 3 * amount);
+
+    // With room for more withdrawals there is headroom right away.
+    let utilization = db
+        .get_caps_utilization(&bitcoin_chain_tip.block_hash, context_window, 5 * amount)
+        .await
+        .unwrap();
+    assert_eq!(
+        utilization.bitcoin_block_height,
+        bitcoin_chain_tip.block_height
+    );
+    assert_eq!(utilization.withdrawn_total, withdrawn_total);
+    assert_eq!(utilization.available, 2 * amount);
+    assert_eq!(utilization.blocks_until_headroom, Some(0));
+
+    // Once the cap is used up, we need to wait for the oldest withdrawal,
+    // two blocks below the chain tip, to leave the window.
+    let utilization = db
+        .get_caps_utilization(&bitcoin_chain_tip.block_hash, context_window, 3 * amount)
+        .await
+        .unwrap();
+    assert_eq!(utilization.available, 0);
+    assert_eq!(
+        utilization.blocks_until_headroom,
+        Some(u64::from(context_window) - 2)
+    );
+
+    // We cannot say anything about a chain tip that we do not know about.
+    let unknown_chain_tip: model::BitcoinBlockHash = Faker.fake_with_rng(&mut rng);
+    let result = db
+        .get_caps_utilization(&unknown_chain_tip, context_window, 3 * amount)
+        .await;
+    assert!(matches!(result, Err(Error::MissingBitcoinBlock(_))));
+
+    signer::testing::storage::drop_db(db).await;
+}
+
 #[tokio::test]
 async fn timestamps() {
     let db = testing::storage::new_test_database().await;
@@ -6713,47 +7043,453 @@ mod p2p_peers {
 
         testing::storage::drop_db(db).await;
     }
-}
 
-/// Module containing a test suite and helpers specific to
-/// `DbRead::get_pending_accepted_withdrawal_requests`.
-mod get_pending_accepted_withdrawal_requests {
-    use signer::{
-        bitcoin::validation::WithdrawalValidationResult,
-        testing::storage::{self, DbReadTestExt as _, DbWriteTestExt as _},
-    };
+    #[tokio::test]
+    async fn write_read_update_p2p_peer_version() {
+        let db = testing::storage::new_test_database().await;
 
-    use super::*;
-    use test_case::test_case;
+        let peer_id = PeerId::random();
+        db.update_peer_version(&peer_id, "sbtc-signer/0.1.0", 1)
+            .await
+            .expect("Failed to insert peer version");
 
-    /// Creates [`WithdrawalSigner`]s for each vote in the provided slice and
-    /// stores them in the database. The signer public key is randomized.
-    async fn store_votes(db: &PgStore, request: &WithdrawalRequest, votes: &[bool]) {
-        for vote in votes {
-            let signer = model::WithdrawalSigner {
-                request_id: request.request_id,
-                block_hash: request.block_hash,
-                txid: request.txid,
-                signer_pub_key: Faker.fake(),
-                is_accepted: *vote,
-            };
-            db.write_withdrawal_signer_decision(&signer)
-                .await
-                .expect("failed to write signer decision");
-        }
+        let versions = db.get_p2p_peer_versions().await.unwrap();
+        assert_eq!(versions.len(), 1);
+        assert_eq!(*versions[0].peer_id, peer_id);
+        assert_eq!(versions[0].agent_version, "sbtc-signer/0.1.0");
+        assert_eq!(versions[0].protocol_version, 1);
+
+        // An upgraded peer replaces its previous entry.
+        db.update_peer_version(&peer_id, "sbtc-signer/0.2.0", 2)
+            .await
+            .expect("Failed to update peer version");
+
+        let versions = db.get_p2p_peer_versions().await.unwrap();
+        assert_eq!(versions.len(), 1);
+        assert_eq!(versions[0].agent_version, "sbtc-signer/0.2.0");
+        assert_eq!(versions[0].protocol_version, 2);
+        assert!(
+            *versions[0].updated_at - time::OffsetDateTime::now_utc() < time::Duration::seconds(5)
+        );
+
+        testing::storage::drop_db(db).await;
     }
 
-    /// Creates and stores a withdrawal request, confirmed in the specified
-    /// bitcoin & stacks blocks.
-    ///
-    /// If votes are provided, signer vote entries are created and stored as
-    /// well (with the same number of votes as the length of the `votes` slice).
-    /// The votes are stored under random signer public keys.
-    async fn store_withdrawal_request(
-        db: &PgStore,
-        request_id: u64,
-        bitcoin_block: &BitcoinBlock,
-        stacks_block: &StacksBlock,
+    #[tokio::test]
+    async fn write_read_delete_p2p_peer_bans() {
+        let db = testing::storage::new_test_database().await;
+
+        // Postgres stores timestamps with microsecond precision, so we
+        // use whole seconds to make the bans round trip exactly.
+        let now = time::OffsetDateTime::now_utc()
+            .replace_nanosecond(0)
+            .unwrap();
+        let ban = model::P2PPeerBan {
+            peer_id: PeerId::random().into(),
+            reason: "invalid-signature".to_string(),
+            score: 100,
+            banned_at: now.into(),
+            expires_at: (now + time::Duration::hours(1)).into(),
+        };
+        let expired_ban = model::P2PPeerBan {
+            peer_id: PeerId::random().into(),
+            expires_at: (now - time::Duration::seconds(1)).into(),
+            ..ban.clone()
+        };
+        db.write_peer_ban(&ban).await.unwrap();
+        db.write_peer_ban(&expired_ban).await.unwrap();
+
+        // Expired bans are not active.
+        let bans = db.get_active_peer_bans().await.unwrap();
+        assert_eq!(bans, vec![ban.clone()]);
+
+        // Banning a peer again replaces its previous ban.
+        let extended_ban = model::P2PPeerBan {
+            reason: "replay".to_string(),
+            score: 120,
+            expires_at: (now + time::Duration::hours(2)).into(),
+            ..ban.clone()
+        };
+        db.write_peer_ban(&extended_ban).await.unwrap();
+
+        let bans = db.get_active_peer_bans().await.unwrap();
+        assert_eq!(bans, vec![extended_ban]);
+
+        db.delete_peer_ban(&ban.peer_id).await.unwrap();
+        assert!(db.get_active_peer_bans().await.unwrap().is_empty());
+
+        testing::storage::drop_db(db).await;
+    }
+
+    #[tokio::test]
+    async fn write_read_p2p_peer_address_records() {
+        let db = testing::storage::new_test_database().await;
+
+        // Postgres stores timestamps with microsecond precision, so we
+        // use whole seconds to make the records round trip exactly.
+        let now = time::OffsetDateTime::now_utc()
+            .replace_nanosecond(0)
+            .unwrap();
+        let public_key: PublicKey = Faker.fake();
+        let addresses: Vec<libp2p::Multiaddr> = vec![
+            "/ip4/10.0.0.1/tcp/4122".parse().unwrap(),
+            "/ip4/10.0.0.1/udp/4122/quic-v1".parse().unwrap(),
+        ];
+        let record = model::P2PPeerAddressRecord {
+            public_key,
+            peer_id: PeerId::from(public_key).into(),
+            addresses: addresses.into_iter().map(Into::into).collect(),
+            expires_at: (now + time::Duration::hours(1)).into(),
+        };
+        let expired_key: PublicKey = Faker.fake();
+        let expired_record = model::P2PPeerAddressRecord {
+            public_key: expired_key,
+            peer_id: PeerId::from(expired_key).into(),
+            expires_at: (now - time::Duration::seconds(1)).into(),
+            ..record.clone()
+        };
+        assert!(db.write_peer_address_record(&record).await.unwrap());
+        assert!(db.write_peer_address_record(&expired_record).await.unwrap());
+
+        // Expired records are not active.
+        let records = db.get_active_peer_address_records().await.unwrap();
+        assert_eq!(records, vec![record.clone()]);
+
+        // An announcement that expires earlier than the stored one is
+        // stale, so it does not replace it.
+        let stale_record = model::P2PPeerAddressRecord {
+            addresses: vec![
+                "/ip4/10.0.0.2/tcp/4122"
+                    .parse::<libp2p::Multiaddr>()
+                    .unwrap()
+                    .into(),
+            ],
+            expires_at: (now + time::Duration::minutes(30)).into(),
+            ..record.clone()
+        };
+        assert!(!db.write_peer_address_record(&stale_record).await.unwrap());
+        assert!(!db.write_peer_address_record(&record).await.unwrap());
+
+        let records = db.get_active_peer_address_records().await.unwrap();
+        assert_eq!(records, vec![record.clone()]);
+
+        // A newer announcement replaces the stored one.
+        let newer_record = model::P2PPeerAddressRecord {
+            expires_at: (now + time::Duration::hours(2)).into(),
+            ..stale_record
+        };
+        assert!(db.write_peer_address_record(&newer_record).await.unwrap());
+
+        let records = db.get_active_peer_address_records().await.unwrap();
+        assert_eq!(records, vec![newer_record]);
+
+        testing::storage::drop_db(db).await;
+    }
+}
+
+mod bitcoin_tx_bodies {
+    use super::*;
+
+    /// Check that cached transaction bodies can be read back, only for
+    /// the block that confirmed them, and that pruning only removes
+    /// bodies below the given block height.
+    #[tokio::test]
+    async fn write_get_prune_bitcoin_tx_bodies() {
+        let db = testing::storage::new_test_database().await;
+        let rng = &mut get_rng();
+
+        let bodies: Vec<model::BitcoinTxBody> = (0..3u64)
+            .map(|height| {
+                let tx_info: BitcoinTxInfo = Faker.fake_with_rng(rng);
+                let block_ref = model::BitcoinBlockRef {
+                    block_hash: Faker.fake_with_rng(rng),
+                    block_height: height.into(),
+                };
+                model::BitcoinTxBody::from_tx_info(&tx_info, &block_ref).unwrap()
+            })
+            .collect();
+
+        db.write_bitcoin_tx_bodies(&bodies).await.unwrap();
+        // Writing the same bodies twice is fine.
+        db.write_bitcoin_tx_bodies(&bodies).await.unwrap();
+
+        for body in &bodies {
+            let cached = db
+                .get_bitcoin_tx_body(&body.txid, &body.block_hash)
+                .await
+                .unwrap();
+            assert_eq!(cached.as_ref(), Some(body));
+        }
+
+        let other_block_hash: model::BitcoinBlockHash = Faker.fake_with_rng(rng);
+        let cached = db
+            .get_bitcoin_tx_body(&bodies[0].txid, &other_block_hash)
+            .await
+            .unwrap();
+        assert!(cached.is_none());
+
+        let pruned = db.prune_bitcoin_tx_bodies(2u64.into()).await.unwrap();
+        assert_eq!(pruned, 2);
+
+        for (body, kept) in bodies.iter().zip([false, false, true]) {
+            let cached = db
+                .get_bitcoin_tx_body(&body.txid, &body.block_hash)
+                .await
+                .unwrap();
+            assert_eq!(cached.is_some(), kept);
+        }
+
+        testing::storage::drop_db(db).await;
+    }
+}
+
+mod block_activity {
+    use super::*;
+
+    /// Check that the block activity summary only counts the sweep
+    /// transactions in the given block, that the fees are the difference
+    /// between the inputs and outputs of those transactions, and that the
+    /// in-memory store returns the same summary as postgres.
+    #[tokio::test]
+    async fn block_activity_summarizes_sweeps_in_block() {
+        let db = testing::storage::new_test_database().await;
+        let mem = storage::memory::Store::new_shared();
+        let rng = &mut get_rng();
+
+        let block: model::BitcoinBlock = Faker.fake_with_rng(rng);
+        db.write_bitcoin_block(&block).await.unwrap();
+        mem.write_bitcoin_block(&block).await.unwrap();
+
+        // An empty block has an empty summary.
+        let summary = db.get_block_activity(&block.block_hash).await.unwrap();
+        assert_eq!(summary, model::BlockActivitySummary::default());
+        let summary = mem.get_block_activity(&block.block_hash).await.unwrap();
+        assert_eq!(summary, model::BlockActivitySummary::default());
+
+        let sweep_txids: [BitcoinTxId; 2] = [Faker.fake_with_rng(rng), Faker.fake_with_rng(rng)];
+        let other_txid: BitcoinTxId = Faker.fake_with_rng(rng);
+        for txid in [sweep_txids[0], other_txid, sweep_txids[1]] {
+            let tx_ref = model::BitcoinTxRef {
+                txid,
+                block_hash: block.block_hash,
+            };
+            db.write_bitcoin_transaction(&tx_ref).await.unwrap();
+            mem.write_bitcoin_transaction(&tx_ref).await.unwrap();
+        }
+
+        // Each sweep spends the signers' UTXO and a deposit, and pays a
+        // withdrawal and 50,000 sats in fees.
+        for sweep_txid in sweep_txids {
+            let prevouts = [
+                (model::TxPrevoutType::SignersInput, 1_000_000),
+                (model::TxPrevoutType::Deposit, 500_000),
+            ];
+            for (prevout_type, amount) in prevouts {
+                let mut prevout: model::TxPrevout = Faker.fake_with_rng(rng);
+                prevout.txid = sweep_txid;
+                prevout.prevout_type = prevout_type;
+                prevout.amount = amount;
+                db.write_tx_prevout(&prevout).await.unwrap();
+                mem.write_tx_prevout(&prevout).await.unwrap();
+            }
+
+            let outputs = [
+                (model::TxOutputType::SignersOutput, 1_200_000),
+                (model::TxOutputType::SignersOpReturn, 0),
+                (model::TxOutputType::Withdrawal, 250_000),
+            ];
+            for (output_index, (output_type, amount)) in outputs.into_iter().enumerate() {
+                let mut output: model::TxOutput = Faker.fake_with_rng(rng);
+                output.txid = sweep_txid;
+                output.output_index = output_index as u32;
+                output.output_type = output_type;
+                output.amount = amount;
+                db.write_tx_output(&output).await.unwrap();
+                mem.write_tx_output(&output).await.unwrap();
+            }
+        }
+
+        let mut expected_txids = sweep_txids.to_vec();
+        expected_txids.sort_by_key(BitcoinTxId::into_bytes);
+        let expected = model::BlockActivitySummary {
+            sweep_txids: expected_txids,
+            deposits_swept: 2,
+            deposit_amount: 1_000_000,
+            withdrawals_fulfilled: 2,
+            withdrawal_amount: 500_000,
+            fees_paid: 100_000,
+        };
+        let summary = db.get_block_activity(&block.block_hash).await.unwrap();
+        assert_eq!(summary, expected);
+        let summary = mem.get_block_activity(&block.block_hash).await.unwrap();
+        assert_eq!(summary, expected);
+
+        testing::storage::drop_db(db).await;
+    }
+}
+
+mod emily_imports {
+    use super::*;
+
+    /// Check that requests imported from Emily are recorded as such, and
+    /// that requests that were not imported are not.
+    #[tokio::test]
+    async fn write_and_check_emily_imported_requests() {
+        let db = testing::storage::new_test_database().await;
+        let rng = &mut get_rng();
+
+        let deposits: Vec<model::DepositRequest> =
+            (0..2).map(|_| Faker.fake_with_rng(rng)).collect();
+        db.write_deposit_requests(deposits.clone()).await.unwrap();
+
+        let stacks_block: model::StacksBlock = Faker.fake_with_rng(rng);
+        db.write_stacks_block(&stacks_block).await.unwrap();
+        let withdrawals: Vec<model::WithdrawalRequest> = (0..2)
+            .map(|_| model::WithdrawalRequest {
+                block_hash: stacks_block.block_hash,
+                ..Faker.fake_with_rng(rng)
+            })
+            .collect();
+        for withdrawal in &withdrawals {
+            db.write_withdrawal_request(withdrawal).await.unwrap();
+        }
+
+        let imported = &deposits[0];
+        db.write_emily_imported_deposit(&imported.txid, imported.output_index)
+            .await
+            .unwrap();
+        // Recording the same import twice is fine.
+        db.write_emily_imported_deposit(&imported.txid, imported.output_index)
+            .await
+            .unwrap();
+        db.write_emily_imported_withdrawal(&withdrawals[0].qualified_id())
+            .await
+            .unwrap();
+
+        for (deposit, expected) in deposits.iter().zip([true, false]) {
+            let is_imported = db
+                .is_emily_imported_deposit(&deposit.txid, deposit.output_index)
+                .await
+                .unwrap();
+            assert_eq!(is_imported, expected);
+        }
+        for (withdrawal, expected) in withdrawals.iter().zip([true, false]) {
+            let is_imported = db
+                .is_emily_imported_withdrawal(&withdrawal.qualified_id())
+                .await
+                .unwrap();
+            assert_eq!(is_imported, expected);
+        }
+
+        testing::storage::drop_db(db).await;
+    }
+}
+
+mod sbtc_supply {
+    use super::*;
+
+    /// Check that completed deposits and accepted withdrawals that were
+    /// confirmed in more than one stacks fork are only counted once.
+    #[tokio::test]
+    async fn get_sbtc_supply_totals_deduplicates_events() {
+        let db = testing::storage::new_test_database().await;
+
+        let totals = db.get_sbtc_supply_totals().await.unwrap();
+        assert_eq!(totals, model::SbtcSupplyTotals::default());
+
+        let mut deposit: CompletedDepositEvent = Faker.fake();
+        deposit.amount = 50_000;
+        db.write_completed_deposit_event(&deposit).await.unwrap();
+        // The same deposit, confirmed in another stacks fork.
+        deposit.block_id = Faker.fake();
+        deposit.txid = Faker.fake();
+        db.write_completed_deposit_event(&deposit).await.unwrap();
+
+        let mut request: WithdrawalRequest = Faker.fake();
+        request.amount = 20_000;
+        db.write_withdrawal_request(&request).await.unwrap();
+
+        let mut accept: WithdrawalAcceptEvent = Faker.fake();
+        accept.request_id = request.request_id;
+        db.write_withdrawal_accept_event(&accept).await.unwrap();
+        accept.block_id = Faker.fake();
+        db.write_withdrawal_accept_event(&accept).await.unwrap();
+
+        let totals = db.get_sbtc_supply_totals().await.unwrap();
+        assert_eq!(totals.minted, 50_000);
+        assert_eq!(totals.burned, 20_000);
+        assert_eq!(totals.expected_supply(), 30_000);
+
+        let snapshot = model::SbtcSupplySnapshot {
+            bitcoin_block_hash: Faker.fake(),
+            bitcoin_block_height: 123u64.into(),
+            totals,
+            reported_supply: 30_000,
+            within_tolerance: true,
+        };
+        db.write_sbtc_supply_snapshot(&snapshot).await.unwrap();
+
+        let stored = sqlx::query_as::<_, model::SbtcSupplySnapshot>(
+            r#"
+            SELECT
+                bitcoin_block_hash
+              , bitcoin_block_height
+              , minted
+              , burned
+              , reported_supply
+              , within_tolerance
+            FROM sbtc_signer.sbtc_supply_snapshots
+            "#,
+        )
+        .fetch_one(db.pool())
+        .await
+        .unwrap();
+        assert_eq!(stored, snapshot);
+
+        testing::storage::drop_db(db).await;
+    }
+}
+
+/// Module containing a test suite and helpers specific to
+/// `DbRead::get_pending_accepted_withdrawal_requests`.
+mod get_pending_accepted_withdrawal_requests {
+    use signer::{
+        bitcoin::validation::WithdrawalValidationResult,
+        testing::storage::{self, DbReadTestExt as _, DbWriteTestExt as _},
+    };
+
+    use super::*;
+    use test_case::test_case;
+
+    /// Creates [`WithdrawalSigner`]s for each vote in the provided slice and
+    /// stores them in the database. The signer public key is randomized.
+    async fn store_votes(db: &PgStore, request: &WithdrawalRequest, votes: &[bool]) {
+        for vote in votes {
+            let signer = model::WithdrawalSigner {
+                request_id: request.request_id,
+                block_hash: request.block_hash,
+                txid: request.txid,
+                signer_pub_key: Faker.fake(),
+                is_accepted: *vote,
+                rejection_reason: None,
+            };
+            db.write_withdrawal_signer_decision(&signer)
+                .await
+                .expect("failed to write signer decision");
+        }
+    }
+
+    /// Creates and stores a withdrawal request, confirmed in the specified
+    /// bitcoin & stacks blocks.
+    ///
+    /// If votes are provided, signer vote entries are created and stored as
+    /// well (with the same number of votes as the length of the `votes` slice).
+    /// The votes are stored under random signer public keys.
+    async fn store_withdrawal_request(
+        db: &PgStore,
+        request_id: u64,
+        bitcoin_block: &BitcoinBlock,
+        stacks_block: &StacksBlock,
         votes: &[bool],
     ) -> WithdrawalRequest {
         let withdrawal_request = WithdrawalRequest {
@@ -6817,6 +7553,7 @@ mod get_pending_accepted_withdrawal_requests {
                 stacks_block_hash: request.block_hash,
                 request_id: request.request_id,
                 validation_result: WithdrawalValidationResult::Ok,
+                validation_checks: ValidationChecks::default(),
                 output_index: 2,
             }])
             .await
@@ -6833,7 +7570,7 @@ mod get_pending_accepted_withdrawal_requests {
             let tx_output = model::TxOutput {
                 txid: bitcoin_sweep_tx.txid,
                 output_index: 2,
-                script_pubkey: Faker.fake(),
+                script_pubkey: request.recipient.clone(),
                 amount: request.amount,
                 output_type: model::TxOutputType::Withdrawal,
             };
@@ -7547,6 +8284,99 @@ mod get_pending_accepted_withdrawal_requests {
         storage::drop_db(db).await;
     }
 
+    /// Asserts that a sweep of a withdrawal confirmed in a forked out
+    /// stacks block only hides requests for that same withdrawal, and not
+    /// a different withdrawal that got the same request ID on the
+    /// canonical stacks chain.
+    ///
+    /// This test creates blockchains with the following structure:
+    ///
+    /// ```text
+    ///          ┌────────┐  ┌────────┐
+    /// Bitcoin: │   B1   ├──►   B2 ◆ │  We sweep (◆) the request confirmed
+    ///          └─▲──────┘  └─▲────▲─┘  in S2b in B2.
+    ///            ┊           ┊    ┊
+    ///          ┌─┴──────┐  ┌─┴──────┐
+    /// Stacks:  │   S1   ├──►  S2a ✔ │  Requests with the same ID are
+    ///          └────┬───┘  └────────┘  confirmed (✔) in S2a and S2b, and
+    ///               │      ┌──────┴─┐  S2b gets forked out.
+    ///               └─────>│  S2b ✔ │
+    ///                      └────────┘
+    /// ```
+    #[test_case(SetupTables::PreSign, false; "bitcoin_withdrawal_outputs, other withdrawal")]
+    #[test_case(SetupTables::WithdrawalIds, false; "bitcoin_withdrawal_tx_outputs, other withdrawal")]
+    #[test_case(SetupTables::Both, false; "rows in both tables, other withdrawal")]
+    #[test_case(SetupTables::PreSign, true; "bitcoin_withdrawal_outputs, same withdrawal")]
+    #[test_case(SetupTables::WithdrawalIds, true; "bitcoin_withdrawal_tx_outputs, same withdrawal")]
+    #[test_case(SetupTables::Both, true; "rows in both tables, same withdrawal")]
+    #[tokio::test]
+    async fn forked_out_sweeps_only_hide_the_same_withdrawal(
+        setup_tables: SetupTables,
+        same_withdrawal: bool,
+    ) {
+        let db = storage::new_test_database().await;
+
+        let signature_threshold = 2;
+        let min_block_height = 0u64;
+
+        // Bitcoin blocks:
+        let bitcoin_1 = BitcoinBlock::new_genesis();
+        let bitcoin_2 = bitcoin_1.new_child();
+        // Stacks blocks:
+        let stacks_1 = StacksBlock::new_genesis().anchored_to(&bitcoin_1);
+        let stacks_2a = stacks_1.new_child().anchored_to(&bitcoin_2);
+        let stacks_2b = stacks_1.new_child().anchored_to(&bitcoin_2);
+
+        db.write_blocks(
+            [&bitcoin_1, &bitcoin_2],
+            [&stacks_1, &stacks_2a, &stacks_2b],
+        )
+        .await;
+
+        // The withdrawal confirmed in the forked out block gets swept.
+        let forked_request =
+            store_withdrawal_request(&db, 1, &bitcoin_2, &stacks_2b, &[true, true]).await;
+        sweep_withdrawal_request(&db, &forked_request, &bitcoin_2.block_hash, setup_tables).await;
+
+        // The canonical block either confirms the same stacks transaction,
+        // or a different withdrawal that got the same request ID.
+        let canonical_request = if same_withdrawal {
+            WithdrawalRequest {
+                block_hash: stacks_2a.block_hash,
+                ..forked_request.clone()
+            }
+        } else {
+            WithdrawalRequest {
+                request_id: forked_request.request_id,
+                block_hash: stacks_2a.block_hash,
+                bitcoin_block_height: bitcoin_2.block_height,
+                ..Faker.fake()
+            }
+        };
+        db.write_withdrawal_request(&canonical_request)
+            .await
+            .expect("failed to write withdrawal request");
+        store_votes(&db, &canonical_request, &[true, true]).await;
+
+        let requests = db
+            .get_pending_accepted_withdrawal_requests(
+                &bitcoin_2.block_hash,
+                &stacks_2a.block_hash,
+                min_block_height.into(),
+                signature_threshold,
+            )
+            .await
+            .expect("failed to query db");
+
+        if same_withdrawal {
+            assert!(requests.is_empty());
+        } else {
+            assert_eq!(requests, [canonical_request]);
+        }
+
+        storage::drop_db(db).await;
+    }
+
     /// Asserts that a single request is not returned if it is swept in any
     /// canonical bitcoin block, despite also being swept in orphaned block(s).
     ///
@@ -8515,3 +9345,2217 @@ mod canonical_bitcoin_blockchain {
         testing::storage::drop_db(db).await;
     }
 }
+
+mod dkg_verification_rounds {
+    use super::*;
+
+    use signer::storage::model::DkgVerificationRound;
+    use signer::storage::model::DkgVerificationRoundStatus;
+
+    /// Check that only active rounds are returned and that updating a
+    /// round records its progress.
+    #[tokio::test]
+    async fn write_read_update_dkg_verification_rounds() {
+        let db = testing::storage::new_test_database().await;
+
+        let round = DkgVerificationRound {
+            aggregate_key: Faker.fake(),
+            bitcoin_block_hash: Faker.fake(),
+            bitcoin_block_height: 100u64.into(),
+            status: DkgVerificationRoundStatus::Active,
+            last_message_type: None,
+            updated_at: OffsetDateTime::UNIX_EPOCH.into(),
+        };
+        let completed_round = DkgVerificationRound {
+            aggregate_key: Faker.fake(),
+            status: DkgVerificationRoundStatus::Completed,
+            ..round.clone()
+        };
+        db.write_dkg_verification_round(&round).await.unwrap();
+        db.write_dkg_verification_round(&completed_round)
+            .await
+            .unwrap();
+
+        let rounds = db.get_active_dkg_verification_rounds().await.unwrap();
+        assert_eq!(rounds.len(), 1);
+        assert_eq!(rounds[0].aggregate_key, round.aggregate_key);
+        assert_eq!(rounds[0].bitcoin_chain_tip(), round.bitcoin_chain_tip());
+        assert_eq!(rounds[0].last_message_type, None);
+
+        // Progress on the round is recorded.
+        let progressed_round = DkgVerificationRound {
+            last_message_type: Some("nonce_request".to_string()),
+            ..round.clone()
+        };
+        db.write_dkg_verification_round(&progressed_round)
+            .await
+            .unwrap();
+
+        let rounds = db.get_active_dkg_verification_rounds().await.unwrap();
+        assert_eq!(rounds.len(), 1);
+        assert_eq!(
+            rounds[0].last_message_type.as_deref(),
+            Some("nonce_request")
+        );
+
+        // An aborted round is no longer active.
+        let aborted_round = DkgVerificationRound {
+            status: DkgVerificationRoundStatus::Aborted,
+            ..round.clone()
+        };
+        db.write_dkg_verification_round(&aborted_round)
+            .await
+            .unwrap();
+        assert!(
+            db.get_active_dkg_verification_rounds()
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        testing::storage::drop_db(db).await;
+    }
+}
+
+mod stacks_event_dead_letters {
+    use super::*;
+
+    use signer::storage::model::StacksEventDeadLetter;
+
+    /// Check that writing the same dead letter again records another
+    /// attempt and that resolved dead letters are no longer returned.
+    #[tokio::test]
+    async fn dead_letters_are_upserted_and_resolved() {
+        let db = testing::storage::new_test_database().await;
+
+        let letter = StacksEventDeadLetter {
+            block_hash: Faker.fake(),
+            txid: Faker.fake(),
+            event_index: 3,
+            raw_value: "0c00000000".to_string(),
+            error: "first error".to_string(),
+            attempts: 1,
+            created_at: OffsetDateTime::UNIX_EPOCH.into(),
+            resolved_at: None,
+        };
+        db.write_stacks_event_dead_letter(&letter).await.unwrap();
+
+        let letters = db.get_unresolved_stacks_event_dead_letters().await.unwrap();
+        assert_eq!(letters.len(), 1);
+        assert_eq!(letters[0].block_hash, letter.block_hash);
+        assert_eq!(letters[0].txid, letter.txid);
+        assert_eq!(letters[0].event_index, letter.event_index);
+        assert_eq!(letters[0].raw_value, letter.raw_value);
+        assert_eq!(letters[0].attempts, 1);
+
+        // Another failed attempt replaces the error and bumps the count.
+        let retried = StacksEventDeadLetter {
+            error: "second error".to_string(),
+            ..letter.clone()
+        };
+        db.write_stacks_event_dead_letter(&retried).await.unwrap();
+
+        let letters = db.get_unresolved_stacks_event_dead_letters().await.unwrap();
+        assert_eq!(letters.len(), 1);
+        assert_eq!(letters[0].error, "second error");
+        assert_eq!(letters[0].attempts, 2);
+
+        db.resolve_stacks_event_dead_letter(&letter.block_hash, &letter.txid, letter.event_index)
+            .await
+            .unwrap();
+        assert!(
+            db.get_unresolved_stacks_event_dead_letters()
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        testing::storage::drop_db(db).await;
+    }
+}
+
+mod signed_sweep_transactions {
+    use super::*;
+
+    use signer::storage::model::SignedSweepTransaction;
+
+    fn signed_sweep(lock_time: u32) -> SignedSweepTransaction {
+        let tx = bitcoin::Transaction {
+            version: bitcoin::transaction::Version::TWO,
+            lock_time: bitcoin::absolute::LockTime::from_consensus(lock_time),
+            input: vec![bitcoin::TxIn {
+                previous_output: bitcoin::OutPoint::new(Faker.fake(), 0),
+                witness: bitcoin::Witness::from_slice(&[[1u8; 64]]),
+                ..Default::default()
+            }],
+            output: Vec::new(),
+        };
+        SignedSweepTransaction {
+            txid: tx.compute_txid().into(),
+            tx,
+            bitcoin_chain_tip: Faker.fake(),
+            created_at: OffsetDateTime::UNIX_EPOCH.into(),
+        }
+    }
+
+    /// Check that signed sweeps round trip through the database, with
+    /// their witness data, and that they can be deleted.
+    #[tokio::test]
+    async fn signed_sweeps_are_written_read_and_deleted() {
+        let db = testing::storage::new_test_database().await;
+
+        let sweep1 = signed_sweep(1);
+        let sweep2 = signed_sweep(2);
+        db.write_signed_sweep_transaction(&sweep1).await.unwrap();
+        db.write_signed_sweep_transaction(&sweep2).await.unwrap();
+        // Writing the same sweep again is a no-op.
+        db.write_signed_sweep_transaction(&sweep1).await.unwrap();
+
+        let sweeps = db.get_signed_sweep_transactions().await.unwrap();
+        assert_eq!(sweeps.len(), 2);
+        // The sweeps are returned in the order that they were written.
+        assert_eq!(sweeps[0].txid, sweep1.txid);
+        assert_eq!(sweeps[0].tx, sweep1.tx);
+        assert_eq!(sweeps[0].bitcoin_chain_tip, sweep1.bitcoin_chain_tip);
+        assert_eq!(sweeps[1].txid, sweep2.txid);
+
+        db.delete_signed_sweep_transactions(&[sweep1.txid])
+            .await
+            .unwrap();
+
+        let sweeps = db.get_signed_sweep_transactions().await.unwrap();
+        assert_eq!(sweeps.len(), 1);
+        assert_eq!(sweeps[0].txid, sweep2.txid);
+
+        testing::storage::drop_db(db).await;
+    }
+}
+
+mod aggregate_key_history {
+    use super::*;
+
+    use signer::storage::model::AggregateKeyPeriod;
+
+    /// Check that each aggregate key is returned with the range of block
+    /// heights during which it locked the signers' UTXO, and that both
+    /// storage implementations agree.
+    #[tokio::test]
+    async fn aggregate_key_history_tracks_signers_outputs() {
+        let db = testing::storage::new_test_database().await;
+        let mem = storage::memory::Store::new_shared();
+        let mut rng = get_rng();
+
+        let mut blocks: Vec<model::BitcoinBlock> = Vec::new();
+        for height in 1u64..=3 {
+            let block = model::BitcoinBlock {
+                block_hash: Faker.fake_with_rng(&mut rng),
+                block_height: height.into(),
+                parent_hash: blocks
+                    .last()
+                    .map(|block| block.block_hash)
+                    .unwrap_or_else(|| Faker.fake_with_rng(&mut rng)),
+            };
+            db.write_bitcoin_block(&block).await.unwrap();
+            mem.write_bitcoin_block(&block).await.unwrap();
+            blocks.push(block);
+        }
+
+        let mut shares = Vec::new();
+        for _ in 0..2 {
+            let aggregate_key: PublicKey = Faker.fake_with_rng(&mut rng);
+            let dkg_shares = model::EncryptedDkgShares {
+                aggregate_key,
+                script_pubkey: aggregate_key.signers_script_pubkey().into(),
+                ..Faker.fake_with_rng(&mut rng)
+            };
+            db.write_encrypted_dkg_shares(&dkg_shares).await.unwrap();
+            mem.write_encrypted_dkg_shares(&dkg_shares).await.unwrap();
+            shares.push(dkg_shares);
+        }
+
+        // The first key locks a donation in the first block and the
+        // second key locks a signers' output in the last block.
+        let outputs = [
+            (&blocks[0], &shares[0], model::TxOutputType::Donation),
+            (&blocks[2], &shares[1], model::TxOutputType::SignersOutput),
+        ];
+        for (block, dkg_shares, output_type) in outputs {
+            let tx_ref = model::BitcoinTxRef {
+                txid: Faker.fake_with_rng(&mut rng),
+                block_hash: block.block_hash,
+            };
+            let tx_output = model::TxOutput {
+                txid: tx_ref.txid,
+                output_index: 0,
+                script_pubkey: dkg_shares.script_pubkey.clone(),
+                amount: 1_000_000,
+                output_type,
+            };
+            db.write_bitcoin_transaction(&tx_ref).await.unwrap();
+            mem.write_bitcoin_transaction(&tx_ref).await.unwrap();
+            db.write_tx_output(&tx_output).await.unwrap();
+            mem.write_tx_output(&tx_output).await.unwrap();
+        }
+
+        let expected = vec![
+            AggregateKeyPeriod {
+                aggregate_key: shares[0].aggregate_key,
+                start_height: 1u64.into(),
+                end_height: Some(3u64.into()),
+                rotate_keys_txid: None,
+            },
+            AggregateKeyPeriod {
+                aggregate_key: shares[1].aggregate_key,
+                start_height: 3u64.into(),
+                end_height: None,
+                rotate_keys_txid: None,
+            },
+        ];
+
+        let chain_tip = blocks[2].block_hash;
+        let db_history = db.get_aggregate_key_history(&chain_tip).await.unwrap();
+        let mem_history = mem.get_aggregate_key_history(&chain_tip).await.unwrap();
+        assert_eq!(db_history, expected);
+        assert_eq!(mem_history, expected);
+
+        // On the blockchain identified by the second block, the second
+        // key has not locked the signers' UTXO yet.
+        let chain_tip = blocks[1].block_hash;
+        let db_history = db.get_aggregate_key_history(&chain_tip).await.unwrap();
+        let mem_history = mem.get_aggregate_key_history(&chain_tip).await.unwrap();
+        let expected = vec![AggregateKeyPeriod {
+            end_height: None,
+            ..expected[0].clone()
+        }];
+        assert_eq!(db_history, expected);
+        assert_eq!(mem_history, expected);
+
+        testing::storage::drop_db(db).await;
+    }
+}
+
+mod request_counts {
+    use super::*;
+
+    use signer::storage::model::RequestCounts;
+    use signer::storage::model::RequestStatusCounts;
+
+    /// Check that deposit and withdrawal requests are counted by the
+    /// stage of processing that they are in, and that both storage
+    /// implementations agree.
+    #[tokio::test]
+    async fn requests_are_counted_by_status() {
+        let db = testing::storage::new_test_database().await;
+        let mem = storage::memory::Store::new_shared();
+        let mut rng = get_rng();
+
+        let mut blocks: Vec<model::BitcoinBlock> = Vec::new();
+        for height in 1u64..=2 {
+            let block = model::BitcoinBlock {
+                block_hash: Faker.fake_with_rng(&mut rng),
+                block_height: height.into(),
+                parent_hash: blocks
+                    .last()
+                    .map(|block| block.block_hash)
+                    .unwrap_or_else(|| Faker.fake_with_rng(&mut rng)),
+            };
+            db.write_bitcoin_block(&block).await.unwrap();
+            mem.write_bitcoin_block(&block).await.unwrap();
+            blocks.push(block);
+        }
+        let stacks_block = model::StacksBlock {
+            bitcoin_anchor: blocks[1].block_hash,
+            ..Faker.fake_with_rng(&mut rng)
+        };
+        db.write_stacks_block(&stacks_block).await.unwrap();
+        mem.write_stacks_block(&stacks_block).await.unwrap();
+
+        // Two of three signers are needed to accept a request, so two
+        // rejections mean that a request can never be accepted.
+        let signer_keys: Vec<PublicKey> = (0..3).map(|_| Faker.fake_with_rng(&mut rng)).collect();
+        let shares = model::EncryptedDkgShares {
+            signer_set_public_keys: signer_keys.clone(),
+            signature_share_threshold: 2,
+            dkg_shares_status: DkgSharesStatus::Verified,
+            ..Faker.fake_with_rng(&mut rng)
+        };
+        db.write_encrypted_dkg_shares(&shares).await.unwrap();
+        mem.write_encrypted_dkg_shares(&shares).await.unwrap();
+
+        // All deposits are confirmed in the first block.
+        let deposit_tx = model::BitcoinTxRef {
+            txid: Faker.fake_with_rng(&mut rng),
+            block_hash: blocks[0].block_hash,
+        };
+        db.write_bitcoin_transaction(&deposit_tx).await.unwrap();
+        mem.write_bitcoin_transaction(&deposit_tx).await.unwrap();
+
+        let deposits: Vec<model::DepositRequest> = (0..6u32)
+            .map(|output_index| model::DepositRequest {
+                txid: deposit_tx.txid,
+                output_index,
+                ..Faker.fake_with_rng(&mut rng)
+            })
+            .collect();
+        for deposit in deposits.iter() {
+            db.write_deposit_request(deposit).await.unwrap();
+            mem.write_deposit_request(deposit).await.unwrap();
+        }
+
+        // The first deposit is accepted and the second is rejected. The
+        // third deposit has no votes so it is pending.
+        let votes = [(&deposits[0], true), (&deposits[1], false)];
+        for (deposit, can_accept) in votes {
+            for signer_pub_key in signer_keys.iter().take(2) {
+                let decision = model::DepositSigner {
+                    txid: deposit.txid,
+                    output_index: deposit.output_index,
+                    signer_pub_key: *signer_pub_key,
+                    can_accept,
+                    can_sign: true,
+                };
+                db.write_deposit_signer_decision(&decision).await.unwrap();
+                mem.write_deposit_signer_decision(&decision).await.unwrap();
+            }
+        }
+
+        // The fourth deposit is in a sweep that we signed but that has
+        // not been confirmed.
+        let sighash = model::BitcoinTxSigHash {
+            prevout_txid: deposits[3].txid,
+            prevout_output_index: deposits[3].output_index,
+            prevout_type: model::TxPrevoutType::Deposit,
+            will_sign: true,
+            ..Faker.fake_with_rng(&mut rng)
+        };
+        db.write_bitcoin_txs_sighashes(&[sighash.clone()])
+            .await
+            .unwrap();
+        mem.write_bitcoin_txs_sighashes(&[sighash]).await.unwrap();
+
+        // The fifth deposit was swept in the second block.
+        let sweep_tx = model::BitcoinTxRef {
+            txid: Faker.fake_with_rng(&mut rng),
+            block_hash: blocks[1].block_hash,
+        };
+        db.write_bitcoin_transaction(&sweep_tx).await.unwrap();
+        mem.write_bitcoin_transaction(&sweep_tx).await.unwrap();
+        let prevout = model::TxPrevout {
+            txid: sweep_tx.txid,
+            prevout_txid: deposits[4].txid,
+            prevout_output_index: deposits[4].output_index,
+            prevout_type: model::TxPrevoutType::Deposit,
+            ..Faker.fake_with_rng(&mut rng)
+        };
+        db.write_tx_prevout(&prevout).await.unwrap();
+        mem.write_tx_prevout(&prevout).await.unwrap();
+
+        // The sixth deposit was completed on stacks.
+        let event = CompletedDepositEvent {
+            txid: Faker.fake_with_rng(&mut rng),
+            block_id: stacks_block.block_hash,
+            amount: deposits[5].amount,
+            outpoint: deposits[5].outpoint(),
+            sweep_block_hash: blocks[1].block_hash,
+            sweep_block_height: 2u64.into(),
+            sweep_txid: sweep_tx.txid,
+        };
+        db.write_completed_deposit_event(&event).await.unwrap();
+        mem.write_completed_deposit_event(&event).await.unwrap();
+
+        // The first withdrawal was completed and the second one has not
+        // been decided on.
+        let withdrawals: Vec<WithdrawalRequest> = (1..=2u64)
+            .map(|request_id| WithdrawalRequest {
+                request_id,
+                block_hash: stacks_block.block_hash,
+                ..Faker.fake_with_rng(&mut rng)
+            })
+            .collect();
+        for withdrawal in withdrawals.iter() {
+            db.write_withdrawal_request(withdrawal).await.unwrap();
+            mem.write_withdrawal_request(withdrawal).await.unwrap();
+        }
+        let event = WithdrawalAcceptEvent {
+            request_id: withdrawals[0].request_id,
+            block_id: stacks_block.block_hash,
+            sweep_block_hash: blocks[1].block_hash,
+            sweep_txid: sweep_tx.txid,
+            ..Faker.fake_with_rng(&mut rng)
+        };
+        db.write_withdrawal_accept_event(&event).await.unwrap();
+        mem.write_withdrawal_accept_event(&event).await.unwrap();
+
+        let expected = RequestCounts {
+            deposits: RequestStatusCounts {
+                pending: 1,
+                accepted: 1,
+                in_flight: 1,
+                swept_awaiting_stacks: 1,
+                completed: 1,
+                rejected: 1,
+            },
+            withdrawals: RequestStatusCounts {
+                pending: 1,
+                completed: 1,
+                ..Default::default()
+            },
+        };
+        let chain_tip = blocks[1].block_hash;
+        let db_counts = db.get_request_counts(&chain_tip).await.unwrap();
+        let mem_counts = mem.get_request_counts(&chain_tip).await.unwrap();
+        assert_eq!(db_counts, expected);
+        assert_eq!(mem_counts, expected);
+
+        // On the blockchain identified by the first block the sweep is
+        // not confirmed and there are no stacks blocks, so the swept and
+        // completed deposits are only accepted or pending.
+        let chain_tip = blocks[0].block_hash;
+        let db_counts = db.get_request_counts(&chain_tip).await.unwrap();
+        let mem_counts = mem.get_request_counts(&chain_tip).await.unwrap();
+        let expected = RequestCounts {
+            deposits: RequestStatusCounts {
+                pending: 3,
+                accepted: 1,
+                in_flight: 1,
+                rejected: 1,
+                ..Default::default()
+            },
+            withdrawals: RequestStatusCounts::default(),
+        };
+        assert_eq!(db_counts, expected);
+        assert_eq!(mem_counts, expected);
+
+        testing::storage::drop_db(db).await;
+    }
+}
+
+mod sweep_packages {
+    use super::*;
+
+    use signer::bitcoin::utxo::Fees;
+    use signer::bitcoin::validation::TxRequestIds;
+    use signer::message::BitcoinPreSignRequest;
+    use signer::storage::model::SweepPackage;
+
+    fn sweep_package(num_transactions: usize) -> SweepPackage {
+        let request_package = (0..num_transactions)
+            .map(|_| TxRequestIds {
+                deposits: vec![bitcoin::OutPoint::new(Faker.fake(), 1)],
+                withdrawals: vec![QualifiedRequestId {
+                    request_id: Faker.fake(),
+                    txid: Faker.fake(),
+                    block_hash: Faker.fake(),
+                }],
+            })
+            .collect();
+        SweepPackage {
+            bitcoin_chain_tip: Faker.fake(),
+            presign_request: BitcoinPreSignRequest {
+                request_package,
+                fee_rate: 12.5,
+                last_fees: None,
+                parallel_sweeps: false,
+                magic_bytes: signer::LEGACY_MAGIC_BYTES,
+            },
+            last_fees: Some(Fees::new(3_000, 150).unwrap()),
+            broadcast_txids: Vec::new(),
+        }
+    }
+
+    /// Check that sweep packages round trip through the database, that
+    /// the transactions broadcast for them are recorded in order, and that
+    /// only the package of the latest chain tip is kept.
+    #[tokio::test]
+    async fn sweep_packages_are_written_read_and_progressed() {
+        let db = testing::storage::new_test_database().await;
+
+        let package = sweep_package(2);
+        let chain_tip = package.bitcoin_chain_tip;
+        db.write_sweep_package(&package).await.unwrap();
+
+        let stored = db.get_sweep_package(&chain_tip).await.unwrap().unwrap();
+        assert_eq!(stored, package);
+        assert!(!stored.is_complete());
+
+        let txid1: BitcoinTxId = Faker.fake();
+        let txid2: BitcoinTxId = Faker.fake();
+        db.write_sweep_package_broadcast(&chain_tip, &txid1)
+            .await
+            .unwrap();
+        // Recording the same transaction again is a no-op.
+        db.write_sweep_package_broadcast(&chain_tip, &txid1)
+            .await
+            .unwrap();
+
+        let stored = db.get_sweep_package(&chain_tip).await.unwrap().unwrap();
+        assert_eq!(stored.broadcast_txids, vec![txid1]);
+        assert!(!stored.is_complete());
+
+        db.write_sweep_package_broadcast(&chain_tip, &txid2)
+            .await
+            .unwrap();
+
+        let stored = db.get_sweep_package(&chain_tip).await.unwrap().unwrap();
+        assert_eq!(stored.broadcast_txids, vec![txid1, txid2]);
+        assert!(stored.is_complete());
+
+        // Writing the package for another chain tip deletes the old one.
+        let next_package = sweep_package(1);
+        db.write_sweep_package(&next_package).await.unwrap();
+
+        assert!(db.get_sweep_package(&chain_tip).await.unwrap().is_none());
+        let stored = db
+            .get_sweep_package(&next_package.bitcoin_chain_tip)
+            .await
+            .unwrap();
+        assert_eq!(stored, Some(next_package));
+
+        testing::storage::drop_db(db).await;
+    }
+}
+
+mod presigned_withdrawals {
+    use super::*;
+
+    fn withdrawal_output(
+        req: &WithdrawalRequest,
+        bitcoin_chain_tip: BitcoinBlockHash,
+        is_valid_tx: bool,
+    ) -> BitcoinWithdrawalOutput {
+        BitcoinWithdrawalOutput {
+            request_id: req.request_id,
+            stacks_txid: req.txid,
+            stacks_block_hash: req.block_hash,
+            bitcoin_chain_tip,
+            is_valid_tx,
+            ..Faker.fake()
+        }
+    }
+
+    /// Check that the withdrawals serviced by every valid package that
+    /// the signer agreed to sign for a chain tip are returned, each once.
+    #[tokio::test]
+    async fn presigned_withdrawals_cover_all_packages_for_the_chain_tip() {
+        let db = testing::storage::new_test_database().await;
+
+        let chain_tip: BitcoinBlockHash = Faker.fake();
+        let requests: Vec<WithdrawalRequest> = (0..3).map(|_| Faker.fake()).collect();
+        for req in requests.iter() {
+            db.write_withdrawal_request(req).await.unwrap();
+        }
+
+        // The first request is serviced by two packages for the chain tip,
+        // the second only by an invalid transaction, and the third only by
+        // a package for another chain tip.
+        let outputs = [
+            withdrawal_output(&requests[0], chain_tip, true),
+            withdrawal_output(&requests[0], chain_tip, true),
+            withdrawal_output(&requests[1], chain_tip, false),
+            withdrawal_output(&requests[2], Faker.fake(), true),
+        ];
+        db.write_bitcoin_withdrawals_outputs(&outputs)
+            .await
+            .unwrap();
+
+        let presigned = db.get_presigned_withdrawals(&chain_tip).await.unwrap();
+        assert_eq!(presigned, vec![requests[0].clone()]);
+
+        let presigned = db.get_presigned_withdrawals(&Faker.fake()).await.unwrap();
+        assert!(presigned.is_empty());
+
+        testing::storage::drop_db(db).await;
+    }
+}
+
+mod request_timestamps {
+    use super::*;
+
+    use signer::storage::model::RequestLifecycleStage;
+
+    /// Check that lifecycle timestamps are recorded once per stage, that
+    /// the first block that a request was seen in is kept, and that the
+    /// timestamps of deposits and withdrawals are tracked separately.
+    #[tokio::test]
+    async fn request_timestamps_are_recorded_once_per_stage() {
+        let db = testing::storage::new_test_database().await;
+
+        let txid: BitcoinTxId = Faker.fake();
+        let block_hash: BitcoinBlockHash = Faker.fake();
+
+        let timestamps = db.get_deposit_request_timestamps(&txid, 0).await.unwrap();
+        assert!(timestamps.is_none());
+
+        let seen = RequestLifecycleStage::Seen {
+            first_seen_block: Some(block_hash),
+        };
+        db.write_deposit_request_timestamp(&txid, 0, seen)
+            .await
+            .unwrap();
+
+        let first = db
+            .get_deposit_request_timestamps(&txid, 0)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.first_seen_block, Some(block_hash));
+        assert!(first.decided_at.is_none());
+        assert!(first.time_to_completion().is_none());
+
+        // Seeing the request again in another block keeps the first block.
+        let seen_again = RequestLifecycleStage::Seen {
+            first_seen_block: Some(Faker.fake()),
+        };
+        db.write_deposit_request_timestamp(&txid, 0, seen_again)
+            .await
+            .unwrap();
+        db.write_deposit_request_timestamp(&txid, 0, RequestLifecycleStage::Decided)
+            .await
+            .unwrap();
+
+        let decided = db
+            .get_deposit_request_timestamps(&txid, 0)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(decided.first_seen_block, Some(block_hash));
+        assert_eq!(decided.created_at, first.created_at);
+        let decided_at = decided.decided_at.unwrap();
+
+        // Recording a stage again does not move its timestamp.
+        db.write_deposit_request_timestamp(&txid, 0, RequestLifecycleStage::Decided)
+            .await
+            .unwrap();
+        db.write_deposit_request_timestamp(&txid, 0, RequestLifecycleStage::Completed)
+            .await
+            .unwrap();
+
+        let completed = db
+            .get_deposit_request_timestamps(&txid, 0)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(completed.decided_at, Some(decided_at));
+        assert!(completed.swept_at.is_none());
+        assert!(completed.time_to_completion().is_some());
+
+        // Other outputs of the same transaction have their own timestamps.
+        let other = db.get_deposit_request_timestamps(&txid, 1).await.unwrap();
+        assert!(other.is_none());
+
+        // Withdrawals are keyed by their request ID, and a request may be
+        // first recorded at a later stage.
+        let request_id: u64 = 42;
+        db.write_withdrawal_request_timestamp(request_id, RequestLifecycleStage::Swept)
+            .await
+            .unwrap();
+
+        let withdrawal = db
+            .get_withdrawal_request_timestamps(request_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(withdrawal.first_seen_block.is_none());
+        assert!(withdrawal.swept_at.is_some());
+        assert!(withdrawal.completed_at.is_none());
+
+        testing::storage::drop_db(db).await;
+    }
+}
+
+mod decision_sync {
+    use super::*;
+
+    /// Check that a signer's decisions in a range of bitcoin blocks can be
+    /// fetched page by page, that the pages do not overlap, and that
+    /// decisions on requests outside of the range are left out.
+    #[tokio::test]
+    async fn signer_decisions_in_range_are_paginated() {
+        let db = testing::storage::new_test_database().await;
+        let mut rng = get_rng();
+
+        let num_signers = 3;
+        let test_model_params = testing::storage::model::Params {
+            num_bitcoin_blocks: 20,
+            num_stacks_blocks_per_bitcoin_block: 2,
+            num_deposit_requests_per_block: 3,
+            num_withdraw_requests_per_block: 3,
+            num_signers_per_request: num_signers,
+            consecutive_blocks: false,
+        };
+        let signer_set = testing::wsts::generate_signer_set_public_keys(&mut rng, num_signers);
+        let test_data = TestData::generate(&mut rng, &signer_set, &test_model_params);
+        test_data.write_to(&db).await;
+
+        let signer_public_key = signer_set[0];
+        let min_height = test_data
+            .bitcoin_blocks
+            .iter()
+            .map(|block| block.block_height)
+            .min()
+            .unwrap();
+        let start_height = min_height + 5;
+        let end_height = min_height + 12;
+        let in_range = |height: BitcoinBlockHeight| (start_height..=end_height).contains(&height);
+
+        let block_heights: BTreeMap<_, _> = test_data
+            .bitcoin_blocks
+            .iter()
+            .map(|block| (block.block_hash, block.block_height))
+            .collect();
+        let deposits_in_range: HashSet<BitcoinTxId> = test_data
+            .bitcoin_transactions
+            .iter()
+            .filter(|tx_ref| {
+                block_heights
+                    .get(&tx_ref.block_hash)
+                    .copied()
+                    .is_some_and(in_range)
+            })
+            .map(|tx_ref| tx_ref.txid)
+            .collect();
+        let withdrawals_in_range: HashSet<(u64, StacksBlockHash)> = test_data
+            .withdraw_requests
+            .iter()
+            .filter(|request| in_range(request.bitcoin_block_height))
+            .map(|request| (request.request_id, request.block_hash))
+            .collect();
+
+        let mut expected_deposits: Vec<_> = test_data
+            .deposit_signers
+            .iter()
+            .filter(|decision| decision.signer_pub_key == signer_public_key)
+            .filter(|decision| deposits_in_range.contains(&decision.txid))
+            .cloned()
+            .collect();
+        expected_deposits.sort_by_key(|decision| (decision.txid, decision.output_index));
+        let mut expected_withdrawals: Vec<_> = test_data
+            .withdraw_signers
+            .iter()
+            .filter(|decision| decision.signer_pub_key == signer_public_key)
+            .filter(|decision| {
+                withdrawals_in_range.contains(&(decision.request_id, decision.block_hash))
+            })
+            .cloned()
+            .collect();
+        expected_withdrawals.sort_by_key(|decision| (decision.request_id, decision.block_hash));
+
+        assert!(!expected_deposits.is_empty());
+        assert!(!expected_withdrawals.is_empty());
+
+        let page_size = 4;
+        let mut deposits = Vec::new();
+        let mut withdrawals = Vec::new();
+        for page in 0.. {
+            let offset = page * u32::from(page_size);
+            let deposit_page = db
+                .get_deposit_signer_decisions_in_range(
+                    &signer_public_key,
+                    start_height,
+                    end_height,
+                    page_size,
+                    offset,
+                )
+                .await
+                .unwrap();
+            let withdrawal_page = db
+                .get_withdrawal_signer_decisions_in_range(
+                    &signer_public_key,
+                    start_height,
+                    end_height,
+                    page_size,
+                    offset,
+                )
+                .await
+                .unwrap();
+            assert_le!(deposit_page.len(), page_size as usize);
+            assert_le!(withdrawal_page.len(), page_size as usize);
+
+            let done = deposit_page.is_empty() && withdrawal_page.is_empty();
+            deposits.extend(deposit_page);
+            withdrawals.extend(withdrawal_page);
+            if done {
+                break;
+            }
+        }
+
+        // The pages do not overlap, so sorting the combined pages gives
+        // back exactly the expected decisions.
+        deposits.sort_by_key(|decision| (decision.txid, decision.output_index));
+        withdrawals.sort_by_key(|decision| (decision.request_id, decision.block_hash));
+        assert_eq!(deposits, expected_deposits);
+        assert_eq!(withdrawals, expected_withdrawals);
+
+        testing::storage::drop_db(db).await;
+    }
+}
+
+mod bitcoin_backfill_progress {
+    use super::*;
+
+    use signer::storage::model::BitcoinBackfillProgress;
+    use signer::storage::model::Timestamp;
+
+    fn timestamp(unix_seconds: i64) -> Timestamp {
+        OffsetDateTime::from_unix_timestamp(unix_seconds)
+            .unwrap()
+            .into()
+    }
+
+    /// Check that the progress of a backfill is updated in place, and that
+    /// the progress of a backfill to a new chain tip replaces it.
+    #[tokio::test]
+    async fn backfill_progress_is_replaced_by_the_latest_backfill() {
+        let db = testing::storage::new_test_database().await;
+
+        let progress = db.get_bitcoin_backfill_progress().await.unwrap();
+        assert!(progress.is_none());
+
+        let mut progress = BitcoinBackfillProgress {
+            target_block_hash: Faker.fake(),
+            target_block_height: 1000u64.into(),
+            start_height: 100u64.into(),
+            processed_height: 99u64.into(),
+            started_at: timestamp(1_700_000_000),
+            updated_at: timestamp(1_700_000_000),
+        };
+        db.write_bitcoin_backfill_progress(&progress).await.unwrap();
+
+        progress.processed_height = 499u64.into();
+        progress.updated_at = timestamp(1_700_000_600);
+        db.write_bitcoin_backfill_progress(&progress).await.unwrap();
+
+        let stored = db.get_bitcoin_backfill_progress().await.unwrap();
+        assert_eq!(stored.as_ref(), Some(&progress));
+        assert_eq!(progress.blocks_processed(), 400);
+        assert_eq!(progress.blocks_remaining(), 501);
+        assert!(!progress.is_complete());
+
+        // The block observer restarts and catches up to a new chain tip.
+        let resumed = BitcoinBackfillProgress {
+            target_block_hash: Faker.fake(),
+            target_block_height: 1010u64.into(),
+            processed_height: 1010u64.into(),
+            updated_at: timestamp(1_700_001_200),
+            ..progress
+        };
+        db.write_bitcoin_backfill_progress(&resumed).await.unwrap();
+
+        let stored = db.get_bitcoin_backfill_progress().await.unwrap();
+        assert_eq!(stored, Some(resumed.clone()));
+        assert!(resumed.is_complete());
+
+        let count: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM sbtc_signer.bitcoin_backfill_progress")
+                .fetch_one(db.pool())
+                .await
+                .unwrap();
+        assert_eq!(count, 1);
+
+        testing::storage::drop_db(db).await;
+    }
+}
+
+mod deposit_status {
+    use super::*;
+
+    use signer::admin::deposit_status::DepositStage;
+    use signer::admin::deposit_status::DepositStatusQuery;
+
+    /// Check that the status of a deposit request follows it from its
+    /// confirmation, through the signers' votes and the sweep, to the
+    /// stacks transaction that completes it.
+    #[tokio::test]
+    async fn deposit_status_follows_the_deposit_lifecycle() {
+        let db = testing::storage::new_test_database().await;
+
+        let outpoint = bitcoin::OutPoint::new(Faker.fake::<BitcoinTxId>().into(), 1);
+        let query = DepositStatusQuery::new(outpoint);
+        assert!(query.execute(&db).await.unwrap().is_none());
+
+        // The deposit is confirmed in the first of three blocks.
+        let block0 = BitcoinBlock {
+            block_hash: Faker.fake(),
+            block_height: 100u64.into(),
+            parent_hash: Faker.fake(),
+        };
+        let block1 = BitcoinBlock {
+            block_hash: Faker.fake(),
+            block_height: 101u64.into(),
+            parent_hash: block0.block_hash,
+        };
+        let block2 = BitcoinBlock {
+            block_hash: Faker.fake(),
+            block_height: 102u64.into(),
+            parent_hash: block1.block_hash,
+        };
+        for block in [&block0, &block1, &block2] {
+            db.write_bitcoin_block(block).await.unwrap();
+        }
+        db.set_canonical_bitcoin_blockchain(&block2.block_hash)
+            .await
+            .unwrap();
+
+        let request = model::DepositRequest {
+            txid: outpoint.txid.into(),
+            output_index: outpoint.vout,
+            ..Faker.fake()
+        };
+        db.write_deposit_request(&request).await.unwrap();
+        db.write_bitcoin_transaction(&model::BitcoinTxRef {
+            txid: request.txid,
+            block_hash: block0.block_hash,
+        })
+        .await
+        .unwrap();
+
+        let status = query.execute(&db).await.unwrap().unwrap();
+        assert_eq!(status.stage, DepositStage::Confirmed);
+        assert_eq!(status.txid, outpoint.txid.to_string());
+        assert_eq!(status.amount, request.amount);
+        assert_eq!(status.max_fee, request.max_fee);
+        let confirmation = status.confirmation.unwrap();
+        assert_eq!(confirmation.block_hash, block0.block_hash);
+        assert_eq!(confirmation.confirmations, 3);
+        assert!(status.votes.is_empty());
+        assert!(status.assessed_fee.is_none());
+
+        // Two signers vote, one of them against the deposit.
+        for can_accept in [true, false] {
+            let decision = model::DepositSigner {
+                txid: request.txid,
+                output_index: request.output_index,
+                signer_pub_key: Faker.fake(),
+                can_accept,
+                can_sign: true,
+            };
+            db.write_deposit_signer_decision(&decision).await.unwrap();
+        }
+
+        let status = query.execute(&db).await.unwrap().unwrap();
+        assert_eq!(status.votes.len(), 2);
+        assert_eq!(status.accepted_votes(), 1);
+
+        // The deposit is swept in the second block.
+        let sweep_txid: BitcoinTxId = Faker.fake();
+        db.write_tx_prevout(&model::TxPrevout {
+            txid: sweep_txid,
+            prevout_txid: request.txid,
+            prevout_output_index: request.output_index,
+            script_pubkey: Faker.fake(),
+            amount: request.amount,
+            prevout_type: model::TxPrevoutType::Deposit,
+        })
+        .await
+        .unwrap();
+        db.write_bitcoin_transaction(&model::BitcoinTxRef {
+            txid: sweep_txid,
+            block_hash: block1.block_hash,
+        })
+        .await
+        .unwrap();
+
+        let status = query.execute(&db).await.unwrap().unwrap();
+        assert_eq!(status.stage, DepositStage::Swept);
+        let sweep = status.sweep.unwrap();
+        assert_eq!(sweep.txid, sweep_txid.to_string());
+        assert_eq!(sweep.confirmation.block_hash, block1.block_hash);
+        assert_eq!(sweep.confirmation.confirmations, 2);
+
+        // The deposit is completed on stacks, minting all but the fee.
+        let event = CompletedDepositEvent {
+            txid: Faker.fake(),
+            block_id: Faker.fake(),
+            amount: request.amount - 1_000,
+            outpoint,
+            sweep_block_hash: block1.block_hash,
+            sweep_block_height: block1.block_height,
+            sweep_txid,
+        };
+        db.write_completed_deposit_event(&event).await.unwrap();
+
+        let status = query.execute(&db).await.unwrap().unwrap();
+        assert_eq!(status.stage, DepositStage::Completed);
+        assert_eq!(status.assessed_fee, Some(1_000));
+        let completion = status.completion.unwrap();
+        assert_eq!(completion.stacks_txid, event.txid.to_string());
+        assert_eq!(completion.amount_minted, event.amount);
+
+        testing::storage::drop_db(db).await;
+    }
+}
+
+mod coordinator_takeovers {
+    use super::*;
+
+    use signer::storage::model::CoordinatorTakeover;
+    use signer::storage::model::WriteOutcome;
+
+    /// Check that only the first takeover of a tenure is recorded.
+    #[tokio::test]
+    async fn only_the_first_takeover_of_a_tenure_is_written() {
+        let db = testing::storage::new_test_database().await;
+
+        let takeover: CoordinatorTakeover = Faker.fake();
+        let other_takeover = CoordinatorTakeover {
+            deputy_public_key: Faker.fake(),
+            ..takeover
+        };
+
+        let stored = db
+            .get_coordinator_takeover(&takeover.bitcoin_chain_tip)
+            .await
+            .unwrap();
+        assert!(stored.is_none());
+
+        let outcome = db.write_coordinator_takeover(&takeover).await.unwrap();
+        assert_eq!(outcome, WriteOutcome::Inserted);
+        let outcome = db
+            .write_coordinator_takeover(&other_takeover)
+            .await
+            .unwrap();
+        assert_eq!(outcome, WriteOutcome::AlreadyExists);
+
+        let stored = db
+            .get_coordinator_takeover(&takeover.bitcoin_chain_tip)
+            .await
+            .unwrap();
+        assert_eq!(stored, Some(takeover));
+
+        testing::storage::drop_db(db).await;
+    }
+}
+
+mod request_overrides {
+    use super::*;
+
+    use signer::storage::model::NewRequestOverride;
+    use signer::storage::model::OverrideDirective;
+    use signer::storage::model::OverrideTarget;
+    use signer::storage::postgres::ColumnEncryptionKey;
+    use signer::storage::postgres::encryption::ENCRYPTED_COLUMN_PREFIX;
+
+    #[tokio::test]
+    async fn write_read_revoke_request_overrides() {
+        let db = testing::storage::new_test_database().await;
+        let mut rng = get_rng();
+
+        let deposit_target = OverrideTarget::Deposit {
+            txid: Faker.fake_with_rng(&mut rng),
+            output_index: 2,
+        };
+        let withdrawal_target = OverrideTarget::Withdrawal { request_id: 42 };
+
+        let reject = NewRequestOverride {
+            target: deposit_target,
+            directive: OverrideDirective::ForceReject,
+            reason: "law enforcement request".to_string(),
+            expires_at: None,
+        };
+        let stored_reject = db.write_request_override(&reject).await.unwrap();
+        assert_eq!(stored_reject.target, deposit_target);
+        assert_eq!(stored_reject.directive, OverrideDirective::ForceReject);
+        assert!(stored_reject.revoked_at.is_none());
+
+        // An override that has already expired never applies.
+        let expired = NewRequestOverride {
+            target: withdrawal_target,
+            directive: OverrideDirective::ForceAccept,
+            reason: "reconsider".to_string(),
+            expires_at: Some((time::OffsetDateTime::now_utc() - time::Duration::hours(1)).into()),
+        };
+        db.write_request_override(&expired).await.unwrap();
+
+        let active = db
+            .get_active_request_override(&deposit_target)
+            .await
+            .unwrap();
+        assert_eq!(active, Some(stored_reject.clone()));
+        let active = db
+            .get_active_request_override(&withdrawal_target)
+            .await
+            .unwrap();
+        assert!(active.is_none());
+
+        // The most recently placed override of a request applies.
+        let accept = NewRequestOverride {
+            directive: OverrideDirective::ForceAccept,
+            reason: "request withdrawn".to_string(),
+            ..reject.clone()
+        };
+        let stored_accept = db.write_request_override(&accept).await.unwrap();
+        let active = db
+            .get_active_request_override(&deposit_target)
+            .await
+            .unwrap();
+        assert_eq!(active.map(|active| active.id), Some(stored_accept.id));
+
+        // Revoking it makes the previous override apply again, and an
+        // override can only be revoked once.
+        assert!(db.revoke_request_override(stored_accept.id).await.unwrap());
+        assert!(!db.revoke_request_override(stored_accept.id).await.unwrap());
+        let active = db
+            .get_active_request_override(&deposit_target)
+            .await
+            .unwrap();
+        assert_eq!(active.map(|active| active.id), Some(stored_reject.id));
+
+        // Every override is kept, newest first.
+        let overrides = db.get_request_overrides().await.unwrap();
+        assert_eq!(overrides.len(), 3);
+        assert_eq!(overrides[0].id, stored_accept.id);
+        assert!(overrides[0].revoked_at.is_some());
+
+        testing::storage::drop_db(db).await;
+    }
+
+    /// Read the reasons of all request overrides, as they are stored in
+    /// the database.
+    async fn raw_override_reasons(db: &PgStore) -> Vec<String> {
+        sqlx::query_scalar::<_, String>(
+            "SELECT reason FROM sbtc_signer.request_overrides ORDER BY id",
+        )
+        .fetch_all(db.pool())
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn request_override_reasons_are_encrypted_at_rest() {
+        let db = testing::storage::new_test_database().await;
+        let mut rng = get_rng();
+
+        let key = ColumnEncryptionKey::new([42; 32]);
+        let encrypted_db = db.clone().with_column_encryption_key(key);
+
+        // This override is written before column encryption is enabled.
+        let legacy = NewRequestOverride {
+            target: OverrideTarget::Withdrawal { request_id: 1 },
+            directive: OverrideDirective::ForceReject,
+            reason: "written in plaintext".to_string(),
+            expires_at: None,
+        };
+        db.write_request_override(&legacy).await.unwrap();
+
+        let request_override = NewRequestOverride {
+            target: OverrideTarget::Deposit {
+                txid: Faker.fake_with_rng(&mut rng),
+                output_index: 0,
+            },
+            reason: "sanctioned sender".to_string(),
+            ..legacy.clone()
+        };
+        let stored = encrypted_db
+            .write_request_override(&request_override)
+            .await
+            .unwrap();
+        assert_eq!(stored.reason, request_override.reason);
+
+        let reasons = raw_override_reasons(&db).await;
+        assert_eq!(reasons[0], legacy.reason);
+        assert!(reasons[1].starts_with(ENCRYPTED_COLUMN_PREFIX));
+
+        // Both the legacy and the encrypted reasons can be read with the
+        // key, but the encrypted one cannot be read without it.
+        let active = encrypted_db
+            .get_active_request_override(&request_override.target)
+            .await
+            .unwrap();
+        assert_eq!(active, Some(stored));
+        let overrides = encrypted_db.get_request_overrides().await.unwrap();
+        assert_eq!(overrides[1].reason, legacy.reason);
+
+        let result = db.get_request_overrides().await;
+        assert!(matches!(result, Err(Error::MissingColumnEncryptionKey)));
+
+        // Migrating the existing rows only touches the legacy one, and
+        // running it again does nothing.
+        assert!(db.encrypt_plaintext_columns().await.is_err());
+        assert_eq!(encrypted_db.encrypt_plaintext_columns().await.unwrap(), 1);
+        assert_eq!(encrypted_db.encrypt_plaintext_columns().await.unwrap(), 0);
+
+        let reasons = raw_override_reasons(&db).await;
+        assert!(
+            reasons
+                .iter()
+                .all(|reason| reason.starts_with(ENCRYPTED_COLUMN_PREFIX))
+        );
+        let overrides = encrypted_db.get_request_overrides().await.unwrap();
+        assert_eq!(overrides[1].reason, legacy.reason);
+
+        testing::storage::drop_db(db).await;
+    }
+}
+
+mod withdrawal_rejection_reasons {
+    use super::*;
+
+    use signer::storage::postgres::ColumnEncryptionKey;
+    use signer::storage::postgres::encryption::ENCRYPTED_COLUMN_PREFIX;
+
+    /// Read the rejection reason of the withdrawal signer decision of the
+    /// given signer, as it is stored in the database.
+    async fn raw_rejection_reason(db: &PgStore, signer_pub_key: &PublicKey) -> Option<String> {
+        sqlx::query_scalar::<_, Option<String>>(
+            r#"
+            SELECT rejection_reason
+            FROM sbtc_signer.withdrawal_signers
+            WHERE signer_pub_key = $1
+            "#,
+        )
+        .bind(signer_pub_key)
+        .fetch_one(db.pool())
+        .await
+        .unwrap()
+    }
+
+    /// Return whether the rejection reason of the withdrawal signer
+    /// decision of the given signer is encrypted in the database.
+    async fn is_encrypted(db: &PgStore, signer_pub_key: &PublicKey) -> bool {
+        raw_rejection_reason(db, signer_pub_key)
+            .await
+            .is_some_and(|reason| reason.starts_with(ENCRYPTED_COLUMN_PREFIX))
+    }
+
+    #[tokio::test]
+    async fn withdrawal_rejection_reasons_are_encrypted_at_rest() {
+        let db = testing::storage::new_test_database().await;
+        let mut rng = get_rng();
+
+        let key = ColumnEncryptionKey::new([42; 32]);
+        let encrypted_db = db.clone().with_column_encryption_key(key);
+
+        let block: StacksBlock = Faker.fake_with_rng(&mut rng);
+        let request = model::WithdrawalRequest {
+            block_hash: block.block_hash,
+            ..Faker.fake_with_rng(&mut rng)
+        };
+        db.write_stacks_block(&block).await.unwrap();
+        db.write_withdrawal_request(&request).await.unwrap();
+
+        // This decision is written before column encryption is enabled.
+        let legacy = WithdrawalSigner {
+            request_id: request.request_id,
+            txid: request.txid,
+            block_hash: request.block_hash,
+            signer_pub_key: Faker.fake_with_rng(&mut rng),
+            is_accepted: false,
+            rejection_reason: Some("written in plaintext".to_string()),
+        };
+        db.write_withdrawal_signer_decision(&legacy).await.unwrap();
+
+        let decision = WithdrawalSigner {
+            signer_pub_key: Faker.fake_with_rng(&mut rng),
+            rejection_reason: Some("sanctioned recipient".to_string()),
+            ..legacy.clone()
+        };
+        encrypted_db
+            .write_withdrawal_signer_decision(&decision)
+            .await
+            .unwrap();
+
+        let reason = raw_rejection_reason(&db, &legacy.signer_pub_key).await;
+        assert_eq!(reason, legacy.rejection_reason);
+        assert!(is_encrypted(&db, &decision.signer_pub_key).await);
+
+        // Both the legacy and the encrypted reasons can be read with the
+        // key, but the encrypted one cannot be read without it.
+        let mut signers = encrypted_db
+            .get_withdrawal_signers(request.request_id, &request.block_hash)
+            .await
+            .unwrap();
+        let mut expected = vec![legacy.clone(), decision.clone()];
+        signers.sort();
+        expected.sort();
+        assert_eq!(signers, expected);
+
+        let result = db
+            .get_withdrawal_signers(request.request_id, &request.block_hash)
+            .await;
+        assert!(matches!(result, Err(Error::MissingColumnEncryptionKey)));
+
+        // Migrating the existing rows only touches the legacy one, and
+        // running it again does nothing.
+        assert_eq!(encrypted_db.encrypt_plaintext_columns().await.unwrap(), 1);
+        assert_eq!(encrypted_db.encrypt_plaintext_columns().await.unwrap(), 0);
+
+        assert!(is_encrypted(&db, &legacy.signer_pub_key).await);
+        assert!(is_encrypted(&db, &decision.signer_pub_key).await);
+        let mut signers = encrypted_db
+            .get_withdrawal_signers(request.request_id, &request.block_hash)
+            .await
+            .unwrap();
+        signers.sort();
+        assert_eq!(signers, expected);
+
+        testing::storage::drop_db(db).await;
+    }
+}
+
+mod chain_history {
+    use super::*;
+
+    #[tokio::test]
+    async fn get_chain_history_clamps_to_stored_blocks() {
+        let db = testing::storage::new_test_database().await;
+        let mut rng = get_rng();
+
+        // Store a chain of five blocks, at heights 100 through 104, whose
+        // first block has a parent that we do not have.
+        let mut parent_hash: BitcoinBlockHash = Faker.fake_with_rng(&mut rng);
+        let mut chain_tip = parent_hash;
+        for height in 100u64..105 {
+            let block = model::BitcoinBlock {
+                block_hash: Faker.fake_with_rng(&mut rng),
+                block_height: height.into(),
+                parent_hash,
+            };
+            db.write_bitcoin_block(&block).await.unwrap();
+            parent_hash = block.block_hash;
+            chain_tip = block.block_hash;
+        }
+
+        // We have all of the three most recent blocks.
+        let history = db
+            .get_chain_history(&chain_tip, 3, 0u64.into())
+            .await
+            .unwrap();
+        assert_eq!(history.available_blocks, 3);
+        assert_eq!(history.oldest_block_height, Some(102u64.into()));
+        assert!(history.history_complete);
+
+        // We only have five of the ten most recent blocks.
+        let history = db
+            .get_chain_history(&chain_tip, 10, 0u64.into())
+            .await
+            .unwrap();
+        assert_eq!(history.requested_blocks, 10);
+        assert_eq!(history.available_blocks, 5);
+        assert_eq!(history.oldest_block_height, Some(100u64.into()));
+        assert!(!history.history_complete);
+
+        // But those are all of the blocks that matter if sBTC started at
+        // the oldest one.
+        let history = db
+            .get_chain_history(&chain_tip, 10, 100u64.into())
+            .await
+            .unwrap();
+        assert!(history.history_complete);
+
+        // We know nothing about an unknown chain tip.
+        let unknown: BitcoinBlockHash = Faker.fake_with_rng(&mut rng);
+        let history = db
+            .get_chain_history(&unknown, 10, 0u64.into())
+            .await
+            .unwrap();
+        assert_eq!(history.available_blocks, 0);
+        assert_eq!(history.oldest_block_height, None);
+        assert!(!history.history_complete);
+
+        testing::storage::drop_db(db).await;
+    }
+}
+
+mod withdrawal_requests_by_id {
+    use super::*;
+
+    /// Check that all withdrawal requests with a request ID are returned,
+    /// oldest first, and none with other request IDs.
+    #[tokio::test]
+    async fn withdrawal_requests_are_fetched_by_request_id() {
+        let db = testing::storage::new_test_database().await;
+        let mut rng = get_rng();
+
+        let request: WithdrawalRequest = Faker.fake_with_rng(&mut rng);
+        let forked = WithdrawalRequest {
+            block_hash: Faker.fake_with_rng(&mut rng),
+            bitcoin_block_height: request.bitcoin_block_height + 1,
+            ..request.clone()
+        };
+        let other = WithdrawalRequest {
+            request_id: request.request_id ^ 1,
+            ..Faker.fake_with_rng(&mut rng)
+        };
+        for req in [&forked, &request, &other] {
+            db.write_withdrawal_request(req).await.unwrap();
+        }
+
+        let requests = db
+            .get_withdrawal_requests_by_id(request.request_id)
+            .await
+            .unwrap();
+        assert_eq!(requests, vec![request.clone(), forked]);
+
+        let unknown = request.request_id ^ 2;
+        let requests = db.get_withdrawal_requests_by_id(unknown).await.unwrap();
+        assert!(requests.is_empty());
+
+        testing::storage::drop_db(db).await;
+    }
+}
+
+mod inflight_contract_calls {
+    use super::*;
+
+    /// Check that there is at most one in-flight contract call for each
+    /// request, and that deleting it only affects that request.
+    #[tokio::test]
+    async fn inflight_contract_calls_are_keyed_by_request() {
+        let db = testing::storage::new_test_database().await;
+        let mut rng = get_rng();
+
+        let deposit = model::InflightContractCall {
+            target: model::ContractCallTarget::Deposit {
+                txid: Faker.fake_with_rng(&mut rng),
+                output_index: 1,
+            },
+            ..Faker.fake_with_rng(&mut rng)
+        };
+        let request_id = model::QualifiedRequestId {
+            request_id: 7,
+            txid: Faker.fake_with_rng(&mut rng),
+            block_hash: Faker.fake_with_rng(&mut rng),
+        };
+        let withdrawal = model::InflightContractCall {
+            target: model::ContractCallTarget::Withdrawal { id: request_id.clone() },
+            bitcoin_block_height: 100u64.into(),
+            ..Faker.fake_with_rng(&mut rng)
+        };
+        // The same request ID in a different stacks block is a different
+        // request, so it gets its own contract call.
+        let forked_withdrawal = model::InflightContractCall {
+            target: model::ContractCallTarget::Withdrawal {
+                id: model::QualifiedRequestId {
+                    block_hash: Faker.fake_with_rng(&mut rng),
+                    ..request_id
+                },
+            },
+            bitcoin_block_height: 100u64.into(),
+            ..Faker.fake_with_rng(&mut rng)
+        };
+        db.write_inflight_contract_call(&deposit).await.unwrap();
+        db.write_inflight_contract_call(&withdrawal).await.unwrap();
+        db.write_inflight_contract_call(&forked_withdrawal)
+            .await
+            .unwrap();
+
+        let stored = db
+            .get_inflight_contract_call(&deposit.target)
+            .await
+            .unwrap();
+        assert_eq!(stored.as_ref(), Some(&deposit));
+        let stored = db
+            .get_inflight_contract_call(&withdrawal.target)
+            .await
+            .unwrap();
+        assert_eq!(stored.as_ref(), Some(&withdrawal));
+        let stored = db
+            .get_inflight_contract_call(&forked_withdrawal.target)
+            .await
+            .unwrap();
+        assert_eq!(stored.as_ref(), Some(&forked_withdrawal));
+
+        // A new contract call for the same request replaces the old one.
+        let resubmitted = model::InflightContractCall {
+            txid: Faker.fake_with_rng(&mut rng),
+            bitcoin_block_height: 103u64.into(),
+            bitcoin_block_hash: Faker.fake_with_rng(&mut rng),
+            ..withdrawal.clone()
+        };
+        db.write_inflight_contract_call(&resubmitted).await.unwrap();
+        let stored = db
+            .get_inflight_contract_call(&withdrawal.target)
+            .await
+            .unwrap();
+        assert_eq!(stored.as_ref(), Some(&resubmitted));
+        let stored = db
+            .get_inflight_contract_call(&forked_withdrawal.target)
+            .await
+            .unwrap();
+        assert_eq!(stored.as_ref(), Some(&forked_withdrawal));
+
+        assert!(
+            db.delete_inflight_contract_call(&deposit.target)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !db.delete_inflight_contract_call(&deposit.target)
+                .await
+                .unwrap()
+        );
+        let stored = db
+            .get_inflight_contract_call(&deposit.target)
+            .await
+            .unwrap();
+        assert_eq!(stored, None);
+        let stored = db
+            .get_inflight_contract_call(&withdrawal.target)
+            .await
+            .unwrap();
+        assert_eq!(stored, Some(resubmitted));
+
+        assert!(
+            db.delete_inflight_contract_call(&forked_withdrawal.target)
+                .await
+                .unwrap()
+        );
+        let stored = db
+            .get_inflight_contract_call(&withdrawal.target)
+            .await
+            .unwrap();
+        assert!(stored.is_some());
+
+        testing::storage::drop_db(db).await;
+    }
+}
+
+mod idempotent_writes {
+    use super::*;
+
+    use signer::storage::model::WriteOutcome;
+
+    /// Check that writing the same stacks events twice reports the second
+    /// write as a duplicate, and that only one row is stored for each.
+    #[tokio::test]
+    async fn duplicate_stacks_events_are_reported() {
+        let db = testing::storage::new_test_database().await;
+        let mut rng = get_rng();
+
+        let completed_deposit: CompletedDepositEvent = Faker.fake_with_rng(&mut rng);
+        let withdrawal_accept: WithdrawalAcceptEvent = Faker.fake_with_rng(&mut rng);
+        let withdrawal_reject: WithdrawalRejectEvent = Faker.fake_with_rng(&mut rng);
+
+        for expected in [WriteOutcome::Inserted, WriteOutcome::AlreadyExists] {
+            let outcome = db
+                .write_completed_deposit_event(&completed_deposit)
+                .await
+                .unwrap();
+            assert_eq!(outcome, expected);
+            let outcome = db
+                .write_withdrawal_accept_event(&withdrawal_accept)
+                .await
+                .unwrap();
+            assert_eq!(outcome, expected);
+            let outcome = db
+                .write_withdrawal_reject_event(&withdrawal_reject)
+                .await
+                .unwrap();
+            assert_eq!(outcome, expected);
+        }
+
+        for table in [
+            "completed_deposit_events",
+            "withdrawal_accept_events",
+            "withdrawal_reject_events",
+        ] {
+            let count =
+                sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) FROM sbtc_signer.{table}"))
+                    .fetch_one(db.pool())
+                    .await
+                    .unwrap();
+            assert_eq!(count, 1, "{table}");
+        }
+
+        // The same event confirmed in another stacks block, such as after
+        // a fork, is not a duplicate.
+        let forked = CompletedDepositEvent {
+            block_id: Faker.fake_with_rng(&mut rng),
+            ..completed_deposit
+        };
+        let outcome = db.write_completed_deposit_event(&forked).await.unwrap();
+        assert_eq!(outcome, WriteOutcome::Inserted);
+
+        testing::storage::drop_db(db).await;
+    }
+
+    /// Check that writing a bitcoin block and a withdrawal request twice
+    /// leaves the original rows in place and reports the duplicates.
+    #[tokio::test]
+    async fn duplicate_rows_are_reported() {
+        let db = testing::storage::new_test_database().await;
+        let mut rng = get_rng();
+
+        let block: BitcoinBlock = Faker.fake_with_rng(&mut rng);
+        let outcome = db.write_bitcoin_block(&block).await.unwrap();
+        assert!(outcome.is_inserted());
+        let outcome = db.write_bitcoin_block(&block).await.unwrap();
+        assert_eq!(outcome, WriteOutcome::AlreadyExists);
+
+        let request: WithdrawalRequest = Faker.fake_with_rng(&mut rng);
+        let outcome = db.write_withdrawal_request(&request).await.unwrap();
+        assert!(outcome.is_inserted());
+
+        let changed = WithdrawalRequest {
+            amount: request.amount + 1,
+            ..request.clone()
+        };
+        let outcome = db.write_withdrawal_request(&changed).await.unwrap();
+        assert_eq!(outcome, WriteOutcome::AlreadyExists);
+
+        let stored = db
+            .get_withdrawal_requests_by_id(request.request_id)
+            .await
+            .unwrap();
+        assert_eq!(stored, vec![request]);
+
+        testing::storage::drop_db(db).await;
+    }
+}
+
+mod withdrawal_output_labels {
+    use super::*;
+
+    use signer::storage::model::WithdrawalOutputLabel;
+
+    async fn get_label(db: &PgStore, output: &model::WithdrawalTxOutput) -> Option<String> {
+        sqlx::query_scalar(
+            r#"
+            SELECT label
+            FROM sbtc_signer.bitcoin_withdrawal_tx_outputs
+            WHERE txid = $1
+              AND output_index = $2
+            "#,
+        )
+        .bind(output.txid)
+        .bind(output.output_index as i32)
+        .fetch_one(db.pool())
+        .await
+        .unwrap()
+    }
+
+    /// Check that withdrawal outputs are labeled when they are written,
+    /// and relabeled when the registry of labels is replaced.
+    #[tokio::test]
+    async fn withdrawal_outputs_are_labeled() {
+        let db = testing::storage::new_test_database().await;
+        let mut rng = get_rng();
+
+        let exchange: model::ScriptPubKey = Faker.fake_with_rng(&mut rng);
+        let custodian: model::ScriptPubKey = Faker.fake_with_rng(&mut rng);
+
+        let labels = [WithdrawalOutputLabel {
+            script_pubkey: exchange.clone(),
+            label: "Exchange".to_string(),
+        }];
+        db.write_withdrawal_output_labels(&labels).await.unwrap();
+
+        let mut outputs = Vec::new();
+        for script_pubkey in [exchange.clone(), custodian.clone()] {
+            let tx_output = model::TxOutput {
+                script_pubkey,
+                output_type: model::TxOutputType::Withdrawal,
+                ..Faker.fake_with_rng(&mut rng)
+            };
+            let output = model::WithdrawalTxOutput {
+                txid: tx_output.txid,
+                output_index: tx_output.output_index,
+                request_id: Faker.fake_with_rng(&mut rng),
+            };
+            db.write_tx_output(&tx_output).await.unwrap();
+            db.write_withdrawal_tx_output(&output).await.unwrap();
+            outputs.push(output);
+        }
+
+        assert_eq!(
+            get_label(&db, &outputs[0]).await.as_deref(),
+            Some("Exchange")
+        );
+        assert_eq!(get_label(&db, &outputs[1]).await, None);
+
+        // Replacing the registry drops the labels that are no longer
+        // configured and applies the new ones to the existing outputs.
+        let labels = [WithdrawalOutputLabel {
+            script_pubkey: custodian,
+            label: "Custodian".to_string(),
+        }];
+        db.write_withdrawal_output_labels(&labels).await.unwrap();
+
+        assert_eq!(get_label(&db, &outputs[0]).await, None);
+        assert_eq!(
+            get_label(&db, &outputs[1]).await.as_deref(),
+            Some("Custodian")
+        );
+
+        let registry: Vec<WithdrawalOutputLabel> =
+            sqlx::query_as("SELECT script_pubkey, label FROM sbtc_signer.withdrawal_output_labels")
+                .fetch_all(db.pool())
+                .await
+                .unwrap();
+        assert_eq!(registry, labels);
+
+        testing::storage::drop_db(db).await;
+    }
+}
+
+mod spilled_state_machines {
+    use super::*;
+
+    use signer::secret::Secret;
+    use signer::storage::model::SpilledStateMachine;
+
+    /// Check that spilled state machines can be taken exactly once, and
+    /// that pruning only deletes those spilled below the given height.
+    #[tokio::test]
+    async fn spilled_state_machines_are_taken_and_pruned() {
+        let db = testing::storage::new_test_database().await;
+
+        let old = SpilledStateMachine {
+            state_machine_id: "old".to_string(),
+            encrypted_state: Secret::new(vec![1, 2, 3]),
+            bitcoin_block_height: 10u64.into(),
+        };
+        let new = SpilledStateMachine {
+            state_machine_id: "new".to_string(),
+            encrypted_state: Secret::new(vec![4, 5, 6]),
+            bitcoin_block_height: 20u64.into(),
+        };
+        db.write_spilled_state_machine(&old).await.unwrap();
+        db.write_spilled_state_machine(&new).await.unwrap();
+
+        // Spilling a state machine again replaces what was stored before.
+        let new = SpilledStateMachine {
+            encrypted_state: Secret::new(vec![7, 8, 9]),
+            ..new
+        };
+        db.write_spilled_state_machine(&new).await.unwrap();
+
+        let pruned = db.prune_spilled_state_machines(15u64.into()).await.unwrap();
+        assert_eq!(pruned, 1);
+        assert!(
+            db.take_spilled_state_machine("old")
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let taken = db.take_spilled_state_machine("new").await.unwrap();
+        assert_eq!(taken, Some(new));
+        assert!(
+            db.take_spilled_state_machine("new")
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        testing::storage::drop_db(db).await;
+    }
+}
+
+mod sweep_tx_proofs {
+    use super::*;
+
+    /// Mine a header on top of the given parent, without any proof of
+    /// work, which storage does not check.
+    fn header(
+        prev_blockhash: bitcoin::BlockHash,
+        rng: &mut impl rand::RngCore,
+    ) -> bitcoin::block::Header {
+        bitcoin::block::Header {
+            version: bitcoin::block::Version::TWO,
+            prev_blockhash,
+            merkle_root: testing::dummy::merkle_root(&Faker, rng),
+            time: Faker.fake_with_rng(rng),
+            bits: bitcoin::CompactTarget::from_consensus(0x207fffff),
+            nonce: Faker.fake_with_rng(rng),
+        }
+    }
+
+    /// Write the bitcoin block with the given header at the given height,
+    /// along with the header itself.
+    async fn write_block(db: &PgStore, header: &bitcoin::block::Header, height: u64) {
+        let block = BitcoinBlock {
+            block_hash: header.block_hash().into(),
+            block_height: height.into(),
+            parent_hash: header.prev_blockhash.into(),
+        };
+        db.write_bitcoin_block(&block).await.unwrap();
+        let outcome = db.write_bitcoin_block_header(header).await.unwrap();
+        assert!(outcome.is_inserted());
+        // Writing the same header twice is fine.
+        let outcome = db.write_bitcoin_block_header(header).await.unwrap();
+        assert!(!outcome.is_inserted());
+    }
+
+    /// Write a proof that the transaction with the given ID was included
+    /// in the block with the given header.
+    async fn write_proof(
+        db: &PgStore,
+        txid: BitcoinTxId,
+        header: &bitcoin::block::Header,
+        height: u64,
+    ) -> model::BitcoinTxMerkleProof {
+        let tx_ref = model::BitcoinTxRef {
+            txid,
+            block_hash: header.block_hash().into(),
+        };
+        db.write_bitcoin_transaction(&tx_ref).await.unwrap();
+
+        let txids = [txid.into(), bitcoin::Txid::all_zeros()];
+        let proof = model::BitcoinTxMerkleProof {
+            txid,
+            block_hash: header.block_hash().into(),
+            block_height: height.into(),
+            merkle_block: bitcoin::MerkleBlock::from_header_txids_with_predicate(
+                header,
+                &txids,
+                |other| *other == txids[0],
+            ),
+        };
+        db.write_bitcoin_tx_merkle_proofs(slice::from_ref(&proof))
+            .await
+            .unwrap();
+        proof
+    }
+
+    /// Check that proofs are only returned for sweep transactions in
+    /// blocks on the canonical chain, and that the headers returned are
+    /// those of the canonical chain, ordered by height.
+    #[tokio::test]
+    async fn sweep_tx_proofs_are_served_from_the_canonical_chain() {
+        let db = testing::storage::new_test_database().await;
+        let rng = &mut get_rng();
+
+        let mut headers = vec![header(testing::dummy::block_hash(&Faker, rng), rng)];
+        for _ in 1..5 {
+            let parent = headers.last().unwrap().block_hash();
+            headers.push(header(parent, rng));
+        }
+        for (height, header) in headers.iter().enumerate() {
+            write_block(&db, header, height as u64).await;
+        }
+        // A block that forks off the canonical chain at height 2.
+        let fork = header(headers[1].block_hash(), rng);
+        write_block(&db, &fork, 2).await;
+
+        let chain_tip: BitcoinBlockHash = headers[4].block_hash().into();
+
+        let sweep_txid: BitcoinTxId = Faker.fake_with_rng(rng);
+        let fork_sweep_txid: BitcoinTxId = Faker.fake_with_rng(rng);
+        let other_txid: BitcoinTxId = Faker.fake_with_rng(rng);
+        let proof = write_proof(&db, sweep_txid, &headers[1], 1).await;
+        write_proof(&db, fork_sweep_txid, &fork, 2).await;
+        write_proof(&db, other_txid, &headers[2], 2).await;
+
+        for txid in [sweep_txid, fork_sweep_txid] {
+            let mut prevout: model::TxPrevout = Faker.fake_with_rng(rng);
+            prevout.txid = txid;
+            prevout.prevout_type = model::TxPrevoutType::SignersInput;
+            db.write_tx_prevout(&prevout).await.unwrap();
+        }
+
+        let stored = db
+            .get_sweep_tx_merkle_proof(&chain_tip, &sweep_txid)
+            .await
+            .unwrap();
+        assert_eq!(stored, Some(proof));
+
+        // The fork is not on the canonical chain, and the other
+        // transaction is not a sweep.
+        for txid in [fork_sweep_txid, other_txid] {
+            let stored = db
+                .get_sweep_tx_merkle_proof(&chain_tip, &txid)
+                .await
+                .unwrap();
+            assert!(stored.is_none());
+        }
+
+        let stored = db
+            .get_bitcoin_block_headers(&chain_tip, 2u64.into(), 144)
+            .await
+            .unwrap();
+        assert_eq!(stored, headers[2..]);
+
+        let stored = db
+            .get_bitcoin_block_headers(&chain_tip, 2u64.into(), 2)
+            .await
+            .unwrap();
+        assert_eq!(stored, headers[2..4]);
+
+        testing::storage::drop_db(db).await;
+    }
+}
+
+mod snapshots {
+    use super::*;
+
+    use signer::admin::snapshot;
+    use signer::testing::storage::DATABASE_URL_BASE;
+    use signer::testing::storage::model::Params;
+
+    fn database_url(db: &PgStore) -> String {
+        let db_name = db
+            .pool()
+            .connect_options()
+            .get_database()
+            .unwrap()
+            .to_string();
+        format!("{DATABASE_URL_BASE}/{db_name}")
+    }
+
+    #[tokio::test]
+    async fn snapshots_restore_into_an_empty_database() {
+        let db = testing::storage::new_test_database().await;
+        let target = testing::storage::new_empty_test_database().await;
+        let mut rng = get_rng();
+
+        let params = Params {
+            num_bitcoin_blocks: 5,
+            num_stacks_blocks_per_bitcoin_block: 2,
+            num_deposit_requests_per_block: 2,
+            num_withdraw_requests_per_block: 2,
+            num_signers_per_request: 0,
+            consecutive_blocks: false,
+        };
+        TestData::generate(&mut rng, &[], &params)
+            .write_to(&db)
+            .await;
+
+        let directory = tempfile::tempdir().unwrap();
+        let created = snapshot::create_snapshot(&db, &database_url(&db), directory.path())
+            .await
+            .unwrap();
+        assert_eq!(created.state.row_counts["bitcoin_blocks"], 5);
+        assert_eq!(created.state.row_counts["deposit_requests"], 10);
+
+        // The dump and the restore both authenticate with the password
+        // from the URL, which is passed to them through PGPASSWORD.
+        let restored = snapshot::restore_snapshot(&database_url(&target), directory.path())
+            .await
+            .unwrap();
+        assert_eq!(restored, created);
+
+        // A second restore is refused since the database now has the
+        // signer's tables.
+        let error = snapshot::restore_snapshot(&database_url(&target), directory.path())
+            .await
+            .unwrap_err();
+        assert!(matches!(error, Error::SnapshotRestoreTargetNotEmpty));
+
+        testing::storage::drop_db(target).await;
+        testing::storage::drop_db(db).await;
+    }
+}
+
+mod stacks_chain_tip_v2 {
+    use super::*;
+
+    use signer::storage::model::StacksChainTip;
+    use signer::storage::model::StacksTenure;
+
+    /// Check that when a new tenure forks off the end of the previous
+    /// tenure, the block from the newer tenure is the chain tip even if
+    /// the orphaned block has a higher block hash, and that both storage
+    /// implementations agree.
+    #[tokio::test]
+    async fn stacks_chain_tip_ties_are_broken_by_tenure() {
+        let db = testing::storage::new_test_database().await;
+        let mem = storage::memory::Store::new_shared();
+        let mut rng = get_rng();
+
+        let mut blocks: Vec<model::BitcoinBlock> = Vec::new();
+        for height in 1u64..=2 {
+            let block = model::BitcoinBlock {
+                block_hash: Faker.fake_with_rng(&mut rng),
+                block_height: height.into(),
+                parent_hash: blocks
+                    .last()
+                    .map(|block| block.block_hash)
+                    .unwrap_or_else(|| Faker.fake_with_rng(&mut rng)),
+            };
+            db.write_bitcoin_block(&block).await.unwrap();
+            mem.write_bitcoin_block(&block).await.unwrap();
+            blocks.push(block);
+        }
+
+        // The first tenure produced two blocks, and the second tenure
+        // built off of the first block of the first tenure.
+        let genesis = model::StacksBlock {
+            block_hash: model::StacksBlockHash::from([0x10; 32]),
+            block_height: 1u64.into(),
+            parent_hash: Faker.fake_with_rng(&mut rng),
+            bitcoin_anchor: blocks[0].block_hash,
+        };
+        let orphaned = model::StacksBlock {
+            block_hash: model::StacksBlockHash::from([0xff; 32]),
+            block_height: 2u64.into(),
+            parent_hash: genesis.block_hash,
+            bitcoin_anchor: blocks[0].block_hash,
+        };
+        let new_tenure = model::StacksBlock {
+            block_hash: model::StacksBlockHash::from([0x01; 32]),
+            block_height: 2u64.into(),
+            parent_hash: genesis.block_hash,
+            bitcoin_anchor: blocks[1].block_hash,
+        };
+        for block in [&genesis, &orphaned, &new_tenure] {
+            db.write_stacks_block(block).await.unwrap();
+            mem.write_stacks_block(block).await.unwrap();
+        }
+
+        let expected = StacksChainTip {
+            block: new_tenure.clone(),
+            tenure: StacksTenure {
+                tenure_id: blocks[1].block_hash,
+                anchor_height: 2u64.into(),
+                block_count: 1,
+                first_block_height: 2u64.into(),
+                last_block_height: 2u64.into(),
+            },
+        };
+        let chain_tip = blocks[1].block_hash;
+        let db_tip = db.get_stacks_chain_tip_v2(&chain_tip).await.unwrap();
+        let mem_tip = mem.get_stacks_chain_tip_v2(&chain_tip).await.unwrap();
+        assert_eq!(db_tip, Some(expected.clone()));
+        assert_eq!(mem_tip, Some(expected));
+
+        // On the blockchain identified by the first bitcoin block, the
+        // second tenure does not exist yet.
+        let expected = StacksChainTip {
+            block: orphaned.clone(),
+            tenure: StacksTenure {
+                tenure_id: blocks[0].block_hash,
+                anchor_height: 1u64.into(),
+                block_count: 2,
+                first_block_height: 1u64.into(),
+                last_block_height: 2u64.into(),
+            },
+        };
+        let chain_tip = blocks[0].block_hash;
+        let db_tip = db.get_stacks_chain_tip_v2(&chain_tip).await.unwrap();
+        let mem_tip = mem.get_stacks_chain_tip_v2(&chain_tip).await.unwrap();
+        assert_eq!(db_tip, Some(expected.clone()));
+        assert_eq!(mem_tip, Some(expected));
+
+        testing::storage::drop_db(db).await;
+    }
+}
+
+mod multisig_stacks_transactions {
+    use super::*;
+
+    use signer::storage::model::MultisigStacksTransaction;
+
+    /// Check that the multi-sig contract calls are only returned for the
+    /// stacks blocks on the given stacks chain within the context window,
+    /// that scanned blocks are tracked, and that both storage
+    /// implementations agree.
+    #[tokio::test]
+    async fn multisig_stacks_transactions_follow_the_stacks_chain() {
+        let db = testing::storage::new_test_database().await;
+        let mem = storage::memory::Store::new_shared();
+        let mut rng = get_rng();
+
+        let mut blocks: Vec<model::BitcoinBlock> = Vec::new();
+        for height in 1u64..=2 {
+            let block = model::BitcoinBlock {
+                block_hash: Faker.fake_with_rng(&mut rng),
+                block_height: height.into(),
+                parent_hash: blocks
+                    .last()
+                    .map(|block| block.block_hash)
+                    .unwrap_or_else(|| Faker.fake_with_rng(&mut rng)),
+            };
+            db.write_bitcoin_block(&block).await.unwrap();
+            mem.write_bitcoin_block(&block).await.unwrap();
+            blocks.push(block);
+        }
+
+        // One stacks block in the first tenure, and two competing blocks
+        // building on it in the second tenure.
+        let first = model::StacksBlock {
+            block_hash: model::StacksBlockHash::from([0x01; 32]),
+            block_height: 1u64.into(),
+            parent_hash: Faker.fake_with_rng(&mut rng),
+            bitcoin_anchor: blocks[0].block_hash,
+        };
+        let canonical = model::StacksBlock {
+            block_hash: model::StacksBlockHash::from([0x02; 32]),
+            block_height: 2u64.into(),
+            parent_hash: first.block_hash,
+            bitcoin_anchor: blocks[1].block_hash,
+        };
+        let orphaned = model::StacksBlock {
+            block_hash: model::StacksBlockHash::from([0x03; 32]),
+            block_height: 2u64.into(),
+            parent_hash: first.block_hash,
+            bitcoin_anchor: blocks[1].block_hash,
+        };
+        for block in [&first, &canonical, &orphaned] {
+            db.write_stacks_block(block).await.unwrap();
+            mem.write_stacks_block(block).await.unwrap();
+        }
+
+        let chain_tip = blocks[1].block_hash;
+        let expected = vec![first.block_hash, canonical.block_hash, orphaned.block_hash];
+        let db_unscanned = db.get_unscanned_stacks_blocks(&chain_tip, 2).await.unwrap();
+        let mem_unscanned = mem
+            .get_unscanned_stacks_blocks(&chain_tip, 2)
+            .await
+            .unwrap();
+        assert_eq!(db_unscanned, expected);
+        assert_eq!(mem_unscanned, expected);
+
+        let mut transactions: Vec<MultisigStacksTransaction> = Vec::new();
+        for block in [&first, &canonical, &orphaned] {
+            let tx = MultisigStacksTransaction {
+                txid: Faker.fake_with_rng(&mut rng),
+                block_hash: block.block_hash,
+                signer_hash: [1; 20],
+                non_signers: vec![Faker.fake_with_rng(&mut rng)],
+            };
+            for _ in 0..2 {
+                db.write_multisig_stacks_transaction(&tx).await.unwrap();
+                mem.write_multisig_stacks_transaction(&tx).await.unwrap();
+            }
+            db.write_scanned_stacks_block(&block.block_hash)
+                .await
+                .unwrap();
+            mem.write_scanned_stacks_block(&block.block_hash)
+                .await
+                .unwrap();
+            transactions.push(tx);
+        }
+
+        let db_unscanned = db.get_unscanned_stacks_blocks(&chain_tip, 2).await.unwrap();
+        let mem_unscanned = mem
+            .get_unscanned_stacks_blocks(&chain_tip, 2)
+            .await
+            .unwrap();
+        assert!(db_unscanned.is_empty());
+        assert!(mem_unscanned.is_empty());
+
+        // The orphaned block is not on the stacks chain.
+        let mut expected = transactions[..2].to_vec();
+        expected.sort_by_key(|tx| tx.txid);
+        let stacks_chain_tip = canonical.block_hash;
+        let mut db_txs = db
+            .get_multisig_stacks_transactions(&chain_tip, &stacks_chain_tip, 2)
+            .await
+            .unwrap();
+        let mut mem_txs = mem
+            .get_multisig_stacks_transactions(&chain_tip, &stacks_chain_tip, 2)
+            .await
+            .unwrap();
+        db_txs.sort_by_key(|tx| tx.txid);
+        mem_txs.sort_by_key(|tx| tx.txid);
+        assert_eq!(db_txs, expected);
+        assert_eq!(mem_txs, expected);
+
+        // The first stacks block is outside of a context window of one
+        // bitcoin block.
+        let expected = transactions[1..2].to_vec();
+        let db_txs = db
+            .get_multisig_stacks_transactions(&chain_tip, &stacks_chain_tip, 1)
+            .await
+            .unwrap();
+        let mem_txs = mem
+            .get_multisig_stacks_transactions(&chain_tip, &stacks_chain_tip, 1)
+            .await
+            .unwrap();
+        assert_eq!(db_txs, expected);
+        assert_eq!(mem_txs, expected);
+
+        testing::storage::drop_db(db).await;
+    }
+}
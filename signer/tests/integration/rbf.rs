@@ -15,6 +15,7 @@ mod serial {
     use signer::DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX;
     use signer::bitcoin::utxo::DepositRequest;
     use signer::bitcoin::utxo::Fees;
+    use signer::bitcoin::utxo::RequestPrioritization;
     use signer::bitcoin::utxo::RequestRef;
     use signer::bitcoin::utxo::SbtcRequests;
     use signer::bitcoin::utxo::SignerBtcState;
@@ -226,12 +227,17 @@ mod serial {
                 last_fees: None,
                 // The value here isn't important, but it matches what happens
                 // in Nakamoto testnet.
-                magic_bytes: [b'T', b'3'],
+                magic_bytes: signer::LEGACY_MAGIC_BYTES,
+                change_outputs: 1,
             },
+            spare_signer_utxos: Vec::new(),
             accept_threshold: failure_threshold,
             num_signers: 2 * failure_threshold,
             sbtc_limits: SbtcLimits::unlimited(),
             max_deposits_per_bitcoin_tx: DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX,
+            max_withdrawals_per_bitcoin_tx: u16::MAX,
+            max_withdrawal_value: u64::MAX,
+            prioritization: RequestPrioritization::Fifo,
         };
 
         // Okay, lets submit the transaction. We also do a sanity check where
@@ -16,6 +16,7 @@ use emily_client::models::CreateDepositRequestBody;
 use signer::bitcoin::MockBitcoinInteract;
 use signer::blocklist_client::BlocklistClient;
 use signer::context::Context as _;
+use signer::decision_policy::BLOCKLISTED_RECIPIENT_REASON;
 use signer::emily_client::EmilyClient;
 use signer::emily_client::MockEmilyInteract;
 use signer::keys::PrivateKey;
@@ -190,7 +191,9 @@ async fn handle_pending_deposit_request_address_script_pub_key() {
         context_window: 10000,
         deposit_decisions_retry_window: 1,
         withdrawal_decisions_retry_window: 1,
+        carried_over: Default::default(),
         blocklist_checker: Some(()),
+        decision_policies: Vec::new(),
         signer_private_key: setup.aggregated_signer.keypair.secret_key().into(),
     };
 
@@ -277,7 +280,9 @@ async fn handle_pending_deposit_request_not_in_signing_set() {
         context_window: 10000,
         deposit_decisions_retry_window: 1,
         withdrawal_decisions_retry_window: 1,
+        carried_over: Default::default(),
         blocklist_checker: Some(()),
+        decision_policies: Vec::new(),
         // We generate a new private key here so that we know (with very
         // high probability) that this signer is not in the signer set.
         signer_private_key: PrivateKey::new(&mut rng),
@@ -368,7 +373,9 @@ async fn persist_received_deposit_decision_fetches_missing_deposit_requests() {
         context_window: 10000,
         deposit_decisions_retry_window: 1,
         withdrawal_decisions_retry_window: 1,
+        carried_over: Default::default(),
         blocklist_checker: Some(()),
+        decision_policies: Vec::new(),
         signer_private_key: PrivateKey::new(&mut rng),
     };
     let txid = setup.deposits[0].0.outpoint.txid.into();
@@ -531,9 +538,11 @@ async fn blocklist_client_retry(num_failures: u8, failing_iters: u8) {
         context: ctx.clone(),
         context_window: 10000,
         blocklist_checker: Some(blocklist_client),
+        decision_policies: Vec::new(),
         signer_private_key: setup.aggregated_signer.keypair.secret_key().into(),
         deposit_decisions_retry_window: 1,
         withdrawal_decisions_retry_window: 1,
+        carried_over: Default::default(),
     };
 
     // We need this so that there is a live "network". Otherwise we will error
@@ -700,9 +709,11 @@ async fn do_not_procceed_with_blocked_addresses(is_withdrawal: bool, is_blocked:
         context: ctx.clone(),
         context_window: 10000,
         blocklist_checker: Some(blocklist_client),
+        decision_policies: Vec::new(),
         signer_private_key: setup.aggregated_signer.keypair.secret_key().into(),
         deposit_decisions_retry_window: 1,
         withdrawal_decisions_retry_window: 1,
+        carried_over: Default::default(),
     };
 
     // We need this so that there is a live "network". Otherwise we will error
@@ -762,6 +773,8 @@ async fn do_not_procceed_with_blocked_addresses(is_withdrawal: bool, is_blocked:
 
         assert_eq!(decisions.len(), 1);
         assert_eq!(decisions.last().unwrap().is_accepted, !is_blocked);
+        let expected_reason = is_blocked.then(|| BLOCKLISTED_RECIPIENT_REASON.to_string());
+        assert_eq!(decisions.last().unwrap().rejection_reason, expected_reason);
         assert!(!votes.is_empty());
         assert!(votes.iter().any(|vote| {
             vote.signer_public_key == signer_public_key && vote.is_accepted.unwrap() != is_blocked
@@ -111,7 +111,7 @@ impl TestRotateKeySetup {
         let shares = EncryptedDkgShares {
             script_pubkey: aggregate_key.signers_script_pubkey().into(),
             tweaked_aggregate_key: aggregate_key.signers_tweaked_pubkey().unwrap(),
-            encrypted_private_shares: Vec::new(),
+            encrypted_private_shares: Vec::new().into(),
             public_shares: Vec::new(),
             aggregate_key,
             signer_set_public_keys: self.signer_keys.clone(),
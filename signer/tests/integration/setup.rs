@@ -29,10 +29,12 @@ use signer::bitcoin::rpc::BitcoinCoreClient;
 use signer::bitcoin::rpc::BitcoinTxInfo;
 use signer::bitcoin::utxo;
 use signer::bitcoin::utxo::Fees;
+use signer::bitcoin::utxo::RequestPrioritization;
 use signer::bitcoin::utxo::SbtcRequests;
 use signer::bitcoin::utxo::SignerBtcState;
 use signer::bitcoin::utxo::SignerUtxo;
 use signer::bitcoin::utxo::TxDeconstructor as _;
+use signer::bitcoin::validation::ValidationChecks;
 use signer::bitcoin::validation::WithdrawalValidationResult;
 use signer::block_observer;
 use signer::block_observer::Deposit;
@@ -215,12 +217,17 @@ impl TestSweepSetup {
                 fee_rate: 10.0,
                 public_key: signers_public_key,
                 last_fees: None,
-                magic_bytes: [b'T', b'3'],
+                magic_bytes: signer::LEGACY_MAGIC_BYTES,
+                change_outputs: 1,
             },
+            spare_signer_utxos: Vec::new(),
             accept_threshold: 4,
             num_signers: 7,
             sbtc_limits: SbtcLimits::unlimited(),
             max_deposits_per_bitcoin_tx: DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX,
+            max_withdrawals_per_bitcoin_tx: u16::MAX,
+            max_withdrawal_value: u64::MAX,
+            prioritization: RequestPrioritization::Fifo,
         };
 
         // There should only be one transaction here since there is only
@@ -390,7 +397,7 @@ impl TestSweepSetup {
         let shares = EncryptedDkgShares {
             script_pubkey: aggregate_key.signers_script_pubkey().into(),
             tweaked_aggregate_key: aggregate_key.signers_tweaked_pubkey().unwrap(),
-            encrypted_private_shares: Vec::new(),
+            encrypted_private_shares: Vec::new().into(),
             public_shares: Vec::new(),
             aggregate_key,
             signer_set_public_keys: self.signer_keys.clone(),
@@ -934,12 +941,17 @@ impl TestSweepSetup2 {
                 fee_rate: 10.0,
                 public_key: aggregated_signer.keypair.x_only_public_key().0,
                 last_fees,
-                magic_bytes: [b'T', b'3'],
+                magic_bytes: signer::LEGACY_MAGIC_BYTES,
+                change_outputs: 1,
             },
+            spare_signer_utxos: Vec::new(),
             accept_threshold: 4,
             num_signers: 7,
             sbtc_limits: SbtcLimits::unlimited(),
             max_deposits_per_bitcoin_tx: DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX,
+            max_withdrawals_per_bitcoin_tx: u16::MAX,
+            max_withdrawal_value: u64::MAX,
+            prioritization: RequestPrioritization::Fifo,
         };
 
         // There should only be one transaction here since there is only
@@ -1024,6 +1036,7 @@ impl TestSweepSetup2 {
             will_sign: true,
             is_valid_tx: true,
             validation_result: signer::bitcoin::validation::InputValidationResult::Ok,
+            validation_checks: ValidationChecks::default(),
             prevout_type: model::TxPrevoutType::SignersInput,
             sighash: Faker.fake_with_rng(&mut OsRng),
         };
@@ -1039,6 +1052,7 @@ impl TestSweepSetup2 {
                 will_sign: true,
                 is_valid_tx: true,
                 validation_result: signer::bitcoin::validation::InputValidationResult::Ok,
+                validation_checks: ValidationChecks::default(),
                 prevout_type: model::TxPrevoutType::SignersInput,
                 sighash: Faker.fake_with_rng(&mut OsRng),
             };
@@ -1063,6 +1077,7 @@ impl TestSweepSetup2 {
                 bitcoin_chain_tip: sweep.block_hash.into(),
                 is_valid_tx: true,
                 validation_result: WithdrawalValidationResult::Ok,
+                validation_checks: ValidationChecks::default(),
                 output_index: index as u32 + 2,
                 bitcoin_txid: sweep.txid.into(),
             };
@@ -1163,6 +1178,7 @@ impl TestSweepSetup2 {
                     txid: withdrawal.request.txid,
                     signer_pub_key,
                     is_accepted: !is_rejected,
+                    rejection_reason: None,
                 })
                 .collect();
 
@@ -1226,7 +1242,7 @@ impl TestSweepSetup2 {
         let shares = EncryptedDkgShares {
             script_pubkey: aggregate_key.signers_script_pubkey().into(),
             tweaked_aggregate_key: aggregate_key.signers_tweaked_pubkey().unwrap(),
-            encrypted_private_shares,
+            encrypted_private_shares: encrypted_private_shares.into(),
             public_shares: public_shares.encode_to_vec(),
             aggregate_key,
             signer_set_public_keys: self.signers.keys.clone(),
@@ -1272,9 +1288,12 @@ pub async fn new_emily_setup() -> (EmilyClient, EmilyTables) {
     for (shortname, table_name) in [
         ("deposit", &tables.deposit),
         ("withdrawal", &tables.withdrawal),
+        ("deposit-archive", &tables.deposit_archive),
+        ("withdrawal-archive", &tables.withdrawal_archive),
         ("chainstate", &tables.chainstate),
         ("limit", &tables.limit),
         ("throttle", &tables.throttle),
+        ("api-key", &tables.api_key),
     ] {
         headers.insert(
             reqwest::header::HeaderName::from_str(&format!("x-context-{shortname}")).unwrap(),
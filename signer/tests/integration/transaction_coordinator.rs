@@ -63,6 +63,7 @@ use signer::bitcoin::utxo::DepositRequest;
 use signer::bitcoin::utxo::Fees;
 use signer::bitcoin::utxo::SOLO_DEPOSIT_TX_VSIZE;
 use signer::bitcoin::utxo::TxDeconstructor as _;
+use signer::bitcoin::validation::ValidationChecks;
 use signer::bitcoin::validation::WithdrawalValidationResult;
 use signer::block_observer;
 use signer::context::P2PEvent;
@@ -82,12 +83,16 @@ use signer::storage::model::StacksBlockRef;
 use signer::storage::model::WithdrawalTxOutput;
 use signer::testing::btc::build_emily_request;
 use signer::testing::btc::get_canonical_chain_tip;
+use signer::testing::byzantine::ByzantineBehavior;
+use signer::testing::byzantine::ByzantineBehaviors;
+use signer::testing::byzantine::ByzantineNetwork;
 use signer::testing::get_rng;
 
 use signer::testing::FuturesIterExt as _;
 use signer::transaction_coordinator::given_key_is_coordinator;
 use signer::transaction_coordinator::should_run_dkg;
 use signer::transaction_signer::STACKS_SIGN_REQUEST_LRU_SIZE;
+use signer::transaction_signer::STACKS_VALIDATION_LRU_SIZE;
 use signer::transaction_signer::assert_allow_dkg_begin;
 use signer::util::FutureExt as _;
 use signer::util::Sleep;
@@ -476,6 +481,9 @@ async fn process_complete_deposit() {
                 .returning(move |_| {
                     Box::pin(std::future::ready(Ok(Some(signer_set_info.clone()))))
                 });
+            client
+                .expect_is_sbtc_contract_paused()
+                .returning(|_| Box::pin(std::future::ready(Ok(false))));
         })
         .await;
 
@@ -650,6 +658,9 @@ async fn mock_stacks_core<D, B, E>(
                     Ok(shares.map(SignerSetInfo::from))
                 })
             });
+        client
+            .expect_is_sbtc_contract_paused()
+            .returning(|_| Box::pin(std::future::ready(Ok(false))));
 
         // Only the client that corresponds to the coordinator will
         // submit a transaction, so we don't make explicit the
@@ -781,6 +792,7 @@ async fn deploy_smart_contracts_coordinator() {
             dkg_begin_pause: None,
             dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
             stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+            stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
         };
         let counter = start_count.clone();
         tokio::spawn(async move {
@@ -794,7 +806,9 @@ async fn deploy_smart_contracts_coordinator() {
             context_window: 10000,
             deposit_decisions_retry_window: 1,
             withdrawal_decisions_retry_window: 1,
+            carried_over: Default::default(),
             blocklist_checker: Some(()),
+            decision_policies: Vec::new(),
             signer_private_key: kp.secret_key().into(),
         };
         let counter = start_count.clone();
@@ -958,6 +972,9 @@ async fn run_dkg_from_scratch() {
             client
                 .expect_get_current_signer_set_info()
                 .returning(move |_| Box::pin(std::future::ready(Ok(None))));
+            client
+                .expect_is_sbtc_contract_paused()
+                .returning(|_| Box::pin(std::future::ready(Ok(false))));
         })
         .await;
 
@@ -1385,6 +1402,9 @@ async fn run_subsequent_dkg() {
             client
                 .expect_get_current_signer_set_info()
                 .returning(move |_| Box::pin(std::future::ready(Ok(None))));
+            client
+                .expect_is_sbtc_contract_paused()
+                .returning(|_| Box::pin(std::future::ready(Ok(false))));
         })
         .await;
 
@@ -1428,6 +1448,7 @@ async fn run_subsequent_dkg() {
             dkg_begin_pause: None,
             dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
             stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+            stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
         });
 
     // We only proceed with the test after all processes have started, and
@@ -1676,6 +1697,7 @@ async fn pseudo_random_dkg() {
             last_presign_block: None,
             dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
             stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+            stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
         };
         let counter = start_count.clone();
         tokio::spawn(async move {
@@ -1689,7 +1711,9 @@ async fn pseudo_random_dkg() {
             context_window: 10000,
             deposit_decisions_retry_window: 1,
             withdrawal_decisions_retry_window: 1,
+            carried_over: Default::default(),
             blocklist_checker: Some(()),
+            decision_policies: Vec::new(),
             signer_private_key: kp.secret_key().into(),
         };
         let counter = start_count.clone();
@@ -1815,7 +1839,7 @@ async fn pseudo_random_dkg() {
         assert_eq!(count, 2);
 
         let new_shares = db.get_latest_encrypted_dkg_shares().await.unwrap().unwrap();
-        let data = &new_shares.encrypted_private_shares;
+        let data = new_shares.encrypted_private_shares.expose_secret();
         let new_decrypted_secrets = wsts::util::decrypt(&keypair.secret_bytes(), data).unwrap();
 
         // We have adjusted the aggregate key of our first DKG run, so we
@@ -1860,7 +1884,7 @@ async fn pseudo_random_dkg() {
         // just do a direct comparison because the encryption algorithm
         // takes some randomness and will generate different encrypted
         // bytes each time, even when given the same plaintext data.
-        let data = &first_shares.encrypted_private_shares;
+        let data = first_shares.encrypted_private_shares.expose_secret();
         let original_decrypted_secrets =
             wsts::util::decrypt(&keypair.secret_bytes(), data).unwrap();
 
@@ -1910,7 +1934,7 @@ async fn pseudo_random_dkg() {
         let new_shares = db.get_latest_encrypted_dkg_shares().await.unwrap().unwrap();
         let new_decrypted_secrets = wsts::util::decrypt(
             &keypair.secret_bytes(),
-            &new_shares.encrypted_private_shares,
+            new_shares.encrypted_private_shares.expose_secret(),
         )
         .unwrap();
 
@@ -1957,7 +1981,7 @@ async fn pseudo_random_dkg() {
 
         // We should have different private shares from the previous two
         // times.
-        let data = &first_shares.encrypted_private_shares;
+        let data = first_shares.encrypted_private_shares.expose_secret();
         let first_decrypted_secrets = wsts::util::decrypt(&keypair.secret_bytes(), data).unwrap();
 
         assert_ne!(new_decrypted_secrets, first_decrypted_secrets);
@@ -2096,6 +2120,7 @@ async fn sign_bitcoin_transaction() {
             dkg_begin_pause: None,
             dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
             stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+            stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
         };
         let counter = start_count.clone();
         tokio::spawn(async move {
@@ -2109,7 +2134,9 @@ async fn sign_bitcoin_transaction() {
             context_window: 10000,
             deposit_decisions_retry_window: 1,
             withdrawal_decisions_retry_window: 1,
+            carried_over: Default::default(),
             blocklist_checker: Some(()),
+            decision_policies: Vec::new(),
             signer_private_key: kp.secret_key().into(),
         };
         let counter = start_count.clone();
@@ -2284,6 +2311,290 @@ async fn sign_bitcoin_transaction() {
     clean_emily_setup(emily_tables).await;
 }
 
+/// Test that an honest majority of signers still sweeps a deposit while
+/// another signer is byzantine.
+///
+/// The setup mirrors the one in [`sign_bitcoin_transaction`], except that
+/// each signer's network handles are wrapped in a [`ByzantineNetwork`].
+/// Once DKG has completed and the signers have a UTXO, one of the three
+/// signers starts lying about the bitcoin chain tip and equivocating on
+/// everything that it sends. The remaining two signers meet the signing
+/// threshold, so they should still agree on the deposit and sign and
+/// broadcast a sweep transaction for it.
+///
+/// Since the byzantine signer may be the coordinator for the tenure that
+/// confirms the deposit, we mine a few more blocks if no sweep shows up
+/// right away.
+///
+/// To start the test environment do:
+/// ```bash
+/// make integration-env-up-ci
+/// ```
+///
+/// then, once everything is up and running, run the test.
+#[tokio::test]
+async fn sign_bitcoin_transaction_with_byzantine_signer() {
+    let (_, signer_key_pairs): (_, [Keypair; 3]) = testing::wallet::regtest_bootstrap_wallet();
+
+    let stack = TestContainersBuilder::start_bitcoin().await;
+    let bitcoin = stack.bitcoin().await;
+    let rpc = bitcoin.rpc();
+    let faucet = &bitcoin.get_faucet();
+
+    let (emily_client, emily_tables) = new_emily_setup().await;
+
+    let network = WanNetwork::default();
+
+    // Ensure we can estimate fees
+    faucet.generate_fee_data();
+
+    let chain_tip_info = get_canonical_chain_tip(rpc);
+
+    // =========================================================================
+    // Step 1 - Create a database, an associated context, and a Keypair for
+    //          each of the signers in the signing set.
+    // =========================================================================
+    let mut signers = Vec::new();
+    for kp in signer_key_pairs.iter() {
+        let db = testing::storage::new_test_database().await;
+        let ctx = TestContext::builder()
+            .with_storage(db.clone())
+            .with_bitcoin_client(bitcoin.get_client())
+            .with_emily_client(emily_client.clone())
+            .with_mocked_stacks_client()
+            .modify_settings(|settings| {
+                settings.signer.bitcoin_processing_delay = Duration::from_millis(200);
+            })
+            .build();
+
+        backfill_bitcoin_blocks(&db, rpc, &chain_tip_info.hash).await;
+
+        let network = network.connect(&ctx);
+
+        signers.push((ctx, db, kp, network));
+    }
+
+    // =========================================================================
+    // Step 2 - Setup the stacks client mocks.
+    // =========================================================================
+    let (broadcast_stacks_tx, _rx) = tokio::sync::broadcast::channel(10);
+
+    for (ctx, db, _, _) in signers.iter_mut() {
+        let broadcast_stacks_tx = broadcast_stacks_tx.clone();
+        let db = db.clone();
+
+        mock_stacks_core(ctx, chain_tip_info.clone(), db, broadcast_stacks_tx).await;
+    }
+
+    // =========================================================================
+    // Step 3 - Start the TxCoordinatorEventLoop, TxSignerEventLoop,
+    //          RequestDeciderEventLoop and BlockObserver processes for each
+    //          signer.
+    // -------------------------------------------------------------------------
+    // - Every signer talks to its peers through a ByzantineNetwork, but
+    //   they all start off honest. We keep the behaviors of the last
+    //   signer around so that we can make it misbehave later.
+    // =========================================================================
+    let start_count = Arc::new(AtomicU8::new(0));
+    let bitcoin_chain_tip_poller = bitcoin.start_chain_tip_poller().await;
+    let mut signer_behaviors = Vec::new();
+
+    for (ctx, _, kp, network) in signers.iter() {
+        ctx.state().set_sbtc_contracts_deployed();
+        let private_key: PrivateKey = kp.secret_key().into();
+        let behaviors = ByzantineBehaviors::default();
+        let spawn = || ByzantineNetwork::new(network.spawn(), private_key, behaviors.clone());
+
+        let ev = TxCoordinatorEventLoop {
+            network: spawn(),
+            context: ctx.clone(),
+            context_window: 10000,
+            private_key,
+            signing_round_max_duration: Duration::from_secs(10),
+            bitcoin_presign_request_max_duration: Duration::from_secs(10),
+            dkg_max_duration: Duration::from_secs(10),
+            is_epoch3: true,
+        };
+        let counter = start_count.clone();
+        tokio::spawn(async move {
+            counter.fetch_add(1, Ordering::Relaxed);
+            ev.run().await
+        });
+
+        let ev = TxSignerEventLoop {
+            network: spawn(),
+            context: ctx.clone(),
+            context_window: 10000,
+            wsts_state_machines: LruCache::new(NonZeroUsize::new(100).unwrap()),
+            signer_private_key: private_key,
+            last_presign_block: None,
+            dkg_begin_pause: None,
+            dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
+            stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+            stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
+        };
+        let counter = start_count.clone();
+        tokio::spawn(async move {
+            counter.fetch_add(1, Ordering::Relaxed);
+            ev.run().await
+        });
+
+        let ev = RequestDeciderEventLoop {
+            network: spawn(),
+            context: ctx.clone(),
+            context_window: 10000,
+            deposit_decisions_retry_window: 1,
+            withdrawal_decisions_retry_window: 1,
+            carried_over: Default::default(),
+            blocklist_checker: Some(()),
+            decision_policies: Vec::new(),
+            signer_private_key: private_key,
+        };
+        let counter = start_count.clone();
+        tokio::spawn(async move {
+            counter.fetch_add(1, Ordering::Relaxed);
+            ev.run().await
+        });
+
+        let block_observer = BlockObserver {
+            context: ctx.clone(),
+            bitcoin_block_source: bitcoin_chain_tip_poller.clone(),
+        };
+        let counter = start_count.clone();
+        tokio::spawn(async move {
+            counter.fetch_add(1, Ordering::Relaxed);
+            block_observer.run().await
+        });
+
+        signer_behaviors.push(behaviors);
+    }
+
+    while start_count.load(Ordering::SeqCst) < 12 {
+        Sleep::for_millis(10).await;
+    }
+
+    // =========================================================================
+    // Step 4 - Wait for DKG
+    // -------------------------------------------------------------------------
+    // - DKG needs every signer, so everyone is still honest here.
+    // =========================================================================
+    let chain_tip = faucet.generate_block().into();
+    wait_for_tenure_completed(&signers, chain_tip).await;
+
+    let (_, db, _, _) = signers.first().unwrap();
+    let shares = db.get_latest_encrypted_dkg_shares().await.unwrap().unwrap();
+
+    // =========================================================================
+    // Step 5 - Prepare for deposits
+    // -------------------------------------------------------------------------
+    // - Make a donation to the signers and give a "depositor" some UTXOs
+    //   so that they can make a deposit for sBTC.
+    // =========================================================================
+    let script_pub_key = shares.aggregate_key.signers_script_pubkey();
+    let address = Address::from_script(&script_pub_key, bitcoin::Network::Regtest).unwrap();
+
+    faucet.send_to(100_000, &address);
+
+    let depositor = Recipient::new(AddressType::P2tr);
+    faucet.send_to(50_000_000, &depositor.address);
+    let chain_tip = faucet.generate_block().into();
+    wait_for_tenure_completed(&signers, chain_tip).await;
+
+    // =========================================================================
+    // Step 6 - Turn the last signer byzantine
+    // -------------------------------------------------------------------------
+    // - Every message that it sends now claims a chain tip that nobody
+    //   else knows about, and is followed by a conflicting copy, so its
+    //   decisions are flipped and its WSTS messages are malformed.
+    // =========================================================================
+    let byzantine_behaviors = signer_behaviors.last().unwrap();
+    let fake_chain_tip: BitcoinBlockHash = Faker.fake_with_rng(&mut OsRng);
+    byzantine_behaviors.enable(ByzantineBehavior::LieAboutChainTip(fake_chain_tip));
+    byzantine_behaviors.enable(ByzantineBehavior::Equivocate);
+
+    // =========================================================================
+    // Step 7 - Make a proper deposit
+    // =========================================================================
+    let utxo = depositor.get_utxos(rpc, None).pop().unwrap();
+
+    let amount = 2_500_000;
+    let signers_public_key = shares.aggregate_key.into();
+    let max_fee = amount / 2;
+    let (deposit_tx, deposit_request, deposit_info) =
+        make_deposit_request(&depositor, amount, utxo, max_fee, signers_public_key);
+    rpc.send_raw_transaction(&deposit_tx).unwrap();
+
+    let body = build_emily_request(&deposit_info, &deposit_tx);
+    let _ = deposit_api::create_deposit(emily_client.config(), body)
+        .await
+        .unwrap();
+
+    // =========================================================================
+    // Step 8 - Confirm the deposit and wait for the honest signers to
+    //          sweep it.
+    // =========================================================================
+    let (ctx, _, _, _) = signers.first().unwrap();
+    let mut txids = Vec::new();
+    for _ in 0..4 {
+        let chain_tip = faucet.generate_block().into();
+        wait_for_tenure_completed(&signers, chain_tip).await;
+
+        txids = ctx.bitcoin_client.inner_client().get_raw_mempool().unwrap();
+        if !txids.is_empty() {
+            break;
+        }
+    }
+    assert_eq!(txids.len(), 1);
+
+    let block_hash = faucet.generate_block().into();
+    wait_for_tenure_completed(&signers, block_hash).await;
+
+    // =========================================================================
+    // Step 9 - Assertions
+    // -------------------------------------------------------------------------
+    // - The sweep spends the signers' UTXO and the deposit, and locks the
+    //   signers' new UTXO with the same aggregate key.
+    // - The honest signers know about the sweep.
+    // =========================================================================
+    let txid = txids.pop().unwrap();
+    let tx_info = ctx
+        .bitcoin_client
+        .get_tx_info(&txid, &block_hash)
+        .unwrap()
+        .unwrap();
+
+    let actual_script_pub_key = tx_info.prevout(0).unwrap().script_pubkey.as_bytes();
+    assert_eq!(actual_script_pub_key, script_pub_key.as_bytes());
+    assert_eq!(&tx_info.tx.output[0].script_pubkey, &script_pub_key);
+    assert!(
+        tx_info
+            .tx
+            .input
+            .iter()
+            .any(|input| input.previous_output == deposit_request.outpoint)
+    );
+
+    let (_, byzantine_db, _, _) = signers.pop().unwrap();
+    for (_, db, _, _) in signers {
+        let script_pubkey = sqlx::query_scalar::<_, model::ScriptPubKey>(
+            r#"
+            SELECT script_pubkey
+            FROM sbtc_signer.bitcoin_tx_outputs
+            WHERE txid = $1
+              AND output_type = 'signers_output'
+            "#,
+        )
+        .bind(txid.to_byte_array())
+        .fetch_one(db.pool())
+        .await
+        .unwrap();
+        assert!(db.is_signer_script_pub_key(&script_pubkey).await.unwrap());
+        testing::storage::drop_db(db).await;
+    }
+    testing::storage::drop_db(byzantine_db).await;
+    clean_emily_setup(emily_tables).await;
+}
+
 /// Test that three signers can successfully sign and broadcast a bitcoin
 /// transaction where the inputs are locked by different aggregate keys.
 ///
@@ -2429,6 +2740,7 @@ async fn sign_bitcoin_transaction_multiple_locking_keys() {
             dkg_begin_pause: None,
             dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
             stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+            stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
         };
         let counter = start_count.clone();
         tokio::spawn(async move {
@@ -2442,7 +2754,9 @@ async fn sign_bitcoin_transaction_multiple_locking_keys() {
             context_window: 10000,
             deposit_decisions_retry_window: 1,
             withdrawal_decisions_retry_window: 1,
+            carried_over: Default::default(),
             blocklist_checker: Some(()),
+            decision_policies: Vec::new(),
             signer_private_key: kp.secret_key().into(),
         };
         let counter = start_count.clone();
@@ -2939,6 +3253,7 @@ async fn wsts_ids_set_during_dkg_and_signing_rounds() {
             dkg_begin_pause: None,
             dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
             stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+            stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
         };
         let counter = start_count.clone();
         tokio::spawn(async move {
@@ -2952,7 +3267,9 @@ async fn wsts_ids_set_during_dkg_and_signing_rounds() {
             context_window: 10000,
             deposit_decisions_retry_window: 1,
             withdrawal_decisions_retry_window: 1,
+            carried_over: Default::default(),
             blocklist_checker: Some(()),
+            decision_policies: Vec::new(),
             signer_private_key: kp.secret_key().into(),
         };
         let counter = start_count.clone();
@@ -3300,6 +3617,7 @@ async fn skip_signer_activites_after_key_rotation() {
             dkg_begin_pause: None,
             dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
             stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+            stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
         };
         let counter = start_count.clone();
         tokio::spawn(async move {
@@ -3313,7 +3631,9 @@ async fn skip_signer_activites_after_key_rotation() {
             context_window: 10000,
             deposit_decisions_retry_window: 1,
             withdrawal_decisions_retry_window: 1,
+            carried_over: Default::default(),
             blocklist_checker: Some(()),
+            decision_policies: Vec::new(),
             signer_private_key: kp.secret_key().into(),
         };
         let counter = start_count.clone();
@@ -3803,6 +4123,9 @@ async fn skip_smart_contract_deployment_and_key_rotation_if_up_to_date() {
                 .returning(move |_| {
                     Box::pin(std::future::ready(Ok(Some(signer_set_info.clone()))))
                 });
+            client
+                .expect_is_sbtc_contract_paused()
+                .returning(|_| Box::pin(std::future::ready(Ok(false))));
 
             // No transactions should be submitted.
             client.expect_submit_tx().never();
@@ -3848,6 +4171,7 @@ async fn skip_smart_contract_deployment_and_key_rotation_if_up_to_date() {
             dkg_begin_pause: None,
             dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
             stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+            stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
         };
         let counter = start_count.clone();
         tokio::spawn(async move {
@@ -3861,7 +4185,9 @@ async fn skip_smart_contract_deployment_and_key_rotation_if_up_to_date() {
             context_window: 10000,
             deposit_decisions_retry_window: 1,
             withdrawal_decisions_retry_window: 1,
+            carried_over: Default::default(),
             blocklist_checker: Some(()),
+            decision_policies: Vec::new(),
             signer_private_key: kp.secret_key().into(),
         };
         let counter = start_count.clone();
@@ -4023,15 +4349,15 @@ async fn test_get_btc_state_with_no_available_sweep_transactions() {
 
     // Get the chain tip and assert that it is the block we just wrote.
     let chain_tip = db
-        .get_bitcoin_canonical_chain_tip()
+        .get_bitcoin_canonical_chain_tip_ref()
         .await
         .unwrap()
         .expect("no chain tip");
-    assert_eq!(chain_tip, bitcoin_block.block_hash);
+    assert_eq!(chain_tip.block_hash, bitcoin_block.block_hash);
 
     // Get the signer UTXO and assert that it is the one we just wrote.
     let utxo = db
-        .get_signer_utxo(&chain_tip)
+        .get_signer_utxo(&chain_tip.block_hash)
         .await
         .unwrap()
         .expect("no signer utxo");
@@ -4049,7 +4375,7 @@ async fn test_get_btc_state_with_no_available_sweep_transactions() {
     assert_eq!(btc_state.public_key, aggregate_key.into());
     assert_eq!(btc_state.fee_rate, 1.3);
     assert_eq!(btc_state.last_fees, None);
-    assert_eq!(btc_state.magic_bytes, [b'T', b'3']);
+    assert_eq!(btc_state.magic_bytes, signer::LEGACY_MAGIC_BYTES);
 
     // Let's grab the state again, this time we expect fees from
     // "bitcoin-core" that are too high.
@@ -4165,11 +4491,15 @@ mod serial {
         .await
         .unwrap();
 
-        let chain_tip = db.get_bitcoin_canonical_chain_tip().await.unwrap().unwrap();
+        let chain_tip = db
+            .get_bitcoin_canonical_chain_tip_ref()
+            .await
+            .unwrap()
+            .unwrap();
 
         // Get the signer UTXO and assert that it is the one we just wrote.
         let utxo = db
-            .get_signer_utxo(&chain_tip)
+            .get_signer_utxo(&chain_tip.block_hash)
             .await
             .unwrap()
             .expect("no signer utxo");
@@ -4212,7 +4542,7 @@ mod serial {
         assert_eq!(btc_state.utxo.public_key, aggregate_key.into());
         assert_eq!(btc_state.public_key, aggregate_key.into());
         assert_eq!(btc_state.last_fees, Some(expected_fees));
-        assert_eq!(btc_state.magic_bytes, [b'T', b'3']);
+        assert_eq!(btc_state.magic_bytes, signer::LEGACY_MAGIC_BYTES);
 
         // Create a 2nd transaction that spends the utxo (simulate RBF).
         let mut tx2 = bitcoin::Transaction {
@@ -4471,6 +4801,9 @@ async fn test_conservative_initial_sbtc_limits() {
                 };
                 Box::pin(std::future::ready(limits))
             });
+            client
+                .expect_set_available_to_mint()
+                .returning(|_| Box::pin(std::future::ready(Ok(()))));
         })
         .await;
     }
@@ -4546,6 +4879,9 @@ async fn test_conservative_initial_sbtc_limits() {
                         signatures_required,
                     }))))
                 });
+            client
+                .expect_is_sbtc_contract_paused()
+                .returning(|_| Box::pin(std::future::ready(Ok(false))));
 
             // The coordinator will get the total supply of sBTC to
             // determine the amount of mintable sBTC.
@@ -4617,6 +4953,7 @@ async fn test_conservative_initial_sbtc_limits() {
             dkg_begin_pause: None,
             dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
             stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+            stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
         };
         let counter = start_count.clone();
         tokio::spawn(async move {
@@ -4629,9 +4966,11 @@ async fn test_conservative_initial_sbtc_limits() {
             context: ctx.clone(),
             context_window: 10000,
             blocklist_checker: Some(()),
+            decision_policies: Vec::new(),
             signer_private_key: kp.secret_key().into(),
             deposit_decisions_retry_window: 1,
             withdrawal_decisions_retry_window: 1,
+            carried_over: Default::default(),
         };
         let counter = start_count.clone();
         tokio::spawn(async move {
@@ -4836,6 +5175,7 @@ async fn sign_bitcoin_transaction_withdrawals() {
             dkg_begin_pause: None,
             dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
             stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+            stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
         };
         let counter = start_count.clone();
         tokio::spawn(async move {
@@ -4849,7 +5189,9 @@ async fn sign_bitcoin_transaction_withdrawals() {
             context_window: 10000,
             deposit_decisions_retry_window: 1,
             withdrawal_decisions_retry_window: 1,
+            carried_over: Default::default(),
             blocklist_checker: Some(()),
+            decision_policies: Vec::new(),
             signer_private_key: kp.secret_key().into(),
         };
         let counter = start_count.clone();
@@ -4986,6 +5328,7 @@ async fn sign_bitcoin_transaction_withdrawals() {
         TestingEmilyWithdrawalStatus::Accepted,
         None,
         None,
+        None,
     )
     .await
     .unwrap()
@@ -4998,6 +5341,7 @@ async fn sign_bitcoin_transaction_withdrawals() {
         TestingEmilyWithdrawalStatus::Pending,
         None,
         None,
+        None,
     )
     .await
     .unwrap()
@@ -5060,6 +5404,7 @@ async fn sign_bitcoin_transaction_withdrawals() {
         TestingEmilyWithdrawalStatus::Accepted,
         None,
         None,
+        None,
     )
     .await
     .unwrap()
@@ -5215,6 +5560,9 @@ async fn process_rejected_withdrawal(is_completed: bool, is_in_mempool: bool) {
                         Ok(shares.map(SignerSetInfo::from))
                     })
                 });
+            client
+                .expect_is_sbtc_contract_paused()
+                .returning(|_| Box::pin(std::future::ready(Ok(false))));
 
             // Dummy value
             client
@@ -5349,6 +5697,7 @@ async fn process_rejected_withdrawal(is_completed: bool, is_in_mempool: bool) {
             // have validated it, so we err towards checking more rather than
             // less txids.
             validation_result: WithdrawalValidationResult::NoVote,
+            validation_checks: ValidationChecks::default(),
             is_valid_tx: false,
         };
         db.write_bitcoin_withdrawals_outputs(&[withdrawal_output])
@@ -5361,6 +5710,7 @@ async fn process_rejected_withdrawal(is_completed: bool, is_in_mempool: bool) {
             prevout_txid: donation.txid.into(),
             prevout_output_index: donation.vout,
             validation_result: signer::bitcoin::validation::InputValidationResult::Ok,
+            validation_checks: ValidationChecks::default(),
             aggregate_key: aggregate_key.into(),
             is_valid_tx: false,
             will_sign: false,
@@ -5406,6 +5756,9 @@ async fn process_rejected_withdrawal(is_completed: bool, is_in_mempool: bool) {
                         signatures_required: signing_threshold as u16,
                     }))))
                 });
+            client
+                .expect_is_sbtc_contract_paused()
+                .returning(|_| Box::pin(std::future::ready(Ok(false))));
         })
         .await;
 
@@ -5569,6 +5922,9 @@ async fn coordinator_skip_onchain_completed_deposits(deposit_completed: bool) {
                     signatures_required: 1,
                 }))))
             });
+        client
+            .expect_is_sbtc_contract_paused()
+            .returning(|_| Box::pin(std::future::ready(Ok(false))));
     })
     .await;
 
@@ -5678,6 +6034,7 @@ mod get_eligible_pending_withdrawal_requests {
     use signer::{
         WITHDRAWAL_DUST_LIMIT,
         bitcoin::MockBitcoinInteract,
+        config::DepositConfirmationPolicy,
         emily_client::MockEmilyInteract,
         network::in_memory2::SignerNetworkInstance,
         storage::model::{
@@ -5727,6 +6084,7 @@ mod get_eligible_pending_withdrawal_requests {
                 txid: request.txid,
                 signer_pub_key,
                 is_accepted,
+                rejection_reason: None,
             };
 
             // Write the decision to the database.
@@ -5950,6 +6308,7 @@ mod get_eligible_pending_withdrawal_requests {
             stacks_chain_tip: &stacks_chain_tip,
             signature_threshold: params.signature_threshold,
             sbtc_limits: &params.sbtc_limits,
+            deposit_confirmation_policy: &DepositConfirmationPolicy::default(),
         };
 
         // Create a request below the dust limit.
@@ -6393,6 +6752,7 @@ async fn reuse_nonce_attack() {
             dkg_begin_pause: None,
             dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
             stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+            stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
         };
         let counter = start_count.clone();
         tokio::spawn(async move {
@@ -6406,7 +6766,9 @@ async fn reuse_nonce_attack() {
             context_window: 1000,
             deposit_decisions_retry_window: 1,
             withdrawal_decisions_retry_window: 1,
+            carried_over: Default::default(),
             blocklist_checker: Some(()),
+            decision_policies: Vec::new(),
             signer_private_key: kp.secret_key().into(),
         };
         let counter = start_count.clone();
@@ -6787,6 +7149,7 @@ async fn construct_and_sign_bitcoin_sbtc_transactions_fee_logic(
             block_hash: withdrawal_request.block_hash,
             signer_pub_key: *signer_public_key,
             is_accepted: true,
+            rejection_reason: None,
         };
         storage
             .write_withdrawal_signer_decision(&decision)
@@ -24,12 +24,14 @@ use signer::storage::postgres::PgStore;
 use signer::testing::IterTestExt as _;
 use signer::testing::btc::get_canonical_chain_tip;
 use signer::transaction_signer::STACKS_SIGN_REQUEST_LRU_SIZE;
+use signer::transaction_signer::STACKS_VALIDATION_LRU_SIZE;
 use test_case::test_case;
 
 use signer::bitcoin::utxo::RequestRef;
 use signer::bitcoin::utxo::Requests;
 use signer::bitcoin::utxo::UnsignedTransaction;
 use signer::bitcoin::validation::TxRequestIds;
+use signer::bitcoin::validation::ValidationChecks;
 use signer::context::Context as _;
 use signer::context::SbtcLimits;
 use signer::error::Error;
@@ -142,6 +144,7 @@ async fn signing_set_validation_check_for_stacks_transactions() {
         dkg_begin_pause: None,
         dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
         stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+        stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
     };
 
     // Let's create a proper sign request.
@@ -244,6 +247,7 @@ async fn signing_set_validation_ignores_aggregate_key_in_request() {
         dkg_begin_pause: None,
         dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
         stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+        stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
     };
 
     // Let's create a proper sign request.
@@ -352,6 +356,7 @@ async fn signer_rejects_stacks_txns_with_too_high_a_fee(
         dkg_begin_pause: None,
         dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
         stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+        stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
     };
 
     // Setup the transaction fee to be the maximum fee configured plus one, so that it
@@ -450,6 +455,7 @@ async fn signer_rejects_multiple_attempts_in_tenure() {
         dkg_begin_pause: None,
         dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
         stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+        stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
     };
 
     // We need this so that there is a live "network". Otherwise will error when
@@ -601,6 +607,7 @@ async fn assert_should_be_able_to_handle_sbtc_requests() {
         dkg_begin_pause: None,
         dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
         stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+        stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
     };
 
     let sbtc_requests: TxRequestIds = TxRequestIds {
@@ -612,6 +619,8 @@ async fn assert_should_be_able_to_handle_sbtc_requests() {
         request_package: vec![sbtc_requests],
         fee_rate,
         last_fees: None,
+        parallel_sweeps: false,
+        magic_bytes: signer::LEGACY_MAGIC_BYTES,
     };
 
     let sbtc_state = signer::bitcoin::utxo::SignerBtcState {
@@ -624,7 +633,8 @@ async fn assert_should_be_able_to_handle_sbtc_requests() {
         fee_rate,
         last_fees: None,
         public_key: setup.aggregated_signer.keypair.public_key().into(),
-        magic_bytes: [b'T', b'3'],
+        magic_bytes: signer::LEGACY_MAGIC_BYTES,
+        change_outputs: 1,
     };
 
     // Create an unsigned transaction with the deposit request
@@ -760,6 +770,7 @@ async fn presign_requests_with_dkg_shares_status(status: DkgSharesStatus, is_ok:
         dkg_begin_pause: None,
         dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
         stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+        stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
     };
 
     let sbtc_requests: TxRequestIds = TxRequestIds {
@@ -771,6 +782,8 @@ async fn presign_requests_with_dkg_shares_status(status: DkgSharesStatus, is_ok:
         request_package: vec![sbtc_requests],
         fee_rate: 2.0,
         last_fees: None,
+        parallel_sweeps: false,
+        magic_bytes: signer::LEGACY_MAGIC_BYTES,
     };
 
     let result = tx_signer
@@ -864,6 +877,7 @@ pub async fn presign_request_ignore_request_if_already_processed_this_block() {
         dkg_begin_pause: None,
         dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
         stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+        stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
     };
 
     let sbtc_requests: TxRequestIds = TxRequestIds {
@@ -875,6 +889,8 @@ pub async fn presign_request_ignore_request_if_already_processed_this_block() {
         request_package: vec![sbtc_requests],
         fee_rate: 2.0,
         last_fees: None,
+        parallel_sweeps: false,
+        magic_bytes: signer::LEGACY_MAGIC_BYTES,
     };
 
     let result = tx_signer
@@ -957,6 +973,7 @@ mod serial {
             dkg_begin_pause: None,
             dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
             stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+            stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
         };
 
         // We need to convince the signer event loop that it should accept the
@@ -983,6 +1000,7 @@ mod serial {
             sighash,
             prevout_type: model::TxPrevoutType::Deposit,
             validation_result: signer::bitcoin::validation::InputValidationResult::Ok,
+            validation_checks: ValidationChecks::default(),
             is_valid_tx: true,
             will_sign: true,
             aggregate_key: PublicKey::from_private_key(&tx_signer.signer_private_key).into(),
@@ -1082,6 +1100,7 @@ mod serial {
             last_presign_block: None,
             dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
             stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+            stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
         };
 
         // We need to convince the signer event loop that it should accept the
@@ -1108,6 +1127,7 @@ mod serial {
             sighash,
             prevout_type: model::TxPrevoutType::Deposit,
             validation_result: signer::bitcoin::validation::InputValidationResult::Ok,
+            validation_checks: ValidationChecks::default(),
             is_valid_tx: true,
             will_sign: true,
             aggregate_key: PublicKey::from_private_key(&tx_signer.signer_private_key).into(),
@@ -1261,6 +1281,7 @@ async fn max_one_state_machine_per_bitcoin_block_hash_for_dkg() {
         dkg_begin_pause: None,
         dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
         stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+        stacks_validations: LruCache::new(STACKS_VALIDATION_LRU_SIZE),
     };
 
     // We need to convince the signer event loop that it should accept the
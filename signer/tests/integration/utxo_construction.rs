@@ -28,6 +28,7 @@ use sbtc::deposits::ReclaimScriptInputs;
 use signer::DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX;
 use signer::bitcoin::rpc::BitcoinCoreClient;
 use signer::bitcoin::utxo::DepositRequest;
+use signer::bitcoin::utxo::RequestPrioritization;
 use signer::bitcoin::utxo::SbtcRequests;
 use signer::bitcoin::utxo::SignerBtcState;
 use signer::bitcoin::utxo::SignerUtxo;
@@ -261,12 +262,17 @@ mod serial {
                 fee_rate: 10.0,
                 public_key: signers_public_key,
                 last_fees: None,
-                magic_bytes: [b'T', b'3'],
+                magic_bytes: signer::LEGACY_MAGIC_BYTES,
+                change_outputs: 1,
             },
+            spare_signer_utxos: Vec::new(),
             accept_threshold: 4,
             num_signers: 7,
             sbtc_limits: SbtcLimits::unlimited(),
             max_deposits_per_bitcoin_tx: DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX,
+            max_withdrawals_per_bitcoin_tx: u16::MAX,
+            max_withdrawal_value: u64::MAX,
+            prioritization: RequestPrioritization::Fifo,
         };
 
         // There should only be one transaction here since there is only one
@@ -326,12 +332,17 @@ mod serial {
                 fee_rate: FEE_RATE,
                 public_key: signers_public_key,
                 last_fees: None,
-                magic_bytes: [b'T', b'3'],
+                magic_bytes: signer::LEGACY_MAGIC_BYTES,
+                change_outputs: 1,
             },
+            spare_signer_utxos: Vec::new(),
             accept_threshold: 4,
             num_signers: 7,
             sbtc_limits: SbtcLimits::unlimited(),
             max_deposits_per_bitcoin_tx: DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX,
+            max_withdrawals_per_bitcoin_tx: u16::MAX,
+            max_withdrawal_value: u64::MAX,
+            prioritization: RequestPrioritization::Fifo,
         };
 
         // There should only be one transaction here since there is only one
@@ -471,12 +482,17 @@ mod serial {
                 fee_rate: FEE_RATE,
                 public_key: signers_public_key,
                 last_fees: None,
-                magic_bytes: [b'T', b'3'],
+                magic_bytes: signer::LEGACY_MAGIC_BYTES,
+                change_outputs: 1,
             },
+            spare_signer_utxos: Vec::new(),
             accept_threshold: 4,
             num_signers: 7,
             sbtc_limits: SbtcLimits::unlimited(),
             max_deposits_per_bitcoin_tx: DEFAULT_MAX_DEPOSITS_PER_BITCOIN_TX,
+            max_withdrawals_per_bitcoin_tx: u16::MAX,
+            max_withdrawal_value: u64::MAX,
+            prioritization: RequestPrioritization::Fifo,
         };
 
         // There should only be one transaction here since there are only
@@ -4,6 +4,7 @@ use rand::rngs::OsRng;
 use sbtc::testing::containers::TestContainersBuilder;
 use sbtc::testing::regtest::Faucet;
 use signer::bitcoin::rpc::BitcoinCoreClient;
+use signer::bitcoin::validation::ValidationChecks;
 use signer::error::Error;
 use signer::stacks::contracts::AsContractCall as _;
 use signer::stacks::contracts::RejectWithdrawalV1;
@@ -520,6 +521,7 @@ async fn reject_withdrawal_validation_request_being_fulfilled() {
         prevout_txid: setup.donation.txid.into(),
         prevout_output_index: setup.donation.vout,
         validation_result: signer::bitcoin::validation::InputValidationResult::Ok,
+        validation_checks: ValidationChecks::default(),
         aggregate_key: setup.signers.aggregate_key().into(),
         is_valid_tx: false,
         will_sign: false,